@@ -0,0 +1,39 @@
+//! Errors from generating values.
+
+use alloc::string::String;
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+
+/// Errors when generating a value.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GenerateError<'shape> {
+    /// The type has no way to be generated, such as an opaque type or a function pointer.
+    UnsupportedShape(&'shape Shape<'shape>),
+    /// An attribute like `#[facet(arbitrary(...))]` couldn't be parsed, or doesn't fit its field.
+    InvalidAttribute(String),
+    /// Any error from facet.
+    Reflect(ReflectError<'shape>),
+}
+
+impl<'shape> From<ReflectError<'shape>> for GenerateError<'shape> {
+    fn from(err: ReflectError<'shape>) -> Self {
+        GenerateError::Reflect(err)
+    }
+}
+
+impl core::fmt::Display for GenerateError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            GenerateError::UnsupportedShape(shape) => {
+                write!(f, "Values of type {shape} can't be generated")
+            }
+            GenerateError::InvalidAttribute(attr) => {
+                write!(f, "Invalid attribute #[facet({attr})]")
+            }
+            GenerateError::Reflect(err) => write!(f, "Reflection error: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for GenerateError<'_> {}
@@ -0,0 +1,455 @@
+//! Generate random values of any Facet type.
+
+use alloc::{borrow::Cow, string::String, sync::Arc};
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use facet_core::{Def, Facet, Field, Type, UserType};
+use facet_reflect::{Partial, ScalarType};
+use log::trace;
+
+use crate::{GenerateError, Source, Strategy, hooks::Hooks};
+
+/// Generates a random value of type `T` with the default [`Generator`] settings.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_arbitrary::SeededSource;
+///
+/// #[derive(Facet, Debug)]
+/// struct Point {
+///     #[facet(arbitrary(range = -10..=10))]
+///     x: i32,
+///     #[facet(arbitrary(range = -10..=10))]
+///     y: i32,
+///     label: Option<String>,
+/// }
+///
+/// let mut source = SeededSource::new(42);
+/// let point: Point = facet_arbitrary::generate(&mut source).unwrap();
+/// assert!((-10..=10).contains(&point.x));
+/// ```
+pub fn generate<'facet: 'shape, 'shape, T: Facet<'facet>>(
+    source: &mut impl Source,
+) -> Result<T, GenerateError<'shape>> {
+    Generator::new().generate(source)
+}
+
+/// Generates random values of Facet types.
+///
+/// Numbers are spread over their whole range, with extra weight on edge cases
+/// like zero and the minimum and maximum values. Fields can narrow this down
+/// with `#[facet(arbitrary(range = 1..=10))]`, and set the length of
+/// collections and strings with `#[facet(arbitrary(len = 1..4))]`.
+#[derive(Clone)]
+pub struct Generator {
+    max_len: usize,
+    max_depth: usize,
+    strategy: Option<Arc<dyn Strategy + Send + Sync>>,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self {
+            max_len: 8,
+            max_depth: 4,
+            strategy: None,
+        }
+    }
+}
+
+impl core::fmt::Debug for Generator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Generator")
+            .field("max_len", &self.max_len)
+            .field("max_depth", &self.max_depth)
+            .field("strategy", &self.strategy.is_some())
+            .finish()
+    }
+}
+
+impl Generator {
+    /// Creates a generator with the default settings: collections and strings
+    /// of at most 8 items, nested at most 4 levels deep.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the longest a collection or string can be, unless a field sets its own `len`.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Sets how deep values can nest before options become `None`, collections
+    /// become as short as allowed, and enums pick the variant least likely to
+    /// nest further. This is what keeps recursive types finite.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Lets `strategy` generate the values it wants to, such as realistic
+    /// names or emails, replacing any strategy set before.
+    pub fn with_strategy(mut self, strategy: impl Strategy + Send + Sync + 'static) -> Self {
+        self.strategy = Some(Arc::new(strategy));
+        self
+    }
+
+    /// Generates a random value of type `T`.
+    pub fn generate<'facet: 'shape, 'shape, T: Facet<'facet>>(
+        &self,
+        source: &mut impl Source,
+    ) -> Result<T, GenerateError<'shape>> {
+        let mut partial = Partial::alloc::<T>()?;
+        let mut run = Run {
+            generator: self,
+            source,
+        };
+        run.value(partial.inner_mut(), &Hooks::default(), 0)?;
+        let value = partial.build()?;
+        Ok(*value)
+    }
+}
+
+/// A single generation, drawing from one source.
+struct Run<'g, S> {
+    generator: &'g Generator,
+    source: S,
+}
+
+impl<S: Source> Run<'_, S> {
+    fn next(&mut self) -> u64 {
+        self.source.next_u64()
+    }
+
+    /// Returns a number in `0..n`.
+    fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            return 0;
+        }
+        ((self.next() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// Returns true once in `n` times.
+    fn one_in(&mut self, n: u64) -> bool {
+        self.below(n) == 0
+    }
+
+    fn len(&mut self, hooks: &Hooks, deep: bool) -> Result<usize, GenerateError<'static>> {
+        let (lo, hi) = match &hooks.len {
+            Some(bounds) => bounds.lens(self.generator.max_len)?,
+            None => (0, self.generator.max_len),
+        };
+        if deep {
+            return Ok(lo);
+        }
+        Ok(self.int_in(lo as i128, hi as i128) as usize)
+    }
+
+    /// Returns an integer in `lo..=hi`, favouring the bounds and zero.
+    fn int_in(&mut self, lo: i128, hi: i128) -> i128 {
+        if self.one_in(8) {
+            return match self.below(3) {
+                0 => lo,
+                1 => hi,
+                _ => 0.clamp(lo, hi),
+            };
+        }
+        let span = hi.abs_diff(lo);
+        let bits = (self.next() as u128) << 64 | self.next() as u128;
+        let offset = match span.checked_add(1) {
+            Some(n) => bits % n,
+            None => bits,
+        };
+        lo.wrapping_add(offset as i128)
+    }
+
+    /// Returns a float in `lo..=hi`, favouring the bounds and zero.
+    fn float_in(&mut self, lo: f64, hi: f64) -> f64 {
+        if self.one_in(8) {
+            return match self.below(3) {
+                0 => lo,
+                1 => hi,
+                _ => 0.0f64.clamp(lo, hi),
+            };
+        }
+        let unit = (self.next() >> 11) as f64 / (1u64 << 53) as f64;
+        // Interpolating like this can't overflow, unlike `lo + unit * (hi - lo)`
+        (lo * (1.0 - unit) + hi * unit).clamp(lo, hi)
+    }
+
+    /// Returns any finite float from its bits, so that every magnitude comes up.
+    fn any_float<F: Copy>(
+        &mut self,
+        from_bits: impl Fn(u64) -> F,
+        is_finite: impl Fn(F) -> bool,
+    ) -> F {
+        loop {
+            let value = from_bits(self.next());
+            if is_finite(value) {
+                return value;
+            }
+        }
+    }
+
+    fn char(&mut self) -> char {
+        if !self.one_in(8) {
+            // Mostly printable ASCII, which keeps failures readable
+            return char::from(b' ' + self.below(95) as u8);
+        }
+        loop {
+            if let Some(c) = char::from_u32(self.below(0x11_0000) as u32) {
+                return c;
+            }
+        }
+    }
+
+    fn string(&mut self, hooks: &Hooks, deep: bool) -> Result<String, GenerateError<'static>> {
+        let len = self.len(hooks, deep)?;
+        Ok((0..len).map(|_| self.char()).collect())
+    }
+
+    fn ip(&mut self) -> IpAddr {
+        if self.one_in(2) {
+            IpAddr::V4(Ipv4Addr::from(self.next() as u32))
+        } else {
+            IpAddr::V6(Ipv6Addr::from(
+                (self.next() as u128) << 64 | self.next() as u128,
+            ))
+        }
+    }
+
+    fn value<'facet, 'shape>(
+        &mut self,
+        wip: &mut Partial<'facet, 'shape>,
+        hooks: &Hooks<'shape>,
+        depth: usize,
+    ) -> Result<(), GenerateError<'shape>> {
+        let shape = wip.shape();
+        let deep = depth >= self.generator.max_depth;
+        trace!("Generating {shape} at depth {depth}");
+
+        if let Some(strategy) = &self.generator.strategy {
+            if strategy.generate(wip, hooks.field, &mut self.source)? {
+                return Ok(());
+            }
+        }
+
+        match shape.def {
+            Def::Option(_) => {
+                if deep || self.one_in(2) {
+                    wip.set_default()?;
+                } else {
+                    wip.begin_some()?;
+                    self.value(wip, hooks, depth + 1)?;
+                    wip.end()?;
+                }
+                return Ok(());
+            }
+            Def::List(_) => {
+                let len = self.len(hooks, deep)?;
+                let items = hooks.items();
+                wip.begin_list()?;
+                for _ in 0..len {
+                    wip.begin_list_item()?;
+                    self.value(wip, &items, depth + 1)?;
+                    wip.end()?;
+                }
+                return Ok(());
+            }
+            Def::Array(ad) => {
+                let items = hooks.items();
+                for index in 0..ad.n {
+                    wip.begin_nth_element(index)?;
+                    self.value(wip, &items, depth + 1)?;
+                    wip.end()?;
+                }
+                return Ok(());
+            }
+            Def::Map(_) => {
+                let len = self.len(hooks, deep)?;
+                let items = hooks.items();
+                wip.begin_map()?;
+                // Keys that come up twice just overwrite each other
+                for _ in 0..len {
+                    wip.begin_key()?;
+                    self.value(wip, &Hooks::default(), depth + 1)?;
+                    wip.end()?;
+                    wip.begin_value()?;
+                    self.value(wip, &items, depth + 1)?;
+                    wip.end()?;
+                }
+                return Ok(());
+            }
+            Def::SmartPointer(_) => {
+                wip.begin_smart_ptr()?;
+                self.value(wip, hooks, depth + 1)?;
+                wip.end()?;
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        if let Some(scalar) = ScalarType::try_from_shape(shape) {
+            return self.scalar(wip, scalar, hooks, deep);
+        }
+
+        if shape.inner.is_some() {
+            wip.begin_inner()?;
+            self.value(wip, hooks, depth)?;
+            wip.end()?;
+            return Ok(());
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(sd)) => self.fields(wip, sd.fields, depth + 1),
+            Type::User(UserType::Enum(ed)) => {
+                if ed.variants.is_empty() {
+                    return Err(GenerateError::UnsupportedShape(shape));
+                }
+                let index = if deep {
+                    // The variant least likely to recurse: options, collections and
+                    // scalars are cut short past the maximum depth, other fields may not be
+                    (0..ed.variants.len())
+                        .min_by_key(|&i| {
+                            let fields = ed.variants[i].data.fields;
+                            let nested = fields
+                                .iter()
+                                .filter(|f| {
+                                    !matches!(
+                                        f.shape().def,
+                                        Def::Scalar(_)
+                                            | Def::Option(_)
+                                            | Def::List(_)
+                                            | Def::Map(_)
+                                    )
+                                })
+                                .count();
+                            (nested, fields.len())
+                        })
+                        .unwrap_or(0)
+                } else {
+                    self.below(ed.variants.len() as u64) as usize
+                };
+                let variant = &ed.variants[index];
+                wip.select_nth_variant(index)?;
+                self.fields(wip, variant.data.fields, depth + 1)
+            }
+            _ => Err(GenerateError::UnsupportedShape(shape)),
+        }
+    }
+
+    /// Fills the fields of a struct or of the selected enum variant.
+    fn fields<'facet, 'shape>(
+        &mut self,
+        wip: &mut Partial<'facet, 'shape>,
+        fields: &'shape [Field<'shape>],
+        depth: usize,
+    ) -> Result<(), GenerateError<'shape>> {
+        for (index, field) in fields.iter().enumerate() {
+            let hooks = Hooks::of(field)?;
+            wip.begin_nth_field(index)?;
+            self.value(wip, &hooks, depth)?;
+            wip.end()?;
+        }
+        Ok(())
+    }
+
+    fn scalar<'facet, 'shape>(
+        &mut self,
+        wip: &mut Partial<'facet, 'shape>,
+        scalar: ScalarType,
+        hooks: &Hooks<'shape>,
+        deep: bool,
+    ) -> Result<(), GenerateError<'shape>> {
+        macro_rules! int {
+            ($ty:ty) => {{
+                let (lo, hi) = match &hooks.range {
+                    Some(bounds) => bounds.ints(<$ty>::MIN as i128, <$ty>::MAX as i128)?,
+                    None => (<$ty>::MIN as i128, <$ty>::MAX as i128),
+                };
+                wip.set(self.int_in(lo, hi) as $ty)?;
+            }};
+        }
+        let floats = |max: f64| {
+            hooks
+                .range
+                .as_ref()
+                .map(|bounds| bounds.floats(-max, max))
+                .transpose()
+        };
+
+        match scalar {
+            ScalarType::Unit => {
+                wip.set(())?;
+            }
+            ScalarType::Bool => {
+                wip.set(self.one_in(2))?;
+            }
+            ScalarType::Char => {
+                wip.set(self.char())?;
+            }
+            ScalarType::String => {
+                wip.set(self.string(hooks, deep)?)?;
+            }
+            ScalarType::CowStr => {
+                wip.set(Cow::<'static, str>::Owned(self.string(hooks, deep)?))?;
+            }
+            ScalarType::ArcStr | ScalarType::RcStr | ScalarType::BoxStr => {
+                wip.parse_from_str(&self.string(hooks, deep)?)?;
+            }
+            ScalarType::F32 => {
+                let value = match floats(f32::MAX as f64)? {
+                    Some((lo, hi)) => self.float_in(lo, hi) as f32,
+                    None => self.any_float(|bits| f32::from_bits(bits as u32), f32::is_finite),
+                };
+                wip.set(value)?;
+            }
+            ScalarType::F64 => {
+                let value = match floats(f64::MAX)? {
+                    Some((lo, hi)) => self.float_in(lo, hi),
+                    None => self.any_float(f64::from_bits, f64::is_finite),
+                };
+                wip.set(value)?;
+            }
+            ScalarType::U8 => int!(u8),
+            ScalarType::U16 => int!(u16),
+            ScalarType::U32 => int!(u32),
+            ScalarType::U64 => int!(u64),
+            ScalarType::USize => int!(usize),
+            ScalarType::I8 => int!(i8),
+            ScalarType::I16 => int!(i16),
+            ScalarType::I32 => int!(i32),
+            ScalarType::I64 => int!(i64),
+            ScalarType::I128 => int!(i128),
+            ScalarType::ISize => int!(isize),
+            ScalarType::U128 => {
+                // Ranges go through `i128`, so they can't reach the top half of `u128`
+                let value = match &hooks.range {
+                    Some(bounds) => {
+                        let (lo, hi) = bounds.ints(0, i128::MAX)?;
+                        self.int_in(lo, hi) as u128
+                    }
+                    None => (self.next() as u128) << 64 | self.next() as u128,
+                };
+                wip.set(value)?;
+            }
+            ScalarType::IpAddr => {
+                wip.set(self.ip())?;
+            }
+            ScalarType::Ipv4Addr => {
+                wip.set(Ipv4Addr::from(self.next() as u32))?;
+            }
+            ScalarType::Ipv6Addr => {
+                wip.set(Ipv6Addr::from(
+                    (self.next() as u128) << 64 | self.next() as u128,
+                ))?;
+            }
+            ScalarType::SocketAddr => {
+                let ip = self.ip();
+                wip.set(SocketAddr::new(ip, self.next() as u16))?;
+            }
+            _ => return Err(GenerateError::UnsupportedShape(wip.shape())),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+//! Per-field `#[facet(arbitrary(...))]` attributes.
+
+use alloc::{borrow::ToOwned, string::String};
+use facet_core::{Field, FieldAttribute};
+
+use crate::GenerateError;
+
+/// What a field's `#[facet(arbitrary(range = 1..=10, len = ..4))]` attribute asks for.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Hooks<'shape> {
+    /// The field the value belongs to, handed to the [`Strategy`](crate::Strategy).
+    pub field: Option<&'shape Field<'shape>>,
+    /// Bounds for numbers, applied through options, pointers and collections.
+    pub range: Option<Bounds>,
+    /// Bounds for the length of the first collection or string reached.
+    pub len: Option<Bounds>,
+}
+
+/// A Rust range expression such as `1..10`, `..=5` or `0..`, with its bounds
+/// still as text so they can be parsed as the field's type.
+#[derive(Debug, Clone)]
+pub(crate) struct Bounds {
+    text: String,
+    lo: Option<String>,
+    hi: Option<String>,
+    inclusive: bool,
+}
+
+impl<'shape> Hooks<'shape> {
+    /// Reads the hooks of a struct or variant field.
+    pub fn of(field: &'shape Field<'shape>) -> Result<Self, GenerateError<'shape>> {
+        let mut hooks = Hooks {
+            field: Some(field),
+            ..Hooks::default()
+        };
+        for attr in field.attributes {
+            let FieldAttribute::Arbitrary(content) = attr else {
+                continue;
+            };
+            let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+            let Some(args) = compact
+                .strip_prefix("arbitrary(")
+                .and_then(|rest| rest.strip_suffix(')'))
+            else {
+                continue;
+            };
+            let invalid = || GenerateError::InvalidAttribute((*content).to_owned());
+            for arg in args.split(',').filter(|arg| !arg.is_empty()) {
+                let (key, value) = arg.split_once('=').ok_or_else(invalid)?;
+                let bounds = Bounds::parse(value).ok_or_else(invalid)?;
+                match key {
+                    "range" => hooks.range = Some(bounds),
+                    "len" => hooks.len = Some(bounds),
+                    _ => return Err(invalid()),
+                }
+            }
+        }
+        Ok(hooks)
+    }
+
+    /// The hooks for the items of a collection: the field and range carry over, the length doesn't.
+    pub fn items(&self) -> Self {
+        Hooks {
+            field: self.field,
+            range: self.range.clone(),
+            len: None,
+        }
+    }
+}
+
+impl Bounds {
+    fn parse(text: &str) -> Option<Self> {
+        let (lo, rest) = text.split_once("..")?;
+        let (hi, inclusive) = match rest.strip_prefix('=') {
+            Some(hi) => (hi, true),
+            None => (rest, false),
+        };
+        if inclusive && hi.is_empty() {
+            return None;
+        }
+        let bound = |s: &str| (!s.is_empty()).then(|| s.to_owned());
+        Some(Bounds {
+            text: text.to_owned(),
+            lo: bound(lo),
+            hi: bound(hi),
+            inclusive,
+        })
+    }
+
+    fn invalid<'shape>(&self) -> GenerateError<'shape> {
+        GenerateError::InvalidAttribute(self.text.clone())
+    }
+
+    /// Inclusive integer bounds, clamped to `min..=max`.
+    pub fn ints<'shape>(
+        &self,
+        min: i128,
+        max: i128,
+    ) -> Result<(i128, i128), GenerateError<'shape>> {
+        let parse = |s: &String| s.parse::<i128>().map_err(|_| self.invalid());
+        let lo = self.lo.as_ref().map(parse).transpose()?.unwrap_or(min);
+        let hi = match self.hi.as_ref().map(parse).transpose()? {
+            Some(hi) if self.inclusive => hi,
+            Some(hi) => hi.checked_sub(1).ok_or_else(|| self.invalid())?,
+            None => max,
+        };
+        let (lo, hi) = (lo.max(min), hi.min(max));
+        if lo > hi {
+            return Err(self.invalid());
+        }
+        Ok((lo, hi))
+    }
+
+    /// Length bounds; without an upper bound, lengths go up to `default_max`
+    /// (or the lower bound, if that is higher).
+    pub fn lens<'shape>(
+        &self,
+        default_max: usize,
+    ) -> Result<(usize, usize), GenerateError<'shape>> {
+        let max = match self.hi {
+            Some(_) => usize::MAX as i128,
+            None => {
+                let (lo, _) = self.ints(0, usize::MAX as i128)?;
+                lo.max(default_max as i128)
+            }
+        };
+        let (lo, hi) = self.ints(0, max)?;
+        Ok((lo as usize, hi as usize))
+    }
+
+    /// Float bounds; the upper bound of `lo..hi` is treated as inclusive.
+    pub fn floats<'shape>(&self, min: f64, max: f64) -> Result<(f64, f64), GenerateError<'shape>> {
+        let parse = |s: &String| s.parse::<f64>().map_err(|_| self.invalid());
+        let lo = self.lo.as_ref().map(parse).transpose()?.unwrap_or(min);
+        let hi = self.hi.as_ref().map(parse).transpose()?.unwrap_or(max);
+        if lo.is_nan() || hi.is_nan() || lo > hi {
+            return Err(self.invalid());
+        }
+        Ok((lo.max(min), hi.min(max)))
+    }
+}
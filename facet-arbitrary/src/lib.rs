@@ -0,0 +1,25 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+#![warn(clippy::std_instead_of_core)]
+#![warn(clippy::std_instead_of_alloc)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+#[cfg(not(feature = "alloc"))]
+compile_error!("feature `alloc` is required");
+
+mod error;
+pub use error::*;
+
+mod source;
+pub use source::*;
+
+mod hooks;
+
+mod strategy;
+pub use strategy::*;
+
+mod generate;
+pub use generate::*;
@@ -0,0 +1,63 @@
+//! Sources of randomness.
+
+/// A source of random bits that drives value generation.
+///
+/// Implemented by [`SeededSource`], by [`RngSource`] for any `rand_core` RNG
+/// with the `rand` feature, and by `arbitrary::Unstructured` with the
+/// `arbitrary` feature.
+pub trait Source {
+    /// Returns the next 64 random bits.
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<S: Source + ?Sized> Source for &mut S {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
+
+/// A small seeded pseudo-random generator (SplitMix64), for reproducible values.
+///
+/// It is fast and has no dependencies, but it is not cryptographically secure.
+#[derive(Debug, Clone)]
+pub struct SeededSource {
+    state: u64,
+}
+
+impl SeededSource {
+    /// Creates a generator; the same seed always produces the same values.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl Source for SeededSource {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Adapts a `rand_core` RNG, such as rand's `StdRng` or `ThreadRng`, into a [`Source`].
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone)]
+pub struct RngSource<R>(pub R);
+
+#[cfg(feature = "rand")]
+impl<R: rand_core::RngCore> Source for RngSource<R> {
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
+
+/// Once the fuzzer's data runs out this yields zeros, which makes collections
+/// empty and picks the first enum variant, so generation always ends.
+#[cfg(feature = "arbitrary")]
+impl Source for arbitrary::Unstructured<'_> {
+    fn next_u64(&mut self) -> u64 {
+        self.arbitrary().unwrap_or(0)
+    }
+}
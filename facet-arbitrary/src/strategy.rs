@@ -0,0 +1,25 @@
+//! Taking over generation for some values.
+
+use facet_core::Field;
+use facet_reflect::Partial;
+
+use crate::{GenerateError, Source};
+
+/// Takes over generating some values, for instance to make them realistic
+/// rather than random. Set one with [`Generator::with_strategy`](crate::Generator::with_strategy).
+///
+/// The strategy is asked first for every value the generator reaches, and the
+/// generator fills in whatever it declines.
+pub trait Strategy {
+    /// Fills `wip` and returns `true`, or returns `false` without touching it.
+    ///
+    /// `field` is the struct or variant field the value belongs to. It is also
+    /// passed for the contents of options and pointers in that field, for the
+    /// items of its lists and arrays, and for the values (not keys) of its maps.
+    fn generate<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        field: Option<&'shape Field<'shape>>,
+        source: &mut dyn Source,
+    ) -> Result<bool, GenerateError<'shape>>;
+}
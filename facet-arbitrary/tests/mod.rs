@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_arbitrary::{GenerateError, Generator, SeededSource, generate};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Settings {
+    #[facet(arbitrary(range = 1..=10))]
+    threads: u8,
+    #[facet(arbitrary(range = -1.0..=1.0))]
+    bias: f32,
+    #[facet(arbitrary(len = 2..4, range = ..0))]
+    offsets: Vec<i64>,
+    #[facet(arbitrary(len = 1..=1))]
+    name: String,
+    #[facet(arbitrary(range = 100..200))]
+    limits: BTreeMap<String, u32>,
+    port: Option<u16>,
+}
+
+#[test]
+fn test_ranges_and_lengths() {
+    let mut source = SeededSource::new(0);
+    for _ in 0..500 {
+        let settings: Settings = generate(&mut source)?;
+        assert!((1..=10).contains(&settings.threads));
+        assert!((-1.0..=1.0).contains(&settings.bias));
+        assert!((2..4).contains(&settings.offsets.len()));
+        assert!(settings.offsets.iter().all(|&o| o < 0));
+        assert_eq!(settings.name.chars().count(), 1);
+        assert!(settings.limits.values().all(|v| (100..200).contains(v)));
+    }
+}
+
+#[test]
+fn test_same_seed_same_values() {
+    let a: Settings = generate(&mut SeededSource::new(9))?;
+    let b: Settings = generate(&mut SeededSource::new(9))?;
+    assert_eq!(a, b);
+}
+
+#[derive(Debug, Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Expr {
+    Add(Box<Expr>, Box<Expr>),
+    Neg(Box<Expr>),
+    Lit(i32),
+    List(Vec<Expr>),
+}
+
+fn depth(expr: &Expr) -> usize {
+    match expr {
+        Expr::Add(a, b) => 1 + depth(a).max(depth(b)),
+        Expr::Neg(e) => 1 + depth(e),
+        Expr::Lit(_) => 1,
+        Expr::List(items) => 1 + items.iter().map(depth).max().unwrap_or(0),
+    }
+}
+
+#[test]
+fn test_recursive_types_stay_finite() {
+    let generator = Generator::new().with_max_depth(6);
+    let mut source = SeededSource::new(1);
+    let mut seen_add = false;
+    for _ in 0..200 {
+        let expr: Expr = generator.generate(&mut source)?;
+        seen_add |= matches!(expr, Expr::Add(..));
+        // Each level of `Expr` goes through a `Box` or a `Vec` as well
+        assert!(depth(&expr) <= 6, "{expr:?}");
+    }
+    assert!(seen_add);
+}
+
+#[test]
+fn test_max_len() {
+    let mut source = SeededSource::new(2);
+    for _ in 0..100 {
+        let items: Vec<u8> = Generator::new().with_max_len(3).generate(&mut source)?;
+        assert!(items.len() <= 3);
+    }
+}
+
+#[test]
+fn test_errors() {
+    #[derive(Debug, Facet)]
+    struct Bad {
+        #[facet(arbitrary(range = 10..5))]
+        value: u8,
+    }
+    let err = generate::<Bad>(&mut SeededSource::new(0)).unwrap_err();
+    assert!(matches!(err, GenerateError::InvalidAttribute(_)));
+
+    #[derive(Debug, Facet)]
+    struct Unknown {
+        #[facet(arbitrary(size = 3))]
+        value: u8,
+    }
+    let err = generate::<Unknown>(&mut SeededSource::new(0)).unwrap_err();
+    assert!(matches!(err, GenerateError::InvalidAttribute(_)));
+
+    let err = generate::<&'static str>(&mut SeededSource::new(0)).unwrap_err();
+    assert!(matches!(err, GenerateError::UnsupportedShape(_)));
+}
@@ -1,11 +1,13 @@
 use alloc::borrow::Cow;
 use alloc::string::ToString;
-use facet_core::{FieldAttribute, Shape, Type, UserType};
+use facet_core::{Def, FieldAttribute, NumericType, PrimitiveType, Shape, Type, UserType};
 use facet_deserialize::{
     DeserErrorKind, Outcome, Raw, Scalar, Span, Spanned, Subspan, SubspanMeta,
 };
 use facet_reflect::Partial;
 
+use crate::arg::extract_subspan;
+
 pub(crate) fn validate_field<'facet, 'shape>(
     field_name: &str,
     shape: &'shape Shape<'shape>,
@@ -76,6 +78,15 @@ pub(crate) fn handle_unset_bool_field_error<'shape>(
     })
 }
 
+/// The flag of a `short = 'x'` attribute, or `""` for a bare `short`
+fn short_flag(attr: &str) -> Option<&str> {
+    let value = attr.trim().strip_prefix("short")?.trim_start();
+    if value.is_empty() {
+        return Some("");
+    }
+    Some(value.strip_prefix('=')?.trim().trim_matches(['\'', '"']))
+}
+
 pub(crate) fn find_field_by_short_flag<'shape>(
     key: &str,
     shape: &'shape Shape<'shape>,
@@ -85,9 +96,13 @@ pub(crate) fn find_field_by_short_flag<'shape>(
             .fields
             .iter()
             .find(|field| {
-                field.attributes.iter().any(|attr| {
-                    matches!(attr, FieldAttribute::Arbitrary(a) if a.contains("short") &&
-                            (a.contains(key) || (key.len() == 1 && field.name == key)))
+                field.attributes.iter().any(|attr| match attr {
+                    FieldAttribute::Arbitrary(a) => match short_flag(a) {
+                        Some("") => key.len() == 1 && field.name == key,
+                        Some(flag) => flag == key,
+                        None => false,
+                    },
+                    _ => false,
                 })
             })
             .map(|field| field.name)
@@ -121,22 +136,52 @@ pub(crate) fn handle_bool_value<'shape>(
 pub(crate) fn validate_value_available<'shape, 'input>(
     arg_idx: usize,
     args: &[&'input str],
+    shape: &Shape,
 ) -> Result<&'input str, DeserErrorKind<'shape>> {
     if arg_idx >= args.len() {
         return Err(create_missing_value_error(args[arg_idx.saturating_sub(1)]));
     }
 
     let arg = args[arg_idx];
-    if arg.starts_with('-') {
+    if is_flag(arg, shape) {
         return Err(create_missing_value_error(args[arg_idx.saturating_sub(1)]));
     }
 
     Ok(arg)
 }
 
+// Get the value after a `--`, which is taken as-is even if it looks like a flag
+pub(crate) fn escaped_value<'input>(arg_idx: usize, args: &[&'input str]) -> Option<&'input str> {
+    match args.get(arg_idx..)? {
+        ["--", value, ..] => Some(value),
+        _ => None,
+    }
+}
+
 // Check if a list has reached its end
-pub(crate) fn is_list_ended(arg_idx: usize, args: &[&str]) -> bool {
-    arg_idx >= args.len() || args[arg_idx].starts_with('-')
+pub(crate) fn is_list_ended(arg_idx: usize, args: &[&str], shape: &Shape) -> bool {
+    arg_idx >= args.len() || is_flag(args[arg_idx], shape)
+}
+
+// Check if an argument is a flag, rather than a value like `-5` for a field that takes one
+pub(crate) fn is_flag(arg: &str, shape: &Shape) -> bool {
+    arg.starts_with('-') && !(takes_negative_number(shape) && arg.parse::<f64>().is_ok())
+}
+
+// Check if values of the shape, or the items of it if it's a list, can be negative numbers
+fn takes_negative_number(shape: &Shape) -> bool {
+    match shape.def {
+        Def::Option(od) => takes_negative_number(od.t()),
+        Def::List(ld) => takes_negative_number(ld.t()),
+        Def::Array(ad) => takes_negative_number(ad.t()),
+        Def::Slice(sd) => takes_negative_number(sd.t()),
+        _ => matches!(
+            shape.ty,
+            Type::Primitive(PrimitiveType::Numeric(
+                NumericType::Integer { signed: true } | NumericType::Float
+            ))
+        ),
+    }
 }
 
 // Validate a struct type and return appropriate error if it's not a struct
@@ -163,6 +208,64 @@ pub(crate) fn create_unknown_field_error<'shape>(
     }
 }
 
+// Check that a flag isn't for a map that's already set, since its entries would be replaced
+pub(crate) fn validate_map_not_set<'facet, 'shape>(
+    field_name: &str,
+    shape: &'shape Shape<'shape>,
+    wip: &Partial<'facet, 'shape>,
+) -> Result<(), DeserErrorKind<'shape>> {
+    if let Type::User(UserType::Struct(st)) = &shape.ty {
+        if let Some(idx) = wip.field_index(field_name) {
+            if matches!(st.fields[idx].shape().def, Def::Map(_))
+                && wip.is_field_set(idx).unwrap_or(false)
+            {
+                return Err(DeserErrorKind::Unimplemented(
+                    "repeating a map argument after other arguments",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Create key and value subspans for a map entry like `key=value` after a flag
+pub(crate) fn create_map_entry_subspans(arg_idx: usize, args: &[&str]) -> Option<Vec<Subspan>> {
+    let flag = args.get(arg_idx.checked_sub(1)?)?;
+    let arg = args.get(arg_idx)?;
+    if !flag.starts_with('-') || arg.starts_with('-') {
+        return None;
+    }
+
+    // Split at the first '=', so values can contain more of them
+    let (key, value) = arg.split_once('=')?;
+    Some(vec![
+        Subspan {
+            offset: 0,
+            len: key.len(),
+            meta: Some(SubspanMeta::KeyValue),
+        },
+        Subspan {
+            offset: key.len() + 1,
+            len: value.len(),
+            meta: Some(SubspanMeta::KeyValue),
+        },
+    ])
+}
+
+// Check if an argument repeats the flag of the map entry before it, with another entry after
+pub(crate) fn is_repeated_map_flag(arg_idx: usize, args: &[&str]) -> bool {
+    arg_idx >= 2
+        && args.get(arg_idx) == Some(&args[arg_idx - 2])
+        && create_map_entry_subspans(arg_idx + 1, args).is_some()
+}
+
+// Check if subspans are for a map entry like `key=value`, rather than a flag like `--key=value`
+pub(crate) fn is_map_entry(subspans: &[Subspan], arg: &str) -> bool {
+    subspans
+        .first()
+        .is_some_and(|key| !extract_subspan(key, arg).starts_with('-'))
+}
+
 /// Create subspans by splitting at all occurrences of a delimiter
 pub(crate) fn create_delimited_subspans(
     arg: &str,
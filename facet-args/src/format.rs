@@ -1,10 +1,10 @@
 use crate::arg::{ArgType, extract_subspan};
 use crate::fields::*;
-use crate::parse::parse_scalar;
+use crate::parse::parse_value;
 use crate::results::*;
 use alloc::borrow::Cow;
 use core::fmt;
-use facet_core::Facet;
+use facet_core::{Def, Facet};
 use facet_deserialize::{
     DeserError, DeserErrorKind, Expectation, Format, NextData, NextResult, Outcome, Raw, Scalar,
     Span, Spanned,
@@ -93,8 +93,28 @@ impl Format for Cli {
 
             // Object key (or finished)
             Expectation::ObjectKeyOrObjectClose => {
+                if let Def::Map(_) = shape.def {
+                    // Entries of a map argument, like `--define key=value`
+                    if has_subspans {
+                        let key = extract_subspan(&subspans[0], args[arg_idx]);
+                        Ok(Spanned {
+                            node: Outcome::Scalar(Scalar::String(Cow::Borrowed(key))),
+                            span: stay_put,
+                        })
+                    } else if is_repeated_map_flag(arg_idx, args) {
+                        // Skip the flag, so the entry after it goes in the same map
+                        wrap_resegmented_result(Vec::new(), step_forth)
+                    } else if let Some(entry) = create_map_entry_subspans(arg_idx, args) {
+                        wrap_resegmented_result(entry, stay_put)
+                    } else {
+                        Ok(Spanned {
+                            node: Outcome::ObjectEnded,
+                            span: stay_put,
+                        })
+                    }
+                }
                 /* Check if we have more arguments */
-                if arg_idx < args.len() {
+                else if arg_idx < args.len() {
                     let arg = args[arg_idx];
 
                     // Check if we need to resegment an arg with '='
@@ -117,14 +137,18 @@ impl Format for Cli {
                         ArgType::LongFlag(key) => {
                             // Validate field exists
                             wrap_string_result(
-                                validate_field(&key, shape, &nd.wip).map(|_| key),
+                                validate_field(&key, shape, &nd.wip)
+                                    .and_then(|_| validate_map_not_set(&key, shape, &nd.wip))
+                                    .map(|_| key),
                                 if has_subspans { stay_put } else { span },
                             )
                         }
                         ArgType::ShortFlag(key) => {
                             // Convert short argument to field name via shape
                             wrap_field_result(
-                                find_field_by_short_flag(key, shape),
+                                find_field_by_short_flag(key, shape).and_then(|name| {
+                                    validate_map_not_set(name, shape, &nd.wip).map(|_| name)
+                                }),
                                 if has_subspans { stay_put } else { span },
                             )
                         }
@@ -147,9 +171,24 @@ impl Format for Cli {
             // Value for the current key
             Expectation::ObjectVal => {
                 // Determine what to do based on the type and available arguments
-                if shape.is_type::<bool>() {
-                    // Handle boolean values (true if we have an arg, false if EOF)
-                    let has_arg = arg_idx < args.len();
+                if let Def::Map(_) = shape.def {
+                    // The entries after the flag are read as the map's keys and values
+                    match create_map_entry_subspans(arg_idx, args) {
+                        Some(_) => Ok(Spanned {
+                            node: Outcome::ObjectStarted,
+                            span: stay_put,
+                        }),
+                        None => Err(Spanned {
+                            node: create_missing_value_error(args[arg_idx.saturating_sub(1)]),
+                            span: Span::new(arg_idx.saturating_sub(1), 0),
+                        }),
+                    }
+                } else if shape.is_type::<bool>()
+                    && !(has_subspans && is_map_entry(subspans, args[arg_idx]))
+                {
+                    // Handle boolean values: true if the flag was given, even as the last
+                    // argument, false if it was injected past the end as an absent flag
+                    let has_arg = arg_idx <= args.len();
                     wrap_result(handle_bool_value(has_arg), Outcome::Scalar, stay_put)
                 } else {
                     // For non-boolean types, check if we have subspans
@@ -167,7 +206,7 @@ impl Format for Cli {
                             _ => {
                                 // Extract the actual substring to use
                                 let part = extract_subspan(subspan, arg);
-                                Some(Ok(parse_scalar(part, span)))
+                                Some(Ok(parse_value(part, shape, span)))
                             }
                         }
                     } else {
@@ -176,9 +215,14 @@ impl Format for Cli {
 
                     // Use the result from above if available, otherwise fall back to regular validation
                     result.unwrap_or_else(|| {
+                        if let Some(arg) = escaped_value(arg_idx, args) {
+                            // Take both the `--` and the value
+                            return Ok(parse_value(arg, shape, Span::new(arg_idx, 2)));
+                        }
+
                         // No usable subspans, fall back to regular validation
-                        match validate_value_available(arg_idx, args) {
-                            Ok(arg) => Ok(parse_scalar(arg, span)),
+                        match validate_value_available(arg_idx, args, shape) {
+                            Ok(arg) => Ok(parse_value(arg, shape, span)),
                             Err(err) => Err(Spanned {
                                 node: err,
                                 span: Span::new(arg_idx.saturating_sub(1), 0),
@@ -191,7 +235,7 @@ impl Format for Cli {
             // List items
             Expectation::ListItemOrListClose => {
                 // End the list if we're out of arguments, or if it's a new flag
-                if is_list_ended(arg_idx, args) {
+                if is_list_ended(arg_idx, args, shape) {
                     // End the list
                     Ok(Spanned {
                         node: Outcome::ListEnded,
@@ -1,5 +1,32 @@
 use alloc::borrow::Cow;
+use facet_core::Shape;
 use facet_deserialize::{Outcome, Raw, Scalar, Span, Spanned};
+use facet_reflect::ScalarType;
+
+pub(crate) fn parse_value<'a>(
+    arg: &'a str,
+    shape: &Shape,
+    span: Span<Raw>,
+) -> Spanned<Outcome<'a>, Raw> {
+    // Keep strings that look like numbers as they are
+    if matches!(
+        ScalarType::try_from_shape(shape),
+        Some(
+            ScalarType::String
+                | ScalarType::Str
+                | ScalarType::CowStr
+                | ScalarType::ArcStr
+                | ScalarType::RcStr
+                | ScalarType::BoxStr
+        )
+    ) {
+        return Spanned {
+            node: Outcome::Scalar(Scalar::String(Cow::Borrowed(arg))),
+            span,
+        };
+    }
+    parse_scalar(arg, span)
+}
 
 pub(crate) fn parse_scalar<'a>(arg: &'a str, span: Span<Raw>) -> Spanned<Outcome<'a>, Raw> {
     // Try to parse numbers in order of specificity
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_deserialize::DeserErrorKind;
+use facet_testhelpers::test;
+
+#[test]
+fn test_map_entries() {
+    #[derive(Facet, Debug)]
+    struct Args {
+        #[facet(named, short = 'D')]
+        define: HashMap<String, String>,
+
+        #[facet(named)]
+        limit: HashMap<String, i64>,
+
+        #[facet(positional)]
+        path: String,
+    }
+
+    let args: Args = facet_args::from_slice(&[
+        "-D",
+        "name=value",
+        "-D",
+        "url=a=b",
+        "--limit",
+        "depth=-1",
+        "--limit",
+        "jobs=4",
+        "input.txt",
+    ])?;
+    assert_eq!(
+        args.define,
+        HashMap::from([
+            ("name".to_string(), "value".to_string()),
+            ("url".to_string(), "a=b".to_string()),
+        ])
+    );
+    assert_eq!(
+        args.limit,
+        HashMap::from([("depth".to_string(), -1), ("jobs".to_string(), 4)])
+    );
+    assert_eq!(args.path, "input.txt");
+}
+
+#[test]
+fn test_map_entry_missing() {
+    #[derive(Facet, Debug)]
+    struct Args {
+        #[facet(named)]
+        define: HashMap<String, String>,
+
+        #[facet(named)]
+        verbose: bool,
+    }
+
+    let err = facet_args::from_slice::<Args>(&["--define", "--verbose"]).unwrap_err();
+    assert!(matches!(err.kind, DeserErrorKind::MissingValue { .. }));
+
+    let err = facet_args::from_slice::<Args>(&["--define", "name"]).unwrap_err();
+    assert!(matches!(err.kind, DeserErrorKind::MissingValue { .. }));
+}
+
+#[test]
+fn test_map_repeated_after_other_arguments() {
+    #[derive(Facet, Debug)]
+    struct Args {
+        #[facet(named)]
+        define: HashMap<String, String>,
+
+        #[facet(named)]
+        verbose: bool,
+    }
+
+    let err = facet_args::from_slice::<Args>(&["--define", "a=1", "--verbose", "--define", "b=2"])
+        .unwrap_err();
+    assert!(matches!(err.kind, DeserErrorKind::Unimplemented(_)));
+}
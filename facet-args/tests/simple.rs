@@ -43,6 +43,27 @@ fn test_arg_parse_easy() {
     assert_eq!(args.consider_casing, 0);
 }
 
+#[test]
+fn test_short_flags_are_matched_exactly() {
+    #[derive(Facet)]
+    struct Args {
+        #[facet(named, short = 'f')]
+        from: String,
+
+        #[facet(named, short = 's')]
+        schema: String,
+
+        #[facet(named, short = 't')]
+        to: String,
+    }
+
+    // `s` and `t` are in `short`, which mustn't matter
+    let args: Args = facet_args::from_slice(&["-t", "yaml", "-s", "schema.json", "-f", "toml"])?;
+    assert_eq!(args.from, "toml");
+    assert_eq!(args.schema, "schema.json");
+    assert_eq!(args.to, "yaml");
+}
+
 #[test]
 fn test_arg_parse() {
     #[derive(Facet)]
@@ -99,6 +120,47 @@ fn test_arg_parse_nums() {
     assert_eq!(args.zzz, 3.0);
 }
 
+#[test]
+fn test_arg_parse_negative_nums() {
+    #[derive(Facet)]
+    struct Args {
+        #[facet(named, short)]
+        x: i64,
+
+        #[facet(named)]
+        offset: i8,
+
+        #[facet(named, short = "z")]
+        zzz: f64,
+
+        #[facet(named)]
+        verbose: bool,
+    }
+
+    let args: Args =
+        facet_args::from_slice(&["-x", "-1", "--offset", "-128", "-z", "-0.5", "--verbose"])?;
+    assert_eq!(args.x, -1);
+    assert_eq!(args.offset, -128);
+    assert_eq!(args.zzz, -0.5);
+    assert!(args.verbose);
+}
+
+#[test]
+fn test_arg_parse_escaped_value() {
+    #[derive(Facet)]
+    struct Args {
+        #[facet(named)]
+        range: String,
+
+        #[facet(named)]
+        offset: i64,
+    }
+
+    let args: Args = facet_args::from_slice(&["--range", "--", "-3..7", "--offset", "--", "-5"])?;
+    assert_eq!(args.range, "-3..7");
+    assert_eq!(args.offset, -5);
+}
+
 // Not yet supported
 #[test]
 #[ignore]
@@ -279,3 +341,14 @@ fn test_missing_required_arg() {
     let err = args_result.unwrap_err();
     insta::assert_snapshot!(err.to_string());
 }
+
+#[test]
+fn test_numeric_string_value() {
+    #[derive(Facet, Debug)]
+    struct Args {
+        #[facet(named)]
+        name: String,
+    }
+    let args: Args = facet_args::from_slice(&["--name", "42"])?;
+    assert_eq!(args.name, "42");
+}
@@ -0,0 +1,227 @@
+//! Decode Avro binary data into Rust values, resolving the writer schema against the Rust type.
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use facet_core::{Facet, Field, FieldFlags, StructType};
+use facet_reflect::{Partial, ScalarType};
+use log::trace;
+
+use crate::{
+    AvroDeError,
+    schema::Schema,
+    types::{AvroType, avro_field_name},
+    wire::{Reader, Value},
+};
+
+/// Decodes Avro binary data written with the schema of `T` itself.
+///
+/// Use [`from_slice_with_schema`] when the data was written with a different
+/// version of the schema.
+pub fn from_slice<'input, 'facet: 'shape, 'shape, T: Facet<'facet>>(
+    input: &'input [u8],
+) -> Result<T, AvroDeError<'shape>> {
+    let writer =
+        Schema::for_shape(T::SHAPE).map_err(|_| AvroDeError::UnsupportedShape(T::SHAPE))?;
+    from_slice_with_schema(input, &writer)
+}
+
+/// Decodes Avro binary data written with `writer`, resolving it against `T` as the
+/// reader schema.
+///
+/// Resolution follows the Avro specification:
+/// - record fields are matched by name, so they can be reordered;
+/// - fields the writer doesn't have take their default value: the field's
+///   `#[facet(default)]`, or `None` for options;
+/// - fields the reader doesn't have are skipped;
+/// - enum symbols and union records are matched by name;
+/// - `int` and `long` are promoted to `float` and `double`, and `string` and
+///   `bytes` are interchangeable.
+pub fn from_slice_with_schema<'input, 'facet: 'shape, 'shape, T: Facet<'facet>>(
+    input: &'input [u8],
+    writer: &Schema,
+) -> Result<T, AvroDeError<'shape>> {
+    let mut reader = Reader::new(input, writer);
+    let value = reader.read(writer)?;
+    if !reader.is_empty() {
+        return Err(AvroDeError::TrailingData);
+    }
+
+    let mut partial = Partial::alloc::<T>()?;
+    fill(partial.inner_mut(), value)?;
+    let value = partial.build()?;
+    Ok(*value)
+}
+
+fn mismatch<'shape>(value: &Value, wip: &Partial<'_, 'shape>) -> AvroDeError<'shape> {
+    AvroDeError::TypeMismatch {
+        writer: value.kind(),
+        shape: wip.shape(),
+    }
+}
+
+fn fill<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: Value,
+) -> Result<(), AvroDeError<'shape>> {
+    let shape = wip.shape();
+    let ty = AvroType::of(shape).ok_or(AvroDeError::UnsupportedShape(shape))?;
+    let out_of_range = |_| AvroDeError::NumberOutOfRange(shape);
+
+    match (ty, value) {
+        (AvroType::Optional(_), Value::Null) => {
+            wip.set_default()?;
+        }
+        (AvroType::Optional(_), value) => {
+            wip.begin_some()?;
+            fill(wip, value)?;
+            wip.end()?;
+        }
+        (AvroType::Transparent(_), value) => {
+            wip.begin_inner()?;
+            fill(wip, value)?;
+            wip.end()?;
+        }
+        (AvroType::Record(sd), Value::Record(_, fields)) => fill_fields(wip, sd, fields)?,
+        (AvroType::Union(ed), Value::Record(name, fields)) => {
+            // Union records are named `Enum.Variant`
+            let variant_name = name.rsplit('.').next().unwrap_or(&name);
+            let Some(index) = ed.variants.iter().position(|v| v.name == variant_name) else {
+                return Err(AvroDeError::UnknownSymbol {
+                    symbol: name,
+                    shape,
+                });
+            };
+            wip.select_nth_variant(index)?;
+            fill_fields(wip, ed.variants[index].data, fields)?;
+        }
+        (AvroType::Enum(ed), Value::Enum(symbol)) => {
+            let Some(index) = ed.variants.iter().position(|v| v.name == symbol) else {
+                return Err(AvroDeError::UnknownSymbol { symbol, shape });
+            };
+            wip.select_nth_variant(index)?;
+        }
+        (AvroType::Array(_), Value::Array(items)) => {
+            wip.begin_list()?;
+            for item in items {
+                wip.begin_list_item()?;
+                fill(wip, item)?;
+                wip.end()?;
+            }
+        }
+        (AvroType::Map(..), Value::Map(entries)) => {
+            wip.begin_map()?;
+            for (key, value) in entries {
+                wip.begin_key()?;
+                fill(wip, Value::String(key))?;
+                wip.end()?;
+                wip.begin_value()?;
+                fill(wip, value)?;
+                wip.end()?;
+            }
+        }
+        (AvroType::Null, Value::Null) => {
+            wip.set_default()?;
+        }
+        (AvroType::Boolean, Value::Boolean(b)) => {
+            wip.set(b)?;
+        }
+        (AvroType::Int | AvroType::Long, Value::Long(v)) => {
+            match ScalarType::try_from_shape(shape) {
+                Some(ScalarType::U8) => wip.set(u8::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::U16) => wip.set(u16::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::U32) => wip.set(u32::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::U64) => wip.set(u64::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::USize) => wip.set(usize::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::I8) => wip.set(i8::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::I16) => wip.set(i16::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::I32) => wip.set(i32::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::I64) => wip.set(v)?,
+                Some(ScalarType::ISize) => wip.set(isize::try_from(v).map_err(out_of_range)?)?,
+                _ => return Err(AvroDeError::UnsupportedShape(shape)),
+            };
+        }
+        (AvroType::Float, Value::Float(v)) => {
+            wip.set(v)?;
+        }
+        (AvroType::Float, Value::Long(v)) => {
+            wip.set(v as f32)?;
+        }
+        (AvroType::Double, Value::Double(v)) => {
+            wip.set(v)?;
+        }
+        (AvroType::Double, Value::Float(v)) => {
+            wip.set(v as f64)?;
+        }
+        (AvroType::Double, Value::Long(v)) => {
+            wip.set(v as f64)?;
+        }
+        (AvroType::Bytes, Value::Bytes(b)) => {
+            wip.set(b)?;
+        }
+        (AvroType::Bytes, Value::String(s)) => {
+            wip.set(s.into_bytes())?;
+        }
+        (AvroType::String | AvroType::Text, Value::Bytes(b)) => {
+            let s = String::from_utf8(b).map_err(|_| AvroDeError::InvalidUtf8)?;
+            fill(wip, Value::String(s))?;
+        }
+        (AvroType::String, Value::String(s)) => {
+            if let Some(ScalarType::CowStr) = ScalarType::try_from_shape(shape) {
+                wip.set(Cow::<'static, str>::Owned(s))?;
+            } else {
+                wip.set(s)?;
+            }
+        }
+        (AvroType::Text, Value::String(s)) => {
+            wip.parse_from_str(&s)
+                .map_err(|_| AvroDeError::InvalidValue(shape))?;
+        }
+        (_, value) => return Err(mismatch(&value, wip)),
+    }
+    Ok(())
+}
+
+/// Fills the fields of a struct or the selected enum variant, matching the writer's
+/// fields by name.
+fn fill_fields<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    sd: StructType<'shape>,
+    mut fields: Vec<(String, Value)>,
+) -> Result<(), AvroDeError<'shape>> {
+    for (index, field) in sd.fields.iter().enumerate() {
+        let name = avro_field_name(field.name);
+        let value = fields
+            .iter()
+            .position(|(n, _)| *n == name)
+            .map(|position| fields.swap_remove(position).1);
+        wip.begin_nth_field(index)?;
+        match value {
+            Some(value) => fill(wip, value)?,
+            None => set_missing(wip, field)?,
+        }
+        wip.end()?;
+    }
+    for (name, _) in fields {
+        trace!("Skipping field {name} that {} doesn't have", wip.shape());
+    }
+    Ok(())
+}
+
+/// Sets a field the writer schema doesn't have to its default, as the reader schema
+/// generated by [`to_avro_schema`](crate::to_avro_schema) declares it.
+fn set_missing<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    field: &Field<'shape>,
+) -> Result<(), AvroDeError<'shape>> {
+    if field.flags.contains(FieldFlags::DEFAULT) {
+        match field.vtable.default_fn {
+            Some(default_fn) => wip.set_field_default(default_fn)?,
+            None => wip.set_default()?,
+        };
+        return Ok(());
+    }
+    if let Some(AvroType::Optional(_)) = AvroType::of(field.shape()) {
+        wip.set_default()?;
+        return Ok(());
+    }
+    Err(AvroDeError::MissingField(field.name))
+}
@@ -0,0 +1,139 @@
+//! Errors from encoding, decoding and parsing Avro schemas.
+
+use alloc::string::String;
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+
+/// Errors when decoding Avro bytes.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AvroDeError<'shape> {
+    /// The input ended in the middle of a value.
+    UnexpectedEof,
+    /// A varint was longer than 10 bytes.
+    VarintOverflow,
+    /// The input has bytes left over after the value.
+    TrailingData,
+    /// A string doesn't contain valid UTF-8.
+    InvalidUtf8,
+    /// A union branch index is out of range for the writer schema.
+    InvalidUnionIndex(i64),
+    /// An enum symbol index is out of range for the writer schema.
+    InvalidEnumIndex(i64),
+    /// The writer schema refers to a named type it doesn't define.
+    UnknownName(String),
+    /// The writer schema type can't be resolved against the Rust type.
+    TypeMismatch {
+        /// The Avro type found in the writer schema.
+        writer: &'static str,
+        /// The Rust type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// An enum symbol or union record doesn't match any variant of the Rust enum.
+    UnknownSymbol {
+        /// The symbol found in the input.
+        symbol: String,
+        /// The enum type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A number doesn't fit the Rust type it's decoded into.
+    NumberOutOfRange(&'shape Shape<'shape>),
+    /// A string couldn't be parsed into the Rust type.
+    InvalidValue(&'shape Shape<'shape>),
+    /// A field is missing from the writer schema and has no default value.
+    MissingField(&'shape str),
+    /// The Rust type can't be represented in Avro.
+    UnsupportedShape(&'shape Shape<'shape>),
+    /// Any error from facet.
+    Reflect(ReflectError<'shape>),
+}
+
+impl<'shape> From<ReflectError<'shape>> for AvroDeError<'shape> {
+    fn from(err: ReflectError<'shape>) -> Self {
+        AvroDeError::Reflect(err)
+    }
+}
+
+impl core::fmt::Display for AvroDeError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AvroDeError::UnexpectedEof => write!(f, "Unexpected end of input"),
+            AvroDeError::VarintOverflow => write!(f, "Varint is too long"),
+            AvroDeError::TrailingData => write!(f, "Unexpected data after the value"),
+            AvroDeError::InvalidUtf8 => write!(f, "Invalid UTF-8 in string"),
+            AvroDeError::InvalidUnionIndex(index) => write!(f, "Invalid union index {index}"),
+            AvroDeError::InvalidEnumIndex(index) => write!(f, "Invalid enum index {index}"),
+            AvroDeError::UnknownName(name) => {
+                write!(f, "Schema refers to undefined type '{name}'")
+            }
+            AvroDeError::TypeMismatch { writer, shape } => {
+                write!(f, "Can't read Avro {writer} as {shape}")
+            }
+            AvroDeError::UnknownSymbol { symbol, shape } => {
+                write!(f, "'{symbol}' is not a variant of {shape}")
+            }
+            AvroDeError::NumberOutOfRange(shape) => {
+                write!(f, "Number out of range for {shape}")
+            }
+            AvroDeError::InvalidValue(shape) => write!(f, "Can't parse string as {shape}"),
+            AvroDeError::MissingField(name) => write!(f, "Missing field '{name}'"),
+            AvroDeError::UnsupportedShape(shape) => {
+                write!(f, "Type {shape} can't be represented in Avro")
+            }
+            AvroDeError::Reflect(err) => write!(f, "Reflection error: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for AvroDeError<'_> {}
+
+/// Errors when encoding Avro bytes or generating an Avro schema.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AvroSerError {
+    /// A type can't be represented in Avro.
+    UnsupportedType(String),
+    /// An unsigned number is too large for an Avro `long`.
+    NumberOutOfRange(String),
+}
+
+impl core::fmt::Display for AvroSerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AvroSerError::UnsupportedType(ty) => {
+                write!(f, "Type {ty} can't be represented in Avro")
+            }
+            AvroSerError::NumberOutOfRange(value) => {
+                write!(f, "{value} doesn't fit in an Avro long")
+            }
+        }
+    }
+}
+
+impl core::error::Error for AvroSerError {}
+
+/// Errors when parsing an Avro schema from JSON.
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum AvroSchemaError {
+    /// The schema is not valid JSON.
+    InvalidJson {
+        /// The byte offset of the error.
+        offset: usize,
+    },
+    /// The JSON is not a valid Avro schema.
+    InvalidSchema(String),
+}
+
+impl core::fmt::Display for AvroSchemaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AvroSchemaError::InvalidJson { offset } => {
+                write!(f, "Invalid JSON at byte {offset}")
+            }
+            AvroSchemaError::InvalidSchema(reason) => write!(f, "Invalid Avro schema: {reason}"),
+        }
+    }
+}
+
+impl core::error::Error for AvroSchemaError {}
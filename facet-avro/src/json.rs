@@ -0,0 +1,272 @@
+//! Just enough JSON to read and write Avro schemas.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Write};
+
+use crate::AvroSchemaError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum JsonValue {
+    Null,
+    Bool(bool),
+    /// Kept as written so that large integers survive a round trip.
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    pub(crate) fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonValue::Null => f.write_str("null"),
+            JsonValue::Bool(b) => write!(f, "{b}"),
+            JsonValue::Number(n) => f.write_str(n),
+            JsonValue::String(s) => write_json_string(f, s),
+            JsonValue::Array(items) => {
+                f.write_char('[')?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_char(']')
+            }
+            JsonValue::Object(entries) => {
+                f.write_char('{')?;
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_json_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+pub(crate) fn write_json_string(f: &mut impl Write, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// Parses a complete JSON document.
+pub(crate) fn parse(input: &str) -> Result<JsonValue, AvroSchemaError> {
+    let mut parser = Parser {
+        input: input.as_bytes(),
+        pos: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos != parser.input.len() {
+        return Err(parser.error());
+    }
+    Ok(value)
+}
+
+struct Parser<'input> {
+    input: &'input [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self) -> AvroSchemaError {
+        AvroSchemaError::InvalidJson { offset: self.pos }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.input.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), AvroSchemaError> {
+        if self.peek() != Some(byte) {
+            return Err(self.error());
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn keyword(&mut self, word: &str, value: JsonValue) -> Result<JsonValue, AvroSchemaError> {
+        if !self.input[self.pos..].starts_with(word.as_bytes()) {
+            return Err(self.error());
+        }
+        self.pos += word.len();
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<JsonValue, AvroSchemaError> {
+        match self.peek().ok_or_else(|| self.error())? {
+            b'n' => self.keyword("null", JsonValue::Null),
+            b't' => self.keyword("true", JsonValue::Bool(true)),
+            b'f' => self.keyword("false", JsonValue::Bool(false)),
+            b'"' => Ok(JsonValue::String(self.string()?)),
+            b'[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(JsonValue::Array(items));
+                        }
+                        _ => return Err(self.error()),
+                    }
+                }
+            }
+            b'{' => {
+                self.pos += 1;
+                let mut entries = Vec::new();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(entries));
+                }
+                loop {
+                    if self.peek() != Some(b'"') {
+                        return Err(self.error());
+                    }
+                    let key = self.string()?;
+                    self.expect(b':')?;
+                    entries.push((key, self.value()?));
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(JsonValue::Object(entries));
+                        }
+                        _ => return Err(self.error()),
+                    }
+                }
+            }
+            b'-' | b'0'..=b'9' => {
+                let start = self.pos;
+                while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') =
+                    self.input.get(self.pos)
+                {
+                    self.pos += 1;
+                }
+                let number =
+                    core::str::from_utf8(&self.input[start..self.pos]).map_err(|_| self.error())?;
+                if number.parse::<f64>().is_err() {
+                    self.pos = start;
+                    return Err(self.error());
+                }
+                Ok(JsonValue::Number(number.to_string()))
+            }
+            _ => Err(self.error()),
+        }
+    }
+
+    /// Parses a string literal; the cursor is on the opening quote.
+    fn string(&mut self) -> Result<String, AvroSchemaError> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let start = self.pos;
+            while let Some(&b) = self.input.get(self.pos) {
+                if b == b'"' || b == b'\\' {
+                    break;
+                }
+                self.pos += 1;
+            }
+            out.push_str(
+                core::str::from_utf8(&self.input[start..self.pos]).map_err(|_| self.error())?,
+            );
+            match self.input.get(self.pos) {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escaped = match self.input.get(self.pos).ok_or_else(|| self.error())? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let code = self.hex4()?;
+                            let code = if (0xd800..0xdc00).contains(&code) {
+                                // A surrogate pair
+                                if !self.input[self.pos + 1..].starts_with(b"\\u") {
+                                    return Err(self.error());
+                                }
+                                self.pos += 2;
+                                let low = self.hex4()?;
+                                0x10000 + ((code - 0xd800) << 10) + (low.wrapping_sub(0xdc00))
+                            } else {
+                                code
+                            };
+                            char::from_u32(code).ok_or_else(|| self.error())?
+                        }
+                        _ => return Err(self.error()),
+                    };
+                    self.pos += 1;
+                    out.push(escaped);
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    /// Reads the four hex digits after `\u`, leaving the cursor on the last one.
+    fn hex4(&mut self) -> Result<u32, AvroSchemaError> {
+        let digits = self
+            .input
+            .get(self.pos + 1..self.pos + 5)
+            .and_then(|d| core::str::from_utf8(d).ok())
+            .ok_or_else(|| self.error())?;
+        let code = u32::from_str_radix(digits, 16).map_err(|_| self.error())?;
+        self.pos += 4;
+        Ok(code)
+    }
+}
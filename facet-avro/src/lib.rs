@@ -0,0 +1,27 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+#![warn(clippy::std_instead_of_core)]
+#![warn(clippy::std_instead_of_alloc)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+#[cfg(not(feature = "alloc"))]
+compile_error!("feature `alloc` is required");
+
+mod error;
+pub use error::*;
+
+mod json;
+mod types;
+mod wire;
+
+mod schema;
+pub use schema::*;
+
+mod deserialize;
+pub use deserialize::*;
+
+mod serialize;
+pub use serialize::*;
@@ -0,0 +1,602 @@
+//! Avro schemas: generated from Facet shapes, or parsed from JSON.
+
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use facet_core::{EnumType, Facet, FieldFlags, Shape, StructType};
+use facet_reflect::{Partial, Peek};
+
+use crate::{
+    AvroSchemaError, AvroSerError,
+    json::{self, JsonValue},
+    types::{AvroType, avro_field_name},
+};
+
+/// An Avro schema.
+///
+/// Named types (records, enums and fixed) are defined where they first appear, and
+/// referred to with [`Schema::Named`] afterwards, as in the JSON form.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Schema {
+    /// `"null"`
+    Null,
+    /// `"boolean"`
+    Boolean,
+    /// `"int"`, a 32-bit signed integer
+    Int,
+    /// `"long"`, a 64-bit signed integer
+    Long,
+    /// `"float"`
+    Float,
+    /// `"double"`
+    Double,
+    /// `"bytes"`
+    Bytes,
+    /// `"string"`
+    String,
+    /// A record with named fields
+    Record(RecordSchema),
+    /// An enum with named symbols
+    Enum(EnumSchema),
+    /// An array with items of the given schema
+    Array(Box<Schema>),
+    /// A map from strings to values of the given schema
+    Map(Box<Schema>),
+    /// A union of several schemas
+    Union(Vec<Schema>),
+    /// A fixed number of bytes
+    Fixed(FixedSchema),
+    /// A reference to a named type defined elsewhere in the schema
+    Named(String),
+}
+
+/// An Avro record schema.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct RecordSchema {
+    /// The full name of the record.
+    pub name: String,
+    /// Documentation for the record.
+    pub doc: Option<String>,
+    /// The fields, in encoding order.
+    pub fields: Vec<RecordField>,
+}
+
+/// A field of an Avro record.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct RecordField {
+    /// The field name.
+    pub name: String,
+    /// Documentation for the field.
+    pub doc: Option<String>,
+    /// The field schema.
+    pub schema: Schema,
+    pub(crate) default: Option<JsonValue>,
+}
+
+impl RecordField {
+    /// The default value readers use when the field is missing from the writer schema, as JSON.
+    pub fn default_json(&self) -> Option<String> {
+        self.default.as_ref().map(|d| d.to_string())
+    }
+}
+
+/// An Avro enum schema.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct EnumSchema {
+    /// The full name of the enum.
+    pub name: String,
+    /// Documentation for the enum.
+    pub doc: Option<String>,
+    /// The symbols, in encoding order.
+    pub symbols: Vec<String>,
+}
+
+/// An Avro fixed schema.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct FixedSchema {
+    /// The full name of the fixed type.
+    pub name: String,
+    /// The number of bytes.
+    pub size: usize,
+}
+
+/// Generates the Avro schema of `T`, as JSON.
+///
+/// The schema matches what [`to_vec`](crate::to_vec) writes, and can be registered
+/// with a schema registry or passed to [`Schema::parse`] on the reading side.
+pub fn to_avro_schema<'facet, T: Facet<'facet>>() -> Result<String, AvroSerError> {
+    Ok(Schema::for_shape(T::SHAPE)?.to_string())
+}
+
+impl Schema {
+    /// Generates the schema of a shape. See [`to_avro_schema`].
+    pub fn for_shape(shape: &Shape<'_>) -> Result<Schema, AvroSerError> {
+        SchemaBuilder {
+            defined: Vec::new(),
+        }
+        .schema(shape)
+    }
+
+    /// Parses a schema from its JSON form.
+    pub fn parse(json: &str) -> Result<Schema, AvroSchemaError> {
+        from_json(&json::parse(json)?, None)
+    }
+
+    /// The name Avro uses for this schema's type, such as `"record"` or `"long"`.
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            Schema::Null => "null",
+            Schema::Boolean => "boolean",
+            Schema::Int => "int",
+            Schema::Long => "long",
+            Schema::Float => "float",
+            Schema::Double => "double",
+            Schema::Bytes => "bytes",
+            Schema::String => "string",
+            Schema::Record(_) => "record",
+            Schema::Enum(_) => "enum",
+            Schema::Array(_) => "array",
+            Schema::Map(_) => "map",
+            Schema::Union(_) => "union",
+            Schema::Fixed(_) => "fixed",
+            Schema::Named(_) => "named type",
+        }
+    }
+
+    /// Every named type defined in this schema, by full name.
+    pub(crate) fn named_types(&self) -> Vec<(&str, &Schema)> {
+        let mut out = Vec::new();
+        self.collect_named(&mut out);
+        out
+    }
+
+    fn collect_named<'a>(&'a self, out: &mut Vec<(&'a str, &'a Schema)>) {
+        match self {
+            Schema::Record(record) => {
+                out.push((&record.name, self));
+                for field in &record.fields {
+                    field.schema.collect_named(out);
+                }
+            }
+            Schema::Enum(EnumSchema { name, .. }) | Schema::Fixed(FixedSchema { name, .. }) => {
+                out.push((name, self))
+            }
+            Schema::Array(items) | Schema::Map(items) => items.collect_named(out),
+            Schema::Union(branches) => {
+                for branch in branches {
+                    branch.collect_named(out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn to_json(&self) -> JsonValue {
+        let string = |s: &str| JsonValue::String(s.to_owned());
+        let named = |kind: &str, name: &str, doc: &Option<String>| {
+            let mut entries = vec![
+                ("type".to_owned(), string(kind)),
+                ("name".to_owned(), string(name)),
+            ];
+            if let Some(doc) = doc {
+                entries.push(("doc".to_owned(), string(doc)));
+            }
+            entries
+        };
+
+        match self {
+            Schema::Record(record) => {
+                let mut entries = named("record", &record.name, &record.doc);
+                let fields = record
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let mut entries = vec![("name".to_owned(), string(&field.name))];
+                        if let Some(doc) = &field.doc {
+                            entries.push(("doc".to_owned(), string(doc)));
+                        }
+                        entries.push(("type".to_owned(), field.schema.to_json()));
+                        if let Some(default) = &field.default {
+                            entries.push(("default".to_owned(), default.clone()));
+                        }
+                        JsonValue::Object(entries)
+                    })
+                    .collect();
+                entries.push(("fields".to_owned(), JsonValue::Array(fields)));
+                JsonValue::Object(entries)
+            }
+            Schema::Enum(en) => {
+                let mut entries = named("enum", &en.name, &en.doc);
+                let symbols = en.symbols.iter().map(|s| string(s)).collect();
+                entries.push(("symbols".to_owned(), JsonValue::Array(symbols)));
+                JsonValue::Object(entries)
+            }
+            Schema::Fixed(fixed) => {
+                let mut entries = named("fixed", &fixed.name, &None);
+                entries.push(("size".to_owned(), JsonValue::Number(fixed.size.to_string())));
+                JsonValue::Object(entries)
+            }
+            Schema::Array(items) => JsonValue::Object(vec![
+                ("type".to_owned(), string("array")),
+                ("items".to_owned(), items.to_json()),
+            ]),
+            Schema::Map(values) => JsonValue::Object(vec![
+                ("type".to_owned(), string("map")),
+                ("values".to_owned(), values.to_json()),
+            ]),
+            Schema::Union(branches) => {
+                JsonValue::Array(branches.iter().map(Schema::to_json).collect())
+            }
+            Schema::Named(name) => string(name),
+            primitive => string(primitive.type_name()),
+        }
+    }
+}
+
+/// Writes the schema as compact JSON.
+impl core::fmt::Display for Schema {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
+}
+
+struct SchemaBuilder<'a> {
+    /// Named types already defined, which later uses refer to by name.
+    defined: Vec<&'a Shape<'a>>,
+}
+
+impl<'a> SchemaBuilder<'a> {
+    fn schema(&mut self, shape: &'a Shape<'a>) -> Result<Schema, AvroSerError> {
+        let unsupported = || AvroSerError::UnsupportedType(shape.to_string());
+        Ok(match AvroType::of(shape).ok_or_else(unsupported)? {
+            AvroType::Null => Schema::Null,
+            AvroType::Boolean => Schema::Boolean,
+            AvroType::Int => Schema::Int,
+            AvroType::Long => Schema::Long,
+            AvroType::Float => Schema::Float,
+            AvroType::Double => Schema::Double,
+            AvroType::String | AvroType::Text => Schema::String,
+            AvroType::Bytes => Schema::Bytes,
+            AvroType::Transparent(inner) => self.schema(inner)?,
+            AvroType::Optional(inner) => match self.schema(inner)? {
+                // A union can't contain another union, nor `null` twice
+                Schema::Null | Schema::Union(_) => return Err(unsupported()),
+                inner => Schema::Union(vec![Schema::Null, inner]),
+            },
+            AvroType::Array(items) => Schema::Array(Box::new(self.schema(items)?)),
+            AvroType::Map(key, value) => {
+                if !matches!(AvroType::of(key), Some(AvroType::String | AvroType::Text)) {
+                    return Err(AvroSerError::UnsupportedType(key.to_string()));
+                }
+                Schema::Map(Box::new(self.schema(value)?))
+            }
+            AvroType::Record(sd) => {
+                if self.defined.contains(&shape) {
+                    return Ok(Schema::Named(shape.type_identifier.to_owned()));
+                }
+                self.defined.push(shape);
+                Schema::Record(RecordSchema {
+                    name: shape.type_identifier.to_owned(),
+                    doc: doc_string(shape.doc),
+                    fields: self.fields(sd)?,
+                })
+            }
+            AvroType::Enum(ed) => {
+                if self.defined.contains(&shape) {
+                    return Ok(Schema::Named(shape.type_identifier.to_owned()));
+                }
+                self.defined.push(shape);
+                Schema::Enum(EnumSchema {
+                    name: shape.type_identifier.to_owned(),
+                    doc: doc_string(shape.doc),
+                    symbols: ed.variants.iter().map(|v| v.name.to_owned()).collect(),
+                })
+            }
+            AvroType::Union(ed) => self.union(shape, ed)?,
+        })
+    }
+
+    /// An enum with data becomes a union of records named `Enum.Variant`.
+    fn union(&mut self, shape: &'a Shape<'a>, ed: EnumType<'a>) -> Result<Schema, AvroSerError> {
+        let variant_name = |name: &str| format!("{}.{name}", shape.type_identifier);
+        if self.defined.contains(&shape) {
+            let branches = ed
+                .variants
+                .iter()
+                .map(|v| Schema::Named(variant_name(v.name)));
+            return Ok(Schema::Union(branches.collect()));
+        }
+        self.defined.push(shape);
+
+        let mut branches = Vec::with_capacity(ed.variants.len());
+        for variant in ed.variants {
+            branches.push(Schema::Record(RecordSchema {
+                name: variant_name(variant.name),
+                doc: doc_string(variant.doc),
+                fields: self.fields(variant.data)?,
+            }));
+        }
+        Ok(Schema::Union(branches))
+    }
+
+    fn fields(&mut self, sd: StructType<'a>) -> Result<Vec<RecordField>, AvroSerError> {
+        let mut fields = Vec::with_capacity(sd.fields.len());
+        for field in sd.fields {
+            let shape = field.shape();
+            let has_default = field.flags.contains(FieldFlags::DEFAULT);
+            let default = if has_default {
+                default_value(shape, field.vtable.default_fn)
+            } else if let Some(AvroType::Optional(_)) = AvroType::of(shape) {
+                Some(JsonValue::Null)
+            } else {
+                None
+            };
+            fields.push(RecordField {
+                name: avro_field_name(field.name).into_owned(),
+                doc: doc_string(field.doc),
+                schema: self.schema(shape)?,
+                default,
+            });
+        }
+        Ok(fields)
+    }
+}
+
+/// Joins doc comment lines, or `None` if there are none.
+fn doc_string(doc: &[&str]) -> Option<String> {
+    let lines: Vec<&str> = doc.iter().map(|line| line.trim()).collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// The JSON default of a field, or `None` if it has no default Avro can express.
+fn default_value(
+    shape: &Shape<'_>,
+    default_fn: Option<facet_core::DefaultInPlaceFn>,
+) -> Option<JsonValue> {
+    let mut partial = Partial::alloc_shape(shape).ok()?;
+    match default_fn {
+        Some(default_fn) => partial.set_field_default(default_fn).ok()?,
+        None => partial.set_default().ok()?,
+    };
+    let value = partial.build().ok()?;
+    default_json(value.peek())
+}
+
+/// Converts a value to the JSON Avro uses for defaults.
+///
+/// Unions take the value of their first branch, so only `None` and the first
+/// variant of an enum with data can be expressed.
+fn default_json(peek: Peek<'_, '_, '_>) -> Option<JsonValue> {
+    Some(match AvroType::of(peek.shape())? {
+        AvroType::Null => JsonValue::Null,
+        AvroType::Boolean => JsonValue::Bool(*peek.get::<bool>().ok()?),
+        AvroType::Int | AvroType::Long => JsonValue::Number(format!("{peek}")),
+        AvroType::Float => json_float(*peek.get::<f32>().ok()? as f64)?,
+        AvroType::Double => json_float(*peek.get::<f64>().ok()?)?,
+        AvroType::String | AvroType::Text => JsonValue::String(match peek.as_str() {
+            Some(s) => s.to_owned(),
+            None => format!("{peek}"),
+        }),
+        // Avro writes bytes defaults as a string of code points 0-255
+        AvroType::Bytes => JsonValue::String(
+            peek.get::<Vec<u8>>()
+                .ok()?
+                .iter()
+                .map(|&b| b as char)
+                .collect(),
+        ),
+        AvroType::Transparent(_) => default_json(peek.innermost_peek())?,
+        AvroType::Optional(_) => match peek.into_option().ok()?.value() {
+            None => JsonValue::Null,
+            Some(_) => return None,
+        },
+        AvroType::Enum(_) => JsonValue::String(
+            peek.into_enum()
+                .ok()?
+                .active_variant()
+                .ok()?
+                .name
+                .to_owned(),
+        ),
+        AvroType::Union(_) => {
+            let pe = peek.into_enum().ok()?;
+            if pe.variant_index().ok()? != 0 {
+                return None;
+            }
+            let variant = pe.active_variant().ok()?;
+            let mut entries = Vec::with_capacity(variant.data.fields.len());
+            for (index, field) in variant.data.fields.iter().enumerate() {
+                let value = default_json(pe.field(index).ok()??)?;
+                entries.push((avro_field_name(field.name).into_owned(), value));
+            }
+            JsonValue::Object(entries)
+        }
+        AvroType::Record(sd) => {
+            let ps = peek.into_struct().ok()?;
+            let mut entries = Vec::with_capacity(sd.fields.len());
+            for (index, field) in sd.fields.iter().enumerate() {
+                let value = default_json(ps.field(index).ok()?)?;
+                entries.push((avro_field_name(field.name).into_owned(), value));
+            }
+            JsonValue::Object(entries)
+        }
+        AvroType::Array(_) => {
+            let list = peek.into_list_like().ok()?;
+            JsonValue::Array(list.iter().map(default_json).collect::<Option<_>>()?)
+        }
+        AvroType::Map(..) => {
+            let map = peek.into_map().ok()?;
+            let mut entries = Vec::with_capacity(map.len());
+            for (key, value) in map.iter() {
+                let key = match key.as_str() {
+                    Some(s) => s.to_owned(),
+                    None => format!("{key}"),
+                };
+                entries.push((key, default_json(value)?));
+            }
+            JsonValue::Object(entries)
+        }
+    })
+}
+
+fn json_float(value: f64) -> Option<JsonValue> {
+    value
+        .is_finite()
+        .then(|| JsonValue::Number(format!("{value:?}")))
+}
+
+fn invalid(reason: impl Into<String>) -> AvroSchemaError {
+    AvroSchemaError::InvalidSchema(reason.into())
+}
+
+fn primitive(name: &str) -> Option<Schema> {
+    Some(match name {
+        "null" => Schema::Null,
+        "boolean" => Schema::Boolean,
+        "int" => Schema::Int,
+        "long" => Schema::Long,
+        "float" => Schema::Float,
+        "double" => Schema::Double,
+        "bytes" => Schema::Bytes,
+        "string" => Schema::String,
+        _ => return None,
+    })
+}
+
+/// Converts parsed JSON to a schema. `namespace` is the enclosing namespace, which
+/// named types without one of their own inherit.
+fn from_json(json: &JsonValue, namespace: Option<&str>) -> Result<Schema, AvroSchemaError> {
+    match json {
+        JsonValue::String(name) => {
+            Ok(primitive(name).unwrap_or_else(|| Schema::Named(name.clone())))
+        }
+        JsonValue::Array(branches) => Ok(Schema::Union(
+            branches
+                .iter()
+                .map(|branch| from_json(branch, namespace))
+                .collect::<Result<_, _>>()?,
+        )),
+        JsonValue::Object(_) => {
+            let ty = json
+                .get("type")
+                .ok_or_else(|| invalid("missing \"type\""))?;
+            let kind = match ty {
+                JsonValue::String(kind) => kind.as_str(),
+                // `{"type": {"type": "array", ...}}` and friends
+                nested => return from_json(nested, namespace),
+            };
+
+            let full_name = || -> Result<(String, Option<String>), AvroSchemaError> {
+                let name = json
+                    .get("name")
+                    .and_then(JsonValue::as_str)
+                    .ok_or_else(|| invalid(format!("{kind} without a name")))?;
+                if let Some((ns, _)) = name.rsplit_once('.') {
+                    return Ok((name.to_owned(), Some(ns.to_owned())));
+                }
+                let ns = json
+                    .get("namespace")
+                    .and_then(JsonValue::as_str)
+                    .or(namespace)
+                    .filter(|ns| !ns.is_empty());
+                Ok(match ns {
+                    Some(ns) => (format!("{ns}.{name}"), Some(ns.to_owned())),
+                    None => (name.to_owned(), None),
+                })
+            };
+            let doc = || {
+                json.get("doc")
+                    .and_then(JsonValue::as_str)
+                    .map(str::to_owned)
+            };
+
+            match kind {
+                "record" | "error" => {
+                    let (name, ns) = full_name()?;
+                    let Some(JsonValue::Array(fields)) = json.get("fields") else {
+                        return Err(invalid(format!("record {name} without fields")));
+                    };
+                    let fields =
+                        fields
+                            .iter()
+                            .map(|field| {
+                                let field_name =
+                                    field.get("name").and_then(JsonValue::as_str).ok_or_else(
+                                        || invalid(format!("field of {name} without a name")),
+                                    )?;
+                                let ty = field.get("type").ok_or_else(|| {
+                                    invalid(format!("field {name}.{field_name} without a type"))
+                                })?;
+                                Ok(RecordField {
+                                    name: field_name.to_owned(),
+                                    doc: field
+                                        .get("doc")
+                                        .and_then(JsonValue::as_str)
+                                        .map(str::to_owned),
+                                    schema: from_json(ty, ns.as_deref())?,
+                                    default: field.get("default").cloned(),
+                                })
+                            })
+                            .collect::<Result<_, AvroSchemaError>>()?;
+                    Ok(Schema::Record(RecordSchema {
+                        name,
+                        doc: doc(),
+                        fields,
+                    }))
+                }
+                "enum" => {
+                    let (name, _) = full_name()?;
+                    let Some(JsonValue::Array(symbols)) = json.get("symbols") else {
+                        return Err(invalid(format!("enum {name} without symbols")));
+                    };
+                    let symbols = symbols
+                        .iter()
+                        .map(|s| s.as_str().map(str::to_owned))
+                        .collect::<Option<_>>()
+                        .ok_or_else(|| invalid(format!("enum {name} has a non-string symbol")))?;
+                    Ok(Schema::Enum(EnumSchema {
+                        name,
+                        doc: doc(),
+                        symbols,
+                    }))
+                }
+                "fixed" => {
+                    let (name, _) = full_name()?;
+                    let size = match json.get("size") {
+                        Some(JsonValue::Number(size)) => size.parse().ok(),
+                        _ => None,
+                    };
+                    let size =
+                        size.ok_or_else(|| invalid(format!("fixed {name} without a size")))?;
+                    Ok(Schema::Fixed(FixedSchema { name, size }))
+                }
+                "array" => {
+                    let items = json
+                        .get("items")
+                        .ok_or_else(|| invalid("array without items"))?;
+                    Ok(Schema::Array(Box::new(from_json(items, namespace)?)))
+                }
+                "map" => {
+                    let values = json
+                        .get("values")
+                        .ok_or_else(|| invalid("map without values"))?;
+                    Ok(Schema::Map(Box::new(from_json(values, namespace)?)))
+                }
+                // Primitives may carry attributes such as `logicalType`, which only
+                // change how the value is interpreted, not how it is encoded.
+                other => Ok(primitive(other).unwrap_or_else(|| Schema::Named(other.to_owned()))),
+            }
+        }
+        _ => Err(invalid(format!("unexpected {json}"))),
+    }
+}
@@ -0,0 +1,134 @@
+//! Encode Rust values as Avro binary data.
+
+use alloc::{format, string::ToString, vec::Vec};
+use facet_core::{Facet, Shape};
+use facet_reflect::{Peek, ScalarType};
+
+use crate::{
+    AvroSerError,
+    types::AvroType,
+    wire::{write_bytes, write_long},
+};
+
+/// Encodes a value as Avro binary data, using the schema from
+/// [`to_avro_schema`](crate::to_avro_schema).
+///
+/// The output is a bare datum: no container file header and no schema registry prefix.
+pub fn to_vec<'facet, T: Facet<'facet>>(value: &T) -> Result<Vec<u8>, AvroSerError> {
+    let mut out = Vec::new();
+    write_value(Peek::new(value), &mut out)?;
+    Ok(out)
+}
+
+fn unsupported(shape: &Shape<'_>) -> AvroSerError {
+    AvroSerError::UnsupportedType(shape.to_string())
+}
+
+fn write_value(peek: Peek<'_, '_, '_>, out: &mut Vec<u8>) -> Result<(), AvroSerError> {
+    let shape = peek.shape();
+    let err = |_| unsupported(shape);
+
+    match AvroType::of(shape).ok_or_else(|| unsupported(shape))? {
+        AvroType::Null => {}
+        AvroType::Boolean => out.push(*peek.get::<bool>().map_err(err)? as u8),
+        AvroType::Int | AvroType::Long => write_long(out, integer(peek)?),
+        AvroType::Float => out.extend_from_slice(&peek.get::<f32>().map_err(err)?.to_le_bytes()),
+        AvroType::Double => out.extend_from_slice(&peek.get::<f64>().map_err(err)?.to_le_bytes()),
+        AvroType::Bytes => write_bytes(out, peek.get::<Vec<u8>>().map_err(err)?),
+        AvroType::String | AvroType::Text => match peek.as_str() {
+            Some(s) => write_bytes(out, s.as_bytes()),
+            None => write_bytes(out, format!("{peek}").as_bytes()),
+        },
+        AvroType::Transparent(_) => write_value(peek.innermost_peek(), out)?,
+        AvroType::Optional(_) => match peek.into_option().map_err(err)?.value() {
+            None => write_long(out, 0),
+            Some(inner) => {
+                write_long(out, 1);
+                write_value(inner, out)?;
+            }
+        },
+        AvroType::Array(_) => {
+            let list = peek.into_list_like().map_err(err)?;
+            if !list.is_empty() {
+                write_long(out, list.len() as i64);
+                for item in list.iter() {
+                    write_value(item, out)?;
+                }
+            }
+            write_long(out, 0);
+        }
+        // Avro map keys are always strings
+        AvroType::Map(key, _)
+            if !matches!(AvroType::of(key), Some(AvroType::String | AvroType::Text)) =>
+        {
+            return Err(unsupported(key));
+        }
+        AvroType::Map(..) => {
+            let map = peek.into_map().map_err(err)?;
+            if !map.is_empty() {
+                write_long(out, map.len() as i64);
+                for (key, value) in map.iter() {
+                    match key.as_str() {
+                        Some(s) => write_bytes(out, s.as_bytes()),
+                        None => write_bytes(out, format!("{key}").as_bytes()),
+                    }
+                    write_value(value, out)?;
+                }
+            }
+            write_long(out, 0);
+        }
+        AvroType::Record(sd) => {
+            let ps = peek.into_struct().map_err(|_| unsupported(shape))?;
+            for index in 0..sd.fields.len() {
+                write_value(ps.field(index).map_err(|_| unsupported(shape))?, out)?;
+            }
+        }
+        AvroType::Enum(_) => {
+            let pe = peek.into_enum().map_err(|_| unsupported(shape))?;
+            write_long(
+                out,
+                pe.variant_index().map_err(|_| unsupported(shape))? as i64,
+            );
+        }
+        AvroType::Union(_) => {
+            let pe = peek.into_enum().map_err(|_| unsupported(shape))?;
+            let variant = pe.active_variant().map_err(|_| unsupported(shape))?;
+            write_long(
+                out,
+                pe.variant_index().map_err(|_| unsupported(shape))? as i64,
+            );
+            for index in 0..variant.data.fields.len() {
+                let field = pe
+                    .field(index)
+                    .map_err(|_| unsupported(shape))?
+                    .ok_or_else(|| unsupported(shape))?;
+                write_value(field, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn integer(peek: Peek<'_, '_, '_>) -> Result<i64, AvroSerError> {
+    let shape = peek.shape();
+    let err = |_| unsupported(shape);
+    let out_of_range = |_| AvroSerError::NumberOutOfRange(format!("{peek}"));
+
+    Ok(match peek.scalar_type() {
+        Some(ScalarType::U8) => *peek.get::<u8>().map_err(err)? as i64,
+        Some(ScalarType::U16) => *peek.get::<u16>().map_err(err)? as i64,
+        Some(ScalarType::U32) => *peek.get::<u32>().map_err(err)? as i64,
+        Some(ScalarType::U64) => {
+            i64::try_from(*peek.get::<u64>().map_err(err)?).map_err(out_of_range)?
+        }
+        Some(ScalarType::USize) => {
+            i64::try_from(*peek.get::<usize>().map_err(err)?).map_err(out_of_range)?
+        }
+        Some(ScalarType::I8) => *peek.get::<i8>().map_err(err)? as i64,
+        Some(ScalarType::I16) => *peek.get::<i16>().map_err(err)? as i64,
+        Some(ScalarType::I32) => *peek.get::<i32>().map_err(err)? as i64,
+        Some(ScalarType::I64) => *peek.get::<i64>().map_err(err)?,
+        Some(ScalarType::ISize) => *peek.get::<isize>().map_err(err)? as i64,
+        _ => return Err(unsupported(shape)),
+    })
+}
@@ -0,0 +1,94 @@
+//! Mapping from Facet shapes to Avro types.
+
+use facet_core::{Def, EnumType, Shape, StructKind, StructType, Type, UserType};
+use facet_reflect::ScalarType;
+
+/// How a shape is represented in Avro.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum AvroType<'shape> {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    String,
+    Bytes,
+    /// Any other scalar, written as a string through `Display` and read back with `FromStr`.
+    Text,
+    /// An enum whose variants are all units.
+    Enum(EnumType<'shape>),
+    /// An enum with data, written as a union of one record per variant.
+    Union(EnumType<'shape>),
+    Record(StructType<'shape>),
+    /// `Option<T>`, written as the union `["null", T]`.
+    Optional(&'shape Shape<'shape>),
+    Array(&'shape Shape<'shape>),
+    /// A map; Avro map keys are always strings.
+    Map(&'shape Shape<'shape>, &'shape Shape<'shape>),
+    /// A transparent wrapper around another shape.
+    Transparent(&'shape Shape<'shape>),
+}
+
+impl AvroType<'_> {
+    pub(crate) fn of<'shape>(shape: &'shape Shape<'shape>) -> Option<AvroType<'shape>> {
+        if shape.is_type::<alloc::vec::Vec<u8>>() {
+            return Some(AvroType::Bytes);
+        }
+
+        match shape.def {
+            Def::Option(od) => return Some(AvroType::Optional(od.t())),
+            Def::List(ld) => return Some(AvroType::Array(ld.t())),
+            Def::Map(md) => return Some(AvroType::Map(md.k(), md.v())),
+            _ => {}
+        }
+
+        if let Some(scalar) = ScalarType::try_from_shape(shape) {
+            let ty = match scalar {
+                ScalarType::Unit => AvroType::Null,
+                ScalarType::Bool => AvroType::Boolean,
+                ScalarType::U8
+                | ScalarType::U16
+                | ScalarType::I8
+                | ScalarType::I16
+                | ScalarType::I32 => AvroType::Int,
+                ScalarType::U32
+                | ScalarType::U64
+                | ScalarType::USize
+                | ScalarType::I64
+                | ScalarType::ISize => AvroType::Long,
+                ScalarType::F32 => AvroType::Float,
+                ScalarType::F64 => AvroType::Double,
+                ScalarType::String | ScalarType::CowStr => AvroType::String,
+                _ if shape.is_display() && shape.is_from_str() => AvroType::Text,
+                _ => return None,
+            };
+            return Some(ty);
+        }
+
+        if let Some(inner) = shape.inner {
+            return Some(AvroType::Transparent(inner()));
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(sd)) => Some(AvroType::Record(sd)),
+            Type::User(UserType::Enum(ed))
+                if ed.variants.iter().all(|v| v.data.kind == StructKind::Unit) =>
+            {
+                Some(AvroType::Enum(ed))
+            }
+            Type::User(UserType::Enum(ed)) => Some(AvroType::Union(ed)),
+            _ if shape.is_display() && shape.is_from_str() => Some(AvroType::Text),
+            _ => None,
+        }
+    }
+}
+
+/// Avro names can't start with a digit, so tuple fields `0`, `1`... become `_0`, `_1`...
+pub(crate) fn avro_field_name(name: &str) -> alloc::borrow::Cow<'_, str> {
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        alloc::format!("_{name}").into()
+    } else {
+        name.into()
+    }
+}
@@ -0,0 +1,209 @@
+//! Low-level Avro binary encoding, and decoding driven by the writer schema.
+
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
+use crate::{AvroDeError, schema::Schema};
+
+pub(crate) fn write_long(out: &mut Vec<u8>, value: i64) {
+    let mut n = ((value << 1) ^ (value >> 63)) as u64;
+    while n >= 0x80 {
+        out.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+pub(crate) fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_long(out, bytes.len() as i64);
+    out.extend_from_slice(bytes);
+}
+
+/// A value decoded with the writer schema, before it's resolved against the Rust type.
+#[derive(Debug)]
+pub(crate) enum Value {
+    Null,
+    Boolean(bool),
+    /// Both `int` and `long`
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    /// Both `bytes` and `fixed`
+    Bytes(Vec<u8>),
+    String(String),
+    /// A record, with its full name and its fields by name
+    Record(String, Vec<(String, Value)>),
+    /// An enum symbol
+    Enum(String),
+    Array(Vec<Value>),
+    Map(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Boolean(_) => "boolean",
+            Value::Long(_) => "long",
+            Value::Float(_) => "float",
+            Value::Double(_) => "double",
+            Value::Bytes(_) => "bytes",
+            Value::String(_) => "string",
+            Value::Record(..) => "record",
+            Value::Enum(_) => "enum",
+            Value::Array(_) => "array",
+            Value::Map(_) => "map",
+        }
+    }
+}
+
+/// Reads Avro binary data described by a writer schema.
+pub(crate) struct Reader<'input, 'schema> {
+    input: &'input [u8],
+    pos: usize,
+    names: Vec<(&'schema str, &'schema Schema)>,
+}
+
+impl<'input, 'schema> Reader<'input, 'schema> {
+    pub(crate) fn new(input: &'input [u8], schema: &'schema Schema) -> Self {
+        Self {
+            input,
+            pos: 0,
+            names: schema.named_types(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    fn take<'shape>(&mut self, len: usize) -> Result<&'input [u8], AvroDeError<'shape>> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .ok_or(AvroDeError::UnexpectedEof)?;
+        let bytes = self
+            .input
+            .get(self.pos..end)
+            .ok_or(AvroDeError::UnexpectedEof)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn read_long<'shape>(&mut self) -> Result<i64, AvroDeError<'shape>> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            n |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(((n >> 1) as i64) ^ -((n & 1) as i64));
+            }
+        }
+        Err(AvroDeError::VarintOverflow)
+    }
+
+    fn read_bytes<'shape>(&mut self) -> Result<&'input [u8], AvroDeError<'shape>> {
+        let len = self.read_long()?;
+        let len = usize::try_from(len).map_err(|_| AvroDeError::UnexpectedEof)?;
+        self.take(len)
+    }
+
+    fn read_string<'shape>(&mut self) -> Result<String, AvroDeError<'shape>> {
+        let s = core::str::from_utf8(self.read_bytes()?).map_err(|_| AvroDeError::InvalidUtf8)?;
+        Ok(s.to_owned())
+    }
+
+    /// Reads the item count of the next block of an array or map; 0 ends the value.
+    fn read_block_len<'shape>(&mut self) -> Result<usize, AvroDeError<'shape>> {
+        let count = self.read_long()?;
+        if count < 0 {
+            // A negative count is followed by the block size in bytes, which lets
+            // readers skip blocks; we don't need it.
+            self.read_long()?;
+        }
+        usize::try_from(count.unsigned_abs()).map_err(|_| AvroDeError::UnexpectedEof)
+    }
+
+    fn resolve<'shape>(&self, name: &str) -> Result<&'schema Schema, AvroDeError<'shape>> {
+        self.names
+            .iter()
+            .find(|(full, _)| *full == name)
+            .or_else(|| {
+                // References may use the short name within the same namespace
+                self.names
+                    .iter()
+                    .find(|(full, _)| full.rsplit('.').next() == name.rsplit('.').next())
+            })
+            .map(|(_, schema)| *schema)
+            .ok_or_else(|| AvroDeError::UnknownName(name.to_owned()))
+    }
+
+    pub(crate) fn read<'shape>(
+        &mut self,
+        schema: &'schema Schema,
+    ) -> Result<Value, AvroDeError<'shape>> {
+        Ok(match schema {
+            Schema::Null => Value::Null,
+            Schema::Boolean => Value::Boolean(self.take(1)?[0] != 0),
+            Schema::Int | Schema::Long => Value::Long(self.read_long()?),
+            Schema::Float => Value::Float(f32::from_le_bytes(self.take(4)?.try_into().unwrap())),
+            Schema::Double => Value::Double(f64::from_le_bytes(self.take(8)?.try_into().unwrap())),
+            Schema::Bytes => Value::Bytes(self.read_bytes()?.to_vec()),
+            Schema::Fixed(fixed) => Value::Bytes(self.take(fixed.size)?.to_vec()),
+            Schema::String => Value::String(self.read_string()?),
+            Schema::Record(record) => {
+                let mut fields = Vec::with_capacity(record.fields.len());
+                for field in &record.fields {
+                    fields.push((field.name.clone(), self.read(&field.schema)?));
+                }
+                Value::Record(record.name.clone(), fields)
+            }
+            Schema::Enum(en) => {
+                let index = self.read_long()?;
+                let symbol = usize::try_from(index)
+                    .ok()
+                    .and_then(|i| en.symbols.get(i))
+                    .ok_or(AvroDeError::InvalidEnumIndex(index))?;
+                Value::Enum(symbol.clone())
+            }
+            Schema::Array(items) => {
+                let mut out = Vec::new();
+                loop {
+                    let len = self.read_block_len()?;
+                    if len == 0 {
+                        break;
+                    }
+                    for _ in 0..len {
+                        out.push(self.read(items)?);
+                    }
+                }
+                Value::Array(out)
+            }
+            Schema::Map(values) => {
+                let mut out = Vec::new();
+                loop {
+                    let len = self.read_block_len()?;
+                    if len == 0 {
+                        break;
+                    }
+                    for _ in 0..len {
+                        let key = self.read_string()?;
+                        out.push((key, self.read(values)?));
+                    }
+                }
+                Value::Map(out)
+            }
+            Schema::Union(branches) => {
+                let index = self.read_long()?;
+                let branch = usize::try_from(index)
+                    .ok()
+                    .and_then(|i| branches.get(i))
+                    .ok_or(AvroDeError::InvalidUnionIndex(index))?;
+                self.read(branch)?
+            }
+            Schema::Named(name) => {
+                let schema = self.resolve(name)?;
+                self.read(schema)?
+            }
+        })
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_avro::{
+    AvroDeError, AvroSerError, Schema, from_slice, from_slice_with_schema, to_avro_schema, to_vec,
+};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet, PartialEq)]
+struct User {
+    id: i64,
+    name: String,
+    email: Option<String>,
+}
+
+#[test]
+fn test_encoding_matches_spec() {
+    let user = User {
+        id: -2,
+        name: "Ada".to_string(),
+        email: Some("a@b".to_string()),
+    };
+    // id: zigzag(-2) = 3, name: len 3 then bytes, email: union branch 1 then the string
+    let expected = [0x03, 0x06, b'A', b'd', b'a', 0x02, 0x06, b'a', b'@', b'b'];
+    assert_eq!(to_vec(&user)?, expected);
+    assert_eq!(from_slice::<User>(&expected)?, user);
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+enum Status {
+    Active,
+    Banned,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Event {
+    Login { at: u64 },
+    Rename { from: String, to: String },
+    Logout,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Account {
+    user: User,
+    status: Status,
+    tags: Vec<String>,
+    limits: HashMap<String, f64>,
+    history: Vec<Event>,
+    avatar: Vec<u8>,
+    ratio: f32,
+}
+
+fn account() -> Account {
+    Account {
+        user: User {
+            id: 7,
+            name: "Ada".to_string(),
+            email: None,
+        },
+        status: Status::Banned,
+        tags: vec!["admin".to_string(), "early".to_string()],
+        limits: HashMap::from([("upload".to_string(), 1.5)]),
+        history: vec![
+            Event::Login { at: 1_700_000_000 },
+            Event::Rename {
+                from: "ada".to_string(),
+                to: "Ada".to_string(),
+            },
+            Event::Logout,
+        ],
+        avatar: vec![0, 1, 255],
+        ratio: 0.25,
+    }
+}
+
+#[test]
+fn test_roundtrip_nested() {
+    let bytes = to_vec(&account())?;
+    assert_eq!(from_slice::<Account>(&bytes)?, account());
+
+    let active = Account {
+        status: Status::Active,
+        ..account()
+    };
+    let bytes = to_vec(&active)?;
+    assert_eq!(from_slice::<Account>(&bytes)?, active);
+}
+
+#[test]
+fn test_schema() {
+    assert_eq!(
+        to_avro_schema::<User>()?,
+        r#"{"type":"record","name":"User","fields":[{"name":"id","type":"long"},{"name":"name","type":"string"},{"name":"email","type":["null","string"],"default":null}]}"#
+    );
+
+    let schema = to_avro_schema::<Account>()?;
+    assert!(schema.contains(r#"{"type":"enum","name":"Status","symbols":["Active","Banned"]}"#));
+    assert!(schema.contains(
+        r#"{"type":"record","name":"Event.Login","fields":[{"name":"at","type":"long"}]}"#
+    ));
+    assert!(schema.contains(r#"{"type":"map","values":"double"}"#));
+}
+
+#[test]
+fn test_schema_parse_roundtrip() {
+    let json = to_avro_schema::<Account>()?;
+    let schema = Schema::parse(&json)?;
+    assert_eq!(schema, Schema::for_shape(Account::SHAPE)?);
+    assert_eq!(schema.to_string(), json);
+}
+
+#[test]
+fn test_recursive_types_are_referenced_by_name() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Node {
+        value: i32,
+        children: Vec<Node>,
+    }
+
+    assert_eq!(
+        to_avro_schema::<Node>()?,
+        r#"{"type":"record","name":"Node","fields":[{"name":"value","type":"int"},{"name":"children","type":{"type":"array","items":"Node"}}]}"#
+    );
+
+    let tree = Node {
+        value: 1,
+        children: vec![Node {
+            value: 2,
+            children: vec![],
+        }],
+    };
+    assert_eq!(from_slice::<Node>(&to_vec(&tree)?)?, tree);
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct UserV1 {
+    name: String,
+    id: i32,
+    legacy: bool,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct UserV2 {
+    id: i64,
+    name: String,
+    email: Option<String>,
+    #[facet(default = 3)]
+    logins: u32,
+}
+
+#[test]
+fn test_schema_resolution() {
+    let writer = Schema::for_shape(UserV1::SHAPE)?;
+    let bytes = to_vec(&UserV1 {
+        name: "Ada".to_string(),
+        id: 7,
+        legacy: true,
+    })?;
+
+    // Fields are reordered, `int` is promoted to `long`, `legacy` is skipped, and the
+    // new fields take their defaults.
+    let user: UserV2 = from_slice_with_schema(&bytes, &writer)?;
+    assert_eq!(
+        user,
+        UserV2 {
+            id: 7,
+            name: "Ada".to_string(),
+            email: None,
+            logins: 3,
+        }
+    );
+
+    let schema = to_avro_schema::<UserV2>()?;
+    assert!(schema.contains(r#"{"name":"logins","type":"long","default":3}"#));
+}
+
+#[test]
+fn test_resolution_with_parsed_writer_schema() {
+    let writer = Schema::parse(
+        r#"{
+            "type": "record",
+            "name": "User",
+            "namespace": "com.example",
+            "fields": [
+                {"name": "name", "type": "bytes"},
+                {"name": "id", "type": "long"},
+                {"name": "extra", "type": {"type": "array", "items": "long"}}
+            ]
+        }"#,
+    )?;
+    // name: bytes "q", id: 5, extra: one block of two longs
+    let bytes = [0x02, b'q', 0x0a, 0x04, 0x02, 0x04, 0x00];
+    let user: User = from_slice_with_schema(&bytes, &writer)?;
+    assert_eq!(
+        user,
+        User {
+            id: 5,
+            name: "q".to_string(),
+            email: None,
+        }
+    );
+}
+
+#[test]
+fn test_missing_field_without_default() {
+    #[derive(Debug, Facet)]
+    struct Strict {
+        id: i64,
+        required: String,
+    }
+
+    #[derive(Debug, Facet)]
+    struct Old {
+        id: i64,
+    }
+
+    let writer = Schema::for_shape(Old::SHAPE)?;
+    let err = from_slice_with_schema::<Strict>(&to_vec(&Old { id: 1 })?, &writer).unwrap_err();
+    assert!(matches!(err, AvroDeError::MissingField("required")));
+}
+
+#[test]
+fn test_errors() {
+    let err = from_slice::<User>(&[0x03, 0x06, b'A']).unwrap_err();
+    assert!(matches!(err, AvroDeError::UnexpectedEof));
+
+    let err = from_slice::<User>(&[0x03, 0x00, 0x04]).unwrap_err();
+    assert!(matches!(err, AvroDeError::InvalidUnionIndex(2)));
+
+    let err = from_slice::<User>(&[0x03, 0x00, 0x00, 0x00]).unwrap_err();
+    assert!(matches!(err, AvroDeError::TrailingData));
+
+    let err = to_vec(&u64::MAX).unwrap_err();
+    assert!(matches!(err, AvroSerError::NumberOutOfRange(_)));
+}
@@ -0,0 +1,57 @@
+//! The `bigapi` catalog: a large, deeply nested API type with UUIDs, dates,
+//! options and many small vectors, generated by facet-fake.
+
+use bigapi_types_facet::{Catalog, generate_mock_catalog};
+use divan::counter::BytesCount;
+use divan::{AllocProfiler, Bencher, black_box};
+
+#[global_allocator]
+static ALLOC: AllocProfiler = AllocProfiler::system();
+
+#[divan::bench(name = "Serialize - Catalog - facet_json")]
+fn bench_catalog_facet_json_serialize(bencher: Bencher) {
+    let catalog = generate_mock_catalog();
+    let len = facet_json::to_string(&catalog).len();
+
+    bencher
+        .counter(BytesCount::new(len))
+        .bench(|| black_box(facet_json::to_string(black_box(&catalog))));
+}
+
+#[divan::bench(name = "Serialize - Catalog - facet_json (reused buffer)")]
+fn bench_catalog_facet_json_serialize_reused(bencher: Bencher) {
+    let catalog = generate_mock_catalog();
+    let mut buffer = Vec::new();
+    facet_json::to_writer(&catalog, &mut buffer).unwrap();
+    let len = buffer.len();
+
+    bencher.counter(BytesCount::new(len)).bench_local(|| {
+        buffer.clear();
+        facet_json::to_writer(black_box(&catalog), &mut buffer).unwrap();
+        black_box(buffer.len())
+    });
+}
+
+#[divan::bench(name = "Deserialize - Catalog - facet_json")]
+fn bench_catalog_facet_json_deserialize(bencher: Bencher) {
+    let json_string = facet_json::to_string(&generate_mock_catalog());
+
+    bencher.counter(BytesCount::of_str(&json_string)).bench(|| {
+        let res: Catalog = black_box(facet_json::from_str(black_box(&json_string))).unwrap();
+        black_box(res)
+    });
+}
+
+#[divan::bench(name = "Serialize - Catalog - facet_msgpack")]
+fn bench_catalog_facet_msgpack_serialize(bencher: Bencher) {
+    let catalog = generate_mock_catalog();
+    let len = facet_msgpack::to_vec(&catalog).len();
+
+    bencher
+        .counter(BytesCount::new(len))
+        .bench(|| black_box(facet_msgpack::to_vec(black_box(&catalog))));
+}
+
+fn main() {
+    divan::main();
+}
@@ -0,0 +1,63 @@
+use divan::{Bencher, black_box};
+
+fn create_floats(len: usize) -> Vec<f64> {
+    (0..len).map(|i| i as f64 * 0.25 - 1000.0).collect()
+}
+
+fn create_ints(len: usize) -> Vec<u64> {
+    (0..len as u64).map(|i| i * 7919).collect()
+}
+
+#[divan::bench(name = "Deserialize - Vec<f64> (100k) - facet_json")]
+fn bench_floats_facet_json_deserialize(bencher: Bencher) {
+    let json_string = serde_json::to_string(&create_floats(100_000)).unwrap();
+
+    bencher.bench(|| {
+        let res: Vec<f64> = black_box(facet_json::from_str(black_box(&json_string))).unwrap();
+        black_box(res)
+    });
+}
+
+#[divan::bench(name = "Deserialize - Vec<f64> (100k) - serde")]
+fn bench_floats_serde_deserialize(bencher: Bencher) {
+    let json_string = serde_json::to_string(&create_floats(100_000)).unwrap();
+
+    bencher.bench(|| {
+        let res: Vec<f64> = black_box(serde_json::from_str(black_box(&json_string))).unwrap();
+        black_box(res)
+    });
+}
+
+#[divan::bench(name = "Deserialize - Vec<u64> (100k) - facet_json")]
+fn bench_ints_facet_json_deserialize(bencher: Bencher) {
+    let json_string = serde_json::to_string(&create_ints(100_000)).unwrap();
+
+    bencher.bench(|| {
+        let res: Vec<u64> = black_box(facet_json::from_str(black_box(&json_string))).unwrap();
+        black_box(res)
+    });
+}
+
+#[divan::bench(name = "Deserialize - Vec<u64> (100k) - facet_msgpack")]
+fn bench_ints_facet_msgpack_deserialize(bencher: Bencher) {
+    let bytes = rmp_serde::to_vec(&create_ints(100_000)).unwrap();
+
+    bencher.bench(|| {
+        let res: Vec<u64> = black_box(facet_msgpack::from_slice(black_box(&bytes))).unwrap();
+        black_box(res)
+    });
+}
+
+#[divan::bench(name = "Deserialize - Vec<u64> (100k) - rmp_serde")]
+fn bench_ints_rmp_serde_deserialize(bencher: Bencher) {
+    let bytes = rmp_serde::to_vec(&create_ints(100_000)).unwrap();
+
+    bencher.bench(|| {
+        let res: Vec<u64> = black_box(rmp_serde::from_slice(black_box(&bytes))).unwrap();
+        black_box(res)
+    });
+}
+
+fn main() {
+    divan::main();
+}
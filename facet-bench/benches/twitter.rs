@@ -0,0 +1,291 @@
+//! A flat, twitter-like timeline: many small records with short strings, some
+//! of them non-ASCII, which is what most API payloads look like.
+
+use divan::counter::BytesCount;
+use divan::{AllocProfiler, Bencher, black_box};
+use facet::Facet;
+use facet_reflect::Peek;
+use facet_serialize::{Serializer, serialize_iterative};
+use serde::{Deserialize, Serialize};
+
+#[global_allocator]
+static ALLOC: AllocProfiler = AllocProfiler::system();
+
+#[derive(Debug, PartialEq, Clone, Facet, Serialize, Deserialize)]
+struct Timeline {
+    statuses: Vec<Status>,
+}
+
+#[derive(Debug, PartialEq, Clone, Facet, Serialize, Deserialize)]
+struct Status {
+    id: u64,
+    id_str: String,
+    created_at: String,
+    text: String,
+    truncated: bool,
+    user: TwitterUser,
+    in_reply_to_status_id: Option<u64>,
+    retweet_count: u32,
+    favorite_count: u32,
+    favorited: bool,
+    retweeted: bool,
+    lang: String,
+    hashtags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Facet, Serialize, Deserialize)]
+struct TwitterUser {
+    id: u64,
+    screen_name: String,
+    name: String,
+    description: String,
+    location: Option<String>,
+    followers_count: u32,
+    friends_count: u32,
+    verified: bool,
+}
+
+const TEXTS: &[&str] = &[
+    "Shipping a new release today, changelog in the thread",
+    "Un café et c'est reparti ☕",
+    "Quoting someone: \"reflection is just data\"\nNew line, too",
+    "今日はいい天気ですね 🌸",
+    "Paths like C:\\Users\\bench need escaping",
+];
+
+fn create_timeline() -> Timeline {
+    let statuses = (0..1000u64)
+        .map(|i| Status {
+            id: 1_000_000_000_000 + i,
+            id_str: (1_000_000_000_000 + i).to_string(),
+            created_at: format!("Mon Jun 0{} 12:{:02}:00 +0000 2025", i % 7 + 1, i % 60),
+            text: TEXTS[i as usize % TEXTS.len()].to_string(),
+            truncated: i % 11 == 0,
+            user: TwitterUser {
+                id: 40_000 + i % 50,
+                screen_name: format!("user_{}", i % 50),
+                name: format!("User Number {}", i % 50),
+                description: TEXTS[(i as usize + 2) % TEXTS.len()].to_string(),
+                location: (i % 3 == 0).then(|| "Lyon, France".to_string()),
+                followers_count: (i * 37 % 10_000) as u32,
+                friends_count: (i * 13 % 1_000) as u32,
+                verified: i % 17 == 0,
+            },
+            in_reply_to_status_id: (i % 4 == 0).then(|| 1_000_000_000_000 + i / 2),
+            retweet_count: (i * 7 % 500) as u32,
+            favorite_count: (i * 3 % 2_000) as u32,
+            favorited: false,
+            retweeted: i % 5 == 0,
+            lang: if i % 2 == 0 { "en" } else { "fr" }.to_string(),
+            hashtags: (0..i % 4).map(|n| format!("tag{n}")).collect(),
+        })
+        .collect();
+    Timeline { statuses }
+}
+
+// JSON
+
+#[divan::bench(name = "Serialize - Twitter - facet_json")]
+fn bench_twitter_facet_json_serialize(bencher: Bencher) {
+    let data = create_timeline();
+    let len = facet_json::to_string(&data).len();
+
+    bencher
+        .counter(BytesCount::new(len))
+        .bench(|| black_box(facet_json::to_string(black_box(&data))));
+}
+
+#[divan::bench(name = "Serialize - Twitter - facet_json (reused buffer)")]
+fn bench_twitter_facet_json_serialize_reused(bencher: Bencher) {
+    let data = create_timeline();
+    let mut buffer = Vec::new();
+    facet_json::to_writer(&data, &mut buffer).unwrap();
+    let len = buffer.len();
+
+    bencher.counter(BytesCount::new(len)).bench_local(|| {
+        buffer.clear();
+        facet_json::to_writer(black_box(&data), &mut buffer).unwrap();
+        black_box(buffer.len())
+    });
+}
+
+#[divan::bench(name = "Serialize - Twitter - serde")]
+fn bench_twitter_serde_serialize(bencher: Bencher) {
+    let data = create_timeline();
+    let len = serde_json::to_string(&data).unwrap().len();
+
+    bencher
+        .counter(BytesCount::new(len))
+        .bench(|| black_box(serde_json::to_string(black_box(&data))));
+}
+
+#[divan::bench(name = "Deserialize - Twitter - facet_json")]
+fn bench_twitter_facet_json_deserialize(bencher: Bencher) {
+    let json_string = serde_json::to_string(&create_timeline()).unwrap();
+
+    bencher.counter(BytesCount::of_str(&json_string)).bench(|| {
+        let res: Timeline = black_box(facet_json::from_str(black_box(&json_string))).unwrap();
+        black_box(res)
+    });
+}
+
+#[divan::bench(name = "Deserialize - Twitter - facet_json (reused parser)")]
+fn bench_twitter_facet_json_deserialize_reused(bencher: Bencher) {
+    let json_string = serde_json::to_string(&create_timeline()).unwrap();
+    let mut parser = facet_json::JsonParser::new();
+
+    bencher
+        .counter(BytesCount::of_str(&json_string))
+        .bench_local(|| {
+            let res: Timeline = black_box(parser.parse(black_box(&json_string))).unwrap();
+            black_box(res)
+        });
+}
+
+#[divan::bench(name = "Deserialize - Twitter - serde")]
+fn bench_twitter_serde_deserialize(bencher: Bencher) {
+    let json_string = serde_json::to_string(&create_timeline()).unwrap();
+
+    bencher.counter(BytesCount::of_str(&json_string)).bench(|| {
+        let res: Timeline = black_box(serde_json::from_str(black_box(&json_string))).unwrap();
+        black_box(res)
+    });
+}
+
+// MessagePack
+
+#[divan::bench(name = "Serialize - Twitter - facet_msgpack")]
+fn bench_twitter_facet_msgpack_serialize(bencher: Bencher) {
+    let data = create_timeline();
+    let len = facet_msgpack::to_vec(&data).len();
+
+    bencher
+        .counter(BytesCount::new(len))
+        .bench(|| black_box(facet_msgpack::to_vec(black_box(&data))));
+}
+
+#[divan::bench(name = "Serialize - Twitter - rmp_serde")]
+fn bench_twitter_rmp_serde_serialize(bencher: Bencher) {
+    let data = create_timeline();
+    let len = rmp_serde::to_vec_named(&data).unwrap().len();
+
+    bencher
+        .counter(BytesCount::new(len))
+        .bench(|| black_box(rmp_serde::to_vec_named(black_box(&data))));
+}
+
+#[divan::bench(name = "Deserialize - Twitter - facet_msgpack")]
+fn bench_twitter_facet_msgpack_deserialize(bencher: Bencher) {
+    let bytes = rmp_serde::to_vec_named(&create_timeline()).unwrap();
+
+    bencher.counter(BytesCount::new(bytes.len())).bench(|| {
+        let res: Timeline = black_box(facet_msgpack::from_slice(black_box(&bytes))).unwrap();
+        black_box(res)
+    });
+}
+
+#[divan::bench(name = "Deserialize - Twitter - rmp_serde")]
+fn bench_twitter_rmp_serde_deserialize(bencher: Bencher) {
+    let bytes = rmp_serde::to_vec_named(&create_timeline()).unwrap();
+
+    bencher.counter(BytesCount::new(bytes.len())).bench(|| {
+        let res: Timeline = black_box(rmp_serde::from_slice(black_box(&bytes))).unwrap();
+        black_box(res)
+    });
+}
+
+// Reflection alone, without any format: regressions in `Peek` show up here
+// first, since the format benchmarks above also measure the writers.
+
+/// A serializer that only counts what it's given.
+struct CountingSerializer(usize);
+
+impl<'shape> Serializer<'shape> for CountingSerializer {
+    type Error = core::convert::Infallible;
+
+    fn serialize_u64(&mut self, _value: u64) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+    fn serialize_u128(&mut self, _value: u128) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+    fn serialize_i64(&mut self, _value: i64) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+    fn serialize_i128(&mut self, _value: i128) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+    fn serialize_f64(&mut self, _value: f64) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+    fn serialize_bool(&mut self, _value: bool) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+    fn serialize_char(&mut self, _value: char) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+    fn serialize_str(&mut self, _value: &str) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+    fn serialize_bytes(&mut self, _value: &[u8]) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+    fn serialize_none(&mut self) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+    fn serialize_unit(&mut self) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+    fn serialize_unit_variant(
+        &mut self,
+        _variant_index: usize,
+        _variant_name: &'shape str,
+    ) -> Result<(), Self::Error> {
+        self.0 += 1;
+        Ok(())
+    }
+    fn serialize_pointer_cycle(
+        &mut self,
+        _shape: &'shape facet::Shape<'shape>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_field_name(&mut self, _name: &'shape str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn start_array(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn start_map(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[divan::bench(name = "Peek - Twitter - walk every value")]
+fn bench_twitter_peek_walk(bencher: Bencher) {
+    let data = create_timeline();
+
+    bencher.bench(|| {
+        let mut counter = CountingSerializer(0);
+        serialize_iterative(Peek::new(black_box(&data)), &mut counter).unwrap();
+        black_box(counter.0)
+    });
+}
+
+fn main() {
+    divan::main();
+}
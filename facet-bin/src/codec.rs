@@ -0,0 +1,711 @@
+//! The bytes of a document: a header with the schema, then the value.
+//!
+//! Integers wider than a byte are LEB128 varints, zigzag-encoded when signed;
+//! floats are little-endian; strings, bytes, lists and maps start with their
+//! length; options start with `0` or `1`; enums start with the variant's index;
+//! structs, tuples and arrays are their items one after the other.
+
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
+
+use crate::{BinSerError, DecodeError, FieldDef, Schema, TypeDef, Value, VariantDef};
+
+/// What every document starts with.
+const MAGIC: &[u8; 4] = b"FBIN";
+
+/// The version of the format written after [`MAGIC`].
+const VERSION: u8 = 1;
+
+/// How deeply values and type definitions can nest when reading or writing, so
+/// that malformed input or values can't overflow the stack.
+const MAX_DEPTH: usize = 512;
+
+/// A value together with the schema that describes it: everything a document
+/// holds.
+///
+/// Reading a `Document` needs no Rust type, so any document can be inspected:
+///
+/// ```
+/// use facet::Facet;
+/// use facet_bin::{Document, Value};
+///
+/// #[derive(Facet)]
+/// struct Reading {
+///     sensor: String,
+///     celsius: f32,
+/// }
+///
+/// let bytes = facet_bin::to_vec(&Reading {
+///     sensor: "attic".to_string(),
+///     celsius: 21.5,
+/// })
+/// .unwrap();
+///
+/// let document = Document::from_slice(&bytes).unwrap();
+/// assert_eq!(
+///     document.value.to_string(),
+///     r#"Reading { sensor: "attic", celsius: 21.5 }"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Document {
+    /// The types of the value.
+    pub schema: Schema,
+    /// The value.
+    pub value: Value,
+}
+
+impl Document {
+    /// Reads a document.
+    pub fn from_slice(input: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = Reader {
+            input,
+            pos: 0,
+            depth: 0,
+            empty: Vec::new(),
+        };
+        let schema = reader.header()?;
+        reader.empty = empty_types(&schema);
+        let value = reader.value(&schema, schema.root)?;
+        if reader.pos != input.len() {
+            return Err(reader.invalid("trailing bytes after the value"));
+        }
+        Ok(Document { schema, value })
+    }
+
+    /// Writes the document, checking that the value matches the schema.
+    pub fn to_vec(&self) -> Result<Vec<u8>, BinSerError> {
+        self.write(false)
+    }
+
+    /// Writes the document in canonical form, which is the same bytes for equal values, so
+    /// that signatures over it can be checked by anyone who writes the value again:
+    ///
+    /// - the entries of every map are sorted by the bytes of their keys, and no two keys are
+    ///   the same
+    /// - every NaN is written the same way
+    ///
+    /// The rest of the format already has a single encoding for each value: integers are
+    /// varints of the fewest bytes, and floats are as wide as their type in the schema.
+    pub fn to_vec_canonical(&self) -> Result<Vec<u8>, BinSerError> {
+        self.write(true)
+    }
+
+    fn write(&self, canonical: bool) -> Result<Vec<u8>, BinSerError> {
+        let mut writer = Writer {
+            out: Vec::new(),
+            canonical,
+            depth: 0,
+        };
+        writer.header(&self.schema);
+        writer.value(&self.schema, self.schema.root, &self.value)?;
+        Ok(writer.out)
+    }
+}
+
+struct Writer {
+    out: Vec<u8>,
+    /// Whether the value is written in canonical form, see [`Document::to_vec_canonical`]
+    canonical: bool,
+    depth: usize,
+}
+
+impl Writer {
+    fn varint(&mut self, mut n: u128) {
+        loop {
+            let byte = (n & 0x7f) as u8;
+            n >>= 7;
+            if n == 0 {
+                self.out.push(byte);
+                return;
+            }
+            self.out.push(byte | 0x80);
+        }
+    }
+
+    fn len(&mut self, len: usize) {
+        self.varint(len as u128);
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.len(bytes.len());
+        self.out.extend_from_slice(bytes);
+    }
+
+    fn header(&mut self, schema: &Schema) {
+        self.out.extend_from_slice(MAGIC);
+        self.out.push(VERSION);
+        self.len(schema.types.len());
+        for def in &schema.types {
+            self.type_def(def);
+        }
+        self.len(schema.root);
+    }
+
+    fn type_def(&mut self, def: &TypeDef) {
+        match def {
+            TypeDef::Unit => self.out.push(0),
+            TypeDef::Bool => self.out.push(1),
+            TypeDef::Unsigned(size) => self.out.extend_from_slice(&[2, *size]),
+            TypeDef::Signed(size) => self.out.extend_from_slice(&[3, *size]),
+            TypeDef::Float(size) => self.out.extend_from_slice(&[4, *size]),
+            TypeDef::Char => self.out.push(5),
+            TypeDef::String => self.out.push(6),
+            TypeDef::Bytes => self.out.push(7),
+            TypeDef::Option(t) => {
+                self.out.push(8);
+                self.len(*t);
+            }
+            TypeDef::List(t) => {
+                self.out.push(9);
+                self.len(*t);
+            }
+            TypeDef::Map { key, value } => {
+                self.out.push(10);
+                self.len(*key);
+                self.len(*value);
+            }
+            TypeDef::Array { len, item } => {
+                self.out.push(11);
+                self.len(*len);
+                self.len(*item);
+            }
+            TypeDef::Struct { name, fields } => {
+                self.out.push(12);
+                self.bytes(name.as_bytes());
+                self.field_defs(fields);
+            }
+            TypeDef::Enum { name, variants } => {
+                self.out.push(13);
+                self.bytes(name.as_bytes());
+                self.len(variants.len());
+                for variant in variants {
+                    self.bytes(variant.name.as_bytes());
+                    self.field_defs(&variant.fields);
+                }
+            }
+        }
+    }
+
+    fn field_defs(&mut self, fields: &[FieldDef]) {
+        self.len(fields.len());
+        for field in fields {
+            self.bytes(field.name.as_bytes());
+            self.len(field.ty);
+        }
+    }
+
+    fn value(&mut self, schema: &Schema, ty: usize, value: &Value) -> Result<(), BinSerError> {
+        if self.depth == MAX_DEPTH {
+            return Err(BinSerError::ValueMismatch("value nested too deeply".into()));
+        }
+        self.depth += 1;
+        let result = self.value_inner(schema, ty, value);
+        self.depth -= 1;
+        result
+    }
+
+    fn value_inner(
+        &mut self,
+        schema: &Schema,
+        ty: usize,
+        value: &Value,
+    ) -> Result<(), BinSerError> {
+        let Some(def) = schema.types.get(ty) else {
+            return Err(BinSerError::ValueMismatch(format!(
+                "type {ty} is not defined"
+            )));
+        };
+        let mismatch = || {
+            BinSerError::ValueMismatch(format!("expected {}, found {}", def.kind(), value.kind()))
+        };
+        match (def, value) {
+            (TypeDef::Unit, Value::Unit) => {}
+            (TypeDef::Bool, Value::Bool(b)) => self.out.push(*b as u8),
+            (&TypeDef::Unsigned(size), &Value::Unsigned(n)) => {
+                if size < 16 && n >> (size as u32 * 8) != 0 {
+                    return Err(BinSerError::ValueMismatch(format!(
+                        "{n} doesn't fit in {size} bytes"
+                    )));
+                }
+                match size {
+                    1 => self.out.push(n as u8),
+                    _ => self.varint(n),
+                }
+            }
+            (&TypeDef::Signed(size), &Value::Signed(n)) => {
+                let bits = size as u32 * 8;
+                if size < 16 && (n < -(1 << (bits - 1)) || n >= 1 << (bits - 1)) {
+                    return Err(BinSerError::ValueMismatch(format!(
+                        "{n} doesn't fit in {size} bytes"
+                    )));
+                }
+                match size {
+                    1 => self.out.push(n as i8 as u8),
+                    _ => self.varint(((n << 1) ^ (n >> 127)) as u128),
+                }
+            }
+            (&TypeDef::Float(size), &Value::Float(n)) => {
+                let n = if self.canonical && n.is_nan() {
+                    f64::NAN
+                } else {
+                    n
+                };
+                match size {
+                    4 => self.out.extend_from_slice(&(n as f32).to_le_bytes()),
+                    _ => self.out.extend_from_slice(&n.to_le_bytes()),
+                }
+            }
+            (TypeDef::Char, &Value::Char(c)) => self.varint(c as u128),
+            (TypeDef::String, Value::String(s)) => self.bytes(s.as_bytes()),
+            (TypeDef::Bytes, Value::Bytes(bytes)) => self.bytes(bytes),
+            (TypeDef::Option(_), Value::Option(None)) => self.out.push(0),
+            (&TypeDef::Option(t), Value::Option(Some(inner))) => {
+                self.out.push(1);
+                self.value(schema, t, inner)?;
+            }
+            (&TypeDef::List(t), Value::List(items)) => {
+                self.len(items.len());
+                for item in items {
+                    self.value(schema, t, item)?;
+                }
+            }
+            (&TypeDef::Array { len, item }, Value::List(items)) => {
+                if items.len() != len {
+                    return Err(BinSerError::ValueMismatch(format!(
+                        "expected {len} items, found {}",
+                        items.len()
+                    )));
+                }
+                for value in items {
+                    self.value(schema, item, value)?;
+                }
+            }
+            (&TypeDef::Map { key, value: v }, Value::Map(entries)) if self.canonical => {
+                self.canonical_map(schema, key, v, entries)?;
+            }
+            (&TypeDef::Map { key, value: v }, Value::Map(entries)) => {
+                self.len(entries.len());
+                for (k, value) in entries {
+                    self.value(schema, key, k)?;
+                    self.value(schema, v, value)?;
+                }
+            }
+            (TypeDef::Struct { fields: defs, .. }, Value::Struct { fields, .. }) => {
+                self.fields(schema, defs, fields)?;
+            }
+            (
+                TypeDef::Enum { name, variants },
+                Value::Enum {
+                    variant, fields, ..
+                },
+            ) => {
+                let Some(index) = variants.iter().position(|v| v.name == *variant) else {
+                    return Err(BinSerError::ValueMismatch(format!(
+                        "{variant} is not a variant of {name}"
+                    )));
+                };
+                self.len(index);
+                self.fields(schema, &variants[index].fields, fields)?;
+            }
+            _ => return Err(mismatch()),
+        }
+        Ok(())
+    }
+
+    /// Writes a map with its entries sorted by the bytes of their keys, in its own
+    /// function to keep the buffers out of the stack frame of every nested value
+    fn canonical_map(
+        &mut self,
+        schema: &Schema,
+        key: usize,
+        v: usize,
+        entries: &[(Value, Value)],
+    ) -> Result<(), BinSerError> {
+        // Each entry is written apart first, to be sorted by the bytes of its key
+        let mut written = Vec::with_capacity(entries.len());
+        for (k, value) in entries {
+            let mut key_writer = Writer {
+                out: Vec::new(),
+                canonical: true,
+                depth: self.depth,
+            };
+            key_writer.value(schema, key, k)?;
+            let mut value_writer = Writer {
+                out: Vec::new(),
+                canonical: true,
+                depth: self.depth,
+            };
+            value_writer.value(schema, v, value)?;
+            written.push((key_writer.out, value_writer.out));
+        }
+        written.sort_by(|(a, _), (b, _)| a.cmp(b));
+        if written.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+            return Err(BinSerError::ValueMismatch(
+                "a map has the same key twice".into(),
+            ));
+        }
+        self.len(written.len());
+        for (k, value) in written {
+            self.out.extend_from_slice(&k);
+            self.out.extend_from_slice(&value);
+        }
+        Ok(())
+    }
+
+    fn fields(
+        &mut self,
+        schema: &Schema,
+        defs: &[FieldDef],
+        fields: &[(String, Value)],
+    ) -> Result<(), BinSerError> {
+        let names_match = defs.len() == fields.len()
+            && defs
+                .iter()
+                .zip(fields)
+                .all(|(def, (name, _))| def.name == *name);
+        if !names_match {
+            let expected: Vec<&str> = defs.iter().map(|def| def.name.as_str()).collect();
+            let found: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+            return Err(BinSerError::ValueMismatch(format!(
+                "expected fields {expected:?}, found {found:?}"
+            )));
+        }
+        for (def, (_, value)) in defs.iter().zip(fields) {
+            self.value(schema, def.ty, value)?;
+        }
+        Ok(())
+    }
+}
+
+struct Reader<'input> {
+    input: &'input [u8],
+    pos: usize,
+    depth: usize,
+    /// Which types of the schema take no bytes, by index.
+    empty: Vec<bool>,
+}
+
+impl<'input> Reader<'input> {
+    fn invalid(&self, message: &'static str) -> DecodeError {
+        DecodeError::Invalid {
+            message,
+            offset: self.pos,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'input [u8], DecodeError> {
+        if self.input.len() - self.pos < len {
+            return Err(DecodeError::UnexpectedEof);
+        }
+        let bytes = &self.input[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u128, DecodeError> {
+        let start = self.pos;
+        let mut n = 0u128;
+        for shift in (0..128).step_by(7) {
+            let byte = self.byte()?;
+            let bits = (byte & 0x7f) as u128;
+            if shift == 126 && bits > 0b11 {
+                break;
+            }
+            n |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        self.pos = start;
+        Err(self.invalid("varint too long"))
+    }
+
+    fn len(&mut self) -> Result<usize, DecodeError> {
+        let len = self.varint()?;
+        usize::try_from(len).map_err(|_| self.invalid("length too large"))
+    }
+
+    /// Checks the length of a list, map or array, which can't have more items
+    /// than there are bytes left unless its items take no space.
+    fn check_count(&self, len: usize, items: &[usize]) -> Result<(), DecodeError> {
+        let sized = items.iter().any(|&ty| !self.empty[ty]);
+        if sized && len > self.input.len() - self.pos {
+            return Err(DecodeError::UnexpectedEof);
+        }
+        if !sized && len > u16::MAX as usize {
+            return Err(self.invalid("too many empty items"));
+        }
+        Ok(())
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let len = self.len()?;
+        let start = self.pos;
+        let bytes = self.take(len)?;
+        core::str::from_utf8(bytes)
+            .map(String::from)
+            .map_err(|_| DecodeError::Invalid {
+                message: "invalid UTF-8",
+                offset: start,
+            })
+    }
+
+    fn header(&mut self) -> Result<Schema, DecodeError> {
+        if self.input.get(..MAGIC.len()) != Some(MAGIC) {
+            return Err(DecodeError::NotFacetBin);
+        }
+        self.pos = MAGIC.len();
+        let version = self.byte()?;
+        if version != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+
+        let count = self.len()?;
+        if count > self.input.len() - self.pos {
+            return Err(DecodeError::UnexpectedEof);
+        }
+        let mut types = Vec::with_capacity(count);
+        for _ in 0..count {
+            types.push(self.type_def(count)?);
+        }
+        let root = self.type_ref(count)?;
+        Ok(Schema { types, root })
+    }
+
+    fn type_ref(&mut self, count: usize) -> Result<usize, DecodeError> {
+        let ty = self.len()?;
+        if ty >= count {
+            return Err(self.invalid("reference to an undefined type"));
+        }
+        Ok(ty)
+    }
+
+    fn type_def(&mut self, count: usize) -> Result<TypeDef, DecodeError> {
+        let def = match self.byte()? {
+            0 => TypeDef::Unit,
+            1 => TypeDef::Bool,
+            tag @ 2..=4 => {
+                let size = self.byte()?;
+                match (tag, size) {
+                    (2, 1 | 2 | 4 | 8 | 16) => TypeDef::Unsigned(size),
+                    (3, 1 | 2 | 4 | 8 | 16) => TypeDef::Signed(size),
+                    (4, 4 | 8) => TypeDef::Float(size),
+                    _ => return Err(self.invalid("invalid number size")),
+                }
+            }
+            5 => TypeDef::Char,
+            6 => TypeDef::String,
+            7 => TypeDef::Bytes,
+            8 => TypeDef::Option(self.type_ref(count)?),
+            9 => TypeDef::List(self.type_ref(count)?),
+            10 => TypeDef::Map {
+                key: self.type_ref(count)?,
+                value: self.type_ref(count)?,
+            },
+            11 => TypeDef::Array {
+                len: self.len()?,
+                item: self.type_ref(count)?,
+            },
+            12 => TypeDef::Struct {
+                name: self.string()?,
+                fields: self.field_defs(count)?,
+            },
+            13 => {
+                let name = self.string()?;
+                let len = self.len()?;
+                if len > self.input.len() - self.pos {
+                    return Err(DecodeError::UnexpectedEof);
+                }
+                let mut variants = Vec::with_capacity(len);
+                for _ in 0..len {
+                    variants.push(VariantDef {
+                        name: self.string()?,
+                        fields: self.field_defs(count)?,
+                    });
+                }
+                TypeDef::Enum { name, variants }
+            }
+            _ => {
+                self.pos -= 1;
+                return Err(self.invalid("unknown type"));
+            }
+        };
+        Ok(def)
+    }
+
+    fn field_defs(&mut self, count: usize) -> Result<Vec<FieldDef>, DecodeError> {
+        let len = self.len()?;
+        if len > self.input.len() - self.pos {
+            return Err(DecodeError::UnexpectedEof);
+        }
+        let mut fields = Vec::with_capacity(len);
+        for _ in 0..len {
+            fields.push(FieldDef {
+                name: self.string()?,
+                ty: self.type_ref(count)?,
+            });
+        }
+        Ok(fields)
+    }
+
+    fn value(&mut self, schema: &Schema, ty: usize) -> Result<Value, DecodeError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.invalid("value nested too deeply"));
+        }
+        self.depth += 1;
+        let value = self.value_inner(schema, ty);
+        self.depth -= 1;
+        value
+    }
+
+    fn value_inner(&mut self, schema: &Schema, ty: usize) -> Result<Value, DecodeError> {
+        let value = match &schema.types[ty] {
+            TypeDef::Unit => Value::Unit,
+            TypeDef::Bool => match self.byte()? {
+                0 => Value::Bool(false),
+                1 => Value::Bool(true),
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.invalid("invalid bool"));
+                }
+            },
+            TypeDef::Unsigned(1) => Value::Unsigned(self.byte()? as u128),
+            &TypeDef::Unsigned(size) => {
+                let start = self.pos;
+                let n = self.varint()?;
+                if size < 16 && n >> (size as u32 * 8) != 0 {
+                    self.pos = start;
+                    return Err(self.invalid("integer out of range"));
+                }
+                Value::Unsigned(n)
+            }
+            TypeDef::Signed(1) => Value::Signed(self.byte()? as i8 as i128),
+            &TypeDef::Signed(size) => {
+                let start = self.pos;
+                let zigzag = self.varint()?;
+                let n = (zigzag >> 1) as i128 ^ -((zigzag & 1) as i128);
+                let bits = size as u32 * 8;
+                if size < 16 && (n < -(1 << (bits - 1)) || n >= 1 << (bits - 1)) {
+                    self.pos = start;
+                    return Err(self.invalid("integer out of range"));
+                }
+                Value::Signed(n)
+            }
+            TypeDef::Float(4) => {
+                let bytes = self.take(4)?;
+                Value::Float(f32::from_le_bytes(bytes.try_into().unwrap()) as f64)
+            }
+            TypeDef::Float(_) => {
+                let bytes = self.take(8)?;
+                Value::Float(f64::from_le_bytes(bytes.try_into().unwrap()))
+            }
+            TypeDef::Char => {
+                let start = self.pos;
+                let c = u32::try_from(self.varint()?)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or(DecodeError::Invalid {
+                        message: "invalid char",
+                        offset: start,
+                    })?;
+                Value::Char(c)
+            }
+            TypeDef::String => Value::String(self.string()?),
+            TypeDef::Bytes => {
+                let len = self.len()?;
+                Value::Bytes(self.take(len)?.to_vec())
+            }
+            &TypeDef::Option(t) => match self.byte()? {
+                0 => Value::Option(None),
+                1 => Value::Option(Some(Box::new(self.value(schema, t)?))),
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.invalid("invalid option tag"));
+                }
+            },
+            &TypeDef::List(t) => {
+                let len = self.len()?;
+                self.check_count(len, &[t])?;
+                let mut items = Vec::with_capacity(len);
+                for _ in 0..len {
+                    items.push(self.value(schema, t)?);
+                }
+                Value::List(items)
+            }
+            &TypeDef::Array { len, item } => {
+                self.check_count(len, &[item])?;
+                let mut items = Vec::with_capacity(len);
+                for _ in 0..len {
+                    items.push(self.value(schema, item)?);
+                }
+                Value::List(items)
+            }
+            &TypeDef::Map { key, value } => {
+                let len = self.len()?;
+                self.check_count(len, &[key, value])?;
+                let mut entries = Vec::with_capacity(len);
+                for _ in 0..len {
+                    entries.push((self.value(schema, key)?, self.value(schema, value)?));
+                }
+                Value::Map(entries)
+            }
+            TypeDef::Struct { name, fields } => Value::Struct {
+                name: name.clone(),
+                fields: self.fields(schema, fields)?,
+            },
+            TypeDef::Enum { name, variants } => {
+                let start = self.pos;
+                let index = self.len()?;
+                let Some(variant) = variants.get(index) else {
+                    self.pos = start;
+                    return Err(self.invalid("invalid variant index"));
+                };
+                Value::Enum {
+                    name: name.clone(),
+                    variant: variant.name.clone(),
+                    fields: self.fields(schema, &variant.fields)?,
+                }
+            }
+        };
+        Ok(value)
+    }
+
+    fn fields(
+        &mut self,
+        schema: &Schema,
+        defs: &[FieldDef],
+    ) -> Result<Vec<(String, Value)>, DecodeError> {
+        defs.iter()
+            .map(|def| Ok((def.name.clone(), self.value(schema, def.ty)?)))
+            .collect()
+    }
+}
+
+/// Which types take no bytes at all, like `()` or `[(); 4]`.
+///
+/// Types start out as taking bytes, and are marked empty until nothing changes,
+/// so a struct that contains itself, which can't have any value, isn't empty.
+fn empty_types(schema: &Schema) -> Vec<bool> {
+    let mut empty = vec![false; schema.types.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (ty, def) in schema.types.iter().enumerate() {
+            let is_empty = match def {
+                TypeDef::Unit => true,
+                &TypeDef::Array { len, item } => len == 0 || empty[item],
+                TypeDef::Struct { fields, .. } => fields.iter().all(|field| empty[field.ty]),
+                _ => false,
+            };
+            if is_empty && !empty[ty] {
+                empty[ty] = true;
+                changed = true;
+            }
+        }
+    }
+    empty
+}
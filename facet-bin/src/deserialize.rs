@@ -0,0 +1,271 @@
+//! Deserialize documents into Rust values.
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use facet_core::{Facet, Field, FieldFlags, StructKind};
+use facet_reflect::{Partial, ScalarType};
+use log::trace;
+
+use crate::{BinDeError, Document, Value, repr::Repr};
+
+/// Deserializes a document into a value of type `T`.
+///
+/// The document's value is matched to `T` by field and variant names, so a
+/// document written by an older or newer version of `T` can still be read:
+/// fields that `T` doesn't have are skipped, and fields that the document
+/// doesn't have take their default value.
+pub fn from_slice<'input, 'facet: 'shape, 'shape, T: Facet<'facet>>(
+    bytes: &'input [u8],
+) -> Result<T, BinDeError<'shape>> {
+    trace!("Reading facet-bin document");
+    let document = Document::from_slice(bytes)?;
+    from_value(document.value)
+}
+
+/// Deserializes a [`Value`] into a value of type `T`.
+///
+/// ```
+/// use facet_bin::Value;
+///
+/// let value = Value::List(vec![Value::Unsigned(1), Value::Unsigned(2)]);
+/// let numbers: Vec<u16> = facet_bin::from_value(value).unwrap();
+/// assert_eq!(numbers, [1, 2]);
+/// ```
+pub fn from_value<'facet: 'shape, 'shape, T: Facet<'facet>>(
+    value: Value,
+) -> Result<T, BinDeError<'shape>> {
+    let mut partial = Partial::alloc::<T>()?;
+    deserialize_value(partial.inner_mut(), value)?;
+    let value = partial.build()?;
+    Ok(*value)
+}
+
+fn mismatch<'shape>(value: &Value, wip: &Partial<'_, 'shape>) -> BinDeError<'shape> {
+    BinDeError::TypeMismatch {
+        found: value.kind(),
+        shape: wip.shape(),
+    }
+}
+
+fn deserialize_value<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: Value,
+) -> Result<(), BinDeError<'shape>> {
+    let shape = wip.shape();
+    trace!("Deserializing {} into {}", value.kind(), shape);
+    let repr = Repr::of(shape).ok_or(BinDeError::UnsupportedShape(shape))?;
+
+    match (repr, value) {
+        (Repr::Bytes, Value::Bytes(bytes)) => {
+            wip.set(bytes)?;
+        }
+        (Repr::Scalar(scalar), value) => deserialize_scalar(wip, scalar, value)?,
+        (Repr::Text, Value::String(s)) => {
+            wip.parse_from_str(&s)
+                .map_err(|_| BinDeError::InvalidValue(shape))?;
+        }
+        (Repr::Option(_), Value::Option(None)) => {
+            wip.set_default()?;
+        }
+        (Repr::Option(_), Value::Option(Some(inner))) => {
+            wip.begin_some()?;
+            deserialize_value(wip, *inner)?;
+            wip.end()?;
+        }
+        (Repr::List(_), Value::List(items)) => {
+            wip.begin_list()?;
+            for item in items {
+                wip.begin_list_item()?;
+                deserialize_value(wip, item)?;
+                wip.end()?;
+            }
+        }
+        (Repr::Array(len, _), Value::List(items)) => {
+            if items.len() != len {
+                return Err(mismatch(&Value::List(items), wip));
+            }
+            for (index, item) in items.into_iter().enumerate() {
+                wip.begin_nth_element(index)?;
+                deserialize_value(wip, item)?;
+                wip.end()?;
+            }
+        }
+        (Repr::Map(..), Value::Map(entries)) => {
+            wip.begin_map()?;
+            for (key, value) in entries {
+                wip.begin_key()?;
+                deserialize_value(wip, key)?;
+                wip.end()?;
+                wip.begin_value()?;
+                deserialize_value(wip, value)?;
+                wip.end()?;
+            }
+        }
+        (Repr::Pointer(_), value) => {
+            wip.begin_smart_ptr()?;
+            deserialize_value(wip, value)?;
+            wip.end()?;
+        }
+        (Repr::Inner(_), value) => {
+            wip.begin_inner()?;
+            deserialize_value(wip, value)?;
+            wip.end()?;
+        }
+        (Repr::Struct(sd), Value::Struct { fields, .. }) => {
+            deserialize_fields(wip, sd.fields, fields, true)?;
+        }
+        (
+            Repr::Enum(_),
+            Value::Enum {
+                variant, fields, ..
+            },
+        ) => deserialize_enum(wip, variant, fields)?,
+        (_, value) => return Err(mismatch(&value, wip)),
+    }
+    Ok(())
+}
+
+/// Fills the fields of a struct or of the selected enum variant, by name.
+fn deserialize_fields<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    values: Vec<(String, Value)>,
+    is_struct: bool,
+) -> Result<(), BinDeError<'shape>> {
+    let shape = wip.shape();
+    for (name, value) in values {
+        let Some(index) = fields.iter().position(|f| f.name == name) else {
+            if shape.has_deny_unknown_fields_attr() {
+                return Err(BinDeError::UnknownField { name, shape });
+            }
+            trace!("Skipping unknown field '{name}' of {shape}");
+            continue;
+        };
+        wip.begin_nth_field(index)?;
+        deserialize_value(wip, value)?;
+        wip.end()?;
+    }
+
+    if is_struct {
+        wip.fill_unset_fields_from_default()?;
+    }
+    for (index, field) in fields.iter().enumerate() {
+        if !wip.is_field_set(index)? {
+            default_field(wip, index, field)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fills a field that the document doesn't have: options become `None`, and
+/// anything else needs a `#[facet(default)]`.
+fn default_field<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    index: usize,
+    field: &Field<'shape>,
+) -> Result<(), BinDeError<'shape>> {
+    let has_default = field.flags.contains(FieldFlags::DEFAULT);
+    if !has_default && !matches!(Repr::of(field.shape()), Some(Repr::Option(_))) {
+        return Err(BinDeError::MissingField(field.name));
+    }
+
+    wip.begin_nth_field(index)?;
+    match field.vtable.default_fn {
+        Some(default_fn) if has_default => wip.set_field_default(default_fn)?,
+        _ => wip.set_default()?,
+    };
+    wip.end()?;
+    Ok(())
+}
+
+fn deserialize_enum<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    name: String,
+    values: Vec<(String, Value)>,
+) -> Result<(), BinDeError<'shape>> {
+    let shape = wip.shape();
+    let Some((_, variant)) = wip.find_variant(&name) else {
+        return Err(BinDeError::UnknownVariant { name, shape });
+    };
+    let fields = variant.data.fields;
+    let kind = variant.data.kind;
+    wip.select_variant_named(&name)?;
+
+    if kind == StructKind::Unit {
+        return Ok(());
+    }
+    deserialize_fields(wip, fields, values, false)
+}
+
+fn deserialize_scalar<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    scalar: ScalarType,
+    value: Value,
+) -> Result<(), BinDeError<'shape>> {
+    let shape = wip.shape();
+    let out_of_range = |_| BinDeError::NumberOutOfRange(shape);
+
+    match (scalar, value) {
+        (ScalarType::Unit, Value::Unit) => {
+            wip.set(())?;
+        }
+        (ScalarType::Bool, Value::Bool(b)) => {
+            wip.set(b)?;
+        }
+        (ScalarType::Char, Value::Char(c)) => {
+            wip.set(c)?;
+        }
+        (ScalarType::F32, Value::Float(f)) => {
+            wip.set(f as f32)?;
+        }
+        (ScalarType::F64, Value::Float(f)) => {
+            wip.set(f)?;
+        }
+        (ScalarType::U8, Value::Unsigned(n)) => {
+            wip.set(u8::try_from(n).map_err(out_of_range)?)?;
+        }
+        (ScalarType::U16, Value::Unsigned(n)) => {
+            wip.set(u16::try_from(n).map_err(out_of_range)?)?;
+        }
+        (ScalarType::U32, Value::Unsigned(n)) => {
+            wip.set(u32::try_from(n).map_err(out_of_range)?)?;
+        }
+        (ScalarType::U64, Value::Unsigned(n)) => {
+            wip.set(u64::try_from(n).map_err(out_of_range)?)?;
+        }
+        (ScalarType::U128, Value::Unsigned(n)) => {
+            wip.set(n)?;
+        }
+        (ScalarType::USize, Value::Unsigned(n)) => {
+            wip.set(usize::try_from(n).map_err(out_of_range)?)?;
+        }
+        (ScalarType::I8, Value::Signed(n)) => {
+            wip.set(i8::try_from(n).map_err(out_of_range)?)?;
+        }
+        (ScalarType::I16, Value::Signed(n)) => {
+            wip.set(i16::try_from(n).map_err(out_of_range)?)?;
+        }
+        (ScalarType::I32, Value::Signed(n)) => {
+            wip.set(i32::try_from(n).map_err(out_of_range)?)?;
+        }
+        (ScalarType::I64, Value::Signed(n)) => {
+            wip.set(i64::try_from(n).map_err(out_of_range)?)?;
+        }
+        (ScalarType::I128, Value::Signed(n)) => {
+            wip.set(n)?;
+        }
+        (ScalarType::ISize, Value::Signed(n)) => {
+            wip.set(isize::try_from(n).map_err(out_of_range)?)?;
+        }
+        (ScalarType::String, Value::String(s)) => {
+            wip.set(s)?;
+        }
+        (ScalarType::CowStr, Value::String(s)) => {
+            wip.set(Cow::<'static, str>::Owned(s))?;
+        }
+        (ScalarType::ArcStr | ScalarType::RcStr | ScalarType::BoxStr, Value::String(s)) => {
+            wip.parse_from_str(&s)?;
+        }
+        (_, value) => return Err(mismatch(&value, wip)),
+    }
+    Ok(())
+}
@@ -0,0 +1,150 @@
+//! Errors from reading and writing documents.
+
+use alloc::string::String;
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+
+/// Errors when reading the bytes of a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The input doesn't start with the facet-bin header.
+    NotFacetBin,
+    /// The input was written with another version of the format.
+    UnsupportedVersion(u8),
+    /// The input ends before the document does.
+    UnexpectedEof,
+    /// The input is malformed.
+    Invalid {
+        /// What went wrong.
+        message: &'static str,
+        /// The byte offset of the error.
+        offset: usize,
+    },
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodeError::NotFacetBin => write!(f, "Not a facet-bin document"),
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported facet-bin version {version}")
+            }
+            DecodeError::UnexpectedEof => write!(f, "Unexpected end of input"),
+            DecodeError::Invalid { message, offset } => {
+                write!(f, "Invalid document at byte {offset}: {message}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+/// Errors when deserializing a document into a Rust value.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BinDeError<'shape> {
+    /// The bytes are not a valid document.
+    Decode(DecodeError),
+    /// A value has a different type than the Rust type expects.
+    TypeMismatch {
+        /// The kind of value found, such as `"string"` or `"struct"`.
+        found: &'static str,
+        /// The Rust type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A field doesn't exist in the Rust type, and the struct denies unknown fields.
+    UnknownField {
+        /// The field's name.
+        name: String,
+        /// The struct type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A variant doesn't exist in the Rust enum.
+    UnknownVariant {
+        /// The variant's name.
+        name: String,
+        /// The enum type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A field is missing and its type has no default value.
+    MissingField(&'shape str),
+    /// A number doesn't fit the Rust type it's deserialized into.
+    NumberOutOfRange(&'shape Shape<'shape>),
+    /// A string couldn't be parsed into the Rust type.
+    InvalidValue(&'shape Shape<'shape>),
+    /// The Rust type can't be represented in the format.
+    UnsupportedShape(&'shape Shape<'shape>),
+    /// Any error from facet.
+    Reflect(ReflectError<'shape>),
+}
+
+impl From<DecodeError> for BinDeError<'_> {
+    fn from(err: DecodeError) -> Self {
+        BinDeError::Decode(err)
+    }
+}
+
+impl<'shape> From<ReflectError<'shape>> for BinDeError<'shape> {
+    fn from(err: ReflectError<'shape>) -> Self {
+        BinDeError::Reflect(err)
+    }
+}
+
+impl core::fmt::Display for BinDeError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BinDeError::Decode(err) => write!(f, "{err}"),
+            BinDeError::TypeMismatch { found, shape } => {
+                write!(f, "Expected {shape}, found a {found}")
+            }
+            BinDeError::UnknownField { name, shape } => {
+                write!(f, "Unknown field '{name}' for {shape}")
+            }
+            BinDeError::UnknownVariant { name, shape } => {
+                write!(f, "'{name}' is not a variant of {shape}")
+            }
+            BinDeError::MissingField(name) => write!(f, "Missing field '{name}'"),
+            BinDeError::NumberOutOfRange(shape) => write!(f, "Number out of range for {shape}"),
+            BinDeError::InvalidValue(shape) => write!(f, "Can't parse string as {shape}"),
+            BinDeError::UnsupportedShape(shape) => {
+                write!(f, "Type {shape} can't be represented in facet-bin")
+            }
+            BinDeError::Reflect(err) => write!(f, "Reflection error: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for BinDeError<'_> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            BinDeError::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Errors when serializing a document.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BinSerError {
+    /// A type can't be represented in the format.
+    UnsupportedType(String),
+    /// A [`Value`](crate::Value) doesn't match the schema it's written with.
+    ValueMismatch(String),
+}
+
+impl core::fmt::Display for BinSerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BinSerError::UnsupportedType(ty) => {
+                write!(f, "Type {ty} can't be represented in facet-bin")
+            }
+            BinSerError::ValueMismatch(message) => {
+                write!(f, "Value doesn't match the schema: {message}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for BinSerError {}
@@ -0,0 +1,96 @@
+//! How each shape is represented in the format.
+
+use alloc::string::{String, ToString};
+use facet_core::{Def, EnumType, Shape, StructKind, StructType, Type, UserType};
+use facet_reflect::ScalarType;
+
+/// What a shape is written as. Serializing, deserializing and building the
+/// schema all go through this, so that they agree.
+#[derive(Clone, Copy)]
+pub(crate) enum Repr<'shape> {
+    /// `Vec<u8>`, written as one run of bytes.
+    Bytes,
+    Scalar(ScalarType),
+    /// A type that is written with `Display` and read with `FromStr`.
+    Text,
+    Option(&'shape Shape<'shape>),
+    List(&'shape Shape<'shape>),
+    Map(&'shape Shape<'shape>, &'shape Shape<'shape>),
+    Array(usize, &'shape Shape<'shape>),
+    /// `Box`, `Arc` and `Rc`, written as what they point to.
+    Pointer(&'shape Shape<'shape>),
+    /// A transparent wrapper, written as the type it wraps.
+    Inner(&'shape Shape<'shape>),
+    Struct(StructType<'shape>),
+    Enum(EnumType<'shape>),
+}
+
+impl<'shape> Repr<'shape> {
+    pub fn of(shape: &'shape Shape<'shape>) -> Option<Self> {
+        if shape.is_type::<alloc::vec::Vec<u8>>() {
+            return Some(Repr::Bytes);
+        }
+        match shape.def {
+            Def::Option(od) => return Some(Repr::Option(od.t())),
+            Def::List(ld) => return Some(Repr::List(ld.t())),
+            Def::Map(md) => return Some(Repr::Map(md.k(), md.v())),
+            Def::Array(ad) => return Some(Repr::Array(ad.n, ad.t())),
+            Def::SmartPointer(spd) => return spd.pointee().map(Repr::Pointer),
+            _ => {}
+        }
+        match ScalarType::try_from_shape(shape) {
+            // Borrowed strings can't be read back
+            Some(ScalarType::Str) => return None,
+            Some(
+                scalar @ (ScalarType::Unit
+                | ScalarType::Bool
+                | ScalarType::Char
+                | ScalarType::String
+                | ScalarType::CowStr
+                | ScalarType::ArcStr
+                | ScalarType::RcStr
+                | ScalarType::BoxStr
+                | ScalarType::F32
+                | ScalarType::F64
+                | ScalarType::U8
+                | ScalarType::U16
+                | ScalarType::U32
+                | ScalarType::U64
+                | ScalarType::U128
+                | ScalarType::USize
+                | ScalarType::I8
+                | ScalarType::I16
+                | ScalarType::I32
+                | ScalarType::I64
+                | ScalarType::I128
+                | ScalarType::ISize),
+            ) => return Some(Repr::Scalar(scalar)),
+            _ => {}
+        }
+        // The same wrappers that `Peek::innermost_peek` unwraps
+        if let Some(inner) = shape.inner {
+            if shape
+                .vtable
+                .sized()
+                .is_some_and(|v| (v.try_borrow_inner)().is_some())
+            {
+                return Some(Repr::Inner(inner()));
+            }
+        }
+        match shape.ty {
+            Type::User(UserType::Struct(sd)) => Some(Repr::Struct(sd)),
+            Type::User(UserType::Enum(ed)) => Some(Repr::Enum(ed)),
+            _ if shape.is_display() && shape.is_from_str() => Some(Repr::Text),
+            _ => None,
+        }
+    }
+}
+
+/// The name a struct or enum is written with: its identifier, or nothing for
+/// tuples, whose identifier is a placeholder.
+pub(crate) fn type_name(shape: &Shape<'_>) -> String {
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) if sd.kind == StructKind::Tuple => String::new(),
+        _ => shape.type_identifier.to_string(),
+    }
+}
@@ -0,0 +1,214 @@
+//! The description of a document's types, written in its header.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use facet_core::{Shape, StructKind};
+use facet_reflect::ScalarType;
+
+use crate::repr::{Repr, type_name};
+
+/// The types in a document: a table of type definitions, and which one the
+/// document's value has.
+///
+/// Types refer to each other by their index in [`Schema::types`], which is how
+/// recursive types are described.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    /// Every type used by the document.
+    pub types: Vec<TypeDef>,
+    /// The index of the document value's type.
+    pub root: usize,
+}
+
+/// One type of a [`Schema`].
+///
+/// Fields that hold a `usize` are indexes in [`Schema::types`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum TypeDef {
+    /// `()`, which takes no space.
+    Unit,
+    /// A boolean.
+    Bool,
+    /// An unsigned integer of the given size in bytes.
+    Unsigned(u8),
+    /// A signed integer of the given size in bytes.
+    Signed(u8),
+    /// A floating point number of the given size in bytes.
+    Float(u8),
+    /// A `char`.
+    Char,
+    /// A UTF-8 string.
+    String,
+    /// A run of bytes.
+    Bytes,
+    /// An optional value.
+    Option(usize),
+    /// Any number of values of one type.
+    List(usize),
+    /// Any number of key-value pairs.
+    Map {
+        /// The type of keys.
+        key: usize,
+        /// The type of values.
+        value: usize,
+    },
+    /// A fixed number of values of one type.
+    Array {
+        /// The number of values.
+        len: usize,
+        /// Their type.
+        item: usize,
+    },
+    /// A struct or tuple.
+    Struct {
+        /// The type's name, without generic parameters; empty for tuples.
+        name: String,
+        /// Its fields, in order. Tuple fields are named by their index.
+        fields: Vec<FieldDef>,
+    },
+    /// An enum.
+    Enum {
+        /// The type's name, without generic parameters.
+        name: String,
+        /// Its variants, in order.
+        variants: Vec<VariantDef>,
+    },
+}
+
+/// A field of a struct or enum variant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDef {
+    /// The field's name, or its index for tuple fields.
+    pub name: String,
+    /// The field's type.
+    pub ty: usize,
+}
+
+/// A variant of an enum.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantDef {
+    /// The variant's name.
+    pub name: String,
+    /// The variant's fields, in order; none for unit variants.
+    pub fields: Vec<FieldDef>,
+}
+
+impl Schema {
+    /// Describes a shape, or returns the first shape it contains that the
+    /// format can't represent.
+    pub fn of<'shape>(shape: &'shape Shape<'shape>) -> Result<Self, &'shape Shape<'shape>> {
+        let mut builder = Builder::default();
+        let root = builder.add(shape)?;
+        Ok(Schema {
+            types: builder.types,
+            root,
+        })
+    }
+
+    /// The definition of the document value's type.
+    pub fn root(&self) -> &TypeDef {
+        &self.types[self.root]
+    }
+}
+
+#[derive(Default)]
+struct Builder<'shape> {
+    types: Vec<TypeDef>,
+    /// Shapes already described, with their index.
+    seen: Vec<(&'shape Shape<'shape>, usize)>,
+}
+
+impl<'shape> Builder<'shape> {
+    fn add(&mut self, shape: &'shape Shape<'shape>) -> Result<usize, &'shape Shape<'shape>> {
+        if let Some(&(_, index)) = self.seen.iter().find(|(seen, _)| seen.id == shape.id) {
+            return Ok(index);
+        }
+        let repr = Repr::of(shape).ok_or(shape)?;
+        if let Repr::Pointer(inner) | Repr::Inner(inner) = repr {
+            return self.add(inner);
+        }
+
+        // Reserve the index first, so that recursive types can refer to it
+        let index = self.types.len();
+        self.types.push(TypeDef::Unit);
+        self.seen.push((shape, index));
+
+        let def = match repr {
+            Repr::Bytes => TypeDef::Bytes,
+            Repr::Scalar(scalar) => scalar_def(scalar),
+            Repr::Text => TypeDef::String,
+            Repr::Option(t) => TypeDef::Option(self.add(t)?),
+            Repr::List(t) => TypeDef::List(self.add(t)?),
+            Repr::Map(k, v) => TypeDef::Map {
+                key: self.add(k)?,
+                value: self.add(v)?,
+            },
+            Repr::Array(len, t) => TypeDef::Array {
+                len,
+                item: self.add(t)?,
+            },
+            Repr::Struct(sd) => TypeDef::Struct {
+                name: type_name(shape),
+                fields: self.fields(sd.fields)?,
+            },
+            Repr::Enum(ed) => TypeDef::Enum {
+                name: type_name(shape),
+                variants: ed
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        Ok(VariantDef {
+                            name: variant.name.to_string(),
+                            fields: match variant.data.kind {
+                                StructKind::Unit => Vec::new(),
+                                _ => self.fields(variant.data.fields)?,
+                            },
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+            },
+            Repr::Pointer(_) | Repr::Inner(_) => unreachable!(),
+        };
+        self.types[index] = def;
+        Ok(index)
+    }
+
+    fn fields(
+        &mut self,
+        fields: &'shape [facet_core::Field<'shape>],
+    ) -> Result<Vec<FieldDef>, &'shape Shape<'shape>> {
+        fields
+            .iter()
+            .map(|field| {
+                Ok(FieldDef {
+                    name: field.name.to_string(),
+                    ty: self.add(field.shape())?,
+                })
+            })
+            .collect()
+    }
+}
+
+fn scalar_def(scalar: ScalarType) -> TypeDef {
+    match scalar {
+        ScalarType::Bool => TypeDef::Bool,
+        ScalarType::Char => TypeDef::Char,
+        ScalarType::U8 => TypeDef::Unsigned(1),
+        ScalarType::U16 => TypeDef::Unsigned(2),
+        ScalarType::U32 => TypeDef::Unsigned(4),
+        ScalarType::U64 | ScalarType::USize => TypeDef::Unsigned(8),
+        ScalarType::U128 => TypeDef::Unsigned(16),
+        ScalarType::I8 => TypeDef::Signed(1),
+        ScalarType::I16 => TypeDef::Signed(2),
+        ScalarType::I32 => TypeDef::Signed(4),
+        ScalarType::I64 | ScalarType::ISize => TypeDef::Signed(8),
+        ScalarType::I128 => TypeDef::Signed(16),
+        ScalarType::F32 => TypeDef::Float(4),
+        ScalarType::F64 => TypeDef::Float(8),
+        ScalarType::Unit => TypeDef::Unit,
+        _ => TypeDef::String,
+    }
+}
@@ -0,0 +1,163 @@
+//! Serialize Rust values as documents.
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use facet_core::{Facet, Field, StructKind};
+use facet_reflect::{Peek, ScalarType};
+
+use crate::{
+    BinSerError, Document, Schema, Value,
+    repr::{Repr, type_name},
+};
+
+/// Serializes a value as a document: the schema of `T`, then the value.
+///
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let bytes = facet_bin::to_vec(&Point { x: 1, y: -2 }).unwrap();
+/// assert!(bytes.starts_with(b"FBIN"));
+/// assert_eq!(facet_bin::from_slice::<Point>(&bytes).unwrap(), Point { x: 1, y: -2 });
+/// ```
+pub fn to_vec<'facet, T: Facet<'facet>>(value: &T) -> Result<Vec<u8>, BinSerError> {
+    to_document(value)?.to_vec()
+}
+
+/// Serializes a value as a document in canonical form, which is the same bytes for equal
+/// values. See [`Document::to_vec_canonical`].
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// let a = HashMap::from([('x', 1), ('y', 2), ('z', 3)]);
+/// let b = HashMap::from([('z', 3), ('y', 2), ('x', 1)]);
+/// assert_eq!(
+///     facet_bin::to_vec_canonical(&a).unwrap(),
+///     facet_bin::to_vec_canonical(&b).unwrap()
+/// );
+/// ```
+pub fn to_vec_canonical<'facet, T: Facet<'facet>>(value: &T) -> Result<Vec<u8>, BinSerError> {
+    to_document(value)?.to_vec_canonical()
+}
+
+/// Converts a value to a [`Document`], without writing it.
+pub fn to_document<'facet, T: Facet<'facet>>(value: &T) -> Result<Document, BinSerError> {
+    let schema =
+        Schema::of(T::SHAPE).map_err(|shape| BinSerError::UnsupportedType(shape.to_string()))?;
+    let value = to_value(Peek::new(value))?;
+    Ok(Document { schema, value })
+}
+
+fn unsupported(peek: Peek<'_, '_, '_>) -> BinSerError {
+    BinSerError::UnsupportedType(peek.shape().to_string())
+}
+
+fn to_value(peek: Peek<'_, '_, '_>) -> Result<Value, BinSerError> {
+    let shape = peek.shape();
+    let err = |_| unsupported(peek);
+    let repr = Repr::of(shape).ok_or_else(|| unsupported(peek))?;
+
+    let value = match repr {
+        Repr::Bytes => Value::Bytes(peek.get::<Vec<u8>>().map_err(err)?.clone()),
+        Repr::Scalar(scalar) => scalar_value(peek, scalar)?,
+        Repr::Text => Value::String(format!("{peek}")),
+        Repr::Option(_) => match peek.into_option().map_err(err)?.value() {
+            Some(inner) => Value::Option(Some(Box::new(to_value(inner)?))),
+            None => Value::Option(None),
+        },
+        Repr::List(_) | Repr::Array(..) => {
+            let list = peek.into_list_like().map_err(err)?;
+            Value::List(list.iter().map(to_value).collect::<Result<_, _>>()?)
+        }
+        Repr::Map(..) => {
+            let map = peek.into_map().map_err(err)?;
+            let mut entries = Vec::with_capacity(map.len());
+            for (key, value) in map.iter() {
+                entries.push((to_value(key)?, to_value(value)?));
+            }
+            Value::Map(entries)
+        }
+        Repr::Pointer(_) => {
+            let pointer = peek.into_smart_pointer().map_err(err)?;
+            to_value(pointer.borrow_inner().ok_or_else(|| unsupported(peek))?)?
+        }
+        Repr::Inner(_) => to_value(peek.innermost_peek())?,
+        Repr::Struct(sd) => {
+            let ps = peek.into_struct().map_err(err)?;
+            let mut fields = Vec::with_capacity(sd.fields.len());
+            for (index, field) in sd.fields.iter().enumerate() {
+                fields.push(field_value(
+                    field,
+                    ps.field(index).map_err(|_| unsupported(peek))?,
+                )?);
+            }
+            Value::Struct {
+                name: type_name(shape),
+                fields,
+            }
+        }
+        Repr::Enum(_) => {
+            let pe = peek.into_enum().map_err(err)?;
+            let variant = pe.active_variant().map_err(|_| unsupported(peek))?;
+            let mut fields = Vec::new();
+            if variant.data.kind != StructKind::Unit {
+                for (index, field) in variant.data.fields.iter().enumerate() {
+                    let value = pe
+                        .field(index)
+                        .ok()
+                        .flatten()
+                        .ok_or_else(|| unsupported(peek))?;
+                    fields.push(field_value(field, value)?);
+                }
+            }
+            Value::Enum {
+                name: type_name(shape),
+                variant: variant.name.to_string(),
+                fields,
+            }
+        }
+    };
+    Ok(value)
+}
+
+fn field_value(field: &Field<'_>, value: Peek<'_, '_, '_>) -> Result<(String, Value), BinSerError> {
+    Ok((field.name.to_string(), to_value(value)?))
+}
+
+fn scalar_value(peek: Peek<'_, '_, '_>, scalar: ScalarType) -> Result<Value, BinSerError> {
+    let err = |_| unsupported(peek);
+    let value = match scalar {
+        ScalarType::Unit => Value::Unit,
+        ScalarType::Bool => Value::Bool(*peek.get::<bool>().map_err(err)?),
+        ScalarType::Char => Value::Char(*peek.get::<char>().map_err(err)?),
+        ScalarType::F32 => Value::Float(*peek.get::<f32>().map_err(err)? as f64),
+        ScalarType::F64 => Value::Float(*peek.get::<f64>().map_err(err)?),
+        ScalarType::U8 => Value::Unsigned(*peek.get::<u8>().map_err(err)? as u128),
+        ScalarType::U16 => Value::Unsigned(*peek.get::<u16>().map_err(err)? as u128),
+        ScalarType::U32 => Value::Unsigned(*peek.get::<u32>().map_err(err)? as u128),
+        ScalarType::U64 => Value::Unsigned(*peek.get::<u64>().map_err(err)? as u128),
+        ScalarType::U128 => Value::Unsigned(*peek.get::<u128>().map_err(err)?),
+        ScalarType::USize => Value::Unsigned(*peek.get::<usize>().map_err(err)? as u128),
+        ScalarType::I8 => Value::Signed(*peek.get::<i8>().map_err(err)? as i128),
+        ScalarType::I16 => Value::Signed(*peek.get::<i16>().map_err(err)? as i128),
+        ScalarType::I32 => Value::Signed(*peek.get::<i32>().map_err(err)? as i128),
+        ScalarType::I64 => Value::Signed(*peek.get::<i64>().map_err(err)? as i128),
+        ScalarType::I128 => Value::Signed(*peek.get::<i128>().map_err(err)?),
+        ScalarType::ISize => Value::Signed(*peek.get::<isize>().map_err(err)? as i128),
+        _ => match peek.as_str() {
+            Some(s) => Value::String(s.to_string()),
+            None => return Err(unsupported(peek)),
+        },
+    };
+    Ok(value)
+}
@@ -0,0 +1,174 @@
+//! Values read without knowing their Rust type.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt::{self, Display, Write};
+
+use crate::TypeDef;
+
+/// A value read from a document by following its schema, without the Rust
+/// type that wrote it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// `()`
+    Unit,
+    /// A boolean.
+    Bool(bool),
+    /// An unsigned integer of any size.
+    Unsigned(u128),
+    /// A signed integer of any size.
+    Signed(i128),
+    /// A floating point number of any size.
+    Float(f64),
+    /// A `char`.
+    Char(char),
+    /// A string.
+    String(String),
+    /// A run of bytes.
+    Bytes(Vec<u8>),
+    /// An optional value.
+    Option(Option<Box<Value>>),
+    /// A list or array.
+    List(Vec<Value>),
+    /// Key-value pairs, in the order they were written.
+    Map(Vec<(Value, Value)>),
+    /// A struct or tuple.
+    Struct {
+        /// The type's name; empty for tuples.
+        name: String,
+        /// The fields, in order. Tuple fields are named by their index.
+        fields: Vec<(String, Value)>,
+    },
+    /// An enum.
+    Enum {
+        /// The type's name.
+        name: String,
+        /// The variant's name.
+        variant: String,
+        /// The variant's fields, in order.
+        fields: Vec<(String, Value)>,
+    },
+}
+
+impl Value {
+    /// A short name for the kind of value, such as `"string"` or `"struct"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Value::Unit => "unit",
+            Value::Bool(_) => "bool",
+            Value::Unsigned(_) => "unsigned integer",
+            Value::Signed(_) => "signed integer",
+            Value::Float(_) => "float",
+            Value::Char(_) => "char",
+            Value::String(_) => "string",
+            Value::Bytes(_) => "bytes",
+            Value::Option(_) => "option",
+            Value::List(_) => "list",
+            Value::Map(_) => "map",
+            Value::Struct { .. } => "struct",
+            Value::Enum { .. } => "enum",
+        }
+    }
+}
+
+/// Writes values the way they'd be written in Rust, such as
+/// `Point { x: 1, y: 2 }` or `Shape::Circle(1.5)`.
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Unit => f.write_str("()"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Unsigned(n) => write!(f, "{n}"),
+            Value::Signed(n) => write!(f, "{n}"),
+            Value::Float(n) => write!(f, "{n:?}"),
+            Value::Char(c) => write!(f, "{c:?}"),
+            Value::String(s) => write!(f, "{s:?}"),
+            Value::Bytes(bytes) => {
+                f.write_str("b\"")?;
+                for &byte in bytes {
+                    for c in core::ascii::escape_default(byte) {
+                        f.write_char(c as char)?;
+                    }
+                }
+                f.write_char('"')
+            }
+            Value::Option(None) => f.write_str("None"),
+            Value::Option(Some(value)) => write!(f, "Some({value})"),
+            Value::List(items) => {
+                f.write_char('[')?;
+                write_separated(f, items, |f, item| write!(f, "{item}"))?;
+                f.write_char(']')
+            }
+            Value::Map(entries) => {
+                f.write_char('{')?;
+                write_separated(f, entries, |f, (k, v)| write!(f, "{k}: {v}"))?;
+                f.write_char('}')
+            }
+            Value::Struct { name, fields } => {
+                f.write_str(name)?;
+                write_fields(f, fields)
+            }
+            Value::Enum {
+                name,
+                variant,
+                fields,
+            } => {
+                write!(f, "{name}::{variant}")?;
+                write_fields(f, fields)
+            }
+        }
+    }
+}
+
+fn write_fields(f: &mut fmt::Formatter<'_>, fields: &[(String, Value)]) -> fmt::Result {
+    if fields.is_empty() {
+        return Ok(());
+    }
+    let is_tuple = fields
+        .iter()
+        .enumerate()
+        .all(|(i, (name, _))| name.parse() == Ok(i));
+    if is_tuple {
+        f.write_char('(')?;
+        write_separated(f, fields, |f, (_, value)| write!(f, "{value}"))?;
+        f.write_char(')')
+    } else {
+        f.write_str(" { ")?;
+        write_separated(f, fields, |f, (name, value)| write!(f, "{name}: {value}"))?;
+        f.write_str(" }")
+    }
+}
+
+fn write_separated<T>(
+    f: &mut fmt::Formatter<'_>,
+    items: &[T],
+    mut write: impl FnMut(&mut fmt::Formatter<'_>, &T) -> fmt::Result,
+) -> fmt::Result {
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        write(f, item)?;
+    }
+    Ok(())
+}
+
+impl TypeDef {
+    /// The kind of value this type holds, as returned by [`Value::kind`].
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            TypeDef::Unit => "unit",
+            TypeDef::Bool => "bool",
+            TypeDef::Unsigned(_) => "unsigned integer",
+            TypeDef::Signed(_) => "signed integer",
+            TypeDef::Float(_) => "float",
+            TypeDef::Char => "char",
+            TypeDef::String => "string",
+            TypeDef::Bytes => "bytes",
+            TypeDef::Option(_) => "option",
+            TypeDef::List(_) | TypeDef::Array { .. } => "list",
+            TypeDef::Map { .. } => "map",
+            TypeDef::Struct { .. } => "struct",
+            TypeDef::Enum { .. } => "enum",
+        }
+    }
+}
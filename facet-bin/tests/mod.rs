@@ -0,0 +1,354 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_bin::{
+    BinDeError, DecodeError, Document, FieldDef, Schema, TypeDef, Value, from_slice, from_value,
+    to_document, to_vec, to_vec_canonical,
+};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Config {
+    name: String,
+    port: u16,
+    offset: i64,
+    ratio: f64,
+    enabled: bool,
+    initial: char,
+    fallback: Option<String>,
+    checksum: Vec<u8>,
+    weights: [f32; 3],
+    limits: BTreeMap<String, u32>,
+    unit: (),
+}
+
+fn config() -> Config {
+    Config {
+        name: "edge".to_string(),
+        port: 8080,
+        offset: -42,
+        ratio: 0.25,
+        enabled: true,
+        initial: 'é',
+        fallback: None,
+        checksum: vec![0, 1, 254, 255],
+        weights: [1.0, 0.5, -2.0],
+        limits: BTreeMap::from([("cpu".to_string(), 4), ("memory".to_string(), 512)]),
+        unit: (),
+    }
+}
+
+#[test]
+fn round_trip_struct() {
+    let config = config();
+    let bytes = to_vec(&config)?;
+    assert!(bytes.starts_with(b"FBIN"));
+    assert_eq!(from_slice::<Config>(&bytes)?, config);
+
+    let config = Config {
+        fallback: Some("origin".to_string()),
+        ..config
+    };
+    assert_eq!(from_slice::<Config>(&to_vec(&config)?)?, config);
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect { width: u32, height: u32 },
+    Line(i32, i32),
+}
+
+#[test]
+fn round_trip_enums() {
+    let shapes = vec![
+        Shape::Empty,
+        Shape::Circle(1.5),
+        Shape::Rect {
+            width: 3,
+            height: 4,
+        },
+        Shape::Line(-1, 1),
+    ];
+    assert_eq!(from_slice::<Vec<Shape>>(&to_vec(&shapes)?)?, shapes);
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Point(i32, i32);
+
+#[test]
+fn round_trip_tuples() {
+    let value = (Point(1, -2), ("a".to_string(), 3u8));
+    assert_eq!(
+        from_slice::<(Point, (String, u8))>(&to_vec(&value)?)?,
+        value
+    );
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Tree {
+    label: String,
+    children: Vec<Tree>,
+    parent: Option<Box<Tree>>,
+}
+
+#[test]
+fn recursive_types() {
+    let tree = Tree {
+        label: "root".to_string(),
+        children: vec![Tree {
+            label: "leaf".to_string(),
+            children: vec![],
+            parent: None,
+        }],
+        parent: Some(Box::new(Tree {
+            label: "up".to_string(),
+            children: vec![],
+            parent: None,
+        })),
+    };
+    let bytes = to_vec(&tree)?;
+    assert_eq!(from_slice::<Tree>(&bytes)?, tree);
+
+    // `Tree` is described once, and refers to itself
+    let schema = Schema::of(Tree::SHAPE).unwrap();
+    let trees = schema
+        .types
+        .iter()
+        .filter(|ty| matches!(ty, TypeDef::Struct { name, .. } if name == "Tree"))
+        .count();
+    assert_eq!(trees, 1);
+}
+
+#[test]
+fn pointers_are_written_as_their_pointee() {
+    let shared = Arc::new(7u32);
+    assert_eq!(to_vec(&shared)?, to_vec(&7u32)?);
+    assert_eq!(from_slice::<Arc<u32>>(&to_vec(&shared)?)?, shared);
+}
+
+#[test]
+fn inspect_without_rust_type() {
+    let document = Document::from_slice(&to_vec(&Point(3, 4))?)?;
+    assert_eq!(
+        document.schema.root(),
+        &TypeDef::Struct {
+            name: "Point".to_string(),
+            fields: vec![
+                FieldDef {
+                    name: "0".to_string(),
+                    ty: 1,
+                },
+                FieldDef {
+                    name: "1".to_string(),
+                    ty: 1,
+                },
+            ],
+        }
+    );
+    assert_eq!(document.schema.types[1], TypeDef::Signed(4));
+    assert_eq!(document.value.to_string(), "Point(3, 4)");
+
+    let shapes = vec![Shape::Empty, Shape::Circle(0.5)];
+    let document = to_document(&shapes)?;
+    assert_eq!(
+        document.value.to_string(),
+        "[Shape::Empty, Shape::Circle(0.5)]"
+    );
+
+    // Documents can be written back as they were read
+    let bytes = to_vec(&config())?;
+    assert_eq!(Document::from_slice(&bytes)?.to_vec()?, bytes);
+}
+
+#[test]
+fn documents_from_values() {
+    let document = Document {
+        schema: Schema::of(<Vec<u8>>::SHAPE).unwrap(),
+        value: Value::Bytes(b"raw".to_vec()),
+    };
+    assert_eq!(from_slice::<Vec<u8>>(&document.to_vec()?)?, b"raw");
+
+    let document = Document {
+        value: Value::String("not bytes".to_string()),
+        ..document
+    };
+    assert!(document.to_vec().is_err());
+}
+
+#[test]
+fn canonical_form() {
+    // Map entries are sorted, whatever order they were added in
+    let forward: HashMap<String, u32> = (0..32).map(|n| (n.to_string(), n)).collect();
+    let backward: HashMap<String, u32> = (0..32).rev().map(|n| (n.to_string(), n)).collect();
+    let bytes = to_vec_canonical(&forward)?;
+    assert_eq!(bytes, to_vec_canonical(&backward)?);
+    assert_eq!(from_slice::<HashMap<String, u32>>(&bytes)?, forward);
+
+    let document = Document::from_slice(&bytes)?;
+    let Value::Map(entries) = &document.value else {
+        panic!("expected a map, found {}", document.value);
+    };
+    let keys: Vec<&Value> = entries.iter().map(|(key, _)| key).collect();
+    let mut sorted = keys.clone();
+    sorted.sort_by_key(|key| match key {
+        Value::String(s) => (s.len(), s.clone()),
+        _ => unreachable!(),
+    });
+    // Strings are sorted by their length first, which they're written with
+    assert_eq!(keys, sorted);
+
+    // Every NaN is the same
+    let nan = f64::from_bits(f64::NAN.to_bits() | 1);
+    assert_eq!(to_vec_canonical(&nan)?, to_vec_canonical(&f64::NAN)?);
+    assert_ne!(to_vec(&nan)?, to_vec(&f64::NAN)?);
+
+    // Documents built by hand can have a key twice
+    let document = Document {
+        schema: Schema::of(<BTreeMap<u8, u8>>::SHAPE).unwrap(),
+        value: Value::Map(vec![
+            (Value::Unsigned(1), Value::Unsigned(2)),
+            (Value::Unsigned(1), Value::Unsigned(3)),
+        ]),
+    };
+    assert!(document.to_vec().is_ok());
+    assert!(document.to_vec_canonical().is_err());
+}
+
+mod v1 {
+    use facet::Facet;
+
+    #[derive(Debug, Facet, PartialEq)]
+    pub struct User {
+        pub id: u32,
+        pub name: String,
+        pub legacy: bool,
+    }
+}
+
+mod v2 {
+    use facet::Facet;
+
+    #[derive(Debug, Facet, PartialEq)]
+    pub struct User {
+        pub id: u64,
+        pub name: String,
+        pub email: Option<String>,
+        #[facet(default)]
+        pub logins: u32,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    #[facet(deny_unknown_fields)]
+    pub struct StrictUser {
+        pub id: u64,
+        pub name: String,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    pub struct NeedsAge {
+        pub id: u32,
+        pub age: u8,
+    }
+}
+
+#[test]
+fn schema_evolution() {
+    let old = v1::User {
+        id: 1,
+        name: "ada".to_string(),
+        legacy: true,
+    };
+    let bytes = to_vec(&old)?;
+
+    assert_eq!(
+        from_slice::<v2::User>(&bytes)?,
+        v2::User {
+            id: 1,
+            name: "ada".to_string(),
+            email: None,
+            logins: 0,
+        }
+    );
+
+    let err = from_slice::<v2::StrictUser>(&bytes).unwrap_err();
+    assert!(matches!(err, BinDeError::UnknownField { ref name, .. } if name == "legacy"));
+
+    let err = from_slice::<v2::NeedsAge>(&bytes).unwrap_err();
+    assert!(matches!(err, BinDeError::MissingField("age")));
+}
+
+#[test]
+fn numbers_out_of_range() {
+    let err = from_value::<u8>(Value::Unsigned(256)).unwrap_err();
+    assert!(matches!(err, BinDeError::NumberOutOfRange(_)));
+    let err = from_value::<u32>(Value::Signed(1)).unwrap_err();
+    assert!(matches!(
+        err,
+        BinDeError::TypeMismatch {
+            found: "signed integer",
+            ..
+        }
+    ));
+    assert_eq!(from_value::<i8>(Value::Signed(-128))?, -128);
+}
+
+#[test]
+fn malformed_input() {
+    assert_eq!(
+        Document::from_slice(b"JSON").unwrap_err(),
+        DecodeError::NotFacetBin
+    );
+
+    let mut bytes = to_vec(&config())?;
+    bytes[4] = 99;
+    assert_eq!(
+        Document::from_slice(&bytes).unwrap_err(),
+        DecodeError::UnsupportedVersion(99)
+    );
+
+    let bytes = to_vec(&config())?;
+    for len in 0..bytes.len() {
+        assert!(Document::from_slice(&bytes[..len]).is_err());
+    }
+
+    let mut bytes = to_vec(&1u32)?;
+    bytes.push(0);
+    assert!(matches!(
+        Document::from_slice(&bytes).unwrap_err(),
+        DecodeError::Invalid { .. }
+    ));
+}
+
+#[test]
+fn malicious_schemas() {
+    // A struct that contains itself can't be written, and nests forever when read
+    let schema = Schema {
+        types: vec![TypeDef::Struct {
+            name: "Loop".to_string(),
+            fields: vec![FieldDef {
+                name: "next".to_string(),
+                ty: 0,
+            }],
+        }],
+        root: 0,
+    };
+    let mut value = Value::Unit;
+    for _ in 0..1000 {
+        value = Value::Struct {
+            name: "Loop".to_string(),
+            fields: vec![("next".to_string(), value)],
+        };
+    }
+    assert!(Document { schema, value }.to_vec().is_err());
+
+    // A list of empty values claiming to be huge
+    let mut bytes = to_vec(&Vec::<()>::new())?;
+    bytes.pop();
+    bytes.extend_from_slice(&[0xff, 0xff, 0xff, 0xff, 0x0f]);
+    assert!(Document::from_slice(&bytes).is_err());
+}
@@ -0,0 +1,176 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use facet_core::{Characteristic, Def, Facet, FieldFlags};
+use facet_reflect::{FieldMeta, Partial};
+
+use crate::BuildError;
+
+/// Builds a `T` one field at a time, by name, over a [`Partial`].
+///
+/// Each value is checked against the type of the field it's for, so a mistake is an error
+/// rather than a wrong value. A field of type `Option<U>` takes a `U` too, for `Some`.
+///
+/// Fields that are never set are filled in when building, from `#[facet(default)]` on the
+/// field or on the struct, and `None` for options. The others must be set, or
+/// [`build`](Self::build) fails with every one of them.
+///
+/// ```
+/// # use facet::Facet;
+/// # use facet_builder::Builder;
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Server {
+///     host: String,
+///     #[facet(default = 8080)]
+///     port: u16,
+///     name: Option<String>,
+/// }
+///
+/// let server = Builder::<Server>::new()
+///     .set("host", String::from("localhost"))?
+///     .set("name", String::from("dev"))?
+///     .build()?;
+/// assert_eq!(server.port, 8080);
+/// assert_eq!(server.name.as_deref(), Some("dev"));
+///
+/// assert!(Builder::<Server>::new().set("port", 80u32).is_err());
+/// assert!(Builder::<Server>::new().build().is_err());
+/// # Ok::<(), facet_builder::BuildError>(())
+/// ```
+pub struct Builder<'facet, T> {
+    partial: Partial<'facet, 'static>,
+    phantom: PhantomData<T>,
+}
+
+impl<'facet, T: Facet<'facet>> Builder<'facet, T> {
+    /// A builder with no field set.
+    pub fn new() -> Self {
+        Self {
+            partial: Partial::alloc_shape(T::SHAPE).expect("sized types can be allocated"),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets `field` to `value`, replacing what it was set to before.
+    pub fn set<U: Facet<'facet>>(mut self, field: &str, value: U) -> Result<Self, BuildError> {
+        let meta = self.field(field)?;
+        let shape = meta.shape();
+        if shape.is_shape(U::SHAPE) {
+            self.partial.set_field(meta.name(), value)?;
+        } else if matches!(shape.def, Def::Option(def) if def.t().is_shape(U::SHAPE)) {
+            self.partial
+                .begin_field(meta.name())?
+                .begin_some()?
+                .set(value)?
+                .end()?
+                .end()?;
+        } else {
+            return Err(BuildError::WrongType {
+                field: meta.name(),
+                expected: shape,
+                actual: U::SHAPE,
+            });
+        }
+        Ok(self)
+    }
+
+    /// Sets `field` to the value parsed from `s`, with the `FromStr` implementation of its
+    /// type.
+    pub fn parse(mut self, field: &str, s: &str) -> Result<Self, BuildError> {
+        let meta = self.field(field)?;
+        self.partial
+            .begin_field(meta.name())?
+            .parse_from_str(s)?
+            .end()?;
+        Ok(self)
+    }
+
+    /// Returns `true` if `field` was set.
+    pub fn is_set(&self, field: &str) -> bool {
+        self.partial
+            .field_index(field)
+            .is_some_and(|index| self.partial.is_field_set(index).unwrap_or(false))
+    }
+
+    /// The fields that still need a value, in declaration order: those not set yet, which
+    /// have no default.
+    pub fn missing(&self) -> Vec<&'static str> {
+        let has_default = T::SHAPE.has_default_attr();
+        self.partial
+            .fields_with_meta()
+            .enumerate()
+            .filter(|&(index, meta)| {
+                !has_default
+                    && !has_field_default(&meta)
+                    && !matches!(meta.shape().def, Def::Option(_))
+                    && !self.partial.is_field_set(index).unwrap_or(false)
+            })
+            .map(|(_, meta)| meta.name())
+            .collect()
+    }
+
+    /// Fills in the fields that weren't set and builds the value.
+    pub fn build(mut self) -> Result<T, BuildError> {
+        let missing = self.missing();
+        if !missing.is_empty() {
+            return Err(BuildError::MissingFields {
+                shape: T::SHAPE,
+                fields: missing,
+            });
+        }
+
+        let fields: Vec<_> = self.partial.fields_with_meta().enumerate().collect();
+        for (index, meta) in fields {
+            if self.partial.is_field_set(index)? {
+                continue;
+            }
+            let field = meta.field();
+            if field.flags.contains(FieldFlags::DEFAULT) {
+                self.partial.begin_nth_field(index)?;
+                match field.vtable.default_fn {
+                    Some(default_fn) => self.partial.set_field_default(default_fn)?,
+                    None => self.partial.set_default()?,
+                };
+                self.partial.end()?;
+            } else if matches!(meta.shape().def, Def::Option(_)) {
+                self.partial.begin_nth_field(index)?.set_default()?.end()?;
+            }
+        }
+        self.partial.fill_unset_fields_from_default()?;
+
+        Ok(self.partial.build()?.materialize()?)
+    }
+
+    fn field(&self, name: &str) -> Result<FieldMeta<'static>, BuildError> {
+        self.partial
+            .fields_with_meta()
+            .find(|meta| meta.name() == name)
+            .ok_or_else(|| BuildError::NoSuchField {
+                shape: T::SHAPE,
+                field: name.into(),
+            })
+    }
+}
+
+impl<'facet, T: Facet<'facet>> Default for Builder<'facet, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a field can be left out for `#[facet(default)]` on it
+fn has_field_default(meta: &FieldMeta<'_>) -> bool {
+    let field = meta.field();
+    field.flags.contains(FieldFlags::DEFAULT)
+        && (field.vtable.default_fn.is_some() || meta.shape().is(Characteristic::Default))
+}
+
+/// Gives every type a [`Builder`]: `Server::builder()` is `Builder::<Server>::new()`.
+pub trait Buildable<'facet>: Facet<'facet> + Sized {
+    /// A builder for this type, with no field set.
+    fn builder() -> Builder<'facet, Self> {
+        Builder::new()
+    }
+}
+
+impl<'facet, T: Facet<'facet>> Buildable<'facet> for T {}
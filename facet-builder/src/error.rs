@@ -0,0 +1,71 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+
+/// Why a [`Builder`](crate::Builder) couldn't set a field or build its value.
+#[derive(Debug)]
+pub enum BuildError {
+    /// The type has no field of that name.
+    NoSuchField {
+        /// The type being built
+        shape: &'static Shape<'static>,
+        /// The name that was given
+        field: String,
+    },
+    /// The value given for a field isn't of the field's type.
+    WrongType {
+        /// The field
+        field: &'static str,
+        /// The type of the field
+        expected: &'static Shape<'static>,
+        /// The type of the value that was given
+        actual: &'static Shape<'static>,
+    },
+    /// Fields without a default were never set.
+    MissingFields {
+        /// The type being built
+        shape: &'static Shape<'static>,
+        /// The fields, in declaration order
+        fields: Vec<&'static str>,
+    },
+    /// Building the value failed, like when a string doesn't parse or an invariant doesn't
+    /// hold.
+    Reflect(ReflectError<'static>),
+}
+
+impl From<ReflectError<'static>> for BuildError {
+    fn from(err: ReflectError<'static>) -> Self {
+        BuildError::Reflect(err)
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::NoSuchField { shape, field } => {
+                write!(f, "{shape} has no field `{field}`")
+            }
+            BuildError::WrongType {
+                field,
+                expected,
+                actual,
+            } => write!(f, "field `{field}` is a {expected}, not a {actual}"),
+            BuildError::MissingFields { shape, fields } => {
+                write!(f, "{shape} is missing ")?;
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "`{field}`")?;
+                }
+                Ok(())
+            }
+            BuildError::Reflect(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl core::error::Error for BuildError {}
@@ -0,0 +1,113 @@
+use facet::Facet;
+use facet_builder::{BuildError, Buildable, Builder};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Server {
+    host: String,
+    #[facet(default = 8080)]
+    port: u16,
+    workers: usize,
+    name: Option<String>,
+    #[facet(default)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn builds_with_defaults() {
+    let server = Server::builder()
+        .set("host", "localhost".to_string())?
+        .set("workers", 4usize)?
+        .build()?;
+    assert_eq!(
+        server,
+        Server {
+            host: "localhost".to_string(),
+            port: 8080,
+            workers: 4,
+            name: None,
+            tags: vec![],
+        }
+    );
+}
+
+#[test]
+fn sets_options_and_parses() {
+    let server = Builder::<Server>::new()
+        .set("host", "localhost".to_string())?
+        .parse("workers", "2")?
+        .set("name", "dev".to_string())?
+        .set("port", 80u16)?
+        .set("port", 81u16)?
+        .build()?;
+    assert_eq!(server.name.as_deref(), Some("dev"));
+    assert_eq!(server.workers, 2);
+    assert_eq!(server.port, 81);
+}
+
+#[test]
+fn reports_missing_fields() {
+    let builder = Server::builder().set("workers", 1usize)?;
+    assert!(builder.is_set("workers"));
+    assert!(!builder.is_set("host"));
+    assert_eq!(builder.missing(), ["host"]);
+
+    let err = Server::builder().build().unwrap_err();
+    assert!(matches!(
+        &err,
+        BuildError::MissingFields { fields, .. } if fields == &["host", "workers"]
+    ));
+    assert_eq!(err.to_string(), "Server is missing `host`, `workers`");
+}
+
+#[test]
+fn rejects_wrong_fields() {
+    let err = Server::builder().set("port", 80u32).err().unwrap();
+    assert!(matches!(err, BuildError::WrongType { field: "port", .. }));
+    assert_eq!(err.to_string(), "field `port` is a u16, not a u32");
+
+    let err = Server::builder()
+        .set("hostname", "x".to_string())
+        .err()
+        .unwrap();
+    assert!(matches!(err, BuildError::NoSuchField { ref field, .. } if field == "hostname"));
+
+    let err = Server::builder().parse("workers", "many").err().unwrap();
+    assert!(matches!(err, BuildError::Reflect(_)));
+}
+
+#[derive(Facet, Debug, Default, PartialEq)]
+#[facet(default)]
+struct Limits {
+    max: u32,
+    min: u32,
+}
+
+#[test]
+fn struct_default_fills_everything() {
+    assert!(Limits::builder().missing().is_empty());
+    let limits = Limits::builder().set("max", 10u32)?.build()?;
+    assert_eq!(limits, Limits { max: 10, min: 0 });
+}
+
+#[derive(Facet, Debug)]
+#[facet(invariants = Range::is_valid)]
+struct Range {
+    start: u32,
+    end: u32,
+}
+
+impl Range {
+    fn is_valid(&self) -> bool {
+        self.start <= self.end
+    }
+}
+
+#[test]
+fn checks_invariants() {
+    let result = Range::builder()
+        .set("start", 2u32)?
+        .set("end", 1u32)?
+        .build();
+    assert!(matches!(result, Err(BuildError::Reflect(_))));
+}
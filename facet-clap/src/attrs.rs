@@ -0,0 +1,168 @@
+//! How fields map to arguments, from their shape and `#[facet(...)]` attributes.
+
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec::Vec,
+};
+use facet_core::{Def, EnumType, Field, FieldAttribute, FieldFlags, Shape, Type, UserType};
+
+use crate::ClapError;
+
+/// What a field becomes on the command line.
+pub(crate) enum Role<'shape> {
+    /// A `bool`, set by passing `--flag`.
+    Flag,
+    /// An argument taking values, parsed as `item`.
+    Value {
+        item: &'shape Shape<'shape>,
+        positional: bool,
+        /// `Vec` fields take the argument any number of times.
+        multiple: bool,
+        required: bool,
+    },
+    /// An enum whose variants are subcommands, with `#[facet(subcommand)]`.
+    Subcommand {
+        enum_type: EnumType<'shape>,
+        optional: bool,
+    },
+    /// A struct whose fields are arguments of this command, with `#[facet(flatten)]`.
+    Flatten(&'shape [Field<'shape>]),
+}
+
+/// A field, read as a command-line argument.
+pub(crate) struct FieldArg<'shape> {
+    /// The argument id, which is also its long name: the field name in kebab-case.
+    pub id: String,
+    pub short: Option<char>,
+    pub role: Role<'shape>,
+}
+
+impl<'shape> FieldArg<'shape> {
+    pub fn of(field: &'shape Field<'shape>) -> Result<Self, ClapError<'shape>> {
+        let mut positional = field.name.parse::<usize>().is_ok();
+        let mut subcommand = false;
+        let mut short = None;
+        for attr in field.attributes {
+            let FieldAttribute::Arbitrary(content) = attr else {
+                continue;
+            };
+            let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+            match compact.as_str() {
+                "positional" => positional = true,
+                "subcommand" => subcommand = true,
+                "short" => short = field.name.chars().next(),
+                _ => {
+                    if let Some(value) = compact.strip_prefix("short=") {
+                        let mut chars = value.trim_matches(['\'', '"']).chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(c), None) => short = Some(c),
+                            _ => return Err(ClapError::InvalidAttribute((*content).to_owned())),
+                        }
+                    }
+                }
+            }
+        }
+
+        let shape = field.shape();
+        let role = if field.flags.contains(FieldFlags::FLATTEN) {
+            match shape.ty {
+                Type::User(UserType::Struct(sd)) => Role::Flatten(sd.fields),
+                _ => return Err(ClapError::UnsupportedShape(shape)),
+            }
+        } else if subcommand {
+            let (inner, optional) = match shape.def {
+                Def::Option(od) => (od.t(), true),
+                _ => (shape, false),
+            };
+            match inner.ty {
+                Type::User(UserType::Enum(enum_type)) => Role::Subcommand {
+                    enum_type,
+                    optional,
+                },
+                _ => return Err(ClapError::UnsupportedShape(shape)),
+            }
+        } else if shape.is_type::<bool>() && !positional {
+            Role::Flag
+        } else {
+            let defaulted = field.flags.contains(FieldFlags::DEFAULT);
+            match shape.def {
+                Def::Option(od) => Role::Value {
+                    item: od.t(),
+                    positional,
+                    multiple: false,
+                    required: false,
+                },
+                Def::List(ld) => Role::Value {
+                    item: ld.t(),
+                    positional,
+                    multiple: true,
+                    required: false,
+                },
+                _ => Role::Value {
+                    item: shape,
+                    positional,
+                    multiple: false,
+                    required: !defaulted,
+                },
+            }
+        };
+
+        Ok(FieldArg {
+            id: kebab_case(field.name),
+            short,
+            role,
+        })
+    }
+}
+
+/// The values an argument accepts, when its type is an enum of unit variants.
+pub(crate) fn possible_values(shape: &Shape<'_>) -> Option<Vec<String>> {
+    let Type::User(UserType::Enum(enum_type)) = shape.ty else {
+        return None;
+    };
+    enum_type
+        .variants
+        .iter()
+        .map(|v| v.data.fields.is_empty().then(|| kebab_case(v.name)))
+        .collect()
+}
+
+/// `FooBar`, `fooBar` and `foo_bar` all become `foo-bar`, like clap's derive does.
+pub(crate) fn kebab_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (index, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' {
+            if !out.is_empty() && !out.ends_with('-') {
+                out.push('-');
+            }
+            continue;
+        }
+        if c.is_uppercase() && index > 0 {
+            let prev = chars[index - 1];
+            let next_lower = chars.get(index + 1).is_some_and(|n| n.is_lowercase());
+            let boundary =
+                prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower);
+            if boundary && !out.ends_with('-') {
+                out.push('-');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// Splits doc comments into a one-paragraph summary, and the full text when
+/// there is more than that.
+pub(crate) fn help(doc: &[&str]) -> Option<(String, Option<String>)> {
+    let lines: Vec<&str> = doc.iter().map(|line| line.trim()).collect();
+    let text = lines.join("\n").trim().to_string();
+    if text.is_empty() {
+        return None;
+    }
+    match text.split_once("\n\n") {
+        Some((summary, _)) => Some((summary.replace('\n', " "), Some(text))),
+        None => Some((text.replace('\n', " "), None)),
+    }
+}
@@ -0,0 +1,158 @@
+//! Build a `clap::Command` from a shape.
+
+use clap::{Arg, ArgAction, Command, builder::PossibleValuesParser};
+use facet_core::{EnumType, Facet, Field, Shape, StructKind, Type, UserType, Variant};
+
+use crate::{
+    ClapError,
+    attrs::{FieldArg, Role, help, kebab_case, possible_values},
+};
+
+/// Builds the `clap::Command` for `T`, named after the type in kebab-case.
+///
+/// Structs become a command whose fields are its arguments: `bool` fields are
+/// flags, `Option` and `Vec` fields are optional, fields with
+/// `#[facet(positional)]` are positional, and the others are `--long-name`
+/// options, with `#[facet(short = 'x')]` adding a short name. A field with
+/// `#[facet(subcommand)]` holds an enum whose variants are subcommands, and
+/// `#[facet(flatten)]` pulls another struct's arguments in. Enums become a
+/// command that requires one of their subcommands. Doc comments become help.
+///
+/// The command can be customized further before parsing, for instance with
+/// `.version(...)`, and its matches read back with [`from_arg_matches`](crate::from_arg_matches).
+///
+/// ```
+/// use facet::Facet;
+///
+/// /// Copies files around
+/// #[derive(Facet)]
+/// struct Copy {
+///     /// Overwrite existing files
+///     #[facet(short = 'f')]
+///     force: bool,
+///     #[facet(positional)]
+///     from: String,
+///     #[facet(positional)]
+///     to: String,
+/// }
+///
+/// let command = facet_clap::command::<Copy>().unwrap();
+/// assert_eq!(command.get_name(), "copy");
+/// assert_eq!(command.get_about().unwrap().to_string(), "Copies files around");
+/// ```
+pub fn command<'facet: 'shape, 'shape, T: Facet<'facet>>() -> Result<Command, ClapError<'shape>> {
+    command_for_shape(T::SHAPE)
+}
+
+pub(crate) fn command_for_shape<'shape>(
+    shape: &'shape Shape<'shape>,
+) -> Result<Command, ClapError<'shape>> {
+    let mut command = with_help(Command::new(kebab_case(shape.type_identifier)), shape.doc);
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => command = add_fields(command, sd.fields)?,
+        Type::User(UserType::Enum(enum_type)) => {
+            command = add_subcommands(command, enum_type)?
+                .subcommand_required(true)
+                .arg_required_else_help(true);
+        }
+        _ => return Err(ClapError::UnsupportedShape(shape)),
+    }
+    Ok(command)
+}
+
+fn with_help(command: Command, doc: &[&str]) -> Command {
+    match help(doc) {
+        Some((about, Some(long_about))) => command.about(about).long_about(long_about),
+        Some((about, None)) => command.about(about),
+        None => command,
+    }
+}
+
+/// Adds the arguments and subcommands for a struct's fields.
+fn add_fields<'shape>(
+    mut command: Command,
+    fields: &'shape [Field<'shape>],
+) -> Result<Command, ClapError<'shape>> {
+    for field in fields {
+        let FieldArg { id, short, role } = FieldArg::of(field)?;
+        let mut arg = Arg::new(id.clone());
+        match role {
+            Role::Flatten(fields) => {
+                command = add_fields(command, fields)?;
+                continue;
+            }
+            Role::Subcommand {
+                enum_type,
+                optional,
+            } => {
+                command = add_subcommands(command, enum_type)?.subcommand_required(!optional);
+                continue;
+            }
+            Role::Flag => {
+                arg = arg.long(id).action(ArgAction::SetTrue);
+            }
+            Role::Value {
+                item,
+                positional,
+                multiple,
+                required,
+            } => {
+                arg = arg.required(required);
+                if !positional {
+                    arg = arg.long(id);
+                }
+                arg = match (multiple, positional) {
+                    (true, true) => arg.action(ArgAction::Append).num_args(1..),
+                    (true, false) => arg.action(ArgAction::Append),
+                    (false, _) => arg.action(ArgAction::Set),
+                };
+                if let Some(values) = possible_values(item) {
+                    arg = arg.value_parser(PossibleValuesParser::new(values));
+                }
+            }
+        }
+        if let Some(short) = short {
+            arg = arg.short(short);
+        }
+        match help(field.doc) {
+            Some((summary, Some(long_help))) => arg = arg.help(summary).long_help(long_help),
+            Some((summary, None)) => arg = arg.help(summary),
+            None => {}
+        }
+        command = command.arg(arg);
+    }
+    Ok(command)
+}
+
+fn add_subcommands<'shape>(
+    mut command: Command,
+    enum_type: EnumType<'shape>,
+) -> Result<Command, ClapError<'shape>> {
+    for variant in enum_type.variants {
+        let subcommand = with_help(Command::new(kebab_case(variant.name)), variant.doc);
+        command = command.subcommand(add_fields(subcommand, variant_fields(variant))?);
+    }
+    Ok(command)
+}
+
+/// The fields that make up a subcommand's arguments: those of the struct in a
+/// variant like `Add(AddArgs)`, or the variant's own fields.
+fn variant_fields<'shape>(variant: &'shape Variant<'shape>) -> &'shape [Field<'shape>] {
+    match struct_payload(variant) {
+        Some(fields) => fields,
+        None => variant.data.fields,
+    }
+}
+
+/// The fields of the struct a variant like `Add(AddArgs)` wraps.
+pub(crate) fn struct_payload<'shape>(
+    variant: &'shape Variant<'shape>,
+) -> Option<&'shape [Field<'shape>]> {
+    match (variant.data.kind, variant.data.fields) {
+        (StructKind::TupleStruct | StructKind::Tuple, [field]) => match field.shape().ty {
+            Type::User(UserType::Struct(sd)) if sd.kind == StructKind::Struct => Some(sd.fields),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,70 @@
+//! Errors from building commands and reading matches.
+
+use alloc::string::String;
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+
+/// Errors when turning a shape into a command, or arguments into a value.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ClapError<'shape> {
+    /// clap rejected the arguments, or they asked for `--help` or `--version`.
+    Clap(clap::Error),
+    /// The type can't be a command, an argument or a subcommand.
+    UnsupportedShape(&'shape Shape<'shape>),
+    /// A `#[facet(...)]` attribute meant for the command line couldn't be parsed.
+    InvalidAttribute(String),
+    /// An argument's value couldn't be parsed as its field's type.
+    InvalidValue {
+        /// The argument, as named on the command line.
+        arg: String,
+        /// The value that was given.
+        value: String,
+        /// The type it should have parsed as.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A required argument was not in the matches.
+    MissingArgument(String),
+    /// A required subcommand was not in the matches.
+    MissingSubcommand(&'shape Shape<'shape>),
+    /// Any error from facet.
+    Reflect(ReflectError<'shape>),
+}
+
+impl<'shape> From<ReflectError<'shape>> for ClapError<'shape> {
+    fn from(err: ReflectError<'shape>) -> Self {
+        ClapError::Reflect(err)
+    }
+}
+
+impl From<clap::Error> for ClapError<'_> {
+    fn from(err: clap::Error) -> Self {
+        ClapError::Clap(err)
+    }
+}
+
+impl core::fmt::Display for ClapError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ClapError::Clap(err) => write!(f, "{err}"),
+            ClapError::UnsupportedShape(shape) => {
+                write!(f, "{shape} can't be used on the command line")
+            }
+            ClapError::InvalidAttribute(attr) => {
+                write!(f, "Invalid attribute #[facet({attr})]")
+            }
+            ClapError::InvalidValue { arg, value, shape } => {
+                write!(f, "Invalid value '{value}' for '{arg}': expected {shape}")
+            }
+            ClapError::MissingArgument(arg) => {
+                write!(f, "Missing required argument '{arg}'")
+            }
+            ClapError::MissingSubcommand(shape) => {
+                write!(f, "Missing subcommand for {shape}")
+            }
+            ClapError::Reflect(err) => write!(f, "Reflection error: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for ClapError<'_> {}
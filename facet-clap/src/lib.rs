@@ -0,0 +1,18 @@
+#![warn(missing_docs)]
+#![warn(clippy::std_instead_of_core)]
+#![warn(clippy::std_instead_of_alloc)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+mod error;
+pub use error::*;
+
+mod attrs;
+
+mod command;
+pub use command::*;
+
+mod matches;
+pub use matches::*;
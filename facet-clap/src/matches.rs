@@ -0,0 +1,259 @@
+//! Fill values from `clap::ArgMatches`.
+
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+};
+use clap::{ArgMatches, error::ErrorKind};
+use facet_core::{Def, EnumType, Facet, Field, FieldFlags, Type, UserType};
+use facet_reflect::{Partial, ScalarType};
+use log::trace;
+use std::ffi::{OsStr, OsString};
+
+use crate::{
+    ClapError,
+    attrs::{FieldArg, Role, kebab_case, possible_values},
+    command,
+    command::struct_payload,
+};
+
+/// Parses the process's arguments into a `T`, like `clap::Parser::parse`.
+///
+/// Help, version and errors are printed the way clap prints them, and exit the
+/// process. Types that can't be a command (see [`command`]) panic.
+pub fn parse<'facet, T: Facet<'facet>>() -> T {
+    let mut command = command::<T>().unwrap_or_else(|err| panic!("{err}"));
+    let matches = command.get_matches_mut();
+    match from_arg_matches(&matches) {
+        Ok(value) => value,
+        Err(ClapError::Clap(err)) => err.format(&mut command).exit(),
+        Err(err) => command.error(ErrorKind::ValueValidation, err).exit(),
+    }
+}
+
+/// Parses `args` into a `T`; the first item is the program name, as in
+/// `std::env::args`.
+///
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Args {
+///     #[facet(short = 'v')]
+///     verbose: bool,
+///     #[facet(short = 'j', default = 1)]
+///     jobs: usize,
+///     #[facet(positional)]
+///     paths: Vec<String>,
+/// }
+///
+/// let args: Args = facet_clap::try_parse_from(["build", "-v", "a.rs", "b.rs"]).unwrap();
+/// assert_eq!(
+///     args,
+///     Args {
+///         verbose: true,
+///         jobs: 1,
+///         paths: vec!["a.rs".to_string(), "b.rs".to_string()],
+///     }
+/// );
+/// ```
+pub fn try_parse_from<'facet: 'shape, 'shape, T, I, S>(args: I) -> Result<T, ClapError<'shape>>
+where
+    T: Facet<'facet>,
+    I: IntoIterator<Item = S>,
+    S: Into<OsString> + Clone,
+{
+    let matches = command::<T>()?.try_get_matches_from(args)?;
+    from_arg_matches(&matches)
+}
+
+/// Fills a `T` from the matches of its [`command`], which may have been
+/// customized or nested in a bigger command before parsing.
+pub fn from_arg_matches<'facet: 'shape, 'shape, T: Facet<'facet>>(
+    matches: &ArgMatches,
+) -> Result<T, ClapError<'shape>> {
+    let mut partial = Partial::alloc::<T>()?;
+    let wip = partial.inner_mut();
+    let shape = wip.shape();
+    trace!("Filling {shape} from argument matches");
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => fill_fields(wip, sd.fields, matches)?,
+        Type::User(UserType::Enum(enum_type)) => fill_subcommand(wip, enum_type, matches)?,
+        _ => return Err(ClapError::UnsupportedShape(shape)),
+    }
+    let value = partial.build()?;
+    Ok(*value)
+}
+
+fn fill_fields<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    matches: &ArgMatches,
+) -> Result<(), ClapError<'shape>> {
+    for (index, field) in fields.iter().enumerate() {
+        let FieldArg { id, role, .. } = FieldArg::of(field)?;
+        match role {
+            Role::Flatten(fields) => {
+                wip.begin_nth_field(index)?;
+                fill_fields(wip, fields, matches)?;
+                wip.end()?;
+            }
+            Role::Subcommand {
+                enum_type,
+                optional,
+            } => {
+                if matches.subcommand().is_none() {
+                    continue;
+                }
+                wip.begin_nth_field(index)?;
+                if optional {
+                    wip.begin_some()?;
+                }
+                fill_subcommand(wip, enum_type, matches)?;
+                if optional {
+                    wip.end()?;
+                }
+                wip.end()?;
+            }
+            Role::Flag => {
+                let Ok(Some(&set)) = matches.try_get_one::<bool>(&id) else {
+                    continue;
+                };
+                wip.begin_nth_field(index)?;
+                wip.set(set)?;
+                wip.end()?;
+            }
+            Role::Value { multiple, .. } => {
+                let Ok(Some(mut values)) = matches.try_get_raw(&id) else {
+                    continue;
+                };
+                wip.begin_nth_field(index)?;
+                if multiple {
+                    wip.begin_list()?;
+                    for value in values {
+                        wip.begin_list_item()?;
+                        set_value(wip, &id, value)?;
+                        wip.end()?;
+                    }
+                } else if let Some(value) = values.next() {
+                    if let Def::Option(_) = wip.shape().def {
+                        wip.begin_some()?;
+                        set_value(wip, &id, value)?;
+                        wip.end()?;
+                    } else {
+                        set_value(wip, &id, value)?;
+                    }
+                }
+                wip.end()?;
+            }
+        }
+    }
+
+    wip.fill_unset_fields_from_default()?;
+    for (index, field) in fields.iter().enumerate() {
+        if !wip.is_field_set(index)? {
+            default_field(wip, index, field)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fills a field that was not on the command line.
+fn default_field<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    index: usize,
+    field: &'shape Field<'shape>,
+) -> Result<(), ClapError<'shape>> {
+    let has_default = field.flags.contains(FieldFlags::DEFAULT);
+    if !has_default && !matches!(field.shape().def, Def::Option(_) | Def::List(_)) {
+        return Err(match FieldArg::of(field)?.role {
+            Role::Subcommand { .. } => ClapError::MissingSubcommand(field.shape()),
+            _ => ClapError::MissingArgument(kebab_case(field.name)),
+        });
+    }
+
+    wip.begin_nth_field(index)?;
+    match field.vtable.default_fn {
+        Some(default_fn) if has_default => wip.set_field_default(default_fn)?,
+        _ => wip.set_default()?,
+    };
+    wip.end()?;
+    Ok(())
+}
+
+/// Selects the variant named by the subcommand in `matches`, and fills it from
+/// the subcommand's own matches.
+fn fill_subcommand<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    enum_type: EnumType<'shape>,
+    matches: &ArgMatches,
+) -> Result<(), ClapError<'shape>> {
+    let shape = wip.shape();
+    let Some((name, sub_matches)) = matches.subcommand() else {
+        return Err(ClapError::MissingSubcommand(shape));
+    };
+    let Some(index) = enum_type
+        .variants
+        .iter()
+        .position(|v| kebab_case(v.name) == name)
+    else {
+        return Err(ClapError::MissingSubcommand(shape));
+    };
+    let variant = &enum_type.variants[index];
+    trace!("Subcommand '{name}' selects {shape}::{}", variant.name);
+    wip.select_nth_variant(index)?;
+    match struct_payload(variant) {
+        Some(fields) => {
+            wip.begin_nth_field(0)?;
+            fill_fields(wip, fields, sub_matches)?;
+            wip.end()?;
+        }
+        None => fill_fields(wip, variant.data.fields, sub_matches)?,
+    }
+    Ok(())
+}
+
+fn set_value<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    arg: &str,
+    value: &OsStr,
+) -> Result<(), ClapError<'shape>> {
+    let shape = wip.shape();
+    let invalid = || ClapError::InvalidValue {
+        arg: arg.to_string(),
+        value: value.to_string_lossy().into_owned(),
+        shape,
+    };
+    let text = value.to_str().ok_or_else(invalid)?;
+
+    if let Type::User(UserType::Enum(enum_type)) = shape.ty {
+        if possible_values(shape).is_some() {
+            let index = enum_type
+                .variants
+                .iter()
+                .position(|v| kebab_case(v.name) == text)
+                .ok_or_else(invalid)?;
+            wip.select_nth_variant(index)?;
+            return Ok(());
+        }
+    }
+
+    match ScalarType::try_from_shape(shape) {
+        Some(ScalarType::String) => {
+            wip.set(String::from(text))?;
+        }
+        Some(ScalarType::CowStr) => {
+            wip.set(Cow::<'static, str>::Owned(text.to_string()))?;
+        }
+        _ if shape.is_from_str() => {
+            wip.parse_from_str(text).map_err(|_| invalid())?;
+        }
+        _ if shape.inner.is_some() => {
+            wip.begin_inner()?;
+            set_value(wip, arg, value)?;
+            wip.end()?;
+        }
+        _ => return Err(ClapError::UnsupportedShape(shape)),
+    }
+    Ok(())
+}
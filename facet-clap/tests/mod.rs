@@ -0,0 +1,181 @@
+use facet::Facet;
+use facet_clap::{ClapError, command, from_arg_matches, try_parse_from};
+use facet_testhelpers::test;
+
+/// Builds things
+///
+/// Reads the manifest in the current directory and builds every target.
+#[derive(Debug, Facet, PartialEq)]
+struct Build {
+    /// Print more output
+    #[facet(short = 'v')]
+    verbose: bool,
+    /// How many jobs to run at once
+    #[facet(short = 'j', default = 4)]
+    jobs: usize,
+    profile: Option<String>,
+    #[facet(short = 'F')]
+    features: Vec<String>,
+    color: Color,
+    #[facet(positional)]
+    targets: Vec<String>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+enum Color {
+    Auto,
+    Always,
+    NeverEver,
+}
+
+#[test]
+fn test_flags_options_and_positionals() {
+    let build: Build = try_parse_from([
+        "build",
+        "-v",
+        "--profile",
+        "release",
+        "-F",
+        "serde",
+        "--features",
+        "std",
+        "--color",
+        "never-ever",
+        "lib",
+        "bin",
+    ])?;
+    assert_eq!(
+        build,
+        Build {
+            verbose: true,
+            jobs: 4,
+            profile: Some("release".to_string()),
+            features: vec!["serde".to_string(), "std".to_string()],
+            color: Color::NeverEver,
+            targets: vec!["lib".to_string(), "bin".to_string()],
+        }
+    );
+
+    let build: Build = try_parse_from(["build", "-j", "12", "--color", "auto"])?;
+    assert_eq!(build.jobs, 12);
+    assert!(!build.verbose);
+    assert_eq!(build.profile, None);
+    assert_eq!(build.color, Color::Auto);
+    assert!(build.targets.is_empty());
+
+    let build: Build = try_parse_from(["build", "--color", "always"])?;
+    assert_eq!(build.color, Color::Always);
+}
+
+#[test]
+fn test_help_from_doc_comments() {
+    let mut command = command::<Build>()?;
+    assert_eq!(command.get_name(), "build");
+    assert_eq!(command.get_about().unwrap().to_string(), "Builds things");
+
+    let jobs = command
+        .get_arguments()
+        .find(|arg| arg.get_id() == "jobs")
+        .unwrap();
+    assert_eq!(jobs.get_short(), Some('j'));
+    assert_eq!(
+        jobs.get_help().unwrap().to_string(),
+        "How many jobs to run at once"
+    );
+
+    let help = command.render_long_help().to_string();
+    assert!(help.contains("Reads the manifest"));
+    assert!(help.contains("never-ever"));
+}
+
+#[test]
+fn test_errors() {
+    let err = try_parse_from::<Build, _, _>(["build"]).unwrap_err();
+    let ClapError::Clap(err) = err else {
+        panic!("expected a clap error, got {err}");
+    };
+    assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+
+    let err =
+        try_parse_from::<Build, _, _>(["build", "--color", "auto", "-j", "many"]).unwrap_err();
+    assert!(matches!(err, ClapError::InvalidValue { ref arg, .. } if arg == "jobs"));
+
+    let err = try_parse_from::<Build, _, _>(["build", "--color", "purple"]).unwrap_err();
+    assert!(matches!(err, ClapError::Clap(_)));
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Cli {
+    #[facet(flatten)]
+    global: Global,
+    #[facet(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Global {
+    #[facet(short = 'q')]
+    quiet: bool,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+enum Command {
+    /// Add a dependency
+    Add {
+        #[facet(positional)]
+        name: String,
+        dry_run: bool,
+    },
+    Remove(RemoveArgs),
+    ListAll,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct RemoveArgs {
+    #[facet(positional)]
+    names: Vec<String>,
+}
+
+#[test]
+fn test_subcommands_and_flatten() {
+    let cli: Cli = try_parse_from(["pm", "-q", "add", "serde", "--dry-run"])?;
+    assert_eq!(
+        cli,
+        Cli {
+            global: Global { quiet: true },
+            command: Some(Command::Add {
+                name: "serde".to_string(),
+                dry_run: true,
+            }),
+        }
+    );
+
+    let cli: Cli = try_parse_from(["pm", "remove", "a", "b"])?;
+    assert_eq!(
+        cli.command,
+        Some(Command::Remove(RemoveArgs {
+            names: vec!["a".to_string(), "b".to_string()],
+        }))
+    );
+
+    let cli: Cli = try_parse_from(["pm", "list-all"])?;
+    assert_eq!(cli.command, Some(Command::ListAll));
+
+    let cli: Cli = try_parse_from(["pm"])?;
+    assert_eq!(cli.command, None);
+
+    // An enum on its own requires a subcommand
+    let command: Command = try_parse_from(["command", "list-all"])?;
+    assert_eq!(command, Command::ListAll);
+    assert!(try_parse_from::<Command, _, _>(["command"]).is_err());
+}
+
+#[test]
+fn test_customized_command() {
+    let command = command::<Cli>()?.name("pm").version("1.2.3");
+    let matches = command.try_get_matches_from(["pm", "-q"])?;
+    let cli: Cli = from_arg_matches(&matches)?;
+    assert!(cli.global.quiet);
+}
@@ -0,0 +1,198 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use core::fmt;
+use core::str::FromStr;
+
+use facet_json::{JsonSerializer, PrettyPrinter};
+use facet_msgpack::MessagePackSerializer;
+use facet_toml::TomlSerializer;
+use facet_yaml::YamlSerializer;
+
+mod read;
+
+mod schema;
+pub use schema::*;
+
+mod value;
+pub use value::*;
+
+/// An error from reading a document, writing it, or reading a schema.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliError {
+    /// The input isn't valid in the format it's read as
+    Deser(String),
+    /// The value can't be written in the target format
+    Ser(String),
+    /// The JSON Schema is malformed, or asks for a check that isn't supported
+    Schema(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Deser(message) | CliError::Ser(message) => f.write_str(message),
+            CliError::Schema(message) => write!(f, "Invalid schema: {message}"),
+        }
+    }
+}
+
+impl core::error::Error for CliError {}
+
+/// A format that documents are read from and written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// JSON, written on one line
+    Json,
+    /// YAML, with one document
+    Yaml,
+    /// TOML, which needs an object at the top
+    Toml,
+    /// MessagePack
+    MsgPack,
+}
+
+impl Format {
+    /// The format of a file named `path`, from its extension: `.json`, `.yaml` or `.yml`,
+    /// `.toml`, or `.msgpack`.
+    ///
+    /// ```
+    /// use facet_cli::Format;
+    ///
+    /// assert_eq!(Format::from_path("config/app.yml"), Some(Format::Yaml));
+    /// assert_eq!(Format::from_path("/dev/stdin"), None);
+    /// ```
+    pub fn from_path(path: &str) -> Option<Format> {
+        let (_, extension) = path.rsplit_once('.')?;
+        match extension {
+            "json" => Some(Format::Json),
+            "yaml" | "yml" => Some(Format::Yaml),
+            "toml" => Some(Format::Toml),
+            "msgpack" => Some(Format::MsgPack),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "yaml" => Ok(Format::Yaml),
+            "toml" => Ok(Format::Toml),
+            "msgpack" => Ok(Format::MsgPack),
+            _ => Err(UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+/// The name of a format that [`Format`] doesn't know.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownFormat(pub String);
+
+impl fmt::Display for UnknownFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Unknown format `{}`, expected `json`, `yaml`, `toml` or `msgpack`",
+            self.0
+        )
+    }
+}
+
+impl core::error::Error for UnknownFormat {}
+
+/// Reads a document in the `from` format into a [`Value`], without a Rust type.
+///
+/// JSON is read through its event stream. TOML dates are read as strings, MessagePack
+/// binary data as arrays of bytes, and scalar map keys as their text.
+pub fn read(input: &[u8], from: Format) -> Result<Value, CliError> {
+    match from {
+        Format::Json => Value::from_json(input),
+        Format::Yaml => read::from_yaml(input),
+        Format::Toml => read::from_toml(input),
+        Format::MsgPack => read::from_msgpack(input),
+    }
+}
+
+/// Writes `value` in the `to` format: objects as maps with string keys, and `null` as
+/// `None` is. See [`Value::serialize`].
+pub fn write(value: &Value, to: Format) -> Result<Vec<u8>, CliError> {
+    match to {
+        Format::Json => {
+            let mut out = Vec::new();
+            value
+                .serialize(&mut JsonSerializer::new(&mut out))
+                .map_err(ser)?;
+            Ok(out)
+        }
+        Format::Yaml => {
+            let mut serializer = YamlSerializer::new();
+            value.serialize(&mut serializer).map_err(ser)?;
+            Ok(serializer.into_string().into_bytes())
+        }
+        Format::Toml => {
+            let mut serializer = TomlSerializer::new();
+            value.serialize(&mut serializer).map_err(ser)?;
+            Ok(serializer.into_string().into_bytes())
+        }
+        Format::MsgPack => {
+            let mut out = Vec::new();
+            value
+                .serialize(&mut MessagePackSerializer::new(&mut out))
+                .map_err(ser)?;
+            Ok(out)
+        }
+    }
+}
+
+fn ser(error: impl fmt::Display) -> CliError {
+    CliError::Ser(error.to_string())
+}
+
+/// Converts a document from one format to another, without a Rust type in between.
+///
+/// ```
+/// use facet_cli::{Format, convert};
+///
+/// let yaml = convert(br#"{"name": "facet", "tags": ["a"]}"#, Format::Json, Format::Yaml).unwrap();
+/// assert_eq!(String::from_utf8(yaml).unwrap(), "---\nname: facet\ntags:\n  - a");
+/// ```
+pub fn convert(input: &[u8], from: Format, to: Format) -> Result<Vec<u8>, CliError> {
+    write(&read(input, from)?, to)
+}
+
+/// Writes a document as JSON laid out over lines of up to `max_width` characters, keeping
+/// arrays and objects that fit on one line. See [`PrettyPrinter`].
+///
+/// ```
+/// use facet_cli::{Format, pretty};
+///
+/// let pretty = pretty(b"id = 1\ntags = [\"a\", \"b\"]", Format::Toml, 20).unwrap();
+/// assert_eq!(pretty, "{\n  \"id\": 1,\n  \"tags\": [\"a\", \"b\"]\n}");
+/// ```
+pub fn pretty(input: &[u8], from: Format, max_width: usize) -> Result<String, CliError> {
+    let compact = convert(input, from, Format::Json)?;
+    let compact = String::from_utf8(compact).expect("JSON is written as UTF-8");
+    Ok(PrettyPrinter::new()
+        .with_max_width(max_width)
+        .format(&compact))
+}
+
+/// Checks a document against a JSON Schema, itself read as JSON, and returns every place
+/// it doesn't match. See [`violations`] for the keywords that are checked.
+///
+/// ```
+/// use facet_cli::{Format, validate};
+///
+/// let schema = br#"{"type": "object", "properties": {"port": {"type": "integer", "format": "uint16"}}}"#;
+/// let violations = validate(b"port = 70000", Format::Toml, schema).unwrap();
+/// assert_eq!(violations[0].to_string(), "/port: 70000 doesn't fit in uint16");
+/// ```
+pub fn validate(input: &[u8], from: Format, schema: &[u8]) -> Result<Vec<Violation>, CliError> {
+    let schema = Value::from_json(schema).map_err(|e| CliError::Schema(e.to_string()))?;
+    violations(&read(input, from)?, &schema)
+}
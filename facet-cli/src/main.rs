@@ -0,0 +1,101 @@
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use facet::Facet;
+use facet_cli::Format;
+
+/// `facet-cli <pretty|convert|validate> <file> [--from <format>] [--to <format>]
+/// [--schema <file>] [--width <n>]`
+#[derive(Facet)]
+struct Args {
+    /// `pretty`, `convert` or `validate`
+    #[facet(positional)]
+    command: String,
+
+    /// The file to read
+    #[facet(positional)]
+    input: String,
+
+    /// The format of the input: `json`, `yaml`, `toml` or `msgpack`. By default, it's
+    /// told by the file's extension, or JSON when there's none.
+    #[facet(named, short = 'f')]
+    from: Option<String>,
+
+    /// The format `convert` writes
+    #[facet(named, short = 't', default = default_to())]
+    to: String,
+
+    /// The JSON Schema that `validate` checks the input against
+    #[facet(named, short = 's')]
+    schema: Option<String>,
+
+    /// The width `pretty` keeps lines to, where values allow
+    #[facet(named, short = 'w', default = 80)]
+    width: usize,
+}
+
+fn default_to() -> String {
+    "json".to_string()
+}
+
+fn main() -> ExitCode {
+    let args: Args = match facet_args::from_std_args() {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::from(2);
+        }
+    };
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn read_file(path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|e| format!("Can't read {path}: {e}"))
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let input = read_file(&args.input)?;
+    let from = match &args.from {
+        Some(from) => from.parse::<Format>().map_err(|e| e.to_string())?,
+        None => Format::from_path(&args.input).unwrap_or(Format::Json),
+    };
+    let output = match args.command.as_str() {
+        "pretty" => {
+            let mut pretty =
+                facet_cli::pretty(&input, from, args.width).map_err(|e| e.to_string())?;
+            pretty.push('\n');
+            pretty.into_bytes()
+        }
+        "convert" => {
+            let to = args.to.parse::<Format>().map_err(|e| e.to_string())?;
+            facet_cli::convert(&input, from, to).map_err(|e| e.to_string())?
+        }
+        "validate" => {
+            let Some(schema) = &args.schema else {
+                return Err("`validate` needs a schema, passed with `--schema`".to_string());
+            };
+            let schema = read_file(schema)?;
+            let violations =
+                facet_cli::validate(&input, from, &schema).map_err(|e| e.to_string())?;
+            if violations.is_empty() {
+                return Ok(());
+            }
+            let lines: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+            return Err(lines.join("\n"));
+        }
+        command => {
+            return Err(format!(
+                "Unknown command `{command}`, expected `pretty`, `convert` or `validate`"
+            ));
+        }
+    };
+    io::stdout()
+        .write_all(&output)
+        .map_err(|e| format!("Can't write the output: {e}"))
+}
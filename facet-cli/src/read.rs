@@ -0,0 +1,275 @@
+use facet_msgpack::{
+    MSGPACK_ARRAY16, MSGPACK_ARRAY32, MSGPACK_BIN8, MSGPACK_BIN16, MSGPACK_BIN32, MSGPACK_FALSE,
+    MSGPACK_FIXARRAY_MAX, MSGPACK_FIXARRAY_MIN, MSGPACK_FIXMAP_MAX, MSGPACK_FIXMAP_MIN,
+    MSGPACK_FIXSTR_MAX, MSGPACK_FIXSTR_MIN, MSGPACK_FLOAT32, MSGPACK_FLOAT64, MSGPACK_INT8,
+    MSGPACK_INT16, MSGPACK_INT32, MSGPACK_INT64, MSGPACK_MAP16, MSGPACK_MAP32, MSGPACK_NIL,
+    MSGPACK_POSFIXINT_MAX, MSGPACK_POSFIXINT_MIN, MSGPACK_STR8, MSGPACK_STR16, MSGPACK_STR32,
+    MSGPACK_TRUE, MSGPACK_UINT8, MSGPACK_UINT16, MSGPACK_UINT32, MSGPACK_UINT64,
+};
+use toml_edit::{ImDocument, InlineTable, Item, Table};
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::{CliError, Value};
+
+/// Integers are read as `U64` unless they're negative, like JSON's are
+fn int(n: i64) -> Value {
+    match u64::try_from(n) {
+        Ok(n) => Value::U64(n),
+        Err(_) => Value::I64(n),
+    }
+}
+
+fn utf8<'input>(input: &'input [u8], format: &str) -> Result<&'input str, CliError> {
+    core::str::from_utf8(input).map_err(|e| CliError::Deser(format!("{format} must be UTF-8: {e}")))
+}
+
+/// Reads a TOML document as an object. Dates and times are read as strings.
+pub(crate) fn from_toml(input: &[u8]) -> Result<Value, CliError> {
+    let document =
+        ImDocument::parse(utf8(input, "TOML")?).map_err(|e| CliError::Deser(e.to_string()))?;
+    Ok(toml_table(&document))
+}
+
+fn toml_table(table: &Table) -> Value {
+    Value::Object(
+        table
+            .iter()
+            .map(|(key, item)| (key.to_string(), toml_item(item)))
+            .collect(),
+    )
+}
+
+fn toml_item(item: &Item) -> Value {
+    match item {
+        Item::None => Value::Null,
+        Item::Value(value) => toml_value(value),
+        Item::Table(table) => toml_table(table),
+        Item::ArrayOfTables(tables) => Value::Array(tables.iter().map(toml_table).collect()),
+    }
+}
+
+fn toml_inline_table(table: &InlineTable) -> Value {
+    Value::Object(
+        table
+            .iter()
+            .map(|(key, value)| (key.to_string(), toml_value(value)))
+            .collect(),
+    )
+}
+
+fn toml_value(value: &toml_edit::Value) -> Value {
+    match value {
+        toml_edit::Value::String(s) => Value::String(s.value().clone()),
+        toml_edit::Value::Integer(n) => int(*n.value()),
+        toml_edit::Value::Float(n) => Value::F64(*n.value()),
+        toml_edit::Value::Boolean(b) => Value::Bool(*b.value()),
+        toml_edit::Value::Datetime(datetime) => Value::String(datetime.value().to_string()),
+        toml_edit::Value::Array(items) => Value::Array(items.iter().map(toml_value).collect()),
+        toml_edit::Value::InlineTable(table) => toml_inline_table(table),
+    }
+}
+
+/// Reads one YAML document; an empty input is `null`. Scalar map keys are read as their
+/// text, since objects only have string keys.
+pub(crate) fn from_yaml(input: &[u8]) -> Result<Value, CliError> {
+    let documents = YamlLoader::load_from_str(utf8(input, "YAML")?)
+        .map_err(|e| CliError::Deser(e.to_string()))?;
+    match documents.as_slice() {
+        [] => Ok(Value::Null),
+        [document] => yaml(document),
+        _ => Err(CliError::Deser(format!(
+            "The input holds {} YAML documents, expected one",
+            documents.len()
+        ))),
+    }
+}
+
+fn yaml(node: &Yaml) -> Result<Value, CliError> {
+    Ok(match node {
+        Yaml::Null => Value::Null,
+        Yaml::Boolean(b) => Value::Bool(*b),
+        Yaml::Integer(n) => int(*n),
+        Yaml::Real(text) => match node.as_f64() {
+            Some(n) => Value::F64(n),
+            None => return Err(CliError::Deser(format!("Invalid YAML number `{text}`"))),
+        },
+        Yaml::String(s) => Value::String(s.clone()),
+        Yaml::Array(items) => Value::Array(items.iter().map(yaml).collect::<Result<_, _>>()?),
+        Yaml::Hash(entries) => Value::Object(
+            entries
+                .iter()
+                .map(|(key, value)| Ok((yaml_key(key)?, yaml(value)?)))
+                .collect::<Result<_, CliError>>()?,
+        ),
+        Yaml::Alias(_) | Yaml::BadValue => {
+            return Err(CliError::Deser("YAML aliases aren't supported".to_string()));
+        }
+    })
+}
+
+fn yaml_key(key: &Yaml) -> Result<String, CliError> {
+    match key {
+        Yaml::String(s) | Yaml::Real(s) => Ok(s.clone()),
+        Yaml::Integer(n) => Ok(n.to_string()),
+        Yaml::Boolean(b) => Ok(b.to_string()),
+        Yaml::Null => Ok("null".to_string()),
+        _ => Err(CliError::Deser(
+            "YAML map keys must be scalars to be read as object keys".to_string(),
+        )),
+    }
+}
+
+/// Reads one MessagePack value. Binary data is read as an array of bytes, and scalar map
+/// keys as their text; extension types can't be read.
+pub(crate) fn from_msgpack(input: &[u8]) -> Result<Value, CliError> {
+    let mut reader = MsgPackReader { input, pos: 0 };
+    let value = reader.value()?;
+    if reader.pos != input.len() {
+        return Err(reader.error("unexpected data after the value"));
+    }
+    Ok(value)
+}
+
+struct MsgPackReader<'input> {
+    input: &'input [u8],
+    pos: usize,
+}
+
+impl<'input> MsgPackReader<'input> {
+    fn error(&self, message: &str) -> CliError {
+        CliError::Deser(format!(
+            "Invalid MessagePack at byte {}: {message}",
+            self.pos
+        ))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'input [u8], CliError> {
+        match self.input.get(self.pos..).and_then(|rest| rest.get(..len)) {
+            Some(bytes) => {
+                self.pos += len;
+                Ok(bytes)
+            }
+            None => Err(self.error("the input ends in the middle of a value")),
+        }
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], CliError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    /// Reads a length of `N` bytes
+    fn len<const N: usize>(&mut self) -> Result<usize, CliError> {
+        let bytes = self.take_array::<N>()?;
+        Ok(bytes.iter().fold(0, |len, &b| (len << 8) | b as usize))
+    }
+
+    fn value(&mut self) -> Result<Value, CliError> {
+        let marker = self.take(1)?[0];
+        Ok(match marker {
+            MSGPACK_POSFIXINT_MIN..=MSGPACK_POSFIXINT_MAX => Value::U64(marker as u64),
+            MSGPACK_FIXMAP_MIN..=MSGPACK_FIXMAP_MAX => self.map((marker & 0x0f) as usize)?,
+            MSGPACK_FIXARRAY_MIN..=MSGPACK_FIXARRAY_MAX => self.array((marker & 0x0f) as usize)?,
+            MSGPACK_FIXSTR_MIN..=MSGPACK_FIXSTR_MAX => self.str((marker & 0x1f) as usize)?,
+            MSGPACK_NIL => Value::Null,
+            MSGPACK_FALSE => Value::Bool(false),
+            MSGPACK_TRUE => Value::Bool(true),
+            MSGPACK_BIN8 | MSGPACK_BIN16 | MSGPACK_BIN32 => {
+                let len = match marker {
+                    MSGPACK_BIN8 => self.len::<1>()?,
+                    MSGPACK_BIN16 => self.len::<2>()?,
+                    _ => self.len::<4>()?,
+                };
+                let bytes = self.take(len)?;
+                Value::Array(bytes.iter().map(|&b| Value::U64(b as u64)).collect())
+            }
+            MSGPACK_FLOAT32 => Value::F64(f32::from_be_bytes(self.take_array()?) as f64),
+            MSGPACK_FLOAT64 => Value::F64(f64::from_be_bytes(self.take_array()?)),
+            MSGPACK_UINT8 => Value::U64(self.take(1)?[0] as u64),
+            MSGPACK_UINT16 => Value::U64(u16::from_be_bytes(self.take_array()?) as u64),
+            MSGPACK_UINT32 => Value::U64(u32::from_be_bytes(self.take_array()?) as u64),
+            MSGPACK_UINT64 => Value::U64(u64::from_be_bytes(self.take_array()?)),
+            MSGPACK_INT8 => int(i8::from_be_bytes(self.take_array()?) as i64),
+            MSGPACK_INT16 => int(i16::from_be_bytes(self.take_array()?) as i64),
+            MSGPACK_INT32 => int(i32::from_be_bytes(self.take_array()?) as i64),
+            MSGPACK_INT64 => int(i64::from_be_bytes(self.take_array()?)),
+            MSGPACK_STR8 => {
+                let len = self.len::<1>()?;
+                self.str(len)?
+            }
+            MSGPACK_STR16 => {
+                let len = self.len::<2>()?;
+                self.str(len)?
+            }
+            MSGPACK_STR32 => {
+                let len = self.len::<4>()?;
+                self.str(len)?
+            }
+            MSGPACK_ARRAY16 => {
+                let len = self.len::<2>()?;
+                self.array(len)?
+            }
+            MSGPACK_ARRAY32 => {
+                let len = self.len::<4>()?;
+                self.array(len)?
+            }
+            MSGPACK_MAP16 => {
+                let len = self.len::<2>()?;
+                self.map(len)?
+            }
+            MSGPACK_MAP32 => {
+                let len = self.len::<4>()?;
+                self.map(len)?
+            }
+            // Negative fixints
+            0xe0..=0xff => Value::I64(marker as i8 as i64),
+            _ => {
+                self.pos -= 1;
+                return Err(self.error(&format!(
+                    "marker 0x{marker:02x} (an extension type, or unused) can't be read"
+                )));
+            }
+        })
+    }
+
+    fn str(&mut self, len: usize) -> Result<Value, CliError> {
+        let start = self.pos;
+        let bytes = self.take(len)?;
+        match core::str::from_utf8(bytes) {
+            Ok(s) => Ok(Value::String(s.to_string())),
+            Err(_) => {
+                self.pos = start;
+                Err(self.error("the string isn't valid UTF-8"))
+            }
+        }
+    }
+
+    fn array(&mut self, len: usize) -> Result<Value, CliError> {
+        // Every item takes at least a byte, which bounds what a bogus length allocates
+        let mut items = Vec::with_capacity(len.min(self.input.len() - self.pos));
+        for _ in 0..len {
+            items.push(self.value()?);
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn map(&mut self, len: usize) -> Result<Value, CliError> {
+        let mut entries = Vec::with_capacity(len.min(self.input.len() - self.pos));
+        for _ in 0..len {
+            let start = self.pos;
+            let key = match self.value()? {
+                Value::String(key) => key,
+                Value::Bool(b) => b.to_string(),
+                Value::U64(n) => n.to_string(),
+                Value::I64(n) => n.to_string(),
+                Value::F64(n) => n.to_string(),
+                Value::Null => "null".to_string(),
+                _ => {
+                    self.pos = start;
+                    return Err(self.error("map keys must be scalars to be read as object keys"));
+                }
+            };
+            entries.push((key, self.value()?));
+        }
+        Ok(Value::Object(entries))
+    }
+}
@@ -0,0 +1,281 @@
+use core::cmp::Ordering;
+use core::fmt;
+
+use crate::{CliError, Value};
+
+/// A place where a document doesn't match its JSON Schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// Where the value is, as a JSON Pointer like `/tags/0`; empty for the whole document
+    pub path: String,
+    /// What's wrong with the value
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Checks `value` against a JSON Schema, like the ones facet-jsonschema generates, and
+/// returns every place it doesn't match.
+///
+/// The keywords checked are `type`, `format` for integers (`int8` to `int128` and `uint8` to
+/// `uint128`), `minimum`, `maximum`, `required`, `properties`, `items`, `minItems` and
+/// `maxItems`. Annotations like `description` and other formats are skipped. Any other
+/// keyword is an error, so that nothing the schema asks for goes unchecked.
+pub fn violations(value: &Value, schema: &Value) -> Result<Vec<Violation>, CliError> {
+    let mut checker = Checker {
+        path: String::new(),
+        violations: Vec::new(),
+    };
+    checker.check(value, schema)?;
+    Ok(checker.violations)
+}
+
+fn schema_error(message: impl Into<String>) -> CliError {
+    CliError::Schema(message.into())
+}
+
+struct Checker {
+    /// The JSON Pointer of the value being checked
+    path: String,
+    violations: Vec<Violation>,
+}
+
+impl Checker {
+    fn violation(&mut self, message: String) {
+        self.violations.push(Violation {
+            path: self.path.clone(),
+            message,
+        });
+    }
+
+    /// Checks a value inside the current one, at `segment`
+    fn check_at(&mut self, segment: &str, value: &Value, schema: &Value) -> Result<(), CliError> {
+        let len = self.path.len();
+        self.path.push('/');
+        self.path
+            .push_str(&segment.replace('~', "~0").replace('/', "~1"));
+        let result = self.check(value, schema);
+        self.path.truncate(len);
+        result
+    }
+
+    fn check(&mut self, value: &Value, schema: &Value) -> Result<(), CliError> {
+        let keywords = match schema {
+            Value::Bool(true) => return Ok(()),
+            Value::Bool(false) => {
+                self.violation("no value is allowed here".to_string());
+                return Ok(());
+            }
+            Value::Object(keywords) => keywords,
+            _ => return Err(schema_error("a schema must be an object or a boolean")),
+        };
+
+        // Other keywords would only repeat that the value is of the wrong type
+        if let Some((_, types)) = keywords.iter().find(|(keyword, _)| keyword == "type") {
+            if !self.check_type(value, types)? {
+                return Ok(());
+            }
+        }
+
+        for (keyword, argument) in keywords {
+            match keyword.as_str() {
+                "type" | "$schema" | "$id" | "$comment" | "title" | "description" | "default"
+                | "examples" | "deprecated" | "readOnly" | "writeOnly" => {}
+                "format" => self.check_format(value, argument)?,
+                "minimum" | "maximum" => {
+                    if number(argument).is_none() {
+                        return Err(schema_error(format!("`{keyword}` must be a number")));
+                    }
+                    let (wrong, word) = if keyword == "minimum" {
+                        (Ordering::Less, "less")
+                    } else {
+                        (Ordering::Greater, "greater")
+                    };
+                    if compare(value, argument) == Some(wrong) {
+                        self.violation(format!(
+                            "{} is {word} than the {keyword}, {}",
+                            number(value).unwrap_or_default(),
+                            number(argument).unwrap_or_default()
+                        ));
+                    }
+                }
+                "required" => {
+                    let Value::Array(names) = argument else {
+                        return Err(schema_error("`required` must be an array of strings"));
+                    };
+                    let Value::Object(entries) = value else {
+                        continue;
+                    };
+                    for name in names {
+                        let Value::String(name) = name else {
+                            return Err(schema_error("`required` must be an array of strings"));
+                        };
+                        if !entries.iter().any(|(key, _)| key == name) {
+                            self.violation(format!("missing required property `{name}`"));
+                        }
+                    }
+                }
+                "properties" => {
+                    let Value::Object(properties) = argument else {
+                        return Err(schema_error("`properties` must be an object"));
+                    };
+                    let Value::Object(entries) = value else {
+                        continue;
+                    };
+                    for (name, property) in properties {
+                        if let Some((_, entry)) = entries.iter().find(|(key, _)| key == name) {
+                            self.check_at(name, entry, property)?;
+                        }
+                    }
+                }
+                "items" => {
+                    let Value::Array(items) = value else {
+                        continue;
+                    };
+                    for (index, item) in items.iter().enumerate() {
+                        self.check_at(&index.to_string(), item, argument)?;
+                    }
+                }
+                "minItems" | "maxItems" => {
+                    let Value::U64(bound) = *argument else {
+                        return Err(schema_error(format!(
+                            "`{keyword}` must be a non-negative integer"
+                        )));
+                    };
+                    let Value::Array(items) = value else {
+                        continue;
+                    };
+                    let len = items.len() as u64;
+                    if keyword == "minItems" && len < bound {
+                        self.violation(format!("expected at least {bound} items, found {len}"));
+                    } else if keyword == "maxItems" && len > bound {
+                        self.violation(format!("expected at most {bound} items, found {len}"));
+                    }
+                }
+                _ => {
+                    return Err(schema_error(format!(
+                        "the `{keyword}` keyword isn't supported"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether `value` has one of the `types`
+    fn check_type(&mut self, value: &Value, types: &Value) -> Result<bool, CliError> {
+        let types: Vec<&Value> = match types {
+            Value::String(_) => vec![types],
+            Value::Array(names) => names.iter().collect(),
+            _ => {
+                return Err(schema_error(
+                    "`type` must be a string or an array of strings",
+                ));
+            }
+        };
+        let mut expected = Vec::new();
+        for name in types {
+            let Value::String(name) = name else {
+                return Err(schema_error(
+                    "`type` must be a string or an array of strings",
+                ));
+            };
+            let matches = match name.as_str() {
+                // Numbers without a fractional part are integers too
+                "integer" => {
+                    value.kind() == "integer" || matches!(value, Value::F64(n) if n.fract() == 0.0)
+                }
+                "number" => number(value).is_some(),
+                "null" | "boolean" | "string" | "array" | "object" => value.kind() == name,
+                _ => return Err(schema_error(format!("unknown type `{name}`"))),
+            };
+            if matches {
+                return Ok(true);
+            }
+            expected.push(name.as_str());
+        }
+        self.violation(format!(
+            "expected {}, found {}",
+            expected.join(" or "),
+            value.kind()
+        ));
+        Ok(false)
+    }
+
+    fn check_format(&mut self, value: &Value, format: &Value) -> Result<(), CliError> {
+        let Value::String(format) = format else {
+            return Err(schema_error("`format` must be a string"));
+        };
+        let (signed, bits) = match format.strip_prefix("u") {
+            Some(rest) => (false, rest),
+            None => (true, format.as_str()),
+        };
+        // Other formats are annotations
+        let Some(bits) = bits
+            .strip_prefix("int")
+            .and_then(|bits| bits.parse::<u32>().ok())
+            .filter(|bits| matches!(bits, 8 | 16 | 32 | 64 | 128))
+        else {
+            return Ok(());
+        };
+        let fits = match *value {
+            Value::U64(n) => fits_unsigned(n as u128, signed, bits),
+            Value::U128(n) => fits_unsigned(n, signed, bits),
+            Value::I64(n) => fits_negative(n as i128, signed, bits),
+            Value::I128(n) => fits_negative(n, signed, bits),
+            // Not an integer, which `type` reports
+            _ => true,
+        };
+        if !fits {
+            self.violation(format!(
+                "{} doesn't fit in {format}",
+                number(value).unwrap_or_default()
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn fits_unsigned(n: u128, signed: bool, bits: u32) -> bool {
+    let bits = if signed { bits - 1 } else { bits };
+    bits == 128 || n >> bits == 0
+}
+
+fn fits_negative(n: i128, signed: bool, bits: u32) -> bool {
+    signed && (bits == 128 || n >= -(1 << (bits - 1)))
+}
+
+/// A number's value, for messages and comparisons that don't need to be exact
+fn number(value: &Value) -> Option<f64> {
+    match *value {
+        Value::U64(n) => Some(n as f64),
+        Value::I64(n) => Some(n as f64),
+        Value::U128(n) => Some(n as f64),
+        Value::I128(n) => Some(n as f64),
+        Value::F64(n) => Some(n),
+        _ => None,
+    }
+}
+
+/// Compares two numbers, exactly when they're both integers
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    let integer = |value: &Value| match *value {
+        Value::U64(n) => Some(n as i128),
+        Value::I64(n) => Some(n as i128),
+        Value::U128(n) => i128::try_from(n).ok(),
+        Value::I128(n) => Some(n),
+        _ => None,
+    };
+    match (integer(a), integer(b)) {
+        (Some(a), Some(b)) => Some(a.cmp(&b)),
+        _ => number(a)?.partial_cmp(&number(b)?),
+    }
+}
@@ -0,0 +1,140 @@
+use facet_deserialize::{Event, Scalar};
+use facet_json::JsonEvents;
+use facet_serialize::Serializer;
+
+use crate::CliError;
+
+/// A document read without a Rust type: the values that JSON, YAML, TOML and MessagePack
+/// have in common.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// `null`, or a format's equivalent
+    Null,
+    /// A boolean
+    Bool(bool),
+    /// An integer that fits in `u64`
+    U64(u64),
+    /// A negative integer that fits in `i64`
+    I64(i64),
+    /// An integer too large for `u64`
+    U128(u128),
+    /// A negative integer too large for `i64`
+    I128(i128),
+    /// A floating point number
+    F64(f64),
+    /// A string
+    String(String),
+    /// A list of values
+    Array(Vec<Value>),
+    /// Entries with string keys, in the order they were read
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Reads one JSON value through [`JsonEvents`].
+    pub fn from_json(input: &[u8]) -> Result<Value, CliError> {
+        // The open arrays and objects, with the key of the entry being read in objects
+        let mut open: Vec<(Value, Option<String>)> = Vec::new();
+        for event in JsonEvents::new(input) {
+            let event = event.map_err(|e| CliError::Deser(e.to_string()))?.node;
+            let value = match event {
+                Event::Scalar(scalar) => Value::from(scalar),
+                Event::ListStarted => {
+                    open.push((Value::Array(Vec::new()), None));
+                    continue;
+                }
+                Event::ObjectStarted => {
+                    open.push((Value::Object(Vec::new()), None));
+                    continue;
+                }
+                Event::Key(key) => {
+                    if let Some((_, pending)) = open.last_mut() {
+                        *pending = Some(key.into_owned());
+                    }
+                    continue;
+                }
+                Event::ListEnded | Event::ObjectEnded => match open.pop() {
+                    Some((value, _)) => value,
+                    None => break,
+                },
+            };
+            match open.last_mut() {
+                Some((Value::Array(items), _)) => items.push(value),
+                Some((Value::Object(entries), key)) => {
+                    entries.push((key.take().unwrap_or_default(), value))
+                }
+                Some(_) => unreachable!("only arrays and objects are open"),
+                None => return Ok(value),
+            }
+        }
+        Err(CliError::Deser(
+            "The input ended before a value".to_string(),
+        ))
+    }
+
+    /// The name of the kind of value, as JSON Schema's `type` has it: `"null"`, `"boolean"`,
+    /// `"integer"`, `"number"`, `"string"`, `"array"` or `"object"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::U64(_) | Value::I64(_) | Value::U128(_) | Value::I128(_) => "integer",
+            Value::F64(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    /// Writes the value to `serializer`: arrays as arrays and objects as maps, with their
+    /// lengths known up front, and `null` as `None` is.
+    pub fn serialize<'shape, S: Serializer<'shape>>(
+        &self,
+        serializer: &mut S,
+    ) -> Result<(), S::Error> {
+        match self {
+            Value::Null => serializer.serialize_none(),
+            Value::Bool(b) => serializer.serialize_bool(*b),
+            Value::U64(n) => serializer.serialize_u64(*n),
+            Value::I64(n) => serializer.serialize_i64(*n),
+            Value::U128(n) => serializer.serialize_u128(*n),
+            Value::I128(n) => serializer.serialize_i128(*n),
+            Value::F64(n) => serializer.serialize_f64(*n),
+            Value::String(s) => serializer.serialize_str(s),
+            Value::Array(items) => {
+                serializer.start_array(Some(items.len()))?;
+                for item in items {
+                    item.serialize(serializer)?;
+                }
+                serializer.end_array()
+            }
+            Value::Object(entries) => {
+                serializer.start_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    serializer.begin_map_key()?;
+                    serializer.serialize_str(key)?;
+                    serializer.end_map_key()?;
+                    serializer.begin_map_value()?;
+                    value.serialize(serializer)?;
+                    serializer.end_map_value()?;
+                }
+                serializer.end_map()
+            }
+        }
+    }
+}
+
+impl From<Scalar<'_>> for Value {
+    fn from(scalar: Scalar<'_>) -> Self {
+        match scalar {
+            Scalar::String(s) => Value::String(s.into_owned()),
+            Scalar::U64(n) => Value::U64(n),
+            Scalar::I64(n) => Value::I64(n),
+            Scalar::F64(n) => Value::F64(n),
+            Scalar::U128(n) => Value::U128(n),
+            Scalar::I128(n) => Value::I128(n),
+            Scalar::Bool(b) => Value::Bool(b),
+            Scalar::Null => Value::Null,
+        }
+    }
+}
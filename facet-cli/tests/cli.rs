@@ -0,0 +1,242 @@
+use facet::Facet;
+use facet_cli::{CliError, Format, UnknownFormat, Value, convert, pretty, read, validate};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Record {
+    id: u64,
+    ok: bool,
+    tags: Vec<String>,
+}
+
+fn record() -> Value {
+    Value::Object(vec![
+        ("id".to_string(), Value::U64(7)),
+        ("ok".to_string(), Value::Bool(true)),
+        (
+            "tags".to_string(),
+            Value::Array(vec![
+                Value::String("a".to_string()),
+                Value::String("b".to_string()),
+            ]),
+        ),
+    ])
+}
+
+#[test]
+fn test_pretty() {
+    let input = br#"{"name":"facet","versions":[1,2,3],"nested":{"a":true,"b":null}}"#;
+    assert_eq!(
+        pretty(input, Format::Json, 80).unwrap(),
+        r#"{"name": "facet", "versions": [1, 2, 3], "nested": {"a": true, "b": null}}"#
+    );
+    assert_eq!(
+        pretty(input, Format::Json, 40).unwrap(),
+        r#"{
+  "name": "facet",
+  "versions": [1, 2, 3],
+  "nested": {"a": true, "b": null}
+}"#
+    );
+}
+
+#[test]
+fn test_convert() {
+    let input = br#" { "id": 7, "ok": true, "tags": ["a", "b"] } "#;
+    assert_eq!(
+        convert(input, Format::Json, Format::Json).unwrap(),
+        br#"{"id":7,"ok":true,"tags":["a","b"]}"#
+    );
+    assert_eq!(
+        convert(input, Format::Json, Format::MsgPack).unwrap(),
+        [
+            0x83, // map of 3
+            0xa2, b'i', b'd', 0x07, // id
+            0xa2, b'o', b'k', 0xc3, // ok
+            0xa4, b't', b'a', b'g', b's', 0x92, 0xa1, b'a', 0xa1, b'b', // tags
+        ]
+    );
+    // TOML is read back, since how it's laid out is up to the TOML serializer
+    let toml = String::from_utf8(convert(input, Format::Json, Format::Toml).unwrap()).unwrap();
+    assert_eq!(
+        facet_toml::from_str::<Record>(&toml).unwrap(),
+        Record {
+            id: 7,
+            ok: true,
+            tags: vec!["a".to_string(), "b".to_string()],
+        }
+    );
+}
+
+#[test]
+fn test_read_every_format() {
+    let json = br#"{"id": 7, "ok": true, "tags": ["a", "b"]}"#;
+    assert_eq!(read(json, Format::Json).unwrap(), record());
+    let yaml = b"id: 7\nok: true\ntags:\n  - a\n  - b\n";
+    assert_eq!(read(yaml, Format::Yaml).unwrap(), record());
+    let toml = b"id = 7\nok = true\ntags = [\"a\", \"b\"]\n";
+    assert_eq!(read(toml, Format::Toml).unwrap(), record());
+    let msgpack = convert(json, Format::Json, Format::MsgPack).unwrap();
+    assert_eq!(read(&msgpack, Format::MsgPack).unwrap(), record());
+
+    // Every format reads back what it writes
+    for format in [Format::Json, Format::Yaml, Format::Toml, Format::MsgPack] {
+        let written = convert(json, Format::Json, format).unwrap();
+        assert_eq!(read(&written, format).unwrap(), record(), "{format:?}");
+    }
+}
+
+#[test]
+fn test_read_scalars() {
+    let yaml = b"small: -3\nratio: 1.5\nnothing: ~\n1: one\n";
+    assert_eq!(
+        read(yaml, Format::Yaml).unwrap(),
+        Value::Object(vec![
+            ("small".to_string(), Value::I64(-3)),
+            ("ratio".to_string(), Value::F64(1.5)),
+            ("nothing".to_string(), Value::Null),
+            ("1".to_string(), Value::String("one".to_string())),
+        ])
+    );
+    assert_eq!(read(b"", Format::Yaml).unwrap(), Value::Null);
+
+    let toml = b"when = 1979-05-27\n[server]\nport = 8080\n[[peers]]\nname = \"a\"\n";
+    assert_eq!(
+        read(toml, Format::Toml).unwrap(),
+        Value::Object(vec![
+            ("when".to_string(), Value::String("1979-05-27".to_string())),
+            (
+                "server".to_string(),
+                Value::Object(vec![("port".to_string(), Value::U64(8080))])
+            ),
+            (
+                "peers".to_string(),
+                Value::Array(vec![Value::Object(vec![(
+                    "name".to_string(),
+                    Value::String("a".to_string())
+                )])])
+            ),
+        ])
+    );
+
+    let msgpack = [
+        0x94, // array of 4
+        0xff, // -1
+        0xcb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0, // 1.5
+        0xc4, 0x02, 0x01, 0x02, // bytes
+        0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // u64::MAX
+    ];
+    assert_eq!(
+        read(&msgpack, Format::MsgPack).unwrap(),
+        Value::Array(vec![
+            Value::I64(-1),
+            Value::F64(1.5),
+            Value::Array(vec![Value::U64(1), Value::U64(2)]),
+            Value::U64(u64::MAX),
+        ])
+    );
+}
+
+#[test]
+fn test_validate() {
+    let schema = facet_jsonschema::to_string::<Record>();
+    let valid = b"id: 7\nok: true\ntags: [a, b]\n";
+    assert_eq!(
+        validate(valid, Format::Yaml, schema.as_bytes()).unwrap(),
+        []
+    );
+
+    let invalid = br#"{"id": -1, "tags": ["a", 2]}"#;
+    let violations: Vec<String> = validate(invalid, Format::Json, schema.as_bytes())
+        .unwrap()
+        .iter()
+        .map(|v| v.to_string())
+        .collect();
+    assert_eq!(
+        violations,
+        [
+            "missing required property `ok`",
+            "/id: -1 doesn't fit in uint64",
+            "/id: -1 is less than the minimum, 0",
+            "/tags/1: expected string, found integer",
+        ]
+    );
+
+    let schema = br#"{"type": ["string", "null"]}"#;
+    assert_eq!(validate(b"null", Format::Json, schema).unwrap(), []);
+    let violations = validate(b"[]", Format::Json, schema).unwrap();
+    assert_eq!(
+        violations[0].to_string(),
+        "expected string or null, found array"
+    );
+
+    let schema =
+        br#"{"type": "array", "minItems": 2, "maxItems": 2, "items": {"type": "integer"}}"#;
+    assert_eq!(validate(b"[1, 2.0]", Format::Json, schema).unwrap(), []);
+    let violations = validate(b"[1]", Format::Json, schema).unwrap();
+    assert_eq!(
+        violations[0].to_string(),
+        "expected at least 2 items, found 1"
+    );
+
+    // Checks that aren't supported are errors, not passes
+    assert!(matches!(
+        validate(b"1", Format::Json, br#"{"oneOf": [{"type": "string"}]}"#),
+        Err(CliError::Schema(_))
+    ));
+    assert!(matches!(
+        validate(b"1", Format::Json, br#"{"type": "unknown"}"#),
+        Err(CliError::Schema(_))
+    ));
+}
+
+#[test]
+fn test_errors() {
+    assert!(matches!(
+        convert(br#"{"id": }"#, Format::Json, Format::Yaml),
+        Err(CliError::Deser(_))
+    ));
+    assert!(matches!(
+        pretty(b"[1, 2", Format::Json, 80),
+        Err(CliError::Deser(_))
+    ));
+    assert!(matches!(
+        read(b"a = ", Format::Toml),
+        Err(CliError::Deser(_))
+    ));
+    assert!(matches!(
+        read(b"a: [", Format::Yaml),
+        Err(CliError::Deser(_))
+    ));
+    assert!(matches!(
+        read(b"---\na: 1\n---\nb: 2\n", Format::Yaml),
+        Err(CliError::Deser(_))
+    ));
+    assert_eq!(
+        read(&[0x92, 0x01], Format::MsgPack),
+        Err(CliError::Deser(
+            "Invalid MessagePack at byte 2: the input ends in the middle of a value".to_string()
+        ))
+    );
+    assert!(matches!(
+        read(&[0x01, 0x02], Format::MsgPack),
+        Err(CliError::Deser(_))
+    ));
+    // TOML integers are `i64`
+    assert!(matches!(
+        convert(
+            br#"{"big": 18446744073709551615}"#,
+            Format::Json,
+            Format::Toml
+        ),
+        Err(CliError::Ser(_))
+    ));
+
+    assert_eq!("yaml".parse(), Ok(Format::Yaml));
+    assert_eq!(
+        "xml".parse::<Format>(),
+        Err(UnknownFormat("xml".to_string()))
+    );
+    assert_eq!(Format::from_path("data.msgpack"), Some(Format::MsgPack));
+    assert_eq!(Format::from_path("Makefile"), None);
+}
@@ -0,0 +1,243 @@
+use facet_core::{Def, FieldFlags, StructType, Type, UserType};
+use facet_reflect::{Partial, ReflectError};
+
+use crate::value::{Tree, Value, push_key};
+use crate::{ConfigError, Source};
+
+/// Something a layer sets, which can be applied onto a [`Partial`].
+trait Node {
+    /// The layer that set this, or the first that set something under it. `parent` is the
+    /// layer that set the enclosing value, for values in an array or inline table.
+    fn source(&self, parent: Option<usize>) -> Option<usize>;
+
+    fn apply(
+        &self,
+        applier: &mut Applier<'_>,
+        wip: &mut Partial<'_, '_>,
+        parent: Option<usize>,
+    ) -> Result<(), ConfigError>;
+}
+
+impl Node for Tree {
+    fn source(&self, _parent: Option<usize>) -> Option<usize> {
+        self.first_source()
+    }
+
+    fn apply(
+        &self,
+        applier: &mut Applier<'_>,
+        wip: &mut Partial<'_, '_>,
+        _parent: Option<usize>,
+    ) -> Result<(), ConfigError> {
+        match self {
+            Tree::Table(entries) => applier.table(wip, entries, None),
+            Tree::Leaf { value, source } => applier.value(wip, value, Some(*source)),
+        }
+    }
+}
+
+impl Node for Value {
+    fn source(&self, parent: Option<usize>) -> Option<usize> {
+        parent
+    }
+
+    fn apply(
+        &self,
+        applier: &mut Applier<'_>,
+        wip: &mut Partial<'_, '_>,
+        parent: Option<usize>,
+    ) -> Result<(), ConfigError> {
+        applier.value(wip, self, parent)
+    }
+}
+
+/// Sets what the merged layers hold onto a value, field by field.
+pub(crate) struct Applier<'a> {
+    sources: &'a [Source],
+    /// The path of the value being set, like `servers[0].port`
+    path: String,
+}
+
+impl<'a> Applier<'a> {
+    pub(crate) fn new(sources: &'a [Source]) -> Self {
+        Applier {
+            sources,
+            path: String::new(),
+        }
+    }
+
+    pub(crate) fn apply(
+        &mut self,
+        wip: &mut Partial<'_, '_>,
+        tree: &Tree,
+    ) -> Result<(), ConfigError> {
+        tree.apply(self, wip, None)
+    }
+
+    fn table<N: Node>(
+        &mut self,
+        wip: &mut Partial<'_, '_>,
+        entries: &[(String, N)],
+        source: Option<usize>,
+    ) -> Result<(), ConfigError> {
+        let shape = wip.shape();
+        if let Def::Option(_) = shape.def {
+            self.reflect(wip.begin_some(), source)?;
+            self.table(wip, entries, source)?;
+            return self.reflect(wip.end(), source);
+        }
+
+        match (shape.def, shape.ty) {
+            (Def::Map(_), _) => {
+                self.reflect(wip.begin_map(), source)?;
+                for (key, node) in entries {
+                    let source = node.source(source);
+                    let len = self.path.len();
+                    push_key(&mut self.path, key);
+                    self.reflect(wip.begin_key(), source)?;
+                    self.reflect(wip.parse_from_str(key), source)?;
+                    self.reflect(wip.end(), source)?;
+                    self.reflect(wip.begin_value(), source)?;
+                    node.apply(self, wip, source)?;
+                    self.reflect(wip.end(), source)?;
+                    self.path.truncate(len);
+                }
+                Ok(())
+            }
+            (_, Type::User(UserType::Struct(sd))) => {
+                for (key, node) in entries {
+                    let source = node.source(source);
+                    let len = self.path.len();
+                    push_key(&mut self.path, key);
+                    let Some(index) = sd
+                        .fields
+                        .iter()
+                        .position(|field| field.name == key.as_str())
+                    else {
+                        return Err(ConfigError::UnknownField {
+                            path: self.path.clone(),
+                            source: self.source(source),
+                        });
+                    };
+                    self.reflect(wip.begin_nth_field(index), source)?;
+                    node.apply(self, wip, source)?;
+                    self.reflect(wip.end(), source)?;
+                    self.path.truncate(len);
+                }
+                self.finish_struct(wip, sd)
+            }
+            _ => Err(self.invalid(source, format!("expected {shape}, found a table"))),
+        }
+    }
+
+    fn value(
+        &mut self,
+        wip: &mut Partial<'_, '_>,
+        value: &Value,
+        source: Option<usize>,
+    ) -> Result<(), ConfigError> {
+        let shape = wip.shape();
+        match value {
+            Value::Table(entries) => return self.table(wip, entries, source),
+            Value::Null if matches!(shape.def, Def::Option(_)) => {
+                return self.reflect(wip.set_default(), source);
+            }
+            Value::Null => {
+                return Err(self.invalid(source, format!("expected {shape}, found null")));
+            }
+            _ => {}
+        }
+        if let Def::Option(_) = shape.def {
+            self.reflect(wip.begin_some(), source)?;
+            self.value(wip, value, source)?;
+            return self.reflect(wip.end(), source);
+        }
+
+        match value {
+            Value::Array(items) => {
+                let Def::List(_) = shape.def else {
+                    return Err(self.invalid(source, format!("expected {shape}, found an array")));
+                };
+                self.reflect(wip.begin_list(), source)?;
+                for (index, item) in items.iter().enumerate() {
+                    let len = self.path.len();
+                    self.path.push_str(&format!("[{index}]"));
+                    self.reflect(wip.begin_list_item(), source)?;
+                    self.value(wip, item, source)?;
+                    self.reflect(wip.end(), source)?;
+                    self.path.truncate(len);
+                }
+                Ok(())
+            }
+            Value::Scalar(text) => {
+                let result = match shape.ty {
+                    Type::User(UserType::Enum(_)) => wip.select_variant_named(text).map(|_| ()),
+                    _ => wip.parse_from_str(text).map(|_| ()),
+                };
+                result.map_err(|_| self.invalid(source, format!("{text:?} isn't a valid {shape}")))
+            }
+            Value::Table(_) | Value::Null => unreachable!(),
+        }
+    }
+
+    /// Sets the fields of a struct that no layer set, or fails if one has no default.
+    /// Structs that no layer set anything in are built from their own defaults the same way.
+    fn finish_struct<'shape>(
+        &mut self,
+        wip: &mut Partial<'_, 'shape>,
+        sd: StructType<'shape>,
+    ) -> Result<(), ConfigError> {
+        if wip.shape().has_default_attr() {
+            return self.reflect(wip.fill_unset_fields_from_default(), None);
+        }
+
+        for (index, field) in sd.fields.iter().enumerate() {
+            if self.reflect_value(wip.is_field_set(index), None)? {
+                continue;
+            }
+            let len = self.path.len();
+            push_key(&mut self.path, field.name);
+            self.reflect(wip.begin_nth_field(index), None)?;
+            if field.flags.contains(FieldFlags::DEFAULT) {
+                self.reflect(wip.set_default_of_field(field), None)?;
+            } else if matches!(field.shape().def, Def::Option(_)) || field.shape().is_unit() {
+                self.reflect(wip.set_default(), None)?;
+            } else if let Type::User(UserType::Struct(_)) = field.shape().ty {
+                self.table::<Value>(wip, &[], None)?;
+            } else {
+                return Err(ConfigError::Missing(self.path.clone()));
+            }
+            self.reflect(wip.end(), None)?;
+            self.path.truncate(len);
+        }
+        Ok(())
+    }
+
+    fn source(&self, source: Option<usize>) -> Option<Source> {
+        source.map(|index| self.sources[index].clone())
+    }
+
+    fn invalid(&self, source: Option<usize>, message: String) -> ConfigError {
+        ConfigError::Invalid {
+            path: self.path.clone(),
+            source: self.source(source),
+            message,
+        }
+    }
+
+    fn reflect<T>(
+        &self,
+        result: Result<T, ReflectError<'_>>,
+        source: Option<usize>,
+    ) -> Result<(), ConfigError> {
+        self.reflect_value(result, source).map(|_| ())
+    }
+
+    fn reflect_value<T>(
+        &self,
+        result: Result<T, ReflectError<'_>>,
+        source: Option<usize>,
+    ) -> Result<T, ConfigError> {
+        result.map_err(|e| self.invalid(source, e.to_string()))
+    }
+}
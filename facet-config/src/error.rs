@@ -0,0 +1,94 @@
+use core::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::Source;
+
+/// Why a configuration couldn't be loaded.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// A config file couldn't be read.
+    Io {
+        /// The file
+        path: PathBuf,
+        /// What went wrong
+        error: io::Error,
+    },
+    /// The format of a config file can't be told from its extension.
+    UnknownFormat(PathBuf),
+    /// A config file isn't valid in its format.
+    Parse {
+        /// The file
+        source: Source,
+        /// What is wrong with it
+        message: String,
+    },
+    /// The command-line flags aren't valid.
+    Args(String),
+    /// A layer sets a field that the type doesn't have.
+    UnknownField {
+        /// The path of the field, like `server.port`
+        path: String,
+        /// The layer that sets it
+        source: Option<Source>,
+    },
+    /// No layer sets a field that has no default.
+    Missing(String),
+    /// A layer sets a field to something it can't hold.
+    Invalid {
+        /// The path of the field, like `server.port`
+        path: String,
+        /// The layer that sets it
+        source: Option<Source>,
+        /// What is wrong with it
+        message: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, error } => {
+                write!(f, "Couldn't read {}: {error}", path.display())
+            }
+            ConfigError::UnknownFormat(path) => write!(
+                f,
+                "Unknown config file format for {}, expected .toml, .json, .yaml or .yml",
+                path.display()
+            ),
+            ConfigError::Parse { source, message } => {
+                write!(f, "Couldn't parse {source}: {message}")
+            }
+            ConfigError::Args(message) => write!(f, "Invalid arguments: {message}"),
+            ConfigError::UnknownField { path, source } => {
+                write!(f, "Unknown field {path}")?;
+                if let Some(source) = source {
+                    write!(f, ", set by {source}")?;
+                }
+                Ok(())
+            }
+            ConfigError::Missing(path) => write!(f, "Missing field {path}"),
+            ConfigError::Invalid {
+                path,
+                source,
+                message,
+            } => {
+                write!(f, "Invalid value for {path}")?;
+                if let Some(source) = source {
+                    write!(f, ", set by {source}")?;
+                }
+                write!(f, ": {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
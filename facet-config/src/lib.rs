@@ -0,0 +1,345 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use core::fmt;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use facet_core::Facet;
+use facet_reflect::Partial;
+
+mod apply;
+use apply::Applier;
+
+mod error;
+pub use error::*;
+
+mod value;
+use value::{Tree, Value};
+
+/// The format of a config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileFormat {
+    /// TOML, with the `toml` feature
+    Toml,
+    /// JSON, with the `json` feature
+    Json,
+    /// YAML, with the `yaml` feature
+    Yaml,
+}
+
+impl FileFormat {
+    /// The format of a file, from its extension: `.toml`, `.json`, `.yaml` or `.yml`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(FileFormat::Toml),
+            "json" => Some(FileFormat::Json),
+            "yaml" | "yml" => Some(FileFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileFormat::Toml => write!(f, "TOML"),
+            FileFormat::Json => write!(f, "JSON"),
+            FileFormat::Yaml => write!(f, "YAML"),
+        }
+    }
+}
+
+/// The layer a value came from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Source {
+    /// A config file, or text given with [`ConfigBuilder::text`] under this name
+    File(PathBuf),
+    /// An environment variable, by name
+    Env(String),
+    /// A command-line flag, like `--server.port`
+    Arg(String),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Env(name) => write!(f, "environment variable {name}"),
+            Source::Arg(flag) => write!(f, "flag {flag}"),
+        }
+    }
+}
+
+/// A layer, read when the configuration is built.
+#[derive(Debug)]
+enum Layer {
+    File {
+        path: PathBuf,
+        required: bool,
+    },
+    Text {
+        name: PathBuf,
+        format: FileFormat,
+        text: String,
+    },
+    Env {
+        prefix: String,
+        /// Read from the process's environment if `None`
+        vars: Option<Vec<(String, String)>>,
+    },
+    Args(Vec<String>),
+}
+
+impl Layer {
+    /// What the layer sets, in order, each with where it came from.
+    fn read(self) -> Result<Vec<(Source, Value)>, ConfigError> {
+        match self {
+            Layer::File { path, required } => {
+                let format =
+                    FileFormat::from_path(&path).ok_or(ConfigError::UnknownFormat(path.clone()))?;
+                let text = match std::fs::read_to_string(&path) {
+                    Ok(text) => text,
+                    Err(e) if !required && e.kind() == io::ErrorKind::NotFound => {
+                        return Ok(Vec::new());
+                    }
+                    Err(error) => return Err(ConfigError::Io { path, error }),
+                };
+                Layer::Text {
+                    name: path,
+                    format,
+                    text,
+                }
+                .read()
+            }
+            Layer::Text { name, format, text } => {
+                let source = Source::File(name);
+                match value::parse(format, &text) {
+                    Ok(value) => Ok(vec![(source, value)]),
+                    Err(message) => Err(ConfigError::Parse { source, message }),
+                }
+            }
+            Layer::Env { prefix, vars } => {
+                let mut vars = vars.unwrap_or_else(|| {
+                    std::env::vars_os()
+                        .filter_map(|(name, value)| {
+                            Some((name.into_string().ok()?, value.into_string().ok()?))
+                        })
+                        .collect()
+                });
+                // The environment has no order, so variables that set the same field always
+                // apply in the same one
+                vars.sort();
+                Ok(vars
+                    .into_iter()
+                    .filter_map(|(name, v)| {
+                        let value = value::env(&prefix, &name, &v)?;
+                        Some((Source::Env(name), value))
+                    })
+                    .collect())
+            }
+            Layer::Args(args) => Ok(value::args(args)
+                .map_err(ConfigError::Args)?
+                .into_iter()
+                .map(|(flag, value)| (Source::Arg(flag), value))
+                .collect()),
+        }
+    }
+}
+
+/// Builds a configuration from layers, each of which overrides the ones added before it.
+///
+/// Tables are merged field by field, so a layer only replaces the fields it sets. Anything
+/// else, like a list, is replaced as a whole.
+#[derive(Debug)]
+pub struct ConfigBuilder<T> {
+    defaults: Option<T>,
+    layers: Vec<Layer>,
+}
+
+impl<T> Default for ConfigBuilder<T> {
+    fn default() -> Self {
+        ConfigBuilder {
+            defaults: None,
+            layers: Vec::new(),
+        }
+    }
+}
+
+impl<T> ConfigBuilder<T> {
+    /// A builder with no layer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts from `defaults`, which the layers override.
+    ///
+    /// Without defaults, the fields that no layer sets get their `#[facet(default)]`, or
+    /// `None` for options, and the configuration fails to build if one has neither.
+    pub fn defaults(mut self, defaults: T) -> Self {
+        self.defaults = Some(defaults);
+        self
+    }
+
+    /// Adds a config file, which has to exist, in the format its extension says.
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.layers.push(Layer::File {
+            path: path.into(),
+            required: true,
+        });
+        self
+    }
+
+    /// Adds a config file, if it exists, in the format its extension says.
+    pub fn optional_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.layers.push(Layer::File {
+            path: path.into(),
+            required: false,
+        });
+        self
+    }
+
+    /// Adds the contents of a config file, in `format`. `name` is what the values it sets
+    /// are reported to come from.
+    pub fn text(
+        mut self,
+        format: FileFormat,
+        name: impl Into<PathBuf>,
+        text: impl Into<String>,
+    ) -> Self {
+        self.layers.push(Layer::Text {
+            name: name.into(),
+            format,
+            text: text.into(),
+        });
+        self
+    }
+
+    /// Adds the environment variables that start with `prefix`, as they are when the
+    /// configuration is built.
+    ///
+    /// The rest of the name is the path of the field, lowercased, with `__` between nested
+    /// fields: with the prefix `APP_`, `APP_SERVER__PORT` sets `server.port`.
+    pub fn env(mut self, prefix: impl Into<String>) -> Self {
+        self.layers.push(Layer::Env {
+            prefix: prefix.into(),
+            vars: None,
+        });
+        self
+    }
+
+    /// Adds the variables in `vars` that start with `prefix`, like [`ConfigBuilder::env`]
+    /// does with the environment.
+    pub fn env_vars<K, V>(
+        mut self,
+        prefix: impl Into<String>,
+        vars: impl IntoIterator<Item = (K, V)>,
+    ) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.layers.push(Layer::Env {
+            prefix: prefix.into(),
+            vars: Some(
+                vars.into_iter()
+                    .map(|(name, value)| (name.into(), value.into()))
+                    .collect(),
+            ),
+        });
+        self
+    }
+
+    /// Adds command-line flags, without the program name.
+    ///
+    /// Each flag is the path of a field: `--server.port=8080` and `--server.port 8080` set
+    /// `server.port`, and a flag with no value, like `--verbose`, sets its field to `true`.
+    /// Dashes in names stand for underscores, so `--log-level` sets `log_level`.
+    pub fn args<A: Into<String>>(mut self, args: impl IntoIterator<Item = A>) -> Self {
+        self.layers
+            .push(Layer::Args(args.into_iter().map(Into::into).collect()));
+        self
+    }
+}
+
+impl<T> ConfigBuilder<T>
+where
+    T: for<'facet> Facet<'facet>,
+{
+    /// Reads every layer, in the order they were added, and builds the configuration.
+    pub fn build(self) -> Result<Config<T>, ConfigError> {
+        let mut sources = Vec::new();
+        let mut tree = Tree::Table(Vec::new());
+        for layer in self.layers {
+            for (source, value) in layer.read()? {
+                tree.merge(value, sources.len());
+                sources.push(source);
+            }
+        }
+
+        let root = |e: facet_reflect::ReflectError<'_>| ConfigError::Invalid {
+            path: String::new(),
+            source: None,
+            message: e.to_string(),
+        };
+        let mut partial = match self.defaults {
+            Some(defaults) => Partial::from_existing_value(defaults),
+            None => Partial::alloc::<T>(),
+        }
+        .map_err(root)?;
+        Applier::new(&sources).apply(partial.inner_mut(), &tree)?;
+        let value = *partial.build().map_err(root)?;
+
+        let mut provenance = BTreeMap::new();
+        tree.provenance(&mut String::new(), &sources, &mut provenance);
+        Ok(Config { value, provenance })
+    }
+}
+
+/// A configuration, with the layer each of its values came from.
+#[derive(Debug, Clone)]
+pub struct Config<T> {
+    /// The configuration
+    pub value: T,
+    provenance: BTreeMap<String, Source>,
+}
+
+impl<T> Config<T> {
+    /// The layer that set the value at `path`, like `server.port`, or `None` if no layer
+    /// set it, so it's the default.
+    ///
+    /// Lists are set as a whole, so the items of one, like `servers[0].port`, come from the
+    /// layer that set the list.
+    pub fn source(&self, path: &str) -> Option<&Source> {
+        let mut path = path;
+        loop {
+            if let Some(source) = self.provenance.get(path) {
+                return Some(source);
+            }
+            path = &path[..path.rfind(['.', '['])?];
+        }
+    }
+
+    /// Every value a layer set, by path, with the layer.
+    pub fn sources(&self) -> impl Iterator<Item = (&str, &Source)> {
+        self.provenance
+            .iter()
+            .map(|(path, source)| (path.as_str(), source))
+    }
+
+    /// The configuration, without where it came from.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T> core::ops::Deref for Config<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
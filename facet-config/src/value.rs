@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+
+use crate::{FileFormat, Source};
+
+/// What a layer sets, before it's given a type.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    /// A number, boolean or string, as text that's parsed into the type of its field
+    Scalar(String),
+    Null,
+    Array(Vec<Value>),
+    Table(Vec<(String, Value)>),
+}
+
+/// The layers merged so far. Tables are merged key by key, and anything else is replaced
+/// whole by the last layer that sets it, whose index in the sources is kept.
+#[derive(Debug)]
+pub(crate) enum Tree {
+    Table(Vec<(String, Tree)>),
+    Leaf { value: Value, source: usize },
+}
+
+impl Tree {
+    pub(crate) fn merge(&mut self, value: Value, source: usize) {
+        match (self, value) {
+            (Tree::Table(entries), Value::Table(values)) => {
+                for (key, value) in values {
+                    match entries.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, tree)) => tree.merge(value, source),
+                        None => entries.push((key, Tree::new(value, source))),
+                    }
+                }
+            }
+            (this, value) => *this = Tree::new(value, source),
+        }
+    }
+
+    fn new(value: Value, source: usize) -> Self {
+        match value {
+            Value::Table(values) => Tree::Table(
+                values
+                    .into_iter()
+                    .map(|(key, value)| (key, Tree::new(value, source)))
+                    .collect(),
+            ),
+            value => Tree::Leaf { value, source },
+        }
+    }
+
+    /// The source of the first leaf, for errors about a whole table.
+    pub(crate) fn first_source(&self) -> Option<usize> {
+        match self {
+            Tree::Table(entries) => entries.iter().find_map(|(_, tree)| tree.first_source()),
+            Tree::Leaf { source, .. } => Some(*source),
+        }
+    }
+
+    /// Records the source of every leaf, by path.
+    pub(crate) fn provenance(
+        &self,
+        path: &mut String,
+        sources: &[Source],
+        provenance: &mut BTreeMap<String, Source>,
+    ) {
+        match self {
+            Tree::Table(entries) => {
+                for (key, tree) in entries {
+                    let len = path.len();
+                    push_key(path, key);
+                    tree.provenance(path, sources, provenance);
+                    path.truncate(len);
+                }
+            }
+            Tree::Leaf { source, .. } => {
+                provenance.insert(path.clone(), sources[*source].clone());
+            }
+        }
+    }
+}
+
+/// Appends `key` to a dotted path.
+pub(crate) fn push_key(path: &mut String, key: &str) {
+    if !path.is_empty() {
+        path.push('.');
+    }
+    path.push_str(key);
+}
+
+/// Parses a config file.
+pub(crate) fn parse(format: FileFormat, text: &str) -> Result<Value, String> {
+    let value = match format {
+        #[cfg(feature = "toml")]
+        FileFormat::Toml => toml(text)?,
+        #[cfg(feature = "json")]
+        FileFormat::Json => json(text)?,
+        #[cfg(feature = "yaml")]
+        FileFormat::Yaml => yaml(text)?,
+        #[allow(unreachable_patterns)]
+        _ => return Err(format!("support for {format} isn't enabled")),
+    };
+    match value {
+        Value::Table(_) => Ok(value),
+        Value::Null => Ok(Value::Table(Vec::new())),
+        _ => Err("expected a table at the top level".to_string()),
+    }
+}
+
+#[cfg(feature = "toml")]
+fn toml(text: &str) -> Result<Value, String> {
+    use toml_edit::{ImDocument, Item, Table, TomlError};
+
+    fn table(t: &Table) -> Value {
+        Value::Table(
+            t.iter()
+                .filter_map(|(key, i)| Some((key.to_string(), item(i)?)))
+                .collect(),
+        )
+    }
+
+    fn item(i: &Item) -> Option<Value> {
+        match i {
+            Item::None => None,
+            Item::Value(v) => Some(value(v)),
+            Item::Table(t) => Some(table(t)),
+            Item::ArrayOfTables(tables) => Some(Value::Array(tables.iter().map(table).collect())),
+        }
+    }
+
+    fn value(v: &toml_edit::Value) -> Value {
+        use toml_edit::Value as Toml;
+
+        match v {
+            Toml::String(s) => Value::Scalar(s.value().clone()),
+            Toml::Integer(i) => Value::Scalar(i.value().to_string()),
+            Toml::Float(f) => Value::Scalar(f.value().to_string()),
+            Toml::Boolean(b) => Value::Scalar(b.value().to_string()),
+            Toml::Datetime(d) => Value::Scalar(d.value().to_string()),
+            Toml::Array(array) => Value::Array(array.iter().map(value).collect()),
+            Toml::InlineTable(t) => Value::Table(
+                t.iter()
+                    .map(|(key, v)| (key.to_string(), value(v)))
+                    .collect(),
+            ),
+        }
+    }
+
+    let doc: ImDocument<String> = text.parse().map_err(|e: TomlError| e.to_string())?;
+    Ok(table(doc.as_table()))
+}
+
+#[cfg(feature = "json")]
+fn json(text: &str) -> Result<Value, String> {
+    use facet_lazy::{Kind, LazyDocument, LazyValue};
+
+    fn value(v: LazyValue<'_, '_>) -> Result<Value, String> {
+        Ok(match v.kind() {
+            Kind::Object => Value::Table(
+                v.entries()
+                    .map(|(key, v)| Ok((key.into_owned(), value(v)?)))
+                    .collect::<Result<_, String>>()?,
+            ),
+            Kind::Array => Value::Array(v.iter().map(value).collect::<Result<_, _>>()?),
+            Kind::String => Value::Scalar(v.deserialize::<String>().map_err(|e| e.to_string())?),
+            Kind::Number | Kind::Bool => Value::Scalar(v.raw().to_string()),
+            Kind::Null => Value::Null,
+        })
+    }
+
+    let doc = LazyDocument::new(text).map_err(|e| e.to_string())?;
+    value(doc.root())
+}
+
+#[cfg(feature = "yaml")]
+fn yaml(text: &str) -> Result<Value, String> {
+    use yaml_rust2::{Yaml, YamlLoader};
+
+    fn key(yaml: &Yaml) -> Result<String, String> {
+        match yaml {
+            Yaml::String(s) | Yaml::Real(s) => Ok(s.clone()),
+            Yaml::Integer(i) => Ok(i.to_string()),
+            Yaml::Boolean(b) => Ok(b.to_string()),
+            _ => Err("map keys must be strings, numbers or booleans".to_string()),
+        }
+    }
+
+    fn value(y: &Yaml) -> Result<Value, String> {
+        Ok(match y {
+            Yaml::String(s) | Yaml::Real(s) => Value::Scalar(s.clone()),
+            Yaml::Integer(i) => Value::Scalar(i.to_string()),
+            Yaml::Boolean(b) => Value::Scalar(b.to_string()),
+            Yaml::Null => Value::Null,
+            Yaml::Array(items) => Value::Array(items.iter().map(value).collect::<Result<_, _>>()?),
+            Yaml::Hash(hash) => Value::Table(
+                hash.iter()
+                    .map(|(k, v)| Ok((key(k)?, value(v)?)))
+                    .collect::<Result<_, String>>()?,
+            ),
+            Yaml::Alias(_) | Yaml::BadValue => return Err("unsupported YAML value".to_string()),
+        })
+    }
+
+    let docs = YamlLoader::load_from_str(text).map_err(|e| e.to_string())?;
+    match docs.as_slice() {
+        [] => Ok(Value::Null),
+        [doc] => value(doc),
+        _ => Err("expected exactly one YAML document".to_string()),
+    }
+}
+
+/// What the environment variable `name` sets, if it starts with `prefix`:
+/// `APP_SERVER__PORT=8080` sets `server.port` for the prefix `APP_`.
+pub(crate) fn env(prefix: &str, name: &str, value: &str) -> Option<Value> {
+    let rest = name.strip_prefix(prefix)?;
+    if rest.is_empty() {
+        return None;
+    }
+    Some(
+        rest.rsplit("__")
+            .fold(Value::Scalar(value.to_string()), |value, key| {
+                Value::Table(vec![(key.to_lowercase(), value)])
+            }),
+    )
+}
+
+/// What each flag sets, with the flag: `--server.port=8080` and `--server.port 8080` set
+/// `server.port`, and `--verbose` on its own sets `verbose` to `true`. Dashes in names
+/// stand for underscores.
+pub(crate) fn args(args: Vec<String>) -> Result<Vec<(String, Value)>, String> {
+    let mut values = Vec::new();
+    let mut args = args.into_iter().peekable();
+    while let Some(arg) = args.next() {
+        let Some(flag) = arg.strip_prefix("--") else {
+            return Err(format!("unexpected argument {arg:?}, flags start with --"));
+        };
+        let (name, value) = match flag.split_once('=') {
+            Some((name, value)) => (name, value.to_string()),
+            None => (
+                flag,
+                args.next_if(|next| !next.starts_with("--"))
+                    .unwrap_or_else(|| "true".to_string()),
+            ),
+        };
+        if name.split('.').any(str::is_empty) {
+            return Err(format!("invalid flag {arg:?}"));
+        }
+        let value = name.rsplit('.').fold(Value::Scalar(value), |value, key| {
+            Value::Table(vec![(key.replace('-', "_"), value)])
+        });
+        values.push((format!("--{name}"), value));
+    }
+    Ok(values)
+}
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_config::{ConfigBuilder, ConfigError, FileFormat, Source};
+
+#[derive(Debug, Clone, PartialEq, Facet)]
+struct App {
+    name: String,
+    server: Server,
+    #[facet(default)]
+    workers: u32,
+    log: Option<Log>,
+    #[facet(default)]
+    labels: HashMap<String, String>,
+    #[facet(default)]
+    upstreams: Vec<Upstream>,
+}
+
+#[derive(Debug, Clone, PartialEq, Facet)]
+struct Server {
+    host: String,
+    port: u16,
+    #[facet(default)]
+    tls: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Facet)]
+#[repr(u8)]
+enum Level {
+    Debug,
+    Warn,
+}
+
+#[derive(Debug, Clone, PartialEq, Facet)]
+struct Log {
+    level: Level,
+    file: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Facet)]
+struct Upstream {
+    url: String,
+    weight: u8,
+}
+
+fn defaults() -> App {
+    App {
+        name: "app".to_string(),
+        server: Server {
+            host: "127.0.0.1".to_string(),
+            port: 8080,
+            tls: false,
+        },
+        workers: 4,
+        log: None,
+        labels: HashMap::new(),
+        upstreams: Vec::new(),
+    }
+}
+
+fn file(name: &str) -> Source {
+    Source::File(name.into())
+}
+
+#[test]
+fn layers_override_in_order() {
+    facet_testhelpers::setup();
+
+    let toml = r#"
+        name = "inventory"
+        workers = 8
+
+        [server]
+        host = "0.0.0.0"
+        port = 8000
+
+        [[upstreams]]
+        url = "http://a"
+        weight = 1
+
+        [[upstreams]]
+        url = "http://b"
+        weight = 2
+    "#;
+    let yaml = "\
+server:
+  tls: true
+log:
+  level: Warn
+upstreams:
+  - url: http://c
+    weight: 3
+";
+    let json = r#"{"labels": {"team": "core"}, "log": {"file": "/var/log/app.log"}}"#;
+
+    let config = ConfigBuilder::new()
+        .defaults(defaults())
+        .text(FileFormat::Toml, "app.toml", toml)
+        .text(FileFormat::Yaml, "local.yaml", yaml)
+        .text(FileFormat::Json, "labels.json", json)
+        .env_vars(
+            "APP_",
+            [
+                ("APP_SERVER__PORT", "9000"),
+                ("APP_LOG__LEVEL", "Debug"),
+                ("OTHER_WORKERS", "1"),
+            ],
+        )
+        .args(["--workers", "16", "--labels.tier=backend"])
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        config.value,
+        App {
+            name: "inventory".to_string(),
+            server: Server {
+                host: "0.0.0.0".to_string(),
+                port: 9000,
+                tls: true,
+            },
+            workers: 16,
+            log: Some(Log {
+                level: Level::Debug,
+                file: Some("/var/log/app.log".to_string()),
+            }),
+            labels: [("team", "core"), ("tier", "backend")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            upstreams: vec![Upstream {
+                url: "http://c".to_string(),
+                weight: 3,
+            }],
+        }
+    );
+
+    assert_eq!(config.source("name"), Some(&file("app.toml")));
+    assert_eq!(config.source("server.host"), Some(&file("app.toml")));
+    assert_eq!(
+        config.source("server.port"),
+        Some(&Source::Env("APP_SERVER__PORT".to_string()))
+    );
+    assert_eq!(config.source("server.tls"), Some(&file("local.yaml")));
+    assert_eq!(config.source("upstreams"), Some(&file("local.yaml")));
+    assert_eq!(config.source("upstreams[0].url"), Some(&file("local.yaml")));
+    assert_eq!(config.source("labels.team"), Some(&file("labels.json")));
+    assert_eq!(
+        config.source("labels.tier"),
+        Some(&Source::Arg("--labels.tier".to_string()))
+    );
+    assert_eq!(
+        config.source("workers"),
+        Some(&Source::Arg("--workers".to_string()))
+    );
+    assert_eq!(config.source("log.file"), Some(&file("labels.json")));
+    assert_eq!(config.source("server"), None);
+
+    // Without the environment, the level is the YAML file's
+    let config = ConfigBuilder::new()
+        .defaults(defaults())
+        .text(FileFormat::Yaml, "local.yaml", yaml)
+        .build()
+        .unwrap();
+    assert_eq!(config.value.log.map(|log| log.level), Some(Level::Warn));
+}
+
+#[test]
+fn defaults_from_the_type() {
+    facet_testhelpers::setup();
+
+    let config = ConfigBuilder::<App>::new()
+        .args(["--name", "solo", "--server.host", "localhost"])
+        .args(["--server.port=1", "--server.tls"])
+        .build()
+        .unwrap();
+    assert_eq!(config.name, "solo");
+    assert_eq!(config.server.port, 1);
+    assert!(config.server.tls);
+    assert_eq!(config.workers, 0);
+    assert_eq!(config.log, None);
+    assert!(config.upstreams.is_empty());
+    assert_eq!(config.source("workers"), None);
+
+    match ConfigBuilder::<App>::new()
+        .args(["--name", "solo", "--server.host", "localhost"])
+        .build()
+    {
+        Err(ConfigError::Missing(path)) => assert_eq!(path, "server.port"),
+        other => panic!("expected a missing field, got {other:?}"),
+    }
+}
+
+#[test]
+fn errors_name_the_layer() {
+    facet_testhelpers::setup();
+
+    let err = ConfigBuilder::new()
+        .defaults(defaults())
+        .env_vars("APP_", [("APP_SERVER__PORT", "90000")])
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid value for server.port, set by environment variable APP_SERVER__PORT: \
+         \"90000\" isn't a valid u16"
+    );
+
+    let err = ConfigBuilder::new()
+        .defaults(defaults())
+        .text(FileFormat::Toml, "app.toml", "[server]\nhots = \"typo\"")
+        .build()
+        .unwrap_err();
+    match err {
+        ConfigError::UnknownField { path, source } => {
+            assert_eq!(path, "server.hots");
+            assert_eq!(source, Some(file("app.toml")));
+        }
+        other => panic!("expected an unknown field, got {other:?}"),
+    }
+
+    let err = ConfigBuilder::new()
+        .defaults(defaults())
+        .text(FileFormat::Json, "app.json", "{\"name\": ")
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, ConfigError::Parse { .. }), "{err:?}");
+
+    let err = ConfigBuilder::new()
+        .defaults(defaults())
+        .args(["workers"])
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, ConfigError::Args(_)), "{err:?}");
+}
+
+#[test]
+fn files() {
+    facet_testhelpers::setup();
+
+    let dir = std::env::temp_dir().join(format!("facet-config-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("app.yml");
+    std::fs::write(&path, "name: from-file\n").unwrap();
+
+    let config = ConfigBuilder::new()
+        .defaults(defaults())
+        .file(&path)
+        .optional_file(dir.join("missing.toml"))
+        .build()
+        .unwrap();
+    assert_eq!(config.name, "from-file");
+    assert_eq!(config.source("name"), Some(&Source::File(path.clone())));
+
+    let err = ConfigBuilder::new()
+        .defaults(defaults())
+        .file(dir.join("missing.toml"))
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, ConfigError::Io { .. }), "{err:?}");
+
+    let err = ConfigBuilder::new()
+        .defaults(defaults())
+        .file(dir.join("app.ini"))
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, ConfigError::UnknownFormat(_)), "{err:?}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
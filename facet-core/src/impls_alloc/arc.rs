@@ -1,7 +1,7 @@
 use crate::{
-    Def, Facet, KnownSmartPointer, PtrConst, PtrMut, PtrUninit, Shape, SmartPointerDef,
-    SmartPointerFlags, SmartPointerVTable, TryBorrowInnerError, TryFromError, TryIntoInnerError,
-    Type, UserType, ValueVTable, value_vtable,
+    Def, Facet, KnownSmartPointer, ParseError, PtrConst, PtrMut, PtrUninit, ScalarAffinity,
+    ScalarDef, Shape, SmartPointerDef, SmartPointerFlags, SmartPointerVTable, TryBorrowInnerError,
+    TryFromError, TryIntoInnerError, Type, UserType, ValueVTable, value_vtable,
 };
 
 unsafe impl<'a, T: Facet<'a>> Facet<'a> for alloc::sync::Arc<T> {
@@ -166,6 +166,34 @@ unsafe impl<'a, T: Facet<'a>> Facet<'a> for alloc::sync::Weak<T> {
     };
 }
 
+unsafe impl Facet<'_> for alloc::sync::Arc<str> {
+    const VTABLE: &'static ValueVTable = &const {
+        unsafe fn parse<'mem>(
+            s: &str,
+            target: PtrUninit<'mem>,
+        ) -> Result<PtrMut<'mem>, ParseError> {
+            Ok(unsafe { target.put(alloc::sync::Arc::<str>::from(s)) })
+        }
+
+        let mut vtable = value_vtable!(alloc::sync::Arc<str>, |f, _opts| write!(f, "Arc<str>"));
+        vtable.sized_mut().unwrap().parse = || Some(parse);
+        vtable
+    };
+
+    const SHAPE: &'static Shape<'static> = &const {
+        Shape::builder_for_sized::<Self>()
+            .def(Def::Scalar(
+                ScalarDef::builder()
+                    // The string is always on the heap, next to the reference counts
+                    .affinity(&const { ScalarAffinity::string().max_inline_length(0).build() })
+                    .build(),
+            ))
+            .type_identifier("Arc")
+            .ty(Type::User(UserType::Opaque))
+            .build()
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::string::String;
@@ -1,7 +1,7 @@
 use crate::{
-    Def, Facet, KnownSmartPointer, PtrConst, PtrMut, PtrUninit, Shape, SmartPointerDef,
-    SmartPointerFlags, SmartPointerVTable, TryBorrowInnerError, TryFromError, TryIntoInnerError,
-    Type, UserType, ValueVTable, value_vtable,
+    Def, Facet, KnownSmartPointer, ParseError, PtrConst, PtrMut, PtrUninit, ScalarAffinity,
+    ScalarDef, Shape, SmartPointerDef, SmartPointerFlags, SmartPointerVTable, TryBorrowInnerError,
+    TryFromError, TryIntoInnerError, Type, UserType, ValueVTable, value_vtable,
 };
 
 unsafe impl<'a, T: Facet<'a>> Facet<'a> for alloc::boxed::Box<T> {
@@ -100,6 +100,34 @@ unsafe impl<'a, T: Facet<'a>> Facet<'a> for alloc::boxed::Box<T> {
     };
 }
 
+unsafe impl Facet<'_> for alloc::boxed::Box<str> {
+    const VTABLE: &'static ValueVTable = &const {
+        unsafe fn parse<'mem>(
+            s: &str,
+            target: PtrUninit<'mem>,
+        ) -> Result<PtrMut<'mem>, ParseError> {
+            Ok(unsafe { target.put(alloc::boxed::Box::<str>::from(s)) })
+        }
+
+        let mut vtable = value_vtable!(alloc::boxed::Box<str>, |f, _opts| write!(f, "Box<str>"));
+        vtable.sized_mut().unwrap().parse = || Some(parse);
+        vtable
+    };
+
+    const SHAPE: &'static Shape<'static> = &const {
+        Shape::builder_for_sized::<Self>()
+            .def(Def::Scalar(
+                ScalarDef::builder()
+                    // `Box<str>` is always on the heap
+                    .affinity(&const { ScalarAffinity::string().max_inline_length(0).build() })
+                    .build(),
+            ))
+            .type_identifier("Box")
+            .ty(Type::User(UserType::Opaque))
+            .build()
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::boxed::Box;
@@ -1,7 +1,7 @@
 use crate::{
-    Def, Facet, KnownSmartPointer, PtrConst, PtrMut, PtrUninit, Shape, SmartPointerDef,
-    SmartPointerFlags, SmartPointerVTable, TryBorrowInnerError, TryFromError, TryIntoInnerError,
-    Type, UserType, ValueVTable, value_vtable,
+    Def, Facet, KnownSmartPointer, ParseError, PtrConst, PtrMut, PtrUninit, ScalarAffinity,
+    ScalarDef, Shape, SmartPointerDef, SmartPointerFlags, SmartPointerVTable, TryBorrowInnerError,
+    TryFromError, TryIntoInnerError, Type, UserType, ValueVTable, value_vtable,
 };
 
 unsafe impl<'a, T: Facet<'a>> Facet<'a> for alloc::rc::Rc<T> {
@@ -155,6 +155,34 @@ unsafe impl<'a, T: Facet<'a>> Facet<'a> for alloc::rc::Weak<T> {
     };
 }
 
+unsafe impl Facet<'_> for alloc::rc::Rc<str> {
+    const VTABLE: &'static ValueVTable = &const {
+        unsafe fn parse<'mem>(
+            s: &str,
+            target: PtrUninit<'mem>,
+        ) -> Result<PtrMut<'mem>, ParseError> {
+            Ok(unsafe { target.put(alloc::rc::Rc::<str>::from(s)) })
+        }
+
+        let mut vtable = value_vtable!(alloc::rc::Rc<str>, |f, _opts| write!(f, "Rc<str>"));
+        vtable.sized_mut().unwrap().parse = || Some(parse);
+        vtable
+    };
+
+    const SHAPE: &'static Shape<'static> = &const {
+        Shape::builder_for_sized::<Self>()
+            .def(Def::Scalar(
+                ScalarDef::builder()
+                    // The string is always on the heap, next to the reference counts
+                    .affinity(&const { ScalarAffinity::string().max_inline_length(0).build() })
+                    .build(),
+            ))
+            .type_identifier("Rc")
+            .ty(Type::User(UserType::Opaque))
+            .build()
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::rc::{Rc, Weak as RcWeak};
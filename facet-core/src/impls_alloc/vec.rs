@@ -183,3 +183,24 @@ where
             .build()
     };
 }
+
+unsafe impl<'a> Facet<'a> for alloc::borrow::Cow<'a, [u8]> {
+    const VTABLE: &'static ValueVTable = &const {
+        value_vtable!(alloc::borrow::Cow<'_, [u8]>, |f, _opts| write!(
+            f,
+            "Cow<'_, [u8]>"
+        ))
+    };
+
+    const SHAPE: &'static Shape<'static> = &const {
+        Shape::builder_for_sized::<Self>()
+            .def(Def::Scalar(
+                ScalarDef::builder()
+                    .affinity(&const { ScalarAffinity::opaque().build() })
+                    .build(),
+            ))
+            .type_identifier("Cow")
+            .ty(Type::User(UserType::Opaque))
+            .build()
+    };
+}
@@ -59,6 +59,14 @@ mod impls_url;
 #[cfg(feature = "jiff02")]
 mod impls_jiff;
 
+#[cfg(feature = "indexmap")]
+mod impls_indexmap;
+
+// Used by the code `#[facet(bitflags)]` generates
+#[cfg(feature = "bitflags")]
+#[doc(hidden)]
+pub use bitflags;
+
 // Const type Id
 mod typeid;
 pub use typeid::*;
@@ -81,6 +89,12 @@ pub unsafe trait Facet<'a>: 'a {
     /// The shape of this type
     ///
     /// Shape embeds all other constants of this trait.
+    ///
+    /// Shapes are built entirely at compile time, with no lazy statics or
+    /// initialization code, so they can be used in `const` items and `static`s.
+    /// The type's fields, variants and attributes can be read in const
+    /// contexts; the functions in its [`ValueVTable`] are function pointers,
+    /// which can only be called at runtime.
     const SHAPE: &'static Shape<'static>;
 
     /// Function pointers to perform various operations: print the full type
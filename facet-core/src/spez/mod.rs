@@ -10,6 +10,7 @@ use core::fmt::{self, Debug};
 use core::marker::PhantomData;
 
 use crate::ptr::{PtrMut, PtrUninit};
+use crate::{Facet, Shape};
 
 /// A wrapper type used for auto-deref specialization.
 ///
@@ -375,3 +376,34 @@ impl<T> SpezHashNo for Spez<T> {
         unreachable!()
     }
 }
+
+//////////////////////////////////////////////////////////////////////////////////////
+// Shape 🔷📐
+//////////////////////////////////////////////////////////////////////////////////////
+
+/// Specialization proxy for [`crate::Facet`]
+pub trait SpezShapeYes {
+    /// Returns the shape of the wrapped type.
+    ///
+    /// This method is called when the wrapped type implements `Facet`.
+    fn spez_shape(&self) -> Option<&'static Shape<'static>>;
+}
+impl<'a, T: Facet<'a>> SpezShapeYes for &SpezEmpty<T> {
+    fn spez_shape(&self) -> Option<&'static Shape<'static>> {
+        Some(T::SHAPE)
+    }
+}
+
+/// Specialization proxy for [`crate::Facet`]
+pub trait SpezShapeNo {
+    /// Fallback implementation when the type doesn't implement `Facet`.
+    ///
+    /// Unlike the other fallbacks, this one is reachable: types that don't implement `Facet`
+    /// just have no shape.
+    fn spez_shape(&self) -> Option<&'static Shape<'static>>;
+}
+impl<T> SpezShapeNo for SpezEmpty<T> {
+    fn spez_shape(&self) -> Option<&'static Shape<'static>> {
+        None
+    }
+}
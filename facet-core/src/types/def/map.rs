@@ -3,6 +3,11 @@ use crate::ptr::{PtrConst, PtrMut, PtrUninit};
 use super::{IterVTable, Shape};
 
 /// Fields for map types
+///
+/// Formats write entries in the order the map's iterator gives them, and insert them in the
+/// order they're read. So a `BTreeMap` is written sorted by key, an `IndexMap` (with the
+/// `indexmap` feature) keeps the order it was read in across a round trip, and a `HashMap`
+/// has no particular order.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 #[repr(C)]
 #[non_exhaustive]
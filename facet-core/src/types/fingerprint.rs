@@ -0,0 +1,295 @@
+use super::{
+    Def, EnumRepr, Field, FieldAttribute, NumericType, PointerType, PrimitiveType, SequenceType,
+    Shape, ShapeAttribute, StructKind, StructType, TextualType, Type, UserType, VariantAttribute,
+};
+
+impl Shape<'_> {
+    /// Returns a hash of this shape's structure: type names, field names and
+    /// order, field types, variants, and attributes, followed recursively
+    /// through fields, type parameters, and collection items.
+    ///
+    /// The hash is stable across compilations and platforms, so binary formats
+    /// can write it in a header and refuse to read data written for a
+    /// different version of a type. Doc comments and memory layout don't
+    /// contribute to it.
+    pub fn fingerprint(&self) -> u64 {
+        let mut fingerprint = Fingerprint::new();
+        fingerprint.shape(self, None);
+        fingerprint.0
+    }
+}
+
+/// The shapes being hashed, innermost first, so that recursive types hash a
+/// reference to an enclosing shape instead of looping forever.
+struct Visiting<'a, 'shape> {
+    shape: &'shape Shape<'shape>,
+    parent: Option<&'a Visiting<'a, 'shape>>,
+}
+
+/// 64-bit FNV-1a: small, and the same everywhere.
+struct Fingerprint(u64);
+
+impl Fingerprint {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Fingerprint(Self::OFFSET_BASIS)
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn tag(&mut self, tag: u8) {
+        self.bytes(&[tag]);
+    }
+
+    fn u64(&mut self, n: u64) {
+        self.bytes(&n.to_le_bytes());
+    }
+
+    fn str(&mut self, s: &str) {
+        self.u64(s.len() as u64);
+        self.bytes(s.as_bytes());
+    }
+
+    fn shape<'shape>(
+        &mut self,
+        shape: &'shape Shape<'shape>,
+        parent: Option<&Visiting<'_, 'shape>>,
+    ) {
+        let mut depth = 0;
+        let mut ancestor = parent;
+        while let Some(visiting) = ancestor {
+            if visiting.shape.id == shape.id {
+                self.tag(0xff);
+                self.u64(depth);
+                return;
+            }
+            depth += 1;
+            ancestor = visiting.parent;
+        }
+        let visiting = Visiting { shape, parent };
+        let this = Some(&visiting);
+
+        self.tag(0);
+        self.str(shape.type_identifier);
+        self.u64(shape.type_params.len() as u64);
+        for param in shape.type_params {
+            self.str(param.name);
+            self.shape(param.shape(), this);
+        }
+        match shape.type_tag {
+            Some(tag) => {
+                self.tag(1);
+                self.str(tag);
+            }
+            None => self.tag(0),
+        }
+        self.u64(shape.attributes.len() as u64);
+        for attr in shape.attributes {
+            match attr {
+                ShapeAttribute::DenyUnknownFields => self.tag(0),
+                ShapeAttribute::Default => self.tag(1),
+                ShapeAttribute::Transparent => self.tag(2),
+                ShapeAttribute::RenameAll(rule) => {
+                    self.tag(3);
+                    self.str(rule);
+                }
+                ShapeAttribute::Arbitrary(content) => {
+                    self.tag(4);
+                    self.str(content);
+                }
+                ShapeAttribute::Discriminant => self.tag(5),
+                ShapeAttribute::Parse => self.tag(6),
+                ShapeAttribute::Format => self.tag(7),
+                ShapeAttribute::BitFlags(flags) => {
+                    self.tag(8);
+                    self.u64(flags.len() as u64);
+                    for flag in flags.iter() {
+                        self.str(flag.name);
+                        self.u64(flag.bits);
+                    }
+                }
+            }
+        }
+        match shape.inner {
+            Some(inner) => {
+                self.tag(1);
+                self.shape(inner(), this);
+            }
+            None => self.tag(0),
+        }
+        self.ty(&shape.ty, this);
+        self.def(&shape.def, this);
+    }
+
+    fn ty<'shape>(&mut self, ty: &Type<'shape>, this: Option<&Visiting<'_, 'shape>>) {
+        match ty {
+            Type::Primitive(primitive) => {
+                self.tag(0);
+                match primitive {
+                    PrimitiveType::Boolean => self.tag(0),
+                    PrimitiveType::Numeric(NumericType::Integer { signed }) => {
+                        self.tag(1);
+                        self.tag(*signed as u8);
+                    }
+                    PrimitiveType::Numeric(NumericType::Float) => self.tag(2),
+                    PrimitiveType::Textual(TextualType::Char) => self.tag(3),
+                    PrimitiveType::Textual(TextualType::Str) => self.tag(4),
+                    PrimitiveType::Never => self.tag(5),
+                }
+            }
+            Type::Sequence(SequenceType::Array(array)) => {
+                self.tag(1);
+                self.u64(array.n as u64);
+                self.shape(array.t, this);
+            }
+            Type::Sequence(SequenceType::Slice(slice)) => {
+                self.tag(2);
+                self.shape(slice.t, this);
+            }
+            Type::User(UserType::Struct(sd)) => {
+                self.tag(3);
+                self.struct_type(sd, this);
+            }
+            Type::User(UserType::Enum(ed)) => {
+                self.tag(4);
+                self.str(enum_repr_name(ed.enum_repr));
+                self.u64(ed.variants.len() as u64);
+                for variant in ed.variants {
+                    self.str(variant.name);
+                    match variant.discriminant {
+                        Some(discriminant) => {
+                            self.tag(1);
+                            self.u64(discriminant as u64);
+                        }
+                        None => self.tag(0),
+                    }
+                    self.u64(variant.attributes.len() as u64);
+                    for attr in variant.attributes {
+                        match attr {
+                            VariantAttribute::Arbitrary(content) => {
+                                self.tag(0);
+                                self.str(content);
+                            }
+                            VariantAttribute::Other => self.tag(1),
+                        }
+                    }
+                    self.struct_type(&variant.data, this);
+                }
+            }
+            Type::User(UserType::Union(ud)) => {
+                self.tag(5);
+                self.fields(ud.fields, this);
+            }
+            Type::User(UserType::Opaque) => self.tag(6),
+            Type::Pointer(PointerType::Reference(vpt)) => {
+                self.tag(7);
+                self.tag(vpt.mutable as u8);
+                self.shape((vpt.target)(), this);
+            }
+            Type::Pointer(PointerType::Raw(vpt)) => {
+                self.tag(8);
+                self.tag(vpt.mutable as u8);
+                self.shape((vpt.target)(), this);
+            }
+            Type::Pointer(PointerType::Function(_)) => self.tag(9),
+        }
+    }
+
+    fn struct_type<'shape>(
+        &mut self,
+        sd: &StructType<'shape>,
+        this: Option<&Visiting<'_, 'shape>>,
+    ) {
+        self.tag(match sd.kind {
+            StructKind::Unit => 0,
+            StructKind::TupleStruct => 1,
+            StructKind::Struct => 2,
+            StructKind::Tuple => 3,
+        });
+        self.fields(sd.fields, this);
+    }
+
+    fn fields<'shape>(
+        &mut self,
+        fields: &'shape [Field<'shape>],
+        this: Option<&Visiting<'_, 'shape>>,
+    ) {
+        self.u64(fields.len() as u64);
+        for field in fields {
+            self.str(field.name);
+            self.u64(field.flags.bits());
+            self.u64(field.attributes.len() as u64);
+            for attr in field.attributes {
+                let FieldAttribute::Arbitrary(content) = attr;
+                self.str(content);
+            }
+            self.shape(field.shape(), this);
+        }
+    }
+
+    fn def<'shape>(&mut self, def: &Def<'shape>, this: Option<&Visiting<'_, 'shape>>) {
+        match def {
+            Def::Undefined => self.tag(0),
+            Def::Scalar(_) => self.tag(1),
+            Def::Map(md) => {
+                self.tag(2);
+                self.shape(md.k(), this);
+                self.shape(md.v(), this);
+            }
+            Def::Set(sd) => {
+                self.tag(3);
+                self.shape(sd.t(), this);
+            }
+            Def::List(ld) => {
+                self.tag(4);
+                self.shape(ld.t(), this);
+            }
+            Def::Array(ad) => {
+                self.tag(5);
+                self.u64(ad.n as u64);
+                self.shape(ad.t(), this);
+            }
+            Def::Slice(sd) => {
+                self.tag(6);
+                self.shape(sd.t(), this);
+            }
+            Def::Option(od) => {
+                self.tag(7);
+                self.shape(od.t(), this);
+            }
+            Def::SmartPointer(spd) => {
+                self.tag(8);
+                match spd.pointee() {
+                    Some(pointee) => {
+                        self.tag(1);
+                        self.shape(pointee, this);
+                    }
+                    None => self.tag(0),
+                }
+            }
+        }
+    }
+}
+
+fn enum_repr_name(repr: EnumRepr) -> &'static str {
+    match repr {
+        EnumRepr::RustNPO => "npo",
+        EnumRepr::U8 => "u8",
+        EnumRepr::U16 => "u16",
+        EnumRepr::U32 => "u32",
+        EnumRepr::U64 => "u64",
+        EnumRepr::USize => "usize",
+        EnumRepr::I8 => "i8",
+        EnumRepr::I16 => "i16",
+        EnumRepr::I32 => "i32",
+        EnumRepr::I64 => "i64",
+        EnumRepr::ISize => "isize",
+    }
+}
@@ -17,6 +17,8 @@ pub use def::*;
 mod ty;
 pub use ty::*;
 
+mod fingerprint;
+
 use crate::{ConstTypeId, Facet};
 
 /// Schema for reflection of a type
@@ -100,7 +102,7 @@ pub enum ShapeLayout {
 
 impl ShapeLayout {
     /// `Layout` if this type is `Sized`
-    pub fn sized_layout(self) -> Result<Layout, UnsizedError> {
+    pub const fn sized_layout(self) -> Result<Layout, UnsizedError> {
         match self {
             ShapeLayout::Sized(layout) => Ok(layout),
             ShapeLayout::Unsized => Err(UnsizedError),
@@ -135,10 +137,85 @@ pub enum ShapeAttribute<'shape> {
     Transparent,
     /// Specifies a case conversion rule for all fields or variants
     RenameAll(&'shape str),
+    /// Serialize the unit variants of this enum as their discriminant integers instead of
+    /// their names
+    Discriminant,
+    /// Deserialize this shape from strings with its vtable's parse function, set by
+    /// `#[facet(parse = ...)]`
+    Parse,
+    /// Serialize this shape as a string with its vtable's display function, set by
+    /// `#[facet(format = ...)]`
+    Format,
+    /// A transparent wrapper around the bits of a set of flags, set by `#[facet(bitflags)]`.
+    /// Formats write it as a list of the names of the flags that are set, and read it from
+    /// either that list or the bits themselves.
+    BitFlags(&'shape [BitFlag<'shape>]),
     /// Custom field attribute containing arbitrary text
     Arbitrary(&'shape str),
 }
 
+/// One named flag of a [`ShapeAttribute::BitFlags`] shape
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitFlag<'shape> {
+    /// The name of the flag, like `READ`
+    pub name: &'shape str,
+    /// The bits that are set when the flag is, widened to `u64`
+    pub bits: u64,
+}
+
+impl<'shape> BitFlag<'shape> {
+    /// Returns the flags that make up `bits`, in declaration order.
+    ///
+    /// Like `bitflags`' own `iter_names`, a flag is only yielded if all of its bits are set and
+    /// some of them aren't covered by an earlier flag. [`BitFlagNames::remaining`] holds the bits
+    /// no flag covers.
+    pub fn names(flags: &'shape [BitFlag<'shape>], bits: u64) -> BitFlagNames<'shape> {
+        BitFlagNames {
+            flags,
+            bits,
+            remaining: bits,
+        }
+    }
+
+    /// Finds the flag called `name`
+    pub fn by_name(flags: &'shape [BitFlag<'shape>], name: &str) -> Option<BitFlag<'shape>> {
+        flags.iter().find(|flag| flag.name == name).copied()
+    }
+}
+
+/// Iterator over the flags that make up some bits, see [`BitFlag::names`]
+#[derive(Debug, Clone)]
+pub struct BitFlagNames<'shape> {
+    flags: &'shape [BitFlag<'shape>],
+    bits: u64,
+    remaining: u64,
+}
+
+impl BitFlagNames<'_> {
+    /// The bits that aren't covered by any of the flags yielded so far
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+}
+
+impl<'shape> Iterator for BitFlagNames<'shape> {
+    type Item = &'shape str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((flag, rest)) = self.flags.split_first() {
+            self.flags = rest;
+            if flag.bits != 0
+                && self.bits & flag.bits == flag.bits
+                && self.remaining & flag.bits != 0
+            {
+                self.remaining &= !flag.bits;
+                return Some(flag.name);
+            }
+        }
+        None
+    }
+}
+
 impl<'shape> Shape<'shape> {
     /// Returns a builder for a shape for some type `T`.
     pub const fn builder_for_sized<'a, T: Facet<'a>>() -> ShapeBuilder<'shape> {
@@ -170,26 +247,223 @@ impl<'shape> Shape<'shape> {
         );
     }
 
+    /// Whether this is a unit-like type, with no fields and no data: `()`, [`PhantomData`], and
+    /// unit structs.
+    ///
+    /// Formats write these as their unit value (`null` in JSON, `nil` in MessagePack, nothing in
+    /// TOML), and fill them in when they're missing from the input.
+    ///
+    /// [`PhantomData`]: core::marker::PhantomData
+    pub const fn is_unit(&self) -> bool {
+        match self.ty {
+            Type::User(UserType::Struct(st)) => {
+                st.fields.is_empty() && matches!(st.kind, StructKind::Unit | StructKind::Tuple)
+            }
+            _ => false,
+        }
+    }
+
     /// See [`ShapeAttribute::DenyUnknownFields`]
-    pub fn has_deny_unknown_fields_attr(&self) -> bool {
-        self.attributes.contains(&ShapeAttribute::DenyUnknownFields)
+    pub const fn has_deny_unknown_fields_attr(&self) -> bool {
+        let mut i = 0;
+        while i < self.attributes.len() {
+            if matches!(self.attributes[i], ShapeAttribute::DenyUnknownFields) {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// See [`ShapeAttribute::Discriminant`]
+    pub const fn has_discriminant_attr(&self) -> bool {
+        let mut i = 0;
+        while i < self.attributes.len() {
+            if matches!(self.attributes[i], ShapeAttribute::Discriminant) {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// See [`ShapeAttribute::Parse`]
+    pub const fn has_parse_attr(&self) -> bool {
+        let mut i = 0;
+        while i < self.attributes.len() {
+            if matches!(self.attributes[i], ShapeAttribute::Parse) {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// See [`ShapeAttribute::Format`]
+    pub const fn has_format_attr(&self) -> bool {
+        let mut i = 0;
+        while i < self.attributes.len() {
+            if matches!(self.attributes[i], ShapeAttribute::Format) {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// The flags of a [`ShapeAttribute::BitFlags`] shape
+    pub const fn bit_flags(&self) -> Option<&'shape [BitFlag<'shape>]> {
+        let mut i = 0;
+        while i < self.attributes.len() {
+            if let ShapeAttribute::BitFlags(flags) = self.attributes[i] {
+                return Some(flags);
+            }
+            i += 1;
+        }
+        None
     }
 
     /// See [`ShapeAttribute::Default`]
-    pub fn has_default_attr(&self) -> bool {
-        self.attributes.contains(&ShapeAttribute::Default)
+    pub const fn has_default_attr(&self) -> bool {
+        let mut i = 0;
+        while i < self.attributes.len() {
+            if matches!(self.attributes[i], ShapeAttribute::Default) {
+                return true;
+            }
+            i += 1;
+        }
+        false
     }
 
     /// See [`ShapeAttribute::RenameAll`]
-    pub fn get_rename_all_attr(&self) -> Option<&str> {
-        self.attributes.iter().find_map(|attr| {
-            if let ShapeAttribute::RenameAll(rule) = attr {
-                Some(*rule)
-            } else {
-                None
+    pub const fn get_rename_all_attr(&self) -> Option<&'shape str> {
+        let mut i = 0;
+        while i < self.attributes.len() {
+            if let ShapeAttribute::RenameAll(rule) = self.attributes[i] {
+                return Some(rule);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Whether this shape takes `key` when it's a `#[facet(flatten)]` field: a struct takes the
+    /// names of its fields, and those its own flattened fields take, and an enum takes the names
+    /// of its variants.
+    pub const fn accepts_flattened_key(&self, key: &str) -> bool {
+        match self.ty {
+            Type::User(UserType::Struct(st)) => {
+                let mut i = 0;
+                while i < st.fields.len() {
+                    let field = &st.fields[i];
+                    if field.flags.contains(FieldFlags::FLATTEN) {
+                        if field.shape.accepts_flattened_key(key) {
+                            return true;
+                        }
+                    } else if str_eq(field.name, key) {
+                        return true;
+                    }
+                    i += 1;
+                }
+                false
+            }
+            Type::User(UserType::Enum(et)) => {
+                let mut i = 0;
+                while i < et.variants.len() {
+                    if str_eq(et.variants[i].name, key) {
+                        return true;
+                    }
+                    i += 1;
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether two `#[facet(flatten)]` fields of this struct take the same key, which makes the
+    /// key ambiguous when deserializing. See [`Shape::accepts_flattened_key`].
+    ///
+    /// The derive macro checks this when it builds the shape, so a conflict fails to compile.
+    pub const fn has_flattened_key_conflict(&self) -> bool {
+        let Type::User(UserType::Struct(st)) = self.ty else {
+            return false;
+        };
+        let mut i = 0;
+        while i < st.fields.len() {
+            if st.fields[i].flags.contains(FieldFlags::FLATTEN) {
+                let mut j = i + 1;
+                while j < st.fields.len() {
+                    if st.fields[j].flags.contains(FieldFlags::FLATTEN)
+                        && shares_flattened_key(st.fields[i].shape, st.fields[j].shape)
+                    {
+                        return true;
+                    }
+                    j += 1;
+                }
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// Fails with `message` if [`Shape::has_flattened_key_conflict`], which stops compilation
+    /// when called to build a shape constant.
+    #[doc(hidden)]
+    pub const fn assert_no_flattened_key_conflict(self, message: &str) -> Self {
+        if self.has_flattened_key_conflict() {
+            panic!("{}", message);
+        }
+        self
+    }
+}
+
+/// Whether any key that `a` takes when flattened is also taken by `b`
+const fn shares_flattened_key(a: &Shape<'_>, b: &Shape<'_>) -> bool {
+    match a.ty {
+        Type::User(UserType::Struct(st)) => {
+            let mut i = 0;
+            while i < st.fields.len() {
+                let field = &st.fields[i];
+                let shared = if field.flags.contains(FieldFlags::FLATTEN) {
+                    shares_flattened_key(field.shape, b)
+                } else {
+                    b.accepts_flattened_key(field.name)
+                };
+                if shared {
+                    return true;
+                }
+                i += 1;
+            }
+            false
+        }
+        Type::User(UserType::Enum(et)) => {
+            let mut i = 0;
+            while i < et.variants.len() {
+                if b.accepts_flattened_key(et.variants[i].name) {
+                    return true;
+                }
+                i += 1;
             }
-        })
+            false
+        }
+        _ => false,
+    }
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
     }
+    true
 }
 
 /// Builder for [`Shape`]
@@ -20,6 +20,65 @@ impl<'shape> EnumType<'shape> {
     pub const fn builder() -> EnumDefBuilder<'shape> {
         EnumDefBuilder::new()
     }
+
+    /// Returns `true` if every variant is a unit variant with a discriminant, like a C enum,
+    /// so that a bare integer can stand for any of them.
+    ///
+    /// The `#[facet(other)]` variant doesn't count, since it stands for the integers that
+    /// don't match any other variant.
+    pub const fn is_c_like(&self) -> bool {
+        let mut i = 0;
+        while i < self.variants.len() {
+            let variant = &self.variants[i];
+            if !variant.is_other()
+                && (variant.discriminant.is_none() || !variant.data.fields.is_empty())
+            {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Returns the index of the `#[facet(other)]` variant, which unknown variant names and
+    /// discriminants are read into.
+    pub fn other_variant_index(&self) -> Option<usize> {
+        self.variants.iter().position(|v| v.is_other())
+    }
+
+    /// Returns the index of the variant whose discriminant is `discriminant`.
+    pub fn variant_index_by_discriminant(&self, discriminant: i64) -> Option<usize> {
+        self.variants
+            .iter()
+            .position(|v| v.discriminant == Some(discriminant))
+    }
+
+    /// Lists the variants' discriminants and names, like `0 (Red), 1 (Green)`, for errors about
+    /// discriminants that don't match any variant.
+    pub fn discriminant_list(&self) -> DiscriminantList<'_, 'shape> {
+        DiscriminantList(self)
+    }
+}
+
+/// Formats the discriminants of an enum's variants, see [`EnumType::discriminant_list`]
+#[derive(Clone, Copy)]
+pub struct DiscriminantList<'a, 'shape>(&'a EnumType<'shape>);
+
+impl core::fmt::Display for DiscriminantList<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut variants = self
+            .0
+            .variants
+            .iter()
+            .filter_map(|v| Some((v.discriminant?, v.name)));
+        if let Some((discriminant, name)) = variants.next() {
+            write!(f, "{discriminant} ({name})")?;
+        }
+        for (discriminant, name) in variants {
+            write!(f, ", {discriminant} ({name})")?;
+        }
+        Ok(())
+    }
 }
 
 /// Builder for EnumDef
@@ -103,6 +162,18 @@ impl<'shape> Variant<'shape> {
         self.attributes
             .contains(&VariantAttribute::Arbitrary(content))
     }
+
+    /// See [`VariantAttribute::Other`]
+    pub const fn is_other(&self) -> bool {
+        let mut i = 0;
+        while i < self.attributes.len() {
+            if matches!(self.attributes[i], VariantAttribute::Other) {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
 }
 
 /// Builder for Variant
@@ -174,6 +245,10 @@ impl<'shape> VariantBuilder<'shape> {
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 #[repr(C)]
 pub enum VariantAttribute<'shape> {
+    /// The catch-all variant, set by `#[facet(other)]`. Variant names and discriminants that
+    /// don't match any other variant are read into it, and into its field if it has one, so
+    /// that it can be written back as it was read.
+    Other,
     /// Custom field attribute containing arbitrary text
     Arbitrary(&'shape str),
 }
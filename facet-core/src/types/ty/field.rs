@@ -1,6 +1,6 @@
 use crate::PtrConst;
 
-use super::{DefaultInPlaceFn, Shape};
+use super::{DefaultInPlaceFn, Shape, Type, UserType};
 use bitflags::bitflags;
 
 /// Describes a field in a struct or tuple
@@ -79,9 +79,22 @@ impl<'shape> Field<'shape> {
     }
 
     /// Checks if field is marked as sensitive through attributes or flags
-    pub fn is_sensitive(&'static self) -> bool {
+    pub const fn is_sensitive(&'static self) -> bool {
         self.flags.contains(FieldFlags::SENSITIVE)
     }
+
+    /// Fails with `message` unless the field's shape is a struct or an enum, the only shapes a
+    /// `#[facet(flatten)]` field can have. Stops compilation when called to build a shape constant.
+    #[doc(hidden)]
+    pub const fn assert_flattenable(self, message: &str) -> Self {
+        if !matches!(
+            self.shape.ty,
+            Type::User(UserType::Struct(_) | UserType::Enum(_))
+        ) {
+            panic!("{}", message);
+        }
+        self
+    }
 }
 
 /// An attribute that can be set on a field
@@ -1,6 +1,7 @@
-use facet_core::Facet;
+use facet_core::{Facet, Shape};
 use facet_reflect::Peek;
-use facet_serialize::{Serializer, serialize_iterative};
+pub use facet_serialize::FloatFormat;
+use facet_serialize::{Serializer, pointer_cycle_message, serialize_iterative};
 use std::io::{self, Write};
 
 /// Serializes a value to CSV
@@ -13,7 +14,7 @@ pub fn to_string<'a, T: Facet<'a>>(value: &'a T) -> String {
 }
 
 /// Serializes a Peek instance to CSV
-pub fn peek_to_string<'a>(peek: &'a Peek<'_, 'a, '_>) -> String {
+pub fn peek_to_string(peek: &Peek<'_, '_, '_>) -> String {
     let mut output = Vec::new();
     let mut serializer = CsvSerializer::new(&mut output);
     serialize_iterative(*peek, &mut serializer).unwrap();
@@ -28,7 +29,7 @@ pub fn to_writer<'a, T: Facet<'a>, W: Write>(value: &'a T, writer: &mut W) -> io
 }
 
 /// Serializes a Peek instance to a writer in CSV format
-pub fn peek_to_writer<'a, W: Write>(peek: &'a Peek<'_, 'a, '_>, writer: &mut W) -> io::Result<()> {
+pub fn peek_to_writer<W: Write>(peek: &Peek<'_, '_, '_>, writer: &mut W) -> io::Result<()> {
     let mut serializer = CsvSerializer::new(writer);
     serialize_iterative(*peek, &mut serializer)
 }
@@ -49,6 +50,9 @@ pub struct CsvSerializer<W> {
 
     /// Newline encoding
     newline: &'static [u8],
+
+    /// How floats are written
+    float_format: FloatFormat,
 }
 impl<W> CsvSerializer<W>
 where
@@ -62,9 +66,16 @@ where
             n_fields: 0,
             delim: b",",
             newline: b"\n",
+            float_format: FloatFormat::Shortest,
         }
     }
 
+    /// Writes floats in `float_format`, like `Fixed(2)` for money columns
+    pub fn with_float_format(mut self, float_format: FloatFormat) -> Self {
+        self.float_format = float_format;
+        self
+    }
+
     fn set_n_fields(&mut self, n_fields: usize) {
         self.n_fields = n_fields;
     }
@@ -213,13 +224,19 @@ where
 
     fn serialize_f32(&mut self, value: f32) -> Result<(), Self::Error> {
         self.start_value()?;
-        write!(self.writer, "{}", value)?;
+        match self.float_format.format_f32(value) {
+            Some(formatted) => self.writer.write_all(formatted.as_bytes())?,
+            None => write!(self.writer, "{}", value)?,
+        }
         self.end_value()
     }
 
     fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
         self.start_value()?;
-        write!(self.writer, "{}", value)?;
+        match self.float_format.format_f64(value) {
+            Some(formatted) => self.writer.write_all(formatted.as_bytes())?,
+            None => write!(self.writer, "{}", value)?,
+        }
         self.end_value()
     }
 
@@ -251,6 +268,10 @@ where
         self.end_value()
     }
 
+    fn serialize_pointer_cycle(&mut self, shape: &'shape Shape<'shape>) -> Result<(), Self::Error> {
+        Err(io::Error::other(pointer_cycle_message(shape)))
+    }
+
     fn serialize_unit(&mut self) -> Result<(), Self::Error> {
         self.start_value()?;
         // skip empty columns
@@ -17,3 +17,24 @@ fn test_writing_flat_structs() {
     });
     assert_eq!(expected_mystruct, actual);
 }
+
+#[test]
+fn test_writing_floats() {
+    #[derive(facet::Facet)]
+    struct Price {
+        amount: f64,
+        rate: f32,
+    }
+
+    let price = Price {
+        amount: 12.5,
+        rate: 0.1,
+    };
+    assert_eq!(facet_csv::to_string(&price), "12.5,0.1\n");
+
+    let mut out = Vec::new();
+    let mut serializer =
+        facet_csv::CsvSerializer::new(&mut out).with_float_format(facet_csv::FloatFormat::Fixed(2));
+    facet_serialize::serialize_iterative(facet_reflect::Peek::new(&price), &mut serializer)?;
+    assert_eq!(String::from_utf8(out)?, "12.50,0.10\n");
+}
@@ -0,0 +1,44 @@
+//! Errors from writing D-Bus values and signatures.
+
+/// Errors when serializing to the D-Bus wire format, or making a signature.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DbusSerError {
+    /// A type has no D-Bus equivalent, like `u128`.
+    UnsupportedType(String),
+    /// A map's key type isn't a basic type (a number, a boolean or a string).
+    NonBasicKey(String),
+    /// A signature is longer than the 255 bytes D-Bus allows.
+    SignatureTooLong(String),
+    /// Types are nested deeper than the 32 arrays and 32 structs D-Bus allows.
+    TooDeep(String),
+    /// An array is larger than the 64 MiB D-Bus allows.
+    ArrayTooLong(usize),
+}
+
+impl core::fmt::Display for DbusSerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DbusSerError::UnsupportedType(ty) => {
+                write!(f, "Type {ty} can't be represented in D-Bus")
+            }
+            DbusSerError::NonBasicKey(ty) => {
+                write!(
+                    f,
+                    "Type {ty} can't be a D-Bus dict key, only basic types can"
+                )
+            }
+            DbusSerError::SignatureTooLong(signature) => {
+                write!(f, "Signature {signature} is longer than 255 bytes")
+            }
+            DbusSerError::TooDeep(ty) => {
+                write!(f, "Type {ty} nests arrays or structs too deeply for D-Bus")
+            }
+            DbusSerError::ArrayTooLong(len) => {
+                write!(f, "Array of {len} bytes is larger than 64 MiB")
+            }
+        }
+    }
+}
+
+impl core::error::Error for DbusSerError {}
@@ -0,0 +1,12 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+mod error;
+pub use error::*;
+
+mod signature;
+pub use signature::{signature, signature_of};
+
+mod serialize;
+pub use serialize::*;
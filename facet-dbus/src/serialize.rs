@@ -0,0 +1,304 @@
+//! Write values in the D-Bus wire format.
+
+use std::borrow::Cow;
+
+use facet_core::{Def, Facet, StructKind, Type, UserType};
+use facet_reflect::{Peek, ScalarType};
+
+use crate::DbusSerError;
+use crate::signature::{
+    check_len, signature, signature_of, write_fields_signature, written_fields,
+};
+
+/// The largest array D-Bus allows, in bytes.
+const MAX_ARRAY_LEN: usize = 64 * 1024 * 1024;
+
+/// The byte order of a message, which its header names as `l` or `B`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Endian {
+    /// Little endian, marked `l`.
+    #[default]
+    Little,
+    /// Big endian, marked `B`.
+    Big,
+}
+
+/// Serializes a value into the body of a little endian D-Bus message.
+///
+/// The body's signature is [`signature::<T>()`](crate::signature). Values are padded
+/// as if the body starts at an 8 byte boundary, which it does in a message.
+///
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet)]
+/// struct Volume {
+///     muted: bool,
+///     level: u8,
+/// }
+///
+/// let body = facet_dbus::to_vec(&Volume { muted: false, level: 80 }).unwrap();
+/// assert_eq!(body, [0, 0, 0, 0, 80]);
+/// ```
+pub fn to_vec<'facet, T: Facet<'facet>>(value: &T) -> Result<Vec<u8>, DbusSerError> {
+    to_vec_with(value, Endian::Little)
+}
+
+/// Serializes a value into the body of a D-Bus message, in the given byte order.
+pub fn to_vec_with<'facet, T: Facet<'facet>>(
+    value: &T,
+    endian: Endian,
+) -> Result<Vec<u8>, DbusSerError> {
+    // Checks the whole type up front, so nothing is half written when it can't be sent
+    signature::<T>()?;
+    let mut writer = Writer {
+        buf: Vec::new(),
+        endian,
+    };
+    writer.value(Peek::new(value))?;
+    Ok(writer.buf)
+}
+
+/// The alignment of the values of a signature, from its first type code.
+fn alignment(signature: &str) -> usize {
+    match signature.as_bytes().first() {
+        Some(b'y' | b'g' | b'v') | None => 1,
+        Some(b'n' | b'q') => 2,
+        Some(b'x' | b't' | b'd' | b'(' | b'{') => 8,
+        Some(_) => 4,
+    }
+}
+
+fn unsupported(peek: Peek<'_, '_, '_>) -> DbusSerError {
+    DbusSerError::UnsupportedType(peek.shape().to_string())
+}
+
+/// Converts a value to a string through `as_str` or, failing that, `Display`.
+fn text<'mem>(peek: Peek<'mem, '_, '_>) -> Result<Cow<'mem, str>, DbusSerError> {
+    peek.as_text().ok_or_else(|| unsupported(peek))
+}
+
+struct Writer {
+    buf: Vec<u8>,
+    endian: Endian,
+}
+
+impl Writer {
+    fn align(&mut self, to: usize) {
+        let padding = (to - self.buf.len() % to) % to;
+        self.buf.resize(self.buf.len() + padding, 0);
+    }
+
+    fn num<const N: usize>(&mut self, le: [u8; N], be: [u8; N]) {
+        self.align(N);
+        match self.endian {
+            Endian::Little => self.buf.extend_from_slice(&le),
+            Endian::Big => self.buf.extend_from_slice(&be),
+        }
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.num(value.to_le_bytes(), value.to_be_bytes());
+    }
+
+    fn string(&mut self, s: &str) {
+        self.u32(s.len() as u32);
+        self.buf.extend_from_slice(s.as_bytes());
+        self.buf.push(0);
+    }
+
+    fn signature(&mut self, signature: &str) {
+        self.buf.push(signature.len() as u8);
+        self.buf.extend_from_slice(signature.as_bytes());
+        self.buf.push(0);
+    }
+
+    /// Writes an array of values with the `element` signature. Its length doesn't count
+    /// the padding before the first element, which is there even when it's empty.
+    fn array(
+        &mut self,
+        element: &str,
+        items: impl FnOnce(&mut Self) -> Result<(), DbusSerError>,
+    ) -> Result<(), DbusSerError> {
+        self.u32(0);
+        let len_at = self.buf.len() - 4;
+        self.align(alignment(element));
+        let start = self.buf.len();
+        items(self)?;
+
+        let len = self.buf.len() - start;
+        if len > MAX_ARRAY_LEN {
+            return Err(DbusSerError::ArrayTooLong(len));
+        }
+        let len = len as u32;
+        let bytes = match self.endian {
+            Endian::Little => len.to_le_bytes(),
+            Endian::Big => len.to_be_bytes(),
+        };
+        self.buf[len_at..len_at + 4].copy_from_slice(&bytes);
+        Ok(())
+    }
+
+    /// Writes the fields of a struct or variant, or a 0 byte when it has none.
+    fn fields<'mem, 'facet, 'shape>(
+        &mut self,
+        fields: impl Iterator<Item = Result<Peek<'mem, 'facet, 'shape>, DbusSerError>>,
+    ) -> Result<(), DbusSerError> {
+        let mut fields = fields.peekable();
+        if fields.peek().is_none() {
+            self.buf.push(0);
+            return Ok(());
+        }
+        self.align(8);
+        for field in fields {
+            self.value(field?)?;
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, peek: Peek<'_, '_, '_>) -> Result<(), DbusSerError> {
+        let shape = peek.shape();
+
+        if let Ok(data) = peek.get::<Vec<u8>>() {
+            return self.array("y", |w| {
+                w.buf.extend_from_slice(data);
+                Ok(())
+            });
+        }
+
+        let element = match shape.def {
+            Def::Option(od) => {
+                let opt = peek.into_option().map_err(|_| unsupported(peek))?;
+                return self.array(&signature_of(od.t())?, |w| match opt.value() {
+                    Some(inner) => w.value(inner),
+                    None => Ok(()),
+                });
+            }
+            Def::Map(_) => {
+                let map = peek.into_map().map_err(|_| unsupported(peek))?;
+                return self.array("{", |w| {
+                    for (key, value) in map.iter() {
+                        w.align(8);
+                        w.value(key)?;
+                        w.value(value)?;
+                    }
+                    Ok(())
+                });
+            }
+            Def::List(ld) => Some(ld.t()),
+            Def::Array(ad) => Some(ad.t()),
+            Def::Slice(sd) => Some(sd.t()),
+            _ => None,
+        };
+        if let Some(element) = element {
+            let list = peek.into_list_like().map_err(|_| unsupported(peek))?;
+            return self.array(&signature_of(element)?, |w| {
+                for item in list.iter() {
+                    w.value(item)?;
+                }
+                Ok(())
+            });
+        }
+
+        if let Some(scalar) = peek.scalar_type() {
+            let err = |_| unsupported(peek);
+            macro_rules! num {
+                ($ty:ty) => {{
+                    let value = *peek.get::<$ty>().map_err(err)?;
+                    self.num(value.to_le_bytes(), value.to_be_bytes());
+                }};
+                ($ty:ty as $wire:ty) => {{
+                    let value = *peek.get::<$ty>().map_err(err)? as $wire;
+                    self.num(value.to_le_bytes(), value.to_be_bytes());
+                }};
+            }
+            match scalar {
+                ScalarType::Unit => self.buf.push(0),
+                ScalarType::Bool => num!(bool as u32),
+                ScalarType::U8 => num!(u8),
+                ScalarType::U16 => num!(u16),
+                ScalarType::U32 => num!(u32),
+                ScalarType::U64 => num!(u64),
+                ScalarType::USize => num!(usize as u64),
+                ScalarType::I8 => num!(i8 as i16),
+                ScalarType::I16 => num!(i16),
+                ScalarType::I32 => num!(i32),
+                ScalarType::I64 => num!(i64),
+                ScalarType::ISize => num!(isize as i64),
+                ScalarType::F32 => num!(f32 as f64),
+                ScalarType::F64 => num!(f64),
+                ScalarType::U128 | ScalarType::I128 | ScalarType::ConstTypeId => {
+                    return Err(unsupported(peek));
+                }
+                _ if shape.is_display() => self.string(&text(peek)?),
+                _ => return Err(unsupported(peek)),
+            }
+            return Ok(());
+        }
+
+        if shape.inner.is_some() {
+            return self.value(peek.innermost_peek());
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(sd)) => {
+                let ps = peek.into_struct().map_err(|_| unsupported(peek))?;
+                self.fields(
+                    written_fields(sd.fields)
+                        .map(|(index, _)| ps.field(index).map_err(|_| unsupported(peek))),
+                )
+            }
+            Type::User(UserType::Enum(_)) => {
+                let pe = peek.into_enum().map_err(|_| unsupported(peek))?;
+                let variant = pe.active_variant().map_err(|_| unsupported(peek))?;
+                if let StructKind::Unit = variant.data.kind {
+                    self.signature("s");
+                    self.string(variant.name);
+                    return Ok(());
+                }
+
+                // A single tuple field is the variant's content, and more fields make a struct
+                let fields = variant.data.fields;
+                let tuple = matches!(
+                    variant.data.kind,
+                    StructKind::Tuple | StructKind::TupleStruct
+                );
+                let mut written = written_fields(fields);
+                let single = match (written.next(), written.next()) {
+                    (Some(only), None) if tuple => Some(only),
+                    _ => None,
+                };
+                let content = match single {
+                    Some((_, field)) => signature_of(field.shape)?,
+                    None => {
+                        let mut content = String::new();
+                        write_fields_signature(shape, fields, &mut content, 0)?;
+                        check_len(content)?
+                    }
+                };
+                let field = |index| {
+                    pe.field(index)
+                        .ok()
+                        .flatten()
+                        .ok_or_else(|| unsupported(peek))
+                };
+
+                self.signature("a{sv}");
+                self.array("{", |w| {
+                    w.align(8);
+                    w.string(variant.name);
+                    w.signature(&content);
+                    match single {
+                        Some((index, _)) => w.value(field(index)?),
+                        None => w.fields(written_fields(fields).map(|(index, _)| field(index))),
+                    }
+                })
+            }
+            _ if shape.is_display() => {
+                self.string(&text(peek)?);
+                Ok(())
+            }
+            _ => Err(unsupported(peek)),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! D-Bus type signatures, made from shapes.
+
+use facet_core::{Def, Facet, Field, FieldFlags, Shape, Type, UserType};
+use facet_reflect::ScalarType;
+
+use crate::DbusSerError;
+
+/// The longest signature D-Bus allows, in bytes.
+const MAX_SIGNATURE_LEN: usize = 255;
+
+/// D-Bus allows 32 nested arrays and 32 nested structs.
+const MAX_DEPTH: usize = 64;
+
+/// The D-Bus signature of the values [`to_vec`](crate::to_vec) writes for `T`.
+///
+/// ```
+/// use facet::Facet;
+/// use std::collections::HashMap;
+///
+/// #[derive(Facet)]
+/// struct Notification {
+///     app_name: String,
+///     id: u32,
+///     actions: Vec<String>,
+///     hints: HashMap<String, u8>,
+/// }
+///
+/// assert_eq!(facet_dbus::signature::<Notification>().unwrap(), "(suasa{sy})");
+/// ```
+pub fn signature<'facet, T: Facet<'facet>>() -> Result<String, DbusSerError> {
+    signature_of(T::SHAPE)
+}
+
+/// The D-Bus signature of the values of a shape, like [`signature`].
+pub fn signature_of(shape: &Shape<'_>) -> Result<String, DbusSerError> {
+    let mut out = String::new();
+    write_signature(shape, &mut out, 0)?;
+    check_len(out)
+}
+
+/// Fails for signatures longer than D-Bus allows.
+pub(crate) fn check_len(signature: String) -> Result<String, DbusSerError> {
+    if signature.len() > MAX_SIGNATURE_LEN {
+        return Err(DbusSerError::SignatureTooLong(signature));
+    }
+    Ok(signature)
+}
+
+/// The type code of a scalar, which is a basic type for everything but `()`.
+pub(crate) fn scalar_code(shape: &Shape<'_>) -> Option<char> {
+    let code = match ScalarType::try_from_shape(shape)? {
+        ScalarType::Unit => 'y',
+        ScalarType::Bool => 'b',
+        ScalarType::U8 => 'y',
+        // D-Bus has no signed byte
+        ScalarType::I8 | ScalarType::I16 => 'n',
+        ScalarType::U16 => 'q',
+        ScalarType::I32 => 'i',
+        ScalarType::U32 => 'u',
+        ScalarType::I64 | ScalarType::ISize => 'x',
+        ScalarType::U64 | ScalarType::USize => 't',
+        // D-Bus has no single precision float
+        ScalarType::F32 | ScalarType::F64 => 'd',
+        ScalarType::U128 | ScalarType::I128 | ScalarType::ConstTypeId => return None,
+        _ if shape.is_display() => 's',
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// The fields of a struct or variant that are written, which are the ones not marked
+/// `#[facet(skip_serializing)]`.
+pub(crate) fn written_fields<'a, 'shape>(
+    fields: &'a [Field<'shape>],
+) -> impl Iterator<Item = (usize, &'a Field<'shape>)> {
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !field.flags.contains(FieldFlags::SKIP_SERIALIZING))
+}
+
+/// Writes the signature of a struct with `fields`: D-Bus has no empty structs, so one
+/// without fields is a byte, which is always 0.
+pub(crate) fn write_fields_signature(
+    shape: &Shape<'_>,
+    fields: &[Field<'_>],
+    out: &mut String,
+    depth: usize,
+) -> Result<(), DbusSerError> {
+    if written_fields(fields).next().is_none() {
+        out.push('y');
+        return Ok(());
+    }
+    if depth >= MAX_DEPTH {
+        return Err(DbusSerError::TooDeep(shape.to_string()));
+    }
+    out.push('(');
+    for (_, field) in written_fields(fields) {
+        write_signature(field.shape, out, depth + 1)?;
+    }
+    out.push(')');
+    Ok(())
+}
+
+pub(crate) fn write_signature(
+    shape: &Shape<'_>,
+    out: &mut String,
+    depth: usize,
+) -> Result<(), DbusSerError> {
+    if depth >= MAX_DEPTH {
+        return Err(DbusSerError::TooDeep(shape.to_string()));
+    }
+    let unsupported = || DbusSerError::UnsupportedType(shape.to_string());
+
+    match shape.def {
+        // `Option`s are arrays of zero or one item
+        Def::Option(od) => {
+            out.push('a');
+            return write_signature(od.t(), out, depth + 1);
+        }
+        Def::List(ld) => {
+            out.push('a');
+            return write_signature(ld.t(), out, depth + 1);
+        }
+        Def::Array(ad) => {
+            out.push('a');
+            return write_signature(ad.t(), out, depth + 1);
+        }
+        Def::Slice(sd) => {
+            out.push('a');
+            return write_signature(sd.t(), out, depth + 1);
+        }
+        Def::Map(md) => {
+            let key = md.k();
+            let code = match ScalarType::try_from_shape(key) {
+                Some(ScalarType::Unit) | None => None,
+                Some(_) => scalar_code(key),
+            };
+            let Some(code) = code else {
+                return Err(DbusSerError::NonBasicKey(key.to_string()));
+            };
+            out.push_str("a{");
+            out.push(code);
+            write_signature(md.v(), out, depth + 1)?;
+            out.push('}');
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    if ScalarType::try_from_shape(shape).is_some() {
+        out.push(scalar_code(shape).ok_or_else(unsupported)?);
+        return Ok(());
+    }
+
+    if let Some(inner) = shape.inner {
+        return write_signature(inner(), out, depth);
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => write_fields_signature(shape, sd.fields, out, depth),
+        // The variant holds the variant name, or a dict from it to the variant's fields
+        Type::User(UserType::Enum(_)) => {
+            out.push('v');
+            Ok(())
+        }
+        _ if shape.is_display() => {
+            out.push('s');
+            Ok(())
+        }
+        _ => Err(unsupported()),
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::{BTreeMap, HashMap};
+use std::net::Ipv4Addr;
+
+use facet::Facet;
+use facet_dbus::{DbusSerError, Endian, signature, to_vec, to_vec_with};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Notification {
+    app_name: String,
+    replaces_id: u32,
+    actions: Vec<String>,
+    hints: HashMap<String, u8>,
+    timeout: i32,
+    #[facet(skip_serializing)]
+    shown: bool,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Empty {}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+enum State {
+    Idle,
+    Level(u8),
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Wide {
+    id: u128,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn test_signatures() {
+    assert_eq!(signature::<u8>().unwrap(), "y");
+    assert_eq!(signature::<i8>().unwrap(), "n");
+    assert_eq!(signature::<f32>().unwrap(), "d");
+    assert_eq!(signature::<String>().unwrap(), "s");
+    assert_eq!(signature::<Vec<u8>>().unwrap(), "ay");
+    assert_eq!(signature::<Option<u32>>().unwrap(), "au");
+    assert_eq!(signature::<(u16, bool)>().unwrap(), "(qb)");
+    assert_eq!(signature::<Empty>().unwrap(), "y");
+    assert_eq!(signature::<State>().unwrap(), "v");
+    assert_eq!(
+        signature::<BTreeMap<String, Vec<Point>>>().unwrap(),
+        "a{sa(ii)}"
+    );
+    assert_eq!(signature::<Notification>().unwrap(), "(suasa{sy}i)");
+}
+
+#[test]
+fn test_unsupported_signatures() {
+    assert!(matches!(
+        signature::<Wide>(),
+        Err(DbusSerError::UnsupportedType(_))
+    ));
+    assert!(matches!(
+        signature::<HashMap<(u8, u8), u8>>(),
+        Err(DbusSerError::NonBasicKey(_))
+    ));
+    assert!(matches!(
+        to_vec(&Wide { id: 1 }),
+        Err(DbusSerError::UnsupportedType(_))
+    ));
+}
+
+#[test]
+fn test_numbers() {
+    assert_eq!(to_vec(&0x01020304u32).unwrap(), [4, 3, 2, 1]);
+    assert_eq!(
+        to_vec_with(&0x01020304u32, Endian::Big).unwrap(),
+        [1, 2, 3, 4]
+    );
+    assert_eq!(to_vec(&true).unwrap(), [1, 0, 0, 0]);
+    assert_eq!(to_vec(&-1i8).unwrap(), [0xff, 0xff]);
+    assert_eq!(to_vec(&1.5f32).unwrap(), 1.5f64.to_le_bytes());
+}
+
+#[test]
+fn test_strings_and_arrays() {
+    assert_eq!(
+        to_vec(&"hi".to_string()).unwrap(),
+        [2, 0, 0, 0, b'h', b'i', 0]
+    );
+    assert_eq!(to_vec(&vec![7u8, 8]).unwrap(), [2, 0, 0, 0, 7, 8]);
+
+    // The padding before the first element isn't part of the length, even when empty
+    assert_eq!(
+        to_vec(&vec![1u64]).unwrap(),
+        [8, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]
+    );
+    assert_eq!(
+        to_vec(&Vec::<u64>::new()).unwrap(),
+        [0, 0, 0, 0, 0, 0, 0, 0]
+    );
+
+    assert_eq!(to_vec(&None::<u32>).unwrap(), [0, 0, 0, 0]);
+    assert_eq!(to_vec(&Some(7u32)).unwrap(), [4, 0, 0, 0, 7, 0, 0, 0]);
+}
+
+#[test]
+fn test_dicts() {
+    let map = BTreeMap::from([(1u8, 2u8), (3, 4)]);
+    assert_eq!(
+        to_vec(&map).unwrap(),
+        [10, 0, 0, 0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0, 0, 0, 3, 4]
+    );
+
+    // Keys that are written with `Display` are strings
+    let map = BTreeMap::from([(Ipv4Addr::new(1, 2, 3, 4), 9u8)]);
+    assert_eq!(signature::<BTreeMap<Ipv4Addr, u8>>().unwrap(), "a{sy}");
+    assert_eq!(
+        to_vec(&map).unwrap(),
+        [
+            13, 0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 0, b'1', b'.', b'2', b'.', b'3', b'.', b'4', 0, 9
+        ]
+    );
+}
+
+#[test]
+fn test_structs() {
+    let point = Point { x: 1, y: -1 };
+    assert_eq!(
+        to_vec(&point).unwrap(),
+        [1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff]
+    );
+    assert_eq!(to_vec(&Empty {}).unwrap(), [0]);
+
+    // Skipped fields aren't written, and aren't in the signature either
+    let notification = Notification {
+        app_name: "a".to_string(),
+        replaces_id: 2,
+        actions: vec![],
+        hints: HashMap::new(),
+        timeout: -1,
+        shown: true,
+    };
+    assert_eq!(
+        to_vec(&notification).unwrap(),
+        [
+            1, 0, 0, 0, b'a', 0, 0, 0, // app_name
+            2, 0, 0, 0, // replaces_id
+            0, 0, 0, 0, // actions
+            0, 0, 0, 0, 0, 0, 0, 0, // hints, padded to its dict entries
+            0xff, 0xff, 0xff, 0xff, // timeout
+        ]
+    );
+}
+
+#[test]
+fn test_enums() {
+    assert_eq!(
+        to_vec(&State::Idle).unwrap(),
+        [1, b's', 0, 0, 4, 0, 0, 0, b'I', b'd', b'l', b'e', 0]
+    );
+    assert_eq!(
+        to_vec(&State::Level(3)).unwrap(),
+        [
+            5, b'a', b'{', b's', b'v', b'}', 0, // signature
+            0, 14, 0, 0, 0, // dict length
+            0, 0, 0, 0, // padding to the dict entry
+            5, 0, 0, 0, b'L', b'e', b'v', b'e', b'l', 0, // key
+            1, b'y', 0, 3, // value
+        ]
+    );
+}
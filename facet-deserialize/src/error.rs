@@ -2,13 +2,14 @@
 use ariadne::{Color, Config, IndexType, Label, Report, ReportKind, Source};
 
 use alloc::string::String;
+use alloc::vec::Vec;
 
 use facet_core::{Shape, Type, UserType};
 use facet_reflect::{ReflectError, VariantError};
 use owo_colors::OwoColorize;
 
 use crate::debug::InputDebug;
-use crate::{Cooked, Outcome, Span};
+use crate::{Cooked, Instruction, Outcome, Span};
 
 /// A JSON parse error, with context. Never would've guessed huh.
 pub struct DeserError<'input, 'shape, C = Cooked> {
@@ -91,12 +92,33 @@ pub enum DeserErrorKind<'shape> {
     /// A required struct field was missing at the end of JSON input.
     MissingField(&'static str),
 
+    /// `null` was found where the value can't be null: it's not an `Option` or `()`, and
+    /// it's not a field with a default.
+    UnexpectedNull {
+        /// The shape that got the `null`
+        shape: &'shape Shape<'shape>,
+    },
+
     /// A number is out of range.
     NumberOutOfRange(f64),
 
     /// An unexpected String was encountered in the input.
     StringAsNumber(String),
 
+    /// A string for a `char` didn't hold exactly one character.
+    NotAChar(String),
+
+    /// A reference couldn't be filled by borrowing from the input.
+    CannotBorrow {
+        /// The reference shape
+        shape: &'shape Shape<'shape>,
+        /// Why it couldn't borrow, and what to use instead
+        reason: &'static str,
+    },
+
+    /// A `{"$ref": n}` points at a shared value that wasn't read before it.
+    UnknownSharedRef(u64),
+
     /// An unexpected field name was encountered in the input.
     UnknownField {
         /// The name of the field that was not recognized
@@ -133,28 +155,91 @@ pub enum DeserErrorKind<'shape> {
         enum_shape: &'shape Shape<'shape>,
     },
 
+    /// No variant of an enum has the discriminant that was read.
+    NoSuchDiscriminant {
+        /// The discriminant that was read
+        discriminant: String,
+
+        /// The enum shape whose variants were checked
+        enum_shape: &'shape Shape<'shape>,
+    },
+
+    /// A `#[facet(bitflags)]` type has no flag with this name.
+    NoSuchFlag {
+        /// The name that was read
+        name: String,
+
+        /// The shape whose flags were checked
+        flags_shape: &'shape Shape<'shape>,
+    },
+
     /// An error occurred when reflecting an enum variant (index) from a user type.
     VariantError(VariantError),
 
-    /// Too many elements for an array.
+    /// Too many elements for an array, or for a tuple. The span is that of the first element
+    /// that doesn't fit.
     ArrayOverflow {
         /// The array shape
         shape: &'shape Shape<'shape>,
 
+        /// Index of the element that doesn't fit
+        index: usize,
+
         /// Maximum allowed length
         max_len: usize,
     },
 
+    /// The deserializer got into a state it should never be in, because of a bug in it or in
+    /// the format that drives it. Deserialization stops rather than going on wrongly.
+    InternalError {
+        /// What should have held and didn't
+        invariant: &'static str,
+        /// The value being built when it happened
+        shape: &'shape Shape<'shape>,
+        /// The instructions that were left, the next one last
+        stack: Vec<Instruction>,
+    },
+
+    /// Input was left after the top-level value.
+    TrailingData,
+
     /// Failed to convert numeric type.
     NumericConversion {
         /// Source type name
         from: &'static str,
 
-        /// Target type name  
+        /// Target type name
         to: &'static str,
+
+        /// The value that didn't convert, as written by its source type
+        value: String,
+
+        /// Why the value didn't convert
+        reason: NumericConversionReason,
     },
 }
 
+/// Why a number couldn't be stored as the numeric type a shape wants.
+#[derive(Debug, PartialEq, Clone)]
+pub enum NumericConversionReason {
+    /// The value is outside the range of the target type.
+    OutOfRange {
+        /// The smallest value of the target type
+        min: String,
+        /// The largest value of the target type
+        max: String,
+    },
+
+    /// The value is negative and the target type is unsigned.
+    Negative,
+
+    /// The value has a fractional part and the target type is an integer.
+    Fractional,
+
+    /// The target type is a kind of number that can't be converted to, like a decimal.
+    Unsupported,
+}
+
 impl<'input, 'shape, C> DeserError<'input, 'shape, C> {
     /// Creates a new deser error, preserving input and location context for accurate reporting.
     pub fn new<I>(
@@ -227,12 +312,36 @@ impl core::fmt::Display for DeserErrorMessage<'_, '_> {
                 write!(f, "Missing {} for {}", expected.red(), field.yellow())
             }
             DeserErrorKind::MissingField(fld) => write!(f, "Missing required field: {}", fld.red()),
+            DeserErrorKind::UnexpectedNull { shape } => write!(
+                f,
+                "Unexpected {} for {}, which has no default",
+                "null".red(),
+                shape.yellow()
+            ),
             DeserErrorKind::NumberOutOfRange(n) => {
                 write!(f, "Number out of range: {}", n.red())
             }
             DeserErrorKind::StringAsNumber(s) => {
                 write!(f, "Expected a string but got number: {}", s.red())
             }
+            DeserErrorKind::NotAChar(s) => {
+                write!(f, "Expected a single character, got \"{}\"", s.red())
+            }
+            DeserErrorKind::CannotBorrow { shape, reason } => {
+                write!(
+                    f,
+                    "Cannot borrow {} from the input: {}",
+                    shape.red(),
+                    reason
+                )
+            }
+            DeserErrorKind::UnknownSharedRef(id) => {
+                write!(
+                    f,
+                    "Reference to shared value {} comes before that value",
+                    id.red()
+                )
+            }
             DeserErrorKind::UnknownField { field_name, shape } => {
                 write!(
                     f,
@@ -254,6 +363,44 @@ impl core::fmt::Display for DeserErrorMessage<'_, '_> {
                     wanted.green()
                 )
             }
+            DeserErrorKind::NoSuchDiscriminant {
+                discriminant,
+                enum_shape,
+            } => {
+                write!(
+                    f,
+                    "No variant of {} has discriminant {}",
+                    enum_shape.yellow(),
+                    discriminant.red()
+                )?;
+                if let Type::User(UserType::Enum(ed)) = enum_shape.ty {
+                    write!(
+                        f,
+                        ". Valid discriminants: [{}]",
+                        ed.discriminant_list().green()
+                    )?;
+                }
+                Ok(())
+            }
+            DeserErrorKind::NoSuchFlag { name, flags_shape } => {
+                write!(
+                    f,
+                    "{} has no flag named {}",
+                    flags_shape.yellow(),
+                    name.red()
+                )?;
+                if let Some(flags) = flags_shape.bit_flags() {
+                    write!(f, ". Available flags: [")?;
+                    for (index, flag) in flags.iter().enumerate() {
+                        if index > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", flag.name.green())?;
+                    }
+                    write!(f, "]")?;
+                }
+                Ok(())
+            }
             DeserErrorKind::NoSuchVariant { name, enum_shape } => {
                 if let Type::User(UserType::Enum(ed)) = enum_shape.ty {
                     write!(
@@ -287,22 +434,79 @@ impl core::fmt::Display for DeserErrorMessage<'_, '_> {
             DeserErrorKind::VariantError(e) => {
                 write!(f, "Variant error: {e}")
             }
-            DeserErrorKind::ArrayOverflow { shape, max_len } => {
+            DeserErrorKind::ArrayOverflow {
+                shape,
+                index,
+                max_len,
+            } => {
                 write!(
                     f,
-                    "Too many elements for array {}: maximum {} elements allowed",
+                    "Too many elements for array {}: element {} doesn't fit, maximum {} elements allowed",
                     shape.blue(),
+                    index.yellow(),
                     max_len.yellow()
                 )
             }
-            DeserErrorKind::NumericConversion { from, to } => {
+            DeserErrorKind::InternalError {
+                invariant,
+                shape,
+                stack,
+            } => {
                 write!(
                     f,
-                    "Cannot convert {} to {}: value out of range or precision loss",
-                    from.red(),
-                    to.green()
+                    "Internal error: {} (building {}, instructions left: {:?})",
+                    invariant.red(),
+                    shape.blue(),
+                    stack
                 )
             }
+            DeserErrorKind::TrailingData => {
+                write!(f, "Unexpected {} after the value", "trailing input".red())
+            }
+            DeserErrorKind::NumericConversion {
+                from,
+                to,
+                value,
+                reason,
+            } => match reason {
+                NumericConversionReason::OutOfRange { min, max } => {
+                    write!(
+                        f,
+                        "{} does not fit in {} ({}..={})",
+                        value.red(),
+                        to.green(),
+                        min,
+                        max
+                    )
+                }
+                NumericConversionReason::Negative => {
+                    write!(
+                        f,
+                        "{} does not fit in {}: it is negative and {} is unsigned",
+                        value.red(),
+                        to.green(),
+                        to
+                    )
+                }
+                NumericConversionReason::Fractional => {
+                    write!(
+                        f,
+                        "{} does not fit in {}: it has a fractional part and {} is an integer",
+                        value.red(),
+                        to.green(),
+                        to
+                    )
+                }
+                NumericConversionReason::Unsupported => {
+                    write!(
+                        f,
+                        "Cannot convert {} {} to {}",
+                        from,
+                        value.red(),
+                        to.green()
+                    )
+                }
+            },
         }
     }
 }
@@ -0,0 +1,223 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use facet_core::Facet;
+use facet_reflect::{Partial, ReflectError};
+use log::trace;
+
+use crate::{
+    Cooked, DeserError, DeserErrorKind, Expectation, Format, InputDebug, NextData, Outcome, Scalar,
+    Span, Spanned, StackRunner, Substack, ToCooked,
+};
+
+/// A piece of the input's structure, as read by [`Events`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<'input> {
+    /// A scalar: a whole value, a list item, or an object entry's value.
+    Scalar(Scalar<'input>),
+    /// The start of a list; its items come next, until [`Event::ListEnded`].
+    ListStarted,
+    /// The end of a list.
+    ListEnded,
+    /// The start of an object; its entries come next, until [`Event::ObjectEnded`].
+    ObjectStarted,
+    /// An object key; the entry's value comes next.
+    Key(Cow<'input, str>),
+    /// The end of an object.
+    ObjectEnded,
+}
+
+/// Reads one value from the input as a stream of [`Event`]s, without a Rust
+/// type to deserialize into.
+///
+/// Tools that only look at a document's structure (linters, converters,
+/// indexers) can walk it without building values: keys are told apart from
+/// string values, and every event carries the span it was read from.
+/// [`transcode`](crate::transcode) is built on it.
+///
+/// Iteration ends after the value's last event, or after the first error.
+/// Formats that look at the value being built to decide how to read the input,
+/// like command-line arguments, can't be read this way.
+pub struct Events<'input, 'shape, F>
+where
+    F: Format + 'shape,
+    'shape: 'input,
+{
+    input: &'input F::Input<'input>,
+    format: F,
+    /// `None` once the value has been read, or after an error
+    nd: Option<NextData<'input, 'input, 'shape, F::SpanType, F::Input<'input>>>,
+    /// Why reading couldn't start, reported by the first call to `next`
+    start_error: Option<ReflectError<'shape>>,
+    /// Whether each open container is an object
+    stack: Vec<bool>,
+    expectation: Expectation,
+}
+
+impl<'input, 'shape, F> Events<'input, 'shape, F>
+where
+    F: Format + 'shape,
+    'shape: 'input,
+{
+    /// Starts reading `input` with `format`.
+    pub fn new(input: &'input F::Input<'input>, format: F) -> Self {
+        // `Format::next` needs a value under construction; this one is never touched
+        let (nd, start_error) = match Partial::alloc_shape(<()>::SHAPE) {
+            Ok(wip) => (Some(wip), None),
+            Err(e) => (None, Some(e)),
+        };
+        let nd = nd.map(|wip| NextData {
+            start: 0,
+            runner: StackRunner {
+                original_input: input,
+                input,
+                stack: Vec::new(),
+                substack: Substack::new(),
+                last_span: Span::new(0, 0),
+                format_source: format.source(),
+                array_indices: Vec::new(),
+                enum_tuple_field_count: None,
+                enum_tuple_current_field: None,
+                shared_refs: false,
+                shared_count: 0,
+                shared_ids: Vec::new(),
+                pending_ref: None,
+                pending_flags: None,
+                pending_flattened_key: None,
+                interner: None,
+                ignored_fields: None,
+                pending_ignored_field: None,
+                field_spans: None,
+                open_field_spans: Vec::new(),
+                projection: None,
+                selections: Vec::new(),
+                scalar_run: None,
+                bulk_scalars: false,
+            },
+            wip,
+        });
+        Self {
+            input,
+            format,
+            nd,
+            start_error,
+            stack: Vec::new(),
+            expectation: Expectation::Value,
+        }
+    }
+
+    /// The number of lists and objects that are open at this point of the input.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Gives back the format, to read another input with it.
+    pub fn into_format(self) -> F {
+        self.format
+    }
+}
+
+impl<'input, 'shape, F> Events<'input, 'shape, F>
+where
+    F: Format + 'shape,
+    F::Input<'input>: InputDebug,
+    Span<F::SpanType>: ToCooked<'input, F>,
+    'shape: 'input,
+{
+    fn error(
+        &self,
+        kind: DeserErrorKind<'shape>,
+        span: Span<F::SpanType>,
+    ) -> DeserError<'input, 'shape, Cooked> {
+        DeserError::new(
+            kind,
+            self.input,
+            span.to_cooked(&self.format, self.input),
+            self.format.source(),
+        )
+    }
+}
+
+impl<'input, 'shape, F> Iterator for Events<'input, 'shape, F>
+where
+    F: Format + 'shape,
+    F::Input<'input>: InputDebug,
+    Span<F::SpanType>: ToCooked<'input, F>,
+    'shape: 'input,
+{
+    type Item = Result<Spanned<Event<'input>>, DeserError<'input, 'shape, Cooked>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(e) = self.start_error.take() {
+                let source = self.format.source();
+                let err = DeserError::new_reflect(e, self.input, Span::default(), source);
+                return Some(Err(err));
+            }
+            let nd = self.nd.take()?;
+            let (mut nd, res) = self.format.next(nd, self.expectation.clone());
+            let outcome = match res {
+                Ok(outcome) => outcome,
+                Err(e) => return Some(Err(self.error(e.node, e.span))),
+            };
+            nd.start = outcome.span.end();
+            trace!("Reading event from {}", outcome.node);
+
+            let key_position = self.expectation == Expectation::ObjectKeyOrObjectClose;
+            let (event, ended) = match outcome.node {
+                Outcome::Resegmented(_) => {
+                    self.nd = Some(nd);
+                    continue;
+                }
+                Outcome::Scalar(Scalar::String(key)) if key_position => {
+                    self.expectation = Expectation::ObjectVal;
+                    (Event::Key(key), false)
+                }
+                Outcome::ObjectEnded if key_position => {
+                    self.stack.pop();
+                    (Event::ObjectEnded, true)
+                }
+                Outcome::ListEnded if self.expectation == Expectation::ListItemOrListClose => {
+                    self.stack.pop();
+                    (Event::ListEnded, true)
+                }
+                got if key_position => {
+                    let kind = DeserErrorKind::UnexpectedOutcome {
+                        got: got.into_owned(),
+                        wanted: "an object key",
+                    };
+                    return Some(Err(self.error(kind, outcome.span)));
+                }
+                Outcome::Scalar(scalar) => (Event::Scalar(scalar), true),
+                Outcome::ListStarted => {
+                    self.stack.push(false);
+                    self.expectation = Expectation::ListItemOrListClose;
+                    (Event::ListStarted, false)
+                }
+                Outcome::ObjectStarted => {
+                    self.stack.push(true);
+                    self.expectation = Expectation::ObjectKeyOrObjectClose;
+                    (Event::ObjectStarted, false)
+                }
+                got @ (Outcome::ListEnded | Outcome::ObjectEnded) => {
+                    let kind = DeserErrorKind::UnexpectedOutcome {
+                        got: got.into_owned(),
+                        wanted: "a value",
+                    };
+                    return Some(Err(self.error(kind, outcome.span)));
+                }
+            };
+
+            let span = outcome.span.to_cooked(&self.format, self.input);
+            // A value is complete: go back to the container it's in, if any
+            if ended {
+                match self.stack.last() {
+                    None => return Some(Ok(Spanned { node: event, span })),
+                    Some(true) => self.expectation = Expectation::ObjectKeyOrObjectClose,
+                    Some(false) => self.expectation = Expectation::ListItemOrListClose,
+                }
+            }
+            self.nd = Some(nd);
+            return Some(Ok(Spanned { node: event, span }));
+        }
+    }
+}
@@ -0,0 +1,140 @@
+#[cfg(feature = "std")]
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use core::fmt;
+
+use crate::{Cooked, Span};
+
+/// A field that was set, with where its value is in the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSpan {
+    /// The field, e.g. `Config.server.port`
+    pub path: String,
+    /// Where the field's value is in the input, from its first byte to its last
+    pub span: Span<Cooked>,
+}
+
+/// Told where the value of every field that is set comes from in the input.
+///
+/// With one set on a [`Scratch`](crate::Scratch), tools that check a value after it's
+/// deserialized can still point at the part of the input it came from. Closures taking a
+/// [`FieldSpan`] can be used directly.
+pub trait FieldSpanSink: Send + Sync {
+    /// Called once for each field, after its value is read. Fields nested in a value are
+    /// reported before it.
+    fn field_span(&self, field: FieldSpan);
+}
+
+impl<F> FieldSpanSink for F
+where
+    F: Fn(FieldSpan) + Send + Sync,
+{
+    fn field_span(&self, field: FieldSpan) {
+        self(field)
+    }
+}
+
+/// A [`FieldSpanSink`] that keeps the span of every field it's told about, by path, until
+/// they're taken.
+///
+/// It can be given the name of the file the input comes from, so spans can be reported as
+/// `config.json:42:5` with [`SpanTable::locate`].
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct SpanTable {
+    source: Option<String>,
+    spans: std::sync::Mutex<BTreeMap<String, Span<Cooked>>>,
+}
+
+#[cfg(feature = "std")]
+impl SpanTable {
+    /// Creates a table with no span in it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Names the file that the input comes from, for [`SpanTable::locate`].
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// The name of the file that the input comes from, if it was given one.
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// Where the value of the field at `path` is in the input, if it was set.
+    pub fn get(&self, path: &str) -> Option<Span<Cooked>> {
+        self.spans().get(path).copied()
+    }
+
+    /// The line and column where the value of the field at `path` starts in `input`, which
+    /// has to be the input that was deserialized.
+    pub fn locate<'a>(&'a self, path: &str, input: &str) -> Option<Location<'a>> {
+        let span = self.get(path)?;
+        let before = input.get(..span.start())?;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Some(Location {
+            source: self.source(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            span,
+        })
+    }
+
+    /// The number of fields set since they were last taken.
+    pub fn len(&self) -> usize {
+        self.spans().len()
+    }
+
+    /// Returns `true` if no field was set since they were last taken.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Takes the spans kept so far, by path, leaving the table empty.
+    pub fn take(&self) -> BTreeMap<String, Span<Cooked>> {
+        core::mem::take(&mut *self.spans())
+    }
+
+    fn spans(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Span<Cooked>>> {
+        // The table is always valid, even if a thread panicked while holding the lock
+        self.spans
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl FieldSpanSink for SpanTable {
+    fn field_span(&self, field: FieldSpan) {
+        self.spans().insert(field.path, field.span);
+    }
+}
+
+/// Where a value starts in a text input, from [`SpanTable::locate`]. It's displayed as
+/// `file:line:column`, or `line:column` without a file name.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location<'a> {
+    /// The file the input comes from, if the table was given one
+    pub source: Option<&'a str>,
+    /// The line the value starts on, from 1
+    pub line: usize,
+    /// The column the value starts at, in characters from 1
+    pub column: usize,
+    /// Where the value is in the input
+    pub span: Span<Cooked>,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(source) = self.source {
+            write!(f, "{source}:")?;
+        }
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
@@ -0,0 +1,81 @@
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+
+use crate::{Cooked, Span};
+
+/// An unknown field that was skipped instead of failing deserialization.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IgnoredField {
+    /// Where the field was found, e.g. `Config.server.timeout`
+    pub path: String,
+    /// The field's name, as it appears in the input
+    pub key: String,
+    /// Where the field's name is in the input
+    pub span: Span<Cooked>,
+}
+
+/// Told about every unknown field that is skipped, for types without
+/// `#[facet(deny_unknown_fields)]`.
+///
+/// Unknown fields are how schema drift between a client and a server usually shows: with
+/// one set on a [`Scratch`](crate::Scratch), they can be logged or counted without making
+/// deserialization any stricter. Closures taking an [`IgnoredField`] can be used directly.
+pub trait IgnoredFieldSink: Send + Sync {
+    /// Called once for each unknown field, in input order.
+    fn ignored_field(&self, field: IgnoredField);
+}
+
+impl<F> IgnoredFieldSink for F
+where
+    F: Fn(IgnoredField) + Send + Sync,
+{
+    fn ignored_field(&self, field: IgnoredField) {
+        self(field)
+    }
+}
+
+/// An [`IgnoredFieldSink`] that keeps every unknown field it's told about, until they're
+/// taken.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct IgnoredFieldLog {
+    fields: std::sync::Mutex<Vec<IgnoredField>>,
+}
+
+#[cfg(feature = "std")]
+impl IgnoredFieldLog {
+    /// Creates a log with no field in it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of fields logged since they were last taken.
+    pub fn len(&self) -> usize {
+        self.fields().len()
+    }
+
+    /// Returns `true` if no field was logged since they were last taken.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Takes the fields logged so far, leaving the log empty.
+    pub fn take(&self) -> Vec<IgnoredField> {
+        core::mem::take(&mut *self.fields())
+    }
+
+    fn fields(&self) -> std::sync::MutexGuard<'_, Vec<IgnoredField>> {
+        // The list is always valid, even if a thread panicked while holding the lock
+        self.fields
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl IgnoredFieldSink for IgnoredFieldLog {
+    fn ignored_field(&self, field: IgnoredField) {
+        self.fields().push(field);
+    }
+}
@@ -0,0 +1,58 @@
+use alloc::sync::Arc;
+
+/// Dedupes the strings read into `Arc<str>` fields and map keys, so that equal
+/// strings share one allocation.
+///
+/// Large documents often repeat the same few strings (enum-like values, map
+/// keys): with an interner set on a [`Scratch`](crate::Scratch), every
+/// `Arc<str>` read with it points at the interner's copy. The interner is
+/// shared, so one can be used for many documents, on many threads.
+pub trait Interner: Send + Sync {
+    /// Returns the shared copy of `s`, making one if there isn't one yet.
+    fn intern(&self, s: &str) -> Arc<str>;
+}
+
+/// An [`Interner`] that keeps every string it has seen, until it's dropped.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct StringInterner {
+    strings: std::sync::Mutex<std::collections::HashSet<Arc<str>>>,
+}
+
+#[cfg(feature = "std")]
+impl StringInterner {
+    /// Creates an interner that hasn't seen any string yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of different strings seen so far.
+    pub fn len(&self) -> usize {
+        self.strings().len()
+    }
+
+    /// Returns `true` if no string has been seen yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn strings(&self) -> std::sync::MutexGuard<'_, std::collections::HashSet<Arc<str>>> {
+        // The set is always valid, even if a thread panicked while holding the lock
+        self.strings
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Interner for StringInterner {
+    fn intern(&self, s: &str) -> Arc<str> {
+        let mut strings = self.strings();
+        if let Some(shared) = strings.get(s) {
+            return shared.clone();
+        }
+        let shared: Arc<str> = Arc::from(s);
+        strings.insert(shared.clone());
+        shared
+    }
+}
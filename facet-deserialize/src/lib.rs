@@ -7,8 +7,11 @@
 
 extern crate alloc;
 
-use alloc::string::ToString;
-use alloc::{vec, vec::Vec};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
 use core::fmt::Debug;
 
 mod debug;
@@ -18,14 +21,43 @@ pub use debug::InputDebug;
 
 pub use error::*;
 
+mod numeric;
+pub use numeric::*;
+
+mod scalar_run;
+use scalar_run::ScalarRun;
+
 mod span;
 use facet_core::{
-    Characteristic, Def, Facet, FieldFlags, PointerType, ScalarAffinity, StructKind, Type, UserType,
+    BitFlag, Characteristic, Def, Facet, FieldFlags, PointerType, Shape, SmartPointerFlags,
+    StructKind, Type, UserType,
 };
 use owo_colors::OwoColorize;
 pub use span::*;
 
-use facet_reflect::{HeapValue, Partial, ReflectError};
+mod events;
+pub use events::*;
+
+mod field_spans;
+pub use field_spans::*;
+
+mod ignored;
+pub use ignored::*;
+
+mod intern;
+pub use intern::*;
+
+mod observer;
+pub use observer::*;
+
+mod projection;
+use projection::Selection;
+pub use projection::*;
+
+mod transcode;
+pub use transcode::*;
+
+use facet_reflect::{HeapValue, Partial, Peek, ReflectError};
 use log::trace;
 
 #[derive(PartialEq, Debug, Clone)]
@@ -246,6 +278,37 @@ pub trait Format {
     >
     where
         'shape: 'input;
+
+    /// Whether an `Rc` or `Arc` can be written as `{"$ref": n}`, to share the `n`th such
+    /// value read so far. Values are numbered from 0, in the order their pointers start.
+    ///
+    /// This reads what `facet_serialize::Serializer::shared_refs` writes.
+    fn shared_refs(&self) -> bool {
+        false
+    }
+
+    /// The span of the input left after the top-level value, which ends at `offset`, or
+    /// `None` if nothing is left. What the format allows after a value, like whitespace in
+    /// JSON, doesn't count.
+    ///
+    /// Formats that don't implement this never have input left.
+    fn trailing<'input>(
+        &self,
+        _input: &'input Self::Input<'input>,
+        _offset: usize,
+    ) -> Option<Span<Self::SpanType>> {
+        None
+    }
+}
+
+/// What to do with input left after the top-level value, like the `b` of `{"a": 1} b`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingInput {
+    /// Fail with [`DeserErrorKind::TrailingData`].
+    #[default]
+    ErrorOnTrailing,
+    /// Stop after the value, leaving the rest of the input unread.
+    AllowTrailing,
 }
 
 /// Trait handling conversion regardless of `Format::SpanType` to `Span<Cooked>`
@@ -307,6 +370,24 @@ pub enum Instruction {
     ListItemOrListClose,
     /// Triggers clearing a substack.
     SubstackClose,
+    /// Expect the first key of an `Rc` or `Arc`, which tells `{"$ref": n}` from its value.
+    SharedPointerKey,
+    /// Expect the id in `{"$ref": n}`.
+    SharedRef,
+    /// Expect the end of `{"$ref": n}`.
+    SharedRefEnd,
+    /// Read the value of the pending key into the `#[facet(flatten)]` field at this index.
+    EnterFlattened(usize),
+    /// Go back to the struct that a flattened field was entered from.
+    LeaveFlattened,
+    /// Move the flattened fields of the struct whose object just ended into it.
+    FinishFlattened,
+    /// Go back to what the [`Projection`] selects of the struct that a field was entered from.
+    LeaveProjection,
+    /// Report the span of the field whose value was just read to the [`FieldSpanSink`].
+    EndFieldSpan,
+    /// Expect the name of a `#[facet(bitflags)]` flag or the end of the list of them.
+    FlagNameOrListClose,
 }
 
 /// Reasons for expecting a value, reflecting the current parse context.
@@ -331,6 +412,8 @@ pub enum PopReason {
     Some,
     /// Ending a smart pointer (ie. wrapping a `T` back into a `Box<T>`, or `Arc<T>` etc.)
     SmartPointer,
+    /// Ending an `Rc` or `Arc` whose value can be referenced later
+    SharedPointer,
     /// Ending a wrapper value such as a newtype
     Wrapper,
 }
@@ -341,11 +424,17 @@ mod deser_impl {
     /// Deserialize a value of type `T` from raw input bytes using format `F`.
     ///
     /// This function sets up the initial working state and drives the deserialization process,
-    /// ensuring that the resulting value is fully materialized and valid.
+    /// ensuring that the resulting value is fully materialized and valid. With a `seed`, the
+    /// value starts out as a clone of it, so the input only needs to hold what changes.
+    ///
+    /// Returns the value and where it ended in the input.
     pub fn deserialize<'input, 'facet, 'shape, T, F>(
         input: &'input F::Input<'input>,
         format: &mut F,
-    ) -> Result<T, DeserError<'input, 'shape, Cooked>>
+        scratch: &mut Scratch,
+        seed: Option<&T>,
+        trailing: TrailingInput,
+    ) -> Result<(T, usize), DeserError<'input, 'shape, Cooked>>
     where
         T: Facet<'facet>,
         F: Format + 'shape,
@@ -356,11 +445,15 @@ mod deser_impl {
         'shape: 'input,
     {
         // Run the entire deserialization process and capture any errors
-        let result: Result<T, DeserError<'input, 'shape, Cooked>> = {
+        let result: Result<(T, usize), DeserError<'input, 'shape, Cooked>> = {
             let source = format.source();
 
             // Step 1: Allocate shape
-            let wip = match Partial::alloc_shape(T::SHAPE) {
+            let wip = match seed {
+                Some(seed) => Partial::from_existing_peek(&Peek::new(seed)),
+                None => Partial::alloc_shape(T::SHAPE),
+            };
+            let wip = match wip {
                 Ok(wip) => wip,
                 Err(e) => {
                     let default_span = Span::<F::SpanType>::default();
@@ -371,26 +464,27 @@ mod deser_impl {
             };
 
             // Step 2: Run deserialize_wip
-            let heap_value = match deserialize_wip(wip, input, format) {
-                Ok(val) => val,
-                Err(e) => {
-                    let cooked_span = e.span.to_cooked(format, input);
-
-                    // Create a completely new error variable with the Cooked type
-                    let cooked_error = DeserError {
-                        input: e.input,
-                        span: cooked_span,
-                        kind: e.kind,
-                        source_id: e.source_id,
-                    };
-
-                    return Err(cooked_error);
-                }
-            };
+            let (heap_value, end) =
+                match deserialize_wip_impl(wip, input, format, scratch, trailing) {
+                    Ok(val) => val,
+                    Err(e) => {
+                        let cooked_span = e.span.to_cooked(format, input);
+
+                        // Create a completely new error variable with the Cooked type
+                        let cooked_error = DeserError {
+                            input: e.input,
+                            span: cooked_span,
+                            kind: e.kind,
+                            source_id: e.source_id,
+                        };
+
+                        return Err(cooked_error);
+                    }
+                };
 
             // Step 3: Materialize
             match heap_value.materialize() {
-                Ok(val) => Ok(val),
+                Ok(val) => Ok((val, end)),
                 Err(e) => {
                     let default_span = Span::<F::SpanType>::default();
                     let cooked_span = default_span.to_cooked(format, input);
@@ -438,15 +532,204 @@ where
     'shape: 'input,
 {
     let mut format_copy = format;
-    deser_impl::deserialize(input, &mut format_copy)
+    deser_impl::deserialize(
+        input,
+        &mut format_copy,
+        &mut Scratch::new(),
+        None,
+        TrailingInput::ErrorOnTrailing,
+    )
+    .map(|(value, _)| value)
+}
+
+/// Like [`deserialize`], but reuses the buffers in `scratch` instead of allocating new ones.
+///
+/// Keeping a [`Scratch`] around between documents saves a few allocations per document, which
+/// adds up when deserializing many small ones.
+pub fn deserialize_with<'input, 'facet, 'shape, T, F>(
+    input: &'input F::Input<'input>,
+    format: F,
+    scratch: &mut Scratch,
+) -> Result<T, DeserError<'input, 'shape, Cooked>>
+where
+    T: Facet<'facet>,
+    F: Format + 'shape,
+    F::Input<'input>: InputDebug,
+    F::SpanType: core::fmt::Debug,
+    Span<F::SpanType>: ToCooked<'input, F>,
+    'input: 'facet,
+    'shape: 'input,
+{
+    let mut format_copy = format;
+    deser_impl::deserialize(
+        input,
+        &mut format_copy,
+        scratch,
+        None,
+        TrailingInput::ErrorOnTrailing,
+    )
+    .map(|(value, _)| value)
+}
+
+/// Like [`deserialize_with`], with a choice of what to do with input left after the value.
+///
+/// Returns the value and how much of the input it took, in the units of the format's spans:
+/// bytes, for formats that read bytes. With [`TrailingInput::AllowTrailing`], the rest of the
+/// input can hold more values, to read from there.
+pub fn deserialize_with_trailing<'input, 'facet, 'shape, T, F>(
+    input: &'input F::Input<'input>,
+    format: F,
+    scratch: &mut Scratch,
+    trailing: TrailingInput,
+) -> Result<(T, usize), DeserError<'input, 'shape, Cooked>>
+where
+    T: Facet<'facet>,
+    F: Format + 'shape,
+    F::Input<'input>: InputDebug,
+    F::SpanType: core::fmt::Debug,
+    Span<F::SpanType>: ToCooked<'input, F>,
+    'input: 'facet,
+    'shape: 'input,
+{
+    let mut format_copy = format;
+    deser_impl::deserialize(input, &mut format_copy, scratch, None, trailing)
+}
+
+/// Deserialize raw input bytes using format `F` on top of an existing value.
+///
+/// The value is updated only where the input says so: fields missing from the input keep
+/// their current values, and nested structs are updated field by field rather than replaced.
+/// Anything else that the input mentions, such as a list or an enum, is replaced as a whole.
+/// `T` must implement `Clone`, since deserialization starts from a copy of `target`, which is
+/// left untouched if it fails.
+pub fn deserialize_into<'input, 'facet, 'shape, T, F>(
+    target: &mut T,
+    input: &'input F::Input<'input>,
+    format: F,
+) -> Result<(), DeserError<'input, 'shape, Cooked>>
+where
+    T: Facet<'facet>,
+    F: Format + 'shape,
+    F::Input<'input>: InputDebug,
+    F::SpanType: core::fmt::Debug,
+    Span<F::SpanType>: ToCooked<'input, F>,
+    'input: 'facet,
+    'shape: 'input,
+{
+    let mut format_copy = format;
+    let (value, _) = deser_impl::deserialize(
+        input,
+        &mut format_copy,
+        &mut Scratch::new(),
+        Some(&*target),
+        TrailingInput::ErrorOnTrailing,
+    )?;
+    *target = value;
+    Ok(())
+}
+
+/// Buffers used while deserializing, which can be kept between documents.
+///
+/// The buffers are only given back when deserialization succeeds: after an error, the next
+/// document starts with empty ones.
+///
+/// A `Scratch` can also hold an [`Interner`], an [`IgnoredFieldSink`], a [`FieldSpanSink`],
+/// a [`Projection`] and a [`DeserObserver`], which are kept after errors too.
+#[derive(Default)]
+pub struct Scratch {
+    stack: Vec<Instruction>,
+    array_indices: Vec<usize>,
+    interner: Option<Arc<dyn Interner>>,
+    ignored_fields: Option<Arc<dyn IgnoredFieldSink>>,
+    field_spans: Option<Arc<dyn FieldSpanSink>>,
+    projection: Option<Arc<Projection>>,
+    observer: Option<Arc<dyn DeserObserver>>,
+}
+
+impl core::fmt::Debug for Scratch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Scratch")
+            .field("stack", &self.stack)
+            .field("array_indices", &self.array_indices)
+            .field("interner", &self.interner.is_some())
+            .field("ignored_fields", &self.ignored_fields.is_some())
+            .field("field_spans", &self.field_spans.is_some())
+            .field("projection", &self.projection)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+impl Scratch {
+    /// Creates empty buffers; they grow as needed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads `Arc<str>` fields and map keys through `interner`, so that equal strings share
+    /// one allocation, within a document and across documents.
+    pub fn with_interner(mut self, interner: Arc<dyn Interner>) -> Self {
+        self.interner = Some(interner);
+        self
+    }
+
+    /// Tells `sink` about every unknown field that is skipped, with where it was found.
+    pub fn with_ignored_fields(mut self, sink: Arc<dyn IgnoredFieldSink>) -> Self {
+        self.ignored_fields = Some(sink);
+        self
+    }
+
+    /// Tells `sink` where the value of every field that is set comes from in the input.
+    pub fn with_field_spans(mut self, sink: Arc<dyn FieldSpanSink>) -> Self {
+        self.field_spans = Some(sink);
+        self
+    }
+
+    /// Only reads the fields that `projection` selects, skipping the others and leaving them
+    /// to their default.
+    pub fn with_projection(mut self, projection: Arc<Projection>) -> Self {
+        self.projection = Some(projection);
+        self
+    }
+
+    /// Tells `observer` about each step of deserialization, instead of logging them.
+    pub fn with_observer(mut self, observer: Arc<dyn DeserObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    fn recycle<C, I: ?Sized>(&mut self, runner: StackRunner<'_, C, I>) {
+        self.stack = runner.stack;
+        self.stack.clear();
+        self.array_indices = runner.array_indices;
+        self.array_indices.clear();
+    }
 }
 
 /// Deserializes a working-in-progress value into a fully materialized heap value.
 /// This function drives the parsing loop until the entire input is consumed and the value is complete.
 pub fn deserialize_wip<'input, 'facet, 'shape, F>(
-    mut wip: Partial<'facet, 'shape>,
+    wip: Partial<'facet, 'shape>,
+    input: &'input F::Input<'input>,
+    format: &mut F,
+) -> Result<HeapValue<'facet, 'shape>, DeserError<'input, 'shape, Cooked>>
+where
+    F: Format + 'shape,
+    F::SpanType: SubstackBehavior,
+    F::Input<'input>: InputDebug,
+    Span<F::SpanType>: ToCooked<'input, F>,
+    'input: 'facet,
+    'shape: 'input,
+{
+    deserialize_wip_with(wip, input, format, &mut Scratch::new())
+}
+
+/// Like [`deserialize_wip`], but reuses the buffers in `scratch` instead of allocating new ones.
+pub fn deserialize_wip_with<'input, 'facet, 'shape, F>(
+    wip: Partial<'facet, 'shape>,
     input: &'input F::Input<'input>,
     format: &mut F,
+    scratch: &mut Scratch,
 ) -> Result<HeapValue<'facet, 'shape>, DeserError<'input, 'shape, Cooked>>
 where
     F: Format + 'shape,
@@ -456,30 +739,114 @@ where
     'input: 'facet,
     'shape: 'input,
 {
+    deserialize_wip_impl(wip, input, format, scratch, TrailingInput::ErrorOnTrailing)
+        .map(|(value, _)| value)
+}
+
+/// Drives the parsing loop, returning the value and where it ended in the input
+fn deserialize_wip_impl<'input, 'facet, 'shape, F>(
+    wip: Partial<'facet, 'shape>,
+    input: &'input F::Input<'input>,
+    format: &mut F,
+    scratch: &mut Scratch,
+    trailing: TrailingInput,
+) -> Result<(HeapValue<'facet, 'shape>, usize), DeserError<'input, 'shape, Cooked>>
+where
+    F: Format + 'shape,
+    F::SpanType: SubstackBehavior,
+    F::Input<'input>: InputDebug,
+    Span<F::SpanType>: ToCooked<'input, F>,
+    'input: 'facet,
+    'shape: 'input,
+{
+    let observer = scratch.observer.clone();
+    let observer = observer.as_deref().unwrap_or(&LogObserver);
+    run_stack(wip, input, format, scratch, trailing, observer)
+        .inspect_err(|error| observer.on_error(error))
+}
+
+/// The parsing loop of [`deserialize_wip_impl`], which tells `observer` about each step
+fn run_stack<'input, 'facet, 'shape, F>(
+    mut wip: Partial<'facet, 'shape>,
+    input: &'input F::Input<'input>,
+    format: &mut F,
+    scratch: &mut Scratch,
+    trailing: TrailingInput,
+    observer: &dyn DeserObserver,
+) -> Result<(HeapValue<'facet, 'shape>, usize), DeserError<'input, 'shape, Cooked>>
+where
+    F: Format + 'shape,
+    F::SpanType: SubstackBehavior,
+    F::Input<'input>: InputDebug,
+    Span<F::SpanType>: ToCooked<'input, F>,
+    'input: 'facet,
+    'shape: 'input,
+{
+    let mut stack = core::mem::take(&mut scratch.stack);
+    stack.extend([
+        Instruction::Pop(PopReason::TopLevel),
+        Instruction::Value(ValueReason::TopLevel),
+    ]);
+
     // This struct is just a bundle of the state that we need to pass around all the time.
     let mut runner = StackRunner {
         original_input: input,
         input,
-        stack: vec![
-            Instruction::Pop(PopReason::TopLevel),
-            Instruction::Value(ValueReason::TopLevel),
-        ],
+        stack,
         substack: Substack::new(),
         last_span: Span::new(0, 0),
         format_source: format.source(),
-        array_indices: Vec::new(),
+        array_indices: core::mem::take(&mut scratch.array_indices),
         enum_tuple_field_count: None,
         enum_tuple_current_field: None,
+        shared_refs: format.shared_refs(),
+        shared_count: 0,
+        shared_ids: Vec::new(),
+        pending_ref: None,
+        pending_flags: None,
+        pending_flattened_key: None,
+        interner: scratch.interner.clone(),
+        ignored_fields: scratch.ignored_fields.clone(),
+        pending_ignored_field: None,
+        field_spans: scratch.field_spans.clone(),
+        open_field_spans: Vec::new(),
+        selections: match scratch.projection {
+            Some(_) => alloc::vec![Selection::Part(0)],
+            None => Vec::new(),
+        },
+        projection: scratch.projection.clone(),
+        scalar_run: None,
+        // Each item has to go through the usual steps to be seen
+        bulk_scalars: scratch.observer.is_none() && scratch.field_spans.is_none(),
     };
+    // Values of the `Rc`s and `Arc`s read so far, by id, for `shared_refs`
+    let mut shared: Vec<Option<HeapValue<'facet, 'shape>>> = Vec::new();
+    // Flattened fields being read, and the Partials they were entered from, innermost last
+    let mut flattened: Vec<FlattenedField<'facet, 'shape>> = Vec::new();
+    let mut suspended: Vec<FlattenedField<'facet, 'shape>> = Vec::new();
+
+    /// An error for an invariant of the deserializer that didn't hold
+    macro_rules! internal {
+        ($invariant:expr) => {{
+            let error = runner.internal_err($invariant, wip.shape());
+            DeserError {
+                input: error.input,
+                span: error.span.to_cooked(format, input),
+                kind: error.kind,
+                source_id: error.source_id,
+            }
+        }};
+    }
 
     macro_rules! next {
         ($runner:ident, $wip:ident, $expectation:expr, $method:ident) => {{
+            let expectation = $expectation;
             let nd = NextData {
                 start: $runner.last_span.end(), // or supply the appropriate start value if available
                 runner: $runner,
                 wip: $wip,
             };
-            let (nd, res) = format.next(nd, $expectation);
+            let (nd, res) = format.next(nd, expectation.clone());
             $runner = nd.runner;
             $wip = nd.wip;
             let outcome = res.map_err(|span_kind| {
@@ -493,13 +860,7 @@ where
                     source_id: error.source_id,
                 }
             })?;
-            if F::SpanType::USES_SUBSTACK {
-                if !$runner.substack.get().is_empty() {
-                    trace!("Substack: {}", "carried".cyan());
-                } else {
-                    trace!("Substack: {}", "-".red());
-                }
-            }
+            observer.on_outcome(&expectation, &outcome.node, outcome.span.to_cooked(format, input));
             $runner.last_span = outcome.span;
             if F::SpanType::USES_SUBSTACK {
                 if let Outcome::Resegmented(subspans) = &outcome.node {
@@ -521,12 +882,11 @@ where
         // Note: frames_count() is no longer available in the new Partial API
         // This was used for debugging/assertions only
 
-        let insn = match runner.stack.pop() {
-            Some(insn) => insn,
-            None => unreachable!("Instruction stack is empty"),
+        let Some(insn) = runner.stack.pop() else {
+            return Err(internal!("the instruction stack is empty"));
         };
 
-        trace!("Instruction {:?}", insn.bright_red());
+        observer.on_instruction(&insn);
 
         match insn {
             Instruction::Pop(reason) => {
@@ -554,7 +914,7 @@ where
                         })?;
                     }
 
-                    return wip.build().map_err(|e| {
+                    let built = wip.build().map_err(|e| {
                         let reflect_error = runner.reflect_err(e);
                         // Convert the reflection error's span to Cooked
                         DeserError {
@@ -563,7 +923,22 @@ where
                             kind: reflect_error.kind,
                             source_id: reflect_error.source_id,
                         }
-                    });
+                    })?;
+                    let end = runner.last_span.end();
+                    if trailing == TrailingInput::ErrorOnTrailing {
+                        if let Some(span) = format.trailing(input, end) {
+                            runner.last_span = span;
+                            let error = runner.err(DeserErrorKind::TrailingData);
+                            return Err(DeserError {
+                                input: error.input,
+                                span: error.span.to_cooked(format, input),
+                                kind: error.kind,
+                                source_id: error.source_id,
+                            });
+                        }
+                    }
+                    scratch.recycle(runner);
+                    return Ok((built, end));
                 } else {
                     wip.end().map_err(|e| {
                         let reflect_error = runner.reflect_err(e);
@@ -575,6 +950,33 @@ where
                             source_id: reflect_error.source_id,
                         }
                     })?;
+
+                    if reason == PopReason::SharedPointer {
+                        // Keep a clone of the pointer, for references to it
+                        let Some(id) = runner.shared_ids.pop() else {
+                            return Err(internal!("a shared pointer ends after it started"));
+                        };
+                        let value = wip
+                            .peek_current()
+                            .and_then(|peek| {
+                                let mut value = Partial::alloc_shape(peek.shape())?;
+                                value.clone_from_peek(&peek)?;
+                                value.build()
+                            })
+                            .map_err(|e| {
+                                let reflect_error = runner.reflect_err(e);
+                                DeserError {
+                                    input: reflect_error.input,
+                                    span: reflect_error.span.to_cooked(format, input),
+                                    kind: reflect_error.kind,
+                                    source_id: reflect_error.source_id,
+                                }
+                            })?;
+                        if shared.len() <= id {
+                            shared.resize_with(id + 1, || None);
+                        }
+                        shared[id] = Some(value);
+                    }
                 }
             }
             Instruction::Value(_why) => {
@@ -583,6 +985,10 @@ where
                     ValueReason::ObjectVal => Expectation::ObjectVal,
                 };
                 next!(runner, wip, expectation, value);
+                // The first value read for a field is where the field's value starts
+                if let Some((_, start @ None)) = runner.open_field_spans.last_mut() {
+                    *start = Some(runner.last_span);
+                }
             }
             Instruction::ObjectKeyOrObjectClose => {
                 next!(
@@ -600,10 +1006,71 @@ where
                     list_item_or_list_close
                 );
             }
+            Instruction::FlagNameOrListClose => {
+                next!(
+                    runner,
+                    wip,
+                    Expectation::ListItemOrListClose,
+                    flag_name_or_list_close
+                );
+            }
             Instruction::SubstackClose => {
                 runner.substack.clear();
             }
+            Instruction::SharedPointerKey => {
+                next!(
+                    runner,
+                    wip,
+                    Expectation::ObjectKeyOrObjectClose,
+                    shared_pointer_key
+                );
+            }
+            Instruction::SharedRef => {
+                next!(runner, wip, Expectation::ObjectVal, shared_ref);
+            }
+            Instruction::SharedRefEnd => {
+                next!(
+                    runner,
+                    wip,
+                    Expectation::ObjectKeyOrObjectClose,
+                    shared_ref_end
+                );
+                let Some(id) = runner.pending_ref.take() else {
+                    return Err(internal!("a `$ref` id was read before its end"));
+                };
+                let Some(value) = usize::try_from(id)
+                    .ok()
+                    .and_then(|id| shared.get(id))
+                    .and_then(Option::as_ref)
+                else {
+                    let error = runner.err(DeserErrorKind::UnknownSharedRef(id));
+                    return Err(DeserError {
+                        input: error.input,
+                        span: error.span.to_cooked(format, input),
+                        kind: error.kind,
+                        source_id: error.source_id,
+                    });
+                };
+                wip.clone_from_peek(&value.peek()).map_err(|e| {
+                    let reflect_error = runner.reflect_err(e);
+                    DeserError {
+                        input: reflect_error.input,
+                        span: reflect_error.span.to_cooked(format, input),
+                        kind: reflect_error.kind,
+                        source_id: reflect_error.source_id,
+                    }
+                })?;
+            }
             Instruction::SkipValue => {
+                if let Some((path, key, span)) = runner.pending_ignored_field.take() {
+                    if let Some(sink) = &runner.ignored_fields {
+                        sink.ignored_field(IgnoredField {
+                            path,
+                            key,
+                            span: span.to_cooked(format, input),
+                        });
+                    }
+                }
                 // Call F::skip to skip over the next value in the input
                 let nd = NextData {
                     start: runner.last_span.end(),
@@ -628,364 +1095,200 @@ where
                 // do the actual skip
                 runner.last_span = span;
             }
+            Instruction::EnterFlattened(index) => {
+                let owner = (suspended.len(), wip.frame_count());
+                let position = flattened
+                    .iter()
+                    .position(|field| field.owner == owner && field.index == index);
+                let field_wip = match position {
+                    Some(position) => flattened.swap_remove(position).wip,
+                    None => {
+                        runner
+                            .begin_flattened(&mut wip, index)
+                            .map_err(|error| DeserError {
+                                input: error.input,
+                                span: error.span.to_cooked(format, input),
+                                kind: error.kind,
+                                source_id: error.source_id,
+                            })?
+                    }
+                };
+                let parent = core::mem::replace(&mut wip, field_wip);
+                suspended.push(FlattenedField {
+                    owner,
+                    index,
+                    wip: parent,
+                });
+                let Some(key) = runner.pending_flattened_key.take() else {
+                    return Err(internal!("a flattened field is entered with its key"));
+                };
+                wip = runner.object_key(wip, key).map_err(|error| DeserError {
+                    input: error.input,
+                    span: error.span.to_cooked(format, input),
+                    kind: error.kind,
+                    source_id: error.source_id,
+                })?;
+            }
+            Instruction::LeaveFlattened => {
+                let Some(parent) = suspended.pop() else {
+                    return Err(internal!("a flattened field is left after it was entered"));
+                };
+                let field_wip = core::mem::replace(&mut wip, parent.wip);
+                flattened.push(FlattenedField {
+                    wip: field_wip,
+                    ..parent
+                });
+            }
+            Instruction::LeaveProjection => {
+                runner.selections.pop();
+            }
+            Instruction::EndFieldSpan => {
+                let Some((path, Some(start))) = runner.open_field_spans.pop() else {
+                    return Err(internal!("a field span ends after its value started"));
+                };
+                if let Some(sink) = &runner.field_spans {
+                    let start = start.to_cooked(format, input).start();
+                    let end = runner.last_span.to_cooked(format, input).end();
+                    sink.field_span(FieldSpan {
+                        path,
+                        span: Span::new(start, end.saturating_sub(start)),
+                    });
+                }
+            }
+            Instruction::FinishFlattened => {
+                wip = runner
+                    .finish_flattened(wip, suspended.len(), &mut flattened)
+                    .map_err(|error| DeserError {
+                        input: error.input,
+                        span: error.span.to_cooked(format, input),
+                        kind: error.kind,
+                        source_id: error.source_id,
+                    })?;
+            }
         }
     }
 }
 
-/// Helper function to check if an f64 has no fractional part
-/// This is needed for no-std compatibility where f64::fract() is not available
-#[inline]
-fn has_no_fractional_part(value: f64) -> bool {
-    value == (value as i64) as f64
-}
-
-/// Trait for numeric type conversions
-trait NumericConvert: Sized {
-    const TYPE_NAME: &'static str;
-
-    fn to_i8(self) -> Option<i8>;
-    fn to_i16(self) -> Option<i16>;
-    fn to_i32(self) -> Option<i32>;
-    fn to_i64(self) -> Option<i64>;
-    fn to_i128(self) -> Option<i128>;
-    fn to_isize(self) -> Option<isize>;
-
-    fn to_u8(self) -> Option<u8>;
-    fn to_u16(self) -> Option<u16>;
-    fn to_u32(self) -> Option<u32>;
-    fn to_u64(self) -> Option<u64>;
-    fn to_u128(self) -> Option<u128>;
-    fn to_usize(self) -> Option<usize>;
-
-    fn to_f32(self) -> Option<f32>;
-    fn to_f64(self) -> Option<f64>;
+/// A `#[facet(flatten)]` field being built apart from its struct, until the struct's object ends
+struct FlattenedField<'facet, 'shape> {
+    /// The struct the field belongs to: how many flattened fields were entered to reach it, and
+    /// its frame count
+    owner: (usize, usize),
+    /// Index of the field in the struct
+    index: usize,
+    wip: Partial<'facet, 'shape>,
 }
 
-impl NumericConvert for u64 {
-    const TYPE_NAME: &'static str = "u64";
-
-    fn to_i8(self) -> Option<i8> {
-        self.try_into().ok()
-    }
-    fn to_i16(self) -> Option<i16> {
-        self.try_into().ok()
-    }
-    fn to_i32(self) -> Option<i32> {
-        self.try_into().ok()
-    }
-    fn to_i64(self) -> Option<i64> {
-        self.try_into().ok()
-    }
-    fn to_i128(self) -> Option<i128> {
-        Some(self as i128)
-    }
-    fn to_isize(self) -> Option<isize> {
-        self.try_into().ok()
+/// Whether `null` is a value of `shape`: an `Option`, or a type without fields like `()`,
+/// maybe in a transparent wrapper. Fields with a default also accept `null`, which gives
+/// them their default.
+fn accepts_null(shape: &Shape<'_>) -> bool {
+    match (shape.def, shape.ty) {
+        (Def::Option(_), _) => true,
+        // `Weak`s are written as `null`, since what they point at is owned elsewhere
+        (Def::SmartPointer(sp), _) if sp.flags.contains(SmartPointerFlags::WEAK) => true,
+        (_, Type::User(UserType::Struct(st))) if st.fields.is_empty() => true,
+        _ => shape.inner.is_some_and(|inner| accepts_null(inner())),
     }
+}
 
-    fn to_u8(self) -> Option<u8> {
-        self.try_into().ok()
-    }
-    fn to_u16(self) -> Option<u16> {
-        self.try_into().ok()
-    }
-    fn to_u32(self) -> Option<u32> {
-        self.try_into().ok()
-    }
-    fn to_u64(self) -> Option<u64> {
-        Some(self)
-    }
-    fn to_u128(self) -> Option<u128> {
-        Some(self as u128)
-    }
-    fn to_usize(self) -> Option<usize> {
-        self.try_into().ok()
-    }
+/// Whether `wip` is a C-like or `#[facet(discriminant)]` enum with no variant picked yet,
+/// which can be read from an integer
+fn is_discriminant_enum(wip: &Partial<'_, '_>) -> bool {
+    let shape = wip.innermost_shape();
+    matches!(shape.ty, Type::User(UserType::Enum(ed)) if ed.is_c_like() || shape.has_discriminant_attr())
+        && wip.selected_variant().is_none()
+}
 
-    fn to_f32(self) -> Option<f32> {
-        Some(self as f32)
-    }
-    fn to_f64(self) -> Option<f64> {
-        Some(self as f64)
+/// The index of the `#[facet(other)]` variant of `shape`, if it's an enum that has one
+fn other_variant_index(shape: &Shape<'_>) -> Option<usize> {
+    match shape.ty {
+        Type::User(UserType::Enum(ed)) => ed.other_variant_index(),
+        _ => None,
     }
 }
 
-impl NumericConvert for i64 {
-    const TYPE_NAME: &'static str = "i64";
+#[doc(hidden)]
+/// Maintains the parsing state and context necessary to drive deserialization.
+///
+/// This struct tracks what the parser expects next, manages input position,
+/// and remembers the span of the last processed token to provide accurate error reporting.
+pub struct StackRunner<'input, C = Cooked, I: ?Sized + 'input = [u8]> {
+    /// A version of the input that doesn't advance as we parse.
+    pub original_input: &'input I,
 
-    fn to_i8(self) -> Option<i8> {
-        self.try_into().ok()
-    }
-    fn to_i16(self) -> Option<i16> {
-        self.try_into().ok()
-    }
-    fn to_i32(self) -> Option<i32> {
-        self.try_into().ok()
-    }
-    fn to_i64(self) -> Option<i64> {
-        Some(self)
-    }
-    fn to_i128(self) -> Option<i128> {
-        Some(self as i128)
-    }
-    fn to_isize(self) -> Option<isize> {
-        self.try_into().ok()
-    }
+    /// The raw input data being deserialized.
+    pub input: &'input I,
 
-    fn to_u8(self) -> Option<u8> {
-        self.try_into().ok()
-    }
-    fn to_u16(self) -> Option<u16> {
-        self.try_into().ok()
-    }
-    fn to_u32(self) -> Option<u32> {
-        self.try_into().ok()
-    }
-    fn to_u64(self) -> Option<u64> {
-        self.try_into().ok()
-    }
-    fn to_u128(self) -> Option<u128> {
-        self.try_into().ok()
-    }
-    fn to_usize(self) -> Option<usize> {
-        self.try_into().ok()
-    }
+    /// Stack of parsing instructions guiding the control flow.
+    pub stack: Vec<Instruction>,
 
-    fn to_f32(self) -> Option<f32> {
-        Some(self as f32)
-    }
-    fn to_f64(self) -> Option<f64> {
-        Some(self as f64)
-    }
-}
-
-impl NumericConvert for f64 {
-    const TYPE_NAME: &'static str = "f64";
+    /// Subspan storage, if the format uses them.
+    pub substack: Substack<C>,
 
-    fn to_i8(self) -> Option<i8> {
-        if has_no_fractional_part(self) && self >= i8::MIN as f64 && self <= i8::MAX as f64 {
-            Some(self as i8)
-        } else {
-            None
-        }
-    }
-    fn to_i16(self) -> Option<i16> {
-        if has_no_fractional_part(self) && self >= i16::MIN as f64 && self <= i16::MAX as f64 {
-            Some(self as i16)
-        } else {
-            None
-        }
-    }
-    fn to_i32(self) -> Option<i32> {
-        if has_no_fractional_part(self) && self >= i32::MIN as f64 && self <= i32::MAX as f64 {
-            Some(self as i32)
-        } else {
-            None
-        }
-    }
-    fn to_i64(self) -> Option<i64> {
-        if has_no_fractional_part(self) && self >= i64::MIN as f64 && self <= i64::MAX as f64 {
-            Some(self as i64)
-        } else {
-            None
-        }
-    }
-    fn to_i128(self) -> Option<i128> {
-        if has_no_fractional_part(self) && self >= i128::MIN as f64 && self <= i128::MAX as f64 {
-            Some(self as i128)
-        } else {
-            None
-        }
-    }
-    fn to_isize(self) -> Option<isize> {
-        if has_no_fractional_part(self) && self >= isize::MIN as f64 && self <= isize::MAX as f64 {
-            Some(self as isize)
-        } else {
-            None
-        }
-    }
+    /// Span of the last processed token, for accurate error reporting.
+    pub last_span: Span<C>,
 
-    fn to_u8(self) -> Option<u8> {
-        if has_no_fractional_part(self) && self >= 0.0 && self <= u8::MAX as f64 {
-            Some(self as u8)
-        } else {
-            None
-        }
-    }
-    fn to_u16(self) -> Option<u16> {
-        if has_no_fractional_part(self) && self >= 0.0 && self <= u16::MAX as f64 {
-            Some(self as u16)
-        } else {
-            None
-        }
-    }
-    fn to_u32(self) -> Option<u32> {
-        if has_no_fractional_part(self) && self >= 0.0 && self <= u32::MAX as f64 {
-            Some(self as u32)
-        } else {
-            None
-        }
-    }
-    fn to_u64(self) -> Option<u64> {
-        if has_no_fractional_part(self) && self >= 0.0 && self <= u64::MAX as f64 {
-            Some(self as u64)
-        } else {
-            None
-        }
-    }
-    fn to_u128(self) -> Option<u128> {
-        if has_no_fractional_part(self) && self >= 0.0 && self <= u128::MAX as f64 {
-            Some(self as u128)
-        } else {
-            None
-        }
-    }
-    fn to_usize(self) -> Option<usize> {
-        if has_no_fractional_part(self) && self >= 0.0 && self <= usize::MAX as f64 {
-            Some(self as usize)
-        } else {
-            None
-        }
-    }
+    /// Format source identifier for error reporting
+    pub format_source: &'static str,
 
-    fn to_f32(self) -> Option<f32> {
-        Some(self as f32)
-    }
-    fn to_f64(self) -> Option<f64> {
-        Some(self)
-    }
-}
+    /// Array index tracking - maps depth to current index for arrays
+    pub array_indices: Vec<usize>,
 
-impl NumericConvert for u128 {
-    const TYPE_NAME: &'static str = "u128";
+    /// Tuple variant field tracking - number of fields in current enum tuple variant
+    pub enum_tuple_field_count: Option<usize>,
 
-    fn to_i8(self) -> Option<i8> {
-        self.try_into().ok()
-    }
-    fn to_i16(self) -> Option<i16> {
-        self.try_into().ok()
-    }
-    fn to_i32(self) -> Option<i32> {
-        self.try_into().ok()
-    }
-    fn to_i64(self) -> Option<i64> {
-        self.try_into().ok()
-    }
-    fn to_i128(self) -> Option<i128> {
-        Some(self as i128)
-    }
-    fn to_isize(self) -> Option<isize> {
-        self.try_into().ok()
-    }
+    /// Tuple variant field tracking - current field index being processed
+    pub enum_tuple_current_field: Option<usize>,
 
-    fn to_u8(self) -> Option<u8> {
-        self.try_into().ok()
-    }
-    fn to_u16(self) -> Option<u16> {
-        self.try_into().ok()
-    }
-    fn to_u32(self) -> Option<u32> {
-        self.try_into().ok()
-    }
-    fn to_u64(self) -> Option<u64> {
-        self.try_into().ok()
-    }
-    fn to_u128(self) -> Option<u128> {
-        Some(self)
-    }
-    fn to_usize(self) -> Option<usize> {
-        self.try_into().ok()
-    }
+    /// Whether `Rc`s and `Arc`s can be read from `{"$ref": n}`
+    pub shared_refs: bool,
 
-    fn to_f32(self) -> Option<f32> {
-        Some(self as f32)
-    }
-    fn to_f64(self) -> Option<f64> {
-        Some(self as f64)
-    }
-}
+    /// Number of `Rc`s and `Arc`s started so far, which is the id of the next one
+    pub shared_count: usize,
 
-impl NumericConvert for i128 {
-    const TYPE_NAME: &'static str = "i128";
+    /// Ids of the `Rc`s and `Arc`s being read
+    pub shared_ids: Vec<usize>,
 
-    fn to_i8(self) -> Option<i8> {
-        self.try_into().ok()
-    }
-    fn to_i16(self) -> Option<i16> {
-        self.try_into().ok()
-    }
-    fn to_i32(self) -> Option<i32> {
-        self.try_into().ok()
-    }
-    fn to_i64(self) -> Option<i64> {
-        self.try_into().ok()
-    }
-    fn to_i128(self) -> Option<i128> {
-        Some(self)
-    }
-    fn to_isize(self) -> Option<isize> {
-        self.try_into().ok()
-    }
+    /// The id read from a `{"$ref": n}` that isn't finished yet
+    pub pending_ref: Option<u64>,
 
-    fn to_u8(self) -> Option<u8> {
-        self.try_into().ok()
-    }
-    fn to_u16(self) -> Option<u16> {
-        self.try_into().ok()
-    }
-    fn to_u32(self) -> Option<u32> {
-        self.try_into().ok()
-    }
-    fn to_u64(self) -> Option<u64> {
-        self.try_into().ok()
-    }
-    fn to_u128(self) -> Option<u128> {
-        self.try_into().ok()
-    }
-    fn to_usize(self) -> Option<usize> {
-        self.try_into().ok()
-    }
+    /// The bits of the `#[facet(bitflags)]` flags named so far, by
+    /// [`Instruction::FlagNameOrListClose`]
+    pub pending_flags: Option<u64>,
 
-    fn to_f32(self) -> Option<f32> {
-        Some(self as f32)
-    }
-    fn to_f64(self) -> Option<f64> {
-        Some(self as f64)
-    }
-}
+    /// The key whose value is read into a flattened field, by [`Instruction::EnterFlattened`]
+    pub pending_flattened_key: Option<Cow<'input, str>>,
 
-#[doc(hidden)]
-/// Maintains the parsing state and context necessary to drive deserialization.
-///
-/// This struct tracks what the parser expects next, manages input position,
-/// and remembers the span of the last processed token to provide accurate error reporting.
-pub struct StackRunner<'input, C = Cooked, I: ?Sized + 'input = [u8]> {
-    /// A version of the input that doesn't advance as we parse.
-    pub original_input: &'input I,
+    /// Where `Arc<str>`s are made, if they're deduped
+    pub interner: Option<Arc<dyn Interner>>,
 
-    /// The raw input data being deserialized.
-    pub input: &'input I,
+    /// Where skipped unknown fields are reported, if anywhere
+    pub ignored_fields: Option<Arc<dyn IgnoredFieldSink>>,
 
-    /// Stack of parsing instructions guiding the control flow.
-    pub stack: Vec<Instruction>,
+    /// The path, name and span of the unknown field whose value is skipped next, by
+    /// [`Instruction::SkipValue`]
+    pub pending_ignored_field: Option<(String, String, Span<C>)>,
 
-    /// Subspan storage, if the format uses them.
-    pub substack: Substack<C>,
+    /// Where field spans are reported, if anywhere
+    pub field_spans: Option<Arc<dyn FieldSpanSink>>,
 
-    /// Span of the last processed token, for accurate error reporting.
-    pub last_span: Span<C>,
+    /// The path of each field whose value is being read, and where the value started once it
+    /// has, innermost last, until [`Instruction::EndFieldSpan`]
+    pub(crate) open_field_spans: Vec<(String, Option<Span<C>>)>,
 
-    /// Format source identifier for error reporting
-    pub format_source: &'static str,
+    /// The fields to read, if not all of them
+    pub projection: Option<Arc<Projection>>,
 
-    /// Array index tracking - maps depth to current index for arrays
-    pub array_indices: Vec<usize>,
+    /// What the projection selects of each struct entered, innermost last
+    pub(crate) selections: Vec<Selection>,
 
-    /// Tuple variant field tracking - number of fields in current enum tuple variant
-    pub enum_tuple_field_count: Option<usize>,
+    /// Items of the list of numbers or booleans being read, not added to it yet
+    pub(crate) scalar_run: Option<ScalarRun>,
 
-    /// Tuple variant field tracking - current field index being processed
-    pub enum_tuple_current_field: Option<usize>,
+    /// Whether lists of numbers and booleans are filled in bulk, which skips the instructions
+    /// of each item. Off when an observer or a field span sink is set.
+    pub(crate) bulk_scalars: bool,
 }
 
 impl<'input, 'shape, C, I: ?Sized + 'input> StackRunner<'input, C, I>
@@ -1002,6 +1305,20 @@ where
         )
     }
 
+    /// Creates an error for an `invariant` of the deserializer that didn't hold, with the
+    /// instructions that were left.
+    fn internal_err(
+        &self,
+        invariant: &'static str,
+        shape: &'shape Shape<'shape>,
+    ) -> DeserError<'input, 'shape, C> {
+        self.err(DeserErrorKind::InternalError {
+            invariant,
+            shape,
+            stack: self.stack.clone(),
+        })
+    }
+
     /// Convenience function to create a DeserError from a ReflectError,
     /// using the original input and last_span for context.
     fn reflect_err(&self, err: ReflectError<'shape>) -> DeserError<'input, 'shape, C> {
@@ -1036,32 +1353,55 @@ where
                             wip.begin_nth_field(index)
                                 .map_err(|e| self.reflect_err(e))?;
 
-                            // Check for field-level default function first, then type-level default
-                            if let Some(field_default_fn) = field.vtable.default_fn {
-                                wip.set_field_default(field_default_fn)
-                                    .map_err(|e| self.reflect_err(e))?;
-                                trace!(
-                                    "Field #{} {} @ {} was set to default value (via field default function)",
-                                    index.yellow(),
-                                    field.name.green(),
-                                    field.offset.blue(),
-                                );
-                            } else if field.shape().is(Characteristic::Default) {
-                                wip.set_default().map_err(|e| self.reflect_err(e))?;
-                                trace!(
-                                    "Field #{} {} @ {} was set to default value (via type default impl)",
-                                    index.yellow(),
-                                    field.name.green(),
-                                    field.offset.blue(),
-                                );
-                            } else {
-                                return Err(self.reflect_err(
-                                    ReflectError::DefaultAttrButNoDefaultImpl {
-                                        shape: field.shape(),
-                                    },
-                                ));
-                            }
+                            wip.set_default_of_field(field)
+                                .map_err(|e| self.reflect_err(e))?;
+                            trace!(
+                                "Field #{} {} @ {} was set to its default value",
+                                index.yellow(),
+                                field.name.green(),
+                                field.offset.blue(),
+                            );
                             wip.end().map_err(|e| self.reflect_err(e))?;
+                        } else if field.shape().is_unit() {
+                            // `()`, `PhantomData` and unit structs hold no data, so they're
+                            // never missing
+                            wip.begin_nth_field(index)
+                                .map_err(|e| self.reflect_err(e))?
+                                .end()
+                                .map_err(|e| self.reflect_err(e))?;
+                        } else if matches!(field.shape().def, Def::Option(_))
+                            && !container_shape.has_default_attr()
+                        {
+                            // An absent `Option` is `None`, unlike an explicit `null` for an
+                            // `Option<Option<T>>`, which is `Some(None)`
+                            wip.begin_nth_field(index)
+                                .map_err(|e| self.reflect_err(e))?
+                                .set_default()
+                                .map_err(|e| self.reflect_err(e))?
+                                .end()
+                                .map_err(|e| self.reflect_err(e))?;
+                            trace!(
+                                "Field #{} {} @ {} was absent, so it was set to None",
+                                index.yellow(),
+                                field.name.green(),
+                                field.offset.blue(),
+                            );
+                        } else if !container_shape.has_default_attr()
+                            && !self.projects(field.name)
+                            && field.shape().is(Characteristic::Default)
+                        {
+                            wip.begin_nth_field(index)
+                                .map_err(|e| self.reflect_err(e))?
+                                .set_default()
+                                .map_err(|e| self.reflect_err(e))?
+                                .end()
+                                .map_err(|e| self.reflect_err(e))?;
+                            trace!(
+                                "Field #{} {} @ {} isn't in the projection, so it was set to its default value",
+                                index.yellow(),
+                                field.name.green(),
+                                field.offset.blue(),
+                            );
                         } else {
                             trace!(
                                 "Field #{} {} @ {} is not initialized",
@@ -1076,36 +1416,12 @@ where
 
                 if has_unset {
                     if container_shape.has_default_attr() {
-                        // let's allocate and build a default value
-                        let default_val = Partial::alloc_shape(container_shape)
-                            .map_err(|e| self.reflect_err(e))?
-                            .set_default()
-                            .map_err(|e| self.reflect_err(e))?
-                            .build()
+                        trace!(
+                            "Setting the unset fields of {} from its default value",
+                            container_shape.blue()
+                        );
+                        wip.set_unset_fields_from_default()
                             .map_err(|e| self.reflect_err(e))?;
-                        let peek = default_val.peek().into_struct().unwrap();
-
-                        for (index, field) in sd.fields.iter().enumerate() {
-                            let is_set = wip.is_field_set(index).map_err(|err| {
-                                trace!("Error checking field set status: {:?}", err);
-                                self.reflect_err(err)
-                            })?;
-                            if !is_set {
-                                trace!(
-                                    "Field #{} {} @ {} is being set to default value (from default instance)",
-                                    index.yellow(),
-                                    field.name.green(),
-                                    field.offset.blue(),
-                                );
-                                wip.begin_nth_field(index)
-                                    .map_err(|e| self.reflect_err(e))?;
-                                // Get the field as a Peek from the default value
-                                let def_field = peek.field(index).unwrap();
-                                wip.set_from_peek(&def_field)
-                                    .map_err(|e| self.reflect_err(e))?;
-                                wip.end().map_err(|e| self.reflect_err(e))?;
-                            }
-                        }
                     } else {
                         // Find the first uninitialized field to report in the error
                         for (index, field) in sd.fields.iter().enumerate() {
@@ -1145,32 +1461,33 @@ where
                                     wip.begin_nth_field(index)
                                         .map_err(|e| self.reflect_err(e))?;
 
-                                    // Check for field-level default function first, then type-level default
-                                    if let Some(field_default_fn) = field.vtable.default_fn {
-                                        wip.set_field_default(field_default_fn)
-                                            .map_err(|e| self.reflect_err(e))?;
-                                        trace!(
-                                            "Field #{} @ {} in variant {} was set to default value (via field default function)",
-                                            index.yellow(),
-                                            field.offset.blue(),
-                                            variant.name
-                                        );
-                                    } else if field.shape().is(Characteristic::Default) {
-                                        wip.set_default().map_err(|e| self.reflect_err(e))?;
-                                        trace!(
-                                            "Field #{} @ {} in variant {} was set to default value (via type default impl)",
-                                            index.yellow(),
-                                            field.offset.blue(),
-                                            variant.name
-                                        );
-                                    } else {
-                                        return Err(self.reflect_err(
-                                            ReflectError::DefaultAttrButNoDefaultImpl {
-                                                shape: field.shape(),
-                                            },
-                                        ));
-                                    }
+                                    wip.set_default_of_field(field)
+                                        .map_err(|e| self.reflect_err(e))?;
+                                    trace!(
+                                        "Field #{} @ {} in variant {} was set to its default value",
+                                        index.yellow(),
+                                        field.offset.blue(),
+                                        variant.name
+                                    );
                                     wip.end().map_err(|e| self.reflect_err(e))?;
+                                } else if field.shape().is_unit() {
+                                    wip.begin_nth_field(index)
+                                        .map_err(|e| self.reflect_err(e))?
+                                        .end()
+                                        .map_err(|e| self.reflect_err(e))?;
+                                } else if matches!(field.shape().def, Def::Option(_)) {
+                                    wip.begin_nth_field(index)
+                                        .map_err(|e| self.reflect_err(e))?
+                                        .set_default()
+                                        .map_err(|e| self.reflect_err(e))?
+                                        .end()
+                                        .map_err(|e| self.reflect_err(e))?;
+                                    trace!(
+                                        "Field #{} @ {} in variant {} was absent, so it was set to None",
+                                        index.yellow(),
+                                        field.offset.blue(),
+                                        variant.name
+                                    );
                                 } else {
                                     trace!(
                                         "Field #{} @ {} in variant {} is not initialized",
@@ -1285,7 +1602,6 @@ where
         Ok(wip)
     }
 
-    /// Internal common handler for GotScalar outcome, to deduplicate code.
     /// Helper to set numeric values with type conversion
     fn set_numeric_value<'facet, N>(
         &self,
@@ -1296,111 +1612,82 @@ where
         'input: 'facet,
         N: NumericConvert,
     {
-        let shape = wip.innermost_shape();
-
-        // Check if this is a numeric scalar
-        if let Def::Scalar(sd) = shape.def {
-            if let ScalarAffinity::Number(num_affinity) = sd.affinity {
-                use facet_core::{IntegerSize, NumberBits, Signedness};
-
-                // Helper closure to convert and set numeric value
-                macro_rules! convert_and_set {
-                    ($converter:expr, $target_type:expr) => {{
-                        let converted = $converter(value).ok_or_else(|| {
-                            self.err(DeserErrorKind::NumericConversion {
-                                from: N::TYPE_NAME,
-                                to: $target_type,
-                            })
-                        })?;
-                        wip.set(converted).map_err(|e| self.reflect_err(e))?;
-                    }};
-                }
+        set_numeric(wip, value).map_err(|kind| self.err(kind))
+    }
 
-                // Check if it's integer or float based on the bits type
-                match num_affinity.bits {
-                    NumberBits::Integer { size, sign } => {
-                        // Integer type - check signed/unsigned and size
-                        match (size, sign) {
-                            (IntegerSize::Fixed(bits), Signedness::Signed) => match bits {
-                                8 => convert_and_set!(N::to_i8, "i8"),
-                                16 => convert_and_set!(N::to_i16, "i16"),
-                                32 => convert_and_set!(N::to_i32, "i32"),
-                                64 => convert_and_set!(N::to_i64, "i64"),
-                                128 => convert_and_set!(N::to_i128, "i128"),
-                                _ => {
-                                    return Err(self.err(DeserErrorKind::NumericConversion {
-                                        from: N::TYPE_NAME,
-                                        to: "unknown fixed-size signed integer",
-                                    }));
-                                }
-                            },
-                            (IntegerSize::Fixed(bits), Signedness::Unsigned) => match bits {
-                                8 => convert_and_set!(N::to_u8, "u8"),
-                                16 => convert_and_set!(N::to_u16, "u16"),
-                                32 => convert_and_set!(N::to_u32, "u32"),
-                                64 => convert_and_set!(N::to_u64, "u64"),
-                                128 => convert_and_set!(N::to_u128, "u128"),
-                                _ => {
-                                    return Err(self.err(DeserErrorKind::NumericConversion {
-                                        from: N::TYPE_NAME,
-                                        to: "unknown fixed-size unsigned integer",
-                                    }));
-                                }
-                            },
-                            (IntegerSize::PointerSized, Signedness::Signed) => {
-                                convert_and_set!(N::to_isize, "isize")
-                            }
-                            (IntegerSize::PointerSized, Signedness::Unsigned) => {
-                                convert_and_set!(N::to_usize, "usize")
-                            }
-                        }
-                    }
-                    NumberBits::Float {
-                        sign_bits,
-                        exponent_bits,
-                        mantissa_bits,
-                        ..
-                    } => {
-                        // Floating point - calculate total bits
-                        let total_bits = sign_bits + exponent_bits + mantissa_bits;
-                        match total_bits {
-                            32 => convert_and_set!(N::to_f32, "f32"),
-                            64 => convert_and_set!(N::to_f64, "f64"),
-                            _ => {
-                                // Unknown float size
-                                return Err(self.err(DeserErrorKind::NumericConversion {
-                                    from: N::TYPE_NAME,
-                                    to: "unknown float size",
-                                }));
-                            }
-                        }
-                    }
-                    _ => {
-                        // Fixed-point, Decimal, or other numeric types not supported
-                        return Err(self.err(DeserErrorKind::NumericConversion {
-                            from: N::TYPE_NAME,
-                            to: "fixed-point or decimal",
-                        }));
-                    }
-                }
-            } else {
-                // Not a number affinity - cannot convert
-                return Err(self.err(DeserErrorKind::UnsupportedType {
-                    got: shape,
-                    wanted: "numeric type",
-                }));
+    /// Selects the variant of a C-like or `#[facet(discriminant)]` enum whose discriminant is
+    /// `raw`, an integer scalar, or else its `#[facet(other)]` variant
+    fn select_variant_by_discriminant<'facet>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        raw: Scalar<'input>,
+    ) -> Result<(), DeserError<'input, 'shape, C>>
+    where
+        'input: 'facet,
+    {
+        let discriminant = match raw {
+            Scalar::U64(value) => i64::try_from(value).ok(),
+            Scalar::I64(value) => Some(value),
+            _ => None,
+        };
+        let shape = wip.innermost_shape();
+        let known = matches!(
+            (shape.ty, discriminant),
+            (Type::User(UserType::Enum(ed)), Some(d))
+                if ed.variants.iter().any(|v| v.discriminant == Some(d))
+        );
+        match discriminant {
+            Some(discriminant) if known => {
+                wip.select_variant(discriminant)
+                    .map_err(|e| self.reflect_err(e))?;
+                Ok(())
             }
-        } else {
-            // Not a scalar def - cannot convert
-            return Err(self.err(DeserErrorKind::UnsupportedType {
-                got: shape,
-                wanted: "scalar type",
-            }));
+            _ => match other_variant_index(shape) {
+                Some(index) => self.select_other_variant(wip, index, raw),
+                None => Err(self.err(DeserErrorKind::NoSuchDiscriminant {
+                    discriminant: match raw {
+                        Scalar::U64(value) => value.to_string(),
+                        Scalar::I64(value) => value.to_string(),
+                        other => other.to_string(),
+                    },
+                    enum_shape: shape,
+                })),
+            },
         }
+    }
 
+    /// Selects the `#[facet(other)]` variant at `index`, and reads `raw`, the unknown name or
+    /// discriminant, into its field if it has one
+    fn select_other_variant<'facet>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        index: usize,
+        raw: Scalar<'input>,
+    ) -> Result<(), DeserError<'input, 'shape, C>>
+    where
+        'input: 'facet,
+    {
+        wip.select_nth_variant(index)
+            .map_err(|e| self.reflect_err(e))?;
+        if wip
+            .selected_variant()
+            .is_some_and(|v| v.data.fields.len() == 1)
+        {
+            wip.begin_nth_field(0).map_err(|e| self.reflect_err(e))?;
+            self.handle_scalar(wip, raw)?;
+            wip.end().map_err(|e| self.reflect_err(e))?;
+        }
         Ok(())
     }
 
+    /// Makes an `Arc<str>`, through the interner if there's one.
+    fn arc_str(&self, s: &str) -> Arc<str> {
+        match &self.interner {
+            Some(interner) => interner.intern(s),
+            None => Arc::from(s),
+        }
+    }
+
     fn handle_scalar<'facet>(
         &self,
         wip: &mut Partial<'facet, 'shape>,
@@ -1412,8 +1699,16 @@ where
         match scalar {
             Scalar::String(cow) => {
                 match wip.innermost_shape().ty {
+                    _ if wip.innermost_shape().has_parse_attr() => {
+                        wip.parse_from_str(&cow).map_err(|e| self.reflect_err(e))?;
+                    }
                     Type::User(UserType::Enum(_)) => {
-                        if wip.selected_variant().is_some() {
+                        if let Some(variant) = wip.selected_variant() {
+                            if variant.data.kind == StructKind::Unit && variant.name == cow {
+                                // A unit variant selected by its key, like a flattened one
+                                // is written: `"Fast": "Fast"`
+                                return Ok(());
+                            }
                             // If we already have a variant selected, just put the string
                             wip.set(cow).map_err(|e| self.reflect_err(e))?;
                         } else {
@@ -1423,24 +1718,59 @@ where
                                     wip.select_nth_variant(variant_index)
                                         .map_err(|e| self.reflect_err(e))?;
                                 }
-                                None => {
-                                    return Err(self.err(DeserErrorKind::NoSuchVariant {
-                                        name: cow.to_string(),
-                                        enum_shape: wip.innermost_shape(),
-                                    }));
-                                }
+                                None => match other_variant_index(wip.innermost_shape()) {
+                                    Some(index) => {
+                                        self.select_other_variant(wip, index, Scalar::String(cow))?
+                                    }
+                                    None => {
+                                        return Err(self.err(DeserErrorKind::NoSuchVariant {
+                                            name: cow.to_string(),
+                                            enum_shape: wip.innermost_shape(),
+                                        }));
+                                    }
+                                },
+                            }
+                        }
+                    }
+                    _ if wip.innermost_shape().is_type::<char>() => {
+                        let mut chars = cow.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(c), None) => {
+                                wip.set(c).map_err(|e| self.reflect_err(e))?;
+                            }
+                            _ => {
+                                return Err(self.err(DeserErrorKind::NotAChar(cow.into_owned())));
                             }
                         }
                     }
                     Type::Pointer(PointerType::Reference(_))
                         if wip.innermost_shape().is_type::<&str>() =>
                     {
-                        // This is for handling the &str type
-                        // The Cow may be Borrowed (we may have an owned string but need a &str)
+                        // An owned string was decoded (unescaped) and isn't in the input as is
                         match cow {
                             Cow::Borrowed(s) => wip.set(s).map_err(|e| self.reflect_err(e))?,
-                            Cow::Owned(s) => wip.set(s).map_err(|e| self.reflect_err(e))?,
-                        }; // Add semicolon to ignore the return value
+                            Cow::Owned(_) => {
+                                return Err(self.err(DeserErrorKind::CannotBorrow {
+                                    shape: wip.innermost_shape(),
+                                    reason: "the string had to be unescaped, use `String` or `Cow<str>` to accept it",
+                                }));
+                            }
+                        };
+                    }
+                    _ if wip.innermost_shape().is_type::<Cow<'_, str>>() => {
+                        wip.set(cow).map_err(|e| self.reflect_err(e))?;
+                    }
+                    _ if wip.innermost_shape().is_type::<Arc<str>>() => {
+                        wip.set(self.arc_str(&cow))
+                            .map_err(|e| self.reflect_err(e))?;
+                    }
+                    _ if wip.innermost_shape().is_type::<Rc<str>>() => {
+                        wip.set(Rc::<str>::from(cow.as_ref()))
+                            .map_err(|e| self.reflect_err(e))?;
+                    }
+                    _ if wip.innermost_shape().is_type::<Box<str>>() => {
+                        wip.set(Box::<str>::from(cow))
+                            .map_err(|e| self.reflect_err(e))?;
                     }
                     _ => {
                         // Check if this is a scalar type that can be parsed from a string
@@ -1464,7 +1794,7 @@ where
                                                 operation,
                                             } if operation.contains("does not support parsing") => {
                                                 // Type doesn't have a parse function, try direct conversion
-                                                wip.set(cow.to_string())
+                                                wip.set(cow.into_owned())
                                                     .map_err(|e| self.reflect_err(e))?;
                                             }
                                             _ => {
@@ -1481,15 +1811,21 @@ where
                                 }
                             } else {
                                 // It's a string type, set directly
-                                wip.set(cow.to_string()).map_err(|e| self.reflect_err(e))?;
+                                wip.set(cow.into_owned()).map_err(|e| self.reflect_err(e))?;
                             }
                         } else {
                             // Not a scalar, just set as String
-                            wip.set(cow.to_string()).map_err(|e| self.reflect_err(e))?;
+                            wip.set(cow.into_owned()).map_err(|e| self.reflect_err(e))?;
                         }
                     }
                 }
             }
+            Scalar::U64(value) if is_discriminant_enum(wip) => {
+                self.select_variant_by_discriminant(wip, Scalar::U64(value))?;
+            }
+            Scalar::I64(value) if is_discriminant_enum(wip) => {
+                self.select_variant_by_discriminant(wip, Scalar::I64(value))?;
+            }
             Scalar::U64(value) => {
                 self.set_numeric_value(wip, value)?;
             }
@@ -1535,7 +1871,38 @@ where
 
         // Handle null values
         if matches!(outcome.node, Outcome::Scalar(Scalar::Null)) {
-            wip.set_default().map_err(|e| self.reflect_err(e))?;
+            let field_default = wip
+                .parent_field()
+                .filter(|field| field.flags.contains(FieldFlags::DEFAULT));
+            if matches!(original_shape.def, Def::Option(od) if matches!(od.t.def, Def::Option(_))) {
+                // `null` for an `Option<Option<T>>` is `Some(None)`, so it can be told apart
+                // from an absent field
+                trace!("  Null for {} is Some(None)", original_shape.blue());
+                wip.begin_some()
+                    .map_err(|e| self.reflect_err(e))?
+                    .set_default()
+                    .map_err(|e| self.reflect_err(e))?
+                    .end()
+                    .map_err(|e| self.reflect_err(e))?;
+            } else if original_shape.is_unit()
+                || wip
+                    .selected_variant()
+                    .is_some_and(|variant| variant.data.kind == StructKind::Unit)
+            {
+                trace!("  Null for {} holds no data", original_shape.blue());
+            } else if accepts_null(original_shape) {
+                wip.set_default().map_err(|e| self.reflect_err(e))?;
+            } else if let Some(field) = field_default {
+                match field.vtable.default_fn {
+                    Some(default_fn) => wip.set_field_default(default_fn),
+                    None => wip.set_default(),
+                }
+                .map_err(|e| self.reflect_err(e))?;
+            } else {
+                return Err(self.err(DeserErrorKind::UnexpectedNull {
+                    shape: original_shape,
+                }));
+            }
             return Ok(wip);
         }
 
@@ -1546,6 +1913,16 @@ where
                 wip.begin_some().map_err(|e| self.reflect_err(e))?;
                 self.stack.push(Instruction::Pop(PopReason::Some));
             } else if let Def::SmartPointer(inner) = wip.shape().def {
+                if self.shared_refs && inner.weak.is_some() {
+                    if matches!(outcome.node, Outcome::ObjectStarted) {
+                        // This is either `{"$ref": n}` or the pointer's value, and the
+                        // first key tells which
+                        self.stack.push(Instruction::SharedPointerKey);
+                        return Ok(wip);
+                    }
+                    wip = self.begin_shared_pointer(wip)?;
+                    continue;
+                }
                 if let Some(pointee) = inner.pointee() {
                     trace!(
                         "  Starting smart pointer for {} (pointee is {})",
@@ -1560,6 +1937,18 @@ where
                 }
                 wip.begin_smart_ptr().map_err(|e| self.reflect_err(e))?;
                 self.stack.push(Instruction::Pop(PopReason::SmartPointer));
+            } else if wip.shape().has_parse_attr()
+                && matches!(outcome.node, Outcome::Scalar(Scalar::String(_)))
+            {
+                // `#[facet(parse = ...)]` reads the wrapper itself from the string
+                break;
+            } else if wip.shape().bit_flags().is_some()
+                && matches!(outcome.node, Outcome::ListStarted)
+            {
+                // `#[facet(bitflags)]` reads a list of flag names, and sets the bits at its end
+                self.pending_flags = Some(0);
+                self.stack.push(Instruction::FlagNameOrListClose);
+                return Ok(wip);
             } else if let Some(inner_fn) = wip.shape().inner {
                 let inner = inner_fn();
                 trace!(
@@ -1582,6 +1971,17 @@ where
             );
         }
 
+        // Only strings are ever borrowed from the input, there's nowhere to put the target of
+        // any other reference
+        if matches!(wip.shape().ty, Type::Pointer(PointerType::Reference(_)))
+            && !wip.shape().is_type::<&str>()
+        {
+            return Err(self.err(DeserErrorKind::CannotBorrow {
+                shape: wip.shape(),
+                reason: "only `&str` can borrow from the input, use an owned type like `Vec<T>` or `Box<T>` instead",
+            }));
+        }
+
         match outcome.node {
             Outcome::Scalar(s) => {
                 trace!("Parsed scalar value: {}", s.cyan());
@@ -1758,16 +2158,165 @@ where
                 // self.stack.push(Instruction::Pop(PopReason::ObjectVal));
                 // self.stack.push(Instruction::Value(ValueReason::ObjectVal));
             }
-            Outcome::ObjectEnded => todo!(),
+            Outcome::ObjectEnded => {
+                // An object that ended before it started: the format read an empty object
+                let shape = wip.shape();
+                match (shape.def, shape.ty) {
+                    (Def::Map(_), _) => {
+                        trace!("Empty object for map value ({})", shape.blue());
+                        wip.begin_map().map_err(|e| self.reflect_err(e))?;
+                    }
+                    _ if shape.has_default_attr() => {
+                        trace!("Empty object for defaulted value ({})", shape.blue());
+                        wip.set_default().map_err(|e| self.reflect_err(e))?;
+                    }
+                    (_, Type::User(UserType::Struct(st))) if st.fields.is_empty() => {
+                        trace!("Empty object for unit struct ({})", shape.blue());
+                        // Structs without fields are initialized from the start
+                    }
+                    _ => {
+                        return Err(self.err(DeserErrorKind::UnexpectedOutcome {
+                            got: Outcome::ObjectEnded,
+                            wanted: "a value, or an empty object for a map or a struct with defaults",
+                        }));
+                    }
+                }
+            }
         }
         Ok(wip)
     }
 
-    fn object_key_or_object_close<'facet>(
+    /// Starts reading the value of an `Rc` or `Arc`, giving it the next id
+    fn begin_shared_pointer<'facet>(
+        &mut self,
+        mut wip: Partial<'facet, 'shape>,
+    ) -> Result<Partial<'facet, 'shape>, DeserError<'input, 'shape, C>> {
+        trace!(
+            "  Starting shared pointer #{} for {}",
+            self.shared_count.yellow(),
+            wip.shape().blue()
+        );
+        wip.begin_smart_ptr().map_err(|e| self.reflect_err(e))?;
+        self.shared_ids.push(self.shared_count);
+        self.shared_count += 1;
+        self.stack.push(Instruction::Pop(PopReason::SharedPointer));
+        Ok(wip)
+    }
+
+    fn shared_pointer_key<'facet>(
+        &mut self,
+        wip: Partial<'facet, 'shape>,
+        outcome: Spanned<Outcome<'input>, C>,
+    ) -> Result<Partial<'facet, 'shape>, DeserError<'input, 'shape, C>>
+    where
+        'input: 'facet,
+    {
+        if matches!(&outcome.node, Outcome::Scalar(Scalar::String(key)) if key == "$ref") {
+            self.stack.push(Instruction::SharedRef);
+            return Ok(wip);
+        }
+
+        // Not a reference: start the object again in the pointer's value, then read the key
+        let wip = self.begin_shared_pointer(wip)?;
+        let started = Spanned {
+            node: Outcome::ObjectStarted,
+            span: outcome.span,
+        };
+        let wip = self.value(wip, started)?;
+        if self.stack.last() == Some(&Instruction::ObjectKeyOrObjectClose) {
+            self.stack.pop();
+        }
+        self.object_key_or_object_close(wip, outcome)
+    }
+
+    fn shared_ref<'facet>(
+        &mut self,
+        wip: Partial<'facet, 'shape>,
+        outcome: Spanned<Outcome<'input>, C>,
+    ) -> Result<Partial<'facet, 'shape>, DeserError<'input, 'shape, C>>
+    where
+        'input: 'facet,
+    {
+        match outcome.node {
+            Outcome::Scalar(Scalar::U64(id)) => {
+                self.pending_ref = Some(id);
+                self.stack.push(Instruction::SharedRefEnd);
+                Ok(wip)
+            }
+            other => Err(self.err(DeserErrorKind::UnexpectedOutcome {
+                got: other.into_owned(),
+                wanted: "the id of a shared value",
+            })),
+        }
+    }
+
+    fn shared_ref_end<'facet>(
+        &mut self,
+        wip: Partial<'facet, 'shape>,
+        outcome: Spanned<Outcome<'input>, C>,
+    ) -> Result<Partial<'facet, 'shape>, DeserError<'input, 'shape, C>>
+    where
+        'input: 'facet,
+    {
+        match outcome.node {
+            Outcome::ObjectEnded => Ok(wip),
+            other => Err(self.err(DeserErrorKind::UnexpectedOutcome {
+                got: other.into_owned(),
+                wanted: "the end of a reference to a shared value",
+            })),
+        }
+    }
+
+    fn flag_name_or_list_close<'facet>(
         &mut self,
         mut wip: Partial<'facet, 'shape>,
         outcome: Spanned<Outcome<'input>, C>,
     ) -> Result<Partial<'facet, 'shape>, DeserError<'input, 'shape, C>>
+    where
+        'input: 'facet,
+    {
+        let shape = wip.shape();
+        let Some(flags) = shape.bit_flags() else {
+            return Err(self.internal_err("flag names are read into shapes with flags", shape));
+        };
+        match outcome.node {
+            Outcome::Scalar(Scalar::String(name)) => {
+                // Bits no flag covers are written like `0x10`
+                let bits = match BitFlag::by_name(flags, &name) {
+                    Some(flag) => flag.bits,
+                    None => name
+                        .strip_prefix("0x")
+                        .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+                        .ok_or_else(|| {
+                            self.err(DeserErrorKind::NoSuchFlag {
+                                name: name.to_string(),
+                                flags_shape: shape,
+                            })
+                        })?,
+                };
+                self.pending_flags = Some(self.pending_flags.unwrap_or(0) | bits);
+                self.stack.push(Instruction::FlagNameOrListClose);
+                Ok(wip)
+            }
+            Outcome::ListEnded => {
+                let bits = self.pending_flags.take().unwrap_or(0);
+                wip.begin_inner().map_err(|e| self.reflect_err(e))?;
+                self.handle_scalar(&mut wip, Scalar::U64(bits))?;
+                wip.end().map_err(|e| self.reflect_err(e))?;
+                Ok(wip)
+            }
+            other => Err(self.err(DeserErrorKind::UnexpectedOutcome {
+                got: other.into_owned(),
+                wanted: "the name of a flag",
+            })),
+        }
+    }
+
+    fn object_key_or_object_close<'facet>(
+        &mut self,
+        wip: Partial<'facet, 'shape>,
+        outcome: Spanned<Outcome<'input>, C>,
+    ) -> Result<Partial<'facet, 'shape>, DeserError<'input, 'shape, C>>
     where
         'input: 'facet,
     {
@@ -1780,193 +2329,20 @@ where
         match outcome.node {
             Outcome::Scalar(Scalar::String(key)) => {
                 trace!("Parsed object key: {}", key.cyan());
-
-                let mut ignore = false;
-                let mut needs_pop = true;
-                let mut handled_by_flatten = false;
-                let has_substack = !self.substack.get().is_empty();
-
-                let shape = wip.innermost_shape();
-                match shape.ty {
-                    Type::User(UserType::Struct(sd)) => {
-                        // First try to find a direct field match
-                        if let Some(index) = wip.field_index(&key) {
-                            trace!("It's a struct field");
-                            wip.begin_nth_field(index)
-                                .map_err(|e| self.reflect_err(e))?;
-                        } else {
-                            trace!(
-                                "Did not find direct field match in innermost shape {}",
-                                shape.blue()
-                            );
-
-                            // Check for flattened fields
-                            let mut found_in_flatten = false;
-                            for (index, field) in sd.fields.iter().enumerate() {
-                                if field.flags.contains(FieldFlags::FLATTEN) {
-                                    trace!("Found flattened field #{}", index);
-                                    // Enter the flattened field
-                                    wip.begin_nth_field(index)
-                                        .map_err(|e| self.reflect_err(e))?;
-
-                                    // Check if this flattened field has the requested key
-                                    if let Some(subfield_index) = wip.field_index(&key) {
-                                        trace!("Found key {} in flattened field", key);
-                                        wip.begin_nth_field(subfield_index)
-                                            .map_err(|e| self.reflect_err(e))?;
-                                        found_in_flatten = true;
-                                        handled_by_flatten = true;
-                                        break;
-                                    } else if let Some((_variant_index, _variant)) =
-                                        wip.find_variant(&key)
-                                    {
-                                        trace!("Found key {} in flattened field", key);
-                                        wip.select_variant_named(&key)
-                                            .map_err(|e| self.reflect_err(e))?;
-                                        found_in_flatten = true;
-                                        break;
-                                    } else {
-                                        // Key not in this flattened field, go back up
-                                        wip.end().map_err(|e| self.reflect_err(e))?;
-                                    }
-                                }
-                            }
-
-                            if !found_in_flatten {
-                                if wip.shape().has_deny_unknown_fields_attr() {
-                                    trace!(
-                                        "It's not a struct field AND we're denying unknown fields"
-                                    );
-                                    return Err(self.err(DeserErrorKind::UnknownField {
-                                        field_name: key.to_string(),
-                                        shape: wip.shape(),
-                                    }));
-                                } else {
-                                    trace!(
-                                        "It's not a struct field and we're ignoring unknown fields"
-                                    );
-                                    ignore = true;
-                                }
-                            }
-                        }
-                    }
-                    Type::User(UserType::Enum(_ed)) => match wip.find_variant(&key) {
-                        Some((index, variant)) => {
-                            trace!(
-                                "Selecting variant {}::{}",
-                                wip.shape().blue(),
-                                variant.name.yellow(),
-                            );
-                            wip.select_nth_variant(index)
-                                .map_err(|e| self.reflect_err(e))?;
-
-                            // Let's see what's in the variant — if it's tuple-like with only one field, we want to push field 0
-                            if matches!(variant.data.kind, StructKind::Tuple)
-                                && variant.data.fields.len() == 1
-                            {
-                                trace!(
-                                    "Tuple variant {}::{} encountered, pushing field 0",
-                                    wip.shape().blue(),
-                                    variant.name.yellow()
-                                );
-                                wip.begin_nth_field(0).map_err(|e| self.reflect_err(e))?;
-                                self.stack.push(Instruction::Pop(PopReason::ObjectVal));
-                            }
-
-                            needs_pop = false;
-                        }
-                        None => {
-                            if let Some(_variant_index) = wip.selected_variant() {
-                                trace!(
-                                    "Already have a variant selected, treating {} as struct field of {}::{}",
-                                    key,
-                                    wip.shape().blue(),
-                                    wip.selected_variant().unwrap().name.yellow(),
-                                );
-                                // Try to find the field index of the key within the selected variant
-                                if let Some(index) = wip.field_index(&key) {
-                                    trace!("Found field {} in selected variant", key.blue());
-                                    wip.begin_nth_field(index)
-                                        .map_err(|e| self.reflect_err(e))?;
-                                } else if wip.shape().has_deny_unknown_fields_attr() {
-                                    trace!("Unknown field in variant and denying unknown fields");
-                                    return Err(self.err(DeserErrorKind::UnknownField {
-                                        field_name: key.to_string(),
-                                        shape: wip.shape(),
-                                    }));
-                                } else {
-                                    trace!(
-                                        "Ignoring unknown field '{}' in variant '{}::{}'",
-                                        key,
-                                        wip.shape(),
-                                        wip.selected_variant().unwrap().name
-                                    );
-                                    ignore = true;
-                                }
-                            } else {
-                                return Err(self.err(DeserErrorKind::NoSuchVariant {
-                                    name: key.to_string(),
-                                    enum_shape: wip.shape(),
-                                }));
-                            }
-                        }
-                    },
-                    _ => {
-                        // Check if it's a map
-                        if let Def::Map(map_def) = shape.def {
-                            wip.begin_key().map_err(|e| self.reflect_err(e))?;
-
-                            // Check if the map key type is transparent (has an inner shape)
-                            let key_shape = map_def.k();
-                            if key_shape.inner.is_some() {
-                                // For transparent types, we need to navigate into the inner type
-                                // The inner type should be String for JSON object keys
-                                // Use begin_inner for consistency with begin_* naming convention
-                                wip.begin_inner().map_err(|e| self.reflect_err(e))?;
-                                wip.set(key.to_string()).map_err(|e| self.reflect_err(e))?;
-                                wip.end().map_err(|e| self.reflect_err(e))?; // End inner
-                            } else {
-                                // For non-transparent types, set the string directly
-                                wip.set(key.to_string()).map_err(|e| self.reflect_err(e))?;
-                            }
-
-                            wip.end().map_err(|e| self.reflect_err(e))?; // Complete the key frame
-                            wip.begin_value().map_err(|e| self.reflect_err(e))?;
-                        } else {
-                            return Err(self.err(DeserErrorKind::Unimplemented(
-                                "object key for non-struct/map",
-                            )));
-                        }
-                    }
-                }
-
                 self.stack.push(Instruction::ObjectKeyOrObjectClose);
-                if ignore {
-                    self.stack.push(Instruction::SkipValue);
-                } else {
-                    if needs_pop && !handled_by_flatten {
-                        trace!("Pushing Pop insn to stack (ObjectVal)");
-                        self.stack.push(Instruction::Pop(PopReason::ObjectVal));
-                        if has_substack {
-                            trace!("Pushing SubstackClose insn to stack");
-                            self.stack.push(Instruction::SubstackClose);
-                        }
-                    } else if handled_by_flatten {
-                        // For flattened fields, we only need one pop for the field itself.
-                        // The flattened struct should remain active until the outer object is finished.
-                        trace!("Pushing Pop insn to stack (ObjectVal) for flattened field");
-                        self.stack.push(Instruction::Pop(PopReason::ObjectVal));
-                        if has_substack {
-                            trace!("Pushing SubstackClose insn to stack");
-                            self.stack.push(Instruction::SubstackClose);
-                        }
-                    }
-                    self.stack.push(Instruction::Value(ValueReason::ObjectVal));
-                }
-                Ok(wip)
+                self.object_key(wip, key)
             }
             Outcome::ObjectEnded => {
                 trace!("Object closing");
+                if let Type::User(UserType::Struct(sd)) = wip.shape().ty {
+                    if sd
+                        .fields
+                        .iter()
+                        .any(|field| field.flags.contains(FieldFlags::FLATTEN))
+                    {
+                        self.stack.push(Instruction::FinishFlattened);
+                    }
+                }
                 Ok(wip)
             }
             Outcome::Resegmented(subspans) => {
@@ -1986,6 +2362,338 @@ where
         }
     }
 
+    /// Selects the field, map entry or variant that an object key names, and pushes the
+    /// instructions that read its value.
+    fn object_key<'facet>(
+        &mut self,
+        mut wip: Partial<'facet, 'shape>,
+        key: Cow<'input, str>,
+    ) -> Result<Partial<'facet, 'shape>, DeserError<'input, 'shape, C>>
+    where
+        'input: 'facet,
+    {
+        let mut ignore = false;
+        let mut needs_pop = true;
+        // The field the key selected, whose span is reported under the path of the value it's in
+        let mut field = None;
+        let parent_path = self.field_spans.as_ref().map(|_| wip.path());
+        let has_substack = !self.substack.get().is_empty();
+
+        let shape = wip.innermost_shape();
+        match shape.ty {
+            Type::User(UserType::Struct(sd)) => {
+                // Fields of the struct itself come first, then flattened fields in order
+                let direct = sd.fields.iter().position(|field| {
+                    !field.flags.contains(FieldFlags::FLATTEN) && field.name == key
+                });
+                if let Some(index) = direct {
+                    if !self.enter_field(&key) {
+                        trace!("Skipping field {}, which isn't in the projection", key);
+                        self.stack.push(Instruction::SkipValue);
+                        return Ok(wip);
+                    }
+                    trace!("It's a struct field");
+                    wip.begin_nth_field(index)
+                        .map_err(|e| self.reflect_err(e))?;
+                    field = Some(sd.fields[index].name);
+                } else if let Some(index) = sd.fields.iter().position(|field| {
+                    field.flags.contains(FieldFlags::FLATTEN)
+                        && field.shape().accepts_flattened_key(&key)
+                }) {
+                    trace!("Found key {} in flattened field #{}", key, index);
+                    self.pending_flattened_key = Some(key);
+                    self.stack.push(Instruction::LeaveFlattened);
+                    self.stack.push(Instruction::EnterFlattened(index));
+                    return Ok(wip);
+                } else if wip.shape().has_deny_unknown_fields_attr() {
+                    trace!("It's not a struct field AND we're denying unknown fields");
+                    return Err(self.err(DeserErrorKind::UnknownField {
+                        field_name: key.to_string(),
+                        shape: wip.shape(),
+                    }));
+                } else {
+                    trace!("It's not a struct field and we're ignoring unknown fields");
+                    ignore = true;
+                }
+            }
+            Type::User(UserType::Enum(_ed)) => match wip.find_variant(&key) {
+                Some((index, variant)) => {
+                    trace!(
+                        "Selecting variant {}::{}",
+                        wip.shape().blue(),
+                        variant.name.yellow(),
+                    );
+                    wip.select_nth_variant(index)
+                        .map_err(|e| self.reflect_err(e))?;
+
+                    // Let's see what's in the variant — if it's tuple-like with only one field, we want to push field 0
+                    if matches!(variant.data.kind, StructKind::Tuple)
+                        && variant.data.fields.len() == 1
+                    {
+                        trace!(
+                            "Tuple variant {}::{} encountered, pushing field 0",
+                            wip.shape().blue(),
+                            variant.name.yellow()
+                        );
+                        wip.begin_nth_field(0).map_err(|e| self.reflect_err(e))?;
+                        self.stack.push(Instruction::Pop(PopReason::ObjectVal));
+                    }
+
+                    needs_pop = false;
+                }
+                None => {
+                    if let Some(variant) = wip.selected_variant() {
+                        trace!(
+                            "Already have a variant selected, treating {} as struct field of {}::{}",
+                            key,
+                            wip.shape().blue(),
+                            wip.selected_variant().unwrap().name.yellow(),
+                        );
+                        // Try to find the field index of the key within the selected variant
+                        if let Some(index) = wip.field_index(&key) {
+                            trace!("Found field {} in selected variant", key.blue());
+                            field = Some(variant.data.fields[index].name);
+                            wip.begin_nth_field(index)
+                                .map_err(|e| self.reflect_err(e))?;
+                        } else if wip.shape().has_deny_unknown_fields_attr() {
+                            trace!("Unknown field in variant and denying unknown fields");
+                            return Err(self.err(DeserErrorKind::UnknownField {
+                                field_name: key.to_string(),
+                                shape: wip.shape(),
+                            }));
+                        } else {
+                            trace!(
+                                "Ignoring unknown field '{}' in variant '{}::{}'",
+                                key,
+                                wip.shape(),
+                                wip.selected_variant().unwrap().name
+                            );
+                            ignore = true;
+                        }
+                    } else if let Some(index) = other_variant_index(wip.shape()) {
+                        // A variant with data that this version doesn't know, like
+                        // `{"NewVariant": {...}}`: its name goes to the catch-all variant,
+                        // and its data is skipped
+                        trace!("Unknown variant {}, selecting the other variant", key);
+                        self.select_other_variant(&mut wip, index, Scalar::String(key))?;
+                        self.stack.push(Instruction::SkipValue);
+                        return Ok(wip);
+                    } else {
+                        return Err(self.err(DeserErrorKind::NoSuchVariant {
+                            name: key.to_string(),
+                            enum_shape: wip.shape(),
+                        }));
+                    }
+                }
+            },
+            _ => {
+                // Check if it's a map
+                if let Def::Map(map_def) = shape.def {
+                    wip.begin_key().map_err(|e| self.reflect_err(e))?;
+
+                    // Check if the map key type is transparent (has an inner shape)
+                    let key_shape = map_def.k();
+                    if key_shape.is_type::<Arc<str>>() {
+                        wip.set(self.arc_str(&key))
+                            .map_err(|e| self.reflect_err(e))?;
+                    } else if key_shape.is_type::<Rc<str>>() {
+                        wip.set(Rc::<str>::from(key.as_ref()))
+                            .map_err(|e| self.reflect_err(e))?;
+                    } else if key_shape.is_type::<Box<str>>() {
+                        wip.set(Box::<str>::from(key.as_ref()))
+                            .map_err(|e| self.reflect_err(e))?;
+                    } else if key_shape.inner.is_some() {
+                        // For transparent types, we need to navigate into the inner type
+                        // The inner type should be String for JSON object keys
+                        // Use begin_inner for consistency with begin_* naming convention
+                        wip.begin_inner().map_err(|e| self.reflect_err(e))?;
+                        wip.set(key.to_string()).map_err(|e| self.reflect_err(e))?;
+                        wip.end().map_err(|e| self.reflect_err(e))?; // End inner
+                    } else {
+                        // For non-transparent types, set the string directly
+                        wip.set(key.to_string()).map_err(|e| self.reflect_err(e))?;
+                    }
+
+                    wip.end().map_err(|e| self.reflect_err(e))?; // Complete the key frame
+                    wip.begin_value().map_err(|e| self.reflect_err(e))?;
+                } else {
+                    return Err(self.err(DeserErrorKind::Unimplemented(
+                        "object key for non-struct/map",
+                    )));
+                }
+            }
+        }
+
+        if ignore {
+            if self.ignored_fields.is_some() {
+                let path = alloc::format!("{}.{}", wip.path(), key);
+                self.pending_ignored_field = Some((path, key.into_owned(), self.last_span));
+            }
+            self.stack.push(Instruction::SkipValue);
+        } else {
+            if needs_pop {
+                trace!("Pushing Pop insn to stack (ObjectVal)");
+                self.stack.push(Instruction::Pop(PopReason::ObjectVal));
+                if has_substack {
+                    trace!("Pushing SubstackClose insn to stack");
+                    self.stack.push(Instruction::SubstackClose);
+                }
+            }
+            if let (Some(field), Some(parent_path)) = (field, parent_path) {
+                let path = alloc::format!("{}.{}", parent_path, field);
+                self.open_field_spans.push((path, None));
+                self.stack.push(Instruction::EndFieldSpan);
+            }
+            self.stack.push(Instruction::Value(ValueReason::ObjectVal));
+        }
+        Ok(wip)
+    }
+
+    /// Whether the projection, if any, reads the field `name` of the struct being read.
+    fn projects(&self, name: &str) -> bool {
+        match (&self.projection, self.selections.last()) {
+            (Some(projection), Some(&selection)) => projection.child(selection, name).is_some(),
+            _ => true,
+        }
+    }
+
+    /// Enters the field `name` of the struct being read, if the projection reads it, with
+    /// what the projection selects of it until [`Instruction::LeaveProjection`].
+    ///
+    /// Returns `false` if the field is skipped.
+    fn enter_field(&mut self, name: &str) -> bool {
+        let (Some(projection), Some(&selection)) = (&self.projection, self.selections.last())
+        else {
+            return true;
+        };
+        match projection.child(selection, name) {
+            Some(child) => {
+                self.selections.push(child);
+                self.stack.push(Instruction::LeaveProjection);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the flattened fields of the struct `wip` is at into it, once its object has ended.
+    ///
+    /// Keys of flattened fields can come in any order, mixed with other keys, so each flattened
+    /// field is built in a Partial of its own until then. `level` is how many of those were
+    /// entered to reach `wip`.
+    fn finish_flattened<'facet>(
+        &mut self,
+        mut wip: Partial<'facet, 'shape>,
+        level: usize,
+        pending: &mut Vec<FlattenedField<'facet, 'shape>>,
+    ) -> Result<Partial<'facet, 'shape>, DeserError<'input, 'shape, C>> {
+        let Type::User(UserType::Struct(sd)) = wip.shape().ty else {
+            return Ok(wip);
+        };
+        let owner = (level, wip.frame_count());
+        for (index, field) in sd.fields.iter().enumerate() {
+            if !field.flags.contains(FieldFlags::FLATTEN) {
+                continue;
+            }
+            let position = pending
+                .iter()
+                .position(|flattened| flattened.owner == owner && flattened.index == index);
+            let flattened = match position {
+                Some(position) => pending.swap_remove(position).wip,
+                None => {
+                    // None of its keys came up: a struct can still be built from defaults,
+                    // anything else is handled like a missing field
+                    let is_set = wip.is_field_set(index).map_err(|e| self.reflect_err(e))?;
+                    if is_set
+                        || field.flags.contains(FieldFlags::DEFAULT)
+                        || matches!(field.shape().def, Def::Option(_))
+                        || !matches!(field.shape().ty, Type::User(UserType::Struct(_)))
+                    {
+                        continue;
+                    }
+                    Partial::alloc_shape(field.shape()).map_err(|e| self.reflect_err(e))?
+                }
+            };
+
+            trace!(
+                "Finishing flattened field #{} {}",
+                index,
+                field.name.green()
+            );
+            let flattened = self.finish_flattened(flattened, level + 1, pending)?;
+            let mut flattened = self.pop(flattened, PopReason::ObjectVal)?;
+            let value = flattened.build().map_err(|e| self.reflect_err(e))?;
+            wip.begin_nth_field(index)
+                .map_err(|e| self.reflect_err(e))?
+                .set_from_heap_value(value)
+                .map_err(|e| self.reflect_err(e))?
+                .end()
+                .map_err(|e| self.reflect_err(e))?;
+        }
+        Ok(wip)
+    }
+
+    /// Starts building the flattened field `index` of the struct `wip` is at. A field that's
+    /// already set, when deserializing into an existing value, starts out as a clone of it.
+    fn begin_flattened<'facet>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        index: usize,
+    ) -> Result<Partial<'facet, 'shape>, DeserError<'input, 'shape, C>> {
+        let Type::User(UserType::Struct(sd)) = wip.shape().ty else {
+            return Err(self.internal_err("flattened fields belong to structs", wip.shape()));
+        };
+        let field_shape = sd.fields[index].shape();
+
+        let is_set = wip.is_field_set(index).map_err(|e| self.reflect_err(e))?;
+        if is_set
+            && field_shape.inner.is_none()
+            && matches!(field_shape.ty, Type::User(UserType::Struct(st)) if st.kind == StructKind::Struct)
+        {
+            // Entering a struct field that's set keeps its value, so it can be cloned
+            wip.begin_nth_field(index)
+                .map_err(|e| self.reflect_err(e))?;
+            let existing = wip
+                .peek_current()
+                .and_then(|peek| Partial::from_existing_peek(&peek));
+            wip.end().map_err(|e| self.reflect_err(e))?;
+            if let Ok(existing) = existing {
+                return Ok(existing);
+            }
+        }
+        Partial::alloc_shape(field_shape).map_err(|e| self.reflect_err(e))
+    }
+
+    /// Adds a list item to the run of items of `item_shape`, if it's a number or a boolean
+    /// that fits
+    fn push_scalar_run(&mut self, item_shape: &Shape<'_>, scalar: &Scalar<'_>) -> bool {
+        if !self.bulk_scalars {
+            return false;
+        }
+        if !self
+            .scalar_run
+            .as_ref()
+            .is_some_and(|run| run.is_for(item_shape))
+        {
+            match ScalarRun::for_shape(item_shape) {
+                Some(run) => self.scalar_run = Some(run),
+                None => return false,
+            }
+        }
+        self.scalar_run.as_mut().unwrap().push(scalar)
+    }
+
+    /// Adds the items of the run to the list `wip` is at
+    fn flush_scalar_run<'facet>(
+        &mut self,
+        wip: &mut Partial<'facet, 'shape>,
+    ) -> Result<(), DeserError<'input, 'shape, C>> {
+        if let Some(run) = &mut self.scalar_run {
+            run.flush(wip).map_err(|e| self.reflect_err(e))?;
+        }
+        Ok(())
+    }
+
     fn list_item_or_list_close<'facet>(
         &mut self,
         mut wip: Partial<'facet, 'shape>,
@@ -2002,6 +2710,7 @@ where
         match outcome.node {
             Outcome::ListEnded => {
                 trace!("List close");
+                self.flush_scalar_run(&mut wip)?;
                 // Clean up array index tracking if this was an array
                 let shape = wip.shape();
                 if matches!(shape.def, Def::Array(_)) {
@@ -2029,6 +2738,16 @@ where
                 Ok(wip)
             }
             _ => {
+                if let (Def::List(list_def), Outcome::Scalar(scalar)) =
+                    (wip.shape().def, &outcome.node)
+                {
+                    if self.push_scalar_run(list_def.t(), scalar) {
+                        self.stack.push(Instruction::ListItemOrListClose);
+                        return Ok(wip);
+                    }
+                }
+                self.flush_scalar_run(&mut wip)?;
+
                 self.stack.push(Instruction::ListItemOrListClose);
                 self.stack.push(Instruction::Pop(PopReason::ListVal));
 
@@ -2048,6 +2767,7 @@ where
                             if current_index >= ad.n {
                                 return Err(self.err(DeserErrorKind::ArrayOverflow {
                                     shape,
+                                    index: current_index,
                                     max_len: ad.n,
                                 }));
                             }
@@ -2061,10 +2781,8 @@ where
                                 *last += 1;
                             }
                         } else {
-                            // This shouldn't happen if we properly initialize in ListStarted
-                            return Err(self.err(DeserErrorKind::Unimplemented(
-                                "Array index tracking not initialized",
-                            )));
+                            return Err(self
+                                .internal_err("arrays track their index from their start", shape));
                         }
                     }
                     Def::List(_) => {
@@ -2080,6 +2798,7 @@ where
                                     // Too many elements for this tuple variant
                                     return Err(self.err(DeserErrorKind::ArrayOverflow {
                                         shape,
+                                        index: current_field,
                                         max_len: field_count,
                                     }));
                                 }
@@ -2116,6 +2835,7 @@ where
                                     // All fields are set, this is too many elements
                                     return Err(self.err(DeserErrorKind::ArrayOverflow {
                                         shape,
+                                        index: struct_type.fields.len(),
                                         max_len: struct_type.fields.len(),
                                     }));
                                 }
@@ -0,0 +1,542 @@
+//! Numeric conversions between what a parser produces and what a shape wants.
+
+use alloc::string::ToString;
+use core::fmt::Display;
+
+use facet_core::{Def, ScalarAffinity};
+use facet_reflect::Partial;
+
+use crate::{DeserErrorKind, NumericConversionReason};
+
+/// Helper function to check if an f64 has no fractional part
+/// This is needed for no-std compatibility where f64::fract() is not available
+#[inline]
+fn has_no_fractional_part(value: f64) -> bool {
+    value == (value as i64) as f64
+}
+
+/// Trait for numeric type conversions
+///
+/// Implemented for the widest integer and float types a parser produces; each
+/// method narrows the value to a target type, or returns `None` if it doesn't fit.
+pub trait NumericConvert: Sized + Copy + Display {
+    /// Name of the source type, used in conversion errors
+    const TYPE_NAME: &'static str;
+
+    /// Whether the value is below zero, used to explain conversion errors
+    fn is_negative(self) -> bool;
+    /// Whether the value has a fractional part, used to explain conversion errors
+    fn is_fractional(self) -> bool;
+
+    /// Converts to `i8`, if the value fits
+    fn to_i8(self) -> Option<i8>;
+    /// Converts to `i16`, if the value fits
+    fn to_i16(self) -> Option<i16>;
+    /// Converts to `i32`, if the value fits
+    fn to_i32(self) -> Option<i32>;
+    /// Converts to `i64`, if the value fits
+    fn to_i64(self) -> Option<i64>;
+    /// Converts to `i128`, if the value fits
+    fn to_i128(self) -> Option<i128>;
+    /// Converts to `isize`, if the value fits
+    fn to_isize(self) -> Option<isize>;
+
+    /// Converts to `u8`, if the value fits
+    fn to_u8(self) -> Option<u8>;
+    /// Converts to `u16`, if the value fits
+    fn to_u16(self) -> Option<u16>;
+    /// Converts to `u32`, if the value fits
+    fn to_u32(self) -> Option<u32>;
+    /// Converts to `u64`, if the value fits
+    fn to_u64(self) -> Option<u64>;
+    /// Converts to `u128`, if the value fits
+    fn to_u128(self) -> Option<u128>;
+    /// Converts to `usize`, if the value fits
+    fn to_usize(self) -> Option<usize>;
+
+    /// Converts to `f32`, if the value fits
+    fn to_f32(self) -> Option<f32>;
+    /// Converts to `f64`, if the value fits
+    fn to_f64(self) -> Option<f64>;
+}
+
+impl NumericConvert for u64 {
+    const TYPE_NAME: &'static str = "u64";
+
+    fn is_negative(self) -> bool {
+        false
+    }
+    fn is_fractional(self) -> bool {
+        false
+    }
+
+    fn to_i8(self) -> Option<i8> {
+        self.try_into().ok()
+    }
+    fn to_i16(self) -> Option<i16> {
+        self.try_into().ok()
+    }
+    fn to_i32(self) -> Option<i32> {
+        self.try_into().ok()
+    }
+    fn to_i64(self) -> Option<i64> {
+        self.try_into().ok()
+    }
+    fn to_i128(self) -> Option<i128> {
+        Some(self as i128)
+    }
+    fn to_isize(self) -> Option<isize> {
+        self.try_into().ok()
+    }
+
+    fn to_u8(self) -> Option<u8> {
+        self.try_into().ok()
+    }
+    fn to_u16(self) -> Option<u16> {
+        self.try_into().ok()
+    }
+    fn to_u32(self) -> Option<u32> {
+        self.try_into().ok()
+    }
+    fn to_u64(self) -> Option<u64> {
+        Some(self)
+    }
+    fn to_u128(self) -> Option<u128> {
+        Some(self as u128)
+    }
+    fn to_usize(self) -> Option<usize> {
+        self.try_into().ok()
+    }
+
+    fn to_f32(self) -> Option<f32> {
+        Some(self as f32)
+    }
+    fn to_f64(self) -> Option<f64> {
+        Some(self as f64)
+    }
+}
+
+impl NumericConvert for i64 {
+    const TYPE_NAME: &'static str = "i64";
+
+    fn is_negative(self) -> bool {
+        self < 0
+    }
+    fn is_fractional(self) -> bool {
+        false
+    }
+
+    fn to_i8(self) -> Option<i8> {
+        self.try_into().ok()
+    }
+    fn to_i16(self) -> Option<i16> {
+        self.try_into().ok()
+    }
+    fn to_i32(self) -> Option<i32> {
+        self.try_into().ok()
+    }
+    fn to_i64(self) -> Option<i64> {
+        Some(self)
+    }
+    fn to_i128(self) -> Option<i128> {
+        Some(self as i128)
+    }
+    fn to_isize(self) -> Option<isize> {
+        self.try_into().ok()
+    }
+
+    fn to_u8(self) -> Option<u8> {
+        self.try_into().ok()
+    }
+    fn to_u16(self) -> Option<u16> {
+        self.try_into().ok()
+    }
+    fn to_u32(self) -> Option<u32> {
+        self.try_into().ok()
+    }
+    fn to_u64(self) -> Option<u64> {
+        self.try_into().ok()
+    }
+    fn to_u128(self) -> Option<u128> {
+        self.try_into().ok()
+    }
+    fn to_usize(self) -> Option<usize> {
+        self.try_into().ok()
+    }
+
+    fn to_f32(self) -> Option<f32> {
+        Some(self as f32)
+    }
+    fn to_f64(self) -> Option<f64> {
+        Some(self as f64)
+    }
+}
+
+impl NumericConvert for f64 {
+    const TYPE_NAME: &'static str = "f64";
+
+    fn is_negative(self) -> bool {
+        self < 0.0
+    }
+    fn is_fractional(self) -> bool {
+        self.is_finite() && !has_no_fractional_part(self)
+    }
+
+    fn to_i8(self) -> Option<i8> {
+        if has_no_fractional_part(self) && self >= i8::MIN as f64 && self <= i8::MAX as f64 {
+            Some(self as i8)
+        } else {
+            None
+        }
+    }
+    fn to_i16(self) -> Option<i16> {
+        if has_no_fractional_part(self) && self >= i16::MIN as f64 && self <= i16::MAX as f64 {
+            Some(self as i16)
+        } else {
+            None
+        }
+    }
+    fn to_i32(self) -> Option<i32> {
+        if has_no_fractional_part(self) && self >= i32::MIN as f64 && self <= i32::MAX as f64 {
+            Some(self as i32)
+        } else {
+            None
+        }
+    }
+    fn to_i64(self) -> Option<i64> {
+        if has_no_fractional_part(self) && self >= i64::MIN as f64 && self <= i64::MAX as f64 {
+            Some(self as i64)
+        } else {
+            None
+        }
+    }
+    fn to_i128(self) -> Option<i128> {
+        if has_no_fractional_part(self) && self >= i128::MIN as f64 && self <= i128::MAX as f64 {
+            Some(self as i128)
+        } else {
+            None
+        }
+    }
+    fn to_isize(self) -> Option<isize> {
+        if has_no_fractional_part(self) && self >= isize::MIN as f64 && self <= isize::MAX as f64 {
+            Some(self as isize)
+        } else {
+            None
+        }
+    }
+
+    fn to_u8(self) -> Option<u8> {
+        if has_no_fractional_part(self) && self >= 0.0 && self <= u8::MAX as f64 {
+            Some(self as u8)
+        } else {
+            None
+        }
+    }
+    fn to_u16(self) -> Option<u16> {
+        if has_no_fractional_part(self) && self >= 0.0 && self <= u16::MAX as f64 {
+            Some(self as u16)
+        } else {
+            None
+        }
+    }
+    fn to_u32(self) -> Option<u32> {
+        if has_no_fractional_part(self) && self >= 0.0 && self <= u32::MAX as f64 {
+            Some(self as u32)
+        } else {
+            None
+        }
+    }
+    fn to_u64(self) -> Option<u64> {
+        if has_no_fractional_part(self) && self >= 0.0 && self <= u64::MAX as f64 {
+            Some(self as u64)
+        } else {
+            None
+        }
+    }
+    fn to_u128(self) -> Option<u128> {
+        if has_no_fractional_part(self) && self >= 0.0 && self <= u128::MAX as f64 {
+            Some(self as u128)
+        } else {
+            None
+        }
+    }
+    fn to_usize(self) -> Option<usize> {
+        if has_no_fractional_part(self) && self >= 0.0 && self <= usize::MAX as f64 {
+            Some(self as usize)
+        } else {
+            None
+        }
+    }
+
+    fn to_f32(self) -> Option<f32> {
+        Some(self as f32)
+    }
+    fn to_f64(self) -> Option<f64> {
+        Some(self)
+    }
+}
+
+impl NumericConvert for u128 {
+    const TYPE_NAME: &'static str = "u128";
+
+    fn is_negative(self) -> bool {
+        false
+    }
+    fn is_fractional(self) -> bool {
+        false
+    }
+
+    fn to_i8(self) -> Option<i8> {
+        self.try_into().ok()
+    }
+    fn to_i16(self) -> Option<i16> {
+        self.try_into().ok()
+    }
+    fn to_i32(self) -> Option<i32> {
+        self.try_into().ok()
+    }
+    fn to_i64(self) -> Option<i64> {
+        self.try_into().ok()
+    }
+    fn to_i128(self) -> Option<i128> {
+        Some(self as i128)
+    }
+    fn to_isize(self) -> Option<isize> {
+        self.try_into().ok()
+    }
+
+    fn to_u8(self) -> Option<u8> {
+        self.try_into().ok()
+    }
+    fn to_u16(self) -> Option<u16> {
+        self.try_into().ok()
+    }
+    fn to_u32(self) -> Option<u32> {
+        self.try_into().ok()
+    }
+    fn to_u64(self) -> Option<u64> {
+        self.try_into().ok()
+    }
+    fn to_u128(self) -> Option<u128> {
+        Some(self)
+    }
+    fn to_usize(self) -> Option<usize> {
+        self.try_into().ok()
+    }
+
+    fn to_f32(self) -> Option<f32> {
+        Some(self as f32)
+    }
+    fn to_f64(self) -> Option<f64> {
+        Some(self as f64)
+    }
+}
+
+impl NumericConvert for i128 {
+    const TYPE_NAME: &'static str = "i128";
+
+    fn is_negative(self) -> bool {
+        self < 0
+    }
+    fn is_fractional(self) -> bool {
+        false
+    }
+
+    fn to_i8(self) -> Option<i8> {
+        self.try_into().ok()
+    }
+    fn to_i16(self) -> Option<i16> {
+        self.try_into().ok()
+    }
+    fn to_i32(self) -> Option<i32> {
+        self.try_into().ok()
+    }
+    fn to_i64(self) -> Option<i64> {
+        self.try_into().ok()
+    }
+    fn to_i128(self) -> Option<i128> {
+        Some(self)
+    }
+    fn to_isize(self) -> Option<isize> {
+        self.try_into().ok()
+    }
+
+    fn to_u8(self) -> Option<u8> {
+        self.try_into().ok()
+    }
+    fn to_u16(self) -> Option<u16> {
+        self.try_into().ok()
+    }
+    fn to_u32(self) -> Option<u32> {
+        self.try_into().ok()
+    }
+    fn to_u64(self) -> Option<u64> {
+        self.try_into().ok()
+    }
+    fn to_u128(self) -> Option<u128> {
+        self.try_into().ok()
+    }
+    fn to_usize(self) -> Option<usize> {
+        self.try_into().ok()
+    }
+
+    fn to_f32(self) -> Option<f32> {
+        Some(self as f32)
+    }
+    fn to_f64(self) -> Option<f64> {
+        Some(self as f64)
+    }
+}
+
+/// Sets a numeric value on the innermost shape of `wip`, converting it to
+/// whichever integer or float type the shape expects.
+///
+/// Fails with [`DeserErrorKind::NumericConversion`] when the value doesn't fit
+/// the target type, and with [`DeserErrorKind::UnsupportedType`] when the shape
+/// isn't numeric at all.
+pub fn set_numeric<'facet, 'shape, N: NumericConvert>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: N,
+) -> Result<(), DeserErrorKind<'shape>> {
+    let shape = wip.innermost_shape();
+
+    // Check if this is a numeric scalar
+    if let Def::Scalar(sd) = shape.def {
+        if let ScalarAffinity::Number(num_affinity) = sd.affinity {
+            use facet_core::{IntegerSize, NumberBits, Signedness};
+
+            // Helper closure to convert and set numeric value
+            macro_rules! convert_and_set {
+                ($converter:expr, $target:ty) => {{
+                    let converted = $converter(value).ok_or_else(|| {
+                        out_of_range(value, stringify!($target), <$target>::MIN, <$target>::MAX)
+                    })?;
+                    wip.set(converted).map_err(DeserErrorKind::ReflectError)?;
+                }};
+            }
+
+            // Check if it's integer or float based on the bits type
+            match num_affinity.bits {
+                NumberBits::Integer { size, sign } => {
+                    // Integer type - check signed/unsigned and size
+                    match (size, sign) {
+                        (IntegerSize::Fixed(bits), Signedness::Signed) => match bits {
+                            8 => convert_and_set!(N::to_i8, i8),
+                            16 => convert_and_set!(N::to_i16, i16),
+                            32 => convert_and_set!(N::to_i32, i32),
+                            64 => convert_and_set!(N::to_i64, i64),
+                            128 => convert_and_set!(N::to_i128, i128),
+                            _ => {
+                                return Err(conversion_error(
+                                    value,
+                                    "unknown fixed-size signed integer",
+                                    NumericConversionReason::Unsupported,
+                                ));
+                            }
+                        },
+                        (IntegerSize::Fixed(bits), Signedness::Unsigned) => match bits {
+                            8 => convert_and_set!(N::to_u8, u8),
+                            16 => convert_and_set!(N::to_u16, u16),
+                            32 => convert_and_set!(N::to_u32, u32),
+                            64 => convert_and_set!(N::to_u64, u64),
+                            128 => convert_and_set!(N::to_u128, u128),
+                            _ => {
+                                return Err(conversion_error(
+                                    value,
+                                    "unknown fixed-size unsigned integer",
+                                    NumericConversionReason::Unsupported,
+                                ));
+                            }
+                        },
+                        (IntegerSize::PointerSized, Signedness::Signed) => {
+                            convert_and_set!(N::to_isize, isize)
+                        }
+                        (IntegerSize::PointerSized, Signedness::Unsigned) => {
+                            convert_and_set!(N::to_usize, usize)
+                        }
+                    }
+                }
+                NumberBits::Float {
+                    sign_bits,
+                    exponent_bits,
+                    mantissa_bits,
+                    ..
+                } => {
+                    // Floating point - calculate total bits
+                    let total_bits = sign_bits + exponent_bits + mantissa_bits;
+                    match total_bits {
+                        32 => convert_and_set!(N::to_f32, f32),
+                        64 => convert_and_set!(N::to_f64, f64),
+                        _ => {
+                            // Unknown float size
+                            return Err(conversion_error(
+                                value,
+                                "unknown float size",
+                                NumericConversionReason::Unsupported,
+                            ));
+                        }
+                    }
+                }
+                _ => {
+                    // Fixed-point, Decimal, or other numeric types not supported
+                    return Err(conversion_error(
+                        value,
+                        "fixed-point or decimal",
+                        NumericConversionReason::Unsupported,
+                    ));
+                }
+            }
+        } else {
+            // Not a number affinity - cannot convert
+            return Err(DeserErrorKind::UnsupportedType {
+                got: shape,
+                wanted: "numeric type",
+            });
+        }
+    } else {
+        // Not a scalar def - cannot convert
+        return Err(DeserErrorKind::UnsupportedType {
+            got: shape,
+            wanted: "scalar type",
+        });
+    }
+
+    Ok(())
+}
+
+/// Builds the error for `value` not fitting between `min` and `max`, the bounds of `to`.
+///
+/// Only conversions to integers fail (every number converts to a float, if imprecisely), so
+/// a fractional value gets its own reason, as does a negative one when `min` is zero.
+fn out_of_range<'shape, N: NumericConvert, T: Display + Default + PartialEq>(
+    value: N,
+    to: &'static str,
+    min: T,
+    max: T,
+) -> DeserErrorKind<'shape> {
+    let reason = if value.is_fractional() {
+        NumericConversionReason::Fractional
+    } else if value.is_negative() && min == T::default() {
+        NumericConversionReason::Negative
+    } else {
+        NumericConversionReason::OutOfRange {
+            min: min.to_string(),
+            max: max.to_string(),
+        }
+    };
+    conversion_error(value, to, reason)
+}
+
+/// Builds the error for `value` not converting to `to`.
+fn conversion_error<'shape, N: NumericConvert>(
+    value: N,
+    to: &'static str,
+    reason: NumericConversionReason,
+) -> DeserErrorKind<'shape> {
+    DeserErrorKind::NumericConversion {
+        from: N::TYPE_NAME,
+        to,
+        value: value.to_string(),
+        reason,
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(feature = "std")]
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use log::trace;
+use owo_colors::OwoColorize;
+
+use crate::{Cooked, DeserError, Expectation, Instruction, Outcome, Span};
+
+/// Told about each step the deserializer takes, to follow what it does with an input.
+///
+/// With one set on a [`Scratch`](crate::Scratch), debuggers and test harnesses get the steps
+/// as values rather than log lines. Without one, the steps are logged at the trace level by
+/// a [`LogObserver`]. All methods do nothing by default.
+pub trait DeserObserver: Send + Sync {
+    /// Called with each instruction, as it's taken off the stack.
+    fn on_instruction(&self, _instruction: &Instruction) {}
+
+    /// Called with each outcome the format reads from the input, with what was expected and
+    /// where the outcome is in the input.
+    fn on_outcome(&self, _expectation: &Expectation, _outcome: &Outcome<'_>, _span: Span<Cooked>) {}
+
+    /// Called with the error that deserialization stops with, if it fails.
+    fn on_error(&self, _error: &DeserError<'_, '_>) {}
+}
+
+/// A [`DeserObserver`] that logs each step at the trace level, which the deserializer uses
+/// when no other is set.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LogObserver;
+
+impl DeserObserver for LogObserver {
+    fn on_instruction(&self, instruction: &Instruction) {
+        trace!("Instruction {:?}", instruction.bright_red());
+    }
+
+    fn on_outcome(&self, expectation: &Expectation, outcome: &Outcome<'_>, span: Span<Cooked>) {
+        trace!(
+            "Read {} at {}..{}, expecting {:?}",
+            outcome.cyan(),
+            span.start(),
+            span.end(),
+            expectation.yellow()
+        );
+    }
+
+    fn on_error(&self, error: &DeserError<'_, '_>) {
+        trace!("Failed at byte {}: {}", error.span.start(), error.message());
+    }
+}
+
+/// A step of deserialization, as recorded by a [`TraceLog`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceEvent {
+    /// An instruction was taken off the stack.
+    Instruction(Instruction),
+    /// The format read an outcome from the input.
+    Outcome {
+        /// What was expected
+        expectation: Expectation,
+        /// What was read
+        outcome: Outcome<'static>,
+        /// Where it is in the input
+        span: Span<Cooked>,
+    },
+    /// Deserialization failed.
+    Error {
+        /// The kind of the error, as it's debug-printed
+        kind: String,
+        /// Where the error is in the input
+        span: Span<Cooked>,
+    },
+}
+
+/// A [`DeserObserver`] that records every step it's told about, until they're taken.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct TraceLog {
+    events: std::sync::Mutex<Vec<TraceEvent>>,
+}
+
+#[cfg(feature = "std")]
+impl TraceLog {
+    /// Creates a log with no step in it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of steps recorded since they were last taken.
+    pub fn len(&self) -> usize {
+        self.events().len()
+    }
+
+    /// Returns `true` if no step was recorded since they were last taken.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Takes the steps recorded so far, leaving the log empty.
+    pub fn take(&self) -> Vec<TraceEvent> {
+        core::mem::take(&mut *self.events())
+    }
+
+    fn events(&self) -> std::sync::MutexGuard<'_, Vec<TraceEvent>> {
+        // The list is always valid, even if a thread panicked while holding the lock
+        self.events
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl DeserObserver for TraceLog {
+    fn on_instruction(&self, instruction: &Instruction) {
+        self.events().push(TraceEvent::Instruction(*instruction));
+    }
+
+    fn on_outcome(&self, expectation: &Expectation, outcome: &Outcome<'_>, span: Span<Cooked>) {
+        self.events().push(TraceEvent::Outcome {
+            expectation: expectation.clone(),
+            outcome: outcome.clone().into_owned(),
+            span,
+        });
+    }
+
+    fn on_error(&self, error: &DeserError<'_, '_>) {
+        self.events().push(TraceEvent::Error {
+            kind: alloc::format!("{:?}", error.kind),
+            span: error.span,
+        });
+    }
+}
@@ -0,0 +1,120 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// The fields to read from a document, as dotted paths like `metadata.version`.
+///
+/// With one set on a [`Scratch`](crate::Scratch), the fields of structs that no path goes
+/// through are skipped by the format without being built, and left to their default. That
+/// makes it cheap to pick a few fields out of a large document, as long as the fields that
+/// are skipped have a default.
+///
+/// A path names struct fields, as they appear in the input. Lists, maps, `Option`s, smart
+/// pointers and enum variants don't take up a segment of their own: `items.name` reads the
+/// `name` of every item in `items`. A path ending at a field reads all of it.
+///
+/// ```
+/// use facet_deserialize::Projection;
+///
+/// let projection = Projection::new(["metadata.version", "items.name"]);
+/// assert!(projection.contains("metadata"));
+/// assert!(projection.contains("metadata.version"));
+/// assert!(!projection.contains("metadata.author"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Projection {
+    /// A tree of the paths' segments, whose root is the first node
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node {
+    name: String,
+    children: Vec<usize>,
+    /// Whether a path ends here, which selects everything under it
+    whole: bool,
+}
+
+/// What is read of the value at some point of a [`Projection`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Selection {
+    /// All of it
+    All,
+    /// The fields under this node of the projection
+    Part(usize),
+}
+
+impl Projection {
+    /// A projection reading the fields at `paths`, and nothing else.
+    pub fn new<I>(paths: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut projection = Projection {
+            nodes: alloc::vec![Node {
+                name: String::new(),
+                children: Vec::new(),
+                whole: false,
+            }],
+        };
+        for path in paths {
+            projection.insert(path.as_ref());
+        }
+        projection
+    }
+
+    fn insert(&mut self, path: &str) {
+        let mut node = 0;
+        for segment in path.split('.') {
+            node = match self.find(node, segment) {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(Node {
+                        name: segment.to_string(),
+                        children: Vec::new(),
+                        whole: false,
+                    });
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.push(child);
+                    child
+                }
+            };
+        }
+        self.nodes[node].whole = true;
+    }
+
+    fn find(&self, node: usize, name: &str) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .copied()
+            .find(|&child| self.nodes[child].name == name)
+    }
+
+    /// Whether the field at `path` is read, because a path goes through it or ends above it.
+    pub fn contains(&self, path: &str) -> bool {
+        let mut selection = Selection::Part(0);
+        for segment in path.split('.') {
+            selection = match self.child(selection, segment) {
+                Some(selection) => selection,
+                None => return false,
+            };
+        }
+        true
+    }
+
+    /// What is read of the field `name` of a value of which `selection` is read, or `None` if
+    /// it's skipped.
+    pub(crate) fn child(&self, selection: Selection, name: &str) -> Option<Selection> {
+        match selection {
+            Selection::All => Some(Selection::All),
+            Selection::Part(node) => self.find(node, name).map(|child| {
+                if self.nodes[child].whole {
+                    Selection::All
+                } else {
+                    Selection::Part(child)
+                }
+            }),
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Items of lists of numbers or booleans, added to the list in bulk rather than one by one.
+
+use alloc::vec::Vec;
+
+use facet_core::Shape;
+use facet_reflect::{Partial, ReflectError};
+
+use crate::{NumericConvert, Scalar};
+
+/// Items read for a list of numbers or booleans, which aren't in the list yet.
+///
+/// Adding items one at a time takes a frame each; a run adds them all with
+/// [`Partial::extend_from_slice`] once the list ends.
+pub(crate) enum ScalarRun {
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+    U64(Vec<u64>),
+    Usize(Vec<usize>),
+    I8(Vec<i8>),
+    I16(Vec<i16>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    Isize(Vec<isize>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    Bool(Vec<bool>),
+}
+
+macro_rules! each_run {
+    ($self:expr, $items:ident => $body:expr) => {
+        match $self {
+            ScalarRun::U8($items) => $body,
+            ScalarRun::U16($items) => $body,
+            ScalarRun::U32($items) => $body,
+            ScalarRun::U64($items) => $body,
+            ScalarRun::Usize($items) => $body,
+            ScalarRun::I8($items) => $body,
+            ScalarRun::I16($items) => $body,
+            ScalarRun::I32($items) => $body,
+            ScalarRun::I64($items) => $body,
+            ScalarRun::Isize($items) => $body,
+            ScalarRun::F32($items) => $body,
+            ScalarRun::F64($items) => $body,
+            ScalarRun::Bool($items) => $body,
+        }
+    };
+}
+
+impl ScalarRun {
+    /// An empty run for items of `shape`, if it's a primitive number or `bool`
+    pub(crate) fn for_shape(shape: &Shape<'_>) -> Option<Self> {
+        let run = if shape.is_type::<u8>() {
+            ScalarRun::U8(Vec::new())
+        } else if shape.is_type::<u16>() {
+            ScalarRun::U16(Vec::new())
+        } else if shape.is_type::<u32>() {
+            ScalarRun::U32(Vec::new())
+        } else if shape.is_type::<u64>() {
+            ScalarRun::U64(Vec::new())
+        } else if shape.is_type::<usize>() {
+            ScalarRun::Usize(Vec::new())
+        } else if shape.is_type::<i8>() {
+            ScalarRun::I8(Vec::new())
+        } else if shape.is_type::<i16>() {
+            ScalarRun::I16(Vec::new())
+        } else if shape.is_type::<i32>() {
+            ScalarRun::I32(Vec::new())
+        } else if shape.is_type::<i64>() {
+            ScalarRun::I64(Vec::new())
+        } else if shape.is_type::<isize>() {
+            ScalarRun::Isize(Vec::new())
+        } else if shape.is_type::<f32>() {
+            ScalarRun::F32(Vec::new())
+        } else if shape.is_type::<f64>() {
+            ScalarRun::F64(Vec::new())
+        } else if shape.is_type::<bool>() {
+            ScalarRun::Bool(Vec::new())
+        } else {
+            return None;
+        };
+        Some(run)
+    }
+
+    /// Whether the run holds items of `shape`
+    pub(crate) fn is_for(&self, shape: &Shape<'_>) -> bool {
+        match self {
+            ScalarRun::U8(_) => shape.is_type::<u8>(),
+            ScalarRun::U16(_) => shape.is_type::<u16>(),
+            ScalarRun::U32(_) => shape.is_type::<u32>(),
+            ScalarRun::U64(_) => shape.is_type::<u64>(),
+            ScalarRun::Usize(_) => shape.is_type::<usize>(),
+            ScalarRun::I8(_) => shape.is_type::<i8>(),
+            ScalarRun::I16(_) => shape.is_type::<i16>(),
+            ScalarRun::I32(_) => shape.is_type::<i32>(),
+            ScalarRun::I64(_) => shape.is_type::<i64>(),
+            ScalarRun::Isize(_) => shape.is_type::<isize>(),
+            ScalarRun::F32(_) => shape.is_type::<f32>(),
+            ScalarRun::F64(_) => shape.is_type::<f64>(),
+            ScalarRun::Bool(_) => shape.is_type::<bool>(),
+        }
+    }
+
+    /// Adds `scalar` to the run. Returns `false`, adding nothing, if it isn't a value of the
+    /// run's type: it's then read the usual way, which reports why.
+    pub(crate) fn push(&mut self, scalar: &Scalar<'_>) -> bool {
+        macro_rules! push {
+            ($items:expr, $to:ident) => {{
+                let value = match *scalar {
+                    Scalar::U64(n) => n.$to(),
+                    Scalar::I64(n) => n.$to(),
+                    Scalar::F64(n) => n.$to(),
+                    Scalar::U128(n) => n.$to(),
+                    Scalar::I128(n) => n.$to(),
+                    _ => None,
+                };
+                match value {
+                    Some(value) => {
+                        $items.push(value);
+                        true
+                    }
+                    None => false,
+                }
+            }};
+        }
+
+        match self {
+            ScalarRun::U8(items) => push!(items, to_u8),
+            ScalarRun::U16(items) => push!(items, to_u16),
+            ScalarRun::U32(items) => push!(items, to_u32),
+            ScalarRun::U64(items) => push!(items, to_u64),
+            ScalarRun::Usize(items) => push!(items, to_usize),
+            ScalarRun::I8(items) => push!(items, to_i8),
+            ScalarRun::I16(items) => push!(items, to_i16),
+            ScalarRun::I32(items) => push!(items, to_i32),
+            ScalarRun::I64(items) => push!(items, to_i64),
+            ScalarRun::Isize(items) => push!(items, to_isize),
+            ScalarRun::F32(items) => push!(items, to_f32),
+            ScalarRun::F64(items) => push!(items, to_f64),
+            ScalarRun::Bool(items) => match scalar {
+                Scalar::Bool(b) => {
+                    items.push(*b);
+                    true
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Adds the items of the run to the list `wip` is at, leaving the run empty
+    pub(crate) fn flush<'shape>(
+        &mut self,
+        wip: &mut Partial<'_, 'shape>,
+    ) -> Result<(), ReflectError<'shape>> {
+        each_run!(self, items => {
+            if !items.is_empty() {
+                wip.extend_from_slice(items)?;
+                items.clear();
+            }
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use alloc::vec::Vec;
+use facet_serialize::Serializer;
+
+use crate::{Cooked, DeserError, Event, Events, Format, InputDebug, Scalar, Span, ToCooked};
+
+/// Error returned by [`transcode`]: either the input couldn't be read, or the
+/// serializer failed.
+#[derive(Debug)]
+pub enum TranscodeError<'input, 'shape, E> {
+    /// The input is malformed.
+    Deser(DeserError<'input, 'shape, Cooked>),
+    /// The serializer returned an error.
+    Ser(E),
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for TranscodeError<'_, '_, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TranscodeError::Deser(err) => write!(f, "{err}"),
+            TranscodeError::Ser(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl<E: core::fmt::Debug + core::fmt::Display> core::error::Error for TranscodeError<'_, '_, E> {}
+
+/// Converts one value from a deserialization format into a serializer, without
+/// a Rust type in between.
+///
+/// The input's [`Event`]s are passed straight to the serializer: objects become
+/// maps with string keys, lists become arrays, and `null` becomes
+/// [`Serializer::serialize_none`]. No value is built in memory. The input is
+/// read twice, first to count the items of every list and object, because some
+/// formats (like MessagePack) write lengths before the items.
+///
+/// Formats that look at the value being built to decide how to read the input,
+/// like command-line arguments, can't be transcoded.
+pub fn transcode<'input, 'shape, F, S>(
+    input: &'input F::Input<'input>,
+    format: F,
+    serializer: &mut S,
+) -> Result<(), TranscodeError<'input, 'shape, S::Error>>
+where
+    F: Format + 'shape,
+    F::Input<'input>: InputDebug,
+    Span<F::SpanType>: ToCooked<'input, F>,
+    S: Serializer<'shape>,
+    'shape: 'input,
+{
+    // The length of every list and object, in the order they start
+    let mut lens: Vec<usize> = Vec::new();
+    // The open containers, as their index in `lens` and whether they're objects
+    let mut open: Vec<(usize, bool)> = Vec::new();
+    let mut events = Events::new(input, format);
+    for event in &mut events {
+        let event = event.map_err(TranscodeError::Deser)?.node;
+        if let Some(&(index, object)) = open.last() {
+            let counted = match event {
+                Event::Key(_) => true,
+                Event::Scalar(_) | Event::ListStarted | Event::ObjectStarted => !object,
+                _ => false,
+            };
+            if counted {
+                lens[index] += 1;
+            }
+        }
+        match event {
+            Event::ListStarted | Event::ObjectStarted => {
+                open.push((lens.len(), matches!(event, Event::ObjectStarted)));
+                lens.push(0);
+            }
+            Event::ListEnded | Event::ObjectEnded => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+
+    let mut lens = lens.into_iter();
+    // Whether each open container is an object
+    let mut open: Vec<bool> = Vec::new();
+    for event in Events::new(input, events.into_format()) {
+        let event = event.map_err(TranscodeError::Deser)?.node;
+        let ended = match event {
+            Event::Scalar(scalar) => {
+                match scalar {
+                    Scalar::String(s) => serializer.serialize_str(&s),
+                    Scalar::U64(n) => serializer.serialize_u64(n),
+                    Scalar::I64(n) => serializer.serialize_i64(n),
+                    Scalar::F64(n) => serializer.serialize_f64(n),
+                    Scalar::U128(n) => serializer.serialize_u128(n),
+                    Scalar::I128(n) => serializer.serialize_i128(n),
+                    Scalar::Bool(b) => serializer.serialize_bool(b),
+                    Scalar::Null => serializer.serialize_none(),
+                }
+                .map_err(TranscodeError::Ser)?;
+                true
+            }
+            Event::ListStarted => {
+                open.push(false);
+                serializer
+                    .start_array(lens.next())
+                    .map_err(TranscodeError::Ser)?;
+                false
+            }
+            Event::ObjectStarted => {
+                open.push(true);
+                serializer
+                    .start_map(lens.next())
+                    .map_err(TranscodeError::Ser)?;
+                false
+            }
+            Event::Key(key) => {
+                serializer.begin_map_key().map_err(TranscodeError::Ser)?;
+                serializer
+                    .serialize_str(&key)
+                    .map_err(TranscodeError::Ser)?;
+                serializer.end_map_key().map_err(TranscodeError::Ser)?;
+                serializer.begin_map_value().map_err(TranscodeError::Ser)?;
+                false
+            }
+            Event::ListEnded => {
+                open.pop();
+                serializer.end_array().map_err(TranscodeError::Ser)?;
+                true
+            }
+            Event::ObjectEnded => {
+                open.pop();
+                serializer.end_map().map_err(TranscodeError::Ser)?;
+                true
+            }
+        };
+        // A value inside an object is complete: so is its entry
+        if ended && open.last() == Some(&true) {
+            serializer.end_map_value().map_err(TranscodeError::Ser)?;
+        }
+    }
+    Ok(())
+}
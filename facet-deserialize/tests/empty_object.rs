@@ -0,0 +1,136 @@
+// An object that ends where a value is expected reads as an empty object.
+
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_deserialize::*;
+
+/// Replays a fixed list of outcomes, one input byte each.
+struct Replay(Vec<Outcome<'static>>);
+
+impl Format for Replay {
+    type Input<'input> = [u8];
+    type SpanType = Cooked;
+
+    fn source(&self) -> &'static str {
+        "replay"
+    }
+
+    fn next<'input, 'facet, 'shape>(
+        &mut self,
+        nd: NextData<'input, 'facet, 'shape, Self::SpanType, Self::Input<'input>>,
+        _expectation: Expectation,
+    ) -> NextResult<
+        'input,
+        'facet,
+        'shape,
+        Spanned<Outcome<'input>, Self::SpanType>,
+        Spanned<DeserErrorKind<'shape>, Self::SpanType>,
+        Self::SpanType,
+        Self::Input<'input>,
+    >
+    where
+        'shape: 'input,
+    {
+        let position = nd.start();
+        let result = match self.0.get(position) {
+            Some(outcome) => Ok(Spanned {
+                node: outcome.clone(),
+                span: Span::new(position, 1),
+            }),
+            None => Err(Spanned {
+                node: DeserErrorKind::UnexpectedEof { wanted: "a value" },
+                span: Span::new(position, 0),
+            }),
+        };
+        (nd, result)
+    }
+
+    fn skip<'input, 'facet, 'shape>(
+        &mut self,
+        nd: NextData<'input, 'facet, 'shape, Self::SpanType, Self::Input<'input>>,
+    ) -> NextResult<
+        'input,
+        'facet,
+        'shape,
+        Span<Self::SpanType>,
+        Spanned<DeserErrorKind<'shape>, Self::SpanType>,
+        Self::SpanType,
+        Self::Input<'input>,
+    >
+    where
+        'shape: 'input,
+    {
+        let span = Span::new(nd.start(), 1);
+        (nd, Ok(span))
+    }
+}
+
+fn replay<'facet, T: Facet<'facet>>(
+    outcomes: Vec<Outcome<'static>>,
+) -> Result<T, DeserError<'static, 'static>> {
+    let input = vec![0u8; outcomes.len()].leak();
+    deserialize(input, Replay(outcomes))
+}
+
+#[derive(Facet, Debug, Default, PartialEq)]
+#[facet(default)]
+struct Settings {
+    level: u8,
+    name: String,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Marker {}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Outer {
+    settings: Settings,
+    count: u32,
+}
+
+#[test]
+fn empty_object_reads_defaulted_structs() {
+    let settings: Settings = replay(vec![Outcome::ObjectEnded]).unwrap();
+    assert_eq!(settings, Settings::default());
+
+    let marker: Marker = replay(vec![Outcome::ObjectEnded]).unwrap();
+    assert_eq!(marker, Marker {});
+
+    let map: HashMap<String, u8> = replay(vec![Outcome::ObjectEnded]).unwrap();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn empty_object_in_a_field() {
+    let outer: Outer = replay(vec![
+        Outcome::ObjectStarted,
+        Outcome::Scalar(Scalar::String("settings".into())),
+        Outcome::ObjectEnded,
+        Outcome::Scalar(Scalar::String("count".into())),
+        Outcome::Scalar(Scalar::U64(3)),
+        Outcome::ObjectEnded,
+    ])
+    .unwrap();
+    assert_eq!(
+        outer,
+        Outer {
+            settings: Settings::default(),
+            count: 3,
+        }
+    );
+}
+
+#[test]
+fn empty_object_is_an_error_for_other_values() {
+    let err = replay::<u32>(vec![Outcome::ObjectEnded]).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        DeserErrorKind::UnexpectedOutcome {
+            got: Outcome::ObjectEnded,
+            ..
+        }
+    ));
+
+    assert!(replay::<Outer>(vec![Outcome::ObjectEnded]).is_err());
+}
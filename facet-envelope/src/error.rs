@@ -0,0 +1,72 @@
+use core::fmt;
+
+/// Why an envelope was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EnvelopeError {
+    /// The bytes don't start with the envelope's magic number.
+    NotAnEnvelope,
+    /// The envelope was written by a newer version of this crate.
+    UnsupportedVersion(u8),
+    /// The bytes end before the envelope does.
+    Truncated,
+    /// Something follows the envelope.
+    TrailingData,
+    /// The checksum doesn't match the contents, which were damaged.
+    ChecksumMismatch {
+        /// The checksum written in the envelope
+        expected: u32,
+        /// The checksum of what was read
+        actual: u32,
+    },
+    /// The payload is in another format than the one asked for.
+    FormatMismatch {
+        /// The format asked for
+        expected: String,
+        /// The format written in the envelope
+        found: String,
+    },
+    /// The payload was written for a version of the type whose shape differs from this one.
+    ShapeMismatch {
+        /// The name of the type asked for
+        type_name: String,
+        /// The fingerprint of the type asked for
+        expected: u64,
+        /// The fingerprint written in the envelope
+        found: u64,
+    },
+    /// The payload isn't a valid value of the type, with the format's error message.
+    Deserialize(String),
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvelopeError::NotAnEnvelope => write!(f, "Not an envelope"),
+            EnvelopeError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported envelope version {version}")
+            }
+            EnvelopeError::Truncated => write!(f, "Envelope is truncated"),
+            EnvelopeError::TrailingData => write!(f, "Trailing data after the envelope"),
+            EnvelopeError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "Envelope is damaged: checksum is {actual:08x}, expected {expected:08x}"
+            ),
+            EnvelopeError::FormatMismatch { expected, found } => {
+                write!(f, "Payload is {found}, expected {expected}")
+            }
+            EnvelopeError::ShapeMismatch {
+                type_name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Payload was written for an incompatible version of {type_name} \
+                 (fingerprint {found:016x}, this version is {expected:016x})"
+            ),
+            EnvelopeError::Deserialize(e) => write!(f, "Deserialization error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for EnvelopeError {}
@@ -0,0 +1,208 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use facet_core::Facet;
+
+mod crc;
+use crc::crc32;
+
+mod error;
+pub use error::*;
+
+/// The first bytes of every envelope
+const MAGIC: [u8; 4] = *b"FENV";
+
+/// The version of the layout written, which is refused by older versions of this crate if
+/// it ever changes
+const VERSION: u8 = 1;
+
+/// A serialized value, with what's needed to know whether it can be read back: the format
+/// it's in, and the fingerprint of the shape of its type (see
+/// [`Shape::fingerprint`](facet_core::Shape::fingerprint)).
+///
+/// An envelope is written as:
+///
+/// | bytes        | contents                                       |
+/// |--------------|------------------------------------------------|
+/// | 4            | `FENV`                                         |
+/// | 1            | the layout version, 1                          |
+/// | 1            | the length of the format tag                   |
+/// | up to 255    | the format tag, in UTF-8                       |
+/// | 8            | the fingerprint, big-endian                    |
+/// | 8            | the length of the payload, big-endian          |
+/// | any          | the payload                                    |
+/// | 4            | the CRC-32 of everything before it, big-endian |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Envelope<'a> {
+    /// A short tag naming the payload's format, like `json`
+    pub format: &'a str,
+    /// The fingerprint of the shape of the type the payload was written for
+    pub fingerprint: u64,
+    /// The serialized value
+    pub payload: &'a [u8],
+}
+
+impl<'a> Envelope<'a> {
+    /// An envelope for `payload`, a value of `T` serialized in `format`.
+    ///
+    /// # Panics
+    ///
+    /// If `format` is longer than 255 bytes.
+    pub fn new<'facet, T: Facet<'facet>>(format: &'a str, payload: &'a [u8]) -> Self {
+        assert!(
+            format.len() <= u8::MAX as usize,
+            "format tag is longer than 255 bytes"
+        );
+        Envelope {
+            format,
+            fingerprint: T::SHAPE.fingerprint(),
+            payload,
+        }
+    }
+
+    /// Writes the envelope, checksum included.
+    ///
+    /// # Panics
+    ///
+    /// If the format tag is longer than 255 bytes.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let format_len =
+            u8::try_from(self.format.len()).expect("format tag is longer than 255 bytes");
+        let mut bytes = Vec::with_capacity(26 + self.format.len() + self.payload.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.push(format_len);
+        bytes.extend_from_slice(self.format.as_bytes());
+        bytes.extend_from_slice(&self.fingerprint.to_be_bytes());
+        bytes.extend_from_slice(&(self.payload.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(self.payload);
+        let crc = crc32(&bytes);
+        bytes.extend_from_slice(&crc.to_be_bytes());
+        bytes
+    }
+
+    /// Reads an envelope that takes up the whole of `bytes`, and checks its checksum, but
+    /// not what it's for: use [`Envelope::check`] for that, or [`open`] to do both.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, EnvelopeError> {
+        if !bytes.starts_with(&MAGIC) {
+            return Err(if MAGIC.starts_with(bytes) {
+                EnvelopeError::Truncated
+            } else {
+                EnvelopeError::NotAnEnvelope
+            });
+        }
+        let rest = &bytes[MAGIC.len()..];
+
+        let (&[version, format_len], rest) = rest
+            .split_first_chunk::<2>()
+            .ok_or(EnvelopeError::Truncated)?;
+        if version != VERSION {
+            return Err(EnvelopeError::UnsupportedVersion(version));
+        }
+        let (format, rest) = rest
+            .split_at_checked(format_len as usize)
+            .ok_or(EnvelopeError::Truncated)?;
+        let (fingerprint, rest) = rest
+            .split_first_chunk::<8>()
+            .ok_or(EnvelopeError::Truncated)?;
+        let (payload_len, rest) = rest
+            .split_first_chunk::<8>()
+            .ok_or(EnvelopeError::Truncated)?;
+        let (payload, rest) = usize::try_from(u64::from_be_bytes(*payload_len))
+            .ok()
+            .and_then(|len| rest.split_at_checked(len))
+            .ok_or(EnvelopeError::Truncated)?;
+        let (expected, rest) = rest
+            .split_first_chunk::<4>()
+            .ok_or(EnvelopeError::Truncated)?;
+        if !rest.is_empty() {
+            return Err(EnvelopeError::TrailingData);
+        }
+
+        let expected = u32::from_be_bytes(*expected);
+        let actual = crc32(&bytes[..bytes.len() - 4]);
+        if actual != expected {
+            return Err(EnvelopeError::ChecksumMismatch { expected, actual });
+        }
+
+        Ok(Envelope {
+            // The checksum matched, so this was written that way
+            format: core::str::from_utf8(format).map_err(|_| EnvelopeError::NotAnEnvelope)?,
+            fingerprint: u64::from_be_bytes(*fingerprint),
+            payload,
+        })
+    }
+
+    /// Checks that the payload is in `format`, and was written for a version of `T` with
+    /// the same shape as this one, then returns it.
+    pub fn check<'facet, T: Facet<'facet>>(&self, format: &str) -> Result<&'a [u8], EnvelopeError> {
+        if self.format != format {
+            return Err(EnvelopeError::FormatMismatch {
+                expected: format.to_string(),
+                found: self.format.to_string(),
+            });
+        }
+        let expected = T::SHAPE.fingerprint();
+        if self.fingerprint != expected {
+            return Err(EnvelopeError::ShapeMismatch {
+                type_name: T::SHAPE.to_string(),
+                expected,
+                found: self.fingerprint,
+            });
+        }
+        Ok(self.payload)
+    }
+}
+
+/// Puts `payload`, a value of `T` serialized in `format`, in an envelope.
+///
+/// # Panics
+///
+/// If `format` is longer than 255 bytes.
+pub fn seal<'facet, T: Facet<'facet>>(format: &str, payload: &[u8]) -> Vec<u8> {
+    Envelope::new::<T>(format, payload).to_vec()
+}
+
+/// Takes the payload out of an envelope written with [`seal`], if it's undamaged, in
+/// `format`, and was written for a version of `T` with the same shape as this one.
+pub fn open<'a, 'facet, T: Facet<'facet>>(
+    bytes: &'a [u8],
+    format: &str,
+) -> Result<&'a [u8], EnvelopeError> {
+    Envelope::parse(bytes)?.check::<T>(format)
+}
+
+/// Serializes `value` to JSON, in an envelope with the `json` format tag.
+#[cfg(feature = "json")]
+pub fn to_vec_json<'facet, T: Facet<'facet>>(value: &'facet T) -> Vec<u8> {
+    seal::<T>("json", facet_json::to_string(value).as_bytes())
+}
+
+/// Deserializes a value from JSON in an envelope written with [`to_vec_json`], if it was
+/// written for a version of `T` with the same shape as this one.
+#[cfg(feature = "json")]
+pub fn from_slice_json<T>(bytes: &[u8]) -> Result<T, EnvelopeError>
+where
+    T: for<'facet> Facet<'facet>,
+{
+    let payload = open::<T>(bytes, "json")?;
+    facet_json::from_slice(payload).map_err(|e| EnvelopeError::Deserialize(e.to_string()))
+}
+
+/// Serializes `value` to MessagePack, in an envelope with the `msgpack` format tag.
+#[cfg(feature = "msgpack")]
+pub fn to_vec_msgpack<'facet, T: Facet<'facet>>(value: &'facet T) -> Vec<u8> {
+    seal::<T>("msgpack", &facet_msgpack::to_vec(value))
+}
+
+/// Deserializes a value from MessagePack in an envelope written with [`to_vec_msgpack`],
+/// if it was written for a version of `T` with the same shape as this one.
+#[cfg(feature = "msgpack")]
+pub fn from_slice_msgpack<T>(bytes: &[u8]) -> Result<T, EnvelopeError>
+where
+    T: for<'facet> Facet<'facet>,
+{
+    let payload = open::<T>(bytes, "msgpack")?;
+    facet_msgpack::from_slice(payload).map_err(|e| EnvelopeError::Deserialize(e.to_string()))
+}
@@ -0,0 +1,175 @@
+use facet::Facet;
+use facet_envelope::{Envelope, EnvelopeError, open, seal};
+
+mod v1 {
+    use facet::Facet;
+
+    #[derive(Debug, PartialEq, Facet)]
+    pub struct Profile {
+        pub name: String,
+        pub age: u32,
+    }
+}
+
+mod v2 {
+    use facet::Facet;
+
+    #[derive(Debug, PartialEq, Facet)]
+    pub struct Profile {
+        pub name: String,
+        pub age: u32,
+        pub email: Option<String>,
+    }
+}
+
+#[test]
+fn layout() {
+    facet_testhelpers::setup();
+
+    let bytes = seal::<u8>("raw", &[42]);
+    let fingerprint = u8::SHAPE.fingerprint();
+
+    let mut expected = b"FENV\x01\x03raw".to_vec();
+    expected.extend_from_slice(&fingerprint.to_be_bytes());
+    expected.extend_from_slice(&1u64.to_be_bytes());
+    expected.push(42);
+    assert_eq!(&bytes[..bytes.len() - 4], expected);
+
+    assert_eq!(
+        Envelope::parse(&bytes),
+        Ok(Envelope {
+            format: "raw",
+            fingerprint,
+            payload: &[42],
+        })
+    );
+    assert_eq!(open::<u8>(&bytes, "raw"), Ok(&[42][..]));
+}
+
+#[test]
+fn checksum() {
+    facet_testhelpers::setup();
+
+    let bytes = seal::<()>("", b"123456789");
+    let envelope = Envelope::parse(&bytes).unwrap();
+    assert_eq!(envelope.payload, b"123456789");
+
+    for i in 0..bytes.len() {
+        let mut damaged = bytes.clone();
+        damaged[i] ^= 0x10;
+        assert!(
+            open::<()>(&damaged, "").is_err(),
+            "flipping a bit of byte {i} went unnoticed"
+        );
+    }
+
+    let mut damaged = bytes.clone();
+    let payload = damaged.len() - 5;
+    damaged[payload] = b'0';
+    assert!(matches!(
+        Envelope::parse(&damaged),
+        Err(EnvelopeError::ChecksumMismatch { .. })
+    ));
+}
+
+#[test]
+fn malformed() {
+    facet_testhelpers::setup();
+
+    let bytes = seal::<u8>("raw", &[42]);
+
+    assert_eq!(Envelope::parse(b""), Err(EnvelopeError::Truncated));
+    assert_eq!(Envelope::parse(b"{}"), Err(EnvelopeError::NotAnEnvelope));
+    for len in 0..bytes.len() {
+        assert_eq!(
+            Envelope::parse(&bytes[..len]),
+            Err(EnvelopeError::Truncated)
+        );
+    }
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(Envelope::parse(&trailing), Err(EnvelopeError::TrailingData));
+
+    let mut newer = bytes.clone();
+    newer[4] = 2;
+    assert_eq!(
+        Envelope::parse(&newer),
+        Err(EnvelopeError::UnsupportedVersion(2))
+    );
+}
+
+#[test]
+fn mismatches() {
+    facet_testhelpers::setup();
+
+    let bytes = seal::<v1::Profile>("json", b"{}");
+
+    assert_eq!(
+        open::<v1::Profile>(&bytes, "msgpack"),
+        Err(EnvelopeError::FormatMismatch {
+            expected: "msgpack".to_string(),
+            found: "json".to_string(),
+        })
+    );
+
+    let err = open::<v2::Profile>(&bytes, "json").unwrap_err();
+    assert_eq!(
+        err,
+        EnvelopeError::ShapeMismatch {
+            type_name: "Profile".to_string(),
+            expected: v2::Profile::SHAPE.fingerprint(),
+            found: v1::Profile::SHAPE.fingerprint(),
+        }
+    );
+    assert!(
+        err.to_string()
+            .starts_with("Payload was written for an incompatible version of Profile"),
+        "{err}"
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json() {
+    use facet_envelope::{from_slice_json, to_vec_json};
+
+    facet_testhelpers::setup();
+
+    let profile = v1::Profile {
+        name: "Jo".to_string(),
+        age: 41,
+    };
+    let bytes = to_vec_json(&profile);
+    assert_eq!(from_slice_json::<v1::Profile>(&bytes), Ok(profile));
+    assert!(matches!(
+        from_slice_json::<v2::Profile>(&bytes),
+        Err(EnvelopeError::ShapeMismatch { .. })
+    ));
+
+    let invalid = seal::<v1::Profile>("json", br#"{"name": "Jo"}"#);
+    assert!(matches!(
+        from_slice_json::<v1::Profile>(&invalid),
+        Err(EnvelopeError::Deserialize(_))
+    ));
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn msgpack() {
+    use facet_envelope::{from_slice_msgpack, to_vec_msgpack};
+
+    facet_testhelpers::setup();
+
+    let profile = v2::Profile {
+        name: "Jo".to_string(),
+        age: 41,
+        email: Some("jo@example.com".to_string()),
+    };
+    let bytes = to_vec_msgpack(&profile);
+    assert_eq!(from_slice_msgpack::<v2::Profile>(&bytes), Ok(profile));
+    assert!(matches!(
+        from_slice_msgpack::<v1::Profile>(&bytes),
+        Err(EnvelopeError::ShapeMismatch { .. })
+    ));
+}
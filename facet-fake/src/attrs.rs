@@ -0,0 +1,102 @@
+//! Per-field `#[facet(fake = "...")]` and `#[facet(fake(range = ...))]` attributes.
+
+use alloc::{borrow::ToOwned, string::String};
+use facet_core::{Field, FieldAttribute};
+
+use crate::{
+    GenerateError,
+    calendar::{SECONDS_PER_DAY, parse_date},
+    kind::Kind,
+};
+
+/// What a field's fake attributes ask for.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Attrs<'shape> {
+    /// The kind of fake, with the attribute that named it for error messages.
+    pub kind: Option<(Kind, &'shape str)>,
+    pub dates: Dates,
+}
+
+/// Inclusive bounds for generated moments, in seconds since the Unix epoch.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Dates {
+    pub from: i64,
+    pub to: i64,
+}
+
+impl Default for Dates {
+    /// From 2000-01-01 to the end of 2029.
+    fn default() -> Self {
+        Dates {
+            from: 946_684_800,
+            to: 1_893_455_999,
+        }
+    }
+}
+
+impl<'shape> Attrs<'shape> {
+    /// Reads the attributes of a struct or variant field.
+    pub fn of(field: Option<&'shape Field<'shape>>) -> Result<Self, GenerateError<'shape>> {
+        let mut attrs = Attrs::default();
+        let Some(field) = field else {
+            return Ok(attrs);
+        };
+        for attr in field.attributes {
+            let FieldAttribute::Arbitrary(content) = attr else {
+                continue;
+            };
+            let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+            let invalid = || GenerateError::InvalidAttribute((*content).to_owned());
+            if let Some(name) = compact.strip_prefix("fake=") {
+                let kind = unquote(name)
+                    .and_then(Kind::from_name)
+                    .ok_or_else(invalid)?;
+                attrs.kind = Some((kind, content));
+            } else if let Some(args) = compact
+                .strip_prefix("fake(")
+                .and_then(|rest| rest.strip_suffix(')'))
+            {
+                for arg in args.split(',').filter(|arg| !arg.is_empty()) {
+                    match arg.split_once('=') {
+                        Some(("range", range)) => {
+                            attrs.dates = Dates::parse(range).ok_or_else(invalid)?;
+                        }
+                        _ => return Err(invalid()),
+                    }
+                }
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+impl Dates {
+    /// Parses a range of dates such as `"1950-01-01".."2006-01-01"`; a missing
+    /// bound keeps its default.
+    fn parse(text: &str) -> Option<Self> {
+        let (from, rest) = text.split_once("..")?;
+        let (to, inclusive) = match rest.strip_prefix('=') {
+            Some(to) => (to, true),
+            None => (rest, false),
+        };
+        let mut dates = Dates::default();
+        if !from.is_empty() {
+            dates.from = parse_date(unquote(from)?)? * SECONDS_PER_DAY;
+        }
+        if !to.is_empty() {
+            let end = parse_date(unquote(to)?)? * SECONDS_PER_DAY;
+            dates.to = if inclusive {
+                end + SECONDS_PER_DAY - 1
+            } else {
+                end - 1
+            };
+        } else if inclusive {
+            return None;
+        }
+        (dates.from <= dates.to).then_some(dates)
+    }
+}
+
+fn unquote(text: &str) -> Option<&str> {
+    text.strip_prefix('"')?.strip_suffix('"')
+}
@@ -0,0 +1,236 @@
+//! Word lists the fakes are drawn from.
+
+pub(crate) const FIRST_NAMES: &[&str] = &[
+    "Ada", "Alan", "Amara", "Ana", "Ben", "Carlos", "Chen", "Chloe", "David", "Elena", "Emma",
+    "Fatima", "Grace", "Hannah", "Hiro", "Ivan", "James", "Jia", "Julia", "Kofi", "Lars", "Leila",
+    "Liam", "Lucia", "Maya", "Mohammed", "Nadia", "Noah", "Olivia", "Omar", "Priya", "Rafael",
+    "Sara", "Sofia", "Tariq", "Tom", "Yuki", "Zoe",
+];
+
+pub(crate) const LAST_NAMES: &[&str] = &[
+    "Adams", "Alvarez", "Baker", "Brown", "Chen", "Clark", "Cohen", "Diaz", "Evans", "Fischer",
+    "Garcia", "Gupta", "Hall", "Hansen", "Ito", "Jones", "Kim", "Kowalski", "Lee", "Lopez",
+    "Martin", "Moreau", "Nguyen", "Novak", "Okafor", "Patel", "Rossi", "Santos", "Schmidt",
+    "Silva", "Smith", "Suzuki", "Taylor", "Walker", "Wang", "Wilson", "Young",
+];
+
+pub(crate) const STREETS: &[&str] = &[
+    "Main",
+    "Oak",
+    "Maple",
+    "Cedar",
+    "Elm",
+    "Pine",
+    "Park",
+    "Lake",
+    "Hill",
+    "Church",
+    "Market",
+    "Mill",
+    "River",
+    "Spring",
+    "Sunset",
+    "Washington",
+    "Highland",
+    "Forest",
+    "Meadow",
+    "Willow",
+];
+
+pub(crate) const STREET_SUFFIXES: &[&str] = &[
+    "Street",
+    "Avenue",
+    "Road",
+    "Lane",
+    "Drive",
+    "Court",
+    "Boulevard",
+    "Way",
+    "Place",
+];
+
+pub(crate) const CITIES: &[&str] = &[
+    "Springfield",
+    "Riverside",
+    "Fairview",
+    "Franklin",
+    "Greenville",
+    "Bristol",
+    "Clinton",
+    "Georgetown",
+    "Salem",
+    "Madison",
+    "Ashland",
+    "Oxford",
+    "Milton",
+    "Newport",
+    "Dayton",
+    "Auburn",
+    "Burlington",
+    "Chester",
+    "Dover",
+    "Kingston",
+];
+
+pub(crate) const STATES: &[&str] = &[
+    "Alabama",
+    "California",
+    "Colorado",
+    "Florida",
+    "Georgia",
+    "Illinois",
+    "Maine",
+    "Michigan",
+    "Nevada",
+    "New York",
+    "Ohio",
+    "Oregon",
+    "Texas",
+    "Utah",
+    "Vermont",
+    "Washington",
+];
+
+/// Country names with their ISO 3166-1 alpha-2 codes.
+pub(crate) const COUNTRIES: &[(&str, &str)] = &[
+    ("Argentina", "AR"),
+    ("Australia", "AU"),
+    ("Brazil", "BR"),
+    ("Canada", "CA"),
+    ("France", "FR"),
+    ("Germany", "DE"),
+    ("India", "IN"),
+    ("Italy", "IT"),
+    ("Japan", "JP"),
+    ("Kenya", "KE"),
+    ("Mexico", "MX"),
+    ("Netherlands", "NL"),
+    ("Nigeria", "NG"),
+    ("Norway", "NO"),
+    ("Poland", "PL"),
+    ("South Korea", "KR"),
+    ("Spain", "ES"),
+    ("Sweden", "SE"),
+    ("United Kingdom", "GB"),
+    ("United States", "US"),
+];
+
+pub(crate) const COMPANY_WORDS: &[&str] = &[
+    "Acme", "Apex", "Blue", "Bright", "Cobalt", "Crest", "Delta", "Ever", "Granite", "Harbor",
+    "Iron", "Lumen", "Nimbus", "North", "Orbit", "Pioneer", "Quantum", "Summit", "Vertex",
+    "Willow",
+];
+
+pub(crate) const COMPANY_SUFFIXES: &[&str] = &[
+    "Inc.",
+    "LLC",
+    "Ltd.",
+    "Group",
+    "Labs",
+    "Systems",
+    "Industries",
+    "Partners",
+    "& Co.",
+];
+
+pub(crate) const JOB_LEVELS: &[&str] = &["Junior", "Senior", "Lead", "Principal", "Chief"];
+
+pub(crate) const JOB_ROLES: &[&str] = &[
+    "Engineer",
+    "Designer",
+    "Analyst",
+    "Accountant",
+    "Consultant",
+    "Manager",
+    "Architect",
+    "Developer",
+    "Administrator",
+    "Officer",
+    "Specialist",
+    "Coordinator",
+];
+
+pub(crate) const PRODUCT_ADJECTIVES: &[&str] = &[
+    "Small",
+    "Ergonomic",
+    "Rustic",
+    "Sleek",
+    "Practical",
+    "Durable",
+    "Handmade",
+    "Compact",
+    "Refined",
+    "Gorgeous",
+    "Lightweight",
+    "Modern",
+];
+
+pub(crate) const PRODUCT_MATERIALS: &[&str] = &[
+    "Steel", "Wooden", "Cotton", "Granite", "Leather", "Bamboo", "Ceramic", "Plastic", "Wool",
+    "Glass",
+];
+
+pub(crate) const PRODUCT_NOUNS: &[&str] = &[
+    "Chair", "Table", "Lamp", "Keyboard", "Bottle", "Backpack", "Mug", "Shoes", "Watch", "Gloves",
+    "Wallet", "Notebook", "Speaker", "Blanket",
+];
+
+/// Reserved for documentation by RFC 2606, so generated emails and URLs never reach anyone.
+pub(crate) const DOMAINS: &[&str] = &["example.com", "example.org", "example.net"];
+
+pub(crate) const CURRENCIES: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "CHF", "CAD", "AUD", "CNY", "INR", "BRL", "SEK", "NOK",
+];
+
+pub(crate) const LOCALES: &[&str] = &[
+    "en-US", "en-GB", "fr-FR", "de-DE", "es-ES", "it-IT", "ja-JP", "pt-BR", "zh-CN", "nl-NL",
+    "sv-SE", "ko-KR",
+];
+
+pub(crate) const LOREM: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "ad",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+    "exercitation",
+    "ullamco",
+    "laboris",
+    "nisi",
+    "aliquip",
+    "ex",
+    "ea",
+    "commodo",
+    "consequat",
+    "duis",
+    "aute",
+    "irure",
+    "in",
+    "voluptate",
+    "velit",
+    "esse",
+    "cillum",
+    "fugiat",
+    "nulla",
+    "pariatur",
+];
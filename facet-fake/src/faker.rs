@@ -0,0 +1,160 @@
+//! Realistic fakes, layered over the random values of `facet-arbitrary`.
+
+use alloc::{
+    borrow::{Cow, ToOwned},
+    format,
+    string::{String, ToString},
+};
+use facet_arbitrary::Strategy;
+use facet_core::{Def, Facet, Field, ScalarAffinity};
+use facet_reflect::{Partial, ReflectError, ScalarType};
+use log::trace;
+
+use crate::{
+    GenerateError, Generator, Source,
+    attrs::Attrs,
+    kind::{Fake, Rng, moment, ulid, url, uuid},
+};
+
+/// Generates a value of type `T` with the default [`Generator`] settings,
+/// filling in realistic fakes wherever [`Faker`] knows how.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_fake::SeededSource;
+///
+/// #[derive(Facet, Debug)]
+/// struct Customer {
+///     #[facet(fake = "name")]
+///     name: String,
+///     #[facet(fake = "email")]
+///     email: String,
+///     #[facet(fake = "city")]
+///     city: Option<String>,
+/// }
+///
+/// let mut source = SeededSource::new(42);
+/// let customer: Customer = facet_fake::fake(&mut source).unwrap();
+/// assert!(customer.email.contains('@'));
+/// ```
+pub fn fake<'facet: 'shape, 'shape, T: Facet<'facet>>(
+    source: &mut impl Source,
+) -> Result<T, GenerateError<'shape>> {
+    Generator::new().with_strategy(Faker).generate(source)
+}
+
+/// A [`Strategy`] for realistic mock data.
+///
+/// Fields with `#[facet(fake = "email")]` (or any other kind listed in the
+/// crate documentation) get a fake of that kind, including inside options and
+/// collections. UUIDs, ULIDs, URLs and dates and times are recognized by their
+/// shape and faked without an attribute; dates fall within
+/// `#[facet(fake(range = "2000-01-01".."2030-01-01"))]`, which is the default.
+///
+/// Everything else is left to the [`Generator`], so numbers, booleans and
+/// enums stay random.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Faker;
+
+impl Strategy for Faker {
+    fn generate<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        field: Option<&'shape Field<'shape>>,
+        source: &mut dyn Source,
+    ) -> Result<bool, GenerateError<'shape>> {
+        let shape = wip.shape();
+        // Options, collections and wrappers are opened up by the generator,
+        // which asks again for what's inside; wrappers that parse from a
+        // string, like `Uuid`, are faked whole
+        if !matches!(shape.def, Def::Scalar(_) | Def::Undefined) {
+            return Ok(false);
+        }
+        if ScalarType::try_from_shape(shape).is_none()
+            && shape.inner.is_some()
+            && !shape.is_from_str()
+        {
+            return Ok(false);
+        }
+
+        let attrs = Attrs::of(field)?;
+        let mut rng = Rng::new(source);
+
+        if let Some((kind, attr)) = attrs.kind {
+            trace!("Faking {shape} as {kind:?}");
+            let set = match kind.generate(&mut rng, attrs.dates) {
+                Fake::Text(text) => set_text(wip, text)?,
+                Fake::Number(number) => set_number(wip, number)?,
+            };
+            if !set {
+                return Err(GenerateError::InvalidAttribute(attr.to_owned()));
+            }
+            return Ok(true);
+        }
+
+        let Def::Scalar(sd) = shape.def else {
+            return Ok(false);
+        };
+        let text = match sd.affinity {
+            ScalarAffinity::UUID(_) => uuid(&mut rng),
+            ScalarAffinity::ULID(_) => ulid(&mut rng, attrs.dates),
+            ScalarAffinity::Url(_) => url(&mut rng),
+            ScalarAffinity::Time(_) => {
+                // Time types each parse their own format, so offer the
+                // formats from most to least precise until one sticks
+                let moment = moment(&mut rng, attrs.dates);
+                let candidates = [
+                    moment.rfc3339(),
+                    moment.naive(),
+                    format!("{}+00:00[UTC]", moment.naive()),
+                    moment.date(),
+                    moment.time(),
+                ];
+                for candidate in &candidates {
+                    if wip.parse_from_str(candidate).is_ok() {
+                        return Ok(true);
+                    }
+                }
+                return Err(GenerateError::UnsupportedShape(shape));
+            }
+            _ => return Ok(false),
+        };
+        trace!("Faking {shape} from its affinity");
+        wip.parse_from_str(&text)?;
+        Ok(true)
+    }
+}
+
+/// Sets a string, or parses it for other types; returns `false` if the type takes neither.
+fn set_text<'shape>(
+    wip: &mut Partial<'_, 'shape>,
+    text: String,
+) -> Result<bool, ReflectError<'shape>> {
+    match ScalarType::try_from_shape(wip.shape()) {
+        Some(ScalarType::String) => {
+            wip.set(text)?;
+        }
+        Some(ScalarType::CowStr) => {
+            wip.set(Cow::<'static, str>::Owned(text))?;
+        }
+        _ if wip.shape().is_from_str() => return Ok(wip.parse_from_str(&text).is_ok()),
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn set_number<'shape>(
+    wip: &mut Partial<'_, 'shape>,
+    number: f64,
+) -> Result<bool, ReflectError<'shape>> {
+    match ScalarType::try_from_shape(wip.shape()) {
+        Some(ScalarType::F64) => {
+            wip.set(number)?;
+        }
+        Some(ScalarType::F32) => {
+            wip.set(number as f32)?;
+        }
+        _ => return set_text(wip, number.to_string()),
+    }
+    Ok(true)
+}
@@ -0,0 +1,241 @@
+//! The kinds of fake values, named by `#[facet(fake = "...")]`.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{Source, attrs::Dates, calendar::Moment, data::*};
+
+/// Draws from a [`Source`].
+pub(crate) struct Rng<'a> {
+    source: &'a mut dyn Source,
+}
+
+impl<'a> Rng<'a> {
+    pub fn new(source: &'a mut dyn Source) -> Self {
+        Self { source }
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.source.next_u64()
+    }
+
+    /// Returns a number in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        ((self.next() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// Returns a number in `lo..=hi`.
+    pub fn between(&mut self, lo: i64, hi: i64) -> i64 {
+        let span = hi.abs_diff(lo).saturating_add(1);
+        lo.wrapping_add(self.below(span) as i64)
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+}
+
+/// A generated fake, to be set on a string-like or a float field.
+pub(crate) enum Fake {
+    Text(String),
+    Number(f64),
+}
+
+macro_rules! kinds {
+    ($($variant:ident => $name:literal,)*) => {
+        /// A kind of fake value.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub(crate) enum Kind {
+            $($variant,)*
+        }
+
+        impl Kind {
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(Kind::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+kinds! {
+    FirstName => "first_name",
+    LastName => "last_name",
+    Name => "name",
+    Username => "username",
+    Email => "email",
+    Phone => "phone",
+    Company => "company",
+    JobTitle => "job_title",
+    Street => "street",
+    City => "city",
+    State => "state",
+    PostalCode => "postal_code",
+    Country => "country",
+    CountryCode => "country_code",
+    Address => "address",
+    Latitude => "latitude",
+    Longitude => "longitude",
+    Word => "word",
+    Sentence => "sentence",
+    Paragraph => "paragraph",
+    Domain => "domain",
+    Url => "url",
+    CurrencyCode => "currency_code",
+    Locale => "locale",
+    Product => "product",
+    Uuid => "uuid",
+    Ulid => "ulid",
+    Date => "date",
+    DateTime => "datetime",
+    Time => "time",
+}
+
+impl Kind {
+    pub fn generate(self, rng: &mut Rng<'_>, dates: Dates) -> Fake {
+        let text = match self {
+            Kind::FirstName => rng.pick(FIRST_NAMES).to_string(),
+            Kind::LastName => rng.pick(LAST_NAMES).to_string(),
+            Kind::Name => format!("{} {}", rng.pick(FIRST_NAMES), rng.pick(LAST_NAMES)),
+            Kind::Username => {
+                let first = rng.pick(FIRST_NAMES).to_lowercase();
+                let last = rng.pick(LAST_NAMES).to_lowercase();
+                match rng.below(3) {
+                    0 => format!("{first}_{last}"),
+                    1 => format!("{first}{}", rng.below(100)),
+                    _ => format!("{}{last}", &first[..1]),
+                }
+            }
+            Kind::Email => {
+                let first = rng.pick(FIRST_NAMES).to_lowercase();
+                let last = rng.pick(LAST_NAMES).to_lowercase();
+                let domain = rng.pick(DOMAINS);
+                match rng.below(2) {
+                    0 => format!("{first}.{last}@{domain}"),
+                    _ => format!("{first}{}@{domain}", rng.below(1000)),
+                }
+            }
+            // 555-0100 to 555-0199 are set aside for fiction in North America
+            Kind::Phone => format!("({}) 555-01{:02}", rng.between(201, 989), rng.below(100)),
+            Kind::Company => format!("{} {}", rng.pick(COMPANY_WORDS), rng.pick(COMPANY_SUFFIXES)),
+            Kind::JobTitle => format!("{} {}", rng.pick(JOB_LEVELS), rng.pick(JOB_ROLES)),
+            Kind::Street => street(rng),
+            Kind::City => rng.pick(CITIES).to_string(),
+            Kind::State => rng.pick(STATES).to_string(),
+            Kind::PostalCode => format!("{:05}", rng.below(100_000)),
+            Kind::Country => rng.pick(COUNTRIES).0.to_string(),
+            Kind::CountryCode => rng.pick(COUNTRIES).1.to_string(),
+            Kind::Address => format!(
+                "{}, {}, {} {:05}",
+                street(rng),
+                rng.pick(CITIES),
+                rng.pick(STATES),
+                rng.below(100_000)
+            ),
+            Kind::Latitude => return Fake::Number(microdegrees(rng, 90)),
+            Kind::Longitude => return Fake::Number(microdegrees(rng, 180)),
+            Kind::Word => rng.pick(LOREM).to_string(),
+            Kind::Sentence => sentence(rng),
+            Kind::Paragraph => {
+                let count = rng.between(3, 6);
+                let sentences: Vec<String> = (0..count).map(|_| sentence(rng)).collect();
+                sentences.join(" ")
+            }
+            Kind::Domain => domain(rng),
+            Kind::Url => url(rng),
+            Kind::CurrencyCode => rng.pick(CURRENCIES).to_string(),
+            Kind::Locale => rng.pick(LOCALES).to_string(),
+            Kind::Product => format!(
+                "{} {} {}",
+                rng.pick(PRODUCT_ADJECTIVES),
+                rng.pick(PRODUCT_MATERIALS),
+                rng.pick(PRODUCT_NOUNS)
+            ),
+            Kind::Uuid => uuid(rng),
+            Kind::Ulid => ulid(rng, dates),
+            Kind::Date => moment(rng, dates).date(),
+            Kind::DateTime => moment(rng, dates).rfc3339(),
+            Kind::Time => moment(rng, dates).time(),
+        };
+        Fake::Text(text)
+    }
+}
+
+fn street(rng: &mut Rng<'_>) -> String {
+    format!(
+        "{} {} {}",
+        rng.between(1, 9999),
+        rng.pick(STREETS),
+        rng.pick(STREET_SUFFIXES)
+    )
+}
+
+fn sentence(rng: &mut Rng<'_>) -> String {
+    let count = rng.between(4, 12);
+    let mut sentence = String::new();
+    for index in 0..count {
+        let word = rng.pick(LOREM);
+        if index == 0 {
+            let mut chars = word.chars();
+            sentence.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+            sentence.push_str(chars.as_str());
+        } else {
+            sentence.push(' ');
+            sentence.push_str(word);
+        }
+    }
+    sentence.push('.');
+    sentence
+}
+
+fn domain(rng: &mut Rng<'_>) -> String {
+    format!(
+        "{}.{}",
+        rng.pick(COMPANY_WORDS).to_lowercase(),
+        rng.pick(DOMAINS)
+    )
+}
+
+pub(crate) fn url(rng: &mut Rng<'_>) -> String {
+    format!("https://{}/{}", domain(rng), rng.pick(LOREM))
+}
+
+/// A coordinate within `-max..=max` degrees, to six decimals.
+fn microdegrees(rng: &mut Rng<'_>, max: i64) -> f64 {
+    rng.between(-max * 1_000_000, max * 1_000_000) as f64 / 1_000_000.0
+}
+
+pub(crate) fn moment(rng: &mut Rng<'_>, dates: Dates) -> Moment {
+    Moment::from_unix(rng.between(dates.from, dates.to))
+}
+
+/// A random (version 4) UUID.
+pub(crate) fn uuid(rng: &mut Rng<'_>) -> String {
+    let bits = (rng.next() as u128) << 64 | rng.next() as u128;
+    let bits = bits & !(0xf << 76) | 0x4 << 76;
+    let bits = bits & !(0x3 << 62) | 0x2 << 62;
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        bits >> 96,
+        (bits >> 80) & 0xffff,
+        (bits >> 64) & 0xffff,
+        (bits >> 48) & 0xffff,
+        bits & 0xffff_ffff_ffff
+    )
+}
+
+/// A ULID whose timestamp falls within `dates`.
+pub(crate) fn ulid(rng: &mut Rng<'_>, dates: Dates) -> String {
+    const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+    let millis = (rng.between(dates.from, dates.to).max(0) * 1000) as u128;
+    let random = ((rng.next() as u128) << 64 | rng.next() as u128) & ((1 << 80) - 1);
+    let bits = (millis & ((1 << 48) - 1)) << 80 | random;
+    (0..26)
+        .map(|index| char::from(CROCKFORD[(bits >> (125 - 5 * index)) as usize & 31]))
+        .collect()
+}
@@ -0,0 +1,21 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+#![warn(clippy::std_instead_of_core)]
+#![warn(clippy::std_instead_of_alloc)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+#[cfg(not(feature = "alloc"))]
+compile_error!("feature `alloc` is required");
+
+pub use facet_arbitrary::{GenerateError, Generator, SeededSource, Source};
+
+mod attrs;
+mod calendar;
+mod data;
+mod kind;
+
+mod faker;
+pub use faker::*;
@@ -0,0 +1,125 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use facet::Facet;
+use facet_fake::{Faker, GenerateError, Generator, SeededSource, fake};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Person {
+    id: uuid::Uuid,
+    #[facet(fake = "first_name")]
+    first_name: String,
+    #[facet(fake = "email")]
+    email: String,
+    #[facet(fake = "phone")]
+    phone: Option<String>,
+    #[facet(fake = "city")]
+    cities: Vec<String>,
+    #[facet(fake(range = "1950-01-01".."2006-01-01"))]
+    date_of_birth: NaiveDate,
+    created_at: NaiveDateTime,
+    #[facet(arbitrary(range = 18..=99))]
+    age: u8,
+}
+
+#[test]
+fn test_fakes_by_attribute() {
+    let mut source = SeededSource::new(0);
+    for _ in 0..200 {
+        let person: Person = fake(&mut source)?;
+        assert!(person.first_name.chars().next().unwrap().is_uppercase());
+        let (user, domain) = person.email.split_once('@').unwrap();
+        assert!(!user.is_empty());
+        assert!(domain.starts_with("example."));
+        if let Some(phone) = &person.phone {
+            assert!(phone.contains(" 555-01"), "{phone}");
+        }
+        assert!(person.cities.iter().all(|c| !c.is_empty()));
+        assert!((18..=99).contains(&person.age));
+    }
+}
+
+#[test]
+fn test_uuids_from_affinity() {
+    let mut source = SeededSource::new(1);
+    let ids: Vec<uuid::Uuid> = fake(&mut source)?;
+    for id in &ids {
+        assert_eq!(id.get_version_num(), 4);
+    }
+
+    let id: ulid::Ulid = fake(&mut source)?;
+    assert_ne!(id, ulid::Ulid::nil());
+}
+
+#[test]
+fn test_dates_within_range() {
+    let from = NaiveDate::from_ymd_opt(1950, 1, 1).unwrap();
+    let until = NaiveDate::from_ymd_opt(2006, 1, 1).unwrap();
+    let mut source = SeededSource::new(2);
+    for _ in 0..200 {
+        let person: Person = fake(&mut source)?;
+        assert!(person.date_of_birth >= from && person.date_of_birth < until);
+        // Without a range, moments fall between 2000 and 2029
+        assert!((2000..2030).contains(&person.created_at.year()));
+    }
+
+    #[derive(Debug, Facet)]
+    struct Meeting {
+        #[facet(fake = "date")]
+        #[facet(fake(range = "2024-02-29"..="2024-02-29"))]
+        day: String,
+    }
+    let meeting: Meeting = fake(&mut source)?;
+    assert_eq!(meeting.day, "2024-02-29");
+}
+
+#[test]
+fn test_coordinates() {
+    #[derive(Debug, Facet)]
+    struct Place {
+        #[facet(fake = "latitude")]
+        latitude: f64,
+        #[facet(fake = "longitude")]
+        longitude: f32,
+    }
+    let mut source = SeededSource::new(3);
+    for _ in 0..100 {
+        let place: Place = fake(&mut source)?;
+        assert!((-90.0..=90.0).contains(&place.latitude));
+        assert!((-180.0..=180.0).contains(&place.longitude));
+    }
+}
+
+#[test]
+fn test_same_seed_same_values() {
+    let generator = Generator::new().with_max_len(2).with_strategy(Faker);
+    let a: Person = generator.generate(&mut SeededSource::new(9))?;
+    let b: Person = generator.generate(&mut SeededSource::new(9))?;
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_errors() {
+    #[derive(Debug, Facet)]
+    struct UnknownKind {
+        #[facet(fake = "horoscope")]
+        sign: String,
+    }
+    let err = fake::<UnknownKind>(&mut SeededSource::new(0)).unwrap_err();
+    assert!(matches!(err, GenerateError::InvalidAttribute(_)));
+
+    #[derive(Debug, Facet)]
+    struct WrongType {
+        #[facet(fake = "email")]
+        email: u32,
+    }
+    let err = fake::<WrongType>(&mut SeededSource::new(0)).unwrap_err();
+    assert!(matches!(err, GenerateError::InvalidAttribute(_)));
+
+    #[derive(Debug, Facet)]
+    struct EmptyRange {
+        #[facet(fake(range = "2020-01-01".."2020-01-01"))]
+        day: NaiveDate,
+    }
+    let err = fake::<EmptyRange>(&mut SeededSource::new(0)).unwrap_err();
+    assert!(matches!(err, GenerateError::InvalidAttribute(_)));
+}
@@ -0,0 +1,123 @@
+use crate::Format;
+
+pub(crate) fn strip_bom(bytes: &[u8]) -> &[u8] {
+    bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes)
+}
+
+/// Guesses the format of `bytes`, or returns `None` if there's nothing to go on.
+///
+/// - Input that isn't UTF-8, or starts with a control character, is MessagePack.
+/// - Otherwise, blank lines and `#` comments are skipped, and the first line decides:
+///   - `---` or `%YAML` is YAML,
+///   - `{` is JSON,
+///   - `[table]` and `[[table]]` are TOML, other `[` are JSON,
+///   - `- item` is YAML,
+///   - `key = value` is TOML,
+///   - `key: value` is YAML,
+///   - a lone string, number, `true`, `false` or `null` is JSON,
+///   - anything else is YAML, which accepts the most.
+///
+/// ```
+/// use facet_formats::{Format, detect};
+///
+/// assert_eq!(detect(br#"{"a": 1}"#), Some(Format::Json));
+/// assert_eq!(detect(b"# settings\n[server]\nport = 80\n"), Some(Format::Toml));
+/// assert_eq!(detect(b"server:\n  port: 80\n"), Some(Format::Yaml));
+/// assert_eq!(detect(&[0x81, 0xa1, b'a', 0x01]), Some(Format::MsgPack));
+/// assert_eq!(detect(b"  \n# nothing\n"), None);
+/// ```
+pub fn detect(bytes: &[u8]) -> Option<Format> {
+    let bytes = strip_bom(bytes);
+    let Ok(text) = core::str::from_utf8(bytes) else {
+        return Some(Format::MsgPack);
+    };
+    if text
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_control() && !c.is_whitespace())
+    {
+        return Some(Format::MsgPack);
+    }
+
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))?;
+
+    if line.starts_with("---") || line.starts_with("%YAML") {
+        return Some(Format::Yaml);
+    }
+    if line.starts_with('{') {
+        return Some(Format::Json);
+    }
+    if line.starts_with('[') {
+        return Some(if is_table_header(line) {
+            Format::Toml
+        } else {
+            Format::Json
+        });
+    }
+    if line == "-" || line.starts_with("- ") {
+        return Some(Format::Yaml);
+    }
+    if is_key_value(line) {
+        return Some(Format::Toml);
+    }
+    if is_mapping_entry(line) {
+        return Some(Format::Yaml);
+    }
+    if line.starts_with(|c: char| c == '"' || c == '-' || c.is_ascii_digit())
+        || matches!(line, "true" | "false" | "null")
+    {
+        return Some(Format::Json);
+    }
+    Some(Format::Yaml)
+}
+
+/// Whether `key` is a TOML key: bare, quoted, or dotted
+fn is_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.split('.').all(|part| {
+            let part = part.trim();
+            (part.len() >= 2 && part.starts_with('"') && part.ends_with('"'))
+                || (!part.is_empty()
+                    && part
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+        })
+}
+
+/// `[table]` or `[[table]]`, maybe followed by a comment
+fn is_table_header(line: &str) -> bool {
+    let line = match line.find(" #") {
+        Some(comment) => line[..comment].trim_end(),
+        None => line,
+    };
+    let name = line
+        .strip_prefix("[[")
+        .and_then(|rest| rest.strip_suffix("]]"))
+        .or_else(|| line.strip_prefix('[')?.strip_suffix(']'));
+    match name {
+        // `[1]` and `[true]` are JSON
+        Some(name) => {
+            is_key(name.trim())
+                && name.contains(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '"')
+                && !matches!(name.trim(), "true" | "false" | "null")
+        }
+        None => false,
+    }
+}
+
+/// `key = value`
+fn is_key_value(line: &str) -> bool {
+    line.split_once('=')
+        .is_some_and(|(key, _)| is_key(key.trim()))
+}
+
+/// `key: value`, or `key:` followed by a nested value
+fn is_mapping_entry(line: &str) -> bool {
+    let Some((key, rest)) = line.split_once(':') else {
+        return false;
+    };
+    !key.trim().is_empty() && (rest.is_empty() || rest.starts_with(' '))
+}
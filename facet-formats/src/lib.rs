@@ -0,0 +1,145 @@
+#![no_std]
+#![warn(missing_docs)]
+#![warn(clippy::std_instead_of_core)]
+#![warn(clippy::std_instead_of_alloc)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+mod detect;
+pub use detect::*;
+
+mod registry;
+pub use registry::*;
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use facet_core::Facet;
+
+/// A data format `facet-formats` can tell apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Format {
+    /// JSON, with `facet-json`
+    Json,
+    /// MessagePack, with `facet-msgpack`
+    MsgPack,
+    /// YAML, with `facet-yaml`
+    Yaml,
+    /// TOML, with `facet-toml`
+    Toml,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Format::Json => "JSON",
+            Format::MsgPack => "MessagePack",
+            Format::Yaml => "YAML",
+            Format::Toml => "TOML",
+        })
+    }
+}
+
+/// An error from [`from_bytes_auto`] or [`from_bytes_as`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum AutoError {
+    /// The input doesn't look like any format (it's empty, or only has comments).
+    UnknownFormat,
+    /// The input is in a format whose feature isn't enabled.
+    Disabled(Format),
+    /// The input looks like `format`, but couldn't be deserialized from it.
+    Format {
+        /// The format the input was read as
+        format: Format,
+        /// The format crate's error message
+        message: String,
+    },
+}
+
+impl AutoError {
+    #[allow(dead_code)]
+    fn format(format: Format, err: impl fmt::Display) -> Self {
+        AutoError::Format {
+            format,
+            message: err.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for AutoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AutoError::UnknownFormat => write!(f, "Couldn't tell what format the input is in"),
+            AutoError::Disabled(format) => {
+                write!(f, "The input is {format}, which isn't enabled")
+            }
+            AutoError::Format { format, message } => {
+                write!(f, "Invalid {format} input: {message}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for AutoError {}
+
+/// Deserializes `bytes` as whichever of JSON, MessagePack, YAML or TOML they look like.
+///
+/// See [`detect`] for how the format is picked. Values can't borrow from `bytes`, since
+/// some formats can't lend their strings out.
+///
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Config {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// let expected = Config { name: "api".to_string(), port: 8080 };
+/// for input in [
+///     r#"{"name": "api", "port": 8080}"#,
+///     "name: api\nport: 8080\n",
+///     "name = \"api\"\nport = 8080\n",
+/// ] {
+///     let config: Config = facet_formats::from_bytes_auto(input.as_bytes()).unwrap();
+///     assert_eq!(config, expected);
+/// }
+/// ```
+pub fn from_bytes_auto<T>(bytes: &[u8]) -> Result<T, AutoError>
+where
+    T: for<'facet> Facet<'facet>,
+{
+    let format = detect(bytes).ok_or(AutoError::UnknownFormat)?;
+    from_bytes_as(bytes, format)
+}
+
+/// Deserializes `bytes` as `format`, for when it's known some other way (like from a file
+/// extension or a content type).
+pub fn from_bytes_as<T>(bytes: &[u8], format: Format) -> Result<T, AutoError>
+where
+    T: for<'facet> Facet<'facet>,
+{
+    let bytes = strip_bom(bytes);
+    #[allow(unused_variables)]
+    let text = || core::str::from_utf8(bytes).map_err(|e| AutoError::format(format, e));
+
+    match format {
+        #[cfg(feature = "json")]
+        Format::Json => facet_json::from_slice(bytes).map_err(|e| AutoError::format(format, e)),
+        #[cfg(feature = "msgpack")]
+        Format::MsgPack => {
+            facet_msgpack::from_slice(bytes).map_err(|e| AutoError::format(format, e))
+        }
+        #[cfg(feature = "yaml")]
+        Format::Yaml => facet_yaml::from_str(text()?).map_err(|e| AutoError::format(format, e)),
+        #[cfg(feature = "toml")]
+        Format::Toml => facet_toml::from_str(text()?).map_err(|e| AutoError::format(format, e)),
+        #[allow(unreachable_patterns)]
+        _ => Err(AutoError::Disabled(format)),
+    }
+}
@@ -0,0 +1,358 @@
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use facet_core::{Facet, Shape};
+use facet_reflect::{HeapValue, Partial, Peek};
+
+/// Serializes a value into the bytes of a media type.
+pub type EncodeFn =
+    for<'mem, 'facet, 'shape> fn(Peek<'mem, 'facet, 'shape>) -> Result<Vec<u8>, String>;
+
+/// Deserializes the bytes of a media type into a value of the partial's shape.
+pub type DecodeFn = for<'a> fn(&'a [u8], Partial<'a, 'a>) -> Result<HeapValue<'a, 'a>, String>;
+
+/// How a [`Registry`] reads and writes one media type. Either half can be missing, for
+/// formats that only go one way, like CSV.
+#[derive(Debug, Clone, Copy)]
+pub struct Codec {
+    /// Writes values as the media type
+    pub encode: Option<EncodeFn>,
+    /// Reads values from the media type
+    pub decode: Option<DecodeFn>,
+}
+
+/// An error from a [`Registry`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum CodecError {
+    /// No codec is registered for the media type.
+    UnknownMediaType(String),
+    /// The media type's codec can't write values.
+    CannotEncode(String),
+    /// The media type's codec can't read values.
+    CannotDecode(String),
+    /// The codec failed.
+    Codec {
+        /// The media type
+        media_type: String,
+        /// The format crate's error message
+        message: String,
+    },
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::UnknownMediaType(mime) => write!(f, "No codec for {mime}"),
+            CodecError::CannotEncode(mime) => write!(f, "Values can't be written as {mime}"),
+            CodecError::CannotDecode(mime) => write!(f, "Values can't be read from {mime}"),
+            CodecError::Codec {
+                media_type,
+                message,
+            } => write!(f, "Invalid {media_type}: {message}"),
+        }
+    }
+}
+
+impl core::error::Error for CodecError {}
+
+/// Media types and how to read and write values as them, for servers that pick a format
+/// from a `Content-Type` or `Accept` header.
+///
+/// [`Registry::default`] knows the formats whose features are enabled:
+///
+/// | Media type            | Feature   | Also known as                            |
+/// |-----------------------|-----------|------------------------------------------|
+/// | `application/json`    | `json`    |                                          |
+/// | `application/msgpack` | `msgpack` | `application/x-msgpack`, `application/vnd.msgpack` |
+/// | `application/yaml`    | `yaml`    | `application/x-yaml`, `text/yaml`        |
+/// | `text/csv`            | `csv`     |                                          |
+///
+/// CSV can only be written.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_formats::Registry;
+/// use facet_reflect::Peek;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let registry = Registry::default();
+/// let point = Point { x: 1, y: 2 };
+///
+/// let json = registry.encode(Peek::new(&point), "application/json").unwrap();
+/// assert_eq!(json, br#"{"x":1,"y":2}"#);
+///
+/// let back: Point = registry.decode_as("application/yaml; charset=utf-8", b"x: 1\ny: 2\n").unwrap();
+/// assert_eq!(back, point);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Registry {
+    /// Media types in the order they were registered, which `negotiate` prefers in.
+    codecs: Vec<(String, Codec)>,
+}
+
+impl Registry {
+    /// A registry without any media types.
+    pub fn empty() -> Self {
+        Registry { codecs: Vec::new() }
+    }
+
+    /// Registers `codec` for `media_type`, replacing any codec it had.
+    pub fn register(&mut self, media_type: &str, codec: Codec) -> &mut Self {
+        let media_type = essence(media_type);
+        match self
+            .codecs
+            .iter_mut()
+            .find(|(known, _)| *known == media_type)
+        {
+            Some((_, known)) => *known = codec,
+            None => self.codecs.push((media_type, codec)),
+        }
+        self
+    }
+
+    /// The codec for a media type. Parameters like `charset` are ignored, and case doesn't
+    /// matter.
+    pub fn get(&self, media_type: &str) -> Option<&Codec> {
+        let media_type = essence(media_type);
+        self.codecs
+            .iter()
+            .find(|(known, _)| *known == media_type)
+            .map(|(_, codec)| codec)
+    }
+
+    /// The registered media types, in the order they were registered.
+    pub fn media_types(&self) -> impl Iterator<Item = &str> {
+        self.codecs
+            .iter()
+            .map(|(media_type, _)| media_type.as_str())
+    }
+
+    /// Serializes a value as `media_type`.
+    pub fn encode(&self, peek: Peek<'_, '_, '_>, media_type: &str) -> Result<Vec<u8>, CodecError> {
+        let codec = self
+            .get(media_type)
+            .ok_or_else(|| CodecError::UnknownMediaType(media_type.to_owned()))?;
+        let encode = codec
+            .encode
+            .ok_or_else(|| CodecError::CannotEncode(media_type.to_owned()))?;
+        encode(peek).map_err(|message| CodecError::Codec {
+            media_type: media_type.to_owned(),
+            message,
+        })
+    }
+
+    /// Deserializes `bytes` as `media_type`, into a value of `shape`.
+    pub fn decode<'a>(
+        &self,
+        shape: &'a Shape<'a>,
+        media_type: &str,
+        bytes: &'a [u8],
+    ) -> Result<HeapValue<'a, 'a>, CodecError> {
+        let codec = self
+            .get(media_type)
+            .ok_or_else(|| CodecError::UnknownMediaType(media_type.to_owned()))?;
+        let decode = codec
+            .decode
+            .ok_or_else(|| CodecError::CannotDecode(media_type.to_owned()))?;
+        let failed = |message: String| CodecError::Codec {
+            media_type: media_type.to_owned(),
+            message,
+        };
+        let wip = Partial::alloc_shape(shape).map_err(|e| failed(e.to_string()))?;
+        decode(bytes, wip).map_err(failed)
+    }
+
+    /// Deserializes `bytes` as `media_type`, like [`Registry::decode`] for a type known at
+    /// compile time.
+    pub fn decode_as<T>(&self, media_type: &str, bytes: &[u8]) -> Result<T, CodecError>
+    where
+        T: for<'facet> Facet<'facet>,
+    {
+        self.decode(T::SHAPE, media_type, bytes)?
+            .materialize::<T>()
+            .map_err(|e| CodecError::Codec {
+                media_type: media_type.to_owned(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Picks the media type to respond with for an `Accept` header: the one with the
+    /// highest `q` that can be written, or `None` if there isn't one.
+    ///
+    /// Wildcards like `text/*` and `*/*` match media types in the order they were
+    /// registered.
+    ///
+    /// ```
+    /// use facet_formats::Registry;
+    ///
+    /// let registry = Registry::default();
+    /// let accept = "application/yaml;q=0.5, application/json, */*;q=0.1";
+    /// assert_eq!(registry.negotiate(accept), Some("application/json"));
+    /// assert_eq!(registry.negotiate("text/*"), Some("text/yaml"));
+    /// assert_eq!(registry.negotiate("image/png"), None);
+    /// ```
+    pub fn negotiate(&self, accept: &str) -> Option<&str> {
+        let mut ranges: Vec<(String, f32)> = accept
+            .split(',')
+            .map(|range| {
+                let mut parts = range.split(';');
+                let media_range = essence(parts.next().unwrap_or_default());
+                let q = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (media_range, q)
+            })
+            .filter(|(media_range, q)| !media_range.is_empty() && *q > 0.0)
+            .collect();
+        // Stable, so ranges with the same `q` keep the client's order
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        ranges.iter().find_map(|(media_range, _)| {
+            self.codecs
+                .iter()
+                .filter(|(_, codec)| codec.encode.is_some())
+                .map(|(media_type, _)| media_type.as_str())
+                .find(|media_type| matches_range(media_type, media_range))
+        })
+    }
+}
+
+impl Default for Registry {
+    /// A registry with the formats whose features are enabled.
+    fn default() -> Self {
+        #[allow(unused_mut)]
+        let mut registry = Registry::empty();
+
+        #[cfg(feature = "json")]
+        registry.register(
+            "application/json",
+            Codec {
+                encode: Some(codecs::encode_json),
+                decode: Some(codecs::decode_json),
+            },
+        );
+
+        #[cfg(feature = "msgpack")]
+        for media_type in [
+            "application/msgpack",
+            "application/x-msgpack",
+            "application/vnd.msgpack",
+        ] {
+            registry.register(
+                media_type,
+                Codec {
+                    encode: Some(codecs::encode_msgpack),
+                    decode: Some(codecs::decode_msgpack),
+                },
+            );
+        }
+
+        #[cfg(feature = "yaml")]
+        for media_type in ["application/yaml", "application/x-yaml", "text/yaml"] {
+            registry.register(
+                media_type,
+                Codec {
+                    encode: Some(codecs::encode_yaml),
+                    decode: Some(codecs::decode_yaml),
+                },
+            );
+        }
+
+        #[cfg(feature = "csv")]
+        registry.register(
+            "text/csv",
+            Codec {
+                encode: Some(codecs::encode_csv),
+                decode: None,
+            },
+        );
+
+        registry
+    }
+}
+
+/// A media type without its parameters, in lowercase.
+fn essence(media_type: &str) -> String {
+    media_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn matches_range(media_type: &str, media_range: &str) -> bool {
+    if media_range == "*/*" || media_range == media_type {
+        return true;
+    }
+    match (media_range.strip_suffix("/*"), media_type.split_once('/')) {
+        (Some(range_type), Some((ty, _))) => range_type == ty,
+        _ => false,
+    }
+}
+
+#[allow(dead_code)]
+mod codecs {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    use facet_reflect::{HeapValue, Partial, Peek};
+
+    #[cfg(feature = "json")]
+    pub(super) fn encode_json(peek: Peek<'_, '_, '_>) -> Result<Vec<u8>, String> {
+        Ok(facet_json::peek_to_string(peek).into_bytes())
+    }
+
+    #[cfg(feature = "json")]
+    pub(super) fn decode_json<'a>(
+        bytes: &'a [u8],
+        wip: Partial<'a, 'a>,
+    ) -> Result<HeapValue<'a, 'a>, String> {
+        facet_json::from_slice_wip(crate::strip_bom(bytes), wip).map_err(|e| e.to_string())
+    }
+
+    #[cfg(feature = "msgpack")]
+    pub(super) fn encode_msgpack(peek: Peek<'_, '_, '_>) -> Result<Vec<u8>, String> {
+        Ok(facet_msgpack::peek_to_vec(peek))
+    }
+
+    #[cfg(feature = "msgpack")]
+    pub(super) fn decode_msgpack<'a>(
+        bytes: &'a [u8],
+        mut wip: Partial<'a, 'a>,
+    ) -> Result<HeapValue<'a, 'a>, String> {
+        facet_msgpack::from_slice_value(bytes, &mut wip).map_err(|e| e.to_string())?;
+        wip.build().map_err(|e| e.to_string())
+    }
+
+    #[cfg(feature = "yaml")]
+    pub(super) fn encode_yaml(peek: Peek<'_, '_, '_>) -> Result<Vec<u8>, String> {
+        facet_yaml::peek_to_string(peek)
+            .map(String::into_bytes)
+            .map_err(|e| e.to_string())
+    }
+
+    #[cfg(feature = "yaml")]
+    pub(super) fn decode_yaml<'a>(
+        bytes: &'a [u8],
+        mut wip: Partial<'a, 'a>,
+    ) -> Result<HeapValue<'a, 'a>, String> {
+        let yaml = core::str::from_utf8(crate::strip_bom(bytes)).map_err(|e| e.to_string())?;
+        facet_yaml::from_str_value(yaml, &mut wip).map_err(|e| e.to_string())?;
+        wip.build().map_err(|e| e.to_string())
+    }
+
+    #[cfg(feature = "csv")]
+    pub(super) fn encode_csv(peek: Peek<'_, '_, '_>) -> Result<Vec<u8>, String> {
+        Ok(facet_csv::peek_to_string(&peek).into_bytes())
+    }
+}
@@ -0,0 +1,85 @@
+use facet::Facet;
+use facet_formats::{AutoError, Format, detect, from_bytes_as, from_bytes_auto};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Config {
+    name: String,
+    port: u16,
+    tags: Vec<String>,
+}
+
+fn expected() -> Config {
+    Config {
+        name: "api".to_string(),
+        port: 8080,
+        tags: vec!["a".to_string(), "b".to_string()],
+    }
+}
+
+#[test]
+fn reads_every_format() {
+    let msgpack = [
+        &[0x83, 0xa4][..],
+        b"name",
+        &[0xa3],
+        b"api",
+        &[0xa4],
+        b"port",
+        &[0xcd, 0x1f, 0x90, 0xa4],
+        b"tags",
+        &[0x92, 0xa1, b'a', 0xa1, b'b'],
+    ]
+    .concat();
+    let inputs: [(&[u8], Format); 4] = [
+        (
+            br#"{"name": "api", "port": 8080, "tags": ["a", "b"]}"#,
+            Format::Json,
+        ),
+        (
+            b"name: api\nport: 8080\ntags:\n  - a\n  - b\n",
+            Format::Yaml,
+        ),
+        (
+            b"# service\nname = \"api\"\nport = 8080\ntags = [\"a\", \"b\"]\n",
+            Format::Toml,
+        ),
+        (&msgpack, Format::MsgPack),
+    ];
+    for (input, format) in inputs {
+        assert_eq!(detect(input), Some(format));
+        assert_eq!(from_bytes_auto::<Config>(input).unwrap(), expected());
+        assert_eq!(from_bytes_as::<Config>(input, format).unwrap(), expected());
+    }
+}
+
+#[test]
+fn detection() {
+    assert_eq!(detect(b"\xEF\xBB\xBF[1, 2]"), Some(Format::Json));
+    assert_eq!(detect(b"[true]"), Some(Format::Json));
+    assert_eq!(detect(b"[[bin]] # first\nname = \"x\""), Some(Format::Toml));
+    assert_eq!(detect(b"server.port = 80"), Some(Format::Toml));
+    assert_eq!(detect(b"---\nport: 80"), Some(Format::Yaml));
+    assert_eq!(detect(b"- a\n- b"), Some(Format::Yaml));
+    assert_eq!(detect(b"url: https://example.com"), Some(Format::Yaml));
+    assert_eq!(detect(br#""https://example.com""#), Some(Format::Json));
+    assert_eq!(detect(b"-12"), Some(Format::Json));
+    assert_eq!(detect(b"\x01"), Some(Format::MsgPack));
+    assert_eq!(detect(b""), None);
+}
+
+#[test]
+fn errors_name_the_format() {
+    let err = from_bytes_auto::<Config>(b"name = 12").unwrap_err();
+    assert!(matches!(
+        err,
+        AutoError::Format {
+            format: Format::Toml,
+            ..
+        }
+    ));
+    assert_eq!(
+        from_bytes_auto::<Config>(b"\n# nothing here\n").unwrap_err(),
+        AutoError::UnknownFormat
+    );
+}
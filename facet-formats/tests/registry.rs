@@ -0,0 +1,109 @@
+use facet::Facet;
+use facet_formats::{Codec, CodecError, Registry};
+use facet_reflect::Peek;
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Order {
+    id: u32,
+    item: String,
+}
+
+fn order() -> Order {
+    Order {
+        id: 7,
+        item: "tea".to_string(),
+    }
+}
+
+#[test]
+fn round_trips_every_decodable_type() {
+    let registry = Registry::default();
+    for media_type in [
+        "application/json",
+        "application/msgpack",
+        "application/x-msgpack",
+        "application/yaml",
+        "text/yaml",
+    ] {
+        let bytes = registry.encode(Peek::new(&order()), media_type).unwrap();
+        let back: Order = registry.decode_as(media_type, &bytes).unwrap();
+        assert_eq!(back, order(), "{media_type}");
+    }
+}
+
+#[test]
+fn decodes_by_shape() {
+    let registry = Registry::default();
+    let value = registry
+        .decode(
+            Order::SHAPE,
+            "Application/JSON",
+            br#"{"id": 7, "item": "tea"}"#,
+        )
+        .unwrap();
+    assert_eq!(value.materialize::<Order>().unwrap(), order());
+}
+
+#[test]
+fn csv_is_write_only() {
+    let registry = Registry::default();
+    let csv = registry
+        .encode(Peek::new(&order()), "text/csv; charset=utf-8")
+        .unwrap();
+    assert_eq!(csv, b"7,tea\n");
+
+    assert_eq!(
+        registry.decode_as::<Order>("text/csv", b"7,tea\n"),
+        Err(CodecError::CannotDecode("text/csv".to_string()))
+    );
+}
+
+#[test]
+fn errors() {
+    let registry = Registry::default();
+    assert_eq!(
+        registry.encode(Peek::new(&order()), "image/png"),
+        Err(CodecError::UnknownMediaType("image/png".to_string()))
+    );
+    assert!(matches!(
+        registry.decode_as::<Order>("application/json", b"{\"id\": \"seven\"}"),
+        Err(CodecError::Codec { .. })
+    ));
+}
+
+#[test]
+fn custom_codecs() {
+    fn encode_type_name(peek: Peek<'_, '_, '_>) -> Result<Vec<u8>, String> {
+        Ok(peek.shape().to_string().into_bytes())
+    }
+
+    let mut registry = Registry::empty();
+    assert_eq!(registry.negotiate("*/*"), None);
+
+    registry.register(
+        "text/plain",
+        Codec {
+            encode: Some(encode_type_name),
+            decode: None,
+        },
+    );
+    assert_eq!(registry.media_types().collect::<Vec<_>>(), ["text/plain"]);
+    assert!(registry.encode(Peek::new(&order()), "text/plain").is_ok());
+}
+
+#[test]
+fn negotiation() {
+    let registry = Registry::default();
+    assert_eq!(
+        registry.negotiate("application/msgpack, application/json;q=0.9"),
+        Some("application/msgpack")
+    );
+    assert_eq!(
+        registry.negotiate("application/json;q=0.2, text/csv;q=0.8"),
+        Some("text/csv")
+    );
+    assert_eq!(registry.negotiate("*/*"), Some("application/json"));
+    assert_eq!(registry.negotiate("application/json;q=0"), None);
+    assert_eq!(registry.negotiate(""), None);
+}
@@ -0,0 +1,120 @@
+//! Per-field `#[facet(form(...))]` attributes.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use facet_reflect::FieldMeta;
+
+use crate::FormError;
+
+/// What a field's `#[facet(form(label = "Name", min_length = 1))]` attribute asks for.
+#[derive(Debug, Default)]
+pub(crate) struct FormAttrs {
+    pub label: Option<String>,
+    pub placeholder: Option<String>,
+    pub widget: Option<String>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub pattern: Option<String>,
+}
+
+impl FormAttrs {
+    /// Reads the form attributes of a struct or variant field.
+    pub fn of(meta: &FieldMeta<'_>) -> Result<Self, FormError> {
+        let mut attrs = FormAttrs::default();
+        let Some(args) = meta.attr_value("form") else {
+            return Ok(attrs);
+        };
+        let invalid = || FormError::InvalidAttribute(alloc::format!("form({args})"));
+        for arg in split_args(args) {
+            let (key, value) = arg.split_once('=').ok_or_else(invalid)?;
+            let value = value.trim();
+            match key.trim() {
+                "label" => attrs.label = Some(string(value).ok_or_else(invalid)?),
+                "placeholder" => attrs.placeholder = Some(string(value).ok_or_else(invalid)?),
+                "widget" => attrs.widget = Some(string(value).ok_or_else(invalid)?),
+                "pattern" => attrs.pattern = Some(string(value).ok_or_else(invalid)?),
+                "min" => attrs.min = Some(number(value).ok_or_else(invalid)?),
+                "max" => attrs.max = Some(number(value).ok_or_else(invalid)?),
+                "min_length" => attrs.min_length = Some(value.parse().map_err(|_| invalid())?),
+                "max_length" => attrs.max_length = Some(value.parse().map_err(|_| invalid())?),
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+/// Splits `a = 1, b = "x, y"` at the commas that aren't in a string.
+fn split_args(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in args.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            ',' if !in_string => {
+                parts.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&args[start..]);
+    parts.retain(|part| !part.trim().is_empty());
+    parts
+}
+
+/// Reads a finite number, which JSON can hold.
+fn number(literal: &str) -> Option<f64> {
+    literal.parse().ok().filter(|n: &f64| n.is_finite())
+}
+
+/// Reads a string literal, like `"^\\d+$"`.
+fn string(literal: &str) -> Option<String> {
+    let inner = literal.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            '0' => out.push('\0'),
+            c @ ('\\' | '"' | '\'') => out.push(c),
+            _ => return None,
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_outside_strings() {
+        assert_eq!(
+            split_args(r#"label = "A, B", min = 1,"#),
+            [r#"label = "A, B""#, " min = 1"]
+        );
+        assert_eq!(split_args(r#"pattern = "\\", max = 2"#).len(), 2);
+        assert!(split_args("").is_empty());
+    }
+
+    #[test]
+    fn reads_string_literals() {
+        assert_eq!(string(r#""Full name""#).as_deref(), Some("Full name"));
+        assert_eq!(string(r#""^\\d+$""#).as_deref(), Some(r"^\d+$"));
+        assert_eq!(string(r#""say \"hi\"""#).as_deref(), Some(r#"say "hi""#));
+        assert_eq!(string("plain"), None);
+    }
+}
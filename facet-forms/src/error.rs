@@ -0,0 +1,26 @@
+//! Errors from describing forms.
+
+use alloc::string::String;
+
+/// Errors when describing a type as a form.
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum FormError {
+    /// A type has no form input, like a function pointer or a recursive type.
+    UnsupportedType(String),
+    /// A `#[facet(form(...))]` attribute couldn't be read.
+    InvalidAttribute(String),
+}
+
+impl core::fmt::Display for FormError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FormError::UnsupportedType(ty) => write!(f, "Type {ty} can't be edited in a form"),
+            FormError::InvalidAttribute(attr) => {
+                write!(f, "Invalid form attribute: {attr}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for FormError {}
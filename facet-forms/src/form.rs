@@ -0,0 +1,471 @@
+//! Describe Facet shapes as forms.
+
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use facet_core::{
+    Def, EnumType, Facet, Field, FieldFlags, IntegerSize, NumberBits, PointerType, ScalarAffinity,
+    Shape, Signedness, SmartPointerDef, StructKind, StructType, Type, UserType,
+};
+use facet_reflect::FieldMeta;
+
+use crate::FormError;
+use crate::attrs::FormAttrs;
+
+/// Describes `T` as a form, in JSON.
+///
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet)]
+/// struct Invite {
+///     /// Email address
+///     #[facet(form(widget = "email"))]
+///     email: String,
+///     admin: bool,
+/// }
+///
+/// let json = facet_forms::to_form_json::<Invite>().unwrap();
+/// assert_eq!(
+///     json,
+///     r#"{"name":"Invite","title":"Invite","fields":[{"name":"email","label":"Email address","required":true,"widget":"email"},{"name":"admin","label":"Admin","required":true,"widget":"checkbox"}]}"#
+/// );
+/// ```
+pub fn to_form_json<'facet, T: Facet<'facet>>() -> Result<String, FormError> {
+    Ok(Form::of::<T>()?.to_json())
+}
+
+/// A form to fill in a struct: its title and its fields
+///
+/// Forms are read from shapes: labels come from doc comments, widgets from the type of each
+/// field, and constraints from the type and from `#[facet(form(...))]` attributes. See the
+/// crate docs for the details.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Form {
+    /// The name of the struct.
+    pub name: String,
+    /// The first line of the struct's doc comment, or its name in words.
+    pub title: String,
+    /// The rest of the struct's doc comment.
+    pub help: Option<String>,
+    /// The fields to fill in, in order.
+    pub fields: Vec<FormField>,
+}
+
+/// One input of a [`Form`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormField {
+    /// The field's name in serialized data, after `#[facet(rename)]`.
+    pub name: String,
+    /// The first line of the field's doc comment, or its name in words.
+    pub label: String,
+    /// The rest of the field's doc comment.
+    pub help: Option<String>,
+    /// A hint shown in the empty input.
+    pub placeholder: Option<String>,
+    /// Whether the field has to be filled in: it's not an `Option` and has no default.
+    pub required: bool,
+    /// How the field is edited.
+    pub widget: Widget,
+    /// The values the field accepts.
+    pub constraints: Constraints,
+}
+
+/// How a [`FormField`] is edited
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Widget {
+    /// A single line of text.
+    Text,
+    /// Several lines of text.
+    TextArea,
+    /// Text that isn't shown, for `#[facet(sensitive)]` fields.
+    Password,
+    /// An email address.
+    Email,
+    /// A URL.
+    Url,
+    /// A whole number.
+    Integer,
+    /// A number that can have a fractional part.
+    Number,
+    /// A yes/no choice.
+    Checkbox,
+    /// A date and time.
+    DateTime,
+    /// One of a fixed list of values, for enums without data.
+    Select(Vec<FormOption>),
+    /// A nested struct, filled in field by field.
+    Group(Vec<FormField>),
+    /// Any number of items, each edited like the given field.
+    List(Box<FormField>),
+    /// Entries with a text key, each value edited like the given field.
+    Map(Box<FormField>),
+    /// One of several variants with their own fields, for enums with data.
+    Choice(Vec<FormVariant>),
+}
+
+impl Widget {
+    /// The widget's name, as used in JSON and in `#[facet(form(widget = "..."))]`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Widget::Text => "text",
+            Widget::TextArea => "textarea",
+            Widget::Password => "password",
+            Widget::Email => "email",
+            Widget::Url => "url",
+            Widget::Integer => "integer",
+            Widget::Number => "number",
+            Widget::Checkbox => "checkbox",
+            Widget::DateTime => "datetime",
+            Widget::Select(_) => "select",
+            Widget::Group(_) => "group",
+            Widget::List(_) => "list",
+            Widget::Map(_) => "map",
+            Widget::Choice(_) => "choice",
+        }
+    }
+}
+
+/// A value of a [`Widget::Select`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormOption {
+    /// The value, as serialized.
+    pub value: String,
+    /// The first line of the variant's doc comment, or its name in words.
+    pub label: String,
+}
+
+/// A variant of a [`Widget::Choice`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormVariant {
+    /// The variant's name, as serialized.
+    pub name: String,
+    /// The first line of the variant's doc comment, or its name in words.
+    pub label: String,
+    /// The variant's fields.
+    pub fields: Vec<FormField>,
+}
+
+/// The values a [`FormField`] accepts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Constraints {
+    /// The smallest number allowed.
+    pub min: Option<f64>,
+    /// The largest number allowed.
+    pub max: Option<f64>,
+    /// The fewest characters, or items for lists.
+    pub min_length: Option<usize>,
+    /// The most characters, or items for lists.
+    pub max_length: Option<usize>,
+    /// A regular expression the text must match.
+    pub pattern: Option<String>,
+}
+
+impl Form {
+    /// Describes `T` as a form. `T` has to be a struct.
+    pub fn of<'facet, T: Facet<'facet>>() -> Result<Self, FormError> {
+        Self::of_shape(T::SHAPE)
+    }
+
+    /// Describes a shape as a form. See [`Form::of`].
+    pub fn of_shape<'shape>(shape: &'shape Shape<'shape>) -> Result<Self, FormError> {
+        let Type::User(UserType::Struct(sd)) = shape.ty else {
+            return Err(FormError::UnsupportedType(format!(
+                "{shape} (forms are made from structs)"
+            )));
+        };
+        let (title, help) = split_doc(shape.doc);
+        let mut builder = Builder {
+            stack: Vec::from([shape]),
+        };
+        Ok(Form {
+            name: shape.type_identifier.to_string(),
+            title: title.unwrap_or_else(|| words(shape.type_identifier)),
+            help,
+            fields: builder.fields(sd)?,
+        })
+    }
+
+    /// The form, in JSON. See the crate docs for its layout.
+    pub fn to_json(&self) -> String {
+        crate::json::form(self)
+    }
+}
+
+struct Builder<'shape> {
+    /// The structs and enums being described, to catch recursive types.
+    stack: Vec<&'shape Shape<'shape>>,
+}
+
+impl<'shape> Builder<'shape> {
+    fn fields(&mut self, sd: StructType<'shape>) -> Result<Vec<FormField>, FormError> {
+        let mut fields = Vec::with_capacity(sd.fields.len());
+        for field in sd.fields {
+            if field.flags.contains(FieldFlags::FLATTEN) {
+                if let Type::User(UserType::Struct(inner)) = field.shape().ty {
+                    fields.extend(self.fields(inner)?);
+                    continue;
+                }
+            }
+            fields.push(self.field(field)?);
+        }
+        Ok(fields)
+    }
+
+    fn field(&mut self, field: &'shape Field<'shape>) -> Result<FormField, FormError> {
+        let meta = FieldMeta::new(*field);
+        let attrs = FormAttrs::of(&meta)?;
+        let (label, help) = split_doc(meta.doc());
+        let (shape, optional) = match meta.shape().def {
+            Def::Option(od) => (od.t(), true),
+            _ => (meta.shape(), false),
+        };
+
+        let mut constraints = Constraints::default();
+        let mut widget = self.widget(shape, &mut constraints)?;
+        if meta.is_sensitive() && widget == Widget::Text {
+            widget = Widget::Password;
+        }
+        if let Some(name) = &attrs.widget {
+            widget = override_widget(widget, name)?;
+        }
+        constraints.min = attrs.min.or(constraints.min);
+        constraints.max = attrs.max.or(constraints.max);
+        constraints.min_length = attrs.min_length.or(constraints.min_length);
+        constraints.max_length = attrs.max_length.or(constraints.max_length);
+        constraints.pattern = attrs.pattern.or(constraints.pattern);
+
+        Ok(FormField {
+            name: meta.name().to_string(),
+            label: attrs.label.or(label).unwrap_or_else(|| words(meta.name())),
+            help,
+            placeholder: attrs.placeholder,
+            required: !optional && !meta.flags().contains(FieldFlags::DEFAULT),
+            widget,
+            constraints,
+        })
+    }
+
+    /// An item of a list or a value of a map
+    fn item(&mut self, shape: &'shape Shape<'shape>, label: &str) -> Result<FormField, FormError> {
+        let (inner, optional) = match shape.def {
+            Def::Option(od) => (od.t(), true),
+            _ => (shape, false),
+        };
+        let mut constraints = Constraints::default();
+        let widget = self.widget(inner, &mut constraints)?;
+        Ok(FormField {
+            name: label.to_lowercase(),
+            label: label.to_string(),
+            help: None,
+            placeholder: None,
+            required: !optional,
+            widget,
+            constraints,
+        })
+    }
+
+    fn widget(
+        &mut self,
+        shape: &'shape Shape<'shape>,
+        constraints: &mut Constraints,
+    ) -> Result<Widget, FormError> {
+        match shape.def {
+            Def::Scalar(sd) => return scalar(shape, sd.affinity, constraints),
+            Def::Option(od) => return self.widget(od.t(), constraints),
+            Def::List(ld) => return Ok(Widget::List(Box::new(self.item(ld.t(), "Item")?))),
+            Def::Slice(sd) => return Ok(Widget::List(Box::new(self.item(sd.t(), "Item")?))),
+            Def::Set(sd) => return Ok(Widget::List(Box::new(self.item((sd.t)(), "Item")?))),
+            Def::Array(ad) => {
+                constraints.min_length = Some(ad.n);
+                constraints.max_length = Some(ad.n);
+                return Ok(Widget::List(Box::new(self.item(ad.t(), "Item")?)));
+            }
+            Def::Map(md) => return Ok(Widget::Map(Box::new(self.item(md.v(), "Value")?))),
+            Def::SmartPointer(SmartPointerDef {
+                pointee: Some(pointee),
+                ..
+            }) => return self.widget(pointee(), constraints),
+            _ => {}
+        }
+
+        if let Some(inner) = shape.inner {
+            return self.widget(inner(), constraints);
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(sd)) if sd.kind != StructKind::Unit => {
+                self.nested(shape, |builder| Ok(Widget::Group(builder.fields(sd)?)))
+            }
+            Type::User(UserType::Enum(ed)) => self.nested(shape, |builder| builder.enum_(ed)),
+            Type::Pointer(PointerType::Reference(pt)) => self.widget((pt.target)(), constraints),
+            _ => Err(FormError::UnsupportedType(shape.to_string())),
+        }
+    }
+
+    /// Describes a struct or an enum, unless it's already being described.
+    fn nested(
+        &mut self,
+        shape: &'shape Shape<'shape>,
+        describe: impl FnOnce(&mut Self) -> Result<Widget, FormError>,
+    ) -> Result<Widget, FormError> {
+        if self.stack.contains(&shape) {
+            return Err(FormError::UnsupportedType(format!(
+                "{shape} (recursive types have no finite form)"
+            )));
+        }
+        self.stack.push(shape);
+        let widget = describe(self)?;
+        self.stack.pop();
+        Ok(widget)
+    }
+
+    fn enum_(&mut self, ed: EnumType<'shape>) -> Result<Widget, FormError> {
+        if ed.variants.iter().all(|v| v.data.kind == StructKind::Unit) {
+            let options = ed
+                .variants
+                .iter()
+                .map(|variant| FormOption {
+                    value: variant.name.to_string(),
+                    label: split_doc(variant.doc)
+                        .0
+                        .unwrap_or_else(|| words(variant.name)),
+                })
+                .collect();
+            return Ok(Widget::Select(options));
+        }
+
+        let mut variants = Vec::with_capacity(ed.variants.len());
+        for variant in ed.variants {
+            variants.push(FormVariant {
+                name: variant.name.to_string(),
+                label: split_doc(variant.doc)
+                    .0
+                    .unwrap_or_else(|| words(variant.name)),
+                fields: self.fields(variant.data)?,
+            });
+        }
+        Ok(Widget::Choice(variants))
+    }
+}
+
+/// Picks a widget from a scalar's affinity, with the bounds its type implies.
+fn scalar(
+    shape: &Shape<'_>,
+    affinity: &ScalarAffinity<'_>,
+    constraints: &mut Constraints,
+) -> Result<Widget, FormError> {
+    let widget = match affinity {
+        ScalarAffinity::Boolean(_) => Widget::Checkbox,
+        ScalarAffinity::Number(na) => match na.bits {
+            NumberBits::Integer { size, sign } => {
+                // Bounds past 2^53 can't be written exactly as JSON numbers
+                if let IntegerSize::Fixed(bits @ ..=53) = size {
+                    let (min, max) = match sign {
+                        Signedness::Unsigned => (0.0, ((1u64 << bits) - 1) as f64),
+                        Signedness::Signed => {
+                            let half = (1u64 << (bits - 1)) as f64;
+                            (-half, half - 1.0)
+                        }
+                    };
+                    constraints.min = Some(min);
+                    constraints.max = Some(max);
+                } else if sign == Signedness::Unsigned {
+                    constraints.min = Some(0.0);
+                }
+                Widget::Integer
+            }
+            _ => Widget::Number,
+        },
+        ScalarAffinity::String(_) | ScalarAffinity::Path(_) => Widget::Text,
+        ScalarAffinity::Char(_) => {
+            constraints.min_length = Some(1);
+            constraints.max_length = Some(1);
+            Widget::Text
+        }
+        ScalarAffinity::IpAddr(_) | ScalarAffinity::SocketAddr(_) => Widget::Text,
+        ScalarAffinity::UUID(_) => {
+            constraints.pattern = Some(
+                "^[0-9a-fA-F]{8}-?[0-9a-fA-F]{4}-?[0-9a-fA-F]{4}-?[0-9a-fA-F]{4}-?[0-9a-fA-F]{12}$"
+                    .to_string(),
+            );
+            Widget::Text
+        }
+        ScalarAffinity::ULID(_) => {
+            constraints.pattern = Some("^[0-9A-HJKMNP-TV-Za-hjkmnp-tv-z]{26}$".to_string());
+            Widget::Text
+        }
+        ScalarAffinity::Url(_) => Widget::Url,
+        ScalarAffinity::Time(_) => Widget::DateTime,
+        _ => return Err(FormError::UnsupportedType(shape.to_string())),
+    };
+    Ok(widget)
+}
+
+/// Applies `#[facet(form(widget = "..."))]`, which can only swap text and number widgets for
+/// one another.
+fn override_widget(widget: Widget, name: &str) -> Result<Widget, FormError> {
+    let text = matches!(
+        widget,
+        Widget::Text | Widget::TextArea | Widget::Password | Widget::Email | Widget::Url
+    );
+    let number = matches!(widget, Widget::Integer | Widget::Number);
+    let chosen = match name {
+        "text" if text => Widget::Text,
+        "textarea" if text => Widget::TextArea,
+        "password" if text => Widget::Password,
+        "email" if text => Widget::Email,
+        "url" if text => Widget::Url,
+        "integer" if number => Widget::Integer,
+        "number" if number => Widget::Number,
+        _ => {
+            return Err(FormError::InvalidAttribute(format!(
+                "widget = \"{name}\" (on a {} field)",
+                widget.name()
+            )));
+        }
+    };
+    Ok(chosen)
+}
+
+/// Splits a doc comment into its first line and the rest, both trimmed.
+fn split_doc(doc: &[&str]) -> (Option<String>, Option<String>) {
+    let mut lines = doc
+        .iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line));
+    let first = lines.next().map(|line| line.trim().to_string());
+    let rest: Vec<&str> = lines.collect();
+    let rest = rest.join("\n");
+    let rest = rest.trim();
+    let rest = (!rest.is_empty()).then(|| rest.to_string());
+    (first.filter(|line| !line.is_empty()), rest)
+}
+
+/// Turns `user_name`, `userName` or `UserName` into `User name`.
+fn words(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c == '_' || c == '-' {
+            if !out.is_empty() && !out.ends_with(' ') {
+                out.push(' ');
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower {
+            out.push(' ');
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        if out.is_empty() {
+            out.extend(c.to_uppercase());
+        } else {
+            out.extend(c.to_lowercase());
+        }
+    }
+    out.trim_end().to_string()
+}
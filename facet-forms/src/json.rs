@@ -0,0 +1,144 @@
+//! Write forms as JSON.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{Constraints, Form, FormField, FormVariant, Widget};
+
+pub(crate) fn form(form: &Form) -> String {
+    let mut out = String::new();
+    out.push('{');
+    key_str(&mut out, "name", &form.name);
+    out.push(',');
+    key_str(&mut out, "title", &form.title);
+    if let Some(help) = &form.help {
+        out.push(',');
+        key_str(&mut out, "help", help);
+    }
+    out.push(',');
+    fields(&mut out, &form.fields);
+    out.push('}');
+    out
+}
+
+/// Writes `"fields":[...]`.
+fn fields(out: &mut String, fields: &[FormField]) {
+    out.push_str("\"fields\":[");
+    for (i, f) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        field(out, f);
+    }
+    out.push(']');
+}
+
+fn field(out: &mut String, field: &FormField) {
+    out.push('{');
+    key_str(out, "name", &field.name);
+    out.push(',');
+    key_str(out, "label", &field.label);
+    if let Some(help) = &field.help {
+        out.push(',');
+        key_str(out, "help", help);
+    }
+    if let Some(placeholder) = &field.placeholder {
+        out.push(',');
+        key_str(out, "placeholder", placeholder);
+    }
+    write!(out, ",\"required\":{},", field.required).unwrap();
+    key_str(out, "widget", field.widget.name());
+    constraints(out, &field.constraints);
+    match &field.widget {
+        Widget::Select(options) => {
+            out.push_str(",\"options\":[");
+            for (i, option) in options.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push('{');
+                key_str(out, "value", &option.value);
+                out.push(',');
+                key_str(out, "label", &option.label);
+                out.push('}');
+            }
+            out.push(']');
+        }
+        Widget::Group(group) => {
+            out.push(',');
+            fields(out, group);
+        }
+        Widget::List(item) => {
+            out.push_str(",\"item\":");
+            self::field(out, item);
+        }
+        Widget::Map(value) => {
+            out.push_str(",\"value\":");
+            self::field(out, value);
+        }
+        Widget::Choice(variants) => {
+            out.push_str(",\"variants\":[");
+            for (i, v) in variants.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                variant(out, v);
+            }
+            out.push(']');
+        }
+        _ => {}
+    }
+    out.push('}');
+}
+
+fn variant(out: &mut String, variant: &FormVariant) {
+    out.push('{');
+    key_str(out, "name", &variant.name);
+    out.push(',');
+    key_str(out, "label", &variant.label);
+    out.push(',');
+    fields(out, &variant.fields);
+    out.push('}');
+}
+
+/// Writes the constraints that are set, each preceded by a comma.
+fn constraints(out: &mut String, constraints: &Constraints) {
+    if let Some(min) = constraints.min {
+        write!(out, ",\"min\":{min}").unwrap();
+    }
+    if let Some(max) = constraints.max {
+        write!(out, ",\"max\":{max}").unwrap();
+    }
+    if let Some(min_length) = constraints.min_length {
+        write!(out, ",\"minLength\":{min_length}").unwrap();
+    }
+    if let Some(max_length) = constraints.max_length {
+        write!(out, ",\"maxLength\":{max_length}").unwrap();
+    }
+    if let Some(pattern) = &constraints.pattern {
+        out.push(',');
+        key_str(out, "pattern", pattern);
+    }
+}
+
+fn key_str(out: &mut String, key: &str, value: &str) {
+    string(out, key);
+    out.push(':');
+    string(out, value);
+}
+
+fn string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_forms::{Constraints, Form, FormError, FormOption, Widget, to_form_json};
+use facet_testhelpers::test;
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Plan {
+    Free,
+    /// Paid yearly
+    ProYearly,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Contact {
+    Email {
+        #[facet(form(widget = "email"))]
+        address: String,
+    },
+    Phone(String),
+}
+
+#[derive(Facet)]
+struct Audit {
+    /// Who made the last change
+    updated_by: String,
+}
+
+/// A customer account
+///
+/// Accounts are created by the sales team.
+#[derive(Facet)]
+#[facet(rename_all = "camelCase")]
+struct Account {
+    /// Display name
+    #[facet(form(min_length = 1, max_length = 64))]
+    display_name: String,
+    #[facet(sensitive)]
+    api_key: String,
+    #[facet(default)]
+    seats: u16,
+    balance: f64,
+    plan: Plan,
+    contact: Option<Contact>,
+    tags: Vec<String>,
+    limits: HashMap<String, u32>,
+    #[facet(flatten)]
+    audit: Audit,
+}
+
+#[test]
+fn describes_fields() {
+    let form = Form::of::<Account>()?;
+    assert_eq!(form.name, "Account");
+    assert_eq!(form.title, "A customer account");
+    assert_eq!(
+        form.help.as_deref(),
+        Some("Accounts are created by the sales team.")
+    );
+
+    let names: Vec<_> = form.fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "displayName",
+            "apiKey",
+            "seats",
+            "balance",
+            "plan",
+            "contact",
+            "tags",
+            "limits",
+            // `rename_all` doesn't reach into flattened structs, as when serializing
+            "updated_by"
+        ]
+    );
+
+    let display_name = &form.fields[0];
+    assert_eq!(display_name.label, "Display name");
+    assert_eq!(display_name.widget, Widget::Text);
+    assert!(display_name.required);
+    assert_eq!(
+        display_name.constraints,
+        Constraints {
+            min_length: Some(1),
+            max_length: Some(64),
+            ..Constraints::default()
+        }
+    );
+
+    assert_eq!(form.fields[1].label, "Api key");
+    assert_eq!(form.fields[1].widget, Widget::Password);
+
+    let seats = &form.fields[2];
+    assert!(!seats.required);
+    assert_eq!(seats.widget, Widget::Integer);
+    assert_eq!(seats.constraints.min, Some(0.0));
+    assert_eq!(seats.constraints.max, Some(65535.0));
+
+    assert_eq!(form.fields[3].widget, Widget::Number);
+    assert_eq!(form.fields[3].constraints, Constraints::default());
+
+    assert_eq!(
+        form.fields[4].widget,
+        Widget::Select(vec![
+            FormOption {
+                value: "Free".into(),
+                label: "Free".into(),
+            },
+            FormOption {
+                value: "ProYearly".into(),
+                label: "Paid yearly".into(),
+            },
+        ])
+    );
+
+    let contact = &form.fields[5];
+    assert!(!contact.required);
+    let Widget::Choice(variants) = &contact.widget else {
+        panic!("expected a choice, got {:?}", contact.widget);
+    };
+    assert_eq!(variants[0].name, "Email");
+    assert_eq!(variants[0].fields[0].widget, Widget::Email);
+    assert_eq!(variants[1].label, "Phone");
+    assert_eq!(variants[1].fields[0].name, "0");
+
+    let Widget::List(item) = &form.fields[6].widget else {
+        panic!("expected a list");
+    };
+    assert_eq!(item.widget, Widget::Text);
+    let Widget::Map(value) = &form.fields[7].widget else {
+        panic!("expected a map");
+    };
+    assert_eq!(value.widget, Widget::Integer);
+
+    assert_eq!(form.fields[8].label, "Who made the last change");
+}
+
+#[derive(Facet)]
+struct Login {
+    /// User name
+    #[facet(form(placeholder = "jane, or jane@example.com", pattern = "^\\S+$"))]
+    user: String,
+    remember: bool,
+}
+
+#[test]
+fn writes_json() {
+    assert_eq!(
+        to_form_json::<Login>()?,
+        concat!(
+            r#"{"name":"Login","title":"Login","fields":["#,
+            r#"{"name":"user","label":"User name","placeholder":"jane, or jane@example.com","required":true,"widget":"text","pattern":"^\\S+$"},"#,
+            r#"{"name":"remember","label":"Remember","required":true,"widget":"checkbox"}"#,
+            r#"]}"#
+        )
+    );
+}
+
+#[derive(Facet)]
+struct Tree {
+    label: String,
+    children: Vec<Tree>,
+}
+
+#[test]
+fn rejects_recursive_types() {
+    assert!(matches!(
+        Form::of::<Tree>(),
+        Err(FormError::UnsupportedType(_))
+    ));
+}
+
+#[derive(Facet)]
+struct BadWidget {
+    #[facet(form(widget = "checkbox"))]
+    name: String,
+}
+
+#[derive(Facet)]
+struct BadBound {
+    #[facet(form(min = "ten"))]
+    count: u8,
+}
+
+#[test]
+fn rejects_invalid_attributes() {
+    assert!(matches!(
+        Form::of::<BadWidget>(),
+        Err(FormError::InvalidAttribute(_))
+    ));
+    assert!(matches!(
+        Form::of::<BadBound>(),
+        Err(FormError::InvalidAttribute(_))
+    ));
+}
+
+#[test]
+fn rejects_non_structs() {
+    assert!(matches!(
+        Form::of::<Plan>(),
+        Err(FormError::UnsupportedType(_))
+    ));
+}
@@ -0,0 +1,79 @@
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use facet::Facet;
+
+pub mod types;
+
+/// Calls `$check::<T>($input)` for every type of the suite in [`types`], plus a few
+/// standalone scalars and collections at the root.
+macro_rules! for_each_type {
+    ($check:ident, $input:expr) => {{
+        let input = $input;
+        $check::<u64>(input);
+        $check::<String>(input);
+        $check::<Vec<i32>>(input);
+        $check::<Option<bool>>(input);
+        $check::<types::Scalars>(input);
+        $check::<types::Collections>(input);
+        $check::<types::Optionals>(input);
+        $check::<types::Point>(input);
+        $check::<types::Meters>(input);
+        $check::<types::Figure>(input);
+        $check::<Vec<types::Figure>>(input);
+        $check::<types::Tree>(input);
+        $check::<types::Attributes>(input);
+        $check::<types::Strict>(input);
+        $check::<types::Document>(input);
+    }};
+}
+
+/// Deserializes `data` as JSON into every type of the suite. Whatever deserializes is
+/// serialized again.
+pub fn json(data: &[u8]) {
+    for_each_type!(json_as, data);
+}
+
+fn json_as<T: for<'a> Facet<'a>>(data: &[u8]) {
+    if let Ok(value) = facet_json::from_slice::<T>(data) {
+        let _ = facet_json::to_string(&value);
+    }
+}
+
+/// Deserializes `data` as MessagePack into every type of the suite. Whatever deserializes is
+/// serialized again.
+pub fn msgpack(data: &[u8]) {
+    for_each_type!(msgpack_as, data);
+}
+
+fn msgpack_as<T: for<'a> Facet<'a>>(data: &[u8]) {
+    if let Ok(value) = facet_msgpack::from_slice::<T>(data) {
+        let _ = facet_msgpack::to_vec(&value);
+    }
+}
+
+/// Deserializes `data` as TOML into every type of the suite, if it is UTF-8. Whatever
+/// deserializes is serialized again.
+pub fn toml(data: &[u8]) {
+    if let Ok(data) = core::str::from_utf8(data) {
+        for_each_type!(toml_as, data);
+    }
+}
+
+fn toml_as<T: for<'a> Facet<'a>>(data: &str) {
+    if let Ok(value) = facet_toml::from_str::<T>(data) {
+        let _ = facet_toml::to_string(&value);
+    }
+}
+
+/// Deserializes `data` as XDR into every type of the suite. Whatever deserializes is
+/// serialized again.
+pub fn xdr(data: &[u8]) {
+    for_each_type!(xdr_as, data);
+}
+
+fn xdr_as<T: for<'a> Facet<'a>>(data: &[u8]) {
+    if let Ok(value) = facet_xdr::deserialize::<T>(data) {
+        let _ = facet_xdr::to_vec(&value);
+    }
+}
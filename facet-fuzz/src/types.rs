@@ -0,0 +1,117 @@
+//! The types every fuzz target deserializes into.
+//!
+//! Between them they cover the shapes a deserializer has to tell apart: scalars of every
+//! width, lists, maps, sets, options, smart pointers, tuple structs, all three kinds of enum
+//! variant, recursion, defaults, renames and flattening.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use facet::Facet;
+
+/// One field of every scalar type.
+#[derive(Facet, Debug)]
+pub struct Scalars {
+    pub boolean: bool,
+    pub unsigned_8: u8,
+    pub unsigned_16: u16,
+    pub unsigned_32: u32,
+    pub unsigned_64: u64,
+    pub signed_8: i8,
+    pub signed_16: i16,
+    pub signed_32: i32,
+    pub signed_64: i64,
+    pub float_32: f32,
+    pub float_64: f64,
+    pub character: char,
+    pub string: String,
+}
+
+/// Lists, maps and sets, some of them nested.
+#[derive(Facet, Debug)]
+pub struct Collections {
+    pub numbers: Vec<u32>,
+    pub bytes: Vec<u8>,
+    pub matrix: Vec<Vec<String>>,
+    pub by_name: HashMap<String, i64>,
+    pub sorted: BTreeMap<String, Vec<bool>>,
+    pub tags: HashSet<String>,
+    pub fixed: [u16; 3],
+}
+
+/// Options around scalars, strings and pointers.
+#[derive(Facet, Debug)]
+pub struct Optionals {
+    pub number: Option<u32>,
+    pub text: Option<String>,
+    pub nested: Option<Option<i16>>,
+    pub boxed: Option<Box<Point>>,
+    pub shared: Option<Arc<str>>,
+}
+
+/// A tuple struct.
+#[derive(Facet, Debug)]
+pub struct Point(pub f64, pub f64);
+
+/// A newtype around a scalar.
+#[derive(Facet, Debug)]
+#[facet(transparent)]
+pub struct Meters(pub u64);
+
+/// Unit, tuple and struct variants.
+#[derive(Facet, Debug)]
+#[repr(u8)]
+pub enum Figure {
+    Empty,
+    Circle(Point, f64),
+    Polygon { points: Vec<Point>, closed: bool },
+    Label(String),
+}
+
+/// A type that contains itself, to reach deep nesting.
+#[derive(Facet, Debug)]
+pub struct Tree {
+    pub value: i32,
+    pub children: Vec<Tree>,
+}
+
+/// Field attributes that change how the input maps to fields.
+#[derive(Facet, Debug)]
+pub struct Attributes {
+    #[facet(rename = "type")]
+    pub kind: String,
+    #[facet(default)]
+    pub count: u32,
+    #[facet(default = 7)]
+    pub answer: u8,
+    #[facet(flatten)]
+    pub position: Position,
+    pub length: Meters,
+}
+
+/// Flattened into [`Attributes`].
+#[derive(Facet, Debug)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A struct that rejects fields it doesn't know.
+#[derive(Facet, Debug)]
+#[facet(deny_unknown_fields)]
+pub struct Strict {
+    pub id: u64,
+    pub name: String,
+}
+
+/// Everything above in one document.
+#[derive(Facet, Debug)]
+pub struct Document {
+    pub scalars: Scalars,
+    pub collections: Collections,
+    pub optionals: Optionals,
+    pub figures: Vec<Figure>,
+    pub tree: Tree,
+    pub attributes: Attributes,
+    pub strict: Strict,
+}
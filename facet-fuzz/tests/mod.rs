@@ -0,0 +1,89 @@
+use facet::Facet;
+use facet_arbitrary::{Generator, SeededSource};
+use facet_fuzz::types::{Attributes, Figure, Scalars, Tree};
+use facet_testhelpers::test;
+
+/// A few random values of type `T`, the same ones every run.
+fn values<T: for<'a> Facet<'a>>() -> Vec<T> {
+    let generator = Generator::new().with_max_len(3).with_max_depth(3);
+    let mut source = SeededSource::new(0);
+    (0..4)
+        .map(|_| generator.generate(&mut source).unwrap())
+        .collect()
+}
+
+/// Encodes random values of several of the suite's types with `$encode`, which returns
+/// `None` for values the format can't write.
+macro_rules! encodings {
+    ($encode:expr) => {{
+        let mut encodings: Vec<Vec<u8>> = Vec::new();
+        encodings.extend(values::<Scalars>().iter().filter_map($encode));
+        encodings.extend(values::<Vec<Figure>>().iter().filter_map($encode));
+        encodings.extend(values::<Tree>().iter().filter_map($encode));
+        encodings.extend(values::<Attributes>().iter().filter_map($encode));
+        encodings
+    }};
+}
+
+/// Feeds `target` each encoding, every prefix of it, and copies of it with one byte replaced.
+fn mutate(target: fn(&[u8]), encodings: &[Vec<u8>]) {
+    assert!(!encodings.is_empty());
+    for encoding in encodings {
+        target(encoding);
+        for len in 0..encoding.len() {
+            target(&encoding[..len]);
+        }
+        let mut mutated = encoding.clone();
+        for index in 0..encoding.len() {
+            for byte in [0x00, 0xff, b'"', b'['] {
+                mutated[index] = byte;
+                target(&mutated);
+            }
+            mutated[index] = encoding[index];
+        }
+    }
+}
+
+#[test]
+fn test_json_mutations() {
+    let encodings = encodings!(|value| Some(facet_json::to_string(value).into_bytes()));
+    mutate(facet_fuzz::json, &encodings);
+}
+
+#[test]
+fn test_msgpack_mutations() {
+    let encodings = encodings!(|value| Some(facet_msgpack::to_vec(value)));
+    mutate(facet_fuzz::msgpack, &encodings);
+}
+
+#[test]
+fn test_toml_mutations() {
+    let encodings = encodings!(|value| facet_toml::to_string(value).ok().map(String::into_bytes));
+    mutate(facet_fuzz::toml, &encodings);
+}
+
+#[test]
+fn test_xdr_mutations() {
+    let encodings = encodings!(|value| facet_xdr::to_vec(value).ok());
+    mutate(facet_fuzz::xdr, &encodings);
+}
+
+#[test]
+fn test_arbitrary_bytes() {
+    let inputs: [&[u8]; 8] = [
+        b"",
+        b"\0",
+        b"[[[[[[[[[[[[[[[[[[[[[[[[[[[[[[[[",
+        b"{\"value\": 1, \"children\": [{\"value\": ",
+        b"[[figures]]\nEmpty = 1",
+        &[0xdd, 0xff, 0xff, 0xff, 0xff],
+        &[0x81, 0xa5, b'o', b't', b'h', b'e', b'r', 0xcf, 0x00],
+        &[0x00, 0x00, 0x00, 0x01, 0xff, 0xff, 0xff, 0xff],
+    ];
+    for input in inputs {
+        facet_fuzz::json(input);
+        facet_fuzz::msgpack(input);
+        facet_fuzz::toml(input);
+        facet_fuzz::xdr(input);
+    }
+}
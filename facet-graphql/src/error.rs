@@ -0,0 +1,31 @@
+//! Errors from generating GraphQL schemas.
+
+use alloc::string::String;
+
+/// Errors when generating a GraphQL schema.
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum GraphQLError {
+    /// A type has no GraphQL equivalent.
+    UnsupportedType(String),
+    /// Two different types would get the same GraphQL name.
+    DuplicateName(String),
+    /// A type, field or variant name isn't a valid GraphQL name.
+    InvalidName(String),
+}
+
+impl core::fmt::Display for GraphQLError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            GraphQLError::UnsupportedType(ty) => {
+                write!(f, "Type {ty} can't be represented in GraphQL")
+            }
+            GraphQLError::DuplicateName(name) => {
+                write!(f, "Two different types are both named '{name}'")
+            }
+            GraphQLError::InvalidName(name) => write!(f, "'{name}' is not a valid GraphQL name"),
+        }
+    }
+}
+
+impl core::error::Error for GraphQLError {}
@@ -0,0 +1,451 @@
+//! Generate GraphQL SDL from Facet shapes.
+
+use alloc::{
+    collections::BTreeSet,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+use facet_core::{
+    Def, EnumType, Facet, FieldFlags, IntegerSize, NumberBits, PointerType, ScalarAffinity,
+    ScalarDef, Shape, Signedness, SmartPointerDef, StructKind, StructType, Type, UserType,
+};
+
+use crate::GraphQLError;
+
+/// Generates GraphQL object, enum and union definitions for `T` and every type it uses.
+///
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet)]
+/// struct User {
+///     id: i32,
+///     name: String,
+///     email: Option<String>,
+/// }
+///
+/// let sdl = facet_graphql::to_graphql_schema::<User>().unwrap();
+/// assert_eq!(sdl, "type User {\n  id: Int!\n  name: String!\n  email: String\n}\n");
+/// ```
+pub fn to_graphql_schema<'facet, T: Facet<'facet>>() -> Result<String, GraphQLError> {
+    GraphQLSchema::new().with_object::<T>().build()
+}
+
+/// Generates GraphQL input object and enum definitions for `T` and every type it uses.
+///
+/// Input object types get an `Input` suffix, unless their name already ends in
+/// `Input`, so they don't clash with object types of the same Rust type.
+pub fn to_graphql_input_schema<'facet, T: Facet<'facet>>() -> Result<String, GraphQLError> {
+    GraphQLSchema::new().with_input::<T>().build()
+}
+
+/// A GraphQL schema made of several root types, used as outputs or as inputs.
+///
+/// Types reachable from several roots are only defined once.
+#[derive(Debug, Default, Clone)]
+pub struct GraphQLSchema<'shape> {
+    roots: Vec<(&'shape Shape<'shape>, Mode)>,
+}
+
+/// Whether a type is returned by the API or passed to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Output,
+    Input,
+}
+
+impl<'shape> GraphQLSchema<'shape> {
+    /// Creates an empty schema.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `T` as an object type, along with every type it uses.
+    pub fn with_object<'facet, T: Facet<'facet>>(self) -> Self {
+        self.with_object_shape(T::SHAPE)
+    }
+
+    /// Adds `T` as an input object type, along with every type it uses.
+    pub fn with_input<'facet, T: Facet<'facet>>(self) -> Self {
+        self.with_input_shape(T::SHAPE)
+    }
+
+    /// Adds a shape as an object type. See [`GraphQLSchema::with_object`].
+    pub fn with_object_shape(mut self, shape: &'shape Shape<'shape>) -> Self {
+        self.roots.push((shape, Mode::Output));
+        self
+    }
+
+    /// Adds a shape as an input object type. See [`GraphQLSchema::with_input`].
+    pub fn with_input_shape(mut self, shape: &'shape Shape<'shape>) -> Self {
+        self.roots.push((shape, Mode::Input));
+        self
+    }
+
+    /// Generates the schema in GraphQL SDL.
+    ///
+    /// Custom scalars come first, then the types of each root in the order they were
+    /// reached.
+    pub fn build(&self) -> Result<String, GraphQLError> {
+        let mut writer = SchemaWriter::default();
+        let mut definitions = Vec::new();
+        let mut next = 0;
+        for &(shape, mode) in &self.roots {
+            writer.type_ref(shape, mode)?;
+            while let Some(definition) = writer.queue.get(next).cloned() {
+                next += 1;
+                let mut out = String::new();
+                match definition {
+                    Definition::Object {
+                        name,
+                        doc,
+                        sd,
+                        mode,
+                    } => writer.write_object(&mut out, &name, doc, sd, mode)?,
+                    Definition::Enum { shape, ed } => write_enum(&mut out, shape, ed)?,
+                    Definition::Union { shape, ed } => writer.write_union(&mut out, shape, ed)?,
+                }
+                definitions.push(out);
+            }
+        }
+
+        let mut out = String::new();
+        for scalar in &writer.scalars {
+            writeln!(out, "scalar {scalar}").unwrap();
+        }
+        for definition in definitions {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&definition);
+        }
+        Ok(out)
+    }
+}
+
+/// A named type waiting to be written.
+#[derive(Clone)]
+enum Definition<'shape> {
+    /// A struct or an enum variant, as an object or input object type.
+    Object {
+        name: String,
+        doc: &'shape [&'shape str],
+        sd: StructType<'shape>,
+        mode: Mode,
+    },
+    /// An enum with only unit variants.
+    Enum {
+        shape: &'shape Shape<'shape>,
+        ed: EnumType<'shape>,
+    },
+    /// An enum with data, as a union of one object type per variant.
+    Union {
+        shape: &'shape Shape<'shape>,
+        ed: EnumType<'shape>,
+    },
+}
+
+#[derive(Default)]
+struct SchemaWriter<'shape> {
+    /// Types to define, in order of discovery.
+    queue: Vec<Definition<'shape>>,
+    /// Every name handed out so far, with the shape and mode it stands for.
+    names: Vec<(String, &'shape Shape<'shape>, Mode)>,
+    /// Custom scalars used by the schema.
+    scalars: BTreeSet<&'static str>,
+}
+
+impl<'shape> SchemaWriter<'shape> {
+    /// Returns the GraphQL type of a field holding `shape`, with its list and non-null wrappers.
+    fn type_ref(
+        &mut self,
+        shape: &'shape Shape<'shape>,
+        mode: Mode,
+    ) -> Result<String, GraphQLError> {
+        match shape.def {
+            Def::Option(od) => {
+                let inner = self.type_ref(od.t(), mode)?;
+                return Ok(inner.strip_suffix('!').unwrap_or(&inner).to_string());
+            }
+            Def::List(ld) => return Ok(format!("[{}]!", self.type_ref(ld.t(), mode)?)),
+            Def::Array(ad) => return Ok(format!("[{}]!", self.type_ref(ad.t(), mode)?)),
+            Def::Slice(sd) => return Ok(format!("[{}]!", self.type_ref(sd.t(), mode)?)),
+            Def::Set(sd) => return Ok(format!("[{}]!", self.type_ref((sd.t)(), mode)?)),
+            // GraphQL has no maps; they are passed around as JSON objects
+            Def::Map(_) => return Ok(format!("{}!", self.custom_scalar("JSON"))),
+            Def::SmartPointer(SmartPointerDef {
+                pointee: Some(pointee),
+                ..
+            }) => return self.type_ref(pointee(), mode),
+            Def::Scalar(sd) => return Ok(format!("{}!", self.scalar(shape, sd)?)),
+            _ => {}
+        }
+
+        if let Some(inner) = shape.inner {
+            return self.type_ref(inner(), mode);
+        }
+
+        let name = match shape.ty {
+            Type::User(UserType::Struct(sd)) => {
+                let name = match mode {
+                    Mode::Input if !shape.type_identifier.ends_with("Input") => {
+                        format!("{}Input", type_name(shape))
+                    }
+                    _ => type_name(shape),
+                };
+                if self.register(&name, shape, mode)? {
+                    self.queue.push(Definition::Object {
+                        name: name.clone(),
+                        doc: shape.doc,
+                        sd,
+                        mode,
+                    });
+                }
+                name
+            }
+            Type::User(UserType::Enum(ed)) => {
+                let name = type_name(shape);
+                if ed.variants.iter().all(|v| v.data.kind == StructKind::Unit) {
+                    // Enums are the same type whether they're used as input or output
+                    if self.register(&name, shape, Mode::Output)? {
+                        self.queue.push(Definition::Enum { shape, ed });
+                    }
+                } else if mode == Mode::Input {
+                    return Err(GraphQLError::UnsupportedType(format!(
+                        "{shape} (enums with data can't be inputs)"
+                    )));
+                } else if self.register(&name, shape, Mode::Output)? {
+                    self.queue.push(Definition::Union { shape, ed });
+                }
+                name
+            }
+            Type::Pointer(PointerType::Reference(pt)) => return self.type_ref((pt.target)(), mode),
+            _ => return Err(GraphQLError::UnsupportedType(shape.to_string())),
+        };
+        Ok(format!("{name}!"))
+    }
+
+    /// Maps a scalar to a built-in GraphQL scalar, or to a custom one based on its affinity.
+    fn scalar(
+        &mut self,
+        shape: &'shape Shape<'shape>,
+        sd: ScalarDef<'shape>,
+    ) -> Result<&'static str, GraphQLError> {
+        let scalar = match sd.affinity {
+            ScalarAffinity::Boolean(_) => "Boolean",
+            ScalarAffinity::Number(na) => match na.bits {
+                // `Int` is a signed 32-bit integer
+                NumberBits::Integer {
+                    size: IntegerSize::Fixed(bits),
+                    sign,
+                } if bits < 32 || (bits == 32 && sign == Signedness::Signed) => "Int",
+                NumberBits::Integer { .. } => self.custom_scalar("BigInt"),
+                NumberBits::Float { .. } => "Float",
+                _ => return Err(GraphQLError::UnsupportedType(shape.to_string())),
+            },
+            ScalarAffinity::String(_)
+            | ScalarAffinity::Char(_)
+            | ScalarAffinity::Path(_)
+            | ScalarAffinity::IpAddr(_)
+            | ScalarAffinity::SocketAddr(_) => "String",
+            ScalarAffinity::UUID(_) | ScalarAffinity::ULID(_) => "ID",
+            ScalarAffinity::Time(_) => self.custom_scalar("DateTime"),
+            ScalarAffinity::Url(_) => self.custom_scalar("URL"),
+            _ => return Err(GraphQLError::UnsupportedType(shape.to_string())),
+        };
+        Ok(scalar)
+    }
+
+    fn custom_scalar(&mut self, name: &'static str) -> &'static str {
+        self.scalars.insert(name);
+        name
+    }
+
+    /// Records that `name` stands for `shape`, returning whether it still needs to be defined.
+    fn register(
+        &mut self,
+        name: &str,
+        shape: &'shape Shape<'shape>,
+        mode: Mode,
+    ) -> Result<bool, GraphQLError> {
+        validate_name(name)?;
+        match self.names.iter().find(|(n, ..)| n == name) {
+            Some((_, s, m)) if *s == shape && *m == mode => Ok(false),
+            Some(_) => Err(GraphQLError::DuplicateName(name.to_string())),
+            None => {
+                self.names.push((name.to_string(), shape, mode));
+                Ok(true)
+            }
+        }
+    }
+
+    fn write_object(
+        &mut self,
+        out: &mut String,
+        name: &str,
+        doc: &[&str],
+        sd: StructType<'shape>,
+        mode: Mode,
+    ) -> Result<(), GraphQLError> {
+        let mut fields = String::new();
+        self.write_fields(&mut fields, sd, mode)?;
+        if fields.is_empty() {
+            // GraphQL objects need at least one field
+            return Err(GraphQLError::UnsupportedType(format!("{name} (no fields)")));
+        }
+
+        write_description(out, doc, "");
+        let keyword = match mode {
+            Mode::Output => "type",
+            Mode::Input => "input",
+        };
+        writeln!(out, "{keyword} {name} {{").unwrap();
+        out.push_str(&fields);
+        out.push_str("}\n");
+        Ok(())
+    }
+
+    fn write_fields(
+        &mut self,
+        out: &mut String,
+        sd: StructType<'shape>,
+        mode: Mode,
+    ) -> Result<(), GraphQLError> {
+        // A single-field tuple, like `Label(String)`, holds its value in a field called `value`
+        let newtype =
+            matches!(sd.kind, StructKind::Tuple | StructKind::TupleStruct) && sd.fields.len() == 1;
+        for field in sd.fields {
+            if mode == Mode::Output && field.flags.contains(FieldFlags::SKIP_SERIALIZING) {
+                continue;
+            }
+            if field.flags.contains(FieldFlags::FLATTEN) {
+                if let Type::User(UserType::Struct(sd)) = field.shape().ty {
+                    self.write_fields(out, sd, mode)?;
+                    continue;
+                }
+            }
+
+            let mut ty = self.type_ref(field.shape(), mode)?;
+            // Inputs can leave out fields that have a default
+            if mode == Mode::Input && field.flags.contains(FieldFlags::DEFAULT) && ty.ends_with('!')
+            {
+                ty.pop();
+            }
+            let name = if newtype {
+                "value".to_string()
+            } else {
+                field_name(field.name)
+            };
+            validate_name(&name)?;
+            write_description(out, field.doc, "  ");
+            writeln!(out, "  {name}: {ty}").unwrap();
+        }
+        Ok(())
+    }
+
+    fn write_union(
+        &mut self,
+        out: &mut String,
+        shape: &'shape Shape<'shape>,
+        ed: EnumType<'shape>,
+    ) -> Result<(), GraphQLError> {
+        let mut members = Vec::with_capacity(ed.variants.len());
+        for variant in ed.variants {
+            let name = format!("{}{}", type_name(shape), variant.name);
+            if variant.data.kind == StructKind::Unit {
+                return Err(GraphQLError::UnsupportedType(format!(
+                    "{shape}::{} (unit variants can't be in a union)",
+                    variant.name
+                )));
+            }
+            // Variant objects are keyed by their enum's shape, so they are only defined once
+            if self.register(&name, shape, Mode::Output)? {
+                self.queue.push(Definition::Object {
+                    name: name.clone(),
+                    doc: variant.doc,
+                    sd: variant.data,
+                    mode: Mode::Output,
+                });
+            }
+            members.push(name);
+        }
+
+        write_description(out, shape.doc, "");
+        writeln!(out, "union {} = {}", type_name(shape), members.join(" | ")).unwrap();
+        Ok(())
+    }
+}
+
+fn write_enum<'shape>(
+    out: &mut String,
+    shape: &'shape Shape<'shape>,
+    ed: EnumType<'shape>,
+) -> Result<(), GraphQLError> {
+    write_description(out, shape.doc, "");
+    writeln!(out, "enum {} {{", type_name(shape)).unwrap();
+    for variant in ed.variants {
+        validate_name(variant.name)?;
+        write_description(out, variant.doc, "  ");
+        writeln!(out, "  {}", variant.name).unwrap();
+    }
+    out.push_str("}\n");
+    Ok(())
+}
+
+/// The GraphQL name of a type: its Rust name followed by the names of its type parameters,
+/// so `Page<User>` becomes `PageUser`.
+fn type_name(shape: &Shape<'_>) -> String {
+    let mut name = shape.type_identifier.to_string();
+    for param in shape.type_params {
+        name.push_str(&type_name(param.shape()));
+    }
+    name
+}
+
+/// Tuple fields are named `0`, `1`, ..., which GraphQL doesn't allow.
+fn field_name(name: &str) -> String {
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+/// Checks a name against GraphQL's `[_A-Za-z][_0-9A-Za-z]*`.
+fn validate_name(name: &str) -> Result<(), GraphQLError> {
+    let valid = name.starts_with(|c: char| c == '_' || c.is_ascii_alphabetic())
+        && name.chars().all(|c| c == '_' || c.is_ascii_alphanumeric());
+    if valid {
+        Ok(())
+    } else {
+        Err(GraphQLError::InvalidName(name.to_string()))
+    }
+}
+
+/// Writes doc comments as a GraphQL description.
+fn write_description(out: &mut String, doc: &[&str], indent: &str) {
+    let lines: Vec<&str> = doc
+        .iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect();
+    match lines.as_slice() {
+        [] => {}
+        [line] if !line.contains(['"', '\\']) => {
+            writeln!(out, "{indent}\"{line}\"").unwrap();
+        }
+        lines => {
+            writeln!(out, "{indent}\"\"\"").unwrap();
+            for line in lines {
+                let line = line.replace("\"\"\"", "\\\"\"\"");
+                if line.is_empty() {
+                    out.push('\n');
+                } else {
+                    writeln!(out, "{indent}{line}").unwrap();
+                }
+            }
+            writeln!(out, "{indent}\"\"\"").unwrap();
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_graphql::{GraphQLError, GraphQLSchema, to_graphql_input_schema, to_graphql_schema};
+use facet_testhelpers::test;
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Status {
+    Draft,
+    /// Visible to everyone
+    Published,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Block {
+    Text { body: String },
+    Image(Image),
+    Divider(u8, u8),
+}
+
+#[derive(Facet)]
+struct Image {
+    url: String,
+    width: u16,
+}
+
+#[derive(Facet)]
+struct Page<T> {
+    items: Vec<T>,
+    total: u64,
+}
+
+/// A blog post.
+///
+/// Posts start out as drafts.
+#[derive(Facet)]
+struct Post {
+    title: String,
+    status: Status,
+    blocks: Vec<Block>,
+    score: Option<f64>,
+    tags: HashMap<String, String>,
+    #[facet(skip_serializing)]
+    secret: String,
+}
+
+#[test]
+fn test_object_schema() {
+    assert_eq!(
+        to_graphql_schema::<Post>()?,
+        r#"scalar JSON
+
+"""
+A blog post.
+
+Posts start out as drafts.
+"""
+type Post {
+  title: String!
+  status: Status!
+  blocks: [Block!]!
+  score: Float
+  tags: JSON!
+}
+
+enum Status {
+  Draft
+  "Visible to everyone"
+  Published
+}
+
+union Block = BlockText | BlockImage | BlockDivider
+
+type BlockText {
+  body: String!
+}
+
+type BlockImage {
+  value: Image!
+}
+
+type BlockDivider {
+  _0: Int!
+  _1: Int!
+}
+
+type Image {
+  url: String!
+  width: Int!
+}
+"#
+    );
+}
+
+#[test]
+fn test_generics_and_shared_types() {
+    #[derive(Facet)]
+    struct Query {
+        images: Page<Image>,
+        featured: Option<Image>,
+    }
+
+    assert_eq!(
+        to_graphql_schema::<Query>()?,
+        r#"scalar BigInt
+
+type Query {
+  images: PageImage!
+  featured: Image
+}
+
+type PageImage {
+  items: [Image!]!
+  total: BigInt!
+}
+
+type Image {
+  url: String!
+  width: Int!
+}
+"#
+    );
+}
+
+#[test]
+fn test_inputs() {
+    #[derive(Facet)]
+    struct NewImage {
+        url: String,
+        #[facet(default)]
+        width: u16,
+    }
+
+    assert_eq!(
+        GraphQLSchema::new()
+            .with_object::<Image>()
+            .with_input::<NewImage>()
+            .with_input::<Image>()
+            .build()?,
+        r#"type Image {
+  url: String!
+  width: Int!
+}
+
+input NewImageInput {
+  url: String!
+  width: Int
+}
+
+input ImageInput {
+  url: String!
+  width: Int!
+}
+"#
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert!(matches!(
+        to_graphql_input_schema::<Block>(),
+        Err(GraphQLError::UnsupportedType(_))
+    ));
+
+    #[derive(Facet)]
+    struct Dashed {
+        #[facet(rename = "not-valid")]
+        field: bool,
+    }
+    assert_eq!(
+        to_graphql_schema::<Dashed>(),
+        Err(GraphQLError::InvalidName("not-valid".to_string()))
+    );
+
+    mod other {
+        #[derive(facet::Facet)]
+        pub struct Image {
+            pub path: String,
+        }
+    }
+    #[derive(Facet)]
+    struct Gallery {
+        local: other::Image,
+        remote: Image,
+    }
+    assert_eq!(
+        to_graphql_schema::<Gallery>(),
+        Err(GraphQLError::DuplicateName("Image".to_string()))
+    );
+}
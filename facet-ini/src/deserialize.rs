@@ -0,0 +1,393 @@
+//! Parse INI strings into Rust values.
+
+use alloc::{borrow::Cow, string::ToString, vec::Vec};
+use facet_core::{Def, Facet, Field, FieldFlags, Shape, StructKind, Type, UserType};
+use facet_reflect::{Partial, ScalarType};
+use log::trace;
+
+use crate::{
+    IniDeError, IniDeErrorKind, IniOptions,
+    parse::{Entry, Table, parse},
+};
+
+/// Deserializes an INI string into a value of type `T` that implements `Facet`.
+///
+/// Keys before the first section header fill the fields of the root struct, every
+/// `[section]` fills the struct field with the same name, and keys that appear more
+/// than once fill `Vec` fields.
+///
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Config {
+///     name: String,
+///     tags: Vec<String>,
+///     server: Server,
+/// }
+///
+/// let ini = r#"
+/// name = demo
+/// tags = a
+/// tags = b
+///
+/// [server]
+/// host = localhost
+/// port = 8080
+/// "#;
+///
+/// let config: Config = facet_ini::from_str(ini).unwrap();
+/// assert_eq!(config.tags, vec!["a".to_string(), "b".to_string()]);
+/// assert_eq!(config.server.port, 8080);
+/// ```
+pub fn from_str<'input, 'facet: 'shape, 'shape, T: Facet<'facet>>(
+    ini: &'input str,
+) -> Result<T, IniDeError<'shape>> {
+    from_str_with_options(ini, &IniOptions::default())
+}
+
+/// Deserializes an INI string into a value of type `T`, using the given options.
+pub fn from_str_with_options<'input, 'facet: 'shape, 'shape, T: Facet<'facet>>(
+    ini: &'input str,
+    options: &IniOptions,
+) -> Result<T, IniDeError<'shape>> {
+    trace!("Parsing INI");
+    let root = parse(ini)?;
+
+    let mut partial = Partial::alloc::<T>().map_err(IniDeError::reflect(None))?;
+    IniDeserializer { options }.deserialize_table(partial.inner_mut(), &root)?;
+    let value = partial.build().map_err(IniDeError::reflect(None))?;
+    Ok(*value)
+}
+
+struct IniDeserializer<'o> {
+    options: &'o IniOptions,
+}
+
+impl IniDeserializer<'_> {
+    fn deserialize_table<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        table: &Table,
+    ) -> Result<(), IniDeError<'shape>> {
+        let shape = wip.shape();
+        let line = Some(table.line);
+        trace!("Deserializing section '{}' into {}", table.name, shape);
+
+        if let Def::Map(_) = shape.def {
+            return self.deserialize_map(wip, table);
+        }
+        if shape.inner.is_some() {
+            wip.begin_inner().map_err(IniDeError::reflect(line))?;
+            self.deserialize_table(wip, table)?;
+            wip.end().map_err(IniDeError::reflect(line))?;
+            return Ok(());
+        }
+        let Type::User(UserType::Struct(sd)) = shape.ty else {
+            return Err(IniDeError::new(
+                IniDeErrorKind::UnsupportedShape(shape),
+                line,
+            ));
+        };
+
+        if shape.has_deny_unknown_fields_attr() {
+            for entry in &table.entries {
+                if !sd
+                    .fields
+                    .iter()
+                    .any(|f| !is_section(f.shape()) && self.options.names_match(&entry.key, f.name))
+                {
+                    return Err(IniDeError::new(
+                        IniDeErrorKind::UnknownKey(entry.key.clone()),
+                        Some(entry.line),
+                    ));
+                }
+            }
+            for child in &table.children {
+                if !sd
+                    .fields
+                    .iter()
+                    .any(|f| is_section(f.shape()) && self.options.names_match(&child.name, f.name))
+                {
+                    return Err(IniDeError::new(
+                        IniDeErrorKind::UnknownSection(child.name.clone()),
+                        Some(child.line),
+                    ));
+                }
+            }
+        }
+
+        for (index, field) in sd.fields.iter().enumerate() {
+            if is_section(field.shape()) {
+                let child = table
+                    .children
+                    .iter()
+                    .find(|c| self.options.names_match(&c.name, field.name));
+                let Some(child) = child else {
+                    continue;
+                };
+                wip.begin_nth_field(index)
+                    .map_err(IniDeError::reflect(line))?;
+                if let Def::Option(_) = wip.shape().def {
+                    wip.begin_some().map_err(IniDeError::reflect(line))?;
+                    self.deserialize_table(wip, child)?;
+                    wip.end().map_err(IniDeError::reflect(line))?;
+                } else {
+                    self.deserialize_table(wip, child)?;
+                }
+                wip.end().map_err(IniDeError::reflect(line))?;
+            } else {
+                let entries: Vec<&Entry> = table
+                    .entries
+                    .iter()
+                    .filter(|e| self.options.names_match(&e.key, field.name))
+                    .collect();
+                if entries.is_empty() {
+                    continue;
+                }
+                wip.begin_nth_field(index)
+                    .map_err(IniDeError::reflect(line))?;
+                self.deserialize_entries(wip, &entries)?;
+                wip.end().map_err(IniDeError::reflect(line))?;
+            }
+        }
+
+        wip.fill_unset_fields_from_default()
+            .map_err(IniDeError::reflect(line))?;
+        for (index, field) in sd.fields.iter().enumerate() {
+            if wip.is_field_set(index).map_err(IniDeError::reflect(line))? {
+                continue;
+            }
+            self.default_field(wip, index, field, table.line)?;
+        }
+
+        Ok(())
+    }
+
+    /// Fills a field that has no key or section in the document.
+    ///
+    /// `Option`s become `None` and lists become empty, since INI has no way to spell out
+    /// either. Anything else needs a `#[facet(default)]`.
+    fn default_field<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        index: usize,
+        field: &Field<'shape>,
+        line: usize,
+    ) -> Result<(), IniDeError<'shape>> {
+        let line = Some(line);
+        let shape = field.shape();
+        let implicit = matches!(shape.def, Def::Option(_) | Def::List(_) | Def::Map(_))
+            || shape.is_type::<()>();
+
+        if let (true, Some(default_fn)) = (
+            field.flags.contains(FieldFlags::DEFAULT),
+            field.vtable.default_fn,
+        ) {
+            wip.begin_nth_field(index)
+                .map_err(IniDeError::reflect(line))?;
+            wip.set_field_default(default_fn)
+                .map_err(IniDeError::reflect(line))?;
+            wip.end().map_err(IniDeError::reflect(line))?;
+        } else if implicit || field.flags.contains(FieldFlags::DEFAULT) {
+            wip.begin_nth_field(index)
+                .map_err(IniDeError::reflect(line))?;
+            wip.set_default().map_err(IniDeError::reflect(line))?;
+            wip.end().map_err(IniDeError::reflect(line))?;
+        } else {
+            return Err(IniDeError::new(
+                IniDeErrorKind::MissingField(field.name),
+                line,
+            ));
+        }
+        Ok(())
+    }
+
+    fn deserialize_map<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        table: &Table,
+    ) -> Result<(), IniDeError<'shape>> {
+        let line = Some(table.line);
+        let Def::Map(map_def) = wip.shape().def else {
+            unreachable!("deserialize_map is only called for maps");
+        };
+        wip.begin_map().map_err(IniDeError::reflect(line))?;
+
+        if is_section(map_def.v()) {
+            for child in &table.children {
+                self.set_map_key(wip, &child.name, child.line)?;
+                wip.begin_value().map_err(IniDeError::reflect(line))?;
+                self.deserialize_table(wip, child)?;
+                wip.end().map_err(IniDeError::reflect(line))?;
+            }
+        } else {
+            for entry in &table.entries {
+                self.set_map_key(wip, &entry.key, entry.line)?;
+                wip.begin_value().map_err(IniDeError::reflect(line))?;
+                self.deserialize_entries(wip, &[entry])?;
+                wip.end().map_err(IniDeError::reflect(line))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn set_map_key<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        key: &str,
+        line: usize,
+    ) -> Result<(), IniDeError<'shape>> {
+        let line = Some(line);
+        wip.begin_key().map_err(IniDeError::reflect(line))?;
+        self.deserialize_value(wip, key, line)?;
+        wip.end().map_err(IniDeError::reflect(line))?;
+        Ok(())
+    }
+
+    /// Deserializes every entry that matched one key.
+    ///
+    /// Lists take one element per entry, and an empty assignment clears the elements
+    /// collected so far, as in systemd units. Anything else takes the last entry.
+    fn deserialize_entries<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        entries: &[&Entry],
+    ) -> Result<(), IniDeError<'shape>> {
+        let Some(last) = entries.last() else {
+            return Ok(());
+        };
+        let line = Some(last.line);
+
+        match wip.shape().def {
+            Def::Option(_) => {
+                wip.begin_some().map_err(IniDeError::reflect(line))?;
+                self.deserialize_entries(wip, entries)?;
+                wip.end().map_err(IniDeError::reflect(line))?;
+            }
+            Def::List(_) => {
+                wip.begin_list().map_err(IniDeError::reflect(line))?;
+                let start = entries
+                    .iter()
+                    .rposition(|e| e.value.is_empty())
+                    .map_or(0, |i| i + 1);
+                for entry in &entries[start..] {
+                    let line = Some(entry.line);
+                    wip.begin_list_item().map_err(IniDeError::reflect(line))?;
+                    self.deserialize_value(wip, &entry.value, line)?;
+                    wip.end().map_err(IniDeError::reflect(line))?;
+                }
+            }
+            _ => self.deserialize_value(wip, &last.value, line)?,
+        }
+        Ok(())
+    }
+
+    fn deserialize_value<'facet, 'shape>(
+        &self,
+        wip: &mut Partial<'facet, 'shape>,
+        value: &str,
+        line: Option<usize>,
+    ) -> Result<(), IniDeError<'shape>> {
+        let shape = wip.shape();
+
+        if let Def::Option(_) = shape.def {
+            wip.begin_some().map_err(IniDeError::reflect(line))?;
+            self.deserialize_value(wip, value, line)?;
+            wip.end().map_err(IniDeError::reflect(line))?;
+            return Ok(());
+        }
+
+        if let Type::User(UserType::Enum(ed)) = shape.ty {
+            let variant = ed
+                .variants
+                .iter()
+                .position(|v| {
+                    v.data.kind == StructKind::Unit && self.options.names_match(value, v.name)
+                })
+                .ok_or_else(|| invalid(value, shape, line))?;
+            wip.select_nth_variant(variant)
+                .map_err(IniDeError::reflect(line))?;
+            return Ok(());
+        }
+
+        match ScalarType::try_from_shape(shape) {
+            Some(ScalarType::Unit) => {
+                wip.set(()).map_err(IniDeError::reflect(line))?;
+            }
+            Some(ScalarType::Bool) => {
+                let b = parse_bool(value).ok_or_else(|| invalid(value, shape, line))?;
+                wip.set(b).map_err(IniDeError::reflect(line))?;
+            }
+            Some(ScalarType::CowStr) => {
+                wip.set(Cow::<'static, str>::Owned(value.to_string()))
+                    .map_err(IniDeError::reflect(line))?;
+            }
+            _ if shape.is_from_str() => {
+                wip.parse_from_str(value)
+                    .map_err(|_| invalid(value, shape, line))?;
+            }
+            _ if shape.inner.is_some() => {
+                wip.begin_inner().map_err(IniDeError::reflect(line))?;
+                self.deserialize_value(wip, value, line)?;
+                wip.end().map_err(IniDeError::reflect(line))?;
+            }
+            _ => {
+                return Err(IniDeError::new(
+                    IniDeErrorKind::UnsupportedShape(shape),
+                    line,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether values of this shape are written as `[sections]` rather than `key = value` lines.
+pub(crate) fn is_section(shape: &Shape<'_>) -> bool {
+    let shape = match shape.def {
+        Def::Option(od) => od.t(),
+        _ => shape,
+    };
+    match shape.def {
+        Def::Map(_) => true,
+        Def::Scalar(_) | Def::List(_) | Def::Option(_) => false,
+        _ => match shape.ty {
+            Type::User(UserType::Struct(sd)) => sd.kind == StructKind::Struct,
+            _ => false,
+        },
+    }
+}
+
+/// Parses the boolean spellings that are common in INI files and systemd units.
+fn parse_bool(value: &str) -> Option<bool> {
+    const TRUE: [&str; 4] = ["true", "yes", "on", "1"];
+    const FALSE: [&str; 4] = ["false", "no", "off", "0"];
+    if TRUE.iter().any(|t| value.eq_ignore_ascii_case(t)) {
+        Some(true)
+    } else if FALSE.iter().any(|f| value.eq_ignore_ascii_case(f)) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn invalid<'shape>(
+    value: &str,
+    shape: &'shape Shape<'shape>,
+    line: Option<usize>,
+) -> IniDeError<'shape> {
+    IniDeError::new(
+        IniDeErrorKind::InvalidValue {
+            value: value.to_string(),
+            shape,
+        },
+        line,
+    )
+}
@@ -0,0 +1,104 @@
+//! Errors from reading and writing INI documents.
+
+use alloc::string::String;
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+
+/// Any error from deserializing INI.
+#[derive(Debug)]
+pub struct IniDeError<'shape> {
+    /// Type of error.
+    pub kind: IniDeErrorKind<'shape>,
+    /// 1-based line of the INI document this error applies to, if known.
+    pub line: Option<usize>,
+}
+
+impl<'shape> IniDeError<'shape> {
+    pub(crate) fn new(kind: IniDeErrorKind<'shape>, line: Option<usize>) -> Self {
+        Self { kind, line }
+    }
+
+    pub(crate) fn reflect(line: Option<usize>) -> impl Fn(ReflectError<'shape>) -> Self {
+        move |e| Self::new(IniDeErrorKind::Reflect(e), line)
+    }
+}
+
+/// Type of deserialization error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum IniDeErrorKind<'shape> {
+    /// The document is not valid INI.
+    Syntax(&'static str),
+    /// A section exists in the document but not in the target type, and unknown fields are denied.
+    UnknownSection(String),
+    /// A key exists in the document but not in the target type, and unknown fields are denied.
+    UnknownKey(String),
+    /// A required field has no matching key or section.
+    MissingField(&'shape str),
+    /// A value couldn't be converted to the target type.
+    InvalidValue {
+        /// The raw value from the document.
+        value: String,
+        /// The type it was supposed to become.
+        shape: &'shape Shape<'shape>,
+    },
+    /// The target type can't be represented in INI.
+    UnsupportedShape(&'shape Shape<'shape>),
+    /// Any error from facet.
+    Reflect(ReflectError<'shape>),
+}
+
+impl core::fmt::Display for IniDeErrorKind<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            IniDeErrorKind::Syntax(msg) => write!(f, "Syntax error: {msg}"),
+            IniDeErrorKind::UnknownSection(name) => write!(f, "Unknown section '{name}'"),
+            IniDeErrorKind::UnknownKey(key) => write!(f, "Unknown key '{key}'"),
+            IniDeErrorKind::MissingField(name) => write!(f, "Missing field '{name}'"),
+            IniDeErrorKind::InvalidValue { value, shape } => {
+                write!(f, "Can't parse '{value}' as {shape}")
+            }
+            IniDeErrorKind::UnsupportedShape(shape) => {
+                write!(f, "Type {shape} can't be deserialized from INI")
+            }
+            IniDeErrorKind::Reflect(e) => write!(f, "Error while reflecting type: {e}"),
+        }
+    }
+}
+
+impl core::fmt::Display for IniDeError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{} at line {}", self.kind, line),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+impl core::error::Error for IniDeError<'_> {}
+
+/// Errors when serializing to INI.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum IniSerError {
+    /// The root value is not a struct or a map.
+    RootNotATable,
+    /// A value of this type can't be written as an INI value.
+    UnsupportedType(String),
+    /// A string contains a newline, which INI values can't hold.
+    MultilineValue(String),
+}
+
+impl core::fmt::Display for IniSerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            IniSerError::RootNotATable => write!(f, "The root value must be a struct or a map"),
+            IniSerError::UnsupportedType(ty) => write!(f, "Unsupported type: {ty}"),
+            IniSerError::MultilineValue(key) => {
+                write!(f, "Value for key '{key}' contains a newline")
+            }
+        }
+    }
+}
+
+impl core::error::Error for IniSerError {}
@@ -0,0 +1,49 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+#![warn(clippy::std_instead_of_core)]
+#![warn(clippy::std_instead_of_alloc)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+#[cfg(not(feature = "alloc"))]
+compile_error!("feature `alloc` is required");
+
+mod error;
+pub use error::*;
+
+mod parse;
+
+mod deserialize;
+pub use deserialize::*;
+
+mod serialize;
+pub use serialize::*;
+
+/// Options controlling how INI documents are matched against Facet shapes.
+#[derive(Debug, Clone, Default)]
+pub struct IniOptions {
+    case_insensitive: bool,
+}
+
+impl IniOptions {
+    /// Create options with the default settings (exact key matching)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Match keys, section names and enum variants ignoring ASCII case
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    fn names_match(&self, ini_name: &str, rust_name: &str) -> bool {
+        if self.case_insensitive {
+            ini_name.eq_ignore_ascii_case(rust_name)
+        } else {
+            ini_name == rust_name
+        }
+    }
+}
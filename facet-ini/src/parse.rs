@@ -0,0 +1,121 @@
+//! Turns INI text into a tree of tables, without looking at any shape.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{IniDeError, IniDeErrorKind};
+
+/// A single `key = value` line.
+pub(crate) struct Entry {
+    pub(crate) key: String,
+    pub(crate) value: String,
+    pub(crate) line: usize,
+}
+
+/// A section, with its own entries and the dotted subsections below it.
+///
+/// The root table holds the entries that appear before the first section header.
+pub(crate) struct Table {
+    pub(crate) name: String,
+    pub(crate) line: usize,
+    pub(crate) entries: Vec<Entry>,
+    pub(crate) children: Vec<Table>,
+}
+
+impl Table {
+    fn new(name: String, line: usize) -> Self {
+        Self {
+            name,
+            line,
+            entries: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Returns the table at `path`, creating every missing table along the way.
+    ///
+    /// Sections that appear more than once are merged, like systemd does for unit files.
+    fn table_at(&mut self, path: &[String], line: usize) -> &mut Table {
+        let mut table = self;
+        for segment in path {
+            let index = match table.children.iter().position(|c| c.name == *segment) {
+                Some(index) => index,
+                None => {
+                    table.children.push(Table::new(segment.clone(), line));
+                    table.children.len() - 1
+                }
+            };
+            table = &mut table.children[index];
+        }
+        table
+    }
+}
+
+/// Parse an INI document.
+///
+/// Supports `;` and `#` comments, `[section]` and `[section.sub]` headers, `key = value`
+/// entries, trailing-backslash line continuations and double-quoted values.
+pub(crate) fn parse(ini: &str) -> Result<Table, IniDeError<'static>> {
+    let mut root = Table::new(String::new(), 0);
+    let mut current: Vec<String> = Vec::new();
+
+    let mut lines = ini.lines().enumerate();
+    while let Some((index, raw)) = lines.next() {
+        let line = index + 1;
+        let trimmed = raw.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with(';') || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = trimmed.strip_prefix('[') {
+            let Some(name) = header.strip_suffix(']') else {
+                return Err(syntax("unterminated section header", line));
+            };
+            let path: Vec<String> = name.split('.').map(|s| s.trim().to_string()).collect();
+            if path.iter().any(|segment| segment.is_empty()) {
+                return Err(syntax("empty section name", line));
+            }
+            root.table_at(&path, line);
+            current = path;
+            continue;
+        }
+
+        let Some((key, value)) = trimmed.split_once('=') else {
+            return Err(syntax("expected `key = value`", line));
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(syntax("missing key before `=`", line));
+        }
+
+        let mut value = value.trim().to_string();
+        while value.ends_with('\\') {
+            value.pop();
+            let Some((_, next)) = lines.next() else {
+                break;
+            };
+            value.truncate(value.trim_end().len());
+            value.push(' ');
+            value.push_str(next.trim());
+        }
+
+        if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+            value = value[1..value.len() - 1].to_string();
+        }
+
+        root.table_at(&current, line).entries.push(Entry {
+            key: key.to_string(),
+            value,
+            line,
+        });
+    }
+
+    Ok(root)
+}
+
+fn syntax(msg: &'static str, line: usize) -> IniDeError<'static> {
+    IniDeError::new(IniDeErrorKind::Syntax(msg), Some(line))
+}
@@ -0,0 +1,137 @@
+//! Write Rust values as INI strings.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+use facet_core::{Def, Facet, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+
+use crate::{IniSerError, deserialize::is_section};
+
+/// Serializes any Facet struct or map to an INI string.
+///
+/// Scalar fields become `key = value` lines, struct and map fields become
+/// `[section]`s (nested ones as `[section.sub]`), lists become one line per element,
+/// and `None` values are left out.
+pub fn to_string<'facet, T: Facet<'facet>>(value: &T) -> Result<String, IniSerError> {
+    let mut out = String::new();
+    write_table(Peek::new(value), &mut Vec::new(), &mut out)?;
+    Ok(out)
+}
+
+fn write_table<'mem, 'facet, 'shape>(
+    peek: Peek<'mem, 'facet, 'shape>,
+    path: &mut Vec<String>,
+    out: &mut String,
+) -> Result<(), IniSerError> {
+    let peek = peek.innermost_peek();
+    let mut sections: Vec<(String, Peek<'mem, 'facet, 'shape>)> = Vec::new();
+
+    if let Def::Map(_) = peek.shape().def {
+        let map = peek.into_map().map_err(|_| IniSerError::RootNotATable)?;
+        for (key, value) in map.iter() {
+            let key = scalar_to_string(key)?;
+            if is_section(value.shape()) {
+                sections.push((key, value));
+            } else {
+                write_entry(&key, value, out)?;
+            }
+        }
+    } else if let Type::User(UserType::Struct(sd)) = peek.shape().ty {
+        if sd.kind != StructKind::Struct {
+            return Err(IniSerError::RootNotATable);
+        }
+        let ps = peek.into_struct().map_err(|_| IniSerError::RootNotATable)?;
+        for (field, value) in ps.fields_for_serialize() {
+            if is_section(value.shape()) {
+                sections.push((field.name.to_string(), value));
+            } else {
+                write_entry(field.name, value, out)?;
+            }
+        }
+    } else {
+        return Err(IniSerError::RootNotATable);
+    }
+
+    for (name, value) in sections {
+        let value = match value.into_option() {
+            Ok(opt) => match opt.value() {
+                Some(inner) => inner,
+                None => continue,
+            },
+            Err(_) => value,
+        };
+        path.push(name);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        writeln!(out, "[{}]", path.join(".")).unwrap();
+        write_table(value, path, out)?;
+        path.pop();
+    }
+    Ok(())
+}
+
+fn write_entry(key: &str, value: Peek<'_, '_, '_>, out: &mut String) -> Result<(), IniSerError> {
+    if let Def::Option(_) = value.shape().def {
+        let opt = value
+            .into_option()
+            .map_err(|_| IniSerError::UnsupportedType(format!("{}", value.shape())))?;
+        return match opt.value() {
+            Some(inner) => write_entry(key, inner, out),
+            None => Ok(()),
+        };
+    }
+
+    if let Def::List(_) | Def::Array(_) | Def::Slice(_) = value.shape().def {
+        let list = value
+            .into_list_like()
+            .map_err(|_| IniSerError::UnsupportedType(format!("{}", value.shape())))?;
+        for item in list.iter() {
+            write_entry(key, item, out)?;
+        }
+        return Ok(());
+    }
+
+    let s = scalar_to_string(value)?;
+    if s.contains(['\n', '\r']) {
+        return Err(IniSerError::MultilineValue(key.to_string()));
+    }
+    if s.trim() != s || s.starts_with('"') {
+        writeln!(out, "{key} = \"{s}\"").unwrap();
+    } else {
+        writeln!(out, "{key} = {s}").unwrap();
+    }
+    Ok(())
+}
+
+fn scalar_to_string(peek: Peek<'_, '_, '_>) -> Result<String, IniSerError> {
+    let peek = peek.innermost_peek();
+
+    if let Type::User(UserType::Enum(_)) = peek.shape().ty {
+        let pe = peek
+            .into_enum()
+            .map_err(|_| IniSerError::UnsupportedType(format!("{}", peek.shape())))?;
+        let variant = pe
+            .active_variant()
+            .map_err(|_| IniSerError::UnsupportedType(format!("{}", peek.shape())))?;
+        if variant.data.kind != StructKind::Unit {
+            return Err(IniSerError::UnsupportedType(format!("{}", peek.shape())));
+        }
+        return Ok(variant.name.to_string());
+    }
+
+    if let Some(s) = peek.as_str() {
+        return Ok(s.to_string());
+    }
+    if matches!(peek.scalar_type(), Some(ScalarType::Unit)) {
+        return Ok(String::new());
+    }
+    if let Some(display) = peek.display() {
+        return Ok(display.to_string());
+    }
+    Err(IniSerError::UnsupportedType(format!("{}", peek.shape())))
+}
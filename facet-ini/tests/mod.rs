@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_ini::{IniDeErrorKind, IniOptions, from_str, from_str_with_options, to_string};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+    tls: Option<Tls>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Tls {
+    cert: String,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+enum Level {
+    Debug,
+    Info,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Config {
+    name: String,
+    verbose: bool,
+    level: Level,
+    tags: Vec<String>,
+    server: Server,
+}
+
+const CONFIG: &str = r#"
+; global settings
+name = demo
+verbose = yes
+level = Info
+tags = a
+tags = b
+
+# the server
+[server]
+host = "  localhost "
+port = 8080
+
+[server.tls]
+cert = /etc/ssl/cert.pem
+"#;
+
+fn config() -> Config {
+    Config {
+        name: "demo".to_string(),
+        verbose: true,
+        level: Level::Info,
+        tags: vec!["a".to_string(), "b".to_string()],
+        server: Server {
+            host: "  localhost ".to_string(),
+            port: 8080,
+            tls: Some(Tls {
+                cert: "/etc/ssl/cert.pem".to_string(),
+            }),
+        },
+    }
+}
+
+#[test]
+fn test_deserialize_sections() {
+    let parsed: Config = from_str(CONFIG)?;
+    assert_eq!(parsed, config());
+}
+
+#[test]
+fn test_roundtrip() {
+    let ini = to_string(&config())?;
+    let parsed: Config = from_str(&ini)?;
+    assert_eq!(parsed, config());
+
+    let debug = Config {
+        level: Level::Debug,
+        ..config()
+    };
+    let ini = to_string(&debug)?;
+    assert_eq!(from_str::<Config>(&ini)?, debug);
+}
+
+#[test]
+fn test_missing_optional_section_and_empty_list() {
+    let parsed: Config =
+        from_str("name = x\nverbose = off\nlevel = Debug\n[server]\nhost = h\nport = 1\n")?;
+    assert!(parsed.tags.is_empty());
+    assert_eq!(parsed.server.tls, None);
+}
+
+#[test]
+fn test_empty_assignment_resets_list() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Service {
+        #[facet(rename = "ExecStart")]
+        exec_start: Vec<String>,
+    }
+
+    let parsed: Service = from_str("ExecStart = /bin/a\nExecStart =\nExecStart = /bin/b\n")?;
+    assert_eq!(parsed.exec_start, vec!["/bin/b".to_string()]);
+}
+
+#[test]
+fn test_line_continuation() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Unit {
+        description: String,
+    }
+
+    let parsed: Unit = from_str("description = a long \\\n    description\n")?;
+    assert_eq!(parsed.description, "a long description");
+}
+
+#[test]
+fn test_case_insensitive_keys() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Unit {
+        #[facet(rename = "Description")]
+        description: String,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct UnitFile {
+        #[facet(rename = "Unit")]
+        unit: Unit,
+    }
+
+    let ini = "[UNIT]\ndescription = hello\n";
+    assert!(from_str::<UnitFile>(ini).is_err());
+
+    let options = IniOptions::new().with_case_insensitive(true);
+    let parsed: UnitFile = from_str_with_options(ini, &options)?;
+    assert_eq!(parsed.unit.description, "hello");
+}
+
+#[test]
+fn test_section_as_map() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Remote {
+        url: String,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct GitConfig {
+        core: HashMap<String, String>,
+        remote: HashMap<String, Remote>,
+    }
+
+    let parsed: GitConfig =
+        from_str("[core]\nbare = false\n[remote.origin]\nurl = https://example.com/repo.git\n")?;
+    assert_eq!(parsed.core["bare"], "false");
+    assert_eq!(parsed.remote["origin"].url, "https://example.com/repo.git");
+}
+
+#[test]
+fn test_deny_unknown_fields() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[facet(deny_unknown_fields)]
+    struct Strict {
+        a: u32,
+    }
+
+    let err = from_str::<Strict>("a = 1\nb = 2\n").unwrap_err();
+    assert!(matches!(err.kind, IniDeErrorKind::UnknownKey(ref key) if key == "b"));
+    assert_eq!(err.line, Some(2));
+}
+
+#[test]
+fn test_errors_have_line_numbers() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Numbers {
+        a: u32,
+    }
+
+    let err = from_str::<Numbers>("\na = nope\n").unwrap_err();
+    assert!(matches!(err.kind, IniDeErrorKind::InvalidValue { .. }));
+    assert_eq!(err.line, Some(2));
+
+    let err = from_str::<Numbers>("[broken\n").unwrap_err();
+    assert!(matches!(err.kind, IniDeErrorKind::Syntax(_)));
+    assert_eq!(err.line, Some(1));
+
+    let err = from_str::<Numbers>("").unwrap_err();
+    assert!(matches!(err.kind, IniDeErrorKind::MissingField("a")));
+}
@@ -1,22 +1,32 @@
 use alloc::format;
+use alloc::sync::Arc;
 
 use facet_core::Facet;
 use facet_deserialize::{
-    Cooked, Expectation, Format, NextData, NextResult, Outcome, Scalar, Span, Spannable, Spanned,
+    Cooked, Events, Expectation, Format, NextData, NextResult, Outcome, Scalar, Scratch, Span,
+    Spannable, Spanned, TrailingInput,
 };
-pub use facet_deserialize::{DeserError, DeserErrorKind};
+pub use facet_deserialize::{
+    DeserError, DeserErrorKind, DeserObserver, Event, FieldSpan, FieldSpanSink, IgnoredField,
+    IgnoredFieldSink, Interner, Projection, TranscodeError,
+};
+use facet_reflect::{HeapValue, Partial};
+use facet_serialize::Serializer;
 use log::trace;
 
 use crate::tokenizer::{Token, TokenError, TokenErrorKind, Tokenizer};
 
 /// Deserialize JSON from a given byte slice
+///
+/// Only whitespace may follow the value: to read a value followed by something else, use
+/// [`from_slice_partial`].
 pub fn from_slice<'input, 'facet, 'shape, T: Facet<'facet>>(
     input: &'input [u8],
 ) -> Result<T, DeserError<'input, 'shape>>
 where
     'input: 'facet,
 {
-    facet_deserialize::deserialize(input, crate::Json)
+    facet_deserialize::deserialize(input, crate::Json::default())
 }
 
 /// Deserialize JSON from a UTF-8 string slice
@@ -29,6 +39,379 @@ where
     from_slice(input.as_bytes())
 }
 
+/// Deserialize the JSON value at the start of a byte slice, returning it and the number of
+/// bytes it took. Unlike [`from_slice`], which fails if anything but whitespace follows the
+/// value, this leaves the rest of the input unread.
+///
+/// ```
+/// let input = br#"{"id": 1} {"id": 2}"#;
+/// let (first, len): (std::collections::HashMap<String, u32>, usize) =
+///     facet_json::from_slice_partial(input).unwrap();
+/// assert_eq!(first["id"], 1);
+/// assert_eq!(&input[len..], br#" {"id": 2}"#);
+/// ```
+pub fn from_slice_partial<'input, 'facet, 'shape, T: Facet<'facet>>(
+    input: &'input [u8],
+) -> Result<(T, usize), DeserError<'input, 'shape>>
+where
+    'input: 'facet,
+{
+    facet_deserialize::deserialize_with_trailing(
+        input,
+        crate::Json::default(),
+        &mut Scratch::new(),
+        TrailingInput::AllowTrailing,
+    )
+}
+
+/// Deserialize the JSON value at the start of a UTF-8 string slice, returning it and the
+/// number of bytes it took. See [`from_slice_partial`].
+pub fn from_str_partial<'input, 'facet, 'shape, T: Facet<'facet>>(
+    input: &'input str,
+) -> Result<(T, usize), DeserError<'input, 'shape>>
+where
+    'input: 'facet,
+{
+    from_slice_partial(input.as_bytes())
+}
+
+/// Update `target` from JSON in a given byte slice, leaving out what the JSON doesn't mention
+///
+/// This works like a JSON merge patch: fields missing from the JSON keep their current
+/// values, and nested objects update nested structs field by field. Lists, maps and enums
+/// that appear in the JSON replace the old ones. `T` must implement `Clone`, and `target` is
+/// left untouched if deserialization fails.
+///
+/// ```
+/// # use facet::Facet;
+/// #[derive(Facet, Clone, Debug, PartialEq)]
+/// struct Settings {
+///     theme: String,
+///     font_size: u32,
+/// }
+///
+/// let mut settings = Settings { theme: "light".into(), font_size: 12 };
+/// facet_json::from_str_into(&mut settings, r#"{"font_size": 14}"#).unwrap();
+/// assert_eq!(settings, Settings { theme: "light".into(), font_size: 14 });
+/// ```
+pub fn from_slice_into<'input, 'facet, 'shape, T: Facet<'facet>>(
+    target: &mut T,
+    input: &'input [u8],
+) -> Result<(), DeserError<'input, 'shape>>
+where
+    'input: 'facet,
+{
+    facet_deserialize::deserialize_into(target, input, crate::Json::default())
+}
+
+/// Update `target` from JSON in a UTF-8 string slice. See [`from_slice_into`].
+pub fn from_str_into<'input, 'facet, 'shape, T: Facet<'facet>>(
+    target: &mut T,
+    input: &'input str,
+) -> Result<(), DeserError<'input, 'shape>>
+where
+    'input: 'facet,
+{
+    from_slice_into(target, input.as_bytes())
+}
+
+/// Deserialize JSON from a byte slice into a value of `wip`'s shape, for when the type is
+/// only known at runtime.
+pub fn from_slice_wip<'input, 'facet, 'shape>(
+    input: &'input [u8],
+    wip: Partial<'facet, 'shape>,
+) -> Result<HeapValue<'facet, 'shape>, DeserError<'input, 'shape>>
+where
+    'input: 'facet,
+    'shape: 'input,
+{
+    facet_deserialize::deserialize_wip(wip, input, &mut crate::Json::default())
+}
+
+/// A JSON deserializer that keeps its buffers between documents.
+///
+/// [`from_str`] and [`from_slice`] start from nothing every time. Services that deserialize
+/// many documents can keep a parser around instead, which saves a few allocations per document.
+///
+/// ```
+/// # use facet::Facet;
+/// # use facet_json::JsonParser;
+/// #[derive(Facet)]
+/// struct Event {
+///     id: u32,
+/// }
+///
+/// let mut parser = JsonParser::new();
+/// for line in [r#"{"id": 1}"#, r#"{"id": 2}"#] {
+///     let event: Event = parser.parse(line).unwrap();
+///     # let _ = event.id;
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct JsonParser {
+    scratch: Scratch,
+    shared_refs: bool,
+}
+
+impl JsonParser {
+    /// Creates a parser with empty buffers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads `{"$ref": n}` in place of an `Arc` as a clone of the `n`th `Arc` read so far,
+    /// so that values shared when they were written with
+    /// [`JsonSerializer::with_shared_refs`](crate::JsonSerializer::with_shared_refs) are
+    /// shared again.
+    pub fn with_shared_refs(mut self, shared_refs: bool) -> Self {
+        self.shared_refs = shared_refs;
+        self
+    }
+
+    /// Reads `Arc<str>` fields and map keys through `interner`, so that the strings repeated
+    /// across the documents read by this parser share one allocation.
+    ///
+    /// ```
+    /// # use facet::Facet;
+    /// # use facet_json::JsonParser;
+    /// # use std::sync::Arc;
+    /// use facet_deserialize::StringInterner;
+    ///
+    /// #[derive(Facet)]
+    /// struct Log {
+    ///     level: Arc<str>,
+    /// }
+    ///
+    /// let mut parser = JsonParser::new().with_interner(Arc::new(StringInterner::new()));
+    /// let first: Log = parser.parse(r#"{"level": "info"}"#).unwrap();
+    /// let second: Log = parser.parse(r#"{"level": "info"}"#).unwrap();
+    /// assert!(Arc::ptr_eq(&first.level, &second.level));
+    /// ```
+    pub fn with_interner(mut self, interner: Arc<dyn Interner>) -> Self {
+        self.scratch = core::mem::take(&mut self.scratch).with_interner(interner);
+        self
+    }
+
+    /// Tells `sink` about every unknown field skipped in the documents read by this parser,
+    /// to keep track of fields that the other side sends but this side doesn't know about.
+    ///
+    /// ```
+    /// # use facet::Facet;
+    /// # use facet_json::JsonParser;
+    /// # use std::sync::Arc;
+    /// use facet_deserialize::IgnoredFieldLog;
+    ///
+    /// #[derive(Facet)]
+    /// struct Ping {
+    ///     id: u32,
+    /// }
+    ///
+    /// let log = Arc::new(IgnoredFieldLog::new());
+    /// let mut parser = JsonParser::new().with_ignored_fields(log.clone());
+    /// let ping: Ping = parser.parse(r#"{"id": 1, "sent_at": 1700000000}"#).unwrap();
+    /// # let _ = ping.id;
+    /// assert_eq!(log.take()[0].path, "Ping.sent_at");
+    /// ```
+    pub fn with_ignored_fields(mut self, sink: Arc<dyn IgnoredFieldSink>) -> Self {
+        self.scratch = core::mem::take(&mut self.scratch).with_ignored_fields(sink);
+        self
+    }
+
+    /// Tells `sink` where the value of every field set by the documents read by this parser
+    /// is, so a value that turns out to be wrong later can be traced back to the input.
+    ///
+    /// ```
+    /// # use facet::Facet;
+    /// # use facet_json::JsonParser;
+    /// # use std::sync::Arc;
+    /// use facet_deserialize::SpanTable;
+    ///
+    /// #[derive(Facet)]
+    /// struct Limits {
+    ///     max_connections: u32,
+    /// }
+    ///
+    /// let input = "{\n  \"max_connections\": 0\n}";
+    /// let spans = Arc::new(SpanTable::new().with_source("limits.json"));
+    /// let mut parser = JsonParser::new().with_field_spans(spans.clone());
+    /// let limits: Limits = parser.parse(input).unwrap();
+    /// assert_eq!(limits.max_connections, 0);
+    ///
+    /// let location = spans.locate("Limits.max_connections", input).unwrap();
+    /// assert_eq!(location.to_string(), "limits.json:2:22");
+    /// ```
+    pub fn with_field_spans(mut self, sink: Arc<dyn FieldSpanSink>) -> Self {
+        self.scratch = core::mem::take(&mut self.scratch).with_field_spans(sink);
+        self
+    }
+
+    /// Tells `observer` about each step taken to read the documents read by this parser,
+    /// instead of logging them, to record or inspect what the deserializer does.
+    ///
+    /// ```
+    /// # use facet_json::JsonParser;
+    /// # use std::sync::Arc;
+    /// use facet_deserialize::{Outcome, Scalar, TraceEvent, TraceLog};
+    ///
+    /// let trace = Arc::new(TraceLog::new());
+    /// let mut parser = JsonParser::new().with_observer(trace.clone());
+    /// let ports: Vec<u16> = parser.parse("[80]").unwrap();
+    /// assert_eq!(ports, [80]);
+    ///
+    /// let outcomes: Vec<Outcome> = trace
+    ///     .take()
+    ///     .into_iter()
+    ///     .filter_map(|event| match event {
+    ///         TraceEvent::Outcome { outcome, .. } => Some(outcome),
+    ///         _ => None,
+    ///     })
+    ///     .collect();
+    /// assert_eq!(
+    ///     outcomes,
+    ///     [
+    ///         Outcome::ListStarted,
+    ///         Outcome::Scalar(Scalar::U64(80)),
+    ///         Outcome::ListEnded
+    ///     ]
+    /// );
+    /// ```
+    pub fn with_observer(mut self, observer: Arc<dyn DeserObserver>) -> Self {
+        self.scratch = core::mem::take(&mut self.scratch).with_observer(observer);
+        self
+    }
+
+    /// Only reads the fields that `projection` selects from the documents read by this
+    /// parser. The others are skipped without being built, and left to their default.
+    ///
+    /// ```
+    /// # use facet::Facet;
+    /// # use facet_json::{JsonParser, Projection};
+    /// # use std::sync::Arc;
+    /// #[derive(Facet, Default)]
+    /// struct Metadata {
+    ///     version: u32,
+    ///     author: String,
+    /// }
+    ///
+    /// #[derive(Facet)]
+    /// struct Catalog {
+    ///     metadata: Metadata,
+    ///     entries: Vec<String>,
+    /// }
+    ///
+    /// let projection = Arc::new(Projection::new(["metadata.version"]));
+    /// let mut parser = JsonParser::new().with_projection(projection);
+    /// let json = r#"{"entries": ["a", "b"], "metadata": {"author": "jo", "version": 3}}"#;
+    /// let catalog: Catalog = parser.parse(json).unwrap();
+    /// assert_eq!(catalog.metadata.version, 3);
+    /// assert!(catalog.metadata.author.is_empty());
+    /// assert!(catalog.entries.is_empty());
+    /// ```
+    pub fn with_projection(mut self, projection: Arc<Projection>) -> Self {
+        self.scratch = core::mem::take(&mut self.scratch).with_projection(projection);
+        self
+    }
+
+    /// Deserialize JSON from a UTF-8 string slice
+    pub fn parse<'input, 'facet, 'shape, T: Facet<'facet>>(
+        &mut self,
+        input: &'input str,
+    ) -> Result<T, DeserError<'input, 'shape>>
+    where
+        'input: 'facet,
+    {
+        self.parse_slice(input.as_bytes())
+    }
+
+    /// Deserialize JSON from a given byte slice
+    pub fn parse_slice<'input, 'facet, 'shape, T: Facet<'facet>>(
+        &mut self,
+        input: &'input [u8],
+    ) -> Result<T, DeserError<'input, 'shape>>
+    where
+        'input: 'facet,
+    {
+        let format = crate::Json {
+            shared_refs: self.shared_refs,
+        };
+        facet_deserialize::deserialize_with(input, format, &mut self.scratch)
+    }
+
+    /// Deserialize the JSON value at the start of a UTF-8 string slice, returning it and the
+    /// number of bytes it took. See [`from_slice_partial`].
+    pub fn parse_partial<'input, 'facet, 'shape, T: Facet<'facet>>(
+        &mut self,
+        input: &'input str,
+    ) -> Result<(T, usize), DeserError<'input, 'shape>>
+    where
+        'input: 'facet,
+    {
+        self.parse_slice_partial(input.as_bytes())
+    }
+
+    /// Deserialize the JSON value at the start of a byte slice, returning it and the number of
+    /// bytes it took. See [`from_slice_partial`].
+    pub fn parse_slice_partial<'input, 'facet, 'shape, T: Facet<'facet>>(
+        &mut self,
+        input: &'input [u8],
+    ) -> Result<(T, usize), DeserError<'input, 'shape>>
+    where
+        'input: 'facet,
+    {
+        let format = crate::Json {
+            shared_refs: self.shared_refs,
+        };
+        facet_deserialize::deserialize_with_trailing(
+            input,
+            format,
+            &mut self.scratch,
+            TrailingInput::AllowTrailing,
+        )
+    }
+}
+
+/// Converts JSON into another format, without deserializing it into a Rust type.
+///
+/// Objects are written as maps with string keys, and `null` as `None`. See
+/// [`facet_deserialize::transcode`].
+pub fn transcode<'input, 'shape, S: Serializer<'shape>>(
+    input: &'input [u8],
+    serializer: &mut S,
+) -> Result<(), TranscodeError<'input, 'shape, S::Error>>
+where
+    'shape: 'input,
+{
+    facet_deserialize::transcode(input, crate::Json::default(), serializer)
+}
+
+/// Reads the structure of a JSON value as a stream of [`Event`]s, without
+/// deserializing it into a Rust type.
+///
+/// Every event carries the span of the input it was read from. Iteration ends
+/// after the value's last event, or after the first error. See
+/// [`facet_deserialize::Events`].
+pub struct JsonEvents<'input, 'shape>(Events<'input, 'shape, crate::Json>);
+
+impl<'input, 'shape> JsonEvents<'input, 'shape> {
+    /// Starts reading a JSON value from `input`.
+    pub fn new(input: &'input [u8]) -> Self {
+        Self(Events::new(input, crate::Json::default()))
+    }
+
+    /// The number of arrays and objects that are open at this point of the input.
+    pub fn depth(&self) -> usize {
+        self.0.depth()
+    }
+}
+
+impl<'input, 'shape> Iterator for JsonEvents<'input, 'shape> {
+    type Item = Result<Spanned<Event<'input>>, DeserError<'input, 'shape>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
 impl Format for crate::Json {
     type Input<'input> = [u8];
     type SpanType = Cooked;
@@ -37,6 +420,19 @@ impl Format for crate::Json {
         "json"
     }
 
+    fn shared_refs(&self) -> bool {
+        self.shared_refs
+    }
+
+    fn trailing(&self, input: &[u8], offset: usize) -> Option<Span> {
+        let start = offset
+            + input[offset..]
+                .iter()
+                .take_while(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+                .count();
+        (start < input.len()).then(|| Span::new(start, input.len() - start))
+    }
+
     fn next<'input, 'facet, 'shape>(
         &mut self,
         nd: NextData<'input, 'facet, 'shape>,
@@ -235,6 +631,16 @@ impl Format for crate::Json {
                                 depth -= 1;
                                 last_span = token.span;
                             }
+                            Token::Eof => {
+                                let span = Span::new(nd.start() + token.span.start(), 0);
+                                return (
+                                    nd,
+                                    Err(DeserErrorKind::UnexpectedEof {
+                                        wanted: "end of the skipped object or array",
+                                    }
+                                    .with_span(span)),
+                                );
+                            }
                             _ => {
                                 last_span = token.span;
                             }
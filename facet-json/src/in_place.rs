@@ -0,0 +1,432 @@
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::str::FromStr;
+
+use facet_core::{Def, Facet, Shape, StructKind, Type, UserType};
+use facet_reflect::{ReflectError, ScalarType, Slot};
+
+/// Deserialize JSON into `storage`, without allocating.
+///
+/// This works without the `alloc` feature, for plain data only: structs, tuples and arrays of
+/// numbers, `bool`, `char`, `()` and `&str` (see [`Slot`]), nested at most 8 levels deep.
+/// Strings are borrowed from the input, so they can't contain escape sequences. Unknown
+/// object keys are skipped, unless the struct denies them.
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use facet::Facet;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Reading<'a> {
+///     sensor: &'a str,
+///     values: [i16; 3],
+/// }
+///
+/// let json = br#"{"sensor": "t1", "values": [12, -3, 7]}"#;
+/// let mut storage = MaybeUninit::<Reading>::uninit();
+/// let reading = facet_json::from_slice_in_place(json, &mut storage).unwrap();
+/// assert_eq!(reading.values, [12, -3, 7]);
+/// ```
+pub fn from_slice_in_place<'input, 'mem, T: Facet<'input>>(
+    input: &'input [u8],
+    storage: &'mem mut MaybeUninit<T>,
+) -> Result<&'mem mut T, InPlaceError<'input>> {
+    let mut reader = Reader { input, pos: 0 };
+    let mut slot = Slot::<T>::new(storage).map_err(|e| reader.error(e.into()))?;
+    reader.value(&mut slot)?;
+    reader.skip_whitespace();
+    if reader.pos != input.len() {
+        return Err(reader.error(InPlaceErrorKind::TrailingData));
+    }
+    slot.build().map_err(|e| reader.error(e.into()))
+}
+
+/// Deserialize JSON from a UTF-8 string slice into `storage`, without allocating. See
+/// [`from_slice_in_place`].
+pub fn from_str_in_place<'input, 'mem, T: Facet<'input>>(
+    input: &'input str,
+    storage: &'mem mut MaybeUninit<T>,
+) -> Result<&'mem mut T, InPlaceError<'input>> {
+    from_slice_in_place(input.as_bytes(), storage)
+}
+
+/// An error from [`from_slice_in_place`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InPlaceError<'input> {
+    /// What went wrong
+    pub kind: InPlaceErrorKind<'input>,
+    /// Byte offset in the input where it went wrong
+    pub offset: usize,
+}
+
+/// The kind of an [`InPlaceError`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum InPlaceErrorKind<'input> {
+    /// The type can't be built in place, or the input doesn't fit it.
+    Reflect(ReflectError<'static>),
+    /// The input ended in the middle of a value.
+    UnexpectedEof,
+    /// Got a byte that doesn't fit here.
+    UnexpectedByte {
+        /// The byte we got
+        got: u8,
+        /// What we wanted instead
+        wanted: &'static str,
+    },
+    /// A string contains an escape sequence, so it can't be borrowed from the input.
+    EscapedString,
+    /// A string isn't valid UTF-8.
+    InvalidUtf8,
+    /// A number doesn't fit in the type it's read into.
+    InvalidNumber(&'input str),
+    /// An array has more or fewer elements than the Rust array.
+    WrongLength {
+        /// The length of the Rust array
+        expected: usize,
+    },
+    /// An object has a key that isn't a field, and its struct has `deny_unknown_fields`.
+    UnknownField(&'input str),
+    /// There's more than whitespace after the value.
+    TrailingData,
+}
+
+impl From<ReflectError<'static>> for InPlaceErrorKind<'_> {
+    fn from(err: ReflectError<'static>) -> Self {
+        InPlaceErrorKind::Reflect(err)
+    }
+}
+
+impl fmt::Display for InPlaceError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            InPlaceErrorKind::Reflect(err) => write!(f, "{err}")?,
+            InPlaceErrorKind::UnexpectedEof => write!(f, "Unexpected end of input")?,
+            InPlaceErrorKind::UnexpectedByte { got, wanted } => {
+                write!(f, "Unexpected {:?}, wanted {wanted}", *got as char)?
+            }
+            InPlaceErrorKind::EscapedString => {
+                write!(f, "Strings with escapes can't be borrowed from the input")?
+            }
+            InPlaceErrorKind::InvalidUtf8 => write!(f, "Invalid UTF-8")?,
+            InPlaceErrorKind::InvalidNumber(number) => write!(f, "Invalid number {number}")?,
+            InPlaceErrorKind::WrongLength { expected } => {
+                write!(f, "Expected an array of {expected} elements")?
+            }
+            InPlaceErrorKind::UnknownField(name) => write!(f, "Unknown field {name:?}")?,
+            InPlaceErrorKind::TrailingData => write!(f, "Trailing data after the value")?,
+        }
+        write!(f, " at offset {}", self.offset)
+    }
+}
+
+impl core::error::Error for InPlaceError<'_> {}
+
+struct Reader<'input> {
+    input: &'input [u8],
+    pos: usize,
+}
+
+impl<'input> Reader<'input> {
+    fn error(&self, kind: InPlaceErrorKind<'input>) -> InPlaceError<'input> {
+        InPlaceError {
+            kind,
+            offset: self.pos,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.input.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    /// Returns the next byte that isn't whitespace, without consuming it.
+    fn peek(&mut self) -> Result<u8, InPlaceError<'input>> {
+        self.skip_whitespace();
+        match self.input.get(self.pos) {
+            Some(&byte) => Ok(byte),
+            None => Err(self.error(InPlaceErrorKind::UnexpectedEof)),
+        }
+    }
+
+    fn expect(&mut self, byte: u8, wanted: &'static str) -> Result<(), InPlaceError<'input>> {
+        let got = self.peek()?;
+        if got != byte {
+            return Err(self.error(InPlaceErrorKind::UnexpectedByte { got, wanted }));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Consumes `byte` if it's next.
+    fn eat(&mut self, byte: u8) -> Result<bool, InPlaceError<'input>> {
+        let found = self.peek()? == byte;
+        if found {
+            self.pos += 1;
+        }
+        Ok(found)
+    }
+
+    fn literal(&mut self, literal: &'static str) -> Result<(), InPlaceError<'input>> {
+        self.skip_whitespace();
+        if self.input[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(match self.input.get(self.pos) {
+                Some(&got) => InPlaceErrorKind::UnexpectedByte {
+                    got,
+                    wanted: literal,
+                },
+                None => InPlaceErrorKind::UnexpectedEof,
+            }))
+        }
+    }
+
+    fn string(&mut self) -> Result<&'input str, InPlaceError<'input>> {
+        self.expect(b'"', "a string")?;
+        let start = self.pos;
+        loop {
+            match self.input.get(self.pos) {
+                None => return Err(self.error(InPlaceErrorKind::UnexpectedEof)),
+                Some(b'"') => break,
+                Some(b'\\') => return Err(self.error(InPlaceErrorKind::EscapedString)),
+                Some(_) => self.pos += 1,
+            }
+        }
+        let s = core::str::from_utf8(&self.input[start..self.pos]).map_err(|e| InPlaceError {
+            kind: InPlaceErrorKind::InvalidUtf8,
+            offset: start + e.valid_up_to(),
+        })?;
+        self.pos += 1;
+        Ok(s)
+    }
+
+    fn number<N: FromStr>(&mut self) -> Result<N, InPlaceError<'input>> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while let Some(b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E') = self.input.get(self.pos) {
+            self.pos += 1;
+        }
+        // Only ASCII was consumed
+        let number = core::str::from_utf8(&self.input[start..self.pos]).unwrap();
+        if number.is_empty() {
+            self.peek()?;
+            return Err(self.error(InPlaceErrorKind::UnexpectedByte {
+                got: self.input[self.pos],
+                wanted: "a number",
+            }));
+        }
+        number.parse().map_err(|_| InPlaceError {
+            kind: InPlaceErrorKind::InvalidNumber(number),
+            offset: start,
+        })
+    }
+
+    /// Skips over one value of any kind.
+    fn skip_value(&mut self) -> Result<(), InPlaceError<'input>> {
+        let mut depth = 0usize;
+        loop {
+            match self.peek()? {
+                b'{' | b'[' => {
+                    depth += 1;
+                    self.pos += 1;
+                }
+                b'}' | b']' if depth > 0 => {
+                    depth -= 1;
+                    self.pos += 1;
+                }
+                b',' | b':' if depth > 0 => self.pos += 1,
+                b'"' => {
+                    self.pos += 1;
+                    loop {
+                        match self.input.get(self.pos) {
+                            None => return Err(self.error(InPlaceErrorKind::UnexpectedEof)),
+                            Some(b'"') => break,
+                            Some(b'\\') => self.pos += 2,
+                            Some(_) => self.pos += 1,
+                        }
+                    }
+                    self.pos += 1;
+                }
+                b't' => self.literal("true")?,
+                b'f' => self.literal("false")?,
+                b'n' => self.literal("null")?,
+                _ => {
+                    self.number::<f64>()?;
+                }
+            }
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    fn value<'facet, T: Facet<'facet>>(
+        &mut self,
+        slot: &mut Slot<'_, 'facet, T>,
+    ) -> Result<(), InPlaceError<'input>>
+    where
+        'input: 'facet,
+    {
+        let shape = slot.shape();
+        if let Some(scalar) = ScalarType::try_from_shape(shape) {
+            return self.scalar(slot, scalar);
+        }
+        if let Def::Array(def) = shape.def {
+            return self.array(slot, def.n);
+        }
+        if let Type::User(UserType::Struct(st)) = shape.ty {
+            return match st.kind {
+                _ if shape.inner.is_some() && st.fields.len() == 1 => {
+                    self.field(slot, 0)?;
+                    Ok(())
+                }
+                StructKind::Tuple | StructKind::TupleStruct => self.array(slot, st.fields.len()),
+                StructKind::Unit => self.literal("null"),
+                _ => self.object(slot, shape),
+            };
+        }
+        // `Slot::new` only accepts the shapes above
+        unreachable!()
+    }
+
+    fn scalar<'facet, T: Facet<'facet>>(
+        &mut self,
+        slot: &mut Slot<'_, 'facet, T>,
+        scalar: ScalarType,
+    ) -> Result<(), InPlaceError<'input>>
+    where
+        'input: 'facet,
+    {
+        let res = match scalar {
+            ScalarType::Unit => {
+                self.literal("null")?;
+                slot.set(())
+            }
+            ScalarType::Bool => {
+                let value = self.peek()? == b't';
+                self.literal(if value { "true" } else { "false" })?;
+                slot.set(value)
+            }
+            ScalarType::Char => {
+                let start = self.pos;
+                let s = self.string()?;
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => slot.set(c),
+                    _ => {
+                        return Err(InPlaceError {
+                            kind: InPlaceErrorKind::UnexpectedByte {
+                                got: b'"',
+                                wanted: "a string of one character",
+                            },
+                            offset: start,
+                        });
+                    }
+                }
+            }
+            ScalarType::Str => {
+                let s = self.string()?;
+                slot.set(s)
+            }
+            ScalarType::F32 => slot.set(self.number::<f32>()?),
+            ScalarType::F64 => slot.set(self.number::<f64>()?),
+            ScalarType::U8 => slot.set(self.number::<u8>()?),
+            ScalarType::U16 => slot.set(self.number::<u16>()?),
+            ScalarType::U32 => slot.set(self.number::<u32>()?),
+            ScalarType::U64 => slot.set(self.number::<u64>()?),
+            ScalarType::U128 => slot.set(self.number::<u128>()?),
+            ScalarType::USize => slot.set(self.number::<usize>()?),
+            ScalarType::I8 => slot.set(self.number::<i8>()?),
+            ScalarType::I16 => slot.set(self.number::<i16>()?),
+            ScalarType::I32 => slot.set(self.number::<i32>()?),
+            ScalarType::I64 => slot.set(self.number::<i64>()?),
+            ScalarType::I128 => slot.set(self.number::<i128>()?),
+            ScalarType::ISize => slot.set(self.number::<isize>()?),
+            // `Slot::new` rejects every other scalar
+            _ => unreachable!(),
+        };
+        res.map_err(|e| self.error(e.into()))?;
+        Ok(())
+    }
+
+    fn field<'facet, T: Facet<'facet>>(
+        &mut self,
+        slot: &mut Slot<'_, 'facet, T>,
+        index: usize,
+    ) -> Result<(), InPlaceError<'input>>
+    where
+        'input: 'facet,
+    {
+        slot.begin_nth_field(index)
+            .map_err(|e| self.error(e.into()))?;
+        self.value(slot)?;
+        slot.end().map_err(|e| self.error(e.into()))?;
+        Ok(())
+    }
+
+    fn array<'facet, T: Facet<'facet>>(
+        &mut self,
+        slot: &mut Slot<'_, 'facet, T>,
+        len: usize,
+    ) -> Result<(), InPlaceError<'input>>
+    where
+        'input: 'facet,
+    {
+        let is_array = matches!(slot.shape().def, Def::Array(_));
+        self.expect(b'[', "an array")?;
+        for index in 0..len {
+            if index > 0 && !self.eat(b',')? || self.peek()? == b']' {
+                return Err(self.error(InPlaceErrorKind::WrongLength { expected: len }));
+            }
+            if is_array {
+                slot.begin_nth_element(index)
+                    .map_err(|e| self.error(e.into()))?;
+                self.value(slot)?;
+                slot.end().map_err(|e| self.error(e.into()))?;
+            } else {
+                self.field(slot, index)?;
+            }
+        }
+        if !self.eat(b']')? {
+            return Err(self.error(InPlaceErrorKind::WrongLength { expected: len }));
+        }
+        Ok(())
+    }
+
+    fn object<'facet, T: Facet<'facet>>(
+        &mut self,
+        slot: &mut Slot<'_, 'facet, T>,
+        shape: &'static Shape<'static>,
+    ) -> Result<(), InPlaceError<'input>>
+    where
+        'input: 'facet,
+    {
+        self.expect(b'{', "an object")?;
+        if self.eat(b'}')? {
+            return Ok(());
+        }
+        loop {
+            self.skip_whitespace();
+            let key_offset = self.pos;
+            let key = self.string()?;
+            self.expect(b':', "a colon")?;
+            match slot.field_index(key) {
+                Some(index) => self.field(slot, index)?,
+                None if shape.has_deny_unknown_fields_attr() => {
+                    return Err(InPlaceError {
+                        kind: InPlaceErrorKind::UnknownField(key),
+                        offset: key_offset,
+                    });
+                }
+                None => self.skip_value()?,
+            }
+            if !self.eat(b',')? {
+                self.expect(b'}', "a comma or the end of the object")?;
+                return Ok(());
+            }
+        }
+    }
+}
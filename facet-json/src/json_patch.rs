@@ -0,0 +1,766 @@
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use facet_core::Facet;
+use facet_deserialize::{DeserError, Event, Scalar, Spanned};
+
+use crate::{JsonEvents, from_str, to_string};
+
+/// A JSON Patch document ([RFC 6902](https://www.rfc-editor.org/rfc/rfc6902)): a list of
+/// operations that turn one JSON document into another.
+///
+/// [`JsonPatch::diff`] finds the operations that turn one value into another, and its
+/// [`Display`](fmt::Display) output is the patch document that HTTP `PATCH` endpoints expect.
+/// A received document is read with [`str::parse`] and applied onto a value with
+/// [`JsonPatch::apply`].
+///
+/// ```
+/// # use facet::Facet;
+/// # use facet_json::JsonPatch;
+/// #[derive(Facet, Clone, Debug, PartialEq)]
+/// struct Settings {
+///     theme: String,
+///     font_size: u32,
+/// }
+///
+/// let old = Settings { theme: "light".into(), font_size: 12 };
+/// let new = Settings { theme: "light".into(), font_size: 14 };
+/// let patch = JsonPatch::diff(&old, &new);
+/// assert_eq!(patch.to_string(), r#"[{"op":"replace","path":"/font_size","value":14}]"#);
+///
+/// let mut settings = old.clone();
+/// patch.apply(&mut settings).unwrap();
+/// assert_eq!(settings, new);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JsonPatch {
+    operations: Vec<PatchOperation>,
+}
+
+/// One operation of a [`JsonPatch`].
+///
+/// Paths are JSON Pointers ([RFC 6901](https://www.rfc-editor.org/rfc/rfc6901)), like
+/// `/server/port` or `/tags/0`, and values are JSON text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchOperation {
+    /// Adds a value to an object, inserts it into an array, or replaces the whole document.
+    Add {
+        /// Where the value goes; the last token of an array path may be `-`, for the end
+        path: String,
+        /// The value, as JSON
+        value: String,
+    },
+    /// Removes the value at `path`.
+    Remove {
+        /// The value to remove
+        path: String,
+    },
+    /// Replaces the value at `path`, which must exist.
+    Replace {
+        /// The value to replace
+        path: String,
+        /// The new value, as JSON
+        value: String,
+    },
+    /// Removes the value at `from` and adds it at `path`.
+    Move {
+        /// The value to move
+        from: String,
+        /// Where it goes
+        path: String,
+    },
+    /// Adds a copy of the value at `from` at `path`.
+    Copy {
+        /// The value to copy
+        from: String,
+        /// Where the copy goes
+        path: String,
+    },
+    /// Checks that the value at `path` equals `value`, failing the whole patch otherwise.
+    Test {
+        /// The value to check
+        path: String,
+        /// The value it must equal, as JSON
+        value: String,
+    },
+}
+
+/// An error from reading or applying a [`JsonPatch`] or a merge patch.
+#[derive(Debug)]
+pub enum JsonPatchError {
+    /// The patch, or a value in one of its operations, isn't valid JSON.
+    Syntax(DeserError<'static, 'static>),
+    /// The patch isn't a list of operations, or an operation is malformed.
+    InvalidOperation {
+        /// The position of the operation in the patch
+        index: usize,
+        /// What's wrong with it
+        reason: &'static str,
+    },
+    /// An operation refers to a location that doesn't exist.
+    PathNotFound {
+        /// The position of the operation in the patch
+        index: usize,
+        /// The JSON Pointer that couldn't be followed
+        path: String,
+    },
+    /// A `test` operation found a different value.
+    TestFailed {
+        /// The position of the operation in the patch
+        index: usize,
+        /// The JSON Pointer of the value that was checked
+        path: String,
+    },
+    /// The patched document doesn't deserialize into the target type.
+    Deserialize(DeserError<'static, 'static>),
+}
+
+impl fmt::Display for JsonPatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonPatchError::Syntax(err) => write!(f, "invalid JSON in patch: {err}"),
+            JsonPatchError::InvalidOperation { index, reason } => {
+                write!(f, "invalid operation {index}: {reason}")
+            }
+            JsonPatchError::PathNotFound { index, path } => {
+                write!(f, "operation {index}: path {path:?} not found")
+            }
+            JsonPatchError::TestFailed { index, path } => {
+                write!(f, "operation {index}: test of {path:?} failed")
+            }
+            JsonPatchError::Deserialize(err) => write!(f, "patched document is invalid: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for JsonPatchError {}
+
+impl JsonPatch {
+    /// Finds the operations that turn `old` into `new`, comparing their JSON.
+    ///
+    /// Objects are compared key by key and arrays item by item, so only what changed is
+    /// replaced. Keys and items that only `old` has are removed, and those that only `new` has
+    /// are added.
+    pub fn diff<'facet, T: Facet<'facet>>(old: &T, new: &T) -> Self {
+        let old = to_string(old);
+        let new = to_string(new);
+        let old = parse(&old).expect("the JSON serializer writes valid JSON");
+        let new = parse(&new).expect("the JSON serializer writes valid JSON");
+        let mut operations = Vec::new();
+        diff(&old, &new, &mut String::new(), &mut operations);
+        Self { operations }
+    }
+
+    /// Makes a patch of `operations`.
+    pub fn new(operations: Vec<PatchOperation>) -> Self {
+        Self { operations }
+    }
+
+    /// The operations, in the order they're applied.
+    pub fn operations(&self) -> &[PatchOperation] {
+        &self.operations
+    }
+
+    /// Returns `true` if the patch changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Applies the patch onto `target`.
+    ///
+    /// The operations run in order on `target`'s JSON, and `target` is replaced by the
+    /// patched document, deserialized like [`from_str`] does. If an operation fails, or the
+    /// result isn't a valid `T`, `target` is left untouched.
+    pub fn apply<T>(&self, target: &mut T) -> Result<(), JsonPatchError>
+    where
+        T: for<'a> Facet<'a>,
+    {
+        let mut doc = parse(&to_string(target)).expect("the JSON serializer writes valid JSON");
+        for (index, operation) in self.operations.iter().enumerate() {
+            apply(&mut doc, operation, index)?;
+        }
+        *target = from_str(&to_json(&doc))
+            .map_err(|err| JsonPatchError::Deserialize(err.into_owned()))?;
+        Ok(())
+    }
+}
+
+impl From<Vec<PatchOperation>> for JsonPatch {
+    fn from(operations: Vec<PatchOperation>) -> Self {
+        Self::new(operations)
+    }
+}
+
+impl FromStr for JsonPatch {
+    type Err = JsonPatchError;
+
+    /// Reads a JSON Patch document: an array of operation objects.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Node::Array(items) = parse(s).map_err(JsonPatchError::Syntax)? else {
+            return Err(JsonPatchError::InvalidOperation {
+                index: 0,
+                reason: "a patch must be an array of operations",
+            });
+        };
+        let operations = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| read_operation(item, index))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { operations })
+    }
+}
+
+impl fmt::Display for JsonPatch {
+    /// Writes the patch as a compact JSON Patch document.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = Vec::new();
+        out.push(b'[');
+        for (i, operation) in self.operations.iter().enumerate() {
+            if i > 0 {
+                out.push(b',');
+            }
+            let (op, from, path, value) = match operation {
+                PatchOperation::Add { path, value } => ("add", None, path, Some(value)),
+                PatchOperation::Remove { path } => ("remove", None, path, None),
+                PatchOperation::Replace { path, value } => ("replace", None, path, Some(value)),
+                PatchOperation::Move { from, path } => ("move", Some(from), path, None),
+                PatchOperation::Copy { from, path } => ("copy", Some(from), path, None),
+                PatchOperation::Test { path, value } => ("test", None, path, Some(value)),
+            };
+            out.extend_from_slice(b"{\"op\":");
+            crate::write_json_string(&mut out, op);
+            if let Some(from) = from {
+                out.extend_from_slice(b",\"from\":");
+                crate::write_json_string(&mut out, from);
+            }
+            out.extend_from_slice(b",\"path\":");
+            crate::write_json_string(&mut out, path);
+            if let Some(value) = value {
+                out.extend_from_slice(b",\"value\":");
+                out.extend_from_slice(value.as_bytes());
+            }
+            out.push(b'}');
+        }
+        out.push(b']');
+        f.write_str(core::str::from_utf8(&out).expect("JSON is UTF-8"))
+    }
+}
+
+/// Makes a JSON Merge Patch ([RFC 7396](https://www.rfc-editor.org/rfc/rfc7396)) that turns
+/// `old` into `new`.
+///
+/// The patch is an object with the members of `new` that changed: nested objects only hold
+/// what changed in them, members that only `old` has are `null`, and anything that isn't an
+/// object is written whole. If neither value is an object, the patch is `new`'s JSON.
+///
+/// Since `null` removes a member, a patch can't set one to `null`: a `None` in a map is
+/// removed rather than kept, while an `Option` field ends up `None` either way.
+///
+/// ```
+/// # use facet::Facet;
+/// #[derive(Facet)]
+/// struct Settings {
+///     theme: String,
+///     font_size: u32,
+///     accent: Option<String>,
+/// }
+///
+/// let old = Settings { theme: "light".into(), font_size: 12, accent: Some("red".into()) };
+/// let new = Settings { theme: "light".into(), font_size: 14, accent: None };
+/// assert_eq!(facet_json::merge_patch(&old, &new), r#"{"font_size":14,"accent":null}"#);
+/// ```
+pub fn merge_patch<'facet, T: Facet<'facet>>(old: &T, new: &T) -> String {
+    let old = parse(&to_string(old)).expect("the JSON serializer writes valid JSON");
+    let new = parse(&to_string(new)).expect("the JSON serializer writes valid JSON");
+    to_json(&merge_diff(&old, &new).unwrap_or_else(|| Node::Object(Vec::new())))
+}
+
+/// Applies a JSON Merge Patch ([RFC 7396](https://www.rfc-editor.org/rfc/rfc7396)) onto
+/// `target`.
+///
+/// Objects in the patch are merged into `target`'s JSON member by member, recursively, and
+/// anything else replaces what it lands on. A `null` member removes the member, so the field
+/// it names gets the value it has when it's missing from the input: `None` for an `Option`,
+/// or its `#[facet(default)]`. The result replaces `target`, which is left untouched if it
+/// isn't a valid `T`.
+///
+/// ```
+/// # use facet::Facet;
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Settings {
+///     theme: String,
+///     font_size: u32,
+///     accent: Option<String>,
+/// }
+///
+/// let mut settings = Settings { theme: "light".into(), font_size: 12, accent: Some("red".into()) };
+/// facet_json::apply_merge_patch(&mut settings, r#"{"font_size": 14, "accent": null}"#).unwrap();
+/// assert_eq!(settings, Settings { theme: "light".into(), font_size: 14, accent: None });
+/// ```
+pub fn apply_merge_patch<T>(target: &mut T, patch: &str) -> Result<(), JsonPatchError>
+where
+    T: for<'a> Facet<'a>,
+{
+    let patch = parse(patch).map_err(JsonPatchError::Syntax)?;
+    let mut doc = parse(&to_string(target)).expect("the JSON serializer writes valid JSON");
+    merge(&mut doc, &patch);
+    *target =
+        from_str(&to_json(&doc)).map_err(|err| JsonPatchError::Deserialize(err.into_owned()))?;
+    Ok(())
+}
+
+/// A JSON document in memory
+#[derive(Debug, Clone)]
+enum Node {
+    /// A scalar, with the text it was read from
+    Scalar(String, Scalar<'static>),
+    Array(Vec<Node>),
+    Object(Vec<(String, Node)>),
+}
+
+impl PartialEq for Node {
+    /// Objects are equal if they have the same entries in any order, and numbers if they
+    /// have the same value, however they're written.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Node::Scalar(_, a), Node::Scalar(_, b)) => scalars_equal(a, b),
+            (Node::Array(a), Node::Array(b)) => a == b,
+            (Node::Object(a), Node::Object(b)) => {
+                a.len() == b.len() && a.iter().all(|(key, value)| entry(b, key) == Some(value))
+            }
+            _ => false,
+        }
+    }
+}
+
+fn scalars_equal(a: &Scalar<'_>, b: &Scalar<'_>) -> bool {
+    match (a, b) {
+        _ if a == b => true,
+        (Scalar::F64(float), other) | (other, Scalar::F64(float)) => {
+            integer(other).is_some_and(|int| int as f64 == *float)
+        }
+        _ => matches!((integer(a), integer(b)), (Some(a), Some(b)) if a == b),
+    }
+}
+
+fn integer(scalar: &Scalar<'_>) -> Option<i128> {
+    match *scalar {
+        Scalar::U64(n) => Some(n.into()),
+        Scalar::I64(n) => Some(n.into()),
+        Scalar::U128(n) => i128::try_from(n).ok(),
+        Scalar::I128(n) => Some(n),
+        _ => None,
+    }
+}
+
+fn entry<'a>(entries: &'a [(String, Node)], key: &str) -> Option<&'a Node> {
+    entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+fn parse(input: &str) -> Result<Node, DeserError<'static, 'static>> {
+    let mut events: JsonEvents<'_, 'static> = JsonEvents::new(input.as_bytes());
+    let first = next(&mut events).map_err(DeserError::into_owned)?;
+    read(&mut events, input, first).map_err(DeserError::into_owned)
+}
+
+fn next<'input>(
+    events: &mut JsonEvents<'input, 'static>,
+) -> Result<Spanned<Event<'input>>, DeserError<'input, 'static>> {
+    events
+        .next()
+        .expect("events end only after an error or a whole value")
+}
+
+/// Reads the value that starts with `first`
+fn read<'input>(
+    events: &mut JsonEvents<'input, 'static>,
+    input: &'input str,
+    first: Spanned<Event<'input>>,
+) -> Result<Node, DeserError<'input, 'static>> {
+    Ok(match first.node {
+        Event::Scalar(scalar) => {
+            let text = input[first.span.start()..first.span.end()].to_string();
+            Node::Scalar(text, owned(scalar))
+        }
+        Event::ListStarted => {
+            let mut items = Vec::new();
+            loop {
+                let event = next(events)?;
+                if event.node == Event::ListEnded {
+                    break;
+                }
+                items.push(read(events, input, event)?);
+            }
+            Node::Array(items)
+        }
+        Event::ObjectStarted => {
+            let mut entries = Vec::new();
+            while let Event::Key(key) = next(events)?.node {
+                let first = next(events)?;
+                entries.push((key.into_owned(), read(events, input, first)?));
+            }
+            Node::Object(entries)
+        }
+        event => unreachable!("a value can't start with {event:?}"),
+    })
+}
+
+fn owned(scalar: Scalar<'_>) -> Scalar<'static> {
+    match scalar {
+        Scalar::String(s) => Scalar::String(Cow::Owned(s.into_owned())),
+        Scalar::U64(n) => Scalar::U64(n),
+        Scalar::I64(n) => Scalar::I64(n),
+        Scalar::F64(n) => Scalar::F64(n),
+        Scalar::U128(n) => Scalar::U128(n),
+        Scalar::I128(n) => Scalar::I128(n),
+        Scalar::Bool(b) => Scalar::Bool(b),
+        Scalar::Null => Scalar::Null,
+    }
+}
+
+fn write(node: &Node, out: &mut Vec<u8>) {
+    match node {
+        Node::Scalar(text, _) => out.extend_from_slice(text.as_bytes()),
+        Node::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write(item, out);
+            }
+            out.push(b']');
+        }
+        Node::Object(entries) => {
+            out.push(b'{');
+            for (i, (key, value)) in entries.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                crate::write_json_string(out, key);
+                out.push(b':');
+                write(value, out);
+            }
+            out.push(b'}');
+        }
+    }
+}
+
+fn to_json(node: &Node) -> String {
+    let mut out = Vec::new();
+    write(node, &mut out);
+    String::from_utf8(out).expect("JSON is UTF-8")
+}
+
+/// Appends `token` to a JSON Pointer, escaping `~` and `/`
+fn push_token(path: &mut String, token: &str) {
+    path.push('/');
+    for c in token.chars() {
+        match c {
+            '~' => path.push_str("~0"),
+            '/' => path.push_str("~1"),
+            c => path.push(c),
+        }
+    }
+}
+
+fn diff(old: &Node, new: &Node, path: &mut String, operations: &mut Vec<PatchOperation>) {
+    let len = path.len();
+    match (old, new) {
+        (Node::Object(old_entries), Node::Object(new_entries)) => {
+            for (key, old_value) in old_entries {
+                push_token(path, key);
+                match entry(new_entries, key) {
+                    Some(new_value) => diff(old_value, new_value, path, operations),
+                    None => operations.push(PatchOperation::Remove { path: path.clone() }),
+                }
+                path.truncate(len);
+            }
+            for (key, new_value) in new_entries {
+                if entry(old_entries, key).is_none() {
+                    push_token(path, key);
+                    operations.push(PatchOperation::Add {
+                        path: path.clone(),
+                        value: to_json(new_value),
+                    });
+                    path.truncate(len);
+                }
+            }
+        }
+        (Node::Array(old_items), Node::Array(new_items)) => {
+            for (i, (old_item, new_item)) in old_items.iter().zip(new_items).enumerate() {
+                push_token(path, &i.to_string());
+                diff(old_item, new_item, path, operations);
+                path.truncate(len);
+            }
+            for i in (new_items.len()..old_items.len()).rev() {
+                push_token(path, &i.to_string());
+                operations.push(PatchOperation::Remove { path: path.clone() });
+                path.truncate(len);
+            }
+            for (i, new_item) in new_items.iter().enumerate().skip(old_items.len()) {
+                push_token(path, &i.to_string());
+                operations.push(PatchOperation::Add {
+                    path: path.clone(),
+                    value: to_json(new_item),
+                });
+                path.truncate(len);
+            }
+        }
+        _ if old == new => {}
+        _ => operations.push(PatchOperation::Replace {
+            path: path.clone(),
+            value: to_json(new),
+        }),
+    }
+}
+
+fn read_operation(item: &Node, index: usize) -> Result<PatchOperation, JsonPatchError> {
+    let invalid = |reason| JsonPatchError::InvalidOperation { index, reason };
+    let Node::Object(entries) = item else {
+        return Err(invalid("an operation must be an object"));
+    };
+    let string = |key, reason| match entry(entries, key) {
+        Some(Node::Scalar(_, Scalar::String(s))) => Ok(s.to_string()),
+        _ => Err(invalid(reason)),
+    };
+    let path = || string("path", "`path` must be a string");
+    let from = || string("from", "`from` must be a string");
+    let value = || {
+        entry(entries, "value")
+            .map(to_json)
+            .ok_or_else(|| invalid("`value` is missing"))
+    };
+    Ok(match string("op", "`op` must be a string")?.as_str() {
+        "add" => PatchOperation::Add {
+            path: path()?,
+            value: value()?,
+        },
+        "remove" => PatchOperation::Remove { path: path()? },
+        "replace" => PatchOperation::Replace {
+            path: path()?,
+            value: value()?,
+        },
+        "move" => PatchOperation::Move {
+            from: from()?,
+            path: path()?,
+        },
+        "copy" => PatchOperation::Copy {
+            from: from()?,
+            path: path()?,
+        },
+        "test" => PatchOperation::Test {
+            path: path()?,
+            value: value()?,
+        },
+        _ => return Err(invalid("unknown `op`")),
+    })
+}
+
+/// Splits a JSON Pointer into its unescaped tokens
+fn tokens(path: &str, index: usize) -> Result<Vec<String>, JsonPatchError> {
+    let invalid = |reason| JsonPatchError::InvalidOperation { index, reason };
+    if path.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = path.strip_prefix('/') else {
+        return Err(invalid("a JSON Pointer must start with `/`"));
+    };
+    rest.split('/')
+        .map(|token| {
+            let mut out = String::with_capacity(token.len());
+            let mut chars = token.chars();
+            while let Some(c) = chars.next() {
+                if c != '~' {
+                    out.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some('0') => out.push('~'),
+                    Some('1') => out.push('/'),
+                    _ => return Err(invalid("`~` must be followed by `0` or `1`")),
+                }
+            }
+            Ok(out)
+        })
+        .collect()
+}
+
+/// Reads an array index, which is written without leading zeros
+fn array_index(token: &str) -> Option<usize> {
+    let digits = token.bytes().all(|b| b.is_ascii_digit());
+    if !digits || token.is_empty() || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+fn get<'a>(mut node: &'a Node, tokens: &[String]) -> Option<&'a Node> {
+    for token in tokens {
+        node = match node {
+            Node::Object(entries) => entry(entries, token)?,
+            Node::Array(items) => items.get(array_index(token)?)?,
+            Node::Scalar(..) => return None,
+        };
+    }
+    Some(node)
+}
+
+fn get_mut<'a>(mut node: &'a mut Node, tokens: &[String]) -> Option<&'a mut Node> {
+    for token in tokens {
+        node = match node {
+            Node::Object(entries) => entries
+                .iter_mut()
+                .find(|(k, _)| k == token)
+                .map(|(_, v)| v)?,
+            Node::Array(items) => items.get_mut(array_index(token)?)?,
+            Node::Scalar(..) => return None,
+        };
+    }
+    Some(node)
+}
+
+fn add(doc: &mut Node, tokens: &[String], value: Node) -> Option<()> {
+    let Some((last, parent)) = tokens.split_last() else {
+        *doc = value;
+        return Some(());
+    };
+    match get_mut(doc, parent)? {
+        Node::Object(entries) => match entries.iter_mut().find(|(k, _)| k == last) {
+            Some((_, old)) => *old = value,
+            None => entries.push((last.clone(), value)),
+        },
+        Node::Array(items) => {
+            let at = match last.as_str() {
+                "-" => items.len(),
+                token => array_index(token).filter(|&i| i <= items.len())?,
+            };
+            items.insert(at, value);
+        }
+        Node::Scalar(..) => return None,
+    }
+    Some(())
+}
+
+fn remove(doc: &mut Node, tokens: &[String]) -> Option<Node> {
+    let (last, parent) = tokens.split_last()?;
+    match get_mut(doc, parent)? {
+        Node::Object(entries) => {
+            let at = entries.iter().position(|(k, _)| k == last)?;
+            Some(entries.remove(at).1)
+        }
+        Node::Array(items) => {
+            let at = array_index(last).filter(|&i| i < items.len())?;
+            Some(items.remove(at))
+        }
+        Node::Scalar(..) => None,
+    }
+}
+
+fn apply(doc: &mut Node, operation: &PatchOperation, index: usize) -> Result<(), JsonPatchError> {
+    let not_found = |path: &str| JsonPatchError::PathNotFound {
+        index,
+        path: path.to_string(),
+    };
+    let value = |json: &str| parse(json).map_err(JsonPatchError::Syntax);
+    match operation {
+        PatchOperation::Add { path, value: json } => {
+            add(doc, &tokens(path, index)?, value(json)?).ok_or_else(|| not_found(path))
+        }
+        PatchOperation::Remove { path } => remove(doc, &tokens(path, index)?)
+            .map(drop)
+            .ok_or_else(|| not_found(path)),
+        PatchOperation::Replace { path, value: json } => {
+            let value = value(json)?;
+            let target = get_mut(doc, &tokens(path, index)?).ok_or_else(|| not_found(path))?;
+            *target = value;
+            Ok(())
+        }
+        PatchOperation::Move { from, path } => {
+            let from_tokens = tokens(from, index)?;
+            let path_tokens = tokens(path, index)?;
+            if path_tokens.len() > from_tokens.len() && path_tokens.starts_with(&from_tokens) {
+                return Err(JsonPatchError::InvalidOperation {
+                    index,
+                    reason: "a value can't be moved into itself",
+                });
+            }
+            let value = remove(doc, &from_tokens).ok_or_else(|| not_found(from))?;
+            add(doc, &path_tokens, value).ok_or_else(|| not_found(path))
+        }
+        PatchOperation::Copy { from, path } => {
+            let value = get(doc, &tokens(from, index)?)
+                .ok_or_else(|| not_found(from))?
+                .clone();
+            add(doc, &tokens(path, index)?, value).ok_or_else(|| not_found(path))
+        }
+        PatchOperation::Test { path, value: json } => {
+            let actual = get(doc, &tokens(path, index)?).ok_or_else(|| not_found(path))?;
+            if *actual == value(json)? {
+                Ok(())
+            } else {
+                Err(JsonPatchError::TestFailed {
+                    index,
+                    path: path.clone(),
+                })
+            }
+        }
+    }
+}
+
+/// The merge patch that turns `old` into `new`, or `None` if they're equal
+fn merge_diff(old: &Node, new: &Node) -> Option<Node> {
+    let (Node::Object(old_entries), Node::Object(new_entries)) = (old, new) else {
+        return (old != new).then(|| new.clone());
+    };
+    let mut patch = Vec::new();
+    for (key, old_value) in old_entries {
+        match entry(new_entries, key) {
+            Some(new_value) => {
+                if let Some(value) = merge_diff(old_value, new_value) {
+                    patch.push((key.clone(), value));
+                }
+            }
+            None => patch.push((key.clone(), Node::Scalar("null".to_string(), Scalar::Null))),
+        }
+    }
+    for (key, new_value) in new_entries {
+        if entry(old_entries, key).is_none() {
+            patch.push((key.clone(), new_value.clone()));
+        }
+    }
+    (!patch.is_empty()).then_some(Node::Object(patch))
+}
+
+/// Merges `patch` into `doc`, as RFC 7396 says
+fn merge(doc: &mut Node, patch: &Node) {
+    let Node::Object(patch_entries) = patch else {
+        *doc = patch.clone();
+        return;
+    };
+    if !matches!(doc, Node::Object(_)) {
+        *doc = Node::Object(Vec::new());
+    }
+    let Node::Object(entries) = doc else {
+        unreachable!()
+    };
+    for (key, value) in patch_entries {
+        let position = entries.iter().position(|(k, _)| k == key);
+        match (position, value) {
+            (Some(at), Node::Scalar(_, Scalar::Null)) => {
+                entries.remove(at);
+            }
+            (None, Node::Scalar(_, Scalar::Null)) => {}
+            (Some(at), value) => merge(&mut entries[at].1, value),
+            (None, value) => {
+                let mut member = Node::Object(Vec::new());
+                merge(&mut member, value);
+                entries.push((key.clone(), member));
+            }
+        }
+    }
+}
@@ -5,21 +5,66 @@
 #![forbid(unsafe_code)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
-pub use facet_deserialize::{DeserError, DeserErrorKind, DeserErrorMessage};
+#[cfg(feature = "alloc")]
+pub use facet_deserialize::{
+    DeserError, DeserErrorKind, DeserErrorMessage, NumericConversionReason,
+};
+#[cfg(feature = "alloc")]
+pub use facet_serialize::{FieldOrder, FloatFormat};
 
+#[cfg(feature = "alloc")]
 mod deserialize;
+#[cfg(feature = "alloc")]
 pub use deserialize::*;
 
+#[cfg(feature = "alloc")]
 mod serialize;
+#[cfg(feature = "alloc")]
 pub use serialize::*;
 
+#[cfg(feature = "alloc")]
+mod pretty;
+#[cfg(feature = "alloc")]
+pub use pretty::*;
+
+#[cfg(feature = "alloc")]
+mod json_patch;
+#[cfg(feature = "alloc")]
+pub use json_patch::*;
+
+#[cfg(feature = "alloc")]
+mod push;
+#[cfg(feature = "alloc")]
+pub use push::*;
+
+#[cfg(feature = "alloc")]
+mod seq;
+#[cfg(feature = "alloc")]
+pub use seq::*;
+
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+
+#[cfg(feature = "alloc")]
 mod tokenizer;
 
+mod in_place;
+pub use in_place::*;
+
 /// The JSON format
-struct Json;
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+struct Json {
+    /// Read `{"$ref": n}` as the `n`th `Rc` or `Arc`
+    shared_refs: bool,
+}
 
 /// `no_std` compatible Write trait used by the json serializer.
 pub trait JsonWrite {
@@ -30,6 +75,7 @@ pub trait JsonWrite {
     fn reserve(&mut self, additional: usize);
 }
 
+#[cfg(feature = "alloc")]
 impl JsonWrite for &mut Vec<u8> {
     fn write(&mut self, buf: &[u8]) {
         self.extend(buf);
@@ -40,6 +86,7 @@ impl JsonWrite for &mut Vec<u8> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl JsonWrite for Vec<u8> {
     fn write(&mut self, buf: &[u8]) {
         self.extend(buf);
@@ -52,18 +99,21 @@ impl JsonWrite for Vec<u8> {
 
 /// Properly escapes and writes a JSON string
 #[inline]
+#[cfg(feature = "alloc")]
 fn write_json_string<W: JsonWrite>(writer: &mut W, s: &str) {
     // Just a little bit of text on how it works. There are two main steps:
-    // 1. Check if the string is completely ASCII and doesn't contain any quotes or backslashes or
-    //    control characters. This is the fast path, because it means that the bytes can be written
-    //    as they are, without any escaping needed. In this case we go over the string in windows
-    //    of 16 bytes (which is completely arbitrary, maybe find some real world data to tune this
-    //    with? I don't know and you don't have to do this dear reader.) and we just feed them into
-    //    the writer.
-    // 2. If the string is not completely ASCII or contains quotes or backslashes or control
-    //    characters, we need to escape them. This is the slow path, because it means that we need
-    //    to write the bytes one by one, and we need to figure out where to put the escapes. So we
-    //    just call `write_json_escaped_char` for each character.
+    // 1. Check if a window of 16 bytes doesn't contain any quotes or backslashes or control
+    //    characters. This is the fast path, because it means that the bytes can be written
+    //    as they are, without any escaping needed. (16 is completely arbitrary, maybe find some
+    //    real world data to tune this with? I don't know and you don't have to do this dear
+    //    reader.)
+    // 2. If the window contains quotes or backslashes or control characters, we need to escape
+    //    them. This is the slow path, where we look for the bytes that need escaping and write
+    //    the runs in between as they are.
+    //
+    // Everything that needs escaping is ASCII, and bytes of multi-byte UTF-8 characters are never
+    // ASCII, so the string can be handled as bytes: non-ASCII text is copied as it is, even when a
+    // window cuts a character in two.
 
     const STEP_SIZE: usize = Window::BITS as usize / 8;
     type Window = u128;
@@ -71,52 +121,57 @@ fn write_json_string<W: JsonWrite>(writer: &mut W, s: &str) {
 
     writer.write(b"\"");
 
-    let mut s = s;
-    while let Some(Ok(chunk)) = s.as_bytes().get(..STEP_SIZE).map(Chunk::try_from) {
+    let mut bytes = s.as_bytes();
+    while let Some(Ok(chunk)) = bytes.get(..STEP_SIZE).map(Chunk::try_from) {
         let window = Window::from_ne_bytes(chunk);
         // Our window is a concatenation of u8 values. For each value, we need to make sure that:
-        // 1. It is ASCII (i.e. the first bit of the u8 is 0, so u8 & 0x80 == 0)
-        // 2. It does not contain quotes (i.e. 0x22)
-        // 3. It does not contain backslashes (i.e. 0x5c)
-        // 4. It does not contain control characters (i.e. characters below 32, including 0)
+        // 1. It does not contain quotes (i.e. 0x22)
+        // 2. It does not contain backslashes (i.e. 0x5c)
+        // 3. It does not contain control characters (i.e. characters below 32, including 0)
         //    This means the bit above the 1st, 2nd or 3rd bit must be set, so u8 & 0xe0 != 0
-        let completely_ascii = window & 0x80808080808080808080808080808080 == 0;
+        // 4. It does not contain DEL (i.e. 0x7f), which we escape too
         let quote_free = !contains_0x22(window);
         let backslash_free = !contains_0x5c(window);
         let control_char_free = top_three_bits_set(window);
-        if completely_ascii && quote_free && backslash_free && control_char_free {
+        let del_free = !contains_0x7f(window);
+        if quote_free && backslash_free && control_char_free && del_free {
             // Yay! Whack it into the writer!
             writer.write(&chunk);
-            s = &s[STEP_SIZE..];
         } else {
-            // Ahw one of the conditions not met. Let's take our time and artisanally handle each
-            // character.
-            let mut chars = s.chars();
-            let mut count = STEP_SIZE;
-            for c in &mut chars {
-                write_json_escaped_char(writer, c);
-                count = count.saturating_sub(c.len_utf8());
-                if count == 0 {
-                    // Done with our chunk
-                    break;
-                }
-            }
-            s = chars.as_str();
+            // Ahw one of the conditions not met. Let's take our time and artisanally handle the
+            // bytes that need it.
+            write_json_escaped_bytes(writer, &chunk);
         }
+        bytes = &bytes[STEP_SIZE..];
     }
 
     // In our loop we checked that we were able to consume at least `STEP_SIZE` bytes every
     // iteration. That means there might be a small remnant at the end that we can handle in the
     // slow method.
-    for c in s.chars() {
-        write_json_escaped_char(writer, c);
-    }
+    write_json_escaped_bytes(writer, bytes);
 
     writer.write(b"\"")
 }
 
+/// Writes bytes of a UTF-8 string, escaping the ones that need it and writing the runs in between
+/// as they are.
+#[inline]
+#[cfg(feature = "alloc")]
+fn write_json_escaped_bytes<W: JsonWrite>(writer: &mut W, bytes: &[u8]) {
+    let mut run_start = 0;
+    for (index, &byte) in bytes.iter().enumerate() {
+        if byte == b'"' || byte == b'\\' || byte.is_ascii_control() {
+            writer.write(&bytes[run_start..index]);
+            write_json_escaped_char(writer, byte as char);
+            run_start = index + 1;
+        }
+    }
+    writer.write(&bytes[run_start..]);
+}
+
 /// Writes a single JSON escaped character
 #[inline]
+#[cfg(feature = "alloc")]
 fn write_json_escaped_char<W: JsonWrite>(writer: &mut W, c: char) {
     match c {
         '"' => writer.write(b"\\\""),
@@ -126,20 +181,7 @@ fn write_json_escaped_char<W: JsonWrite>(writer: &mut W, c: char) {
         '\t' => writer.write(b"\\t"),
         '\u{08}' => writer.write(b"\\b"),
         '\u{0C}' => writer.write(b"\\f"),
-        c if c.is_ascii_control() => {
-            let code_point = c as u32;
-            // Extract individual hex digits (nibbles) from the code point
-            let to_hex = |d: u32| char::from_digit(d, 16).unwrap() as u8;
-            let buf = [
-                b'\\',
-                b'u',
-                to_hex((code_point >> 12) & 0xF),
-                to_hex((code_point >> 8) & 0xF),
-                to_hex((code_point >> 4) & 0xF),
-                to_hex(code_point & 0xF),
-            ];
-            writer.write(&buf);
-        }
+        c if c.is_ascii_control() => write_unicode_escape(writer, c as u16),
         c if c.is_ascii() => {
             writer.write(&[c as u8]);
         }
@@ -151,7 +193,60 @@ fn write_json_escaped_char<W: JsonWrite>(writer: &mut W, c: char) {
     }
 }
 
+/// Writes a UTF-16 code unit as `\uXXXX`
 #[inline]
+#[cfg(feature = "alloc")]
+fn write_unicode_escape<W: JsonWrite>(writer: &mut W, unit: u16) {
+    // Extract individual hex digits (nibbles) from the code unit
+    let to_hex = |d: u16| char::from_digit(d as u32, 16).unwrap() as u8;
+    writer.write(&[
+        b'\\',
+        b'u',
+        to_hex((unit >> 12) & 0xF),
+        to_hex((unit >> 8) & 0xF),
+        to_hex((unit >> 4) & 0xF),
+        to_hex(unit & 0xF),
+    ]);
+}
+
+/// Like [`write_json_string`], but can also escape non-ASCII characters as `\uXXXX` (as
+/// surrogate pairs past U+FFFF) and `/` as `\/`.
+#[cfg(feature = "alloc")]
+fn write_json_string_escaping<W: JsonWrite>(
+    writer: &mut W,
+    s: &str,
+    ascii_only: bool,
+    escape_slashes: bool,
+) {
+    writer.write(b"\"");
+    let mut run_start = 0;
+    for (index, c) in s.char_indices() {
+        let escape = c == '"'
+            || c == '\\'
+            || c.is_ascii_control()
+            || (escape_slashes && c == '/')
+            || (ascii_only && !c.is_ascii());
+        if !escape {
+            continue;
+        }
+        writer.write(&s.as_bytes()[run_start..index]);
+        match c {
+            '/' => writer.write(b"\\/"),
+            c if c.is_ascii() => write_json_escaped_char(writer, c),
+            c => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    write_unicode_escape(writer, *unit);
+                }
+            }
+        }
+        run_start = index + c.len_utf8();
+    }
+    writer.write(&s.as_bytes()[run_start..]);
+    writer.write(b"\"")
+}
+
+#[inline]
+#[cfg(feature = "alloc")]
 fn contains_0x22(val: u128) -> bool {
     let xor_result = val ^ 0x22222222222222222222222222222222;
     let has_zero = (xor_result.wrapping_sub(0x01010101010101010101010101010101))
@@ -161,6 +256,7 @@ fn contains_0x22(val: u128) -> bool {
 }
 
 #[inline]
+#[cfg(feature = "alloc")]
 fn contains_0x5c(val: u128) -> bool {
     let xor_result = val ^ 0x5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c5c;
     let has_zero = (xor_result.wrapping_sub(0x01010101010101010101010101010101))
@@ -169,8 +265,19 @@ fn contains_0x5c(val: u128) -> bool {
     has_zero != 0
 }
 
+#[inline]
+#[cfg(feature = "alloc")]
+fn contains_0x7f(val: u128) -> bool {
+    let xor_result = val ^ 0x7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f;
+    let has_zero = (xor_result.wrapping_sub(0x01010101010101010101010101010101))
+        & !xor_result
+        & 0x80808080808080808080808080808080;
+    has_zero != 0
+}
+
 /// For each of the 16 u8s that make up a u128, check if the top three bits are set.
 #[inline]
+#[cfg(feature = "alloc")]
 fn top_three_bits_set(value: u128) -> bool {
     let xor_result = value & 0xe0e0e0e0e0e0e0e0e0e0e0e0e0e0e0e0;
     let has_zero = (xor_result.wrapping_sub(0x01010101010101010101010101010101))
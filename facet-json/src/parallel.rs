@@ -0,0 +1,149 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use facet_core::{Def, Facet, ShapeAttribute, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek, PeekListLike};
+use rayon::prelude::*;
+
+use crate::{SerializeError, peek_to_writer, write_json_string};
+
+/// Serializes a value to a JSON string, splitting large lists across threads.
+///
+/// See [`to_vec_parallel`].
+pub fn to_string_parallel<'facet, T: Facet<'facet> + Sync>(
+    value: &T,
+    threshold: usize,
+) -> Result<String, SerializeError> {
+    Ok(String::from_utf8(to_vec_parallel(value, threshold)?).unwrap())
+}
+
+/// Serializes a value to JSON bytes, splitting large lists across threads.
+///
+/// If `value` is a list (or array) with at least `threshold` elements, or a struct with such
+/// lists among its fields, those lists are cut into chunks that rayon serializes into separate
+/// buffers, which are then joined in order. Everything else is serialized on the calling thread.
+/// The output is the same as [`to_string`](crate::to_string)'s.
+///
+/// Spreading work across threads has a cost, so `threshold` should be in the thousands: this is
+/// for exports of many megabytes, not for every document.
+///
+/// Fails like [`to_writer`](crate::to_writer).
+pub fn to_vec_parallel<'facet, T: Facet<'facet> + Sync>(
+    value: &T,
+    threshold: usize,
+) -> Result<Vec<u8>, SerializeError> {
+    let mut out = Vec::new();
+    let peek = Peek::new(value);
+
+    if let Some(list) = large_list(peek, threshold) {
+        write_list(&mut out, value, list.len(), None)?;
+        return Ok(out);
+    }
+
+    if !is_plain_struct(peek) {
+        peek_to_writer(peek, &mut out)?;
+        return Ok(out);
+    }
+
+    let peek_struct = peek.into_struct().unwrap();
+    if peek_struct
+        .fields_for_serialize()
+        .any(|(field, _)| field.flattened)
+    {
+        // Flattened enums are written differently than standalone ones
+        peek_to_writer(peek, &mut out)?;
+        return Ok(out);
+    }
+
+    out.push(b'{');
+    for (index, (field, field_peek)) in peek_struct.fields_for_serialize().enumerate() {
+        if index > 0 {
+            out.push(b',');
+        }
+        write_json_string(&mut out, field.name);
+        out.push(b':');
+        match large_list(field_peek, threshold) {
+            Some(list) => write_list(&mut out, value, list.len(), Some(index))?,
+            None => peek_to_writer(field_peek, &mut out)?,
+        }
+    }
+    out.push(b'}');
+    Ok(out)
+}
+
+/// Returns `peek` as a list if it has at least `threshold` elements and is written as a JSON
+/// array of values (byte lists aren't worth it).
+fn large_list<'mem, 'facet, 'shape>(
+    peek: Peek<'mem, 'facet, 'shape>,
+    threshold: usize,
+) -> Option<PeekListLike<'mem, 'facet, 'shape>> {
+    let shape = peek.shape();
+    if !matches!(shape.def, Def::List(_) | Def::Array(_))
+        || shape.attributes.contains(&ShapeAttribute::Transparent)
+    {
+        return None;
+    }
+    let list = peek.into_list_like().ok()?;
+    (list.len() >= threshold && !list.def().t().is_type::<u8>()).then_some(list)
+}
+
+/// Whether `peek` is a struct that's written as a JSON object of its fields
+fn is_plain_struct(peek: Peek<'_, '_, '_>) -> bool {
+    let shape = peek.shape();
+    matches!(shape.def, Def::Undefined)
+        && !shape.attributes.contains(&ShapeAttribute::Transparent)
+        && matches!(shape.ty, Type::User(UserType::Struct(st)) if st.kind == StructKind::Struct)
+}
+
+/// Writes the list found in `root` (itself, or its `field`th serialized field) as a JSON array,
+/// serializing chunks of it in parallel.
+///
+/// `Peek` can't be sent to other threads, so each chunk finds the list again from `root`.
+fn write_list<'facet, T: Facet<'facet> + Sync>(
+    out: &mut Vec<u8>,
+    root: &T,
+    len: usize,
+    field: Option<usize>,
+) -> Result<(), SerializeError> {
+    let chunk_len = len.div_ceil(rayon::current_num_threads() * 4).max(1);
+    let chunks: Vec<Vec<u8>> = (0..len.div_ceil(chunk_len))
+        .into_par_iter()
+        .map(|chunk| {
+            let peek = Peek::new(root);
+            let list_peek = match field {
+                Some(index) => {
+                    let (_, field_peek) = peek
+                        .into_struct()
+                        .unwrap()
+                        .fields_for_serialize()
+                        .nth(index)
+                        .unwrap();
+                    field_peek
+                }
+                None => peek,
+            };
+            let list = list_peek.into_list_like().unwrap();
+
+            let start = chunk * chunk_len;
+            let end = (start + chunk_len).min(len);
+            let mut buf = Vec::new();
+            for i in start..end {
+                if i > start {
+                    buf.push(b',');
+                }
+                peek_to_writer(list.get(i).unwrap(), &mut buf)?;
+            }
+            Ok(buf)
+        })
+        .collect::<Result<_, _>>()?;
+
+    out.reserve(chunks.iter().map(|chunk| chunk.len() + 1).sum::<usize>() + 1);
+    out.push(b'[');
+    for (i, chunk) in chunks.iter().enumerate() {
+        if i > 0 {
+            out.push(b',');
+        }
+        out.extend_from_slice(chunk);
+    }
+    out.push(b']');
+    Ok(())
+}
@@ -0,0 +1,300 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use facet_core::Facet;
+use facet_reflect::Peek;
+
+use crate::peek_to_string;
+
+/// Serializes a value implementing `Facet` to JSON laid out over lines, with the defaults of
+/// [`PrettyPrinter`].
+pub fn to_string_pretty<'facet, T: Facet<'facet>>(value: &T) -> String {
+    PrettyPrinter::new().to_string(value)
+}
+
+/// Lays JSON out over lines for people to read.
+///
+/// Arrays and objects that fit in the remaining width of their line stay on it, like
+/// `[1, 2, 3]` or `{"x": 1, "y": 2}`. Larger ones put each item on a line of its own,
+/// indented, and the same goes for the items in turn. This keeps small values compact in
+/// documents that also hold large ones.
+///
+/// ```
+/// # use facet::Facet;
+/// # use facet_json::PrettyPrinter;
+/// #[derive(Facet)]
+/// struct Route {
+///     path: String,
+///     methods: Vec<String>,
+/// }
+///
+/// let route = Route {
+///     path: "/users".to_string(),
+///     methods: vec!["GET".to_string(), "POST".to_string()],
+/// };
+/// assert_eq!(
+///     PrettyPrinter::new().to_string(&route),
+///     r#"{"path": "/users", "methods": ["GET", "POST"]}"#
+/// );
+/// assert_eq!(
+///     PrettyPrinter::new().with_max_width(30).to_string(&route),
+///     "{\n  \"path\": \"/users\",\n  \"methods\": [\"GET\", \"POST\"]\n}"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyPrinter {
+    max_width: usize,
+    indent: usize,
+}
+
+impl Default for PrettyPrinter {
+    fn default() -> Self {
+        Self {
+            max_width: 80,
+            indent: 2,
+        }
+    }
+}
+
+impl PrettyPrinter {
+    /// Creates a printer for lines of up to 80 characters, indented by 2 spaces per level.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps lines to `max_width` characters, where values allow: a string or number longer
+    /// than that still goes on one line.
+    pub fn with_max_width(mut self, max_width: usize) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Indents each level of nesting by `indent` spaces.
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Serializes a value implementing `Facet` to JSON laid out by this printer.
+    pub fn to_string<'facet, T: Facet<'facet>>(&self, value: &T) -> String {
+        self.peek_to_string(Peek::new(value))
+    }
+
+    /// Serializes a `Peek` instance to JSON laid out by this printer.
+    pub fn peek_to_string<'input, 'facet, 'shape>(
+        &self,
+        peek: Peek<'input, 'facet, 'shape>,
+    ) -> String {
+        self.format(&peek_to_string(peek))
+    }
+
+    /// Lays out JSON text, like the output of a [`JsonSerializer`](crate::JsonSerializer)
+    /// with other options. Text that isn't valid JSON comes out garbled, but doesn't panic.
+    pub fn format(&self, json: &str) -> String {
+        let mut parser = Parser { json, pos: 0 };
+        let node = parser.value();
+        let mut out = String::with_capacity(json.len() * 2);
+        self.write(&node, 0, 0, 0, &mut out);
+        out
+    }
+
+    /// Writes `node`, which starts at `column` on a line indented `level` times, and is
+    /// followed by `trailing` characters on its last line.
+    fn write(
+        &self,
+        node: &Node<'_>,
+        level: usize,
+        column: usize,
+        trailing: usize,
+        out: &mut String,
+    ) {
+        let fits = column + node.width + trailing <= self.max_width;
+        match &node.kind {
+            Kind::Scalar(text) => out.push_str(text),
+            Kind::Array(items) if fits || items.is_empty() => {
+                out.push('[');
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(", ");
+                    }
+                    self.write(item, level, 0, 0, out);
+                }
+                out.push(']');
+            }
+            Kind::Object(entries) if fits || entries.is_empty() => {
+                out.push('{');
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(", ");
+                    }
+                    out.push_str(key);
+                    out.push_str(": ");
+                    self.write(value, level, 0, 0, out);
+                }
+                out.push('}');
+            }
+            Kind::Array(items) => {
+                out.push('[');
+                let column = (level + 1) * self.indent;
+                for (index, item) in items.iter().enumerate() {
+                    self.newline(level + 1, out);
+                    let last = index + 1 == items.len();
+                    self.write(item, level + 1, column, usize::from(!last), out);
+                    if !last {
+                        out.push(',');
+                    }
+                }
+                self.newline(level, out);
+                out.push(']');
+            }
+            Kind::Object(entries) => {
+                out.push('{');
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    self.newline(level + 1, out);
+                    out.push_str(key);
+                    out.push_str(": ");
+                    let last = index + 1 == entries.len();
+                    let column = (level + 1) * self.indent + width(key) + 2;
+                    self.write(value, level + 1, column, usize::from(!last), out);
+                    if !last {
+                        out.push(',');
+                    }
+                }
+                self.newline(level, out);
+                out.push('}');
+            }
+        }
+    }
+
+    fn newline(&self, level: usize, out: &mut String) {
+        out.push('\n');
+        out.extend(core::iter::repeat_n(' ', level * self.indent));
+    }
+}
+
+/// A JSON value, with its width when it's written on one line.
+struct Node<'json> {
+    width: usize,
+    kind: Kind<'json>,
+}
+
+enum Kind<'json> {
+    /// A string, number, boolean or `null`, as it's written
+    Scalar(&'json str),
+    Array(Vec<Node<'json>>),
+    /// Entries, with their keys as they're written, quotes included
+    Object(Vec<(&'json str, Node<'json>)>),
+}
+
+/// The width of `text` on a line, in characters.
+fn width(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Reads JSON text into [`Node`]s, keeping strings and numbers as they're written.
+struct Parser<'json> {
+    json: &'json str,
+    pos: usize,
+}
+
+impl<'json> Parser<'json> {
+    fn value(&mut self) -> Node<'json> {
+        self.skip_whitespace();
+        match self.json.as_bytes().get(self.pos) {
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                while !self.close(b']') {
+                    items.push(self.value());
+                }
+                let width = 2 + items.iter().map(|item| item.width + 2).sum::<usize>();
+                Node {
+                    width: width - 2 * usize::from(!items.is_empty()),
+                    kind: Kind::Array(items),
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut entries = Vec::new();
+                while !self.close(b'}') {
+                    self.skip_whitespace();
+                    let key = self.scalar();
+                    self.skip_whitespace();
+                    if self.json.as_bytes().get(self.pos) == Some(&b':') {
+                        self.pos += 1;
+                    }
+                    entries.push((key, self.value()));
+                }
+                let width = 2 + entries
+                    .iter()
+                    .map(|(key, value)| width(key) + 2 + value.width + 2)
+                    .sum::<usize>();
+                Node {
+                    width: width - 2 * usize::from(!entries.is_empty()),
+                    kind: Kind::Object(entries),
+                }
+            }
+            _ => {
+                let text = self.scalar();
+                Node {
+                    width: width(text),
+                    kind: Kind::Scalar(text),
+                }
+            }
+        }
+    }
+
+    /// Reads a string, number, boolean or `null`.
+    fn scalar(&mut self) -> &'json str {
+        let start = self.pos;
+        let mut chars = self.json[start..].chars();
+        let len = if self.json[start..].starts_with('"') {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('\\') => {
+                        chars.next();
+                    }
+                    Some('"') | None => break,
+                    Some(_) => {}
+                }
+            }
+            self.json.len() - start - chars.as_str().len()
+        } else {
+            let rest = chars.as_str();
+            let len = rest
+                .find(|c: char| matches!(c, ',' | ':' | ']' | '}' | '[' | '{') || c.is_whitespace())
+                .unwrap_or(rest.len());
+            // Always move on, so text that isn't valid JSON can't stall the parser
+            match len {
+                0 => rest.chars().next().map_or(0, char::len_utf8),
+                len => len,
+            }
+        };
+        self.pos += len;
+        &self.json[start..self.pos]
+    }
+
+    /// Skips the comma before the next item of an array or object, or the `close` bracket
+    /// that ends it, and returns `true` for the latter.
+    fn close(&mut self, close: u8) -> bool {
+        self.skip_whitespace();
+        match self.json.as_bytes().get(self.pos) {
+            Some(&b',') => {
+                self.pos += 1;
+                false
+            }
+            Some(&byte) if byte == close => {
+                self.pos += 1;
+                true
+            }
+            // Text that isn't valid JSON ends here
+            None | Some(b']' | b'}') => true,
+            Some(_) => false,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.json[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+}
@@ -0,0 +1,395 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use crate::tokenizer::{Token, TokenErrorKind, Tokenizer};
+
+/// A JSON parser that's given its input bit by bit, as it arrives.
+///
+/// Each call to [`feed`](Self::feed) appends bytes and returns the events they complete. A
+/// token cut in two by the end of a chunk is held back until the rest of it arrives, so
+/// servers can parse requests as they're read from the network. When a top-level value is
+/// complete, a [`JsonEvent::DocumentEnd`] follows it, and the next value starts a new document:
+/// whitespace-separated documents (like JSON lines) can be parsed one after the other.
+///
+/// The parser only keeps the bytes it hasn't turned into events yet, unless it's asked to keep
+/// whole documents with [`with_documents`](Self::with_documents), so that they can be
+/// deserialized once they're complete.
+///
+/// ```
+/// use facet_json::{JsonEvent, JsonPushParser};
+///
+/// let mut parser = JsonPushParser::new();
+/// let events: Vec<_> = parser.feed(br#"{"id": 12"#).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(events, [JsonEvent::StartObject, JsonEvent::Key("id".into())]);
+///
+/// // `12` might have been the start of `123`, so it's only read now
+/// let events: Vec<_> = parser.feed(b"}\n").collect::<Result<_, _>>().unwrap();
+/// assert_eq!(
+///     events,
+///     [JsonEvent::U64(12), JsonEvent::EndObject, JsonEvent::DocumentEnd]
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct JsonPushParser {
+    buf: Vec<u8>,
+    /// Next byte of `buf` to read
+    pos: usize,
+    /// Offset in the whole input of `buf[0]`
+    offset: usize,
+    /// Open containers, `true` for objects
+    stack: Vec<bool>,
+    state: State,
+    /// Where the document being read starts in `buf`, if any
+    doc_start: Option<usize>,
+    last_document: Option<Range<usize>>,
+    keep_documents: bool,
+    /// A `DocumentEnd` is due after the event just returned, for the document in this range
+    document_ended: Option<Range<usize>>,
+    finished: bool,
+    failed: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum State {
+    /// A value: at the top level, or after a key or a comma in an array
+    #[default]
+    Value,
+    /// Right after `[`
+    FirstValueOrEnd,
+    /// Right after `{`
+    FirstKeyOrEnd,
+    /// After a comma in an object
+    Key,
+    Colon,
+    CommaOrEnd,
+}
+
+/// Something read by a [`JsonPushParser`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonEvent {
+    /// `{`
+    StartObject,
+    /// `}`
+    EndObject,
+    /// `[`
+    StartArray,
+    /// `]`
+    EndArray,
+    /// An object key
+    Key(String),
+    /// A string value
+    String(String),
+    /// A number without a sign, decimal point or exponent
+    U64(u64),
+    /// A negative number without a decimal point or exponent
+    I64(i64),
+    /// A number without a sign, decimal point or exponent, too big for a `u64`
+    U128(u128),
+    /// A negative number without a decimal point or exponent, too small for an `i64`
+    I128(i128),
+    /// A number with a decimal point or exponent
+    F64(f64),
+    /// `true` or `false`
+    Bool(bool),
+    /// `null`
+    Null,
+    /// A top-level value is complete.
+    DocumentEnd,
+}
+
+/// An error from a [`JsonPushParser`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushError {
+    /// What went wrong
+    pub kind: PushErrorKind,
+    /// Offset in the whole input (every chunk fed so far) where it went wrong
+    pub offset: usize,
+}
+
+/// The kind of a [`PushError`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum PushErrorKind {
+    /// A character that can't start a token
+    UnexpectedCharacter(char),
+    /// A token that doesn't fit here
+    UnexpectedToken {
+        /// The token we got
+        got: String,
+        /// What we wanted instead
+        wanted: &'static str,
+    },
+    /// A string isn't valid UTF-8.
+    InvalidUtf8,
+    /// A number doesn't fit in any of the number events.
+    NumberOutOfRange,
+    /// The input was finished in the middle of a document.
+    UnexpectedEof,
+}
+
+impl fmt::Display for PushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            PushErrorKind::UnexpectedCharacter(c) => write!(f, "Unexpected character {c:?}")?,
+            PushErrorKind::UnexpectedToken { got, wanted } => {
+                write!(f, "Unexpected {got}, wanted {wanted}")?
+            }
+            PushErrorKind::InvalidUtf8 => write!(f, "Invalid UTF-8")?,
+            PushErrorKind::NumberOutOfRange => write!(f, "Number out of range")?,
+            PushErrorKind::UnexpectedEof => write!(f, "Unexpected end of input")?,
+        }
+        write!(f, " at offset {}", self.offset)
+    }
+}
+
+impl core::error::Error for PushError {}
+
+/// The events completed by a chunk given to a [`JsonPushParser`]. Events that aren't read from
+/// this iterator are returned by the next one.
+pub struct PushEvents<'p> {
+    parser: &'p mut JsonPushParser,
+}
+
+impl Iterator for PushEvents<'_> {
+    type Item = Result<JsonEvent, PushError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parser.next_event()
+    }
+}
+
+impl PushEvents<'_> {
+    /// See [`JsonPushParser::last_document`].
+    pub fn last_document(&self) -> Option<&[u8]> {
+        self.parser.last_document()
+    }
+}
+
+impl JsonPushParser {
+    /// Creates a parser that hasn't read anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the bytes of the document being read, so that it can be deserialized with
+    /// [`last_document`](Self::last_document) once it's complete.
+    pub fn with_documents(mut self, keep_documents: bool) -> Self {
+        self.keep_documents = keep_documents;
+        self
+    }
+
+    /// Appends `bytes` to the input, and returns the events they complete.
+    pub fn feed(&mut self, bytes: &[u8]) -> PushEvents<'_> {
+        let keep = match self.doc_start {
+            Some(start) if self.keep_documents => start,
+            _ => self.pos,
+        };
+        self.buf.drain(..keep);
+        self.offset += keep;
+        self.pos -= keep;
+        self.doc_start = self.doc_start.map(|start| start.saturating_sub(keep));
+        self.last_document = None;
+
+        self.buf.extend_from_slice(bytes);
+        PushEvents { parser: self }
+    }
+
+    /// Marks the end of the input, and returns the events that were waiting for it, like a
+    /// number at the very end. Returns an error if the input stops in the middle of a document.
+    pub fn finish(&mut self) -> PushEvents<'_> {
+        self.finished = true;
+        PushEvents { parser: self }
+    }
+
+    /// Returns whether the parser is between documents: everything it was given is complete.
+    pub fn is_idle(&self) -> bool {
+        self.doc_start.is_none() && self.buf[self.pos..].iter().all(u8::is_ascii_whitespace)
+    }
+
+    /// Returns the bytes of the document that was just completed, if the parser keeps
+    /// documents and the last event was a [`JsonEvent::DocumentEnd`]. They can be deserialized
+    /// with [`from_slice`](crate::from_slice).
+    pub fn last_document(&self) -> Option<&[u8]> {
+        self.last_document.clone().map(|range| &self.buf[range])
+    }
+
+    fn error(&mut self, kind: PushErrorKind, at: usize) -> Option<Result<JsonEvent, PushError>> {
+        self.failed = true;
+        Some(Err(PushError {
+            kind,
+            offset: self.offset + at,
+        }))
+    }
+
+    /// Whether the token at `pos` might continue in the next chunk
+    fn incomplete(&self) -> bool {
+        let rest = &self.buf[self.pos..];
+        match rest[0] {
+            b't' => b"true".starts_with(rest) && rest.len() < 4,
+            b'f' => b"false".starts_with(rest) && rest.len() < 5,
+            b'n' => b"null".starts_with(rest) && rest.len() < 4,
+            b'"' => {
+                let mut i = 1;
+                loop {
+                    match rest.get(i) {
+                        None => return true,
+                        Some(b'"') => return false,
+                        Some(b'\\') => i += 2,
+                        Some(_) => i += 1,
+                    }
+                }
+            }
+            b'-' | b'0'..=b'9' => rest
+                .iter()
+                .all(|b| matches!(b, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E')),
+            _ => false,
+        }
+    }
+
+    fn next_event(&mut self) -> Option<Result<JsonEvent, PushError>> {
+        if self.failed {
+            return None;
+        }
+        self.last_document = None;
+        if let Some(document) = self.document_ended.take() {
+            if self.keep_documents {
+                self.last_document = Some(document);
+            }
+            return Some(Ok(JsonEvent::DocumentEnd));
+        }
+
+        loop {
+            while self.buf.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+                self.pos += 1;
+            }
+            if self.pos == self.buf.len() {
+                if self.finished && self.doc_start.is_some() {
+                    return self.error(PushErrorKind::UnexpectedEof, self.pos);
+                }
+                return None;
+            }
+            if self.incomplete() {
+                if !self.finished {
+                    return None;
+                }
+                if matches!(self.buf[self.pos], b't' | b'f' | b'n') {
+                    return self.error(PushErrorKind::UnexpectedEof, self.buf.len());
+                }
+            }
+
+            let start = self.pos;
+            let mut tokenizer = Tokenizer::new(&self.buf[start..]);
+            let token = match tokenizer.next_token() {
+                Ok(token) => token,
+                Err(err) => {
+                    let at = start + err.span.start();
+                    return match err.kind {
+                        TokenErrorKind::UnexpectedEof(_) if !self.finished => None,
+                        TokenErrorKind::UnexpectedEof(_) => {
+                            self.error(PushErrorKind::UnexpectedEof, at)
+                        }
+                        TokenErrorKind::UnexpectedCharacter(c) => {
+                            self.error(PushErrorKind::UnexpectedCharacter(c), at)
+                        }
+                        TokenErrorKind::InvalidUtf8(_) => {
+                            self.error(PushErrorKind::InvalidUtf8, at)
+                        }
+                        TokenErrorKind::NumberOutOfRange(_) => {
+                            self.error(PushErrorKind::NumberOutOfRange, at)
+                        }
+                    };
+                }
+            };
+            self.pos = start + token.span.end();
+
+            let value = match token.node {
+                Token::Colon if self.state == State::Colon => {
+                    self.state = State::Value;
+                    continue;
+                }
+                Token::Comma if self.state == State::CommaOrEnd => {
+                    self.state = match self.stack.last() {
+                        Some(true) => State::Key,
+                        _ => State::Value,
+                    };
+                    continue;
+                }
+                Token::String(key) if matches!(self.state, State::FirstKeyOrEnd | State::Key) => {
+                    self.state = State::Colon;
+                    return Some(Ok(JsonEvent::Key(key.into_owned())));
+                }
+                Token::RBrace
+                    if self.state == State::FirstKeyOrEnd
+                        || (self.state == State::CommaOrEnd
+                            && self.stack.last() == Some(&true)) =>
+                {
+                    self.stack.pop();
+                    JsonEvent::EndObject
+                }
+                Token::RBracket
+                    if self.state == State::FirstValueOrEnd
+                        || (self.state == State::CommaOrEnd
+                            && self.stack.last() == Some(&false)) =>
+                {
+                    self.stack.pop();
+                    JsonEvent::EndArray
+                }
+                token if matches!(self.state, State::Value | State::FirstValueOrEnd) => {
+                    if self.stack.is_empty() {
+                        self.doc_start = Some(start);
+                    }
+                    match token {
+                        Token::LBrace => {
+                            self.stack.push(true);
+                            self.state = State::FirstKeyOrEnd;
+                            return Some(Ok(JsonEvent::StartObject));
+                        }
+                        Token::LBracket => {
+                            self.stack.push(false);
+                            self.state = State::FirstValueOrEnd;
+                            return Some(Ok(JsonEvent::StartArray));
+                        }
+                        Token::String(s) => JsonEvent::String(s.into_owned()),
+                        Token::F64(n) => JsonEvent::F64(n),
+                        Token::I64(n) => JsonEvent::I64(n),
+                        Token::U64(n) => JsonEvent::U64(n),
+                        Token::U128(n) => JsonEvent::U128(n),
+                        Token::I128(n) => JsonEvent::I128(n),
+                        Token::True => JsonEvent::Bool(true),
+                        Token::False => JsonEvent::Bool(false),
+                        Token::Null => JsonEvent::Null,
+                        token => return self.unexpected(token.to_string(), start),
+                    }
+                }
+                token => return self.unexpected(token.to_string(), start),
+            };
+
+            // `value` just ended
+            if self.stack.is_empty() {
+                let doc_start = self.doc_start.take().unwrap_or(start);
+                self.document_ended = Some(doc_start..self.pos);
+                self.state = State::Value;
+            } else {
+                self.state = State::CommaOrEnd;
+            }
+            return Some(Ok(value));
+        }
+    }
+
+    fn unexpected(&mut self, got: String, at: usize) -> Option<Result<JsonEvent, PushError>> {
+        let wanted = match self.state {
+            State::Value => "a value",
+            State::FirstValueOrEnd => "a value or ']'",
+            State::FirstKeyOrEnd => "a key or '}'",
+            State::Key => "a key",
+            State::Colon => "':'",
+            State::CommaOrEnd => match self.stack.last() {
+                Some(true) => "',' or '}'",
+                _ => "',' or ']'",
+            },
+        };
+        self.error(PushErrorKind::UnexpectedToken { got, wanted }, at)
+    }
+}
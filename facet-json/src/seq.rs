@@ -0,0 +1,148 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use facet_core::Facet;
+use facet_deserialize::{DeserError, DeserErrorKind, Span};
+
+use crate::{JsonParser, JsonWrite, SerializeError};
+
+/// The record separator that starts every JSON text of a sequence
+const RS: u8 = 0x1E;
+
+/// Reads a JSON text sequence (RFC 7464, `application/json-seq`): JSON texts that each start
+/// with an ASCII record separator (`0x1E`) and end with a line feed.
+///
+/// See [`JsonSeq`].
+///
+/// ```
+/// let input = b"\x1e{\"id\": 1}\n\x1e{\"id\": 2}\n";
+/// let records: Vec<std::collections::HashMap<String, u32>> = facet_json::from_slice_seq(input)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(records[1]["id"], 2);
+/// ```
+pub fn from_slice_seq<'input, T: Facet<'input>>(input: &'input [u8]) -> JsonSeq<'input, T> {
+    JsonSeq::new(input)
+}
+
+/// Reads a JSON text sequence from a UTF-8 string slice. See [`from_slice_seq`].
+pub fn from_str_seq<'input, T: Facet<'input>>(input: &'input str) -> JsonSeq<'input, T> {
+    JsonSeq::new(input.as_bytes())
+}
+
+/// The values of a JSON text sequence, in order.
+///
+/// Each text is read on its own, so one that's malformed or was cut short is returned as an
+/// error and the iterator goes on with the next one, as RFC 7464 recommends. Spans in errors
+/// are relative to the start of the text. Empty texts, like those between two separators in a
+/// row, are skipped.
+///
+/// A number, `true`, `false` or `null` that isn't followed by whitespace may have been cut
+/// short, so it's an error too.
+pub struct JsonSeq<'input, T> {
+    input: &'input [u8],
+    pos: usize,
+    parser: JsonParser,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<'input, T: Facet<'input>> JsonSeq<'input, T> {
+    /// Starts reading a JSON text sequence from `input`.
+    pub fn new(input: &'input [u8]) -> Self {
+        Self::with_parser(input, JsonParser::new())
+    }
+
+    /// Starts reading a JSON text sequence from `input`, parsing each text with `parser`.
+    pub fn with_parser(input: &'input [u8], parser: JsonParser) -> Self {
+        let pos = usize::from(input.first() == Some(&RS));
+        Self {
+            input,
+            pos,
+            parser,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The offset in the input of the next text to read.
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<'input, T: Facet<'input>> Iterator for JsonSeq<'input, T> {
+    type Item = Result<T, DeserError<'input, 'static>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = &self.input[self.pos..];
+            if rest.is_empty() {
+                return None;
+            }
+            let (text, next) = match rest.iter().position(|&b| b == RS) {
+                Some(len) => (&rest[..len], self.pos + len + 1),
+                None => (rest, self.input.len()),
+            };
+            self.pos = next;
+
+            let Some(first) = text.iter().position(|b| !b.is_ascii_whitespace()) else {
+                continue;
+            };
+            if !matches!(text[first], b'{' | b'[' | b'"')
+                && !text.last().is_some_and(u8::is_ascii_whitespace)
+            {
+                return Some(Err(DeserError::new(
+                    DeserErrorKind::UnexpectedEof {
+                        wanted: "whitespace after a number, `true`, `false` or `null`",
+                    },
+                    text,
+                    Span::new(text.len(), 0),
+                    "json",
+                )));
+            }
+            return Some(self.parser.parse_slice(text));
+        }
+    }
+}
+
+/// Writes `value` as one JSON text of a sequence (RFC 7464): a record separator, the JSON,
+/// and a line feed.
+///
+/// Fails like [`to_writer`](crate::to_writer), after the record separator is written.
+pub fn to_writer_seq<'facet, T: Facet<'facet>, W: JsonWrite>(
+    value: &T,
+    mut writer: W,
+) -> Result<(), SerializeError> {
+    writer.write(&[RS]);
+    crate::to_writer(value, ByRef(&mut writer))?;
+    writer.write(b"\n");
+    Ok(())
+}
+
+/// Writes `values` as a JSON text sequence (RFC 7464), which [`from_slice_seq`] reads back.
+///
+/// ```
+/// let seq = facet_json::to_vec_seq(&[1u32, 2]).unwrap();
+/// assert_eq!(seq, b"\x1e1\n\x1e2\n");
+/// ```
+pub fn to_vec_seq<'a, 'facet, T: Facet<'facet> + 'a>(
+    values: impl IntoIterator<Item = &'a T>,
+) -> Result<Vec<u8>, SerializeError> {
+    let mut out = Vec::new();
+    for value in values {
+        to_writer_seq(value, &mut out)?;
+    }
+    Ok(out)
+}
+
+/// Lends a writer to a serializer that takes its writer by value
+struct ByRef<'w, W>(&'w mut W);
+
+impl<W: JsonWrite> JsonWrite for ByRef<'_, W> {
+    fn write(&mut self, buf: &[u8]) {
+        self.0.write(buf);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
+    }
+}
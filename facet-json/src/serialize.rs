@@ -1,16 +1,27 @@
 use alloc::string::String;
 use alloc::vec::Vec;
-use facet_core::Facet;
+use facet_core::{Facet, Shape};
 use facet_reflect::Peek;
-use facet_serialize::{Serializer, serialize_iterative};
+use facet_serialize::{
+    FieldOrder, FloatFormat, Serializer, pointer_cycle_message, serialize_iterative,
+};
 use log::debug;
 
 /// Serializes a value implementing `Facet` to a JSON string.
+///
+/// # Panics
+///
+/// If the value holds an `Rc` or `Arc` that points back at it. [`to_writer`] returns
+/// [`SerializeError::PointerCycle`] instead.
 pub fn to_string<'facet, T: Facet<'facet>>(value: &T) -> String {
     peek_to_string(Peek::new(value))
 }
 
 /// Serializes a `Peek` instance to a JSON string.
+///
+/// # Panics
+///
+/// If the value holds an `Rc` or `Arc` that points back at it, like [`to_string`].
 pub fn peek_to_string<'input, 'facet, 'shape>(peek: Peek<'input, 'facet, 'shape>) -> String {
     let mut s = Vec::new();
     peek_to_writer(peek, &mut s).unwrap();
@@ -34,9 +45,29 @@ pub fn peek_to_writer<'mem, 'facet, 'shape, W: crate::JsonWrite>(
     serialize_iterative(peek, &mut serializer)
 }
 
-/// Serialization error for json, which cannot fail.
+/// Serialization error for json, which only fails on values it can't write.
 #[derive(Debug)]
-pub enum SerializeError {}
+pub enum SerializeError {
+    /// An `Rc` or `Arc` points back at a value that is still being written. Shared
+    /// references, from [`JsonSerializer::with_shared_refs`], can write it.
+    PointerCycle(String),
+}
+
+impl core::fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SerializeError::PointerCycle(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl core::error::Error for SerializeError {}
+
+impl From<SerializeError> for facet_serialize::SerializeError {
+    fn from(error: SerializeError) -> Self {
+        facet_serialize::SerializeError::custom(error)
+    }
+}
 
 #[derive(Debug)]
 enum StackItem {
@@ -55,6 +86,11 @@ enum ObjectItemState {
 pub struct JsonSerializer<W: crate::JsonWrite> {
     writer: W,
     stack: Vec<StackItem>,
+    shared_refs: bool,
+    ascii_only: bool,
+    escape_slashes: bool,
+    field_order: FieldOrder,
+    float_format: FloatFormat,
 }
 
 impl<W: crate::JsonWrite> JsonSerializer<W> {
@@ -63,6 +99,59 @@ impl<W: crate::JsonWrite> JsonSerializer<W> {
         Self {
             writer,
             stack: Vec::new(),
+            shared_refs: false,
+            ascii_only: false,
+            escape_slashes: false,
+            field_order: FieldOrder::Declaration,
+            float_format: FloatFormat::Shortest,
+        }
+    }
+
+    /// Writes an `Rc` or `Arc` whose value was already written as `{"$ref": n}`, where `n`
+    /// counts the shared values in the order they were first written.
+    ///
+    /// This keeps shared values from being repeated, and lets cycles be written.
+    pub fn with_shared_refs(mut self, shared_refs: bool) -> Self {
+        self.shared_refs = shared_refs;
+        self
+    }
+
+    /// Escapes every non-ASCII character in strings as `\uXXXX` (or a surrogate pair of them),
+    /// so the output is plain ASCII.
+    pub fn with_ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+
+    /// Escapes `/` in strings as `\/`, which keeps `</script>` out of JSON embedded in HTML.
+    pub fn with_escaped_slashes(mut self, escape_slashes: bool) -> Self {
+        self.escape_slashes = escape_slashes;
+        self
+    }
+
+    /// Writes struct fields in `order`, rather than the order they're declared in.
+    pub fn with_field_order(mut self, field_order: FieldOrder) -> Self {
+        self.field_order = field_order;
+        self
+    }
+
+    /// Writes floats in `float_format`, rather than with the fewest digits that read back the
+    /// same.
+    pub fn with_float_format(mut self, float_format: FloatFormat) -> Self {
+        self.float_format = float_format;
+        self
+    }
+
+    fn write_string(&mut self, s: &str) {
+        if self.ascii_only || self.escape_slashes {
+            crate::write_json_string_escaping(
+                &mut self.writer,
+                s,
+                self.ascii_only,
+                self.escape_slashes,
+            );
+        } else {
+            crate::write_json_string(&mut self.writer, s);
         }
     }
 
@@ -109,6 +198,18 @@ impl<W: crate::JsonWrite> JsonSerializer<W> {
 impl<'shape, W: crate::JsonWrite> Serializer<'shape> for JsonSerializer<W> {
     type Error = SerializeError;
 
+    fn field_order(&self) -> FieldOrder {
+        self.field_order
+    }
+
+    fn shared_refs(&self) -> bool {
+        self.shared_refs
+    }
+
+    fn serialize_pointer_cycle(&mut self, shape: &'shape Shape<'shape>) -> Result<(), Self::Error> {
+        Err(SerializeError::PointerCycle(pointer_cycle_message(shape)))
+    }
+
     fn serialize_u8(&mut self, value: u8) -> Result<(), Self::Error> {
         self.start_value()?;
         self.writer
@@ -195,17 +296,23 @@ impl<'shape, W: crate::JsonWrite> Serializer<'shape> for JsonSerializer<W> {
 
     fn serialize_f32(&mut self, value: f32) -> Result<(), Self::Error> {
         self.start_value()?;
-        // self.writer.write(value.to_string().as_bytes());
-        self.writer
-            .write(ryu::Buffer::new().format(value).as_bytes());
+        match self.float_format.format_f32(value) {
+            Some(formatted) => self.writer.write(formatted.as_bytes()),
+            None => self
+                .writer
+                .write(ryu::Buffer::new().format(value).as_bytes()),
+        }
         self.end_value()
     }
 
     fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
         self.start_value()?;
-        // self.writer.write(value.to_string().as_bytes());
-        self.writer
-            .write(ryu::Buffer::new().format(value).as_bytes());
+        match self.float_format.format_f64(value) {
+            Some(formatted) => self.writer.write(formatted.as_bytes()),
+            None => self
+                .writer
+                .write(ryu::Buffer::new().format(value).as_bytes()),
+        }
         self.end_value()
     }
 
@@ -217,16 +324,14 @@ impl<'shape, W: crate::JsonWrite> Serializer<'shape> for JsonSerializer<W> {
 
     fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
         self.start_value()?;
-        self.writer.write(b"\"");
-        crate::write_json_escaped_char(&mut self.writer, value);
-        self.writer.write(b"\"");
+        self.write_string(value.encode_utf8(&mut [0; 4]));
         self.end_value()
     }
 
     fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
         self.writer.reserve(value.len() + 2);
         self.start_value()?;
-        crate::write_json_string(&mut self.writer, value);
+        self.write_string(value);
         self.end_value()
     }
 
@@ -256,7 +361,7 @@ impl<'shape, W: crate::JsonWrite> Serializer<'shape> for JsonSerializer<W> {
         variant_name: &'shape str,
     ) -> Result<(), Self::Error> {
         self.start_value()?;
-        crate::write_json_string(&mut self.writer, variant_name);
+        self.write_string(variant_name);
         self.end_value()
     }
 
@@ -326,7 +431,7 @@ impl<'shape, W: crate::JsonWrite> Serializer<'shape> for JsonSerializer<W> {
                 ObjectItemState::Value => unreachable!(),
             }
         }
-        crate::write_json_string(&mut self.writer, name);
+        self.write_string(name);
         if let Some(StackItem::ObjectItem { object_state }) = self.stack.last_mut() {
             *object_state = ObjectItemState::Value;
         }
@@ -239,15 +239,14 @@ impl<'input> Tokenizer<'input> {
         if !done {
             while let Some(&b) = self.input.get(self.pos) {
                 if self.parse_char(b, &mut buf)? == ControlFlow::Break(()) {
+                    done = true;
                     break;
                 }
             }
         }
 
         // Check if we reached the end without finding a closing quote
-        if self.pos > self.input.len()
-            || (self.pos == self.input.len() && self.input[self.pos - 1] != b'"')
-        {
+        if !done {
             return Err(TokenError {
                 kind: TokenErrorKind::UnexpectedEof("in string literal"),
                 span: Span::new(start, self.pos - start),
@@ -341,9 +340,27 @@ impl<'input> Tokenizer<'input> {
                                 }
                             };
 
+                            // A high surrogate must be followed by an escaped low surrogate, and
+                            // the two make up a code point past U+FFFF
+                            let mut code_point = code_point as u32;
+                            if (0xD800..0xDC00).contains(&code_point) {
+                                let low = self
+                                    .input
+                                    .get(self.pos + 4..self.pos + 10)
+                                    .and_then(|escape| escape.strip_prefix(b"\\u"))
+                                    .and_then(|hex| str::from_utf8(hex).ok())
+                                    .and_then(|hex| u16::from_str_radix(hex, 16).ok())
+                                    .filter(|low| (0xDC00..0xE000).contains(low));
+                                if let Some(low) = low {
+                                    code_point = 0x10000
+                                        + ((code_point - 0xD800) << 10)
+                                        + (low as u32 - 0xDC00);
+                                    self.pos += 6;
+                                }
+                            }
+
                             // Convert to UTF-8 and append to buffer
-                            // Handle basic Unicode code points (BMP)
-                            let c = match char::from_u32(code_point as u32) {
+                            let c = match char::from_u32(code_point) {
                                 Some(c) => c,
                                 None => {
                                     return Err(TokenError {
@@ -631,4 +648,15 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_unterminated_string() {
+        for input in [r#"""#, r#""abc"#, r#""a\""#] {
+            let err = Tokenizer::new(input.as_bytes()).next_token().unwrap_err();
+            assert!(
+                matches!(err.kind, TokenErrorKind::UnexpectedEof(_)),
+                "{input}: {err:?}"
+            );
+        }
+    }
 }
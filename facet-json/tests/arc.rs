@@ -27,3 +27,103 @@ fn test_deserialize_struct_with_arc_field() {
 
     assert_eq!(wrapper, expected);
 }
+
+#[derive(Debug, Facet)]
+struct Shared {
+    first: Arc<SomeStruct>,
+    second: Arc<SomeStruct>,
+    weak: std::sync::Weak<SomeStruct>,
+}
+
+fn shared() -> Shared {
+    let first = Arc::new(SomeStruct { value: 1 });
+    Shared {
+        second: first.clone(),
+        weak: Arc::downgrade(&first),
+        first,
+    }
+}
+
+#[test]
+fn test_weak_is_written_as_null() {
+    assert_eq!(
+        facet_json::to_string(&shared()),
+        r#"{"first":{"value":1},"second":{"value":1},"weak":null}"#
+    );
+
+    let back: Shared = from_str(r#"{"first":{"value":1},"second":{"value":2},"weak":null}"#)?;
+    assert_eq!(back.second.value, 2);
+    assert!(back.weak.upgrade().is_none());
+}
+
+#[test]
+fn test_shared_refs() {
+    let mut out = Vec::new();
+    let mut serializer = facet_json::JsonSerializer::new(&mut out).with_shared_refs(true);
+    facet_serialize::serialize_iterative(facet_reflect::Peek::new(&shared()), &mut serializer)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(out)?,
+        r#"{"first":{"value":1},"second":{"$ref":0},"weak":null}"#
+    );
+}
+
+#[derive(Debug, Facet)]
+struct Config {
+    name: String,
+    limits: Arc<SomeStruct>,
+}
+
+#[derive(Debug, Facet)]
+struct Tenants {
+    first: Config,
+    second: Config,
+    defaults: Vec<Arc<SomeStruct>>,
+}
+
+#[test]
+fn test_shared_refs_round_trip() {
+    let limits = Arc::new(SomeStruct { value: 10 });
+    let tenants = Tenants {
+        first: Config {
+            name: "a".to_string(),
+            limits: limits.clone(),
+        },
+        second: Config {
+            name: "b".to_string(),
+            limits: limits.clone(),
+        },
+        defaults: vec![Arc::new(SomeStruct { value: 1 }), limits],
+    };
+
+    let mut out = Vec::new();
+    let mut serializer = facet_json::JsonSerializer::new(&mut out).with_shared_refs(true);
+    facet_serialize::serialize_iterative(facet_reflect::Peek::new(&tenants), &mut serializer)
+        .unwrap();
+    let json = String::from_utf8(out)?;
+    assert_eq!(
+        json,
+        r#"{"first":{"name":"a","limits":{"value":10}},"second":{"name":"b","limits":{"$ref":0}},"defaults":[{"value":1},{"$ref":0}]}"#
+    );
+
+    let back: Tenants = facet_json::JsonParser::new()
+        .with_shared_refs(true)
+        .parse(&json)
+        .unwrap();
+    assert_eq!(back.first.limits.value, 10);
+    assert!(Arc::ptr_eq(&back.first.limits, &back.second.limits));
+    assert!(Arc::ptr_eq(&back.first.limits, &back.defaults[1]));
+    assert!(!Arc::ptr_eq(&back.first.limits, &back.defaults[0]));
+}
+
+#[test]
+fn test_shared_ref_to_unknown_value() {
+    let err = facet_json::JsonParser::new()
+        .with_shared_refs(true)
+        .parse::<Wrapper>(r#"{"inner":{"$ref":0}}"#)
+        .unwrap_err();
+    assert!(
+        matches!(err.kind, facet_json::DeserErrorKind::UnknownSharedRef(0)),
+        "{err}"
+    );
+}
@@ -0,0 +1,72 @@
+use facet::Facet;
+use facet_json::{DeserErrorKind, from_str, to_string};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq, Clone, Copy)]
+#[facet(transparent, bitflags)]
+struct Permissions(u8);
+
+bitflags::bitflags! {
+    impl Permissions: u8 {
+        const READ = 0b001;
+        const WRITE = 0b010;
+        const EXECUTE = 0b100;
+        const READ_WRITE = Self::READ.bits() | Self::WRITE.bits();
+    }
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Mount {
+    path: String,
+    permissions: Permissions,
+}
+
+#[test]
+fn bitflags_serialize_as_names() {
+    let mount = Mount {
+        path: "/srv".to_string(),
+        permissions: Permissions::READ | Permissions::EXECUTE,
+    };
+    assert_eq!(
+        to_string(&mount),
+        r#"{"path":"/srv","permissions":["READ","EXECUTE"]}"#
+    );
+
+    // Unknown bits are kept, written like `bitflags` writes them
+    let mount = Mount {
+        path: "/srv".to_string(),
+        permissions: Permissions::from_bits_retain(0b1001),
+    };
+    assert_eq!(
+        to_string(&mount),
+        r#"{"path":"/srv","permissions":["READ","0x8"]}"#
+    );
+}
+
+#[test]
+fn bitflags_deserialize_from_names_or_bits() {
+    let mount: Mount = from_str(r#"{"path": "/srv", "permissions": ["READ_WRITE", "EXECUTE"]}"#)?;
+    assert_eq!(mount.permissions, Permissions::all());
+
+    let mount: Mount = from_str(r#"{"path": "/srv", "permissions": 5}"#)?;
+    assert_eq!(mount.permissions, Permissions::READ | Permissions::EXECUTE);
+
+    let mount: Mount = from_str(r#"{"path": "/srv", "permissions": []}"#)?;
+    assert_eq!(mount.permissions, Permissions::empty());
+
+    let permissions: Vec<Permissions> = from_str(r#"[["WRITE", "0x8"], 1]"#)?;
+    assert_eq!(
+        permissions,
+        [Permissions::from_bits_retain(0b1010), Permissions::READ]
+    );
+}
+
+#[test]
+fn bitflags_unknown_name() {
+    let err =
+        from_str::<Mount>(r#"{"path": "/srv", "permissions": ["READ", "DELETE"]}"#).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        DeserErrorKind::NoSuchFlag { ref name, .. } if name == "DELETE"
+    ));
+}
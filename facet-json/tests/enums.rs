@@ -405,3 +405,193 @@ fn enum_struct_variants_2() {
         facet_json::from_str(&json_good).map_err(|e| eyre::eyre!("{}", e))?;
     assert_eq!(deserialized_good, good);
 }
+
+#[test]
+fn json_unit_enum_variant_as_discriminant() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(discriminant)]
+    #[repr(u8)]
+    enum Status {
+        Active = 1,
+        Suspended = 4,
+        Closed,
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Account {
+        status: Status,
+    }
+
+    assert_eq!(facet_json::to_string(&Status::Suspended), "4");
+    assert_eq!(facet_json::to_string(&Status::Closed), "5");
+    assert_eq!(
+        facet_json::to_string(&Account {
+            status: Status::Active
+        }),
+        r#"{"status":1}"#
+    );
+
+    let status: Status = from_str("4")?;
+    assert_eq!(status, Status::Suspended);
+    let account: Account = from_str(r#"{"status":5}"#)?;
+    assert_eq!(account.status, Status::Closed);
+
+    // Names are still accepted
+    let status: Status = from_str(r#""Active""#)?;
+    assert_eq!(status, Status::Active);
+
+    let err = from_str::<Status>("2").unwrap_err();
+    assert!(err.to_string().contains("has discriminant"), "{err}");
+}
+
+#[test]
+fn json_negative_discriminant() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(discriminant)]
+    #[repr(i8)]
+    enum Direction {
+        Down = -1,
+        Level = 0,
+        Up = 1,
+    }
+
+    for (direction, json) in [
+        (Direction::Down, "-1"),
+        (Direction::Level, "0"),
+        (Direction::Up, "1"),
+    ] {
+        assert_eq!(facet_json::to_string(&direction), json);
+        assert_eq!(from_str::<Direction>(json)?, direction);
+    }
+}
+
+#[test]
+fn json_c_like_enum_from_discriminant() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Level {
+        Low = 1,
+        Medium = 5,
+        High = 10,
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Alarm {
+        level: Level,
+    }
+
+    // Written as names, but read from names or discriminants
+    assert_eq!(facet_json::to_string(&Level::Medium), r#""Medium""#);
+    let level: Level = from_str("10")?;
+    assert_eq!(level, Level::High);
+    let alarm: Alarm = from_str(r#"{"level":1}"#)?;
+    assert_eq!(alarm.level, Level::Low);
+
+    let err = from_str::<Level>("7").unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("has discriminant"), "{message}");
+    assert!(
+        message.contains("1 (Low), 5 (Medium), 10 (High)"),
+        "{message}"
+    );
+
+    let err = from_str::<Level>("-1").unwrap_err();
+    assert!(err.to_string().contains("has discriminant"), "{err}");
+}
+
+#[test]
+fn json_other_variant_keeps_unknown_name() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Event {
+        Created,
+        Deleted,
+        #[facet(other)]
+        Unknown(String),
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Status {
+        Active,
+        #[facet(other)]
+        Unrecognized,
+    }
+
+    let events: Vec<Event> = from_str(r#"["Created", "Archived", "Deleted"]"#)?;
+    assert_eq!(
+        events,
+        [
+            Event::Created,
+            Event::Unknown("Archived".to_string()),
+            Event::Deleted
+        ]
+    );
+    assert_eq!(
+        facet_json::to_string(&events),
+        r#"["Created","Archived","Deleted"]"#
+    );
+
+    let status: Status = from_str(r#""Active""#)?;
+    assert_eq!(status, Status::Active);
+    let status: Status = from_str(r#""Suspended""#)?;
+    assert_eq!(status, Status::Unrecognized);
+}
+
+#[test]
+fn json_other_variant_for_unknown_discriminant() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(discriminant)]
+    #[repr(u8)]
+    enum Code {
+        Success = 0,
+        NotFound = 4,
+        #[facet(other)]
+        Unknown(u8) = 255,
+    }
+
+    let codes: Vec<Code> = from_str("[0, 9, 4]")?;
+    assert_eq!(codes, [Code::Success, Code::Unknown(9), Code::NotFound]);
+    assert_eq!(facet_json::to_string(&codes), "[0,9,4]");
+}
+
+#[test]
+fn json_other_variant_skips_unknown_variant_data() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Message {
+        Text {
+            body: String,
+        },
+        Ping,
+        #[facet(other)]
+        Unknown(String),
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Kind {
+        Plain,
+        #[facet(other)]
+        Unrecognized,
+    }
+
+    let messages: Vec<Message> = from_str(
+        r#"[{"Text": {"body": "hi"}}, {"Reaction": {"emoji": "+1", "to": [1, 2]}}, "Ping"]"#,
+    )?;
+    assert_eq!(
+        messages,
+        [
+            Message::Text {
+                body: "hi".to_string()
+            },
+            Message::Unknown("Reaction".to_string()),
+            Message::Ping
+        ]
+    );
+
+    let kind: Kind = from_str(r#""Plain""#)?;
+    assert_eq!(kind, Kind::Plain);
+    let kind: Kind = from_str(r#"{"Rich": [1, {"a": null}]}"#)?;
+    assert_eq!(kind, Kind::Unrecognized);
+}
@@ -49,6 +49,11 @@ fn test_eof_errors() {
     let err = result.unwrap_err();
     assert!(matches!(err.kind, DeserErrorKind::UnexpectedEof { .. }));
 
+    // Test EOF inside the value of an unknown field
+    let result = from_str::<SimpleObject>("{\"other\": [1, {\"a\": 2");
+    let err = result.unwrap_err();
+    assert!(matches!(err.kind, DeserErrorKind::UnexpectedEof { .. }));
+
     // Test string with escape followed by EOF
     let result = from_str::<String>("\"hello\\");
     let err = result.unwrap_err();
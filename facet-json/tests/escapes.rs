@@ -230,3 +230,46 @@ fn test_hex_digit_generation() -> Result<(), Box<dyn std::error::Error>> {
     }
     Ok(())
 }
+
+fn to_string_with(
+    value: &str,
+    ascii_only: bool,
+    escape_slashes: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut out = Vec::new();
+    let mut serializer = facet_json::JsonSerializer::new(&mut out)
+        .with_ascii_only(ascii_only)
+        .with_escaped_slashes(escape_slashes);
+    facet_serialize::serialize_iterative(facet_reflect::Peek::new(&value), &mut serializer)
+        .unwrap();
+    Ok(String::from_utf8(out)?)
+}
+
+#[test]
+fn test_ascii_only() -> Result<(), Box<dyn std::error::Error>> {
+    let input = "café 😊 \"a/b\"\n";
+    let serialized = to_string_with(input, true, false)?;
+    assert_eq!(serialized, r#""caf\u00e9 \ud83d\ude0a \"a/b\"\n""#);
+    assert!(serialized.is_ascii());
+    assert_eq!(facet_json::from_str::<String>(&serialized).unwrap(), input);
+    Ok(())
+}
+
+#[test]
+fn test_escaped_slashes() -> Result<(), Box<dyn std::error::Error>> {
+    let input = "</script> é";
+    let serialized = to_string_with(input, false, true)?;
+    assert_eq!(serialized, r#""<\/script> é""#);
+    assert_eq!(facet_json::from_str::<String>(&serialized).unwrap(), input);
+
+    assert_eq!(to_string_with(input, false, false)?, r#""</script> é""#);
+    Ok(())
+}
+
+#[test]
+fn test_surrogate_pairs() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(facet_json::from_str::<String>(r#""\ud83d\ude0a""#)?, "😊");
+    assert!(facet_json::from_str::<String>(r#""\ud83d""#).is_err());
+    assert!(facet_json::from_str::<String>(r#""\ud83dA""#).is_err());
+    Ok(())
+}
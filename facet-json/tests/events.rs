@@ -0,0 +1,71 @@
+use facet_deserialize::{DeserErrorKind, Scalar};
+use facet_json::{Event, JsonEvents};
+use facet_testhelpers::test;
+
+#[test]
+fn events_of_nested_json() {
+    let events = JsonEvents::new(br#"{"a": [1, "x"], "b": {}, "c": null}"#)
+        .map(|event| event.map(|spanned| spanned.node))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        events,
+        vec![
+            Event::ObjectStarted,
+            Event::Key("a".into()),
+            Event::ListStarted,
+            Event::Scalar(Scalar::U64(1)),
+            Event::Scalar(Scalar::String("x".into())),
+            Event::ListEnded,
+            Event::Key("b".into()),
+            Event::ObjectStarted,
+            Event::ObjectEnded,
+            Event::Key("c".into()),
+            Event::Scalar(Scalar::Null),
+            Event::ObjectEnded,
+        ]
+    );
+}
+
+#[test]
+fn events_carry_spans() {
+    let input = br#"{"name": true}"#;
+    let spans = JsonEvents::new(input)
+        .map(|event| event.map(|spanned| spanned.span))
+        .collect::<Result<Vec<_>, _>>()?;
+    let texts: Vec<&str> = spans
+        .iter()
+        .map(|span| core::str::from_utf8(&input[span.start()..span.end()]).unwrap())
+        .collect();
+    assert_eq!(texts, ["{", "\"name\"", "true", "}"]);
+}
+
+#[test]
+fn events_stop_after_one_value() {
+    let mut events = JsonEvents::new(b"42");
+    assert_eq!(events.next().unwrap()?.node, Event::Scalar(Scalar::U64(42)));
+    assert!(events.next().is_none());
+}
+
+#[test]
+fn events_track_depth() {
+    let mut events = JsonEvents::new(b"[[1]]");
+    let mut depths = Vec::new();
+    while let Some(event) = events.next() {
+        event?;
+        depths.push(events.depth());
+    }
+    assert_eq!(depths, [1, 2, 2, 1, 0]);
+}
+
+#[test]
+fn events_stop_after_an_error() {
+    let mut events = JsonEvents::new(b"[1, }");
+    assert_eq!(events.next().unwrap()?.node, Event::ListStarted);
+    assert_eq!(events.next().unwrap()?.node, Event::Scalar(Scalar::U64(1)));
+    let err = events.next().unwrap().unwrap_err();
+    assert!(matches!(
+        err.kind,
+        DeserErrorKind::UnexpectedChar { got: '}', .. }
+    ));
+    assert!(events.next().is_none());
+}
@@ -0,0 +1,73 @@
+use facet::Facet;
+use facet_deserialize::{FieldSpan, SpanTable};
+use facet_json::JsonParser;
+use facet_testhelpers::test;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Facet)]
+struct Config {
+    name: String,
+    server: Server,
+    ports: Vec<u16>,
+}
+
+#[derive(Debug, Facet)]
+struct Server {
+    host: String,
+    timeout: Option<u32>,
+}
+
+#[test]
+fn field_spans_are_kept_by_path() {
+    let spans = Arc::new(SpanTable::new().with_source("config.json"));
+    let mut parser = JsonParser::new().with_field_spans(spans.clone());
+
+    let input = r#"{
+  "name": "api",
+  "server": {"host": "localhost", "timeout": 30},
+  "ports": [80, 443]
+}"#;
+    let config: Config = parser.parse(input)?;
+    assert_eq!(config.server.timeout, Some(30));
+
+    let text = |path: &str| {
+        let span = spans.get(path).unwrap();
+        &input[span.start()..span.end()]
+    };
+    assert_eq!(text("Config.name"), r#""api""#);
+    assert_eq!(text("Config.server.host"), r#""localhost""#);
+    assert_eq!(text("Config.server.timeout"), "30");
+    assert_eq!(
+        text("Config.server"),
+        r#"{"host": "localhost", "timeout": 30}"#
+    );
+    assert_eq!(text("Config.ports"), "[80, 443]");
+    assert_eq!(spans.len(), 5);
+
+    let location = spans.locate("Config.server.timeout", input).unwrap();
+    assert_eq!((location.line, location.column), (3, 46));
+    assert_eq!(location.to_string(), "config.json:3:46");
+
+    assert!(spans.take().contains_key("Config.ports"));
+    assert!(spans.is_empty());
+
+    // Fields that aren't in the input have no span
+    let input = r#"{"name": "b", "server": {"host": "h"}, "ports": []}"#;
+    let _: Config = parser.parse(input)?;
+    assert_eq!(spans.get("Config.server.timeout"), None);
+    let span = spans.get("Config.ports").unwrap();
+    assert_eq!(&input[span.start()..span.end()], "[]");
+}
+
+#[test]
+fn field_spans_with_closure() {
+    let paths = Arc::new(Mutex::new(Vec::new()));
+    let sink = {
+        let paths = paths.clone();
+        move |field: FieldSpan| paths.lock().unwrap().push(field.path)
+    };
+    let mut parser = JsonParser::new().with_field_spans(Arc::new(sink));
+
+    let _: Server = parser.parse(r#"{"host": "h", "extra": [1], "timeout": null}"#)?;
+    assert_eq!(*paths.lock().unwrap(), ["Server.host", "Server.timeout"]);
+}
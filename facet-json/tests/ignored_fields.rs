@@ -0,0 +1,52 @@
+use facet::Facet;
+use facet_deserialize::{IgnoredField, IgnoredFieldLog};
+use facet_json::JsonParser;
+use facet_testhelpers::test;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Facet)]
+struct Server {
+    host: String,
+    tls: Tls,
+}
+
+#[derive(Debug, Facet)]
+struct Tls {
+    enabled: bool,
+}
+
+#[test]
+fn ignored_fields_are_logged() {
+    let log = Arc::new(IgnoredFieldLog::new());
+    let mut parser = JsonParser::new().with_ignored_fields(log.clone());
+
+    let input =
+        r#"{"host": "a", "region": {"name": "eu"}, "tls": {"enabled": true, "alpn": ["h2"]}}"#;
+    let server: Server = parser.parse(input)?;
+    assert_eq!(server.host, "a");
+    assert!(server.tls.enabled);
+
+    let fields = log.take();
+    let paths: Vec<_> = fields.iter().map(|field| field.path.as_str()).collect();
+    assert_eq!(paths, ["Server.region", "Server.tls.alpn"]);
+    assert_eq!(fields[1].key, "alpn");
+    let span = fields[1].span;
+    assert!(input[span.start()..span.end()].contains("alpn"));
+
+    // Documents without unknown fields log nothing
+    let _: Server = parser.parse(r#"{"host": "b", "tls": {"enabled": false}}"#)?;
+    assert!(log.is_empty());
+}
+
+#[test]
+fn ignored_fields_with_closure() {
+    let keys = Arc::new(Mutex::new(Vec::new()));
+    let sink = {
+        let keys = keys.clone();
+        move |field: IgnoredField| keys.lock().unwrap().push(field.key)
+    };
+    let mut parser = JsonParser::new().with_ignored_fields(Arc::new(sink));
+
+    let _: Tls = parser.parse(r#"{"version": 3, "enabled": true}"#)?;
+    assert_eq!(*keys.lock().unwrap(), ["version"]);
+}
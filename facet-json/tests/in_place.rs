@@ -0,0 +1,101 @@
+use core::mem::MaybeUninit;
+
+use facet::Facet;
+use facet_json::{InPlaceErrorKind, from_slice_in_place, from_str_in_place};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Reading<'a> {
+    sensor: &'a str,
+    unit: char,
+    values: [i16; 3],
+    calibrated: bool,
+    position: Position,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Position(f32, f32);
+
+#[derive(Facet, Debug, PartialEq)]
+#[facet(deny_unknown_fields)]
+struct Strict {
+    a: u8,
+}
+
+#[test]
+fn deserialize_in_place() {
+    let json = r#"{
+        "position": [1.5, -2],
+        "values": [12, -3, 7],
+        "firmware": {"version": [1, 2], "notes": "skipped \"entirely\""},
+        "sensor": "t1",
+        "unit": "C",
+        "calibrated": true
+    }"#;
+    let mut storage = MaybeUninit::uninit();
+    assert_eq!(
+        *from_str_in_place::<Reading>(json, &mut storage)?,
+        Reading {
+            sensor: "t1",
+            unit: 'C',
+            values: [12, -3, 7],
+            calibrated: true,
+            position: Position(1.5, -2.0),
+        }
+    );
+
+    let mut storage = MaybeUninit::uninit();
+    assert_eq!(
+        *from_slice_in_place::<(u64, [bool; 2])>(
+            b" [18446744073709551615, [false, true]] ",
+            &mut storage
+        )?,
+        (u64::MAX, [false, true])
+    );
+}
+
+#[test]
+fn in_place_errors() {
+    fn kind(json: &str) -> InPlaceErrorKind<'_> {
+        let mut storage = MaybeUninit::<Reading>::uninit();
+        from_str_in_place(json, &mut storage).unwrap_err().kind
+    }
+
+    let valid = r#"{"sensor": "t1", "unit": "C", "values": [1, 2, 3], "calibrated": false, "position": [0, 0]}"#;
+    let mut storage = MaybeUninit::<Reading>::uninit();
+    assert!(from_str_in_place(valid, &mut storage).is_ok());
+
+    assert_eq!(
+        kind(&valid.replace("[1, 2, 3]", "[1, 2]")),
+        InPlaceErrorKind::WrongLength { expected: 3 }
+    );
+    assert_eq!(
+        kind(&valid.replace("[1, 2, 3]", "[1, 2, 99999]")),
+        InPlaceErrorKind::InvalidNumber("99999")
+    );
+    assert_eq!(
+        kind(&valid.replace("\"t1\"", "\"t\\n1\"")),
+        InPlaceErrorKind::EscapedString
+    );
+    assert_eq!(
+        kind(&format!("{valid} {{}}")),
+        InPlaceErrorKind::TrailingData
+    );
+    assert_eq!(kind(&valid[..40]), InPlaceErrorKind::UnexpectedEof);
+    assert!(matches!(
+        kind(&valid.replace("\"unit\": \"C\", ", "")),
+        InPlaceErrorKind::Reflect(_)
+    ));
+
+    let mut storage = MaybeUninit::<Strict>::uninit();
+    let err = from_str_in_place(r#"{"a": 1, "b": 2}"#, &mut storage).unwrap_err();
+    assert_eq!(err.kind, InPlaceErrorKind::UnknownField("b"));
+    assert_eq!(err.offset, 9);
+
+    // Types that need allocating can't be deserialized in place
+    let mut storage = MaybeUninit::<Vec<u8>>::uninit();
+    assert!(matches!(
+        from_str_in_place("[1]", &mut storage).unwrap_err().kind,
+        InPlaceErrorKind::Reflect(_)
+    ));
+}
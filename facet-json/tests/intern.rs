@@ -0,0 +1,38 @@
+use facet::Facet;
+use facet_deserialize::StringInterner;
+use facet_json::{JsonParser, from_str};
+use facet_testhelpers::test;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Debug, Facet)]
+struct Entry {
+    kind: Arc<str>,
+    labels: HashMap<Arc<str>, u32>,
+}
+
+#[test]
+fn arc_str_without_interner() {
+    let entry: Entry = from_str(r#"{"kind": "a\"b", "labels": {"x": 1}}"#)?;
+    assert_eq!(&*entry.kind, "a\"b");
+    assert_eq!(entry.labels.get("x"), Some(&1));
+}
+
+#[test]
+fn interner_dedupes_strings() {
+    let interner = Arc::new(StringInterner::new());
+    let mut parser = JsonParser::new().with_interner(interner.clone());
+
+    let entries: Vec<Entry> = parser.parse(
+        r#"[{"kind": "node", "labels": {"zone": 1}}, {"kind": "node", "labels": {"zone": 2}}]"#,
+    )?;
+    assert!(Arc::ptr_eq(&entries[0].kind, &entries[1].kind));
+    let key = |entry: &Entry| entry.labels.keys().next().unwrap().clone();
+    assert!(Arc::ptr_eq(&key(&entries[0]), &key(&entries[1])));
+
+    // The interner is kept between documents, including after an error
+    assert!(parser.parse::<Entry>(r#"{"kind": "node", "#).is_err());
+    let entry: Entry = parser.parse(r#"{"kind": "zone", "labels": {}}"#)?;
+    assert!(Arc::ptr_eq(&entry.kind, &key(&entries[0])));
+    assert_eq!(interner.len(), 2);
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_json::{JsonPatch, JsonPatchError, PatchOperation};
+use facet_testhelpers::test;
+
+#[derive(Facet, Clone, Debug, PartialEq)]
+struct Config {
+    name: String,
+    retries: u32,
+    timeout: Option<u32>,
+    tags: Vec<String>,
+    server: Server,
+}
+
+#[derive(Facet, Clone, Debug, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+fn config() -> Config {
+    Config {
+        name: "app".to_string(),
+        retries: 3,
+        timeout: Some(30),
+        tags: vec!["a".to_string(), "b".to_string()],
+        server: Server {
+            host: "localhost".to_string(),
+            port: 80,
+        },
+    }
+}
+
+#[test]
+fn test_diff_of_equal_values_is_empty() {
+    let patch = JsonPatch::diff(&config(), &config());
+    assert!(patch.is_empty());
+    assert_eq!(patch.to_string(), "[]");
+}
+
+#[test]
+fn test_diff_replaces_changed_fields() {
+    let mut new = config();
+    new.server.port = 8080;
+    new.timeout = None;
+    let patch = JsonPatch::diff(&config(), &new);
+    assert_eq!(
+        patch.operations(),
+        [
+            PatchOperation::Replace {
+                path: "/timeout".to_string(),
+                value: "null".to_string(),
+            },
+            PatchOperation::Replace {
+                path: "/server/port".to_string(),
+                value: "8080".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_diff_of_lists() {
+    let mut shorter = config();
+    shorter.tags = vec!["x".to_string()];
+    assert_eq!(
+        JsonPatch::diff(&config(), &shorter).to_string(),
+        r#"[{"op":"replace","path":"/tags/0","value":"x"},{"op":"remove","path":"/tags/1"}]"#
+    );
+
+    let mut longer = config();
+    longer.tags.push("c".to_string());
+    assert_eq!(
+        JsonPatch::diff(&config(), &longer).to_string(),
+        r#"[{"op":"add","path":"/tags/2","value":"c"}]"#
+    );
+}
+
+#[test]
+fn test_diff_of_maps_escapes_keys() {
+    let old = HashMap::from([("a/b".to_string(), 1), ("c~d".to_string(), 2)]);
+    let new = HashMap::from([("a/b".to_string(), 1), ("e".to_string(), 3)]);
+    assert_eq!(
+        JsonPatch::diff(&old, &new).to_string(),
+        r#"[{"op":"remove","path":"/c~0d"},{"op":"add","path":"/e","value":3}]"#
+    );
+}
+
+#[test]
+fn test_diff_then_apply_round_trips() {
+    let mut new = config();
+    new.name = "service".to_string();
+    new.tags = vec!["b".to_string(), "c".to_string(), "d".to_string()];
+    new.server.host = "example.com".to_string();
+    let patch = JsonPatch::diff(&config(), &new);
+
+    let mut value = config();
+    patch.apply(&mut value)?;
+    assert_eq!(value, new);
+}
+
+#[test]
+fn test_apply_parsed_patch() {
+    let patch: JsonPatch = r#"[
+        {"op": "test", "path": "/retries", "value": 3},
+        {"op": "replace", "path": "/retries", "value": 5},
+        {"op": "add", "path": "/tags/0", "value": "first"},
+        {"op": "add", "path": "/tags/-", "value": "last"},
+        {"op": "remove", "path": "/timeout"},
+        {"op": "copy", "from": "/server/host", "path": "/name"}
+    ]"#
+    .parse()?;
+
+    let mut value = config();
+    patch.apply(&mut value)?;
+    assert_eq!(value.retries, 5);
+    assert_eq!(value.tags, ["first", "a", "b", "last"]);
+    assert_eq!(value.timeout, None);
+    assert_eq!(value.name, "localhost");
+}
+
+#[test]
+fn test_apply_move() {
+    let patch: JsonPatch = r#"[{"op": "move", "from": "/a", "path": "/b"}]"#.parse()?;
+    let mut map = HashMap::from([("a".to_string(), 1)]);
+    patch.apply(&mut map)?;
+    assert_eq!(map, HashMap::from([("b".to_string(), 1)]));
+}
+
+#[test]
+fn test_failed_test_leaves_target() {
+    let patch: JsonPatch = r#"[
+        {"op": "replace", "path": "/retries", "value": 5},
+        {"op": "test", "path": "/name", "value": "other"}
+    ]"#
+    .parse()?;
+    let mut value = config();
+    let err = patch.apply(&mut value).unwrap_err();
+    assert!(matches!(err, JsonPatchError::TestFailed { index: 1, .. }));
+    assert_eq!(value, config());
+}
+
+#[test]
+fn test_missing_path() {
+    let patch: JsonPatch = r#"[{"op": "remove", "path": "/tags/5"}]"#.parse()?;
+    let err = patch.apply(&mut config()).unwrap_err();
+    assert!(matches!(err, JsonPatchError::PathNotFound { index: 0, .. }));
+
+    let patch: JsonPatch = r#"[{"op": "replace", "path": "/nope/deeper", "value": 1}]"#.parse()?;
+    assert!(patch.apply(&mut config()).is_err());
+}
+
+#[test]
+fn test_patched_document_of_wrong_type() {
+    let patch: JsonPatch = r#"[{"op": "replace", "path": "/retries", "value": "many"}]"#.parse()?;
+    let mut value = config();
+    let err = patch.apply(&mut value).unwrap_err();
+    assert!(matches!(err, JsonPatchError::Deserialize(_)));
+    assert_eq!(value, config());
+}
+
+#[test]
+fn test_invalid_patches() {
+    for patch in [
+        r#"{"op": "remove", "path": "/a"}"#,
+        r#"[{"op": "launch", "path": "/a"}]"#,
+        r#"[{"op": "add", "path": "/a"}]"#,
+        r#"[{"op": "move", "path": "/a"}]"#,
+        r#"[{"path": "/a"}]"#,
+    ] {
+        assert!(
+            matches!(
+                patch.parse::<JsonPatch>(),
+                Err(JsonPatchError::InvalidOperation { .. })
+            ),
+            "{patch}"
+        );
+    }
+    assert!(matches!(
+        "[{".parse::<JsonPatch>(),
+        Err(JsonPatchError::Syntax(_))
+    ));
+
+    let patch: JsonPatch =
+        r#"[{"op": "move", "from": "/server", "path": "/server/inner"}]"#.parse()?;
+    assert!(matches!(
+        patch.apply(&mut config()),
+        Err(JsonPatchError::InvalidOperation { index: 0, .. })
+    ));
+}
+
+#[test]
+fn test_patch_document_round_trips() {
+    let text = r#"[{"op":"move","from":"/a","path":"/b"},{"op":"test","path":"/b","value":[1,{"c":null}]}]"#;
+    let patch: JsonPatch = text.parse()?;
+    assert_eq!(patch.to_string(), text);
+    assert_eq!(patch.to_string().parse::<JsonPatch>()?, patch);
+}
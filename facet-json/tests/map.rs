@@ -47,3 +47,22 @@ fn test_hashmap_to_json() {
     let json = String::from_utf8(buffer).unwrap();
     assert_eq!(json, expected_json);
 }
+
+#[test]
+fn test_index_map_keeps_input_order() {
+    let json = r#"{"zebra":1,"apple":2,"mango":3}"#;
+
+    let map: indexmap::IndexMap<String, i32> = facet_json::from_str(json)?;
+    assert_eq!(
+        map.keys().map(String::as_str).collect::<Vec<_>>(),
+        ["zebra", "apple", "mango"]
+    );
+    assert_eq!(to_string(&map), json);
+}
+
+#[test]
+fn test_btree_map_is_written_sorted() {
+    let map: std::collections::BTreeMap<String, i32> =
+        facet_json::from_str(r#"{"zebra":1,"apple":2,"mango":3}"#)?;
+    assert_eq!(to_string(&map), r#"{"apple":2,"mango":3,"zebra":1}"#);
+}
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_json::{JsonPatchError, apply_merge_patch, merge_patch};
+use facet_testhelpers::test;
+
+#[derive(Facet, Clone, Debug, PartialEq)]
+struct Profile {
+    name: String,
+    nickname: Option<String>,
+    #[facet(default)]
+    age: u32,
+    address: Address,
+    tags: Vec<String>,
+}
+
+#[derive(Facet, Clone, Debug, PartialEq)]
+struct Address {
+    city: String,
+    zip: Option<String>,
+}
+
+fn profile() -> Profile {
+    Profile {
+        name: "Ada".to_string(),
+        nickname: Some("countess".to_string()),
+        age: 36,
+        address: Address {
+            city: "London".to_string(),
+            zip: Some("W1".to_string()),
+        },
+        tags: vec!["math".to_string()],
+    }
+}
+
+#[test]
+fn test_merge_patch_of_equal_values_is_empty() {
+    assert_eq!(merge_patch(&profile(), &profile()), "{}");
+}
+
+#[test]
+fn test_merge_patch_holds_changes_only() {
+    let mut new = profile();
+    new.nickname = None;
+    new.address.city = "Paris".to_string();
+    new.tags.push("poetry".to_string());
+    assert_eq!(
+        merge_patch(&profile(), &new),
+        r#"{"nickname":null,"address":{"city":"Paris"},"tags":["math","poetry"]}"#
+    );
+}
+
+#[test]
+fn test_merge_patch_round_trips() {
+    let mut new = profile();
+    new.name = "Augusta".to_string();
+    new.nickname = None;
+    new.address.zip = None;
+    new.tags.clear();
+
+    let mut value = profile();
+    apply_merge_patch(&mut value, &merge_patch(&profile(), &new))?;
+    assert_eq!(value, new);
+}
+
+#[test]
+fn test_apply_merges_nested_objects() {
+    let mut value = profile();
+    apply_merge_patch(&mut value, r#"{"address": {"zip": "EC1"}}"#)?;
+    assert_eq!(value.address.city, "London");
+    assert_eq!(value.address.zip.as_deref(), Some("EC1"));
+    assert_eq!(value.name, "Ada");
+}
+
+#[test]
+fn test_null_removes_and_defaults() {
+    let mut value = profile();
+    apply_merge_patch(&mut value, r#"{"nickname": null, "age": null}"#)?;
+    assert_eq!(value.nickname, None);
+    assert_eq!(value.age, 0);
+}
+
+#[test]
+fn test_null_on_required_field_fails() {
+    let mut value = profile();
+    let err = apply_merge_patch(&mut value, r#"{"name": null}"#).unwrap_err();
+    assert!(matches!(err, JsonPatchError::Deserialize(_)));
+    assert_eq!(value, profile());
+}
+
+#[test]
+fn test_apply_to_maps() {
+    let mut map = HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+    apply_merge_patch(&mut map, r#"{"a": null, "c": 3}"#)?;
+    assert_eq!(
+        map,
+        HashMap::from([("b".to_string(), 2), ("c".to_string(), 3)])
+    );
+}
+
+#[test]
+fn test_non_object_patch_replaces() {
+    let mut list = vec![1, 2, 3];
+    apply_merge_patch(&mut list, "[4]")?;
+    assert_eq!(list, [4]);
+    assert_eq!(merge_patch(&vec![1], &vec![2]), "[2]");
+}
+
+#[test]
+fn test_invalid_merge_patch() {
+    let mut value = profile();
+    assert!(matches!(
+        apply_merge_patch(&mut value, "{"),
+        Err(JsonPatchError::Syntax(_))
+    ));
+}
@@ -0,0 +1,66 @@
+use facet::Facet;
+use facet_json::{DeserErrorKind, from_str};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Settings {
+    name: String,
+    retries: u32,
+    #[facet(default)]
+    tags: Vec<String>,
+    #[facet(default = 30)]
+    timeout: u32,
+    proxy: Option<String>,
+}
+
+#[test]
+fn null_for_option_is_none() {
+    let settings: Settings =
+        from_str(r#"{"name": "a", "retries": 1, "proxy": null, "timeout": 5}"#)?;
+    assert_eq!(settings.proxy, None);
+}
+
+#[test]
+fn null_for_defaulted_field_is_default() {
+    let settings: Settings =
+        from_str(r#"{"name": "a", "retries": 1, "tags": null, "timeout": null}"#)?;
+    assert_eq!(
+        settings,
+        Settings {
+            name: "a".to_string(),
+            retries: 1,
+            tags: vec![],
+            timeout: 30,
+            proxy: None,
+        }
+    );
+}
+
+#[test]
+fn null_for_required_field_is_an_error() {
+    let json = r#"{"name": "a", "retries": null}"#;
+    let err = from_str::<Settings>(json).unwrap_err();
+    assert!(
+        matches!(err.kind, DeserErrorKind::UnexpectedNull { shape } if shape.is_type::<u32>()),
+        "{:?}",
+        err.kind
+    );
+    assert_eq!(&json[err.span.start()..err.span.end()], "null");
+
+    let err = from_str::<Settings>(r#"{"name": null, "retries": 1}"#).unwrap_err();
+    assert!(matches!(err.kind, DeserErrorKind::UnexpectedNull { .. }));
+}
+
+#[test]
+fn null_elsewhere() {
+    assert!(matches!(
+        from_str::<u32>("null").unwrap_err().kind,
+        DeserErrorKind::UnexpectedNull { .. }
+    ));
+    assert!(matches!(
+        from_str::<Vec<u32>>("[1, null]").unwrap_err().kind,
+        DeserErrorKind::UnexpectedNull { .. }
+    ));
+    assert_eq!(from_str::<Vec<Option<u32>>>("[1, null]")?, [Some(1), None]);
+    from_str::<()>("null")?;
+}
@@ -56,3 +56,91 @@ fn json_read_more_types() {
     assert!((test_struct.f32_val - std::f32::consts::PI).abs() < f32::EPSILON);
     assert!((test_struct.f64_val - std::f64::consts::PI).abs() < f64::EPSILON);
 }
+
+#[test]
+fn json_write_float_formats() {
+    #[derive(Facet)]
+    struct Reading {
+        single: f32,
+        double: f64,
+        tiny: f64,
+    }
+
+    let reading = Reading {
+        single: 0.1,
+        double: 2.5,
+        tiny: 1.5e-7,
+    };
+    let to_string = |format| {
+        let mut out = Vec::new();
+        let mut serializer = facet_json::JsonSerializer::new(&mut out).with_float_format(format);
+        facet_serialize::serialize_iterative(facet_reflect::Peek::new(&reading), &mut serializer)
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    };
+
+    assert_eq!(
+        to_string(facet_json::FloatFormat::Shortest),
+        r#"{"single":0.1,"double":2.5,"tiny":1.5e-7}"#
+    );
+    assert_eq!(
+        to_string(facet_json::FloatFormat::Fixed(3)),
+        r#"{"single":0.100,"double":2.500,"tiny":0.000}"#
+    );
+    assert_eq!(
+        to_string(facet_json::FloatFormat::Scientific { min: -3, max: 0 }),
+        r#"{"single":0.1,"double":2.5,"tiny":1.5e-7}"#
+    );
+    assert_eq!(
+        to_string(facet_json::FloatFormat::Scientific { min: 0, max: 0 }),
+        r#"{"single":1e-1,"double":2.5,"tiny":1.5e-7}"#
+    );
+}
+
+#[test]
+fn json_read_numbers_that_do_not_fit() {
+    use facet_json::{DeserErrorKind, NumericConversionReason};
+
+    #[derive(Facet, Debug)]
+    struct Small {
+        value: u8,
+    }
+
+    let kind = |json| from_str::<Small>(json).unwrap_err().kind;
+
+    assert_eq!(
+        kind(r#"{"value": 300}"#),
+        DeserErrorKind::NumericConversion {
+            from: "u64",
+            to: "u8",
+            value: "300".to_string(),
+            reason: NumericConversionReason::OutOfRange {
+                min: "0".to_string(),
+                max: "255".to_string(),
+            },
+        }
+    );
+    assert_eq!(
+        kind(r#"{"value": -1}"#),
+        DeserErrorKind::NumericConversion {
+            from: "i64",
+            to: "u8",
+            value: "-1".to_string(),
+            reason: NumericConversionReason::Negative,
+        }
+    );
+    assert_eq!(
+        kind(r#"{"value": 2.5}"#),
+        DeserErrorKind::NumericConversion {
+            from: "f64",
+            to: "u8",
+            value: "2.5".to_string(),
+            reason: NumericConversionReason::Fractional,
+        }
+    );
+
+    let err = from_str::<Small>(r#"{"value": 300}"#).unwrap_err();
+    let message = err.message().to_string();
+    assert!(message.contains("does not fit in"), "{message}");
+    assert!(message.contains("(0..=255)"), "{message}");
+}
@@ -0,0 +1,117 @@
+use facet::Facet;
+use facet_deserialize::{
+    DeserObserver, Expectation, Instruction, Outcome, PopReason, Scalar, Span, TraceEvent,
+    TraceLog, ValueReason,
+};
+use facet_json::JsonParser;
+use facet_testhelpers::test;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Point {
+    x: u32,
+}
+
+#[test]
+fn test_observer_records_steps() {
+    let trace = Arc::new(TraceLog::new());
+    let mut parser = JsonParser::new().with_observer(trace.clone());
+    let point: Point = parser.parse(r#"{"x": 7}"#)?;
+    assert_eq!(point, Point { x: 7 });
+
+    let events = trace.take();
+    assert_eq!(
+        events.first(),
+        Some(&TraceEvent::Instruction(Instruction::Value(
+            ValueReason::TopLevel
+        )))
+    );
+    assert_eq!(
+        events.last(),
+        Some(&TraceEvent::Instruction(Instruction::Pop(
+            PopReason::TopLevel
+        )))
+    );
+    assert!(events.contains(&TraceEvent::Outcome {
+        expectation: Expectation::ObjectVal,
+        outcome: Outcome::Scalar(Scalar::U64(7)),
+        span: Span::new(6, 1),
+    }));
+
+    let outcomes: Vec<Outcome> = events
+        .into_iter()
+        .filter_map(|event| match event {
+            TraceEvent::Outcome { outcome, .. } => Some(outcome),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        outcomes,
+        [
+            Outcome::ObjectStarted,
+            Outcome::Scalar(Scalar::String("x".into())),
+            Outcome::Scalar(Scalar::U64(7)),
+            Outcome::ObjectEnded,
+        ]
+    );
+}
+
+#[test]
+fn test_observer_sees_each_item_of_number_lists() {
+    let trace = Arc::new(TraceLog::new());
+    let mut parser = JsonParser::new().with_observer(trace.clone());
+    let numbers: Vec<u32> = parser.parse("[1, 2, 3]")?;
+    assert_eq!(numbers, [1, 2, 3]);
+
+    // Lists of numbers aren't filled in bulk while observed, so each item is its own value
+    let items = trace
+        .take()
+        .into_iter()
+        .filter(|event| *event == TraceEvent::Instruction(Instruction::Pop(PopReason::ListVal)))
+        .count();
+    assert_eq!(items, 3);
+}
+
+#[test]
+fn test_observer_records_error() {
+    let trace = Arc::new(TraceLog::new());
+    let mut parser = JsonParser::new().with_observer(trace.clone());
+    let error = parser.parse::<Point>(r#"{"x": true}"#).unwrap_err();
+
+    let events = trace.take();
+    let Some(TraceEvent::Error { span, .. }) = events.last() else {
+        panic!("expected the trace to end with an error, got {events:?}");
+    };
+    assert_eq!(*span, error.span);
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| matches!(event, TraceEvent::Error { .. }))
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_observer_defaults() {
+    // Observers only implement what they need
+    #[derive(Default)]
+    struct Counter(AtomicUsize);
+
+    impl DeserObserver for Counter {
+        fn on_instruction(&self, _instruction: &Instruction) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let counter = Arc::new(Counter::default());
+    let mut parser = JsonParser::new().with_observer(counter.clone());
+    let _: Vec<u32> = parser.parse("[1, 2]")?;
+    let first = counter.0.swap(0, Ordering::Relaxed);
+    assert!(first > 0);
+
+    // The parser keeps its observer between documents
+    let _: Vec<u32> = parser.parse("[1, 2]")?;
+    assert_eq!(counter.0.load(Ordering::Relaxed), first);
+}
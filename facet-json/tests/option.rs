@@ -62,3 +62,82 @@ fn test_from_json_with_nested_options() {
         Some(42)
     );
 }
+
+#[test]
+fn test_absent_null_and_present_are_distinct() {
+    #[derive(Facet, Debug, PartialEq)]
+    struct Patch {
+        nickname: Option<Option<String>>,
+        age: Option<u32>,
+    }
+
+    assert_eq!(
+        from_str::<Patch>("{}")?,
+        Patch {
+            nickname: None,
+            age: None
+        }
+    );
+    assert_eq!(
+        from_str::<Patch>(r#"{"nickname": null, "age": null}"#)?,
+        Patch {
+            nickname: Some(None),
+            age: None
+        }
+    );
+    assert_eq!(
+        from_str::<Patch>(r#"{"nickname": "Al", "age": 30}"#)?,
+        Patch {
+            nickname: Some(Some("Al".to_string())),
+            age: Some(30)
+        }
+    );
+}
+
+#[test]
+fn test_absent_option_in_enum_variant() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Change {
+        Rename { name: Option<Option<String>> },
+    }
+
+    assert_eq!(
+        from_str::<Change>(r#"{"Rename": {}}"#)?,
+        Change::Rename { name: None }
+    );
+    assert_eq!(
+        from_str::<Change>(r#"{"Rename": {"name": null}}"#)?,
+        Change::Rename { name: Some(None) }
+    );
+}
+
+#[test]
+fn test_absent_option_keeps_container_default() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(default)]
+    struct Settings {
+        theme: Option<String>,
+    }
+
+    impl Default for Settings {
+        fn default() -> Self {
+            Settings {
+                theme: Some("dark".to_string()),
+            }
+        }
+    }
+
+    assert_eq!(from_str::<Settings>("{}")?.theme.as_deref(), Some("dark"));
+}
+
+#[test]
+fn test_null_for_nested_option_at_top_level() {
+    assert_eq!(from_str::<Option<Option<u8>>>("null")?, Some(None));
+    assert_eq!(from_str::<Option<Option<u8>>>("5")?, Some(Some(5)));
+    assert_eq!(
+        from_str::<Vec<Option<Option<u8>>>>("[null, 1]")?,
+        [Some(None), Some(Some(1))]
+    );
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "rayon")]
+
+use facet::Facet;
+use facet_json::{to_string, to_string_parallel};
+use facet_testhelpers::test;
+
+#[derive(Facet)]
+struct Export {
+    name: String,
+    rows: Vec<Row>,
+    #[facet(skip_serializing_if = Option::is_none)]
+    note: Option<String>,
+    tags: Vec<String>,
+    raw: Vec<u8>,
+}
+
+#[derive(Facet)]
+struct Row {
+    id: u64,
+    label: String,
+    score: Option<f64>,
+    kind: Kind,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Kind {
+    Plain,
+    Tagged(String),
+    Pair { a: i32, b: i32 },
+}
+
+fn export(rows: u64) -> Export {
+    Export {
+        name: "nightly \"dump\"".to_string(),
+        rows: (0..rows)
+            .map(|i| Row {
+                id: i,
+                label: format!("row {i} — ok"),
+                score: (i % 3 == 0).then(|| i as f64 / 8.0),
+                kind: match i % 3 {
+                    0 => Kind::Plain,
+                    1 => Kind::Tagged(format!("t{i}")),
+                    _ => Kind::Pair { a: -1, b: i as i32 },
+                },
+            })
+            .collect(),
+        note: None,
+        tags: vec!["a".to_string(), "b".to_string()],
+        raw: vec![1, 2, 3],
+    }
+}
+
+#[test]
+fn struct_with_large_list_matches_sequential() {
+    for rows in [0, 1, 7, 1000, 4099] {
+        let data = export(rows);
+        assert_eq!(to_string_parallel(&data, 5)?, to_string(&data));
+    }
+}
+
+#[test]
+fn top_level_list_matches_sequential() {
+    let rows = export(3001).rows;
+    assert_eq!(to_string_parallel(&rows, 100)?, to_string(&rows));
+    assert_eq!(to_string_parallel(&Vec::<Row>::new(), 0)?, "[]");
+}
+
+#[test]
+fn below_threshold_is_sequential() {
+    let data = export(10);
+    assert_eq!(to_string_parallel(&data, 1_000_000)?, to_string(&data));
+    assert_eq!(to_string_parallel(&42u32, 0)?, "42");
+}
@@ -0,0 +1,36 @@
+use facet::Facet;
+use facet_json::JsonParser;
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Order {
+    id: u64,
+    items: Vec<Vec<String>>,
+    note: Option<String>,
+}
+
+#[test]
+fn parser_is_reusable() {
+    let mut parser = JsonParser::new();
+
+    for id in 0..3 {
+        let input = format!(r#"{{"id": {id}, "items": [["a"], ["b\n", "c"]], "note": null}}"#);
+        assert_eq!(
+            parser.parse::<Order>(&input).unwrap(),
+            Order {
+                id,
+                items: vec![
+                    vec!["a".to_string()],
+                    vec!["b\n".to_string(), "c".to_string()]
+                ],
+                note: None,
+            }
+        );
+
+        // A failed document doesn't affect the next one
+        assert!(parser.parse::<Order>(r#"{"id": 1, "items": [["#).is_err());
+        assert!(parser.parse_slice::<Vec<u8>>(b"[1, 2, 300]").is_err());
+    }
+
+    assert_eq!(parser.parse_slice::<Vec<u8>>(b"[1, 2, 3]")?, vec![1, 2, 3]);
+}
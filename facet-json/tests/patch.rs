@@ -0,0 +1,89 @@
+use facet::Facet;
+use facet_json::from_str_into;
+use facet_testhelpers::test;
+
+#[derive(Facet, Clone, Debug, PartialEq)]
+struct Config {
+    name: String,
+    retries: u32,
+    timeout: Option<u32>,
+    tags: Vec<String>,
+    server: Server,
+}
+
+#[derive(Facet, Clone, Debug, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+fn config() -> Config {
+    Config {
+        name: "app".to_string(),
+        retries: 3,
+        timeout: Some(30),
+        tags: vec!["a".to_string()],
+        server: Server {
+            host: "localhost".to_string(),
+            port: 80,
+        },
+    }
+}
+
+#[test]
+fn test_patch_keeps_missing_fields() {
+    let mut config = config();
+    from_str_into(&mut config, r#"{"retries": 5}"#)?;
+    assert_eq!(
+        config,
+        Config {
+            retries: 5,
+            ..self::config()
+        }
+    );
+
+    from_str_into(&mut config, "{}")?;
+    assert_eq!(
+        config,
+        Config {
+            retries: 5,
+            ..self::config()
+        }
+    );
+}
+
+#[test]
+fn test_patch_nested_struct() {
+    let mut config = config();
+    from_str_into(&mut config, r#"{"server": {"port": 8080}}"#)?;
+    assert_eq!(
+        config.server,
+        Server {
+            host: "localhost".to_string(),
+            port: 8080,
+        }
+    );
+}
+
+#[test]
+fn test_patch_replaces_lists_and_options() {
+    let mut config = config();
+    from_str_into(&mut config, r#"{"tags": ["b", "c"], "timeout": null}"#)?;
+    assert_eq!(config.tags, vec!["b".to_string(), "c".to_string()]);
+    assert_eq!(config.timeout, None);
+    assert_eq!(config.name, "app");
+}
+
+#[test]
+fn test_patch_error_leaves_target() {
+    let mut config = config();
+    assert!(from_str_into(&mut config, r#"{"name": "other", "retries": "many"}"#).is_err());
+    assert_eq!(config, self::config());
+}
+
+#[test]
+fn test_patch_scalar() {
+    let mut value = 1u64;
+    from_str_into(&mut value, "2")?;
+    assert_eq!(value, 2);
+}
@@ -0,0 +1,103 @@
+use facet::Facet;
+use facet_json::{PrettyPrinter, to_string_pretty};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn test_pretty_small_values_stay_on_one_line() {
+    let points = vec![Point { x: 1, y: 2 }, Point { x: 3, y: 4 }];
+    assert_eq!(
+        to_string_pretty(&points),
+        r#"[{"x": 1, "y": 2}, {"x": 3, "y": 4}]"#
+    );
+}
+
+#[test]
+fn test_pretty_wraps_what_does_not_fit() {
+    let points = vec![
+        Point { x: 1, y: 2 },
+        Point { x: 3, y: 4 },
+        Point { x: 5, y: 6 },
+    ];
+    assert_eq!(
+        PrettyPrinter::new().with_max_width(20).to_string(&points),
+        r#"[
+  {"x": 1, "y": 2},
+  {"x": 3, "y": 4},
+  {"x": 5, "y": 6}
+]"#
+    );
+
+    // With the comma after them, the first two points no longer fit
+    assert_eq!(
+        PrettyPrinter::new()
+            .with_max_width(20)
+            .with_indent(4)
+            .to_string(&points),
+        r#"[
+    {
+        "x": 1,
+        "y": 2
+    },
+    {
+        "x": 3,
+        "y": 4
+    },
+    {"x": 5, "y": 6}
+]"#
+    );
+}
+
+#[test]
+fn test_pretty_nested() {
+    let json = r#"{"name":"facet", "tags":["a","b"],
+        "nested":{"x":1,"y":["alpha","beta","gamma","delta"]}}"#;
+    assert_eq!(
+        PrettyPrinter::new().with_max_width(30).format(json),
+        r#"{
+  "name": "facet",
+  "tags": ["a", "b"],
+  "nested": {
+    "x": 1,
+    "y": [
+      "alpha",
+      "beta",
+      "gamma",
+      "delta"
+    ]
+  }
+}"#
+    );
+}
+
+#[test]
+fn test_pretty_empty_containers_and_strings() {
+    let json = r#"{"a":[],"b":{},"c":"x\"],{y","d":[""]}"#;
+    assert_eq!(
+        PrettyPrinter::new().with_max_width(0).format(json),
+        r#"{
+  "a": [],
+  "b": {},
+  "c": "x\"],{y",
+  "d": [
+    ""
+  ]
+}"#
+    );
+}
+
+#[test]
+fn test_pretty_width_counts_characters() {
+    // 14 characters, but 20 bytes
+    let json = r#"["ééé", "ééé"]"#;
+    assert_eq!(PrettyPrinter::new().with_max_width(14).format(json), json);
+    assert_eq!(
+        PrettyPrinter::new().with_max_width(13).format(json),
+        "[\n  \"ééé\",\n  \"ééé\"\n]"
+    );
+}
@@ -179,3 +179,23 @@ fn test_string_escaping() {
     println!("{json}");
     assert!(json.contains(r#""control":"Contains\u0001control"#));
 }
+
+#[test]
+fn json_char_is_a_one_character_string() {
+    #[derive(Facet, Debug, PartialEq)]
+    struct Glyph {
+        c: char,
+    }
+
+    assert_eq!(to_string(&Glyph { c: 'é' }), r#"{"c":"é"}"#);
+    assert_eq!(to_string(&Glyph { c: '"' }), r#"{"c":"\""}"#);
+    assert_eq!(from_str::<Glyph>(r#"{"c":"é"}"#)?, Glyph { c: 'é' });
+
+    for json in [r#"{"c":"ab"}"#, r#"{"c":""}"#] {
+        let err = from_str::<Glyph>(json).unwrap_err();
+        assert!(
+            err.to_string().contains("Expected a single character"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use facet::Facet;
+use facet_deserialize::IgnoredFieldLog;
+use facet_json::{JsonParser, Projection};
+use facet_testhelpers::test;
+use std::sync::Arc;
+
+#[derive(Debug, Facet, Default, PartialEq)]
+struct Metadata {
+    version: u32,
+    author: String,
+}
+
+#[derive(Debug, Facet, Default, PartialEq)]
+struct Item {
+    name: String,
+    price: f64,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Catalog {
+    metadata: Metadata,
+    items: Vec<Item>,
+    notes: Option<String>,
+}
+
+const CATALOG: &str = r#"{
+    "items": [
+        {"name": "tea", "price": 3.5, "tags": ["hot"]},
+        {"name": "cake", "price": 4.0, "tags": []}
+    ],
+    "notes": "closed on mondays",
+    "metadata": {"author": "jo", "version": 7}
+}"#;
+
+fn parser(paths: &[&str]) -> JsonParser {
+    JsonParser::new().with_projection(Arc::new(Projection::new(paths)))
+}
+
+#[test]
+fn nested_field() {
+    let catalog: Catalog = parser(&["metadata.version"]).parse(CATALOG)?;
+    assert_eq!(
+        catalog,
+        Catalog {
+            metadata: Metadata {
+                version: 7,
+                author: String::new(),
+            },
+            items: Vec::new(),
+            notes: None,
+        }
+    );
+}
+
+#[test]
+fn whole_field() {
+    let catalog: Catalog = parser(&["metadata", "notes"]).parse(CATALOG)?;
+    assert_eq!(catalog.metadata.author, "jo");
+    assert_eq!(catalog.notes.as_deref(), Some("closed on mondays"));
+    assert!(catalog.items.is_empty());
+}
+
+#[test]
+fn fields_of_list_items() {
+    let catalog: Catalog = parser(&["items.name", "items.price"]).parse(CATALOG)?;
+    assert_eq!(
+        catalog.items,
+        [
+            Item {
+                name: "tea".to_string(),
+                price: 3.5,
+                tags: Vec::new(),
+            },
+            Item {
+                name: "cake".to_string(),
+                price: 4.0,
+                tags: Vec::new(),
+            },
+        ]
+    );
+    assert_eq!(catalog.metadata, Metadata::default());
+}
+
+#[test]
+fn skipped_fields_are_not_checked() {
+    let input = r#"{"items": 42, "metadata": {"version": 1, "author": null}}"#;
+    let catalog: Catalog = parser(&["metadata.version"]).parse(input)?;
+    assert_eq!(catalog.metadata.version, 1);
+
+    let log = Arc::new(IgnoredFieldLog::new());
+    let mut parser = parser(&["metadata.version"]).with_ignored_fields(log.clone());
+    let _: Catalog = parser.parse(CATALOG)?;
+    assert!(log.is_empty());
+}
+
+#[test]
+fn skipped_fields_without_default() {
+    #[derive(Debug, Facet)]
+    struct Port(u16);
+
+    #[derive(Debug, Facet)]
+    struct Server {
+        host: String,
+        port: Port,
+    }
+
+    let input = r#"{"host": "a", "port": 80}"#;
+    let err = parser(&["host"]).parse::<Server>(input).unwrap_err();
+    assert!(err.to_string().contains("port"));
+}
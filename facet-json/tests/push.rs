@@ -0,0 +1,134 @@
+use facet::Facet;
+use facet_json::{JsonEvent, JsonPushParser, PushErrorKind};
+use facet_testhelpers::test;
+
+fn events(chunks: &[&[u8]]) -> Vec<JsonEvent> {
+    let mut parser = JsonPushParser::new();
+    let mut events = Vec::new();
+    for chunk in chunks {
+        for event in parser.feed(chunk) {
+            events.push(event.unwrap());
+        }
+    }
+    for event in parser.finish() {
+        events.push(event.unwrap());
+    }
+    events
+}
+
+#[test]
+fn chunk_boundaries_dont_matter() {
+    let input = r#" {"name": "café \"au lait\"", "sizes": [1, -2, 3.5e2, 18446744073709551616],
+        "hot": true, "sugar": null, "extra": {}} 42 "next" [] -7"#
+        .as_bytes();
+    let whole = events(&[input]);
+    assert_eq!(
+        &whole[..3],
+        [
+            JsonEvent::StartObject,
+            JsonEvent::Key("name".into()),
+            JsonEvent::String("café \"au lait\"".into()),
+        ]
+    );
+    assert_eq!(
+        whole
+            .iter()
+            .filter(|e| **e == JsonEvent::DocumentEnd)
+            .count(),
+        5
+    );
+    assert_eq!(whole[whole.len() - 2], JsonEvent::I64(-7));
+
+    for size in 1..=7 {
+        let chunks: Vec<&[u8]> = input.chunks(size).collect();
+        assert_eq!(events(&chunks), whole, "chunks of {size} bytes");
+    }
+}
+
+#[test]
+fn numbers_wait_for_a_delimiter() {
+    let mut parser = JsonPushParser::new();
+    assert_eq!(parser.feed(b"[12").count(), 1);
+    let rest: Vec<_> = parser.feed(b"34]").map(Result::unwrap).collect();
+    assert_eq!(
+        rest,
+        [
+            JsonEvent::U64(1234),
+            JsonEvent::EndArray,
+            JsonEvent::DocumentEnd
+        ]
+    );
+
+    assert_eq!(parser.feed(b"5").count(), 0);
+    assert!(!parser.is_idle());
+    let rest: Vec<_> = parser.finish().map(Result::unwrap).collect();
+    assert_eq!(rest, [JsonEvent::U64(5), JsonEvent::DocumentEnd]);
+    assert!(parser.is_idle());
+}
+
+#[test]
+fn errors() {
+    let first_error = |input: &[u8]| {
+        let mut parser = JsonPushParser::new();
+        if let Some(err) = parser.feed(input).find_map(Result::err) {
+            return err;
+        }
+        parser.finish().find_map(Result::err).unwrap()
+    };
+
+    let err = first_error(b"[1,]");
+    assert!(matches!(err.kind, PushErrorKind::UnexpectedToken { .. }));
+    assert_eq!(err.offset, 3);
+
+    let err = first_error(br#"{"a" 1}"#);
+    assert_eq!(
+        err.kind,
+        PushErrorKind::UnexpectedToken {
+            got: "1".into(),
+            wanted: "':'"
+        }
+    );
+
+    assert_eq!(first_error(b"[1, 2").kind, PushErrorKind::UnexpectedEof);
+    assert_eq!(first_error(b"tru").kind, PushErrorKind::UnexpectedEof);
+    assert_eq!(first_error(br#""abc"#).kind, PushErrorKind::UnexpectedEof);
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Request {
+    method: String,
+    id: u32,
+}
+
+#[test]
+fn deserialize_documents_as_they_complete() {
+    let stream = br#"{"method": "get", "id": 1}
+{"method": "put", "id": 2}
+"#;
+    let mut parser = JsonPushParser::new().with_documents(true);
+    let mut requests = Vec::new();
+    for chunk in stream.chunks(5) {
+        let mut events = parser.feed(chunk);
+        while let Some(event) = events.next() {
+            if event.unwrap() == JsonEvent::DocumentEnd {
+                let request: Request =
+                    facet_json::from_slice(events.last_document().unwrap()).unwrap();
+                requests.push(request);
+            }
+        }
+    }
+    assert!(parser.is_idle());
+    assert_eq!(
+        requests,
+        [
+            Request {
+                method: "get".into(),
+                id: 1
+            },
+            Request {
+                method: "put".into(),
+                id: 2
+            }
+        ]
+    );
+}
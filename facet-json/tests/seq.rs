@@ -0,0 +1,63 @@
+use facet::Facet;
+use facet_deserialize::DeserErrorKind;
+use facet_json::{from_slice_seq, from_str_seq, to_vec_seq};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Event {
+    id: u32,
+    name: String,
+}
+
+#[test]
+fn test_seq_round_trip() {
+    let events = [
+        Event {
+            id: 1,
+            name: "start".to_string(),
+        },
+        Event {
+            id: 2,
+            name: "line\nbreak".to_string(),
+        },
+    ];
+    let seq = to_vec_seq(&events)?;
+    assert_eq!(
+        seq,
+        b"\x1e{\"id\":1,\"name\":\"start\"}\n\x1e{\"id\":2,\"name\":\"line\\nbreak\"}\n"
+    );
+
+    // The errors borrow `seq`, so they can't be returned
+    let back = from_slice_seq::<Event>(&seq)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(back, events);
+}
+
+#[test]
+fn test_seq_skips_empty_texts() {
+    let values =
+        from_str_seq::<u32>("\x1e\x1e1\n\x1e \n\x1e2\n\x1e").collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(values, [1, 2]);
+}
+
+#[test]
+fn test_seq_recovers_from_bad_texts() {
+    let results: Vec<_> = from_str_seq::<Vec<u32>>("\x1e[1]\n\x1e[2, \x1e[3]\n\x1e[4]").collect();
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].as_ref().unwrap(), &[1]);
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().unwrap(), &[3]);
+    assert_eq!(results[3].as_ref().unwrap(), &[4]);
+}
+
+#[test]
+fn test_seq_truncated_scalars() {
+    let results: Vec<_> = from_str_seq::<u32>("\x1e12\n\x1e34\x1e56 ").collect();
+    assert_eq!(*results[0].as_ref().unwrap(), 12);
+    assert!(matches!(
+        results[1].as_ref().unwrap_err().kind,
+        DeserErrorKind::UnexpectedEof { .. }
+    ));
+    assert_eq!(*results[2].as_ref().unwrap(), 56);
+}
@@ -35,6 +35,22 @@ fn test_array_overflow() {
     }
 }
 
+#[test]
+fn test_array_overflow_points_at_the_element() {
+    let json = r#"{"values": [1, 2, 3, [4]]}"#;
+
+    let err = from_str::<SimpleArray>(json).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        facet_json::DeserErrorKind::ArrayOverflow {
+            index: 3,
+            max_len: 3,
+            ..
+        }
+    ));
+    assert_eq!(err.span.start(), 21);
+}
+
 #[derive(Debug, Facet, PartialEq)]
 struct NestedArraySimple {
     matrix: [[u64; 2]; 2],
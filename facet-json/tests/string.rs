@@ -41,3 +41,84 @@ fn test_strings() {
     let json = to_string(&test_struct);
     assert_eq!(json, r#"{"foo":"foo"}"#);
 }
+
+#[derive(Debug, PartialEq, Facet)]
+struct Borrowed<'a, 'b> {
+    name: &'a str,
+    note: std::borrow::Cow<'b, str>,
+}
+
+#[test]
+fn test_borrowed_strings_with_independent_lifetimes() {
+    let json = r#"{"name":"plain","note":"es\"caped"}"#;
+    let value: Borrowed = facet_json::from_str(json).unwrap();
+    assert_eq!(value.name, "plain");
+    assert_eq!(value.note, "es\"caped");
+    assert!(matches!(value.note, std::borrow::Cow::Owned(_)));
+
+    let json = r#"{"name":"plain","note":"plain too"}"#;
+    let value: Borrowed = facet_json::from_str(json).unwrap();
+    assert!(matches!(value.note, std::borrow::Cow::Borrowed(_)));
+    assert_eq!(to_string(&value), json);
+}
+
+#[test]
+fn test_escaped_str_cannot_borrow() {
+    let json = r#"{"name":"es\"caped","note":""}"#;
+    let err = facet_json::from_str::<Borrowed>(json).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        facet_json::DeserErrorKind::CannotBorrow { .. }
+    ));
+}
+
+#[test]
+fn test_nested_references() {
+    #[derive(Debug, Facet)]
+    struct Tagged<'a, 'b> {
+        tags: &'a [&'b str],
+        first: &'a &'b str,
+    }
+
+    let tags = ["x", "y"];
+    let value = Tagged {
+        tags: &tags,
+        first: &tags[0],
+    };
+    assert_eq!(to_string(&value), r#"{"tags":["x","y"],"first":"x"}"#);
+
+    // The slice has nowhere to live, only `&str` can borrow from the input
+    let err = facet_json::from_str::<Tagged>(r#"{"tags":["x"],"first":"x"}"#).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        facet_json::DeserErrorKind::CannotBorrow { .. }
+    ));
+}
+
+#[test]
+fn test_shared_and_boxed_strs() {
+    use std::collections::BTreeMap;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[derive(Debug, PartialEq, Facet)]
+    struct Strs {
+        arc: Arc<str>,
+        rc: Rc<str>,
+        boxed: Box<str>,
+        by_name: BTreeMap<Box<str>, Arc<str>>,
+    }
+
+    let json = r#"{"arc":"a\"","rc":"r","boxed":"b","by_name":{"k":"v"}}"#;
+    let strs: Strs = facet_json::from_str(json)?;
+    assert_eq!(
+        strs,
+        Strs {
+            arc: "a\"".into(),
+            rc: "r".into(),
+            boxed: "b".into(),
+            by_name: [("k".into(), "v".into())].into(),
+        }
+    );
+    assert_eq!(to_string(&strs), json);
+}
@@ -65,8 +65,8 @@ fn test_reading_flat_structs() {
         name: String,
         #[facet(flatten)]
         struct_: InnerStruct,
-        // #[facet(flatten)]
-        // enum_: InnerEnum,
+        #[facet(flatten)]
+        enum_: InnerEnum,
     }
 
     #[derive(Debug, PartialEq, Eq, facet::Facet)]
@@ -89,10 +89,10 @@ fn test_reading_flat_structs() {
     let expected1 = Outer {
         name: "test1".to_string(),
         struct_: InnerStruct { val: 1 },
-        // enum_: InnerEnum::Variant1 {
-        //     field1: "aaa".to_string(),
-        //     field2: "bbb".to_string(),
-        // },
+        enum_: InnerEnum::Variant1 {
+            field1: "aaa".to_string(),
+            field2: "bbb".to_string(),
+        },
     };
     assert_eq!(expected1, actual1);
 
@@ -101,7 +101,7 @@ fn test_reading_flat_structs() {
     let expected2 = Outer {
         name: "test2".to_string(),
         struct_: InnerStruct { val: 2 },
-        // enum_: InnerEnum::Variant2("ccc".to_string()),
+        enum_: InnerEnum::Variant2("ccc".to_string()),
     };
     assert_eq!(expected2, actual2);
 
@@ -111,11 +111,104 @@ fn test_reading_flat_structs() {
     let expected3 = Outer {
         name: "test3".to_string(),
         struct_: InnerStruct { val: 3 },
-        // enum_: InnerEnum::Variant3("ddd".to_string(), "eee".to_string()),
+        enum_: InnerEnum::Variant3("ddd".to_string(), "eee".to_string()),
     };
     assert_eq!(expected3, actual3);
 }
 
+#[test]
+fn test_reading_multiple_flat_structs() {
+    #[derive(Debug, PartialEq, facet::Facet)]
+    struct Request {
+        id: u32,
+        #[facet(flatten)]
+        auth: Auth,
+        #[facet(flatten)]
+        paging: Paging,
+        #[facet(flatten)]
+        trace: Trace,
+    }
+
+    #[derive(Debug, PartialEq, facet::Facet)]
+    struct Auth {
+        user: String,
+        #[facet(flatten)]
+        scope: Scope,
+    }
+
+    #[derive(Debug, PartialEq, facet::Facet)]
+    struct Scope {
+        admin: bool,
+    }
+
+    #[derive(Debug, PartialEq, facet::Facet)]
+    struct Paging {
+        offset: u64,
+        limit: u64,
+    }
+
+    #[derive(Debug, PartialEq, facet::Facet)]
+    struct Trace {
+        trace_id: Option<String>,
+    }
+
+    // Keys of different flattened structs can come in any order
+    let request: Request = facet_json::from_str(
+        r#"{"limit":10,"user":"amy","id":1,"admin":true,"offset":20,"unknown":[1]}"#,
+    )
+    .expect("Failed to parse JSON");
+    assert_eq!(
+        request,
+        Request {
+            id: 1,
+            auth: Auth {
+                user: "amy".to_string(),
+                scope: Scope { admin: true },
+            },
+            paging: Paging {
+                offset: 20,
+                limit: 10,
+            },
+            trace: Trace { trace_id: None },
+        }
+    );
+
+    let error = facet_json::from_str::<Request>(r#"{"id":1,"user":"amy","admin":true,"limit":1}"#)
+        .expect_err("offset is missing");
+    assert!(error.to_string().contains("Paging::offset"));
+}
+
+#[test]
+fn test_reading_flat_struct_precedence() {
+    #[derive(Debug, PartialEq, facet::Facet)]
+    struct Outer {
+        name: String,
+        #[facet(flatten)]
+        inner: Inner,
+    }
+
+    #[derive(Debug, PartialEq, facet::Facet)]
+    struct Inner {
+        #[facet(default)]
+        name: String,
+        val: u64,
+    }
+
+    // A field of the struct itself takes the key before a flattened one
+    let outer: Outer =
+        facet_json::from_str(r#"{"val":1,"name":"outer"}"#).expect("Failed to parse JSON");
+    assert_eq!(
+        outer,
+        Outer {
+            name: "outer".to_string(),
+            inner: Inner {
+                name: String::new(),
+                val: 1,
+            },
+        }
+    );
+}
+
 #[test]
 fn test_writing_flat_structs() {
     #[derive(facet::Facet)]
@@ -166,3 +259,96 @@ fn test_writing_flat_structs() {
     });
     assert_eq!(expected3, actual3);
 }
+
+#[test]
+fn json_write_struct_fields_sorted() {
+    #[derive(facet::Facet)]
+    struct Settings {
+        zone: String,
+        #[facet(rename = "Beta")]
+        beta: bool,
+        alpha: u32,
+        mode: Mode,
+    }
+
+    #[derive(facet::Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Mode {
+        Fast { workers: u32, burst: bool },
+    }
+
+    let settings = Settings {
+        zone: "eu".to_string(),
+        beta: true,
+        alpha: 3,
+        mode: Mode::Fast {
+            workers: 4,
+            burst: false,
+        },
+    };
+    let to_string = |order| {
+        let mut out = Vec::new();
+        let mut serializer = facet_json::JsonSerializer::new(&mut out).with_field_order(order);
+        facet_serialize::serialize_iterative(facet_reflect::Peek::new(&settings), &mut serializer)
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    };
+
+    assert_eq!(
+        to_string(facet_json::FieldOrder::Declaration),
+        r#"{"zone":"eu","Beta":true,"alpha":3,"mode":{"Fast":{"workers":4,"burst":false}}}"#
+    );
+    assert_eq!(
+        to_string(facet_json::FieldOrder::Alphabetical),
+        r#"{"Beta":true,"alpha":3,"mode":{"Fast":{"burst":false,"workers":4}},"zone":"eu"}"#
+    );
+    assert_eq!(
+        to_string(facet_json::FieldOrder::ReverseAlphabetical),
+        r#"{"zone":"eu","mode":{"Fast":{"workers":4,"burst":false}},"alpha":3,"Beta":true}"#
+    );
+}
+
+#[test]
+fn test_reading_flat_enums() {
+    #[derive(Debug, PartialEq, facet::Facet)]
+    struct Job {
+        name: String,
+        #[facet(flatten)]
+        mode: Mode,
+    }
+
+    #[derive(Debug, PartialEq, facet::Facet)]
+    #[repr(C)]
+    enum Mode {
+        Fast,
+        Slow { delay: u32 },
+    }
+
+    let fast = Job {
+        name: "a".to_string(),
+        mode: Mode::Fast,
+    };
+    let json = facet_json::to_string(&fast);
+    assert_eq!(json, r#"{"name":"a","Fast":"Fast"}"#);
+    assert_eq!(facet_json::from_str::<Job>(&json).unwrap(), fast);
+    assert_eq!(
+        facet_json::from_str::<Job>(r#"{"Fast":null,"name":"a"}"#).unwrap(),
+        fast
+    );
+
+    let slow = Job {
+        name: "b".to_string(),
+        mode: Mode::Slow { delay: 3 },
+    };
+    let json = facet_json::to_string(&slow);
+    assert_eq!(json, r#"{"name":"b","Slow":{"delay":3}}"#);
+    assert_eq!(facet_json::from_str::<Job>(&json).unwrap(), slow);
+    assert_eq!(
+        facet_json::from_str::<Job>(r#"{"Slow":{"delay":3},"name":"b"}"#).unwrap(),
+        slow
+    );
+
+    // Without a variant key, the flattened enum is missing
+    assert!(facet_json::from_str::<Job>(r#"{"name":"c"}"#).is_err());
+}
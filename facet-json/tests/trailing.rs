@@ -0,0 +1,56 @@
+use facet::Facet;
+use facet_deserialize::DeserErrorKind;
+use facet_json::{JsonParser, from_str, from_str_partial};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Event {
+    id: u32,
+}
+
+#[test]
+fn test_trailing_whitespace_is_allowed() {
+    assert_eq!(from_str::<Event>("{\"id\": 1}  \n\t")?, Event { id: 1 });
+}
+
+#[test]
+fn test_trailing_input_is_an_error() {
+    let errors = [
+        from_str::<Event>(r#"{"id": 1} {"id": 2}"#).unwrap_err(),
+        from_str::<Event>(r#"{"id": 1}}"#).unwrap_err(),
+        from_str::<Vec<u32>>("[1] x").unwrap_err(),
+        from_str::<u32>("1 2").unwrap_err(),
+    ];
+    for err in errors {
+        assert_eq!(err.kind, DeserErrorKind::TrailingData);
+    }
+
+    let err = from_str::<Event>(r#"{"id": 1}   oops"#).unwrap_err();
+    assert_eq!(err.span.start(), 12);
+}
+
+#[test]
+fn test_partial_returns_consumed_bytes() {
+    let input = r#"{"id": 1} {"id": 2}"#;
+    let (first, len) = from_str_partial::<Event>(input)?;
+    assert_eq!(first, Event { id: 1 });
+    assert_eq!(len, 9);
+
+    let (second, rest) = from_str_partial::<Event>(&input[len..])?;
+    assert_eq!(second, Event { id: 2 });
+    assert_eq!(len + rest, input.len());
+}
+
+#[test]
+fn test_parser_reads_concatenated_values() {
+    let input = b"{\"id\": 1}\n{\"id\": 2}\n{\"id\": 3}\n";
+    let mut parser = JsonParser::new();
+    let mut offset = 0;
+    let mut events = Vec::new();
+    while input[offset..].iter().any(|b| !b.is_ascii_whitespace()) {
+        let (event, len) = parser.parse_slice_partial::<Event>(&input[offset..])?;
+        events.push(event);
+        offset += len;
+    }
+    assert_eq!(events, [Event { id: 1 }, Event { id: 2 }, Event { id: 3 }]);
+}
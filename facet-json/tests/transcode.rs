@@ -0,0 +1,76 @@
+use facet::Facet;
+use facet_deserialize::DeserErrorKind;
+use facet_json::{JsonSerializer, TranscodeError, transcode};
+use facet_msgpack::MessagePackSerializer;
+use facet_testhelpers::test;
+
+fn json_to_json(input: &str) -> String {
+    let mut out = Vec::new();
+    transcode(input.as_bytes(), &mut JsonSerializer::new(&mut out)).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn transcode_json_to_json() {
+    assert_eq!(
+        json_to_json(
+            r#" { "name": "café", "tags": ["a", ["b"]], "n": -3, "x": 1.5,
+                 "ok": true, "none": null, "nested": { "empty": {} } } "#
+        ),
+        r#"{"name":"café","tags":["a",["b"]],"n":-3,"x":1.5,"ok":true,"none":null,"nested":{"empty":{}}}"#
+    );
+    assert_eq!(json_to_json("18446744073709551615"), "18446744073709551615");
+    assert_eq!(json_to_json("[]"), "[]");
+}
+
+#[derive(Facet)]
+struct Release {
+    name: String,
+    version: u32,
+    yanked: bool,
+    authors: Vec<String>,
+    checksum: Option<String>,
+}
+
+#[test]
+fn transcode_json_to_msgpack() {
+    let json = r#"{
+        "name": "facet",
+        "version": 27,
+        "yanked": false,
+        "authors": ["amos", "fasterthanlime"],
+        "checksum": null
+    }"#;
+
+    let mut msgpack = Vec::new();
+    transcode(
+        json.as_bytes(),
+        &mut MessagePackSerializer::new(&mut msgpack),
+    )?;
+
+    let typed = facet_msgpack::to_vec(&Release {
+        name: "facet".to_string(),
+        version: 27,
+        yanked: false,
+        authors: vec!["amos".to_string(), "fasterthanlime".to_string()],
+        checksum: None,
+    });
+    assert_eq!(msgpack, typed);
+}
+
+#[test]
+fn transcode_errors() {
+    let mut out = Vec::new();
+    let err = transcode(b"[1, 2", &mut JsonSerializer::new(&mut out)).unwrap_err();
+    assert!(matches!(
+        err,
+        TranscodeError::Deser(ref e) if matches!(e.kind, DeserErrorKind::UnexpectedEof { .. })
+    ));
+
+    let mut out = Vec::new();
+    let err = transcode(b"{1: 2}", &mut JsonSerializer::new(&mut out)).unwrap_err();
+    assert!(matches!(
+        err,
+        TranscodeError::Deser(ref e) if matches!(e.kind, DeserErrorKind::UnexpectedOutcome { .. })
+    ));
+}
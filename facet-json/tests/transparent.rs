@@ -155,3 +155,57 @@ fn transparent_string_as_map_key() {
     );
     assert_eq!(map.len(), 3);
 }
+
+/// A number of bytes, written like `10MB`
+#[derive(Facet, Debug, PartialEq)]
+#[facet(transparent, parse = parse_byte_size, format = format_byte_size)]
+struct ByteSize(u64);
+
+fn parse_byte_size(s: &str) -> Result<ByteSize, &'static str> {
+    let digits = s.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let number: u64 = digits.parse().map_err(|_| "expected a number of bytes")?;
+    let unit = match &s[digits.len()..] {
+        "" | "B" => 1,
+        "KB" => 1_000,
+        "MB" => 1_000_000,
+        "GB" => 1_000_000_000,
+        _ => return Err("expected a unit of B, KB, MB or GB"),
+    };
+    Ok(ByteSize(number * unit))
+}
+
+fn format_byte_size(size: &ByteSize, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    match size.0 {
+        0 => write!(f, "0B"),
+        n if n % 1_000_000 == 0 => write!(f, "{}MB", n / 1_000_000),
+        n if n % 1_000 == 0 => write!(f, "{}KB", n / 1_000),
+        n => write!(f, "{n}B"),
+    }
+}
+
+#[test]
+fn transparent_with_parse_and_format() {
+    #[derive(Facet, Debug, PartialEq)]
+    struct Limits {
+        upload: ByteSize,
+        cache: ByteSize,
+        spill: Option<ByteSize>,
+    }
+
+    let limits: Limits = from_str(r#"{"upload": "10MB", "cache": 2048, "spill": "512KB"}"#)?;
+    assert_eq!(
+        limits,
+        Limits {
+            upload: ByteSize(10_000_000),
+            cache: ByteSize(2048),
+            spill: Some(ByteSize(512_000)),
+        }
+    );
+
+    assert_eq!(
+        facet_json::to_string(&limits),
+        r#"{"upload":"10MB","cache":"2048B","spill":"512KB"}"#
+    );
+
+    assert!(from_str::<Limits>(r#"{"upload": "10XB", "cache": 1, "spill": null}"#).is_err());
+}
@@ -0,0 +1,49 @@
+use core::marker::PhantomData;
+
+use facet::Facet;
+use facet_json::{from_str, to_string};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Marker;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Tagged {
+    id: u32,
+    marker: Marker,
+    phantom: PhantomData<String>,
+    unit: (),
+}
+
+fn tagged() -> Tagged {
+    Tagged {
+        id: 7,
+        marker: Marker,
+        phantom: PhantomData,
+        unit: (),
+    }
+}
+
+#[test]
+fn unit_types_serialize_as_null() {
+    assert_eq!(to_string(&()), "null");
+    assert_eq!(to_string(&Marker), "null");
+    assert_eq!(to_string(&PhantomData::<u8>), "null");
+    assert_eq!(
+        to_string(&tagged()),
+        r#"{"id":7,"marker":null,"phantom":null,"unit":null}"#
+    );
+}
+
+#[test]
+fn unit_types_round_trip() {
+    let json = to_string(&tagged());
+    assert_eq!(from_str::<Tagged>(&json).unwrap(), tagged());
+    assert_eq!(from_str::<Marker>("null")?, Marker);
+    assert_eq!(from_str::<PhantomData<u8>>("null")?, PhantomData);
+}
+
+#[test]
+fn unit_fields_can_be_missing() {
+    assert_eq!(from_str::<Tagged>(r#"{"id": 7}"#)?, tagged());
+}
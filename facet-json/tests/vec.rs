@@ -158,3 +158,22 @@ fn test_vec_of_structs_missing_field() {
     #[cfg(not(miri))]
     insta::assert_debug_snapshot!(result);
 }
+
+#[test]
+fn json_read_scalar_vecs() {
+    let floats: Vec<f32> = from_str("[1, -2, 3.5, 1e2]")?;
+    assert_eq!(floats, vec![1.0, -2.0, 3.5, 100.0]);
+
+    let bools: Vec<Vec<bool>> = from_str("[[true, false], [], [true]]")?;
+    assert_eq!(bools, vec![vec![true, false], vec![], vec![true]]);
+
+    let options: Vec<Option<i8>> = from_str("[1, null, -1]")?;
+    assert_eq!(options, vec![Some(1), None, Some(-1)]);
+
+    assert!(from_str::<Vec<u8>>("[1, 2, 256]").is_err());
+    // Items read the usual way stay in order with the others
+    let mixed: Vec<u8> = from_str("[1, \"2\", 3]")?;
+    assert_eq!(mixed, vec![1, 2, 3]);
+
+    assert!(from_str::<Vec<i32>>("[1, 2.5]").is_err());
+}
@@ -0,0 +1,89 @@
+use alloc::string::String;
+use core::fmt;
+
+use facet_json::DeserError;
+
+/// Why a document couldn't be indexed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexError {
+    /// What is wrong
+    pub kind: IndexErrorKind,
+    /// The byte offset where it was found
+    pub offset: usize,
+}
+
+/// What is wrong with a document that couldn't be indexed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IndexErrorKind {
+    /// The document isn't UTF-8.
+    InvalidUtf8,
+    /// A byte that can't be there.
+    UnexpectedByte {
+        /// The byte found
+        got: u8,
+        /// What could be there instead
+        wanted: &'static str,
+    },
+    /// The document ended in the middle of a value.
+    UnexpectedEof {
+        /// What was still needed
+        wanted: &'static str,
+    },
+    /// Something starting like `true`, `false` or `null` that isn't one of them.
+    InvalidLiteral,
+    /// A backslash in a string that doesn't start a valid escape.
+    InvalidEscape,
+    /// A control character in a string, which has to be escaped.
+    ControlCharacter,
+    /// Something other than whitespace after the value.
+    TrailingCharacters,
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            IndexErrorKind::InvalidUtf8 => write!(f, "Invalid UTF-8")?,
+            IndexErrorKind::UnexpectedByte { got, wanted } => {
+                write!(f, "Unexpected byte 0x{got:02X}, wanted {wanted}")?
+            }
+            IndexErrorKind::UnexpectedEof { wanted } => {
+                write!(f, "Unexpected end of input, wanted {wanted}")?
+            }
+            IndexErrorKind::InvalidLiteral => write!(f, "Invalid literal")?,
+            IndexErrorKind::InvalidEscape => write!(f, "Invalid escape in string")?,
+            IndexErrorKind::ControlCharacter => write!(f, "Unescaped control character in string")?,
+            IndexErrorKind::TrailingCharacters => write!(f, "Trailing characters after the value")?,
+        }
+        write!(f, " at byte {}", self.offset)
+    }
+}
+
+impl core::error::Error for IndexError {}
+
+/// Why a part of a [`LazyDocument`](crate::LazyDocument) couldn't be read.
+#[derive(Debug)]
+pub enum LazyError<'input> {
+    /// Nothing is at the JSON pointer.
+    NotFound(String),
+    /// The value is there, but doesn't deserialize into the type. Its span is in the whole
+    /// document.
+    Deserialize(DeserError<'input, 'static>),
+}
+
+impl fmt::Display for LazyError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LazyError::NotFound(pointer) => write!(f, "No value at {pointer:?}"),
+            LazyError::Deserialize(err) => err.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for LazyError<'_> {}
+
+impl<'input> From<DeserError<'input, 'static>> for LazyError<'input> {
+    fn from(err: DeserError<'input, 'static>) -> Self {
+        LazyError::Deserialize(err)
+    }
+}
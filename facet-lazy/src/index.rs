@@ -0,0 +1,224 @@
+//! The structural index of a JSON document: where each value starts and ends, and how they
+//! nest, found in one pass without decoding anything.
+
+use alloc::vec::Vec;
+
+use crate::{IndexError, IndexErrorKind, Kind};
+
+/// A value of the document. Nodes are in document order, so the first child of a container
+/// is right after it, and the next sibling of a node is at its `next`.
+#[derive(Debug, Clone)]
+pub(crate) struct Node {
+    pub(crate) kind: Kind,
+    /// Byte offset of the value's first byte
+    pub(crate) start: usize,
+    /// Byte offset just past the value's last byte
+    pub(crate) end: usize,
+    /// The member name, for values of an object
+    pub(crate) key: Option<Key>,
+    /// Number of members or elements, for containers
+    pub(crate) len: usize,
+    /// Index of the node after this one and all the nodes in it
+    pub(crate) next: usize,
+}
+
+/// Where a member name is, without its quotes.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Key {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    /// Whether it has escapes, so it has to be decoded before it's compared
+    pub(crate) escaped: bool,
+}
+
+/// Indexes `input`, which must hold exactly one JSON value, maybe surrounded by whitespace.
+///
+/// Strings and literals are checked, but numbers are only checked once they're deserialized.
+/// Containers are tracked on a heap-allocated stack, so nesting doesn't overflow the call
+/// stack.
+pub(crate) fn index(input: &str) -> Result<Vec<Node>, IndexError> {
+    let mut indexer = Indexer {
+        bytes: input.as_bytes(),
+        pos: 0,
+        nodes: Vec::new(),
+        open: Vec::new(),
+    };
+    indexer.value(None)?;
+
+    while let Some(&container) = indexer.open.last() {
+        indexer.skip_whitespace();
+        let kind = indexer.nodes[container].kind;
+        let close = if kind == Kind::Object { b'}' } else { b']' };
+        if indexer.peek() == Some(close) {
+            indexer.pos += 1;
+            let next = indexer.nodes.len();
+            let node = &mut indexer.nodes[container];
+            node.end = indexer.pos;
+            node.next = next;
+            indexer.open.pop();
+            continue;
+        }
+
+        if indexer.nodes[container].len > 0 {
+            indexer.expect(b',', "',' or the end of the container")?;
+            indexer.skip_whitespace();
+        }
+        let key = if kind == Kind::Object {
+            if indexer.peek() != Some(b'"') {
+                return Err(indexer.unexpected("a member name"));
+            }
+            let key = indexer.string()?;
+            indexer.skip_whitespace();
+            indexer.expect(b':', "':'")?;
+            Some(key)
+        } else {
+            None
+        };
+        indexer.nodes[container].len += 1;
+        indexer.value(key)?;
+    }
+
+    indexer.skip_whitespace();
+    if indexer.pos < indexer.bytes.len() {
+        return Err(indexer.error(IndexErrorKind::TrailingCharacters));
+    }
+    Ok(indexer.nodes)
+}
+
+struct Indexer<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    nodes: Vec<Node>,
+    /// The containers whose end hasn't been reached yet, innermost last
+    open: Vec<usize>,
+}
+
+impl Indexer<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn error(&self, kind: IndexErrorKind) -> IndexError {
+        IndexError {
+            kind,
+            offset: self.pos,
+        }
+    }
+
+    fn unexpected(&self, wanted: &'static str) -> IndexError {
+        self.error(match self.peek() {
+            Some(got) => IndexErrorKind::UnexpectedByte { got, wanted },
+            None => IndexErrorKind::UnexpectedEof { wanted },
+        })
+    }
+
+    fn expect(&mut self, byte: u8, wanted: &'static str) -> Result<(), IndexError> {
+        if self.peek() != Some(byte) {
+            return Err(self.unexpected(wanted));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Indexes the value at the cursor. Scalars are read to their end, while containers are
+    /// only opened, for the caller to read their contents.
+    fn value(&mut self, key: Option<Key>) -> Result<(), IndexError> {
+        self.skip_whitespace();
+        let kind = match self.peek() {
+            Some(b'{') => Kind::Object,
+            Some(b'[') => Kind::Array,
+            Some(b'"') => Kind::String,
+            Some(b'-' | b'0'..=b'9') => Kind::Number,
+            Some(b't' | b'f') => Kind::Bool,
+            Some(b'n') => Kind::Null,
+            _ => return Err(self.unexpected("a value")),
+        };
+        let start = self.pos;
+        let node = self.nodes.len();
+        self.nodes.push(Node {
+            kind,
+            start,
+            end: start,
+            key,
+            len: 0,
+            next: node + 1,
+        });
+
+        match kind {
+            Kind::Object | Kind::Array => {
+                self.pos += 1;
+                self.open.push(node);
+                return Ok(());
+            }
+            Kind::String => {
+                self.string()?;
+            }
+            Kind::Number => {
+                while matches!(
+                    self.peek(),
+                    Some(b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E')
+                ) {
+                    self.pos += 1;
+                }
+            }
+            Kind::Bool | Kind::Null => {
+                let literal: &[u8] = match self.peek() {
+                    Some(b't') => b"true",
+                    Some(b'f') => b"false",
+                    _ => b"null",
+                };
+                if !self.bytes[self.pos..].starts_with(literal) {
+                    return Err(self.error(IndexErrorKind::InvalidLiteral));
+                }
+                self.pos += literal.len();
+            }
+        }
+        self.nodes[node].end = self.pos;
+        Ok(())
+    }
+
+    /// Reads the string at the cursor, returning where its contents are.
+    fn string(&mut self) -> Result<Key, IndexError> {
+        self.pos += 1;
+        let start = self.pos;
+        let mut escaped = false;
+        loop {
+            match self.peek() {
+                None => return Err(self.unexpected("the end of the string")),
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    escaped = true;
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => {
+                            self.pos += 1
+                        }
+                        Some(b'u') => {
+                            let digits = self.bytes.get(self.pos + 1..self.pos + 5);
+                            if !digits.is_some_and(|d| d.iter().all(u8::is_ascii_hexdigit)) {
+                                return Err(self.error(IndexErrorKind::InvalidEscape));
+                            }
+                            self.pos += 5;
+                        }
+                        _ => return Err(self.error(IndexErrorKind::InvalidEscape)),
+                    }
+                }
+                Some(0x00..=0x1f) => return Err(self.error(IndexErrorKind::ControlCharacter)),
+                Some(_) => self.pos += 1,
+            }
+        }
+        let end = self.pos;
+        self.pos += 1;
+        Ok(Key {
+            start,
+            end,
+            escaped,
+        })
+    }
+}
@@ -0,0 +1,292 @@
+#![no_std]
+#![warn(missing_docs)]
+#![warn(clippy::std_instead_of_core)]
+#![warn(clippy::std_instead_of_alloc)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use facet_core::Facet;
+use facet_deserialize::Span;
+use facet_json::DeserError;
+use facet_reflect::{HeapValue, Partial};
+
+mod error;
+pub use error::*;
+
+mod index;
+use index::Node;
+
+/// What kind of JSON value a [`LazyValue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// `{...}`
+    Object,
+    /// `[...]`
+    Array,
+    /// `"..."`
+    String,
+    /// A number, which isn't checked until it's deserialized
+    Number,
+    /// `true` or `false`
+    Bool,
+    /// `null`
+    Null,
+}
+
+/// A JSON document that is indexed once, then deserialized a part at a time.
+///
+/// Indexing finds where every value starts and ends, without decoding any of them. Then
+/// values are found by key, index or [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901),
+/// and only the ones that are read are deserialized, into any Facet type. Types like
+/// `Cow<str>` borrow from the document.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_lazy::LazyDocument;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Author {
+///     name: String,
+/// }
+///
+/// let doc = LazyDocument::new(r#"{"title": "Facet", "authors": [{"name": "Amos"}]}"#).unwrap();
+/// let title: String = doc.get("/title").unwrap();
+/// assert_eq!(title, "Facet");
+///
+/// let authors = doc.root().get("authors").unwrap();
+/// assert_eq!(authors.len(), 1);
+/// let first: Author = authors.at(0).unwrap().deserialize().unwrap();
+/// assert_eq!(first.name, "Amos");
+/// ```
+pub struct LazyDocument<'input> {
+    input: &'input str,
+    nodes: Vec<Node>,
+}
+
+impl<'input> LazyDocument<'input> {
+    /// Indexes a document holding one JSON value.
+    pub fn new(input: &'input str) -> Result<Self, IndexError> {
+        let nodes = index::index(input)?;
+        Ok(LazyDocument { input, nodes })
+    }
+
+    /// Indexes a document holding one JSON value, which must be UTF-8.
+    pub fn from_slice(input: &'input [u8]) -> Result<Self, IndexError> {
+        let input = core::str::from_utf8(input).map_err(|e| IndexError {
+            kind: IndexErrorKind::InvalidUtf8,
+            offset: e.valid_up_to(),
+        })?;
+        Self::new(input)
+    }
+
+    /// The whole document.
+    pub fn input(&self) -> &'input str {
+        self.input
+    }
+
+    /// The value the document holds.
+    pub fn root(&self) -> LazyValue<'_, 'input> {
+        LazyValue { doc: self, node: 0 }
+    }
+
+    /// The value at a JSON pointer like `/items/0/name`, where `~1` stands for `/` and `~0`
+    /// for `~` in keys. The empty pointer is the root.
+    pub fn pointer(&self, pointer: &str) -> Option<LazyValue<'_, 'input>> {
+        self.root().pointer(pointer)
+    }
+
+    /// Deserializes the value at a JSON pointer.
+    pub fn get<'facet, T>(&self, pointer: &str) -> Result<T, LazyError<'input>>
+    where
+        T: Facet<'facet>,
+        'input: 'facet,
+    {
+        let value = self
+            .pointer(pointer)
+            .ok_or_else(|| LazyError::NotFound(pointer.into()))?;
+        Ok(value.deserialize()?)
+    }
+
+    /// Moves an error from deserializing the value at `offset` into the whole document.
+    fn relocate<'shape>(
+        &self,
+        err: DeserError<'input, 'shape>,
+        offset: usize,
+    ) -> DeserError<'input, 'shape> {
+        DeserError {
+            input: Cow::Borrowed(self.input.as_bytes()),
+            span: Span::new(err.span.start() + offset, err.span.len()),
+            kind: err.kind,
+            source_id: err.source_id,
+        }
+    }
+}
+
+impl fmt::Debug for LazyDocument<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyDocument")
+            .field("len", &self.input.len())
+            .field("values", &self.nodes.len())
+            .finish()
+    }
+}
+
+/// A value in a [`LazyDocument`], which isn't deserialized until it's asked to.
+#[derive(Clone, Copy)]
+pub struct LazyValue<'doc, 'input> {
+    doc: &'doc LazyDocument<'input>,
+    node: usize,
+}
+
+impl<'doc, 'input> LazyValue<'doc, 'input> {
+    fn node(&self) -> &'doc Node {
+        &self.doc.nodes[self.node]
+    }
+
+    /// What kind of value it is.
+    pub fn kind(&self) -> Kind {
+        self.node().kind
+    }
+
+    /// Where the value is in the document, in bytes.
+    pub fn span(&self) -> Range<usize> {
+        self.node().start..self.node().end
+    }
+
+    /// The value's JSON text, as it is in the document.
+    pub fn raw(&self) -> &'input str {
+        &self.doc.input[self.span()]
+    }
+
+    /// The number of members of an object or elements of an array, or 0 for other values.
+    pub fn len(&self) -> usize {
+        self.node().len
+    }
+
+    /// Whether the value is an empty object or array, or isn't an object or array.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The member values of an object, or the elements of an array, in document order.
+    pub fn iter(&self) -> impl Iterator<Item = LazyValue<'doc, 'input>> + use<'doc, 'input> {
+        let doc = self.doc;
+        let first = LazyValue {
+            doc,
+            node: self.node + 1,
+        };
+        // The first child of a container is right after it, and each child is followed by
+        // its next sibling
+        core::iter::successors(Some(first), move |value| {
+            Some(LazyValue {
+                doc,
+                node: doc.nodes[value.node].next,
+            })
+        })
+        .take(self.len())
+    }
+
+    /// The members of an object, with their names, in document order.
+    pub fn entries(
+        &self,
+    ) -> impl Iterator<Item = (Cow<'input, str>, LazyValue<'doc, 'input>)> + use<'doc, 'input> {
+        self.iter().filter_map(|value| Some((value.key()?, value)))
+    }
+
+    /// The name of the member this value is, if it's in an object.
+    pub fn key(&self) -> Option<Cow<'input, str>> {
+        let key = self.node().key?;
+        let raw = &self.doc.input[key.start..key.end];
+        if !key.escaped {
+            return Some(Cow::Borrowed(raw));
+        }
+        // Escapes were checked by the index, so only lone surrogates can fail to decode
+        let quoted = &self.doc.input[key.start - 1..key.end + 1];
+        Some(facet_json::from_str::<String>(quoted).map_or(Cow::Borrowed(raw), Cow::Owned))
+    }
+
+    /// The member `name` of an object. If there are several, the last one, like when the
+    /// object is deserialized.
+    pub fn get(&self, name: &str) -> Option<LazyValue<'doc, 'input>> {
+        if self.kind() != Kind::Object {
+            return None;
+        }
+        self.iter()
+            .filter(|value| {
+                let key = self.doc.nodes[value.node].key;
+                match key {
+                    Some(key) if !key.escaped => &self.doc.input[key.start..key.end] == name,
+                    _ => value.key().is_some_and(|key| key == name),
+                }
+            })
+            .last()
+    }
+
+    /// The element `index` of an array.
+    pub fn at(&self, index: usize) -> Option<LazyValue<'doc, 'input>> {
+        if self.kind() != Kind::Array {
+            return None;
+        }
+        self.iter().nth(index)
+    }
+
+    /// The value at a JSON pointer from this one. See [`LazyDocument::pointer`].
+    pub fn pointer(&self, pointer: &str) -> Option<LazyValue<'doc, 'input>> {
+        if pointer.is_empty() {
+            return Some(*self);
+        }
+        let mut value = *self;
+        for token in pointer.strip_prefix('/')?.split('/') {
+            let token: Cow<'_, str> = if token.contains('~') {
+                Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+            } else {
+                Cow::Borrowed(token)
+            };
+            value = match value.kind() {
+                Kind::Object => value.get(&token)?,
+                Kind::Array => value.at(token.parse().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+
+    /// Deserializes the value. Errors point into the whole document.
+    pub fn deserialize<'facet, 'shape, T>(&self) -> Result<T, DeserError<'input, 'shape>>
+    where
+        T: Facet<'facet>,
+        'input: 'facet,
+    {
+        facet_json::from_str(self.raw()).map_err(|err| self.doc.relocate(err, self.node().start))
+    }
+
+    /// Deserializes the value into a partial value, for types only known by their shape.
+    pub fn deserialize_into<'facet, 'shape>(
+        &self,
+        wip: Partial<'facet, 'shape>,
+    ) -> Result<HeapValue<'facet, 'shape>, DeserError<'input, 'shape>>
+    where
+        'input: 'facet,
+        'shape: 'input,
+    {
+        facet_json::from_slice_wip(self.raw().as_bytes(), wip)
+            .map_err(|err| self.doc.relocate(err, self.node().start))
+    }
+}
+
+impl fmt::Debug for LazyValue<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyValue")
+            .field("kind", &self.kind())
+            .field("span", &self.span())
+            .finish()
+    }
+}
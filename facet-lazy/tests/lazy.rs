@@ -0,0 +1,152 @@
+use std::borrow::Cow;
+
+use facet::Facet;
+use facet_lazy::{IndexErrorKind, Kind, LazyDocument, LazyError};
+use facet_reflect::Partial;
+use facet_testhelpers::test;
+
+const CATALOG: &str = r#"{
+    "metadata": {"version": 7, "author": "jo"},
+    "items": [
+        {"name": "tea", "price": 3.5, "tags": ["hot", "drink"]},
+        {"name": "cake", "price": 4, "tags": []}
+    ],
+    "a/b": true,
+    "m~n": null,
+    "\u0065scaped": "yes"
+}"#;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Item {
+    name: String,
+    price: f64,
+    tags: Vec<String>,
+}
+
+#[test]
+fn navigation() {
+    let doc = LazyDocument::new(CATALOG)?;
+    let root = doc.root();
+    assert_eq!(root.kind(), Kind::Object);
+    assert_eq!(root.len(), 5);
+
+    let keys: Vec<_> = root.entries().map(|(key, _)| key).collect();
+    assert_eq!(keys, ["metadata", "items", "a/b", "m~n", "escaped"]);
+
+    let items = root.get("items").unwrap();
+    assert_eq!(items.kind(), Kind::Array);
+    assert_eq!(items.len(), 2);
+    assert_eq!(items.at(1).unwrap().get("price").unwrap().raw(), "4");
+    assert!(items.at(2).is_none());
+    assert!(items.get("name").is_none());
+
+    let kinds: Vec<_> = items.at(0).unwrap().iter().map(|v| v.kind()).collect();
+    assert_eq!(kinds, [Kind::String, Kind::Number, Kind::Array]);
+    assert!(root.get("escaped").is_some());
+}
+
+#[test]
+fn pointers() {
+    let doc = LazyDocument::new(CATALOG)?;
+    assert_eq!(doc.pointer("").unwrap().span(), doc.root().span());
+    assert_eq!(doc.pointer("/items/0/tags/1").unwrap().raw(), r#""drink""#);
+    assert_eq!(doc.pointer("/a~1b").unwrap().kind(), Kind::Bool);
+    assert_eq!(doc.pointer("/m~0n").unwrap().kind(), Kind::Null);
+    assert!(doc.pointer("/items/x").is_none());
+    assert!(doc.pointer("/metadata/version/0").is_none());
+    assert!(doc.pointer("items").is_none());
+}
+
+#[test]
+fn deserialize_parts() {
+    let doc = LazyDocument::new(CATALOG)?;
+    let version: u32 = doc.get("/metadata/version")?;
+    assert_eq!(version, 7);
+
+    let item: Item = doc.get("/items/1")?;
+    assert_eq!(
+        item,
+        Item {
+            name: "cake".to_string(),
+            price: 4.0,
+            tags: Vec::new(),
+        }
+    );
+
+    let author: Cow<'_, str> = doc.get("/metadata/author")?;
+    assert_eq!(author, "jo");
+
+    let names: Vec<String> = doc
+        .pointer("/items")
+        .unwrap()
+        .iter()
+        .map(|item| item.get("name").unwrap().deserialize())
+        .collect::<Result<_, _>>()?;
+    assert_eq!(names, ["tea", "cake"]);
+}
+
+#[test]
+fn deserialize_by_shape() {
+    let doc = LazyDocument::new(CATALOG)?;
+    let wip = Partial::alloc_shape(Item::SHAPE)?;
+    let item = doc.pointer("/items/0").unwrap().deserialize_into(wip)?;
+    assert_eq!(item.materialize::<Item>()?.tags, ["hot", "drink"]);
+}
+
+#[test]
+fn errors_point_into_the_document() {
+    let doc = LazyDocument::new(CATALOG)?;
+    let err = doc.get::<u32>("/metadata/author").unwrap_err();
+    let err = match err {
+        LazyError::Deserialize(err) => err,
+        err => panic!("expected a deserialization error, got {err}"),
+    };
+    let author = CATALOG.find(r#""jo""#).unwrap();
+    assert_eq!(err.span.start(), author);
+    assert_eq!(err.input.len(), CATALOG.len());
+
+    assert!(matches!(
+        doc.get::<u32>("/metadata/missing"),
+        Err(LazyError::NotFound(pointer)) if pointer == "/metadata/missing"
+    ));
+}
+
+#[test]
+fn invalid_documents() {
+    for (input, kind, offset) in [
+        ("", IndexErrorKind::UnexpectedEof { wanted: "a value" }, 0),
+        (
+            r#"{"a" 1}"#,
+            IndexErrorKind::UnexpectedByte {
+                got: b'1',
+                wanted: "':'",
+            },
+            5,
+        ),
+        (
+            "[1, 2",
+            IndexErrorKind::UnexpectedEof {
+                wanted: "',' or the end of the container",
+            },
+            5,
+        ),
+        ("[tru]", IndexErrorKind::InvalidLiteral, 1),
+        (r#""\q""#, IndexErrorKind::InvalidEscape, 2),
+        ("\"a\nb\"", IndexErrorKind::ControlCharacter, 2),
+        ("{} {}", IndexErrorKind::TrailingCharacters, 3),
+    ] {
+        let err = LazyDocument::new(input).unwrap_err();
+        assert_eq!((err.kind, err.offset), (kind, offset), "{input:?}");
+    }
+
+    let err = LazyDocument::from_slice(b"\"\xff\"").unwrap_err();
+    assert_eq!((err.kind, err.offset), (IndexErrorKind::InvalidUtf8, 1));
+}
+
+#[test]
+fn deep_nesting() {
+    let depth = 100_000;
+    let input = "[".repeat(depth) + &"]".repeat(depth);
+    let doc = LazyDocument::new(&input)?;
+    assert_eq!(doc.root().len(), 1);
+}
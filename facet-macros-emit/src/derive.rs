@@ -1,7 +1,7 @@
 use facet_macros_parse::{ToTokens, *};
 use quote::{TokenStreamExt as _, quote};
 
-use crate::{LifetimeName, RenameRule, process_enum, process_struct};
+use crate::{LifetimeName, PFacetAttr, PStructField, RenameRule, process_enum, process_struct};
 
 pub fn facet_macros(input: TokenStream) -> TokenStream {
     let mut i = input.to_token_iter();
@@ -13,10 +13,8 @@ pub fn facet_macros(input: TokenStream) -> TokenStream {
             AdtDecl::Enum(parsed) => process_enum::process_enum(parsed),
         },
         Err(err) => {
-            panic!(
-                "Could not parse type declaration: {}\nError: {}",
-                input, err
-            );
+            let message = format!("Could not parse type declaration: {}", err);
+            quote! { compile_error!(#message); }
         }
     }
 }
@@ -34,9 +32,106 @@ pub(crate) fn generate_static_decl(type_name: &Ident) -> TokenStream {
     }
 }
 
+/// Types of the fields whose shapes come from their own `Facet` impl, so all but opaque ones
+pub(crate) fn facet_field_types<'a>(
+    fields: impl IntoIterator<Item = &'a PStructField>,
+) -> Vec<&'a TokenStream> {
+    fields
+        .into_iter()
+        .filter(|field| {
+            !field
+                .attrs
+                .facet
+                .iter()
+                .any(|attr| matches!(attr, PFacetAttr::Opaque))
+        })
+        .map(|field| &field.ty)
+        .collect()
+}
+
+/// How the field types of a container use one of its type parameters
+#[derive(Default)]
+struct ParamUsage {
+    /// The parameter shows up as a type of its own, e.g. `T` or `Vec<T>`
+    direct: bool,
+    /// The parameter shows up through an associated type, e.g. `T::Output`
+    projected: bool,
+}
+
+impl ParamUsage {
+    fn of(name: &Ident, field_types: &[&TokenStream]) -> Self {
+        let mut usage = Self::default();
+        for ty in field_types {
+            usage.visit(name, (*ty).clone());
+        }
+        usage
+    }
+
+    fn visit(&mut self, name: &Ident, ty: TokenStream) {
+        let tokens: Vec<TokenTree> = ty.into_iter().collect();
+        for (index, token) in tokens.iter().enumerate() {
+            match token {
+                TokenTree::Group(group) => self.visit(name, group.stream()),
+                TokenTree::Ident(ident) if ident == name => {
+                    // `some::path::T` names another type, `'T` is a lifetime
+                    let previous = index.checked_sub(1).map(|index| &tokens[index]);
+                    if matches!(previous, Some(TokenTree::Punct(punct)) if matches!(punct.as_char(), ':' | '\''))
+                    {
+                        continue;
+                    }
+                    match tokens.get(index + 1) {
+                        Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {
+                            self.projected = true
+                        }
+                        Some(TokenTree::Ident(next)) if next == "as" => self.projected = true,
+                        _ => self.direct = true,
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Parameters only reached through associated types don't need to implement `Facet`
+    /// themselves, the associated types do
+    fn needs_facet(&self) -> bool {
+        self.direct || !self.projected
+    }
+}
+
+/// Field types that reach into a type parameter through an associated type, which need a
+/// `Facet` bound of their own since the parameter's bound doesn't cover them
+fn projected_field_types<'a>(
+    generics: Option<&GenericParams>,
+    field_types: &[&'a TokenStream],
+) -> Vec<&'a TokenStream> {
+    let Some(generics) = generics else {
+        return Vec::new();
+    };
+    let mut projected: Vec<&TokenStream> = Vec::new();
+    for ty in field_types {
+        let reaches_param = generics.params.0.iter().any(|p| match &p.value {
+            GenericParam::Type { name, .. } => ParamUsage::of(name, &[*ty]).projected,
+            _ => false,
+        });
+        let seen = projected
+            .iter()
+            .any(|other| other.to_string() == ty.to_string());
+        if reaches_param && !seen {
+            projected.push(ty);
+        }
+    }
+    projected
+}
+
+/// Builds the where clause of the impl: the user's own predicates, verbatim, followed by the
+/// bounds the derive needs on generic parameters and on field types like `T::Output`.
+///
+/// `field_types` are the types of the fields whose shapes are taken through `Facet`.
 pub(crate) fn build_where_clauses(
     where_clauses: Option<&WhereClauses>,
     generics: Option<&GenericParams>,
+    field_types: &[&TokenStream],
 ) -> TokenStream {
     let mut where_clause_tokens = TokenStream::new();
     let mut has_clauses = false;
@@ -72,13 +167,27 @@ pub(crate) fn build_where_clauses(
                     if has_clauses {
                         where_clause_tokens.extend(quote! { , });
                     }
-                    where_clause_tokens.extend(quote! { #name: ::facet::Facet<'__facet> });
+                    if ParamUsage::of(name, field_types).needs_facet() {
+                        where_clause_tokens.extend(quote! { #name: ::facet::Facet<'__facet> });
+                    } else {
+                        // `Facet<'__facet>` needs the type to outlive `'__facet`, and so
+                        // do the parameters it's generic over
+                        where_clause_tokens.extend(quote! { #name: '__facet });
+                    }
                     has_clauses = true;
                 }
             }
         }
     }
 
+    for ty in projected_field_types(generics, field_types) {
+        if has_clauses {
+            where_clause_tokens.extend(quote! { , });
+        }
+        where_clause_tokens.extend(quote! { #ty: ::facet::Facet<'__facet> });
+        has_clauses = true;
+    }
+
     if !has_clauses {
         quote! {}
     } else {
@@ -86,7 +195,10 @@ pub(crate) fn build_where_clauses(
     }
 }
 
-pub(crate) fn build_type_params(generics: Option<&GenericParams>) -> TokenStream {
+pub(crate) fn build_type_params(
+    generics: Option<&GenericParams>,
+    field_types: &[&TokenStream],
+) -> TokenStream {
     let mut type_params = Vec::new();
     if let Some(generics) = generics {
         for p in &generics.params.0 {
@@ -98,6 +210,9 @@ pub(crate) fn build_type_params(generics: Option<&GenericParams>) -> TokenStream
                     // ignore for now
                 }
                 GenericParam::Type { name, .. } => {
+                    if !ParamUsage::of(name, field_types).needs_facet() {
+                        continue;
+                    }
                     let name_str = name.to_string();
                     type_params.push(quote! {
                         ::facet::TypeParam {
@@ -122,6 +237,7 @@ pub(crate) fn build_type_params(generics: Option<&GenericParams>) -> TokenStream
 pub(crate) fn generate_type_name_fn(
     type_name: &Ident,
     generics: Option<&GenericParams>,
+    field_types: &[&TokenStream],
 ) -> TokenStream {
     let type_name_str = type_name.to_string();
 
@@ -133,6 +249,11 @@ pub(crate) fn generate_type_name_fn(
             GenericParam::Const { name, .. } => Some(quote! {
                 write!(f, "{:?}", #name)?;
             }),
+            GenericParam::Type { name, .. } if !ParamUsage::of(name, field_types).needs_facet() => {
+                Some(quote! {
+                    write!(f, "{}", ::core::any::type_name::<#name>())?;
+                })
+            }
             GenericParam::Type { name, .. } => Some(quote! {
                 <#name as ::facet::Facet>::SHAPE.vtable.type_name()(f, opts)?;
             }),
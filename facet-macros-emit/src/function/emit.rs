@@ -74,13 +74,32 @@ pub fn generate_function_shape(parsed: FunctionSignature) -> TokenStream {
         .map(|doc| quote! { #doc })
         .collect();
 
+    // Types that don't implement `Facet` still get a name, but no shape
+    let fn_type = |ty: &TokenStream| {
+        let name = type_name(ty.clone());
+        quote! {
+            ::facet::FnType::new(#name, || {
+                use ::facet::spez::*;
+                (&&SpezEmpty::<#ty>::SPEZ).spez_shape()
+            })
+        }
+    };
+    let param_types: Vec<_> = types.iter().map(fn_type).collect();
+    let return_fn_type = fn_type(&return_type);
+
     let shape_definition = quote! {
         pub fn shape #generics () -> FunctionShape<( #( #types ),* ), #return_type, #generics_type> {
             FunctionShape::new(
                 #fn_name_str,
                 #arity,
                 &[ #( #names ),* ],
-                &[ #( #documentation_lines ),* ]
+                &[ #( #documentation_lines ),* ],
+                ::facet::FnShape::new(
+                    #fn_name_str,
+                    const { &[ #( ::facet::FnParam::new(#names, #param_types) ),* ] },
+                    #return_fn_type,
+                    &[ #( #documentation_lines ),* ],
+                ),
             )
         }
     };
@@ -98,6 +117,7 @@ pub fn generate_function_shape(parsed: FunctionSignature) -> TokenStream {
                 pub param_count: usize,
                 pub param_names: &'static [&'static str],
                 pub documentation: &'static [&'static str],
+                fn_shape: ::facet::FnShape<'static>,
                 _args: core::marker::PhantomData<Args>,
                 _ret: core::marker::PhantomData<Ret>,
                 _generics: core::marker::PhantomData<Generics>,
@@ -109,12 +129,14 @@ pub fn generate_function_shape(parsed: FunctionSignature) -> TokenStream {
                     param_count: usize,
                     param_names: &'static [&'static str],
                     documentation: &'static [&'static str],
+                    fn_shape: ::facet::FnShape<'static>,
                 ) -> Self {
                     Self {
                         name,
                         param_count,
                         param_names,
                         documentation,
+                        fn_shape,
                         _args: core::marker::PhantomData,
                         _ret: core::marker::PhantomData,
                         _generics: core::marker::PhantomData,
@@ -122,6 +144,14 @@ pub fn generate_function_shape(parsed: FunctionSignature) -> TokenStream {
                 }
             }
 
+            impl<Args, Ret, Generics> core::ops::Deref for FunctionShape<Args, Ret, Generics> {
+                type Target = ::facet::FnShape<'static>;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.fn_shape
+                }
+            }
+
             #shape_definition
         }
 
@@ -137,6 +167,50 @@ pub fn generate_function_shape(parsed: FunctionSignature) -> TokenStream {
     out
 }
 
+/// Writes a type the way it's usually written, e.g. `Vec<Option<u64>>` and not
+/// `Vec < Option < u64 > >` like `TokenStream`'s `Display` does.
+fn type_name(ty: TokenStream) -> String {
+    use facet_macros_parse::{Delimiter, TokenTree};
+
+    fn push(out: &mut String, ty: TokenStream) {
+        for tt in ty {
+            match tt {
+                TokenTree::Ident(ident) => {
+                    if out.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                        out.push(' ');
+                    }
+                    out.push_str(&ident.to_string());
+                }
+                TokenTree::Literal(literal) => out.push_str(&literal.to_string()),
+                TokenTree::Punct(punct) => match punct.as_char() {
+                    ',' => out.push_str(", "),
+                    ';' => out.push_str("; "),
+                    '=' => out.push_str(" = "),
+                    '-' => out.push_str(" -"),
+                    '>' if out.ends_with('-') => out.push_str("> "),
+                    '+' => out.push_str(" + "),
+                    c => out.push(c),
+                },
+                TokenTree::Group(group) => {
+                    let (open, close) = match group.delimiter() {
+                        Delimiter::Parenthesis => ("(", ")"),
+                        Delimiter::Bracket => ("[", "]"),
+                        Delimiter::Brace => ("{", "}"),
+                        Delimiter::None => ("", ""),
+                    };
+                    out.push_str(open);
+                    push(out, group.stream());
+                    out.push_str(close);
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    push(&mut out, ty);
+    out
+}
+
 fn generate_fn_shape_call(parsed: ParsedFnShapeInput) -> TokenStream {
     let fn_name = parsed.name;
     let generic_args = parsed.generics;
@@ -11,6 +11,7 @@ pub use parsed::*;
 
 mod process_enum;
 mod process_struct;
+mod validate;
 
 mod derive;
 pub use derive::*;
@@ -1,5 +1,5 @@
 use crate::{BoundedGenericParams, RenameRule};
-use facet_macros_parse::{Ident, ReprInner, ToTokens, TokenStream};
+use facet_macros_parse::{AttributeError, Ident, ReprInner, Span, ToTokens, TokenStream};
 use quote::quote;
 
 /// For struct fields, they can either be identifiers (`my_struct.foo`)
@@ -59,6 +59,10 @@ pub enum PFacetAttr {
     /// `#[facet(deny_unknown_fields)]`
     DenyUnknownFields,
 
+    /// Valid in container (enums)
+    /// `#[facet(discriminant)]` — serialize unit variants as their discriminant integers
+    Discriminant,
+
     /// Valid in field
     /// `#[facet(default = expr)]` — when deserializing and missing, use `fn_name` to provide a default value
     DefaultEquals { expr: TokenStream },
@@ -71,7 +75,7 @@ pub enum PFacetAttr {
     /// Valid in field, enum variant, container
     /// An arbitrary/unknown string, like,
     /// `#[facet(bleh)]`
-    Arbitrary { content: String },
+    Arbitrary { content: String, span: Span },
 
     /// Valid in container
     /// `#[facet(rename_all = "rule")]` — rename all fields following a rule
@@ -88,15 +92,35 @@ pub enum PFacetAttr {
     /// Valid in container
     /// `#[facet(type_tag = "com.example.MyType")]` — identify type by tag and serialize with this tag
     TypeTag { content: String },
+
+    /// Valid in container (structs)
+    /// `#[facet(parse = path::to::fn)]` — a `fn(&str) -> Result<Self, &'static str>` that
+    /// reads the type from a string, like `"10MB"`
+    Parse { expr: TokenStream },
+
+    /// Valid in container (structs)
+    /// `#[facet(format = path::to::fn)]` — a `fn(&Self, &mut Formatter) -> fmt::Result` that
+    /// writes the type as a string when serializing
+    Format { expr: TokenStream },
+
+    /// Valid in container (transparent structs)
+    /// `#[facet(bitflags)]` — the struct is the bits of a `bitflags` type, written as a list
+    /// of flag names
+    Bitflags,
+
+    /// Valid in variant
+    /// `#[facet(other)]` — the catch-all variant for unknown variant names and discriminants
+    Other,
 }
 
 impl PFacetAttr {
     /// Parse a `FacetAttr` attribute into a `PFacetAttr`.
-    /// Pushes to `dest` for each parsed attribute.
+    /// Pushes to `dest` for each parsed attribute, and to `errors` for each invalid one.
     pub fn parse(
         facet_attr: &facet_macros_parse::FacetAttr,
         display_name: &mut String,
         dest: &mut Vec<PFacetAttr>,
+        errors: &mut Vec<AttributeError>,
     ) {
         use facet_macros_parse::FacetInner;
 
@@ -112,7 +136,16 @@ impl PFacetAttr {
                     let expr = invariant.expr.to_token_stream();
                     dest.push(PFacetAttr::Invariants { expr });
                 }
+                FacetInner::Parse(parse) => dest.push(PFacetAttr::Parse {
+                    expr: parse.expr.to_token_stream(),
+                }),
+                FacetInner::Format(format) => dest.push(PFacetAttr::Format {
+                    expr: format.expr.to_token_stream(),
+                }),
+                FacetInner::Bitflags(_) => dest.push(PFacetAttr::Bitflags),
+                FacetInner::Other(_) => dest.push(PFacetAttr::Other),
                 FacetInner::DenyUnknownFields(_) => dest.push(PFacetAttr::DenyUnknownFields),
+                FacetInner::Discriminant(_) => dest.push(PFacetAttr::Discriminant),
                 FacetInner::DefaultEquals(default_equals) => dest.push(PFacetAttr::DefaultEquals {
                     expr: default_equals.expr.to_token_stream(),
                 }),
@@ -125,12 +158,19 @@ impl PFacetAttr {
                     if let Some(rule) = RenameRule::from_str(rule_str) {
                         dest.push(PFacetAttr::RenameAll { rule });
                     } else {
-                        panic!("Unknown #[facet(rename_all = ...)] rule: {}", rule_str);
+                        errors.push(AttributeError {
+                            span: first_span(rename_all.value.to_token_stream()),
+                            message: format!(
+                                "Unknown #[facet(rename_all = ...)] rule: {}",
+                                rule_str
+                            ),
+                        });
                     }
                 }
                 FacetInner::Arbitrary(tt) => {
                     dest.push(PFacetAttr::Arbitrary {
                         content: tt.tokens_to_string(),
+                        span: first_span(tt.to_token_stream()),
                     });
                 }
                 FacetInner::SkipSerializing(_) => {
@@ -151,6 +191,14 @@ impl PFacetAttr {
     }
 }
 
+/// The span of the first token in `tokens`, to point compile errors at
+fn first_span(tokens: TokenStream) -> Span {
+    tokens
+        .into_iter()
+        .next()
+        .map_or_else(Span::call_site, |token| token.span())
+}
+
 /// Parsed attr
 pub enum PAttr {
     /// A single line of doc comments
@@ -384,6 +432,13 @@ pub struct PAttrs {
 
     /// rename_all rule (if any)
     pub rename_all: Option<RenameRule>,
+
+    /// `#[cfg(...)]` attributes, copied onto whatever is generated for the item, so that
+    /// it's left out along with the item
+    pub cfg: Vec<TokenStream>,
+
+    /// Attributes that couldn't be parsed, reported as compile errors
+    pub errors: Vec<AttributeError>,
 }
 
 impl PAttrs {
@@ -392,6 +447,8 @@ impl PAttrs {
         let mut facet_attrs: Vec<PFacetAttr> = Vec::new();
         let mut repr: Option<PRepr> = None;
         let mut rename_all: Option<RenameRule> = None;
+        let mut cfg: Vec<TokenStream> = Vec::new();
+        let mut errors: Vec<AttributeError> = Vec::new();
 
         for attr in attrs {
             match &attr.body.content {
@@ -422,10 +479,15 @@ impl PAttrs {
                     };
                 }
                 facet_macros_parse::AttributeInner::Facet(facet_attr) => {
-                    PFacetAttr::parse(facet_attr, display_name, &mut facet_attrs);
+                    PFacetAttr::parse(facet_attr, display_name, &mut facet_attrs, &mut errors);
+                }
+                other if other.is_cfg() => {
+                    cfg.push(attr.to_token_stream());
                 }
-                _ => {
-                    // Ignore unknown AttributeInner types
+                other => {
+                    // Ignore unknown AttributeInner types, unless it's a `facet` attribute that
+                    // didn't parse
+                    errors.extend(other.facet_error());
                 }
             }
         }
@@ -441,6 +503,8 @@ impl PAttrs {
             facet: facet_attrs,
             repr: repr.unwrap_or(PRepr::Rust(None)),
             rename_all,
+            cfg,
+            errors,
         }
     }
 
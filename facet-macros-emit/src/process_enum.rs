@@ -10,17 +10,28 @@ use quote::{format_ident, quote};
 pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
     // Use already-parsed PEnum, including container/variant/field attributes and rename rules
     let pe = PEnum::parse(&parsed);
+    if let Err(errors) = crate::validate::validate_enum(&pe) {
+        return errors;
+    }
 
     let enum_name = &pe.container.name;
     let enum_name_str = enum_name.to_string();
 
-    let type_name_fn = generate_type_name_fn(enum_name, parsed.generics.as_ref());
+    let field_types =
+        facet_field_types(pe.variants.iter().flat_map(|variant| match &variant.kind {
+            PVariantKind::Tuple { fields } | PVariantKind::Struct { fields } => &fields[..],
+            PVariantKind::Unit => &[],
+        }));
+    let type_name_fn = generate_type_name_fn(enum_name, parsed.generics.as_ref(), &field_types);
 
     let bgp = pe.container.bgp.clone();
     // Use the AST directly for where clauses and generics, as PContainer/PEnum doesn't store them
-    let where_clauses_tokens =
-        build_where_clauses(parsed.clauses.as_ref(), parsed.generics.as_ref());
-    let type_params = build_type_params(parsed.generics.as_ref());
+    let where_clauses_tokens = build_where_clauses(
+        parsed.clauses.as_ref(),
+        parsed.generics.as_ref(),
+        &field_types,
+    );
+    let type_params = build_type_params(parsed.generics.as_ref(), &field_types);
 
     // Container-level docs from PAttrs
     let maybe_container_doc = match &pe.container.attrs.doc[..] {
@@ -35,7 +46,10 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                 PFacetAttr::DenyUnknownFields => {
                     attribute_tokens.push(quote! { ::facet::ShapeAttribute::DenyUnknownFields });
                 }
-                PFacetAttr::Arbitrary { content } => {
+                PFacetAttr::Discriminant => {
+                    attribute_tokens.push(quote! { ::facet::ShapeAttribute::Discriminant });
+                }
+                PFacetAttr::Arbitrary { content, .. } => {
                     attribute_tokens.push(quote! { ::facet::ShapeAttribute::Arbitrary(#content) });
                 }
                 PFacetAttr::RenameAll { rule } => {
@@ -182,10 +196,16 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                     } else {
                         let mut attrs_list = Vec::new();
                         for attr in &pv.attrs.facet {
-                            if let PFacetAttr::Arbitrary { content } = attr {
-                                attrs_list.push(
-                                    quote! { ::facet::VariantAttribute::Arbitrary(#content) },
-                                );
+                            match attr {
+                                PFacetAttr::Arbitrary { content, .. } => {
+                                    attrs_list.push(
+                                        quote! { ::facet::VariantAttribute::Arbitrary(#content) },
+                                    );
+                                }
+                                PFacetAttr::Other => {
+                                    attrs_list.push(quote! { ::facet::VariantAttribute::Other });
+                                }
+                                _ => {}
                             }
                         }
                         if attrs_list.is_empty() {
@@ -249,7 +269,8 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                             .map(|(idx, pf)| {
                                 let field_ident = format_ident!("_{}", idx);
                                 let typ = &pf.ty;
-                                quote! { #field_ident: #typ }
+                                let cfg = &pf.attrs.cfg;
+                                quote! { #(#cfg)* #field_ident: #typ }
                             })
                             .collect();
                         shadow_defs.push(quote! {
@@ -299,7 +320,8 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                                     }
                                 };
                                 let typ = &pf.ty;
-                                quote! { #field_name: #typ }
+                                let cfg = &pf.attrs.cfg;
+                                quote! { #(#cfg)* #field_name: #typ }
                             })
                             .collect();
 
@@ -398,10 +420,16 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                     } else {
                         let mut attrs_list = Vec::new();
                         for attr in &pv.attrs.facet {
-                            if let PFacetAttr::Arbitrary { content } = attr {
-                                attrs_list.push(
-                                    quote! { ::facet::VariantAttribute::Arbitrary(#content) },
-                                );
+                            match attr {
+                                PFacetAttr::Arbitrary { content, .. } => {
+                                    attrs_list.push(
+                                        quote! { ::facet::VariantAttribute::Arbitrary(#content) },
+                                    );
+                                }
+                                PFacetAttr::Other => {
+                                    attrs_list.push(quote! { ::facet::VariantAttribute::Other });
+                                }
+                                _ => {}
                             }
                         }
                         if attrs_list.is_empty() {
@@ -452,7 +480,8 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                             .map(|(idx, pf)| {
                                 let field_ident = format_ident!("_{}", idx);
                                 let typ = &pf.ty;
-                                quote! { #field_ident: #typ }
+                                let cfg = &pf.attrs.cfg;
+                                quote! { #(#cfg)* #field_ident: #typ }
                             })
                             .collect();
                         shadow_defs.push(quote! {
@@ -513,7 +542,8 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                                     }
                                 };
                                 let typ = &pf.ty;
-                                quote! { #field_name: #typ }
+                                let cfg = &pf.attrs.cfg;
+                                quote! { #(#cfg)* #field_name: #typ }
                             })
                             .collect();
                         shadow_defs.push(quote! {
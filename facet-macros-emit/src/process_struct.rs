@@ -41,6 +41,7 @@ pub(crate) fn gen_field_from_pfield(
         .collect();
     let mut shape_of = quote! { shape_of };
     let mut asserts: Vec<TokenStream> = vec![];
+    let mut flatten_check = quote! {};
 
     // Process attributes other than rename rules, which are handled by PName
     for attr in &field.attrs.facet {
@@ -93,11 +94,15 @@ pub(crate) fn gen_field_from_pfield(
                 } else {
                     flags = quote! { #flags.union(::facet::FieldFlags::FLATTEN) };
                 }
+                let message = format!(
+                    "`#[facet(flatten)]` on field `{field_name_effective}` needs a struct or an enum to flatten"
+                );
+                flatten_check = quote! { .assert_flattenable(#message) };
             }
             PFacetAttr::Opaque => {
                 shape_of = quote! { shape_of_opaque };
             }
-            PFacetAttr::Arbitrary { content } => {
+            PFacetAttr::Arbitrary { content, .. } => {
                 attribute_list.push(quote! { ::facet::FieldAttribute::Arbitrary(#content) });
             }
             PFacetAttr::SkipSerializing => {
@@ -120,7 +125,12 @@ pub(crate) fn gen_field_from_pfield(
             PFacetAttr::Transparent
             | PFacetAttr::Invariants { .. }
             | PFacetAttr::DenyUnknownFields
-            | PFacetAttr::TypeTag { .. } => {}
+            | PFacetAttr::Discriminant
+            | PFacetAttr::TypeTag { .. }
+            | PFacetAttr::Parse { .. }
+            | PFacetAttr::Format { .. }
+            | PFacetAttr::Bitflags
+            | PFacetAttr::Other => {}
         }
     }
 
@@ -164,7 +174,11 @@ pub(crate) fn gen_field_from_pfield(
         }
     };
 
+    // A field that's compiled out is left out of the shape too
+    let cfg = &field.attrs.cfg;
+
     quote! {
+        #(#cfg)*
         {
             #(#asserts)*;
             ::facet::Field::builder()
@@ -178,6 +192,7 @@ pub(crate) fn gen_field_from_pfield(
                 #maybe_field_doc
                 #maybe_vtable
                 .build()
+                #flatten_check
         }
     }
 }
@@ -193,12 +208,21 @@ pub(crate) fn gen_field_from_pfield(
 /// ```
 pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
     let ps = PStruct::parse(&parsed); // Use the parsed representation
+    if let Err(errors) = crate::validate::validate_struct(&ps) {
+        return errors;
+    }
 
     let struct_name_ident = format_ident!("{}", ps.container.name);
     let struct_name = &ps.container.name;
     let struct_name_str = struct_name.to_string();
 
-    let type_name_fn = generate_type_name_fn(struct_name, parsed.generics.as_ref());
+    let field_types = match &ps.kind {
+        PStructKind::Struct { fields } | PStructKind::TupleStruct { fields } => {
+            facet_field_types(fields)
+        }
+        PStructKind::UnitStruct => Vec::new(),
+    };
+    let type_name_fn = generate_type_name_fn(struct_name, parsed.generics.as_ref(), &field_types);
 
     // TODO: I assume the `PrimitiveRepr` is only relevant for enums, and does not need to be preserved?
     let repr = match &ps.container.attrs.repr {
@@ -237,8 +261,9 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
         StructKind::TupleStruct { clauses, .. } => clauses.as_ref(),
         StructKind::UnitStruct { clauses, .. } => clauses.as_ref(),
     };
-    let where_clauses = build_where_clauses(where_clauses_ast, parsed.generics.as_ref());
-    let type_params = build_type_params(parsed.generics.as_ref());
+    let where_clauses =
+        build_where_clauses(where_clauses_ast, parsed.generics.as_ref(), &field_types);
+    let type_params = build_type_params(parsed.generics.as_ref(), &field_types);
 
     // Static decl using PStruct BGP
     let static_decl = if ps.container.bgp.params.is_empty() {
@@ -270,8 +295,34 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
                 PFacetAttr::Transparent => {
                     items.push(quote! { ::facet::ShapeAttribute::Transparent });
                 }
+                PFacetAttr::Parse { .. } => {
+                    items.push(quote! { ::facet::ShapeAttribute::Parse });
+                }
+                PFacetAttr::Format { .. } => {
+                    items.push(quote! { ::facet::ShapeAttribute::Format });
+                }
+                PFacetAttr::Bitflags => {
+                    // The flags' names and bits, read from the `bitflags::Flags` impl at compile
+                    // time
+                    items.push(quote! {
+                        ::facet::ShapeAttribute::BitFlags(&const {
+                            const FLAGS: &[::facet::bitflags::Flag<#struct_name_ident>] =
+                                <#struct_name_ident as ::facet::bitflags::Flags>::FLAGS;
+                            let mut table = [::facet::BitFlag { name: "", bits: 0 }; FLAGS.len()];
+                            let mut i = 0;
+                            while i < FLAGS.len() {
+                                table[i] = ::facet::BitFlag {
+                                    name: FLAGS[i].name(),
+                                    bits: FLAGS[i].value().0 as u64,
+                                };
+                                i += 1;
+                            }
+                            table
+                        })
+                    });
+                }
                 PFacetAttr::RenameAll { .. } => {}
-                PFacetAttr::Arbitrary { content } => {
+                PFacetAttr::Arbitrary { content, .. } => {
                     items.push(quote! { ::facet::ShapeAttribute::Arbitrary(#content) });
                 }
                 // Others not applicable at container level or handled elsewhere
@@ -282,6 +333,8 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
                 | PFacetAttr::SkipSerializingIf { .. }
                 | PFacetAttr::Flatten
                 | PFacetAttr::Child
+                | PFacetAttr::Discriminant
+                | PFacetAttr::Other
                 | PFacetAttr::TypeTag { .. } => {}
             }
         }
@@ -337,6 +390,48 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
         }
     };
 
+    // Parse and format functions from PStruct, which replace the vtable's `FromStr` and
+    // `Display` ones
+    let parse_format_maybe = {
+        let bgp_display = ps.container.bgp.display_without_bounds();
+        let mut items = Vec::new();
+        for attr in &ps.container.attrs.facet {
+            match attr {
+                PFacetAttr::Parse { expr } => items.push(quote! {
+                    unsafe fn parse<'mem>(
+                        s: &str,
+                        target: ::facet::PtrUninit<'mem>,
+                    ) -> Result<::facet::PtrMut<'mem>, ::facet::ParseError> {
+                        let value: #struct_name_ident #bgp_display =
+                            #expr(s).map_err(::facet::ParseError::Generic)?;
+                        Ok(unsafe { target.put(value) })
+                    }
+
+                    {
+                        let vtable_sized = vtable.sized_mut().unwrap();
+                        vtable_sized.parse = || Some(parse);
+                    }
+                }),
+                PFacetAttr::Format { expr } => items.push(quote! {
+                    unsafe fn display<'mem>(
+                        value: ::facet::PtrConst<'mem>,
+                        f: &mut ::core::fmt::Formatter,
+                    ) -> ::core::fmt::Result {
+                        let value = unsafe { value.get::<#struct_name_ident #bgp_display>() };
+                        #expr(value, f)
+                    }
+
+                    {
+                        let vtable_sized = vtable.sized_mut().unwrap();
+                        vtable_sized.display = || Some(display);
+                    }
+                }),
+                _ => {}
+            }
+        }
+        quote! { #(#items)* }
+    };
+
     // Transparent logic using PStruct
     let inner_field = if ps.container.attrs.is_transparent() {
         match &ps.kind {
@@ -483,6 +578,32 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
         quote! {}
     };
 
+    // Two flattened fields taking the same key can't be told apart when deserializing, so
+    // building the shape fails to compile
+    let flattened_count = match &ps.kind {
+        PStructKind::Struct { fields } | PStructKind::TupleStruct { fields } => fields
+            .iter()
+            .filter(|field| {
+                field
+                    .attrs
+                    .facet
+                    .iter()
+                    .any(|attr| matches!(attr, PFacetAttr::Flatten))
+            })
+            .count(),
+        PStructKind::UnitStruct => 0,
+    };
+    let flatten_check = if flattened_count > 1 {
+        let message = format!(
+            "two `#[facet(flatten)]` fields of `{struct_name_str}` take the same key, so it can't be deserialized"
+        );
+        quote! {
+            .assert_no_flattened_key_conflict(#message)
+        }
+    } else {
+        quote! {}
+    };
+
     // Generics from PStruct
     let facet_bgp = ps
         .container
@@ -500,6 +621,7 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
             const VTABLE: &'static ::facet::ValueVTable = &const {
                 let mut vtable = ::facet::value_vtable!(Self, #type_name_fn);
                 #invariant_maybe
+                #parse_format_maybe
                 #try_from_inner_code // Use the generated code for transparent types
                 vtable
             };
@@ -523,6 +645,7 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
                     #container_attributes_tokens // From ps.container.attrs.facet
                     #type_tag_maybe
                     .build()
+                #flatten_check
             };
         }
     };
@@ -0,0 +1,241 @@
+use crate::parsed::{IdentOrLiteral, PAttrs, PFacetAttr, PVariantKind};
+use crate::{PEnum, PStruct, PStructField, PStructKind};
+use facet_macros_parse::{FACET_ATTRIBUTES, Ident, Span, ToTokens, TokenStream};
+use quote::quote_spanned;
+
+/// Collects compile errors for attribute combinations that can't work
+#[derive(Default)]
+struct Errors(TokenStream);
+
+impl Errors {
+    fn push(&mut self, span: Span, message: String) {
+        self.0
+            .extend(quote_spanned! { span=> compile_error!(#message); });
+    }
+
+    fn finish(self) -> Result<(), TokenStream> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(self.0)
+        }
+    }
+}
+
+/// Checks the attributes of a struct, returning `compile_error!`s for the ones that don't make
+/// sense together
+pub(crate) fn validate_struct(ps: &PStruct) -> Result<(), TokenStream> {
+    let mut errors = Errors::default();
+    let name = &ps.container.name;
+
+    check_attrs(&mut errors, name.span(), &ps.container.attrs);
+    match &ps.kind {
+        PStructKind::Struct { fields } | PStructKind::TupleStruct { fields } => {
+            check_fields(&mut errors, name, fields);
+        }
+        PStructKind::UnitStruct => {}
+    }
+
+    errors.finish()
+}
+
+/// Checks the attributes of an enum and its variants, returning `compile_error!`s for the ones
+/// that don't make sense together
+pub(crate) fn validate_enum(pe: &PEnum) -> Result<(), TokenStream> {
+    let mut errors = Errors::default();
+    let name = &pe.container.name;
+
+    check_attrs(&mut errors, name.span(), &pe.container.attrs);
+    for (index, variant) in pe.variants.iter().enumerate() {
+        let span = field_span(name, &variant.name.raw);
+        check_attrs(&mut errors, span, &variant.attrs);
+        if let Some(previous) = pe.variants[..index]
+            .iter()
+            .find(|previous| previous.name.effective == variant.name.effective)
+        {
+            errors.push(
+                span,
+                format!(
+                    "variants `{}` and `{}` of `{name}` both have the name \"{}\"",
+                    raw_name(&previous.name.raw),
+                    raw_name(&variant.name.raw),
+                    variant.name.effective,
+                ),
+            );
+        }
+        match &variant.kind {
+            PVariantKind::Tuple { fields } | PVariantKind::Struct { fields } => {
+                check_fields(&mut errors, name, fields);
+            }
+            PVariantKind::Unit => {}
+        }
+
+        if has(&variant.attrs, |attr| matches!(attr, PFacetAttr::Other)) {
+            if let Some(previous) = pe.variants[..index]
+                .iter()
+                .find(|previous| has(&previous.attrs, |attr| matches!(attr, PFacetAttr::Other)))
+            {
+                errors.push(
+                    span,
+                    format!(
+                        "variants `{}` and `{}` of `{name}` are both `#[facet(other)]`, there can only be one",
+                        raw_name(&previous.name.raw),
+                        raw_name(&variant.name.raw),
+                    ),
+                );
+            }
+            if let PVariantKind::Tuple { fields } | PVariantKind::Struct { fields } = &variant.kind
+            {
+                if fields.len() > 1 {
+                    errors.push(
+                        span,
+                        format!(
+                            "`#[facet(other)]` variant `{}` can have at most one field, to hold the unknown name or discriminant",
+                            raw_name(&variant.name.raw),
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    errors.finish()
+}
+
+fn check_fields(errors: &mut Errors, container: &Ident, fields: &[PStructField]) {
+    for (index, field) in fields.iter().enumerate() {
+        let span = field_span(container, &field.name.raw);
+        check_attrs(errors, span, &field.attrs);
+
+        // A flattened field's own name never shows up as a key
+        if has(&field.attrs, |attr| matches!(attr, PFacetAttr::Flatten)) {
+            continue;
+        }
+        // Fields behind `#[cfg(...)]` may be alternatives that never exist together
+        if !field.attrs.cfg.is_empty() {
+            continue;
+        }
+        if let Some(previous) = fields[..index].iter().find(|previous| {
+            previous.name.effective == field.name.effective
+                && previous.attrs.cfg.is_empty()
+                && !has(&previous.attrs, |attr| matches!(attr, PFacetAttr::Flatten))
+        }) {
+            errors.push(
+                span,
+                format!(
+                    "fields `{}` and `{}` of `{container}` both use the key \"{}\"",
+                    raw_name(&previous.name.raw),
+                    raw_name(&field.name.raw),
+                    field.name.effective,
+                ),
+            );
+        }
+    }
+}
+
+fn check_attrs(errors: &mut Errors, span: Span, attrs: &PAttrs) {
+    for error in &attrs.errors {
+        errors.push(error.span, error.message.clone());
+    }
+
+    let default = has(attrs, |attr| matches!(attr, PFacetAttr::Default));
+    let default_equals = has(attrs, |attr| {
+        matches!(attr, PFacetAttr::DefaultEquals { .. })
+    });
+    if default && default_equals {
+        errors.push(
+            span,
+            "`default` and `default = ...` can't both be used, pick one".to_string(),
+        );
+    }
+
+    let skip = has(attrs, |attr| matches!(attr, PFacetAttr::SkipSerializing));
+    let skip_if = has(attrs, |attr| {
+        matches!(attr, PFacetAttr::SkipSerializingIf { .. })
+    });
+    if skip && skip_if {
+        errors.push(
+            span,
+            "`skip_serializing` always skips, so `skip_serializing_if` has no effect".to_string(),
+        );
+    }
+
+    let bitflags = has(attrs, |attr| matches!(attr, PFacetAttr::Bitflags));
+    let transparent = has(attrs, |attr| matches!(attr, PFacetAttr::Transparent));
+    if bitflags && !transparent {
+        errors.push(
+            span,
+            "`bitflags` wraps the bits of the flags, so it needs `transparent` too".to_string(),
+        );
+    }
+
+    for attr in &attrs.facet {
+        if let PFacetAttr::Arbitrary { content, span } = attr {
+            if let Some(message) = misspelled_attribute(content) {
+                errors.push(*span, message);
+            }
+        }
+    }
+}
+
+/// Arbitrary attributes are left for other crates to read, unless they look like a mistyped
+/// known attribute, or a known one written in a form the derive doesn't accept
+fn misspelled_attribute(content: &str) -> Option<String> {
+    let name: String = content
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    if name.is_empty() {
+        return None;
+    }
+
+    if let Some((_, usage)) = FACET_ATTRIBUTES.iter().find(|(known, _)| *known == name) {
+        return Some(format!(
+            "`#[facet({content})]` isn't supported, write it as `{usage}`"
+        ));
+    }
+
+    let (known, _) = FACET_ATTRIBUTES.iter().find(|(known, _)| {
+        let max_distance = if known.len() >= 8 { 2 } else { 1 };
+        edit_distance(&name.to_lowercase(), known) <= max_distance
+    })?;
+    let supported = FACET_ATTRIBUTES
+        .iter()
+        .map(|(known, _)| *known)
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "unknown attribute `{name}`, did you mean `{known}`? The supported attributes are: {supported}"
+    ))
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+fn has(attrs: &PAttrs, predicate: impl Fn(&PFacetAttr) -> bool) -> bool {
+    attrs.facet.iter().any(predicate)
+}
+
+fn raw_name(name: &IdentOrLiteral) -> String {
+    quote::ToTokens::to_token_stream(name).tokens_to_string()
+}
+
+fn field_span(container: &Ident, name: &IdentOrLiteral) -> Span {
+    match name {
+        IdentOrLiteral::Ident(ident) => ident.span(),
+        IdentOrLiteral::Literal(_) => container.span(),
+    }
+}
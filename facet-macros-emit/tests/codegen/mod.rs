@@ -654,7 +654,7 @@ fn enum_with_facet_attributes() {
         #[facet(name = "MyCoolEnum", repr = "u16", type_tag = "rs.facet.MyCoolEnum")]
         #[repr(u16)] // Ensure repr matches if specified in facet attribute
         enum EnumWithAttributes {
-            #[facet(name = "FirstVariant", discriminant = 10)]
+            #[facet(name = "FirstVariant")]
             VariantA,
 
             #[facet(skip)]
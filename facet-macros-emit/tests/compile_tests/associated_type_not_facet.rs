@@ -0,0 +1,22 @@
+use facet::Facet;
+
+trait Backend {
+    type Id;
+}
+
+struct Handle;
+
+struct Postgres;
+
+impl Backend for Postgres {
+    type Id = Handle;
+}
+
+#[derive(Facet)]
+struct Row<B: Backend> {
+    id: B::Id,
+}
+
+const _: &facet::Shape = <Row<Postgres> as Facet>::SHAPE;
+
+fn main() {}
@@ -0,0 +1,19 @@
+use facet::Facet;
+
+#[derive(Facet)]
+struct Server {
+    #[facet(rename = "port")]
+    listen_port: u16,
+    port: u16,
+    #[facet(default, default = 30)]
+    timeout: u32,
+    #[facet(flaten)]
+    tls: Tls,
+}
+
+#[derive(Facet)]
+struct Tls {
+    cert: String,
+}
+
+fn main() {}
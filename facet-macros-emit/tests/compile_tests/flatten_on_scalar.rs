@@ -0,0 +1,12 @@
+use facet::Facet;
+
+#[derive(Facet)]
+struct Server {
+    name: String,
+    #[facet(flatten)]
+    port: u16,
+}
+
+const _: &facet::Shape = <Server as Facet>::SHAPE;
+
+fn main() {}
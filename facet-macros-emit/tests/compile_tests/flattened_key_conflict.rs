@@ -0,0 +1,25 @@
+use facet::Facet;
+
+#[derive(Facet)]
+struct Listen {
+    host: String,
+    port: u16,
+}
+
+#[derive(Facet)]
+struct Upstream {
+    host: String,
+    timeout: u32,
+}
+
+#[derive(Facet)]
+struct Proxy {
+    #[facet(flatten)]
+    listen: Listen,
+    #[facet(flatten)]
+    upstream: Upstream,
+}
+
+const _: &facet::Shape = <Proxy as Facet>::SHAPE;
+
+fn main() {}
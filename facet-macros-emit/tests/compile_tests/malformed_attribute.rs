@@ -0,0 +1,10 @@
+use facet::Facet;
+
+#[derive(Facet)]
+#[facet(rename_all = "shouting")]
+struct Login {
+    #[facet(sensitive = true)]
+    password: String,
+}
+
+fn main() {}
@@ -190,3 +190,85 @@ fn test_default_but_no_default() {
     // Run the test
     run_compilation_test(&test);
 }
+
+#[test]
+#[cfg(not(miri))]
+fn test_flattened_key_conflict() {
+    // Define the test case
+    let test = CompilationTest {
+        name: "flattened_key_conflict",
+        source: include_str!("./flattened_key_conflict.rs"),
+        expected_errors: &[
+            "two `#[facet(flatten)]` fields of `Proxy` take the same key, so it can't be deserialized",
+        ],
+    };
+
+    // Run the test
+    run_compilation_test(&test);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_flatten_on_scalar() {
+    // Define the test case
+    let test = CompilationTest {
+        name: "flatten_on_scalar",
+        source: include_str!("./flatten_on_scalar.rs"),
+        expected_errors: &[
+            "`#[facet(flatten)]` on field `port` needs a struct or an enum to flatten",
+        ],
+    };
+
+    // Run the test
+    run_compilation_test(&test);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_conflicting_attributes() {
+    // Define the test case
+    let test = CompilationTest {
+        name: "conflicting_attributes",
+        source: include_str!("./conflicting_attributes.rs"),
+        expected_errors: &[
+            "fields `listen_port` and `port` of `Server` both use the key \"port\"",
+            "`default` and `default = ...` can't both be used, pick one",
+            "unknown attribute `flaten`, did you mean `flatten`?",
+        ],
+    };
+
+    // Run the test
+    run_compilation_test(&test);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_malformed_attribute() {
+    // Define the test case
+    let test = CompilationTest {
+        name: "malformed_attribute",
+        source: include_str!("./malformed_attribute.rs"),
+        expected_errors: &[
+            "Unknown #[facet(rename_all = ...)] rule: shouting",
+            "unexpected `=` in `#[facet(...)]`, the supported attributes are: sensitive, opaque",
+            "6 |     #[facet(sensitive = true)]",
+        ],
+    };
+
+    // Run the test
+    run_compilation_test(&test);
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_associated_type_not_facet() {
+    // Define the test case
+    let test = CompilationTest {
+        name: "associated_type_not_facet",
+        source: include_str!("./associated_type_not_facet.rs"),
+        expected_errors: &["error[E0277]", "Handle: Facet"],
+    };
+
+    // Run the test
+    run_compilation_test(&test);
+}
@@ -40,6 +40,8 @@ keyword! {
     pub KOpaque = "opaque";
     /// The "deny_unknown_fields" keyword.
     pub KDenyUnknownFields = "deny_unknown_fields";
+    /// The "discriminant" keyword.
+    pub KDiscriminant = "discriminant";
     /// The "default" keyword.
     pub KDefault = "default";
     /// The "transparent" keyword.
@@ -58,6 +60,14 @@ keyword! {
     pub KSkipSerializingIf = "skip_serializing_if";
     /// The "type_tag" keyword.
     pub KTypeTag = "type_tag";
+    /// The "parse" keyword.
+    pub KParse = "parse";
+    /// The "format" keyword.
+    pub KFormat = "format";
+    /// The "bitflags" keyword.
+    pub KBitflags = "bitflags";
+    /// The "other" keyword.
+    pub KOther = "other";
 }
 
 operator! {
@@ -74,6 +84,10 @@ operator! {
 /// Parses tokens and groups until `C` is found on the current token tree level.
 pub type VerbatimUntil<C> = Many<Cons<Except<C>, AngleTokenTree>>;
 
+/// Parses tokens and groups until a `:` that isn't part of a `::` path separator, so
+/// predicates like `T::Output` or `<T as Trait>::Output` are read whole.
+pub type WherePredicate = Many<Either<PathSep, Cons<Except<Colon>, AngleTokenTree>>>;
+
 /// Represents a module path, consisting of an optional path separator followed by
 /// a path-separator-delimited sequence of identifiers.
 pub type ModPath = Cons<Option<PathSep>, PathSepDelimited<Ident>>;
@@ -145,6 +159,8 @@ unsynn! {
         Opaque(KOpaque),
         /// A deny_unknown_fields attribute that specifies whether unknown fields are allowed.
         DenyUnknownFields(KDenyUnknownFields),
+        /// A discriminant attribute that makes a C-like enum serialize as its discriminants.
+        Discriminant(KDiscriminant),
         /// A default attribute with an explicit value (#[facet(default = "myfunc")])
         DefaultEquals(DefaultEqualsInner),
         /// A default attribute with no explicit value (#[facet(default)])
@@ -165,6 +181,14 @@ unsynn! {
         SkipSerializingIf(SkipSerializingIfInner),
         /// A type_tag attribute that specifies the identifying tag for self describing formats
         TypeTag(TypeTagInner),
+        /// A parse attribute that reads the type from a string (#[facet(parse = path::to::fn)])
+        Parse(ParseInner),
+        /// A format attribute that writes the type as a string (#[facet(format = path::to::fn)])
+        Format(FormatInner),
+        /// A bitflags attribute for transparent wrappers around the bits of a bitflags type
+        Bitflags(KBitflags),
+        /// An other attribute for the catch-all variant of an enum
+        Other(KOther),
         /// Any other attribute represented as a sequence of token trees.
         Arbitrary(VerbatimUntil<Comma>),
     }
@@ -207,6 +231,26 @@ unsynn! {
         pub expr: LiteralString,
     }
 
+    /// Inner value for #[facet(parse = ...)]
+    pub struct ParseInner {
+        /// The "parse" keyword.
+        pub _kw_parse: KParse,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The parse function as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>,
+    }
+
+    /// Inner value for #[facet(format = ...)]
+    pub struct FormatInner {
+        /// The "format" keyword.
+        pub _kw_format: KFormat,
+        /// The equals sign '='.
+        pub _eq: Eq,
+        /// The format function as verbatim until comma.
+        pub expr: VerbatimUntil<Comma>,
+    }
+
     /// Inner value for #[facet(default = ...)]
     pub struct DefaultEqualsInner {
         /// The "default" keyword.
@@ -340,9 +384,8 @@ unsynn! {
     /// e.g., `T: Trait` or `'a: 'b`.
     #[derive(Clone)]
     pub struct WhereClause {
-        // FIXME: This likely breaks for absolute `::` paths
-        /// The type or lifetime being constrained (e.g., `T` or `'a`).
-        pub _pred: VerbatimUntil<Colon>,
+        /// The type or lifetime being constrained (e.g., `T`, `T::Output` or `'a`).
+        pub _pred: WherePredicate,
         /// The colon separating the constrained item and its bounds.
         pub _colon: Colon,
         /// The bounds applied to the type or lifetime (e.g., `Trait` or `'b`).
@@ -559,12 +602,15 @@ impl core::fmt::Display for WhereClauses {
 
 impl core::fmt::Display for WhereClause {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}: {}",
-            VerbatimDisplay(&self._pred),
-            VerbatimDisplay(&self.bounds)
-        )
+        for it in self._pred.0.iter() {
+            match &it.value {
+                Either::First(_) => write!(f, "::")?,
+                Either::Second(it) => write!(f, "{}", it.second)?,
+                Either::Third(Invalid) => unreachable!(),
+                Either::Fourth(Invalid) => unreachable!(),
+            }
+        }
+        write!(f, ": {}", VerbatimDisplay(&self.bounds))
     }
 }
 
@@ -597,6 +643,98 @@ impl Struct {
     }
 }
 
+/// The attributes `#[facet(...)]` supports, and how each one is written
+pub const FACET_ATTRIBUTES: &[(&str, &str)] = &[
+    ("sensitive", "sensitive"),
+    ("opaque", "opaque"),
+    ("transparent", "transparent"),
+    ("flatten", "flatten"),
+    ("child", "child"),
+    ("invariants", "invariants = path::to::fn"),
+    ("deny_unknown_fields", "deny_unknown_fields"),
+    ("discriminant", "discriminant"),
+    ("default", "default` or `default = expr"),
+    ("rename", "rename = \"name\""),
+    ("rename_all", "rename_all = \"rule\""),
+    ("skip_serializing", "skip_serializing"),
+    ("skip_serializing_if", "skip_serializing_if = path::to::fn"),
+    ("type_tag", "type_tag = \"tag\""),
+    ("parse", "parse = path::to::fn"),
+    ("format", "format = path::to::fn"),
+    ("bitflags", "bitflags"),
+    ("other", "other"),
+];
+
+/// A problem with an attribute, and the token to report it on
+#[derive(Clone)]
+pub struct AttributeError {
+    /// Where the compile error should point
+    pub span: Span,
+    /// What's wrong
+    pub message: String,
+}
+
+impl AttributeInner {
+    /// Returns `true` for a `#[cfg(...)]` attribute.
+    pub fn is_cfg(&self) -> bool {
+        matches!(
+            self,
+            AttributeInner::Any(tokens)
+                if matches!(tokens.first(), Some(TokenTree::Ident(ident)) if ident == "cfg")
+        )
+    }
+
+    /// Finds where a `#[facet(...)]` attribute stops parsing.
+    ///
+    /// Such an attribute doesn't parse as [`AttributeInner::Facet`], and ends up as
+    /// [`AttributeInner::Any`] instead, so it would otherwise be ignored.
+    pub fn facet_error(&self) -> Option<AttributeError> {
+        let AttributeInner::Any(tokens) = self else {
+            return None;
+        };
+        let [TokenTree::Ident(keyword), TokenTree::Group(group)] = tokens.as_slice() else {
+            return None;
+        };
+        if keyword != "facet" || group.delimiter() != proc_macro2::Delimiter::Parenthesis {
+            return None;
+        }
+
+        // Parse one attribute at a time, up to the first token that doesn't fit
+        let stream = group.stream();
+        let mut it = stream.to_token_iter();
+        let unexpected = loop {
+            if it.parse::<FacetInner>().is_err() {
+                break it.parse::<TokenTree>().ok();
+            }
+            match it.parse::<TokenTree>() {
+                Ok(TokenTree::Punct(punct)) if punct.as_char() == ',' => {}
+                Ok(token) => break Some(token),
+                Err(_) => break None,
+            }
+        };
+
+        let supported = FACET_ATTRIBUTES
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(match unexpected {
+            Some(token) => AttributeError {
+                span: token.span(),
+                message: format!(
+                    "unexpected `{token}` in `#[facet(...)]`, the supported attributes are: {supported}"
+                ),
+            },
+            None => AttributeError {
+                span: group.span(),
+                message: format!(
+                    "couldn't parse `#[facet{group}]`, the supported attributes are: {supported}"
+                ),
+            },
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -646,4 +784,52 @@ mod tests {
             panic!("Expected a regular struct with named fields");
         }
     }
+
+    #[test]
+    fn test_facet_attribute_error_points_at_token() {
+        let input = quote! {
+            #[facet(sensitive = 1)]
+            struct Secret;
+        };
+
+        let mut it = input.to_token_iter();
+        let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+        let error = parsed.attributes[0]
+            .body
+            .content
+            .facet_error()
+            .expect("the attribute should be reported");
+        assert!(
+            error
+                .message
+                .starts_with("unexpected `=` in `#[facet(...)]`")
+        );
+        assert!(error.message.contains("skip_serializing_if"));
+    }
+
+    #[test]
+    fn test_cfg_attribute() {
+        let input = quote! {
+            #[cfg(feature = "tls")]
+            #[cfg_attr(feature = "tls", facet(sensitive))]
+            struct Secret;
+        };
+
+        let mut it = input.to_token_iter();
+        let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+        assert!(parsed.attributes[0].body.content.is_cfg());
+        assert!(!parsed.attributes[1].body.content.is_cfg());
+    }
+
+    #[test]
+    fn test_valid_facet_attribute_has_no_error() {
+        let input = quote! {
+            #[facet(sensitive, rename = "hidden")]
+            struct Secret;
+        };
+
+        let mut it = input.to_token_iter();
+        let parsed = it.parse::<Struct>().expect("Failed to parse struct");
+        assert!(parsed.attributes[0].body.content.facet_error().is_none());
+    }
 }
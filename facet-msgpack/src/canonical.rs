@@ -0,0 +1,321 @@
+use core::fmt;
+use std::io::{self, Write};
+
+use facet_core::Facet;
+use facet_reflect::Peek;
+
+use crate::constants::*;
+use crate::serialize::{
+    peek_to_vec, write_array_len, write_bin, write_f32, write_f64, write_i64, write_map_len,
+    write_str, write_u64,
+};
+
+/// How deeply arrays and maps can be nested in the input of [`canonicalize`]
+const MAX_DEPTH: usize = 512;
+
+const INFALLIBLE: &str = "writing to a Vec can't fail";
+
+/// Serializes any Facet type to canonical MessagePack, which is the same bytes for equal
+/// values, so that signatures over it can be checked by anyone who serializes the value again.
+///
+/// See [`canonicalize`] for what makes it canonical.
+///
+/// ```
+/// use facet_msgpack::to_vec_canonical;
+/// use std::collections::HashMap;
+///
+/// let a = HashMap::from([("x", 1), ("y", 2), ("z", 3)]);
+/// let b = HashMap::from([("z", 3), ("y", 2), ("x", 1)]);
+/// assert_eq!(to_vec_canonical(&a).unwrap(), to_vec_canonical(&b).unwrap());
+/// ```
+pub fn to_vec_canonical<'a, T: Facet<'a>>(value: &'a T) -> Result<Vec<u8>, CanonicalError> {
+    peek_to_vec_canonical(Peek::new(value))
+}
+
+/// Serializes a `Peek` instance to canonical MessagePack, like [`to_vec_canonical`]
+pub fn peek_to_vec_canonical(peek: Peek<'_, '_, '_>) -> Result<Vec<u8>, CanonicalError> {
+    canonicalize(&peek_to_vec(peek))
+}
+
+/// Rewrites a MessagePack value in canonical form:
+///
+/// - the entries of every map, including structs, are sorted by the bytes of their keys, and
+///   no two keys are the same
+/// - integers, lengths and extension headers take the fewest bytes they can, and integers
+///   that aren't negative are always unsigned
+/// - floats holding an integer are written as that integer, except `-0.0`; others are `f32`
+///   when that's exact, and `f64` otherwise; every NaN is the same `f32` NaN
+///
+/// Any MessagePack value can be rewritten, not only the ones this crate writes.
+pub fn canonicalize(msgpack: &[u8]) -> Result<Vec<u8>, CanonicalError> {
+    let mut reader = Reader {
+        input: msgpack,
+        pos: 0,
+    };
+    let mut out = Vec::with_capacity(msgpack.len());
+    reader.value(&mut out, 0)?;
+    if reader.pos < msgpack.len() {
+        return Err(CanonicalError::TrailingData(reader.pos));
+    }
+    Ok(out)
+}
+
+/// Why MessagePack couldn't be rewritten in canonical form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CanonicalError {
+    /// The input ended in the middle of a value
+    InsufficientData,
+    /// A byte that doesn't start any MessagePack value
+    InvalidMarker {
+        /// The byte
+        marker: u8,
+        /// Its offset
+        offset: usize,
+    },
+    /// A string that isn't UTF-8, at this offset
+    InvalidUtf8(usize),
+    /// A map has a key twice, the second time at this offset
+    DuplicateKey(usize),
+    /// Arrays and maps are nested too deeply, from this offset on
+    TooDeep(usize),
+    /// Data was left after the value, from this offset on
+    TrailingData(usize),
+}
+
+impl fmt::Display for CanonicalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanonicalError::InsufficientData => write!(f, "Insufficient data to decode"),
+            CanonicalError::InvalidMarker { marker, offset } => {
+                write!(
+                    f,
+                    "Invalid MessagePack marker 0x{marker:02x} at offset {offset}"
+                )
+            }
+            CanonicalError::InvalidUtf8(offset) => {
+                write!(f, "String at offset {offset} isn't UTF-8")
+            }
+            CanonicalError::DuplicateKey(offset) => {
+                write!(f, "Duplicate map key at offset {offset}")
+            }
+            CanonicalError::TooDeep(offset) => {
+                write!(
+                    f,
+                    "Nested more than {MAX_DEPTH} levels deep at offset {offset}"
+                )
+            }
+            CanonicalError::TrailingData(offset) => {
+                write!(f, "Trailing data after the value, from offset {offset}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CanonicalError {}
+
+struct Reader<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CanonicalError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.input.len())
+            .ok_or(CanonicalError::InsufficientData)?;
+        let bytes = &self.input[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], CanonicalError> {
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(self.take(N)?);
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, CanonicalError> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Reads a length of 1, 2 or 4 bytes, for the `8`, `16` and `32` variants of a type,
+    /// which are `0`, `1` and `2` markers after its first one
+    fn len(&mut self, variant: u8) -> Result<usize, CanonicalError> {
+        Ok(match variant {
+            0 => self.u8()?.into(),
+            1 => u16::from_be_bytes(self.array()?).into(),
+            _ => u32::from_be_bytes(self.array()?) as usize,
+        })
+    }
+
+    /// Reads one value, writing it in canonical form to `out`.
+    fn value(&mut self, out: &mut Vec<u8>, depth: usize) -> Result<(), CanonicalError> {
+        let offset = self.pos;
+        let marker = self.u8()?;
+        let written = match marker {
+            // Fixints and constants are already as short as they can be; negative fixints are
+            // 0xe0 to 0xff
+            MSGPACK_POSFIXINT_MIN..=MSGPACK_POSFIXINT_MAX
+            | 0xe0..=0xff
+            | MSGPACK_NIL
+            | MSGPACK_FALSE
+            | MSGPACK_TRUE => out.write_all(&[marker]),
+            MSGPACK_UINT8 => write_u64(out, self.u8()?.into()),
+            MSGPACK_UINT16 => write_u64(out, u16::from_be_bytes(self.array()?).into()),
+            MSGPACK_UINT32 => write_u64(out, u32::from_be_bytes(self.array()?).into()),
+            MSGPACK_UINT64 => write_u64(out, u64::from_be_bytes(self.array()?)),
+            MSGPACK_INT8 => write_i64(out, i8::from_be_bytes(self.array()?).into()),
+            MSGPACK_INT16 => write_i64(out, i16::from_be_bytes(self.array()?).into()),
+            MSGPACK_INT32 => write_i64(out, i32::from_be_bytes(self.array()?).into()),
+            MSGPACK_INT64 => write_i64(out, i64::from_be_bytes(self.array()?)),
+            MSGPACK_FLOAT32 => write_float(out, f32::from_be_bytes(self.array()?).into()),
+            MSGPACK_FLOAT64 => write_float(out, f64::from_be_bytes(self.array()?)),
+            MSGPACK_BIN8 | MSGPACK_BIN16 | MSGPACK_BIN32 => {
+                let len = self.len(marker - MSGPACK_BIN8)?;
+                write_bin(out, self.take(len)?)
+            }
+            MSGPACK_FIXSTR_MIN..=MSGPACK_FIXSTR_MAX => {
+                return self.string(out, usize::from(marker & 0x1f), offset);
+            }
+            MSGPACK_STR8 | MSGPACK_STR16 | MSGPACK_STR32 => {
+                let len = self.len(marker - MSGPACK_STR8)?;
+                return self.string(out, len, offset);
+            }
+            MSGPACK_FIXEXT1..=MSGPACK_FIXEXT16 => {
+                return self.ext(out, 1 << (marker - MSGPACK_FIXEXT1));
+            }
+            MSGPACK_EXT8 | MSGPACK_EXT16 | MSGPACK_EXT32 => {
+                let len = self.len(marker - MSGPACK_EXT8)?;
+                return self.ext(out, len);
+            }
+            MSGPACK_FIXARRAY_MIN..=MSGPACK_FIXARRAY_MAX => {
+                return self.list(out, usize::from(marker & 0x0f), depth, offset);
+            }
+            MSGPACK_ARRAY16 | MSGPACK_ARRAY32 => {
+                let len = self.len(marker - MSGPACK_ARRAY16 + 1)?;
+                return self.list(out, len, depth, offset);
+            }
+            MSGPACK_FIXMAP_MIN..=MSGPACK_FIXMAP_MAX => {
+                return self.map(out, usize::from(marker & 0x0f), depth, offset);
+            }
+            MSGPACK_MAP16 | MSGPACK_MAP32 => {
+                let len = self.len(marker - MSGPACK_MAP16 + 1)?;
+                return self.map(out, len, depth, offset);
+            }
+            _ => return Err(CanonicalError::InvalidMarker { marker, offset }),
+        };
+        written.expect(INFALLIBLE);
+        Ok(())
+    }
+
+    fn string(
+        &mut self,
+        out: &mut Vec<u8>,
+        len: usize,
+        offset: usize,
+    ) -> Result<(), CanonicalError> {
+        let s = core::str::from_utf8(self.take(len)?)
+            .map_err(|_| CanonicalError::InvalidUtf8(offset))?;
+        write_str(out, s).expect(INFALLIBLE);
+        Ok(())
+    }
+
+    fn ext(&mut self, out: &mut Vec<u8>, len: usize) -> Result<(), CanonicalError> {
+        let ty = self.u8()?;
+        let data = self.take(len)?;
+        match len {
+            // fixext 1, 2, 4, 8 and 16 are consecutive markers
+            1 | 2 | 4 | 8 | 16 => out.push(MSGPACK_FIXEXT1 + len.trailing_zeros() as u8),
+            _ if len <= 0xff => out.extend_from_slice(&[MSGPACK_EXT8, len as u8]),
+            _ if len <= 0xffff => {
+                out.push(MSGPACK_EXT16);
+                out.extend_from_slice(&(len as u16).to_be_bytes());
+            }
+            _ => {
+                out.push(MSGPACK_EXT32);
+                out.extend_from_slice(&(len as u32).to_be_bytes());
+            }
+        }
+        out.push(ty);
+        out.extend_from_slice(data);
+        Ok(())
+    }
+
+    fn list(
+        &mut self,
+        out: &mut Vec<u8>,
+        len: usize,
+        depth: usize,
+        offset: usize,
+    ) -> Result<(), CanonicalError> {
+        if depth >= MAX_DEPTH {
+            return Err(CanonicalError::TooDeep(offset));
+        }
+        write_array_len(out, len).expect(INFALLIBLE);
+        for _ in 0..len {
+            self.value(out, depth + 1)?;
+        }
+        Ok(())
+    }
+
+    fn map(
+        &mut self,
+        out: &mut Vec<u8>,
+        len: usize,
+        depth: usize,
+        offset: usize,
+    ) -> Result<(), CanonicalError> {
+        if depth >= MAX_DEPTH {
+            return Err(CanonicalError::TooDeep(offset));
+        }
+        // Not allocated up front, since `len` hasn't been checked against the input yet
+        let mut entries = Vec::new();
+        for _ in 0..len {
+            let key_offset = self.pos;
+            let mut key = Vec::new();
+            self.value(&mut key, depth + 1)?;
+            let mut value = Vec::new();
+            self.value(&mut value, depth + 1)?;
+            entries.push((key, value, key_offset));
+        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        if let Some(pair) = entries.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(CanonicalError::DuplicateKey(pair[0].2.max(pair[1].2)));
+        }
+
+        write_map_len(out, len).expect(INFALLIBLE);
+        for (key, value, _) in entries {
+            out.extend_from_slice(&key);
+            out.extend_from_slice(&value);
+        }
+        Ok(())
+    }
+}
+
+/// Writes a float as an integer if it holds one, or else in the fewest bytes that keep it
+/// exact.
+fn write_float(out: &mut Vec<u8>, n: f64) -> io::Result<()> {
+    // 2^64 and -2^63 are exact, unlike `u64::MAX as f64`
+    const TWO_POW_64: f64 = 18_446_744_073_709_551_616.0;
+    const MINUS_TWO_POW_63: f64 = -9_223_372_036_854_775_808.0;
+
+    if n.is_nan() {
+        // The quiet NaN with no payload
+        return out.write_all(&[MSGPACK_FLOAT32, 0x7f, 0xc0, 0x00, 0x00]);
+    }
+    let is_integer = n.fract() == 0.0 && !(n == 0.0 && n.is_sign_negative());
+    if is_integer && (0.0..TWO_POW_64).contains(&n) {
+        return write_u64(out, n as u64);
+    }
+    if is_integer && (MINUS_TWO_POW_63..0.0).contains(&n) {
+        return write_i64(out, n as i64);
+    }
+    if f64::from(n as f32) == n {
+        write_f32(out, n as f32)
+    } else {
+        write_f64(out, n)
+    }
+}
@@ -1,7 +1,11 @@
 use crate::constants::*;
 use crate::errors::Error as DecodeError;
 
-use facet_core::{Def, Facet, Type, UserType};
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use facet_core::{Def, Facet, Shape, Type, UserType};
 use facet_reflect::Partial;
 use log::trace;
 
@@ -28,18 +32,35 @@ use log::trace;
 /// let user: User = from_slice(&msgpack_data).unwrap();
 /// assert_eq!(user, User { id: 42, username: "user123".to_string() });
 /// ```
-pub fn from_slice<T: Facet<'static>>(msgpack: &[u8]) -> Result<T, DecodeError<'static>> {
+///
+/// Fails with [`DecodeError::TrailingData`] if anything follows the value: to read a value
+/// followed by something else, use [`from_slice_partial`].
+///
+/// `&[u8]` and `Cow<[u8]>` fields borrow MessagePack `bin` values from `msgpack` instead of
+/// copying them.
+/// Those values can't outlive `msgpack`:
+///
+/// ```compile_fail
+/// let bytes: &[u8] = {
+///     let msgpack = vec![0xc4, 0x01, 0x2a];
+///     facet_msgpack::from_slice(&msgpack).unwrap()
+/// };
+/// ```
+pub fn from_slice<'input, 'facet, T: Facet<'facet>>(
+    msgpack: &'input [u8],
+) -> Result<T, DecodeError<'static>>
+where
+    'input: 'facet,
+{
     trace!("from_slice: Starting deserialization for type {}", T::SHAPE);
-    let mut typed_partial = Partial::alloc::<T>()?;
-    trace!(
-        "from_slice: Allocated TypedPartial, inner shape: {}",
-        typed_partial.inner_mut().shape()
-    );
-    from_slice_value(msgpack, typed_partial.inner_mut())?;
+    // Untyped, so the shape (and the error) can stay `'static` while `T` borrows from `msgpack`
+    let mut wip = Partial::alloc_shape(T::SHAPE)?;
+    trace!("from_slice: Allocated Partial, shape: {}", wip.shape());
+    from_slice_value(msgpack, &mut wip)?;
     trace!("from_slice: Deserialization complete, building value");
-    let boxed_value = typed_partial.build()?;
+    let value = wip.build()?.materialize::<T>()?;
     trace!("from_slice: Value built successfully");
-    Ok(*boxed_value)
+    Ok(value)
 }
 
 /// Deserializes MessagePack-encoded data into a Facet value.
@@ -77,22 +98,68 @@ pub fn from_slice<T: Facet<'static>>(msgpack: &[u8]) -> Result<T, DecodeError<'s
 /// # Returns
 /// * `Ok(Opaque)` containing the deserialized data if successful
 /// * `Err(DecodeError)` if an error occurred during deserialization
+///   or if anything follows the value
 ///
 /// # MessagePack Format
 /// This implementation follows the MessagePack specification:
 /// <https://github.com/msgpack/msgpack/blob/master/spec.md>
-pub fn from_slice_value<'facet, 'shape>(
-    msgpack: &[u8],
+pub fn from_slice_value<'input, 'facet, 'shape>(
+    msgpack: &'input [u8],
+    wip: &mut Partial<'facet, 'shape>,
+) -> Result<(), DecodeError<'shape>>
+where
+    'input: 'facet,
+{
+    let len = decode_partial(msgpack, wip)?;
+    if len != msgpack.len() {
+        return Err(DecodeError::TrailingData(len));
+    }
+    Ok(())
+}
+
+/// Deserializes the MessagePack value at the start of `msgpack`, returning it and the number of
+/// bytes it took.
+///
+/// Unlike [`from_slice`], which fails if anything follows the value, this leaves the rest of
+/// the input unread, for input holding values one after the other.
+///
+/// ```
+/// // 1, then "a"
+/// let msgpack = [0x01, 0xa1, 0x61];
+/// let (first, len): (u8, usize) = facet_msgpack::from_slice_partial(&msgpack).unwrap();
+/// assert_eq!(first, 1);
+/// let (second, _): (String, usize) = facet_msgpack::from_slice_partial(&msgpack[len..]).unwrap();
+/// assert_eq!(second, "a");
+/// ```
+pub fn from_slice_partial<'input, 'facet, T: Facet<'facet>>(
+    msgpack: &'input [u8],
+) -> Result<(T, usize), DecodeError<'static>>
+where
+    'input: 'facet,
+{
+    // Untyped for the same reason as in `from_slice`
+    let mut wip = Partial::alloc_shape(T::SHAPE)?;
+    let len = decode_partial(msgpack, &mut wip)?;
+    let value = wip.build()?.materialize::<T>()?;
+    Ok((value, len))
+}
+
+/// Decodes the value at the start of `msgpack` into `wip`, returning where it ended
+fn decode_partial<'input, 'facet, 'shape>(
+    msgpack: &'input [u8],
     wip: &mut Partial<'facet, 'shape>,
-) -> Result<(), DecodeError<'shape>> {
-    trace!("from_slice_value: Starting with shape {}", wip.shape());
+) -> Result<usize, DecodeError<'shape>>
+where
+    'input: 'facet,
+{
+    trace!("decode_partial: Starting with shape {}", wip.shape());
     let mut decoder = Decoder::new(msgpack);
     let result = decoder.deserialize_value(wip);
     match &result {
-        Ok(_) => trace!("from_slice_value: Deserialization successful"),
-        Err(e) => trace!("from_slice_value: Deserialization failed: {:?}", e),
+        Ok(_) => trace!("decode_partial: Deserialization successful"),
+        Err(e) => trace!("decode_partial: Deserialization failed: {:?}", e),
     }
-    result
+    result.map(|()| decoder.offset)
 }
 
 struct Decoder<'input> {
@@ -154,21 +221,96 @@ impl<'input, 'shape> Decoder<'input> {
             MSGPACK_UINT8 => Ok(self.decode_u8()? as u64),
             MSGPACK_UINT16 => Ok(self.decode_u16()? as u64),
             MSGPACK_UINT32 => Ok(self.decode_u32()? as u64),
-            MSGPACK_UINT64 => {
-                if self.offset + 8 > self.input.len() {
-                    return Err(DecodeError::InsufficientData);
-                }
-                let value = u64::from_be_bytes(
-                    self.input[self.offset..self.offset + 8].try_into().unwrap(),
-                );
-                self.offset += 8;
-                Ok(value)
-            }
+            MSGPACK_UINT64 => self.decode_u64_be(),
             prefix @ MSGPACK_POSFIXINT_MIN..=MSGPACK_POSFIXINT_MAX => Ok(prefix as u64),
             _ => Err(DecodeError::UnexpectedType),
         }
     }
 
+    /// Decodes a MessagePack-encoded signed 64-bit integer.
+    /// Handles the following MessagePack types:
+    /// - positive fixint (0x00 - 0x7f) and negative fixint (0xe0 - 0xff)
+    /// - int8 (0xd0) to int64 (0xd3): signed integers (big-endian)
+    /// - uint8 (0xcc) to uint64 (0xcf): unsigned integers that fit in an `i64`
+    ///
+    /// Ref: <https://github.com/msgpack/msgpack/blob/master/spec.md#int-format-family>
+    fn decode_i64(&mut self) -> Result<i64, DecodeError<'static>> {
+        if self.offset >= self.input.len() {
+            return Err(DecodeError::InsufficientData);
+        }
+        match self.input[self.offset] {
+            MSGPACK_INT8 => {
+                self.offset += 1;
+                Ok(self.decode_u8()? as i8 as i64)
+            }
+            MSGPACK_INT16 => {
+                self.offset += 1;
+                Ok(self.decode_u16()? as i16 as i64)
+            }
+            MSGPACK_INT32 => {
+                self.offset += 1;
+                Ok(self.decode_u32()? as i32 as i64)
+            }
+            MSGPACK_INT64 => {
+                self.offset += 1;
+                Ok(self.decode_u64_be()? as i64)
+            }
+            prefix
+                if prefix as i8 >= MSGPACK_NEGFIXINT_MIN
+                    && prefix as i8 <= MSGPACK_NEGFIXINT_MAX =>
+            {
+                self.offset += 1;
+                Ok(prefix as i8 as i64)
+            }
+            _ => i64::try_from(self.decode_u64()?).map_err(|_| DecodeError::IntegerOverflow),
+        }
+    }
+
+    /// Decodes a 128-bit unsigned integer.
+    /// Handles the following MessagePack types:
+    /// - bin8 (0xc4) of 16 bytes: the integer in big-endian byte order, as it's written
+    /// - any unsigned integer, for values written by other encoders
+    fn decode_u128(&mut self) -> Result<u128, DecodeError<'static>> {
+        if self.peek_bin()? {
+            Ok(u128::from_be_bytes(self.decode_int128_bytes()?))
+        } else {
+            Ok(self.decode_u64()? as u128)
+        }
+    }
+
+    /// Decodes a 128-bit signed integer.
+    /// Handles the following MessagePack types:
+    /// - bin8 (0xc4) of 16 bytes: the integer in big-endian two's complement, as it's written
+    /// - any signed or unsigned integer, for values written by other encoders
+    fn decode_i128(&mut self) -> Result<i128, DecodeError<'static>> {
+        if self.peek_bin()? {
+            Ok(i128::from_be_bytes(self.decode_int128_bytes()?))
+        } else if self.input[self.offset] == MSGPACK_UINT64 {
+            Ok(self.decode_u64()? as i128)
+        } else {
+            Ok(self.decode_i64()? as i128)
+        }
+    }
+
+    /// Decodes the 16 bytes of a 128-bit integer.
+    fn decode_int128_bytes(&mut self) -> Result<[u8; 16], DecodeError<'static>> {
+        self.decode_bin()?
+            .try_into()
+            .map_err(|_| DecodeError::InvalidData)
+    }
+
+    /// Decodes a 64-bit unsigned integer in big-endian byte order.
+    /// This is a low-level method used by other decoders.
+    fn decode_u64_be(&mut self) -> Result<u64, DecodeError<'static>> {
+        if self.offset + 8 > self.input.len() {
+            return Err(DecodeError::InsufficientData);
+        }
+        let value =
+            u64::from_be_bytes(self.input[self.offset..self.offset + 8].try_into().unwrap());
+        self.offset += 8;
+        Ok(value)
+    }
+
     /// Decodes a MessagePack-encoded string.
     /// Handles the following MessagePack types:
     /// - fixstr (0xa0 - 0xbf): string up to 31 bytes
@@ -198,6 +340,30 @@ impl<'input, 'shape> Decoder<'input> {
         Ok(value)
     }
 
+    /// Decodes a MessagePack-encoded byte array, borrowing it from the input.
+    /// Handles the following MessagePack types:
+    /// - bin8 (0xc4): byte array up to 255 bytes
+    /// - bin16 (0xc5): byte array up to 65535 bytes
+    /// - bin32 (0xc6): byte array up to 4294967295 bytes
+    ///
+    /// Ref: <https://github.com/msgpack/msgpack/blob/master/spec.md#formats-bin>
+    fn decode_bin(&mut self) -> Result<&'input [u8], DecodeError<'static>> {
+        let len = match self.decode_u8()? {
+            MSGPACK_BIN8 => self.decode_u8()? as usize,
+            MSGPACK_BIN16 => self.decode_u16()? as usize,
+            MSGPACK_BIN32 => self.decode_u32()? as usize,
+            _ => return Err(DecodeError::UnexpectedType),
+        };
+
+        if self.offset + len > self.input.len() {
+            return Err(DecodeError::InsufficientData);
+        }
+
+        let value = &self.input[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(value)
+    }
+
     /// Decodes a MessagePack-encoded map length.
     /// Handles the following MessagePack types:
     /// - fixmap (0x80 - 0x8f): map with up to 15 elements
@@ -254,7 +420,6 @@ impl<'input, 'shape> Decoder<'input> {
     /// - nil (0xc0): nil/null value
     ///
     /// Ref: <https://github.com/msgpack/msgpack/blob/master/spec.md#formats-nil>
-    #[allow(dead_code)]
     fn decode_nil(&mut self) -> Result<(), DecodeError<'static>> {
         match self.decode_u8()? {
             MSGPACK_NIL => Ok(()),
@@ -264,7 +429,6 @@ impl<'input, 'shape> Decoder<'input> {
 
     /// Peeks at the next byte to check if it's a nil value without advancing the offset.
     /// Returns true if the next value is nil, false otherwise.
-    #[allow(dead_code)]
     fn peek_nil(&mut self) -> Result<bool, DecodeError<'static>> {
         if self.offset >= self.input.len() {
             return Err(DecodeError::InsufficientData);
@@ -285,6 +449,37 @@ impl<'input, 'shape> Decoder<'input> {
             || prefix == MSGPACK_STR32)
     }
 
+    /// Peeks at the next byte to check if it's an integer value without advancing the offset.
+    /// Returns true if the next value is a signed or unsigned integer, false otherwise.
+    fn peek_int(&mut self) -> Result<bool, DecodeError<'static>> {
+        if self.offset >= self.input.len() {
+            return Err(DecodeError::InsufficientData);
+        }
+        let prefix = self.input[self.offset];
+        Ok(prefix <= MSGPACK_POSFIXINT_MAX
+            || prefix as i8 >= MSGPACK_NEGFIXINT_MIN
+            || (MSGPACK_UINT8..=MSGPACK_INT64).contains(&prefix))
+    }
+
+    /// Peeks at the next byte to check if it's a bin value without advancing the offset.
+    /// Returns true if the next value is a byte array, false otherwise.
+    fn peek_bin(&mut self) -> Result<bool, DecodeError<'static>> {
+        if self.offset >= self.input.len() {
+            return Err(DecodeError::InsufficientData);
+        }
+        let prefix = self.input[self.offset];
+        Ok(prefix == MSGPACK_BIN8 || prefix == MSGPACK_BIN16 || prefix == MSGPACK_BIN32)
+    }
+
+    /// Skips the next `len` bytes, which must all be there.
+    fn skip_bytes(&mut self, len: usize) -> Result<(), DecodeError<'static>> {
+        if self.offset + len > self.input.len() {
+            return Err(DecodeError::InsufficientData);
+        }
+        self.offset += len;
+        Ok(())
+    }
+
     /// Skips a MessagePack value of any type.
     /// This is used when encountering unknown field names in a struct.
     fn skip_value(&mut self) -> Result<(), DecodeError<'static>> {
@@ -325,39 +520,18 @@ impl<'input, 'shape> Decoder<'input> {
                 Ok(())
             }
 
-            // Integer formats
-            MSGPACK_UINT8 => {
-                self.offset += 1;
-                Ok(())
-            }
-            MSGPACK_UINT16 => {
-                self.offset += 2;
-                Ok(())
-            }
-            MSGPACK_UINT32 => {
-                self.offset += 4;
-                Ok(())
-            }
-            MSGPACK_UINT64 => {
-                self.offset += 8;
-                Ok(())
-            }
-            MSGPACK_INT8 => {
-                self.offset += 1;
-                Ok(())
-            }
-            MSGPACK_INT16 => {
-                self.offset += 2;
-                Ok(())
-            }
-            MSGPACK_INT32 => {
-                self.offset += 4;
-                Ok(())
-            }
-            MSGPACK_INT64 => {
-                self.offset += 8;
+            // Binary formats
+            MSGPACK_BIN8 | MSGPACK_BIN16 | MSGPACK_BIN32 => {
+                self.offset -= 1;
+                self.decode_bin()?;
                 Ok(())
             }
+
+            // Integer formats
+            MSGPACK_UINT8 | MSGPACK_INT8 => self.skip_bytes(1),
+            MSGPACK_UINT16 | MSGPACK_INT16 => self.skip_bytes(2),
+            MSGPACK_UINT32 | MSGPACK_INT32 => self.skip_bytes(4),
+            MSGPACK_UINT64 | MSGPACK_INT64 => self.skip_bytes(8),
             // Fixed integers are already handled by decode_u8
 
             // Boolean and nil
@@ -419,10 +593,34 @@ impl<'input, 'shape> Decoder<'input> {
     fn deserialize_value<'facet>(
         &mut self,
         wip: &mut Partial<'facet, 'shape>,
-    ) -> Result<(), DecodeError<'shape>> {
+    ) -> Result<(), DecodeError<'shape>>
+    where
+        'input: 'facet,
+    {
         let shape = wip.shape();
         trace!("Deserializing {:?}", shape);
 
+        // `()`, `PhantomData` and unit structs are written as nil, and hold no data
+        if shape.is_unit() && self.peek_nil()? {
+            self.decode_nil()?;
+            return Ok(());
+        }
+
+        // Byte arrays are borrowed from the input when the target type allows it
+        if shape.is_type::<&[u8]>() {
+            let bytes = self.decode_bin()?;
+            wip.set(bytes)?;
+            return Ok(());
+        } else if shape.is_type::<Cow<'_, [u8]>>() {
+            let bytes = self.decode_bin()?;
+            wip.set(Cow::Borrowed(bytes))?;
+            return Ok(());
+        } else if shape.is_type::<Vec<u8>>() && self.peek_bin()? {
+            let bytes = self.decode_bin()?;
+            wip.set(bytes.to_vec())?;
+            return Ok(());
+        }
+
         // First check the type system (Type)
         match &shape.ty {
             Type::User(UserType::Struct(struct_type))
@@ -439,8 +637,8 @@ impl<'input, 'shape> Decoder<'input> {
                     match wip.field_index(&key) {
                         Some(index) => {
                             seen_fields[index] = true;
-                            self.deserialize_value(wip.begin_nth_field(index).unwrap())?;
-                            wip.end().unwrap();
+                            self.deserialize_value(wip.begin_nth_field(index)?)?;
+                            wip.end()?;
                         }
                         None => {
                             // Skip unknown field value
@@ -464,6 +662,10 @@ impl<'input, 'shape> Decoder<'input> {
                                 wip.set_default()?;
                             }
 
+                            wip.end()?;
+                        } else if field.shape().is_unit() {
+                            // `()`, `PhantomData` and unit structs hold no data
+                            wip.begin_nth_field(i)?;
                             wip.end()?;
                         } else {
                             // Non-default field was missing
@@ -500,6 +702,7 @@ impl<'input, 'shape> Decoder<'input> {
 
                 // Check if it's a unit variant which is represented as a string
                 if self.peek_string()? {
+                    let start = self.offset;
                     let variant_name = self.decode_string()?;
                     for (idx, variant) in enum_type.variants.iter().enumerate() {
                         if variant.name == variant_name {
@@ -507,12 +710,37 @@ impl<'input, 'shape> Decoder<'input> {
                             return Ok(());
                         }
                     }
+                    if let Some(idx) = enum_type.other_variant_index() {
+                        return self.deserialize_other_variant(wip, idx, start);
+                    }
                     return Err(DecodeError::InvalidEnum(format!(
                         "Unknown variant: {}",
                         variant_name
                     )));
                 }
 
+                // `#[facet(discriminant)]` enums write unit variants as their discriminant, and
+                // C-like enums can be read from one
+                if (enum_type.is_c_like() || shape.has_discriminant_attr()) && self.peek_int()? {
+                    let start = self.offset;
+                    let discriminant = self.decode_i64()?;
+                    if enum_type
+                        .variant_index_by_discriminant(discriminant)
+                        .is_none()
+                    {
+                        if let Some(idx) = enum_type.other_variant_index() {
+                            return self.deserialize_other_variant(wip, idx, start);
+                        }
+                        return Err(DecodeError::InvalidEnum(format!(
+                            "Unknown discriminant: {}, expected one of: {}",
+                            discriminant,
+                            enum_type.discriminant_list()
+                        )));
+                    }
+                    wip.select_variant(discriminant)?;
+                    return Ok(());
+                }
+
                 // Otherwise it's represented as a map with single entry where key is the variant name
                 let map_len = self.decode_map_len()?;
                 if map_len != 1 {
@@ -603,6 +831,18 @@ impl<'input, 'shape> Decoder<'input> {
             if shape.is_type::<String>() {
                 let s = self.decode_string()?;
                 wip.set(s)?;
+            } else if shape.is_type::<Arc<str>>()
+                || shape.is_type::<Rc<str>>()
+                || shape.is_type::<Box<str>>()
+            {
+                let s = self.decode_string()?;
+                wip.parse_from_str(&s)?;
+            } else if shape.is_type::<u128>() {
+                let n = self.decode_u128()?;
+                wip.set(n)?;
+            } else if shape.is_type::<i128>() {
+                let n = self.decode_i128()?;
+                wip.set(n)?;
             } else if shape.is_type::<u64>() {
                 let n = self.decode_u64()?;
                 wip.set(n)?;
@@ -680,16 +920,36 @@ impl<'input, 'shape> Decoder<'input> {
                 self.deserialize_value(wip)?;
                 wip.end()?;
             }
-        } else if let Def::List(_list_def) = shape.def {
+        } else if let Def::List(list_def) = shape.def {
             trace!("Deserializing list");
             let array_len = self.decode_array_len()?;
             wip.begin_list()?;
 
+            if self.decode_scalar_list(wip, list_def.t(), array_len)? {
+                return Ok(());
+            }
             for _ in 0..array_len {
                 wip.begin_list_item()?;
                 self.deserialize_value(wip)?;
                 wip.end()?;
             }
+        } else if let Def::Array(array_def) = shape.def {
+            trace!("Deserializing array");
+            let array_len = self.decode_array_len()?;
+
+            for index in 0..array_len {
+                if index >= array_def.n {
+                    return Err(DecodeError::ArrayOverflow {
+                        shape,
+                        index,
+                        max_len: array_def.n,
+                        offset: self.offset,
+                    });
+                }
+                wip.begin_nth_element(index)?;
+                self.deserialize_value(wip)?;
+                wip.end()?;
+            }
         } else if let Def::Option(_option_def) = shape.def {
             trace!("Deserializing option with shape: {}", shape);
             if self.peek_nil()? {
@@ -714,4 +974,62 @@ impl<'input, 'shape> Decoder<'input> {
 
         Ok(())
     }
+
+    /// Selects the `#[facet(other)]` variant at `index` for an unknown variant name or
+    /// discriminant, and reads that again from `start` into the variant's field if it has one
+    fn deserialize_other_variant<'facet>(
+        &mut self,
+        wip: &mut Partial<'facet, 'shape>,
+        index: usize,
+        start: usize,
+    ) -> Result<(), DecodeError<'shape>>
+    where
+        'input: 'facet,
+    {
+        wip.select_nth_variant(index)?;
+        if wip
+            .selected_variant()
+            .is_some_and(|v| v.data.fields.len() == 1)
+        {
+            self.offset = start;
+            wip.begin_nth_field(0)?;
+            self.deserialize_value(wip)?;
+            wip.end()?;
+        }
+        Ok(())
+    }
+
+    /// Reads the `len` items of a list of integers or booleans, and adds them to the list
+    /// `wip` is at all at once. Returns `false`, reading nothing, for other item types.
+    fn decode_scalar_list<'facet>(
+        &mut self,
+        wip: &mut Partial<'facet, 'shape>,
+        item_shape: &Shape<'_>,
+        len: usize,
+    ) -> Result<bool, DecodeError<'shape>> {
+        macro_rules! ints {
+            ($($t:ty),*) => {$(
+                if item_shape.is_type::<$t>() {
+                    let items = (0..len)
+                        .map(|_| {
+                            <$t>::try_from(self.decode_u64()?)
+                                .map_err(|_| DecodeError::IntegerOverflow)
+                        })
+                        .collect::<Result<Vec<$t>, _>>()?;
+                    wip.extend_from_slice(&items)?;
+                    return Ok(true);
+                }
+            )*};
+        }
+        ints!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+        if item_shape.is_type::<bool>() {
+            let items = (0..len)
+                .map(|_| self.decode_bool())
+                .collect::<Result<Vec<_>, _>>()?;
+            wip.extend_from_slice(&items)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
 }
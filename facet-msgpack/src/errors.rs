@@ -1,5 +1,6 @@
 use core::fmt;
 
+use facet_core::Shape;
 use facet_reflect::ReflectError;
 
 #[derive(Debug)]
@@ -28,6 +29,19 @@ pub enum Error<'shape> {
     ReflectError(ReflectError<'shape>),
     /// Invalid enum variant
     InvalidEnum(String),
+    /// Data was left after the value, from this offset on
+    TrailingData(usize),
+    /// An array has more elements than the fixed-size array it's read into
+    ArrayOverflow {
+        /// The array shape
+        shape: &'shape Shape<'shape>,
+        /// Index of the element that doesn't fit
+        index: usize,
+        /// Maximum allowed length
+        max_len: usize,
+        /// Offset of the element that doesn't fit
+        offset: usize,
+    },
 }
 
 impl<'shape> From<ReflectError<'shape>> for Error<'shape> {
@@ -60,6 +74,19 @@ impl fmt::Display for Error<'_> {
             Error::InvalidEnum(message) => {
                 write!(f, "Invalid enum variant: {}", message)
             }
+            Error::TrailingData(offset) => {
+                write!(f, "Trailing data after the value, at offset {}", offset)
+            }
+            Error::ArrayOverflow {
+                shape,
+                index,
+                max_len,
+                offset,
+            } => write!(
+                f,
+                "Too many elements for array {}: element {} doesn't fit, maximum {} elements allowed, at offset {}",
+                shape, index, max_len, offset
+            ),
         }
     }
 }
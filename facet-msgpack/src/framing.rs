@@ -0,0 +1,197 @@
+use std::io::{self, Read, Write};
+
+/// How the length of each frame is written before its payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LengthPrefix {
+    /// 4 bytes, big-endian
+    #[default]
+    U32,
+    /// An unsigned LEB128 varint, 1 byte for payloads up to 127 bytes
+    Varint,
+}
+
+/// The most bytes a varint length prefix can take.
+const MAX_VARINT_LEN: usize = 10;
+
+impl LengthPrefix {
+    /// Write the prefix for a payload of `len` bytes.
+    fn write<W: Write>(self, writer: &mut W, len: usize) -> io::Result<()> {
+        match self {
+            LengthPrefix::U32 => {
+                let len = u32::try_from(len).map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "frame is too long for a u32 length prefix",
+                    )
+                })?;
+                writer.write_all(&len.to_be_bytes())
+            }
+            LengthPrefix::Varint => {
+                let mut buf = [0; MAX_VARINT_LEN];
+                let mut len = len as u64;
+                let mut i = 0;
+                loop {
+                    let byte = (len & 0x7f) as u8;
+                    len >>= 7;
+                    if len == 0 {
+                        buf[i] = byte;
+                        break;
+                    }
+                    buf[i] = byte | 0x80;
+                    i += 1;
+                }
+                writer.write_all(&buf[..=i])
+            }
+        }
+    }
+
+    /// Read a prefix from the start of `bytes`, returning the payload length and how many
+    /// bytes the prefix took, or `None` if `bytes` ends before the prefix does.
+    fn read(self, bytes: &[u8]) -> io::Result<Option<(usize, usize)>> {
+        match self {
+            LengthPrefix::U32 => Ok(bytes
+                .first_chunk::<4>()
+                .map(|prefix| (u32::from_be_bytes(*prefix) as usize, 4))),
+            LengthPrefix::Varint => {
+                let mut len: u64 = 0;
+                for (i, &byte) in bytes.iter().take(MAX_VARINT_LEN).enumerate() {
+                    len |= u64::from(byte & 0x7f) << (7 * i);
+                    if byte & 0x80 == 0 {
+                        let len = usize::try_from(len).map_err(|_| frame_too_long())?;
+                        return Ok(Some((len, i + 1)));
+                    }
+                }
+                if bytes.len() >= MAX_VARINT_LEN {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "varint length prefix is too long",
+                    ));
+                }
+                Ok(None)
+            }
+        }
+    }
+}
+
+fn frame_too_long() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "frame is longer than the limit")
+}
+
+/// Writes `payload` to `writer` as one frame: its length, then the payload itself.
+///
+/// Use it to send values over a stream, like a TCP connection, where the reader needs to know
+/// where each one ends:
+///
+/// ```
+/// use facet::Facet;
+/// use facet_msgpack::{FrameDecoder, LengthPrefix, from_slice, to_vec, write_frame};
+///
+/// #[derive(Debug, PartialEq, Facet)]
+/// struct Ping {
+///     seq: u32,
+/// }
+///
+/// let mut stream = Vec::new();
+/// write_frame(&mut stream, &to_vec(&Ping { seq: 1 }), LengthPrefix::U32)?;
+/// write_frame(&mut stream, &to_vec(&Ping { seq: 2 }), LengthPrefix::U32)?;
+///
+/// let mut decoder = FrameDecoder::new(LengthPrefix::U32);
+/// decoder.push(&stream);
+/// let frame = decoder.next_frame()?.unwrap();
+/// assert_eq!(from_slice::<Ping>(&frame).unwrap(), Ping { seq: 1 });
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_frame<W: Write>(
+    writer: &mut W,
+    payload: &[u8],
+    prefix: LengthPrefix,
+) -> io::Result<()> {
+    prefix.write(writer, payload.len())?;
+    writer.write_all(payload)
+}
+
+/// Reads one frame written by [`write_frame`] from `reader`, blocking until all of it is there.
+///
+/// Returns `None` if the stream ends cleanly before the next frame, and an
+/// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) error if it ends partway through one.
+pub fn read_frame<R: Read>(reader: &mut R, prefix: LengthPrefix) -> io::Result<Option<Vec<u8>>> {
+    let mut decoder = FrameDecoder::new(prefix);
+    let mut byte = [0];
+    // Read the prefix a byte at a time, so nothing past this frame is consumed
+    let len = loop {
+        if let Some((len, _)) = prefix.read(&decoder.buffer)? {
+            break len;
+        }
+        if reader.read(&mut byte)? == 0 {
+            if decoder.buffer.is_empty() {
+                return Ok(None);
+            }
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        decoder.push(&byte);
+    };
+
+    let mut payload = vec![0; len];
+    reader.read_exact(&mut payload)?;
+    Ok(Some(payload))
+}
+
+/// Splits a stream of bytes written by [`write_frame`] back into frames, however the bytes
+/// arrive.
+///
+/// Feed it bytes as they're read with [`push`](Self::push), then take out each complete frame
+/// with [`next_frame`](Self::next_frame). Partial frames are kept until the rest arrives.
+#[derive(Debug, Clone)]
+pub struct FrameDecoder {
+    prefix: LengthPrefix,
+    buffer: Vec<u8>,
+    max_len: usize,
+}
+
+impl FrameDecoder {
+    /// Creates a decoder for frames with the given length prefix.
+    pub fn new(prefix: LengthPrefix) -> Self {
+        Self {
+            prefix,
+            buffer: Vec::new(),
+            max_len: usize::MAX,
+        }
+    }
+
+    /// Rejects frames longer than `max_len` bytes, so a bad length from the other end doesn't
+    /// make the decoder wait for, and buffer, a huge payload.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Adds bytes read from the stream.
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Takes out the next complete frame's payload, or `None` if the rest of it hasn't arrived.
+    ///
+    /// Fails if the length prefix is malformed or over the limit. The stream can't be
+    /// recovered after that.
+    pub fn next_frame(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let Some((len, prefix_len)) = self.prefix.read(&self.buffer)? else {
+            return Ok(None);
+        };
+        if len > self.max_len {
+            return Err(frame_too_long());
+        }
+        if self.buffer.len() - prefix_len < len {
+            return Ok(None);
+        }
+
+        let payload = self.buffer[prefix_len..prefix_len + len].to_vec();
+        self.buffer.drain(..prefix_len + len);
+        Ok(Some(payload))
+    }
+
+    /// How many bytes are buffered that aren't part of a returned frame yet.
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+}
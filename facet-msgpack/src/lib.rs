@@ -13,3 +13,9 @@ pub use deserialize::*;
 
 mod serialize;
 pub use serialize::*;
+
+mod framing;
+pub use framing::*;
+
+mod canonical;
+pub use canonical::*;
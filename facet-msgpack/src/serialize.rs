@@ -1,12 +1,18 @@
-use facet_core::Facet;
+use facet_core::{Facet, Shape, ShapeLayout};
 use facet_reflect::Peek;
-use facet_serialize::{Serializer, serialize_iterative}; // Import the necessary items from facet-serialize
+use facet_serialize::{Serializer, pointer_cycle_message, serialize_iterative}; // Import the necessary items from facet-serialize
 use log::trace;
-use std::io::{self, Write};
+use std::io::{self, BufWriter, Write};
 
 /// Serializes any Facet type to MessagePack bytes
 pub fn to_vec<'a, T: Facet<'a>>(value: &'a T) -> Vec<u8> {
-    let mut buffer = Vec::new();
+    to_vec_with_capacity(value, size_hint(T::SHAPE))
+}
+
+/// Serializes any Facet type to MessagePack bytes, in a buffer that starts with room for
+/// `capacity` bytes
+pub fn to_vec_with_capacity<'a, T: Facet<'a>>(value: &'a T, capacity: usize) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(capacity);
     let peek = Peek::new(value);
     let mut serializer = MessagePackSerializer {
         writer: &mut buffer,
@@ -15,11 +21,48 @@ pub fn to_vec<'a, T: Facet<'a>>(value: &'a T) -> Vec<u8> {
     buffer
 }
 
-// Define the MessagePackSerializer struct
-struct MessagePackSerializer<'w, W: Write> {
+/// Serializes a `Peek` instance to MessagePack bytes
+pub fn peek_to_vec(peek: Peek<'_, '_, '_>) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(size_hint(peek.shape()));
+    let mut serializer = MessagePackSerializer {
+        writer: &mut buffer,
+    };
+    serialize_iterative(peek, &mut serializer).unwrap();
+    buffer
+}
+
+/// Serializes any Facet type to MessagePack and writes it to `writer`.
+///
+/// The output is buffered in small chunks that are written out as they fill up, so large values
+/// are never held in memory all at once.
+pub fn to_writer<'a, T: Facet<'a>, W: Write>(value: &'a T, writer: &mut W) -> io::Result<()> {
+    let mut writer = BufWriter::new(writer);
+    let mut serializer = MessagePackSerializer::new(&mut writer);
+    serialize_iterative(Peek::new(value), &mut serializer)?;
+    writer.flush()
+}
+
+/// Guesses how many bytes a value of `shape` takes in MessagePack from its size in memory,
+/// which is close for structs of numbers.
+fn size_hint(shape: &Shape) -> usize {
+    match shape.layout {
+        ShapeLayout::Sized(layout) => layout.size(),
+        ShapeLayout::Unsized => 0,
+    }
+}
+
+/// A serializer for MessagePack that implements the `facet_serialize::Serializer` trait.
+pub struct MessagePackSerializer<'w, W: Write> {
     writer: &'w mut W,
 }
 
+impl<'w, W: Write> MessagePackSerializer<'w, W> {
+    /// Creates a new MessagePack serializer with the given writer.
+    pub fn new(writer: &'w mut W) -> Self {
+        Self { writer }
+    }
+}
+
 // Implement the Serializer trait for MessagePackSerializer
 impl<'shape, W: Write> Serializer<'shape> for MessagePackSerializer<'_, W> {
     type Error = io::Error; // Use io::Error as the error type
@@ -47,11 +90,10 @@ impl<'shape, W: Write> Serializer<'shape> for MessagePackSerializer<'_, W> {
         write_u64(self.writer, value)
     }
 
-    // TODO: Implement serialize_u128 if needed for MessagePack, otherwise return error or panic
-    fn serialize_u128(&mut self, _value: u128) -> Result<(), Self::Error> {
-        Err(io::Error::other(
-            "u128 is not directly supported by MessagePack",
-        ))
+    // MessagePack has no 128-bit integers, so these are written as 16 big-endian bytes
+    fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
+        trace!("Serializing u128: {}", value);
+        write_bin(self.writer, &value.to_be_bytes())
     }
 
     // Map usize to u64 as MessagePack doesn't have a specific usize type
@@ -80,11 +122,10 @@ impl<'shape, W: Write> Serializer<'shape> for MessagePackSerializer<'_, W> {
         write_i64(self.writer, value)
     }
 
-    // TODO: Implement serialize_i128 if needed for MessagePack, otherwise return error or panic
-    fn serialize_i128(&mut self, _value: i128) -> Result<(), Self::Error> {
-        Err(io::Error::other(
-            "i128 is not directly supported by MessagePack",
-        ))
+    // Written as 16 big-endian bytes in two's complement, like u128
+    fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
+        trace!("Serializing i128: {}", value);
+        write_bin(self.writer, &value.to_be_bytes())
     }
 
     // Map isize to i64 as MessagePack doesn't have a specific isize type
@@ -130,6 +171,10 @@ impl<'shape, W: Write> Serializer<'shape> for MessagePackSerializer<'_, W> {
         write_nil(self.writer)
     }
 
+    fn serialize_pointer_cycle(&mut self, shape: &'shape Shape<'shape>) -> Result<(), Self::Error> {
+        Err(io::Error::other(pointer_cycle_message(shape)))
+    }
+
     fn serialize_unit(&mut self) -> Result<(), Self::Error> {
         trace!("Serializing unit");
         write_nil(self.writer) // Represent unit as nil
@@ -224,17 +269,17 @@ fn write_bool<W: Write>(writer: &mut W, val: bool) -> io::Result<()> {
     }
 }
 
-fn write_f32<W: Write>(writer: &mut W, n: f32) -> io::Result<()> {
+pub(crate) fn write_f32<W: Write>(writer: &mut W, n: f32) -> io::Result<()> {
     writer.write_all(&[0xca])?; // float 32
     writer.write_all(&n.to_be_bytes())
 }
 
-fn write_f64<W: Write>(writer: &mut W, n: f64) -> io::Result<()> {
+pub(crate) fn write_f64<W: Write>(writer: &mut W, n: f64) -> io::Result<()> {
     writer.write_all(&[0xcb])?; // float 64
     writer.write_all(&n.to_be_bytes())
 }
 
-fn write_bin<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+pub(crate) fn write_bin<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
     let len = bytes.len();
     match len {
         0..=255 => {
@@ -255,7 +300,7 @@ fn write_bin<W: Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
     writer.write_all(bytes)
 }
 
-fn write_array_len<W: Write>(writer: &mut W, len: usize) -> io::Result<()> {
+pub(crate) fn write_array_len<W: Write>(writer: &mut W, len: usize) -> io::Result<()> {
     match len {
         0..=15 => {
             // fixarray
@@ -278,7 +323,7 @@ fn write_array_len<W: Write>(writer: &mut W, len: usize) -> io::Result<()> {
 // (write_str, write_u8, write_u16, write_u32, write_u64, write_i8, write_i16, write_i32, write_i64, write_map_len)
 // These remain largely unchanged.
 
-fn write_str<W: Write>(writer: &mut W, s: &str) -> io::Result<()> {
+pub(crate) fn write_str<W: Write>(writer: &mut W, s: &str) -> io::Result<()> {
     let bytes = s.as_bytes();
     let len = bytes.len();
 
@@ -359,7 +404,7 @@ fn write_u32<W: Write>(writer: &mut W, n: u32) -> io::Result<()> {
     }
 }
 
-fn write_u64<W: Write>(writer: &mut W, n: u64) -> io::Result<()> {
+pub(crate) fn write_u64<W: Write>(writer: &mut W, n: u64) -> io::Result<()> {
     match n {
         0..=127 => {
             // positive fixint
@@ -453,7 +498,7 @@ fn write_i32<W: Write>(writer: &mut W, n: i32) -> io::Result<()> {
     }
 }
 
-fn write_i64<W: Write>(writer: &mut W, n: i64) -> io::Result<()> {
+pub(crate) fn write_i64<W: Write>(writer: &mut W, n: i64) -> io::Result<()> {
     match n {
         -32..=-1 => {
             // negative fixint
@@ -485,7 +530,7 @@ fn write_i64<W: Write>(writer: &mut W, n: i64) -> io::Result<()> {
     }
 }
 
-fn write_map_len<W: Write>(writer: &mut W, len: usize) -> io::Result<()> {
+pub(crate) fn write_map_len<W: Write>(writer: &mut W, len: usize) -> io::Result<()> {
     match len {
         0..=15 => {
             // fixmap
@@ -0,0 +1,61 @@
+use std::borrow::Cow;
+
+use eyre::Result;
+use facet::Facet;
+use facet_msgpack::{from_slice, to_vec};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Blob<'a> {
+    raw: &'a [u8],
+    cow: Cow<'a, [u8]>,
+    owned: Vec<u8>,
+}
+
+#[test]
+fn msgpack_bin_fields_borrow_from_input() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let data = [
+        0x83, // Map with 3 elements
+        0xa3, b'r', b'a', b'w', // "raw"
+        0xc4, 0x02, 0x01, 0x02, // bin8 [1, 2]
+        0xa3, b'c', b'o', b'w', // "cow"
+        0xc4, 0x01, 0x03, // bin8 [3]
+        0xa5, b'o', b'w', b'n', b'e', b'd', // "owned"
+        0xc4, 0x02, 0x04, 0x05, // bin8 [4, 5]
+    ];
+
+    let blob: Blob = from_slice(&data)?;
+    assert_eq!(blob.raw, &[1, 2]);
+    assert!(core::ptr::eq(blob.raw.as_ptr(), &data[7]));
+    assert!(matches!(blob.cow, Cow::Borrowed(&[3])));
+    assert_eq!(blob.owned, vec![4, 5]);
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_bin_roundtrip() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let blob = Blob {
+        raw: &[0xff; 300],
+        cow: Cow::Owned(vec![1, 2, 3]),
+        owned: vec![],
+    };
+    let data = to_vec(&blob);
+    assert_eq!(from_slice::<Blob>(&data)?, blob);
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_top_level_bin() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let data = [0xc5, 0x00, 0x01, 0x2a]; // bin16 [42]
+    assert_eq!(from_slice::<&[u8]>(&data)?, &[42]);
+    assert_eq!(from_slice::<Cow<[u8]>>(&data)?, Cow::Borrowed(&[42][..]));
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_msgpack::{CanonicalError, canonicalize, from_slice, to_vec_canonical};
+
+#[derive(Debug, Clone, PartialEq, Facet)]
+struct Grant {
+    user: String,
+    scopes: HashMap<String, u32>,
+    expires: u64,
+}
+
+#[test]
+fn same_bytes_for_equal_values() {
+    facet_testhelpers::setup();
+
+    let scopes: Vec<(String, u32)> = (0..32).map(|i| (format!("scope{i}"), i)).collect();
+    let a = Grant {
+        user: "jo".to_string(),
+        scopes: scopes.iter().cloned().collect(),
+        expires: 1_700_000_000,
+    };
+    let b = Grant {
+        scopes: scopes.iter().rev().cloned().collect(),
+        ..a.clone()
+    };
+
+    let bytes = to_vec_canonical(&a).unwrap();
+    assert_eq!(bytes, to_vec_canonical(&b).unwrap());
+    assert_eq!(canonicalize(&bytes).unwrap(), bytes);
+    assert_eq!(from_slice::<Grant>(&bytes).unwrap(), a);
+}
+
+#[test]
+fn struct_fields_are_sorted() {
+    facet_testhelpers::setup();
+
+    #[derive(Facet)]
+    struct Point {
+        y: u8,
+        x: u8,
+    }
+
+    let bytes = to_vec_canonical(&Point { y: 2, x: 1 }).unwrap();
+    assert_eq!(bytes, [0x82, 0xa1, b'x', 0x01, 0xa1, b'y', 0x02]);
+}
+
+#[test]
+fn shortest_integers() {
+    facet_testhelpers::setup();
+
+    // uint64 1, int32 -1, int16 200 and uint32 65536
+    let input = [
+        0x94, 0xcf, 0, 0, 0, 0, 0, 0, 0, 1, 0xd2, 0xff, 0xff, 0xff, 0xff, 0xd1, 0, 200, 0xce, 0, 1,
+        0, 0,
+    ];
+    assert_eq!(
+        canonicalize(&input).unwrap(),
+        [0x94, 0x01, 0xff, 0xcc, 200, 0xce, 0, 1, 0, 0]
+    );
+
+    // A str16 of 3 bytes and an ext8 of 4 bytes
+    let input = [0x92, 0xda, 0, 3, b'a', b'b', b'c', 0xc7, 4, 1, 9, 9, 9, 9];
+    assert_eq!(
+        canonicalize(&input).unwrap(),
+        [0x92, 0xa3, b'a', b'b', b'c', 0xd6, 1, 9, 9, 9, 9]
+    );
+}
+
+#[test]
+fn floats() {
+    facet_testhelpers::setup();
+
+    let float64 = |n: f64| {
+        let mut bytes = vec![0xcb];
+        bytes.extend_from_slice(&n.to_be_bytes());
+        canonicalize(&bytes).unwrap()
+    };
+
+    assert_eq!(float64(3.0), [0x03]);
+    assert_eq!(float64(-40.0), [0xd0, 0xd8]);
+    assert_eq!(float64(0.5), [0xca, 0x3f, 0x00, 0x00, 0x00]);
+    assert_eq!(float64(0.1), {
+        let mut bytes = vec![0xcb];
+        bytes.extend_from_slice(&0.1f64.to_be_bytes());
+        bytes
+    });
+    assert_eq!(float64(-0.0), [0xca, 0x80, 0x00, 0x00, 0x00]);
+    assert_eq!(float64(f64::INFINITY), [0xca, 0x7f, 0x80, 0x00, 0x00]);
+    assert_eq!(float64(f64::NAN), [0xca, 0x7f, 0xc0, 0x00, 0x00]);
+    assert_eq!(float64(-f64::NAN), float64(f64::NAN));
+}
+
+#[test]
+fn errors() {
+    facet_testhelpers::setup();
+
+    // {"a": 1, "a": 2}
+    let duplicate = [0x82, 0xa1, b'a', 0x01, 0xa1, b'a', 0x02];
+    assert_eq!(
+        canonicalize(&duplicate),
+        Err(CanonicalError::DuplicateKey(4))
+    );
+
+    assert_eq!(
+        canonicalize(&[0x92, 0x01]),
+        Err(CanonicalError::InsufficientData)
+    );
+    assert_eq!(
+        canonicalize(&[0x91, 0xc1]),
+        Err(CanonicalError::InvalidMarker {
+            marker: 0xc1,
+            offset: 1
+        })
+    );
+    assert_eq!(
+        canonicalize(&[0xa1, 0xff]),
+        Err(CanonicalError::InvalidUtf8(0))
+    );
+    assert_eq!(
+        canonicalize(&[0x01, 0x02]),
+        Err(CanonicalError::TrailingData(1))
+    );
+    assert_eq!(
+        canonicalize(&[0x91; 1000]),
+        Err(CanonicalError::TooDeep(512))
+    );
+    // A huge length with nothing after it fails without allocating for it
+    assert_eq!(
+        canonicalize(&[0xdf, 0xff, 0xff, 0xff, 0xff]),
+        Err(CanonicalError::InsufficientData)
+    );
+}
@@ -107,3 +107,87 @@ fn msgpack_deserialize_struct_variant() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn msgpack_unit_enum_variant_as_discriminant() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(discriminant)]
+    #[repr(i16)]
+    enum Status {
+        Unknown = -1,
+        Active = 1,
+        Archived = 300,
+    }
+
+    let bytes = facet_msgpack::to_vec(&Status::Active);
+    assert_eq!(bytes, [0x01]);
+    assert_eq!(from_slice::<Status>(&bytes)?, Status::Active);
+
+    let bytes = facet_msgpack::to_vec(&Status::Archived);
+    assert_eq!(from_slice::<Status>(&bytes)?, Status::Archived);
+
+    let bytes = facet_msgpack::to_vec(&Status::Unknown);
+    assert_eq!(from_slice::<Status>(&bytes)?, Status::Unknown);
+
+    // Names are still accepted
+    let data = [0xa6, 0x41, 0x63, 0x74, 0x69, 0x76, 0x65]; // "Active"
+    assert_eq!(from_slice::<Status>(&data)?, Status::Active);
+
+    assert!(from_slice::<Status>(&[0x02]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_c_like_enum_from_discriminant() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Level {
+        Low = 1,
+        Medium = 5,
+        High = 10,
+    }
+
+    assert_eq!(from_slice::<Level>(&[0x01])?, Level::Low);
+    assert_eq!(from_slice::<Level>(&[0x05])?, Level::Medium);
+    assert_eq!(from_slice::<Level>(&[0xcc, 0x0a])?, Level::High); // uint8 10
+
+    let err = from_slice::<Level>(&[0x02]).unwrap_err();
+    assert!(
+        err.to_string().contains("1 (Low), 5 (Medium), 10 (High)"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_other_variant_keeps_unknown_name() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Event {
+        Created,
+        #[facet(other)]
+        Unknown(String),
+    }
+
+    // fixstr "Created", then fixstr "Moved"
+    assert_eq!(
+        from_slice::<Event>(&[0xa7, b'C', b'r', b'e', b'a', b't', b'e', b'd'])?,
+        Event::Created
+    );
+    let event = from_slice::<Event>(&[0xa5, b'M', b'o', b'v', b'e', b'd'])?;
+    assert_eq!(event, Event::Unknown("Moved".to_string()));
+    assert_eq!(
+        facet_msgpack::to_vec(&event),
+        [0xa5, b'M', b'o', b'v', b'e', b'd']
+    );
+
+    Ok(())
+}
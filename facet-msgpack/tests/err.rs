@@ -43,3 +43,19 @@ fn bad_hex_2() -> Result<()> {
     insta::assert_snapshot!(err);
     Ok(())
 }
+
+#[test]
+fn truncated_unknown_field() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let data = [
+        0x81, // Fixmap with 1 element
+        0xa3, // Fixstr with length 3
+        0x62, 0x61, 0x7a, // "baz", which FooBar doesn't have
+        0xcf, // uint64, of which only one byte follows
+        0x00,
+    ];
+    let err = facet_msgpack::from_slice_partial::<FooBar>(&data).unwrap_err();
+    assert!(matches!(err, facet_msgpack::DecodeError::InsufficientData));
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use std::io::{Cursor, ErrorKind};
+
+use facet::Facet;
+use facet_msgpack::{FrameDecoder, LengthPrefix, from_slice, read_frame, to_vec, write_frame};
+
+#[derive(Debug, PartialEq, Facet)]
+struct Message {
+    id: u32,
+    body: String,
+}
+
+fn messages() -> Vec<Message> {
+    vec![
+        Message {
+            id: 1,
+            body: "hello".to_string(),
+        },
+        Message {
+            id: 2,
+            body: "x".repeat(300),
+        },
+    ]
+}
+
+fn write_stream(prefix: LengthPrefix) -> Vec<u8> {
+    let mut stream = Vec::new();
+    for message in messages() {
+        write_frame(&mut stream, &to_vec(&message), prefix).unwrap();
+    }
+    stream
+}
+
+#[test]
+fn test_decoder_partial_reads() {
+    facet_testhelpers::setup();
+
+    for prefix in [LengthPrefix::U32, LengthPrefix::Varint] {
+        let stream = write_stream(prefix);
+        let mut decoder = FrameDecoder::new(prefix);
+        let mut decoded = Vec::new();
+        // Bytes arriving a few at a time, split anywhere
+        for chunk in stream.chunks(3) {
+            decoder.push(chunk);
+            while let Some(frame) = decoder.next_frame().unwrap() {
+                decoded.push(from_slice::<Message>(&frame).unwrap());
+            }
+        }
+        assert_eq!(decoded, messages());
+        assert_eq!(decoder.buffered_len(), 0);
+    }
+}
+
+#[test]
+fn test_prefix_bytes() {
+    facet_testhelpers::setup();
+
+    let mut stream = Vec::new();
+    write_frame(&mut stream, &[0xaa; 300], LengthPrefix::U32).unwrap();
+    assert_eq!(stream[..4], [0, 0, 1, 44]);
+
+    let mut stream = Vec::new();
+    write_frame(&mut stream, &[0xaa; 300], LengthPrefix::Varint).unwrap();
+    assert_eq!(stream[..2], [0xac, 0x02]);
+    assert_eq!(stream.len(), 302);
+}
+
+#[test]
+fn test_read_frame() {
+    facet_testhelpers::setup();
+
+    let mut reader = Cursor::new(write_stream(LengthPrefix::Varint));
+    let mut decoded = Vec::new();
+    while let Some(frame) = read_frame(&mut reader, LengthPrefix::Varint).unwrap() {
+        decoded.push(from_slice::<Message>(&frame).unwrap());
+    }
+    assert_eq!(decoded, messages());
+
+    // Cut off partway through the second frame
+    let mut stream = write_stream(LengthPrefix::U32);
+    stream.truncate(stream.len() - 1);
+    let mut reader = Cursor::new(stream);
+    assert!(
+        read_frame(&mut reader, LengthPrefix::U32)
+            .unwrap()
+            .is_some()
+    );
+    let err = read_frame(&mut reader, LengthPrefix::U32).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_max_len() {
+    facet_testhelpers::setup();
+
+    let mut decoder = FrameDecoder::new(LengthPrefix::U32).with_max_len(100);
+    decoder.push(&write_stream(LengthPrefix::U32));
+    assert!(decoder.next_frame().unwrap().is_some());
+    let err = decoder.next_frame().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
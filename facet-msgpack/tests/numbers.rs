@@ -73,3 +73,49 @@ fn msgpack_deserialize_more_types() -> Result<()> {
 
     Ok(())
 }
+
+#[derive(Facet, Debug, PartialEq)]
+struct Wide {
+    unsigned: u128,
+    signed: i128,
+}
+
+#[test]
+fn msgpack_128_bit_integers_as_bin() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let wide = Wide {
+        unsigned: u128::MAX - 1,
+        signed: -2,
+    };
+    let bytes = facet_msgpack::to_vec(&wide);
+    let mut expected = vec![0x82];
+    expected.extend(b"\xa8unsigned\xc4\x10");
+    expected.extend([0xff; 15]);
+    expected.push(0xfe);
+    expected.extend(b"\xa6signed\xc4\x10");
+    expected.extend([0xff; 15]);
+    expected.push(0xfe);
+    assert_eq!(bytes, expected);
+    assert_eq!(from_slice::<Wide>(&bytes)?, wide);
+
+    let wide = Wide {
+        unsigned: 1 << 64,
+        signed: i128::MIN,
+    };
+    assert_eq!(from_slice::<Wide>(&facet_msgpack::to_vec(&wide))?, wide);
+
+    // Smaller integers from other encoders are accepted too
+    let data = [
+        0x82, // Map with 2 elements
+        0xa8, b'u', b'n', b's', b'i', b'g', b'n', b'e', b'd', // "unsigned"
+        0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // uint64 max
+        0xa6, b's', b'i', b'g', b'n', b'e', b'd', // "signed"
+        0xd0, 0x80, // int8 -128
+    ];
+    let wide: Wide = from_slice(&data)?;
+    assert_eq!(wide.unsigned, u64::MAX as u128);
+    assert_eq!(wide.signed, -128);
+
+    Ok(())
+}
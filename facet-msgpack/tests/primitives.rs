@@ -51,3 +51,28 @@ fn test_str_escaped() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_shared_and_boxed_strs() -> Result<()> {
+    facet_testhelpers::setup();
+
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct S {
+        arc: Arc<str>,
+        rc: Rc<str>,
+        boxed: Box<str>,
+    }
+
+    let value = S {
+        arc: "a".into(),
+        rc: "r".into(),
+        boxed: "b".into(),
+    };
+    let data = facet_msgpack::to_vec(&value);
+    assert_eq!(from_slice::<S>(&data)?, value);
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use eyre::Result;
+use facet_msgpack::{DecodeError, from_slice, from_slice_partial};
+
+#[test]
+fn test_trailing_data_is_an_error() {
+    facet_testhelpers::setup();
+
+    // 1, then 2
+    let data = [0x01, 0x02];
+    assert!(matches!(
+        from_slice::<u8>(&data),
+        Err(DecodeError::TrailingData(1))
+    ));
+}
+
+#[test]
+fn test_partial_returns_consumed_bytes() -> Result<()> {
+    facet_testhelpers::setup();
+
+    // ["a", 5], then "b"
+    let data = [0x92, 0xa1, 0x61, 0x05, 0xa1, 0x62];
+    let (first, len) = from_slice_partial::<(String, u8)>(&data)?;
+    assert_eq!(first, ("a".to_string(), 5));
+    assert_eq!(len, 4);
+
+    let (second, rest) = from_slice_partial::<String>(&data[len..])?;
+    assert_eq!(second, "b");
+    assert_eq!(len + rest, data.len());
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use core::marker::PhantomData;
+
+use eyre::Result;
+use facet::Facet;
+use facet_msgpack::{from_slice, to_vec};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Marker;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Tagged {
+    id: u32,
+    marker: Marker,
+    phantom: PhantomData<String>,
+    unit: (),
+}
+
+fn tagged() -> Tagged {
+    Tagged {
+        id: 7,
+        marker: Marker,
+        phantom: PhantomData,
+        unit: (),
+    }
+}
+
+#[test]
+fn msgpack_unit_types_serialize_as_nil() -> Result<()> {
+    facet_testhelpers::setup();
+
+    assert_eq!(to_vec(&()), [0xc0]);
+    assert_eq!(to_vec(&Marker), [0xc0]);
+    assert_eq!(to_vec(&PhantomData::<u8>), [0xc0]);
+    assert_eq!(from_slice::<Tagged>(&to_vec(&tagged()))?, tagged());
+
+    Ok(())
+}
+
+#[test]
+fn msgpack_unit_fields_can_be_missing() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let data = [
+        0x81, // Map with 1 element
+        0xa2, b'i', b'd', // "id"
+        0x07, // 7
+    ];
+    assert_eq!(from_slice::<Tagged>(&data)?, tagged());
+
+    Ok(())
+}
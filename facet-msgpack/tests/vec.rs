@@ -60,3 +60,29 @@ fn test_nested_arrays() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_fixed_size_arrays() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let data = [
+        0x93, // Array with 3 elements
+        0x01, 0x02, 0x03, // 1, 2, 3
+    ];
+
+    let array: [u8; 3] = from_slice(&data)?;
+    assert_eq!(array, [1, 2, 3]);
+
+    let err = from_slice::<[u8; 2]>(&data).unwrap_err();
+    assert!(matches!(
+        err,
+        facet_msgpack::DecodeError::ArrayOverflow {
+            index: 2,
+            max_len: 2,
+            offset: 3,
+            ..
+        }
+    ));
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use facet::Facet;
-use facet_msgpack::to_vec;
+use facet_msgpack::{to_vec, to_vec_with_capacity, to_writer};
 
 #[test]
 fn test_integers() {
@@ -120,3 +120,47 @@ fn test_struct() {
 
     assert_eq!(msgpack, expected);
 }
+
+#[test]
+fn test_to_writer() {
+    facet_testhelpers::setup();
+
+    /// Records the size of each write it gets
+    #[derive(Default)]
+    struct ChunkRecorder {
+        bytes: Vec<u8>,
+        chunks: Vec<usize>,
+    }
+
+    impl std::io::Write for ChunkRecorder {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.bytes.extend_from_slice(buf);
+            self.chunks.push(buf.len());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, PartialEq, Clone, Facet)]
+    struct Samples {
+        name: String,
+        values: Vec<u64>,
+    }
+
+    let samples = Samples {
+        name: "sensor".to_string(),
+        values: (0..100_000).map(|i| i * 1_000_003).collect(),
+    };
+
+    let mut recorder = ChunkRecorder::default();
+    to_writer(&samples, &mut recorder).unwrap();
+
+    assert_eq!(recorder.bytes, to_vec(&samples));
+    assert_eq!(recorder.bytes, to_vec_with_capacity(&samples, 16));
+    // Written out as it goes, without holding the whole output in memory
+    assert!(recorder.chunks.len() > 1);
+    assert!(recorder.chunks.iter().all(|&len| len <= 8 * 1024));
+}
@@ -0,0 +1,158 @@
+use std::io::{self, Cursor, Read, Write};
+
+use chacha20poly1305::aead::{Aead as _, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+
+use crate::{FinishWrite, Layer};
+
+/// The byte that starts every payload, naming the algorithm it's encrypted with, so others
+/// can be added without misreading existing payloads. `1` is XChaCha20-Poly1305.
+const XCHACHA20_POLY1305: u8 = 1;
+
+/// Length of the random nonce written after the algorithm byte
+const NONCE_LEN: usize = 24;
+
+/// Encrypts and authenticates payloads with XChaCha20-Poly1305 and a 256-bit key.
+///
+/// Each payload starts with a byte naming the algorithm, then a random 192-bit nonce, then
+/// the ciphertext. The algorithm byte is authenticated along with the associated data. Nonces that large can be picked at random for any number of payloads
+/// under the same key without repeating. Reading fails with [`io::ErrorKind::InvalidData`]
+/// if the payload was changed, encrypted with another key or other associated data, or
+/// starts with an algorithm byte this version doesn't know.
+///
+/// The payload is encrypted once it's all written, so it's held in memory until then, and
+/// the same when it's read.
+#[derive(Clone)]
+pub struct Aead {
+    cipher: XChaCha20Poly1305,
+    associated_data: Vec<u8>,
+}
+
+impl Aead {
+    /// Encrypts with `key`, which should come from a secure random source or a key
+    /// derivation function, never straight from a password.
+    pub fn new(key: [u8; 32]) -> Self {
+        Aead {
+            cipher: XChaCha20Poly1305::new(&Key::from(key)),
+            associated_data: Vec::new(),
+        }
+    }
+
+    /// Binds payloads to `associated_data`, like a file name or a record id, which isn't
+    /// written but has to be the same to read them back.
+    pub fn with_associated_data(mut self, associated_data: impl Into<Vec<u8>>) -> Self {
+        self.associated_data = associated_data.into();
+        self
+    }
+
+    /// The data authenticated with the ciphertext: the algorithm byte, then the associated data
+    fn aad(&self, algorithm: u8) -> Vec<u8> {
+        let mut aad = Vec::with_capacity(1 + self.associated_data.len());
+        aad.push(algorithm);
+        aad.extend_from_slice(&self.associated_data);
+        aad
+    }
+}
+
+impl core::fmt::Debug for Aead {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Never prints the key
+        f.debug_struct("Aead").finish_non_exhaustive()
+    }
+}
+
+impl Layer for Aead {
+    fn writer<'w>(
+        &self,
+        inner: Box<dyn FinishWrite + 'w>,
+    ) -> io::Result<Box<dyn FinishWrite + 'w>> {
+        Ok(Box::new(AeadWriter {
+            aead: self.clone(),
+            plaintext: Vec::new(),
+            inner,
+        }))
+    }
+
+    fn reader<'r>(&self, mut inner: Box<dyn Read + 'r>) -> io::Result<Box<dyn Read + 'r>> {
+        let mut payload = Vec::new();
+        inner.read_to_end(&mut payload)?;
+        let Some((&algorithm, rest)) = payload.split_first() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "encrypted payload is empty",
+            ));
+        };
+        if algorithm != XCHACHA20_POLY1305 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("encrypted payload uses unknown algorithm {algorithm}"),
+            ));
+        }
+        if rest.len() < NONCE_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "encrypted payload is too short",
+            ));
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &self.aad(algorithm),
+                },
+            )
+            .map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "encrypted payload failed authentication",
+                )
+            })?;
+        Ok(Box::new(Cursor::new(plaintext)))
+    }
+}
+
+struct AeadWriter<'w> {
+    aead: Aead,
+    plaintext: Vec<u8>,
+    inner: Box<dyn FinishWrite + 'w>,
+}
+
+impl Write for AeadWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.plaintext.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // Nothing can be written before the whole payload is known
+        Ok(())
+    }
+}
+
+impl FinishWrite for AeadWriter<'_> {
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        let AeadWriter {
+            aead,
+            plaintext,
+            mut inner,
+        } = *self;
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = aead
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: &plaintext,
+                    aad: &aead.aad(XCHACHA20_POLY1305),
+                },
+            )
+            .map_err(|_| io::Error::other("payload is too large to encrypt"))?;
+        inner.write_all(&[XCHACHA20_POLY1305])?;
+        inner.write_all(&nonce)?;
+        inner.write_all(&ciphertext)?;
+        inner.finish()
+    }
+}
@@ -0,0 +1,40 @@
+use core::fmt;
+use std::io;
+
+/// Why a payload couldn't be written or read back.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PayloadError {
+    /// Writing or reading failed, in a layer or in the underlying writer or reader.
+    ///
+    /// Payloads that can't be decompressed or decrypted fail with
+    /// [`io::ErrorKind::InvalidData`].
+    Io(io::Error),
+    /// The bytes under the layers aren't a valid value of the format, with the format's
+    /// error message.
+    Deserialize(String),
+}
+
+impl fmt::Display for PayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayloadError::Io(e) => write!(f, "I/O error: {e}"),
+            PayloadError::Deserialize(e) => write!(f, "Deserialization error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PayloadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PayloadError::Io(e) => Some(e),
+            PayloadError::Deserialize(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for PayloadError {
+    fn from(e: io::Error) -> Self {
+        PayloadError::Io(e)
+    }
+}
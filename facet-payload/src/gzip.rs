@@ -0,0 +1,63 @@
+use std::io::{self, Read, Write};
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use crate::{FinishWrite, Layer};
+
+/// Compresses payloads with gzip.
+#[derive(Debug, Clone, Copy)]
+pub struct Gzip {
+    level: u32,
+}
+
+impl Gzip {
+    /// Compresses at `level`, from 0 (none) to 9 (smallest).
+    pub fn new(level: u32) -> Self {
+        Gzip {
+            level: level.min(9),
+        }
+    }
+}
+
+impl Default for Gzip {
+    /// Compresses at level 6, like the `gzip` tool.
+    fn default() -> Self {
+        Gzip::new(6)
+    }
+}
+
+impl Layer for Gzip {
+    fn writer<'w>(
+        &self,
+        inner: Box<dyn FinishWrite + 'w>,
+    ) -> io::Result<Box<dyn FinishWrite + 'w>> {
+        Ok(Box::new(GzipWriter(GzEncoder::new(
+            inner,
+            Compression::new(self.level),
+        ))))
+    }
+
+    fn reader<'r>(&self, inner: Box<dyn Read + 'r>) -> io::Result<Box<dyn Read + 'r>> {
+        Ok(Box::new(GzDecoder::new(inner)))
+    }
+}
+
+struct GzipWriter<'w>(GzEncoder<Box<dyn FinishWrite + 'w>>);
+
+impl Write for GzipWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl FinishWrite for GzipWriter<'_> {
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        self.0.finish()?.finish()
+    }
+}
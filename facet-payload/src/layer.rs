@@ -0,0 +1,113 @@
+use std::io::{self, Read, Write};
+
+/// A writer that has to be told when the payload ends, like a compressor that writes a
+/// trailer, or a cipher that only encrypts once it has everything.
+pub trait FinishWrite: Write {
+    /// Writes whatever is left, then finishes the writer under this one.
+    fn finish(self: Box<Self>) -> io::Result<()>;
+}
+
+/// A transformation of serialized bytes, like compression or encryption, that plugs in
+/// between a format's serializer and the `io::Write` it writes to, and between the
+/// `io::Read` it's read back from and the deserializer.
+pub trait Layer {
+    /// Wraps `inner`, so that what's written to the returned writer is transformed before
+    /// it's written to `inner`.
+    fn writer<'w>(&self, inner: Box<dyn FinishWrite + 'w>)
+    -> io::Result<Box<dyn FinishWrite + 'w>>;
+
+    /// Wraps `inner`, so that reading from the returned reader undoes what
+    /// [`Layer::writer`] did.
+    fn reader<'r>(&self, inner: Box<dyn Read + 'r>) -> io::Result<Box<dyn Read + 'r>>;
+}
+
+impl<L: Layer + ?Sized> Layer for &L {
+    fn writer<'w>(
+        &self,
+        inner: Box<dyn FinishWrite + 'w>,
+    ) -> io::Result<Box<dyn FinishWrite + 'w>> {
+        (**self).writer(inner)
+    }
+
+    fn reader<'r>(&self, inner: Box<dyn Read + 'r>) -> io::Result<Box<dyn Read + 'r>> {
+        (**self).reader(inner)
+    }
+}
+
+/// Several layers applied one after the other, like compression then encryption.
+///
+/// An empty stack writes bytes as they are.
+///
+/// ```
+/// # #[cfg(all(feature = "gzip", feature = "aead"))] {
+/// use facet_payload::{Aead, Gzip, Layers};
+///
+/// // Compressed, then encrypted; read back by decrypting, then decompressing
+/// let layers = Layers::new().then(Gzip::default()).then(Aead::new([7; 32]));
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Layers {
+    layers: Vec<Box<dyn Layer + Send + Sync>>,
+}
+
+impl Layers {
+    /// A stack with no layer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a layer that transforms the output of the ones added before it.
+    pub fn then(mut self, layer: impl Layer + Send + Sync + 'static) -> Self {
+        self.layers.push(Box::new(layer));
+        self
+    }
+}
+
+impl core::fmt::Debug for Layers {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Layers")
+            .field("len", &self.layers.len())
+            .finish()
+    }
+}
+
+impl Layer for Layers {
+    fn writer<'w>(
+        &self,
+        inner: Box<dyn FinishWrite + 'w>,
+    ) -> io::Result<Box<dyn FinishWrite + 'w>> {
+        // The last layer is the one that writes to `inner`
+        self.layers
+            .iter()
+            .rev()
+            .try_fold(inner, |inner, layer| layer.writer(inner))
+    }
+
+    fn reader<'r>(&self, inner: Box<dyn Read + 'r>) -> io::Result<Box<dyn Read + 'r>> {
+        // The last layer is undone first, since it's the one that reads from `inner`
+        self.layers
+            .iter()
+            .rev()
+            .try_fold(inner, |inner, layer| layer.reader(inner))
+    }
+}
+
+/// The writer under all the layers, which only needs flushing at the end.
+pub(crate) struct Sink<W>(pub(crate) W);
+
+impl<W: Write> Write for Sink<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl<W: Write> FinishWrite for Sink<W> {
+    fn finish(mut self: Box<Self>) -> io::Result<()> {
+        self.0.flush()
+    }
+}
@@ -0,0 +1,158 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use std::io::{self, Read, Write};
+
+use facet_core::Facet;
+
+mod error;
+pub use error::*;
+
+mod layer;
+pub use layer::*;
+
+#[cfg(feature = "gzip")]
+mod gzip;
+#[cfg(feature = "gzip")]
+pub use gzip::*;
+
+#[cfg(feature = "zstd")]
+mod zstd;
+#[cfg(feature = "zstd")]
+pub use zstd::*;
+
+#[cfg(feature = "aead")]
+mod aead;
+#[cfg(feature = "aead")]
+pub use aead::*;
+
+/// A serialization format that payloads can be written in.
+pub trait Format {
+    /// Writes `value` to `writer`.
+    fn serialize<'a, T: Facet<'a>>(value: &'a T, writer: &mut dyn Write) -> io::Result<()>;
+
+    /// Reads a value from the whole of `bytes`.
+    fn deserialize<T>(bytes: &[u8]) -> Result<T, String>
+    where
+        T: for<'a> Facet<'a>;
+}
+
+/// JSON, with `facet-json`
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json;
+
+#[cfg(feature = "json")]
+impl Format for Json {
+    fn serialize<'a, T: Facet<'a>>(value: &'a T, writer: &mut dyn Write) -> io::Result<()> {
+        writer.write_all(facet_json::to_string(value).as_bytes())
+    }
+
+    fn deserialize<T>(bytes: &[u8]) -> Result<T, String>
+    where
+        T: for<'a> Facet<'a>,
+    {
+        facet_json::from_slice(bytes).map_err(|e| e.to_string())
+    }
+}
+
+/// MessagePack, with `facet-msgpack`
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MsgPack;
+
+#[cfg(feature = "msgpack")]
+impl Format for MsgPack {
+    fn serialize<'a, T: Facet<'a>>(value: &'a T, mut writer: &mut dyn Write) -> io::Result<()> {
+        facet_msgpack::to_writer(value, &mut writer)
+    }
+
+    fn deserialize<T>(bytes: &[u8]) -> Result<T, String>
+    where
+        T: for<'a> Facet<'a>,
+    {
+        facet_msgpack::from_slice(bytes).map_err(|e| e.to_string())
+    }
+}
+
+/// Serializes `value` as `F`, through `layer`, to `writer`.
+///
+/// The layers are finished once the value is written, so compressors write their trailer
+/// and ciphers encrypt, and `writer` is flushed.
+pub fn to_writer<'a, T, F, L, W>(value: &'a T, layer: &L, writer: W) -> Result<(), PayloadError>
+where
+    T: Facet<'a>,
+    F: Format,
+    L: Layer + ?Sized,
+    W: Write,
+{
+    let mut writer = layer.writer(Box::new(Sink(writer)))?;
+    F::serialize(value, &mut writer)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Deserializes a value of `F` from `reader`, undoing `layer`. The whole of `reader` is
+/// read.
+pub fn from_reader<T, F, L, R>(reader: R, layer: &L) -> Result<T, PayloadError>
+where
+    T: for<'a> Facet<'a>,
+    F: Format,
+    L: Layer + ?Sized,
+    R: Read,
+{
+    let mut reader = layer.reader(Box::new(reader))?;
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    F::deserialize(&bytes).map_err(PayloadError::Deserialize)
+}
+
+/// Serializes `value` as `F`, through `layer`, which is usually compression but can be any
+/// [`Layer`], or a stack of them in [`Layers`].
+///
+/// ```
+/// # #[cfg(feature = "gzip")] {
+/// use facet::Facet;
+/// use facet_payload::{Gzip, Json, from_slice_compressed, to_vec_compressed};
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Log {
+///     lines: Vec<String>,
+/// }
+///
+/// let log = Log {
+///     lines: vec!["all good".to_string(); 100],
+/// };
+/// let bytes = to_vec_compressed::<Log, Json>(&log, &Gzip::default()).unwrap();
+/// assert!(bytes.len() < 100);
+///
+/// let back: Log = from_slice_compressed::<Log, Json>(&bytes, &Gzip::default()).unwrap();
+/// assert_eq!(back, log);
+/// # }
+/// ```
+pub fn to_vec_compressed<'a, T, F>(
+    value: &'a T,
+    layer: &(impl Layer + ?Sized),
+) -> Result<Vec<u8>, PayloadError>
+where
+    T: Facet<'a>,
+    F: Format,
+{
+    let mut bytes = Vec::new();
+    to_writer::<T, F, _, _>(value, layer, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Deserializes a value of `F` from `bytes`, undoing `layer`, like
+/// [`to_vec_compressed`] in reverse.
+pub fn from_slice_compressed<T, F>(
+    bytes: &[u8],
+    layer: &(impl Layer + ?Sized),
+) -> Result<T, PayloadError>
+where
+    T: for<'a> Facet<'a>,
+    F: Format,
+{
+    from_reader::<T, F, _, _>(bytes, layer)
+}
@@ -0,0 +1,57 @@
+use std::io::{self, Read, Write};
+
+use ::zstd::stream::read::Decoder;
+use ::zstd::stream::write::Encoder;
+
+use crate::{FinishWrite, Layer};
+
+/// Compresses payloads with Zstandard.
+#[derive(Debug, Clone, Copy)]
+pub struct Zstd {
+    level: i32,
+}
+
+impl Zstd {
+    /// Compresses at `level`, from 1 (fastest) to 22 (smallest). 0 is the default level.
+    pub fn new(level: i32) -> Self {
+        Zstd { level }
+    }
+}
+
+impl Default for Zstd {
+    /// Compresses at zstd's default level, 3.
+    fn default() -> Self {
+        Zstd::new(::zstd::DEFAULT_COMPRESSION_LEVEL)
+    }
+}
+
+impl Layer for Zstd {
+    fn writer<'w>(
+        &self,
+        inner: Box<dyn FinishWrite + 'w>,
+    ) -> io::Result<Box<dyn FinishWrite + 'w>> {
+        Ok(Box::new(ZstdWriter(Encoder::new(inner, self.level)?)))
+    }
+
+    fn reader<'r>(&self, inner: Box<dyn Read + 'r>) -> io::Result<Box<dyn Read + 'r>> {
+        Ok(Box::new(Decoder::new(inner)?))
+    }
+}
+
+struct ZstdWriter<'w>(Encoder<'static, Box<dyn FinishWrite + 'w>>);
+
+impl Write for ZstdWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl FinishWrite for ZstdWriter<'_> {
+    fn finish(self: Box<Self>) -> io::Result<()> {
+        self.0.finish()?.finish()
+    }
+}
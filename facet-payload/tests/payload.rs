@@ -0,0 +1,175 @@
+#![cfg(feature = "json")]
+
+use facet::Facet;
+use facet_payload::{Json, Layers, PayloadError, from_slice_compressed, to_vec_compressed};
+
+#[derive(Debug, Clone, PartialEq, Facet)]
+struct Report {
+    title: String,
+    readings: Vec<f64>,
+    tags: Vec<String>,
+}
+
+fn report() -> Report {
+    Report {
+        title: "north station".to_string(),
+        readings: (0..200).map(|i| f64::from(i) / 4.0).collect(),
+        tags: vec!["weather".to_string(); 20],
+    }
+}
+
+#[test]
+fn no_layers() {
+    facet_testhelpers::setup();
+
+    let bytes = to_vec_compressed::<Report, Json>(&report(), &Layers::new()).unwrap();
+    assert_eq!(bytes, facet_json::to_string(&report()).into_bytes());
+
+    let back: Report = from_slice_compressed::<Report, Json>(&bytes, &Layers::new()).unwrap();
+    assert_eq!(back, report());
+}
+
+#[test]
+fn invalid_payload() {
+    facet_testhelpers::setup();
+
+    let json = br#"{"title": 3}"#;
+    let err = from_slice_compressed::<Report, Json>(json, &Layers::new()).unwrap_err();
+    assert!(matches!(err, PayloadError::Deserialize(_)));
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip() {
+    use facet_payload::Gzip;
+
+    facet_testhelpers::setup();
+
+    let plain = facet_json::to_string(&report()).len();
+    let bytes = to_vec_compressed::<Report, Json>(&report(), &Gzip::new(9)).unwrap();
+    assert!(bytes.len() < plain / 2);
+    assert_eq!(&bytes[..2], [0x1f, 0x8b]);
+
+    let back: Report = from_slice_compressed::<Report, Json>(&bytes, &Gzip::default()).unwrap();
+    assert_eq!(back, report());
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd() {
+    use facet_payload::Zstd;
+
+    facet_testhelpers::setup();
+
+    let plain = facet_json::to_string(&report()).len();
+    let bytes = to_vec_compressed::<Report, Json>(&report(), &Zstd::default()).unwrap();
+    assert!(bytes.len() < plain / 2);
+    assert_eq!(&bytes[..4], [0x28, 0xb5, 0x2f, 0xfd]);
+
+    let back: Report = from_slice_compressed::<Report, Json>(&bytes, &Zstd::default()).unwrap();
+    assert_eq!(back, report());
+}
+
+#[cfg(feature = "aead")]
+#[test]
+fn aead() {
+    use chacha20poly1305::aead::{Aead as _, KeyInit, Payload};
+    use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+    use facet_payload::Aead;
+
+    facet_testhelpers::setup();
+
+    let aead = Aead::new([7; 32]).with_associated_data("reports/north");
+    let bytes = to_vec_compressed::<Report, Json>(&report(), &aead).unwrap();
+    let back: Report = from_slice_compressed::<Report, Json>(&bytes, &aead).unwrap();
+    assert_eq!(back, report());
+
+    // A new nonce every time
+    let again = to_vec_compressed::<Report, Json>(&report(), &aead).unwrap();
+    assert_ne!(bytes, again);
+
+    let invalid_data =
+        |bytes: &[u8], aead: &Aead| match from_slice_compressed::<Report, Json>(bytes, aead) {
+            Err(PayloadError::Io(e)) => e.kind() == std::io::ErrorKind::InvalidData,
+            _ => false,
+        };
+
+    assert!(invalid_data(&bytes, &Aead::new([8; 32])));
+    assert!(invalid_data(
+        &bytes,
+        &Aead::new([7; 32]).with_associated_data("reports/south")
+    ));
+    let mut tampered = bytes.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert!(invalid_data(&tampered, &aead));
+    assert!(invalid_data(&bytes[..8], &aead));
+    assert!(invalid_data(&[], &aead));
+
+    // The first byte names the algorithm
+    assert_eq!(bytes[0], 1);
+    let mut unknown = bytes.clone();
+    unknown[0] = 2;
+    assert!(invalid_data(&unknown, &aead));
+
+    // ...and is authenticated ahead of the associated data
+    let cipher = XChaCha20Poly1305::new(&[7; 32].into());
+    let (nonce, ciphertext) = bytes[1..].split_at(24);
+    let decrypt = |aad: &[u8]| {
+        cipher.decrypt(
+            XNonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+    };
+    assert!(decrypt(b"reports/north").is_err());
+    assert_eq!(
+        decrypt(b"\x01reports/north").unwrap(),
+        facet_json::to_string(&report()).into_bytes()
+    );
+}
+
+#[cfg(all(feature = "gzip", feature = "aead"))]
+#[test]
+fn compressed_then_encrypted() {
+    use facet_payload::{Aead, Gzip};
+
+    facet_testhelpers::setup();
+
+    let layers = Layers::new().then(Gzip::default()).then(Aead::new([7; 32]));
+    let bytes = to_vec_compressed::<Report, Json>(&report(), &layers).unwrap();
+    let back: Report = from_slice_compressed::<Report, Json>(&bytes, &layers).unwrap();
+    assert_eq!(back, report());
+
+    // Compressed before it was encrypted, so it's as small as the compressed payload plus
+    // the algorithm byte, nonce and tag
+    let compressed = to_vec_compressed::<Report, Json>(&report(), &Gzip::default()).unwrap();
+    assert_eq!(bytes.len(), compressed.len() + 1 + 24 + 16);
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn msgpack() {
+    use facet_payload::MsgPack;
+
+    facet_testhelpers::setup();
+
+    // facet-msgpack doesn't read floats back yet
+    #[derive(Debug, PartialEq, Facet)]
+    struct Counts {
+        title: String,
+        counts: Vec<u32>,
+    }
+
+    let counts = Counts {
+        title: "north".to_string(),
+        counts: vec![3, 300, 70000],
+    };
+    let bytes = to_vec_compressed::<Counts, MsgPack>(&counts, &Layers::new()).unwrap();
+    assert_eq!(bytes, facet_msgpack::to_vec(&counts));
+
+    let back: Counts = from_slice_compressed::<Counts, MsgPack>(&bytes, &Layers::new()).unwrap();
+    assert_eq!(back, counts);
+}
@@ -0,0 +1,388 @@
+//! The binary property list format, `bplist00`.
+
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{
+    PlistDeError,
+    value::{CORE_DATA_EPOCH, Value, civil_from_days, days_from_civil},
+};
+
+const MAGIC: &[u8] = b"bplist00";
+const TRAILER_LEN: usize = 32;
+
+/// Whether the input starts with the binary plist magic number.
+pub(crate) fn is_binary(input: &[u8]) -> bool {
+    input.starts_with(MAGIC)
+}
+
+fn error(message: &'static str) -> PlistDeError<'static> {
+    PlistDeError::Binary(message)
+}
+
+/// Parses a binary property list.
+pub(crate) fn parse(input: &[u8]) -> Result<Value, PlistDeError<'static>> {
+    if !is_binary(input) || input.len() < MAGIC.len() + TRAILER_LEN {
+        return Err(error("missing bplist00 header or trailer"));
+    }
+    let trailer = &input[input.len() - TRAILER_LEN..];
+    let offset_size = trailer[6] as usize;
+    let ref_size = trailer[7] as usize;
+    let num_objects = be_uint(&trailer[8..16]);
+    let top_object = be_uint(&trailer[16..24]);
+    let table_offset = be_uint(&trailer[24..32]);
+
+    if !(1..=8).contains(&offset_size) || !(1..=8).contains(&ref_size) {
+        return Err(error("invalid offset or reference size"));
+    }
+    let table_len = num_objects
+        .checked_mul(offset_size as u64)
+        .ok_or_else(|| error("offset table is too large"))?;
+    let table_end = table_offset
+        .checked_add(table_len)
+        .ok_or_else(|| error("offset table is too large"))?;
+    if table_end > (input.len() - TRAILER_LEN) as u64 || top_object >= num_objects {
+        return Err(error("offset table out of bounds"));
+    }
+
+    let table = &input[table_offset as usize..table_end as usize];
+    let reader = Reader {
+        input,
+        offsets: table.chunks(offset_size).map(be_uint).collect(),
+        ref_size,
+        // Bounds object data so it can't run into the offset table
+        data_end: table_offset as usize,
+    };
+    reader.object(top_object, &mut Vec::new())
+}
+
+fn be_uint(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |acc, &b| (acc << 8) | b as u64)
+}
+
+struct Reader<'input> {
+    input: &'input [u8],
+    offsets: Vec<u64>,
+    ref_size: usize,
+    data_end: usize,
+}
+
+impl Reader<'_> {
+    fn bytes(&self, pos: usize, len: usize) -> Result<&[u8], PlistDeError<'static>> {
+        pos.checked_add(len)
+            .filter(|end| *end <= self.data_end)
+            .map(|end| &self.input[pos..end])
+            .ok_or_else(|| error("object out of bounds"))
+    }
+
+    /// Reads the element count that follows a marker, returning it and the position after it.
+    fn count(&self, info: u8, pos: usize) -> Result<(usize, usize), PlistDeError<'static>> {
+        if info != 0x0f {
+            return Ok((info as usize, pos));
+        }
+        let marker = self.bytes(pos, 1)?[0];
+        if marker >> 4 != 0x1 {
+            return Err(error("invalid length marker"));
+        }
+        let len = 1 << (marker & 0x0f);
+        if len > 8 {
+            return Err(error("invalid length marker"));
+        }
+        let count = be_uint(self.bytes(pos + 1, len)?);
+        let count = usize::try_from(count).map_err(|_| error("length is too large"))?;
+        Ok((count, pos + 1 + len))
+    }
+
+    fn refs(&self, pos: usize, count: usize) -> Result<Vec<u64>, PlistDeError<'static>> {
+        let len = count
+            .checked_mul(self.ref_size)
+            .ok_or_else(|| error("length is too large"))?;
+        Ok(self
+            .bytes(pos, len)?
+            .chunks(self.ref_size)
+            .map(be_uint)
+            .collect())
+    }
+
+    /// Reads an object. `stack` holds the containers being read, to reject cycles.
+    fn object(&self, index: u64, stack: &mut Vec<u64>) -> Result<Value, PlistDeError<'static>> {
+        let offset = *usize::try_from(index)
+            .ok()
+            .and_then(|i| self.offsets.get(i))
+            .ok_or_else(|| error("object reference out of bounds"))?;
+        let pos = usize::try_from(offset).map_err(|_| error("object out of bounds"))?;
+        let marker = self.bytes(pos, 1)?[0];
+        let info = marker & 0x0f;
+        let pos = pos + 1;
+
+        Ok(match marker >> 4 {
+            0x0 => match marker {
+                0x08 => Value::Boolean(false),
+                0x09 => Value::Boolean(true),
+                _ => return Err(error("unsupported null or fill object")),
+            },
+            0x1 => {
+                let len = 1usize << info;
+                let bytes = self.bytes(pos, len)?;
+                Value::Integer(match len {
+                    1 | 2 | 4 => be_uint(bytes) as i128,
+                    8 => be_uint(bytes) as i64 as i128,
+                    16 => {
+                        let high = be_uint(&bytes[..8]) as i64 as i128;
+                        (high << 64) | be_uint(&bytes[8..]) as i128
+                    }
+                    _ => return Err(error("invalid integer size")),
+                })
+            }
+            0x2 => Value::Real(match info {
+                2 => f32::from_be_bytes(self.bytes(pos, 4)?.try_into().unwrap()) as f64,
+                3 => f64::from_be_bytes(self.bytes(pos, 8)?.try_into().unwrap()),
+                _ => return Err(error("invalid real size")),
+            }),
+            0x3 if info == 3 => {
+                let secs = f64::from_be_bytes(self.bytes(pos, 8)?.try_into().unwrap());
+                Value::Date(format_date(secs))
+            }
+            0x4 => {
+                let (len, pos) = self.count(info, pos)?;
+                Value::Data(self.bytes(pos, len)?.to_vec())
+            }
+            0x5 => {
+                let (len, pos) = self.count(info, pos)?;
+                let bytes = self.bytes(pos, len)?;
+                if !bytes.is_ascii() {
+                    return Err(error("non-ASCII byte in ASCII string"));
+                }
+                Value::String(bytes.iter().map(|&b| b as char).collect())
+            }
+            0x6 => {
+                let (len, pos) = self.count(info, pos)?;
+                let len = len
+                    .checked_mul(2)
+                    .ok_or_else(|| error("length is too large"))?;
+                let units = self
+                    .bytes(pos, len)?
+                    .chunks(2)
+                    .map(|unit| u16::from_be_bytes([unit[0], unit[1]]));
+                Value::String(
+                    char::decode_utf16(units)
+                        .collect::<Result<String, _>>()
+                        .map_err(|_| error("invalid UTF-16 string"))?,
+                )
+            }
+            0x8 => Value::Uid(be_uint(self.bytes(pos, info as usize + 1)?)),
+            // Arrays and sets
+            0xa | 0xc => {
+                let (count, pos) = self.count(info, pos)?;
+                let refs = self.refs(pos, count)?;
+                self.enter(index, stack)?;
+                let items = refs
+                    .into_iter()
+                    .map(|r| self.object(r, stack))
+                    .collect::<Result<_, _>>()?;
+                stack.pop();
+                Value::Array(items)
+            }
+            0xd => {
+                let (count, pos) = self.count(info, pos)?;
+                let refs = self.refs(
+                    pos,
+                    count
+                        .checked_mul(2)
+                        .ok_or_else(|| error("length is too large"))?,
+                )?;
+                let (keys, values) = refs.split_at(count);
+                self.enter(index, stack)?;
+                let mut entries = Vec::with_capacity(count);
+                for (&key, &value) in keys.iter().zip(values) {
+                    let Value::String(key) = self.object(key, stack)? else {
+                        return Err(error("dictionary key is not a string"));
+                    };
+                    entries.push((key, self.object(value, stack)?));
+                }
+                stack.pop();
+                Value::Dict(entries)
+            }
+            _ => return Err(error("unknown object type")),
+        })
+    }
+
+    fn enter(&self, index: u64, stack: &mut Vec<u64>) -> Result<(), PlistDeError<'static>> {
+        if stack.contains(&index) {
+            return Err(error("object graph has a cycle"));
+        }
+        stack.push(index);
+        Ok(())
+    }
+}
+
+/// Formats seconds since 2001-01-01 as an ISO 8601 date, as XML plists write them.
+fn format_date(secs: f64) -> String {
+    let secs = secs.floor() as i64 + CORE_DATA_EPOCH;
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let time = secs.rem_euclid(86_400);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// Parses an ISO 8601 date in UTC into seconds since 2001-01-01.
+fn parse_date(date: &str) -> Option<f64> {
+    let date = date.strip_suffix('Z')?;
+    let (day, time) = date.split_once('T')?;
+    let mut day = day.splitn(3, '-').map(str::parse::<i64>);
+    let mut time = time.splitn(3, ':').map(str::parse::<i64>);
+    let (year, month, day) = (day.next()?.ok()?, day.next()?.ok()?, day.next()?.ok()?);
+    let (hour, minute, second) = (time.next()?.ok()?, time.next()?.ok()?, time.next()?.ok()?);
+    let days = days_from_civil(year, u32::try_from(month).ok()?, u32::try_from(day).ok()?);
+    Some((days * 86_400 + hour * 3600 + minute * 60 + second - CORE_DATA_EPOCH) as f64)
+}
+
+/// An object to write: a value, or a dictionary key.
+enum Object<'a> {
+    Value(&'a Value),
+    Key(&'a str),
+}
+
+/// Writes a value as a binary property list.
+pub(crate) fn write(value: &Value) -> Vec<u8> {
+    // Number the objects depth-first, recording the children of each container.
+    let mut objects: Vec<(Object<'_>, Vec<usize>)> = Vec::new();
+    flatten(value, &mut objects);
+    let ref_size = int_size(objects.len() as u64 - 1);
+
+    let mut out = MAGIC.to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (object, children) in &objects {
+        offsets.push(out.len() as u64);
+        match object {
+            Object::Key(key) => write_string(&mut out, key),
+            Object::Value(value) => write_object(&mut out, value, children, ref_size),
+        }
+    }
+
+    let table_offset = out.len() as u64;
+    let offset_size = int_size(table_offset);
+    for offset in offsets {
+        out.extend_from_slice(&offset.to_be_bytes()[8 - offset_size..]);
+    }
+
+    out.extend_from_slice(&[0; 6]);
+    out.push(offset_size as u8);
+    out.push(ref_size as u8);
+    out.extend_from_slice(&(objects.len() as u64).to_be_bytes());
+    out.extend_from_slice(&0u64.to_be_bytes());
+    out.extend_from_slice(&table_offset.to_be_bytes());
+    out
+}
+
+fn flatten<'a>(value: &'a Value, objects: &mut Vec<(Object<'a>, Vec<usize>)>) -> usize {
+    let index = objects.len();
+    objects.push((Object::Value(value), Vec::new()));
+    let children = match value {
+        Value::Array(items) => items.iter().map(|item| flatten(item, objects)).collect(),
+        Value::Dict(entries) => {
+            let mut keys = Vec::with_capacity(entries.len());
+            for (key, _) in entries {
+                keys.push(objects.len());
+                objects.push((Object::Key(key), Vec::new()));
+            }
+            let values = entries.iter().map(|(_, value)| flatten(value, objects));
+            keys.into_iter().chain(values.collect::<Vec<_>>()).collect()
+        }
+        _ => return index,
+    };
+    objects[index].1 = children;
+    index
+}
+
+/// The number of bytes (1, 2, 4 or 8) needed to store `n`.
+fn int_size(n: u64) -> usize {
+    match n {
+        0..=0xff => 1,
+        0x100..=0xffff => 2,
+        0x1_0000..=0xffff_ffff => 4,
+        _ => 8,
+    }
+}
+
+fn write_marker(out: &mut Vec<u8>, kind: u8, len: usize) {
+    if len < 0x0f {
+        out.push(kind << 4 | len as u8);
+    } else {
+        out.push(kind << 4 | 0x0f);
+        write_integer(out, len as i128);
+    }
+}
+
+fn write_integer(out: &mut Vec<u8>, value: i128) {
+    if let Ok(value) = u64::try_from(value) {
+        if value <= 0xffff_ffff {
+            let size = int_size(value);
+            out.push(0x10 | size.trailing_zeros() as u8);
+            out.extend_from_slice(&value.to_be_bytes()[8 - size..]);
+            return;
+        }
+    }
+    if let Ok(value) = i64::try_from(value) {
+        out.push(0x13);
+        out.extend_from_slice(&value.to_be_bytes());
+    } else {
+        out.push(0x14);
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+fn write_string(out: &mut Vec<u8>, s: &str) {
+    if s.is_ascii() {
+        write_marker(out, 0x5, s.len());
+        out.extend_from_slice(s.as_bytes());
+    } else {
+        let units: Vec<u16> = s.encode_utf16().collect();
+        write_marker(out, 0x6, units.len());
+        for unit in units {
+            out.extend_from_slice(&unit.to_be_bytes());
+        }
+    }
+}
+
+fn write_object(out: &mut Vec<u8>, value: &Value, children: &[usize], ref_size: usize) {
+    let write_refs = |out: &mut Vec<u8>, kind: u8, count: usize| {
+        write_marker(out, kind, count);
+        for &child in children {
+            out.extend_from_slice(&(child as u64).to_be_bytes()[8 - ref_size..]);
+        }
+    };
+
+    match value {
+        Value::Boolean(false) => out.push(0x08),
+        Value::Boolean(true) => out.push(0x09),
+        Value::Integer(i) => write_integer(out, *i),
+        Value::Real(r) => {
+            out.push(0x23);
+            out.extend_from_slice(&r.to_be_bytes());
+        }
+        Value::Date(date) => match parse_date(date) {
+            Some(secs) => {
+                out.push(0x33);
+                out.extend_from_slice(&secs.to_be_bytes());
+            }
+            // Not a date we understand; keep the text rather than lose it
+            None => write_string(out, date),
+        },
+        Value::Data(data) => {
+            write_marker(out, 0x4, data.len());
+            out.extend_from_slice(data);
+        }
+        Value::String(s) => write_string(out, s),
+        Value::Uid(uid) => {
+            let size = int_size(*uid);
+            out.push(0x80 | (size as u8 - 1));
+            out.extend_from_slice(&uid.to_be_bytes()[8 - size..]);
+        }
+        Value::Array(items) => write_refs(out, 0xa, items.len()),
+        Value::Dict(entries) => write_refs(out, 0xd, entries.len()),
+    }
+}
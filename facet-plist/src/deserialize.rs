@@ -0,0 +1,348 @@
+//! Deserialize property lists into Rust values.
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use facet_core::{Def, Facet, Field, FieldFlags, StructKind, Type, UserType};
+use facet_reflect::{Partial, ScalarType};
+use log::trace;
+
+use crate::{PlistDeError, binary, value::Value, xml};
+
+/// Deserializes an XML property list into a value of type `T`.
+///
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Info {
+///     #[facet(rename = "CFBundleIdentifier")]
+///     bundle_id: String,
+///     #[facet(rename = "LSRequiresIPhoneOS")]
+///     requires_ios: Option<bool>,
+/// }
+///
+/// let plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <plist version="1.0">
+/// <dict>
+///     <key>CFBundleIdentifier</key>
+///     <string>rs.facet.demo</string>
+/// </dict>
+/// </plist>"#;
+///
+/// let info: Info = facet_plist::from_str(plist).unwrap();
+/// assert_eq!(info.bundle_id, "rs.facet.demo");
+/// assert_eq!(info.requires_ios, None);
+/// ```
+pub fn from_str<'input, 'facet: 'shape, 'shape, T: Facet<'facet>>(
+    plist: &'input str,
+) -> Result<T, PlistDeError<'shape>> {
+    trace!("Parsing XML plist");
+    from_value(xml::parse(plist)?)
+}
+
+/// Deserializes a property list into a value of type `T`.
+///
+/// Binary (`bplist00`) and XML property lists are both accepted, told apart by
+/// their header.
+pub fn from_slice<'input, 'facet: 'shape, 'shape, T: Facet<'facet>>(
+    plist: &'input [u8],
+) -> Result<T, PlistDeError<'shape>> {
+    if binary::is_binary(plist) {
+        trace!("Parsing binary plist");
+        return from_value(binary::parse(plist)?);
+    }
+    let plist = core::str::from_utf8(plist).map_err(|e| PlistDeError::Xml {
+        message: "invalid UTF-8",
+        offset: e.valid_up_to(),
+    })?;
+    from_str(plist)
+}
+
+fn from_value<'facet: 'shape, 'shape, T: Facet<'facet>>(
+    value: Value,
+) -> Result<T, PlistDeError<'shape>> {
+    let mut partial = Partial::alloc::<T>()?;
+    deserialize_value(partial.inner_mut(), value)?;
+    let value = partial.build()?;
+    Ok(*value)
+}
+
+fn mismatch<'shape>(value: &Value, wip: &Partial<'_, 'shape>) -> PlistDeError<'shape> {
+    PlistDeError::TypeMismatch {
+        found: value.kind(),
+        shape: wip.shape(),
+    }
+}
+
+fn deserialize_value<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: Value,
+) -> Result<(), PlistDeError<'shape>> {
+    let shape = wip.shape();
+    trace!("Deserializing plist {} into {}", value.kind(), shape);
+
+    if shape.is_type::<Vec<u8>>() {
+        let Value::Data(data) = value else {
+            return Err(mismatch(&value, wip));
+        };
+        wip.set(data)?;
+        return Ok(());
+    }
+
+    match shape.def {
+        Def::Option(_) => {
+            wip.begin_some()?;
+            deserialize_value(wip, value)?;
+            wip.end()?;
+            return Ok(());
+        }
+        Def::List(_) => {
+            let Value::Array(items) = value else {
+                return Err(mismatch(&value, wip));
+            };
+            wip.begin_list()?;
+            for item in items {
+                wip.begin_list_item()?;
+                deserialize_value(wip, item)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::Array(ad) => {
+            let Value::Array(items) = value else {
+                return Err(mismatch(&value, wip));
+            };
+            if items.len() != ad.n {
+                return Err(mismatch(&Value::Array(items), wip));
+            }
+            for (index, item) in items.into_iter().enumerate() {
+                wip.begin_nth_element(index)?;
+                deserialize_value(wip, item)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::Map(_) => {
+            let Value::Dict(entries) = value else {
+                return Err(mismatch(&value, wip));
+            };
+            wip.begin_map()?;
+            for (key, value) in entries {
+                wip.begin_key()?;
+                deserialize_value(wip, Value::String(key))?;
+                wip.end()?;
+                wip.begin_value()?;
+                deserialize_value(wip, value)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    if ScalarType::try_from_shape(shape).is_some() {
+        return deserialize_scalar(wip, value);
+    }
+
+    if shape.inner.is_some() {
+        wip.begin_inner()?;
+        deserialize_value(wip, value)?;
+        wip.end()?;
+        return Ok(());
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => match (sd.kind, value) {
+            (StructKind::Tuple | StructKind::TupleStruct, Value::Array(items)) => {
+                deserialize_tuple(wip, sd.fields, items)
+            }
+            (StructKind::Struct | StructKind::Unit, Value::Dict(entries)) => {
+                deserialize_fields(wip, sd.fields, entries)
+            }
+            (_, value) => Err(mismatch(&value, wip)),
+        },
+        Type::User(UserType::Enum(_)) => deserialize_enum(wip, value),
+        _ if shape.is_from_str() => deserialize_scalar(wip, value),
+        _ => Err(PlistDeError::UnsupportedShape(shape)),
+    }
+}
+
+fn deserialize_tuple<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    items: Vec<Value>,
+) -> Result<(), PlistDeError<'shape>> {
+    if items.len() != fields.len() {
+        return Err(mismatch(&Value::Array(items), wip));
+    }
+    for (index, item) in items.into_iter().enumerate() {
+        wip.begin_nth_field(index)?;
+        deserialize_value(wip, item)?;
+        wip.end()?;
+    }
+    Ok(())
+}
+
+/// Fills the fields of a struct or of the selected enum variant from a dictionary.
+fn deserialize_fields<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    entries: Vec<(String, Value)>,
+) -> Result<(), PlistDeError<'shape>> {
+    let shape = wip.shape();
+    for (key, value) in entries {
+        let Some(index) = fields.iter().position(|f| f.name == key) else {
+            if shape.has_deny_unknown_fields_attr() {
+                return Err(PlistDeError::UnknownField { name: key, shape });
+            }
+            trace!("Skipping unknown key '{key}' of {shape}");
+            continue;
+        };
+        wip.begin_nth_field(index)?;
+        deserialize_value(wip, value)?;
+        wip.end()?;
+    }
+
+    if let Type::User(UserType::Struct(_)) = shape.ty {
+        wip.fill_unset_fields_from_default()?;
+    }
+    for (index, field) in fields.iter().enumerate() {
+        if !wip.is_field_set(index)? {
+            default_field(wip, index, field)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fills a field that has no key in the dictionary.
+///
+/// Property lists have no null, so missing keys are how `None` is spelled;
+/// anything else needs a `#[facet(default)]`.
+fn default_field<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    index: usize,
+    field: &Field<'shape>,
+) -> Result<(), PlistDeError<'shape>> {
+    let has_default = field.flags.contains(FieldFlags::DEFAULT);
+    if !has_default && !matches!(field.shape().def, Def::Option(_)) {
+        return Err(PlistDeError::MissingField(field.name));
+    }
+
+    wip.begin_nth_field(index)?;
+    match field.vtable.default_fn {
+        Some(default_fn) if has_default => wip.set_field_default(default_fn)?,
+        _ => wip.set_default()?,
+    };
+    wip.end()?;
+    Ok(())
+}
+
+/// Enums are externally tagged: unit variants are strings, and other variants are a
+/// dictionary with the variant name as its only key.
+fn deserialize_enum<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: Value,
+) -> Result<(), PlistDeError<'shape>> {
+    let shape = wip.shape();
+    let (name, content) = match value {
+        Value::String(name) => (name, None),
+        Value::Dict(mut entries) if entries.len() == 1 => {
+            let (name, content) = entries.remove(0);
+            (name, Some(content))
+        }
+        value => return Err(mismatch(&value, wip)),
+    };
+
+    let Some((_, variant)) = wip.find_variant(&name) else {
+        return Err(PlistDeError::UnknownVariant { name, shape });
+    };
+    let fields = variant.data.fields;
+    let kind = variant.data.kind;
+    wip.select_variant_named(&name)?;
+
+    match (kind, content) {
+        (StructKind::Unit, None) => Ok(()),
+        (StructKind::Struct, Some(Value::Dict(entries))) => {
+            deserialize_fields(wip, fields, entries)
+        }
+        (StructKind::Tuple | StructKind::TupleStruct, Some(content)) if fields.len() == 1 => {
+            wip.begin_nth_field(0)?;
+            deserialize_value(wip, content)?;
+            wip.end()?;
+            Ok(())
+        }
+        (StructKind::Tuple | StructKind::TupleStruct, Some(Value::Array(items))) => {
+            deserialize_tuple(wip, fields, items)
+        }
+        (_, Some(content)) => Err(mismatch(&content, wip)),
+        (_, None) => Err(mismatch(&Value::String(name), wip)),
+    }
+}
+
+fn deserialize_scalar<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: Value,
+) -> Result<(), PlistDeError<'shape>> {
+    let shape = wip.shape();
+    let out_of_range = |_| PlistDeError::NumberOutOfRange(shape);
+
+    match (ScalarType::try_from_shape(shape), value) {
+        (Some(ScalarType::Bool), Value::Boolean(b)) => {
+            wip.set(b)?;
+        }
+        (Some(ScalarType::F32), Value::Real(r)) => {
+            wip.set(r as f32)?;
+        }
+        (Some(ScalarType::F64), Value::Real(r)) => {
+            wip.set(r)?;
+        }
+        (Some(ScalarType::F32), Value::Integer(i)) => {
+            wip.set(i as f32)?;
+        }
+        (Some(ScalarType::F64), Value::Integer(i)) => {
+            wip.set(i as f64)?;
+        }
+        (Some(ScalarType::U8), Value::Integer(i)) => {
+            wip.set(u8::try_from(i).map_err(out_of_range)?)?;
+        }
+        (Some(ScalarType::U16), Value::Integer(i)) => {
+            wip.set(u16::try_from(i).map_err(out_of_range)?)?;
+        }
+        (Some(ScalarType::U32), Value::Integer(i)) => {
+            wip.set(u32::try_from(i).map_err(out_of_range)?)?;
+        }
+        (Some(ScalarType::U64), Value::Integer(i)) => {
+            wip.set(u64::try_from(i).map_err(out_of_range)?)?;
+        }
+        (Some(ScalarType::USize), Value::Integer(i)) => {
+            wip.set(usize::try_from(i).map_err(out_of_range)?)?;
+        }
+        (Some(ScalarType::I8), Value::Integer(i)) => {
+            wip.set(i8::try_from(i).map_err(out_of_range)?)?;
+        }
+        (Some(ScalarType::I16), Value::Integer(i)) => {
+            wip.set(i16::try_from(i).map_err(out_of_range)?)?;
+        }
+        (Some(ScalarType::I32), Value::Integer(i)) => {
+            wip.set(i32::try_from(i).map_err(out_of_range)?)?;
+        }
+        (Some(ScalarType::I64), Value::Integer(i)) => {
+            wip.set(i64::try_from(i).map_err(out_of_range)?)?;
+        }
+        (Some(ScalarType::ISize), Value::Integer(i)) => {
+            wip.set(isize::try_from(i).map_err(out_of_range)?)?;
+        }
+        (Some(ScalarType::String), Value::String(s) | Value::Date(s)) => {
+            wip.set(s)?;
+        }
+        (Some(ScalarType::CowStr), Value::String(s) | Value::Date(s)) => {
+            wip.set(Cow::<'static, str>::Owned(s))?;
+        }
+        // Dates also land here, for types like timestamps that parse ISO 8601
+        (_, Value::String(s) | Value::Date(s)) if shape.is_from_str() => {
+            wip.parse_from_str(&s)
+                .map_err(|_| PlistDeError::InvalidValue(shape))?;
+        }
+        (_, value) => return Err(mismatch(&value, wip)),
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! Errors from reading and writing property lists.
+
+use alloc::string::String;
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+
+/// Errors when deserializing a property list.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PlistDeError<'shape> {
+    /// The XML is malformed or is not a property list.
+    Xml {
+        /// What went wrong.
+        message: &'static str,
+        /// The byte offset of the error.
+        offset: usize,
+    },
+    /// The binary property list is malformed.
+    Binary(&'static str),
+    /// A value has a different type than the Rust type expects.
+    TypeMismatch {
+        /// The property list type found, such as `"dict"` or `"integer"`.
+        found: &'static str,
+        /// The Rust type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A dictionary has a key that doesn't match any field, and the struct denies unknown fields.
+    UnknownField {
+        /// The key.
+        name: String,
+        /// The struct type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A string or dictionary key doesn't match any variant of the enum.
+    UnknownVariant {
+        /// The variant name found.
+        name: String,
+        /// The enum type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A field is missing and its type has no default value.
+    MissingField(&'shape str),
+    /// A number doesn't fit the Rust type it's deserialized into.
+    NumberOutOfRange(&'shape Shape<'shape>),
+    /// A string couldn't be parsed into the Rust type.
+    InvalidValue(&'shape Shape<'shape>),
+    /// The Rust type can't be represented in a property list.
+    UnsupportedShape(&'shape Shape<'shape>),
+    /// Any error from facet.
+    Reflect(ReflectError<'shape>),
+}
+
+impl<'shape> From<ReflectError<'shape>> for PlistDeError<'shape> {
+    fn from(err: ReflectError<'shape>) -> Self {
+        PlistDeError::Reflect(err)
+    }
+}
+
+impl core::fmt::Display for PlistDeError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PlistDeError::Xml { message, offset } => {
+                write!(f, "Invalid XML property list at byte {offset}: {message}")
+            }
+            PlistDeError::Binary(message) => write!(f, "Invalid binary property list: {message}"),
+            PlistDeError::TypeMismatch { found, shape } => {
+                write!(f, "Expected {shape}, found a plist {found}")
+            }
+            PlistDeError::UnknownField { name, shape } => {
+                write!(f, "Unknown key '{name}' for {shape}")
+            }
+            PlistDeError::UnknownVariant { name, shape } => {
+                write!(f, "'{name}' is not a variant of {shape}")
+            }
+            PlistDeError::MissingField(name) => write!(f, "Missing field '{name}'"),
+            PlistDeError::NumberOutOfRange(shape) => {
+                write!(f, "Number out of range for {shape}")
+            }
+            PlistDeError::InvalidValue(shape) => write!(f, "Can't parse string as {shape}"),
+            PlistDeError::UnsupportedShape(shape) => {
+                write!(f, "Type {shape} can't be represented in a property list")
+            }
+            PlistDeError::Reflect(err) => write!(f, "Reflection error: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for PlistDeError<'_> {}
+
+/// Errors when serializing a property list.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PlistSerError {
+    /// A type can't be represented in a property list.
+    UnsupportedType(String),
+    /// A map's key type is neither a string nor has a `Display` implementation, so it can't
+    /// be a dictionary key.
+    UnsupportedKeyType(String),
+    /// A `None` outside of a struct field; property lists have no null value.
+    NoneOutsideStruct,
+}
+
+impl core::fmt::Display for PlistSerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PlistSerError::UnsupportedType(ty) => {
+                write!(f, "Type {ty} can't be represented in a property list")
+            }
+            PlistSerError::UnsupportedKeyType(ty) => {
+                write!(f, "Type {ty} can't be a key of a property list dictionary")
+            }
+            PlistSerError::NoneOutsideStruct => write!(
+                f,
+                "Property lists have no null value; None is only allowed in struct fields"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for PlistSerError {}
@@ -0,0 +1,155 @@
+//! Serialize Rust values as property lists.
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use facet_core::{Def, Facet, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+
+use crate::{PlistSerError, binary, value::Value, xml};
+
+/// Serializes a value as an XML property list.
+///
+/// `None` fields are left out, since property lists have no null value.
+pub fn to_string<'facet, T: Facet<'facet>>(value: &T) -> Result<String, PlistSerError> {
+    Ok(xml::write(&to_value(Peek::new(value))?))
+}
+
+/// Serializes a value as a binary (`bplist00`) property list.
+pub fn to_binary<'facet, T: Facet<'facet>>(value: &T) -> Result<Vec<u8>, PlistSerError> {
+    Ok(binary::write(&to_value(Peek::new(value))?))
+}
+
+fn unsupported(peek: Peek<'_, '_, '_>) -> PlistSerError {
+    PlistSerError::UnsupportedType(peek.shape().to_string())
+}
+
+fn to_value(peek: Peek<'_, '_, '_>) -> Result<Value, PlistSerError> {
+    let shape = peek.shape();
+    let err = |_| unsupported(peek);
+
+    if let Ok(data) = peek.get::<Vec<u8>>() {
+        return Ok(Value::Data(data.clone()));
+    }
+
+    match shape.def {
+        Def::Option(_) => {
+            let opt = peek.into_option().map_err(err)?;
+            return match opt.value() {
+                Some(inner) => to_value(inner),
+                None => Err(PlistSerError::NoneOutsideStruct),
+            };
+        }
+        Def::List(_) | Def::Array(_) | Def::Slice(_) => {
+            let list = peek.into_list_like().map_err(err)?;
+            return Ok(Value::Array(
+                list.iter().map(to_value).collect::<Result<_, _>>()?,
+            ));
+        }
+        Def::Map(_) => {
+            let map = peek.into_map().map_err(err)?;
+            let mut entries = Vec::with_capacity(map.len());
+            for (key, value) in map.iter() {
+                let name = key
+                    .as_text()
+                    .ok_or_else(|| PlistSerError::UnsupportedKeyType(key.shape().to_string()))?;
+                entries.push((name.into_owned(), to_value(value)?));
+            }
+            return Ok(Value::Dict(entries));
+        }
+        _ => {}
+    }
+
+    if let Some(scalar) = peek.scalar_type() {
+        let value = match scalar {
+            ScalarType::Bool => Value::Boolean(*peek.get::<bool>().map_err(err)?),
+            ScalarType::F32 => Value::Real(*peek.get::<f32>().map_err(err)? as f64),
+            ScalarType::F64 => Value::Real(*peek.get::<f64>().map_err(err)?),
+            ScalarType::U8 => Value::Integer(*peek.get::<u8>().map_err(err)? as i128),
+            ScalarType::U16 => Value::Integer(*peek.get::<u16>().map_err(err)? as i128),
+            ScalarType::U32 => Value::Integer(*peek.get::<u32>().map_err(err)? as i128),
+            ScalarType::U64 => Value::Integer(*peek.get::<u64>().map_err(err)? as i128),
+            ScalarType::USize => Value::Integer(*peek.get::<usize>().map_err(err)? as i128),
+            ScalarType::I8 => Value::Integer(*peek.get::<i8>().map_err(err)? as i128),
+            ScalarType::I16 => Value::Integer(*peek.get::<i16>().map_err(err)? as i128),
+            ScalarType::I32 => Value::Integer(*peek.get::<i32>().map_err(err)? as i128),
+            ScalarType::I64 => Value::Integer(*peek.get::<i64>().map_err(err)? as i128),
+            ScalarType::ISize => Value::Integer(*peek.get::<isize>().map_err(err)? as i128),
+            ScalarType::Unit => return Err(unsupported(peek)),
+            _ if shape.is_display() => {
+                let text = peek.as_text().ok_or_else(|| unsupported(peek))?;
+                Value::String(text.into_owned())
+            }
+            _ => return Err(unsupported(peek)),
+        };
+        return Ok(value);
+    }
+
+    if shape.inner.is_some() {
+        return to_value(peek.innermost_peek());
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => {
+            let ps = peek.into_struct().map_err(err)?;
+            match sd.kind {
+                StructKind::Tuple | StructKind::TupleStruct => {
+                    let items = (0..ps.field_count())
+                        .map(|index| to_value(ps.field(index).map_err(|_| unsupported(peek))?))
+                        .collect::<Result<_, _>>()?;
+                    Ok(Value::Array(items))
+                }
+                _ => fields_to_dict(ps.fields_for_serialize()),
+            }
+        }
+        Type::User(UserType::Enum(_)) => {
+            let pe = peek.into_enum().map_err(|_| unsupported(peek))?;
+            let variant = pe.active_variant().map_err(|_| unsupported(peek))?;
+            let content = match variant.data.kind {
+                StructKind::Unit => return Ok(Value::String(variant.name.to_string())),
+                StructKind::Struct => fields_to_dict(pe.fields_for_serialize())?,
+                _ => {
+                    let fields = variant.data.fields.len();
+                    let mut items = Vec::with_capacity(fields);
+                    for index in 0..fields {
+                        let field = pe
+                            .field(index)
+                            .ok()
+                            .flatten()
+                            .ok_or_else(|| unsupported(peek))?;
+                        items.push(to_value(field)?);
+                    }
+                    if fields == 1 {
+                        items.remove(0)
+                    } else {
+                        Value::Array(items)
+                    }
+                }
+            };
+            Ok(Value::Dict(vec![(variant.name.to_string(), content)]))
+        }
+        _ if shape.is_display() => {
+            let text = peek.as_text().ok_or_else(|| unsupported(peek))?;
+            Ok(Value::String(text.into_owned()))
+        }
+        _ => Err(unsupported(peek)),
+    }
+}
+
+fn fields_to_dict<'mem, 'facet, 'shape>(
+    fields: impl Iterator<Item = (facet_core::Field<'shape>, Peek<'mem, 'facet, 'shape>)>,
+) -> Result<Value, PlistSerError> {
+    let mut entries = Vec::new();
+    for (field, value) in fields {
+        // Missing keys are how property lists spell `None`
+        if let Def::Option(_) = value.shape().def {
+            if value.into_option().is_ok_and(|opt| opt.is_none()) {
+                continue;
+            }
+        }
+        entries.push((field.name.to_string(), to_value(value)?));
+    }
+    Ok(Value::Dict(entries))
+}
@@ -0,0 +1,65 @@
+//! The property list data model shared by the XML and binary formats.
+
+use alloc::{string::String, vec::Vec};
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    Dict(Vec<(String, Value)>),
+    Array(Vec<Value>),
+    String(String),
+    /// Wide enough for every `i64` and `u64`
+    Integer(i128),
+    Real(f64),
+    Boolean(bool),
+    /// An ISO 8601 date such as `2024-05-01T12:00:00Z`
+    Date(String),
+    Data(Vec<u8>),
+    /// A keyed archiver object reference, only found in binary plists
+    Uid(u64),
+}
+
+impl Value {
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            Value::Dict(_) => "dict",
+            Value::Array(_) => "array",
+            Value::String(_) => "string",
+            Value::Integer(_) => "integer",
+            Value::Real(_) => "real",
+            Value::Boolean(_) => "boolean",
+            Value::Date(_) => "date",
+            Value::Data(_) => "data",
+            Value::Uid(_) => "uid",
+        }
+    }
+}
+
+/// Days since 1970-01-01 to a (year, month, day) civil date.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's algorithm
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// A (year, month, day) civil date to days since 1970-01-01.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Seconds between the Unix epoch and the Core Data epoch (2001-01-01), which binary
+/// plists count dates from.
+pub(crate) const CORE_DATA_EPOCH: i64 = 978_307_200;
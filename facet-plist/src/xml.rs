@@ -0,0 +1,376 @@
+//! The XML property list format.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{PlistDeError, value::Value};
+
+const HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+"#;
+
+/// Parses an XML property list document.
+pub(crate) fn parse(input: &str) -> Result<Value, PlistDeError<'static>> {
+    let mut parser = Parser { input, pos: 0 };
+    parser.skip_prolog()?;
+    let root = match parser.next_tag()? {
+        Tag::Open("plist") => {
+            let value = parser.value()?;
+            parser.expect_close("plist")?;
+            value
+        }
+        // Some tools write a bare value without the `<plist>` wrapper
+        tag => parser.value_from(tag)?,
+    };
+    parser.skip_misc()?;
+    if parser.pos != input.len() {
+        return Err(parser.error("unexpected content after the root element"));
+    }
+    Ok(root)
+}
+
+#[derive(Debug)]
+enum Tag<'input> {
+    Open(&'input str),
+    Close(&'input str),
+    Empty(&'input str),
+}
+
+struct Parser<'input> {
+    input: &'input str,
+    pos: usize,
+}
+
+fn error_at(offset: usize, message: &'static str) -> PlistDeError<'static> {
+    PlistDeError::Xml { message, offset }
+}
+
+impl<'input> Parser<'input> {
+    fn error(&self, message: &'static str) -> PlistDeError<'static> {
+        error_at(self.pos, message)
+    }
+
+    fn rest(&self) -> &'input str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips whitespace, comments and processing instructions.
+    fn skip_misc(&mut self) -> Result<(), PlistDeError<'static>> {
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            let end = if rest.starts_with("<!--") {
+                rest.find("-->").map(|i| i + 3)
+            } else if rest.starts_with("<?") {
+                rest.find("?>").map(|i| i + 2)
+            } else {
+                return Ok(());
+            };
+            self.pos += end.ok_or_else(|| self.error("unterminated comment"))?;
+        }
+    }
+
+    fn skip_prolog(&mut self) -> Result<(), PlistDeError<'static>> {
+        // A UTF-8 byte order mark
+        if self.rest().starts_with('\u{feff}') {
+            self.pos += '\u{feff}'.len_utf8();
+        }
+        self.skip_misc()?;
+        if self.rest().starts_with("<!DOCTYPE") {
+            let end = self
+                .rest()
+                .find('>')
+                .ok_or_else(|| self.error("unterminated DOCTYPE"))?;
+            self.pos += end + 1;
+        }
+        self.skip_misc()
+    }
+
+    fn next_tag(&mut self) -> Result<Tag<'input>, PlistDeError<'static>> {
+        self.skip_misc()?;
+        let rest = self.rest();
+        if !rest.starts_with('<') {
+            return Err(self.error("expected an element"));
+        }
+        let end = rest
+            .find('>')
+            .ok_or_else(|| self.error("unterminated tag"))?;
+        let inner = &rest[1..end];
+        self.pos += end + 1;
+
+        if let Some(name) = inner.strip_prefix('/') {
+            return Ok(Tag::Close(name.trim()));
+        }
+        let (inner, empty) = match inner.strip_suffix('/') {
+            Some(inner) => (inner, true),
+            None => (inner, false),
+        };
+        // Attributes such as `version="1.0"` don't matter
+        let name = inner.split_whitespace().next().unwrap_or("");
+        Ok(if empty {
+            Tag::Empty(name)
+        } else {
+            Tag::Open(name)
+        })
+    }
+
+    fn expect_close(&mut self, name: &str) -> Result<(), PlistDeError<'static>> {
+        match self.next_tag()? {
+            Tag::Close(n) if n == name => Ok(()),
+            _ => Err(self.error("mismatched closing tag")),
+        }
+    }
+
+    /// Reads the text of an element up to its closing tag, decoding entities.
+    fn text(&mut self, name: &str) -> Result<String, PlistDeError<'static>> {
+        let rest = self.rest();
+        let end = rest
+            .find('<')
+            .ok_or_else(|| self.error("unterminated element"))?;
+        let raw = &rest[..end];
+        let mut text = String::with_capacity(raw.len());
+        let mut chunks = raw.split('&');
+        text.push_str(chunks.next().unwrap_or(""));
+        for chunk in chunks {
+            let (entity, tail) = chunk
+                .split_once(';')
+                .ok_or_else(|| self.error("unterminated entity"))?;
+            let c = match entity {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => {
+                    let code = if let Some(hex) = entity.strip_prefix("#x") {
+                        u32::from_str_radix(hex, 16).ok()
+                    } else if let Some(dec) = entity.strip_prefix('#') {
+                        dec.parse().ok()
+                    } else {
+                        None
+                    };
+                    code.and_then(char::from_u32)
+                        .ok_or_else(|| self.error("unknown entity"))?
+                }
+            };
+            text.push(c);
+            text.push_str(tail);
+        }
+        self.pos += end;
+        self.expect_close(name)?;
+        Ok(text)
+    }
+
+    fn value(&mut self) -> Result<Value, PlistDeError<'static>> {
+        let tag = self.next_tag()?;
+        self.value_from(tag)
+    }
+
+    fn value_from(&mut self, tag: Tag<'input>) -> Result<Value, PlistDeError<'static>> {
+        let start = self.pos;
+        Ok(match tag {
+            Tag::Empty("true") => Value::Boolean(true),
+            Tag::Empty("false") => Value::Boolean(false),
+            Tag::Empty("dict") => Value::Dict(Vec::new()),
+            Tag::Empty("array") => Value::Array(Vec::new()),
+            Tag::Empty("string") => Value::String(String::new()),
+            Tag::Empty("data") => Value::Data(Vec::new()),
+            Tag::Open("true") => {
+                self.expect_close("true")?;
+                Value::Boolean(true)
+            }
+            Tag::Open("false") => {
+                self.expect_close("false")?;
+                Value::Boolean(false)
+            }
+            Tag::Open("string") => Value::String(self.text("string")?),
+            Tag::Open("date") => Value::Date(self.text("date")?.trim().into()),
+            Tag::Open("integer") => {
+                let text = self.text("integer")?;
+                let text = text.trim();
+                let value = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+                    Some(hex) => i128::from_str_radix(hex, 16).ok(),
+                    None => text.parse().ok(),
+                };
+                Value::Integer(value.ok_or_else(|| error_at(start, "invalid integer"))?)
+            }
+            Tag::Open("real") => {
+                let text = self.text("real")?;
+                let value = match text.trim() {
+                    "nan" => Some(f64::NAN),
+                    "inf" | "+infinity" => Some(f64::INFINITY),
+                    "-inf" | "-infinity" => Some(f64::NEG_INFINITY),
+                    text => text.parse().ok(),
+                };
+                Value::Real(value.ok_or_else(|| error_at(start, "invalid real"))?)
+            }
+            Tag::Open("data") => {
+                let text = self.text("data")?;
+                Value::Data(
+                    base64_decode(&text).ok_or_else(|| error_at(start, "invalid base64 data"))?,
+                )
+            }
+            Tag::Open("array") => {
+                let mut items = Vec::new();
+                loop {
+                    match self.next_tag()? {
+                        Tag::Close("array") => break,
+                        tag => items.push(self.value_from(tag)?),
+                    }
+                }
+                Value::Array(items)
+            }
+            Tag::Open("dict") => {
+                let mut entries = Vec::new();
+                loop {
+                    match self.next_tag()? {
+                        Tag::Close("dict") => break,
+                        Tag::Open("key") => {
+                            let key = self.text("key")?;
+                            entries.push((key, self.value()?));
+                        }
+                        Tag::Empty("key") => entries.push((String::new(), self.value()?)),
+                        _ => return Err(self.error("expected <key> in <dict>")),
+                    }
+                }
+                Value::Dict(entries)
+            }
+            _ => return Err(error_at(start, "unknown element")),
+        })
+    }
+}
+
+/// Writes a value as an XML property list document.
+pub(crate) fn write(value: &Value) -> String {
+    let mut out = String::from(HEADER);
+    write_value(&mut out, value, 0);
+    out.push_str("</plist>\n");
+    out
+}
+
+fn indent(out: &mut String, depth: usize) {
+    for _ in 0..depth {
+        out.push('\t');
+    }
+}
+
+fn write_value(out: &mut String, value: &Value, depth: usize) {
+    indent(out, depth);
+    match value {
+        Value::Dict(entries) if entries.is_empty() => out.push_str("<dict/>\n"),
+        Value::Dict(entries) => {
+            out.push_str("<dict>\n");
+            for (key, value) in entries {
+                indent(out, depth + 1);
+                out.push_str("<key>");
+                escape(out, key);
+                out.push_str("</key>\n");
+                write_value(out, value, depth + 1);
+            }
+            indent(out, depth);
+            out.push_str("</dict>\n");
+        }
+        Value::Array(items) if items.is_empty() => out.push_str("<array/>\n"),
+        Value::Array(items) => {
+            out.push_str("<array>\n");
+            for item in items {
+                write_value(out, item, depth + 1);
+            }
+            indent(out, depth);
+            out.push_str("</array>\n");
+        }
+        Value::String(s) => {
+            out.push_str("<string>");
+            escape(out, s);
+            out.push_str("</string>\n");
+        }
+        Value::Integer(i) => writeln!(out, "<integer>{i}</integer>").unwrap(),
+        Value::Real(r) if r.is_nan() => out.push_str("<real>nan</real>\n"),
+        Value::Real(r) if r.is_infinite() => {
+            let sign = if *r < 0.0 { "-" } else { "+" };
+            writeln!(out, "<real>{sign}infinity</real>").unwrap();
+        }
+        Value::Real(r) => writeln!(out, "<real>{r}</real>").unwrap(),
+        Value::Boolean(true) => out.push_str("<true/>\n"),
+        Value::Boolean(false) => out.push_str("<false/>\n"),
+        Value::Date(date) => {
+            out.push_str("<date>");
+            escape(out, date);
+            out.push_str("</date>\n");
+        }
+        Value::Data(data) => {
+            out.push_str("<data>");
+            base64_encode(out, data);
+            out.push_str("</data>\n");
+        }
+        // XML plists spell UIDs as a dict with a `CF$UID` key
+        Value::Uid(uid) => writeln!(
+            out,
+            "<dict><key>CF$UID</key><integer>{uid}</integer></dict>"
+        )
+        .unwrap(),
+    }
+}
+
+fn escape(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            c => out.push(c),
+        }
+    }
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(out: &mut String, data: &[u8]) {
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+}
+
+/// Decodes base64, ignoring whitespace since plist data is usually wrapped.
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for c in text.bytes() {
+        let v = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            c if c.is_ascii_whitespace() => continue,
+            _ => return None,
+        };
+        acc = acc << 6 | v as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    Some(out)
+}
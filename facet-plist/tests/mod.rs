@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_plist::{PlistDeError, PlistSerError, from_slice, from_str, to_binary, to_string};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Info {
+    #[facet(rename = "CFBundleIdentifier")]
+    bundle_id: String,
+    #[facet(rename = "CFBundleVersion")]
+    version: u32,
+    #[facet(rename = "LSMinimumSystemVersion")]
+    minimum_system: Option<String>,
+    #[facet(rename = "UIRequiredDeviceCapabilities")]
+    capabilities: Vec<String>,
+    #[facet(rename = "NSHighResolutionCapable")]
+    high_resolution: bool,
+}
+
+const INFO_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<!-- written by Xcode -->
+	<key>CFBundleIdentifier</key>
+	<string>rs.facet.demo</string>
+	<key>CFBundleVersion</key>
+	<integer>42</integer>
+	<key>CFBundleDevelopmentRegion</key>
+	<string>en</string>
+	<key>UIRequiredDeviceCapabilities</key>
+	<array>
+		<string>arm64</string>
+		<string>metal &amp; gpu</string>
+	</array>
+	<key>NSHighResolutionCapable</key>
+	<true/>
+</dict>
+</plist>
+"#;
+
+#[test]
+fn test_read_info_plist() {
+    let info: Info = from_str(INFO_PLIST)?;
+    assert_eq!(
+        info,
+        Info {
+            bundle_id: "rs.facet.demo".to_string(),
+            version: 42,
+            minimum_system: None,
+            capabilities: vec!["arm64".to_string(), "metal & gpu".to_string()],
+            high_resolution: true,
+        }
+    );
+}
+
+#[test]
+fn test_write_xml() {
+    let info = Info {
+        bundle_id: "rs.facet.demo".to_string(),
+        version: 1,
+        minimum_system: None,
+        capabilities: vec![],
+        high_resolution: false,
+    };
+    assert_eq!(
+        to_string(&info)?,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>CFBundleIdentifier</key>
+	<string>rs.facet.demo</string>
+	<key>CFBundleVersion</key>
+	<integer>1</integer>
+	<key>UIRequiredDeviceCapabilities</key>
+	<array/>
+	<key>NSHighResolutionCapable</key>
+	<false/>
+</dict>
+</plist>
+"#
+    );
+}
+
+#[test]
+fn test_binary_matches_known_bytes() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Small {
+        a: u8,
+    }
+
+    // `plistlib.dumps({"a": 1}, fmt=plistlib.FMT_BINARY)`
+    let expected = [
+        b'b', b'p', b'l', b'i', b's', b't', b'0', b'0', // magic
+        0xd1, 0x01, 0x02, // dict with key ref 1 and value ref 2
+        0x51, b'a', // "a"
+        0x10, 0x01, // 1
+        0x08, 0x0b, 0x0d, // offset table
+        0, 0, 0, 0, 0, 0, 1, 1, // offset and ref sizes
+        0, 0, 0, 0, 0, 0, 0, 3, // object count
+        0, 0, 0, 0, 0, 0, 0, 0, // root object
+        0, 0, 0, 0, 0, 0, 0, 0x0f, // offset table offset
+    ];
+    assert_eq!(to_binary(&Small { a: 1 })?, expected);
+    assert_eq!(from_slice::<Small>(&expected)?, Small { a: 1 });
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Figure {
+    Point,
+    Circle { radius: f64 },
+    Label(String),
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Document {
+    title: String,
+    shapes: Vec<Figure>,
+    scores: HashMap<String, i64>,
+    thumbnail: Vec<u8>,
+    origin: (i32, i32),
+    note: Option<String>,
+}
+
+fn document() -> Document {
+    Document {
+        title: "Ünïcode title".to_string(),
+        shapes: vec![
+            Figure::Point,
+            Figure::Circle { radius: 2.5 },
+            Figure::Label("hello".to_string()),
+        ],
+        scores: HashMap::from([("alice".to_string(), -3), ("bob".to_string(), 1 << 40)]),
+        thumbnail: (0..=255).collect(),
+        origin: (-1, 1),
+        note: Some("kept".to_string()),
+    }
+}
+
+#[test]
+fn test_roundtrip_xml() {
+    let xml = to_string(&document())?;
+    assert!(xml.contains("<string>Point</string>"));
+    assert!(xml.contains("<key>Circle</key>"));
+    assert_eq!(from_str::<Document>(&xml)?, document());
+}
+
+#[test]
+fn test_roundtrip_binary() {
+    let binary = to_binary(&document())?;
+    assert!(binary.starts_with(b"bplist00"));
+    assert_eq!(from_slice::<Document>(&binary)?, document());
+}
+
+#[test]
+fn test_from_slice_reads_xml() {
+    let info: Info = from_slice(INFO_PLIST.as_bytes())?;
+    assert_eq!(info.version, 42);
+}
+
+#[test]
+fn test_defaults_and_unknown_keys() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[facet(deny_unknown_fields)]
+    struct Strict {
+        name: String,
+        #[facet(default = 8)]
+        retries: u8,
+    }
+
+    let strict: Strict = from_str("<plist><dict><key>name</key><string>x</string></dict></plist>")?;
+    assert_eq!(
+        strict,
+        Strict {
+            name: "x".to_string(),
+            retries: 8,
+        }
+    );
+
+    let err = from_str::<Strict>(
+        "<plist><dict><key>name</key><string>x</string><key>extra</key><true/></dict></plist>",
+    )
+    .unwrap_err();
+    assert!(matches!(err, PlistDeError::UnknownField { ref name, .. } if name == "extra"));
+
+    let err = from_str::<Strict>("<plist><dict/></plist>").unwrap_err();
+    assert!(matches!(err, PlistDeError::MissingField("name")));
+}
+
+#[test]
+fn test_errors() {
+    let err = from_str::<Info>("<plist><dict><key>CFBundleIdentifier</key></plist>").unwrap_err();
+    assert!(matches!(err, PlistDeError::Xml { .. }));
+
+    let err = from_str::<u8>("<plist><integer>300</integer></plist>").unwrap_err();
+    assert!(matches!(err, PlistDeError::NumberOutOfRange(_)));
+
+    let err = from_slice::<u8>(b"bplist00garbage").unwrap_err();
+    assert!(matches!(err, PlistDeError::Binary(_)));
+
+    let err = to_string(&None::<u8>).unwrap_err();
+    assert!(matches!(err, PlistSerError::NoneOutsideStruct));
+
+    let grid: HashMap<(u8, u8), i64> = HashMap::from([((0, 1), 2)]);
+    let err = to_string(&grid).unwrap_err();
+    assert!(matches!(err, PlistSerError::UnsupportedKeyType(_)));
+}
@@ -0,0 +1,273 @@
+//! `Debug` and `Display` implementations for any Facet type, through reflection
+
+use core::fmt::{self, Debug, Display, Formatter};
+
+use facet_core::{Def, Facet, FieldFlags, PointerType, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek};
+
+/// Formats a value like `#[derive(Debug)]` would, through reflection
+///
+/// Fields marked `#[facet(sensitive)]` are written as `[REDACTED]`, and `{:#?}` spreads the
+/// value over several lines, as usual. This lets big structs drop their hand-written `Debug`
+/// impls.
+///
+/// ```
+/// # use facet::Facet;
+/// # use facet_pretty::FacetDebug;
+/// #[derive(Facet)]
+/// struct Login {
+///     user: String,
+///     #[facet(sensitive)]
+///     password: String,
+/// }
+///
+/// let login = Login {
+///     user: "amos".into(),
+///     password: "hunter2".into(),
+/// };
+/// assert_eq!(
+///     format!("{:?}", FacetDebug(&login)),
+///     r#"Login { user: "amos", password: [REDACTED] }"#
+/// );
+/// ```
+pub struct FacetDebug<'a, T: ?Sized>(pub &'a T);
+
+impl<'a, T: Facet<'a> + ?Sized> Debug for FacetDebug<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Reflected {
+            value: Peek::new(self.0),
+            display: false,
+        }
+        .fmt(f)
+    }
+}
+
+/// Formats a value like [`FacetDebug`], but with the `Display` impl of its scalars
+///
+/// Strings are written without quotes, and so are other scalars that can be displayed, like
+/// paths or addresses. `{:#}` spreads the value over several lines.
+///
+/// ```
+/// # use facet::Facet;
+/// # use facet_pretty::FacetDisplay;
+/// #[derive(Facet)]
+/// struct Endpoint {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let endpoint = Endpoint {
+///     host: "localhost".into(),
+///     port: 8080,
+/// };
+/// assert_eq!(
+///     FacetDisplay(&endpoint).to_string(),
+///     "Endpoint { host: localhost, port: 8080 }"
+/// );
+/// ```
+pub struct FacetDisplay<'a, T: ?Sized>(pub &'a T);
+
+impl<'a, T: Facet<'a> + ?Sized> Display for FacetDisplay<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Reflected {
+            value: Peek::new(self.0),
+            display: true,
+        }
+        .fmt(f)
+    }
+}
+
+/// A value being formatted by [`FacetDebug`] or [`FacetDisplay`]
+///
+/// It goes through `Debug` either way, so that the `debug_*` builders can format it.
+#[derive(Clone, Copy)]
+struct Reflected<'mem, 'facet, 'shape> {
+    value: Peek<'mem, 'facet, 'shape>,
+    /// Whether scalars are written with their `Display` impl
+    display: bool,
+}
+
+impl<'mem, 'facet, 'shape> Reflected<'mem, 'facet, 'shape> {
+    fn with(self, value: Peek<'mem, 'facet, 'shape>) -> Self {
+        Self { value, ..self }
+    }
+
+    fn scalar(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.value.display() {
+            Some(display) if self.display => Display::fmt(&display, f),
+            // Falls back to the type's name
+            _ => Debug::fmt(&self.value, f),
+        }
+    }
+
+    fn fields(
+        &self,
+        f: &mut Formatter<'_>,
+        name: &str,
+        kind: StructKind,
+        fields: &impl HasFields<'mem, 'facet, 'shape>,
+    ) -> fmt::Result {
+        match kind {
+            StructKind::Unit => f.write_str(name),
+            StructKind::Tuple | StructKind::TupleStruct => {
+                let mut tuple = f.debug_tuple(name);
+                for (field, value) in fields.fields() {
+                    let value = self.with(value);
+                    tuple.field(redact(field.flags, &value));
+                }
+                tuple.finish()
+            }
+            _ => {
+                let mut struct_ = f.debug_struct(name);
+                for (field, value) in fields.fields() {
+                    let value = self.with(value);
+                    struct_.field(field.name, redact(field.flags, &value));
+                }
+                struct_.finish()
+            }
+        }
+    }
+}
+
+/// Stands in for the value of a sensitive field
+struct Redacted;
+
+impl Debug for Redacted {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+fn redact(flags: FieldFlags, value: &dyn Debug) -> &dyn Debug {
+    if flags.contains(FieldFlags::SENSITIVE) {
+        &Redacted
+    } else {
+        value
+    }
+}
+
+impl Debug for Reflected<'_, '_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let value = self.value;
+        let shape = value.shape();
+        match (shape.def, shape.ty) {
+            (Def::Scalar(_), _) => self.scalar(f),
+            (Def::Option(_), _) => match value.into_option().unwrap().value() {
+                Some(inner) => f.debug_tuple("Some").field(&self.with(inner)).finish(),
+                None => f.write_str("None"),
+            },
+            (Def::SmartPointer(_), _) => match value.into_smart_pointer().unwrap().borrow_inner() {
+                Some(inner) => self.with(inner).fmt(f),
+                None => self.scalar(f),
+            },
+            (Def::Map(_), _) => f
+                .debug_map()
+                .entries(
+                    value
+                        .into_map()
+                        .unwrap()
+                        .iter()
+                        .map(|(k, v)| (self.with(k), self.with(v))),
+                )
+                .finish(),
+            (Def::List(_) | Def::Array(_), _) => f
+                .debug_list()
+                .entries(value.into_list_like().unwrap().iter().map(|v| self.with(v)))
+                .finish(),
+            (_, Type::Pointer(PointerType::Reference(_))) => {
+                if let Ok(list) = value.into_list_like() {
+                    f.debug_list()
+                        .entries(list.iter().map(|v| self.with(v)))
+                        .finish()
+                } else if let Ok(pointee) = value.into_pointee() {
+                    self.with(pointee).fmt(f)
+                } else {
+                    self.scalar(f)
+                }
+            }
+            (_, Type::User(UserType::Struct(ty))) => {
+                let struct_ = value.into_struct().unwrap();
+                // Tuples have no name, like with `#[derive(Debug)]`
+                let name = if ty.kind == StructKind::Tuple {
+                    ""
+                } else {
+                    shape.type_identifier
+                };
+                self.fields(f, name, ty.kind, &struct_)
+            }
+            (_, Type::User(UserType::Enum(_))) => {
+                let enum_ = value.into_enum().unwrap();
+                match enum_.active_variant() {
+                    Ok(variant) => self.fields(f, variant.name, variant.data.kind, &enum_),
+                    Err(_) => self.scalar(f),
+                }
+            }
+            _ => self.scalar(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::collections::BTreeMap;
+    use facet::Facet;
+
+    #[derive(Facet)]
+    struct Account {
+        name: String,
+        #[facet(sensitive)]
+        token: String,
+        roles: Vec<Role>,
+        limits: BTreeMap<String, Option<u32>>,
+        origin: (u8, char),
+    }
+
+    #[derive(Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Role {
+        Admin,
+        Member(u32),
+        Guest { until: String },
+    }
+
+    fn account() -> Account {
+        Account {
+            name: "amos".into(),
+            token: "secret".into(),
+            roles: vec![
+                Role::Admin,
+                Role::Member(3),
+                Role::Guest {
+                    until: "soon".into(),
+                },
+            ],
+            limits: [("cpu".to_string(), Some(2)), ("mem".to_string(), None)].into(),
+            origin: (1, 'x'),
+        }
+    }
+
+    #[test]
+    fn test_facet_debug() {
+        assert_eq!(
+            format!("{:?}", FacetDebug(&account())),
+            r#"Account { name: "amos", token: [REDACTED], roles: [Admin, Member(3), Guest { until: "soon" }], limits: {"cpu": Some(2), "mem": None}, origin: (1, 'x') }"#
+        );
+    }
+
+    #[test]
+    fn test_facet_debug_alternate() {
+        let output = format!("{:#?}", FacetDebug(&account()));
+        assert!(output.starts_with("Account {\n    name: \"amos\",\n    token: [REDACTED],\n"));
+        assert!(!output.contains("secret"));
+    }
+
+    #[test]
+    fn test_facet_display() {
+        assert_eq!(
+            FacetDisplay(&account()).to_string(),
+            "Account { name: amos, token: [REDACTED], roles: [Admin, Member(3), Guest { until: soon }], limits: {cpu: Some(2), mem: None}, origin: (1, x) }"
+        );
+    }
+}
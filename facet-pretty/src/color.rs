@@ -31,6 +31,33 @@ impl RGB {
     }
 }
 
+/// When a [`PrettyPrinter`](crate::PrettyPrinter) writes ANSI colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMode {
+    /// Colors, unless the `NO_COLOR` or `CI` environment variable is set to a non-empty value
+    #[default]
+    Auto,
+    /// Colors, whatever the environment says
+    Always,
+    /// Plain text, whatever the environment says, e.g. for log files and golden tests
+    Never,
+}
+
+impl ColorMode {
+    /// Whether colors are written in this mode, in the current environment
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorMode::Auto => !env_flag("NO_COLOR") && !env_flag("CI"),
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+        }
+    }
+}
+
+fn env_flag(name: &str) -> bool {
+    std::env::var_os(name).is_some_and(|value| !value.is_empty())
+}
+
 /// A color generator that produces unique colors based on a hash value
 pub struct ColorGenerator {
     base_hue: f32,
@@ -1,6 +1,6 @@
 //! Display trait implementations for pretty-printing Facet types
 
-use core::fmt::{self, Display, Formatter};
+use core::fmt::{self, Display, Formatter, Write};
 
 use crate::printer::PrettyPrinter;
 use facet_core::Facet;
@@ -24,6 +24,9 @@ pub trait FacetPretty<'a>: Facet<'a> {
 
     /// Get a displayable wrapper with custom printer settings
     fn pretty_with(&'a self, printer: PrettyPrinter) -> PrettyDisplay<'a, Self>;
+
+    /// Pretty-print this value to `out`, with the given printer settings
+    fn pretty_to(&'a self, out: &mut impl Write, printer: &PrettyPrinter) -> fmt::Result;
 }
 
 impl<'a, T: Facet<'a>> FacetPretty<'a> for T {
@@ -40,12 +43,16 @@ impl<'a, T: Facet<'a>> FacetPretty<'a> for T {
             printer,
         }
     }
+
+    fn pretty_to(&'a self, out: &mut impl Write, printer: &PrettyPrinter) -> fmt::Result {
+        printer.write_to(self, out)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use core::fmt::Write;
+    use crate::ColorMode;
     use facet::Facet;
 
     // Use the derive macro from facet
@@ -78,4 +85,17 @@ mod tests {
         // Just check that it contains the field name and doesn't panic
         assert!(output.contains("field"));
     }
+
+    #[test]
+    fn test_pretty_to_plain_text() {
+        let test = TestStruct { field: 42 };
+        let printer = PrettyPrinter::new().with_color_mode(ColorMode::Never);
+
+        let mut output = String::new();
+        test.pretty_to(&mut output, &printer).unwrap();
+
+        assert!(output.contains("field"));
+        assert!(!output.contains('\x1b'));
+        assert_eq!(output, printer.format(&test));
+    }
 }
@@ -6,10 +6,12 @@
 
 extern crate alloc;
 
+mod adapters;
 mod color;
 mod display;
 mod printer;
 
+pub use adapters::*;
 pub use color::*;
 pub use display::*;
 pub use printer::*;
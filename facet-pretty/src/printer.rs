@@ -14,7 +14,7 @@ use facet_core::{
 };
 use facet_reflect::{Peek, ValueId};
 
-use crate::color::ColorGenerator;
+use crate::color::{ColorGenerator, ColorMode};
 
 /// A formatter for pretty-printing Facet types
 pub struct PrettyPrinter {
@@ -31,7 +31,7 @@ impl Default for PrettyPrinter {
             indent_size: 2,
             max_depth: None,
             color_generator: ColorGenerator::default(),
-            use_colors: std::env::var_os("NO_COLOR").is_none(),
+            use_colors: ColorMode::Auto.enabled(),
             list_u8_as_bytes: true,
         }
     }
@@ -91,6 +91,12 @@ impl PrettyPrinter {
         self
     }
 
+    /// Choose whether to write colors, or to decide from the environment, see [`ColorMode`]
+    pub fn with_color_mode(mut self, mode: ColorMode) -> Self {
+        self.use_colors = mode.enabled();
+        self
+    }
+
     /// Format a value to a string
     pub fn format<'a, T: Facet<'a>>(&self, value: &T) -> String {
         let value = Peek::new(value);
@@ -112,6 +118,11 @@ impl PrettyPrinter {
         self.format_peek_internal(value, f, &mut HashMap::new())
     }
 
+    /// Format a value to any [`Write`], like a `String` or a log file adapter
+    pub fn write_to<'a, T: Facet<'a>>(&self, value: &T, out: &mut impl Write) -> fmt::Result {
+        self.format_peek_internal(Peek::new(value), out, &mut HashMap::new())
+    }
+
     /// Format a value to a string
     pub fn format_peek(&self, value: Peek<'_, '_, '_>) -> String {
         let mut output = String::new();
@@ -829,10 +840,10 @@ impl PrettyPrinter {
 
         impl fmt::Display for DisplayWrapper<'_, '_, '_> {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                if self.0.shape().is_display() {
-                    write!(f, "{}", self.0)?;
-                } else if self.0.shape().is_debug() {
-                    write!(f, "{:?}", self.0)?;
+                if let Some(display) = self.0.display() {
+                    write!(f, "{display}")?;
+                } else if let Some(debug) = self.0.debug() {
+                    write!(f, "{debug:?}")?;
                 } else {
                     write!(f, "{}", self.0.shape())?;
                     write!(f, "(⋯)")?;
@@ -961,7 +972,7 @@ mod tests {
         let printer = PrettyPrinter::default();
         assert_eq!(printer.indent_size, 2);
         assert_eq!(printer.max_depth, None);
-        assert!(printer.use_colors);
+        assert_eq!(printer.use_colors, ColorMode::Auto.enabled());
     }
 
     #[test]
@@ -975,4 +986,18 @@ mod tests {
         assert_eq!(printer.max_depth, Some(3));
         assert!(!printer.use_colors);
     }
+
+    #[test]
+    fn test_color_mode_overrides_environment() {
+        assert!(
+            PrettyPrinter::new()
+                .with_color_mode(ColorMode::Always)
+                .use_colors
+        );
+        assert!(
+            !PrettyPrinter::new()
+                .with_color_mode(ColorMode::Never)
+                .use_colors
+        );
+    }
 }
@@ -0,0 +1,265 @@
+//! Decode proto3 messages into Rust values.
+
+use alloc::{borrow::Cow, string::ToString, vec, vec::Vec};
+use facet_core::{Facet, Field, FieldFlags, Type, UserType};
+use facet_reflect::{Partial, ScalarType};
+use log::trace;
+
+use crate::{
+    ProtoDeError,
+    types::{ProtoType, field_tag},
+    wire::{Reader, WireValue, unzigzag},
+};
+
+/// Decodes a proto3 message into a Facet struct.
+///
+/// Unknown fields are skipped, and missing fields get their default value.
+pub fn from_slice<'input, 'facet: 'shape, 'shape, T: Facet<'facet>>(
+    input: &'input [u8],
+) -> Result<T, ProtoDeError<'shape>> {
+    let mut partial = Partial::alloc::<T>()?;
+    read_message(partial.inner_mut(), input)?;
+    let value = partial.build()?;
+    Ok(*value)
+}
+
+fn read_message<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    input: &[u8],
+) -> Result<(), ProtoDeError<'shape>> {
+    let shape = wip.shape();
+    let Type::User(UserType::Struct(sd)) = shape.ty else {
+        return Err(ProtoDeError::UnsupportedShape(shape));
+    };
+
+    let tags: Vec<u32> = sd
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| field_tag(field, index))
+        .collect();
+
+    // Fields may appear in any order and more than once, so collect everything first.
+    let mut values: Vec<Vec<WireValue<'_>>> = vec![Vec::new(); sd.fields.len()];
+    let mut reader = Reader::new(input);
+    while !reader.is_empty() {
+        let (tag, value) = reader.read_record()?;
+        match tags.iter().position(|t| *t == tag) {
+            Some(index) => values[index].push(value),
+            None => trace!("Skipping unknown field {tag} of {shape}"),
+        }
+    }
+
+    for (index, field) in sd.fields.iter().enumerate() {
+        wip.begin_nth_field(index)?;
+        if values[index].is_empty() {
+            set_missing(wip, field)?;
+        } else {
+            read_field(wip, tags[index], &values[index])?;
+        }
+        wip.end()?;
+    }
+    Ok(())
+}
+
+fn set_missing<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    field: &Field<'shape>,
+) -> Result<(), ProtoDeError<'shape>> {
+    if field.flags.contains(FieldFlags::DEFAULT) {
+        if let Some(default_fn) = field.vtable.default_fn {
+            wip.set_field_default(default_fn)?;
+            return Ok(());
+        }
+    }
+    set_implicit_default(wip).map_err(|e| match e {
+        ProtoDeError::Reflect(_) => ProtoDeError::MissingField(field.name),
+        e => e,
+    })
+}
+
+/// Sets the proto3 default: zero for scalars and enums, empty for lists and maps, and a
+/// message with every field defaulted for structs.
+fn set_implicit_default<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+) -> Result<(), ProtoDeError<'shape>> {
+    match ProtoType::of(wip.shape()) {
+        Some(ProtoType::Message(_)) => read_message(wip, &[]),
+        Some(ProtoType::Enum(_)) => {
+            wip.select_variant(0)?;
+            Ok(())
+        }
+        _ => {
+            wip.set_default()?;
+            Ok(())
+        }
+    }
+}
+
+/// Reads every occurrence of one field. `values` is never empty.
+fn read_field<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    tag: u32,
+    values: &[WireValue<'_>],
+) -> Result<(), ProtoDeError<'shape>> {
+    let shape = wip.shape();
+    let ty = ProtoType::of(shape).ok_or(ProtoDeError::UnsupportedShape(shape))?;
+    let Some(last) = values.last() else {
+        return set_implicit_default(wip);
+    };
+
+    match ty {
+        ProtoType::Optional(_) => {
+            wip.begin_some()?;
+            read_field(wip, tag, values)?;
+            wip.end()?;
+        }
+        ProtoType::Transparent(_) => {
+            wip.begin_inner()?;
+            read_field(wip, tag, values)?;
+            wip.end()?;
+        }
+        ProtoType::Repeated(elem) => {
+            let elem_ty = ProtoType::of(elem).ok_or(ProtoDeError::UnsupportedShape(elem))?;
+            wip.begin_list()?;
+            for value in values {
+                match value {
+                    WireValue::Bytes(packed) if elem_ty.is_packable() => {
+                        let mut reader = Reader::new(packed);
+                        while !reader.is_empty() {
+                            let item = reader.read_value(elem_ty.wire_type())?;
+                            wip.begin_list_item()?;
+                            read_field(wip, tag, &[item])?;
+                            wip.end()?;
+                        }
+                    }
+                    value => {
+                        wip.begin_list_item()?;
+                        read_field(wip, tag, core::slice::from_ref(value))?;
+                        wip.end()?;
+                    }
+                }
+            }
+        }
+        ProtoType::Map(..) => {
+            wip.begin_map()?;
+            for value in values {
+                let mut key = None;
+                let mut val = None;
+                let mut reader = Reader::new(expect_bytes(tag, value)?);
+                while !reader.is_empty() {
+                    match reader.read_record()? {
+                        (1, v) => key = Some(v),
+                        (2, v) => val = Some(v),
+                        _ => {}
+                    }
+                }
+                wip.begin_key()?;
+                read_field(wip, 1, key.as_slice())?;
+                wip.end()?;
+                wip.begin_value()?;
+                read_field(wip, 2, val.as_slice())?;
+                wip.end()?;
+            }
+        }
+        ProtoType::Message(_) => read_message(wip, expect_bytes(tag, last)?)?,
+        _ => read_scalar(wip, ty, tag, *last)?,
+    }
+    Ok(())
+}
+
+fn expect_bytes<'input, 'shape>(
+    tag: u32,
+    value: &WireValue<'input>,
+) -> Result<&'input [u8], ProtoDeError<'shape>> {
+    match value {
+        WireValue::Bytes(bytes) => Ok(bytes),
+        other => Err(ProtoDeError::WrongWireType {
+            tag,
+            expected: crate::wire::LEN,
+            got: other.wire_type(),
+        }),
+    }
+}
+
+fn read_scalar<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    ty: ProtoType<'shape>,
+    tag: u32,
+    value: WireValue<'_>,
+) -> Result<(), ProtoDeError<'shape>> {
+    let shape = wip.shape();
+    if value.wire_type() != ty.wire_type() {
+        return Err(ProtoDeError::WrongWireType {
+            tag,
+            expected: ty.wire_type(),
+            got: value.wire_type(),
+        });
+    }
+
+    let out_of_range = |_| ProtoDeError::NumberOutOfRange(shape);
+    match (ty, value) {
+        (ProtoType::Bool, WireValue::Varint(v)) => {
+            wip.set(v != 0)?;
+        }
+        (ProtoType::UInt32 | ProtoType::UInt64, WireValue::Varint(v)) => {
+            match ScalarType::try_from_shape(shape) {
+                Some(ScalarType::U8) => wip.set(u8::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::U16) => wip.set(u16::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::U32) => wip.set(u32::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::U64) => wip.set(v)?,
+                Some(ScalarType::USize) => wip.set(usize::try_from(v).map_err(out_of_range)?)?,
+                _ => return Err(ProtoDeError::UnsupportedShape(shape)),
+            };
+        }
+        (ProtoType::SInt32 | ProtoType::SInt64, WireValue::Varint(v)) => {
+            let v = unzigzag(v);
+            match ScalarType::try_from_shape(shape) {
+                Some(ScalarType::I8) => wip.set(i8::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::I16) => wip.set(i16::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::I32) => wip.set(i32::try_from(v).map_err(out_of_range)?)?,
+                Some(ScalarType::I64) => wip.set(v)?,
+                Some(ScalarType::ISize) => wip.set(isize::try_from(v).map_err(out_of_range)?)?,
+                _ => return Err(ProtoDeError::UnsupportedShape(shape)),
+            };
+        }
+        (ProtoType::Float, WireValue::Fixed32(v)) => {
+            wip.set(f32::from_bits(v))?;
+        }
+        (ProtoType::Double, WireValue::Fixed64(v)) => {
+            wip.set(f64::from_bits(v))?;
+        }
+        (ProtoType::Bytes, WireValue::Bytes(b)) => {
+            wip.set(b.to_vec())?;
+        }
+        (ProtoType::String, WireValue::Bytes(b)) => {
+            let s = core::str::from_utf8(b).map_err(|_| ProtoDeError::InvalidUtf8)?;
+            if let Some(ScalarType::CowStr) = ScalarType::try_from_shape(shape) {
+                wip.set(Cow::<'static, str>::Owned(s.to_string()))?;
+            } else {
+                wip.set(s.to_string())?;
+            }
+        }
+        (ProtoType::Text, WireValue::Bytes(b)) => {
+            let s = core::str::from_utf8(b).map_err(|_| ProtoDeError::InvalidUtf8)?;
+            wip.parse_from_str(s)
+                .map_err(|_| ProtoDeError::InvalidValue(shape))?;
+        }
+        (ProtoType::Enum(ed), WireValue::Varint(v)) => {
+            let discriminant = v as i64;
+            if !ed
+                .variants
+                .iter()
+                .any(|variant| variant.discriminant == Some(discriminant))
+            {
+                return Err(ProtoDeError::UnknownEnumValue {
+                    value: discriminant,
+                    shape,
+                });
+            }
+            wip.select_variant(discriminant)?;
+        }
+        _ => return Err(ProtoDeError::UnsupportedShape(shape)),
+    }
+    Ok(())
+}
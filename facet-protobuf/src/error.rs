@@ -0,0 +1,105 @@
+//! Errors from encoding and decoding protobuf messages.
+
+use alloc::string::String;
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+
+/// Errors when decoding protobuf bytes.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ProtoDeError<'shape> {
+    /// The input ended in the middle of a value.
+    UnexpectedEof,
+    /// A varint was longer than 10 bytes.
+    VarintOverflow,
+    /// A key used a wire type that proto3 doesn't define (or a deprecated group).
+    InvalidWireType(u8),
+    /// A field was encoded with a wire type that doesn't match its Rust type.
+    WrongWireType {
+        /// The field number.
+        tag: u32,
+        /// The wire type the Rust type needs.
+        expected: u8,
+        /// The wire type found in the input.
+        got: u8,
+    },
+    /// A number doesn't fit the Rust type it's decoded into.
+    NumberOutOfRange(&'shape Shape<'shape>),
+    /// A string field doesn't contain valid UTF-8.
+    InvalidUtf8,
+    /// An enum value doesn't match any variant discriminant.
+    UnknownEnumValue {
+        /// The value found in the input.
+        value: i64,
+        /// The enum type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A string couldn't be parsed into the Rust type.
+    InvalidValue(&'shape Shape<'shape>),
+    /// A field is missing and its type has no default value.
+    MissingField(&'shape str),
+    /// The Rust type can't be represented in proto3.
+    UnsupportedShape(&'shape Shape<'shape>),
+    /// Any error from facet.
+    Reflect(ReflectError<'shape>),
+}
+
+impl<'shape> From<ReflectError<'shape>> for ProtoDeError<'shape> {
+    fn from(err: ReflectError<'shape>) -> Self {
+        ProtoDeError::Reflect(err)
+    }
+}
+
+impl core::fmt::Display for ProtoDeError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ProtoDeError::UnexpectedEof => write!(f, "Unexpected end of input"),
+            ProtoDeError::VarintOverflow => write!(f, "Varint is too long"),
+            ProtoDeError::InvalidWireType(wire_type) => {
+                write!(f, "Invalid wire type {wire_type}")
+            }
+            ProtoDeError::WrongWireType { tag, expected, got } => write!(
+                f,
+                "Field {tag} has wire type {got}, but its type needs wire type {expected}"
+            ),
+            ProtoDeError::NumberOutOfRange(shape) => {
+                write!(f, "Number out of range for {shape}")
+            }
+            ProtoDeError::InvalidUtf8 => write!(f, "Invalid UTF-8 in string field"),
+            ProtoDeError::UnknownEnumValue { value, shape } => {
+                write!(f, "Value {value} is not a variant of {shape}")
+            }
+            ProtoDeError::InvalidValue(shape) => write!(f, "Can't parse string as {shape}"),
+            ProtoDeError::MissingField(name) => write!(f, "Missing field '{name}'"),
+            ProtoDeError::UnsupportedShape(shape) => {
+                write!(f, "Type {shape} can't be represented in proto3")
+            }
+            ProtoDeError::Reflect(err) => write!(f, "Reflection error: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for ProtoDeError<'_> {}
+
+/// Errors when encoding protobuf bytes or generating a `.proto` schema.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ProtoSerError {
+    /// The root value is not a struct.
+    RootNotAMessage,
+    /// A type can't be represented in proto3.
+    UnsupportedType(String),
+}
+
+impl core::fmt::Display for ProtoSerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ProtoSerError::RootNotAMessage => write!(f, "The root value must be a struct"),
+            ProtoSerError::UnsupportedType(ty) => {
+                write!(f, "Type {ty} can't be represented in proto3")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ProtoSerError {}
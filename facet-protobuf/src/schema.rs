@@ -0,0 +1,159 @@
+//! Generate `.proto` schemas from Facet shapes.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Write;
+use facet_core::{EnumType, Facet, Shape, StructType};
+
+use crate::{
+    ProtoSerError,
+    types::{ProtoType, field_tag},
+};
+
+/// Generates a proto3 `.proto` schema describing `T` and every message and enum it uses.
+///
+/// The schema matches what [`to_vec`](crate::to_vec) writes and
+/// [`from_slice`](crate::from_slice) reads.
+pub fn to_proto_schema<'facet, T: Facet<'facet>>(
+    package: Option<&str>,
+) -> Result<String, ProtoSerError> {
+    proto_schema_for_shape(T::SHAPE, package)
+}
+
+/// Generates a proto3 `.proto` schema for a shape. See [`to_proto_schema`].
+pub fn proto_schema_for_shape<'shape>(
+    shape: &'shape Shape<'shape>,
+    package: Option<&str>,
+) -> Result<String, ProtoSerError> {
+    let Some(ProtoType::Message(_)) = ProtoType::of(shape) else {
+        return Err(ProtoSerError::RootNotAMessage);
+    };
+
+    let mut out = String::from("syntax = \"proto3\";\n");
+    if let Some(package) = package {
+        writeln!(out, "\npackage {package};").unwrap();
+    }
+
+    let mut writer = SchemaWriter {
+        queue: vec![shape],
+    };
+    let mut next = 0;
+    while let Some(shape) = writer.queue.get(next).copied() {
+        next += 1;
+        out.push('\n');
+        write_doc(&mut out, shape.doc, "");
+        match ProtoType::of(shape) {
+            Some(ProtoType::Message(sd)) => writer.write_message(&mut out, shape, sd)?,
+            Some(ProtoType::Enum(ed)) => write_enum(&mut out, shape, ed),
+            _ => return Err(ProtoSerError::UnsupportedType(shape.to_string())),
+        }
+    }
+    Ok(out)
+}
+
+struct SchemaWriter<'shape> {
+    /// Messages and enums to define, in order of discovery.
+    queue: Vec<&'shape Shape<'shape>>,
+}
+
+impl<'shape> SchemaWriter<'shape> {
+    fn write_message(
+        &mut self,
+        out: &mut String,
+        shape: &'shape Shape<'shape>,
+        sd: StructType<'shape>,
+    ) -> Result<(), ProtoSerError> {
+        writeln!(out, "message {} {{", shape.type_identifier).unwrap();
+        for (index, field) in sd.fields.iter().enumerate() {
+            let field_shape = field.shape();
+            let unsupported = || ProtoSerError::UnsupportedType(field_shape.to_string());
+            let (label, ty) = match ProtoType::of(field_shape).ok_or_else(unsupported)? {
+                ProtoType::Optional(inner) => ("optional ", self.type_ref(inner)?),
+                ProtoType::Repeated(elem) => ("repeated ", self.type_ref(elem)?),
+                ProtoType::Map(k, v) => (
+                    "",
+                    format!("map<{}, {}>", self.type_ref(k)?, self.type_ref(v)?),
+                ),
+                _ => ("", self.type_ref(field_shape)?),
+            };
+            write_doc(out, field.doc, "  ");
+            writeln!(
+                out,
+                "  {label}{ty} {} = {};",
+                field.name,
+                field_tag(field, index)
+            )
+            .unwrap();
+        }
+        out.push_str("}\n");
+        Ok(())
+    }
+
+    /// The name of a type as it appears in a field declaration.
+    fn type_ref(&mut self, shape: &'shape Shape<'shape>) -> Result<String, ProtoSerError> {
+        let unsupported = || ProtoSerError::UnsupportedType(shape.to_string());
+        let name = match ProtoType::of(shape).ok_or_else(unsupported)? {
+            ProtoType::Bool => "bool",
+            ProtoType::UInt32 => "uint32",
+            ProtoType::UInt64 => "uint64",
+            ProtoType::SInt32 => "sint32",
+            ProtoType::SInt64 => "sint64",
+            ProtoType::Float => "float",
+            ProtoType::Double => "double",
+            ProtoType::String | ProtoType::Text => "string",
+            ProtoType::Bytes => "bytes",
+            ProtoType::Transparent(inner) => return self.type_ref(inner),
+            ProtoType::Message(_) | ProtoType::Enum(_) => {
+                if !self.queue.contains(&shape) {
+                    self.queue.push(shape);
+                }
+                return Ok(shape.type_identifier.to_string());
+            }
+            // proto3 has no nested `repeated`, `optional` or `map`
+            ProtoType::Optional(_) | ProtoType::Repeated(_) | ProtoType::Map(..) => {
+                return Err(unsupported());
+            }
+        };
+        Ok(name.to_string())
+    }
+}
+
+fn write_enum(out: &mut String, shape: &Shape<'_>, ed: EnumType<'_>) {
+    let prefix = screaming_snake_case(shape.type_identifier);
+    writeln!(out, "enum {} {{", shape.type_identifier).unwrap();
+    for (index, variant) in ed.variants.iter().enumerate() {
+        write_doc(out, variant.doc, "  ");
+        writeln!(
+            out,
+            "  {prefix}_{} = {};",
+            screaming_snake_case(variant.name),
+            variant.discriminant.unwrap_or(index as i64)
+        )
+        .unwrap();
+    }
+    out.push_str("}\n");
+}
+
+fn write_doc(out: &mut String, doc: &[&str], indent: &str) {
+    for line in doc {
+        writeln!(out, "{indent}//{line}").unwrap();
+    }
+}
+
+/// `HttpStatus` -> `HTTP_STATUS`, as proto style guides want for enum values.
+fn screaming_snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && prev_lower {
+            out.push('_');
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        out.extend(c.to_uppercase());
+    }
+    out
+}
@@ -0,0 +1,170 @@
+//! Encode Rust values as proto3 messages.
+
+use alloc::{format, string::ToString, vec::Vec};
+use facet_core::{Facet, Shape, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+
+use crate::{
+    ProtoSerError,
+    types::{ProtoType, field_tag},
+    wire::{write_key, write_len_delimited, write_varint, zigzag},
+};
+
+/// Encodes a Facet struct as a proto3 message.
+pub fn to_vec<'facet, T: Facet<'facet>>(value: &T) -> Result<Vec<u8>, ProtoSerError> {
+    let peek = Peek::new(value);
+    let Type::User(UserType::Struct(_)) = peek.shape().ty else {
+        return Err(ProtoSerError::RootNotAMessage);
+    };
+    let mut out = Vec::new();
+    write_message(peek, &mut out)?;
+    Ok(out)
+}
+
+fn unsupported(shape: &Shape<'_>) -> ProtoSerError {
+    ProtoSerError::UnsupportedType(shape.to_string())
+}
+
+fn write_message(peek: Peek<'_, '_, '_>, out: &mut Vec<u8>) -> Result<(), ProtoSerError> {
+    let ps = peek.into_struct().map_err(|_| unsupported(peek.shape()))?;
+    let fields = ps.ty().fields;
+    for (field, value) in ps.fields_for_serialize() {
+        let index = fields
+            .iter()
+            .position(|f| f.name == field.name)
+            .ok_or_else(|| unsupported(value.shape()))?;
+        write_field(field_tag(&field, index), value, out, true)?;
+    }
+    Ok(())
+}
+
+/// Writes one field. With `implicit_presence`, scalars holding their default value
+/// are left out, as proto3 does for fields that aren't `optional`.
+fn write_field(
+    tag: u32,
+    peek: Peek<'_, '_, '_>,
+    out: &mut Vec<u8>,
+    implicit_presence: bool,
+) -> Result<(), ProtoSerError> {
+    let shape = peek.shape();
+    let ty = ProtoType::of(shape).ok_or_else(|| unsupported(shape))?;
+
+    match ty {
+        ProtoType::Optional(_) => {
+            let opt = peek.into_option().map_err(|_| unsupported(shape))?;
+            if let Some(inner) = opt.value() {
+                write_field(tag, inner, out, false)?;
+            }
+        }
+        ProtoType::Transparent(_) => {
+            write_field(tag, peek.innermost_peek(), out, implicit_presence)?;
+        }
+        ProtoType::Repeated(elem) => {
+            let elem_ty = ProtoType::of(elem).ok_or_else(|| unsupported(elem))?;
+            let list = peek.into_list_like().map_err(|_| unsupported(shape))?;
+            if elem_ty.is_packable() {
+                let mut packed = Vec::new();
+                for item in list.iter() {
+                    scalar_payload(elem_ty, item)?.write(&mut packed);
+                }
+                if !packed.is_empty() {
+                    write_len_delimited(out, tag, &packed);
+                }
+            } else {
+                for item in list.iter() {
+                    write_field(tag, item, out, false)?;
+                }
+            }
+        }
+        ProtoType::Map(..) => {
+            let map = peek.into_map().map_err(|_| unsupported(shape))?;
+            for (key, value) in map.iter() {
+                let mut entry = Vec::new();
+                write_field(1, key, &mut entry, false)?;
+                write_field(2, value, &mut entry, false)?;
+                write_len_delimited(out, tag, &entry);
+            }
+        }
+        ProtoType::Message(_) => {
+            let mut message = Vec::new();
+            write_message(peek, &mut message)?;
+            write_len_delimited(out, tag, &message);
+        }
+        _ => {
+            let payload = scalar_payload(ty, peek)?;
+            if implicit_presence && payload.is_default() {
+                return Ok(());
+            }
+            write_key(out, tag, ty.wire_type());
+            payload.write(out);
+        }
+    }
+    Ok(())
+}
+
+/// The encoded form of a single scalar, without its key.
+enum Payload {
+    Varint(u64),
+    Fixed32(u32),
+    Fixed64(u64),
+    Bytes(Vec<u8>),
+}
+
+impl Payload {
+    fn is_default(&self) -> bool {
+        match self {
+            Payload::Varint(v) | Payload::Fixed64(v) => *v == 0,
+            Payload::Fixed32(v) => *v == 0,
+            Payload::Bytes(b) => b.is_empty(),
+        }
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            Payload::Varint(v) => write_varint(out, *v),
+            Payload::Fixed32(v) => out.extend_from_slice(&v.to_le_bytes()),
+            Payload::Fixed64(v) => out.extend_from_slice(&v.to_le_bytes()),
+            Payload::Bytes(b) => {
+                write_varint(out, b.len() as u64);
+                out.extend_from_slice(b);
+            }
+        }
+    }
+}
+
+fn scalar_payload(ty: ProtoType<'_>, peek: Peek<'_, '_, '_>) -> Result<Payload, ProtoSerError> {
+    let shape = peek.shape();
+    let err = |_| unsupported(shape);
+
+    Ok(match ty {
+        ProtoType::Bool => Payload::Varint(*peek.get::<bool>().map_err(err)? as u64),
+        ProtoType::UInt32 | ProtoType::UInt64 => Payload::Varint(match peek.scalar_type() {
+            Some(ScalarType::U8) => *peek.get::<u8>().map_err(err)? as u64,
+            Some(ScalarType::U16) => *peek.get::<u16>().map_err(err)? as u64,
+            Some(ScalarType::U32) => *peek.get::<u32>().map_err(err)? as u64,
+            Some(ScalarType::U64) => *peek.get::<u64>().map_err(err)?,
+            Some(ScalarType::USize) => *peek.get::<usize>().map_err(err)? as u64,
+            _ => return Err(unsupported(shape)),
+        }),
+        ProtoType::SInt32 | ProtoType::SInt64 => Payload::Varint(zigzag(match peek.scalar_type() {
+            Some(ScalarType::I8) => *peek.get::<i8>().map_err(err)? as i64,
+            Some(ScalarType::I16) => *peek.get::<i16>().map_err(err)? as i64,
+            Some(ScalarType::I32) => *peek.get::<i32>().map_err(err)? as i64,
+            Some(ScalarType::I64) => *peek.get::<i64>().map_err(err)?,
+            Some(ScalarType::ISize) => *peek.get::<isize>().map_err(err)? as i64,
+            _ => return Err(unsupported(shape)),
+        })),
+        ProtoType::Float => Payload::Fixed32(peek.get::<f32>().map_err(err)?.to_bits()),
+        ProtoType::Double => Payload::Fixed64(peek.get::<f64>().map_err(err)?.to_bits()),
+        ProtoType::Bytes => Payload::Bytes(peek.get::<Vec<u8>>().map_err(err)?.clone()),
+        ProtoType::String | ProtoType::Text => Payload::Bytes(match peek.as_str() {
+            Some(s) => s.as_bytes().to_vec(),
+            None => format!("{peek}").into_bytes(),
+        }),
+        ProtoType::Enum(_) => {
+            let pe = peek.into_enum().map_err(err)?;
+            Payload::Varint(pe.discriminant() as u64)
+        }
+        _ => return Err(unsupported(shape)),
+    })
+}
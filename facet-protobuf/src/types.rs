@@ -0,0 +1,120 @@
+//! Mapping from Facet shapes to proto3 types and field numbers.
+
+use facet_core::{
+    Def, EnumType, Field, FieldAttribute, Shape, StructKind, StructType, Type, UserType,
+};
+use facet_reflect::ScalarType;
+
+use crate::wire::{FIXED32, FIXED64, LEN, VARINT};
+
+/// How a shape is represented in proto3.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ProtoType<'shape> {
+    Bool,
+    UInt32,
+    UInt64,
+    SInt32,
+    SInt64,
+    Float,
+    Double,
+    String,
+    Bytes,
+    /// Any other scalar, written as a string through `Display` and read back with `FromStr`.
+    Text,
+    Enum(EnumType<'shape>),
+    Message(StructType<'shape>),
+    Optional(&'shape Shape<'shape>),
+    Repeated(&'shape Shape<'shape>),
+    Map(&'shape Shape<'shape>, &'shape Shape<'shape>),
+    /// A transparent wrapper around another shape.
+    Transparent(&'shape Shape<'shape>),
+}
+
+impl ProtoType<'_> {
+    pub(crate) fn of<'shape>(shape: &'shape Shape<'shape>) -> Option<ProtoType<'shape>> {
+        if shape.is_type::<alloc::vec::Vec<u8>>() {
+            return Some(ProtoType::Bytes);
+        }
+
+        match shape.def {
+            Def::Option(od) => return Some(ProtoType::Optional(od.t())),
+            Def::List(ld) => return Some(ProtoType::Repeated(ld.t())),
+            Def::Map(md) => return Some(ProtoType::Map(md.k(), md.v())),
+            _ => {}
+        }
+
+        if let Some(scalar) = ScalarType::try_from_shape(shape) {
+            let ty = match scalar {
+                ScalarType::Bool => ProtoType::Bool,
+                ScalarType::U8 | ScalarType::U16 | ScalarType::U32 => ProtoType::UInt32,
+                ScalarType::U64 | ScalarType::USize => ProtoType::UInt64,
+                ScalarType::I8 | ScalarType::I16 | ScalarType::I32 => ProtoType::SInt32,
+                ScalarType::I64 | ScalarType::ISize => ProtoType::SInt64,
+                ScalarType::F32 => ProtoType::Float,
+                ScalarType::F64 => ProtoType::Double,
+                ScalarType::String | ScalarType::CowStr => ProtoType::String,
+                _ if shape.is_display() && shape.is_from_str() => ProtoType::Text,
+                _ => return None,
+            };
+            return Some(ty);
+        }
+
+        if let Some(inner) = shape.inner {
+            return Some(ProtoType::Transparent(inner()));
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(sd)) => Some(ProtoType::Message(sd)),
+            Type::User(UserType::Enum(ed))
+                if ed.variants.iter().all(|v| v.data.kind == StructKind::Unit) =>
+            {
+                Some(ProtoType::Enum(ed))
+            }
+            _ if shape.is_display() && shape.is_from_str() => Some(ProtoType::Text),
+            _ => None,
+        }
+    }
+
+    /// The wire type used for a single value of this type.
+    pub(crate) fn wire_type(&self) -> u8 {
+        match self {
+            ProtoType::Bool
+            | ProtoType::UInt32
+            | ProtoType::UInt64
+            | ProtoType::SInt32
+            | ProtoType::SInt64
+            | ProtoType::Enum(_) => VARINT,
+            ProtoType::Float => FIXED32,
+            ProtoType::Double => FIXED64,
+            _ => LEN,
+        }
+    }
+
+    /// Whether repeated values of this type use the packed encoding.
+    pub(crate) fn is_packable(&self) -> bool {
+        self.wire_type() != LEN
+    }
+}
+
+/// Returns the protobuf field number of a struct field.
+///
+/// This is the `N` from `#[facet(proto(tag = N))]` if present, and `index + 1` otherwise,
+/// where `index` is the position of the field in its struct.
+pub fn field_tag(field: &Field<'_>, index: usize) -> u32 {
+    field
+        .attributes
+        .iter()
+        .find_map(|attr| match attr {
+            FieldAttribute::Arbitrary(content) => parse_proto_tag(content),
+            _ => None,
+        })
+        .unwrap_or(index as u32 + 1)
+}
+
+/// Parses the tag out of `proto(tag = N)`, ignoring whitespace.
+fn parse_proto_tag(content: &str) -> Option<u32> {
+    let compact: alloc::string::String = content.chars().filter(|c| !c.is_whitespace()).collect();
+    let args = compact.strip_prefix("proto(")?.strip_suffix(')')?;
+    args.split(',')
+        .find_map(|arg| arg.strip_prefix("tag=")?.parse().ok())
+}
@@ -0,0 +1,137 @@
+//! Low-level proto3 wire format primitives.
+
+use alloc::vec::Vec;
+
+use crate::ProtoDeError;
+
+pub(crate) const VARINT: u8 = 0;
+pub(crate) const FIXED64: u8 = 1;
+pub(crate) const LEN: u8 = 2;
+pub(crate) const FIXED32: u8 = 5;
+
+/// A decoded field payload, before it's matched against a shape.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum WireValue<'input> {
+    Varint(u64),
+    Fixed64(u64),
+    Fixed32(u32),
+    Bytes(&'input [u8]),
+}
+
+impl WireValue<'_> {
+    pub(crate) fn wire_type(&self) -> u8 {
+        match self {
+            WireValue::Varint(_) => VARINT,
+            WireValue::Fixed64(_) => FIXED64,
+            WireValue::Fixed32(_) => FIXED32,
+            WireValue::Bytes(_) => LEN,
+        }
+    }
+}
+
+pub(crate) fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+pub(crate) fn write_key(out: &mut Vec<u8>, tag: u32, wire_type: u8) {
+    write_varint(out, ((tag as u64) << 3) | wire_type as u64);
+}
+
+pub(crate) fn write_len_delimited(out: &mut Vec<u8>, tag: u32, bytes: &[u8]) {
+    write_key(out, tag, LEN);
+    write_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+pub(crate) fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+pub(crate) fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+/// Reads records out of an encoded message.
+pub(crate) struct Reader<'input> {
+    input: &'input [u8],
+    pos: usize,
+}
+
+impl<'input> Reader<'input> {
+    pub(crate) fn new(input: &'input [u8]) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    pub(crate) fn read_varint<'shape>(&mut self) -> Result<u64, ProtoDeError<'shape>> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .input
+                .get(self.pos)
+                .ok_or(ProtoDeError::UnexpectedEof)?;
+            self.pos += 1;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(ProtoDeError::VarintOverflow)
+    }
+
+    fn read_exact<'shape>(&mut self, len: usize) -> Result<&'input [u8], ProtoDeError<'shape>> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.input.len())
+            .ok_or(ProtoDeError::UnexpectedEof)?;
+        let bytes = &self.input[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    pub(crate) fn read_fixed32<'shape>(&mut self) -> Result<u32, ProtoDeError<'shape>> {
+        let bytes = self.read_exact(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub(crate) fn read_fixed64<'shape>(&mut self) -> Result<u64, ProtoDeError<'shape>> {
+        let bytes = self.read_exact(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Reads one payload of the given wire type.
+    pub(crate) fn read_value<'shape>(
+        &mut self,
+        wire_type: u8,
+    ) -> Result<WireValue<'input>, ProtoDeError<'shape>> {
+        Ok(match wire_type {
+            VARINT => WireValue::Varint(self.read_varint()?),
+            FIXED64 => WireValue::Fixed64(self.read_fixed64()?),
+            FIXED32 => WireValue::Fixed32(self.read_fixed32()?),
+            LEN => {
+                let len = self.read_varint()?;
+                let len = usize::try_from(len).map_err(|_| ProtoDeError::UnexpectedEof)?;
+                WireValue::Bytes(self.read_exact(len)?)
+            }
+            other => return Err(ProtoDeError::InvalidWireType(other)),
+        })
+    }
+
+    /// Reads a field key and its payload.
+    pub(crate) fn read_record<'shape>(
+        &mut self,
+    ) -> Result<(u32, WireValue<'input>), ProtoDeError<'shape>> {
+        let key = self.read_varint()?;
+        let tag = u32::try_from(key >> 3).map_err(|_| ProtoDeError::VarintOverflow)?;
+        let value = self.read_value((key & 0x7) as u8)?;
+        Ok((tag, value))
+    }
+}
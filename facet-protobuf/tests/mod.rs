@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_protobuf::{ProtoDeError, from_slice, to_proto_schema, to_vec};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet, PartialEq)]
+struct SearchRequest {
+    #[facet(proto(tag = 1))]
+    query: String,
+    #[facet(proto(tag = 2))]
+    page_number: i32,
+    #[facet(proto(tag = 3))]
+    results_per_page: u32,
+}
+
+#[test]
+fn test_encode_matches_protoc() {
+    let request = SearchRequest {
+        query: "facet".to_string(),
+        page_number: -2,
+        results_per_page: 150,
+    };
+    // query: 0a 05 "facet", page_number: zigzag(-2) = 3, results_per_page: varint 150
+    let expected = [
+        0x0a, 0x05, b'f', b'a', b'c', b'e', b't', 0x10, 0x03, 0x18, 0x96, 0x01,
+    ];
+    assert_eq!(to_vec(&request)?, expected);
+    assert_eq!(from_slice::<SearchRequest>(&expected)?, request);
+}
+
+#[test]
+fn test_default_values_are_omitted() {
+    let request = SearchRequest {
+        query: String::new(),
+        page_number: 0,
+        results_per_page: 0,
+    };
+    assert!(to_vec(&request)?.is_empty());
+    assert_eq!(from_slice::<SearchRequest>(&[])?, request);
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+enum Corpus {
+    Universal = 0,
+    Web = 1,
+    Images = 2,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Result_ {
+    #[facet(proto(tag = 1))]
+    url: String,
+    #[facet(proto(tag = 2))]
+    snippets: Vec<String>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct SearchResponse {
+    #[facet(proto(tag = 1))]
+    results: Vec<Result_>,
+    #[facet(proto(tag = 4))]
+    scores: Vec<f32>,
+    #[facet(proto(tag = 5))]
+    corpus: Corpus,
+    #[facet(proto(tag = 6))]
+    next_page: Option<u64>,
+    #[facet(proto(tag = 7))]
+    headers: HashMap<String, i64>,
+    #[facet(proto(tag = 8))]
+    raw: Vec<u8>,
+}
+
+fn response() -> SearchResponse {
+    SearchResponse {
+        results: vec![
+            Result_ {
+                url: "https://facet.rs".to_string(),
+                snippets: vec!["reflection".to_string(), "for rust".to_string()],
+            },
+            Result_ {
+                url: "https://docs.rs/facet".to_string(),
+                snippets: vec![],
+            },
+        ],
+        scores: vec![0.5, 1.25],
+        corpus: Corpus::Web,
+        next_page: Some(0),
+        headers: HashMap::from([("x-count".to_string(), -7)]),
+        raw: vec![0, 1, 2, 255],
+    }
+}
+
+#[test]
+fn test_roundtrip_nested() {
+    let bytes = to_vec(&response())?;
+    let decoded: SearchResponse = from_slice(&bytes)?;
+    assert_eq!(decoded, response());
+
+    // `Universal` is the default value, so it's omitted and read back as such
+    for corpus in [Corpus::Universal, Corpus::Images] {
+        let response = SearchResponse {
+            corpus,
+            ..response()
+        };
+        let bytes = to_vec(&response)?;
+        assert_eq!(from_slice::<SearchResponse>(&bytes)?, response);
+    }
+}
+
+#[test]
+fn test_packed_repeated_scalars() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Packed {
+        #[facet(proto(tag = 4))]
+        values: Vec<u32>,
+    }
+
+    let bytes = to_vec(&Packed {
+        values: vec![3, 270, 86942],
+    })?;
+    assert_eq!(bytes, [0x22, 0x06, 0x03, 0x8e, 0x02, 0x9e, 0xa7, 0x05]);
+
+    // Unpacked encoding of the same field must be accepted too
+    let unpacked = [0x20, 0x03, 0x20, 0x8e, 0x02, 0x20, 0x9e, 0xa7, 0x05];
+    assert_eq!(from_slice::<Packed>(&unpacked)?.values, vec![3, 270, 86942]);
+}
+
+#[test]
+fn test_unknown_fields_are_skipped() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Small {
+        #[facet(proto(tag = 1))]
+        query: String,
+    }
+
+    let bytes = to_vec(&SearchRequest {
+        query: "q".to_string(),
+        page_number: 1,
+        results_per_page: 2,
+    })?;
+    assert_eq!(from_slice::<Small>(&bytes)?.query, "q");
+}
+
+#[test]
+fn test_tags_default_to_position() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Untagged {
+        a: u32,
+        b: bool,
+    }
+
+    let bytes = to_vec(&Untagged { a: 1, b: true })?;
+    assert_eq!(bytes, [0x08, 0x01, 0x10, 0x01]);
+}
+
+#[test]
+fn test_wrong_wire_type() {
+    let err = from_slice::<SearchRequest>(&[0x08, 0x01]).unwrap_err();
+    assert!(matches!(
+        err,
+        ProtoDeError::WrongWireType {
+            tag: 1,
+            expected: 2,
+            got: 0
+        }
+    ));
+}
+
+#[test]
+fn test_truncated_input() {
+    let err = from_slice::<SearchRequest>(&[0x0a, 0x05, b'f']).unwrap_err();
+    assert!(matches!(err, ProtoDeError::UnexpectedEof));
+}
+
+#[test]
+fn test_schema() {
+    let schema = to_proto_schema::<SearchResponse>(Some("search.v1"))?;
+    assert_eq!(
+        schema,
+        r#"syntax = "proto3";
+
+package search.v1;
+
+message SearchResponse {
+  repeated Result_ results = 1;
+  repeated float scores = 4;
+  Corpus corpus = 5;
+  optional uint64 next_page = 6;
+  map<string, sint64> headers = 7;
+  bytes raw = 8;
+}
+
+message Result_ {
+  string url = 1;
+  repeated string snippets = 2;
+}
+
+enum Corpus {
+  CORPUS_UNIVERSAL = 0;
+  CORPUS_WEB = 1;
+  CORPUS_IMAGES = 2;
+}
+"#
+    );
+}
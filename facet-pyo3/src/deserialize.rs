@@ -0,0 +1,394 @@
+//! Convert Python objects into Rust values.
+
+use std::borrow::Cow;
+
+use facet_core::{Def, Facet, Field, FieldFlags, StructKind, Type, UserType};
+use facet_reflect::{Partial, ScalarType};
+use log::trace;
+use pyo3::prelude::*;
+use pyo3::types::{
+    PyBool, PyByteArray, PyBytes, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple,
+};
+
+use crate::PyDeError;
+
+/// Converts a Python object into a value of type `T`.
+///
+/// This reads what [`to_py`](crate::to_py) writes, and a little more: lists and tuples
+/// can stand for each other, `bytearray`s can be read as `Vec<u8>`, and integers can be
+/// read as floats.
+pub fn from_py<'facet: 'shape, 'shape, T: Facet<'facet>>(
+    object: &Bound<'_, PyAny>,
+) -> Result<T, PyDeError<'shape>> {
+    let mut partial = Partial::alloc::<T>()?;
+    deserialize_object(partial.inner_mut(), object)?;
+    let value = partial.build()?;
+    Ok(*value)
+}
+
+/// The name of the Python type of an object, as it's named in errors.
+fn kind(object: &Bound<'_, PyAny>) -> String {
+    object
+        .get_type()
+        .name()
+        .map_or_else(|_| "object".to_string(), |name| name.to_string())
+}
+
+fn mismatch<'shape>(object: &Bound<'_, PyAny>, wip: &Partial<'_, 'shape>) -> PyDeError<'shape> {
+    PyDeError::TypeMismatch {
+        found: kind(object),
+        shape: wip.shape(),
+    }
+}
+
+/// The items of a list or a tuple, or `None` for anything else.
+fn items<'py>(object: &Bound<'py, PyAny>) -> Option<Vec<Bound<'py, PyAny>>> {
+    if let Ok(list) = object.downcast::<PyList>() {
+        return Some(list.iter().collect());
+    }
+    if let Ok(tuple) = object.downcast::<PyTuple>() {
+        return Some(tuple.iter().collect());
+    }
+    None
+}
+
+fn deserialize_object<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    object: &Bound<'_, PyAny>,
+) -> Result<(), PyDeError<'shape>> {
+    let shape = wip.shape();
+    trace!("Converting Python {} into {}", kind(object), shape);
+
+    if shape.is_type::<Vec<u8>>() {
+        if let Ok(bytes) = object.downcast::<PyBytes>() {
+            wip.set(bytes.as_bytes().to_vec())?;
+            return Ok(());
+        }
+        if let Ok(bytes) = object.downcast::<PyByteArray>() {
+            wip.set(bytes.to_vec())?;
+            return Ok(());
+        }
+    }
+
+    match shape.def {
+        Def::Option(_) => {
+            if object.is_none() {
+                wip.set_default()?;
+            } else {
+                wip.begin_some()?;
+                deserialize_object(wip, object)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::List(_) => {
+            let Some(items) = items(object) else {
+                return Err(mismatch(object, wip));
+            };
+            wip.begin_list()?;
+            for item in items {
+                wip.begin_list_item()?;
+                deserialize_object(wip, &item)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::Array(ad) => {
+            let Some(items) = items(object) else {
+                return Err(mismatch(object, wip));
+            };
+            if items.len() != ad.n {
+                return Err(mismatch(object, wip));
+            }
+            for (index, item) in items.into_iter().enumerate() {
+                wip.begin_nth_element(index)?;
+                deserialize_object(wip, &item)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::Map(_) => {
+            let Ok(dict) = object.downcast::<PyDict>() else {
+                return Err(mismatch(object, wip));
+            };
+            wip.begin_map()?;
+            for (key, value) in dict.iter() {
+                wip.begin_key()?;
+                deserialize_object(wip, &key)?;
+                wip.end()?;
+                wip.begin_value()?;
+                deserialize_object(wip, &value)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    if ScalarType::try_from_shape(shape).is_some() {
+        return deserialize_scalar(wip, object);
+    }
+
+    if shape.inner.is_some() {
+        wip.begin_inner()?;
+        deserialize_object(wip, object)?;
+        wip.end()?;
+        return Ok(());
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => match sd.kind {
+            StructKind::Tuple | StructKind::TupleStruct => {
+                let Some(items) = items(object) else {
+                    return Err(mismatch(object, wip));
+                };
+                deserialize_tuple(wip, sd.fields, object, items)
+            }
+            StructKind::Struct | StructKind::Unit => {
+                let Ok(dict) = object.downcast::<PyDict>() else {
+                    return Err(mismatch(object, wip));
+                };
+                deserialize_fields(wip, sd.fields, dict)
+            }
+            _ => Err(PyDeError::UnsupportedShape(shape)),
+        },
+        Type::User(UserType::Enum(_)) => deserialize_enum(wip, object),
+        _ if shape.is_from_str() => deserialize_scalar(wip, object),
+        _ => Err(PyDeError::UnsupportedShape(shape)),
+    }
+}
+
+fn deserialize_tuple<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    object: &Bound<'_, PyAny>,
+    items: Vec<Bound<'_, PyAny>>,
+) -> Result<(), PyDeError<'shape>> {
+    if items.len() != fields.len() {
+        return Err(mismatch(object, wip));
+    }
+    for (index, item) in items.into_iter().enumerate() {
+        wip.begin_nth_field(index)?;
+        deserialize_object(wip, &item)?;
+        wip.end()?;
+    }
+    Ok(())
+}
+
+/// Fills the fields of a struct or of the selected enum variant from a dict.
+fn deserialize_fields<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    dict: &Bound<'_, PyDict>,
+) -> Result<(), PyDeError<'shape>> {
+    let shape = wip.shape();
+    for (key, value) in dict.iter() {
+        let Ok(key) = key.extract::<String>() else {
+            return Err(mismatch(&key, wip));
+        };
+        let Some(index) = fields.iter().position(|f| f.name == key) else {
+            if shape.has_deny_unknown_fields_attr() {
+                return Err(PyDeError::UnknownField { name: key, shape });
+            }
+            trace!("Skipping unknown key '{key}' of {shape}");
+            continue;
+        };
+        wip.begin_nth_field(index)?;
+        deserialize_object(wip, &value)?;
+        wip.end()?;
+    }
+
+    if let Type::User(UserType::Struct(_)) = shape.ty {
+        wip.fill_unset_fields_from_default()?;
+    }
+    for (index, field) in fields.iter().enumerate() {
+        if !wip.is_field_set(index)? {
+            default_field(wip, index, field)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fills a field that has no key in the dict: `None` for options, and the default for
+/// fields with `#[facet(default)]`.
+fn default_field<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    index: usize,
+    field: &Field<'shape>,
+) -> Result<(), PyDeError<'shape>> {
+    let has_default = field.flags.contains(FieldFlags::DEFAULT);
+    if !has_default && !matches!(field.shape().def, Def::Option(_)) {
+        return Err(PyDeError::MissingField(field.name));
+    }
+
+    wip.begin_nth_field(index)?;
+    match field.vtable.default_fn {
+        Some(default_fn) if has_default => wip.set_field_default(default_fn)?,
+        _ => wip.set_default()?,
+    };
+    wip.end()?;
+    Ok(())
+}
+
+/// Enums are externally tagged: unit variants are strings, and other variants are a
+/// dict with the variant name as its only key.
+fn deserialize_enum<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    object: &Bound<'_, PyAny>,
+) -> Result<(), PyDeError<'shape>> {
+    let shape = wip.shape();
+    let (name, content) = if let Ok(name) = object.downcast::<PyString>() {
+        (name.to_cow()?.into_owned(), None)
+    } else {
+        match object.downcast::<PyDict>() {
+            Ok(dict) if dict.len() == 1 => {
+                let Some((name, content)) = dict.iter().next() else {
+                    return Err(mismatch(object, wip));
+                };
+                let Ok(name) = name.extract::<String>() else {
+                    return Err(mismatch(&name, wip));
+                };
+                (name, Some(content))
+            }
+            _ => return Err(mismatch(object, wip)),
+        }
+    };
+
+    let Some((_, variant)) = wip.find_variant(&name) else {
+        return Err(PyDeError::UnknownVariant { name, shape });
+    };
+    let fields = variant.data.fields;
+    let kind = variant.data.kind;
+    wip.select_variant_named(&name)?;
+
+    match (kind, content) {
+        (StructKind::Unit, None) => Ok(()),
+        (StructKind::Struct, Some(content)) => match content.downcast::<PyDict>() {
+            Ok(dict) => deserialize_fields(wip, fields, dict),
+            Err(_) => Err(mismatch(&content, wip)),
+        },
+        (StructKind::Tuple | StructKind::TupleStruct, Some(content)) if fields.len() == 1 => {
+            wip.begin_nth_field(0)?;
+            deserialize_object(wip, &content)?;
+            wip.end()?;
+            Ok(())
+        }
+        (StructKind::Tuple | StructKind::TupleStruct, Some(content)) => match items(&content) {
+            Some(items) => deserialize_tuple(wip, fields, &content, items),
+            None => Err(mismatch(&content, wip)),
+        },
+        (_, Some(content)) => Err(mismatch(&content, wip)),
+        (_, None) => Err(mismatch(object, wip)),
+    }
+}
+
+/// An integer from an `int`; `bool`s are `int`s in Python, but not here.
+fn integer<'shape>(
+    object: &Bound<'_, PyAny>,
+    wip: &Partial<'_, 'shape>,
+) -> Result<i128, PyDeError<'shape>> {
+    if !object.is_instance_of::<PyInt>() || object.is_instance_of::<PyBool>() {
+        return Err(mismatch(object, wip));
+    }
+    object
+        .extract::<i128>()
+        .map_err(|_| PyDeError::NumberOutOfRange(wip.shape()))
+}
+
+fn deserialize_scalar<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    object: &Bound<'_, PyAny>,
+) -> Result<(), PyDeError<'shape>> {
+    let shape = wip.shape();
+    let out_of_range = |_| PyDeError::NumberOutOfRange(shape);
+
+    // Strings go through `FromStr` for any type that has it
+    if let Ok(s) = object.downcast::<PyString>() {
+        let s = s.to_cow()?;
+        return match ScalarType::try_from_shape(shape) {
+            Some(ScalarType::String) => {
+                wip.set(s.to_string())?;
+                Ok(())
+            }
+            Some(ScalarType::CowStr) => {
+                wip.set(Cow::<'static, str>::Owned(s.to_string()))?;
+                Ok(())
+            }
+            _ if shape.is_from_str() => {
+                wip.parse_from_str(&s)
+                    .map_err(|_| PyDeError::InvalidValue(shape))?;
+                Ok(())
+            }
+            _ => Err(mismatch(object, wip)),
+        };
+    }
+
+    match ScalarType::try_from_shape(shape) {
+        Some(ScalarType::Unit) if object.is_none() => {
+            wip.set(())?;
+        }
+        Some(ScalarType::Bool) => {
+            let Ok(b) = object.downcast::<PyBool>() else {
+                return Err(mismatch(object, wip));
+            };
+            wip.set(b.is_true())?;
+        }
+        Some(ScalarType::F32 | ScalarType::F64) => {
+            let n = if object.is_instance_of::<PyFloat>() {
+                object.extract::<f64>()?
+            } else {
+                integer(object, wip)? as f64
+            };
+            if shape.is_type::<f32>() {
+                wip.set(n as f32)?;
+            } else {
+                wip.set(n)?;
+            }
+        }
+        Some(ScalarType::U8) => {
+            wip.set(u8::try_from(integer(object, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::U16) => {
+            wip.set(u16::try_from(integer(object, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::U32) => {
+            wip.set(u32::try_from(integer(object, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::U64) => {
+            wip.set(u64::try_from(integer(object, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::USize) => {
+            wip.set(usize::try_from(integer(object, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::U128) => {
+            // Past `i128::MAX`, so not through `integer`
+            if !object.is_instance_of::<PyInt>() || object.is_instance_of::<PyBool>() {
+                return Err(mismatch(object, wip));
+            }
+            let n = object
+                .extract::<u128>()
+                .map_err(|_| PyDeError::NumberOutOfRange(shape))?;
+            wip.set(n)?;
+        }
+        Some(ScalarType::I8) => {
+            wip.set(i8::try_from(integer(object, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::I16) => {
+            wip.set(i16::try_from(integer(object, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::I32) => {
+            wip.set(i32::try_from(integer(object, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::I64) => {
+            wip.set(i64::try_from(integer(object, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::ISize) => {
+            wip.set(isize::try_from(integer(object, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::I128) => {
+            wip.set(integer(object, wip)?)?;
+        }
+        _ => return Err(mismatch(object, wip)),
+    }
+    Ok(())
+}
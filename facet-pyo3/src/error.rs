@@ -0,0 +1,138 @@
+//! Errors from converting to and from Python objects.
+
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+use pyo3::PyErr;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+
+/// Errors when converting a Python object into a Rust value.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PyDeError<'shape> {
+    /// An object has a different type than the Rust type expects.
+    TypeMismatch {
+        /// The Python type found, such as `"dict"` or `"int"`.
+        found: String,
+        /// The Rust type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A dict has a key that doesn't match any field, and the struct denies unknown fields.
+    UnknownField {
+        /// The key.
+        name: String,
+        /// The struct type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A string or dict key doesn't match any variant of the enum.
+    UnknownVariant {
+        /// The variant name found.
+        name: String,
+        /// The enum type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A field is missing and its type has no default value.
+    MissingField(&'shape str),
+    /// A number doesn't fit the Rust type it's converted into.
+    NumberOutOfRange(&'shape Shape<'shape>),
+    /// A string couldn't be parsed into the Rust type.
+    InvalidValue(&'shape Shape<'shape>),
+    /// The Rust type can't be converted from a Python object.
+    UnsupportedShape(&'shape Shape<'shape>),
+    /// Python raised an exception while the object was read.
+    Python(PyErr),
+    /// Any error from facet.
+    Reflect(ReflectError<'shape>),
+}
+
+impl<'shape> From<ReflectError<'shape>> for PyDeError<'shape> {
+    fn from(err: ReflectError<'shape>) -> Self {
+        PyDeError::Reflect(err)
+    }
+}
+
+impl From<PyErr> for PyDeError<'_> {
+    fn from(err: PyErr) -> Self {
+        PyDeError::Python(err)
+    }
+}
+
+impl core::fmt::Display for PyDeError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PyDeError::TypeMismatch { found, shape } => {
+                write!(f, "Expected {shape}, found a Python {found}")
+            }
+            PyDeError::UnknownField { name, shape } => {
+                write!(f, "Unknown key '{name}' for {shape}")
+            }
+            PyDeError::UnknownVariant { name, shape } => {
+                write!(f, "'{name}' is not a variant of {shape}")
+            }
+            PyDeError::MissingField(name) => write!(f, "Missing field '{name}'"),
+            PyDeError::NumberOutOfRange(shape) => {
+                write!(f, "Number out of range for {shape}")
+            }
+            PyDeError::InvalidValue(shape) => write!(f, "Can't parse string as {shape}"),
+            PyDeError::UnsupportedShape(shape) => {
+                write!(f, "Type {shape} can't be converted from a Python object")
+            }
+            PyDeError::Python(err) => write!(f, "Python error: {err}"),
+            PyDeError::Reflect(err) => write!(f, "Reflection error: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for PyDeError<'_> {}
+
+/// Raised as a `TypeError` for objects of the wrong type, and as a `ValueError` for
+/// objects of the right type with the wrong value, so that `?` works in `#[pyfunction]`s.
+impl From<PyDeError<'_>> for PyErr {
+    fn from(err: PyDeError<'_>) -> Self {
+        match err {
+            PyDeError::Python(err) => err,
+            PyDeError::TypeMismatch { .. } | PyDeError::UnsupportedShape(_) => {
+                PyTypeError::new_err(err.to_string())
+            }
+            _ => PyValueError::new_err(err.to_string()),
+        }
+    }
+}
+
+/// Errors when converting a Rust value into a Python object.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PySerError {
+    /// A type can't be represented as a Python object.
+    UnsupportedType(String),
+    /// Python raised an exception while the object was built.
+    Python(PyErr),
+}
+
+impl From<PyErr> for PySerError {
+    fn from(err: PyErr) -> Self {
+        PySerError::Python(err)
+    }
+}
+
+impl core::fmt::Display for PySerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PySerError::UnsupportedType(ty) => {
+                write!(f, "Type {ty} can't be converted to a Python object")
+            }
+            PySerError::Python(err) => write!(f, "Python error: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for PySerError {}
+
+/// Raised as a `TypeError`, like [`PyDeError`].
+impl From<PySerError> for PyErr {
+    fn from(err: PySerError) -> Self {
+        match err {
+            PySerError::Python(err) => err,
+            PySerError::UnsupportedType(_) => PyTypeError::new_err(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Convert Rust values into Python objects.
+
+use facet_core::{Def, Facet, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+use pyo3::IntoPyObjectExt;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyList, PyTuple};
+
+use crate::PySerError;
+
+/// Converts a value into a Python object.
+///
+/// Structs and maps become dicts, lists become lists, tuples become tuples and `Vec<u8>`
+/// becomes `bytes`. `None` is `None`, and integers of any width are `int`s.
+pub fn to_py<'py, 'facet, T: Facet<'facet>>(
+    py: Python<'py>,
+    value: &T,
+) -> Result<Bound<'py, PyAny>, PySerError> {
+    to_object(py, Peek::new(value))
+}
+
+fn unsupported(peek: Peek<'_, '_, '_>) -> PySerError {
+    PySerError::UnsupportedType(peek.shape().to_string())
+}
+
+/// Converts a value to a Python string through `as_str` or, failing that, `Display`.
+fn text<'py>(py: Python<'py>, peek: Peek<'_, '_, '_>) -> Result<Bound<'py, PyAny>, PySerError> {
+    let text = peek.as_text().ok_or_else(|| unsupported(peek))?;
+    Ok(text.as_ref().into_bound_py_any(py)?)
+}
+
+fn to_object<'py>(
+    py: Python<'py>,
+    peek: Peek<'_, '_, '_>,
+) -> Result<Bound<'py, PyAny>, PySerError> {
+    let shape = peek.shape();
+    let err = |_| unsupported(peek);
+
+    if let Ok(data) = peek.get::<Vec<u8>>() {
+        return Ok(PyBytes::new(py, data).into_any());
+    }
+
+    match shape.def {
+        Def::Option(_) => {
+            let opt = peek.into_option().map_err(err)?;
+            return match opt.value() {
+                Some(inner) => to_object(py, inner),
+                None => Ok(py.None().into_bound(py)),
+            };
+        }
+        Def::List(_) | Def::Array(_) | Def::Slice(_) => {
+            let list = peek.into_list_like().map_err(err)?;
+            let items = PyList::empty(py);
+            for item in list.iter() {
+                items.append(to_object(py, item)?)?;
+            }
+            return Ok(items.into_any());
+        }
+        Def::Map(_) => {
+            let map = peek.into_map().map_err(err)?;
+            let dict = PyDict::new(py);
+            for (key, value) in map.iter() {
+                dict.set_item(to_object(py, key)?, to_object(py, value)?)?;
+            }
+            return Ok(dict.into_any());
+        }
+        _ => {}
+    }
+
+    if let Some(scalar) = peek.scalar_type() {
+        let object = match scalar {
+            ScalarType::Unit => py.None().into_bound(py),
+            ScalarType::Bool => (*peek.get::<bool>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::F32 => (*peek.get::<f32>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::F64 => (*peek.get::<f64>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::U8 => (*peek.get::<u8>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::U16 => (*peek.get::<u16>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::U32 => (*peek.get::<u32>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::U64 => (*peek.get::<u64>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::U128 => (*peek.get::<u128>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::USize => (*peek.get::<usize>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::I8 => (*peek.get::<i8>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::I16 => (*peek.get::<i16>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::I32 => (*peek.get::<i32>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::I64 => (*peek.get::<i64>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::I128 => (*peek.get::<i128>().map_err(err)?).into_bound_py_any(py)?,
+            ScalarType::ISize => (*peek.get::<isize>().map_err(err)?).into_bound_py_any(py)?,
+            _ if shape.is_display() => text(py, peek)?,
+            _ => return Err(unsupported(peek)),
+        };
+        return Ok(object);
+    }
+
+    if shape.inner.is_some() {
+        return to_object(py, peek.innermost_peek());
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => {
+            let ps = peek.into_struct().map_err(err)?;
+            match sd.kind {
+                StructKind::Tuple | StructKind::TupleStruct => {
+                    let mut items = Vec::with_capacity(ps.field_count());
+                    for index in 0..ps.field_count() {
+                        let field = ps.field(index).map_err(|_| unsupported(peek))?;
+                        items.push(to_object(py, field)?);
+                    }
+                    Ok(PyTuple::new(py, items)?.into_any())
+                }
+                _ => fields_to_dict(py, ps.fields_for_serialize()),
+            }
+        }
+        Type::User(UserType::Enum(_)) => {
+            let pe = peek.into_enum().map_err(|_| unsupported(peek))?;
+            let variant = pe.active_variant().map_err(|_| unsupported(peek))?;
+            let content = match variant.data.kind {
+                StructKind::Unit => return Ok(variant.name.into_bound_py_any(py)?),
+                StructKind::Struct => fields_to_dict(py, pe.fields_for_serialize())?,
+                _ => {
+                    let fields = variant.data.fields.len();
+                    let mut items = Vec::with_capacity(fields);
+                    for index in 0..fields {
+                        let field = pe
+                            .field(index)
+                            .ok()
+                            .flatten()
+                            .ok_or_else(|| unsupported(peek))?;
+                        items.push(to_object(py, field)?);
+                    }
+                    if fields == 1 {
+                        items.remove(0)
+                    } else {
+                        PyTuple::new(py, items)?.into_any()
+                    }
+                }
+            };
+            let dict = PyDict::new(py);
+            dict.set_item(variant.name, content)?;
+            Ok(dict.into_any())
+        }
+        _ if shape.is_display() => text(py, peek),
+        _ => Err(unsupported(peek)),
+    }
+}
+
+fn fields_to_dict<'py, 'mem, 'facet, 'shape>(
+    py: Python<'py>,
+    fields: impl Iterator<Item = (facet_core::Field<'shape>, Peek<'mem, 'facet, 'shape>)>,
+) -> Result<Bound<'py, PyAny>, PySerError> {
+    let dict = PyDict::new(py);
+    for (field, value) in fields {
+        dict.set_item(field.name, to_object(py, value)?)?;
+    }
+    Ok(dict.into_any())
+}
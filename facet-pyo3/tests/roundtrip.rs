@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_pyo3::{PyDeError, from_py, to_py};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Job {
+    name: String,
+    retries: u32,
+    tags: Vec<String>,
+    owner: Option<String>,
+    checksum: Vec<u8>,
+    window: (u8, u8),
+    state: State,
+    #[facet(default)]
+    priority: i64,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+enum State {
+    Queued,
+    Failed { reason: String },
+}
+
+fn job() -> Job {
+    Job {
+        name: "backup".to_string(),
+        retries: 3,
+        tags: vec!["nightly".to_string()],
+        owner: None,
+        checksum: vec![0xde, 0xad],
+        window: (1, 5),
+        state: State::Failed {
+            reason: "disk full".to_string(),
+        },
+        priority: -1,
+    }
+}
+
+#[test]
+fn struct_roundtrip() {
+    Python::with_gil(|py| {
+        let job = job();
+        let object = to_py(py, &job).unwrap();
+
+        let dict = object.downcast::<PyDict>().unwrap();
+        assert!(dict.get_item("owner").unwrap().unwrap().is_none());
+        let checksum = dict.get_item("checksum").unwrap().unwrap();
+        assert_eq!(
+            checksum.downcast::<PyBytes>().unwrap().as_bytes(),
+            [0xde, 0xad]
+        );
+        let window = dict.get_item("window").unwrap().unwrap();
+        assert_eq!(window.extract::<(u8, u8)>().unwrap(), (1, 5));
+
+        assert_eq!(from_py::<Job>(&object).unwrap(), job);
+    });
+}
+
+#[test]
+fn reads_python_literals() {
+    Python::with_gil(|py| {
+        let object = py
+            .eval(
+                c"{'name': 'sync', 'retries': 0, 'tags': ('a', 'b'), 'checksum': bytearray(b'x'), \
+                  'window': [0, 23], 'state': 'Queued', 'extra': object()}",
+                None,
+                None,
+            )
+            .unwrap();
+
+        let job: Job = from_py(&object).unwrap();
+        assert_eq!(job.tags, ["a", "b"]);
+        assert_eq!(job.owner, None);
+        assert_eq!(job.checksum, b"x");
+        assert_eq!(job.window, (0, 23));
+        assert_eq!(job.state, State::Queued);
+        assert_eq!(job.priority, 0);
+    });
+}
+
+#[test]
+fn wide_integers_and_int_keys() {
+    Python::with_gil(|py| {
+        let map: BTreeMap<u32, u128> = [(1, u128::MAX)].into_iter().collect();
+        let object = to_py(py, &map).unwrap();
+        assert_eq!(from_py::<BTreeMap<u32, u128>>(&object).unwrap(), map);
+    });
+}
+
+#[test]
+fn errors() {
+    Python::with_gil(|py| {
+        let err = from_py::<u8>(&py.eval(c"256", None, None).unwrap()).unwrap_err();
+        assert!(matches!(err, PyDeError::NumberOutOfRange(_)));
+
+        // `bool` is an `int` in Python, but not a number here
+        let err = from_py::<u8>(&py.eval(c"True", None, None).unwrap()).unwrap_err();
+        assert!(matches!(err, PyDeError::TypeMismatch { ref found, .. } if found == "bool"));
+
+        let err = from_py::<State>(&py.eval(c"'Done'", None, None).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "'Done' is not a variant of State");
+        let err = PyErr::from(err);
+        assert!(err.is_instance_of::<pyo3::exceptions::PyValueError>(py));
+    });
+}
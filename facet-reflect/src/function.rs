@@ -0,0 +1,138 @@
+use core::fmt;
+
+use facet_core::Shape;
+
+/// What a function marked `#[facet_fn]` says about itself: its name, parameters, return type
+/// and doc comment
+///
+/// `fn_shape!(name)` gives one (through `Deref`), so that RPC and CLI generators can walk a
+/// function's signature the way they walk a struct's fields.
+#[derive(Clone, Copy, Debug)]
+pub struct FnShape<'shape> {
+    name: &'shape str,
+    params: &'shape [FnParam<'shape>],
+    ret: FnType<'shape>,
+    doc: &'shape [&'shape str],
+}
+
+impl<'shape> FnShape<'shape> {
+    /// Describes a function; used by `#[facet_fn]`.
+    pub const fn new(
+        name: &'shape str,
+        params: &'shape [FnParam<'shape>],
+        ret: FnType<'shape>,
+        doc: &'shape [&'shape str],
+    ) -> Self {
+        Self {
+            name,
+            params,
+            ret,
+            doc,
+        }
+    }
+
+    /// The name of the function.
+    pub const fn name(&self) -> &'shape str {
+        self.name
+    }
+
+    /// The parameters of the function, in order.
+    pub fn params(&self) -> core::slice::Iter<'shape, FnParam<'shape>> {
+        self.params.iter()
+    }
+
+    /// The number of parameters the function takes.
+    pub const fn param_count(&self) -> usize {
+        self.params.len()
+    }
+
+    /// The parameter called `name`, if there's one.
+    pub fn param(&self, name: &str) -> Option<&'shape FnParam<'shape>> {
+        self.params.iter().find(|param| param.name == name)
+    }
+
+    /// The type the function returns, which is `()` if it doesn't say.
+    pub const fn ret(&self) -> FnType<'shape> {
+        self.ret
+    }
+
+    /// The lines of the function's doc comment, as the compiler gives them: most start with
+    /// a space.
+    pub const fn doc(&self) -> &'shape [&'shape str] {
+        self.doc
+    }
+}
+
+impl<'shape> IntoIterator for &FnShape<'shape> {
+    type Item = &'shape FnParam<'shape>;
+    type IntoIter = core::slice::Iter<'shape, FnParam<'shape>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.params()
+    }
+}
+
+/// A parameter of a function marked `#[facet_fn]`
+#[derive(Clone, Copy, Debug)]
+pub struct FnParam<'shape> {
+    name: &'shape str,
+    ty: FnType<'shape>,
+}
+
+impl<'shape> FnParam<'shape> {
+    /// Describes a parameter; used by `#[facet_fn]`.
+    pub const fn new(name: &'shape str, ty: FnType<'shape>) -> Self {
+        Self { name, ty }
+    }
+
+    /// The name of the parameter, as written in the signature.
+    pub const fn name(&self) -> &'shape str {
+        self.name
+    }
+
+    /// The type of the parameter.
+    pub const fn ty(&self) -> FnType<'shape> {
+        self.ty
+    }
+
+    /// The shape of the parameter's type, if it implements `Facet`.
+    pub fn shape(&self) -> Option<&'static Shape<'static>> {
+        self.ty.shape()
+    }
+}
+
+/// The type of a parameter or return value of a function marked `#[facet_fn]`
+///
+/// Every type has a name, but only types that implement `Facet` have a shape: a generic
+/// parameter only has one when it's bound by `Facet`.
+#[derive(Clone, Copy)]
+pub struct FnType<'shape> {
+    name: &'shape str,
+    shape: fn() -> Option<&'static Shape<'static>>,
+}
+
+impl<'shape> FnType<'shape> {
+    /// Describes a type; used by `#[facet_fn]`.
+    pub const fn new(name: &'shape str, shape: fn() -> Option<&'static Shape<'static>>) -> Self {
+        Self { name, shape }
+    }
+
+    /// The type as written in the signature, e.g. `Vec<String>` or `T`.
+    pub const fn name(&self) -> &'shape str {
+        self.name
+    }
+
+    /// The shape of the type, if it implements `Facet`.
+    pub fn shape(&self) -> Option<&'static Shape<'static>> {
+        (self.shape)()
+    }
+}
+
+impl fmt::Debug for FnType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnType")
+            .field("name", &self.name)
+            .field("shape", &self.shape())
+            .finish()
+    }
+}
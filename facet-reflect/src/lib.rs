@@ -4,6 +4,7 @@
 #![warn(clippy::std_instead_of_alloc)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
 mod error;
@@ -14,12 +15,29 @@ mod partial;
 #[cfg(feature = "alloc")]
 pub use partial::*;
 
+mod function;
+pub use function::*;
+
+mod meta;
+pub use meta::*;
+
 mod peek;
 pub use peek::*;
 
+#[cfg(feature = "alloc")]
+mod redact;
+#[cfg(feature = "alloc")]
+pub use redact::*;
+
 mod scalar;
 pub use scalar::*;
 
+mod slot;
+pub use slot::*;
+
+#[cfg(feature = "registry")]
+pub mod registry;
+
 #[cfg(feature = "log")]
 #[allow(unused_imports)]
 pub(crate) use log::{debug, trace};
@@ -0,0 +1,163 @@
+use facet_core::{Field, FieldAttribute, FieldFlags, Shape};
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// What a struct or enum variant field says about itself: its name, shape, flags, attributes
+/// and doc comment
+///
+/// Code that describes types rather than values (help text, schemas, forms) reads fields
+/// through this, instead of going through `facet_core::Field` and parsing its attributes by
+/// hand. It comes with values from [`HasFields::fields_with_meta`](crate::HasFields), and
+/// from [`Partial::fields_with_meta`](crate::Partial::fields_with_meta) while building one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldMeta<'shape> {
+    field: Field<'shape>,
+}
+
+impl<'shape> FieldMeta<'shape> {
+    /// Reads the metadata of `field`.
+    pub fn new(field: Field<'shape>) -> Self {
+        Self { field }
+    }
+
+    /// The field, for what isn't covered here, like its offset.
+    pub fn field(&self) -> Field<'shape> {
+        self.field
+    }
+
+    /// The name the field is known by in serialized data, after `#[facet(rename)]` and
+    /// `#[facet(rename_all)]`. Fields of tuples and tuple structs are named by their index.
+    pub fn name(&self) -> &'shape str {
+        self.field.name
+    }
+
+    /// The shape of the field's type.
+    pub fn shape(&self) -> &'shape Shape<'shape> {
+        self.field.shape
+    }
+
+    /// The flags set on the field, like `FieldFlags::SENSITIVE`.
+    pub fn flags(&self) -> FieldFlags {
+        self.field.flags
+    }
+
+    /// Returns `true` if the field is marked `#[facet(sensitive)]`.
+    pub fn is_sensitive(&self) -> bool {
+        self.field.flags.contains(FieldFlags::SENSITIVE)
+    }
+
+    /// The attributes that facet doesn't know about, as written in `#[facet(...)]`.
+    pub fn attributes(&self) -> &'shape [FieldAttribute<'shape>] {
+        self.field.attributes
+    }
+
+    /// The lines of the field's doc comment, as the compiler gives them: most start with a
+    /// space.
+    pub fn doc(&self) -> &'shape [&'shape str] {
+        self.field.doc
+    }
+
+    /// The field's doc comment as one string, without the space each line starts with, or
+    /// `None` if it has none.
+    #[cfg(feature = "alloc")]
+    pub fn doc_text(&self) -> Option<String> {
+        let (first, rest) = self.field.doc.split_first()?;
+        let mut text = String::from(unindent(first));
+        for line in rest {
+            text.push('\n');
+            text.push_str(unindent(line));
+        }
+        Some(text)
+    }
+
+    /// Returns `true` if the field has the attribute `key`, either alone as in
+    /// `#[facet(positional)]`, or with a value as in `#[facet(short = 'v')]` or
+    /// `#[facet(proto(tag = 1))]`.
+    pub fn has_attr(&self, key: &str) -> bool {
+        self.attrs().any(|(k, _)| k == key)
+    }
+
+    /// The value of the attribute `key`: what follows `=`, without quotes, or what's in
+    /// parentheses. An attribute without a value gives `Some("")`, and a missing one `None`.
+    ///
+    /// With `#[facet(short = 'v')]`, `attr_value("short")` is `Some("v")`; with
+    /// `#[facet(proto(tag = 1))]`, `attr_value("proto")` is `Some("tag = 1")`.
+    pub fn attr_value(&self, key: &str) -> Option<&'shape str> {
+        self.attrs().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    /// The values of every attribute `key`, in order, for attributes that can be repeated, as
+    /// in `#[facet(validate(range(min = 1)))] #[facet(validate(custom = "odd"))]`.
+    pub fn attr_values<'k>(
+        &self,
+        key: &'k str,
+    ) -> impl Iterator<Item = &'shape str> + use<'shape, 'k> {
+        Self::split_attrs(self.field.attributes)
+            .filter(move |(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// The attributes, split into keys and values
+    fn attrs(&self) -> impl Iterator<Item = (&'shape str, &'shape str)> {
+        Self::split_attrs(self.field.attributes)
+    }
+
+    fn split_attrs(
+        attributes: &'shape [FieldAttribute<'shape>],
+    ) -> impl Iterator<Item = (&'shape str, &'shape str)> {
+        attributes.iter().filter_map(|attr| match attr {
+            FieldAttribute::Arbitrary(content) => Some(split_attr(content)),
+            _ => None,
+        })
+    }
+}
+
+impl<'shape> From<Field<'shape>> for FieldMeta<'shape> {
+    fn from(field: Field<'shape>) -> Self {
+        Self::new(field)
+    }
+}
+
+/// Splits `key`, `key = value` or `key(args)` into its key and value
+fn split_attr(content: &str) -> (&str, &str) {
+    let content = content.trim();
+    let end = content
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+        .unwrap_or(content.len());
+    let (key, rest) = content.split_at(end);
+    let rest = rest.trim();
+    let value = if let Some(value) = rest.strip_prefix('=') {
+        let value = value.trim();
+        ['"', '\'']
+            .iter()
+            .find_map(|&q| value.strip_prefix(q)?.strip_suffix(q))
+            .unwrap_or(value)
+    } else if let Some(args) = rest.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+        args.trim()
+    } else {
+        rest
+    };
+    (key, value)
+}
+
+#[cfg(feature = "alloc")]
+fn unindent(line: &str) -> &str {
+    line.strip_prefix(' ').unwrap_or(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_attributes() {
+        assert_eq!(split_attr("positional"), ("positional", ""));
+        assert_eq!(split_attr("short = 'v'"), ("short", "v"));
+        assert_eq!(split_attr("short='v'"), ("short", "v"));
+        assert_eq!(split_attr("label = \"Full name\""), ("label", "Full name"));
+        assert_eq!(split_attr("min = 3"), ("min", "3"));
+        assert_eq!(split_attr("proto(tag = 1)"), ("proto", "tag = 1"));
+        assert_eq!(split_attr(" skip_if_empty "), ("skip_if_empty", ""));
+    }
+}
@@ -0,0 +1,102 @@
+use facet_core::{Characteristic, Def, FieldFlags, KnownSmartPointer, SequenceType, Shape, Type};
+use facet_core::{Field, UserType};
+
+use crate::{HeapValue, Partial, ReflectError};
+
+/// Builds the default value of `shape`, even if its type doesn't implement `Default`.
+///
+/// A type that implements `Default` gets its default. Otherwise, a struct or tuple is built
+/// field by field: a field with `#[facet(default)]` gets the default that gives it, and any
+/// other field its own default value, found the same way. An enum gets its first variant,
+/// with its fields filled in like a struct's, an array has every item filled in, and a `Box`
+/// or `Arc` holds the default value of what it points to.
+///
+/// Fails with [`ReflectError::MissingCharacteristic`] for a value that can't be made up, like
+/// a reference, an enum without variants, or an opaque type without `Default`.
+///
+/// ```
+/// # use facet::Facet;
+/// # use facet_reflect::ReflectError;
+/// #[derive(Facet)]
+/// struct Settings {
+///     #[facet(default = 30)]
+///     timeout: u32,
+///     retries: u8,
+///     name: Option<String>,
+/// }
+///
+/// let settings: Settings = facet_reflect::default_value(Settings::SHAPE)?.materialize()?;
+/// assert_eq!(settings.timeout, 30);
+/// assert_eq!(settings.retries, 0);
+/// assert_eq!(settings.name, None);
+/// # Ok::<(), ReflectError>(())
+/// ```
+pub fn default_value<'facet, 'shape>(
+    shape: &'shape Shape<'shape>,
+) -> Result<HeapValue<'facet, 'shape>, ReflectError<'shape>> {
+    let mut partial = Partial::alloc_shape(shape)?;
+    set_default_value(&mut partial)?;
+    partial.build()
+}
+
+/// Sets the current frame of `partial` to the default value of its shape
+fn set_default_value<'shape>(
+    partial: &mut Partial<'_, 'shape>,
+) -> Result<(), ReflectError<'shape>> {
+    let shape = partial.shape();
+    if shape.is(Characteristic::Default) {
+        partial.set_default()?;
+        return Ok(());
+    }
+
+    if let Def::SmartPointer(def) = shape.def {
+        if matches!(
+            def.known,
+            Some(KnownSmartPointer::Box | KnownSmartPointer::Arc)
+        ) {
+            partial.begin_smart_ptr()?;
+            set_default_value(partial)?;
+            partial.end()?;
+            return Ok(());
+        }
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(struct_type)) => {
+            set_default_fields(partial, struct_type.fields)
+        }
+        Type::User(UserType::Enum(enum_type)) if !enum_type.variants.is_empty() => {
+            partial.select_nth_variant(0)?;
+            set_default_fields(partial, enum_type.variants[0].data.fields)
+        }
+        Type::Sequence(SequenceType::Array(array_type)) => {
+            for index in 0..array_type.n {
+                partial.begin_nth_element(index)?;
+                set_default_value(partial)?;
+                partial.end()?;
+            }
+            Ok(())
+        }
+        _ => Err(ReflectError::MissingCharacteristic {
+            shape,
+            characteristic: Characteristic::Default,
+        }),
+    }
+}
+
+/// Sets every field of the current struct or enum variant to its default
+fn set_default_fields<'shape>(
+    partial: &mut Partial<'_, 'shape>,
+    fields: &'shape [Field<'shape>],
+) -> Result<(), ReflectError<'shape>> {
+    for (index, field) in fields.iter().enumerate() {
+        partial.begin_nth_field(index)?;
+        if field.flags.contains(FieldFlags::DEFAULT) {
+            partial.set_default_of_field(field)?;
+        } else {
+            set_default_value(partial)?;
+        }
+        partial.end()?;
+    }
+    Ok(())
+}
@@ -131,7 +131,7 @@ use alloc::vec;
 
 mod iset;
 
-use crate::{Peek, ReflectError, trace};
+use crate::{FieldMeta, Peek, ReflectError, trace};
 use facet_core::DefaultInPlaceFn;
 
 use core::marker::PhantomData;
@@ -140,9 +140,12 @@ mod heap_value;
 use alloc::vec::Vec;
 pub use heap_value::*;
 
+mod default_value;
+pub use default_value::*;
+
 use facet_core::{
-    Def, EnumRepr, Facet, KnownSmartPointer, PtrConst, PtrMut, PtrUninit, Shape, Type, UserType,
-    Variant,
+    Characteristic, Def, EnumRepr, Facet, Field, KnownSmartPointer, PtrConst, PtrMut, PtrUninit,
+    Shape, StructKind, Type, UserType, Variant,
 };
 use iset::ISet;
 
@@ -402,6 +405,16 @@ impl<'shape> Frame<'shape> {
     }
 }
 
+/// Whether a field holding a struct with named fields can be updated field by field when it is
+/// entered again, instead of being dropped and built from scratch.
+fn is_updatable_in_place(shape: &Shape<'_>) -> bool {
+    shape.inner.is_none()
+        && matches!(
+            shape.ty,
+            Type::User(UserType::Struct(struct_type)) if struct_type.kind == StructKind::Struct
+        )
+}
+
 impl<'facet, 'shape> Partial<'facet, 'shape> {
     /// Allocates a new Partial instance with the given shape
     pub fn alloc_shape(shape: &'shape Shape<'shape>) -> Result<Self, ReflectError<'shape>> {
@@ -427,6 +440,32 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
         })
     }
 
+    /// Allocates a new TypedPartial that starts out holding `value`, fully initialized
+    ///
+    /// Fields can then be selected and set again, each replacing only its own value: this is
+    /// how deserializers update an existing value with what the input holds. Re-entering a
+    /// struct field updates that struct in place too, rather than starting it over.
+    pub fn from_existing_value<T>(
+        value: T,
+    ) -> Result<TypedPartial<'facet, 'shape, T>, ReflectError<'shape>>
+    where
+        T: Facet<'facet>,
+    {
+        let mut partial = Self::alloc::<T>()?;
+        partial.set(value)?;
+        Ok(partial)
+    }
+
+    /// Allocates a new Partial that starts out holding a clone of the value in `peek`, fully
+    /// initialized. See [`Partial::from_existing_value`].
+    ///
+    /// Fails if the value's shape doesn't implement `Clone`.
+    pub fn from_existing_peek(peek: &Peek<'_, '_, 'shape>) -> Result<Self, ReflectError<'shape>> {
+        let mut partial = Self::alloc_shape(peek.shape())?;
+        partial.clone_from_peek(peek)?;
+        Ok(partial)
+    }
+
     /// Creates a Partial from an existing pointer and shape (used for nested initialization)
     pub fn from_ptr(data: PtrUninit<'_>, shape: &'shape Shape<'shape>) -> Self {
         // We need to convert the lifetime, which is safe because we're storing it in a frame
@@ -510,6 +549,13 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
             return Err(ReflectError::Unsized { shape: fr.shape });
         }
 
+        // A struct field that's entered again keeps its value, which is replaced here
+        if matches!(fr.tracker, Tracker::Init) {
+            if let Some(drop_fn) = fr.shape.vtable.sized().and_then(|v| (v.drop_in_place)()) {
+                unsafe { drop_fn(PtrMut::new(fr.data.as_mut_byte_ptr())) };
+            }
+        }
+
         unsafe {
             fr.data.copy_from(src_value, fr.shape).unwrap();
         }
@@ -563,6 +609,66 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
         })
     }
 
+    /// Sets the current frame, entered for `field`, to the value `#[facet(default)]` gives it:
+    /// the result of the field's default function if it has one, or else its type's default
+    pub fn set_default_of_field(
+        &mut self,
+        field: &Field<'shape>,
+    ) -> Result<&mut Self, ReflectError<'shape>> {
+        if let Some(default_fn) = field.vtable.default_fn {
+            self.set_field_default(default_fn)
+        } else if field.shape().is(Characteristic::Default) {
+            self.set_default()
+        } else {
+            Err(ReflectError::DefaultAttrButNoDefaultImpl {
+                shape: field.shape(),
+            })
+        }
+    }
+
+    /// Sets the fields of the struct at the current frame that aren't set yet to the ones of
+    /// the struct's default value, which is what `#[facet(default)]` on a struct asks for.
+    ///
+    /// The fields are moved out of the default value, and the rest of it is dropped.
+    pub fn set_unset_fields_from_default(&mut self) -> Result<&mut Self, ReflectError<'shape>> {
+        self.require_active()?;
+
+        let shape = self.frames.last().unwrap().shape;
+        let Type::User(UserType::Struct(sd)) = shape.ty else {
+            return Err(ReflectError::WasNotA {
+                expected: "struct",
+                actual: shape,
+            });
+        };
+
+        let mut default_value = Self::alloc_shape(shape)?.set_default()?.build()?;
+        // From here on, the default value's fields are either moved out or dropped one by one,
+        // and the guard only frees its memory
+        let guard = default_value.guard.take().unwrap();
+        for (index, field) in sd.fields.iter().enumerate() {
+            // SAFETY: the field is in bounds of the default value, which is of `shape`
+            let field_ptr = unsafe { guard.ptr.add(field.offset) };
+            if self.is_field_set(index)? {
+                if let Some(drop_fn) = field
+                    .shape()
+                    .vtable
+                    .sized()
+                    .and_then(|v| (v.drop_in_place)())
+                {
+                    // SAFETY: the field is initialized, and never read again
+                    unsafe { drop_fn(PtrMut::new(field_ptr)) };
+                }
+            } else {
+                self.begin_nth_field(index)?;
+                // SAFETY: the field is initialized and of its shape, and is moved out here
+                unsafe { self.set_shape(PtrConst::new(field_ptr), field.shape())? };
+                self.end()?;
+            }
+        }
+        drop(guard);
+        Ok(self)
+    }
+
     /// Sets the current frame using a function that initializes the value
     pub fn set_from_function<F>(&mut self, f: F) -> Result<&mut Self, ReflectError<'shape>>
     where
@@ -947,6 +1053,15 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
                     }
                     let field = &struct_type.fields[idx];
 
+                    // A struct that was set as a whole can still have its fields replaced
+                    if matches!(frame.tracker, Tracker::Init) {
+                        frame.tracker = Tracker::Struct {
+                            iset: ISet::new(0),
+                            current_child: None,
+                        };
+                    }
+
+                    let mut keep_existing = false;
                     match &mut frame.tracker {
                         Tracker::Uninit => {
                             frame.tracker = Tracker::Struct {
@@ -958,8 +1073,12 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
                             iset,
                             current_child,
                         } => {
-                            // Check if this field was already initialized
-                            if iset.get(idx) {
+                            if iset.get(idx) && is_updatable_in_place(field.shape) {
+                                // Keep the existing struct, so that only the fields set
+                                // through the new frame are replaced
+                                keep_existing = true;
+                                iset.unset(idx);
+                            } else if iset.get(idx) {
                                 // Drop the existing value before re-initializing
                                 let field_ptr = unsafe { frame.data.field_init_at(field.offset) };
                                 if let Some(drop_fn) =
@@ -978,8 +1097,11 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
                     // Push a new frame for this field onto the frames stack.
                     let field_ptr = unsafe { frame.data.field_uninit_at(field.offset) };
                     let field_shape = field.shape;
-                    self.frames
-                        .push(Frame::new(field_ptr, field_shape, FrameOwnership::Field));
+                    let mut field_frame = Frame::new(field_ptr, field_shape, FrameOwnership::Field);
+                    if keep_existing {
+                        field_frame.tracker = Tracker::Init;
+                    }
+                    self.frames.push(field_frame);
 
                     Ok(self)
                 }
@@ -1567,6 +1689,84 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
         Ok(self)
     }
 
+    /// Appends a copy of every item of `items` to the list, which must have been started with
+    /// [`begin_list`](Self::begin_list), without a frame for each of them.
+    ///
+    /// This is a fast path for lists of numbers or other `Copy` values: the item type must be
+    /// the list's element type. A `Vec<U>` is extended in one go.
+    pub fn extend_from_slice<U>(&mut self, items: &[U]) -> Result<&mut Self, ReflectError<'shape>>
+    where
+        U: Facet<'facet> + Copy,
+    {
+        self.require_active()?;
+        let frame = self.frames.last_mut().unwrap();
+
+        let list_def = match &frame.shape.def {
+            Def::List(list_def) => list_def,
+            _ => {
+                return Err(ReflectError::OperationFailed {
+                    shape: frame.shape,
+                    operation: "extend_from_slice can only be called on List types",
+                });
+            }
+        };
+
+        match &frame.tracker {
+            Tracker::List {
+                is_initialized: true,
+                current_child: false,
+            } => {}
+            Tracker::List {
+                is_initialized: true,
+                current_child: true,
+            } => {
+                return Err(ReflectError::OperationFailed {
+                    shape: frame.shape,
+                    operation: "already pushing an element, call pop() first",
+                });
+            }
+            _ => {
+                return Err(ReflectError::OperationFailed {
+                    shape: frame.shape,
+                    operation: "must call begin_list() before extend_from_slice()",
+                });
+            }
+        }
+
+        let element_shape = list_def.t();
+        if !element_shape.is_shape(U::SHAPE) {
+            return Err(ReflectError::WrongShape {
+                expected: element_shape,
+                actual: U::SHAPE,
+            });
+        }
+
+        if frame.shape.is_shape(Vec::<U>::SHAPE) {
+            // SAFETY: the frame holds an initialized `Vec<U>`, as its shape and tracker say
+            let vec = unsafe { &mut *(frame.data.as_mut_byte_ptr() as *mut Vec<U>) };
+            vec.extend_from_slice(items);
+            return Ok(self);
+        }
+
+        let Some(push_fn) = list_def.vtable.push else {
+            return Err(ReflectError::OperationFailed {
+                shape: frame.shape,
+                operation: "List missing push function",
+            });
+        };
+        for item in items {
+            // `push` moves the item out with a bitwise read, which is a copy for `Copy` types
+            let mut item = *item;
+            unsafe {
+                push_fn(
+                    PtrMut::new(frame.data.as_mut_byte_ptr()),
+                    PtrMut::new(&raw mut item),
+                );
+            }
+        }
+        Ok(self)
+    }
+
     /// Pops the current frame off the stack, indicating we're done initializing the current field.
     pub fn end(&mut self) -> Result<&mut Self, ReflectError<'shape>> {
         self.require_active()?;
@@ -2135,6 +2335,46 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
         }
     }
 
+    /// Iterates over the metadata of the fields of the current struct, or of the selected
+    /// enum variant. Fields come in order, so their position is the index
+    /// [`Partial::is_field_set`] takes.
+    pub fn fields_with_meta(&self) -> impl Iterator<Item = FieldMeta<'shape>> + use<'shape> {
+        let fields: &'shape [Field<'shape>] = match self.frames.last() {
+            Some(frame) => match (&frame.tracker, frame.shape.ty) {
+                (Tracker::Enum { variant, .. }, _) => variant.data.fields,
+                (_, Type::User(UserType::Struct(struct_def))) => struct_def.fields,
+                _ => &[],
+            },
+            None => &[],
+        };
+        fields.iter().copied().map(FieldMeta::new)
+    }
+
+    /// Returns the struct or enum variant field the current frame is building, if it is
+    /// building one.
+    pub fn parent_field(&self) -> Option<&'shape Field<'shape>> {
+        let parent = self.frames.len().checked_sub(2).map(|i| &self.frames[i])?;
+
+        match (&parent.tracker, parent.shape.ty) {
+            (
+                Tracker::Struct {
+                    current_child: Some(idx),
+                    ..
+                },
+                Type::User(UserType::Struct(struct_type)),
+            ) => struct_type.fields.get(*idx),
+            (
+                Tracker::Enum {
+                    variant,
+                    current_child: Some(idx),
+                    ..
+                },
+                _,
+            ) => variant.data.fields.get(*idx),
+            _ => None,
+        }
+    }
+
     /// Get the currently selected variant for an enum
     pub fn selected_variant(&self) -> Option<Variant<'shape>> {
         let frame = self.frames.last()?;
@@ -2301,6 +2541,76 @@ impl<'facet, 'shape> Partial<'facet, 'shape> {
         unsafe { self.set_shape(src_ptr.thin().unwrap(), src_shape) }
     }
 
+    /// Moves a value built by another Partial into the current frame
+    pub fn set_from_heap_value(
+        &mut self,
+        mut value: HeapValue<'facet, 'shape>,
+    ) -> Result<&mut Self, ReflectError<'shape>> {
+        self.require_active()?;
+
+        let frame = self.frames.last().unwrap();
+        if !frame.shape.is_shape(value.shape) {
+            return Err(ReflectError::WrongShape {
+                expected: frame.shape,
+                actual: value.shape,
+            });
+        }
+
+        let guard = value.guard.take().unwrap();
+        let shape = value.shape;
+        self.set_from_function(move |dst| {
+            // SAFETY: `guard` holds a value of the shape `dst` was allocated for. It's moved out
+            // here, so the guard only frees the memory
+            unsafe { dst.copy_from(PtrConst::new(guard.ptr), shape) }
+                .map_err(|_| ReflectError::Unsized { shape })?;
+            drop(guard);
+            Ok(())
+        })
+    }
+
+    /// Sets the current frame to a clone of the value in `peek`, using its shape's `Clone` impl
+    pub fn clone_from_peek(
+        &mut self,
+        peek: &Peek<'_, '_, 'shape>,
+    ) -> Result<&mut Self, ReflectError<'shape>> {
+        self.require_active()?;
+
+        let frame = self.frames.last().unwrap();
+        if !frame.shape.is_shape(peek.shape()) {
+            return Err(ReflectError::WrongShape {
+                expected: frame.shape,
+                actual: peek.shape(),
+            });
+        }
+        let Some(clone_fn) = peek.shape().vtable.sized().and_then(|v| (v.clone_into)()) else {
+            return Err(ReflectError::OperationFailed {
+                shape: peek.shape(),
+                operation: "type does not implement Clone",
+            });
+        };
+        let Some(src_ptr) = peek.data().thin() else {
+            return Err(ReflectError::Unsized {
+                shape: peek.shape(),
+            });
+        };
+
+        self.set_from_function(move |dst| {
+            // SAFETY: `src_ptr` holds a valid value of the shape `dst` was allocated for
+            unsafe { clone_fn(src_ptr, dst) };
+            Ok(())
+        })
+    }
+
+    /// Returns the value of the current frame, if it is fully initialized
+    pub fn peek_current(&self) -> Result<Peek<'_, 'facet, 'shape>, ReflectError<'shape>> {
+        self.require_active()?;
+
+        let frame = self.frames.last().unwrap();
+        frame.require_full_initialization()?;
+        // SAFETY: the frame's data is fully initialized, and of the frame's shape
+        Ok(unsafe { Peek::unchecked_new(frame.data.assume_init().as_const(), frame.shape) })
+    }
+
     /// Copy a field from a struct's default value (safe wrapper for deserialization)
     /// This method creates the Peek internally to avoid exposing unsafe code to callers
     pub fn set_field_from_default(
@@ -2598,6 +2908,15 @@ impl<'facet, 'shape, T> TypedPartial<'facet, 'shape, T> {
         Ok(self)
     }
 
+    /// Forwards extend_from_slice to the inner wip instance.
+    pub fn extend_from_slice<U>(&mut self, items: &[U]) -> Result<&mut Self, ReflectError<'shape>>
+    where
+        U: Facet<'facet> + Copy,
+    {
+        self.inner.extend_from_slice(items)?;
+        Ok(self)
+    }
+
     /// Forwards begin_map to the inner wip instance.
     pub fn begin_map(&mut self) -> Result<&mut Self, ReflectError<'shape>> {
         self.inner.begin_map()?;
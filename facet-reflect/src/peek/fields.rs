@@ -1,8 +1,11 @@
 use core::ops::Range;
 
-use facet_core::{Field, FieldFlags};
+use facet_core::Field;
+#[cfg(feature = "alloc")]
+use facet_core::FieldFlags;
 
-use crate::Peek;
+use crate::{FieldMeta, Peek};
+#[cfg(feature = "alloc")]
 use alloc::{vec, vec::Vec};
 
 use super::{PeekEnum, PeekStruct, PeekTuple};
@@ -15,7 +18,16 @@ pub trait HasFields<'mem, 'facet, 'shape> {
     /// Iterates over all fields in this type, providing both field metadata and value
     fn fields(&self) -> FieldIter<'mem, 'facet, 'shape>;
 
+    /// Iterates over all fields in this type, like [`HasFields::fields`], with their metadata
+    /// read through [`FieldMeta`]
+    fn fields_with_meta(&self) -> FieldsWithMeta<'mem, 'facet, 'shape> {
+        FieldsWithMeta {
+            inner: self.fields(),
+        }
+    }
+
     /// Iterates over fields in this type that should be included when it is serialized
+    #[cfg(feature = "alloc")]
     fn fields_for_serialize(&self) -> FieldsForSerializeIter<'mem, 'facet, 'shape> {
         FieldsForSerializeIter {
             stack: vec![self.fields()],
@@ -23,6 +35,25 @@ pub trait HasFields<'mem, 'facet, 'shape> {
     }
 }
 
+/// An iterator over all the fields of a struct or enum, with their metadata. See
+/// [`HasFields::fields_with_meta`]
+pub struct FieldsWithMeta<'mem, 'facet, 'shape> {
+    inner: FieldIter<'mem, 'facet, 'shape>,
+}
+
+impl<'mem, 'facet, 'shape> Iterator for FieldsWithMeta<'mem, 'facet, 'shape> {
+    type Item = (FieldMeta<'shape>, Peek<'mem, 'facet, 'shape>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (field, value) = self.inner.next()?;
+        Some((FieldMeta::new(field), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
 /// An iterator over all the fields of a struct or enum. See [`HasFields::fields`]
 pub struct FieldIter<'mem, 'facet, 'shape> {
     state: FieldIterState<'mem, 'facet, 'shape>,
@@ -36,6 +67,8 @@ enum FieldIterState<'mem, 'facet, 'shape> {
         peek_enum: PeekEnum<'mem, 'facet, 'shape>,
         fields: &'shape [Field<'shape>],
     },
+    // Only built when flattening, which needs `alloc`
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
     FlattenedEnum {
         field: Field<'shape>,
         value: Peek<'mem, 'facet, 'shape>,
@@ -149,10 +182,12 @@ impl DoubleEndedIterator for FieldIter<'_, '_, '_> {
 impl ExactSizeIterator for FieldIter<'_, '_, '_> {}
 
 /// An iterator over the fields of a struct or enum that should be serialized. See [`HasFields::fields_for_serialize`]
+#[cfg(feature = "alloc")]
 pub struct FieldsForSerializeIter<'mem, 'facet, 'shape> {
     stack: Vec<FieldIter<'mem, 'facet, 'shape>>,
 }
 
+#[cfg(feature = "alloc")]
 impl<'mem, 'facet, 'shape> Iterator for FieldsForSerializeIter<'mem, 'facet, 'shape> {
     type Item = (Field<'shape>, Peek<'mem, 'facet, 'shape>);
 
@@ -1,7 +1,7 @@
 use facet_core::{IterVTable, PtrConst, PtrMut, Shape, ShapeLayout};
 
 use super::Peek;
-use core::{fmt::Debug, marker::PhantomData};
+use core::fmt::Debug;
 
 /// Fields for types which act like lists
 #[derive(Clone, Copy)]
@@ -33,27 +33,28 @@ impl<'shape> ListLikeDef<'shape> {
     }
 }
 
-/// Iterator over a `PeekListLike`
+/// Iterator over a `PeekListLike`, from either end
 pub struct PeekListLikeIter<'mem, 'facet, 'shape> {
     state: PeekListLikeIterState<'mem>,
+    /// Index of the next item from the front
     index: usize,
+    /// One past the index of the next item from the back
     len: usize,
-    def: ListLikeDef<'shape>,
-    _list: PhantomData<Peek<'mem, 'facet, 'shape>>,
+    list: PeekListLike<'mem, 'facet, 'shape>,
 }
 
 impl<'mem, 'facet, 'shape> Iterator for PeekListLikeIter<'mem, 'facet, 'shape> {
     type Item = Peek<'mem, 'facet, 'shape>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let item_ptr = match self.state {
-            PeekListLikeIterState::Ptr { data, stride } => {
-                if self.index >= self.len {
-                    return None;
-                }
+        if self.index >= self.len {
+            return None;
+        }
 
-                unsafe { data.field(stride * self.index) }
-            }
+        let item_ptr = match self.state {
+            PeekListLikeIterState::Ptr { data, stride } => unsafe {
+                data.field(stride * self.index)
+            },
             PeekListLikeIterState::Iter { iter, vtable } => unsafe { (vtable.next)(iter)? },
         };
 
@@ -61,7 +62,7 @@ impl<'mem, 'facet, 'shape> Iterator for PeekListLikeIter<'mem, 'facet, 'shape> {
         // calculating the iterator's size
         self.index += 1;
 
-        Some(unsafe { Peek::unchecked_new(item_ptr, self.def.t()) })
+        Some(unsafe { Peek::unchecked_new(item_ptr, self.list.def.t()) })
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -70,6 +71,26 @@ impl<'mem, 'facet, 'shape> Iterator for PeekListLikeIter<'mem, 'facet, 'shape> {
     }
 }
 
+impl<'mem, 'facet, 'shape> DoubleEndedIterator for PeekListLikeIter<'mem, 'facet, 'shape> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+        self.len -= 1;
+
+        let item_ptr = match self.state {
+            PeekListLikeIterState::Ptr { data, stride } => unsafe { data.field(stride * self.len) },
+            PeekListLikeIterState::Iter { iter, vtable } => match vtable.next_back {
+                Some(next_back) => unsafe { next_back(iter)? },
+                // Lists that can't iterate backwards can still be indexed
+                None => return self.list.get(self.len),
+            },
+        };
+
+        Some(unsafe { Peek::unchecked_new(item_ptr, self.list.def.t()) })
+    }
+}
+
 impl<'mem, 'facet, 'shape> ExactSizeIterator for PeekListLikeIter<'mem, 'facet, 'shape> {}
 
 impl<'mem, 'facet, 'shape> IntoIterator for &'mem PeekListLike<'mem, 'facet, 'shape> {
@@ -207,8 +228,24 @@ impl<'mem, 'facet, 'shape> PeekListLike<'mem, 'facet, 'shape> {
             state,
             index: 0,
             len: self.len(),
-            def: self.def(),
-            _list: PhantomData,
+            list: self,
+        }
+    }
+
+    /// Borrows the items as bytes, if this is a list of `u8` stored contiguously, like
+    /// `Vec<u8>`, `[u8; N]` or `&[u8]`
+    pub fn as_bytes(&self) -> Option<&'mem [u8]> {
+        if !self.def.t().is_type::<u8>() {
+            return None;
+        }
+        let as_ptr = match self.def {
+            ListLikeDef::List(def) => def.vtable.as_ptr?,
+            ListLikeDef::Array(def) => def.vtable.as_ptr,
+            ListLikeDef::Slice(def) => def.vtable.as_ptr,
+        };
+        unsafe {
+            let data = as_ptr(self.value.data().thin().unwrap());
+            Some(core::slice::from_raw_parts(data.as_byte_ptr(), self.len))
         }
     }
 
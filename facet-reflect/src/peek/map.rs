@@ -2,28 +2,76 @@ use facet_core::{MapDef, PtrConst, PtrMut};
 
 use super::Peek;
 
-/// Iterator over key-value pairs in a `PeekMap`
+/// Iterator over key-value pairs in a `PeekMap`, from either end
 pub struct PeekMapIter<'mem, 'facet, 'shape> {
     map: PeekMap<'mem, 'facet, 'shape>,
     iter: PtrMut<'mem>,
+    /// Number of entries taken from the front
+    index: usize,
+    /// Number of entries not taken from the back
+    len: usize,
+}
+
+impl<'mem, 'facet, 'shape> PeekMapIter<'mem, 'facet, 'shape> {
+    fn entry(
+        &self,
+        (key_ptr, value_ptr): (PtrConst<'mem>, PtrConst<'mem>),
+    ) -> (Peek<'mem, 'facet, 'shape>, Peek<'mem, 'facet, 'shape>) {
+        unsafe {
+            (
+                Peek::unchecked_new(key_ptr, self.map.def.k()),
+                Peek::unchecked_new(value_ptr, self.map.def.v()),
+            )
+        }
+    }
 }
 
 impl<'mem, 'facet, 'shape> Iterator for PeekMapIter<'mem, 'facet, 'shape> {
     type Item = (Peek<'mem, 'facet, 'shape>, Peek<'mem, 'facet, 'shape>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        unsafe {
-            let next = (self.map.def.vtable.iter_vtable.next)(self.iter);
-            next.map(|(key_ptr, value_ptr)| {
-                (
-                    Peek::unchecked_new(key_ptr, self.map.def.k()),
-                    Peek::unchecked_new(value_ptr, self.map.def.v()),
-                )
-            })
+        if self.index >= self.len {
+            return None;
         }
+        let next = unsafe { (self.map.def.vtable.iter_vtable.next)(self.iter)? };
+        self.index += 1;
+        Some(self.entry(next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len.saturating_sub(self.index);
+        (remaining, Some(remaining))
     }
 }
 
+impl<'mem, 'facet, 'shape> DoubleEndedIterator for PeekMapIter<'mem, 'facet, 'shape> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+        self.len -= 1;
+
+        let iter_vtable = &self.map.def.vtable.iter_vtable;
+        let next = match iter_vtable.next_back {
+            Some(next_back) => unsafe { next_back(self.iter)? },
+            // Maps that can't iterate backwards, like `HashMap`, are walked from the start
+            None => unsafe {
+                let init = iter_vtable.init_with_value.unwrap();
+                let iter = init(self.map.value.data().thin().unwrap());
+                let mut next = None;
+                for _ in 0..=self.len {
+                    next = (iter_vtable.next)(iter);
+                }
+                (iter_vtable.dealloc)(iter);
+                next?
+            },
+        };
+        Some(self.entry(next))
+    }
+}
+
+impl<'mem, 'facet, 'shape> ExactSizeIterator for PeekMapIter<'mem, 'facet, 'shape> {}
+
 impl<'mem, 'facet, 'shape> Drop for PeekMapIter<'mem, 'facet, 'shape> {
     fn drop(&mut self) {
         unsafe { (self.map.def.vtable.iter_vtable.dealloc)(self.iter) }
@@ -94,7 +142,12 @@ impl<'mem, 'facet, 'shape> PeekMap<'mem, 'facet, 'shape> {
     pub fn iter(self) -> PeekMapIter<'mem, 'facet, 'shape> {
         let iter_init_with_value_fn = self.def.vtable.iter_vtable.init_with_value.unwrap();
         let iter = unsafe { iter_init_with_value_fn(self.value.data().thin().unwrap()) };
-        PeekMapIter { map: self, iter }
+        PeekMapIter {
+            map: self,
+            iter,
+            index: 0,
+            len: self.len(),
+        }
     }
 
     /// Def getter
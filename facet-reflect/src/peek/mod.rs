@@ -29,3 +29,8 @@ pub use smartptr::*;
 
 mod tuple;
 pub use tuple::*;
+
+#[cfg(feature = "alloc")]
+mod query;
+#[cfg(feature = "alloc")]
+pub use query::*;
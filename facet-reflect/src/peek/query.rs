@@ -0,0 +1,591 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+
+use facet_core::{Def, StructKind, Type};
+
+use crate::{HasFields, Peek, ScalarType};
+
+/// A JSONPath-like query, which finds values deep inside a [`Peek`].
+///
+/// A query starts with `$`, for the value it runs on, followed by steps:
+///
+/// - `.name` or `['name']`: a struct field, a field of the enum's active variant, or a map entry
+///   whose key displays as `name`. Naming the active variant of an enum, as in `.Click`, keeps
+///   the enum, so `$.event.Click.x` reads like the JSON for it.
+/// - `[2]` or `.2`: an item of a list, array or slice, or a field of a tuple; `[-1]` counts
+///   from the end.
+/// - `[*]` or `.*`: every item, field or map value.
+/// - `[?(@.age >= 18)]`: every item, field or map value for which the filter holds. A filter
+///   compares a path from `@` with a number, a string in single or double quotes, `true`,
+///   `false` or `null`, using `==`, `!=`, `<`, `<=`, `>` or `>=`. A filter without a comparison,
+///   like `[?(@.email)]`, holds when the path leads to a value that isn't `None`.
+///
+/// Options and smart pointers are looked through, so `.name` works on an `Option<Box<User>>`,
+/// and a `None` has nothing in it. Each match is the value as it is, though: `$.nickname` finds
+/// the `Option<String>` itself.
+///
+/// ```
+/// # use facet::Facet;
+/// # use facet_reflect::Peek;
+/// #[derive(Facet)]
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let users = vec![
+///     User { name: "Ada".into(), age: 36 },
+///     User { name: "Tim".into(), age: 12 },
+/// ];
+/// let names: Vec<_> = Peek::new(&users)
+///     .query("$[?(@.age >= 18)].name")
+///     .unwrap()
+///     .into_iter()
+///     .map(|name| name.as_str().unwrap())
+///     .collect();
+/// assert_eq!(names, ["Ada"]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    steps: Vec<Step>,
+}
+
+/// An error in the text of a [`Query`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryError {
+    /// Where in the query the error is, in bytes
+    pub position: usize,
+    /// What was expected there
+    pub expected: &'static str,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {} at {}", self.expected, self.position)
+    }
+}
+
+impl core::error::Error for QueryError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Step {
+    Name(String),
+    Index(isize),
+    Wildcard,
+    Filter(Filter),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Filter {
+    path: Vec<Step>,
+    test: Option<(Op, Literal)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Literal {
+    Number(f64),
+    String(String),
+    Bool(bool),
+    Null,
+}
+
+impl Query {
+    /// Parses a query, like `$.users[*].address.city`.
+    pub fn parse(query: &str) -> Result<Self, QueryError> {
+        let mut parser = Parser { query, pos: 0 };
+        parser.eat("$");
+        let steps = parser.steps()?;
+        if parser.pos < query.len() {
+            return Err(parser.error("`.` or `[`"));
+        }
+        Ok(Self { steps })
+    }
+
+    /// Finds the values the query matches in `peek`, in the order they're reached.
+    pub fn eval<'mem, 'facet, 'shape>(
+        &self,
+        peek: Peek<'mem, 'facet, 'shape>,
+    ) -> Vec<Peek<'mem, 'facet, 'shape>> {
+        let mut matches = Vec::new();
+        select(peek, &self.steps, &mut matches);
+        matches
+    }
+
+    /// The first value the query matches in `peek`.
+    pub fn first<'mem, 'facet, 'shape>(
+        &self,
+        peek: Peek<'mem, 'facet, 'shape>,
+    ) -> Option<Peek<'mem, 'facet, 'shape>> {
+        self.eval(peek).into_iter().next()
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl<'mem, 'facet, 'shape> Peek<'mem, 'facet, 'shape> {
+    /// Finds the values that a [`Query`], like `$.users[*].address.city`, matches in this
+    /// value.
+    pub fn query(&self, query: &str) -> Result<Vec<Peek<'mem, 'facet, 'shape>>, QueryError> {
+        Ok(Query::parse(query)?.eval(*self))
+    }
+}
+
+struct Parser<'q> {
+    query: &'q str,
+    pos: usize,
+}
+
+impl<'q> Parser<'q> {
+    fn rest(&self) -> &'q str {
+        &self.query[self.pos..]
+    }
+
+    fn error(&self, expected: &'static str) -> QueryError {
+        QueryError {
+            position: self.pos,
+            expected,
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let found = self.rest().starts_with(token);
+        if found {
+            self.pos += token.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, token: &'static str) -> Result<(), QueryError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(token))
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Reads steps until something that isn't one
+    fn steps(&mut self) -> Result<Vec<Step>, QueryError> {
+        let mut steps = Vec::new();
+        loop {
+            if self.eat(".") {
+                if self.eat("*") {
+                    steps.push(Step::Wildcard);
+                } else {
+                    let name = self.name()?;
+                    steps.push(match name.parse() {
+                        Ok(index) => Step::Index(index),
+                        Err(_) => Step::Name(name.to_string()),
+                    });
+                }
+            } else if self.eat("[") {
+                self.skip_spaces();
+                steps.push(self.bracket()?);
+                self.skip_spaces();
+                self.expect("]")?;
+            } else {
+                return Ok(steps);
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&'q str, QueryError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("a name"));
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    /// Reads what's between `[` and `]`
+    fn bracket(&mut self) -> Result<Step, QueryError> {
+        if self.eat("*") {
+            return Ok(Step::Wildcard);
+        }
+        if self.eat("?") {
+            self.skip_spaces();
+            let parenthesized = self.eat("(");
+            let filter = self.filter()?;
+            if parenthesized {
+                self.skip_spaces();
+                self.expect(")")?;
+            }
+            return Ok(Step::Filter(filter));
+        }
+        if let Some(name) = self.string()? {
+            return Ok(Step::Name(name));
+        }
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '-'))
+            .unwrap_or(rest.len());
+        let index = rest[..len]
+            .parse()
+            .map_err(|_| self.error("`*`, `?`, a string or an index"))?;
+        self.pos += len;
+        Ok(Step::Index(index))
+    }
+
+    /// Reads a string in single or double quotes, if there's one
+    fn string(&mut self) -> Result<Option<String>, QueryError> {
+        let Some(quote) = self
+            .rest()
+            .chars()
+            .next()
+            .filter(|&c| c == '\'' || c == '"')
+        else {
+            return Ok(None);
+        };
+        self.pos += 1;
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some((_, c)) => out.push(c),
+                    None => break,
+                },
+                c if c == quote => {
+                    self.pos += i + 1;
+                    return Ok(Some(out));
+                }
+                c => out.push(c),
+            }
+        }
+        self.pos = self.query.len();
+        Err(self.error("the end of the string"))
+    }
+
+    fn filter(&mut self) -> Result<Filter, QueryError> {
+        self.skip_spaces();
+        self.expect("@")?;
+        let path = self.steps()?;
+        self.skip_spaces();
+        let op = [
+            ("==", Op::Eq),
+            ("!=", Op::Ne),
+            ("<=", Op::Le),
+            (">=", Op::Ge),
+            ("<", Op::Lt),
+            (">", Op::Gt),
+        ]
+        .into_iter()
+        .find(|(token, _)| self.eat(token));
+        let Some((_, op)) = op else {
+            return Ok(Filter { path, test: None });
+        };
+        self.skip_spaces();
+        let literal = self.literal()?;
+        Ok(Filter {
+            path,
+            test: Some((op, literal)),
+        })
+    }
+
+    fn literal(&mut self) -> Result<Literal, QueryError> {
+        if let Some(s) = self.string()? {
+            return Ok(Literal::String(s));
+        }
+        for (token, literal) in [
+            ("true", Literal::Bool(true)),
+            ("false", Literal::Bool(false)),
+            ("null", Literal::Null),
+        ] {
+            if self.eat(token) {
+                return Ok(literal);
+            }
+        }
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')))
+            .unwrap_or(rest.len());
+        let number = rest[..len]
+            .parse()
+            .map_err(|_| self.error("a number, a string, `true`, `false` or `null`"))?;
+        self.pos += len;
+        Ok(Literal::Number(number))
+    }
+}
+
+fn select<'mem, 'facet, 'shape>(
+    peek: Peek<'mem, 'facet, 'shape>,
+    steps: &[Step],
+    matches: &mut Vec<Peek<'mem, 'facet, 'shape>>,
+) {
+    let Some((step, rest)) = steps.split_first() else {
+        matches.push(peek);
+        return;
+    };
+    let Some(value) = resolve(peek) else {
+        return;
+    };
+    match step {
+        Step::Name(name) => {
+            if let Some(child) = child_by_name(value, name) {
+                select(child, rest, matches);
+            }
+        }
+        Step::Index(index) => {
+            let child =
+                child_by_index(value, *index).or_else(|| child_by_name(value, &index.to_string()));
+            if let Some(child) = child {
+                select(child, rest, matches);
+            }
+        }
+        Step::Wildcard => {
+            for child in children(value) {
+                select(child, rest, matches);
+            }
+        }
+        Step::Filter(filter) => {
+            for child in children(value) {
+                if filter.holds(child) {
+                    select(child, rest, matches);
+                }
+            }
+        }
+    }
+}
+
+/// Looks through options, smart pointers, references and transparent wrappers; `None` has
+/// nothing to look into
+fn resolve<'mem, 'facet, 'shape>(
+    mut peek: Peek<'mem, 'facet, 'shape>,
+) -> Option<Peek<'mem, 'facet, 'shape>> {
+    loop {
+        peek = match (peek.shape().def, peek.shape().ty) {
+            (Def::Option(_), _) => peek.into_option().ok()?.value()?,
+            (Def::SmartPointer(_), _) => peek.into_smart_pointer().ok()?.borrow_inner()?,
+            (_, Type::Pointer(_)) if peek.as_str().is_none() && peek.into_list_like().is_err() => {
+                match peek.into_pointee() {
+                    Ok(pointee) => pointee,
+                    Err(_) => return Some(peek),
+                }
+            }
+            _ if peek.shape().inner.is_some() => {
+                let inner = peek.innermost_peek();
+                if inner.shape() == peek.shape() {
+                    return Some(peek);
+                }
+                inner
+            }
+            _ => return Some(peek),
+        };
+    }
+}
+
+fn child_by_name<'mem, 'facet, 'shape>(
+    peek: Peek<'mem, 'facet, 'shape>,
+    name: &str,
+) -> Option<Peek<'mem, 'facet, 'shape>> {
+    if let Ok(ps) = peek.into_struct() {
+        return ps.field_by_name(name).ok();
+    }
+    if let Ok(pe) = peek.into_enum() {
+        if pe.variant_name_active().ok()? == name {
+            return Some(peek);
+        }
+        return pe.field_by_name(name).ok().flatten();
+    }
+    if let Ok(pm) = peek.into_map() {
+        return pm
+            .iter()
+            .find(|(key, _)| key_matches(*key, name))
+            .map(|(_, value)| value);
+    }
+    None
+}
+
+fn key_matches(key: Peek<'_, '_, '_>, name: &str) -> bool {
+    let key = resolve(key).unwrap_or(key);
+    match key.as_str() {
+        Some(s) => s == name,
+        None => key.to_string() == name,
+    }
+}
+
+fn child_by_index<'mem, 'facet, 'shape>(
+    peek: Peek<'mem, 'facet, 'shape>,
+    index: isize,
+) -> Option<Peek<'mem, 'facet, 'shape>> {
+    let at = |len: usize| {
+        let index = if index < 0 {
+            len.checked_sub(index.unsigned_abs())?
+        } else {
+            index.unsigned_abs()
+        };
+        (index < len).then_some(index)
+    };
+    if let Ok(list) = peek.into_list_like() {
+        return list.get(at(list.len())?);
+    }
+    if let Ok(ps) = peek.into_struct() {
+        if matches!(ps.ty().kind, StructKind::Tuple | StructKind::TupleStruct) {
+            return ps.field(at(ps.field_count())?).ok();
+        }
+    }
+    None
+}
+
+fn children<'mem, 'facet, 'shape>(
+    peek: Peek<'mem, 'facet, 'shape>,
+) -> Vec<Peek<'mem, 'facet, 'shape>> {
+    if let Ok(list) = peek.into_list_like() {
+        return list.iter().collect();
+    }
+    if let Ok(pm) = peek.into_map() {
+        return pm.iter().map(|(_, value)| value).collect();
+    }
+    if let Ok(ps) = peek.into_struct() {
+        return ps.fields().map(|(_, value)| value).collect();
+    }
+    if let Ok(pe) = peek.into_enum() {
+        return pe.fields().map(|(_, value)| value).collect();
+    }
+    Vec::new()
+}
+
+impl Filter {
+    fn holds(&self, peek: Peek<'_, '_, '_>) -> bool {
+        let mut found = Vec::new();
+        select(peek, &self.path, &mut found);
+        let Some((op, literal)) = &self.test else {
+            return found.into_iter().any(|value| resolve(value).is_some());
+        };
+        if found.is_empty() && *literal == Literal::Null {
+            return *op == Op::Eq;
+        }
+        found.into_iter().any(|value| {
+            let ordering = compare(value, literal);
+            match op {
+                Op::Eq => ordering == Some(Ordering::Equal),
+                Op::Ne => ordering != Some(Ordering::Equal),
+                Op::Lt => ordering == Some(Ordering::Less),
+                Op::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                Op::Gt => ordering == Some(Ordering::Greater),
+                Op::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+            }
+        })
+    }
+}
+
+/// Compares a value with a literal, if they're of comparable kinds
+fn compare(peek: Peek<'_, '_, '_>, literal: &Literal) -> Option<Ordering> {
+    let Some(value) = resolve(peek) else {
+        return (*literal == Literal::Null).then_some(Ordering::Equal);
+    };
+    match literal {
+        Literal::Null => Some(Ordering::Greater),
+        Literal::Bool(b) => value.get::<bool>().ok().map(|v| v.cmp(b)),
+        Literal::Number(n) => number(value)?.partial_cmp(n),
+        Literal::String(s) => {
+            if let Some(v) = value.as_str() {
+                return Some(v.cmp(s.as_str()));
+            }
+            if let Ok(&c) = value.get::<char>() {
+                return Some(c.to_string().as_str().cmp(s.as_str()));
+            }
+            let pe = value.into_enum().ok()?;
+            let variant = pe.active_variant().ok()?;
+            variant
+                .data
+                .fields
+                .is_empty()
+                .then(|| variant.name.cmp(s.as_str()))
+        }
+    }
+}
+
+fn number(peek: Peek<'_, '_, '_>) -> Option<f64> {
+    Some(match peek.scalar_type()? {
+        ScalarType::F32 => *peek.get::<f32>().ok()? as f64,
+        ScalarType::F64 => *peek.get::<f64>().ok()?,
+        ScalarType::U8 => *peek.get::<u8>().ok()? as f64,
+        ScalarType::U16 => *peek.get::<u16>().ok()? as f64,
+        ScalarType::U32 => *peek.get::<u32>().ok()? as f64,
+        ScalarType::U64 => *peek.get::<u64>().ok()? as f64,
+        ScalarType::U128 => *peek.get::<u128>().ok()? as f64,
+        ScalarType::USize => *peek.get::<usize>().ok()? as f64,
+        ScalarType::I8 => *peek.get::<i8>().ok()? as f64,
+        ScalarType::I16 => *peek.get::<i16>().ok()? as f64,
+        ScalarType::I32 => *peek.get::<i32>().ok()? as f64,
+        ScalarType::I64 => *peek.get::<i64>().ok()? as f64,
+        ScalarType::I128 => *peek.get::<i128>().ok()? as f64,
+        ScalarType::ISize => *peek.get::<isize>().ok()? as f64,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_steps() {
+        let query = Query::parse("$.users[*]['first name'][-1].0[?(@.age >= 18)]").unwrap();
+        assert_eq!(
+            query.steps,
+            [
+                Step::Name("users".into()),
+                Step::Wildcard,
+                Step::Name("first name".into()),
+                Step::Index(-1),
+                Step::Index(0),
+                Step::Filter(Filter {
+                    path: alloc::vec![Step::Name("age".into())],
+                    test: Some((Op::Ge, Literal::Number(18.0))),
+                }),
+            ]
+        );
+        assert_eq!(Query::parse("").unwrap().steps, []);
+        assert_eq!(
+            Query::parse("$[?@.name == \"a\\\"b\"]").unwrap().steps,
+            [Step::Filter(Filter {
+                path: alloc::vec![Step::Name("name".into())],
+                test: Some((Op::Eq, Literal::String("a\"b".into()))),
+            })]
+        );
+    }
+
+    #[test]
+    fn reports_where_parsing_failed() {
+        assert_eq!(
+            Query::parse("$.a[").unwrap_err(),
+            QueryError {
+                position: 4,
+                expected: "`*`, `?`, a string or an index",
+            }
+        );
+        assert_eq!(Query::parse("$.").unwrap_err().position, 2);
+        assert_eq!(Query::parse("$a").unwrap_err().position, 1);
+        assert_eq!(Query::parse("$['a").unwrap_err().position, 4);
+        assert_eq!(Query::parse("$[?(@.a == )]").unwrap_err().position, 11);
+    }
+}
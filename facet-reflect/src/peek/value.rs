@@ -176,41 +176,115 @@ impl<'mem, 'facet, 'shape> Peek<'mem, 'facet, 'shape> {
         self.data.as_byte_ptr() == other.data.as_byte_ptr()
     }
 
-    /// Returns true if this scalar is equal to the other scalar
+    /// Returns true if this value is equal to the other value
     ///
     /// # Returns
     ///
-    /// `false` if equality comparison is not supported for this scalar type
+    /// `None` if the two values have different shapes, or if equality
+    /// comparison is not supported for this type
     #[inline]
     pub fn partial_eq(&self, other: &Peek<'_, '_, '_>) -> Option<bool> {
+        if self.shape != other.shape {
+            return None;
+        }
         match (self.data, other.data) {
             (GenericPtr::Thin(a), GenericPtr::Thin(b)) => unsafe {
-                (self.vtable().sized().unwrap().partial_eq)().map(|f| f(a, b))
+                (self.vtable().sized()?.partial_eq)().map(|f| f(a, b))
             },
             (GenericPtr::Wide(a), GenericPtr::Wide(b)) => unsafe {
-                (self.vtable().r#unsized().unwrap().partial_eq)().map(|f| f(a, b))
+                (self.vtable().r#unsized()?.partial_eq)().map(|f| f(a, b))
             },
             _ => None,
         }
     }
 
-    /// Compares this scalar with another and returns their ordering
+    /// Compares this value with another and returns their ordering
     ///
     /// # Returns
     ///
-    /// `None` if comparison is not supported for this scalar type
+    /// `None` if the two values have different shapes, or if comparison is
+    /// not supported for this type
     #[inline]
-    pub fn partial_cmp(&self, other: &Peek<'_, '_, '_>) -> Option<Option<Ordering>> {
+    pub fn partial_ord(&self, other: &Peek<'_, '_, '_>) -> Option<Option<Ordering>> {
+        if self.shape != other.shape {
+            return None;
+        }
         match (self.data, other.data) {
             (GenericPtr::Thin(a), GenericPtr::Thin(b)) => unsafe {
-                (self.vtable().sized().unwrap().partial_ord)().map(|f| f(a, b))
+                (self.vtable().sized()?.partial_ord)().map(|f| f(a, b))
             },
             (GenericPtr::Wide(a), GenericPtr::Wide(b)) => unsafe {
-                (self.vtable().r#unsized().unwrap().partial_ord)().map(|f| f(a, b))
+                (self.vtable().r#unsized()?.partial_ord)().map(|f| f(a, b))
             },
             _ => None,
         }
     }
+
+    /// Same as [`Peek::partial_ord`]
+    #[inline]
+    pub fn partial_cmp(&self, other: &Peek<'_, '_, '_>) -> Option<Option<Ordering>> {
+        self.partial_ord(other)
+    }
+
+    /// Returns a value that formats this one with its `Display` implementation,
+    /// or `None` if the type doesn't implement `Display`.
+    ///
+    /// Unlike formatting the `Peek` itself, this never falls back to printing
+    /// the shape.
+    pub fn display(&self) -> Option<PeekDisplay<'mem, 'facet, 'shape>> {
+        let supported = match self.data {
+            GenericPtr::Thin(_) => self.vtable().sized().and_then(|v| (v.display)()).is_some(),
+            GenericPtr::Wide(_) => self
+                .vtable()
+                .r#unsized()
+                .and_then(|v| (v.display)())
+                .is_some(),
+        };
+        supported.then_some(PeekDisplay(*self))
+    }
+
+    /// Returns a value that formats this one with its `Debug` implementation,
+    /// or `None` if the type doesn't implement `Debug`.
+    pub fn debug(&self) -> Option<PeekDebug<'mem, 'facet, 'shape>> {
+        let supported = match self.data {
+            GenericPtr::Thin(_) => self.vtable().sized().and_then(|v| (v.debug)()).is_some(),
+            GenericPtr::Wide(_) => self
+                .vtable()
+                .r#unsized()
+                .and_then(|v| (v.debug)())
+                .is_some(),
+        };
+        supported.then_some(PeekDebug(*self))
+    }
+
+    /// Formats with the vtable's `Display`, if there is one
+    fn fmt_display(&self, f: &mut core::fmt::Formatter<'_>) -> Option<core::fmt::Result> {
+        match self.data {
+            GenericPtr::Thin(ptr) => {
+                let display_fn = (self.vtable().sized()?.display)()?;
+                Some(unsafe { display_fn(ptr, f) })
+            }
+            GenericPtr::Wide(ptr) => {
+                let display_fn = (self.vtable().r#unsized()?.display)()?;
+                Some(unsafe { display_fn(ptr, f) })
+            }
+        }
+    }
+
+    /// Formats with the vtable's `Debug`, if there is one
+    fn fmt_debug(&self, f: &mut core::fmt::Formatter<'_>) -> Option<core::fmt::Result> {
+        match self.data {
+            GenericPtr::Thin(ptr) => {
+                let debug_fn = (self.vtable().sized()?.debug)()?;
+                Some(unsafe { debug_fn(ptr, f) })
+            }
+            GenericPtr::Wide(ptr) => {
+                let debug_fn = (self.vtable().r#unsized()?.debug)()?;
+                Some(unsafe { debug_fn(ptr, f) })
+            }
+        }
+    }
+
     /// Hashes this scalar
     ///
     /// # Returns
@@ -305,14 +379,31 @@ impl<'mem, 'facet, 'shape> Peek<'mem, 'facet, 'shape> {
     /// Returns None if the value is not a string or couldn't be extracted
     pub fn as_str(&self) -> Option<&'mem str> {
         let peek = self.innermost_peek();
+        #[cfg(feature = "alloc")]
+        match peek.scalar_type() {
+            Some(ScalarType::ArcStr) => {
+                return unsafe { Some(peek.data.get::<alloc::sync::Arc<str>>()) };
+            }
+            Some(ScalarType::RcStr) => {
+                return unsafe { Some(peek.data.get::<alloc::rc::Rc<str>>()) };
+            }
+            Some(ScalarType::BoxStr) => {
+                return unsafe { Some(peek.data.get::<alloc::boxed::Box<str>>()) };
+            }
+            _ => {}
+        }
         if let Some(ScalarType::Str) = peek.scalar_type() {
             unsafe { Some(peek.data.get::<&str>()) }
         } else if let Some(ScalarType::String) = peek.scalar_type() {
-            unsafe { Some(peek.data.get::<alloc::string::String>().as_str()) }
+            #[cfg(feature = "alloc")]
+            return unsafe { Some(peek.data.get::<alloc::string::String>().as_str()) };
+            #[cfg(not(feature = "alloc"))]
+            unreachable!("`String` can't be recognized without `alloc`")
         } else if let Type::Pointer(PointerType::Reference(vpt)) = peek.shape.ty {
+            // A reference to a string, like `&&str`
             let target_shape = (vpt.target)();
-            if let Some(ScalarType::Str) = ScalarType::try_from_shape(target_shape) {
-                unsafe { Some(peek.data.get::<&str>()) }
+            if ScalarType::try_from_shape(target_shape).is_some() {
+                peek.into_pointee().ok()?.as_str()
             } else {
                 None
             }
@@ -321,9 +412,28 @@ impl<'mem, 'facet, 'shape> Peek<'mem, 'facet, 'shape> {
         }
     }
 
-    /// Try to get the value as a byte slice if it's a &[u8] type
+    /// Returns the value as text, like formats whose map keys are strings need: the string
+    /// itself for string types, or what the `Display` implementation writes for others.
+    /// Returns `None` if the type is neither.
+    #[cfg(feature = "alloc")]
+    pub fn as_text(&self) -> Option<alloc::borrow::Cow<'mem, str>> {
+        match self.as_str() {
+            Some(s) => Some(alloc::borrow::Cow::Borrowed(s)),
+            None => Some(alloc::borrow::Cow::Owned(alloc::format!(
+                "{}",
+                self.display()?
+            ))),
+        }
+    }
+
+    /// Try to get the value as a byte slice if it's a &[u8] or Cow<[u8]> type
     /// Returns None if the value is not a byte slice or couldn't be extracted
     pub fn as_bytes(&self) -> Option<&'mem [u8]> {
+        #[cfg(feature = "alloc")]
+        if self.shape.is_type::<alloc::borrow::Cow<'_, [u8]>>() {
+            return unsafe { Some(self.data.get::<alloc::borrow::Cow<'_, [u8]>>().as_ref()) };
+        }
+
         // Check if it's a direct &[u8]
         if let Type::Pointer(PointerType::Reference(vpt)) = self.shape.ty {
             let target_shape = (vpt.target)();
@@ -437,6 +547,35 @@ impl<'mem, 'facet, 'shape> Peek<'mem, 'facet, 'shape> {
         }
     }
 
+    /// Follows a reference (`&T`, `&mut T`) to the value it points to, which may be unsized,
+    /// like the `[&str]` behind a `&[&str]`
+    pub fn into_pointee(self) -> Result<Self, ReflectError<'shape>> {
+        let Type::Pointer(PointerType::Reference(vpt)) = self.shape.ty else {
+            return Err(ReflectError::WasNotA {
+                expected: "reference",
+                actual: self.shape,
+            });
+        };
+        let Some(ptr) = self.data.thin() else {
+            return Err(ReflectError::WasNotA {
+                expected: "sized reference",
+                actual: self.shape,
+            });
+        };
+
+        // The reference itself is stored at `ptr`, wide if its target is unsized
+        let data = if vpt.wide {
+            GenericPtr::Wide(unsafe { *ptr.as_ptr::<PtrConstWide<'mem>>() })
+        } else {
+            GenericPtr::Thin(PtrConst::new(unsafe { *ptr.as_ptr::<*const u8>() }))
+        };
+        Ok(Peek {
+            data,
+            shape: (vpt.target)(),
+            invariant: PhantomData,
+        })
+    }
+
     /// Tries to identify this value as an option
     pub fn into_option(
         self,
@@ -509,37 +648,39 @@ impl<'mem, 'facet, 'shape> Peek<'mem, 'facet, 'shape> {
 
 impl<'mem, 'facet, 'shape> core::fmt::Display for Peek<'mem, 'facet, 'shape> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        match self.data {
-            GenericPtr::Thin(ptr) => {
-                if let Some(display_fn) = (self.vtable().sized().unwrap().display)() {
-                    return unsafe { display_fn(ptr, f) };
-                }
-            }
-            GenericPtr::Wide(ptr) => {
-                if let Some(display_fn) = (self.vtable().r#unsized().unwrap().display)() {
-                    return unsafe { display_fn(ptr, f) };
-                }
-            }
-        }
-        write!(f, "⟨{}⟩", self.shape)
+        self.fmt_display(f)
+            .unwrap_or_else(|| write!(f, "⟨{}⟩", self.shape))
     }
 }
 
 impl<'mem, 'facet, 'shape> core::fmt::Debug for Peek<'mem, 'facet, 'shape> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        match self.data {
-            GenericPtr::Thin(ptr) => {
-                if let Some(debug_fn) = (self.vtable().sized().unwrap().debug)() {
-                    return unsafe { debug_fn(ptr, f) };
-                }
-            }
-            GenericPtr::Wide(ptr) => {
-                if let Some(debug_fn) = (self.vtable().r#unsized().unwrap().debug)() {
-                    return unsafe { debug_fn(ptr, f) };
-                }
-            }
-        }
-        write!(f, "⟨{}⟩", self.shape)
+        self.fmt_debug(f)
+            .unwrap_or_else(|| write!(f, "⟨{}⟩", self.shape))
+    }
+}
+
+/// Formats a value with its `Display` implementation, see [`Peek::display`]
+#[derive(Clone, Copy)]
+pub struct PeekDisplay<'mem, 'facet, 'shape>(Peek<'mem, 'facet, 'shape>);
+
+impl core::fmt::Display for PeekDisplay<'_, '_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0
+            .fmt_display(f)
+            .expect("PeekDisplay is only made for types that implement Display")
+    }
+}
+
+/// Formats a value with its `Debug` implementation, see [`Peek::debug`]
+#[derive(Clone, Copy)]
+pub struct PeekDebug<'mem, 'facet, 'shape>(Peek<'mem, 'facet, 'shape>);
+
+impl core::fmt::Debug for PeekDebug<'_, '_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0
+            .fmt_debug(f)
+            .expect("PeekDebug is only made for types that implement Debug")
     }
 }
 
@@ -551,7 +692,7 @@ impl<'mem, 'facet, 'shape> core::cmp::PartialEq for Peek<'mem, 'facet, 'shape> {
 
 impl<'mem, 'facet, 'shape> core::cmp::PartialOrd for Peek<'mem, 'facet, 'shape> {
     fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
-        self.partial_cmp(other).unwrap_or(None)
+        self.partial_ord(other).unwrap_or(None)
     }
 }
 
@@ -0,0 +1,263 @@
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use facet_core::{Def, KnownSmartPointer, Shape, StructKind, Type, UserType};
+
+use crate::{HasFields, HeapValue, Partial, Peek, ReflectError};
+
+/// What [`redact`] replaces: fields marked `#[facet(sensitive)]`, and values at the paths it's
+/// given.
+///
+/// A path names fields, list items and map entries, separated by dots, as in `users.0.email` or
+/// `headers.authorization`; `*` stands for any one of them, as in `users.*.email`. The fields
+/// of an enum's active variant are named as if they were the enum's own.
+///
+/// Replaced strings, `Box<str>` and the like included, become the placeholder, `"[REDACTED]"`
+/// unless told otherwise. Other values become their default, so a number becomes `0` and an
+/// `Option` becomes `None`.
+#[derive(Clone, Debug)]
+pub struct RedactPolicy {
+    placeholder: String,
+    sensitive: bool,
+    paths: Vec<String>,
+}
+
+impl Default for RedactPolicy {
+    fn default() -> Self {
+        Self {
+            placeholder: "[REDACTED]".to_string(),
+            sensitive: true,
+            paths: Vec::new(),
+        }
+    }
+}
+
+impl RedactPolicy {
+    /// A policy that replaces the fields marked `#[facet(sensitive)]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces strings with `placeholder`.
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Replaces the values at `path` too.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// Whether fields marked `#[facet(sensitive)]` are replaced, which they are by default.
+    pub fn with_sensitive(mut self, sensitive: bool) -> Self {
+        self.sensitive = sensitive;
+        self
+    }
+
+    fn matches(&self, path: &[String]) -> bool {
+        self.paths.iter().any(|pattern| {
+            let mut segments = pattern.split('.');
+            path.iter()
+                .all(|step| segments.next().is_some_and(|s| s == "*" || s == step))
+                && segments.next().is_none()
+        })
+    }
+}
+
+/// Copies the value behind `peek`, replacing what `policy` says to, for a copy that's safe to
+/// log or export.
+///
+/// ```
+/// # use facet::Facet;
+/// # use facet_reflect::{Peek, RedactPolicy};
+/// #[derive(Facet)]
+/// struct Account {
+///     user: String,
+///     #[facet(sensitive)]
+///     password: String,
+///     pin: u16,
+/// }
+///
+/// let account = Account { user: "ada".into(), password: "hunter2".into(), pin: 1234 };
+/// let policy = RedactPolicy::new().with_path("pin");
+/// let redacted = facet_reflect::redact(Peek::new(&account), &policy)?;
+/// let redacted: Account = redacted.materialize()?;
+/// assert_eq!(redacted.user, "ada");
+/// assert_eq!(redacted.password, "[REDACTED]");
+/// assert_eq!(redacted.pin, 0);
+/// # Ok::<(), facet_reflect::ReflectError>(())
+/// ```
+///
+/// Values that aren't taken apart, like strings and numbers, are copied with their `Clone`
+/// implementation, and replaced ones are built with their `Default` implementation: a value
+/// without the one it needs gives an error.
+pub fn redact<'facet, 'shape>(
+    peek: Peek<'_, 'facet, 'shape>,
+    policy: &RedactPolicy,
+) -> Result<HeapValue<'facet, 'shape>, ReflectError<'shape>> {
+    let mut partial = Partial::alloc_shape(peek.shape())?;
+    copy(&mut partial, peek, policy, &mut Vec::new())?;
+    partial.build()
+}
+
+/// Sets the current frame of `partial` to a redacted copy of `peek`, found at `path`
+fn copy<'shape>(
+    partial: &mut Partial<'_, 'shape>,
+    peek: Peek<'_, '_, 'shape>,
+    policy: &RedactPolicy,
+    path: &mut Vec<String>,
+) -> Result<(), ReflectError<'shape>> {
+    if !path.is_empty() && policy.matches(path) {
+        return replace(partial, policy);
+    }
+
+    match peek.shape().def {
+        Def::Option(_) => {
+            match peek.into_option()?.value() {
+                Some(value) => {
+                    partial.begin_some()?;
+                    copy(partial, value, policy, path)?;
+                    partial.end()?;
+                }
+                None => {
+                    partial.set_default()?;
+                }
+            }
+            return Ok(());
+        }
+        Def::SmartPointer(_) if is_buildable_pointer(peek.shape()) => {
+            if let Some(inner) = peek.into_smart_pointer()?.borrow_inner() {
+                partial.begin_smart_ptr()?;
+                copy(partial, inner, policy, path)?;
+                partial.end()?;
+                return Ok(());
+            }
+        }
+        Def::List(_) => {
+            partial.begin_list()?;
+            for (i, item) in peek.into_list()?.iter().enumerate() {
+                partial.begin_list_item()?;
+                nested(partial, item, policy, path, i.to_string())?;
+                partial.end()?;
+            }
+            return Ok(());
+        }
+        Def::Array(_) => {
+            for (i, item) in peek.into_list_like()?.iter().enumerate() {
+                partial.begin_nth_element(i)?;
+                nested(partial, item, policy, path, i.to_string())?;
+                partial.end()?;
+            }
+            return Ok(());
+        }
+        Def::Map(_) => {
+            partial.begin_map()?;
+            for (key, value) in peek.into_map()?.iter() {
+                partial.begin_key()?;
+                partial.clone_from_peek(&key)?;
+                partial.end()?;
+                partial.begin_value()?;
+                let segment = match key.as_str() {
+                    Some(s) => s.to_string(),
+                    None => key.to_string(),
+                };
+                nested(partial, value, policy, path, segment)?;
+                partial.end()?;
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    match peek.shape().ty {
+        Type::User(UserType::Struct(ty))
+            if ty.kind != StructKind::Unit && !ty.fields.is_empty() =>
+        {
+            for (i, (meta, field)) in peek.into_struct()?.fields_with_meta().enumerate() {
+                partial.begin_nth_field(i)?;
+                if policy.sensitive && meta.is_sensitive() {
+                    replace(partial, policy)?;
+                } else {
+                    nested(partial, field, policy, path, meta.name().to_string())?;
+                }
+                partial.end()?;
+            }
+        }
+        Type::User(UserType::Enum(_)) => {
+            let pe = peek.into_enum()?;
+            let variant = pe
+                .active_variant()
+                .map_err(|_| ReflectError::OperationFailed {
+                    shape: peek.shape(),
+                    operation: "could not read the active variant",
+                })?;
+            partial.select_variant_named(variant.name)?;
+            for (i, (meta, field)) in pe.fields_with_meta().enumerate() {
+                partial.begin_nth_enum_field(i)?;
+                if policy.sensitive && meta.is_sensitive() {
+                    replace(partial, policy)?;
+                } else {
+                    nested(partial, field, policy, path, meta.name().to_string())?;
+                }
+                partial.end()?;
+            }
+        }
+        _ => {
+            partial.clone_from_peek(&peek)?;
+        }
+    }
+    Ok(())
+}
+
+/// Copies `peek` one step further down `path`
+fn nested<'shape>(
+    partial: &mut Partial<'_, 'shape>,
+    peek: Peek<'_, '_, 'shape>,
+    policy: &RedactPolicy,
+    path: &mut Vec<String>,
+    segment: String,
+) -> Result<(), ReflectError<'shape>> {
+    path.push(segment);
+    let result = copy(partial, peek, policy, path);
+    path.pop();
+    result
+}
+
+/// Sets the current frame to the placeholder if it's a string, or to its default, looking
+/// through boxes
+fn replace<'shape>(
+    partial: &mut Partial<'_, 'shape>,
+    policy: &RedactPolicy,
+) -> Result<(), ReflectError<'shape>> {
+    let shape = partial.shape();
+    if shape.is_type::<String>() {
+        partial.set(policy.placeholder.clone())?;
+    } else if shape.is_type::<Box<str>>() {
+        partial.set(Box::<str>::from(policy.placeholder.as_str()))?;
+    } else if shape.is_type::<Arc<str>>() {
+        partial.set(Arc::<str>::from(policy.placeholder.as_str()))?;
+    } else if shape.is_type::<Rc<str>>() {
+        partial.set(Rc::<str>::from(policy.placeholder.as_str()))?;
+    } else if is_buildable_pointer(shape) {
+        partial.begin_smart_ptr()?;
+        replace(partial, policy)?;
+        partial.end()?;
+    } else {
+        partial.set_default()?;
+    }
+    Ok(())
+}
+
+/// Whether the smart pointer can be built around its pointee with `begin_smart_ptr`
+fn is_buildable_pointer(shape: &Shape<'_>) -> bool {
+    matches!(
+        shape.def,
+        Def::SmartPointer(def)
+            if matches!(def.known, Some(KnownSmartPointer::Box | KnownSmartPointer::Arc))
+    )
+}
@@ -0,0 +1,83 @@
+//! A process-wide registry of shapes, looked up by type name.
+//!
+//! Register the types a program knows about once, then find their shape from
+//! a name read at runtime, for instance to deserialize into the type named by
+//! a field of a message, and build a value of it with [`Partial::alloc_shape`](crate::Partial::alloc_shape).
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_reflect::{Partial, registry};
+//!
+//! #[derive(Facet)]
+//! struct Ping {
+//!     seq: u32,
+//! }
+//!
+//! registry::register::<Ping>();
+//! registry::register_as("port", <u16 as Facet>::SHAPE);
+//!
+//! let ping = registry::lookup(core::any::type_name::<Ping>()).unwrap();
+//! assert!(ping.is_type::<Ping>());
+//!
+//! let port = registry::lookup("port").unwrap();
+//! let mut partial = Partial::alloc_shape(port).unwrap();
+//! partial.parse_from_str("8080").unwrap();
+//! assert_eq!(partial.build().unwrap().to_string(), "8080");
+//! ```
+
+use alloc::vec::Vec;
+use facet_core::{Facet, Shape};
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, PoisonError, RwLock},
+};
+
+type Shapes = HashMap<&'static str, &'static Shape<'static>>;
+
+fn shapes() -> &'static RwLock<Shapes> {
+    static SHAPES: OnceLock<RwLock<Shapes>> = OnceLock::new();
+    SHAPES.get_or_init(Default::default)
+}
+
+/// Registers `T` under its fully-qualified type name, as returned by
+/// [`core::any::type_name`], e.g. `my_crate::messages::Ping`.
+///
+/// Returns the shape previously registered under that name, if any.
+pub fn register<'a, T: Facet<'a> + ?Sized>() -> Option<&'static Shape<'static>> {
+    register_as(core::any::type_name::<T>(), T::SHAPE)
+}
+
+/// Registers `shape` under `name`, which can be any string: a type name, or
+/// a tag used by some protocol.
+///
+/// Returns the shape previously registered under that name, if any.
+pub fn register_as(
+    name: &'static str,
+    shape: &'static Shape<'static>,
+) -> Option<&'static Shape<'static>> {
+    shapes()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(name, shape)
+}
+
+/// Returns the shape registered under `name`.
+pub fn lookup(name: &str) -> Option<&'static Shape<'static>> {
+    shapes()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(name)
+        .copied()
+}
+
+/// Returns every registered name and shape, sorted by name.
+pub fn registered() -> Vec<(&'static str, &'static Shape<'static>)> {
+    let mut all: Vec<_> = shapes()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .map(|(&name, &shape)| (name, shape))
+        .collect();
+    all.sort_unstable_by_key(|&(name, _)| name);
+    all
+}
@@ -18,6 +18,12 @@ pub enum ScalarType {
     String,
     /// `alloc::borrow::Cow<'_, str>`.
     CowStr,
+    /// `alloc::sync::Arc<str>`.
+    ArcStr,
+    /// `alloc::rc::Rc<str>`.
+    RcStr,
+    /// `alloc::boxed::Box<str>`.
+    BoxStr,
     /// Primitive type `f32`.
     F32,
     /// Primitive type `f64`.
@@ -66,6 +72,12 @@ impl ScalarType {
             return Some(ScalarType::String);
         } else if shape.id == ConstTypeId::of::<alloc::borrow::Cow<'_, str>>() {
             return Some(ScalarType::CowStr);
+        } else if shape.id == ConstTypeId::of::<alloc::sync::Arc<str>>() {
+            return Some(ScalarType::ArcStr);
+        } else if shape.id == ConstTypeId::of::<alloc::rc::Rc<str>>() {
+            return Some(ScalarType::RcStr);
+        } else if shape.id == ConstTypeId::of::<alloc::boxed::Box<str>>() {
+            return Some(ScalarType::BoxStr);
         } else if shape.id == ConstTypeId::of::<core::net::SocketAddr>() {
             return Some(ScalarType::SocketAddr);
         }
@@ -151,7 +163,22 @@ mod tests {
         #[cfg(feature = "std")]
         assert_eq!(
             ScalarType::CowStr,
-            ScalarType::try_from_shape(alloc::borrow::Cow::SHAPE).unwrap()
+            ScalarType::try_from_shape(alloc::borrow::Cow::<str>::SHAPE).unwrap()
+        );
+        #[cfg(feature = "std")]
+        assert_eq!(
+            ScalarType::ArcStr,
+            ScalarType::try_from_shape(alloc::sync::Arc::<str>::SHAPE).unwrap()
+        );
+        #[cfg(feature = "std")]
+        assert_eq!(
+            ScalarType::RcStr,
+            ScalarType::try_from_shape(alloc::rc::Rc::<str>::SHAPE).unwrap()
+        );
+        #[cfg(feature = "std")]
+        assert_eq!(
+            ScalarType::BoxStr,
+            ScalarType::try_from_shape(alloc::boxed::Box::<str>::SHAPE).unwrap()
         );
         assert_eq!(
             ScalarType::F32,
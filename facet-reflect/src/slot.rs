@@ -0,0 +1,324 @@
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+
+use facet_core::{Def, Facet, Field, PtrUninit, Shape, Type, UserType};
+
+use crate::{ReflectError, ScalarType};
+
+/// A value being built directly in storage provided by the caller, without allocating.
+///
+/// Unlike [`Partial`](crate::Partial), a slot works without `alloc`, but only for plain data:
+/// structs, tuples and arrays whose leaves are numbers, `bool`, `char`, `()` or `&str`. None of
+/// those need dropping, so abandoning a slot halfway through leaks nothing.
+///
+/// Fields and elements are entered with `begin_*` and left with [`Slot::end`], like with
+/// `Partial`. `DEPTH` is how many levels of nesting the value may have, counting itself.
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use facet_reflect::Slot;
+///
+/// let mut storage = MaybeUninit::<(u8, [u16; 2])>::uninit();
+/// let mut slot = Slot::<_, 4>::new(&mut storage).unwrap();
+/// slot.begin_nth_field(0).unwrap().set(1u8).unwrap().end().unwrap();
+/// slot.begin_nth_field(1).unwrap();
+/// for i in 0..2 {
+///     slot.begin_nth_element(i).unwrap().set(10u16).unwrap().end().unwrap();
+/// }
+/// slot.end().unwrap();
+/// assert_eq!(*slot.build().unwrap(), (1, [10, 10]));
+/// ```
+pub struct Slot<'mem, 'facet, T: Facet<'facet>, const DEPTH: usize = 8> {
+    frames: [Option<Frame<'mem>>; DEPTH],
+    len: usize,
+    storage: PhantomData<&'mem mut T>,
+    invariant: PhantomData<fn(&'facet ()) -> &'facet ()>,
+}
+
+#[derive(Clone, Copy)]
+struct Frame<'mem> {
+    data: PtrUninit<'mem>,
+    shape: &'static Shape<'static>,
+    kind: Kind,
+    /// For structs, one bit per initialized field. For arrays, the number of initialized
+    /// elements (which are filled in order). For scalars, 1 once set.
+    filled: u64,
+    /// Which field or element of the parent this is
+    index: usize,
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Scalar,
+    Struct(&'static [Field<'static>]),
+    Array {
+        len: usize,
+        item: &'static Shape<'static>,
+    },
+}
+
+impl Kind {
+    fn of(shape: &'static Shape<'static>) -> Result<Self, ReflectError<'static>> {
+        use ScalarType::*;
+
+        if let Some(scalar) = ScalarType::try_from_shape(shape) {
+            return match scalar {
+                Unit | Bool | Char | Str | F32 | F64 | U8 | U16 | U32 | U64 | U128 | USize | I8
+                | I16 | I32 | I64 | I128 | ISize => Ok(Kind::Scalar),
+                _ => Err(unsupported(shape)),
+            };
+        }
+        if let Def::Array(def) = shape.def {
+            return Ok(Kind::Array {
+                len: def.n,
+                item: def.t,
+            });
+        }
+        match shape.ty {
+            Type::User(UserType::Struct(st)) if st.fields.len() <= 64 => {
+                Ok(Kind::Struct(st.fields))
+            }
+            _ => Err(unsupported(shape)),
+        }
+    }
+
+    /// The value of `filled` once everything is initialized
+    fn complete(self) -> u64 {
+        match self {
+            Kind::Scalar => 1,
+            Kind::Struct(fields) if fields.len() == 64 => u64::MAX,
+            Kind::Struct(fields) => (1 << fields.len()) - 1,
+            Kind::Array { len, .. } => len as u64,
+        }
+    }
+}
+
+fn unsupported(shape: &'static Shape<'static>) -> ReflectError<'static> {
+    ReflectError::OperationFailed {
+        shape,
+        operation: "only numbers, bool, char, &str, and structs and arrays of them (with at most 64 fields) can be built in a slot",
+    }
+}
+
+/// Checks that every part of `shape` can be built in a slot, nesting at most `depth` levels.
+fn check(shape: &'static Shape<'static>, depth: usize) -> Result<(), ReflectError<'static>> {
+    if depth == 0 {
+        return Err(ReflectError::OperationFailed {
+            shape,
+            operation: "the value is nested too deeply for this slot",
+        });
+    }
+    match Kind::of(shape)? {
+        Kind::Scalar => Ok(()),
+        Kind::Struct(fields) => fields
+            .iter()
+            .try_for_each(|field| check(field.shape(), depth - 1)),
+        Kind::Array { item, .. } => check(item, depth - 1),
+    }
+}
+
+impl<'mem, 'facet, T: Facet<'facet>, const DEPTH: usize> Slot<'mem, 'facet, T, DEPTH> {
+    /// Prepares to build a `T` in `storage`, checking that it can be built in a slot.
+    pub fn new(storage: &'mem mut MaybeUninit<T>) -> Result<Self, ReflectError<'static>> {
+        check(T::SHAPE, DEPTH)?;
+        let mut frames = [None; DEPTH];
+        frames[0] = Some(Frame {
+            data: PtrUninit::from_maybe_uninit(storage),
+            shape: T::SHAPE,
+            kind: Kind::of(T::SHAPE)?,
+            filled: 0,
+            index: 0,
+        });
+        Ok(Self {
+            frames,
+            len: 1,
+            storage: PhantomData,
+            invariant: PhantomData,
+        })
+    }
+
+    fn frame(&self) -> &Frame<'mem> {
+        self.frames[self.len - 1].as_ref().unwrap()
+    }
+
+    fn frame_mut(&mut self) -> &mut Frame<'mem> {
+        self.frames[self.len - 1].as_mut().unwrap()
+    }
+
+    /// Returns the shape of the value currently being built.
+    pub fn shape(&self) -> &'static Shape<'static> {
+        self.frame().shape
+    }
+
+    /// Returns how deeply nested the value currently being built is; 1 for the slot's own value.
+    pub fn frame_count(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the index of the field called `name`, if the current value is a struct.
+    pub fn field_index(&self, name: &str) -> Option<usize> {
+        match self.frame().kind {
+            Kind::Struct(fields) => fields.iter().position(|field| field.name == name),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the current value is a struct whose `index`th field is initialized.
+    pub fn is_field_set(&self, index: usize) -> bool {
+        match self.frame().kind {
+            Kind::Struct(_) if index < 64 => self.frame().filled & (1 << index) != 0,
+            _ => false,
+        }
+    }
+
+    /// Sets the current value.
+    pub fn set<U: Facet<'facet>>(&mut self, value: U) -> Result<&mut Self, ReflectError<'static>> {
+        let frame = self.frame_mut();
+        if !frame.shape.is_shape(U::SHAPE) {
+            return Err(ReflectError::WrongShape {
+                expected: frame.shape,
+                actual: U::SHAPE,
+            });
+        }
+        // SAFETY: the shapes match, so `data` points to storage for a `U`. Whatever was there
+        // before doesn't need dropping, since `new` only accepts types that never do.
+        unsafe { frame.data.put(value) };
+        frame.filled = frame.kind.complete();
+        Ok(self)
+    }
+
+    fn push(&mut self, data: PtrUninit<'mem>, shape: &'static Shape<'static>, index: usize) {
+        // `new` checked that the value isn't nested deeper than `DEPTH`
+        self.frames[self.len] = Some(Frame {
+            data,
+            shape,
+            // `new` checked every shape reachable from `T`
+            kind: Kind::of(shape).unwrap(),
+            filled: 0,
+            index,
+        });
+        self.len += 1;
+    }
+
+    /// Starts building the `index`th field of the current struct. Setting a field again
+    /// overwrites it.
+    pub fn begin_nth_field(&mut self, index: usize) -> Result<&mut Self, ReflectError<'static>> {
+        let frame = *self.frame();
+        let Kind::Struct(fields) = frame.kind else {
+            return Err(ReflectError::WasNotA {
+                expected: "struct",
+                actual: frame.shape,
+            });
+        };
+        let Some(field) = fields.get(index) else {
+            return Err(ReflectError::OperationFailed {
+                shape: frame.shape,
+                operation: "field index out of bounds",
+            });
+        };
+        self.frame_mut().filled &= !(1 << index);
+        // SAFETY: the offset comes from the struct's shape
+        let data = unsafe { frame.data.field_uninit_at(field.offset) };
+        self.push(data, field.shape(), index);
+        Ok(self)
+    }
+
+    /// Starts building the field called `name` of the current struct.
+    pub fn begin_field(&mut self, name: &str) -> Result<&mut Self, ReflectError<'static>> {
+        match self.field_index(name) {
+            Some(index) => self.begin_nth_field(index),
+            None => Err(ReflectError::OperationFailed {
+                shape: self.shape(),
+                operation: "no field with this name",
+            }),
+        }
+    }
+
+    /// Starts building the `index`th element of the current array. Elements are filled in
+    /// order: `index` may be at most the number of elements built so far.
+    pub fn begin_nth_element(&mut self, index: usize) -> Result<&mut Self, ReflectError<'static>> {
+        let frame = *self.frame();
+        let Kind::Array { len, item } = frame.kind else {
+            return Err(ReflectError::WasNotA {
+                expected: "array",
+                actual: frame.shape,
+            });
+        };
+        if index >= len {
+            return Err(ReflectError::ArrayIndexOutOfBounds {
+                shape: frame.shape,
+                index,
+                size: len,
+            });
+        }
+        if index as u64 > frame.filled {
+            return Err(ReflectError::ArrayNotFullyInitialized {
+                shape: frame.shape,
+                pushed_count: frame.filled as usize,
+                expected_size: len,
+            });
+        }
+        let stride = item
+            .layout
+            .sized_layout()
+            .map_err(|_| unsupported(item))?
+            .size();
+        // SAFETY: the element is within the array
+        let data = unsafe { frame.data.field_uninit_at(index * stride) };
+        self.push(data, item, index);
+        Ok(self)
+    }
+
+    fn require_complete(frame: &Frame<'mem>) -> Result<(), ReflectError<'static>> {
+        if frame.filled == frame.kind.complete() {
+            return Ok(());
+        }
+        Err(match frame.kind {
+            Kind::Scalar => ReflectError::UninitializedValue { shape: frame.shape },
+            Kind::Struct(fields) => ReflectError::UninitializedField {
+                shape: frame.shape,
+                field_name: fields[frame.filled.trailing_ones() as usize].name,
+            },
+            Kind::Array { len, .. } => ReflectError::ArrayNotFullyInitialized {
+                shape: frame.shape,
+                pushed_count: frame.filled as usize,
+                expected_size: len,
+            },
+        })
+    }
+
+    /// Finishes the current field or element, which must be fully initialized, and goes back
+    /// to its parent.
+    pub fn end(&mut self) -> Result<&mut Self, ReflectError<'static>> {
+        if self.len == 1 {
+            return Err(ReflectError::InvariantViolation {
+                invariant: "Slot::end() called on the slot's own value",
+            });
+        }
+        let frame = *self.frame();
+        Self::require_complete(&frame)?;
+        self.frames[self.len - 1] = None;
+        self.len -= 1;
+
+        let parent = self.frame_mut();
+        match parent.kind {
+            Kind::Struct(_) => parent.filled |= 1 << frame.index,
+            Kind::Array { .. } if frame.index as u64 == parent.filled => parent.filled += 1,
+            _ => {}
+        }
+        Ok(self)
+    }
+
+    /// Checks that the value is fully initialized, and returns it.
+    pub fn build(self) -> Result<&'mem mut T, ReflectError<'static>> {
+        if self.len != 1 {
+            return Err(ReflectError::InvariantViolation {
+                invariant: "Slot::build() expects a single frame — end() until that's the case",
+            });
+        }
+        let frame = self.frame();
+        Self::require_complete(frame)?;
+        // SAFETY: every field and element is initialized, so the whole value is
+        Ok(unsafe { frame.data.assume_init().as_mut::<T>() })
+    }
+}
@@ -0,0 +1,89 @@
+use facet::Facet;
+use facet_reflect::{ReflectError, default_value};
+use facet_testhelpers::test;
+
+#[derive(Facet, PartialEq, Debug)]
+struct Config {
+    #[facet(default = 30)]
+    timeout: u32,
+    name: String,
+    server: Server,
+    ports: [u16; 2],
+    fallback: Option<Box<Server>>,
+    mode: Mode,
+}
+
+#[derive(Facet, PartialEq, Debug)]
+struct Server {
+    host: String,
+    #[facet(default = default_port())]
+    port: u16,
+    limits: Box<Limits>,
+}
+
+#[derive(Facet, PartialEq, Debug, Default)]
+struct Limits {
+    max: u32,
+}
+
+#[derive(Facet, PartialEq, Debug)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Mode {
+    Fast { threads: u8 },
+    Slow,
+}
+
+fn default_port() -> u16 {
+    8080
+}
+
+#[test]
+fn builds_without_default_impl() {
+    let config: Config = default_value(Config::SHAPE)?.materialize()?;
+    assert_eq!(
+        config,
+        Config {
+            timeout: 30,
+            name: String::new(),
+            server: Server {
+                host: String::new(),
+                port: 8080,
+                limits: Box::new(Limits { max: 0 }),
+            },
+            ports: [0, 0],
+            fallback: None,
+            mode: Mode::Fast { threads: 0 },
+        }
+    );
+}
+
+#[test]
+fn uses_default_impl() {
+    #[derive(Facet, PartialEq, Debug)]
+    struct Counter {
+        count: u32,
+    }
+
+    impl Default for Counter {
+        fn default() -> Self {
+            Counter { count: 1 }
+        }
+    }
+
+    let counter: Counter = default_value(Counter::SHAPE)?.materialize()?;
+    assert_eq!(counter, Counter { count: 1 });
+}
+
+#[test]
+fn fails_without_a_default() {
+    #[derive(Facet)]
+    struct Borrowed<'a> {
+        value: &'a u32,
+    }
+
+    assert!(matches!(
+        default_value(Borrowed::SHAPE),
+        Err(ReflectError::MissingCharacteristic { .. })
+    ));
+}
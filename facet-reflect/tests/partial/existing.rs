@@ -0,0 +1,96 @@
+use facet::Facet;
+use facet_reflect::{Partial, Peek};
+use facet_testhelpers::test;
+
+#[derive(Facet, PartialEq, Eq, Debug, Clone)]
+struct Outer {
+    name: String,
+    inner: Inner,
+    tags: Vec<String>,
+}
+
+#[derive(Facet, PartialEq, Eq, Debug, Clone)]
+struct Inner {
+    x: i32,
+    label: String,
+}
+
+fn outer() -> Outer {
+    Outer {
+        name: String::from("outer"),
+        inner: Inner {
+            x: 1,
+            label: String::from("inner"),
+        },
+        tags: vec![String::from("a")],
+    }
+}
+
+#[test]
+fn from_existing_value_builds_unchanged() {
+    let v = Partial::from_existing_value(outer())?.build()?;
+    assert_eq!(*v, outer());
+}
+
+#[test]
+fn from_existing_value_replaces_selected_fields() {
+    let v = Partial::from_existing_value(outer())?
+        .begin_field("name")?
+        .set(String::from("renamed"))?
+        .end()?
+        .begin_field("inner")?
+        .begin_field("x")?
+        .set(42)?
+        .end()?
+        .end()?
+        .build()?;
+
+    assert_eq!(
+        *v,
+        Outer {
+            name: String::from("renamed"),
+            inner: Inner {
+                x: 42,
+                label: String::from("inner"),
+            },
+            tags: vec![String::from("a")],
+        }
+    );
+}
+
+#[test]
+fn from_existing_peek_clones() {
+    let original = outer();
+    let mut partial = Partial::from_existing_peek(&Peek::new(&original))?;
+    partial
+        .begin_field("tags")?
+        .set(vec![String::from("b")])?
+        .end()?;
+    let v = partial.build()?.materialize::<Outer>()?;
+
+    assert_eq!(v.tags, vec![String::from("b")]);
+    assert_eq!(v.inner, original.inner);
+    assert_eq!(original, outer());
+}
+
+#[test]
+fn from_existing_value_drop_midway() {
+    let mut partial = Partial::from_existing_value(outer())?;
+    partial
+        .begin_field("inner")?
+        .begin_field("label")?
+        .set(String::from("replaced"))?;
+    drop(partial);
+}
+
+#[test]
+fn from_existing_peek_requires_clone() {
+    #[derive(Facet)]
+    struct NotClone {
+        x: i32,
+    }
+
+    let value = NotClone { x: 1 };
+    assert!(Partial::from_existing_peek(&Peek::new(&value)).is_err());
+    let _ = value.x;
+}
@@ -438,6 +438,34 @@ fn wip_list_string() {
     assert_eq!(list, vec!["hello".to_string(), "world".to_string()]);
 }
 
+#[test]
+fn wip_list_extend_from_slice() {
+    let mut partial = Partial::alloc::<Vec<f64>>()?;
+    partial.begin_list()?;
+    partial.extend_from_slice(&[1.0, 2.5])?;
+    partial.begin_list_item()?;
+    partial.set(4.0)?;
+    partial.end()?;
+    partial.extend_from_slice::<f64>(&[])?;
+    partial.extend_from_slice(&[-1.0])?;
+    let list = *partial.build()?;
+
+    assert_eq!(list, vec![1.0, 2.5, 4.0, -1.0]);
+}
+
+#[test]
+fn wip_list_extend_from_slice_errors() {
+    let mut partial = Partial::alloc::<Vec<u32>>()?;
+    assert!(partial.extend_from_slice(&[1u32]).is_err());
+    partial.begin_list()?;
+    assert!(matches!(
+        partial.extend_from_slice(&[1u64]),
+        Err(ReflectError::WrongShape { .. })
+    ));
+    partial.begin_list_item()?;
+    assert!(partial.extend_from_slice(&[1u32]).is_err());
+}
+
 #[derive(Facet, Debug, PartialEq)]
 struct WithList {
     name: String,
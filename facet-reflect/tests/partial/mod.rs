@@ -2,7 +2,9 @@
 
 mod arc;
 mod array_building;
+mod default_value;
 mod empty_tuples;
+mod existing;
 mod invariant;
 mod list_leak;
 mod map;
@@ -485,7 +485,7 @@ fn test_string_traits() {
             .build(),
     );
     check_facts(
-        Cow::Owned("hello".to_string()),
+        Cow::<str>::Owned("hello".to_string()),
         Cow::Owned("world".to_string()),
         FactBuilder::new()
             .debug()
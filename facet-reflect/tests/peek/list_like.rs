@@ -93,3 +93,44 @@ fn peek_list_like_slice() {
     // Test out of bounds
     assert!(peek_list.get(5).is_none());
 }
+
+#[test]
+fn peek_list_like_iter_from_both_ends() {
+    let test_list = vec![1, 2, 3, 4, 5];
+    let peek_list = Peek::new(&test_list).into_list_like()?;
+
+    let mut iter = peek_list.iter();
+    assert_eq!(*iter.next().unwrap().get::<i32>()?, 1);
+    assert_eq!(*iter.next_back().unwrap().get::<i32>()?, 5);
+    assert_eq!(iter.len(), 3);
+
+    let middle: Vec<i32> = iter.rev().map(|item| *item.get::<i32>().unwrap()).collect();
+    assert_eq!(middle, [4, 3, 2]);
+
+    let test_array = [1u16, 2, 3];
+    let reversed: Vec<u16> = Peek::new(&test_array)
+        .into_list_like()?
+        .iter()
+        .rev()
+        .map(|item| *item.get::<u16>().unwrap())
+        .collect();
+    assert_eq!(reversed, [3, 2, 1]);
+}
+
+#[test]
+fn peek_list_like_as_bytes() {
+    let test_array = [1u8, 2, 3];
+    assert_eq!(
+        Peek::new(&test_array).into_list_like()?.as_bytes(),
+        Some(&[1u8, 2, 3][..])
+    );
+
+    let test_vec = vec![4u8, 5];
+    assert_eq!(
+        Peek::new(&test_vec).into_list_like()?.as_bytes(),
+        Some(&[4u8, 5][..])
+    );
+
+    let test_list = vec![1u32, 2];
+    assert_eq!(Peek::new(&test_list).into_list_like()?.as_bytes(), None);
+}
@@ -76,3 +76,35 @@ fn test_peek_map_different_types() {
     assert_eq!(peek_map.get(&2).unwrap().get::<&str>()?, &"two");
     assert!(peek_map.get(&3).is_none());
 }
+
+#[test]
+fn test_peek_map_iter_from_both_ends() {
+    let source: std::collections::BTreeMap<u8, u8> = [(1, 10), (2, 20), (3, 30)].into();
+    let mut iter = Peek::new(&source).into_map()?.iter();
+
+    let (key, value) = iter.next_back().unwrap();
+    assert_eq!((*key.get::<u8>()?, *value.get::<u8>()?), (3, 30));
+    assert_eq!(*iter.next().unwrap().0.get::<u8>()?, 1);
+    assert_eq!(iter.len(), 1);
+    assert_eq!(*iter.next_back().unwrap().0.get::<u8>()?, 2);
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_peek_map_rev_without_next_back() {
+    // `HashMap` iterators only go forward
+    let source: HashMap<u8, u8> = (0..6).map(|i| (i, i * 10)).collect();
+    let peek_map = Peek::new(&source).into_map()?;
+
+    let forward: Vec<u8> = peek_map
+        .iter()
+        .map(|(key, _)| *key.get::<u8>().unwrap())
+        .collect();
+    let mut backward: Vec<u8> = peek_map
+        .iter()
+        .rev()
+        .map(|(key, _)| *key.get::<u8>().unwrap())
+        .collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+}
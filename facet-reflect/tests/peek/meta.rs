@@ -0,0 +1,97 @@
+use facet::{Facet, FieldFlags};
+use facet_reflect::{HasFields, Partial, Peek};
+use facet_testhelpers::test;
+
+#[derive(Facet)]
+#[facet(rename_all = "camelCase")]
+struct Settings {
+    /// How loud the output is
+    ///
+    /// Counted in levels.
+    #[facet(short = 'v')]
+    verbosity_level: u8,
+    #[facet(rename = "user")]
+    #[facet(positional)]
+    user_name: String,
+    #[facet(sensitive)]
+    #[facet(proto(tag = 3))]
+    token: String,
+}
+
+#[test]
+fn struct_fields_with_meta() {
+    let settings = Settings {
+        verbosity_level: 2,
+        user_name: "amos".into(),
+        token: "secret".into(),
+    };
+    let peek = Peek::new(&settings).into_struct()?;
+    let fields: Vec<_> = peek.fields_with_meta().collect();
+    assert_eq!(fields.len(), 3);
+
+    let (verbosity, value) = fields[0];
+    assert_eq!(verbosity.name(), "verbosityLevel");
+    assert_eq!(verbosity.shape(), u8::SHAPE);
+    assert_eq!(*value.get::<u8>()?, 2);
+    assert_eq!(
+        verbosity.doc_text().as_deref(),
+        Some("How loud the output is\n\nCounted in levels.")
+    );
+    assert_eq!(verbosity.attr_value("short"), Some("v"));
+    assert!(!verbosity.has_attr("positional"));
+
+    let (user, _) = fields[1];
+    assert_eq!(user.name(), "user");
+    assert!(user.has_attr("positional"));
+    assert_eq!(user.attr_value("positional"), Some(""));
+    assert_eq!(user.doc_text(), None);
+
+    let (token, _) = fields[2];
+    assert!(token.is_sensitive());
+    assert!(token.flags().contains(FieldFlags::SENSITIVE));
+    assert_eq!(token.attr_value("proto"), Some("tag = 3"));
+    assert_eq!(token.attributes().len(), 1);
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Figure {
+    Circle {
+        /// In meters
+        radius: f64,
+    },
+    Point,
+}
+
+#[test]
+fn enum_fields_with_meta() {
+    let circle = Figure::Circle { radius: 1.5 };
+    let peek = Peek::new(&circle).into_enum()?;
+    let (radius, value) = peek.fields_with_meta().next().unwrap();
+    assert_eq!(radius.name(), "radius");
+    assert_eq!(radius.doc_text().as_deref(), Some("In meters"));
+    assert_eq!(*value.get::<f64>()?, 1.5);
+
+    let point = Figure::Point;
+    let peek = Peek::new(&point).into_enum()?;
+    assert_eq!(peek.fields_with_meta().count(), 0);
+}
+
+#[test]
+fn partial_fields_with_meta() {
+    let mut partial = Partial::alloc_shape(Settings::SHAPE)?;
+    let names: Vec<_> = partial.fields_with_meta().map(|f| f.name()).collect();
+    assert_eq!(names, ["verbosityLevel", "user", "token"]);
+
+    partial.begin_field("verbosityLevel")?;
+    assert_eq!(partial.fields_with_meta().count(), 0);
+    partial.set(3u8)?;
+    partial.end()?;
+
+    let mut partial = Partial::alloc_shape(Figure::SHAPE)?;
+    assert_eq!(partial.fields_with_meta().count(), 0);
+    partial.select_variant_named("Circle")?;
+    let meta = partial.fields_with_meta().next().unwrap();
+    assert_eq!(meta.name(), "radius");
+}
@@ -5,7 +5,9 @@ mod facts;
 mod list;
 mod list_like;
 mod map;
+mod meta;
 mod option;
+mod query;
 mod reference;
 mod smartptr;
 mod struct_;
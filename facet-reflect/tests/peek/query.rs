@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_reflect::{Peek, Query};
+use facet_testhelpers::test;
+
+#[derive(Facet)]
+struct Directory {
+    users: Vec<User>,
+    teams: HashMap<String, Vec<String>>,
+}
+
+#[derive(Facet)]
+struct User {
+    name: String,
+    age: u32,
+    address: Option<Box<Address>>,
+    status: Status,
+}
+
+#[derive(Facet)]
+struct Address {
+    city: String,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Status {
+    Active,
+    Suspended { reason: String },
+}
+
+fn directory() -> Directory {
+    Directory {
+        users: vec![
+            User {
+                name: "Ada".into(),
+                age: 36,
+                address: Some(Box::new(Address {
+                    city: "London".into(),
+                })),
+                status: Status::Active,
+            },
+            User {
+                name: "Tim".into(),
+                age: 12,
+                address: None,
+                status: Status::Suspended {
+                    reason: "spam".into(),
+                },
+            },
+        ],
+        teams: HashMap::from([("core".into(), vec!["Ada".into()])]),
+    }
+}
+
+fn strings(matches: Vec<Peek<'_, '_, '_>>) -> Vec<String> {
+    matches.iter().map(|peek| peek.to_string()).collect()
+}
+
+#[test]
+fn query_fields_and_items() {
+    let directory = directory();
+    let peek = Peek::new(&directory);
+    assert_eq!(strings(peek.query("$.users[*].name")?), ["Ada", "Tim"]);
+    assert_eq!(strings(peek.query("$.users[1].age")?), ["12"]);
+    assert_eq!(strings(peek.query("$.users[-1].name")?), ["Tim"]);
+    assert_eq!(strings(peek.query("$.users.0.name")?), ["Ada"]);
+    assert!(peek.query("$.users[2].name")?.is_empty());
+    assert!(peek.query("$.nope")?.is_empty());
+}
+
+#[test]
+fn query_looks_through_options_and_boxes() {
+    let directory = directory();
+    let peek = Peek::new(&directory);
+    assert_eq!(strings(peek.query("$.users[*].address.city")?), ["London"]);
+    let addresses = peek.query("$.users[*].address")?;
+    assert_eq!(addresses.len(), 2);
+    assert!(addresses[1].into_option()?.is_none());
+}
+
+#[test]
+fn query_maps_and_enums() {
+    let directory = directory();
+    let peek = Peek::new(&directory);
+    assert_eq!(strings(peek.query("$.teams.core[0]")?), ["Ada"]);
+    assert_eq!(strings(peek.query("$.teams['core'][*]")?), ["Ada"]);
+    assert_eq!(
+        strings(peek.query("$.users[*].status.Suspended.reason")?),
+        ["spam"]
+    );
+    assert_eq!(strings(peek.query("$.users[*].status.reason")?), ["spam"]);
+}
+
+#[test]
+fn query_filters() {
+    let directory = directory();
+    let peek = Peek::new(&directory);
+    assert_eq!(
+        strings(peek.query("$.users[?(@.age >= 18)].name")?),
+        ["Ada"]
+    );
+    assert_eq!(strings(peek.query("$.users[?(@.age < 18)].name")?), ["Tim"]);
+    assert_eq!(
+        strings(peek.query("$.users[?(@.name != 'Ada')].age")?),
+        ["12"]
+    );
+    assert_eq!(strings(peek.query("$.users[?(@.address)].name")?), ["Ada"]);
+    assert_eq!(
+        strings(peek.query("$.users[?(@.address == null)].name")?),
+        ["Tim"]
+    );
+    assert_eq!(
+        strings(peek.query("$.users[?(@.status == \"Active\")].name")?),
+        ["Ada"]
+    );
+    assert_eq!(
+        strings(peek.query("$.users[?(@.address.city == 'London')].name")?),
+        ["Ada"]
+    );
+}
+
+#[test]
+fn query_filters_scalars() {
+    let numbers = [3, 14, 15, 92];
+    assert_eq!(
+        strings(Peek::new(&numbers).query("$[?(@ > 10)]")?),
+        ["14", "15", "92"]
+    );
+    let flags = vec![true, false];
+    assert_eq!(Peek::new(&flags).query("$[?(@ == true)]")?.len(), 1);
+}
+
+#[test]
+fn parsed_query_is_reusable() {
+    let query: Query = "$.users[*].age".parse()?;
+    let directory = directory();
+    assert_eq!(strings(query.eval(Peek::new(&directory))), ["36", "12"]);
+    assert_eq!(
+        query.first(Peek::new(&directory)).unwrap().get::<u32>()?,
+        &36
+    );
+}
+
+#[test]
+fn query_syntax_errors() {
+    let directory = directory();
+    let peek = Peek::new(&directory);
+    let err = peek.query("$.users[").unwrap_err();
+    assert_eq!(err.position, 8);
+    assert_eq!(
+        err.to_string(),
+        "expected `*`, `?`, a string or an index at 8"
+    );
+    assert!(peek.query("$.users[?(@.age ~ 3)]").is_err());
+    assert!(peek.query("users").is_err());
+}
@@ -1,3 +1,4 @@
+use facet::Facet;
 use facet_reflect::Peek;
 
 #[test]
@@ -72,3 +73,52 @@ fn str_mut_ref_ref() {
 
     assert_eq!(format!("{peek}"), "abc");
 }
+
+#[test]
+fn pointee_of_sized_ref() {
+    let n = 42u32;
+    let r = &n;
+    let peek = Peek::new::<&u32>(&r).into_pointee().unwrap();
+
+    assert_eq!(peek.shape(), u32::SHAPE);
+    assert_eq!(*peek.get::<u32>().unwrap(), 42);
+}
+
+#[test]
+fn pointee_of_slice_ref() {
+    let words = ["a", "b"];
+    let r: &[&str] = &words;
+    let peek = Peek::new::<&[&str]>(&r).into_pointee().unwrap();
+
+    assert_eq!(peek.shape(), <[&str]>::SHAPE);
+}
+
+#[test]
+fn pointee_of_non_ref() {
+    assert!(Peek::new(&42u32).into_pointee().is_err());
+}
+
+#[test]
+fn struct_with_independent_lifetimes() {
+    #[derive(Facet)]
+    struct Borrowed<'a, 'b> {
+        name: &'a str,
+        tags: &'a [&'b str],
+    }
+
+    let tags = ["x", "y"];
+    let value = Borrowed {
+        name: "n",
+        tags: &tags,
+    };
+    let peek = Peek::new(&value).into_struct().unwrap();
+
+    assert_eq!(peek.field_by_name("name").unwrap().as_str(), Some("n"));
+    let tags = peek
+        .field_by_name("tags")
+        .unwrap()
+        .into_list_like()
+        .unwrap();
+    assert_eq!(tags.len(), 2);
+    assert_eq!(tags.get(1).unwrap().as_str(), Some("y"));
+}
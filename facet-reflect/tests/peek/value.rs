@@ -1,5 +1,7 @@
+use std::cmp::Ordering;
 use std::hash::{DefaultHasher, Hash, Hasher};
 
+use facet::Facet;
 use facet_reflect::Peek;
 use facet_testhelpers::test;
 
@@ -33,3 +35,72 @@ fn test_peek_value_twostrings() {
     assert_eq!(av.to_string(), "⟨Option<i32>⟩");
     assert_eq!(format!("{a:?}"), format!("{av:?}"));
 }
+
+#[test]
+fn test_peek_value_display_and_debug() {
+    let n = 42_i32;
+    let pn = Peek::new(&n);
+    assert_eq!(pn.display().unwrap().to_string(), "42");
+    assert_eq!(format!("{:?}", pn.debug().unwrap()), "42");
+
+    let s: &str = "hi";
+    let ps = Peek::new(s);
+    assert_eq!(ps.display().unwrap().to_string(), "hi");
+    assert_eq!(format!("{:?}", ps.debug().unwrap()), "\"hi\"");
+
+    let o = Some(42_i32);
+    let po = Peek::new(&o);
+    assert!(po.display().is_none());
+    assert_eq!(format!("{:?}", po.debug().unwrap()), "Some(42)");
+
+    #[derive(Facet)]
+    struct Opaque {
+        n: i32,
+    }
+    let op = Opaque { n: 1 };
+    let pop = Peek::new(&op);
+    assert_eq!(op.n, 1);
+    assert!(pop.display().is_none());
+    assert!(pop.debug().is_none());
+}
+
+#[test]
+fn test_peek_value_as_text() {
+    let s = String::from("key");
+    assert_eq!(Peek::new(&s).as_text().as_deref(), Some("key"));
+
+    let n = 42_u32;
+    assert_eq!(Peek::new(&n).as_text().as_deref(), Some("42"));
+
+    let o = Some(42_i32);
+    assert!(Peek::new(&o).as_text().is_none());
+}
+
+#[test]
+fn test_peek_value_partial_eq_and_partial_ord() {
+    let (a, b) = (1_u8, 2_u8);
+    let (pa, pb) = (Peek::new(&a), Peek::new(&b));
+    assert_eq!(pa.partial_eq(&pb), Some(false));
+    assert_eq!(pa.partial_eq(&pa), Some(true));
+    assert_eq!(pa.partial_ord(&pb), Some(Some(Ordering::Less)));
+
+    let (x, y) = (f64::NAN, 1.0_f64);
+    assert_eq!(Peek::new(&x).partial_ord(&Peek::new(&y)), Some(None));
+
+    // Values of different types can't be compared
+    let c = 1_u16;
+    let pc = Peek::new(&c);
+    assert_eq!(pa.partial_eq(&pc), None);
+    assert_eq!(pa.partial_ord(&pc), None);
+    assert_ne!(pa, pc);
+
+    #[derive(Facet)]
+    struct Opaque {
+        n: i32,
+    }
+    let op = Opaque { n: 1 };
+    assert_eq!(op.n, 1);
+    let pop = Peek::new(&op);
+    assert_eq!(pop.partial_eq(&pop), None);
+    assert_eq!(pop.partial_ord(&pop), None);
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_reflect::{Peek, RedactPolicy};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq, Clone)]
+struct User {
+    name: String,
+    #[facet(sensitive)]
+    password: String,
+    #[facet(sensitive)]
+    api_key: Option<String>,
+    emails: Vec<Email>,
+    headers: HashMap<String, String>,
+    login: Login,
+}
+
+#[derive(Facet, Debug, PartialEq, Clone)]
+struct Email {
+    address: String,
+    verified: bool,
+}
+
+#[derive(Facet, Debug, PartialEq, Clone)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Login {
+    Anonymous,
+    Password {
+        #[facet(sensitive)]
+        hash: Box<str>,
+        attempts: u32,
+    },
+}
+
+fn user() -> User {
+    User {
+        name: "ada".to_string(),
+        password: "hunter2".to_string(),
+        api_key: Some("sk-123".to_string()),
+        emails: vec![
+            Email {
+                address: "ada@example.com".to_string(),
+                verified: true,
+            },
+            Email {
+                address: "ada@work.example".to_string(),
+                verified: false,
+            },
+        ],
+        headers: HashMap::from([
+            ("authorization".to_string(), "Bearer abc".to_string()),
+            ("accept".to_string(), "*/*".to_string()),
+        ]),
+        login: Login::Password {
+            hash: "$2b$12$...".into(),
+            attempts: 2,
+        },
+    }
+}
+
+#[test]
+fn redacts_sensitive_fields() {
+    let redacted: User =
+        facet_reflect::redact(Peek::new(&user()), &RedactPolicy::new())?.materialize()?;
+    assert_eq!(
+        redacted,
+        User {
+            password: "[REDACTED]".to_string(),
+            api_key: None,
+            login: Login::Password {
+                hash: "[REDACTED]".into(),
+                attempts: 2,
+            },
+            ..user()
+        }
+    );
+}
+
+#[test]
+fn redacts_paths() {
+    let policy = RedactPolicy::new()
+        .with_sensitive(false)
+        .with_placeholder("***")
+        .with_path("emails.*.address")
+        .with_path("headers.authorization")
+        .with_path("login.attempts");
+    let redacted: User = facet_reflect::redact(Peek::new(&user()), &policy)?.materialize()?;
+
+    let mut expected = user();
+    for email in &mut expected.emails {
+        email.address = "***".to_string();
+    }
+    expected
+        .headers
+        .insert("authorization".to_string(), "***".to_string());
+    expected.login = Login::Password {
+        hash: "$2b$12$...".into(),
+        attempts: 0,
+    };
+    assert_eq!(redacted, expected);
+}
+
+#[test]
+fn copies_unchanged_without_matches() {
+    let user = User {
+        login: Login::Anonymous,
+        ..user()
+    };
+    let policy = RedactPolicy::new().with_sensitive(false).with_path("nope");
+    let redacted: User = facet_reflect::redact(Peek::new(&user), &policy)?.materialize()?;
+    assert_eq!(redacted, user);
+}
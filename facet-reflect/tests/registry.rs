@@ -0,0 +1,56 @@
+#![cfg(feature = "registry")]
+
+use facet::Facet;
+use facet_reflect::{Partial, registry};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Login {
+    user: String,
+    attempts: u32,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Logout {
+    user: String,
+}
+
+#[test]
+fn test_register_and_lookup_by_type_name() {
+    registry::register::<Login>();
+    registry::register::<Logout>();
+
+    let name = core::any::type_name::<Login>();
+    assert!(name.ends_with("::Login"));
+    let shape = registry::lookup(name).unwrap();
+    assert!(shape.is_type::<Login>());
+
+    let mut partial = Partial::alloc_shape(shape)?;
+    partial.set_field("user", String::from("amos"))?;
+    partial.set_field("attempts", 3_u32)?;
+    let value = partial.build()?.materialize::<Login>()?;
+    assert_eq!(
+        value,
+        Login {
+            user: "amos".to_string(),
+            attempts: 3,
+        }
+    );
+
+    assert!(registry::lookup("Login").is_none());
+}
+
+#[test]
+fn test_register_as_replaces() {
+    assert!(registry::register_as("test.event", Login::SHAPE).is_none());
+    let previous = registry::register_as("test.event", Logout::SHAPE).unwrap();
+    assert!(previous.is_type::<Login>());
+    assert!(registry::lookup("test.event").unwrap().is_type::<Logout>());
+
+    let names: Vec<_> = registry::registered()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert!(names.contains(&"test.event"));
+    assert!(names.is_sorted());
+}
@@ -0,0 +1,113 @@
+use core::mem::MaybeUninit;
+
+use facet::Facet;
+use facet_reflect::{ReflectError, Slot};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Packet<'a> {
+    id: u16,
+    tag: &'a str,
+    payload: [Sample; 2],
+}
+
+#[derive(Facet, Debug, PartialEq, Clone, Copy)]
+struct Sample(i8, bool);
+
+#[test]
+fn build_in_place() {
+    let mut storage = MaybeUninit::uninit();
+    let mut slot = Slot::<Packet>::new(&mut storage)?;
+
+    // Fields can come in any order
+    slot.begin_field("tag")?.set("hello")?.end()?;
+    slot.begin_field("payload")?;
+    for (i, sample) in [Sample(-1, true), Sample(2, false)].into_iter().enumerate() {
+        slot.begin_nth_element(i)?;
+        slot.begin_nth_field(0)?.set(sample.0)?.end()?;
+        slot.begin_nth_field(1)?.set(sample.1)?.end()?;
+        slot.end()?;
+    }
+    slot.end()?;
+    assert!(!slot.is_field_set(0));
+    slot.begin_field("id")?.set(7u16)?.end()?;
+    assert!(slot.is_field_set(0));
+
+    assert_eq!(
+        *slot.build()?,
+        Packet {
+            id: 7,
+            tag: "hello",
+            payload: [Sample(-1, true), Sample(2, false)],
+        }
+    );
+}
+
+#[test]
+fn whole_values_can_be_set() {
+    let mut storage = MaybeUninit::uninit();
+    let mut slot = Slot::<[Sample; 2]>::new(&mut storage)?;
+    slot.set([Sample(1, true); 2])?;
+    assert_eq!(*slot.build()?, [Sample(1, true); 2]);
+}
+
+#[test]
+fn incomplete_values_are_rejected() {
+    let mut storage = MaybeUninit::<Packet>::uninit();
+    let mut slot = Slot::<Packet>::new(&mut storage)?;
+    slot.begin_field("id")?.set(7u16)?.end()?;
+    slot.begin_field("payload")?;
+    slot.begin_nth_element(0)?.set(Sample(0, false))?.end()?;
+    assert!(matches!(
+        slot.end(),
+        Err(ReflectError::ArrayNotFullyInitialized {
+            pushed_count: 1,
+            expected_size: 2,
+            ..
+        })
+    ));
+    // Elements are filled in order
+    assert!(slot.begin_nth_element(2).is_err());
+    slot.begin_nth_element(1)?.set(Sample(1, true))?.end()?;
+    slot.end()?;
+
+    assert!(matches!(
+        slot.build(),
+        Err(ReflectError::UninitializedField {
+            field_name: "tag",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn wrong_shapes_are_rejected() {
+    let mut storage = MaybeUninit::<Sample>::uninit();
+    let mut slot = Slot::<Sample>::new(&mut storage)?;
+    slot.begin_nth_field(0)?;
+    assert!(matches!(
+        slot.set(1u8),
+        Err(ReflectError::WrongShape { .. })
+    ));
+    assert!(slot.begin_nth_field(0).is_err());
+}
+
+#[derive(Facet)]
+struct Owned {
+    name: String,
+}
+
+#[test]
+fn only_plain_data_is_supported() {
+    let mut storage = MaybeUninit::<Owned>::uninit();
+    assert!(Slot::<Owned>::new(&mut storage).is_err());
+
+    let mut storage = MaybeUninit::<Vec<u8>>::uninit();
+    assert!(Slot::<Vec<u8>>::new(&mut storage).is_err());
+
+    // `Packet` nests four levels deep: the packet, its payload, a sample, and its fields
+    let mut storage = MaybeUninit::<Packet>::uninit();
+    assert!(Slot::<Packet, 3>::new(&mut storage).is_err());
+    let mut storage = MaybeUninit::<Packet>::uninit();
+    assert!(Slot::<Packet, 4>::new(&mut storage).is_ok());
+}
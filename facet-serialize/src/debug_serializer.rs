@@ -10,11 +10,15 @@ struct DebugSerializer<W> {
 #[derive(Debug)]
 enum DebugError {
     Fmt(core::fmt::Error),
+    PointerCycle(alloc::string::String),
 }
 
 impl core::fmt::Display for DebugError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        core::fmt::Debug::fmt(self, f)
+        match self {
+            DebugError::Fmt(err) => write!(f, "{err}"),
+            DebugError::PointerCycle(message) => f.write_str(message),
+        }
     }
 }
 
@@ -193,6 +197,15 @@ where
         Ok(())
     }
 
+    fn serialize_pointer_cycle(
+        &mut self,
+        shape: &'shape facet_core::Shape<'shape>,
+    ) -> Result<(), Self::Error> {
+        Err(DebugError::PointerCycle(crate::pointer_cycle_message(
+            shape,
+        )))
+    }
+
     fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
         self.write_comma()?;
         write!(self.writer, "{{")?;
@@ -0,0 +1,100 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+
+/// An error from serializing, in a form that every format's errors convert into.
+///
+/// Generic code can require `S::Error: Into<SerializeError>` to handle failures from any
+/// format the same way. [`serialize_with_path`](crate::serialize_with_path) also says where
+/// in the value the failure happened.
+#[derive(Debug)]
+pub struct SerializeError {
+    kind: SerializeErrorKind,
+    path: Option<String>,
+}
+
+/// What went wrong in a [`SerializeError`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SerializeErrorKind {
+    /// The format can't represent this kind of value, like a byte array in TOML
+    UnsupportedShape(String),
+    /// Any other failure, with the format's message
+    Custom(String),
+    /// Writing the output failed
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl SerializeError {
+    /// The format can't represent `what`, like "a byte array".
+    pub fn unsupported_shape(what: impl fmt::Display) -> Self {
+        SerializeErrorKind::UnsupportedShape(what.to_string()).into()
+    }
+
+    /// Any other failure, with the format's message.
+    pub fn custom(message: impl fmt::Display) -> Self {
+        SerializeErrorKind::Custom(message.to_string()).into()
+    }
+
+    /// Returns what went wrong.
+    pub fn kind(&self) -> &SerializeErrorKind {
+        &self.kind
+    }
+
+    /// Returns where in the value the error happened, like `Config.servers[2].name`, if known.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Sets where in the value the error happened.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+impl From<SerializeErrorKind> for SerializeError {
+    fn from(kind: SerializeErrorKind) -> Self {
+        Self { kind, path: None }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for SerializeError {
+    fn from(error: std::io::Error) -> Self {
+        SerializeErrorKind::Io(error).into()
+    }
+}
+
+impl From<core::convert::Infallible> for SerializeError {
+    fn from(error: core::convert::Infallible) -> Self {
+        match error {}
+    }
+}
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            SerializeErrorKind::UnsupportedShape(what) => {
+                write!(f, "The format doesn't support {what}")?
+            }
+            SerializeErrorKind::Custom(message) => f.write_str(message)?,
+            #[cfg(feature = "std")]
+            SerializeErrorKind::Io(error) => write!(f, "IO error: {error}")?,
+        }
+        if let Some(path) = &self.path {
+            write!(f, " (at {path})")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for SerializeError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self.kind {
+            #[cfg(feature = "std")]
+            SerializeErrorKind::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
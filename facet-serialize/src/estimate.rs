@@ -0,0 +1,151 @@
+use core::convert::Infallible;
+
+use facet_core::Shape;
+use facet_reflect::Peek;
+
+use crate::{Serializer, serialize_iterative};
+
+/// Estimates how many bytes `peek` takes when serialized, without serializing it.
+///
+/// This walks the value once, counting the lengths of strings, byte arrays and collections,
+/// and sizes numbers by their magnitude. The result matches compact binary formats like
+/// MessagePack closely, so it can be used to pre-allocate output buffers or write a length
+/// prefix before the payload. Text formats take more space, with quotes and separators.
+pub fn estimate_size<'mem, 'facet, 'shape>(peek: Peek<'mem, 'facet, 'shape>) -> usize {
+    let mut estimator = SizeEstimator { size: 0 };
+    match serialize_iterative(peek, &mut estimator) {
+        Ok(()) => estimator.size,
+        Err(error) => match error {},
+    }
+}
+
+/// Counts the bytes a compact binary format would write.
+struct SizeEstimator {
+    size: usize,
+}
+
+impl SizeEstimator {
+    /// Count a marker byte and the length that follows it, for a string, array or map of `len`.
+    fn add_header(&mut self, len: Option<usize>) {
+        self.size += match len {
+            Some(0..=31) => 1,
+            Some(32..=0xff) => 2,
+            Some(0x100..=0xffff) => 3,
+            _ => 5,
+        };
+    }
+
+    /// Count a marker byte and as many bytes as `magnitude` needs, or none if it fits in the
+    /// marker.
+    fn add_number(&mut self, magnitude: u128) {
+        self.size += match magnitude {
+            0..=0x7f => 1,
+            0x80..=0xff => 2,
+            0x100..=0xffff => 3,
+            0x1_0000..=0xffff_ffff => 5,
+            0x1_0000_0000..=0xffff_ffff_ffff_ffff => 9,
+            _ => 17,
+        };
+    }
+}
+
+impl<'shape> Serializer<'shape> for SizeEstimator {
+    type Error = Infallible;
+
+    fn serialize_u64(&mut self, value: u64) -> Result<(), Self::Error> {
+        self.add_number(value.into());
+        Ok(())
+    }
+
+    fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
+        self.add_number(value);
+        Ok(())
+    }
+
+    fn serialize_i64(&mut self, value: i64) -> Result<(), Self::Error> {
+        self.add_number(value.unsigned_abs().into());
+        Ok(())
+    }
+
+    fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
+        self.add_number(value.unsigned_abs());
+        Ok(())
+    }
+
+    fn serialize_f32(&mut self, _value: f32) -> Result<(), Self::Error> {
+        self.size += 5;
+        Ok(())
+    }
+
+    fn serialize_f64(&mut self, _value: f64) -> Result<(), Self::Error> {
+        self.size += 9;
+        Ok(())
+    }
+
+    fn serialize_bool(&mut self, _value: bool) -> Result<(), Self::Error> {
+        self.size += 1;
+        Ok(())
+    }
+
+    fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
+        self.size += 1 + value.len_utf8();
+        Ok(())
+    }
+
+    fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.add_header(Some(value.len()));
+        self.size += value.len();
+        Ok(())
+    }
+
+    fn serialize_bytes(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        self.add_header(Some(value.len()));
+        self.size += value.len();
+        Ok(())
+    }
+
+    fn serialize_none(&mut self) -> Result<(), Self::Error> {
+        self.size += 1;
+        Ok(())
+    }
+
+    fn serialize_unit(&mut self) -> Result<(), Self::Error> {
+        self.size += 1;
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        &mut self,
+        _variant_index: usize,
+        variant_name: &'shape str,
+    ) -> Result<(), Self::Error> {
+        self.serialize_str(variant_name)
+    }
+
+    fn serialize_pointer_cycle(
+        &mut self,
+        _shape: &'shape Shape<'shape>,
+    ) -> Result<(), Self::Error> {
+        // Serializing would fail here, so there's nothing more to count
+        Ok(())
+    }
+
+    fn start_object(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
+        self.add_header(len);
+        Ok(())
+    }
+
+    fn serialize_field_name(&mut self, name: &'shape str) -> Result<(), Self::Error> {
+        self.serialize_str(name)
+    }
+
+    fn start_array(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
+        self.add_header(len);
+        Ok(())
+    }
+
+    fn start_map(&mut self, len: Option<usize>) -> Result<(), Self::Error> {
+        self.add_header(len);
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{Display, LowerExp};
+
+/// How text formats write floats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FloatFormat {
+    /// The fewest digits that read back as the same float, like `0.1`, switching to scientific
+    /// notation where the format usually does
+    #[default]
+    Shortest,
+    /// Exactly this many digits after the decimal point, like `0.10` for two
+    ///
+    /// With zero digits floats are written like integers, which formats with typed numbers,
+    /// like TOML and YAML, read back as integers.
+    Fixed(usize),
+    /// The fewest digits that read back as the same float, in scientific notation like `1.5e-7`
+    /// when the decimal exponent is below `min` or above `max`, and like `0.5` otherwise
+    Scientific {
+        /// The smallest exponent written without scientific notation
+        min: i32,
+        /// The largest exponent written without scientific notation
+        max: i32,
+    },
+}
+
+impl FloatFormat {
+    /// Writes `value` in this format, or returns `None` when the format should write it the
+    /// way it usually does: for [`FloatFormat::Shortest`], infinities and NaN.
+    pub fn format_f64(self, value: f64) -> Option<String> {
+        self.format(value, value.is_finite())
+    }
+
+    /// Writes `value` like [`format_f64`](Self::format_f64), with the digits of an `f32`,
+    /// so `0.1f32` is `0.1` rather than `0.10000000149011612`.
+    pub fn format_f32(self, value: f32) -> Option<String> {
+        self.format(value, value.is_finite())
+    }
+
+    fn format<F: Display + LowerExp>(self, value: F, finite: bool) -> Option<String> {
+        if !finite {
+            return None;
+        }
+        match self {
+            FloatFormat::Shortest => None,
+            FloatFormat::Fixed(digits) => Some(format!("{value:.digits$}")),
+            FloatFormat::Scientific { min, max } => {
+                let scientific = format!("{value:e}");
+                let exponent: i32 = scientific
+                    .split_once('e')
+                    .and_then(|(_, exponent)| exponent.parse().ok())
+                    .unwrap_or_default();
+                if exponent < min || exponent > max {
+                    Some(scientific)
+                } else {
+                    let mut positional = format!("{value}");
+                    // Keep it a float for formats that tell integers apart
+                    if !positional.contains('.') {
+                        positional.push_str(".0");
+                    }
+                    Some(positional)
+                }
+            }
+        }
+    }
+}
@@ -11,19 +11,43 @@ use alloc::string::String;
 use alloc::vec::Vec;
 
 use facet_core::{
-    Def, Facet, Field, PointerType, ScalarAffinity, ShapeAttribute, StructKind, Type, UserType,
+    BitFlag, Def, Facet, Field, PointerType, ScalarAffinity, Shape, ShapeAttribute,
+    SmartPointerFlags, StructKind, Type, UserType,
 };
 use facet_reflect::{
     FieldIter, FieldsForSerializeIter, HasFields, Peek, PeekListLikeIter, PeekMapIter, ScalarType,
+    ValueId,
 };
+use hashbrown::HashMap;
 use log::{debug, trace};
 
 mod debug_serializer;
 
+mod error;
+pub use error::{SerializeError, SerializeErrorKind};
+
+mod estimate;
+pub use estimate::estimate_size;
+
+mod float;
+pub use float::FloatFormat;
+
 fn variant_is_newtype_like(variant: &facet_core::Variant) -> bool {
     variant.data.kind == facet_core::StructKind::Tuple && variant.data.fields.len() == 1
 }
 
+/// The order struct fields are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FieldOrder {
+    /// The order they're declared in
+    #[default]
+    Declaration,
+    /// Sorted by name
+    Alphabetical,
+    /// Sorted by name, last first
+    ReverseAlphabetical,
+}
+
 // --- Serializer Trait Definition ---
 
 /// A trait for implementing format-specific serialization logic.
@@ -51,7 +75,13 @@ pub trait Serializer<'shape> {
     fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error>;
 
     /// Serialize a character.
-    fn serialize_char(&mut self, value: char) -> Result<(), Self::Error>;
+    ///
+    /// By default this is a string holding just that character, which is how text formats
+    /// write a `char`. Binary formats may write the code point instead.
+    #[inline(always)]
+    fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
+        self.serialize_str(value.encode_utf8(&mut [0; 4]))
+    }
 
     /// Serialize a UTF-8 string slice.
     fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error>;
@@ -219,6 +249,87 @@ pub trait Serializer<'shape> {
         let _ = discriminant;
         Ok(())
     }
+
+    /// Serialize a unit variant of an enum marked `#[facet(discriminant)]`, by its
+    /// discriminant rather than its name.
+    ///
+    /// Writes the discriminant as an `i64` by default. Formats that already wrote it in
+    /// [`start_enum_variant`](Self::start_enum_variant) should write nothing here.
+    #[inline(always)]
+    fn serialize_unit_variant_discriminant(
+        &mut self,
+        discriminant: i64,
+    ) -> Result<(), Self::Error> {
+        self.serialize_i64(discriminant)
+    }
+
+    /// The order to write the fields of structs and struct variants in.
+    ///
+    /// Sorting them keeps the output the same when fields are moved around in the code,
+    /// which makes for stable snapshots and diffs.
+    #[inline(always)]
+    fn field_order(&self) -> FieldOrder {
+        FieldOrder::Declaration
+    }
+
+    /// Whether an `Rc` or `Arc` pointing at a value that was already written should be
+    /// written as a reference to it, with [`serialize_shared_ref`](Self::serialize_shared_ref).
+    ///
+    /// This is off by default: shared values are written out again each time, and cycles
+    /// are reported with [`serialize_pointer_cycle`](Self::serialize_pointer_cycle).
+    #[inline(always)]
+    fn shared_refs(&self) -> bool {
+        false
+    }
+
+    /// Serialize a reference to a value behind an `Rc` or `Arc` that was already written.
+    ///
+    /// Shared values are numbered from 0, in the order they're first written. This writes
+    /// `{"$ref": id}` by default.
+    fn serialize_shared_ref(&mut self, id: usize) -> Result<(), Self::Error> {
+        self.start_object(Some(1))?;
+        self.serialize_field_name("$ref")?;
+        self.serialize_usize(id)?;
+        self.end_object()
+    }
+
+    /// Called when an `Rc` or `Arc` points back at a value of type `shape` that is still
+    /// being written, which would otherwise never finish.
+    ///
+    /// Formats should return an error here, with [`pointer_cycle_message`] as its message.
+    fn serialize_pointer_cycle(&mut self, shape: &'shape Shape<'shape>) -> Result<(), Self::Error>;
+
+    /// Called before writing a field, element, map entry, or enum variant's contents.
+    ///
+    /// `path` leads from the root value to the one about to be written, and ends with the
+    /// new segment. `container` is the shape of the struct, list, map, or enum it's in.
+    /// Formats that flatten or namespace nested values, like CSV headers or XML elements,
+    /// can use these to know where they are. Does nothing by default.
+    #[inline(always)]
+    fn begin_path_segment(
+        &mut self,
+        path: &[PathSegment<'_, '_, 'shape>],
+        container: &'shape Shape<'shape>,
+    ) -> Result<(), Self::Error> {
+        let _ = (path, container);
+        Ok(())
+    }
+
+    /// Called after the value from the matching
+    /// [`begin_path_segment`](Self::begin_path_segment) was written.
+    #[inline(always)]
+    fn end_path_segment(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Explains why a value of type `shape` can't be serialized, for
+/// [`Serializer::serialize_pointer_cycle`].
+pub fn pointer_cycle_message(shape: &Shape<'_>) -> String {
+    alloc::format!(
+        "Cycle detected: a {shape} contains an `Rc` or `Arc` that points back at it. \
+         Use a `Weak` for back-references, or enable shared references in the serializer"
+    )
 }
 
 // --- Iterative Serialization Logic ---
@@ -227,40 +338,83 @@ pub trait Serializer<'shape> {
 enum SerializeTask<'mem, 'facet, 'shape> {
     Value(Peek<'mem, 'facet, 'shape>, Option<Field<'shape>>),
     Object {
+        shape: &'shape Shape<'shape>,
         entries: FieldsForSerializeIter<'mem, 'facet, 'shape>,
         first: bool,
         len: usize,
     },
     Array {
+        shape: &'shape Shape<'shape>,
         items: PeekListLikeIter<'mem, 'facet, 'shape>,
-        first: bool,
+        index: usize,
     },
     Map {
+        shape: &'shape Shape<'shape>,
         entries: PeekMapIter<'mem, 'facet, 'shape>,
         first: bool,
         len: usize,
     },
     TupleStruct {
+        shape: &'shape Shape<'shape>,
         items: FieldsForSerializeIter<'mem, 'facet, 'shape>,
-        first: bool,
+        index: usize,
         len: usize,
     },
     Tuple {
+        shape: &'shape Shape<'shape>,
         items: FieldIter<'mem, 'facet, 'shape>,
-        first: bool,
+        index: usize,
     },
     // End markers
     EndObject,
-    EndArray,
     EndMapKey,
     EndMapValue,
     EndField,
+    EndPointer,
+    // Path tracking, around each field, element, or map entry
+    PushPath(PathSegment<'mem, 'facet, 'shape>, &'shape Shape<'shape>),
+    PopPath,
     // Field-related tasks
     SerializeFieldName(&'shape str),
     SerializeMapKey(Peek<'mem, 'facet, 'shape>),
     SerializeMapValue(Peek<'mem, 'facet, 'shape>),
 }
 
+/// One step from a value to a value inside it.
+///
+/// Displays as it appears in a path, like `.name` or `[2]`.
+#[derive(Clone, Copy, Debug)]
+pub enum PathSegment<'mem, 'facet, 'shape> {
+    /// A struct or struct variant field
+    Field(&'shape str),
+    /// An element of a list, array, or tuple, or a tuple field
+    Index(usize),
+    /// The entry of a map with this key
+    Key(Peek<'mem, 'facet, 'shape>),
+    /// The active variant of an enum
+    Variant(&'shape str),
+}
+
+impl core::fmt::Display for PathSegment<'_, '_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PathSegment::Field(name) => write!(f, ".{name}"),
+            PathSegment::Index(index) => write!(f, "[{index}]"),
+            PathSegment::Key(key) => write!(f, "[{key:?}]"),
+            PathSegment::Variant(name) => write!(f, "::{name}"),
+        }
+    }
+}
+
+/// Writes a path like `Config.servers[2].name`, starting at a value of type `root`.
+fn format_path(root: &Shape<'_>, path: &[PathSegment<'_, '_, '_>]) -> String {
+    let mut out = alloc::format!("{root}");
+    for segment in path {
+        out.push_str(&alloc::format!("{segment}"));
+    }
+    out
+}
+
 /// Serializes a `Peek` value using the provided `Serializer`.
 ///
 /// This function uses an iterative approach with a stack to avoid recursion depth limits.
@@ -268,17 +422,80 @@ pub fn serialize_iterative<'mem, 'facet, 'shape, S>(
     peek: Peek<'mem, 'facet, 'shape>,
     serializer: &mut S,
 ) -> Result<(), S::Error>
+where
+    S: Serializer<'shape>,
+{
+    serialize_tracking_path(peek, serializer, &mut Vec::new())
+}
+
+/// Serializes a `Peek` value like [`serialize_iterative`], converting any error into a
+/// [`SerializeError`] that says where in the value it happened.
+pub fn serialize_with_path<'mem, 'facet, 'shape, S>(
+    peek: Peek<'mem, 'facet, 'shape>,
+    serializer: &mut S,
+) -> Result<(), SerializeError>
+where
+    S: Serializer<'shape>,
+    S::Error: Into<SerializeError>,
+{
+    let mut path = Vec::new();
+    serialize_tracking_path(peek, serializer, &mut path)
+        .map_err(|error| error.into().with_path(format_path(peek.shape(), &path)))
+}
+
+/// Serializes `peek`, keeping `path` pointed at the value being written.
+fn serialize_tracking_path<'mem, 'facet, 'shape, S>(
+    peek: Peek<'mem, 'facet, 'shape>,
+    serializer: &mut S,
+    path: &mut Vec<PathSegment<'mem, 'facet, 'shape>>,
+) -> Result<(), S::Error>
 where
     S: Serializer<'shape>,
 {
     let mut stack = Vec::new();
     stack.push(SerializeTask::Value(peek, None));
 
+    // Values behind an `Rc` or `Arc` that are being written, to catch cycles
+    let mut active_pointees: Vec<ValueId<'shape>> = Vec::new();
+    // Values behind an `Rc` or `Arc` that were written, by id, for `shared_refs`
+    let mut shared: HashMap<ValueId<'shape>, usize> = HashMap::new();
+
     while let Some(task) = stack.pop() {
         match task {
             SerializeTask::Value(mut cpeek, maybe_field) => {
                 debug!("Serializing a value, shape is {}", cpeek.shape());
 
+                // `#[facet(format = ...)]` writes the value as a string
+                if cpeek.shape().has_format_attr() {
+                    if let Some(display) = cpeek.display() {
+                        serializer.serialize_str(&alloc::format!("{display}"))?;
+                        continue;
+                    }
+                }
+
+                // `#[facet(bitflags)]` writes the names of the flags that are set
+                if let Some(flags) = cpeek.shape().bit_flags() {
+                    let bits = cpeek
+                        .into_struct()
+                        .ok()
+                        .and_then(|ps| ps.field(0).ok())
+                        .and_then(flag_bits);
+                    if let Some(bits) = bits {
+                        let mut names = BitFlag::names(flags, bits);
+                        let mut items: Vec<String> = names.by_ref().map(String::from).collect();
+                        if names.remaining() != 0 {
+                            // Bits no flag covers, written like `bitflags` does
+                            items.push(alloc::format!("{:#x}", names.remaining()));
+                        }
+                        serializer.start_array(Some(items.len()))?;
+                        for item in &items {
+                            serializer.serialize_str(item)?;
+                        }
+                        serializer.end_array()?;
+                        continue;
+                    }
+                }
+
                 if cpeek
                     .shape()
                     .attributes
@@ -303,6 +520,14 @@ where
                     cpeek.shape()
                 );
                 match (cpeek.shape().def, cpeek.shape().ty) {
+                    _ if cpeek.shape().is_unit() => {
+                        // `()`, `PhantomData` and unit structs
+                        serializer.serialize_unit()?
+                    }
+                    (Def::Scalar(_), _) if cpeek.as_bytes().is_some() => {
+                        // `Cow<[u8]>`
+                        serializer.serialize_bytes(cpeek.as_bytes().unwrap())?
+                    }
                     (Def::Scalar(sd), _) => {
                         let cpeek = cpeek.innermost_peek();
 
@@ -326,6 +551,9 @@ where
                             Some(ScalarType::CowStr) => serializer.serialize_str(
                                 cpeek.get::<alloc::borrow::Cow<'_, str>>().unwrap().as_ref(),
                             )?,
+                            Some(ScalarType::ArcStr | ScalarType::RcStr | ScalarType::BoxStr) => {
+                                serializer.serialize_str(cpeek.as_str().unwrap())?
+                            }
 
                             // Float types
                             Some(ScalarType::F32) => {
@@ -414,32 +642,30 @@ where
                                 // serialize as array
                                 let peek_list = cpeek.into_list_like().unwrap();
                                 stack.push(SerializeTask::Array {
+                                    shape: cpeek.shape(),
                                     items: peek_list.iter(),
-                                    first: true,
+                                    index: 0,
                                 });
                             }
                         } else {
                             let peek_list = cpeek.into_list_like().unwrap();
                             stack.push(SerializeTask::Array {
+                                shape: cpeek.shape(),
                                 items: peek_list.iter(),
-                                first: true,
+                                index: 0,
                             });
                         }
                     }
                     (Def::Array(ad), _) => {
                         if ad.t().is_type::<u8>() {
-                            let bytes: Vec<u8> = cpeek
-                                .into_list_like()
-                                .unwrap()
-                                .iter()
-                                .map(|p| *p.get::<u8>().unwrap())
-                                .collect();
-                            serializer.serialize_bytes(&bytes)?;
+                            let peek_list = cpeek.into_list_like().unwrap();
+                            serializer.serialize_bytes(peek_list.as_bytes().unwrap())?;
                         } else {
                             let peek_list = cpeek.into_list_like().unwrap();
                             stack.push(SerializeTask::Array {
+                                shape: cpeek.shape(),
                                 items: peek_list.iter(),
-                                first: true,
+                                index: 0,
                             });
                         }
                     }
@@ -449,8 +675,9 @@ where
                         } else {
                             let peek_list = cpeek.into_list_like().unwrap();
                             stack.push(SerializeTask::Array {
+                                shape: cpeek.shape(),
                                 items: peek_list.iter(),
-                                first: true,
+                                index: 0,
                             });
                         }
                     }
@@ -458,6 +685,7 @@ where
                         let peek_map = cpeek.into_map().unwrap();
                         let len = peek_map.len();
                         stack.push(SerializeTask::Map {
+                            shape: cpeek.shape(),
                             entries: peek_map.iter(),
                             first: true,
                             len,
@@ -471,21 +699,42 @@ where
                             serializer.serialize_none()?;
                         }
                     }
-                    (Def::SmartPointer(_), _) => {
+                    (Def::SmartPointer(spd), _) if spd.flags.contains(SmartPointerFlags::WEAK) => {
+                        // What a `Weak` points at is owned elsewhere, and it may be gone
+                        serializer.serialize_none()?;
+                    }
+                    (Def::SmartPointer(spd), _) => {
                         // For smart pointers, we need to borrow the inner value and serialize it
                         // This is similar to how transparent structs work - we serialize the inner value directly
 
                         let sp = cpeek.into_smart_pointer().unwrap();
-                        if let Some(inner_peek) = sp.borrow_inner() {
-                            // Push the inner value to be serialized
-                            stack.push(SerializeTask::Value(inner_peek, None));
-                        } else {
+                        let Some(inner_peek) = sp.borrow_inner() else {
                             // The smart pointer doesn't support borrowing or has an opaque pointee
                             // We can't serialize it
                             todo!(
                                 "Smart pointer without borrow support or with opaque pointee cannot be serialized"
                             );
+                        };
+
+                        // Pointers with a weak counterpart (`Rc`, `Arc`) can share their value
+                        if spd.weak.is_none() {
+                            stack.push(SerializeTask::Value(inner_peek, None));
+                            continue;
                         }
+                        let id = inner_peek.id();
+                        if serializer.shared_refs() {
+                            if let Some(&index) = shared.get(&id) {
+                                serializer.serialize_shared_ref(index)?;
+                                continue;
+                            }
+                            shared.insert(id, shared.len());
+                        } else if active_pointees.contains(&id) {
+                            serializer.serialize_pointer_cycle(inner_peek.shape())?;
+                            continue;
+                        }
+                        active_pointees.push(id);
+                        stack.push(SerializeTask::EndPointer);
+                        stack.push(SerializeTask::Value(inner_peek, None));
                     }
                     (_, Type::User(UserType::Struct(sd))) => {
                         debug!("Serializing struct: shape={}", cpeek.shape(),);
@@ -508,8 +757,9 @@ where
                                 debug!("  Serializing {} fields as tuple", sd.fields.len());
 
                                 stack.push(SerializeTask::Tuple {
+                                    shape: cpeek.shape(),
                                     items: fields_iter,
-                                    first: true,
+                                    index: 0,
                                 });
                                 trace!(
                                     "  Pushed TupleFields to stack for tuple, will handle {} fields",
@@ -523,8 +773,9 @@ where
                                 debug!("  Serializing {} fields as array", fields);
 
                                 stack.push(SerializeTask::TupleStruct {
+                                    shape: cpeek.shape(),
                                     items: peek_struct.fields_for_serialize(),
-                                    first: true,
+                                    index: 0,
                                     len: fields,
                                 });
                                 trace!(
@@ -532,6 +783,27 @@ where
                                     fields
                                 );
                             }
+                            StructKind::Struct
+                                if serializer.field_order() != FieldOrder::Declaration =>
+                            {
+                                let peek_struct = cpeek.into_struct().unwrap();
+                                let fields = sorted_fields(
+                                    peek_struct.fields_for_serialize(),
+                                    serializer.field_order(),
+                                );
+                                serializer.start_object(Some(fields.len()))?;
+                                stack.push(SerializeTask::EndObject);
+                                for (field, field_peek) in fields.into_iter().rev() {
+                                    stack.push(SerializeTask::EndField);
+                                    stack.push(SerializeTask::PopPath);
+                                    stack.push(SerializeTask::Value(field_peek, Some(field)));
+                                    stack.push(SerializeTask::PushPath(
+                                        PathSegment::Field(field.name),
+                                        cpeek.shape(),
+                                    ));
+                                    stack.push(SerializeTask::SerializeFieldName(field.name));
+                                }
+                            }
                             StructKind::Struct => {
                                 debug!("  Handling record struct");
                                 let peek_struct = cpeek.into_struct().unwrap();
@@ -539,6 +811,7 @@ where
                                 debug!("  Serializing {} fields as object", fields);
 
                                 stack.push(SerializeTask::Object {
+                                    shape: cpeek.shape(),
                                     entries: peek_struct.fields_for_serialize(),
                                     first: true,
                                     len: fields,
@@ -565,6 +838,18 @@ where
                             "Active variant index is {}, variant is {:?}",
                             variant_index, variant
                         );
+
+                        // A `#[facet(other)]` variant writes back the name or discriminant it
+                        // was read from
+                        if variant.is_other() && variant.data.fields.len() == 1 {
+                            if let Some((field, field_peek)) =
+                                peek_enum.fields_for_serialize().next()
+                            {
+                                stack.push(SerializeTask::Value(field_peek, Some(field)));
+                                continue;
+                            }
+                        }
+
                         let discriminant = variant
                             .discriminant
                             .map(|d| d as u64)
@@ -574,8 +859,17 @@ where
 
                         if variant.data.fields.is_empty() {
                             // Unit variant
-                            serializer.serialize_unit_variant(variant_index, variant.name)?;
+                            if cpeek.shape().has_discriminant_attr() {
+                                serializer.serialize_unit_variant_discriminant(
+                                    variant.discriminant.unwrap_or(variant_index as i64),
+                                )?;
+                            } else {
+                                serializer.serialize_unit_variant(variant_index, variant.name)?;
+                            }
                         } else {
+                            path.push(PathSegment::Variant(variant.name));
+                            serializer.begin_path_segment(path, cpeek.shape())?;
+                            stack.push(SerializeTask::PopPath);
                             if !flattened {
                                 // For now, treat all enum variants with data as objects
                                 serializer.start_object(Some(1))?;
@@ -583,28 +877,33 @@ where
 
                                 // Serialize variant name as field name
                                 serializer.serialize_field_name(variant.name)?;
+                                stack.push(SerializeTask::EndField);
                             }
 
                             if variant_is_newtype_like(variant) {
                                 // Newtype variant - serialize the inner value directly
-                                let fields = peek_enum.fields_for_serialize().collect::<Vec<_>>();
-                                let (field, field_peek) = fields[0];
+                                let (field, field_peek) =
+                                    peek_enum.fields_for_serialize().next().unwrap();
                                 // TODO: error if `skip_serialize` is set?
                                 stack.push(SerializeTask::Value(field_peek, Some(field)));
                             } else if variant.data.kind == StructKind::Tuple
                                 || variant.data.kind == StructKind::TupleStruct
                             {
                                 // Tuple variant - serialize as array
-                                let fields = peek_enum.fields_for_serialize().count();
-                                serializer.start_array(Some(fields))?;
-                                stack.push(SerializeTask::EndArray);
-
-                                // Push fields in reverse order for tuple variant
-                                let fields_for_serialize =
-                                    peek_enum.fields_for_serialize().collect::<Vec<_>>();
-                                for (field, field_peek) in fields_for_serialize.into_iter().rev() {
-                                    stack.push(SerializeTask::Value(field_peek, Some(field)));
-                                }
+                                stack.push(SerializeTask::TupleStruct {
+                                    shape: cpeek.shape(),
+                                    items: peek_enum.fields_for_serialize(),
+                                    index: 0,
+                                    len: peek_enum.fields_for_serialize().count(),
+                                });
+                            } else if serializer.field_order() == FieldOrder::Declaration {
+                                // Struct variant - serialize as object
+                                stack.push(SerializeTask::Object {
+                                    shape: cpeek.shape(),
+                                    entries: peek_enum.fields_for_serialize(),
+                                    first: true,
+                                    len: peek_enum.fields_for_serialize().count(),
+                                });
                             } else {
                                 // Struct variant - serialize as object
                                 let fields = peek_enum.fields_for_serialize().count();
@@ -612,11 +911,18 @@ where
                                 stack.push(SerializeTask::EndObject);
 
                                 // Push fields in reverse order for struct variant
-                                let fields_for_serialize =
-                                    peek_enum.fields_for_serialize().collect::<Vec<_>>();
+                                let fields_for_serialize = sorted_fields(
+                                    peek_enum.fields_for_serialize(),
+                                    serializer.field_order(),
+                                );
                                 for (field, field_peek) in fields_for_serialize.into_iter().rev() {
                                     stack.push(SerializeTask::EndField);
+                                    stack.push(SerializeTask::PopPath);
                                     stack.push(SerializeTask::Value(field_peek, Some(field)));
+                                    stack.push(SerializeTask::PushPath(
+                                        PathSegment::Field(field.name),
+                                        cpeek.shape(),
+                                    ));
                                     stack.push(SerializeTask::SerializeFieldName(field.name));
                                 }
                             }
@@ -633,6 +939,17 @@ where
                         } else if let PointerType::Function(_) = pointer_type {
                             // Serialize function pointers as units
                             serializer.serialize_unit()?;
+                        } else if let Ok(peek_list) = cpeek.into_list_like() {
+                            // `&[T]` is serialized like the slice it points to
+                            stack.push(SerializeTask::Array {
+                                shape: cpeek.shape(),
+                                items: peek_list.iter(),
+                                index: 0,
+                            });
+                        } else if let (PointerType::Reference(_), Ok(pointee)) =
+                            (pointer_type, cpeek.into_pointee())
+                        {
+                            stack.push(SerializeTask::Value(pointee, None));
                         } else {
                             // Handle other pointer types with innermost_peek which is safe
                             let innermost = cpeek.innermost_peek();
@@ -657,6 +974,7 @@ where
             }
 
             SerializeTask::Object {
+                shape,
                 mut entries,
                 first,
                 len,
@@ -671,16 +989,26 @@ where
                 };
 
                 stack.push(SerializeTask::Object {
+                    shape,
                     entries,
                     first: false,
                     len,
                 });
                 stack.push(SerializeTask::EndField);
+                stack.push(SerializeTask::PopPath);
                 stack.push(SerializeTask::Value(value, Some(field)));
+                stack.push(SerializeTask::PushPath(
+                    PathSegment::Field(field.name),
+                    shape,
+                ));
                 stack.push(SerializeTask::SerializeFieldName(field.name));
             }
-            SerializeTask::Array { mut items, first } => {
-                if first {
+            SerializeTask::Array {
+                shape,
+                mut items,
+                index,
+            } => {
+                if index == 0 {
                     serializer.start_array(Some(items.len()))?;
                 }
 
@@ -690,12 +1018,16 @@ where
                 };
 
                 stack.push(SerializeTask::Array {
+                    shape,
                     items,
-                    first: false,
+                    index: index + 1,
                 });
+                stack.push(SerializeTask::PopPath);
                 stack.push(SerializeTask::Value(value, None));
+                stack.push(SerializeTask::PushPath(PathSegment::Index(index), shape));
             }
             SerializeTask::Map {
+                shape,
                 mut entries,
                 first,
                 len,
@@ -710,19 +1042,23 @@ where
                 };
 
                 stack.push(SerializeTask::Map {
+                    shape,
                     entries,
                     first: false,
                     len,
                 });
+                stack.push(SerializeTask::PopPath);
                 stack.push(SerializeTask::SerializeMapValue(value));
                 stack.push(SerializeTask::SerializeMapKey(key));
+                stack.push(SerializeTask::PushPath(PathSegment::Key(key), shape));
             }
             SerializeTask::TupleStruct {
+                shape,
                 mut items,
-                first,
+                index,
                 len,
             } => {
-                if first {
+                if index == 0 {
                     serializer.start_array(Some(len))?;
                 }
 
@@ -732,14 +1068,21 @@ where
                 };
 
                 stack.push(SerializeTask::TupleStruct {
+                    shape,
                     items,
-                    first: false,
+                    index: index + 1,
                     len,
                 });
+                stack.push(SerializeTask::PopPath);
                 stack.push(SerializeTask::Value(value, Some(field)));
+                stack.push(SerializeTask::PushPath(PathSegment::Index(index), shape));
             }
-            SerializeTask::Tuple { mut items, first } => {
-                if first {
+            SerializeTask::Tuple {
+                shape,
+                mut items,
+                index,
+            } => {
+                if index == 0 {
                     serializer.start_array(Some(items.len()))?;
                 }
 
@@ -749,10 +1092,13 @@ where
                 };
 
                 stack.push(SerializeTask::Tuple {
+                    shape,
                     items,
-                    first: false,
+                    index: index + 1,
                 });
+                stack.push(SerializeTask::PopPath);
                 stack.push(SerializeTask::Value(value, Some(field)));
+                stack.push(SerializeTask::PushPath(PathSegment::Index(index), shape));
             }
 
             // --- Field name and map key/value handling ---
@@ -774,9 +1120,6 @@ where
             SerializeTask::EndObject => {
                 serializer.end_object()?;
             }
-            SerializeTask::EndArray => {
-                serializer.end_array()?;
-            }
             SerializeTask::EndMapKey => {
                 serializer.end_map_key()?;
             }
@@ -786,6 +1129,17 @@ where
             SerializeTask::EndField => {
                 serializer.end_field()?;
             }
+            SerializeTask::EndPointer => {
+                active_pointees.pop();
+            }
+            SerializeTask::PushPath(segment, container) => {
+                path.push(segment);
+                serializer.begin_path_segment(path, container)?;
+            }
+            SerializeTask::PopPath => {
+                serializer.end_path_segment()?;
+                path.pop();
+            }
         }
     }
 
@@ -793,6 +1147,39 @@ where
     Ok(())
 }
 
+/// Reads the bits of a `#[facet(bitflags)]` type, widened to `u64`
+fn flag_bits(peek: Peek<'_, '_, '_>) -> Option<u64> {
+    Some(match peek.scalar_type()? {
+        ScalarType::U8 => *peek.get::<u8>().ok()? as u64,
+        ScalarType::U16 => *peek.get::<u16>().ok()? as u64,
+        ScalarType::U32 => *peek.get::<u32>().ok()? as u64,
+        ScalarType::U64 => *peek.get::<u64>().ok()?,
+        ScalarType::USize => *peek.get::<usize>().ok()? as u64,
+        ScalarType::I8 => *peek.get::<i8>().ok()? as u64,
+        ScalarType::I16 => *peek.get::<i16>().ok()? as u64,
+        ScalarType::I32 => *peek.get::<i32>().ok()? as u64,
+        ScalarType::I64 => *peek.get::<i64>().ok()? as u64,
+        ScalarType::ISize => *peek.get::<isize>().ok()? as u64,
+        _ => return None,
+    })
+}
+
+/// Collects `fields`, in the given order
+fn sorted_fields<'mem, 'facet, 'shape>(
+    fields: FieldsForSerializeIter<'mem, 'facet, 'shape>,
+    order: FieldOrder,
+) -> Vec<(Field<'shape>, Peek<'mem, 'facet, 'shape>)> {
+    let mut fields: Vec<_> = fields.collect();
+    match order {
+        FieldOrder::Declaration => {}
+        FieldOrder::Alphabetical => fields.sort_by_key(|(field, _)| field.name),
+        FieldOrder::ReverseAlphabetical => {
+            fields.sort_by_key(|(field, _)| core::cmp::Reverse(field.name))
+        }
+    }
+    fields
+}
+
 // --- Helper Trait for Ergonomics ---
 
 /// Extension trait to simplify calling the generic serializer.
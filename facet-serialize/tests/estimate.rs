@@ -0,0 +1,38 @@
+use facet::Facet;
+use facet_reflect::Peek;
+use facet_serialize::estimate_size;
+
+#[derive(Facet)]
+struct Point {
+    x: u8,
+    y: i32,
+    name: String,
+}
+
+#[test]
+fn test_estimate_struct() {
+    facet_testhelpers::setup();
+
+    let point = Point {
+        x: 5,
+        y: -1000,
+        name: "origin".to_string(),
+    };
+    // 1 for the header, 2 + 1 for `x`, 2 + 3 for `y` and 5 + 7 for `name`
+    assert_eq!(estimate_size(Peek::new(&point)), 21);
+}
+
+#[test]
+fn test_estimate_collections() {
+    facet_testhelpers::setup();
+
+    // A 2 byte header for the long string
+    let strings = vec!["x".repeat(40)];
+    assert_eq!(estimate_size(Peek::new(&strings)), 1 + 2 + 40);
+
+    let bytes = vec![0u8; 1000];
+    assert_eq!(estimate_size(Peek::new(&bytes)), 3 + 1000);
+
+    let empty: Vec<Point> = Vec::new();
+    assert_eq!(estimate_size(Peek::new(&empty)), 1);
+}
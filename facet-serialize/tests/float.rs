@@ -0,0 +1,45 @@
+use facet_serialize::FloatFormat;
+
+#[test]
+fn test_shortest_is_left_to_the_format() {
+    assert_eq!(FloatFormat::Shortest.format_f64(0.1), None);
+    assert_eq!(FloatFormat::Shortest.format_f32(0.1), None);
+}
+
+#[test]
+fn test_fixed() {
+    let format = FloatFormat::Fixed(2);
+    assert_eq!(format.format_f64(1.23456).as_deref(), Some("1.23"));
+    assert_eq!(format.format_f64(-2.0).as_deref(), Some("-2.00"));
+    assert_eq!(format.format_f32(0.1).as_deref(), Some("0.10"));
+    assert_eq!(
+        FloatFormat::Fixed(0).format_f64(42.0).as_deref(),
+        Some("42")
+    );
+}
+
+#[test]
+fn test_scientific() {
+    let format = FloatFormat::Scientific { min: -3, max: 6 };
+    assert_eq!(format.format_f64(1e20).as_deref(), Some("1e20"));
+    assert_eq!(format.format_f64(1.5e-7).as_deref(), Some("1.5e-7"));
+    assert_eq!(format.format_f64(-1234.5).as_deref(), Some("-1234.5"));
+    assert_eq!(format.format_f64(3.0).as_deref(), Some("3.0"));
+    assert_eq!(format.format_f64(0.001).as_deref(), Some("0.001"));
+    assert_eq!(format.format_f32(0.1).as_deref(), Some("0.1"));
+
+    let always = FloatFormat::Scientific { min: 1, max: 0 };
+    assert_eq!(always.format_f64(250.0).as_deref(), Some("2.5e2"));
+}
+
+#[test]
+fn test_non_finite_is_left_to_the_format() {
+    for format in [
+        FloatFormat::Fixed(2),
+        FloatFormat::Scientific { min: -3, max: 6 },
+    ] {
+        assert_eq!(format.format_f64(f64::NAN), None);
+        assert_eq!(format.format_f64(f64::INFINITY), None);
+        assert_eq!(format.format_f32(f32::NEG_INFINITY), None);
+    }
+}
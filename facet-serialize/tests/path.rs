@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_core::Shape;
+use facet_reflect::Peek;
+use facet_serialize::{PathSegment, Serializer, serialize_iterative};
+
+/// Writes down each value's path and the shape it's in, and ignores the values themselves
+#[derive(Default)]
+struct PathRecorder {
+    entered: Vec<String>,
+    depth: usize,
+}
+
+impl<'shape> Serializer<'shape> for PathRecorder {
+    type Error = core::convert::Infallible;
+
+    fn begin_path_segment(
+        &mut self,
+        path: &[PathSegment<'_, '_, 'shape>],
+        container: &'shape Shape<'shape>,
+    ) -> Result<(), Self::Error> {
+        let path: String = path.iter().map(|segment| segment.to_string()).collect();
+        self.entered.push(format!("{path} in {container}"));
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn end_path_segment(&mut self) -> Result<(), Self::Error> {
+        self.depth -= 1;
+        Ok(())
+    }
+
+    fn serialize_u64(&mut self, _value: u64) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_u128(&mut self, _value: u128) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_i64(&mut self, _value: i64) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_i128(&mut self, _value: i128) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_f64(&mut self, _value: f64) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_bool(&mut self, _value: bool) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_str(&mut self, _value: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_bytes(&mut self, _value: &[u8]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_none(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_unit(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_unit_variant(
+        &mut self,
+        _variant_index: usize,
+        _variant_name: &'shape str,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_pointer_cycle(
+        &mut self,
+        _shape: &'shape Shape<'shape>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_field_name(&mut self, _name: &'shape str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn start_array(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn start_map(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Column {
+    Text { width: u32 },
+    Number(u8, bool),
+}
+
+#[derive(Facet)]
+struct Table {
+    name: String,
+    columns: Vec<Column>,
+    labels: BTreeMap<String, u32>,
+}
+
+#[test]
+fn test_path_segments() {
+    facet_testhelpers::setup();
+
+    let table = Table {
+        name: "users".to_string(),
+        columns: vec![Column::Text { width: 20 }, Column::Number(3, true)],
+        labels: BTreeMap::from([("id".to_string(), 1)]),
+    };
+    let mut recorder = PathRecorder::default();
+    serialize_iterative(Peek::new(&table), &mut recorder).unwrap();
+
+    assert_eq!(
+        recorder.entered,
+        [
+            ".name in Table",
+            ".columns in Table",
+            ".columns[0] in Vec<Column>",
+            ".columns[0]::Text in Column",
+            ".columns[0]::Text.width in Column",
+            ".columns[1] in Vec<Column>",
+            ".columns[1]::Number in Column",
+            ".columns[1]::Number[0] in Column",
+            ".columns[1]::Number[1] in Column",
+            ".labels in Table",
+            ".labels[\"id\"] in BTreeMap<String, u32>",
+        ]
+    );
+    assert_eq!(recorder.depth, 0);
+}
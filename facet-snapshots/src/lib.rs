@@ -0,0 +1,285 @@
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use std::collections::BTreeMap;
+
+use bigapi_types_facet::{
+    Address, Business, BusinessOwner, Catalog, CatalogMetadata, Category, Gender, GeoLocation,
+    Product, ProductDimensions, ProductMetadata, Settings, Theme, User, UserProfile,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use facet::Facet;
+use uuid::Uuid;
+
+/// Integers of several widths and signs, and floats that print in different notations.
+#[derive(Facet, Debug, PartialEq)]
+pub struct Numbers {
+    pub unsigned: u8,
+    pub small: i8,
+    pub medium: i32,
+    pub large: u64,
+    pub negative: i64,
+    pub single: f32,
+    pub double: f64,
+    pub whole: f64,
+    pub huge: f64,
+    pub tiny: f64,
+}
+
+/// Strings that need quoting or escaping in at least one format.
+#[derive(Facet, Debug, PartialEq)]
+pub struct Text {
+    pub plain: String,
+    pub quoted: String,
+    pub path: String,
+    pub control: String,
+    pub unicode: String,
+    pub character: char,
+}
+
+/// Lists, a fixed-size array and a map.
+#[derive(Facet, Debug, PartialEq)]
+pub struct Collections {
+    pub list: Vec<u32>,
+    pub empty: Vec<u32>,
+    pub nested: Vec<Vec<i8>>,
+    pub fixed: [u16; 2],
+    pub by_name: BTreeMap<String, u32>,
+}
+
+/// A present and an absent option.
+#[derive(Facet, Debug, PartialEq)]
+pub struct Optionals {
+    pub present: Option<u32>,
+    pub absent: Option<u32>,
+}
+
+/// Fields declared out of alphabetical order, one of them renamed.
+#[derive(Facet, Debug, PartialEq)]
+pub struct Ordering {
+    pub zebra: u32,
+    #[facet(rename = "type")]
+    pub kind: String,
+    pub apple: u32,
+}
+
+/// Unit, newtype, tuple and struct variants.
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+pub enum Event {
+    Started,
+    Moved(i32),
+    Resized(u16, u16),
+    Renamed { from: String, to: String },
+}
+
+/// One event of each kind.
+#[derive(Facet, Debug, PartialEq)]
+pub struct Timeline {
+    pub events: Vec<Event>,
+}
+
+/// The [`Numbers`] in the snapshots.
+pub fn numbers() -> Numbers {
+    Numbers {
+        unsigned: 200,
+        small: -5,
+        medium: -1000,
+        large: 4_000_000_000,
+        negative: -40_000,
+        single: 0.1,
+        double: -2.5,
+        whole: 3.0,
+        huge: 1e20,
+        tiny: 1.5e-7,
+    }
+}
+
+/// The [`Text`] in the snapshots.
+pub fn text() -> Text {
+    Text {
+        plain: "hello".to_string(),
+        quoted: "say \"hi\"".to_string(),
+        path: "C:\\data".to_string(),
+        control: "tab\tbell\u{7}".to_string(),
+        unicode: "café 🦀".to_string(),
+        character: 'ß',
+    }
+}
+
+/// The [`Collections`] in the snapshots.
+pub fn collections() -> Collections {
+    Collections {
+        list: vec![1, 2, 3],
+        empty: vec![],
+        nested: vec![vec![1], vec![-1, 0]],
+        fixed: [7, 8],
+        by_name: BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)]),
+    }
+}
+
+/// The [`Optionals`] in the snapshots.
+pub fn optionals() -> Optionals {
+    Optionals {
+        present: Some(1),
+        absent: None,
+    }
+}
+
+/// The [`Ordering`] in the snapshots.
+pub fn ordering() -> Ordering {
+    Ordering {
+        zebra: 1,
+        kind: "kind".to_string(),
+        apple: 2,
+    }
+}
+
+/// The [`Timeline`] in the snapshots.
+pub fn timeline() -> Timeline {
+    Timeline {
+        events: vec![
+            Event::Started,
+            Event::Moved(-3),
+            Event::Resized(640, 480),
+            Event::Renamed {
+                from: "a".to_string(),
+                to: "b".to_string(),
+            },
+        ],
+    }
+}
+
+fn datetime(year: i32, month: u32, day: u32, hour: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .unwrap()
+        .and_hms_opt(hour, 30, 0)
+        .unwrap()
+}
+
+fn address(street: &str, city: &str, geo: Option<GeoLocation>) -> Address {
+    Address {
+        street: street.to_string(),
+        city: city.to_string(),
+        state: "Île-de-France".to_string(),
+        postal_code: "75001".to_string(),
+        country: "France".to_string(),
+        geo,
+    }
+}
+
+/// A small catalog with one business, one owner and one product, written out by hand so that
+/// it doesn't change when the fake data generator does.
+pub fn catalog() -> Catalog {
+    let owner = User {
+        id: Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8),
+        username: "camille".to_string(),
+        email: "camille@example.com".to_string(),
+        created_at: datetime(2021, 3, 4, 9),
+        updated_at: datetime(2024, 5, 6, 18),
+        profile: UserProfile {
+            first_name: "Camille".to_string(),
+            last_name: "Durand".to_string(),
+            date_of_birth: NaiveDate::from_ymd_opt(1985, 7, 14).unwrap(),
+            gender: Gender::PreferNotToSay,
+            bio: Some("Bakes \"real\" bread.\nNo shortcuts.".to_string()),
+            avatar_url: None,
+            home_address: address("12 rue de Rivoli", "Paris", None),
+        },
+        settings: Settings {
+            user_id: Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8),
+            email_notifications: true,
+            push_notifications: false,
+            theme: Theme::Dark,
+            language: "fr".to_string(),
+        },
+    };
+
+    let bread = Category {
+        id: Uuid::from_u128(0x1),
+        name: "bread".to_string(),
+        description: None,
+        parent: Some(Box::new(Category {
+            id: Uuid::from_u128(0x2),
+            name: "bakery".to_string(),
+            description: Some("Baked goods".to_string()),
+            parent: None,
+        })),
+    };
+
+    let baguette = Product {
+        id: Uuid::from_u128(0xa1a2a3a4_b1b2_c1c2_d1d2_d3d4d5d6d7d8),
+        name: "Baguette".to_string(),
+        description: Some("Crusty, 250 g".to_string()),
+        price_cents: 130,
+        currency: "EUR".to_string(),
+        available: true,
+        metadata: Some(ProductMetadata {
+            sku: Some("BG-250".to_string()),
+            categories: vec!["bread".to_string(), "daily".to_string()],
+            weight_grams: Some(250),
+            dimensions: Some(ProductDimensions {
+                length_mm: Some(650.0),
+                width_mm: Some(60.5),
+                height_mm: None,
+            }),
+        }),
+        reviews: vec![],
+        categories: vec![bread],
+    };
+
+    Catalog {
+        id: Uuid::from_u128(0x936da01f_9abd_4d9d_80c7_02af85c822a8),
+        businesses: vec![Business {
+            id: Uuid::from_u128(0x550e8400_e29b_41d4_a716_446655440000),
+            name: "Boulangerie Durand".to_string(),
+            address: address(
+                "3 place des Vosges",
+                "Paris",
+                Some(GeoLocation {
+                    latitude: 48.8553,
+                    longitude: 2.3655,
+                }),
+            ),
+            owner: BusinessOwner {
+                user: owner,
+                ownership_percent: 62.5,
+            },
+            users: vec![],
+            branches: vec![],
+            products: vec![baguette],
+            created_at: datetime(2020, 1, 2, 7),
+        }],
+        created_at: datetime(2025, 6, 7, 12),
+        metadata: CatalogMetadata {
+            version: "1.0".to_string(),
+            region: "FR".to_string(),
+        },
+    }
+}
+
+/// Formats `bytes` like `hexdump -C`: sixteen bytes per line after their offset, then the
+/// same bytes as ASCII, with a `.` for each byte that isn't printable.
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut lines = Vec::new();
+    for (index, chunk) in bytes.chunks(16).enumerate() {
+        let mut line = format!("{:08x} ", index * 16);
+        for column in 0..16 {
+            match chunk.get(column) {
+                Some(byte) => line.push_str(&format!(" {byte:02x}")),
+                None => line.push_str("   "),
+            }
+        }
+        line.push_str("  |");
+        for &byte in chunk {
+            line.push(if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            });
+        }
+        line.push('|');
+        lines.push(line);
+    }
+    lines.join("\n")
+}
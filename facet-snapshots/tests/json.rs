@@ -0,0 +1,40 @@
+//! The JSON every value of the corpus is written as.
+
+use facet_snapshots::{catalog, collections, numbers, optionals, ordering, text, timeline};
+use facet_testhelpers::test;
+use insta::assert_snapshot;
+
+#[test]
+fn test_numbers() {
+    assert_snapshot!(facet_json::to_string(&numbers()));
+}
+
+#[test]
+fn test_text() {
+    assert_snapshot!(facet_json::to_string(&text()));
+}
+
+#[test]
+fn test_collections() {
+    assert_snapshot!(facet_json::to_string(&collections()));
+}
+
+#[test]
+fn test_optionals() {
+    assert_snapshot!(facet_json::to_string(&optionals()));
+}
+
+#[test]
+fn test_ordering() {
+    assert_snapshot!(facet_json::to_string(&ordering()));
+}
+
+#[test]
+fn test_timeline() {
+    assert_snapshot!(facet_json::to_string(&timeline()));
+}
+
+#[test]
+fn test_catalog() {
+    assert_snapshot!(facet_json::to_string(&catalog()));
+}
@@ -0,0 +1,42 @@
+//! The MessagePack every value of the corpus is written as, as hex dumps.
+
+use facet_snapshots::{
+    catalog, collections, hex_dump, numbers, optionals, ordering, text, timeline,
+};
+use facet_testhelpers::test;
+use insta::assert_snapshot;
+
+#[test]
+fn test_numbers() {
+    assert_snapshot!(hex_dump(&facet_msgpack::to_vec(&numbers())));
+}
+
+#[test]
+fn test_text() {
+    assert_snapshot!(hex_dump(&facet_msgpack::to_vec(&text())));
+}
+
+#[test]
+fn test_collections() {
+    assert_snapshot!(hex_dump(&facet_msgpack::to_vec(&collections())));
+}
+
+#[test]
+fn test_optionals() {
+    assert_snapshot!(hex_dump(&facet_msgpack::to_vec(&optionals())));
+}
+
+#[test]
+fn test_ordering() {
+    assert_snapshot!(hex_dump(&facet_msgpack::to_vec(&ordering())));
+}
+
+#[test]
+fn test_timeline() {
+    assert_snapshot!(hex_dump(&facet_msgpack::to_vec(&timeline())));
+}
+
+#[test]
+fn test_catalog() {
+    assert_snapshot!(hex_dump(&facet_msgpack::to_vec(&catalog())));
+}
@@ -0,0 +1,36 @@
+//! The TOML every value of the corpus is written as. The catalog is left out: the `None`s
+//! TOML can't write would make it a snapshot of what's missing.
+
+use facet_snapshots::{collections, numbers, optionals, ordering, text, timeline};
+use facet_testhelpers::test;
+use insta::assert_snapshot;
+
+#[test]
+fn test_numbers() {
+    assert_snapshot!(facet_toml::to_string(&numbers())?);
+}
+
+#[test]
+fn test_text() {
+    assert_snapshot!(facet_toml::to_string(&text())?);
+}
+
+#[test]
+fn test_collections() {
+    assert_snapshot!(facet_toml::to_string(&collections())?);
+}
+
+#[test]
+fn test_optionals() {
+    assert_snapshot!(facet_toml::to_string(&optionals())?);
+}
+
+#[test]
+fn test_ordering() {
+    assert_snapshot!(facet_toml::to_string(&ordering())?);
+}
+
+#[test]
+fn test_timeline() {
+    assert_snapshot!(facet_toml::to_string(&timeline())?);
+}
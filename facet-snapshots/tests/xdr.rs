@@ -0,0 +1,23 @@
+//! The XDR the values of the corpus that XDR can describe are written as, as hex dumps.
+//!
+//! XDR has no maps, and facet-xdr doesn't write options, fixed-size arrays or tuple variants
+//! the way it reads them yet, so the other values are left out.
+
+use facet_snapshots::{hex_dump, numbers, ordering, text};
+use facet_testhelpers::test;
+use insta::assert_snapshot;
+
+#[test]
+fn test_numbers() {
+    assert_snapshot!(hex_dump(&facet_xdr::to_vec(&numbers())?));
+}
+
+#[test]
+fn test_text() {
+    assert_snapshot!(hex_dump(&facet_xdr::to_vec(&text())?));
+}
+
+#[test]
+fn test_ordering() {
+    assert_snapshot!(hex_dump(&facet_xdr::to_vec(&ordering())?));
+}
@@ -0,0 +1,305 @@
+//! Map database rows into Rust values.
+
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+};
+use facet_core::{Def, Facet, Field, FieldFlags, PrimitiveType, StructKind, Type, UserType};
+use facet_deserialize::{DeserErrorKind, set_numeric};
+use facet_reflect::{Partial, ScalarType};
+use log::trace;
+
+use crate::{Row, SqlDeError, SqlValue};
+
+/// Maps a database row into a value of type `T`.
+///
+/// Structs are filled by column name, honouring `#[facet(rename = "...")]`.
+/// Columns without a matching field are skipped unless the struct has
+/// `#[facet(deny_unknown_fields)]`; fields without a matching column become
+/// `None` or their `#[facet(default)]`. Tuples and tuple structs are filled by
+/// column position, and any other type is read from a single-column row.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_sql::{Row, SqlValue};
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct User {
+///     id: u32,
+///     #[facet(rename = "display_name")]
+///     name: String,
+///     email: Option<String>,
+/// }
+///
+/// // A row as a driver would return it
+/// struct Columns(Vec<(&'static str, SqlValue<'static>)>);
+///
+/// impl Row for Columns {
+///     type Error = core::convert::Infallible;
+///
+///     fn column_count(&self) -> usize {
+///         self.0.len()
+///     }
+///
+///     fn column_name(&self, index: usize) -> Result<&str, Self::Error> {
+///         Ok(self.0[index].0)
+///     }
+///
+///     fn value(&self, index: usize) -> Result<SqlValue<'_>, Self::Error> {
+///         Ok(self.0[index].1)
+///     }
+/// }
+///
+/// let row = Columns(vec![
+///     ("id", SqlValue::Integer(7)),
+///     ("display_name", SqlValue::Text("Ada")),
+///     ("email", SqlValue::Null),
+/// ]);
+///
+/// let user: User = facet_sql::from_row(&row).unwrap();
+/// assert_eq!(
+///     user,
+///     User {
+///         id: 7,
+///         name: "Ada".to_string(),
+///         email: None,
+///     }
+/// );
+/// ```
+pub fn from_row<'facet: 'shape, 'shape, T: Facet<'facet>, R: Row + ?Sized>(
+    row: &R,
+) -> Result<T, SqlDeError<'shape>> {
+    let mut partial = Partial::alloc::<T>()?;
+    let wip = partial.inner_mut();
+    let shape = wip.shape();
+    trace!(
+        "Mapping a row of {} columns into {}",
+        row.column_count(),
+        shape
+    );
+
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => match sd.kind {
+            StructKind::Struct => deserialize_named(wip, sd.fields, row)?,
+            StructKind::Tuple | StructKind::TupleStruct => {
+                deserialize_positional(wip, sd.fields, row)?
+            }
+            _ => return Err(SqlDeError::UnsupportedShape(shape)),
+        },
+        _ => {
+            if row.column_count() != 1 {
+                return Err(SqlDeError::ColumnCount {
+                    expected: 1,
+                    found: row.column_count(),
+                });
+            }
+            let (column, value) = column(row, 0)?;
+            deserialize_value(wip, column, value)?;
+        }
+    }
+
+    let value = partial.build()?;
+    Ok(*value)
+}
+
+/// Reads the name and value of a column.
+fn column<'row, 'shape, R: Row + ?Sized>(
+    row: &'row R,
+    index: usize,
+) -> Result<(&'row str, SqlValue<'row>), SqlDeError<'shape>> {
+    let driver = |e: R::Error| SqlDeError::Driver(e.to_string());
+    Ok((
+        row.column_name(index).map_err(driver)?,
+        row.value(index).map_err(driver)?,
+    ))
+}
+
+fn deserialize_named<'facet, 'shape, R: Row + ?Sized>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    row: &R,
+) -> Result<(), SqlDeError<'shape>> {
+    let shape = wip.shape();
+    for index in 0..row.column_count() {
+        let (column, value) = column(row, index)?;
+        let Some(field_index) = fields.iter().position(|f| f.name == column) else {
+            if shape.has_deny_unknown_fields_attr() {
+                return Err(SqlDeError::UnknownColumn {
+                    column: column.to_string(),
+                    shape,
+                });
+            }
+            trace!("Skipping unknown column '{column}' of {shape}");
+            continue;
+        };
+        wip.begin_nth_field(field_index)?;
+        deserialize_value(wip, column, value)?;
+        wip.end()?;
+    }
+
+    wip.fill_unset_fields_from_default()?;
+    for (index, field) in fields.iter().enumerate() {
+        if !wip.is_field_set(index)? {
+            default_field(wip, index, field)?;
+        }
+    }
+    Ok(())
+}
+
+fn deserialize_positional<'facet, 'shape, R: Row + ?Sized>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    row: &R,
+) -> Result<(), SqlDeError<'shape>> {
+    if row.column_count() != fields.len() {
+        return Err(SqlDeError::ColumnCount {
+            expected: fields.len(),
+            found: row.column_count(),
+        });
+    }
+    for index in 0..fields.len() {
+        let (column, value) = column(row, index)?;
+        wip.begin_nth_field(index)?;
+        deserialize_value(wip, column, value)?;
+        wip.end()?;
+    }
+    Ok(())
+}
+
+/// Fills a field that has no column in the row.
+fn default_field<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    index: usize,
+    field: &Field<'shape>,
+) -> Result<(), SqlDeError<'shape>> {
+    let has_default = field.flags.contains(FieldFlags::DEFAULT);
+    if !has_default && !matches!(field.shape().def, Def::Option(_)) {
+        return Err(SqlDeError::MissingColumn(field.name));
+    }
+
+    wip.begin_nth_field(index)?;
+    match field.vtable.default_fn {
+        Some(default_fn) if has_default => wip.set_field_default(default_fn)?,
+        _ => wip.set_default()?,
+    };
+    wip.end()?;
+    Ok(())
+}
+
+fn deserialize_value<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    column: &str,
+    value: SqlValue<'_>,
+) -> Result<(), SqlDeError<'shape>> {
+    let shape = wip.shape();
+    trace!(
+        "Deserializing column '{column}' ({}) into {shape}",
+        value.kind()
+    );
+    let mismatch = || SqlDeError::TypeMismatch {
+        column: column.to_string(),
+        found: value.kind(),
+        shape,
+    };
+
+    if let Def::Option(_) = shape.def {
+        if value == SqlValue::Null {
+            wip.set_default()?;
+        } else {
+            wip.begin_some()?;
+            deserialize_value(wip, column, value)?;
+            wip.end()?;
+        }
+        return Ok(());
+    }
+
+    if shape.is_type::<Vec<u8>>() {
+        let SqlValue::Blob(blob) = value else {
+            return Err(mismatch());
+        };
+        wip.set(blob.to_vec())?;
+        return Ok(());
+    }
+
+    let scalar = ScalarType::try_from_shape(shape);
+    if scalar.is_none() && shape.inner.is_some() {
+        wip.begin_inner()?;
+        deserialize_value(wip, column, value)?;
+        wip.end()?;
+        return Ok(());
+    }
+
+    let numeric = |kind: DeserErrorKind<'shape>| match kind {
+        DeserErrorKind::NumericConversion {
+            from,
+            to,
+            value,
+            reason,
+        } => SqlDeError::NumericConversion {
+            column: column.to_string(),
+            from,
+            to,
+            value,
+            reason: Box::new(reason),
+        },
+        DeserErrorKind::ReflectError(err) => SqlDeError::Reflect(err),
+        _ => mismatch(),
+    };
+
+    match (scalar, value) {
+        (_, SqlValue::Null) => {
+            return Err(SqlDeError::UnexpectedNull {
+                column: column.to_string(),
+                shape,
+            });
+        }
+        (Some(ScalarType::Bool), SqlValue::Bool(b)) => {
+            wip.set(b)?;
+        }
+        // SQLite has no boolean type and stores them as 0 and 1
+        (Some(ScalarType::Bool), SqlValue::Integer(i)) => {
+            wip.set(i != 0)?;
+        }
+        (_, SqlValue::Integer(i)) => set_numeric(wip, i).map_err(numeric)?,
+        (_, SqlValue::Real(r)) => set_numeric(wip, r).map_err(numeric)?,
+        (Some(ScalarType::String), SqlValue::Text(text)) => {
+            wip.set(String::from(text))?;
+        }
+        (Some(ScalarType::CowStr), SqlValue::Text(text)) => {
+            wip.set(Cow::<'static, str>::Owned(text.to_string()))?;
+        }
+        (
+            Some(ScalarType::ArcStr | ScalarType::RcStr | ScalarType::BoxStr),
+            SqlValue::Text(text),
+        ) => {
+            wip.parse_from_str(text)?;
+        }
+        (None, SqlValue::Text(text)) if matches!(shape.ty, Type::User(UserType::Enum(_))) => {
+            // Unit variants are stored by name
+            match wip.find_variant(text) {
+                Some((_, variant)) if variant.data.kind == StructKind::Unit => {
+                    wip.select_variant_named(text)?;
+                }
+                _ => {
+                    return Err(SqlDeError::InvalidValue {
+                        column: column.to_string(),
+                        shape,
+                    });
+                }
+            }
+        }
+        // Numbers are read from numeric columns only, text is for types like UUIDs and dates
+        (_, SqlValue::Text(text))
+            if shape.is_from_str()
+                && !matches!(shape.ty, Type::Primitive(PrimitiveType::Numeric(_))) =>
+        {
+            wip.parse_from_str(text)
+                .map_err(|_| SqlDeError::InvalidValue {
+                    column: column.to_string(),
+                    shape,
+                })?;
+        }
+        _ => return Err(mismatch()),
+    }
+    Ok(())
+}
@@ -0,0 +1,134 @@
+//! Errors from mapping rows to Rust values.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use facet_core::Shape;
+use facet_deserialize::NumericConversionReason;
+use facet_reflect::ReflectError;
+
+/// Errors when mapping a database row to a Rust value.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SqlDeError<'shape> {
+    /// The database driver failed to read a column.
+    Driver(String),
+    /// A column doesn't match any field, and the struct denies unknown fields.
+    UnknownColumn {
+        /// The column name.
+        column: String,
+        /// The struct type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A field has no matching column and its type has no default value.
+    MissingColumn(&'shape str),
+    /// A tuple was mapped from a row with a different number of columns.
+    ColumnCount {
+        /// The number of tuple fields.
+        expected: usize,
+        /// The number of columns in the row.
+        found: usize,
+    },
+    /// A column is `NULL` but its field isn't an `Option`.
+    UnexpectedNull {
+        /// The column name.
+        column: String,
+        /// The field type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A column has a different type than its field expects.
+    TypeMismatch {
+        /// The column name.
+        column: String,
+        /// The SQL type found, such as `"integer"` or `"text"`.
+        found: &'static str,
+        /// The field type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A number doesn't fit the numeric type of its field.
+    NumericConversion {
+        /// The column name.
+        column: String,
+        /// The type the column was read as.
+        from: &'static str,
+        /// The type of the field.
+        to: &'static str,
+        /// The number in the column.
+        value: String,
+        /// Why the number doesn't fit, boxed to keep the error small.
+        reason: Box<NumericConversionReason>,
+    },
+    /// A text column couldn't be parsed into its field type.
+    InvalidValue {
+        /// The column name.
+        column: String,
+        /// The field type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// The Rust type can't be mapped from a row.
+    UnsupportedShape(&'shape Shape<'shape>),
+    /// Any error from facet.
+    Reflect(ReflectError<'shape>),
+}
+
+impl<'shape> From<ReflectError<'shape>> for SqlDeError<'shape> {
+    fn from(err: ReflectError<'shape>) -> Self {
+        SqlDeError::Reflect(err)
+    }
+}
+
+impl core::fmt::Display for SqlDeError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SqlDeError::Driver(message) => write!(f, "Database error: {message}"),
+            SqlDeError::UnknownColumn { column, shape } => {
+                write!(f, "Unknown column '{column}' for {shape}")
+            }
+            SqlDeError::MissingColumn(name) => write!(f, "No column for field '{name}'"),
+            SqlDeError::ColumnCount { expected, found } => {
+                write!(f, "Expected {expected} columns, found {found}")
+            }
+            SqlDeError::UnexpectedNull { column, shape } => {
+                write!(f, "Column '{column}' is NULL, but {shape} is not an Option")
+            }
+            SqlDeError::TypeMismatch {
+                column,
+                found,
+                shape,
+            } => write!(f, "Column '{column}': expected {shape}, found {found}"),
+            SqlDeError::NumericConversion {
+                column,
+                from,
+                to,
+                value,
+                reason,
+            } => match &**reason {
+                NumericConversionReason::OutOfRange { min, max } => {
+                    write!(
+                        f,
+                        "Column '{column}': {value} does not fit in {to} ({min}..={max})"
+                    )
+                }
+                NumericConversionReason::Negative => write!(
+                    f,
+                    "Column '{column}': {value} does not fit in {to}: it is negative and {to} is unsigned"
+                ),
+                NumericConversionReason::Fractional => write!(
+                    f,
+                    "Column '{column}': {value} does not fit in {to}: it has a fractional part and {to} is an integer"
+                ),
+                NumericConversionReason::Unsupported => {
+                    write!(f, "Column '{column}': can't convert {from} {value} to {to}")
+                }
+            },
+            SqlDeError::InvalidValue { column, shape } => {
+                write!(f, "Column '{column}': can't parse text as {shape}")
+            }
+            SqlDeError::UnsupportedShape(shape) => {
+                write!(f, "Type {shape} can't be mapped from a database row")
+            }
+            SqlDeError::Reflect(err) => write!(f, "Reflection error: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for SqlDeError<'_> {}
@@ -0,0 +1,45 @@
+//! Rows from `tokio-postgres`, and from `postgres`, which re-exports its row type.
+
+use tokio_postgres::types::{FromSql, Type};
+
+use crate::{Row, SqlValue};
+
+impl Row for tokio_postgres::Row {
+    type Error = tokio_postgres::Error;
+
+    fn column_count(&self) -> usize {
+        self.len()
+    }
+
+    fn column_name(&self, index: usize) -> Result<&str, Self::Error> {
+        // Out-of-range indices are reported by `try_get` when the value is read
+        Ok(self.columns().get(index).map_or("", |c| c.name()))
+    }
+
+    fn value(&self, index: usize) -> Result<SqlValue<'_>, Self::Error> {
+        fn get<'a, T: FromSql<'a>>(
+            row: &'a tokio_postgres::Row,
+            index: usize,
+            f: impl FnOnce(T) -> SqlValue<'a>,
+        ) -> Result<SqlValue<'a>, tokio_postgres::Error> {
+            Ok(row
+                .try_get::<_, Option<T>>(index)?
+                .map_or(SqlValue::Null, f))
+        }
+
+        let ty = self.columns().get(index).map(|c| c.type_());
+        match ty {
+            Some(&Type::BOOL) => get(self, index, SqlValue::Bool),
+            Some(&Type::CHAR) => get(self, index, |i: i8| SqlValue::Integer(i.into())),
+            Some(&Type::INT2) => get(self, index, |i: i16| SqlValue::Integer(i.into())),
+            Some(&Type::INT4) => get(self, index, |i: i32| SqlValue::Integer(i.into())),
+            Some(&Type::INT8) => get(self, index, SqlValue::Integer),
+            Some(&Type::OID) => get(self, index, |i: u32| SqlValue::Integer(i.into())),
+            Some(&Type::FLOAT4) => get(self, index, |r: f32| SqlValue::Real(r.into())),
+            Some(&Type::FLOAT8) => get(self, index, SqlValue::Real),
+            Some(&Type::BYTEA) => get(self, index, SqlValue::Blob),
+            // Text-like types; anything else fails with a wrong-type error from the driver
+            _ => get(self, index, SqlValue::Text),
+        }
+    }
+}
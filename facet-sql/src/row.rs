@@ -0,0 +1,53 @@
+//! The driver-independent view of a database row.
+
+/// A single column value, borrowed from the row it was read from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum SqlValue<'row> {
+    /// `NULL`
+    Null,
+    /// A boolean, for databases that have a boolean type.
+    Bool(bool),
+    /// Any integer type.
+    Integer(i64),
+    /// Any floating-point type.
+    Real(f64),
+    /// Text, including `VARCHAR` and `CHAR`.
+    Text(&'row str),
+    /// Binary data.
+    Blob(&'row [u8]),
+}
+
+impl SqlValue<'_> {
+    /// Returns the name of the SQL type of this value, for error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SqlValue::Null => "NULL",
+            SqlValue::Bool(_) => "boolean",
+            SqlValue::Integer(_) => "integer",
+            SqlValue::Real(_) => "real",
+            SqlValue::Text(_) => "text",
+            SqlValue::Blob(_) => "blob",
+        }
+    }
+}
+
+/// A database row: a list of named columns.
+///
+/// Implemented for `rusqlite::Row` with the `rusqlite` feature, and for
+/// `tokio_postgres::Row` (which the `postgres` crate re-exports) with the
+/// `postgres` feature. Other drivers can be supported by implementing this on a
+/// wrapper around their row type.
+pub trait Row {
+    /// The error the driver returns when a column can't be read.
+    type Error: core::fmt::Display;
+
+    /// Returns the number of columns.
+    fn column_count(&self) -> usize;
+
+    /// Returns the name of the column at `index`.
+    fn column_name(&self, index: usize) -> Result<&str, Self::Error>;
+
+    /// Returns the value of the column at `index`.
+    fn value(&self, index: usize) -> Result<SqlValue<'_>, Self::Error>;
+}
@@ -0,0 +1,29 @@
+//! Rows from `rusqlite`.
+
+use rusqlite::types::{Type, ValueRef};
+
+use crate::{Row, SqlValue};
+
+impl Row for rusqlite::Row<'_> {
+    type Error = rusqlite::Error;
+
+    fn column_count(&self) -> usize {
+        self.as_ref().column_count()
+    }
+
+    fn column_name(&self, index: usize) -> Result<&str, Self::Error> {
+        self.as_ref().column_name(index)
+    }
+
+    fn value(&self, index: usize) -> Result<SqlValue<'_>, Self::Error> {
+        Ok(match self.get_ref(index)? {
+            ValueRef::Null => SqlValue::Null,
+            ValueRef::Integer(i) => SqlValue::Integer(i),
+            ValueRef::Real(r) => SqlValue::Real(r),
+            ValueRef::Text(text) => SqlValue::Text(core::str::from_utf8(text).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(index, Type::Text, e.into())
+            })?),
+            ValueRef::Blob(blob) => SqlValue::Blob(blob),
+        })
+    }
+}
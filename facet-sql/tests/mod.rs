@@ -0,0 +1,165 @@
+use facet::Facet;
+use facet_sql::{Row, SqlDeError, SqlValue, from_row};
+use facet_testhelpers::test;
+
+/// A row as a driver would return it.
+struct Columns(Vec<(&'static str, SqlValue<'static>)>);
+
+impl Row for Columns {
+    type Error = core::convert::Infallible;
+
+    fn column_count(&self) -> usize {
+        self.0.len()
+    }
+
+    fn column_name(&self, index: usize) -> Result<&str, Self::Error> {
+        Ok(self.0[index].0)
+    }
+
+    fn value(&self, index: usize) -> Result<SqlValue<'_>, Self::Error> {
+        Ok(self.0[index].1)
+    }
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+enum Role {
+    Admin,
+    Member,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct User {
+    id: u32,
+    #[facet(rename = "display_name")]
+    name: String,
+    email: Option<String>,
+    role: Role,
+    active: bool,
+    score: f64,
+    avatar: Option<Vec<u8>>,
+    #[facet(default = 3)]
+    level: u8,
+}
+
+#[test]
+fn test_struct_by_column_name() {
+    let row = Columns(vec![
+        ("avatar", SqlValue::Blob(&[1, 2, 3])),
+        ("display_name", SqlValue::Text("Ada")),
+        ("id", SqlValue::Integer(7)),
+        ("email", SqlValue::Null),
+        ("role", SqlValue::Text("Admin")),
+        ("active", SqlValue::Integer(1)),
+        ("score", SqlValue::Integer(10)),
+        ("created_at", SqlValue::Text("2024-01-01")),
+    ]);
+
+    let user: User = from_row(&row)?;
+    assert_eq!(
+        user,
+        User {
+            id: 7,
+            name: "Ada".to_string(),
+            email: None,
+            role: Role::Admin,
+            active: true,
+            score: 10.0,
+            avatar: Some(vec![1, 2, 3]),
+            level: 3,
+        }
+    );
+}
+
+#[test]
+fn test_missing_option_column_is_none() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Sparse {
+        id: i64,
+        note: Option<String>,
+    }
+
+    let row = Columns(vec![("id", SqlValue::Integer(1))]);
+    assert_eq!(from_row::<Sparse, _>(&row)?, Sparse { id: 1, note: None });
+}
+
+#[test]
+fn test_tuple_and_single_column() {
+    let row = Columns(vec![
+        ("a", SqlValue::Integer(-4)),
+        ("b", SqlValue::Text("x")),
+        ("c", SqlValue::Real(2.0)),
+    ]);
+    assert_eq!(
+        from_row::<(i16, String, u64), _>(&row)?,
+        (-4, "x".to_string(), 2)
+    );
+
+    let count = Columns(vec![("count(*)", SqlValue::Integer(12))]);
+    assert_eq!(from_row::<usize, _>(&count)?, 12);
+
+    let role = Columns(vec![("role", SqlValue::Text("Member"))]);
+    assert_eq!(from_row::<Role, _>(&role)?, Role::Member);
+}
+
+#[test]
+fn test_errors() {
+    #[derive(Debug, Facet)]
+    #[facet(deny_unknown_fields)]
+    struct Strict {
+        id: u8,
+    }
+
+    let err = from_row::<Strict, _>(&Columns(vec![("id", SqlValue::Integer(256))])).unwrap_err();
+    assert!(matches!(
+        err,
+        SqlDeError::NumericConversion { ref column, to: "u8", .. } if column == "id"
+    ));
+    assert_eq!(
+        err.to_string(),
+        "Column 'id': 256 does not fit in u8 (0..=255)"
+    );
+
+    let err = from_row::<Strict, _>(&Columns(vec![("id", SqlValue::Integer(-1))])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Column 'id': -1 does not fit in u8: it is negative and u8 is unsigned"
+    );
+
+    let err = from_row::<Strict, _>(&Columns(vec![("id", SqlValue::Real(1.5))])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Column 'id': 1.5 does not fit in u8: it has a fractional part and u8 is an integer"
+    );
+
+    let err = from_row::<Strict, _>(&Columns(vec![("id", SqlValue::Null)])).unwrap_err();
+    assert!(matches!(err, SqlDeError::UnexpectedNull { .. }));
+
+    let err = from_row::<Strict, _>(&Columns(vec![("id", SqlValue::Text("1"))])).unwrap_err();
+    assert!(matches!(
+        err,
+        SqlDeError::TypeMismatch { found: "text", .. }
+    ));
+
+    let err = from_row::<Strict, _>(&Columns(vec![
+        ("id", SqlValue::Integer(1)),
+        ("extra", SqlValue::Null),
+    ]))
+    .unwrap_err();
+    assert!(matches!(err, SqlDeError::UnknownColumn { ref column, .. } if column == "extra"));
+
+    let err = from_row::<Strict, _>(&Columns(vec![])).unwrap_err();
+    assert!(matches!(err, SqlDeError::MissingColumn("id")));
+
+    let err = from_row::<(u8, u8), _>(&Columns(vec![("a", SqlValue::Integer(1))])).unwrap_err();
+    assert!(matches!(
+        err,
+        SqlDeError::ColumnCount {
+            expected: 2,
+            found: 1
+        }
+    ));
+
+    let err = from_row::<Role, _>(&Columns(vec![("role", SqlValue::Text("Guest"))])).unwrap_err();
+    assert!(matches!(err, SqlDeError::InvalidValue { .. }));
+}
@@ -70,6 +70,9 @@ impl<'input, 'shape> TomlDeError<'input, 'shape> {
             TomlDeErrorKind::InvalidKey(field) => {
                 format!("Invalid Rust key '{field}'")
             }
+            TomlDeErrorKind::UnsupportedShape(shape) => {
+                format!("Can't deserialize Rust type '{shape}' from TOML")
+            }
             TomlDeErrorKind::ExpectedFieldWithName(name) => {
                 format!("Expected field with name '{name}'")
             }
@@ -168,6 +171,8 @@ pub enum TomlDeErrorKind<'shape> {
     UnrecognizedScalar(&'shape Shape<'shape>),
     /// Rust value is not a valid key.
     InvalidKey(&'shape Shape<'shape>),
+    /// Found a Rust type that can't be deserialized from TOML.
+    UnsupportedShape(&'shape Shape<'shape>),
     /// Expected a TOML field with the specified name, but couldn't find it.
     ExpectedFieldWithName(&'shape str),
     /// Expected at least one field, got zero.
@@ -8,10 +8,13 @@ mod to_scalar;
 
 use alloc::{
     borrow::Cow,
+    boxed::Box,
+    rc::Rc,
     string::{String, ToString},
+    sync::Arc,
 };
 pub use error::{TomlDeError, TomlDeErrorKind};
-use facet_core::{Characteristic, Def, Facet, FieldFlags, StructKind, Type, UserType};
+use facet_core::{BitFlag, Characteristic, Def, Facet, FieldFlags, StructKind, Type, UserType};
 use facet_reflect::{Partial, ReflectError, ScalarType};
 use log::trace;
 use owo_colors::OwoColorize;
@@ -107,7 +110,14 @@ fn deserialize_item<'input, 'facet, 'shape>(
         Def::List(_) => deserialize_as_list(toml, wip, item)?,
         Def::Map(_) => deserialize_as_map(toml, wip, item)?,
         Def::SmartPointer(_) => deserialize_as_smartpointer(toml, wip, item)?,
-        _ => todo!(),
+        _ => {
+            return Err(TomlDeError::new(
+                toml,
+                TomlDeErrorKind::UnsupportedShape(wip.shape()),
+                item.span(),
+                wip.path(),
+            ));
+        }
     }
     Ok(())
 }
@@ -124,6 +134,72 @@ fn deserialize_as_struct<'input, 'a, 'shape>(
         "struct".blue()
     );
 
+    // `#[facet(parse = ...)]` reads the struct itself from a string
+    if wip.shape().has_parse_attr() {
+        if let Some(s) = item.as_str() {
+            reflect!(wip, toml, item.span(), parse_from_str(s));
+            return Ok(());
+        }
+    }
+
+    // `#[facet(bitflags)]` reads an array of flag names into the bits
+    if let Some(flags) = wip.shape().bit_flags() {
+        if let Some(array) = item.as_array() {
+            let mut bits = 0;
+            for value in array.iter() {
+                let name = value.as_str().ok_or_else(|| {
+                    TomlDeError::new(
+                        toml,
+                        TomlDeErrorKind::ExpectedType {
+                            expected: "string",
+                            got: value.type_name(),
+                        },
+                        value.span(),
+                        wip.path(),
+                    )
+                })?;
+                // Bits no flag covers are written like `0x10`
+                bits |= match BitFlag::by_name(flags, name) {
+                    Some(flag) => flag.bits,
+                    None => name
+                        .strip_prefix("0x")
+                        .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+                        .ok_or_else(|| {
+                            TomlDeError::new(
+                                toml,
+                                TomlDeErrorKind::FailedTypeConversion {
+                                    toml_type_name: "string",
+                                    rust_type: wip.shape(),
+                                    reason: Some(alloc::format!("no flag is named {name}")),
+                                },
+                                value.span(),
+                                wip.path(),
+                            )
+                        })?,
+                };
+            }
+
+            reflect!(wip, toml, item.span(), begin_nth_field(0));
+            deserialize_item(toml, wip, &toml_edit::value(bits as i64))?;
+            reflect!(wip, toml, item.span(), end());
+            return Ok(());
+        }
+    }
+
+    // Tuple structs are written as arrays, with an item for each field
+    if let Some(array) = item.as_array() {
+        if matches!(def.kind, StructKind::TupleStruct | StructKind::Tuple)
+            && array.len() == def.fields.len()
+        {
+            for (index, value) in array.iter().enumerate() {
+                reflect!(wip, toml, value.span(), begin_nth_field(index));
+                deserialize_item(toml, wip, &Item::Value(value.clone()))?;
+                reflect!(wip, toml, value.span(), end());
+            }
+            return Ok(());
+        }
+    }
+
     // Parse as a the inner struct type if item is a single value and the struct is a unit struct
     if item.is_value() && !item.is_inline_table() {
         // Only allow unit structs
@@ -191,9 +267,8 @@ fn deserialize_as_struct<'input, 'a, 'shape>(
                             wip.path(),
                         ));
                     }
-                } else if field.shape().is_type::<()>() {
-                    // Default of `()` is `()`
-                    reflect!(wip, toml, item.span(), set_default());
+                } else if field.shape().is_unit() {
+                    // `()`, `PhantomData` and unit structs hold no data, so there's nothing to set
                 } else {
                     return Err(TomlDeError::new(
                         toml,
@@ -225,7 +300,17 @@ fn deserialize_as_enum<'input, 'a, 'shape>(
     );
 
     match item {
-        Item::None => todo!(),
+        Item::None | Item::ArrayOfTables(_) => {
+            return Err(TomlDeError::new(
+                toml,
+                TomlDeErrorKind::ExpectedType {
+                    expected: "string or table",
+                    got: item.type_name(),
+                },
+                item.span(),
+                wip.path(),
+            ));
+        }
 
         Item::Value(value) => {
             trace!("Entering {}", "value".cyan());
@@ -277,6 +362,21 @@ fn deserialize_as_enum<'input, 'a, 'shape>(
                 )
             })?;
 
+            // An unknown name is read into the `#[facet(other)]` variant, if there's one
+            if wip.find_variant(variant_name).is_none() {
+                if let Type::User(UserType::Enum(ed)) = wip.shape().ty {
+                    if let Some(index) = ed.other_variant_index() {
+                        reflect!(wip, toml, item.span(), select_nth_variant(index));
+                        if ed.variants[index].data.fields.len() == 1 {
+                            reflect!(wip, toml, item.span(), begin_nth_field(0));
+                            deserialize_item(toml, wip, item)?;
+                            reflect!(wip, toml, item.span(), end());
+                        }
+                        return Ok(());
+                    }
+                }
+            }
+
             build_enum_from_variant_name(toml, wip, variant_name, item)?
         }
 
@@ -303,8 +403,6 @@ fn deserialize_as_enum<'input, 'a, 'shape>(
                 ));
             }
         }
-
-        Item::ArrayOfTables(_array_of_tables) => todo!(),
     }
 
     trace!("Finished deserializing {}", "enum".blue());
@@ -358,6 +456,8 @@ fn build_enum_from_variant_name<'input, 'a, 'shape>(
                     // Default of `Option<T>` is `None`
                     reflect!(wip, toml, item.span(), set_default());
                 }
+                // `()`, `PhantomData` and unit structs hold no data, so there's nothing to set
+                None if field.shape().is_unit() => {}
                 None => {
                     return Err(TomlDeError::new(
                         toml,
@@ -514,7 +614,21 @@ fn deserialize_as_map<'input, 'a, 'shape>(
                 reflect!(wip, toml, item.span(), set(k.to_string()));
             }
             ScalarType::CowStr => {
-                reflect!(wip, toml, item.span(), set(Cow::Owned(k.to_string())));
+                reflect!(
+                    wip,
+                    toml,
+                    item.span(),
+                    set(Cow::<str>::Owned(k.to_string()))
+                );
+            }
+            ScalarType::ArcStr => {
+                reflect!(wip, toml, item.span(), set(Arc::<str>::from(k)));
+            }
+            ScalarType::RcStr => {
+                reflect!(wip, toml, item.span(), set(Rc::<str>::from(k)));
+            }
+            ScalarType::BoxStr => {
+                reflect!(wip, toml, item.span(), set(Box::<str>::from(k)));
             }
             _ => {
                 return Err(TomlDeError::new(
@@ -572,8 +686,8 @@ fn deserialize_as_option<'input, 'a, 'shape>(
 }
 
 fn deserialize_as_smartpointer<'input, 'a, 'shape>(
-    _toml: &'input str,
-    _wip: &mut Partial<'a, 'shape>,
+    toml: &'input str,
+    wip: &mut Partial<'a, 'shape>,
     item: &Item,
 ) -> Result<(), TomlDeError<'input, 'shape>> {
     trace!(
@@ -582,9 +696,16 @@ fn deserialize_as_smartpointer<'input, 'a, 'shape>(
         "smart pointer".blue()
     );
 
+    // Deserialize the pointee, and wrap it
+    reflect!(wip, toml, item.span(), begin_smart_ptr());
+
+    deserialize_item(toml, wip, item)?;
+
+    reflect!(wip, toml, item.span(), end());
+
     trace!("Finished deserializing {}", "smart pointer".blue());
 
-    todo!();
+    Ok(())
 }
 
 fn deserialize_as_scalar<'input, 'a, 'shape>(
@@ -614,6 +735,12 @@ fn deserialize_as_scalar<'input, 'a, 'shape>(
         // Regular String and &str are handled by from_str
         #[cfg(feature = "alloc")]
         ScalarType::CowStr => to_scalar::put_string::<Cow<'_, str>>(toml, wip, item)?,
+        #[cfg(feature = "alloc")]
+        ScalarType::ArcStr => to_scalar::put_string::<Arc<str>>(toml, wip, item)?,
+        #[cfg(feature = "alloc")]
+        ScalarType::RcStr => to_scalar::put_string::<Rc<str>>(toml, wip, item)?,
+        #[cfg(feature = "alloc")]
+        ScalarType::BoxStr => to_scalar::put_string::<Box<str>>(toml, wip, item)?,
 
         ScalarType::F32 => to_scalar::put_number::<f32>(toml, wip, item)?,
         ScalarType::F64 => to_scalar::put_number::<f64>(toml, wip, item)?,
@@ -134,20 +134,21 @@ pub(crate) fn put_char<'input, 'a, 'shape>(
     })?;
 
     let value = if let Value::String(string) = v {
-        let value = string.value();
-        if value.len() > 1 || value.is_empty() {
-            return Err(TomlDeError::new(
-                toml,
-                TomlDeErrorKind::ExpectedType {
-                    expected: "char",
-                    got: v.type_name(),
-                },
-                v.span(),
-                wip.path(),
-            ));
+        let mut chars = string.value().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => {
+                return Err(TomlDeError::new(
+                    toml,
+                    TomlDeErrorKind::ExpectedType {
+                        expected: "char",
+                        got: v.type_name(),
+                    },
+                    v.span(),
+                    wip.path(),
+                ));
+            }
         }
-
-        value.chars().next().unwrap()
     } else {
         return Err(TomlDeError::new(
             toml,
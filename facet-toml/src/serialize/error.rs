@@ -16,6 +16,13 @@ pub enum TomlSerError {
     UnsupportedByteArray,
     /// Invalid array of tables (expected structs)
     InvalidArrayOfTables,
+    /// An `Rc` or `Arc` points back at a value that contains it.
+    PointerCycle(alloc::string::String),
+    /// A TOML document is a table, so only a struct or map can be written at the top level.
+    RootNotTable {
+        /// Type of the TOML value that was written at the top level.
+        toml_type: &'static str,
+    },
 }
 
 impl core::fmt::Display for TomlSerError {
@@ -33,12 +40,25 @@ impl core::fmt::Display for TomlSerError {
             Self::InvalidArrayOfTables => {
                 write!(f, "Invalid array of tables: expected array of structs")
             }
+            Self::PointerCycle(message) => f.write_str(message),
+            Self::RootNotTable { toml_type } => {
+                write!(f, "A TOML document has to be a table, found {toml_type}")
+            }
         }
     }
 }
 
 impl core::error::Error for TomlSerError {}
 
+impl From<TomlSerError> for facet_serialize::SerializeError {
+    fn from(error: TomlSerError) -> Self {
+        match error {
+            TomlSerError::UnsupportedByteArray => Self::unsupported_shape("byte arrays"),
+            error => Self::custom(error),
+        }
+    }
+}
+
 impl core::fmt::Debug for TomlSerError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         core::fmt::Display::fmt(self, f)
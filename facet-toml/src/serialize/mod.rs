@@ -3,7 +3,6 @@
 #[cfg(not(feature = "alloc"))]
 compile_error!("feature `alloc` is required");
 
-mod array_of_tables;
 mod error;
 
 use alloc::{
@@ -15,10 +14,11 @@ use core::ops::{Deref, DerefMut};
 use owo_colors::OwoColorize;
 
 pub use error::TomlSerError;
-use facet_reflect::HasFields;
-use facet_serialize::{Serialize, Serializer};
+use facet_core::Shape;
+pub use facet_serialize::FloatFormat;
+use facet_serialize::{Serialize, Serializer, pointer_cycle_message};
 use log::trace;
-use toml_edit::{DocumentMut, Item, Table, Value};
+use toml_edit::{Array, ArrayOfTables, DocumentMut, InlineTable, Item, Table, Value};
 
 /// Serializer for TOML values.
 pub struct TomlSerializer<'shape> {
@@ -28,6 +28,30 @@ pub struct TomlSerializer<'shape> {
     key_stack: KeyStack<'shape>,
     /// What type the current item is.
     current: KeyOrValue,
+    /// For each open table or array, whether it's an element of an array.
+    containers: Vec<bool>,
+    /// Arrays holding a value TOML can't write, like `None`, which are left out.
+    unwritable_arrays: Vec<KeyStack<'shape>>,
+    /// Tables with at most this many values are written inline.
+    inline_table_threshold: Option<usize>,
+    /// Whether to write arrays of tables as `[[name]]` sections.
+    array_of_tables: bool,
+    /// How to quote strings.
+    string_style: StringStyle,
+    /// How to write floats.
+    float_format: FloatFormat,
+}
+
+/// How to quote TOML strings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StringStyle {
+    /// Basic strings, or literal strings when that avoids escapes.
+    #[default]
+    Auto,
+    /// Basic strings, like `"C:\\path"`.
+    Basic,
+    /// Literal strings, like `'C:\path'`, for strings without `'` or control characters.
+    Literal,
 }
 
 impl<'shape> TomlSerializer<'shape> {
@@ -37,17 +61,55 @@ impl<'shape> TomlSerializer<'shape> {
             document: DocumentMut::new(),
             key_stack: KeyStack::new(),
             current: KeyOrValue::Value,
+            containers: Vec::new(),
+            unwritable_arrays: Vec::new(),
+            inline_table_threshold: None,
+            array_of_tables: true,
+            string_style: StringStyle::Auto,
+            float_format: FloatFormat::Shortest,
         }
     }
 
+    /// Write tables with at most `max_values` values and no subtables inline, like
+    /// `point = { x = 1, y = 2 }`, instead of as a `[point]` section.
+    pub fn with_inline_table_threshold(mut self, max_values: usize) -> Self {
+        self.inline_table_threshold = Some(max_values);
+        self
+    }
+
+    /// Whether to write arrays of tables as `[[name]]` sections, which is the default, or as
+    /// inline arrays like `name = [{ a = 1 }, { a = 2 }]`.
+    pub fn with_array_of_tables(mut self, array_of_tables: bool) -> Self {
+        self.array_of_tables = array_of_tables;
+        self
+    }
+
+    /// Set how to quote strings.
+    pub fn with_string_style(mut self, string_style: StringStyle) -> Self {
+        self.string_style = string_style;
+        self
+    }
+
+    /// Set how to write floats.
+    pub fn with_float_format(mut self, float_format: FloatFormat) -> Self {
+        self.float_format = float_format;
+        self
+    }
+
     /// Get the output serialized TOML document.
-    pub fn into_raw_document(self) -> DocumentMut {
-        self.document
+    pub fn into_raw_document(mut self) -> DocumentMut {
+        // Remove the deepest first, so the paths to the others stay the same
+        for keys in core::mem::take(&mut self.unwritable_arrays).iter().rev() {
+            remove_item(self.document.as_item_mut(), keys);
+        }
+        let mut document = core::mem::take(&mut self.document);
+        self.format_table(document.as_table_mut());
+        document
     }
 
     /// Get the output serialized TOML string.
     pub fn into_string(self) -> String {
-        self.document.to_string()
+        self.into_raw_document().to_string()
     }
 
     /// Write a value depending on the context.
@@ -57,7 +119,11 @@ impl<'shape> TomlSerializer<'shape> {
         match self.current {
             // Write the value
             KeyOrValue::Value => {
-                self.set_current_item(value);
+                if let Some(array) = self.item_mut().as_array_mut() {
+                    array.push(value);
+                } else {
+                    self.set_current_item(value)?;
+                }
             }
             // Push the value as a new item
             KeyOrValue::Key => {
@@ -75,40 +141,251 @@ impl<'shape> TomlSerializer<'shape> {
         Ok(())
     }
 
+    /// Write a float written out by the float format, or `value` as `toml_edit` writes it.
+    fn write_float(&mut self, formatted: Option<String>, value: f64) -> Result<(), TomlSerError> {
+        match formatted.and_then(|formatted| formatted.parse::<Value>().ok()) {
+            Some(mut formatted) => {
+                formatted.decor_mut().clear();
+                self.write_value(formatted)
+            }
+            None => self.write_value(value),
+        }
+    }
+
+    /// Leave out the current item, for values TOML can't write.
+    fn write_nothing(&mut self) {
+        if self.item_mut().is_array() {
+            // Leaving out the element would change the array, so leave out all of it
+            self.unwritable_arrays.push(self.key_stack.clone());
+        } else if let Some(Segment::Key(key)) = self.key_stack.last().cloned() {
+            let depth = self.key_stack.len() - 1;
+            if let Some(table) = self.item_at_mut(depth).as_table_like_mut() {
+                table.remove(&key);
+            }
+        }
+    }
+
+    /// Start a table or array at the current item, or as the next element if it's an array.
+    fn start_container(
+        &mut self,
+        container: impl FnOnce(bool) -> Item,
+    ) -> Result<(), TomlSerError> {
+        let element = self.item_mut().as_array_mut().map(|array| {
+            // A placeholder until the container replaces it
+            array.push(false);
+            array.len() - 1
+        });
+        if let Some(index) = element {
+            self.key_stack.push(Segment::Index(index));
+        }
+        self.containers.push(element.is_some());
+
+        // Arrays hold values, so the tables in them have to be inline
+        let inline = self
+            .key_stack
+            .iter()
+            .any(|segment| matches!(segment, Segment::Index(_)));
+        self.set_current_item(container(inline))
+    }
+
+    /// Finish the current table or array.
+    fn end_container(&mut self) {
+        if self.containers.pop() == Some(true) {
+            self.key_stack.pop();
+        }
+    }
+
     /// Convert the item at the current key to another type.
-    fn set_current_item(&mut self, item: impl Into<Item>) {
+    fn set_current_item(&mut self, item: impl Into<Item>) -> Result<(), TomlSerError> {
         let item = item.into();
         trace!("Set item {} to {}", self.key_stack, item.type_name());
 
+        // A TOML document is a table
+        if self.key_stack.is_empty() && !item.is_table() {
+            return Err(TomlSerError::RootNotTable {
+                toml_type: item.type_name(),
+            });
+        }
         *self.item_mut() = item;
+        Ok(())
     }
 
     /// Get the mutable item for the current key.
     fn item_mut(&'_ mut self) -> &'_ mut Item {
-        self.key_stack
-            .iter()
-            .fold(self.document.as_item_mut(), |item, key| {
-                item.get_mut(key.as_ref()).unwrap()
-            })
+        self.item_at_mut(self.key_stack.len())
+    }
+
+    /// Get the mutable item for the first `depth` keys.
+    fn item_at_mut(&'_ mut self, depth: usize) -> &'_ mut Item {
+        item_at_mut(self.document.as_item_mut(), &self.key_stack[..depth])
     }
 
     /// Create a new empty item at the key.
     fn push_key(&mut self, key: impl Into<Cow<'shape, str>>) {
         let key = key.into();
         // Push empty item
-        self.item_mut()
-            .as_table_mut()
+        let item = self.item_mut();
+        // Inline tables can only hold values, so use a placeholder until it's written
+        let empty = if item.is_table() {
+            Item::None
+        } else {
+            Item::Value(Value::from(false))
+        };
+        item.as_table_like_mut()
             .expect("the current item to be a table when pushing a new key")
-            .insert(&key, Item::None);
+            .insert(&key, empty);
 
         // Push the key on the stack
-        self.key_stack.push(key);
+        self.key_stack.push(Segment::Key(key));
     }
 
     /// Pop the current key, which means the item is finished.
     fn pop_key(&mut self) {
         self.key_stack.pop();
     }
+
+    /// Apply the formatting options to the items in `table`.
+    fn format_table(&self, table: &mut Table) {
+        for (_, item) in table.iter_mut() {
+            self.format_item(item);
+        }
+    }
+
+    /// Apply the formatting options to `item`, a value in a table.
+    fn format_item(&self, item: &mut Item) {
+        match item {
+            Item::Table(table) => {
+                self.format_table(table);
+                let inline = self.inline_table_threshold.is_some_and(|max_values| {
+                    table.len() <= max_values && table.iter().all(|(_, item)| item.is_value())
+                });
+                if inline {
+                    let table = core::mem::take(table);
+                    *item = Item::Value(Value::InlineTable(table.into_inline_table()));
+                }
+            }
+            Item::Value(Value::Array(array))
+                if self.array_of_tables
+                    && !array.is_empty()
+                    && array.iter().all(Value::is_inline_table) =>
+            {
+                let mut array_of_tables = ArrayOfTables::new();
+                for value in core::mem::take(array).into_iter() {
+                    let Value::InlineTable(table) = value else {
+                        unreachable!()
+                    };
+                    let mut table = expand_table(table);
+                    self.format_table(&mut table);
+                    array_of_tables.push(table);
+                }
+                *item = Item::ArrayOfTables(array_of_tables);
+            }
+            Item::Value(value) => self.format_value(value),
+            Item::ArrayOfTables(array_of_tables) => {
+                for table in array_of_tables.iter_mut() {
+                    self.format_table(table);
+                }
+            }
+            Item::None => {}
+        }
+    }
+
+    /// Apply the formatting options to `value`.
+    fn format_value(&self, value: &mut Value) {
+        match value {
+            Value::String(string) => {
+                if let Some(formatted) = self.string_style.write(string.value()) {
+                    *value = formatted;
+                }
+            }
+            Value::Array(array) => {
+                for value in array.iter_mut() {
+                    self.format_value(value);
+                }
+            }
+            Value::InlineTable(table) => {
+                for (_, value) in table.iter_mut() {
+                    self.format_value(value);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl StringStyle {
+    /// Writes `s` in this style, or `None` to leave it to `toml_edit`.
+    fn write(self, s: &str) -> Option<Value> {
+        let quoted = match self {
+            StringStyle::Auto => return None,
+            StringStyle::Literal
+                if !s.contains(|c: char| c == '\'' || (c.is_control() && c != '\t')) =>
+            {
+                alloc::format!("'{s}'")
+            }
+            StringStyle::Basic | StringStyle::Literal => {
+                let mut quoted = String::from('"');
+                for c in s.chars() {
+                    match c {
+                        '"' => quoted.push_str("\\\""),
+                        '\\' => quoted.push_str("\\\\"),
+                        '\n' => quoted.push_str("\\n"),
+                        '\r' => quoted.push_str("\\r"),
+                        '\t' => quoted.push_str("\\t"),
+                        c if c.is_control() => {
+                            quoted.push_str(&alloc::format!("\\u{:04X}", c as u32))
+                        }
+                        c => quoted.push(c),
+                    }
+                }
+                quoted.push('"');
+                quoted
+            }
+        };
+        let mut value: Value = quoted.parse().ok()?;
+        value.decor_mut().clear();
+        Some(value)
+    }
+}
+
+/// A step from a TOML item to one inside it.
+#[derive(Debug, Clone, PartialEq)]
+enum Segment<'shape> {
+    /// The value at a key of a table.
+    Key(Cow<'shape, str>),
+    /// An element of an array.
+    Index(usize),
+}
+
+/// Get the item that `keys` lead to from `item`.
+fn item_at_mut<'item>(item: &'item mut Item, keys: &[Segment<'_>]) -> &'item mut Item {
+    keys.iter().fold(item, |item, segment| match segment {
+        Segment::Key(key) => item.get_mut(key.as_ref()).unwrap(),
+        Segment::Index(index) => item.get_mut(*index).unwrap(),
+    })
+}
+
+/// Remove the item that `keys` lead to from `root`, if it's in a table.
+fn remove_item(root: &mut Item, keys: &[Segment<'_>]) {
+    let [parent @ .., Segment::Key(key)] = keys else {
+        return;
+    };
+    if let Some(table) = item_at_mut(root, parent).as_table_like_mut() {
+        table.remove(key);
+    }
+}
+
+/// Turn an inline table from an array into a `[[name]]` section, with its inline tables as
+/// subsections.
+fn expand_table(table: InlineTable) -> Table {
+    let mut table = table.into_table();
+    for (_, item) in table.iter_mut() {
+        if let Item::Value(Value::InlineTable(inline)) = item {
+            let inline = core::mem::take(inline);
+            *item = Item::Table(expand_table(inline));
+        }
+    }
+    table
 }
 
 impl<'shape> Default for TomlSerializer<'shape> {
@@ -148,18 +425,18 @@ impl<'shape> Serializer<'shape> for TomlSerializer<'shape> {
         self.write_value(toml_number)
     }
 
+    fn serialize_f32(&mut self, value: f32) -> Result<(), Self::Error> {
+        self.write_float(self.float_format.format_f32(value), value as f64)
+    }
+
     fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
-        self.write_value(value)
+        self.write_float(self.float_format.format_f64(value), value)
     }
 
     fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error> {
         self.write_value(value)
     }
 
-    fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
-        self.write_value(value.to_string())
-    }
-
     fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
         self.write_value(value)
     }
@@ -169,43 +446,54 @@ impl<'shape> Serializer<'shape> for TomlSerializer<'shape> {
     }
 
     fn serialize_none(&mut self) -> Result<(), Self::Error> {
+        self.write_nothing();
         Ok(())
     }
 
+    fn serialize_pointer_cycle(&mut self, shape: &'shape Shape<'shape>) -> Result<(), Self::Error> {
+        Err(TomlSerError::PointerCycle(pointer_cycle_message(shape)))
+    }
+
     fn serialize_unit(&mut self) -> Result<(), Self::Error> {
+        self.write_nothing();
         Ok(())
     }
 
     fn serialize_unit_variant(
         &mut self,
         _variant_index: usize,
-        _variant_name: &'shape str,
+        variant_name: &'shape str,
     ) -> Result<(), Self::Error> {
-        todo!()
+        // Read back as the variant's name, like the deserializer expects
+        self.write_value(variant_name)
     }
 
     fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        let mut table = Table::new();
-        // Also show the table when it's empty
-        table.set_implicit(false);
+        self.start_container(new_table)
+    }
 
-        self.set_current_item(table);
+    fn start_array(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        self.start_container(|_| Item::Value(Value::Array(Array::new())))
+    }
 
-        Ok(())
+    fn start_map(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
+        self.start_container(new_table)
     }
 
-    fn start_array(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        self.set_current_item(toml_edit::array());
+    fn end_object(&mut self) -> Result<(), Self::Error> {
+        self.end_container();
 
         Ok(())
     }
 
-    fn start_map(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        let mut table = Table::new();
-        // Also show the table when it's empty
-        table.set_implicit(false);
+    fn end_array(&mut self) -> Result<(), Self::Error> {
+        self.end_container();
 
-        self.set_current_item(table);
+        Ok(())
+    }
+
+    fn end_map(&mut self) -> Result<(), Self::Error> {
+        self.end_container();
 
         Ok(())
     }
@@ -253,8 +541,20 @@ enum KeyOrValue {
     Value,
 }
 
+/// Create an empty table, inline if it's in an array.
+fn new_table(inline: bool) -> Item {
+    if inline {
+        Item::Value(Value::InlineTable(InlineTable::new()))
+    } else {
+        let mut table = Table::new();
+        // Also show the table when it's empty
+        table.set_implicit(false);
+        Item::Table(table)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
-struct KeyStack<'shape>(Vec<Cow<'shape, str>>);
+struct KeyStack<'shape>(Vec<Segment<'shape>>);
 
 impl KeyStack<'_> {
     fn new() -> Self {
@@ -263,7 +563,7 @@ impl KeyStack<'_> {
 }
 
 impl<'shape> Deref for KeyStack<'shape> {
-    type Target = Vec<Cow<'shape, str>>;
+    type Target = Vec<Segment<'shape>>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -278,55 +578,25 @@ impl<'shape> DerefMut for KeyStack<'shape> {
 
 impl core::fmt::Display for KeyStack<'_> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut iter = self.iter();
-        if let Some(first) = iter.next() {
-            write!(f, "[{}", first.cyan())?;
-            for key in iter {
-                write!(f, ".{}", key.cyan())?;
+        if self.is_empty() {
+            return write!(f, "«{}»", "root".red());
+        }
+        write!(f, "[")?;
+        for (i, segment) in self.iter().enumerate() {
+            match segment {
+                Segment::Key(key) if i == 0 => write!(f, "{}", key.cyan())?,
+                Segment::Key(key) => write!(f, ".{}", key.cyan())?,
+                Segment::Index(index) => write!(f, "[{}]", index.yellow())?,
             }
-            write!(f, "]")?;
-        } else {
-            write!(f, "«{}»", "root".red())?;
         }
-        Ok(())
+        write!(f, "]")
     }
 }
 
 /// Serialize any `Facet` type to a TOML string.
 #[cfg(feature = "alloc")]
 pub fn to_string<'a, T: facet_core::Facet<'a>>(value: &'a T) -> Result<String, TomlSerError> {
-    // First peek at the value to understand its structure
-    let peek = facet_reflect::Peek::new(value);
-
-    // Check if the root is a struct with fields that are arrays of tables
-    if let Ok(struct_peek) = peek.into_struct() {
-        let mut serializer = TomlSerializer::new();
-
-        // Process each field
-        for (field, field_value) in struct_peek.fields_for_serialize() {
-            // Check if this field is an array of tables
-            if array_of_tables::is_array_of_tables(&field_value) {
-                // Handle array of tables specially
-                let list = field_value.into_list_like().unwrap();
-                let aot = array_of_tables::serialize_array_of_tables(list)?;
-                serializer
-                    .document
-                    .insert(field.name, Item::ArrayOfTables(aot));
-            } else {
-                // Normal field serialization
-                serializer.push_key(field.name);
-                trace!("Push field {}", field.name);
-                facet_serialize::serialize_iterative(field_value, &mut serializer)?;
-                serializer.pop_key();
-                trace!("Pop field {}", field.name);
-            }
-        }
-
-        Ok(serializer.into_string())
-    } else {
-        // Not a struct at root, use normal serialization
-        let mut serializer = TomlSerializer::new();
-        value.serialize(&mut serializer)?;
-        Ok(serializer.into_string())
-    }
+    let mut serializer = TomlSerializer::new();
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_string())
 }
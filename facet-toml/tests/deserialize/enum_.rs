@@ -205,3 +205,28 @@ fn test_enum_root() {
     assert_eq!(facet_toml::from_str::<Root>("B = 2")?, Root::B(2));
     assert_eq!(facet_toml::from_str::<Root>("[C]")?, Root::C);
 }
+
+#[test]
+fn test_array_of_tables_as_enum() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: WithStructVariants,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum WithStructVariants {
+        OneField { one: f64 },
+    }
+
+    assert_eq!(
+        facet_toml::from_str::<Root>("[[value]]\nOneField.one = 0.5")
+            .unwrap_err()
+            .kind,
+        TomlDeErrorKind::ExpectedType {
+            expected: "string or table",
+            got: "array of tables",
+        }
+    );
+}
@@ -112,6 +112,13 @@ fn test_char() {
             got: "string"
         }
     );
+
+    // One character, several bytes
+    assert_eq!(
+        facet_toml::from_str::<Root>("value = 'é'")?,
+        Root { value: 'é' },
+    );
+    assert!(facet_toml::from_str::<Root>("value = ''").is_err());
 }
 
 #[cfg(feature = "std")]
@@ -532,6 +539,28 @@ fn test_unit() {
     assert_eq!(facet_toml::from_str::<Root>("")?, Root { value: () });
 }
 
+#[test]
+fn test_phantom_data_and_unit_struct() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Marker;
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: i32,
+        marker: Marker,
+        phantom: core::marker::PhantomData<String>,
+    }
+
+    assert_eq!(
+        facet_toml::from_str::<Root>("value = 1")?,
+        Root {
+            value: 1,
+            marker: Marker,
+            phantom: core::marker::PhantomData,
+        }
+    );
+}
+
 #[test]
 fn test_unparsable_scalar() {
     // The error type might have changed with our implementation
@@ -539,3 +568,30 @@ fn test_unparsable_scalar() {
     let result = facet_toml::from_str::<ConstTypeId>("value = 1");
     assert!(result.is_err(), "Expected an error but got {:?}", result);
 }
+
+#[test]
+fn test_shared_and_boxed_strs() {
+    use std::collections::HashMap;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        arc: Arc<str>,
+        rc: Rc<str>,
+        boxed: Box<str>,
+        by_name: HashMap<Arc<str>, Box<str>>,
+    }
+
+    assert_eq!(
+        facet_toml::from_str::<Root>(
+            "arc = \"a\"\nrc = \"r\"\nboxed = \"b\"\n[by_name]\nk = \"v\""
+        )?,
+        Root {
+            arc: "a".into(),
+            rc: "r".into(),
+            boxed: "b".into(),
+            by_name: [("k".into(), "v".into())].into(),
+        }
+    );
+}
@@ -281,3 +281,143 @@ fn test_default_struct_fields() {
         },
     );
 }
+
+#[test]
+fn test_boxed_struct_field() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        inner: Box<Inner>,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Inner {
+        value: i32,
+    }
+
+    assert_eq!(
+        facet_toml::from_str::<Root>("inner.value = 1")?,
+        Root {
+            inner: Box::new(Inner { value: 1 })
+        },
+    );
+}
+
+#[test]
+fn test_unsupported_field_type() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        tags: std::collections::HashSet<String>,
+    }
+
+    assert!(matches!(
+        facet_toml::from_str::<Root>("tags = ['a']")
+            .unwrap_err()
+            .kind,
+        TomlDeErrorKind::UnsupportedShape(_)
+    ));
+}
+
+/// A number of seconds, written like `5s` or `2m`
+#[derive(Debug, Facet, PartialEq)]
+#[facet(transparent, parse = parse_seconds, format = format_seconds)]
+struct Seconds(u64);
+
+fn parse_seconds(s: &str) -> Result<Seconds, &'static str> {
+    let (number, unit) = s.split_at(s.len().saturating_sub(1));
+    let number: u64 = number.parse().map_err(|_| "expected a number of seconds")?;
+    match unit {
+        "s" => Ok(Seconds(number)),
+        "m" => Ok(Seconds(number * 60)),
+        _ => Err("expected a unit of s or m"),
+    }
+}
+
+fn format_seconds(seconds: &Seconds, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if seconds.0 % 60 == 0 {
+        write!(f, "{}m", seconds.0 / 60)
+    } else {
+        write!(f, "{}s", seconds.0)
+    }
+}
+
+#[test]
+fn test_struct_with_parse_and_format() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        timeout: Seconds,
+        retry: Seconds,
+        idle: Seconds,
+    }
+
+    let root = facet_toml::from_str::<Root>(
+        r#"
+        timeout = "5s"
+        retry = "2m"
+        idle = 30
+        "#,
+    )?;
+    assert_eq!(
+        root,
+        Root {
+            timeout: Seconds(5),
+            retry: Seconds(120),
+            idle: Seconds(30),
+        },
+    );
+
+    let toml = facet_toml::to_string(&root)?;
+    assert!(toml.contains(r#"retry = "2m""#), "{toml}");
+    // The error borrows `toml`, so it can't be returned
+    assert_eq!(facet_toml::from_str::<Root>(&toml).unwrap(), root);
+
+    assert!(facet_toml::from_str::<Root>(r#"timeout = "5h""#).is_err());
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[facet(transparent, bitflags)]
+struct Features(u32);
+
+bitflags::bitflags! {
+    impl Features: u32 {
+        const TLS = 1;
+        const HTTP2 = 1 << 1;
+        const COMPRESSION = 1 << 2;
+    }
+}
+
+#[test]
+fn test_struct_with_bitflags() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        server: Features,
+        client: Features,
+    }
+
+    let root = facet_toml::from_str::<Root>(
+        r#"
+        server = ["TLS", "COMPRESSION"]
+        client = 2
+        "#,
+    )?;
+    assert_eq!(
+        root,
+        Root {
+            server: Features::TLS | Features::COMPRESSION,
+            client: Features::HTTP2,
+        },
+    );
+
+    let toml = facet_toml::to_string(&root)?;
+    assert!(toml.contains(r#""HTTP2""#), "{toml}");
+    assert_eq!(facet_toml::from_str::<Root>(&toml).unwrap(), root);
+
+    assert!(
+        facet_toml::from_str::<Root>(
+            r#"
+            server = ["UDP"]
+            client = 0
+            "#
+        )
+        .is_err()
+    );
+}
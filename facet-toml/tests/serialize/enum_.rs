@@ -205,3 +205,30 @@ fn test_enum_root() {
     assert_eq!(facet_toml::from_str::<Root>("B = 2")?, Root::B(2));
     assert_eq!(facet_toml::from_str::<Root>("[C]")?, Root::C);
 }
+
+#[test]
+fn test_serialize_unit_variant() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: UnitOnlyEnum,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    #[repr(u8)]
+    enum UnitOnlyEnum {
+        VariantA,
+        VariantB,
+    }
+
+    let root = Root {
+        value: UnitOnlyEnum::VariantB,
+    };
+    assert_eq!(facet_toml::to_string(&root)?, "value = \"VariantB\"\n");
+    crate::assert_serialize!(Root, root);
+
+    let root = Root {
+        value: UnitOnlyEnum::VariantA,
+    };
+    assert_eq!(facet_toml::to_string(&root)?, "value = \"VariantA\"\n");
+    crate::assert_serialize!(Root, root);
+}
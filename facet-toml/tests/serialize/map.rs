@@ -204,3 +204,35 @@ fn test_invalid_map_key() {
         TomlSerError::InvalidKeyConversion { .. }
     ));
 }
+
+#[test]
+fn test_index_map_keeps_input_order() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        values: indexmap::IndexMap<String, i32>,
+    }
+
+    let root: Root = facet_toml::from_str("[values]\nzebra = 1\napple = 2\nmango = 3\n")?;
+    assert_eq!(
+        root.values.keys().map(String::as_str).collect::<Vec<_>>(),
+        ["zebra", "apple", "mango"]
+    );
+    assert_eq!(
+        facet_toml::to_string(&root)?,
+        "[values]\nzebra = 1\napple = 2\nmango = 3\n"
+    );
+}
+
+#[test]
+fn test_btree_map_is_written_sorted() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        values: std::collections::BTreeMap<String, i32>,
+    }
+
+    let root: Root = facet_toml::from_str("[values]\nzebra = 1\napple = 2\nmango = 3\n")?;
+    assert_eq!(
+        facet_toml::to_string(&root)?,
+        "[values]\napple = 2\nmango = 3\nzebra = 1\n"
+    );
+}
@@ -24,6 +24,17 @@ fn test_string() {
     );
 }
 
+#[test]
+fn test_char() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: char,
+    }
+
+    assert_serialize!(Root, Root { value: 'c' });
+    assert_serialize!(Root, Root { value: 'é' });
+}
+
 #[test]
 #[ignore = "Must be fixed in facet-serialize"]
 fn test_cow_string() {
@@ -276,6 +287,28 @@ fn test_unit() {
     }
 
     assert_serialize!(Root, Root { unit: () });
+    assert_eq!(facet_toml::to_string(&Root { unit: () })?, "");
+}
+
+#[test]
+fn test_phantom_data_and_unit_struct() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Marker;
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: i32,
+        marker: Marker,
+        phantom: core::marker::PhantomData<String>,
+    }
+
+    let root = Root {
+        value: 1,
+        marker: Marker,
+        phantom: core::marker::PhantomData,
+    };
+    assert_eq!(facet_toml::to_string(&root)?, "value = 1\n");
+    assert_serialize!(Root, root);
 }
 
 #[test]
@@ -326,3 +359,17 @@ fn test_i128_out_of_range() {
         TomlSerError::InvalidNumberToI64Conversion { .. }
     ));
 }
+
+#[test]
+fn test_root_not_table() {
+    assert!(matches!(
+        facet_toml::to_string(&1u32).unwrap_err(),
+        TomlSerError::RootNotTable {
+            toml_type: "integer"
+        }
+    ));
+    assert!(matches!(
+        facet_toml::to_string(&vec![1u32]).unwrap_err(),
+        TomlSerError::RootNotTable { toml_type: "array" }
+    ));
+}
@@ -0,0 +1,121 @@
+//! Tests for the TOML serializer's formatting options.
+
+use facet::Facet;
+use facet_serialize::Serialize;
+use facet_testhelpers::test;
+use facet_toml::{FloatFormat, StringStyle, TomlSerializer};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Server {
+    name: String,
+    origin: Point,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Root {
+    path: String,
+    values: Vec<i32>,
+    point: Point,
+    servers: Vec<Server>,
+}
+
+fn root() -> Root {
+    Root {
+        path: "C:\\data".to_string(),
+        values: vec![1, 2],
+        point: Point { x: 1, y: 2 },
+        servers: vec![Server {
+            name: "a".to_string(),
+            origin: Point { x: 3, y: 4 },
+        }],
+    }
+}
+
+fn serialize(mut serializer: TomlSerializer) -> String {
+    root().serialize(&mut serializer).unwrap();
+    let toml = serializer.into_string();
+    assert_eq!(facet_toml::from_str::<Root>(&toml).unwrap(), root());
+    toml
+}
+
+#[test]
+fn test_default_style() {
+    assert_eq!(
+        serialize(TomlSerializer::new()),
+        "path = 'C:\\data'\nvalues = [1, 2]\n\n[point]\nx = 1\ny = 2\n\n[[servers]]\nname = \"a\"\n\n[servers.origin]\nx = 3\ny = 4\n"
+    );
+}
+
+#[test]
+fn test_inline_table_threshold() {
+    assert_eq!(
+        serialize(TomlSerializer::new().with_inline_table_threshold(2)),
+        "path = 'C:\\data'\nvalues = [1, 2]\npoint = { x = 1, y = 2 }\n\n[[servers]]\nname = \"a\"\norigin = { x = 3, y = 4 }\n"
+    );
+    assert_eq!(
+        serialize(TomlSerializer::new().with_inline_table_threshold(1)),
+        serialize(TomlSerializer::new()),
+    );
+}
+
+#[test]
+fn test_inline_array_of_tables() {
+    assert_eq!(
+        serialize(TomlSerializer::new().with_array_of_tables(false)),
+        "path = 'C:\\data'\nvalues = [1, 2]\nservers = [{ name = \"a\", origin = { x = 3, y = 4 } }]\n\n[point]\nx = 1\ny = 2\n"
+    );
+}
+
+#[test]
+fn test_string_style() {
+    assert_eq!(
+        serialize(TomlSerializer::new().with_string_style(StringStyle::Basic)),
+        "path = \"C:\\\\data\"\nvalues = [1, 2]\n\n[point]\nx = 1\ny = 2\n\n[[servers]]\nname = \"a\"\n\n[servers.origin]\nx = 3\ny = 4\n"
+    );
+    assert_eq!(
+        serialize(TomlSerializer::new().with_string_style(StringStyle::Literal)),
+        "path = 'C:\\data'\nvalues = [1, 2]\n\n[point]\nx = 1\ny = 2\n\n[[servers]]\nname = 'a'\n\n[servers.origin]\nx = 3\ny = 4\n"
+    );
+}
+
+#[test]
+fn test_float_format() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Reading {
+        single: f32,
+        double: f64,
+        tiny: f64,
+    }
+
+    let reading = Reading {
+        single: 0.1,
+        double: 2.5,
+        tiny: 1.5e-7,
+    };
+    let to_string = |float_format| {
+        let mut serializer = TomlSerializer::new().with_float_format(float_format);
+        reading.serialize(&mut serializer).unwrap();
+        serializer.into_string()
+    };
+
+    assert_eq!(
+        to_string(FloatFormat::Shortest),
+        "single = 0.10000000149011612\ndouble = 2.5\ntiny = 0.00000015\n"
+    );
+    assert_eq!(
+        to_string(FloatFormat::Fixed(3)),
+        "single = 0.100\ndouble = 2.500\ntiny = 0.000\n"
+    );
+    let scientific = to_string(FloatFormat::Scientific { min: -3, max: 6 });
+    assert_eq!(scientific, "single = 0.1\ndouble = 2.5\ntiny = 1.5e-7\n");
+    assert_eq!(
+        facet_toml::from_str::<Reading>(&scientific).unwrap(),
+        reading
+    );
+}
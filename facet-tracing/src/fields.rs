@@ -0,0 +1,252 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use facet_core::{Def, Facet, FieldFlags, PointerType, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+use log::kv::{self, Key, Source, Value, VisitSource};
+
+/// The leaves of a value, as key-value fields for a `log` record
+///
+/// The value is walked once, when the fields are made: each scalar becomes a field, keyed by
+/// its dotted path from the root, like `user.address.city`. See the crate docs for how each
+/// kind of value is flattened.
+///
+/// ```
+/// # use facet_tracing::Fields;
+/// use log::kv::Source;
+///
+/// let point = (3, "left");
+/// let fields = Fields::prefixed("point", &point);
+/// assert_eq!(fields.count(), 2);
+/// assert_eq!(fields.get(log::kv::Key::from("point.1")).unwrap().to_borrowed_str(), Some("left"));
+/// ```
+pub struct Fields<'mem, 'facet, 'shape> {
+    leaves: Vec<Leaf<'mem, 'facet, 'shape>>,
+}
+
+impl<'mem, 'facet, 'shape> Fields<'mem, 'facet, 'shape> {
+    /// Reads the leaves of `value`, keyed by their path from it.
+    ///
+    /// A scalar has no path: it's recorded under the `value` key.
+    pub fn new<T: Facet<'facet> + ?Sized>(value: &'mem T) -> Self {
+        Self::from_peek("", Peek::new(value))
+    }
+
+    /// Reads the leaves of `value`, keyed by their path from `prefix`.
+    pub fn prefixed<T: Facet<'facet> + ?Sized>(prefix: &str, value: &'mem T) -> Self {
+        Self::from_peek(prefix, Peek::new(value))
+    }
+
+    /// Reads the leaves of a value that's already being looked at.
+    pub fn from_peek(prefix: &str, value: Peek<'mem, 'facet, 'shape>) -> Self {
+        let mut fields = Self { leaves: Vec::new() };
+        let mut path = String::from(prefix);
+        fields.walk(&mut path, value);
+        if prefix.is_empty() {
+            if let [leaf] = &mut fields.leaves[..] {
+                if leaf.key.is_empty() {
+                    leaf.key = String::from("value");
+                }
+            }
+        }
+        fields
+    }
+
+    /// The number of fields.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Returns `true` if the value has no leaves, like an empty list.
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    fn push(&mut self, path: &str, value: LeafValue<'mem, 'facet, 'shape>) {
+        self.leaves.push(Leaf {
+            key: String::from(path),
+            value,
+        });
+    }
+
+    /// Walks `value` with `segment` added to `path`, then takes it off again.
+    fn walk_at(
+        &mut self,
+        path: &mut String,
+        segment: impl fmt::Display,
+        value: Peek<'mem, 'facet, 'shape>,
+    ) {
+        let len = enter(path, segment);
+        self.walk(path, value);
+        path.truncate(len);
+    }
+
+    fn walk(&mut self, path: &mut String, value: Peek<'mem, 'facet, 'shape>) {
+        let shape = value.shape();
+        match (shape.def, shape.ty) {
+            (Def::Scalar(_), _) => self.push(path, LeafValue::Scalar(value)),
+            (Def::Option(_), _) => match value.into_option().unwrap().value() {
+                Some(inner) => self.walk(path, inner),
+                None => self.push(path, LeafValue::Null),
+            },
+            (Def::SmartPointer(_), _) => match value.into_smart_pointer().unwrap().borrow_inner() {
+                Some(inner) => self.walk(path, inner),
+                None => self.push(path, LeafValue::Scalar(value)),
+            },
+            (Def::Map(_), _) => {
+                for (key, value) in value.into_map().unwrap().iter() {
+                    self.walk_at(path, map_key(key), value);
+                }
+            }
+            (Def::List(_) | Def::Array(_) | Def::Slice(_), _) => {
+                let list = value.into_list_like().unwrap();
+                for (index, item) in list.iter().enumerate() {
+                    self.walk_at(path, index, item);
+                }
+            }
+            (_, Type::Pointer(PointerType::Reference(_))) => {
+                // `&str` is a string, not the `str` it points to
+                if value.as_str().is_some() {
+                    self.push(path, LeafValue::Scalar(value));
+                } else if let Ok(list) = value.into_list_like() {
+                    for (index, item) in list.iter().enumerate() {
+                        self.walk_at(path, index, item);
+                    }
+                } else if let Ok(pointee) = value.into_pointee() {
+                    self.walk(path, pointee);
+                } else {
+                    self.push(path, LeafValue::Scalar(value));
+                }
+            }
+            (_, Type::User(UserType::Struct(ty))) if ty.kind != StructKind::Unit => {
+                self.fields(path, &value.into_struct().unwrap());
+            }
+            (_, Type::User(UserType::Enum(_))) => {
+                let enum_ = value.into_enum().unwrap();
+                match enum_.active_variant() {
+                    Ok(variant) if variant.data.kind == StructKind::Unit => {
+                        self.push(path, LeafValue::Name(variant.name));
+                    }
+                    Ok(variant) => {
+                        let len = enter(path, variant.name);
+                        self.fields(path, &enum_);
+                        path.truncate(len);
+                    }
+                    Err(_) => self.push(path, LeafValue::Scalar(value)),
+                }
+            }
+            _ => self.push(path, LeafValue::Scalar(value)),
+        }
+    }
+
+    fn fields(&mut self, path: &mut String, fields: &impl HasFields<'mem, 'facet, 'shape>) {
+        for (field, value) in fields.fields() {
+            if field.flags.contains(FieldFlags::SENSITIVE) {
+                let len = enter(path, field.name);
+                self.push(path, LeafValue::Redacted);
+                path.truncate(len);
+            } else {
+                self.walk_at(path, field.name, value);
+            }
+        }
+    }
+}
+
+impl Source for Fields<'_, '_, '_> {
+    fn visit<'kvs>(&'kvs self, visitor: &mut dyn VisitSource<'kvs>) -> Result<(), kv::Error> {
+        for leaf in &self.leaves {
+            visitor.visit_pair(Key::from_str(&leaf.key), leaf.value.to_value())?;
+        }
+        Ok(())
+    }
+
+    fn count(&self) -> usize {
+        self.leaves.len()
+    }
+}
+
+/// Adds `segment` to `path`, returning the length to truncate it back to
+fn enter(path: &mut String, segment: impl fmt::Display) -> usize {
+    let len = path.len();
+    if !path.is_empty() {
+        path.push('.');
+    }
+    // Writing to a `String` can't fail
+    let _ = write!(path, "{segment}");
+    len
+}
+
+/// The path segment of a map entry
+fn map_key(key: Peek<'_, '_, '_>) -> String {
+    match key.as_str() {
+        Some(s) => String::from(s),
+        None => match key.display() {
+            Some(display) => display.to_string(),
+            None => alloc::format!("{key:?}"),
+        },
+    }
+}
+
+struct Leaf<'mem, 'facet, 'shape> {
+    key: String,
+    value: LeafValue<'mem, 'facet, 'shape>,
+}
+
+enum LeafValue<'mem, 'facet, 'shape> {
+    Scalar(Peek<'mem, 'facet, 'shape>),
+    /// A unit variant
+    Name(&'shape str),
+    Null,
+    Redacted,
+}
+
+impl LeafValue<'_, '_, '_> {
+    fn to_value(&self) -> Value<'_> {
+        let peek = match self {
+            LeafValue::Scalar(peek) => peek,
+            LeafValue::Name(name) => return Value::from(*name),
+            LeafValue::Null => return Value::null(),
+            LeafValue::Redacted => return Value::from("[REDACTED]"),
+        };
+        if let Some(s) = peek.as_str() {
+            return Value::from(s);
+        }
+        let value = match peek.scalar_type() {
+            Some(ScalarType::Bool) => peek.get::<bool>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::Char) => peek.get::<char>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::F32) => peek.get::<f32>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::F64) => peek.get::<f64>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::U8) => peek.get::<u8>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::U16) => peek.get::<u16>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::U32) => peek.get::<u32>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::U64) => peek.get::<u64>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::U128) => peek.get::<u128>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::USize) => peek.get::<usize>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::I8) => peek.get::<i8>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::I16) => peek.get::<i16>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::I32) => peek.get::<i32>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::I64) => peek.get::<i64>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::I128) => peek.get::<i128>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::ISize) => peek.get::<isize>().ok().map(|&v| Value::from(v)),
+            Some(ScalarType::Unit) => Some(Value::null()),
+            _ => None,
+        };
+        value.unwrap_or_else(|| Value::from_display(self))
+    }
+}
+
+/// Scalars without a native `log` value are recorded through this
+impl fmt::Display for LeafValue<'_, '_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeafValue::Scalar(peek) => match peek.display() {
+                Some(display) => fmt::Display::fmt(&display, f),
+                None => fmt::Debug::fmt(peek, f),
+            },
+            LeafValue::Name(name) => f.write_str(name),
+            LeafValue::Null => f.write_str("null"),
+            LeafValue::Redacted => f.write_str("[REDACTED]"),
+        }
+    }
+}
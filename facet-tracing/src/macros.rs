@@ -0,0 +1,76 @@
+/// Logs a message at `level`, with Facet values recorded as [`Fields`](crate::Fields)
+///
+/// Each `key = value` pair is flattened under `key`, then the message follows a `;`, with the
+/// same syntax as the `log` macros:
+///
+/// ```
+/// # use facet_tracing::event;
+/// let origin = (0.0, 1.5);
+/// event!(log::Level::Info, origin = origin; "moved to {}", "origin");
+/// // origin.0=0.0 origin.1=1.5
+/// ```
+///
+/// The values are only walked when the level is enabled.
+#[macro_export]
+macro_rules! event {
+    (target: $target:expr, $lvl:expr, $($key:ident = $value:expr),+ $(,)? ; $($arg:tt)+) => {{
+        let level: $crate::__log::Level = $lvl;
+        if level <= $crate::__log::STATIC_MAX_LEVEL && level <= $crate::__log::max_level() {
+            let fields = [$($crate::Fields::prefixed(stringify!($key), &$value)),+];
+            $crate::__log::logger().log(
+                &$crate::__log::Record::builder()
+                    .level(level)
+                    .target($target)
+                    .module_path_static(Some(module_path!()))
+                    .file_static(Some(file!()))
+                    .line(Some(line!()))
+                    .args(format_args!($($arg)+))
+                    .key_values(&fields)
+                    .build(),
+            );
+        }
+    }};
+    ($lvl:expr, $($key:ident = $value:expr),+ $(,)? ; $($arg:tt)+) => {
+        $crate::event!(target: module_path!(), $lvl, $($key = $value),+ ; $($arg)+)
+    };
+}
+
+/// Logs a message at the error level, see [`event!`]
+#[macro_export]
+macro_rules! error {
+    ($($tt:tt)+) => {
+        $crate::event!($crate::__log::Level::Error, $($tt)+)
+    };
+}
+
+/// Logs a message at the warn level, see [`event!`]
+#[macro_export]
+macro_rules! warn {
+    ($($tt:tt)+) => {
+        $crate::event!($crate::__log::Level::Warn, $($tt)+)
+    };
+}
+
+/// Logs a message at the info level, see [`event!`]
+#[macro_export]
+macro_rules! info {
+    ($($tt:tt)+) => {
+        $crate::event!($crate::__log::Level::Info, $($tt)+)
+    };
+}
+
+/// Logs a message at the debug level, see [`event!`]
+#[macro_export]
+macro_rules! debug {
+    ($($tt:tt)+) => {
+        $crate::event!($crate::__log::Level::Debug, $($tt)+)
+    };
+}
+
+/// Logs a message at the trace level, see [`event!`]
+#[macro_export]
+macro_rules! trace {
+    ($($tt:tt)+) => {
+        $crate::event!($crate::__log::Level::Trace, $($tt)+)
+    };
+}
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_testhelpers::test;
+use facet_tracing::Fields;
+use log::kv::{self, Key, Source, Value, VisitSource};
+
+/// The fields as `key=value` strings, in order.
+fn pairs(fields: &Fields) -> Vec<String> {
+    struct Collect(Vec<String>);
+
+    impl<'kvs> VisitSource<'kvs> for Collect {
+        fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+            self.0.push(format!("{key}={value}"));
+            Ok(())
+        }
+    }
+
+    let mut collect = Collect(Vec::new());
+    fields.visit(&mut collect).unwrap();
+    collect.0
+}
+
+#[derive(Facet)]
+struct User {
+    id: u64,
+    name: String,
+    #[facet(sensitive)]
+    token: String,
+    address: Address,
+    tags: Vec<String>,
+    nickname: Option<String>,
+}
+
+#[derive(Facet)]
+struct Address {
+    city: String,
+    zip: (u16, char),
+}
+
+fn user() -> User {
+    User {
+        id: 42,
+        name: "amos".into(),
+        token: "secret".into(),
+        address: Address {
+            city: "Lyon".into(),
+            zip: (69, 'x'),
+        },
+        tags: vec!["admin".into(), "beta".into()],
+        nickname: None,
+    }
+}
+
+#[test]
+fn flattens_nested_structs() {
+    let user = user();
+    assert_eq!(
+        pairs(&Fields::prefixed("user", &user)),
+        [
+            "user.id=42",
+            "user.name=amos",
+            "user.token=[REDACTED]",
+            "user.address.city=Lyon",
+            "user.address.zip.0=69",
+            "user.address.zip.1=x",
+            "user.tags.0=admin",
+            "user.tags.1=beta",
+            "user.nickname=None",
+        ]
+    );
+}
+
+#[test]
+fn without_prefix() {
+    let user = user();
+    let fields = Fields::new(&user);
+    assert_eq!(fields.len(), 9);
+    assert_eq!(pairs(&fields)[0], "id=42");
+
+    assert_eq!(pairs(&Fields::new(&7u8)), ["value=7"]);
+    assert_eq!(pairs(&Fields::prefixed("n", &7u8)), ["n=7"]);
+}
+
+#[test]
+fn keeps_native_values() {
+    let user = user();
+    let fields = Fields::prefixed("user", &user);
+    assert_eq!(fields.get(Key::from("user.id")).unwrap().to_u64(), Some(42));
+    assert_eq!(
+        fields
+            .get(Key::from("user.name"))
+            .unwrap()
+            .to_borrowed_str(),
+        Some("amos")
+    );
+    assert_eq!(
+        fields
+            .get(Key::from("user.address.zip.1"))
+            .unwrap()
+            .to_char(),
+        Some('x')
+    );
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Event {
+    Quit,
+    Click { x: i32, y: i32 },
+    Key(char),
+}
+
+#[test]
+fn enum_variants() {
+    assert_eq!(
+        pairs(&Fields::prefixed("event", &Event::Quit)),
+        ["event=Quit"]
+    );
+    assert_eq!(
+        pairs(&Fields::prefixed("event", &Event::Click { x: 1, y: -2 })),
+        ["event.Click.x=1", "event.Click.y=-2"]
+    );
+    assert_eq!(
+        pairs(&Fields::prefixed("event", &Event::Key('q'))),
+        ["event.Key.0=q"]
+    );
+}
+
+#[test]
+fn maps_and_options() {
+    let limits: BTreeMap<String, Option<u32>> =
+        [("cpu".to_string(), Some(2)), ("mem".to_string(), None)].into();
+    assert_eq!(
+        pairs(&Fields::prefixed("limits", &limits)),
+        ["limits.cpu=2", "limits.mem=None"]
+    );
+
+    let empty: Vec<u8> = Vec::new();
+    assert!(Fields::prefixed("empty", &empty).is_empty());
+}
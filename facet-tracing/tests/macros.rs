@@ -0,0 +1,64 @@
+use std::sync::Mutex;
+
+use facet::Facet;
+use log::kv::{self, Key, Value, VisitSource};
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Keeps the messages and fields of every record.
+struct Capture(Mutex<Vec<String>>);
+
+impl Log for Capture {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        struct Collect(String);
+
+        impl<'kvs> VisitSource<'kvs> for Collect {
+            fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+                self.0.push_str(&format!(" {key}={value}"));
+                Ok(())
+            }
+        }
+
+        let mut line = Collect(format!("{} {}", record.level(), record.args()));
+        record.key_values().visit(&mut line).unwrap();
+        self.0.lock().unwrap().push(line.0);
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+#[derive(Facet)]
+struct Order {
+    id: u32,
+    items: Vec<&'static str>,
+}
+
+#[test]
+fn macros_record_fields() {
+    log::set_logger(&CAPTURE).unwrap();
+    log::set_max_level(LevelFilter::Info);
+
+    let order = Order {
+        id: 7,
+        items: vec!["tea"],
+    };
+    let user = "amos";
+    facet_tracing::info!(order = order, user = user; "placed order {}", order.id);
+    facet_tracing::warn!(order = order; "late");
+    facet_tracing::debug!(order = order; "filtered out");
+    facet_tracing::event!(target: "shop", log::Level::Error, id = order.id; "failed");
+
+    assert_eq!(
+        *CAPTURE.0.lock().unwrap(),
+        [
+            "INFO placed order 7 order.id=7 order.items.0=tea user=amos",
+            "WARN late order.id=7 order.items.0=tea",
+            "ERROR failed id=7",
+        ]
+    );
+}
@@ -2,6 +2,9 @@
 #![forbid(unsafe_code)]
 #![doc = include_str!("../README.md")]
 
+use std::rc::Rc;
+use std::sync::Arc;
+
 use facet_core::{Def, Facet, Type, UserType};
 use facet_reflect::Partial;
 use log::*;
@@ -239,6 +242,11 @@ fn deserialize_scalar_field<'mem, 'shape>(
             if wip.shape().is_type::<String>() {
                 let s = value.to_string();
                 wip.set(s)?;
+            } else if wip.shape().is_type::<Arc<str>>()
+                || wip.shape().is_type::<Rc<str>>()
+                || wip.shape().is_type::<Box<str>>()
+            {
+                wip.parse_from_str(value)?;
             } else if wip.shape().is_type::<u64>() {
                 match value.parse::<u64>() {
                     Ok(num) => wip.set(num)?,
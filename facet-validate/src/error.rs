@@ -0,0 +1,55 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A rule that a value breaks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// Where the value is, like `users[2].email`, or empty for the value itself
+    pub path: String,
+    /// The rule, like `range` or `custom`
+    pub rule: &'static str,
+    /// What's wrong, like `must be at most 10`
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl core::error::Error for Violation {}
+
+/// An error from [`validated`](crate::validated): the value couldn't be deserialized, or
+/// it breaks some rules.
+#[derive(Debug)]
+pub enum Invalid<E> {
+    /// Deserialization failed.
+    Deserialize(E),
+    /// The value was deserialized, but breaks these rules.
+    Violations(Vec<Violation>),
+}
+
+impl<E: fmt::Display> fmt::Display for Invalid<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Invalid::Deserialize(err) => write!(f, "{err}"),
+            Invalid::Violations(violations) => {
+                for (i, violation) in violations.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{violation}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> core::error::Error for Invalid<E> {}
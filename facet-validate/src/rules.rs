@@ -0,0 +1,205 @@
+//! Rules declared with `#[facet(validate(...))]`.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use facet_reflect::FieldMeta;
+
+/// One rule of a field.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Rule {
+    /// `range(min = 1, max = 10)`: a number between `min` and `max`, both included
+    Range { min: Option<f64>, max: Option<f64> },
+    /// `length(min = 1, max = 64)`: as many characters, items or entries
+    Length {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    /// `regex = "^[a-z]+$"`: a string that the pattern matches
+    Regex(String),
+    /// `custom = "name"`: a check registered under that name
+    Custom(String),
+}
+
+impl Rule {
+    /// The name the rule is written with.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::Range { .. } => "range",
+            Rule::Length { .. } => "length",
+            Rule::Regex(_) => "regex",
+            Rule::Custom(_) => "custom",
+        }
+    }
+
+    /// Reads the rules of a field, from all of its `validate(...)` attributes. An attribute
+    /// that can't be read is returned as an error, with what's wrong with it.
+    pub fn of(meta: &FieldMeta<'_>) -> Result<Vec<Rule>, String> {
+        let mut rules = Vec::new();
+        for args in meta.attr_values("validate") {
+            for rule in split_args(args) {
+                rules.push(parse(rule.trim()).ok_or_else(|| format!("invalid rule `{rule}`"))?);
+            }
+        }
+        Ok(rules)
+    }
+}
+
+/// Reads `range(...)`, `length(...)`, `regex = "..."` or `custom = "..."`
+fn parse(rule: &str) -> Option<Rule> {
+    let end = rule
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(rule.len());
+    let (name, rest) = rule.split_at(end);
+    let rest = rest.trim();
+    if let Some(value) = rest.strip_prefix('=') {
+        let value = value.trim();
+        return match name {
+            "regex" => Some(Rule::Regex(string(value)?)),
+            "custom" => Some(Rule::Custom(string(value).unwrap_or_else(|| value.into()))),
+            _ => None,
+        };
+    }
+    let args = rest.strip_prefix('(')?.strip_suffix(')')?;
+    let mut bounds: [Option<&str>; 2] = [None, None];
+    for arg in split_args(args) {
+        let (key, value) = arg.split_once('=')?;
+        let slot = match key.trim() {
+            "min" => &mut bounds[0],
+            "max" => &mut bounds[1],
+            _ => return None,
+        };
+        *slot = Some(value.trim());
+    }
+    if bounds == [None, None] {
+        return None;
+    }
+    let [min, max] = bounds;
+    match name {
+        "range" => Some(Rule::Range {
+            min: bound(min, number)?,
+            max: bound(max, number)?,
+        }),
+        "length" => Some(Rule::Length {
+            min: bound(min, |v| v.parse().ok())?,
+            max: bound(max, |v| v.parse().ok())?,
+        }),
+        _ => None,
+    }
+}
+
+/// Reads a bound that may be left out, failing if it's there but can't be read
+fn bound<T>(value: Option<&str>, read: impl Fn(&str) -> Option<T>) -> Option<Option<T>> {
+    match value {
+        Some(value) => read(value).map(Some),
+        None => Some(None),
+    }
+}
+
+/// Splits `a = 1, b(c, d), e = "f, g"` at the commas that aren't in parentheses or strings.
+fn split_args(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in args.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => depth = depth.saturating_sub(1),
+            ',' if !in_string && depth == 0 => {
+                parts.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&args[start..]);
+    parts.retain(|part| !part.trim().is_empty());
+    parts
+}
+
+/// Reads a number, like `-1` or `0.5`, which may come as `- 1` from the derive macro.
+fn number(literal: &str) -> Option<f64> {
+    let literal: String = literal.split_whitespace().collect();
+    literal.parse().ok().filter(|n: &f64| !n.is_nan())
+}
+
+/// Reads a string literal, like `"^\\d+$"`.
+fn string(literal: &str) -> Option<String> {
+    let inner = literal.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            '0' => out.push('\0'),
+            c @ ('\\' | '"' | '\'') => out.push(c),
+            _ => return None,
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rules() {
+        assert_eq!(
+            parse("range(min = 1, max = 10)"),
+            Some(Rule::Range {
+                min: Some(1.0),
+                max: Some(10.0)
+            })
+        );
+        assert_eq!(
+            parse("range(max = - 0.5)"),
+            Some(Rule::Range {
+                min: None,
+                max: Some(-0.5)
+            })
+        );
+        assert_eq!(
+            parse("length(min = 1)"),
+            Some(Rule::Length {
+                min: Some(1),
+                max: None
+            })
+        );
+        assert_eq!(
+            parse(r#"regex = "^\\d+$""#),
+            Some(Rule::Regex(r"^\d+$".into()))
+        );
+        assert_eq!(parse(r#"custom = "odd""#), Some(Rule::Custom("odd".into())));
+        assert_eq!(parse("custom = odd"), Some(Rule::Custom("odd".into())));
+    }
+
+    #[test]
+    fn rejects_bad_rules() {
+        assert_eq!(parse("range()"), None);
+        assert_eq!(parse("range(min = one)"), None);
+        assert_eq!(parse("length(min = -1)"), None);
+        assert_eq!(parse("length(least = 1)"), None);
+        assert_eq!(parse("regex = ^a$"), None);
+        assert_eq!(parse("email"), None);
+    }
+
+    #[test]
+    fn splits_outside_parentheses_and_strings() {
+        assert_eq!(
+            split_args(r#"range(min = 1, max = 2), regex = "a,b""#),
+            ["range(min = 1, max = 2)", r#" regex = "a,b""#]
+        );
+    }
+}
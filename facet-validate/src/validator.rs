@@ -0,0 +1,308 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use facet_core::{Def, Facet, Type};
+use facet_reflect::{FieldMeta, HasFields, Peek, ScalarType};
+
+use crate::rules::Rule;
+use crate::{Invalid, Violation};
+
+/// A check for `#[facet(validate(custom = "name"))]`, which returns what's wrong with the value
+/// it's given. Options and smart pointers are looked through before the check is called.
+pub type CustomCheck = fn(Peek<'_, '_, '_>) -> Result<(), String>;
+
+/// Checks values against the rules in their fields' `#[facet(validate(...))]` attributes.
+///
+/// Fields of nested structs and enums, list items and map values are checked too, with their
+/// own rules. A field that is `None` is skipped.
+///
+/// Custom checks are looked up by name, first among those added with
+/// [`Validator::with_custom`], then among those registered with [`register_custom`].
+#[derive(Clone, Debug, Default)]
+pub struct Validator {
+    custom: BTreeMap<&'static str, CustomCheck>,
+}
+
+impl Validator {
+    /// A validator with no custom checks of its own.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `check` for `#[facet(validate(custom = "name"))]`.
+    pub fn with_custom(mut self, name: &'static str, check: CustomCheck) -> Self {
+        self.custom.insert(name, check);
+        self
+    }
+
+    /// Checks `value`, returning every rule it breaks.
+    pub fn validate<'facet, T: Facet<'facet>>(&self, value: &T) -> Result<(), Vec<Violation>> {
+        self.validate_peek(Peek::new(value))
+    }
+
+    /// Checks the value behind `peek`, returning every rule it breaks.
+    pub fn validate_peek(&self, peek: Peek<'_, '_, '_>) -> Result<(), Vec<Violation>> {
+        let mut violations = Vec::new();
+        self.walk(peek, &mut String::new(), &mut violations);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    fn walk(&self, peek: Peek<'_, '_, '_>, path: &mut String, violations: &mut Vec<Violation>) {
+        let Some(value) = resolve(peek) else {
+            return;
+        };
+        let len = path.len();
+        if let Ok(ps) = value.into_struct() {
+            for (meta, field) in ps.fields_with_meta() {
+                push_field(path, meta.name());
+                self.check_field(&meta, field, path, violations);
+                self.walk(field, path, violations);
+                path.truncate(len);
+            }
+        } else if let Ok(pe) = value.into_enum() {
+            for (meta, field) in pe.fields_with_meta() {
+                push_field(path, meta.name());
+                self.check_field(&meta, field, path, violations);
+                self.walk(field, path, violations);
+                path.truncate(len);
+            }
+        } else if let Ok(list) = value.into_list_like() {
+            for (i, item) in list.iter().enumerate() {
+                write!(path, "[{i}]").unwrap();
+                self.walk(item, path, violations);
+                path.truncate(len);
+            }
+        } else if let Ok(map) = value.into_map() {
+            for (key, item) in map.iter() {
+                write!(path, "[{key}]").unwrap();
+                self.walk(item, path, violations);
+                path.truncate(len);
+            }
+        }
+    }
+
+    fn check_field(
+        &self,
+        meta: &FieldMeta<'_>,
+        field: Peek<'_, '_, '_>,
+        path: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        let violation = |rule: &'static str, message: String| Violation {
+            path: path.to_string(),
+            rule,
+            message,
+        };
+        let rules = match Rule::of(meta) {
+            Ok(rules) => rules,
+            Err(message) => {
+                violations.push(violation("validate", message));
+                return;
+            }
+        };
+        let Some(value) = resolve(field) else {
+            return;
+        };
+        for rule in rules {
+            if let Err(message) = self.check(&rule, value) {
+                violations.push(violation(rule.name(), message));
+            }
+        }
+    }
+
+    fn check(&self, rule: &Rule, value: Peek<'_, '_, '_>) -> Result<(), String> {
+        match rule {
+            Rule::Range { min, max } => {
+                let n = number(value)
+                    .ok_or_else(|| format!("`range` applies to numbers, not {}", value.shape()))?;
+                if let Some(min) = min.filter(|&min| n < min) {
+                    return Err(format!("must be at least {min}"));
+                }
+                if let Some(max) = max.filter(|&max| n > max) {
+                    return Err(format!("must be at most {max}"));
+                }
+            }
+            Rule::Length { min, max } => {
+                let (len, unit) = length(value).ok_or_else(|| {
+                    format!(
+                        "`length` applies to strings, lists and maps, not {}",
+                        value.shape()
+                    )
+                })?;
+                if let Some(min) = min.filter(|&min| len < min) {
+                    return Err(format!("must have at least {min} {unit}"));
+                }
+                if let Some(max) = max.filter(|&max| len > max) {
+                    return Err(format!("must have at most {max} {unit}"));
+                }
+            }
+            Rule::Regex(pattern) => {
+                let s = value
+                    .as_str()
+                    .ok_or_else(|| format!("`regex` applies to strings, not {}", value.shape()))?;
+                if !matches(pattern, s)? {
+                    return Err(format!("must match `{pattern}`"));
+                }
+            }
+            Rule::Custom(name) => {
+                let check = self
+                    .custom(name)
+                    .ok_or_else(|| format!("no custom check named `{name}`"))?;
+                check(value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn custom(&self, name: &str) -> Option<CustomCheck> {
+        if let Some(&check) = self.custom.get(name) {
+            return Some(check);
+        }
+        #[cfg(feature = "std")]
+        if let Some(&check) = REGISTRY.read().unwrap().get(name) {
+            return Some(check);
+        }
+        None
+    }
+}
+
+#[cfg(feature = "std")]
+static REGISTRY: std::sync::RwLock<BTreeMap<&'static str, CustomCheck>> =
+    std::sync::RwLock::new(BTreeMap::new());
+
+/// Runs `check` for `#[facet(validate(custom = "name"))]`, in every [`Validator`] that
+/// doesn't have a check of that name itself, and in [`validate`] and [`is_valid`].
+#[cfg(feature = "std")]
+pub fn register_custom(name: &'static str, check: CustomCheck) {
+    REGISTRY.write().unwrap().insert(name, check);
+}
+
+/// Checks `value` against the rules in its `#[facet(validate(...))]` attributes, returning
+/// every rule it breaks.
+///
+/// ```
+/// # use facet::Facet;
+/// #[derive(Facet)]
+/// struct Signup {
+///     #[facet(validate(length(min = 3, max = 16), regex = "^[a-z0-9_]+$"))]
+///     username: String,
+///     #[facet(validate(range(min = 13)))]
+///     age: u8,
+/// }
+///
+/// let signup = Signup { username: "Ada!".into(), age: 12 };
+/// let violations = facet_validate::validate(&signup).unwrap_err();
+/// assert_eq!(violations[0].to_string(), "username: must match `^[a-z0-9_]+$`");
+/// assert_eq!(violations[1].to_string(), "age: must be at least 13");
+/// ```
+pub fn validate<'facet, T: Facet<'facet>>(value: &T) -> Result<(), Vec<Violation>> {
+    Validator::new().validate(value)
+}
+
+/// Returns `true` if `value` breaks none of the rules in its `#[facet(validate(...))]`
+/// attributes.
+///
+/// With `#[facet(invariants = facet_validate::is_valid)]` on a struct, building one with
+/// `Partial`, which every deserializer does, fails when a rule is broken.
+pub fn is_valid<'facet, T: Facet<'facet>>(value: &T) -> bool {
+    validate(value).is_ok()
+}
+
+/// Validates a freshly deserialized value, for the rules that broke.
+///
+/// ```
+/// # use facet::Facet;
+/// # use facet_validate::Invalid;
+/// #[derive(Facet)]
+/// struct Page {
+///     #[facet(validate(range(min = 1, max = 100)))]
+///     size: u32,
+/// }
+///
+/// let page = facet_validate::validated(facet_json::from_str::<Page>(r#"{"size": 500}"#));
+/// let Err(Invalid::Violations(violations)) = page else { panic!() };
+/// assert_eq!(violations[0].message, "must be at most 100");
+/// ```
+pub fn validated<'facet, T: Facet<'facet>, E>(result: Result<T, E>) -> Result<T, Invalid<E>> {
+    let value = result.map_err(Invalid::Deserialize)?;
+    validate(&value).map_err(Invalid::Violations)?;
+    Ok(value)
+}
+
+fn push_field(path: &mut String, name: &str) {
+    if !path.is_empty() {
+        path.push('.');
+    }
+    path.push_str(name);
+}
+
+/// Looks through options, smart pointers and references; `None` has nothing to check
+fn resolve<'mem, 'facet, 'shape>(
+    mut peek: Peek<'mem, 'facet, 'shape>,
+) -> Option<Peek<'mem, 'facet, 'shape>> {
+    loop {
+        peek = match (peek.shape().def, peek.shape().ty) {
+            (Def::Option(_), _) => peek.into_option().ok()?.value()?,
+            (Def::SmartPointer(_), _) => peek.into_smart_pointer().ok()?.borrow_inner()?,
+            (_, Type::Pointer(_)) if peek.as_str().is_none() && peek.into_list_like().is_err() => {
+                match peek.into_pointee() {
+                    Ok(pointee) => pointee,
+                    Err(_) => return Some(peek),
+                }
+            }
+            _ => return Some(peek),
+        };
+    }
+}
+
+/// The length of a string, list or map, and what it counts
+fn length(peek: Peek<'_, '_, '_>) -> Option<(usize, &'static str)> {
+    if let Some(s) = peek.as_str() {
+        return Some((s.chars().count(), "characters"));
+    }
+    if let Ok(list) = peek.into_list_like() {
+        return Some((list.len(), "items"));
+    }
+    if let Ok(map) = peek.into_map() {
+        return Some((map.len(), "entries"));
+    }
+    None
+}
+
+fn number(peek: Peek<'_, '_, '_>) -> Option<f64> {
+    Some(match peek.scalar_type()? {
+        ScalarType::F32 => *peek.get::<f32>().ok()? as f64,
+        ScalarType::F64 => *peek.get::<f64>().ok()?,
+        ScalarType::U8 => *peek.get::<u8>().ok()? as f64,
+        ScalarType::U16 => *peek.get::<u16>().ok()? as f64,
+        ScalarType::U32 => *peek.get::<u32>().ok()? as f64,
+        ScalarType::U64 => *peek.get::<u64>().ok()? as f64,
+        ScalarType::U128 => *peek.get::<u128>().ok()? as f64,
+        ScalarType::USize => *peek.get::<usize>().ok()? as f64,
+        ScalarType::I8 => *peek.get::<i8>().ok()? as f64,
+        ScalarType::I16 => *peek.get::<i16>().ok()? as f64,
+        ScalarType::I32 => *peek.get::<i32>().ok()? as f64,
+        ScalarType::I64 => *peek.get::<i64>().ok()? as f64,
+        ScalarType::I128 => *peek.get::<i128>().ok()? as f64,
+        ScalarType::ISize => *peek.get::<isize>().ok()? as f64,
+        _ => return None,
+    })
+}
+
+#[cfg(feature = "regex")]
+fn matches(pattern: &str, s: &str) -> Result<bool, String> {
+    let regex = regex::Regex::new(pattern).map_err(|_| format!("invalid pattern `{pattern}`"))?;
+    Ok(regex.is_match(s))
+}
+
+#[cfg(not(feature = "regex"))]
+fn matches(_pattern: &str, _s: &str) -> Result<bool, String> {
+    Err("`regex` rules need the `regex` feature".to_string())
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_reflect::Peek;
+use facet_testhelpers::test;
+use facet_validate::{Invalid, Validator, Violation, is_valid, validate, validated};
+
+#[derive(Facet)]
+struct Account {
+    #[facet(validate(length(min = 3, max = 16)))]
+    #[facet(validate(regex = "^[a-z0-9_]+$"))]
+    username: String,
+    #[facet(validate(range(min = 13, max = 130)))]
+    age: u32,
+    #[facet(validate(range(min = -12, max = 14)))]
+    utc_offset: Option<i8>,
+    #[facet(validate(length(max = 2)))]
+    emails: Vec<Email>,
+    #[facet(validate(length(min = 1)))]
+    roles: HashMap<String, Role>,
+}
+
+#[derive(Facet)]
+struct Email {
+    #[facet(validate(regex = "^[^@]+@[^@]+$"))]
+    address: String,
+    #[facet(validate(custom = "verified_domain"))]
+    domain: Box<str>,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Role {
+    Admin,
+    Member {
+        #[facet(validate(range(min = 1)))]
+        level: u8,
+    },
+}
+
+fn verified_domain(value: Peek<'_, '_, '_>) -> Result<(), String> {
+    match value.as_str() {
+        Some("example.com") => Ok(()),
+        _ => Err("must be a verified domain".into()),
+    }
+}
+
+fn validator() -> Validator {
+    Validator::new().with_custom("verified_domain", verified_domain)
+}
+
+fn account() -> Account {
+    Account {
+        username: "ada_l".into(),
+        age: 36,
+        utc_offset: None,
+        emails: vec![Email {
+            address: "ada@example.com".into(),
+            domain: "example.com".into(),
+        }],
+        roles: HashMap::from([("ops".into(), Role::Admin)]),
+    }
+}
+
+fn messages(violations: Vec<Violation>) -> Vec<String> {
+    violations.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn valid_value_passes() {
+    validator().validate(&account()).unwrap();
+}
+
+#[test]
+fn reports_every_broken_rule() {
+    let account = Account {
+        username: "Ada".into(),
+        age: 7,
+        utc_offset: Some(20),
+        emails: vec![
+            Email {
+                address: "ada".into(),
+                domain: "example.org".into(),
+            },
+            Email {
+                address: "a@b".into(),
+                domain: "example.com".into(),
+            },
+            Email {
+                address: "c@d".into(),
+                domain: "example.com".into(),
+            },
+        ],
+        roles: HashMap::from([("dev".into(), Role::Member { level: 0 })]),
+    };
+    let violations = validator().validate(&account).unwrap_err();
+    assert_eq!(
+        messages(violations),
+        [
+            "username: must match `^[a-z0-9_]+$`",
+            "age: must be at least 13",
+            "utc_offset: must be at most 14",
+            "emails: must have at most 2 items",
+            "emails[0].address: must match `^[^@]+@[^@]+$`",
+            "emails[0].domain: must be a verified domain",
+            "roles[dev].level: must be at least 1",
+        ]
+    );
+}
+
+#[test]
+fn violations_name_their_rule() {
+    let mut account = account();
+    account.username = "x".into();
+    account.roles.clear();
+    let violations = validator().validate(&account).unwrap_err();
+    assert_eq!(
+        violations,
+        [
+            Violation {
+                path: "username".into(),
+                rule: "length",
+                message: "must have at least 3 characters".into(),
+            },
+            Violation {
+                path: "roles".into(),
+                rule: "length",
+                message: "must have at least 1 entries".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn missing_custom_check_is_a_violation() {
+    let violations = Validator::new().validate(&account()).unwrap_err();
+    assert_eq!(
+        messages(violations),
+        ["emails[0].domain: no custom check named `verified_domain`"]
+    );
+}
+
+#[test]
+fn registered_custom_check() {
+    #[derive(Facet)]
+    struct Grid {
+        #[facet(validate(custom = "even_for_registry_test"))]
+        columns: u32,
+    }
+
+    fn even(value: Peek<'_, '_, '_>) -> Result<(), String> {
+        match value.get::<u32>() {
+            Ok(n) if n % 2 == 0 => Ok(()),
+            _ => Err("must be even".into()),
+        }
+    }
+
+    facet_validate::register_custom("even_for_registry_test", even);
+    assert!(validate(&Grid { columns: 4 }).is_ok());
+    assert_eq!(
+        messages(validate(&Grid { columns: 3 }).unwrap_err()),
+        ["columns: must be even"]
+    );
+}
+
+#[test]
+fn misplaced_and_invalid_rules() {
+    #[derive(Facet)]
+    struct Odd {
+        #[facet(validate(range(min = 1)))]
+        name: String,
+        #[facet(validate(lenght(min = 1)))]
+        tags: Vec<String>,
+    }
+
+    let violations = validate(&Odd {
+        name: "x".into(),
+        tags: vec![],
+    })
+    .unwrap_err();
+    assert_eq!(violations[0].rule, "range");
+    assert_eq!(
+        violations[0].message,
+        "`range` applies to numbers, not String"
+    );
+    assert_eq!(violations[1].rule, "validate");
+}
+
+#[test]
+fn validated_after_deserializing() {
+    #[derive(Facet, Debug)]
+    struct Page {
+        #[facet(validate(range(min = 1, max = 100)))]
+        size: u32,
+    }
+
+    let page = validated(facet_json::from_str::<Page>(r#"{"size": 50}"#))?;
+    assert_eq!(page.size, 50);
+
+    match validated(facet_json::from_str::<Page>(r#"{"size": 500}"#)) {
+        Err(Invalid::Violations(violations)) => {
+            assert_eq!(messages(violations), ["size: must be at most 100"])
+        }
+        other => panic!("expected violations, got {other:?}"),
+    }
+    assert!(matches!(
+        validated(facet_json::from_str::<Page>(r#"{"size": "big"}"#)),
+        Err(Invalid::Deserialize(_))
+    ));
+}
+
+#[test]
+fn rules_as_invariants() {
+    #[derive(Facet, Debug)]
+    #[facet(invariants = facet_validate::is_valid)]
+    struct Page {
+        #[facet(validate(range(min = 1, max = 100)))]
+        size: u32,
+    }
+
+    assert!(facet_json::from_str::<Page>(r#"{"size": 500}"#).is_err());
+    assert_eq!(facet_json::from_str::<Page>(r#"{"size": 50}"#)?.size, 50);
+    assert!(is_valid(&Page { size: 1 }));
+    assert!(!is_valid(&Page { size: 0 }));
+}
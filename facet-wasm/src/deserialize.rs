@@ -0,0 +1,421 @@
+//! Convert JavaScript values into Rust values.
+
+use std::borrow::Cow;
+
+use facet_core::{Def, Facet, Field, FieldFlags, StructKind, Type, UserType};
+use facet_reflect::{Partial, ScalarType};
+use js_sys::{Array, Map, Object, Uint8Array};
+use log::trace;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::WasmDeError;
+
+/// Converts a JavaScript value into a value of type `T`.
+///
+/// This reads what [`to_js`](crate::to_js) writes, and a little more: `null` is `None`
+/// too, integers can be numbers or `BigInt`s, `Map`s can stand for objects and any
+/// array of numbers can be read as a `Vec<u8>`.
+pub fn from_js<'facet: 'shape, 'shape, T: Facet<'facet>>(
+    value: &JsValue,
+) -> Result<T, WasmDeError<'shape>> {
+    let mut partial = Partial::alloc::<T>()?;
+    deserialize_value(partial.inner_mut(), value)?;
+    let value = partial.build()?;
+    Ok(*value)
+}
+
+/// The JavaScript type of a value, as it's named in errors.
+fn kind(value: &JsValue) -> &'static str {
+    if value.is_undefined() {
+        "undefined"
+    } else if value.is_null() {
+        "null"
+    } else if value.as_bool().is_some() {
+        "boolean"
+    } else if value.as_f64().is_some() {
+        "number"
+    } else if value.is_bigint() {
+        "bigint"
+    } else if value.is_string() {
+        "string"
+    } else if Array::is_array(value) {
+        "array"
+    } else if value.is_instance_of::<Uint8Array>() {
+        "Uint8Array"
+    } else if value.is_function() {
+        "function"
+    } else if value.is_symbol() {
+        "symbol"
+    } else {
+        "object"
+    }
+}
+
+fn mismatch<'shape>(value: &JsValue, wip: &Partial<'_, 'shape>) -> WasmDeError<'shape> {
+    WasmDeError::TypeMismatch {
+        found: kind(value),
+        shape: wip.shape(),
+    }
+}
+
+/// The entries of a plain object or of a `Map`, or `None` for anything else.
+fn entries(value: &JsValue) -> Option<Vec<(JsValue, JsValue)>> {
+    if let Some(map) = value.dyn_ref::<Map>() {
+        let mut entries = Vec::with_capacity(map.size() as usize);
+        map.for_each(&mut |value, key| entries.push((key, value)));
+        return Some(entries);
+    }
+    if kind(value) != "object" {
+        return None;
+    }
+    let entries = Object::entries(value.unchecked_ref())
+        .iter()
+        .map(|entry| {
+            let entry: Array = entry.unchecked_into();
+            (entry.get(0), entry.get(1))
+        })
+        .collect();
+    Some(entries)
+}
+
+fn deserialize_value<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: &JsValue,
+) -> Result<(), WasmDeError<'shape>> {
+    let shape = wip.shape();
+    trace!("Converting JavaScript {} into {}", kind(value), shape);
+
+    if shape.is_type::<Vec<u8>>() {
+        if let Some(data) = value.dyn_ref::<Uint8Array>() {
+            wip.set(data.to_vec())?;
+            return Ok(());
+        }
+    }
+
+    match shape.def {
+        Def::Option(_) => {
+            if value.is_undefined() || value.is_null() {
+                wip.set_default()?;
+            } else {
+                wip.begin_some()?;
+                deserialize_value(wip, value)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::List(_) => {
+            let Some(items) = value.dyn_ref::<Array>() else {
+                return Err(mismatch(value, wip));
+            };
+            wip.begin_list()?;
+            for item in items.iter() {
+                wip.begin_list_item()?;
+                deserialize_value(wip, &item)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::Array(ad) => {
+            let Some(items) = value.dyn_ref::<Array>() else {
+                return Err(mismatch(value, wip));
+            };
+            if items.length() as usize != ad.n {
+                return Err(mismatch(value, wip));
+            }
+            for (index, item) in items.iter().enumerate() {
+                wip.begin_nth_element(index)?;
+                deserialize_value(wip, &item)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        Def::Map(_) => {
+            let Some(entries) = entries(value) else {
+                return Err(mismatch(value, wip));
+            };
+            wip.begin_map()?;
+            for (key, value) in entries {
+                wip.begin_key()?;
+                deserialize_value(wip, &key)?;
+                wip.end()?;
+                wip.begin_value()?;
+                deserialize_value(wip, &value)?;
+                wip.end()?;
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    if ScalarType::try_from_shape(shape).is_some() {
+        return deserialize_scalar(wip, value);
+    }
+
+    if shape.inner.is_some() {
+        wip.begin_inner()?;
+        deserialize_value(wip, value)?;
+        wip.end()?;
+        return Ok(());
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => match sd.kind {
+            StructKind::Tuple | StructKind::TupleStruct => {
+                let Some(items) = value.dyn_ref::<Array>() else {
+                    return Err(mismatch(value, wip));
+                };
+                deserialize_tuple(wip, sd.fields, items)
+            }
+            StructKind::Struct | StructKind::Unit => {
+                let Some(entries) = entries(value) else {
+                    return Err(mismatch(value, wip));
+                };
+                deserialize_fields(wip, sd.fields, entries)
+            }
+            _ => Err(WasmDeError::UnsupportedShape(shape)),
+        },
+        Type::User(UserType::Enum(_)) => deserialize_enum(wip, value),
+        _ if shape.is_from_str() => deserialize_scalar(wip, value),
+        _ => Err(WasmDeError::UnsupportedShape(shape)),
+    }
+}
+
+fn deserialize_tuple<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    items: &Array,
+) -> Result<(), WasmDeError<'shape>> {
+    if items.length() as usize != fields.len() {
+        return Err(mismatch(items, wip));
+    }
+    for (index, item) in items.iter().enumerate() {
+        wip.begin_nth_field(index)?;
+        deserialize_value(wip, &item)?;
+        wip.end()?;
+    }
+    Ok(())
+}
+
+/// Fills the fields of a struct or of the selected enum variant from an object.
+fn deserialize_fields<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    fields: &'shape [Field<'shape>],
+    entries: Vec<(JsValue, JsValue)>,
+) -> Result<(), WasmDeError<'shape>> {
+    let shape = wip.shape();
+    for (key, value) in entries {
+        let Some(key) = key.as_string() else {
+            return Err(mismatch(&key, wip));
+        };
+        let Some(index) = fields.iter().position(|f| f.name == key) else {
+            if shape.has_deny_unknown_fields_attr() {
+                return Err(WasmDeError::UnknownField { name: key, shape });
+            }
+            trace!("Skipping unknown key '{key}' of {shape}");
+            continue;
+        };
+        // `undefined` fields are treated like missing ones
+        if value.is_undefined() {
+            continue;
+        }
+        wip.begin_nth_field(index)?;
+        deserialize_value(wip, &value)?;
+        wip.end()?;
+    }
+
+    if let Type::User(UserType::Struct(_)) = shape.ty {
+        wip.fill_unset_fields_from_default()?;
+    }
+    for (index, field) in fields.iter().enumerate() {
+        if !wip.is_field_set(index)? {
+            default_field(wip, index, field)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fills a field that has no key in the object: `None` for options, and the default
+/// for fields with `#[facet(default)]`.
+fn default_field<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    index: usize,
+    field: &Field<'shape>,
+) -> Result<(), WasmDeError<'shape>> {
+    let has_default = field.flags.contains(FieldFlags::DEFAULT);
+    if !has_default && !matches!(field.shape().def, Def::Option(_)) {
+        return Err(WasmDeError::MissingField(field.name));
+    }
+
+    wip.begin_nth_field(index)?;
+    match field.vtable.default_fn {
+        Some(default_fn) if has_default => wip.set_field_default(default_fn)?,
+        _ => wip.set_default()?,
+    };
+    wip.end()?;
+    Ok(())
+}
+
+/// Enums are externally tagged: unit variants are strings, and other variants are an
+/// object with the variant name as its only key.
+fn deserialize_enum<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: &JsValue,
+) -> Result<(), WasmDeError<'shape>> {
+    let shape = wip.shape();
+    let (name, content) = if let Some(name) = value.as_string() {
+        (name, None)
+    } else {
+        match entries(value) {
+            Some(mut entries) if entries.len() == 1 => {
+                let (name, content) = entries.remove(0);
+                let Some(name) = name.as_string() else {
+                    return Err(mismatch(&name, wip));
+                };
+                (name, Some(content))
+            }
+            _ => return Err(mismatch(value, wip)),
+        }
+    };
+
+    let Some((_, variant)) = wip.find_variant(&name) else {
+        return Err(WasmDeError::UnknownVariant { name, shape });
+    };
+    let fields = variant.data.fields;
+    let kind = variant.data.kind;
+    wip.select_variant_named(&name)?;
+
+    match (kind, content) {
+        (StructKind::Unit, None) => Ok(()),
+        (StructKind::Struct, Some(content)) => match entries(&content) {
+            Some(entries) => deserialize_fields(wip, fields, entries),
+            None => Err(mismatch(&content, wip)),
+        },
+        (StructKind::Tuple | StructKind::TupleStruct, Some(content)) if fields.len() == 1 => {
+            wip.begin_nth_field(0)?;
+            deserialize_value(wip, &content)?;
+            wip.end()?;
+            Ok(())
+        }
+        (StructKind::Tuple | StructKind::TupleStruct, Some(content)) => {
+            match content.dyn_ref::<Array>() {
+                Some(items) => deserialize_tuple(wip, fields, items),
+                None => Err(mismatch(&content, wip)),
+            }
+        }
+        (_, Some(content)) => Err(mismatch(&content, wip)),
+        (_, None) => Err(mismatch(value, wip)),
+    }
+}
+
+/// An integer from a number without a fractional part, or from a `BigInt`.
+fn integer<'shape>(
+    value: &JsValue,
+    wip: &Partial<'_, 'shape>,
+) -> Result<i128, WasmDeError<'shape>> {
+    if let Some(n) = value.as_f64() {
+        if n.fract() != 0.0 || !n.is_finite() {
+            return Err(WasmDeError::NumberOutOfRange(wip.shape()));
+        }
+        return Ok(n as i128);
+    }
+    if value.is_bigint() {
+        return i128::try_from(value.clone())
+            .map_err(|_| WasmDeError::NumberOutOfRange(wip.shape()));
+    }
+    Err(mismatch(value, wip))
+}
+
+fn deserialize_scalar<'facet, 'shape>(
+    wip: &mut Partial<'facet, 'shape>,
+    value: &JsValue,
+) -> Result<(), WasmDeError<'shape>> {
+    let shape = wip.shape();
+    let out_of_range = |_| WasmDeError::NumberOutOfRange(shape);
+
+    // Strings go through `FromStr` for any type that has it, like map keys read from
+    // object keys
+    if let Some(s) = value.as_string() {
+        return match ScalarType::try_from_shape(shape) {
+            Some(ScalarType::String) => {
+                wip.set(s)?;
+                Ok(())
+            }
+            Some(ScalarType::CowStr) => {
+                wip.set(Cow::<'static, str>::Owned(s))?;
+                Ok(())
+            }
+            _ if shape.is_from_str() => {
+                wip.parse_from_str(&s)
+                    .map_err(|_| WasmDeError::InvalidValue(shape))?;
+                Ok(())
+            }
+            _ => Err(mismatch(value, wip)),
+        };
+    }
+
+    match ScalarType::try_from_shape(shape) {
+        Some(ScalarType::Unit) if value.is_undefined() || value.is_null() => {
+            wip.set(())?;
+        }
+        Some(ScalarType::Bool) => {
+            let Some(b) = value.as_bool() else {
+                return Err(mismatch(value, wip));
+            };
+            wip.set(b)?;
+        }
+        Some(ScalarType::F32 | ScalarType::F64) => {
+            let n = match value.as_f64() {
+                Some(n) => n,
+                None => integer(value, wip)? as f64,
+            };
+            if shape.is_type::<f32>() {
+                wip.set(n as f32)?;
+            } else {
+                wip.set(n)?;
+            }
+        }
+        Some(ScalarType::U8) => {
+            wip.set(u8::try_from(integer(value, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::U16) => {
+            wip.set(u16::try_from(integer(value, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::U32) => {
+            wip.set(u32::try_from(integer(value, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::U64) => {
+            wip.set(u64::try_from(integer(value, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::USize) => {
+            wip.set(usize::try_from(integer(value, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::U128) => {
+            let n = if value.is_bigint() {
+                u128::try_from(value.clone()).map_err(|_| WasmDeError::NumberOutOfRange(shape))?
+            } else {
+                u128::try_from(integer(value, wip)?).map_err(out_of_range)?
+            };
+            wip.set(n)?;
+        }
+        Some(ScalarType::I8) => {
+            wip.set(i8::try_from(integer(value, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::I16) => {
+            wip.set(i16::try_from(integer(value, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::I32) => {
+            wip.set(i32::try_from(integer(value, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::I64) => {
+            wip.set(i64::try_from(integer(value, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::ISize) => {
+            wip.set(isize::try_from(integer(value, wip)?).map_err(out_of_range)?)?;
+        }
+        Some(ScalarType::I128) => {
+            wip.set(integer(value, wip)?)?;
+        }
+        _ => return Err(mismatch(value, wip)),
+    }
+    Ok(())
+}
@@ -0,0 +1,119 @@
+//! Errors from converting to and from JavaScript values.
+
+use facet_core::Shape;
+use facet_reflect::ReflectError;
+use wasm_bindgen::JsValue;
+
+/// Errors when converting a JavaScript value into a Rust value.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WasmDeError<'shape> {
+    /// A value has a different type than the Rust type expects.
+    TypeMismatch {
+        /// The JavaScript type found, such as `"object"` or `"array"`.
+        found: &'static str,
+        /// The Rust type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// An object has a key that doesn't match any field, and the struct denies unknown fields.
+    UnknownField {
+        /// The key.
+        name: String,
+        /// The struct type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A string or object key doesn't match any variant of the enum.
+    UnknownVariant {
+        /// The variant name found.
+        name: String,
+        /// The enum type.
+        shape: &'shape Shape<'shape>,
+    },
+    /// A field is missing and its type has no default value.
+    MissingField(&'shape str),
+    /// A number isn't an integer, or doesn't fit the Rust type it's converted into.
+    NumberOutOfRange(&'shape Shape<'shape>),
+    /// A string couldn't be parsed into the Rust type.
+    InvalidValue(&'shape Shape<'shape>),
+    /// The Rust type can't be converted from a JavaScript value.
+    UnsupportedShape(&'shape Shape<'shape>),
+    /// Any error from facet.
+    Reflect(ReflectError<'shape>),
+}
+
+impl<'shape> From<ReflectError<'shape>> for WasmDeError<'shape> {
+    fn from(err: ReflectError<'shape>) -> Self {
+        WasmDeError::Reflect(err)
+    }
+}
+
+impl core::fmt::Display for WasmDeError<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WasmDeError::TypeMismatch { found, shape } => {
+                write!(f, "Expected {shape}, found a JavaScript {found}")
+            }
+            WasmDeError::UnknownField { name, shape } => {
+                write!(f, "Unknown key '{name}' for {shape}")
+            }
+            WasmDeError::UnknownVariant { name, shape } => {
+                write!(f, "'{name}' is not a variant of {shape}")
+            }
+            WasmDeError::MissingField(name) => write!(f, "Missing field '{name}'"),
+            WasmDeError::NumberOutOfRange(shape) => {
+                write!(f, "Number out of range for {shape}")
+            }
+            WasmDeError::InvalidValue(shape) => write!(f, "Can't parse string as {shape}"),
+            WasmDeError::UnsupportedShape(shape) => {
+                write!(f, "Type {shape} can't be converted from a JavaScript value")
+            }
+            WasmDeError::Reflect(err) => write!(f, "Reflection error: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for WasmDeError<'_> {}
+
+/// Thrown as a JavaScript `Error`, so that `?` works in `#[wasm_bindgen]` functions that
+/// return `Result<_, JsValue>`.
+impl From<WasmDeError<'_>> for JsValue {
+    fn from(err: WasmDeError<'_>) -> Self {
+        js_sys::Error::new(&err.to_string()).into()
+    }
+}
+
+/// Errors when converting a Rust value into a JavaScript value.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WasmSerError {
+    /// A type can't be represented as a JavaScript value.
+    UnsupportedType(String),
+    /// A map's key type is neither a string nor has a `Display` implementation, so it can't
+    /// be a property name.
+    UnsupportedKeyType(String),
+}
+
+impl core::fmt::Display for WasmSerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WasmSerError::UnsupportedType(ty) => {
+                write!(f, "Type {ty} can't be converted to a JavaScript value")
+            }
+            WasmSerError::UnsupportedKeyType(ty) => {
+                write!(
+                    f,
+                    "Type {ty} can't be a property name of a JavaScript object"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for WasmSerError {}
+
+/// Thrown as a JavaScript `Error`, like [`WasmDeError`].
+impl From<WasmSerError> for JsValue {
+    fn from(err: WasmSerError) -> Self {
+        js_sys::Error::new(&err.to_string()).into()
+    }
+}
@@ -0,0 +1,15 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+mod error;
+pub use error::*;
+
+mod deserialize;
+pub use deserialize::*;
+
+mod serialize;
+pub use serialize::*;
+
+/// The largest integer that a JavaScript number holds exactly, `2^53 - 1`.
+pub(crate) const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
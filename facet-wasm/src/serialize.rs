@@ -0,0 +1,171 @@
+//! Convert Rust values into JavaScript values.
+
+use facet_core::{Def, Facet, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek, ScalarType};
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use wasm_bindgen::JsValue;
+
+use crate::{MAX_SAFE_INTEGER, WasmSerError};
+
+/// Converts a value into a JavaScript value.
+///
+/// Structs and maps become plain objects, lists become arrays and `Vec<u8>` becomes a
+/// `Uint8Array`. `None` is `undefined`, and `None` fields are left out of their object.
+/// 64-bit integers are numbers when they fit in one exactly, and `BigInt`s when they
+/// don't; 128-bit integers are always `BigInt`s.
+pub fn to_js<'facet, T: Facet<'facet>>(value: &T) -> Result<JsValue, WasmSerError> {
+    to_value(Peek::new(value))
+}
+
+fn unsupported(peek: Peek<'_, '_, '_>) -> WasmSerError {
+    WasmSerError::UnsupportedType(peek.shape().to_string())
+}
+
+/// Sets a property on an object made by this module.
+fn set(object: &Object, key: &str, value: &JsValue) {
+    // Plain objects take any key, so this can't fail
+    let _ = Reflect::set(object, &JsValue::from_str(key), value);
+}
+
+/// A number if `n` fits in one exactly, or a `BigInt`.
+fn integer(n: i128) -> JsValue {
+    if n.unsigned_abs() <= MAX_SAFE_INTEGER as u128 {
+        JsValue::from_f64(n as f64)
+    } else {
+        JsValue::from(n)
+    }
+}
+
+fn to_value(peek: Peek<'_, '_, '_>) -> Result<JsValue, WasmSerError> {
+    let shape = peek.shape();
+    let err = |_| unsupported(peek);
+
+    if let Ok(data) = peek.get::<Vec<u8>>() {
+        return Ok(Uint8Array::from(data.as_slice()).into());
+    }
+
+    match shape.def {
+        Def::Option(_) => {
+            let opt = peek.into_option().map_err(err)?;
+            return match opt.value() {
+                Some(inner) => to_value(inner),
+                None => Ok(JsValue::UNDEFINED),
+            };
+        }
+        Def::List(_) | Def::Array(_) | Def::Slice(_) => {
+            let list = peek.into_list_like().map_err(err)?;
+            let array = Array::new();
+            for item in list.iter() {
+                array.push(&to_value(item)?);
+            }
+            return Ok(array.into());
+        }
+        Def::Map(_) => {
+            let map = peek.into_map().map_err(err)?;
+            let object = Object::new();
+            for (key, value) in map.iter() {
+                let name = key
+                    .as_text()
+                    .ok_or_else(|| WasmSerError::UnsupportedKeyType(key.shape().to_string()))?;
+                set(&object, &name, &to_value(value)?);
+            }
+            return Ok(object.into());
+        }
+        _ => {}
+    }
+
+    if let Some(scalar) = peek.scalar_type() {
+        let value = match scalar {
+            ScalarType::Unit => JsValue::UNDEFINED,
+            ScalarType::Bool => JsValue::from_bool(*peek.get::<bool>().map_err(err)?),
+            ScalarType::F32 => JsValue::from_f64(*peek.get::<f32>().map_err(err)? as f64),
+            ScalarType::F64 => JsValue::from_f64(*peek.get::<f64>().map_err(err)?),
+            ScalarType::U8 => JsValue::from_f64(*peek.get::<u8>().map_err(err)? as f64),
+            ScalarType::U16 => JsValue::from_f64(*peek.get::<u16>().map_err(err)? as f64),
+            ScalarType::U32 => JsValue::from_f64(*peek.get::<u32>().map_err(err)? as f64),
+            ScalarType::U64 => integer(*peek.get::<u64>().map_err(err)? as i128),
+            ScalarType::USize => integer(*peek.get::<usize>().map_err(err)? as i128),
+            ScalarType::U128 => JsValue::from(*peek.get::<u128>().map_err(err)?),
+            ScalarType::I8 => JsValue::from_f64(*peek.get::<i8>().map_err(err)? as f64),
+            ScalarType::I16 => JsValue::from_f64(*peek.get::<i16>().map_err(err)? as f64),
+            ScalarType::I32 => JsValue::from_f64(*peek.get::<i32>().map_err(err)? as f64),
+            ScalarType::I64 => integer(*peek.get::<i64>().map_err(err)? as i128),
+            ScalarType::ISize => integer(*peek.get::<isize>().map_err(err)? as i128),
+            ScalarType::I128 => JsValue::from(*peek.get::<i128>().map_err(err)?),
+            _ if shape.is_display() => {
+                JsValue::from_str(&peek.as_text().ok_or_else(|| unsupported(peek))?)
+            }
+            _ => return Err(unsupported(peek)),
+        };
+        return Ok(value);
+    }
+
+    if shape.inner.is_some() {
+        return to_value(peek.innermost_peek());
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => {
+            let ps = peek.into_struct().map_err(err)?;
+            match sd.kind {
+                StructKind::Tuple | StructKind::TupleStruct => {
+                    let array = Array::new();
+                    for index in 0..ps.field_count() {
+                        let field = ps.field(index).map_err(|_| unsupported(peek))?;
+                        array.push(&to_value(field)?);
+                    }
+                    Ok(array.into())
+                }
+                _ => fields_to_object(ps.fields_for_serialize()),
+            }
+        }
+        Type::User(UserType::Enum(_)) => {
+            let pe = peek.into_enum().map_err(|_| unsupported(peek))?;
+            let variant = pe.active_variant().map_err(|_| unsupported(peek))?;
+            let content = match variant.data.kind {
+                StructKind::Unit => return Ok(JsValue::from_str(variant.name)),
+                StructKind::Struct => fields_to_object(pe.fields_for_serialize())?,
+                _ => {
+                    let fields = variant.data.fields.len();
+                    let array = Array::new();
+                    for index in 0..fields {
+                        let field = pe
+                            .field(index)
+                            .ok()
+                            .flatten()
+                            .ok_or_else(|| unsupported(peek))?;
+                        array.push(&to_value(field)?);
+                    }
+                    if fields == 1 {
+                        array.get(0)
+                    } else {
+                        array.into()
+                    }
+                }
+            };
+            let object = Object::new();
+            set(&object, variant.name, &content);
+            Ok(object.into())
+        }
+        _ if shape.is_display() => Ok(JsValue::from_str(
+            &peek.as_text().ok_or_else(|| unsupported(peek))?,
+        )),
+        _ => Err(unsupported(peek)),
+    }
+}
+
+fn fields_to_object<'mem, 'facet, 'shape>(
+    fields: impl Iterator<Item = (facet_core::Field<'shape>, Peek<'mem, 'facet, 'shape>)>,
+) -> Result<JsValue, WasmSerError> {
+    let object = Object::new();
+    for (field, value) in fields {
+        // Left out rather than set to `undefined`, so that `"key" in object` is false
+        if let Def::Option(_) = value.shape().def {
+            if value.into_option().is_ok_and(|opt| opt.is_none()) {
+                continue;
+            }
+        }
+        set(&object, field.name, &to_value(value)?);
+    }
+    Ok(object.into())
+}
@@ -0,0 +1,121 @@
+//! Run with `cargo test -p facet-wasm --target wasm32-unknown-unknown`, with
+//! `wasm-bindgen-test-runner` as the target's runner.
+#![cfg(target_arch = "wasm32")]
+
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_wasm::{WasmDeError, WasmSerError, from_js, to_js};
+use js_sys::{Array, Object, Reflect, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Todo {
+    title: String,
+    done: bool,
+    tags: Vec<String>,
+    due: Option<u32>,
+    id: u64,
+    attachment: Vec<u8>,
+    state: State,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+enum State {
+    Open,
+    Blocked { by: String },
+}
+
+fn get(object: &JsValue, key: &str) -> JsValue {
+    Reflect::get(object, &JsValue::from_str(key)).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn struct_roundtrip() {
+    let todo = Todo {
+        title: "write docs".to_string(),
+        done: false,
+        tags: vec!["docs".to_string()],
+        due: None,
+        id: u64::MAX,
+        attachment: vec![1, 2, 3],
+        state: State::Blocked {
+            by: "review".to_string(),
+        },
+    };
+
+    let js = to_js(&todo).unwrap();
+    assert_eq!(get(&js, "title"), "write docs");
+    assert_eq!(get(&js, "done"), false);
+    assert!(Array::is_array(&get(&js, "tags")));
+    assert!(!Reflect::has(&js, &JsValue::from_str("due")).unwrap());
+    // Too large for a number
+    assert!(get(&js, "id").is_bigint());
+    assert!(get(&js, "attachment").is_instance_of::<Uint8Array>());
+    assert_eq!(get(&get(&get(&js, "state"), "Blocked"), "by"), "review");
+
+    assert_eq!(from_js::<Todo>(&js).unwrap(), todo);
+}
+
+#[wasm_bindgen_test]
+fn reads_plain_javascript() {
+    let object = Object::new();
+    let set = |key: &str, value: JsValue| {
+        Reflect::set(&object, &JsValue::from_str(key), &value).unwrap();
+    };
+    set("title", "ship it".into());
+    set("done", true.into());
+    set("tags", Array::of2(&"a".into(), &"b".into()).into());
+    set("due", JsValue::NULL);
+    set("id", 7.into());
+    set("attachment", Array::of1(&255.into()).into());
+    set("state", "Open".into());
+    set("extra", "ignored".into());
+
+    let todo: Todo = from_js(&object).unwrap();
+    assert_eq!(todo.tags, ["a", "b"]);
+    assert_eq!(todo.due, None);
+    assert_eq!(todo.id, 7);
+    assert_eq!(todo.attachment, [255]);
+    assert_eq!(todo.state, State::Open);
+}
+
+#[wasm_bindgen_test]
+fn map_keys_are_parsed() {
+    let scores: HashMap<u32, f64> = [(1, 0.5), (2, 1.5)].into_iter().collect();
+    let js = to_js(&scores).unwrap();
+    assert_eq!(get(&js, "2"), 1.5);
+    assert_eq!(from_js::<HashMap<u32, f64>>(&js).unwrap(), scores);
+
+    // A key that can't be written as text isn't written as its type name
+    let grid: HashMap<(u8, u8), f64> = [((0, 1), 0.5)].into_iter().collect();
+    let err = to_js(&grid).unwrap_err();
+    assert!(matches!(err, WasmSerError::UnsupportedKeyType(_)));
+}
+
+#[wasm_bindgen_test]
+fn errors() {
+    let err = from_js::<u8>(&JsValue::from_f64(1.5)).unwrap_err();
+    assert!(matches!(err, WasmDeError::NumberOutOfRange(_)));
+
+    let err = from_js::<u8>(&JsValue::from_f64(256.0)).unwrap_err();
+    assert!(matches!(err, WasmDeError::NumberOutOfRange(_)));
+
+    let err = from_js::<Todo>(&JsValue::from_str("todo")).unwrap_err();
+    assert!(matches!(
+        err,
+        WasmDeError::TypeMismatch {
+            found: "string",
+            ..
+        }
+    ));
+
+    // Thrown as an `Error`
+    let thrown: JsValue = from_js::<State>(&JsValue::from_str("Closed"))
+        .unwrap_err()
+        .into();
+    let message = thrown.unchecked_into::<js_sys::Error>().message();
+    assert_eq!(message, "'Closed' is not a variant of State");
+}
@@ -0,0 +1,58 @@
+//! The actix-web extractor and responses.
+
+use core::future::Future;
+use core::pin::Pin;
+
+use actix_web::body::BoxBody;
+use actix_web::dev::Payload;
+use actix_web::http::{StatusCode, header};
+use actix_web::web::Bytes;
+use actix_web::{FromRequest, HttpRequest, HttpResponse, Responder, ResponseError};
+use facet_core::Facet;
+
+use crate::{FacetJson, JsonRejection};
+
+impl<T> FromRequest for FacetJson<T>
+where
+    T: for<'facet> Facet<'facet> + 'static,
+{
+    type Error = JsonRejection;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        let body = Bytes::from_request(req, payload);
+        Box::pin(async move {
+            let body = body
+                .await
+                .map_err(|err| JsonRejection::BodyRead(err.to_string()))?;
+            FacetJson::from_bytes(content_type.as_deref(), &body)
+        })
+    }
+}
+
+impl<'facet, T: Facet<'facet>> Responder for FacetJson<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Ok()
+            .content_type("application/json")
+            .body(self.to_body())
+    }
+}
+
+impl ResponseError for JsonRejection {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.status()).unwrap_or(StatusCode::BAD_REQUEST)
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        HttpResponse::build(self.status_code())
+            .content_type("application/json")
+            .body(self.body())
+    }
+}
@@ -0,0 +1,47 @@
+//! The axum extractor and responses.
+
+use ::axum::body::Bytes;
+use ::axum::extract::{FromRequest, Request};
+use ::axum::http::{StatusCode, header};
+use ::axum::response::{IntoResponse, Response};
+use facet_core::Facet;
+
+use crate::{FacetJson, JsonRejection};
+
+impl<T, S> FromRequest<S> for FacetJson<T>
+where
+    T: for<'facet> Facet<'facet>,
+    S: Send + Sync,
+{
+    type Rejection = JsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|err| JsonRejection::BodyRead(err.body_text()))?;
+        FacetJson::from_bytes(content_type.as_deref(), &body)
+    }
+}
+
+impl<'facet, T: Facet<'facet>> IntoResponse for FacetJson<T> {
+    fn into_response(self) -> Response {
+        ([(header::CONTENT_TYPE, "application/json")], self.to_body()).into_response()
+    }
+}
+
+impl IntoResponse for JsonRejection {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status()).unwrap_or(StatusCode::BAD_REQUEST);
+        (
+            status,
+            [(header::CONTENT_TYPE, "application/json")],
+            self.body(),
+        )
+            .into_response()
+    }
+}
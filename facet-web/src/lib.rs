@@ -0,0 +1,95 @@
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+use core::ops::{Deref, DerefMut};
+
+use facet_core::Facet;
+
+mod rejection;
+pub use rejection::*;
+
+#[cfg(feature = "axum")]
+mod axum;
+
+#[cfg(feature = "actix")]
+mod actix;
+
+/// A JSON request body or response, read and written with `facet-json`.
+///
+/// As an extractor, it rejects requests whose `Content-Type` isn't JSON, and bodies that
+/// don't deserialize into `T`, with a [`JsonRejection`]. As a response, it's `T` as JSON
+/// with a `200 OK` status.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FacetJson<T>(pub T);
+
+impl<T> FacetJson<T>
+where
+    T: for<'facet> Facet<'facet>,
+{
+    /// Deserializes a request body, checking its `Content-Type` first, like the
+    /// extractors do. This is the framework-independent part of them.
+    ///
+    /// ```
+    /// use facet::Facet;
+    /// use facet_web::{FacetJson, JsonRejection};
+    ///
+    /// #[derive(Facet, Debug, PartialEq)]
+    /// struct Login {
+    ///     user: String,
+    /// }
+    ///
+    /// let FacetJson(login) =
+    ///     FacetJson::<Login>::from_bytes(Some("application/json"), br#"{"user":"ada"}"#).unwrap();
+    /// assert_eq!(login.user, "ada");
+    ///
+    /// let err = FacetJson::<Login>::from_bytes(Some("application/json"), b"{}").unwrap_err();
+    /// assert_eq!(err.status(), 422);
+    /// ```
+    pub fn from_bytes(content_type: Option<&str>, body: &[u8]) -> Result<Self, JsonRejection> {
+        if !content_type.is_some_and(is_json_content_type) {
+            return Err(JsonRejection::UnsupportedMediaType);
+        }
+        facet_json::from_slice(body)
+            .map(FacetJson)
+            .map_err(|err| JsonRejection::Invalid(InvalidJson::new(&err)))
+    }
+}
+
+impl<'facet, T: Facet<'facet>> FacetJson<T> {
+    /// Serializes the value into a response body.
+    pub fn to_body(&self) -> String {
+        facet_json::to_string(&self.0)
+    }
+}
+
+/// Whether a `Content-Type` is `application/json`, or a JSON based type like
+/// `application/problem+json`. Parameters like `charset` are ignored.
+pub fn is_json_content_type(content_type: &str) -> bool {
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    let Some((ty, subtype)) = essence.split_once('/') else {
+        return false;
+    };
+    let subtype = subtype.to_ascii_lowercase();
+    ty.eq_ignore_ascii_case("application") && (subtype == "json" || subtype.ends_with("+json"))
+}
+
+impl<T> From<T> for FacetJson<T> {
+    fn from(value: T) -> Self {
+        FacetJson(value)
+    }
+}
+
+impl<T> Deref for FacetJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for FacetJson<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
@@ -0,0 +1,135 @@
+//! Why a request body was rejected, and the error responses for it.
+
+use facet_json::DeserError;
+
+/// Why a [`FacetJson`](crate::FacetJson) extractor rejected a request.
+///
+/// Its response has a JSON body like `{"error": "..."}`, which for
+/// [`JsonRejection::Invalid`] also says where in the request body the error is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JsonRejection {
+    /// The `Content-Type` is missing, or isn't JSON. It's a `415 Unsupported Media Type`.
+    UnsupportedMediaType,
+    /// The body couldn't be read, like when it's too large. It's a `400 Bad Request`.
+    BodyRead(String),
+    /// The body isn't JSON, or doesn't match the type. It's a `422 Unprocessable Entity`.
+    Invalid(InvalidJson),
+}
+
+/// Where and why a request body couldn't be deserialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidJson {
+    /// What went wrong, without terminal colors.
+    pub message: String,
+    /// The byte offset of the error in the body.
+    pub offset: usize,
+    /// The length of the error in the body, in bytes.
+    pub len: usize,
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error in characters, starting at 1.
+    pub column: usize,
+}
+
+impl InvalidJson {
+    /// Keeps what's needed for an error response from a `facet-json` error, which
+    /// borrows the request body.
+    pub fn new(err: &DeserError<'_, '_>) -> Self {
+        let input = &err.input[..];
+        let offset = err.span.start().min(input.len());
+        let line_start = input[..offset]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |newline| newline + 1);
+        let line = input[..line_start].iter().filter(|&&b| b == b'\n').count() + 1;
+        let column = String::from_utf8_lossy(&input[line_start..offset])
+            .chars()
+            .count()
+            + 1;
+
+        InvalidJson {
+            message: strip_ansi(&err.message().to_string()),
+            offset,
+            len: err.span.len(),
+            line,
+            column,
+        }
+    }
+}
+
+impl JsonRejection {
+    /// The HTTP status code of the response.
+    pub fn status(&self) -> u16 {
+        match self {
+            JsonRejection::UnsupportedMediaType => 415,
+            JsonRejection::BodyRead(_) => 400,
+            JsonRejection::Invalid(_) => 422,
+        }
+    }
+
+    /// The JSON body of the response.
+    ///
+    /// ```
+    /// use facet_web::{FacetJson, JsonRejection};
+    ///
+    /// let err = FacetJson::<u32>::from_bytes(Some("application/json"), b"\n  true").unwrap_err();
+    /// let JsonRejection::Invalid(invalid) = &err else { unreachable!() };
+    /// assert_eq!(invalid.line, 2);
+    /// assert!(err.body().starts_with(r#"{"error":"#));
+    /// ```
+    pub fn body(&self) -> String {
+        let error = facet_json::to_string(&self.to_string());
+        match self {
+            JsonRejection::Invalid(invalid) => format!(
+                r#"{{"error":{error},"offset":{},"len":{},"line":{},"column":{}}}"#,
+                invalid.offset, invalid.len, invalid.line, invalid.column
+            ),
+            _ => format!(r#"{{"error":{error}}}"#),
+        }
+    }
+}
+
+impl core::fmt::Display for JsonRejection {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            JsonRejection::UnsupportedMediaType => {
+                write!(
+                    f,
+                    "Expected a request with `Content-Type: application/json`"
+                )
+            }
+            JsonRejection::BodyRead(message) => {
+                write!(f, "Failed to read the request body: {message}")
+            }
+            JsonRejection::Invalid(invalid) => write!(
+                f,
+                "{} at line {} column {}",
+                invalid.message, invalid.line, invalid.column
+            ),
+        }
+    }
+}
+
+impl core::error::Error for JsonRejection {}
+
+/// Removes the terminal color codes `facet-deserialize` puts in its messages.
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        // Skips `ESC [`, any parameters, and the final letter
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "actix")]
+
+use actix_web::body::to_bytes;
+use actix_web::http::{StatusCode, header};
+use actix_web::test::TestRequest;
+use actix_web::{FromRequest, Responder, ResponseError};
+use facet::Facet;
+use facet_web::FacetJson;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Ping {
+    seq: u32,
+}
+
+#[tokio::test]
+async fn test_extract() {
+    let (req, mut payload) = TestRequest::post()
+        .insert_header((header::CONTENT_TYPE, "application/json"))
+        .set_payload(r#"{"seq": 7}"#)
+        .to_http_parts();
+    let FacetJson(ping) = FacetJson::<Ping>::from_request(&req, &mut payload)
+        .await
+        .unwrap();
+    assert_eq!(ping, Ping { seq: 7 });
+}
+
+#[tokio::test]
+async fn test_rejections() {
+    let (req, mut payload) = TestRequest::post()
+        .set_payload(r#"{"seq": 7}"#)
+        .to_http_parts();
+    let err = FacetJson::<Ping>::from_request(&req, &mut payload)
+        .await
+        .unwrap_err();
+    assert_eq!(err.status_code(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let (req, mut payload) = TestRequest::post()
+        .insert_header((header::CONTENT_TYPE, "application/json"))
+        .set_payload(r#"{"seq": "seven"}"#)
+        .to_http_parts();
+    let err = FacetJson::<Ping>::from_request(&req, &mut payload)
+        .await
+        .unwrap_err();
+    let response = err.error_response();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = to_bytes(response.into_body()).await.unwrap();
+    assert!(body.starts_with(br#"{"error":"#));
+}
+
+#[tokio::test]
+async fn test_respond() {
+    let req = TestRequest::default().to_http_request();
+    let response = FacetJson(Ping { seq: 8 }).respond_to(&req);
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body()).await.unwrap();
+    assert_eq!(&body[..], br#"{"seq":8}"#);
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "axum")]
+
+use axum::body::{Body, to_bytes};
+use axum::extract::FromRequest;
+use axum::http::{Request, StatusCode, header};
+use axum::response::IntoResponse;
+use facet::Facet;
+use facet_web::FacetJson;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Ping {
+    seq: u32,
+}
+
+fn request(content_type: &str, body: &'static str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .header(header::CONTENT_TYPE, content_type)
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_extract() {
+    let req = request("application/json", r#"{"seq": 7}"#);
+    let FacetJson(ping) = FacetJson::<Ping>::from_request(req, &()).await.unwrap();
+    assert_eq!(ping, Ping { seq: 7 });
+}
+
+#[tokio::test]
+async fn test_rejections() {
+    let req = request("text/plain", r#"{"seq": 7}"#);
+    let err = FacetJson::<Ping>::from_request(req, &()).await.unwrap_err();
+    assert_eq!(
+        err.into_response().status(),
+        StatusCode::UNSUPPORTED_MEDIA_TYPE
+    );
+
+    let req = request("application/json", r#"{"seq": "seven"}"#);
+    let err = FacetJson::<Ping>::from_request(req, &()).await.unwrap_err();
+    let response = err.into_response();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(body.starts_with(br#"{"error":"#));
+}
+
+#[tokio::test]
+async fn test_respond() {
+    let response = FacetJson(Ping { seq: 8 }).into_response();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[..], br#"{"seq":8}"#);
+}
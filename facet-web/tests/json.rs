@@ -0,0 +1,89 @@
+use facet::Facet;
+use facet_testhelpers::test;
+use facet_web::{FacetJson, JsonRejection, is_json_content_type};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Signup {
+    email: String,
+    age: u8,
+}
+
+#[test]
+fn test_content_types() {
+    assert!(is_json_content_type("application/json"));
+    assert!(is_json_content_type("Application/JSON; charset=utf-8"));
+    assert!(is_json_content_type("application/problem+json"));
+    assert!(!is_json_content_type("text/json"));
+    assert!(!is_json_content_type("application/jsonp"));
+    assert!(!is_json_content_type("application/x-www-form-urlencoded"));
+}
+
+#[test]
+fn test_from_bytes() {
+    let body = br#"{"email": "ada@example.com", "age": 36}"#;
+    let FacetJson(signup) =
+        FacetJson::<Signup>::from_bytes(Some("application/json"), body).unwrap();
+    assert_eq!(
+        signup,
+        Signup {
+            email: "ada@example.com".to_string(),
+            age: 36,
+        }
+    );
+
+    assert_eq!(
+        FacetJson::<Signup>::from_bytes(None, body),
+        Err(JsonRejection::UnsupportedMediaType)
+    );
+    assert_eq!(
+        FacetJson::<Signup>::from_bytes(Some("text/plain"), body),
+        Err(JsonRejection::UnsupportedMediaType)
+    );
+}
+
+#[test]
+fn test_invalid_body() {
+    let body = b"{\n  \"email\": \"ada@example.com\",\n  \"age\": 300\n}";
+    let err = FacetJson::<Signup>::from_bytes(Some("application/json"), body).unwrap_err();
+    assert_eq!(err.status(), 422);
+
+    let JsonRejection::Invalid(invalid) = &err else {
+        panic!("expected an invalid body, got {err:?}");
+    };
+    assert_eq!(invalid.line, 3);
+    assert_eq!(&body[invalid.offset..invalid.offset + 3], b"300");
+    assert!(!invalid.message.contains('\x1b'));
+
+    let expected = format!(
+        r#""offset":{},"len":{},"line":3,"column":{}}}"#,
+        invalid.offset, invalid.len, invalid.column
+    );
+    assert!(err.body().starts_with(r#"{"error":""#));
+    assert!(err.body().ends_with(&expected));
+}
+
+#[test]
+fn test_rejection_bodies() {
+    let err = JsonRejection::UnsupportedMediaType;
+    assert_eq!(err.status(), 415);
+    assert_eq!(
+        err.body(),
+        r#"{"error":"Expected a request with `Content-Type: application/json`"}"#
+    );
+
+    let err = JsonRejection::BodyRead("too \"large\"".to_string());
+    assert_eq!(err.status(), 400);
+    assert_eq!(
+        err.body(),
+        r#"{"error":"Failed to read the request body: too \"large\""}"#
+    );
+}
+
+#[test]
+fn test_to_body() {
+    let signup = FacetJson(Signup {
+        email: "ada@example.com".to_string(),
+        age: 36,
+    });
+    assert_eq!(signup.to_body(), r#"{"email":"ada@example.com","age":36}"#);
+}
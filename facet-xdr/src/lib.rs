@@ -2,13 +2,17 @@
 #![forbid(unsafe_code)]
 #![doc = include_str!("../README.md")]
 
+use std::borrow::Cow;
 use std::io::Write;
+use std::rc::Rc;
+use std::sync::Arc;
 
 use facet_core::{
-    Def, Facet, IntegerSize, NumberBits, ScalarAffinity, Signedness, StructKind, Type, UserType,
+    Def, Facet, IntegerSize, NumberBits, ScalarAffinity, Shape, Signedness, StructKind, Type,
+    UserType,
 };
-use facet_reflect::{HeapValue, Partial, Peek};
-use facet_serialize::{Serializer, serialize_iterative};
+use facet_reflect::{HeapValue, Partial, Peek, ReflectError};
+use facet_serialize::{Serializer, pointer_cycle_message, serialize_iterative};
 
 /// Errors when serializing to XDR bytes
 #[derive(Debug)]
@@ -21,6 +25,8 @@ pub enum XdrSerError {
     TooManyVariants,
     /// Unsupported type
     UnsupportedType,
+    /// An `Rc` or `Arc` points back at a value that contains it
+    PointerCycle(String),
 }
 
 impl core::fmt::Display for XdrSerError {
@@ -30,6 +36,7 @@ impl core::fmt::Display for XdrSerError {
             XdrSerError::TooManyBytes => write!(f, "Too many bytes for field"),
             XdrSerError::TooManyVariants => write!(f, "Enum variant discriminant too large"),
             XdrSerError::UnsupportedType => write!(f, "Unsupported type"),
+            XdrSerError::PointerCycle(message) => f.write_str(message),
         }
     }
 }
@@ -43,6 +50,17 @@ impl core::error::Error for XdrSerError {
     }
 }
 
+impl From<XdrSerError> for facet_serialize::SerializeError {
+    fn from(error: XdrSerError) -> Self {
+        match error {
+            #[cfg(feature = "std")]
+            XdrSerError::Io(error) => error.into(),
+            XdrSerError::UnsupportedType => Self::unsupported_shape("this type"),
+            error => Self::custom(error),
+        }
+    }
+}
+
 /// Serialize any Facet type to XDR bytes
 pub fn to_vec<'f, F: Facet<'f>>(value: &'f F) -> Result<Vec<u8>, XdrSerError> {
     let mut buffer = Vec::new();
@@ -61,6 +79,23 @@ struct XdrSerializer<'w, W: Write> {
 impl<'shape, W: Write> Serializer<'shape> for XdrSerializer<'_, W> {
     type Error = XdrSerError;
 
+    // XDR has no integers narrower than 32 bits, and the deserializer reads them as such
+    fn serialize_u8(&mut self, value: u8) -> Result<(), Self::Error> {
+        self.serialize_u32(value as u32)
+    }
+
+    fn serialize_u16(&mut self, value: u16) -> Result<(), Self::Error> {
+        self.serialize_u32(value as u32)
+    }
+
+    fn serialize_i8(&mut self, value: i8) -> Result<(), Self::Error> {
+        self.serialize_i32(value as i32)
+    }
+
+    fn serialize_i16(&mut self, value: i16) -> Result<(), Self::Error> {
+        self.serialize_i32(value as i32)
+    }
+
     fn serialize_u32(&mut self, value: u32) -> Result<(), Self::Error> {
         self.writer
             .write_all(&value.to_be_bytes())
@@ -73,8 +108,11 @@ impl<'shape, W: Write> Serializer<'shape> for XdrSerializer<'_, W> {
             .map_err(Self::Error::Io)
     }
 
-    fn serialize_u128(&mut self, _value: u128) -> Result<(), Self::Error> {
-        Err(Self::Error::UnsupportedType)
+    // Two hypers, most significant first
+    fn serialize_u128(&mut self, value: u128) -> Result<(), Self::Error> {
+        self.writer
+            .write_all(&value.to_be_bytes())
+            .map_err(Self::Error::Io)
     }
 
     fn serialize_i32(&mut self, value: i32) -> Result<(), Self::Error> {
@@ -89,8 +127,10 @@ impl<'shape, W: Write> Serializer<'shape> for XdrSerializer<'_, W> {
             .map_err(Self::Error::Io)
     }
 
-    fn serialize_i128(&mut self, _value: i128) -> Result<(), Self::Error> {
-        Err(Self::Error::UnsupportedType)
+    fn serialize_i128(&mut self, value: i128) -> Result<(), Self::Error> {
+        self.writer
+            .write_all(&value.to_be_bytes())
+            .map_err(Self::Error::Io)
     }
 
     fn serialize_f32(&mut self, value: f32) -> Result<(), Self::Error> {
@@ -144,6 +184,10 @@ impl<'shape, W: Write> Serializer<'shape> for XdrSerializer<'_, W> {
         Ok(())
     }
 
+    fn serialize_pointer_cycle(&mut self, shape: &'shape Shape<'shape>) -> Result<(), Self::Error> {
+        Err(XdrSerError::PointerCycle(pointer_cycle_message(shape)))
+    }
+
     fn serialize_unit(&mut self) -> Result<(), Self::Error> {
         Ok(())
     }
@@ -156,6 +200,13 @@ impl<'shape, W: Write> Serializer<'shape> for XdrSerializer<'_, W> {
         Ok(())
     }
 
+    fn serialize_unit_variant_discriminant(
+        &mut self,
+        _discriminant: i64,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
         Ok(())
     }
@@ -214,6 +265,22 @@ pub enum XdrDeserError {
     InvalidVariant {
         /// Position of this error in bytes
         position: usize,
+        /// The discriminant that was read
+        discriminant: u32,
+        /// The discriminants the enum has, like `0 (Red), 1 (Green)`
+        expected: String,
+    },
+    /// Invalid character
+    InvalidChar {
+        /// Position of this error in bytes
+        position: usize,
+    },
+    /// The input doesn't fit the type being deserialized
+    InvalidValue {
+        /// Position of this error in bytes
+        position: usize,
+        /// Why the value was rejected
+        message: String,
     },
     /// Invalid string
     InvalidString {
@@ -238,8 +305,22 @@ impl core::fmt::Display for XdrDeserError {
             XdrDeserError::InvalidOptional { position } => {
                 write!(f, "Invalid discriminant for optional at byte {}", position)
             }
-            XdrDeserError::InvalidVariant { position } => {
-                write!(f, "Invalid enum discriminant at byte {}", position)
+            XdrDeserError::InvalidVariant {
+                position,
+                discriminant,
+                expected,
+            } => {
+                write!(
+                    f,
+                    "Invalid enum discriminant {} at byte {}, expected one of: {}",
+                    discriminant, position, expected
+                )
+            }
+            XdrDeserError::InvalidChar { position } => {
+                write!(f, "Invalid character at byte {}", position)
+            }
+            XdrDeserError::InvalidValue { position, message } => {
+                write!(f, "Invalid value at byte {}: {}", position, message)
             }
             XdrDeserError::InvalidString { position, .. } => {
                 write!(f, "Invalid string at byte {}", position)
@@ -268,7 +349,8 @@ enum PopReason {
 enum DeserializeTask {
     Value,
     Field(usize),
-    ListItem,
+    /// The given number of list items are left to read
+    ListItems(u32),
     Pop(PopReason),
 }
 
@@ -299,78 +381,123 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
         Ok(u64::from_be_bytes(bytes.try_into().unwrap()))
     }
 
+    fn next_u128(&mut self) -> Result<u128, XdrDeserError> {
+        let high = self.next_u64()? as u128;
+        let low = self.next_u64()? as u128;
+        Ok(high << 64 | low)
+    }
+
     fn next_data(&mut self, expected_len: Option<u32>) -> Result<&'input [u8], XdrDeserError> {
         let len = self.next_u32()? as usize;
         if let Some(expected_len) = expected_len {
             assert_eq!(len, expected_len as usize);
         }
-        self.pos += len;
-        let pad_len = len % 4;
-        let data = &self.input[self.pos - len..self.pos];
-        if pad_len != 0 {
-            self.pos += 4 - pad_len;
+        self.next_fixed_data(len)
+    }
+
+    /// Reads `len` bytes of opaque data, and the padding after them.
+    fn next_fixed_data(&mut self, len: usize) -> Result<&'input [u8], XdrDeserError> {
+        let padded_len = len
+            .checked_next_multiple_of(4)
+            .ok_or(XdrDeserError::UnexpectedEof)?;
+        if self.input.len() - self.pos < padded_len {
+            return Err(XdrDeserError::UnexpectedEof);
         }
+        let data = &self.input[self.pos..self.pos + len];
+        self.pos += padded_len;
         Ok(data)
     }
 
+    fn invalid_value(&self, error: ReflectError<'_>) -> XdrDeserError {
+        XdrDeserError::InvalidValue {
+            position: self.pos,
+            message: error.to_string(),
+        }
+    }
+
     fn next<'f>(
         &mut self,
         mut wip: Partial<'f, 'shape>,
-    ) -> Result<Partial<'f, 'shape>, XdrDeserError> {
+    ) -> Result<Partial<'f, 'shape>, XdrDeserError>
+    where
+        'input: 'f,
+    {
+        // Opaque data is borrowed from the input when the target type allows it
+        if wip.shape().is_type::<&[u8]>() {
+            let data = self.next_data(None)?;
+            wip.set(data).map_err(|e| self.invalid_value(e))?;
+            return Ok(wip);
+        } else if wip.shape().is_type::<Cow<'_, [u8]>>() {
+            let data = self.next_data(None)?;
+            wip.set(Cow::Borrowed(data))
+                .map_err(|e| self.invalid_value(e))?;
+            return Ok(wip);
+        }
+
         match (wip.shape().def, wip.shape().ty) {
             (Def::Scalar(sd), _) => match sd.affinity {
                 ScalarAffinity::Number(na) => match na.bits {
                     NumberBits::Integer { size, sign } => match (size, sign) {
                         (IntegerSize::Fixed(8), Signedness::Unsigned) => {
                             let value = self.next_u32()? as u8;
-                            wip.set(value).unwrap();
+                            wip.set(value).map_err(|e| self.invalid_value(e))?;
                             Ok(wip)
                         }
                         (IntegerSize::Fixed(16), Signedness::Unsigned) => {
                             let value = self.next_u32()? as u16;
-                            wip.set(value).unwrap();
+                            wip.set(value).map_err(|e| self.invalid_value(e))?;
                             Ok(wip)
                         }
                         (IntegerSize::Fixed(32), Signedness::Unsigned) => {
                             let value = self.next_u32()?;
-                            wip.set(value).unwrap();
+                            wip.set(value).map_err(|e| self.invalid_value(e))?;
                             Ok(wip)
                         }
                         (IntegerSize::Fixed(64), Signedness::Unsigned) => {
                             let value = self.next_u64()?;
-                            wip.set(value).unwrap();
+                            wip.set(value).map_err(|e| self.invalid_value(e))?;
+                            Ok(wip)
+                        }
+                        (IntegerSize::Fixed(128), Signedness::Unsigned) => {
+                            let value = self.next_u128()?;
+                            wip.set(value).map_err(|e| self.invalid_value(e))?;
                             Ok(wip)
                         }
                         (IntegerSize::Fixed(8), Signedness::Signed) => {
                             let value = self.next_u32()? as i8;
-                            wip.set(value).unwrap();
+                            wip.set(value).map_err(|e| self.invalid_value(e))?;
                             Ok(wip)
                         }
                         (IntegerSize::Fixed(16), Signedness::Signed) => {
                             let value = self.next_u32()? as i16;
-                            wip.set(value).unwrap();
+                            wip.set(value).map_err(|e| self.invalid_value(e))?;
                             Ok(wip)
                         }
                         (IntegerSize::Fixed(32), Signedness::Signed) => {
                             let value = self.next_u32()? as i32;
-                            wip.set(value).unwrap();
+                            wip.set(value).map_err(|e| self.invalid_value(e))?;
                             Ok(wip)
                         }
                         (IntegerSize::Fixed(64), Signedness::Signed) => {
                             let value = self.next_u64()? as i64;
-                            wip.set(value).unwrap();
+                            wip.set(value).map_err(|e| self.invalid_value(e))?;
+                            Ok(wip)
+                        }
+                        (IntegerSize::Fixed(128), Signedness::Signed) => {
+                            let value = self.next_u128()? as i128;
+                            wip.set(value).map_err(|e| self.invalid_value(e))?;
                             Ok(wip)
                         }
                         (IntegerSize::PointerSized, Signedness::Unsigned) => {
                             // Handle usize - use 64-bit on most platforms
                             let value = self.next_u64()? as usize;
-                            wip.set(value).unwrap();
+                            wip.set(value).map_err(|e| self.invalid_value(e))?;
                             Ok(wip)
                         }
                         (IntegerSize::PointerSized, Signedness::Signed) => {
                             // Handle isize - use 64-bit on most platforms
                             let value = self.next_u64()? as isize;
-                            wip.set(value).unwrap();
+                            wip.set(value).map_err(|e| self.invalid_value(e))?;
                             Ok(wip)
                         }
                         _ => Err(XdrDeserError::UnsupportedNumericType),
@@ -385,12 +512,12 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
                         if bits == 32 {
                             let bits = self.next_u32()?;
                             let float = f32::from_bits(bits);
-                            wip.set(float).unwrap();
+                            wip.set(float).map_err(|e| self.invalid_value(e))?;
                             Ok(wip)
                         } else if bits == 64 {
                             let bits = self.next_u64()?;
                             let float = f64::from_bits(bits);
-                            wip.set(float).unwrap();
+                            wip.set(float).map_err(|e| self.invalid_value(e))?;
                             Ok(wip)
                         } else {
                             Err(XdrDeserError::UnsupportedNumericType)
@@ -405,16 +532,25 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
                             source: e,
                         }
                     })?;
-                    wip.set(string.to_owned()).unwrap();
+                    if wip.shape().is_type::<Arc<str>>()
+                        || wip.shape().is_type::<Rc<str>>()
+                        || wip.shape().is_type::<Box<str>>()
+                    {
+                        wip.parse_from_str(string)
+                            .map_err(|e| self.invalid_value(e))?;
+                    } else {
+                        wip.set(string.to_owned())
+                            .map_err(|e| self.invalid_value(e))?;
+                    }
                     Ok(wip)
                 }
                 ScalarAffinity::Boolean(_) => match self.next_u32()? {
                     0 => {
-                        wip.set(false).unwrap();
+                        wip.set(false).map_err(|e| self.invalid_value(e))?;
                         Ok(wip)
                     }
                     1 => {
-                        wip.set(true).unwrap();
+                        wip.set(true).map_err(|e| self.invalid_value(e))?;
                         Ok(wip)
                     }
                     _ => Err(XdrDeserError::InvalidBoolean {
@@ -423,7 +559,10 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
                 },
                 ScalarAffinity::Char(_) => {
                     let value = self.next_u32()?;
-                    wip.set(char::from_u32(value).unwrap()).unwrap();
+                    let value = char::from_u32(value).ok_or(XdrDeserError::InvalidChar {
+                        position: self.pos - 4,
+                    })?;
+                    wip.set(value).map_err(|e| self.invalid_value(e))?;
                     Ok(wip)
                 }
                 _ => Err(XdrDeserError::UnsupportedType),
@@ -431,64 +570,49 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
             (Def::List(ld), _) => {
                 if ld.t().is_type::<u8>() {
                     let data = self.next_data(None)?;
-                    wip.set(data.to_vec()).unwrap();
+                    wip.set(data.to_vec()).map_err(|e| self.invalid_value(e))?;
                     Ok(wip)
                 } else {
                     let len = self.next_u32()?;
-                    wip.begin_list().unwrap();
-                    if len == 0 {
-                        Ok(wip)
-                    } else {
-                        for _ in 0..len {
-                            self.stack.push(DeserializeTask::ListItem);
-                        }
-                        Ok(wip)
-                    }
+                    wip.begin_list().map_err(|e| self.invalid_value(e))?;
+                    self.stack.push(DeserializeTask::ListItems(len));
+                    Ok(wip)
                 }
             }
             (Def::Array(ad), _) => {
                 let len = ad.n;
                 if ad.t().is_type::<u8>() {
-                    self.pos += len;
-                    let pad_len = len % 4;
-                    for byte in &self.input[self.pos - len..self.pos] {
-                        wip.begin_list_item().unwrap();
-                        wip.set(*byte).unwrap();
-                        wip.end().unwrap();
-                    }
-                    if pad_len != 0 {
-                        self.pos += 4 - pad_len;
+                    for byte in self.next_fixed_data(len)? {
+                        wip.begin_list_item().map_err(|e| self.invalid_value(e))?;
+                        wip.set(*byte).map_err(|e| self.invalid_value(e))?;
+                        wip.end().map_err(|e| self.invalid_value(e))?;
                     }
                     Ok(wip)
                 } else {
-                    for _ in 0..len {
-                        self.stack.push(DeserializeTask::ListItem);
-                    }
+                    self.stack.push(DeserializeTask::ListItems(len as u32));
                     Ok(wip)
                 }
             }
             (Def::Slice(sd), _) => {
                 if sd.t().is_type::<u8>() {
                     let data = self.next_data(None)?;
-                    wip.set(data.to_vec()).unwrap();
+                    wip.set(data.to_vec()).map_err(|e| self.invalid_value(e))?;
                     Ok(wip)
                 } else {
                     let len = self.next_u32()?;
-                    for _ in 0..len {
-                        self.stack.push(DeserializeTask::ListItem);
-                    }
+                    self.stack.push(DeserializeTask::ListItems(len));
                     Ok(wip)
                 }
             }
             (Def::Option(_), _) => match self.next_u32()? {
                 0 => {
-                    wip.set_default().unwrap();
+                    wip.set_default().map_err(|e| self.invalid_value(e))?;
                     Ok(wip)
                 }
                 1 => {
                     self.stack.push(DeserializeTask::Pop(PopReason::Some));
                     self.stack.push(DeserializeTask::Value);
-                    wip.select_variant(1).unwrap();
+                    wip.select_variant(1).map_err(|e| self.invalid_value(e))?;
                     Ok(wip)
                 }
                 _ => Err(XdrDeserError::InvalidOptional {
@@ -499,14 +623,13 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
                 UserType::Struct(st) => {
                     if st.kind == StructKind::Tuple {
                         // Handle tuple structs
-                        for _field in st.fields.iter() {
-                            self.stack.push(DeserializeTask::ListItem);
-                        }
+                        self.stack
+                            .push(DeserializeTask::ListItems(st.fields.len() as u32));
                         Ok(wip)
                     } else {
                         // Handle regular structs
                         for (index, _field) in st.fields.iter().enumerate().rev() {
-                            if !wip.is_field_set(index).unwrap() {
+                            if !wip.is_field_set(index).map_err(|e| self.invalid_value(e))? {
                                 self.stack.push(DeserializeTask::Field(index));
                             }
                         }
@@ -515,21 +638,28 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
                 }
                 UserType::Enum(et) => {
                     let discriminant = self.next_u32()?;
-                    if let Some(variant) = et
-                        .variants
-                        .iter()
-                        .find(|v| v.discriminant == Some(discriminant as i64))
-                        .or(et.variants.get(discriminant as usize))
-                    {
-                        for (index, _field) in variant.data.fields.iter().enumerate().rev() {
-                            self.stack.push(DeserializeTask::Field(index));
-                        }
-                        wip.select_variant(discriminant as i64).unwrap();
-                        Ok(wip)
+                    // Fall back on the variant's index when the variants have no discriminants
+                    let variant_index = if et.variants.iter().any(|v| v.discriminant.is_some()) {
+                        et.variant_index_by_discriminant(discriminant as i64)
                     } else {
-                        Err(XdrDeserError::InvalidVariant {
+                        Some(discriminant as usize)
+                    };
+                    let variant =
+                        variant_index.and_then(|index| Some((index, et.variants.get(index)?)));
+                    match variant {
+                        Some((variant_index, variant)) => {
+                            for (index, _field) in variant.data.fields.iter().enumerate().rev() {
+                                self.stack.push(DeserializeTask::Field(index));
+                            }
+                            wip.select_nth_variant(variant_index)
+                                .map_err(|e| self.invalid_value(e))?;
+                            Ok(wip)
+                        }
+                        None => Err(XdrDeserError::InvalidVariant {
                             position: self.pos - 4,
-                        })
+                            discriminant,
+                            expected: et.discriminant_list().to_string(),
+                        }),
                     }
                 }
                 _ => Err(XdrDeserError::UnsupportedType),
@@ -540,10 +670,13 @@ impl<'shape, 'input> XdrDeserializerStack<'input> {
 }
 
 /// Deserialize an XDR slice given some some [`Partial`] into a [`HeapValue`]
-pub fn deserialize_wip<'facet, 'shape>(
-    input: &[u8],
+pub fn deserialize_wip<'input, 'facet, 'shape>(
+    input: &'input [u8],
     mut wip: Partial<'facet, 'shape>,
-) -> Result<HeapValue<'facet, 'shape>, XdrDeserError> {
+) -> Result<HeapValue<'facet, 'shape>, XdrDeserError>
+where
+    'input: 'facet,
+{
     let mut runner = XdrDeserializerStack {
         input,
         pos: 0,
@@ -560,9 +693,9 @@ pub fn deserialize_wip<'facet, 'shape>(
         match runner.stack.pop() {
             Some(DeserializeTask::Pop(reason)) => {
                 if reason == PopReason::TopLevel {
-                    return Ok(wip.build().unwrap());
+                    return wip.build().map_err(|e| runner.invalid_value(e));
                 } else {
-                    wip.end().unwrap();
+                    wip.end().map_err(|e| runner.invalid_value(e))?;
                 }
             }
             Some(DeserializeTask::Value) => {
@@ -573,14 +706,19 @@ pub fn deserialize_wip<'facet, 'shape>(
                     .stack
                     .push(DeserializeTask::Pop(PopReason::ObjectOrListVal));
                 runner.stack.push(DeserializeTask::Value);
-                wip.begin_nth_field(index).unwrap();
+                wip.begin_nth_field(index)
+                    .map_err(|e| runner.invalid_value(e))?;
             }
-            Some(DeserializeTask::ListItem) => {
+            Some(DeserializeTask::ListItems(0)) => {}
+            Some(DeserializeTask::ListItems(left)) => {
+                // One task stands for all the items, so that a huge length can't make the stack
+                // huge before the input runs out
+                runner.stack.push(DeserializeTask::ListItems(left - 1));
                 runner
                     .stack
                     .push(DeserializeTask::Pop(PopReason::ObjectOrListVal));
                 runner.stack.push(DeserializeTask::Value);
-                wip.begin_list_item().unwrap();
+                wip.begin_list_item().map_err(|e| runner.invalid_value(e))?;
             }
             None => unreachable!("Instruction stack is empty"),
         }
@@ -588,8 +726,24 @@ pub fn deserialize_wip<'facet, 'shape>(
 }
 
 /// Deserialize a slice of XDR bytes into any Facet type
-pub fn deserialize<'f, F: facet_core::Facet<'f>>(input: &[u8]) -> Result<F, XdrDeserError> {
-    let v = deserialize_wip(input, Partial::alloc_shape(F::SHAPE).unwrap())?;
-    let f: F = v.materialize().unwrap();
-    Ok(f)
+///
+/// `&[u8]` and `Cow<[u8]>` fields borrow variable-length opaque data from `input` instead of
+/// copying it.
+/// They can't outlive `input`:
+///
+/// ```compile_fail
+/// let bytes: &[u8] = {
+///     let input = vec![0, 0, 0, 1, 42, 0, 0, 0];
+///     facet_xdr::deserialize(&input).unwrap()
+/// };
+/// ```
+pub fn deserialize<'input, 'f, F: facet_core::Facet<'f>>(
+    input: &'input [u8],
+) -> Result<F, XdrDeserError>
+where
+    'input: 'f,
+{
+    let wip = Partial::alloc_shape(F::SHAPE).map_err(|_| XdrDeserError::UnsupportedType)?;
+    let v = deserialize_wip(input, wip)?;
+    v.materialize().map_err(|_| XdrDeserError::UnsupportedType)
 }
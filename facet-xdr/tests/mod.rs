@@ -47,3 +47,150 @@ fn test_deserialize_file_example() {
     let file: File = deserialize(&FILE_EXAMPLE_BYTES)?;
     assert_eq!(file, file_example());
 }
+
+#[derive(Debug, Facet, PartialEq)]
+struct BorrowedFile<'a> {
+    filename: String,
+    filetype: FileType,
+    owner: String,
+    data: &'a [u8],
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct CowFile<'a> {
+    filename: String,
+    filetype: FileType,
+    owner: String,
+    data: std::borrow::Cow<'a, [u8]>,
+}
+
+#[test]
+fn test_deserialize_borrowed_opaque() {
+    let bytes = FILE_EXAMPLE_BYTES;
+
+    let file: BorrowedFile = deserialize(&bytes)?;
+    assert_eq!(file.data, b"(quit)");
+    assert!(core::ptr::eq(file.data.as_ptr(), &bytes[40]));
+    assert_eq!(&to_vec(&file)?[..], FILE_EXAMPLE_BYTES);
+
+    let file: CowFile = deserialize(&bytes)?;
+    assert!(matches!(file.data, std::borrow::Cow::Borrowed(b"(quit)")));
+    assert_eq!(&to_vec(&file)?[..], FILE_EXAMPLE_BYTES);
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Wide {
+    unsigned: u128,
+    signed: i128,
+}
+
+#[test]
+fn test_128_bit_integers_as_two_hypers() {
+    let wide = Wide {
+        unsigned: u128::MAX - 1,
+        signed: -2,
+    };
+    let bytes = to_vec(&wide)?;
+    assert_eq!(
+        bytes,
+        [
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xfe, //
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xfe,
+        ]
+    );
+    assert_eq!(deserialize::<Wide>(&bytes)?, wide);
+
+    let wide = Wide {
+        unsigned: 1 << 64,
+        signed: i128::MIN,
+    };
+    assert_eq!(deserialize::<Wide>(&to_vec(&wide)?)?, wide);
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Narrow {
+    byte: u8,
+    short: u16,
+    signed_byte: i8,
+    signed_short: i16,
+}
+
+#[test]
+fn test_narrow_integers_as_ints() {
+    let narrow = Narrow {
+        byte: 200,
+        short: 640,
+        signed_byte: -5,
+        signed_short: -1000,
+    };
+    let bytes = to_vec(&narrow)?;
+    assert_eq!(
+        bytes,
+        [
+            0x00, 0x00, 0x00, 0xc8, //
+            0x00, 0x00, 0x02, 0x80, //
+            0xff, 0xff, 0xff, 0xfb, //
+            0xff, 0xff, 0xfc, 0x18,
+        ]
+    );
+    assert_eq!(deserialize::<Narrow>(&bytes)?, narrow);
+}
+
+#[test]
+fn test_malformed_input_is_an_error() {
+    use facet_xdr::XdrDeserError;
+
+    // Every truncation of a valid file, including in the middle of the opaque data or its padding
+    for len in 0..FILE_EXAMPLE_BYTES.len() {
+        let result = deserialize::<File>(&FILE_EXAMPLE_BYTES[..len]);
+        assert!(matches!(result, Err(XdrDeserError::UnexpectedEof)), "{len}");
+    }
+
+    // A list that claims more items than there are bytes
+    let result = deserialize::<Vec<u32>>(&[0xff, 0xff, 0xff, 0xff, 0, 0, 0, 1]);
+    assert!(matches!(result, Err(XdrDeserError::UnexpectedEof)));
+
+    // A surrogate isn't a `char`
+    let result = deserialize::<char>(&[0, 0, 0xd8, 0]);
+    assert!(matches!(
+        result,
+        Err(XdrDeserError::InvalidChar { position: 0 })
+    ));
+
+    // Past the last variant
+    let result = deserialize::<FileType>(&[0, 0, 0, 3]);
+    assert!(matches!(
+        result,
+        Err(XdrDeserError::InvalidVariant {
+            position: 0,
+            discriminant: 3,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_enum_with_explicit_discriminants() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[repr(u32)]
+    enum Level {
+        Low = 1,
+        Medium = 5,
+        High = 10,
+    }
+
+    for (level, discriminant) in [(Level::Low, 1), (Level::Medium, 5), (Level::High, 10)] {
+        let bytes = to_vec(&level).unwrap();
+        assert_eq!(bytes, [0, 0, 0, discriminant]);
+        assert_eq!(deserialize::<Level>(&bytes).unwrap(), level);
+    }
+
+    // An index isn't a discriminant
+    let err = deserialize::<Level>(&[0, 0, 0, 2]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid enum discriminant 2 at byte 0, expected one of: 1 (Low), 5 (Medium), 10 (High)"
+    );
+}
@@ -8,21 +8,129 @@ mod error;
 use alloc::{
     format,
     string::{String, ToString},
+    vec::Vec,
 };
 use error::AnyErr;
 use facet_core::{Characteristic, Def, Facet, FieldFlags, Type, UserType};
 use facet_reflect::Partial;
-use yaml_rust2::{Yaml, YamlLoader};
+use yaml_rust2::{
+    Event, Yaml, YamlLoader,
+    parser::{MarkedEventReceiver, Parser},
+    scanner::{Marker, TScalarStyle},
+};
 
 /// Deserializes a YAML string into a value of type `T` that implements `Facet`.
 pub fn from_str<'input: 'facet, 'facet, T: Facet<'facet>>(yaml: &'input str) -> Result<T, AnyErr> {
-    let mut typed_partial = Partial::alloc::<T>()?;
-    {
-        let wip = typed_partial.inner_mut();
-        from_str_value(wip, yaml)?;
+    YamlParser::new().parse(yaml)
+}
+
+/// Parses YAML documents with options.
+#[derive(Default)]
+pub struct YamlParser {
+    yaml_1_1: bool,
+}
+
+impl YamlParser {
+    /// Creates a parser that reads YAML 1.2, like [`from_str`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads `yes`, `no`, `on` and `off` as booleans and numbers like `0755` as octal, the
+    /// way YAML 1.1 does, for documents written for older parsers.
+    ///
+    /// Map keys are left as they're written, so a field named `on` can still be read.
+    pub fn with_yaml_1_1(mut self, yaml_1_1: bool) -> Self {
+        self.yaml_1_1 = yaml_1_1;
+        self
+    }
+
+    /// Deserializes a YAML string into a value of type `T` that implements `Facet`.
+    pub fn parse<'input: 'facet, 'facet, T: Facet<'facet>>(
+        &self,
+        yaml: &'input str,
+    ) -> Result<T, AnyErr> {
+        let mut typed_partial = Partial::alloc::<T>()?;
+        {
+            let wip = typed_partial.inner_mut();
+            load_value(wip, yaml, self.yaml_1_1)?;
+        }
+        let boxed_value = typed_partial.build().map_err(|e| AnyErr(e.to_string()))?;
+        Ok(*boxed_value)
+    }
+}
+
+/// Deserializes a YAML string into `wip`, for when the type is only known at runtime.
+pub fn from_str_value<'facet, 'shape>(
+    yaml: &str,
+    wip: &mut Partial<'facet, 'shape>,
+) -> Result<(), AnyErr> {
+    load_value(wip, yaml, false)
+}
+
+/// Loads documents like [`YamlLoader`], reading plain scalars that aren't map keys like
+/// YAML 1.1 does.
+#[derive(Default)]
+struct Yaml11Loader {
+    loader: YamlLoader,
+    /// For each open map, whether its next node is a key, and `None` for each open sequence
+    expecting_key: Vec<Option<bool>>,
+}
+
+impl MarkedEventReceiver for Yaml11Loader {
+    fn on_event(&mut self, ev: Event, mark: Marker) {
+        let is_key = self.expecting_key.last() == Some(&Some(true));
+        if let Event::Scalar(..)
+        | Event::Alias(_)
+        | Event::MappingStart(..)
+        | Event::SequenceStart(..) = ev
+        {
+            // A node fills the next key or value of the map it's in
+            if let Some(Some(expecting_key)) = self.expecting_key.last_mut() {
+                *expecting_key = !*expecting_key;
+            }
+        }
+        let ev = match ev {
+            Event::MappingStart(..) => {
+                self.expecting_key.push(Some(true));
+                ev
+            }
+            Event::SequenceStart(..) => {
+                self.expecting_key.push(None);
+                ev
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.expecting_key.pop();
+                ev
+            }
+            Event::Scalar(value, TScalarStyle::Plain, anchor, None) if !is_key => {
+                Event::Scalar(yaml_1_1_scalar(value), TScalarStyle::Plain, anchor, None)
+            }
+            ev => ev,
+        };
+        self.loader.on_event(ev, mark);
+    }
+}
+
+/// Rewrites a plain scalar that YAML 1.1 reads differently to how YAML 1.2 writes it.
+fn yaml_1_1_scalar(value: String) -> String {
+    match value.as_str() {
+        "yes" | "Yes" | "YES" | "on" | "On" | "ON" => return "true".to_string(),
+        "no" | "No" | "NO" | "off" | "Off" | "OFF" => return "false".to_string(),
+        _ => {}
+    }
+    let (sign, digits) = match value.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", value.strip_prefix('+').unwrap_or(&value)),
+    };
+    let octal = digits
+        .strip_prefix('0')
+        .filter(|octal| !octal.is_empty())
+        .and_then(|octal| i64::from_str_radix(octal, 8).ok());
+    match octal {
+        Some(octal) => format!("{sign}{octal}"),
+        None => value,
     }
-    let boxed_value = typed_partial.build().map_err(|e| AnyErr(e.to_string()))?;
-    Ok(*boxed_value)
 }
 
 fn yaml_type(ty: &Yaml) -> &'static str {
@@ -53,11 +161,20 @@ fn yaml_to_u64(ty: &Yaml) -> Result<u64, AnyErr> {
     }
 }
 
-fn from_str_value<'facet, 'shape>(
+fn load_value<'facet, 'shape>(
     wip: &mut Partial<'facet, 'shape>,
     yaml: &str,
+    yaml_1_1: bool,
 ) -> Result<(), AnyErr> {
-    let docs = YamlLoader::load_from_str(yaml).map_err(|e| e.to_string())?;
+    let docs = if yaml_1_1 {
+        let mut loader = Yaml11Loader::default();
+        Parser::new_from_str(yaml)
+            .load(&mut loader, true)
+            .map_err(|e| e.to_string())?;
+        loader.loader.documents().to_vec()
+    } else {
+        YamlLoader::load_from_str(yaml).map_err(|e| e.to_string())?
+    };
     if docs.len() != 1 {
         return Err("Expected exactly one YAML document".into());
     }
@@ -100,6 +217,11 @@ fn deserialize_value<'facet, 'shape>(
         return Ok(());
     }
 
+    // `()`, `PhantomData` and unit structs are written as null, and hold no data
+    if shape.is_unit() && matches!(value, Yaml::Null) {
+        return Ok(());
+    }
+
     // First check the type system (Type)
     if let Type::User(UserType::Struct(sd)) = &shape.ty {
         if let Yaml::Hash(hash) = value {
@@ -152,6 +274,17 @@ fn deserialize_value<'facet, 'shape>(
                             )));
                         }
 
+                        wip.end().map_err(|e| AnyErr(e.to_string()))?;
+                    } else if field.shape().is_unit() {
+                        // `()`, `PhantomData` and unit structs hold no data
+                        wip.begin_nth_field(index)
+                            .map_err(|e| AnyErr(e.to_string()))?;
+                        wip.end().map_err(|e| AnyErr(e.to_string()))?;
+                    } else if let Def::Option(_) = field.shape().def {
+                        // A missing `Option` is `None`, like in JSON
+                        wip.begin_nth_field(index)
+                            .map_err(|e| AnyErr(e.to_string()))?;
+                        wip.set_default().map_err(|e| AnyErr(e.to_string()))?;
                         wip.end().map_err(|e| AnyErr(e.to_string()))?;
                     }
                 }
@@ -369,6 +502,16 @@ fn deserialize_value<'facet, 'shape>(
                     }
                 };
                 wip.set(b).map_err(|e| AnyErr(e.to_string()))?;
+            } else if innermost_shape.is_type::<char>() {
+                let s = value
+                    .as_str()
+                    .ok_or_else(|| AnyErr(format!("Expected string, got: {}", yaml_type(value))))?;
+                let mut chars = s.chars();
+                let c = match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => return Err(AnyErr(format!("Expected a single character, got {s:?}"))),
+                };
+                wip.set(c).map_err(|e| AnyErr(e.to_string()))?;
             } else if innermost_shape.is_type::<String>() {
                 // For strings, set directly
                 let s = value
@@ -393,6 +536,29 @@ fn deserialize_value<'facet, 'shape>(
 
             deserialize_as_list(wip, value)?;
         }
+        Def::Array(array_def) => {
+            #[cfg(feature = "log")]
+            log::debug!("Processing array type");
+
+            let Yaml::Array(array) = value else {
+                return Err(AnyErr(format!(
+                    "Expected a YAML array, got: {}",
+                    yaml_type(value)
+                )));
+            };
+            for (index, element) in array.iter().enumerate() {
+                if index >= array_def.n {
+                    return Err(AnyErr(format!(
+                        "Too many elements for array {shape}: element {index} doesn't fit, maximum {} elements allowed",
+                        array_def.n
+                    )));
+                }
+                wip.begin_nth_element(index)
+                    .map_err(|e| AnyErr(e.to_string()))?;
+                deserialize_value(wip, element)?;
+                wip.end().map_err(|e| AnyErr(e.to_string()))?;
+            }
+        }
         Def::Map(_) => {
             #[cfg(feature = "log")]
             log::debug!("Processing map type");
@@ -0,0 +1,41 @@
+//! Errors from editing YAML documents.
+
+use alloc::string::String;
+
+use crate::YamlSerError;
+
+/// Any error from editing a YAML document.
+pub enum YamlEditError {
+    /// The document isn't valid YAML.
+    Parse(String),
+    /// The document holds more than one YAML document, so it isn't clear which to edit.
+    MultipleDocuments,
+    /// The new value couldn't be serialized.
+    Serialize(YamlSerError),
+}
+
+impl core::fmt::Display for YamlEditError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Parse(message) => write!(f, "Invalid YAML document: {message}"),
+            Self::MultipleDocuments => {
+                write!(f, "Expected exactly one YAML document to edit")
+            }
+            Self::Serialize(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl core::error::Error for YamlEditError {}
+
+impl From<YamlSerError> for YamlEditError {
+    fn from(error: YamlSerError) -> Self {
+        Self::Serialize(error)
+    }
+}
+
+impl core::fmt::Debug for YamlEditError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
+    }
+}
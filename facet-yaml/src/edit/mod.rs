@@ -0,0 +1,772 @@
+//! Edit YAML documents in place, keeping what an edit doesn't change as it was written.
+
+mod error;
+
+use alloc::{
+    format,
+    string::{String, ToString as _},
+    vec::Vec,
+};
+use core::cmp::Reverse;
+
+pub use error::YamlEditError;
+use facet_serialize::Serialize as _;
+use yaml_rust2::{
+    Event, Yaml, YamlEmitter,
+    parser::{MarkedEventReceiver, Parser},
+    scanner::{Marker, TScalarStyle},
+};
+
+use crate::YamlSerializer;
+use crate::serialize::{is_scalar, write_flow};
+
+/// A YAML document that is updated from typed values, for files that people maintain by
+/// hand and tools rewrite.
+///
+/// [`YamlDocument::set`] only rewrites the values that changed: comments, blank lines,
+/// quoting and the order of keys stay as they were everywhere else. Keys that the new value
+/// doesn't have are removed with the lines they're on, and keys it adds go after the others
+/// in their map.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_yaml::YamlDocument;
+///
+/// #[derive(Facet)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let mut doc = YamlDocument::parse("# Where to listen\nhost: localhost\nport: 8080 # dev\n")?;
+/// let mut server: Server = facet_yaml::from_str(doc.as_str())?;
+/// server.port = 9090;
+/// doc.set(&server)?;
+/// assert_eq!(doc.as_str(), "# Where to listen\nhost: localhost\nport: 9090 # dev\n");
+/// # Ok::<(), Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YamlDocument {
+    text: String,
+}
+
+impl YamlDocument {
+    /// Reads a document, which has to be valid YAML.
+    pub fn parse(text: impl Into<String>) -> Result<Self, YamlEditError> {
+        let text = text.into();
+        Tree::parse(&text)?;
+        Ok(Self { text })
+    }
+
+    /// Updates the document to hold `value`, rewriting only what changed.
+    pub fn set<'a, T: facet_core::Facet<'a>>(&mut self, value: &'a T) -> Result<(), YamlEditError> {
+        let mut serializer = YamlSerializer::new();
+        value.serialize(&mut serializer)?;
+        let yaml = serializer.into_raw_document();
+        self.text = Tree::parse(&self.text)?.update(&yaml);
+        Ok(())
+    }
+
+    /// The text of the document.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the text of the document.
+    pub fn into_string(self) -> String {
+        self.text
+    }
+}
+
+impl core::fmt::Display for YamlDocument {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// Rewrites the YAML document `yaml` to hold `value`, like [`YamlDocument::set`] does.
+pub fn update_str<'a, T: facet_core::Facet<'a>>(
+    yaml: &str,
+    value: &'a T,
+) -> Result<String, YamlEditError> {
+    let mut document = YamlDocument::parse(yaml)?;
+    document.set(value)?;
+    Ok(document.into_string())
+}
+
+/// The nodes of a document, with where they are in its text.
+struct Tree<'a> {
+    text: &'a str,
+    /// `None` if the document is empty, or only has comments
+    root: Option<Node>,
+}
+
+/// A node, from the byte it starts at to the byte after it ends.
+struct Node {
+    start: usize,
+    end: usize,
+    kind: Kind,
+}
+
+enum Kind {
+    /// A scalar, as a YAML 1.2 parser reads it
+    Scalar(Yaml),
+    /// An alias, which is always rewritten with the value it stands for
+    Alias,
+    Seq {
+        flow: bool,
+        items: Vec<Item>,
+    },
+    Map {
+        flow: bool,
+        entries: Vec<Entry>,
+    },
+}
+
+/// An item of a sequence. `lead` is where the text that holds it starts: right after its
+/// `-` in block style, or at the item itself in flow style.
+struct Item {
+    lead: usize,
+    node: Node,
+}
+
+/// An entry of a map, with its key as it reads. `lead` is right after its `:`.
+struct Entry {
+    key: String,
+    key_start: usize,
+    lead: usize,
+    value: Node,
+}
+
+/// Where a value is written, which decides how it's written.
+#[derive(Clone, Copy)]
+enum Place {
+    /// The whole document
+    Root,
+    /// After the `:` of a block map entry, whose key is indented by this much
+    Value(usize),
+    /// After the `-` of a block sequence item, which is indented by this much
+    Item(usize),
+    /// In a flow collection, after a `:` if `true`
+    Flow(bool),
+}
+
+/// Text to put in place of a range of the document.
+struct Edit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl<'a> Tree<'a> {
+    fn parse(text: &'a str) -> Result<Self, YamlEditError> {
+        let mut builder = Builder {
+            text,
+            offsets: text
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain([text.len()])
+                .collect(),
+            open: Vec::new(),
+            root: None,
+            documents: 0,
+        };
+        Parser::new_from_str(text)
+            .load(&mut builder, true)
+            .map_err(|e| YamlEditError::Parse(e.to_string()))?;
+        if builder.documents > 1 {
+            return Err(YamlEditError::MultipleDocuments);
+        }
+        Ok(Self {
+            text,
+            root: builder.root,
+        })
+    }
+
+    /// The text of the document, holding `yaml` instead.
+    fn update(&self, yaml: &Yaml) -> String {
+        let Some(root) = &self.root else {
+            // Only comments to keep
+            let mut text = self.text.trim_end().to_string();
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(&emit(yaml));
+            text.push('\n');
+            return text;
+        };
+
+        let mut edits = Vec::new();
+        self.update_node(root, root.start, Place::Root, yaml, &mut edits);
+
+        // Edits are applied from the end, so the ones left still have the right offsets.
+        // Insertions at the same offset end up in the order they were made.
+        let mut edits: Vec<_> = edits.into_iter().enumerate().collect();
+        edits.sort_by_key(|(index, edit)| Reverse((edit.start, *index)));
+        let mut text = self.text.to_string();
+        for (_, edit) in edits {
+            text.replace_range(edit.start..edit.end, &edit.text);
+        }
+        text
+    }
+
+    /// Makes `node`, whose text starts at `lead`, hold `yaml`: in place if they're both maps
+    /// or sequences, or by rewriting it.
+    fn update_node(
+        &self,
+        node: &Node,
+        lead: usize,
+        place: Place,
+        yaml: &Yaml,
+        edits: &mut Vec<Edit>,
+    ) {
+        let updated = match (&node.kind, yaml) {
+            (Kind::Scalar(old), new) if is_scalar(new) => same_scalar(old, new),
+            (Kind::Map { flow, entries }, Yaml::Hash(hash)) => {
+                self.update_map(*flow, entries, hash, edits)
+            }
+            (Kind::Seq { flow, items }, Yaml::Array(new_items)) => {
+                self.update_seq(*flow, items, new_items, edits)
+            }
+            _ => false,
+        };
+        if !updated {
+            // Collections written in flow style stay in it
+            let place = match (&node.kind, place) {
+                (
+                    Kind::Seq { flow: true, .. } | Kind::Map { flow: true, .. },
+                    Place::Value(_) | Place::Item(_),
+                ) => Place::Flow(true),
+                _ => place,
+            };
+            edits.push(Edit {
+                start: lead,
+                end: node.end,
+                text: render(yaml, place),
+            });
+        }
+    }
+
+    /// Updates the entries of a map one by one, or returns `false` without making any edit
+    /// if the map has to be rewritten as a whole.
+    fn update_map(
+        &self,
+        flow: bool,
+        entries: &[Entry],
+        hash: &yaml_rust2::yaml::Hash,
+        edits: &mut Vec<Edit>,
+    ) -> bool {
+        let new_value = |key: &str| {
+            hash.iter()
+                .find(|(k, _)| key_text(k).as_deref() == Some(key))
+                .map(|(_, v)| v)
+        };
+        let Some(last) = entries
+            .iter()
+            .rev()
+            .find(|entry| new_value(&entry.key).is_some())
+        else {
+            return false;
+        };
+        // Whole lines are removed, which only works for entries on lines of their own
+        let mut removed = entries
+            .iter()
+            .filter(|entry| new_value(&entry.key).is_none());
+        if removed.any(|entry| flow || !leads_line(self.text, entry.key_start)) {
+            return false;
+        }
+
+        let indent = column(self.text, entries[0].key_start);
+        let place = if flow {
+            Place::Flow(true)
+        } else {
+            Place::Value(indent)
+        };
+        for entry in entries {
+            match new_value(&entry.key) {
+                Some(value) => self.update_node(&entry.value, entry.lead, place, value, edits),
+                None => edits.push(self.remove_lines(entry.key_start, entry.value.end)),
+            }
+        }
+        for (key, value) in hash {
+            let Some(key) = key_text(key) else {
+                continue;
+            };
+            if entries.iter().any(|entry| entry.key == key) {
+                continue;
+            }
+            let key = write_flow(&Yaml::String(key));
+            let (at, text) = if flow {
+                (last.value.end, format!(", {key}:{}", render(value, place)))
+            } else {
+                let pad = " ".repeat(indent);
+                let at = line_end(self.text, last.value.end);
+                (at, format!("\n{pad}{key}:{}", render(value, place)))
+            };
+            edits.push(Edit {
+                start: at,
+                end: at,
+                text,
+            });
+        }
+        true
+    }
+
+    /// Updates the items of a sequence one by one, or returns `false` without making any edit
+    /// if the sequence has to be rewritten as a whole.
+    fn update_seq(
+        &self,
+        flow: bool,
+        items: &[Item],
+        new_items: &[Yaml],
+        edits: &mut Vec<Edit>,
+    ) -> bool {
+        if items.is_empty() || new_items.is_empty() {
+            return false;
+        }
+        let kept = new_items.len().min(items.len());
+        let removed = &items[kept..];
+        if !removed.is_empty()
+            && (flow
+                || removed
+                    .iter()
+                    .any(|item| !leads_line(self.text, item.lead - 1)))
+        {
+            return false;
+        }
+
+        let indent = column(self.text, items[0].lead.saturating_sub(1));
+        let place = if flow {
+            Place::Flow(false)
+        } else {
+            Place::Item(indent)
+        };
+        for (item, new) in items.iter().zip(new_items) {
+            self.update_node(&item.node, item.lead, place, new, edits);
+        }
+        for item in removed {
+            edits.push(self.remove_lines(item.lead - 1, item.node.end));
+        }
+        let last = &items[kept - 1];
+        for new in &new_items[kept..] {
+            let (at, text) = if flow {
+                (last.node.end, format!(", {}", render(new, place)))
+            } else {
+                let pad = " ".repeat(indent);
+                let at = line_end(self.text, last.node.end);
+                (at, format!("\n{pad}-{}", render(new, place)))
+            };
+            edits.push(Edit {
+                start: at,
+                end: at,
+                text,
+            });
+        }
+        true
+    }
+
+    /// Removes the lines from the one `start` is on to the one `end` is on.
+    fn remove_lines(&self, start: usize, end: usize) -> Edit {
+        Edit {
+            start: line_start(self.text, start),
+            end: (line_end(self.text, end) + 1).min(self.text.len()),
+            text: String::new(),
+        }
+    }
+}
+
+/// Builds a [`Tree`] from the events of the parser.
+struct Builder<'a> {
+    text: &'a str,
+    /// The offset of each character, since markers count characters
+    offsets: Vec<usize>,
+    /// The collections being read, innermost last
+    open: Vec<Open>,
+    root: Option<Node>,
+    documents: usize,
+}
+
+/// A collection whose end wasn't read yet.
+enum Open {
+    Seq {
+        start: usize,
+        flow: bool,
+        items: Vec<Item>,
+    },
+    Map {
+        start: usize,
+        flow: bool,
+        entries: Vec<Entry>,
+        /// The key whose value is read next, with where it starts and ends
+        key: Option<(String, usize, usize)>,
+    },
+}
+
+impl Builder<'_> {
+    /// Adds a node to the collection it's in, or makes it the root. `key` is the text of
+    /// scalars, for when they're map keys.
+    fn add(&mut self, mut node: Node, key: Option<String>) {
+        let text = self.text;
+        match self.open.last_mut() {
+            None => self.root = Some(node),
+            Some(Open::Seq { flow, items, .. }) => {
+                let lead = if *flow {
+                    node.start
+                } else {
+                    dash_end(text, node.start)
+                };
+                place_empty(&mut node, lead);
+                items.push(Item { lead, node });
+            }
+            Some(Open::Map {
+                entries,
+                key: pending,
+                ..
+            }) => match pending.take() {
+                None => *pending = Some((key.unwrap_or_default(), node.start, node.end)),
+                Some((key, key_start, key_end)) => {
+                    let lead = colon_end(text, key_end);
+                    place_empty(&mut node, lead);
+                    entries.push(Entry {
+                        key,
+                        key_start,
+                        lead,
+                        value: node,
+                    });
+                }
+            },
+        }
+    }
+
+    /// Where a node whose event is at `mark` starts, after its anchor and tag.
+    fn start(&self, mark: Marker) -> usize {
+        let text = self.text;
+        let mut pos = self
+            .offsets
+            .get(mark.index())
+            .copied()
+            .unwrap_or(text.len());
+        while text[pos..].starts_with(['&', '!']) {
+            let end = token_end(text, pos);
+            pos = end + (text[end..].len() - text[end..].trim_start().len());
+        }
+        pos
+    }
+}
+
+impl MarkedEventReceiver for Builder<'_> {
+    fn on_event(&mut self, ev: Event, mark: Marker) {
+        let text = self.text;
+        match ev {
+            Event::DocumentStart => self.documents += 1,
+            Event::Scalar(value, style, ..) => {
+                let start = self.start(mark);
+                let (end, yaml) = match style {
+                    TScalarStyle::SingleQuoted | TScalarStyle::DoubleQuoted => {
+                        (quoted_end(text, start), Yaml::String(value.clone()))
+                    }
+                    TScalarStyle::Literal | TScalarStyle::Folded => {
+                        (block_end(text, start), Yaml::String(value.clone()))
+                    }
+                    // An empty node, which is placed by the collection it's in
+                    _ if value == "~" && !text[start..].starts_with('~') => (start, Yaml::Null),
+                    _ => (plain_end(text, start, &value), Yaml::from_str(&value)),
+                };
+                let node = Node {
+                    start,
+                    end,
+                    kind: Kind::Scalar(yaml),
+                };
+                self.add(node, Some(value));
+            }
+            Event::Alias(_) => {
+                let start = self.start(mark);
+                let node = Node {
+                    start,
+                    end: token_end(text, start),
+                    kind: Kind::Alias,
+                };
+                self.add(node, None);
+            }
+            Event::SequenceStart(..) => {
+                let start = self.start(mark);
+                self.open.push(Open::Seq {
+                    start,
+                    flow: text[start..].starts_with('['),
+                    items: Vec::new(),
+                });
+            }
+            Event::MappingStart(..) => {
+                let start = self.start(mark);
+                self.open.push(Open::Map {
+                    start,
+                    flow: text[start..].starts_with('{'),
+                    entries: Vec::new(),
+                    key: None,
+                });
+            }
+            Event::SequenceEnd => {
+                if let Some(Open::Seq { start, flow, items }) = self.open.pop() {
+                    let end = if flow {
+                        flow_end(text, start)
+                    } else {
+                        items.last().map_or(start, |item| item.node.end)
+                    };
+                    let kind = Kind::Seq { flow, items };
+                    self.add(Node { start, end, kind }, None);
+                }
+            }
+            Event::MappingEnd => {
+                if let Some(Open::Map {
+                    start,
+                    flow,
+                    entries,
+                    ..
+                }) = self.open.pop()
+                {
+                    let (start, end) = if flow {
+                        (start, flow_end(text, start))
+                    } else {
+                        // The event of a block map is marked at its first `:`, not its first key
+                        (
+                            entries.first().map_or(start, |entry| entry.key_start),
+                            entries.last().map_or(start, |entry| entry.value.end),
+                        )
+                    };
+                    let kind = Kind::Map { flow, entries };
+                    self.add(Node { start, end, kind }, None);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Puts an empty node, which has no text, right after the `-` or `:` it follows.
+fn place_empty(node: &mut Node, lead: usize) {
+    if node.start == node.end {
+        node.start = lead;
+        node.end = lead;
+    }
+}
+
+/// `yaml` as it's written at `place`.
+fn render(yaml: &Yaml, place: Place) -> String {
+    let inline = match yaml {
+        Yaml::Array(items) => items.is_empty(),
+        Yaml::Hash(hash) => hash.is_empty(),
+        _ => true,
+    };
+    match place {
+        Place::Root => emit(yaml),
+        Place::Flow(after_colon) => {
+            let space = if after_colon { " " } else { "" };
+            format!("{space}{}", write_flow(yaml))
+        }
+        Place::Value(_) | Place::Item(_) if inline => format!(" {}", write_flow(yaml)),
+        Place::Value(indent) => {
+            // On the lines after the key, indented further
+            let pad = " ".repeat(indent + 2);
+            emit(yaml)
+                .lines()
+                .map(|line| format!("\n{pad}{line}"))
+                .collect()
+        }
+        Place::Item(indent) => {
+            // Starting on the line of the `-`, with the rest lined up under it
+            let pad = format!("\n{}", " ".repeat(indent + 2));
+            let text = emit(yaml);
+            let lines: Vec<&str> = text.lines().collect();
+            format!(" {}", lines.join(&pad))
+        }
+    }
+}
+
+/// `yaml` in block style, as a document of its own.
+fn emit(yaml: &Yaml) -> String {
+    let mut output = String::new();
+    YamlEmitter::new(&mut output).dump(yaml).unwrap();
+    match output.strip_prefix("---\n") {
+        Some(body) => body.to_string(),
+        None => output,
+    }
+}
+
+/// The key `yaml` stands for, as it reads.
+fn key_text(yaml: &Yaml) -> Option<String> {
+    match yaml {
+        Yaml::String(s) | Yaml::Real(s) => Some(s.clone()),
+        Yaml::Integer(i) => Some(i.to_string()),
+        Yaml::Boolean(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Whether the scalar `old` already holds `new`, so `1.50` isn't rewritten as `1.5`.
+fn same_scalar(old: &Yaml, new: &Yaml) -> bool {
+    fn number(yaml: &Yaml) -> Option<f64> {
+        match yaml {
+            Yaml::Integer(i) => Some(*i as f64),
+            Yaml::Real(r) => r.parse().ok(),
+            _ => None,
+        }
+    }
+    old == new || matches!((number(old), number(new)), (Some(a), Some(b)) if a == b)
+}
+
+fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map_or(0, |i| i + 1)
+}
+
+fn line_end(text: &str, pos: usize) -> usize {
+    text[pos..].find('\n').map_or(text.len(), |i| pos + i)
+}
+
+/// The column of `pos`, in characters.
+fn column(text: &str, pos: usize) -> usize {
+    text[line_start(text, pos)..pos].chars().count()
+}
+
+/// Whether only indentation comes before `pos` on its line.
+fn leads_line(text: &str, pos: usize) -> bool {
+    text[line_start(text, pos)..pos].trim().is_empty()
+}
+
+/// How many spaces `line` starts with.
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// The end of an anchor, tag or alias that starts at `pos`.
+fn token_end(text: &str, pos: usize) -> usize {
+    text[pos..]
+        .find(|c: char| c.is_whitespace() || matches!(c, ',' | ']' | '}'))
+        .map_or(text.len(), |i| pos + i)
+}
+
+/// Right after the `-` before the block sequence item at `start`.
+fn dash_end(text: &str, start: usize) -> usize {
+    let before = text[..start].trim_end();
+    if before.ends_with('-') {
+        before.len()
+    } else {
+        start
+    }
+}
+
+/// Right after the `:` that follows the key ending at `key_end`.
+fn colon_end(text: &str, key_end: usize) -> usize {
+    let rest = &text[key_end..];
+    let after_spaces = rest.trim_start_matches([' ', '\t']);
+    if after_spaces.starts_with(':') {
+        key_end + (rest.len() - after_spaces.len()) + 1
+    } else {
+        key_end
+    }
+}
+
+/// The end of the plain scalar that starts at `start` and reads as `value`. The line breaks
+/// in a plain scalar are folded into spaces, or into all but one of them if there are more.
+fn plain_end(text: &str, start: usize, value: &str) -> usize {
+    let mut pos = start;
+    let mut value = value.chars().peekable();
+    while let Some(&expected) = value.peek() {
+        let Some(c) = text[pos..].chars().next() else {
+            break;
+        };
+        if c == expected {
+            pos += c.len_utf8();
+            value.next();
+            continue;
+        }
+        let run = text[pos..]
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(text.len() - pos);
+        let breaks = text[pos..pos + run].matches('\n').count();
+        if breaks == 0 {
+            break;
+        }
+        pos += run;
+        for _ in 0..breaks.saturating_sub(1).max(1) {
+            value.next();
+        }
+    }
+    pos
+}
+
+/// The end of the quoted scalar that starts at `start`.
+fn quoted_end(text: &str, start: usize) -> usize {
+    let Some(quote) = text[start..].chars().next() else {
+        return text.len();
+    };
+    let mut chars = text[start + 1..].char_indices();
+    while let Some((i, c)) = chars.next() {
+        if quote == '"' && c == '\\' {
+            chars.next();
+        } else if c == quote {
+            let end = start + 1 + i + 1;
+            // `''` is a quote in a single-quoted scalar
+            if quote == '\'' && text[end..].starts_with('\'') {
+                chars.next();
+                continue;
+            }
+            return end;
+        }
+    }
+    text.len()
+}
+
+/// The end of the literal or folded scalar whose header is at `start`: its last line that's
+/// indented further than the line of the header.
+fn block_end(text: &str, start: usize) -> usize {
+    let parent = indentation(&text[line_start(text, start)..]);
+    let mut end = line_end(text, start);
+    let mut pos = end;
+    let mut indent = None;
+    while pos < text.len() {
+        let next = pos + 1;
+        pos = line_end(text, next);
+        let line = &text[next..pos];
+        if line.trim().is_empty() {
+            continue;
+        }
+        let n = indentation(line);
+        if n <= parent || n < *indent.get_or_insert(n) {
+            break;
+        }
+        end = pos;
+    }
+    end
+}
+
+/// The end of the flow collection that starts at `start`, after its closing bracket.
+fn flow_end(text: &str, start: usize) -> usize {
+    let mut depth = 0usize;
+    let mut pos = start;
+    while let Some(c) = text[pos..].chars().next() {
+        let token_start = text[..pos]
+            .chars()
+            .next_back()
+            .is_none_or(|p| p.is_whitespace() || matches!(p, '[' | '{' | ',' | ':'));
+        match c {
+            '[' | '{' => depth += 1,
+            ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return pos + 1;
+                }
+            }
+            '"' | '\'' if token_start => {
+                pos = quoted_end(text, pos);
+                continue;
+            }
+            '#' if token_start => {
+                pos = line_end(text, pos);
+                continue;
+            }
+            _ => {}
+        }
+        pos += c.len_utf8();
+    }
+    text.len()
+}
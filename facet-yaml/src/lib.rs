@@ -16,3 +16,8 @@ pub use deserialize::*;
 mod serialize;
 #[cfg(feature = "serialize")]
 pub use serialize::*;
+
+#[cfg(feature = "serialize")]
+mod edit;
+#[cfg(feature = "serialize")]
+pub use edit::*;
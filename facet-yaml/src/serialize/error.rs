@@ -14,6 +14,8 @@ pub enum YamlSerError {
     },
     /// YAML doesn't support byte arrays.
     UnsupportedByteArray,
+    /// An `Rc` or `Arc` points back at a value that contains it.
+    PointerCycle(alloc::string::String),
 }
 
 impl core::fmt::Display for YamlSerError {
@@ -28,12 +30,22 @@ impl core::fmt::Display for YamlSerError {
             Self::UnsupportedByteArray => {
                 write!(f, "YAML doesn't support byte arrays")
             }
+            Self::PointerCycle(message) => f.write_str(message),
         }
     }
 }
 
 impl core::error::Error for YamlSerError {}
 
+impl From<YamlSerError> for facet_serialize::SerializeError {
+    fn from(error: YamlSerError) -> Self {
+        match error {
+            YamlSerError::UnsupportedByteArray => Self::unsupported_shape("byte arrays"),
+            error => Self::custom(error),
+        }
+    }
+}
+
 impl core::fmt::Debug for YamlSerError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         core::fmt::Display::fmt(self, f)
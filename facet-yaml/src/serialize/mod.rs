@@ -14,7 +14,9 @@ use alloc::{
 };
 
 pub use error::YamlSerError;
-use facet_serialize::{Serialize, Serializer};
+use facet_core::{FieldAttribute, Shape, ShapeAttribute, Type, UserType};
+pub use facet_serialize::FloatFormat;
+use facet_serialize::{PathSegment, Serialize, Serializer, pointer_cycle_message};
 use yaml_rust2::{
     Yaml, YamlEmitter,
     yaml::{Array, Hash},
@@ -28,6 +30,19 @@ pub struct YamlSerializer<'shape> {
     yaml: Yaml,
     /// What type the current item is.
     current: KeyOrValue,
+    /// Containers with at most this many items are written in flow style.
+    flow_threshold: Option<usize>,
+    /// Values that set their style with `#[facet(flow)]` or `#[facet(block)]`, by key.
+    styles: Vec<(Vec<Cow<'shape, str>>, Style)>,
+    /// Whether to quote strings that YAML 1.1 would read as something else.
+    yaml_1_1: bool,
+    /// How to write floats.
+    float_format: FloatFormat,
+    /// The documents and keys to go back to when a container in a list ends, one for each
+    /// list the current item is in.
+    outer: Vec<(Yaml, Vec<Cow<'shape, str>>)>,
+    /// Whether each container being written is an item of a list.
+    containers: Vec<bool>,
 }
 
 impl<'shape> YamlSerializer<'shape> {
@@ -37,9 +52,40 @@ impl<'shape> YamlSerializer<'shape> {
             key_stack: Vec::new(),
             yaml: Yaml::BadValue,
             current: KeyOrValue::Value,
+            flow_threshold: None,
+            styles: Vec::new(),
+            yaml_1_1: false,
+            float_format: FloatFormat::Shortest,
+            outer: Vec::new(),
+            containers: Vec::new(),
         }
     }
 
+    /// Write lists and maps with at most `max_items` items in flow style, like `[1, 2]` or
+    /// `{a: 1, b: 2}`, when their items are scalars or flow style themselves.
+    ///
+    /// Others are written in block style, like everything is by default. A field can pick
+    /// its style with `#[facet(flow)]` or `#[facet(block)]`, on the field or its type.
+    pub fn with_flow_threshold(mut self, max_items: usize) -> Self {
+        self.flow_threshold = Some(max_items);
+        self
+    }
+
+    /// Quote strings that a YAML 1.1 parser would read as something else, like `0b101` or
+    /// `2001-12-14`, so older parsers read them as strings too.
+    ///
+    /// Strings like `yes` and `0755` are always quoted.
+    pub fn with_yaml_1_1(mut self, yaml_1_1: bool) -> Self {
+        self.yaml_1_1 = yaml_1_1;
+        self
+    }
+
+    /// Set how to write floats.
+    pub fn with_float_format(mut self, float_format: FloatFormat) -> Self {
+        self.float_format = float_format;
+        self
+    }
+
     /// Get the output serialized YAML document.
     pub fn into_raw_document(self) -> Yaml {
         self.yaml
@@ -47,13 +93,89 @@ impl<'shape> YamlSerializer<'shape> {
 
     /// Get the output serialized YAML string.
     pub fn into_string(self) -> String {
+        let yaml = if self.yaml_1_1 {
+            quote_yaml_1_1(&self.yaml)
+        } else {
+            self.yaml.clone()
+        };
+        let yaml = match &yaml {
+            Yaml::Array(items) => {
+                Yaml::Array(items.iter().map(|item| self.styled(item, None)).collect())
+            }
+            Yaml::Hash(hash) => Yaml::Hash(self.styled_hash(hash, Some(&[]))),
+            yaml => yaml.clone(),
+        };
+
         let mut output = String::new();
         let mut emitter = YamlEmitter::new(&mut output);
-        emitter.dump(&self.yaml).unwrap();
+        emitter.dump(&yaml).unwrap();
 
         output
     }
 
+    /// Returns `yaml` with the containers in it that should be in flow style written out.
+    ///
+    /// `keys` leads to it from the root, if it's not inside a list.
+    fn styled(&self, yaml: &Yaml, keys: Option<&[Cow<'shape, str>]>) -> Yaml {
+        if let Some(flow) = self.flow(yaml, keys) {
+            // The emitter writes reals as they are, so this puts the flow text in verbatim
+            return Yaml::Real(flow);
+        }
+        match yaml {
+            Yaml::Array(items) => {
+                Yaml::Array(items.iter().map(|item| self.styled(item, None)).collect())
+            }
+            Yaml::Hash(hash) => Yaml::Hash(self.styled_hash(hash, keys)),
+            yaml => yaml.clone(),
+        }
+    }
+
+    /// [`styled`](Self::styled) for each value of a hash.
+    fn styled_hash(&self, hash: &Hash, keys: Option<&[Cow<'shape, str>]>) -> Hash {
+        hash.iter()
+            .map(|(key, value)| {
+                let keys = child_keys(keys, key);
+                (key.clone(), self.styled(value, keys.as_deref()))
+            })
+            .collect()
+    }
+
+    /// Writes `yaml` in flow style, if it's a container that should be.
+    fn flow(&self, yaml: &Yaml, keys: Option<&[Cow<'shape, str>]>) -> Option<String> {
+        let len = match yaml {
+            Yaml::Array(items) => items.len(),
+            Yaml::Hash(hash) => hash.len(),
+            _ => return None,
+        };
+        let style = keys.and_then(|keys| {
+            self.styles
+                .iter()
+                .find(|(styled_keys, _)| styled_keys.as_slice() == keys)
+                .map(|(_, style)| *style)
+        });
+        match style {
+            Some(Style::Flow) => Some(write_flow(yaml)),
+            Some(Style::Block) => None,
+            None => {
+                let max_items = self.flow_threshold?;
+                if len == 0 || len > max_items {
+                    return None;
+                }
+                let items_fit = match yaml {
+                    Yaml::Array(items) => items
+                        .iter()
+                        .all(|item| is_scalar(item) || self.flow(item, None).is_some()),
+                    Yaml::Hash(hash) => hash.iter().all(|(key, value)| {
+                        is_scalar(value)
+                            || self.flow(value, child_keys(keys, key).as_deref()).is_some()
+                    }),
+                    _ => unreachable!(),
+                };
+                items_fit.then(|| write_flow(yaml))
+            }
+        }
+    }
+
     /// Write a value depending on the context.
     fn write_value(&mut self, value: Yaml) -> Result<(), YamlSerError> {
         match self.current {
@@ -62,7 +184,7 @@ impl<'shape> YamlSerializer<'shape> {
                 if let Some(array) = self.current_mut().as_mut_vec() {
                     // Push it when it's an array, so we don't have to keep track of its keys
                     array.push(value);
-                } else if value == Yaml::Null {
+                } else if value == Yaml::Null && !self.key_stack.is_empty() {
                     // Remove the last item if it's none and a hash value
                     self.remove_current();
                 } else {
@@ -110,6 +232,34 @@ impl<'shape> YamlSerializer<'shape> {
         self.key_stack.pop()
     }
 
+    /// Start a hash or array at the current item, or as the next item if it's an array.
+    fn start_container(&mut self, container: Yaml) {
+        let in_list = self.current_mut().is_array();
+        if in_list {
+            // Written on its own, and pushed when it ends, since the keys can't index lists
+            let outer = core::mem::replace(&mut self.yaml, container);
+            self.outer
+                .push((outer, core::mem::take(&mut self.key_stack)));
+        } else {
+            self.set_current(container);
+        }
+        self.containers.push(in_list);
+    }
+
+    /// Finish the current hash or array.
+    fn end_container(&mut self) {
+        if self.containers.pop() != Some(true) {
+            return;
+        }
+        if let Some((outer, key_stack)) = self.outer.pop() {
+            let item = core::mem::replace(&mut self.yaml, outer);
+            self.key_stack = key_stack;
+            if let Some(array) = self.current_mut().as_mut_vec() {
+                array.push(item);
+            }
+        }
+    }
+
     /// Convert the item at the current key to another type.
     fn set_current(&mut self, yaml: Yaml) {
         #[cfg(feature = "log")]
@@ -196,18 +346,25 @@ impl<'shape> Serializer<'shape> for YamlSerializer<'shape> {
         self.write_value(Yaml::Integer(yaml_number))
     }
 
+    fn serialize_f32(&mut self, value: f32) -> Result<(), Self::Error> {
+        match self.float_format.format_f32(value) {
+            Some(formatted) => self.write_value(Yaml::Real(formatted)),
+            None => self.serialize_f64(value as f64),
+        }
+    }
+
     fn serialize_f64(&mut self, value: f64) -> Result<(), Self::Error> {
-        self.write_value(Yaml::Real(value.to_string()))
+        let formatted = self
+            .float_format
+            .format_f64(value)
+            .unwrap_or_else(|| value.to_string());
+        self.write_value(Yaml::Real(formatted))
     }
 
     fn serialize_bool(&mut self, value: bool) -> Result<(), Self::Error> {
         self.write_value(Yaml::Boolean(value))
     }
 
-    fn serialize_char(&mut self, value: char) -> Result<(), Self::Error> {
-        self.write_value(Yaml::String(value.to_string()))
-    }
-
     fn serialize_str(&mut self, value: &str) -> Result<(), Self::Error> {
         self.write_value(Yaml::String(value.to_string()))
     }
@@ -220,6 +377,10 @@ impl<'shape> Serializer<'shape> for YamlSerializer<'shape> {
         self.write_value(Yaml::Null)
     }
 
+    fn serialize_pointer_cycle(&mut self, shape: &'shape Shape<'shape>) -> Result<(), Self::Error> {
+        Err(YamlSerError::PointerCycle(pointer_cycle_message(shape)))
+    }
+
     fn serialize_unit(&mut self) -> Result<(), Self::Error> {
         self.write_value(Yaml::Null)
     }
@@ -233,19 +394,37 @@ impl<'shape> Serializer<'shape> for YamlSerializer<'shape> {
     }
 
     fn start_object(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        self.set_current(Yaml::Hash(Hash::new()));
+        self.start_container(Yaml::Hash(Hash::new()));
 
         Ok(())
     }
 
     fn start_array(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        self.set_current(Yaml::Array(Array::new()));
+        self.start_container(Yaml::Array(Array::new()));
 
         Ok(())
     }
 
     fn start_map(&mut self, _len: Option<usize>) -> Result<(), Self::Error> {
-        self.set_current(Yaml::Hash(Hash::new()));
+        self.start_container(Yaml::Hash(Hash::new()));
+
+        Ok(())
+    }
+
+    fn end_object(&mut self) -> Result<(), Self::Error> {
+        self.end_container();
+
+        Ok(())
+    }
+
+    fn end_array(&mut self) -> Result<(), Self::Error> {
+        self.end_container();
+
+        Ok(())
+    }
+
+    fn end_map(&mut self) -> Result<(), Self::Error> {
+        self.end_container();
 
         Ok(())
     }
@@ -279,6 +458,168 @@ impl<'shape> Serializer<'shape> for YamlSerializer<'shape> {
 
         Ok(())
     }
+
+    fn begin_path_segment(
+        &mut self,
+        path: &[PathSegment<'_, '_, 'shape>],
+        container: &'shape Shape<'shape>,
+    ) -> Result<(), Self::Error> {
+        // Styles are looked up by the keys from the root, which items of lists don't have
+        if !self.outer.is_empty() {
+            return Ok(());
+        }
+        let Some(field) = field_at(path, container) else {
+            return Ok(());
+        };
+        let field_style = field.attributes.iter().find_map(|attr| match attr {
+            FieldAttribute::Arbitrary(content) => Style::from_attribute(content),
+            _ => None,
+        });
+        let style = field_style.or_else(|| {
+            field.shape().attributes.iter().find_map(|attr| match attr {
+                ShapeAttribute::Arbitrary(content) => Style::from_attribute(content),
+                _ => None,
+            })
+        });
+        if let Some(style) = style {
+            // The field name was already pushed, so this is where the value goes
+            self.styles.push((self.key_stack.clone(), style));
+        }
+
+        Ok(())
+    }
+}
+
+/// How to write a list or map.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Style {
+    /// On one line, like `[1, 2]` or `{a: 1, b: 2}`.
+    Flow,
+    /// One item per line.
+    Block,
+}
+
+impl Style {
+    /// Reads the style from a `#[facet(flow)]` or `#[facet(block)]` attribute.
+    fn from_attribute(content: &str) -> Option<Self> {
+        match content.trim() {
+            "flow" => Some(Self::Flow),
+            "block" => Some(Self::Block),
+            _ => None,
+        }
+    }
+}
+
+/// Finds the field the last segment of `path` points at, if it's a field.
+fn field_at<'shape>(
+    path: &[PathSegment<'_, '_, 'shape>],
+    container: &'shape Shape<'shape>,
+) -> Option<&'shape facet_core::Field<'shape>> {
+    let [.., segment] = path else {
+        return None;
+    };
+    let PathSegment::Field(name) = segment else {
+        return None;
+    };
+    let fields = match container.ty {
+        Type::User(UserType::Struct(struct_type)) => struct_type.fields,
+        Type::User(UserType::Enum(enum_type)) => {
+            // Fields of a struct variant come after the variant
+            let [.., PathSegment::Variant(variant), _] = path else {
+                return None;
+            };
+            enum_type
+                .variants
+                .iter()
+                .find(|v| v.name == *variant)?
+                .data
+                .fields
+        }
+        _ => return None,
+    };
+    fields.iter().find(|field| field.name == *name)
+}
+
+/// The keys leading to the value at `key` of the hash at `keys`, if it can be found that way.
+fn child_keys<'shape>(
+    keys: Option<&[Cow<'shape, str>]>,
+    key: &Yaml,
+) -> Option<Vec<Cow<'shape, str>>> {
+    let key = key.as_str()?;
+    keys.map(|keys| {
+        let mut keys = keys.to_vec();
+        keys.push(Cow::Owned(key.to_string()));
+        keys
+    })
+}
+
+/// Returns `yaml` with the strings in it that YAML 1.1 reads as something else quoted.
+fn quote_yaml_1_1(yaml: &Yaml) -> Yaml {
+    match yaml {
+        // The emitter writes reals as they are. These strings have no characters to escape
+        Yaml::String(s) if is_yaml_1_1_non_string(s) => Yaml::Real(alloc::format!("\"{s}\"")),
+        Yaml::Array(items) => Yaml::Array(items.iter().map(quote_yaml_1_1).collect()),
+        Yaml::Hash(hash) => Yaml::Hash(
+            hash.iter()
+                .map(|(key, value)| (quote_yaml_1_1(key), quote_yaml_1_1(value)))
+                .collect(),
+        ),
+        yaml => yaml.clone(),
+    }
+}
+
+/// Whether YAML 1.1 reads `s` as a number or timestamp, when YAML 1.2 reads it as a string.
+fn is_yaml_1_1_non_string(s: &str) -> bool {
+    let unsigned = s.strip_prefix(['-', '+']).unwrap_or(s);
+    // Binary numbers, like `0b1010`
+    let binary = unsigned.strip_prefix("0b").is_some_and(|digits| {
+        !digits.is_empty() && digits.chars().all(|c| matches!(c, '0' | '1' | '_'))
+    });
+    // Numbers with separators, like `1_000` or `1_000.5`
+    let separated = unsigned.contains('_')
+        && unsigned.starts_with(|c: char| c.is_ascii_digit())
+        && unsigned
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '_' || c == '.');
+    // Timestamps, which start with a date like `2001-12-14`
+    let bytes = s.as_bytes();
+    let timestamp = bytes.len() >= 8
+        && bytes[..4].iter().all(u8::is_ascii_digit)
+        && bytes[4] == b'-'
+        && s[5..].split_once('-').is_some_and(|(month, rest)| {
+            (1..=2).contains(&month.len())
+                && month.bytes().all(|b| b.is_ascii_digit())
+                && rest.bytes().take_while(u8::is_ascii_digit).count() >= 1
+        });
+    binary || separated || timestamp
+}
+
+/// Whether `yaml` isn't a list or map.
+pub(crate) fn is_scalar(yaml: &Yaml) -> bool {
+    !matches!(yaml, Yaml::Array(_) | Yaml::Hash(_))
+}
+
+/// Writes `yaml` in flow style, like `[1, {a: 2}]`.
+pub(crate) fn write_flow(yaml: &Yaml) -> String {
+    match yaml {
+        Yaml::Array(items) => {
+            let items: Vec<String> = items.iter().map(write_flow).collect();
+            alloc::format!("[{}]", items.join(", "))
+        }
+        Yaml::Hash(hash) => {
+            let entries: Vec<String> = hash
+                .iter()
+                .map(|(key, value)| alloc::format!("{}: {}", write_flow(key), write_flow(value)))
+                .collect();
+            alloc::format!("{{{}}}", entries.join(", "))
+        }
+        scalar => {
+            let mut output = String::new();
+            YamlEmitter::new(&mut output).dump(scalar).unwrap();
+            // Scalars are quoted when they contain flow indicators like `,` or `]`
+            output.strip_prefix("---\n").unwrap_or(&output).to_string()
+        }
+    }
 }
 
 /// What type the current item is.
@@ -299,6 +640,15 @@ pub fn to_string<'a, T: facet_core::Facet<'a>>(value: &'a T) -> Result<String, Y
     Ok(serializer.into_string())
 }
 
+/// Serialize a `Peek` instance to a YAML string.
+#[cfg(feature = "alloc")]
+pub fn peek_to_string(peek: facet_reflect::Peek<'_, '_, '_>) -> Result<String, YamlSerError> {
+    let mut serializer = YamlSerializer::new();
+    facet_serialize::serialize_iterative(peek, &mut serializer)?;
+
+    Ok(serializer.into_string())
+}
+
 /// Static type name for a YAML type.
 fn type_name(yaml: &Yaml) -> &'static str {
     match yaml {
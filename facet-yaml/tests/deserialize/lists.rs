@@ -74,3 +74,21 @@ fn test_deserialize_nested_lists() {
     let nested: Vec<Vec<u64>> = facet_yaml::from_str(yaml)?;
     assert_eq!(nested, vec![vec![1, 2], vec![3, 4]]);
 }
+
+#[test]
+fn test_deserialize_fixed_size_array() {
+    let yaml = r#"
+        - 1
+        - 2
+        - 3
+    "#;
+
+    let array: [u32; 3] = facet_yaml::from_str(yaml)?;
+    assert_eq!(array, [1, 2, 3]);
+
+    let err = facet_yaml::from_str::<[u32; 2]>(yaml).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Too many elements for array [u32; 2]: element 2 doesn't fit, maximum 2 elements allowed"
+    );
+}
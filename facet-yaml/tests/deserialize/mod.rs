@@ -5,3 +5,4 @@ mod ip_test;
 mod lists;
 mod maps;
 mod transparent;
+mod yaml_1_1;
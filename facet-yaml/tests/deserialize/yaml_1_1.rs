@@ -0,0 +1,50 @@
+use facet::Facet;
+use facet_testhelpers::test;
+use facet_yaml::YamlParser;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Legacy {
+    on: bool,
+    debug: bool,
+    mode: u32,
+    offset: i32,
+    name: String,
+}
+
+#[test]
+fn test_yaml_1_1_booleans_and_octals() {
+    let yaml = r#"
+            on: yes
+            debug: Off
+            mode: 0755
+            offset: -017
+            name: '0755'
+        "#;
+
+    let legacy: Legacy = YamlParser::new().with_yaml_1_1(true).parse(yaml)?;
+    assert_eq!(
+        legacy,
+        Legacy {
+            on: true,
+            debug: false,
+            mode: 0o755,
+            offset: -0o17,
+            name: "0755".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_yaml_1_2_octals_are_decimal() {
+    let yaml = r#"
+            on: true
+            debug: false
+            mode: 0755
+            offset: 0o17
+            name: x
+        "#;
+
+    let legacy: Legacy = facet_yaml::from_str(yaml)?;
+    assert_eq!(legacy.mode, 755);
+    assert_eq!(legacy.offset, 0o17);
+}
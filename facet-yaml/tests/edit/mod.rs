@@ -0,0 +1,161 @@
+//! Tests for editing YAML documents in place.
+
+use std::collections::HashMap;
+
+use eyre::Result;
+use facet::Facet;
+use facet_yaml::{YamlDocument, YamlEditError};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Config {
+    name: String,
+    server: Server,
+    tags: Vec<String>,
+    upstreams: Vec<Upstream>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+    debug: Option<bool>,
+    timeout: Option<u32>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Upstream {
+    host: String,
+    weight: u8,
+}
+
+#[test]
+fn test_edit_keeps_comments_and_order() -> Result<()> {
+    facet_testhelpers::setup();
+
+    let yaml = r#"# Service configuration
+name: api # shown in logs
+
+server:
+  # Where to listen
+  host: "0.0.0.0"
+  port: 8080
+  debug: true
+
+tags: [web, public]
+
+upstreams:
+  - host: a.internal # primary
+    weight: 1
+  - host: b.internal
+    weight: 2
+"#;
+    let mut doc = YamlDocument::parse(yaml)?;
+    let mut config: Config = facet_yaml::from_str(doc.as_str())?;
+    config.server.port = 9090;
+    config.server.debug = None;
+    config.server.timeout = Some(30);
+    config.tags.push("internal".to_string());
+    config.upstreams[1].weight = 5;
+    config.upstreams.push(Upstream {
+        host: "c.internal".to_string(),
+        weight: 1,
+    });
+    doc.set(&config)?;
+
+    assert_eq!(
+        doc.as_str(),
+        r#"# Service configuration
+name: api # shown in logs
+
+server:
+  # Where to listen
+  host: "0.0.0.0"
+  port: 9090
+  timeout: 30
+
+tags: [web, public, internal]
+
+upstreams:
+  - host: a.internal # primary
+    weight: 1
+  - host: b.internal
+    weight: 5
+  - host: c.internal
+    weight: 1
+"#
+    );
+    assert_eq!(facet_yaml::from_str::<Config>(doc.as_str())?, config);
+    Ok(())
+}
+
+#[test]
+fn test_edit_unchanged_values_keep_their_spelling() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Limits {
+        ratio: f64,
+        mask: u32,
+        label: String,
+    }
+
+    let yaml = "ratio: 1.50\nmask:   0xff   # bits\nlabel: 'it''s'\n";
+    let limits: Limits = facet_yaml::from_str(yaml)?;
+    assert_eq!(facet_yaml::update_str(yaml, &limits)?, yaml);
+    Ok(())
+}
+
+#[test]
+fn test_edit_removes_items() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Lists {
+        list: Vec<u32>,
+        map: HashMap<String, u32>,
+    }
+
+    let yaml = "list:\n  - 1\n  - 2 # two\n  - 3\nmap: {a: 1, b: 2}\n";
+    let lists = Lists {
+        list: vec![1, 2],
+        map: [("a".to_string(), 1)].into_iter().collect(),
+    };
+    assert_eq!(
+        facet_yaml::update_str(yaml, &lists)?,
+        "list:\n  - 1\n  - 2 # two\nmap: {a: 1}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_edit_empty_document() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Size {
+        width: u32,
+        height: u32,
+    }
+
+    let mut doc = YamlDocument::parse("# Written by the tool\n")?;
+    doc.set(&Size {
+        width: 1,
+        height: 2,
+    })?;
+    assert_eq!(doc.as_str(), "# Written by the tool\nwidth: 1\nheight: 2\n");
+    Ok(())
+}
+
+#[test]
+fn test_edit_errors() {
+    facet_testhelpers::setup();
+
+    assert!(matches!(
+        YamlDocument::parse("a: 1\n---\na: 2\n"),
+        Err(YamlEditError::MultipleDocuments)
+    ));
+    assert!(matches!(
+        YamlDocument::parse("a: [1"),
+        Err(YamlEditError::Parse(_))
+    ));
+}
@@ -7,3 +7,6 @@ mod deserialize;
 // We deserialize the serialized data as well so we need both feature flags
 #[cfg(all(feature = "alloc", feature = "serialize", feature = "deserialize"))]
 mod serialize;
+// Edited documents are read back to check them
+#[cfg(all(feature = "alloc", feature = "serialize", feature = "deserialize"))]
+mod edit;
@@ -28,6 +28,42 @@ fn test_scalar_list() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_nested_list() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Point {
+        x: i32,
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        points: Vec<Point>,
+        grid: Vec<Vec<u16>>,
+    }
+
+    assert_serialize!(
+        Root,
+        Root {
+            points: vec![
+                Point {
+                    x: 1,
+                    tags: vec!["a".to_string()],
+                },
+                Point {
+                    x: 2,
+                    tags: Vec::new(),
+                },
+            ],
+            grid: vec![vec![1, 2], Vec::new(), vec![3]],
+        },
+    );
+
+    Ok(())
+}
+
 #[test]
 #[ignore = "Must be fixed in facet-yaml deserialize"]
 fn test_option_scalar_list() -> Result<()> {
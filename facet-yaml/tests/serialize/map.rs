@@ -235,3 +235,43 @@ fn test_invalid_map_key() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_index_map_keeps_input_order() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        values: indexmap::IndexMap<String, i32>,
+    }
+
+    let root: Root = facet_yaml::from_str("values:\n  zebra: 1\n  apple: 2\n  mango: 3\n")?;
+    assert_eq!(
+        root.values.keys().map(String::as_str).collect::<Vec<_>>(),
+        ["zebra", "apple", "mango"]
+    );
+    assert_eq!(
+        facet_yaml::to_string(&root)?,
+        "---\nvalues:\n  zebra: 1\n  apple: 2\n  mango: 3"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_btree_map_is_written_sorted() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        values: std::collections::BTreeMap<String, i32>,
+    }
+
+    let root: Root = facet_yaml::from_str("values:\n  zebra: 1\n  apple: 2\n  mango: 3\n")?;
+    assert_eq!(
+        facet_yaml::to_string(&root)?,
+        "---\nvalues:\n  apple: 2\n  mango: 3\n  zebra: 1"
+    );
+
+    Ok(())
+}
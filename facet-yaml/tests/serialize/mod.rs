@@ -3,6 +3,7 @@ mod list;
 mod map;
 mod scalar;
 mod struct_;
+mod style;
 
 /// Assert that the YAML used to serialize a value can be used to deserialize the value too.
 #[macro_export]
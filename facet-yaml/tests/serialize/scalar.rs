@@ -28,6 +28,28 @@ fn test_string() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_char() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        value: char,
+    }
+
+    assert_serialize!(Root, Root { value: 'c' });
+    assert_serialize!(Root, Root { value: 'é' });
+    assert_eq!(
+        facet_yaml::to_string(&Root { value: 'c' })?,
+        "---\nvalue: c"
+    );
+
+    assert!(facet_yaml::from_str::<Root>("value: ab").is_err());
+    assert!(facet_yaml::from_str::<Root>("value: ''").is_err());
+
+    Ok(())
+}
+
 #[test]
 #[ignore = "Must be fixed in facet-serialize"]
 fn test_cow_string() -> Result<()> {
@@ -177,6 +199,39 @@ fn test_f32() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_float_format() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        single: f32,
+        double: f64,
+        tiny: f64,
+    }
+
+    let root = Root {
+        single: 0.1,
+        double: 2.5,
+        tiny: 1.5e-7,
+    };
+    let to_string = |float_format| -> Result<String> {
+        let mut serializer = facet_yaml::YamlSerializer::new().with_float_format(float_format);
+        facet_serialize::serialize_iterative(facet_reflect::Peek::new(&root), &mut serializer)?;
+        Ok(serializer.into_string())
+    };
+
+    assert_eq!(
+        to_string(facet_yaml::FloatFormat::Fixed(3))?,
+        "---\nsingle: 0.100\ndouble: 2.500\ntiny: 0.000"
+    );
+    let yaml = to_string(facet_yaml::FloatFormat::Scientific { min: -3, max: 6 })?;
+    assert_eq!(yaml, "---\nsingle: 0.1\ndouble: 2.5\ntiny: 1.5e-7");
+    assert_eq!(facet_yaml::from_str::<Root>(&yaml).unwrap(), root);
+
+    Ok(())
+}
+
 #[test]
 fn test_usize() -> Result<()> {
     facet_testhelpers::setup();
@@ -383,16 +438,27 @@ fn test_nested_optional_scalar() -> Result<()> {
 }
 
 #[test]
-#[ignore = "Must be fixed in facet-yaml deserialize"]
 fn test_unit() -> Result<()> {
     facet_testhelpers::setup();
 
+    #[derive(Debug, Facet, PartialEq)]
+    struct Marker;
+
     #[derive(Debug, Facet, PartialEq)]
     struct Root {
         unit: (),
+        marker: Marker,
+        phantom: core::marker::PhantomData<String>,
     }
 
-    assert_serialize!(Root, Root { unit: () });
+    assert_serialize!(
+        Root,
+        Root {
+            unit: (),
+            marker: Marker,
+            phantom: core::marker::PhantomData,
+        }
+    );
 
     Ok(())
 }
@@ -462,3 +528,30 @@ fn test_i128_out_of_range() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_yaml_1_1_quoting() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        values: Vec<String>,
+    }
+
+    let root = Root {
+        values: ["0b101", "1_000", "2001-12-14", "yes", "plain"]
+            .map(String::from)
+            .to_vec(),
+    };
+    let mut serializer = facet_yaml::YamlSerializer::new().with_yaml_1_1(true);
+    facet_serialize::serialize_iterative(facet_reflect::Peek::new(&root), &mut serializer)?;
+    let yaml = serializer.into_string();
+
+    assert_eq!(
+        yaml,
+        "---\nvalues:\n  - \"0b101\"\n  - \"1_000\"\n  - \"2001-12-14\"\n  - \"yes\"\n  - plain"
+    );
+    assert_eq!(facet_yaml::from_str::<Root>(&yaml).unwrap(), root);
+
+    Ok(())
+}
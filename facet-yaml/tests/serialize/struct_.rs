@@ -190,3 +190,38 @@ fn test_optional_default_struct_fields() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_error_path() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet)]
+    struct Server {
+        name: String,
+        key: Vec<u8>,
+    }
+
+    #[derive(Debug, Facet)]
+    struct Root {
+        servers: Vec<Server>,
+    }
+
+    let root = Root {
+        servers: vec![Server {
+            name: "db".to_string(),
+            key: vec![1, 2],
+        }],
+    };
+    let mut serializer = facet_yaml::YamlSerializer::new();
+    let err =
+        facet_serialize::serialize_with_path(facet_reflect::Peek::new(&root), &mut serializer)
+            .unwrap_err();
+
+    assert!(matches!(
+        err.kind(),
+        facet_serialize::SerializeErrorKind::UnsupportedShape(_)
+    ));
+    assert_eq!(err.path(), Some("Root.servers[0].key"));
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! Tests for flow and block style lists and maps.
+
+use eyre::Result;
+use facet::Facet;
+use facet_serialize::Serialize as _;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn to_string_with<'a, T: Facet<'a>>(
+    value: &'a T,
+    serializer: facet_yaml::YamlSerializer<'static>,
+) -> Result<String> {
+    let mut serializer = serializer;
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_string())
+}
+
+#[test]
+fn test_flow_threshold() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        tags: Vec<String>,
+        origin: Point,
+        values: Vec<u32>,
+    }
+
+    let root = Root {
+        tags: vec!["a, b".to_string(), "c".to_string()],
+        origin: Point { x: 1, y: 2 },
+        values: vec![1, 2, 3],
+    };
+    let serializer = facet_yaml::YamlSerializer::new().with_flow_threshold(2);
+    let yaml = to_string_with(&root, serializer)?;
+
+    assert_eq!(
+        yaml,
+        "---\ntags: [\"a, b\", c]\norigin: {x: 1, \"y\": 2}\nvalues:\n  - 1\n  - 2\n  - 3"
+    );
+    assert_eq!(facet_yaml::from_str::<Root>(&yaml).unwrap(), root);
+
+    Ok(())
+}
+
+#[test]
+fn test_style_attributes() -> Result<()> {
+    facet_testhelpers::setup();
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Root {
+        #[facet(flow)]
+        values: Vec<u32>,
+        #[facet(block)]
+        origin: Point,
+    }
+
+    let root = Root {
+        values: vec![1, 2, 3],
+        origin: Point { x: 1, y: 2 },
+    };
+    let serializer = facet_yaml::YamlSerializer::new().with_flow_threshold(2);
+    let yaml = to_string_with(&root, serializer)?;
+
+    assert_eq!(yaml, "---\nvalues: [1, 2, 3]\norigin:\n  x: 1\n  \"y\": 2");
+
+    Ok(())
+}
@@ -45,12 +45,20 @@ pub use facet_core::*;
 ///
 /// * `deny_unknown_fields` Always throw an error when encountering unknown fields during deserialization. When this attribute is not present unknown fields are ignored.
 ///
+/// * `discriminant` Serialize the unit variants of an enum as their discriminant integers (like `2`) instead of their names. Both are accepted when deserializing.
+///
 /// * `skip_serializing` Don't allow this type to be serialized.
 ///
 /// * `skip_serializing_if = ".."` Don't allow this type to be serialized if the function returns `true`.
 ///
 /// * `invariants = ".."` Called when doing `Partial::build`. **TODO**
 ///
+/// * `parse = ".."` Deserialize a struct from a string with the given `fn(&str) -> Result<Self, &'static str>`, for humanized values like `"10MB"` or `"5s"`. Formats still accept the struct's usual form, like the bare number of a `transparent` newtype.
+///
+/// * `format = ".."` Serialize a struct as the string written by the given `fn(&Self, &mut fmt::Formatter) -> fmt::Result`.
+///
+/// * `bitflags` On a `transparent` newtype that the `bitflags` crate implements `Flags` for, with `bitflags! { impl Name: u8 { .. } }`. Serialize it as a list of the names of the flags that are set, and deserialize it from such a list or from its bits. Needs the `bitflags` feature.
+///
 /// # Field Attributes
 ///
 /// ```rust
@@ -68,10 +76,19 @@ pub use facet_core::*;
 ///
 /// * `default = ".."` Use the expression when the field is missing during deserializing.
 ///
+///   With either form, an explicit `null` (in formats that have one) also gives the field its default. Other fields that aren't an `Option` reject `null`.
+///
+///   A missing `Option` field is `None` even without `default`. For an `Option<Option<T>>`,
+///   `null` is `Some(None)`, so a missing field and an explicit `null` can be told apart.
+///
 /// * `sensitive` Don't show the value in debug outputs.
 ///
 /// * `flatten` Flatten the value's content into the container structure.
 ///
+///   A flattened struct's fields, and a flattened enum's variant names, become keys of the
+///   container. The container's own fields take precedence, and two flattened fields can't
+///   take the same key.
+///
 /// * `child` Mark as child node in a hierarchy. **TODO**
 ///
 /// * `skip_serializing` Ignore when serializing.
@@ -96,6 +113,8 @@ pub use facet_core::*;
 ///
 /// * `skip_serializing_if = ".."` Ignore when serializing if the function returns `true`.
 ///
+/// * `other` Deserialize variant names and discriminants that don't match any other variant into this one, so that newer inputs don't fail to deserialize. If the variant has a field, like `Other(String)` or `Unknown(u8)`, the name or discriminant is kept in it and serialized back as it was read.
+///
 /// # Examples
 ///
 /// **TODO**.
@@ -0,0 +1,108 @@
+// Everything here is checked at compile time: if a shape needed any runtime
+// initialization, this file wouldn't build.
+#![allow(dead_code)]
+
+use facet::{Facet, Shape, ShapeLayout, Type, UserType};
+
+#[derive(Facet)]
+#[facet(deny_unknown_fields)]
+struct Sensor {
+    id: u16,
+    label: &'static str,
+    #[facet(sensitive)]
+    calibration: [f32; 4],
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+enum Reading {
+    Idle,
+    Value(f32),
+    Pair { low: i16, high: i16 },
+}
+
+#[derive(Facet)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+/// Shapes can be gathered in a `static`.
+static SHAPES: &[&Shape] = &[
+    Sensor::SHAPE,
+    Reading::SHAPE,
+    <Wrapper<Sensor>>::SHAPE,
+    <Vec<Option<u32>>>::SHAPE,
+    str::SHAPE,
+];
+
+const fn size_of_shape(shape: &Shape) -> usize {
+    match shape.layout.sized_layout() {
+        Ok(layout) => layout.size(),
+        Err(_) => 0,
+    }
+}
+
+const fn field_count(shape: &Shape) -> usize {
+    match shape.ty {
+        Type::User(UserType::Struct(st)) => st.fields.len(),
+        _ => 0,
+    }
+}
+
+const fn variant_count(shape: &Shape) -> usize {
+    match shape.ty {
+        Type::User(UserType::Enum(en)) => en.variants.len(),
+        _ => 0,
+    }
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const _: () = {
+    assert!(size_of_shape(Sensor::SHAPE) == core::mem::size_of::<Sensor>());
+    assert!(size_of_shape(<Wrapper<u64>>::SHAPE) == 8);
+    assert!(matches!(str::SHAPE.layout, ShapeLayout::Unsized));
+
+    assert!(field_count(Sensor::SHAPE) == 3);
+    assert!(variant_count(Reading::SHAPE) == 3);
+    assert!(str_eq(Sensor::SHAPE.type_identifier, "Sensor"));
+
+    assert!(Sensor::SHAPE.has_deny_unknown_fields_attr());
+    assert!(!Sensor::SHAPE.has_default_attr());
+    assert!(Sensor::SHAPE.get_rename_all_attr().is_none());
+
+    // Field shapes are reachable too
+    let Type::User(UserType::Struct(st)) = <Wrapper<Sensor>>::SHAPE.ty else {
+        panic!("not a struct");
+    };
+    assert!(field_count(st.fields[0].shape) == 3);
+};
+
+#[test]
+fn shapes_in_statics() {
+    assert_eq!(SHAPES.len(), 5);
+    assert!(SHAPES[0].is_type::<Sensor>());
+    assert_eq!(SHAPES[2].to_string(), "Wrapper<Sensor>");
+}
+
+#[test]
+fn const_queries_match_runtime() {
+    const DENY: bool = Sensor::SHAPE.has_deny_unknown_fields_attr();
+    assert_eq!(DENY, Sensor::SHAPE.has_deny_unknown_fields_attr());
+
+    const SIZE: usize = size_of_shape(Reading::SHAPE);
+    assert_eq!(SIZE, core::mem::size_of::<Reading>());
+}
@@ -585,3 +585,43 @@ fn test_macro_u16() {
         Value3 = test_macro_u16!(),
     }
 }
+
+#[test]
+fn struct_with_cfg_gated_fields() {
+    #[derive(Debug, Facet)]
+    struct Blah {
+        foo: u32,
+        #[cfg(any())]
+        gone: String,
+        #[cfg(not(any()))]
+        bar: u16,
+    }
+
+    if let Type::User(UserType::Struct(StructType { fields, .. })) = Blah::SHAPE.ty {
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].name, "foo");
+        assert_eq!(fields[1].name, "bar");
+        assert_eq!(fields[1].offset, offset_of!(Blah, bar));
+    } else {
+        panic!("Expected Struct innards");
+    }
+
+    #[derive(Debug, Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Choice {
+        Pick {
+            #[cfg(any())]
+            gone: String,
+            index: u32,
+        },
+    }
+
+    if let Type::User(UserType::Enum(enum_type)) = Choice::SHAPE.ty {
+        let fields = enum_type.variants[0].data.fields;
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].name, "index");
+    } else {
+        panic!("Expected Enum innards");
+    }
+}
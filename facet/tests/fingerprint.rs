@@ -0,0 +1,132 @@
+// The types here only exist for their shapes
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use facet::Facet;
+
+mod v1 {
+    use facet::Facet;
+
+    /// A point
+    #[derive(Facet)]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+}
+
+mod v1_documented {
+    use facet::Facet;
+
+    /// A point on the plane, with a different doc comment
+    #[derive(Facet)]
+    pub struct Point {
+        /// Horizontal
+        pub x: i32,
+        /// Vertical
+        pub y: i32,
+    }
+}
+
+mod v2_renamed {
+    use facet::Facet;
+
+    #[derive(Facet)]
+    pub struct Point {
+        pub x: i32,
+        pub z: i32,
+    }
+}
+
+mod v2_reordered {
+    use facet::Facet;
+
+    #[derive(Facet)]
+    pub struct Point {
+        pub y: i32,
+        pub x: i32,
+    }
+}
+
+mod v2_wider {
+    use facet::Facet;
+
+    #[derive(Facet)]
+    pub struct Point {
+        pub x: i64,
+        pub y: i32,
+    }
+}
+
+mod v2_attribute {
+    use facet::Facet;
+
+    #[derive(Facet)]
+    pub struct Point {
+        #[facet(default)]
+        pub x: i32,
+        pub y: i32,
+    }
+}
+
+#[test]
+fn fingerprint_follows_structure() {
+    let v1 = v1::Point::SHAPE.fingerprint();
+    assert_eq!(v1, v1::Point::SHAPE.fingerprint());
+    assert_eq!(v1, v1_documented::Point::SHAPE.fingerprint());
+
+    for changed in [
+        v2_renamed::Point::SHAPE,
+        v2_reordered::Point::SHAPE,
+        v2_wider::Point::SHAPE,
+        v2_attribute::Point::SHAPE,
+    ] {
+        assert_ne!(v1, changed.fingerprint());
+    }
+}
+
+#[test]
+fn fingerprint_follows_collections_and_variants() {
+    assert_ne!(
+        <Vec<u32>>::SHAPE.fingerprint(),
+        <Vec<u64>>::SHAPE.fingerprint()
+    );
+    assert_ne!(
+        <Option<u32>>::SHAPE.fingerprint(),
+        <u32>::SHAPE.fingerprint()
+    );
+
+    #[derive(Facet)]
+    #[repr(u8)]
+    enum Before {
+        A,
+        B(u32),
+    }
+
+    #[derive(Facet)]
+    #[repr(u8)]
+    enum After {
+        A,
+        B(u64),
+    }
+
+    assert_ne!(Before::SHAPE.fingerprint(), After::SHAPE.fingerprint());
+}
+
+#[test]
+fn fingerprint_of_recursive_types() {
+    #[derive(Facet)]
+    struct Tree {
+        children: Vec<Tree>,
+        parent: Option<Arc<Tree>>,
+    }
+
+    #[derive(Facet)]
+    struct Chain {
+        next: Option<Box<Chain>>,
+    }
+
+    assert_eq!(Tree::SHAPE.fingerprint(), Tree::SHAPE.fingerprint());
+    assert_ne!(Tree::SHAPE.fingerprint(), Chain::SHAPE.fingerprint());
+}
@@ -136,6 +136,57 @@ fn function_shape_clone() {
     assert_eq!(shape1.param_names, shape2.param_names);
 }
 
+#[cfg(feature = "function")]
+#[test]
+fn function_shape_params_are_typed() {
+    use facet::Facet;
+
+    /// Looks a user up
+    #[facet_fn]
+    fn find_user(id: u64, name: Option<String>) -> Vec<String> {
+        name.into_iter()
+            .map(|name| format!("{id}:{name}"))
+            .collect()
+    }
+
+    assert_eq!(find_user(1, Some("amos".to_string())), ["1:amos"]);
+
+    let shape = fn_shape!(find_user);
+    assert_eq!(shape.name(), "find_user");
+    assert_eq!(shape.doc(), &[" Looks a user up"]);
+
+    let params: Vec<_> = shape
+        .params()
+        .map(|param| (param.name(), param.ty().name()))
+        .collect();
+    assert_eq!(params, [("id", "u64"), ("name", "Option<String>")]);
+    assert_eq!(
+        shape.param("name").and_then(|param| param.shape()),
+        Some(<Option<String> as Facet>::SHAPE)
+    );
+    assert!(shape.param("nope").is_none());
+    assert_eq!(shape.ret().shape(), Some(<Vec<String> as Facet>::SHAPE));
+    assert_eq!((&*shape).into_iter().count(), shape.param_count());
+}
+
+#[cfg(feature = "function")]
+#[test]
+fn function_shape_types_without_facet() {
+    #[facet_fn]
+    fn generic_mul<T: core::ops::Mul<Output = T>>(x: T, y: T) -> T {
+        x * y
+    }
+
+    assert_eq!(generic_mul::<u8>(3, 4), 12);
+
+    // `T` isn't bound by `Facet`, so it has a name but no shape
+    let shape = fn_shape!(generic_mul<u8>);
+    let x = shape.param("x").unwrap();
+    assert_eq!(x.ty().name(), "T");
+    assert!(x.shape().is_none());
+    assert!(shape.ret().shape().is_none());
+}
+
 // #[cfg(feature = "function")]
 // #[test]
 // fn function_with_complex_parameter_types() {
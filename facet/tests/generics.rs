@@ -230,3 +230,89 @@ fn type_params_nonnull_u8() {
     assert_eq!(t.name, "T");
     assert_eq!(t.shape(), u8::SHAPE);
 }
+
+trait Backend {
+    type Id;
+    type Payload;
+}
+
+/// Doesn't implement `Facet`, only its associated types do
+struct Postgres;
+
+impl Backend for Postgres {
+    type Id = u64;
+    type Payload = String;
+}
+
+#[test]
+fn associated_type_fields() {
+    #[derive(Facet)]
+    struct Row<B: Backend> {
+        id: B::Id,
+        payload: Vec<<B as Backend>::Payload>,
+    }
+
+    let shape = Row::<Postgres>::SHAPE;
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => {
+            assert_eq!(sd.fields.len(), 2);
+            assert_eq!(sd.fields[0].shape(), u64::SHAPE);
+            assert_eq!(format!("{}", sd.fields[1].shape()), "Vec<String>");
+        }
+        _ => unreachable!(),
+    }
+
+    // `B` is only reached through its associated types, so it has no shape of its own
+    assert!(shape.type_params.is_empty());
+}
+
+#[test]
+fn associated_type_fields_with_where_clause() {
+    #[derive(Facet)]
+    struct Pair<B>
+    where
+        B: Backend,
+        B::Id: Copy + 'static,
+    {
+        first: B::Id,
+        second: B::Id,
+    }
+
+    #[derive(Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Message<B, T>
+    where
+        B: Backend,
+    {
+        Created { id: B::Id, extra: T },
+        Deleted(<B as Backend>::Id),
+    }
+
+    let shape = Pair::<Postgres>::SHAPE;
+    match shape.ty {
+        Type::User(UserType::Struct(sd)) => {
+            assert_eq!(sd.fields.len(), 2);
+            assert_eq!(sd.fields[0].shape(), u64::SHAPE);
+            assert_eq!(sd.fields[1].shape(), u64::SHAPE);
+        }
+        _ => unreachable!(),
+    }
+
+    let shape = Message::<Postgres, bool>::SHAPE;
+    match shape.ty {
+        Type::User(UserType::Enum(ed)) => {
+            assert_eq!(ed.variants.len(), 2);
+            let created = &ed.variants[0].data.fields;
+            assert_eq!(created[0].shape(), u64::SHAPE);
+            assert_eq!(created[1].shape(), bool::SHAPE);
+            assert_eq!(ed.variants[1].data.fields[0].shape(), u64::SHAPE);
+        }
+        _ => unreachable!(),
+    }
+
+    // `T` is used directly and keeps its type parameter, `B` doesn't have one
+    assert_eq!(shape.type_params.len(), 1);
+    assert_eq!(shape.type_params[0].name, "T");
+    assert_eq!(shape.type_params[0].shape(), bool::SHAPE);
+}
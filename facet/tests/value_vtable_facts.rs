@@ -867,7 +867,7 @@ fn test_string_traits() {
             .ref_unwind_safe(),
     );
     check_facts(
-        &Cow::Owned("hello".to_string()),
+        &Cow::<str>::Owned("hello".to_string()),
         &Cow::Owned("world".to_string()),
         FactBuilder::new()
             .debug()
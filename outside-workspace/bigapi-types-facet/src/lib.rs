@@ -14,6 +14,7 @@ pub struct Catalog {
 #[derive(Facet, Clone)]
 pub struct CatalogMetadata {
     pub version: String,
+    #[facet(fake = "country_code")]
     pub region: String,
 }
 
@@ -21,6 +22,7 @@ pub struct CatalogMetadata {
 #[derive(Facet, Clone)]
 pub struct Business {
     pub id: Uuid,
+    #[facet(fake = "company")]
     pub name: String,
     pub address: Address,
     pub owner: BusinessOwner,
@@ -33,12 +35,14 @@ pub struct Business {
 #[derive(Facet, Clone)]
 pub struct BusinessOwner {
     pub user: User,
+    #[facet(arbitrary(range = 0.0..=100.0))]
     pub ownership_percent: f32,
 }
 
 #[derive(Facet, Clone)]
 pub struct Branch {
     pub id: Uuid,
+    #[facet(fake = "city")]
     pub name: String,
     pub address: Address,
     pub employees: Vec<BusinessUser>,
@@ -65,7 +69,9 @@ pub struct BusinessUser {
 #[derive(Facet, Clone)]
 pub struct User {
     pub id: Uuid,
+    #[facet(fake = "username")]
     pub username: String,
+    #[facet(fake = "email")]
     pub email: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
@@ -75,28 +81,40 @@ pub struct User {
 
 #[derive(Facet, Clone)]
 pub struct UserProfile {
+    #[facet(fake = "first_name")]
     pub first_name: String,
+    #[facet(fake = "last_name")]
     pub last_name: String,
+    #[facet(fake(range = "1940-01-01".."2008-01-01"))]
     pub date_of_birth: NaiveDate,
     pub gender: Gender,
+    #[facet(fake = "paragraph")]
     pub bio: Option<String>,
+    #[facet(fake = "url")]
     pub avatar_url: Option<String>,
     pub home_address: Address,
 }
 
 #[derive(Facet, Clone)]
 pub struct Address {
+    #[facet(fake = "street")]
     pub street: String,
+    #[facet(fake = "city")]
     pub city: String,
+    #[facet(fake = "state")]
     pub state: String,
+    #[facet(fake = "postal_code")]
     pub postal_code: String,
+    #[facet(fake = "country")]
     pub country: String,
     pub geo: Option<GeoLocation>,
 }
 
 #[derive(Facet, Clone)]
 pub struct GeoLocation {
+    #[facet(fake = "latitude")]
     pub latitude: f64,
+    #[facet(fake = "longitude")]
     pub longitude: f64,
 }
 
@@ -112,9 +130,12 @@ pub enum Gender {
 #[derive(Facet, Clone)]
 pub struct Product {
     pub id: Uuid,
+    #[facet(fake = "product")]
     pub name: String,
+    #[facet(fake = "sentence")]
     pub description: Option<String>,
     pub price_cents: u64,
+    #[facet(fake = "currency_code")]
     pub currency: String,
     pub available: bool,
     pub metadata: Option<ProductMetadata>,
@@ -125,6 +146,7 @@ pub struct Product {
 #[derive(Facet, Clone)]
 pub struct ProductMetadata {
     pub sku: Option<String>,
+    #[facet(fake = "word")]
     pub categories: Vec<String>,
     pub weight_grams: Option<u32>,
     pub dimensions: Option<ProductDimensions>,
@@ -141,7 +163,9 @@ pub struct ProductDimensions {
 pub struct ProductReview {
     pub id: Uuid,
     pub reviewer: UserSummary,
+    #[facet(arbitrary(range = 1..=5))]
     pub rating: u8,
+    #[facet(fake = "paragraph")]
     pub text: Option<String>,
     pub created_at: NaiveDateTime,
 }
@@ -149,7 +173,9 @@ pub struct ProductReview {
 #[derive(Facet, Clone)]
 pub struct Category {
     pub id: Uuid,
+    #[facet(fake = "word")]
     pub name: String,
+    #[facet(fake = "sentence")]
     pub description: Option<String>,
     pub parent: Option<Box<Category>>,
 }
@@ -158,14 +184,18 @@ pub struct Category {
 #[derive(Facet, Clone)]
 pub struct UserSummary {
     pub id: Uuid,
+    #[facet(fake = "username")]
     pub username: String,
+    #[facet(fake = "url")]
     pub avatar_url: Option<String>,
 }
 
 #[derive(Facet, Clone)]
 pub struct Role {
     pub id: Uuid,
+    #[facet(fake = "job_title")]
     pub name: String,
+    #[facet(fake = "sentence")]
     pub description: Option<String>,
     pub permissions: Vec<Permission>,
 }
@@ -173,7 +203,9 @@ pub struct Role {
 #[derive(Facet, Clone)]
 pub struct Permission {
     pub id: Uuid,
+    #[facet(fake = "word")]
     pub name: String,
+    #[facet(fake = "sentence")]
     pub description: Option<String>,
 }
 
@@ -183,6 +215,7 @@ pub struct Settings {
     pub email_notifications: bool,
     pub push_notifications: bool,
     pub theme: Theme,
+    #[facet(fake = "locale")]
     pub language: String,
 }
 
@@ -199,182 +232,11 @@ pub enum Theme {
 // -----------------------------------
 
 pub fn generate_mock_catalog() -> Catalog {
-    use chrono::{NaiveDate, NaiveDateTime};
-    use uuid::Uuid;
+    use facet_fake::{Faker, Generator, SeededSource};
 
-    // Helper for now
-    fn now() -> NaiveDateTime {
-        chrono::Utc::now().naive_utc()
-    }
-    fn today() -> NaiveDate {
-        chrono::Utc::now().date_naive()
-    }
-    fn mock_address() -> Address {
-        Address {
-            street: "123 Main St.".to_string(),
-            city: "Metropolis".to_string(),
-            state: "Stateville".to_string(),
-            postal_code: "12345".to_string(),
-            country: "Countryland".to_string(),
-            geo: Some(GeoLocation {
-                latitude: 51.0,
-                longitude: -0.1,
-            }),
-        }
-    }
-    fn mock_role() -> Role {
-        Role {
-            id: Uuid::new_v4(),
-            name: "Employee".to_string(),
-            description: Some("Regular employee role".to_string()),
-            permissions: vec![Permission {
-                id: Uuid::new_v4(),
-                name: "access_dashboard".to_string(),
-                description: Some("Can access the dashboard".to_string()),
-            }],
-        }
-    }
-    fn mock_user(idx: u32) -> User {
-        User {
-            id: Uuid::new_v4(),
-            username: format!("user{}", idx),
-            email: format!("user{}@email.com", idx),
-            created_at: now(),
-            updated_at: now(),
-            profile: UserProfile {
-                first_name: format!("First{}", idx),
-                last_name: format!("Last{}", idx),
-                date_of_birth: today(),
-                gender: if idx % 2 == 0 {
-                    Gender::Male
-                } else {
-                    Gender::Female
-                },
-                bio: Some(format!("Bio of user {}", idx)),
-                avatar_url: None,
-                home_address: mock_address(),
-            },
-            settings: Settings {
-                user_id: Uuid::new_v4(),
-                email_notifications: true,
-                push_notifications: idx % 2 == 0,
-                theme: if idx % 2 == 0 {
-                    Theme::Light
-                } else {
-                    Theme::Dark
-                },
-                language: "en-US".to_string(),
-            },
-        }
-    }
-    fn mock_user_summary(idx: u32) -> UserSummary {
-        UserSummary {
-            id: Uuid::new_v4(),
-            username: format!("user{}", idx),
-            avatar_url: None,
-        }
-    }
-    fn mock_category(id: u8) -> Category {
-        if id == 0 {
-            Category {
-                id: Uuid::new_v4(),
-                name: "Root Category".to_string(),
-                description: Some("Top of the tree".to_string()),
-                parent: None,
-            }
-        } else {
-            Category {
-                id: Uuid::new_v4(),
-                name: format!("Subcategory {}", id),
-                description: Some(format!("Subcategory number {}", id)),
-                parent: Some(Box::new(mock_category(id - 1))),
-            }
-        }
-    }
-    fn mock_product(idx: u32) -> Product {
-        Product {
-            id: Uuid::new_v4(),
-            name: format!("Product{}", idx),
-            description: Some(format!("Description for product {}", idx)),
-            price_cents: (1000 + (idx * 100)) as u64,
-            currency: "USD".to_string(),
-            available: true,
-            metadata: Some(ProductMetadata {
-                sku: Some(format!("SKU{}", idx)),
-                categories: vec!["Electronics".to_string(), "Gadgets".to_string()],
-                weight_grams: Some(500 + 10 * idx),
-                dimensions: Some(ProductDimensions {
-                    length_mm: Some(100.0 + idx as f32),
-                    width_mm: Some(50.0 + idx as f32),
-                    height_mm: Some(25.5 + idx as f32),
-                }),
-            }),
-            reviews: vec![ProductReview {
-                id: Uuid::new_v4(),
-                reviewer: mock_user_summary(idx),
-                rating: 4 + ((idx % 2) as u8),
-                text: Some(format!("Review for product {}", idx)),
-                created_at: now(),
-            }],
-            categories: vec![mock_category(idx as u8)],
-        }
-    }
-
-    // Construct mock business users
-    let business_users: Vec<BusinessUser> = (1..=2)
-        .map(|i| BusinessUser {
-            user: mock_user(i),
-            roles: vec![mock_role()],
-            is_active: true,
-            created_at: now(),
-        })
-        .collect();
-
-    // mock owner
-    let owner = BusinessOwner {
-        user: mock_user(100),
-        ownership_percent: 100.0,
-    };
-
-    // mock branch with inventory
-    let products: Vec<Product> = (1..=3).map(mock_product).collect();
-    let branch_inventory: Vec<BranchInventory> = products
-        .iter()
-        .cloned()
-        .map(|p| BranchInventory {
-            product: p,
-            stock: 50,
-            location_code: Some("A-01".to_string()),
-        })
-        .collect();
-
-    let branch = Branch {
-        id: Uuid::new_v4(),
-        name: "Central Branch".to_string(),
-        address: mock_address(),
-        employees: business_users.clone(),
-        inventory: branch_inventory,
-        open: true,
-    };
-
-    let business = Business {
-        id: Uuid::new_v4(),
-        name: "Awesome Business".to_string(),
-        address: mock_address(),
-        owner,
-        users: business_users,
-        branches: vec![branch],
-        products,
-        created_at: now(),
-    };
-
-    Catalog {
-        id: Uuid::new_v4(),
-        businesses: vec![business],
-        created_at: now(),
-        metadata: CatalogMetadata {
-            version: "1.0.1!".to_string(),
-            region: "US".to_string(),
-        },
-    }
+    Generator::new()
+        .with_max_len(3)
+        .with_strategy(Faker)
+        .generate(&mut SeededSource::new(0))
+        .expect("Failed to generate mock catalog!")
 }